@@ -12,7 +12,8 @@ use crate::{
 use clap::{value_parser, Parser, Subcommand};
 use reth_chainspec::ChainSpec;
 use reth_cli_commands::{
-    config_cmd, db, dump_genesis, import, init_cmd, init_state,
+    backup, config_cmd, db, dump_genesis, export, import, import_blocks, import_era, init_cmd,
+    init_state,
     node::{self, NoArgs},
     p2p, prune, recover, stage,
 };
@@ -154,6 +155,9 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
             Commands::Import(command) => runner.run_blocking_until_ctrl_c(
                 command.execute(|chain_spec| block_executor!(chain_spec)),
             ),
+            Commands::ImportEra(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::ImportBlocks(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::Export(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             #[cfg(feature = "optimism")]
             Commands::ImportOp(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             #[cfg(feature = "optimism")]
@@ -172,6 +176,7 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
             Commands::Debug(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::Recover(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::Prune(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Backup(command) => runner.run_blocking_until_ctrl_c(command.execute()),
         }
     }
 
@@ -200,6 +205,15 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// This syncs RLP encoded blocks from a file.
     #[command(name = "import")]
     Import(import::ImportCommand),
+    /// This imports pre-merge blocks from era1 files.
+    #[command(name = "import-era")]
+    ImportEra(import_era::ImportEraCommand),
+    /// This imports blocks and receipts from an RLP or JSONL file written by `reth export`.
+    #[command(name = "import-blocks")]
+    ImportBlocks(import_blocks::ImportBlocksCommand),
+    /// This exports a range of blocks and their receipts to an RLP or JSONL file.
+    #[command(name = "export")]
+    Export(export::ExportCommand),
     /// This syncs RLP encoded OP blocks below Bedrock from a file, without executing.
     #[cfg(feature = "optimism")]
     #[command(name = "import-op")]
@@ -235,6 +249,9 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand),
+    /// Create and restore incremental backups of the database and static files
+    #[command(name = "backup")]
+    Backup(backup::Command),
 }
 
 #[cfg(test)]
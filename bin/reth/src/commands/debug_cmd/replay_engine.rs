@@ -1,4 +1,8 @@
-use std::{path::PathBuf, sync::Arc, time::Duration};
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use clap::Parser;
 use eyre::Context;
@@ -47,8 +51,42 @@ pub struct Command {
     engine_api_store: PathBuf,
 
     /// The number of milliseconds between Engine API messages.
+    ///
+    /// Set to `0` to replay messages back to back, e.g. for benchmarking.
     #[arg(long = "interval", default_value_t = 1_000)]
     interval: u64,
+
+    /// Stop replaying once a payload or forkchoice state is reported invalid.
+    #[arg(long = "stop-on-invalid")]
+    stop_on_invalid: bool,
+}
+
+/// Statistics of replayed Engine API messages of one kind.
+#[derive(Debug, Default)]
+struct ReplayStats {
+    /// Number of replayed messages.
+    count: u64,
+    /// Number of messages that were reported invalid.
+    invalid: u64,
+    /// Total time spent processing the messages.
+    total: Duration,
+    /// Longest time spent processing a single message.
+    max: Duration,
+}
+
+impl ReplayStats {
+    /// Records a replayed message.
+    fn record(&mut self, elapsed: Duration, invalid: bool) {
+        self.count += 1;
+        self.invalid += invalid as u64;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+    }
+
+    /// Returns the average time spent processing a message.
+    fn average(&self) -> Duration {
+        self.total.checked_div(self.count as u32).unwrap_or_default()
+    }
 }
 
 impl Command {
@@ -168,6 +206,8 @@ impl Command {
             let _ = tx.send(res);
         });
 
+        let mut new_payload_stats = ReplayStats::default();
+        let mut fcu_stats = ReplayStats::default();
         let engine_api_store = EngineMessageStore::new(self.engine_api_store.clone());
         for filepath in engine_api_store.engine_messages_iter()? {
             let contents =
@@ -175,23 +215,51 @@ impl Command {
             let message = serde_json::from_slice(&contents)
                 .wrap_err(format!("failed to parse: {}", filepath.display()))?;
             debug!(target: "reth::cli", filepath = %filepath.display(), ?message, "Forwarding Engine API message");
-            match message {
+            let started_at = Instant::now();
+            let invalid = match message {
                 StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs } => {
                     let response =
                         beacon_engine_handle.fork_choice_updated(state, payload_attrs).await?;
                     debug!(target: "reth::cli", ?response, "Received for forkchoice updated");
+                    let invalid = response.is_invalid();
+                    fcu_stats.record(started_at.elapsed(), invalid);
+                    invalid
                 }
                 StoredEngineApiMessage::NewPayload { payload, cancun_fields } => {
                     let response = beacon_engine_handle.new_payload(payload, cancun_fields).await?;
                     debug!(target: "reth::cli", ?response, "Received for new payload");
+                    let invalid = response.is_invalid();
+                    new_payload_stats.record(started_at.elapsed(), invalid);
+                    invalid
                 }
             };
 
+            if invalid {
+                warn!(target: "reth::cli", filepath = %filepath.display(), "Engine API message was reported invalid");
+                if self.stop_on_invalid {
+                    break
+                }
+            }
+
             // Pause before next message
             tokio::time::sleep(Duration::from_millis(self.interval)).await;
         }
 
         info!(target: "reth::cli", "Finished replaying engine API messages");
+        for (method, stats) in
+            [("newPayload", &new_payload_stats), ("forkchoiceUpdated", &fcu_stats)]
+        {
+            info!(
+                target: "reth::cli",
+                method,
+                count = stats.count,
+                invalid = stats.invalid,
+                total = ?stats.total,
+                average = ?stats.average(),
+                max = ?stats.max,
+                "Replayed engine API messages"
+            );
+        }
 
         match rx.await? {
             Ok(()) => info!("Beacon consensus engine exited successfully"),
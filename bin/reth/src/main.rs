@@ -39,22 +39,24 @@ fn main() {
         let enable_engine2 = engine_args.experimental;
         match enable_engine2 {
             true => {
-                let handle = builder
-                    .with_types_and_provider::<EthereumNode, BlockchainProvider2<_>>()
-                    .with_components(EthereumNode::components())
-                    .with_add_ons::<EthereumAddOns>()
-                    .launch_with_fn(|builder| {
-                        let launcher = EngineNodeLauncher::new(
-                            builder.task_executor().clone(),
-                            builder.config().datadir(),
-                        );
-                        builder.launch_with(launcher)
-                    })
-                    .await?;
+                let handle = Box::pin(
+                    builder
+                        .with_types_and_provider::<EthereumNode, BlockchainProvider2<_>>()
+                        .with_components(EthereumNode::components())
+                        .with_add_ons::<EthereumAddOns>()
+                        .launch_with_fn(|builder| {
+                            let launcher = EngineNodeLauncher::new(
+                                builder.task_executor().clone(),
+                                builder.config().datadir(),
+                            );
+                            builder.launch_with(launcher)
+                        }),
+                )
+                .await?;
                 handle.node_exit_future.await
             }
             false => {
-                let handle = builder.launch_node(EthereumNode::default()).await?;
+                let handle = Box::pin(builder.launch_node(EthereumNode::default())).await?;
                 handle.node_exit_future.await
             }
         }
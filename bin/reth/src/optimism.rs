@@ -1,10 +1,11 @@
 #![allow(missing_docs, rustdoc::missing_crate_level_docs)]
 
 use clap::Parser;
-use reth::cli::Cli;
+use reth::{api::FullNodeComponents, cli::Cli};
 use reth_node_builder::EngineNodeLauncher;
 use reth_node_optimism::{
-    args::RollupArgs, node::OptimismAddOns, rpc::SequencerClient, OptimismNode,
+    args::RollupArgs, forwarder::SequencerForwarder, node::OptimismAddOns, rpc::SequencerClient,
+    OptimismNode,
 };
 use reth_provider::providers::BlockchainProvider2;
 use std::sync::Arc;
@@ -29,6 +30,7 @@ fn main() {
     if let Err(err) = Cli::<RollupArgs>::parse().run(|builder, rollup_args| async move {
         let enable_engine2 = rollup_args.experimental;
         let sequencer_http_arg = rollup_args.sequencer_http.clone();
+        let forwarder_config = rollup_args.sequencer_forwarder_config();
         match enable_engine2 {
             true => {
                 let handle = builder
@@ -39,7 +41,11 @@ fn main() {
                         // register sequencer tx forwarder
                         if let Some(sequencer_http) = sequencer_http_arg {
                             ctx.registry.set_eth_raw_transaction_forwarder(Arc::new(
-                                SequencerClient::new(sequencer_http),
+                                SequencerForwarder::spawn(
+                                    SequencerClient::new(sequencer_http),
+                                    forwarder_config,
+                                    ctx.node().task_executor(),
+                                ),
                             ));
                         }
 
@@ -63,7 +69,11 @@ fn main() {
                         // register sequencer tx forwarder
                         if let Some(sequencer_http) = sequencer_http_arg {
                             ctx.registry.set_eth_raw_transaction_forwarder(Arc::new(
-                                SequencerClient::new(sequencer_http),
+                                SequencerForwarder::spawn(
+                                    SequencerClient::new(sequencer_http),
+                                    forwarder_config,
+                                    ctx.node().task_executor(),
+                                ),
                             ));
                         }
 
@@ -113,6 +113,12 @@ pub trait BlockchainTreeEngine: BlockchainTreeViewer + Send + Sync {
     ///
     /// Returns `Ok` if the blocks were canonicalized, or if the blocks were already canonical.
     fn make_canonical(&self, block_hash: BlockHash) -> Result<CanonicalOutcome, CanonicalError>;
+
+    /// Unwinds the canonical chain down to the given block, which stays canonical.
+    ///
+    /// The unwound blocks are removed from the database and kept in the tree as a side chain. This
+    /// does nothing if the given block is not below the canonical tip.
+    fn unwind(&self, unwind_to: BlockNumber) -> Result<(), CanonicalError>;
 }
 
 /// Represents the kind of validation that should be performed when inserting a block.
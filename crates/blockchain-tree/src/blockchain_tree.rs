@@ -2378,11 +2378,13 @@ mod tests {
             provider_factory: externals.provider_factory.clone(),
             executor_factory: externals.executor_factory.clone(),
             consensus: externals.consensus.clone(),
+            state_root_pool: externals.state_root_pool.clone(),
         };
         let cloned_externals_2 = TreeExternals {
             provider_factory: externals.provider_factory.clone(),
             executor_factory: externals.executor_factory.clone(),
             consensus: externals.consensus.clone(),
+            state_root_pool: externals.state_root_pool.clone(),
         };
 
         // last finalized block would be number 9.
@@ -228,7 +228,11 @@ impl AppendableChain {
                     provider.block_execution_data_provider.execution_outcome().clone();
                 execution_outcome.extend(initial_execution_outcome.clone());
                 let hashed_state = execution_outcome.hash_state_slow();
-                ParallelStateRoot::new(consistent_view, hashed_state)
+                let mut state_root = ParallelStateRoot::new(consistent_view, hashed_state);
+                if let Some(state_root_pool) = &externals.state_root_pool {
+                    state_root = state_root.with_thread_pool(state_root_pool.clone());
+                }
+                state_root
                     .incremental_root_with_updates()
                     .map(|(root, updates)| (root, Some(updates)))
                     .map_err(ProviderError::from)?
@@ -1,5 +1,6 @@
 //! Blockchain tree externals.
 
+use rayon::ThreadPool;
 use reth_consensus::Consensus;
 use reth_db::{static_file::HeaderMask, tables};
 use reth_db_api::{cursor::DbCursorRO, database::Database, transaction::DbTx};
@@ -20,6 +21,7 @@ use std::{collections::BTreeMap, sync::Arc};
 /// - A handle to the consensus engine
 /// - The executor factory to execute blocks with
 /// - The chain spec
+/// - An optional thread pool to compute state roots of new blocks on
 #[derive(Debug)]
 pub struct TreeExternals<DB, E> {
     /// The provider factory, used to commit the canonical chain, or unwind it.
@@ -28,6 +30,8 @@ pub struct TreeExternals<DB, E> {
     pub(crate) consensus: Arc<dyn Consensus>,
     /// The executor factory to execute blocks with.
     pub(crate) executor_factory: E,
+    /// The thread pool to compute state roots on, partitioning the account trie.
+    pub(crate) state_root_pool: Option<Arc<ThreadPool>>,
 }
 
 impl<DB, E> TreeExternals<DB, E> {
@@ -37,7 +41,13 @@ impl<DB, E> TreeExternals<DB, E> {
         consensus: Arc<dyn Consensus>,
         executor_factory: E,
     ) -> Self {
-        Self { provider_factory, consensus, executor_factory }
+        Self { provider_factory, consensus, executor_factory, state_root_pool: None }
+    }
+
+    /// Set the thread pool to compute state roots of new blocks on.
+    pub fn with_state_root_pool(mut self, state_root_pool: Arc<ThreadPool>) -> Self {
+        self.state_root_pool = Some(state_root_pool);
+        self
     }
 }
 
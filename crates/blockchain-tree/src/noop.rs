@@ -74,6 +74,10 @@ impl BlockchainTreeEngine for NoopBlockchainTree {
     ) -> Result<BTreeMap<BlockNumber, BlockHash>, CanonicalError> {
         Ok(BTreeMap::new())
     }
+
+    fn unwind(&self, _unwind_to: BlockNumber) -> Result<(), CanonicalError> {
+        Ok(())
+    }
 }
 
 impl BlockchainTreeViewer for NoopBlockchainTree {
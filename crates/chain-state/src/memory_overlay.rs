@@ -9,7 +9,7 @@ use reth_storage_api::{
 };
 use reth_trie::{
     prefix_set::TriePrefixSetsMut, updates::TrieUpdates, AccountProof, HashedPostState,
-    HashedStorage,
+    HashedStorage, MultiProof,
 };
 use std::collections::HashMap;
 
@@ -162,6 +162,16 @@ impl StateProofProvider for MemoryOverlayStateProvider {
         self.historical.hashed_proof(state, address, slots)
     }
 
+    fn multiproof(
+        &self,
+        hashed_state: HashedPostState,
+        targets: HashMap<B256, Vec<B256>>,
+    ) -> ProviderResult<MultiProof> {
+        let mut state = self.hashed_post_state.clone();
+        state.extend(hashed_state);
+        self.historical.multiproof(state, targets)
+    }
+
     // TODO: Currently this does not reuse available in-memory trie nodes.
     fn witness(
         &self,
@@ -0,0 +1,56 @@
+use derive_more::Display;
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+/// Errors of invalid chain specs, see [`ChainSpecBuilder::try_build`](crate::ChainSpecBuilder).
+#[derive(Clone, Debug, Display, PartialEq, Eq)]
+pub enum ChainSpecError {
+    /// The chain is not set.
+    #[display("chain is not set")]
+    MissingChain,
+    /// The genesis is not set.
+    #[display("genesis is not set")]
+    MissingGenesis,
+    /// The hardfork is not known.
+    #[display("unknown hardfork `{name}`, expected one of: {known}")]
+    UnknownHardfork {
+        /// Name of the hardfork.
+        name: String,
+        /// Names of the known hardforks.
+        known: String,
+    },
+    /// The activation of the hardfork doesn't set exactly one condition.
+    #[display("{hardfork} must be activated by exactly one of `block`, `timestamp` or `ttd`")]
+    AmbiguousActivation {
+        /// Name of the hardfork.
+        hardfork: String,
+    },
+    /// The hardfork is activated by the wrong kind of condition.
+    #[display("{hardfork} must be activated by {expected}")]
+    InvalidActivation {
+        /// Name of the hardfork.
+        hardfork: &'static str,
+        /// The kind of condition the hardfork is activated by.
+        expected: &'static str,
+    },
+    /// A hardfork is activated, but a hardfork it depends on isn't.
+    #[display("{required_by} is activated, but {hardfork} is not")]
+    MissingHardfork {
+        /// Name of the missing hardfork.
+        hardfork: &'static str,
+        /// Name of the activated hardfork.
+        required_by: &'static str,
+    },
+    /// A hardfork is activated before a hardfork that precedes it.
+    #[display("{hardfork} is activated before {previous}")]
+    HardforkOrder {
+        /// Name of the hardfork.
+        hardfork: &'static str,
+        /// Name of the preceding hardfork.
+        previous: &'static str,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ChainSpecError {}
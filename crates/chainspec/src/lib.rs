@@ -10,7 +10,10 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use alloy_chains::{Chain, ChainKind, NamedChain};
+pub use error::ChainSpecError;
 pub use info::ChainInfo;
+#[cfg(feature = "std")]
+pub use schedule::{ChainSpecFile, ChainSpecFileError, HardforkActivation, HardforkSchedule};
 pub use spec::{
     BaseFeeParams, BaseFeeParamsKind, ChainSpec, ChainSpecBuilder, ChainSpecProvider,
     DepositContract, ForkBaseFeeParams, DEV, HOLESKY, MAINNET, SEPOLIA,
@@ -26,6 +29,12 @@ extern crate alloc;
 /// The chain info module.
 mod info;
 
+mod error;
+
+/// Hardfork schedules and chain files.
+#[cfg(feature = "std")]
+mod schedule;
+
 /// The chain spec module.
 mod spec;
 
@@ -0,0 +1,326 @@
+use crate::{spec::known_hardfork_names, ChainSpec, ChainSpecBuilder, ChainSpecError};
+use alloy_chains::Chain;
+use alloy_genesis::Genesis;
+use alloy_primitives::U256;
+use derive_more::{Display, From};
+use reth_ethereum_forks::{EthereumHardfork, ForkCondition};
+use reth_fs_util::FsPathError;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+#[cfg(feature = "optimism")]
+use reth_ethereum_forks::OptimismHardfork;
+
+/// The activation of a hardfork in a [`HardforkSchedule`].
+///
+/// Exactly one of `block`, `timestamp` or `ttd` must be set. `block` may be set together with
+/// `ttd` for the block the total difficulty is reached at.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HardforkActivation {
+    /// Block number the hardfork is activated at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block: Option<u64>,
+    /// Timestamp the hardfork is activated at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    /// Total difficulty the hardfork is activated at, either an integer or a quantity string.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_ttd"
+    )]
+    pub ttd: Option<U256>,
+}
+
+/// Deserializes the total difficulty from an integer, or a string for values that don't fit into
+/// TOML integers.
+fn deserialize_ttd<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<U256>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Ttd {
+        Integer(u64),
+        Quantity(U256),
+    }
+
+    Ok(Some(match Ttd::deserialize(deserializer)? {
+        Ttd::Integer(ttd) => U256::from(ttd),
+        Ttd::Quantity(ttd) => ttd,
+    }))
+}
+
+impl HardforkActivation {
+    /// Returns the condition the hardfork is activated by.
+    fn condition(&self, hardfork: &str) -> Result<ForkCondition, ChainSpecError> {
+        match (self.block, self.timestamp, self.ttd) {
+            (Some(block), None, None) => Ok(ForkCondition::Block(block)),
+            (None, Some(timestamp), None) => Ok(ForkCondition::Timestamp(timestamp)),
+            (fork_block, None, Some(total_difficulty)) => {
+                Ok(ForkCondition::TTD { fork_block, total_difficulty })
+            }
+            _ => Err(ChainSpecError::AmbiguousActivation { hardfork: hardfork.to_string() }),
+        }
+    }
+}
+
+/// The hardforks of a chain and when they're activated.
+///
+/// Hardfork names are case insensitive, and may separate words with `_` or `-`, e.g.
+/// `spurious_dragon`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardforkSchedule {
+    /// Chain id that overrides the chain id of the genesis.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<u64>,
+    /// Hardforks by name.
+    #[serde(default)]
+    pub hardforks: BTreeMap<String, HardforkActivation>,
+}
+
+impl HardforkSchedule {
+    /// Loads the schedule from a TOML file, or a JSON file if the extension isn't `.toml`.
+    pub fn load(path: &Path) -> Result<Self, ChainSpecFileError> {
+        load(path)
+    }
+}
+
+impl ChainSpecBuilder {
+    /// Applies the hardfork schedule, replacing the activation of the hardforks it contains.
+    pub fn hardfork_schedule(
+        mut self,
+        schedule: &HardforkSchedule,
+    ) -> Result<Self, ChainSpecError> {
+        if let Some(chain_id) = schedule.chain_id {
+            self = self.chain(Chain::from_id(chain_id));
+        }
+
+        for (name, activation) in &schedule.hardforks {
+            let condition = activation.condition(name)?;
+            let normalized = name.replace(['_', '-'], "");
+            if let Ok(fork) = EthereumHardfork::from_str(&normalized) {
+                self.insert_hardfork(fork, condition);
+                continue
+            }
+            #[cfg(feature = "optimism")]
+            if let Ok(fork) = OptimismHardfork::from_str(&normalized) {
+                self.insert_hardfork(fork, condition);
+                continue
+            }
+            return Err(ChainSpecError::UnknownHardfork {
+                name: name.clone(),
+                known: known_hardfork_names(),
+            })
+        }
+
+        Ok(self)
+    }
+}
+
+/// A custom chain, defined by its genesis and hardfork schedule.
+///
+/// ```toml
+/// # path to the genesis JSON, relative to the chain file
+/// genesis = "genesis.json"
+/// # overrides the chain id of the genesis config
+/// chain_id = 4242
+///
+/// [hardforks]
+/// london = { block = 0 }
+/// paris = { ttd = 0 }
+/// shanghai = { timestamp = 0 }
+/// cancun = { timestamp = 1720000000 }
+/// ```
+///
+/// Hardforks of the schedule override the ones configured in the genesis, and the resulting chain
+/// spec is validated with [`ChainSpecBuilder::try_build`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainSpecFile {
+    /// Path of the genesis JSON file. Relative paths are relative to the chain file.
+    pub genesis: PathBuf,
+    /// The hardfork schedule.
+    #[serde(flatten)]
+    pub schedule: HardforkSchedule,
+}
+
+impl ChainSpecFile {
+    /// Loads the chain file from a TOML file, or a JSON file if the extension isn't `.toml`.
+    ///
+    /// A relative genesis path is resolved against the directory of the chain file.
+    pub fn load(path: &Path) -> Result<Self, ChainSpecFileError> {
+        let mut file: Self = load(path)?;
+        if file.genesis.is_relative() {
+            if let Some(dir) = path.parent() {
+                file.genesis = dir.join(&file.genesis);
+            }
+        }
+        Ok(file)
+    }
+
+    /// Reads the genesis and builds the validated chain spec.
+    pub fn chain_spec(&self) -> Result<ChainSpec, ChainSpecFileError> {
+        let genesis: Genesis = reth_fs_util::read_json_file(&self.genesis)?;
+        Ok(ChainSpecBuilder::from_genesis(genesis)
+            .hardfork_schedule(&self.schedule)?
+            .try_build()?)
+    }
+}
+
+/// Errors of loading a [`ChainSpecFile`] or [`HardforkSchedule`].
+#[derive(Debug, Display, From)]
+pub enum ChainSpecFileError {
+    /// The file can't be read.
+    FsPath(FsPathError),
+    /// The TOML file can't be parsed.
+    #[display("failed to parse {}: {_1}", _0.display())]
+    Toml(PathBuf, toml::de::Error),
+    /// The JSON file can't be parsed.
+    #[display("failed to parse {}: {_1}", _0.display())]
+    Json(PathBuf, serde_json::Error),
+    /// The chain spec is invalid.
+    #[display("invalid chain spec: {_0}")]
+    ChainSpec(ChainSpecError),
+}
+
+impl std::error::Error for ChainSpecFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::FsPath(err) => Some(err),
+            Self::Toml(_, err) => Some(err),
+            Self::Json(_, err) => Some(err),
+            Self::ChainSpec(err) => Some(err),
+        }
+    }
+}
+
+/// Parses the file as TOML if its extension is `.toml`, and as JSON otherwise.
+fn load<T: DeserializeOwned>(path: &Path) -> Result<T, ChainSpecFileError> {
+    let content = reth_fs_util::read_to_string(path)?;
+    if path.extension().is_some_and(|extension| extension == "toml") {
+        toml::from_str(&content).map_err(|err| ChainSpecFileError::Toml(path.to_path_buf(), err))
+    } else {
+        serde_json::from_str(&content)
+            .map_err(|err| ChainSpecFileError::Json(path.to_path_buf(), err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthereumHardforks;
+
+    const GENESIS: &str = r#"{
+        "config": { "chainId": 1337 },
+        "difficulty": "0x0",
+        "gasLimit": "0x1c9c380",
+        "alloc": {}
+    }"#;
+
+    const SCHEDULE: &str = r#"
+        chain_id = 4242
+
+        [hardforks]
+        homestead = { block = 0 }
+        tangerine = { block = 0 }
+        spurious_dragon = { block = 0 }
+        byzantium = { block = 0 }
+        constantinople = { block = 0 }
+        petersburg = { block = 0 }
+        istanbul = { block = 0 }
+        berlin = { block = 0 }
+        london = { block = 10 }
+        paris = { ttd = 0, block = 10 }
+        shanghai = { timestamp = 0 }
+        cancun = { timestamp = 1720000000 }
+    "#;
+
+    fn builder() -> ChainSpecBuilder {
+        ChainSpecBuilder::from_genesis(serde_json::from_str(GENESIS).unwrap())
+    }
+
+    #[test]
+    fn build_from_schedule() {
+        let schedule: HardforkSchedule = toml::from_str(SCHEDULE).unwrap();
+        let spec = builder().hardfork_schedule(&schedule).unwrap().try_build().unwrap();
+
+        assert_eq!(spec.chain, Chain::from_id(4242));
+        assert_eq!(
+            spec.fork(EthereumHardfork::SpuriousDragon),
+            ForkCondition::Block(0),
+            "names may separate words with underscores"
+        );
+        assert_eq!(spec.fork(EthereumHardfork::London), ForkCondition::Block(10));
+        assert_eq!(
+            spec.fork(EthereumHardfork::Paris),
+            ForkCondition::TTD { fork_block: Some(10), total_difficulty: U256::ZERO }
+        );
+        assert!(spec.is_shanghai_active_at_timestamp(0));
+        assert_eq!(
+            toml::from_str::<HardforkActivation>(r#"ttd = "0x10""#).unwrap().ttd,
+            Some(U256::from(16))
+        );
+        assert!(!spec.is_cancun_active_at_timestamp(1719999999));
+        assert!(spec.is_cancun_active_at_timestamp(1720000000));
+    }
+
+    #[test]
+    fn invalid_schedules() {
+        let schedule = |extra: &str| -> HardforkSchedule {
+            let mut schedule: HardforkSchedule = toml::from_str(SCHEDULE).unwrap();
+            schedule.hardforks.extend(toml::from_str::<HardforkSchedule>(extra).unwrap().hardforks);
+            schedule
+        };
+        let build =
+            |extra: &str| builder().hardfork_schedule(&schedule(extra)).and_then(|b| b.try_build());
+
+        assert!(matches!(
+            build("hardforks.shanghaii = { timestamp = 0 }"),
+            Err(ChainSpecError::UnknownHardfork { name, .. }) if name == "shanghaii"
+        ));
+        assert_eq!(
+            build("hardforks.cancun = { block = 0, timestamp = 0 }").unwrap_err(),
+            ChainSpecError::AmbiguousActivation { hardfork: "cancun".to_string() }
+        );
+        assert_eq!(
+            build("hardforks.cancun = { block = 0 }").unwrap_err(),
+            ChainSpecError::InvalidActivation { hardfork: "Cancun", expected: "timestamp" }
+        );
+        assert_eq!(
+            build("hardforks.berlin = { block = 11 }").unwrap_err(),
+            ChainSpecError::HardforkOrder { hardfork: "London", previous: "Berlin" }
+        );
+
+        let mut without_paris = schedule("");
+        without_paris.hardforks.remove("paris");
+        assert_eq!(
+            builder().hardfork_schedule(&without_paris).unwrap().try_build().unwrap_err(),
+            ChainSpecError::MissingHardfork { hardfork: "Paris", required_by: "Shanghai" }
+        );
+    }
+
+    #[test]
+    fn load_chain_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("genesis.json"), GENESIS).unwrap();
+        let path = dir.path().join("chain.toml");
+        std::fs::write(&path, format!("genesis = \"genesis.json\"\n{SCHEDULE}")).unwrap();
+
+        let file = ChainSpecFile::load(&path).unwrap();
+        assert_eq!(file.genesis, dir.path().join("genesis.json"));
+        let spec = file.chain_spec().unwrap();
+        assert_eq!(spec.chain, Chain::from_id(4242));
+        assert!(spec.is_cancun_active_at_timestamp(1720000000));
+
+        // the schedule alone, as JSON
+        let path = dir.path().join("schedule.json");
+        std::fs::write(&path, r#"{ "hardforks": { "london": { "block": 0 } } }"#).unwrap();
+        let schedule = HardforkSchedule::load(&path).unwrap();
+        assert_eq!(
+            schedule.hardforks["london"],
+            HardforkActivation { block: Some(0), ..Default::default() }
+        );
+    }
+}
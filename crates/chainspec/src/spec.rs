@@ -1,6 +1,12 @@
-use crate::{constants::MAINNET_DEPOSIT_CONTRACT, EthChainSpec};
+use crate::{constants::MAINNET_DEPOSIT_CONTRACT, ChainSpecError, EthChainSpec};
 #[cfg(not(feature = "std"))]
-use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
 use alloy_chains::{Chain, ChainKind, NamedChain};
 use alloy_genesis::Genesis;
 use alloy_primitives::{address, b256, Address, BlockNumber, B256, U256};
@@ -826,15 +832,28 @@ pub struct ChainSpecBuilder {
     chain: Option<Chain>,
     genesis: Option<Genesis>,
     hardforks: ChainHardforks,
+    deposit_contract: Option<DepositContract>,
+    base_fee_params: BaseFeeParamsKind,
 }
 
 impl ChainSpecBuilder {
     /// Construct a new builder from the mainnet chain spec.
     pub fn mainnet() -> Self {
+        Self::from(&*MAINNET)
+    }
+
+    /// Construct a new builder from a genesis, e.g. parsed from a `genesis.json` file.
+    ///
+    /// The chain id, hardforks and deposit contract are read from the config of the genesis, see
+    /// the [`From<Genesis>`](ChainSpec#impl-From<Genesis>-for-ChainSpec) implementation.
+    pub fn from_genesis(genesis: Genesis) -> Self {
+        let spec = ChainSpec::from(genesis);
         Self {
-            chain: Some(MAINNET.chain),
-            genesis: Some(MAINNET.genesis.clone()),
-            hardforks: MAINNET.hardforks.clone(),
+            chain: Some(spec.chain),
+            genesis: Some(spec.genesis),
+            hardforks: spec.hardforks,
+            deposit_contract: spec.deposit_contract,
+            base_fee_params: spec.base_fee_params,
         }
     }
 }
@@ -864,6 +883,12 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Add the given hardfork of any kind with the given activation condition to the spec.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn insert_hardfork<H: Hardfork>(&mut self, fork: H, condition: ForkCondition) {
+        self.hardforks.insert(fork, condition);
+    }
+
     /// Enable the Paris hardfork at the given TTD.
     ///
     /// Does not set the merge netsplit block.
@@ -1047,10 +1072,33 @@ impl ChainSpecBuilder {
             genesis_hash: None,
             hardforks: self.hardforks,
             paris_block_and_final_difficulty,
-            deposit_contract: None,
+            deposit_contract: self.deposit_contract,
+            base_fee_params: self.base_fee_params,
             ..Default::default()
         }
     }
+
+    /// Validates and builds the resulting [`ChainSpec`].
+    ///
+    /// Unlike [`Self::build`], this returns an error if the chain or the genesis is missing, or if
+    /// the hardforks are inconsistent:
+    /// - each hardfork must be activated by the kind of condition it's activated by on mainnet,
+    ///   e.g. Shanghai by a timestamp
+    /// - the hardforks must be activated in order, e.g. Berlin not after London
+    /// - the hardforks an activated hardfork builds on must be activated as well, e.g. Paris for
+    ///   Shanghai. Frontier and the difficulty bomb delays are optional.
+    ///
+    /// The hardforks of the chain spec are sorted in the order they're activated in.
+    pub fn try_build(mut self) -> Result<ChainSpec, ChainSpecError> {
+        if self.chain.is_none() {
+            return Err(ChainSpecError::MissingChain)
+        }
+        if self.genesis.is_none() {
+            return Err(ChainSpecError::MissingGenesis)
+        }
+        self.hardforks = ordered_hardforks(&self.hardforks)?;
+        Ok(self.build())
+    }
 }
 
 impl From<&Arc<ChainSpec>> for ChainSpecBuilder {
@@ -1059,10 +1107,156 @@ impl From<&Arc<ChainSpec>> for ChainSpecBuilder {
             chain: Some(value.chain),
             genesis: Some(value.genesis.clone()),
             hardforks: value.hardforks.clone(),
+            deposit_contract: value.deposit_contract.clone(),
+            base_fee_params: value.base_fee_params.clone(),
+        }
+    }
+}
+
+/// The kind of condition a hardfork is activated by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ActivationKind {
+    Block,
+    TotalDifficulty,
+    Timestamp,
+}
+
+impl ActivationKind {
+    const fn of(condition: &ForkCondition) -> Option<Self> {
+        match condition {
+            ForkCondition::Block(_) => Some(Self::Block),
+            ForkCondition::TTD { .. } => Some(Self::TotalDifficulty),
+            ForkCondition::Timestamp(_) => Some(Self::Timestamp),
+            ForkCondition::Never => None,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Block => "block",
+            Self::TotalDifficulty => "total difficulty",
+            Self::Timestamp => "timestamp",
         }
     }
 }
 
+/// Returns the known hardforks in the order they're activated in, with the kind of condition they
+/// are activated by and whether they're required by the hardforks after them.
+fn known_hardforks() -> Vec<(Box<dyn Hardfork>, ActivationKind, bool)> {
+    use ActivationKind::{Block, Timestamp, TotalDifficulty};
+
+    #[cfg_attr(not(feature = "optimism"), allow(unused_mut))]
+    let mut hardforks = vec![
+        (EthereumHardfork::Frontier.boxed(), Block, false),
+        (EthereumHardfork::Homestead.boxed(), Block, true),
+        (EthereumHardfork::Dao.boxed(), Block, false),
+        (EthereumHardfork::Tangerine.boxed(), Block, true),
+        (EthereumHardfork::SpuriousDragon.boxed(), Block, true),
+        (EthereumHardfork::Byzantium.boxed(), Block, true),
+        (EthereumHardfork::Constantinople.boxed(), Block, true),
+        (EthereumHardfork::Petersburg.boxed(), Block, true),
+        (EthereumHardfork::Istanbul.boxed(), Block, true),
+        (EthereumHardfork::MuirGlacier.boxed(), Block, false),
+        (EthereumHardfork::Berlin.boxed(), Block, true),
+        (EthereumHardfork::London.boxed(), Block, true),
+        (EthereumHardfork::ArrowGlacier.boxed(), Block, false),
+        (EthereumHardfork::GrayGlacier.boxed(), Block, false),
+        (EthereumHardfork::Paris.boxed(), TotalDifficulty, true),
+        (EthereumHardfork::Shanghai.boxed(), Timestamp, true),
+        (EthereumHardfork::Cancun.boxed(), Timestamp, true),
+        (EthereumHardfork::Prague.boxed(), Timestamp, true),
+    ];
+
+    // Optimism hardforks are activated before the L1 hardforks after the ones they include
+    #[cfg(feature = "optimism")]
+    for (before, fork, kind) in [
+        (EthereumHardfork::Shanghai, OptimismHardfork::Bedrock, Block),
+        (EthereumHardfork::Shanghai, OptimismHardfork::Regolith, Timestamp),
+        (EthereumHardfork::Cancun, OptimismHardfork::Canyon, Timestamp),
+        (EthereumHardfork::Prague, OptimismHardfork::Ecotone, Timestamp),
+        (EthereumHardfork::Prague, OptimismHardfork::Fjord, Timestamp),
+        (EthereumHardfork::Prague, OptimismHardfork::Granite, Timestamp),
+    ] {
+        let position = hardforks
+            .iter()
+            .position(|(known, _, _)| known.name() == before.name())
+            .expect("L1 hardfork is known");
+        hardforks.insert(position, (fork.boxed(), kind, false));
+    }
+
+    hardforks
+}
+
+/// Validates the hardforks and returns them in the order they're activated in, see
+/// [`ChainSpecBuilder::try_build`].
+fn ordered_hardforks(hardforks: &ChainHardforks) -> Result<ChainHardforks, ChainSpecError> {
+    let configured = hardforks
+        .forks_iter()
+        .map(|(fork, condition)| (fork.name(), condition))
+        .collect::<Vec<_>>();
+    let known = known_hardforks();
+    if let Some((unknown, _)) =
+        configured.iter().find(|(name, _)| !known.iter().any(|(fork, _, _)| fork.name() == *name))
+    {
+        return Err(ChainSpecError::UnknownHardfork {
+            name: unknown.to_string(),
+            known: known_hardfork_names(),
+        })
+    }
+
+    let mut ordered = Vec::with_capacity(hardforks.len());
+    let mut missing = None;
+    let mut last_block: Option<(&'static str, u64)> = None;
+    let mut last_timestamp: Option<(&'static str, u64)> = None;
+    for (fork, kind, required) in known {
+        let name = fork.name();
+        let condition = configured
+            .iter()
+            .find(|(configured, _)| *configured == name)
+            .map(|(_, condition)| *condition)
+            .unwrap_or_default();
+        let Some(activation) = ActivationKind::of(&condition) else {
+            if required {
+                missing.get_or_insert(name);
+            }
+            continue
+        };
+
+        if activation != kind {
+            return Err(ChainSpecError::InvalidActivation { hardfork: name, expected: kind.name() })
+        }
+        if let Some(hardfork) = missing {
+            return Err(ChainSpecError::MissingHardfork { hardfork, required_by: name })
+        }
+        let last = match condition {
+            ForkCondition::Block(block) => Some((&mut last_block, block)),
+            ForkCondition::Timestamp(timestamp) => Some((&mut last_timestamp, timestamp)),
+            _ => None,
+        };
+        if let Some((last, value)) = last {
+            if let Some((previous, previous_value)) = *last {
+                if value < previous_value {
+                    return Err(ChainSpecError::HardforkOrder { hardfork: name, previous })
+                }
+            }
+            *last = Some((name, value));
+        }
+
+        ordered.push((fork, condition));
+    }
+
+    Ok(ChainHardforks::new(ordered))
+}
+
+/// Returns the names of the known hardforks, separated by commas.
+pub(crate) fn known_hardfork_names() -> String {
+    known_hardforks()
+        .iter()
+        .map(|(fork, _, _)| fork.name().to_lowercase())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// `PoS` deposit contract details.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DepositContract {
@@ -0,0 +1,191 @@
+//! Command that creates and restores incremental backups of the database and static files.
+
+use crate::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::{Parser, Subcommand};
+use reth_backup::{Backup, BackupPaths, DEFAULT_CHUNK_SIZE};
+use reth_object_store::{
+    parse_s3_location, HttpObjectStore, LocalObjectStore, ObjectStore, S3Config, S3ObjectStore,
+};
+use reth_primitives::Address;
+use secp256k1::SecretKey;
+use std::{path::PathBuf, str::FromStr};
+use tokio::runtime::Handle;
+use tracing::info;
+use url::Url;
+
+/// `reth backup` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth backup` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands {
+    /// Backs up the database and static files, uploading only the data that changed since the
+    /// previous backup.
+    ///
+    /// The database is opened read-only, so a running node can be backed up.
+    Create {
+        #[command(flatten)]
+        storage: StorageArgs,
+
+        /// Size of the chunks files are split into, in bytes.
+        #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_CHUNK_SIZE)]
+        chunk_size: usize,
+
+        /// File with the hex encoded private key the manifest of the backup is signed with.
+        ///
+        /// Nodes bootstrapped from the backup verify that it's signed by the address of the key.
+        #[arg(long, value_name = "PATH")]
+        signer_key: Option<PathBuf>,
+    },
+    /// Restores a backup into an empty datadir.
+    ///
+    /// An interrupted restore is resumed when the command is run again.
+    Restore {
+        #[command(flatten)]
+        storage: StorageArgs,
+
+        /// Id of the backup to restore. Defaults to the latest backup.
+        #[arg(long, value_name = "ID")]
+        backup: Option<u64>,
+
+        /// Only restore a backup whose manifest is signed by the given address.
+        #[arg(long, value_name = "ADDRESS")]
+        trusted_signer: Option<Address>,
+    },
+}
+
+/// Arguments that select where backups are kept.
+#[derive(Debug, Parser)]
+pub struct StorageArgs {
+    /// Where backups are kept, either a directory or an S3 location, e.g.
+    /// `s3://my-bucket/mainnet/`.
+    ///
+    /// Backups published on a web server, e.g. `https://snapshots.example.com/mainnet/`, can be
+    /// restored but not created.
+    #[arg(value_name = "LOCATION")]
+    location: String,
+
+    /// Endpoint of the S3-compatible storage.
+    #[arg(long = "s3.endpoint", value_name = "URL", default_value = "https://s3.amazonaws.com")]
+    s3_endpoint: Url,
+
+    /// Region of the S3 bucket.
+    #[arg(long = "s3.region", value_name = "REGION", default_value = "us-east-1")]
+    s3_region: String,
+
+    /// Access key id of the S3 credentials.
+    #[arg(long = "s3.access-key-id", value_name = "ID", env = "AWS_ACCESS_KEY_ID")]
+    s3_access_key_id: Option<String>,
+
+    /// Secret access key of the S3 credentials.
+    #[arg(
+        long = "s3.secret-access-key",
+        value_name = "KEY",
+        env = "AWS_SECRET_ACCESS_KEY",
+        hide_env_values = true
+    )]
+    s3_secret_access_key: Option<String>,
+}
+
+impl Command {
+    /// Execute `backup` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let storage = match &self.command {
+            Subcommands::Create { storage, .. } | Subcommands::Restore { storage, .. } => storage,
+        };
+
+        if storage.location.starts_with("s3://") {
+            let (bucket, prefix) = parse_s3_location(&storage.location)
+                .ok_or_else(|| eyre::eyre!("S3 location is missing the bucket"))?;
+            let config = S3Config {
+                endpoint: storage.s3_endpoint.clone(),
+                region: storage.s3_region.clone(),
+                bucket,
+                prefix,
+                access_key_id: storage
+                    .s3_access_key_id
+                    .clone()
+                    .ok_or_else(|| eyre::eyre!("S3 access key id is missing"))?,
+                secret_access_key: storage
+                    .s3_secret_access_key
+                    .clone()
+                    .ok_or_else(|| eyre::eyre!("S3 secret access key is missing"))?,
+            };
+            let storage = S3ObjectStore::new(config, Handle::current());
+            // Requests block on the runtime, so they are sent from a blocking task
+            tokio::task::spawn_blocking(move || self.run(storage)).await?
+        } else if storage.location.starts_with("http://") ||
+            storage.location.starts_with("https://")
+        {
+            let storage = HttpObjectStore::new(storage.location.parse()?, Handle::current())?;
+            tokio::task::spawn_blocking(move || self.run(storage)).await?
+        } else {
+            let storage = LocalObjectStore::new(PathBuf::from(&storage.location));
+            self.run(storage)
+        }
+    }
+
+    fn run<S: ObjectStore>(self, storage: S) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Create {
+                storage: StorageArgs { location, .. },
+                chunk_size,
+                signer_key,
+            } => {
+                eyre::ensure!(chunk_size > 0, "Chunk size must not be zero");
+                let signer = signer_key.map(|path| load_signer_key(&path)).transpose()?;
+                let Environment { provider_factory, data_dir, .. } =
+                    self.env.init(AccessRights::RO)?;
+                let paths =
+                    BackupPaths { db: data_dir.db(), static_files: data_dir.static_files() };
+
+                info!(target: "reth::cli", %location, "Creating backup");
+                let mut backup = Backup::new(storage).with_chunk_size(chunk_size);
+                if let Some(signer) = signer {
+                    backup = backup.with_signer(signer);
+                }
+                let (manifest, stats) = backup.create(provider_factory.db_ref(), &paths)?;
+                info!(
+                    target: "reth::cli",
+                    id = manifest.id,
+                    size = manifest.size(),
+                    uploaded_bytes = stats.uploaded_bytes,
+                    "Backup created"
+                );
+            }
+            Subcommands::Restore {
+                storage: StorageArgs { location, .. },
+                backup: id,
+                trusted_signer,
+            } => {
+                let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain);
+                let paths =
+                    BackupPaths { db: data_dir.db(), static_files: data_dir.static_files() };
+
+                info!(target: "reth::cli", %location, ?paths, "Restoring backup");
+                let mut backup = Backup::new(storage);
+                if let Some(signer) = trusted_signer {
+                    backup = backup.with_trusted_signer(signer);
+                }
+                let manifest = backup.restore(id, &paths)?;
+                info!(target: "reth::cli", id = manifest.id, "Backup restored");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the hex encoded private key that backups are signed with.
+fn load_signer_key(path: &std::path::Path) -> eyre::Result<SecretKey> {
+    let key = reth_fs_util::read_to_string(path)?;
+    SecretKey::from_str(key.trim().trim_start_matches("0x"))
+        .map_err(|err| eyre::eyre!("invalid signer key in {}: {err}", path.display()))
+}
@@ -0,0 +1,331 @@
+//! Files of consecutive blocks and their receipts, as written by `reth export` and read by
+//! `reth import-blocks`.
+//!
+//! Two formats are supported:
+//!
+//! - [`BlockFileFormat::Rlp`]: the RLP encoded blocks, one after another, the same format `reth
+//!   import` and `geth import` read. Receipts are written to a separate file next to it, see
+//!   [`receipts_path`], as one RLP list of receipts with their bloom per block.
+//! - [`BlockFileFormat::Jsonl`]: one JSON encoded [`BlockRecord`] per line, including the receipts
+//!   of the block.
+use alloy_rlp::{Decodable, Encodable};
+use clap::ValueEnum;
+use reth_primitives::{Block, Receipt, ReceiptWithBloom};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// The format of a block file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BlockFileFormat {
+    /// RLP encoded blocks, one after another.
+    Rlp,
+    /// One JSON object per line, containing the block and its receipts.
+    Jsonl,
+}
+
+impl BlockFileFormat {
+    /// Returns the format of the file at the given path, based on its extension.
+    ///
+    /// Files with a `.jsonl` or `.json` extension are JSONL files, everything else is RLP.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("jsonl" | "json") => Self::Jsonl,
+            _ => Self::Rlp,
+        }
+    }
+}
+
+/// Returns the path of the receipts file that belongs to the RLP block file at the given path.
+pub fn receipts_path(path: &Path) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".receipts");
+    path.into()
+}
+
+/// A block of a block file, with its receipts if the file contains them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockRecord {
+    /// The block.
+    pub block: Block,
+    /// The receipts of the transactions of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipts: Option<Vec<Receipt>>,
+}
+
+/// The serialized form of a [`BlockRecord`], to write a block without cloning it.
+#[derive(Serialize)]
+struct BlockRecordRef<'a> {
+    block: &'a Block,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    receipts: Option<&'a Vec<Receipt>>,
+}
+
+/// Writes blocks to a block file.
+#[derive(Debug)]
+pub struct BlockFileWriter<W> {
+    format: BlockFileFormat,
+    blocks: W,
+    /// The receipts file of an RLP block file.
+    receipts: Option<W>,
+    /// Whether the receipts of the blocks are written.
+    with_receipts: bool,
+}
+
+impl BlockFileWriter<BufWriter<File>> {
+    /// Creates the block file at the given path, and the receipts file next to it if receipts are
+    /// written to an RLP block file.
+    pub fn create(path: &Path, format: BlockFileFormat, with_receipts: bool) -> eyre::Result<Self> {
+        let blocks = BufWriter::new(reth_fs_util::create_file(path)?);
+        let receipts = if with_receipts && format == BlockFileFormat::Rlp {
+            Some(BufWriter::new(reth_fs_util::create_file(receipts_path(path))?))
+        } else {
+            None
+        };
+        Ok(Self { format, blocks, receipts, with_receipts })
+    }
+}
+
+impl<W: Write> BlockFileWriter<W> {
+    /// Creates a writer of blocks without receipts.
+    pub const fn new(format: BlockFileFormat, blocks: W) -> Self {
+        Self { format, blocks, receipts: None, with_receipts: false }
+    }
+
+    /// Writes the receipts of the blocks as well.
+    ///
+    /// The receipts writer is required for RLP block files, JSONL block files contain the receipts.
+    pub fn with_receipts(mut self, receipts: Option<W>) -> Self {
+        self.receipts = receipts;
+        self.with_receipts = true;
+        self
+    }
+
+    /// Returns `true` if the receipts of the blocks are written.
+    pub const fn writes_receipts(&self) -> bool {
+        self.with_receipts
+    }
+
+    /// Appends a block to the file.
+    ///
+    /// Fails if receipts are written, but the record has none.
+    pub fn write(&mut self, record: &BlockRecord) -> eyre::Result<()> {
+        if self.with_receipts && record.receipts.is_none() {
+            eyre::bail!("receipts of block #{} are missing", record.block.number)
+        }
+
+        match self.format {
+            BlockFileFormat::Rlp => {
+                if self.with_receipts && self.receipts.is_none() {
+                    eyre::bail!("receipts of an RLP block file require a receipts writer")
+                }
+
+                let mut buf = Vec::with_capacity(record.block.length());
+                record.block.encode(&mut buf);
+                self.blocks.write_all(&buf)?;
+
+                if let (Some(writer), Some(receipts)) = (&mut self.receipts, &record.receipts) {
+                    let receipts = receipts
+                        .iter()
+                        .map(|receipt| receipt.clone().with_bloom())
+                        .collect::<Vec<ReceiptWithBloom>>();
+                    buf.clear();
+                    receipts.encode(&mut buf);
+                    writer.write_all(&buf)?;
+                }
+            }
+            BlockFileFormat::Jsonl => {
+                let receipts = if self.with_receipts { record.receipts.as_ref() } else { None };
+                serde_json::to_writer(
+                    &mut self.blocks,
+                    &BlockRecordRef { block: &record.block, receipts },
+                )?;
+                self.blocks.write_all(b"\n")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Flushes the written blocks and receipts.
+    pub fn flush(&mut self) -> eyre::Result<()> {
+        self.blocks.flush()?;
+        if let Some(writer) = &mut self.receipts {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the blocks of a block file, one at a time.
+#[derive(Debug)]
+pub struct BlockFileReader<R> {
+    format: BlockFileFormat,
+    blocks: R,
+    /// The receipts file of an RLP block file.
+    receipts: Option<R>,
+    /// The buffer of the current line of a JSONL block file.
+    line: String,
+}
+
+impl BlockFileReader<BufReader<File>> {
+    /// Opens the block file at the given path, and the receipts file next to it if it's an RLP
+    /// block file that has one.
+    pub fn open(path: &Path, format: BlockFileFormat) -> eyre::Result<Self> {
+        let blocks = BufReader::new(reth_fs_util::open(path)?);
+        let receipts_path = receipts_path(path);
+        let receipts = if format == BlockFileFormat::Rlp && receipts_path.exists() {
+            Some(BufReader::new(reth_fs_util::open(receipts_path)?))
+        } else {
+            None
+        };
+        Ok(Self::new(format, blocks, receipts))
+    }
+}
+
+impl<R: BufRead> BlockFileReader<R> {
+    /// Creates a reader of the given blocks, and the receipts if it's an RLP block file with
+    /// receipts.
+    pub const fn new(format: BlockFileFormat, blocks: R, receipts: Option<R>) -> Self {
+        Self { format, blocks, receipts, line: String::new() }
+    }
+
+    /// Reads the next block of the file, or returns `None` at the end of the file.
+    pub fn next_record(&mut self) -> eyre::Result<Option<BlockRecord>> {
+        match self.format {
+            BlockFileFormat::Rlp => {
+                let Some(item) = read_rlp_item(&mut self.blocks)? else { return Ok(None) };
+                let block = Block::decode(&mut &item[..])?;
+
+                let receipts = match &mut self.receipts {
+                    Some(reader) => {
+                        let Some(item) = read_rlp_item(reader)? else {
+                            eyre::bail!("receipts of block #{} are missing", block.number)
+                        };
+                        let receipts = Vec::<ReceiptWithBloom>::decode(&mut &item[..])?;
+                        Some(receipts.into_iter().map(|receipt| receipt.receipt).collect())
+                    }
+                    None => None,
+                };
+                Ok(Some(BlockRecord { block, receipts }))
+            }
+            BlockFileFormat::Jsonl => loop {
+                self.line.clear();
+                if self.blocks.read_line(&mut self.line)? == 0 {
+                    return Ok(None)
+                }
+                if !self.line.trim().is_empty() {
+                    return Ok(Some(serde_json::from_str(&self.line)?))
+                }
+            },
+        }
+    }
+}
+
+/// Reads the next RLP list from the reader, or returns `None` at the end of the file.
+fn read_rlp_item<R: BufRead>(reader: &mut R) -> eyre::Result<Option<Vec<u8>>> {
+    if reader.fill_buf()?.is_empty() {
+        return Ok(None)
+    }
+
+    let mut item = vec![0];
+    reader.read_exact(&mut item)?;
+    let payload_length = match item[0] {
+        prefix @ 0xc0..=0xf7 => (prefix - 0xc0) as usize,
+        prefix @ 0xf8..=0xff => {
+            let length_of_length = (prefix - 0xf7) as usize;
+            item.resize(1 + length_of_length, 0);
+            reader.read_exact(&mut item[1..])?;
+            item[1..]
+                .iter()
+                .try_fold(0usize, |length, byte| {
+                    length.checked_mul(256).map(|length| length + *byte as usize)
+                })
+                .ok_or_else(|| eyre::eyre!("RLP list is too long"))?
+        }
+        prefix => eyre::bail!("expected an RLP list, got prefix {prefix:#x}"),
+    };
+
+    let header_length = item.len();
+    item.resize(header_length + payload_length, 0);
+    reader.read_exact(&mut item[header_length..])?;
+    Ok(Some(item))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, Log, TxType};
+
+    #[allow(clippy::needless_update)]
+    fn records() -> Vec<BlockRecord> {
+        (1..=3)
+            .map(|number| BlockRecord {
+                block: Block {
+                    header: Header { number, gas_limit: 30_000_000, ..Default::default() },
+                    ..Default::default()
+                },
+                receipts: Some(vec![Receipt {
+                    tx_type: TxType::Eip1559,
+                    success: true,
+                    cumulative_gas_used: 21_000 * number,
+                    logs: vec![Log::empty()],
+                    ..Default::default()
+                }]),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn block_file_roundtrip() {
+        for format in [BlockFileFormat::Rlp, BlockFileFormat::Jsonl] {
+            let mut blocks = Vec::new();
+            let mut receipts = Vec::new();
+            let mut writer =
+                BlockFileWriter::new(format, &mut blocks).with_receipts(Some(&mut receipts));
+            for record in records() {
+                writer.write(&record).unwrap();
+            }
+            writer.flush().unwrap();
+
+            let receipts = (format == BlockFileFormat::Rlp).then_some(&receipts[..]);
+            let mut reader = BlockFileReader::new(format, &blocks[..], receipts);
+            let mut read = Vec::new();
+            while let Some(record) = reader.next_record().unwrap() {
+                read.push(record);
+            }
+            assert_eq!(read, records(), "{format:?}");
+        }
+    }
+
+    #[test]
+    fn block_file_without_receipts() {
+        for format in [BlockFileFormat::Rlp, BlockFileFormat::Jsonl] {
+            let mut blocks = Vec::new();
+            let mut writer = BlockFileWriter::new(format, &mut blocks);
+            for record in records() {
+                writer.write(&record).unwrap();
+            }
+            writer.flush().unwrap();
+
+            let mut reader = BlockFileReader::new(format, &blocks[..], None);
+            for record in records() {
+                assert_eq!(
+                    reader.next_record().unwrap(),
+                    Some(BlockRecord { block: record.block, receipts: None })
+                );
+            }
+            assert_eq!(reader.next_record().unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn block_file_format_from_path() {
+        assert_eq!(BlockFileFormat::from_path(Path::new("blocks.jsonl")), BlockFileFormat::Jsonl);
+        assert_eq!(BlockFileFormat::from_path(Path::new("blocks.rlp")), BlockFileFormat::Rlp);
+        assert_eq!(BlockFileFormat::from_path(Path::new("blocks")), BlockFileFormat::Rlp);
+        assert_eq!(receipts_path(Path::new("blocks.rlp")), Path::new("blocks.rlp.receipts"));
+    }
+}
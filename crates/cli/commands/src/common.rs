@@ -3,7 +3,8 @@
 use clap::Parser;
 use reth_beacon_consensus::EthBeaconConsensus;
 use reth_chainspec::ChainSpec;
-use reth_config::{config::EtlConfig, Config};
+use reth_cli_util::parse_key_value;
+use reth_config::{Config, ConfigOverrides};
 use reth_db::{init_db, open_db_read_only, DatabaseEnv};
 use reth_db_common::init::init_genesis;
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
@@ -34,6 +35,15 @@ pub struct EnvironmentArgs {
     #[arg(long, value_name = "FILE")]
     pub config: Option<PathBuf>,
 
+    /// The profile of the configuration file to apply.
+    #[arg(long, value_name = "NAME")]
+    pub config_profile: Option<String>,
+
+    /// Override a setting of the configuration file, e.g.
+    /// `--config.set stages.execution.max_blocks=1000`.
+    #[arg(long = "config.set", value_name = "KEY=VALUE", value_parser = parse_key_value)]
+    pub config_overrides: Vec<(String, String)>,
+
     /// The chain this node is running.
     ///
     /// Possible values are either a built-in chain or the path to a chain specification file.
@@ -72,9 +82,18 @@ impl EnvironmentArgs {
             )
             .unwrap_or_default();
 
+        if let Some(profile) = &self.config_profile {
+            config = config.with_profile(profile)?;
+        }
+
+        config = ConfigOverrides::from_env()
+            .with_cli_overrides(self.config_overrides.iter().cloned())
+            .apply(&config)?
+            .config;
+
         // Make sure ETL doesn't default to /tmp/, but to whatever datadir is set to
-        if config.stages.etl.dir.is_none() {
-            config.stages.etl.dir = Some(EtlConfig::from_datadir(data_dir.data_dir()));
+        if self.datadir.etl_path.is_some() || config.stages.etl.dir.is_none() {
+            config.stages.etl.dir = Some(data_dir.etl());
         }
 
         info!(target: "reth::cli", ?db_path, ?sf_path, "Opening storage");
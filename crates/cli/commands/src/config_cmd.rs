@@ -16,6 +16,10 @@ pub struct Command {
     /// Show the default config
     #[arg(long, verbatim_doc_comment, conflicts_with = "config")]
     default: bool,
+
+    /// Check the config for invalid or questionable values instead of showing it
+    #[arg(long, verbatim_doc_comment)]
+    validate: bool,
 }
 
 impl Command {
@@ -33,6 +37,22 @@ impl Command {
             Config::from_path(&path)
                 .wrap_err_with(|| format!("Could not load config file: {}", path.display()))?
         };
+
+        if self.validate {
+            let report = config.validate();
+            for issue in report.issues() {
+                let severity = if issue.is_error() { "error" } else { "warning" };
+                println!("{severity}: {issue}");
+            }
+            if report.has_errors() {
+                bail!("Config is invalid");
+            }
+            if report.issues().is_empty() {
+                println!("Config is valid");
+            }
+            return Ok(())
+        }
+
         println!("{}", toml::to_string_pretty(&config)?);
         Ok(())
     }
@@ -0,0 +1,217 @@
+use clap::Parser;
+use eyre::WrapErr;
+use human_bytes::human_bytes;
+use reth_db::{mdbx::DatabaseArguments, tables, DatabaseEnv, DatabaseEnvKind};
+use reth_db_api::{database::Database, transaction::DbTx};
+use reth_provider::ProviderFactory;
+use std::{
+    fs::{self, File},
+    os::fd::AsRawFd,
+    path::Path,
+    sync::Arc,
+};
+
+/// The name of the MDBX data file.
+const DATA_FILE: &str = "mdbx.dat";
+/// The name of the MDBX lock file.
+const LOCK_FILE: &str = "mdbx.lck";
+/// The name of the compacted copy of the data file, before it replaces the data file.
+const COMPACTED_FILE: &str = "mdbx.dat.compact";
+/// The name of the backup of the data file, while it's replaced by the compacted copy.
+const BACKUP_FILE: &str = "mdbx.dat.backup";
+
+/// The arguments for the `reth db compact` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Only reports the size of the freelist, without compacting the database.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl Command {
+    /// Execute `db compact` command
+    ///
+    /// Makes a compacted copy of the database, which omits the pages on the freelist, and replaces
+    /// the database with it. The node must not be running.
+    pub fn execute(
+        self,
+        db_path: &Path,
+        db_args: DatabaseArguments,
+        provider_factory: ProviderFactory<Arc<DatabaseEnv>>,
+    ) -> eyre::Result<()> {
+        let db = provider_factory.db_ref();
+        let page_size = db.stat()?.page_size() as f64;
+        let freelist = db.freelist()? as f64 * page_size;
+        let size = (db.info()?.last_pgno() + 1) as f64 * page_size;
+
+        println!("Database size: {}", human_bytes(size));
+        println!("Freelist size: {}", human_bytes(freelist));
+        if self.dry_run {
+            return Ok(())
+        }
+
+        println!("Compacting database, this may take a while...");
+        let compacted_file = db_path.join(COMPACTED_FILE);
+        let copied = File::create(&compacted_file).map_err(eyre::Report::from).and_then(|file| {
+            db.copy_to_fd(file.as_raw_fd(), true)?;
+            Ok(file.sync_all()?)
+        });
+        if let Err(err) = copied {
+            let _ = fs::remove_file(&compacted_file);
+            return Err(err.wrap_err("failed to copy the database"))
+        }
+
+        // close the database before replacing its files
+        drop(provider_factory);
+        replace_data_file(db_path, db_args)?;
+
+        println!(
+            "Compacted database from {} to {}",
+            human_bytes(size),
+            human_bytes(size - freelist)
+        );
+
+        Ok(())
+    }
+}
+
+/// Replaces the data file with its compacted copy.
+///
+/// The data file is moved to a backup first, which is only removed once the database opens with
+/// the compacted copy. If any step fails, the backup is moved back, so the database is left as it
+/// was.
+fn replace_data_file(db_path: &Path, db_args: DatabaseArguments) -> eyre::Result<()> {
+    let data_file = db_path.join(DATA_FILE);
+    let backup_file = db_path.join(BACKUP_FILE);
+    let compacted_file = db_path.join(COMPACTED_FILE);
+    // the lock file describes the data file it was created for, it's recreated on the next open
+    let lock_file = db_path.join(LOCK_FILE);
+
+    fs::rename(&data_file, &backup_file)?;
+    let swapped = (|| {
+        fs::rename(&compacted_file, &data_file)?;
+        remove_if_exists(&lock_file)?;
+        let db = DatabaseEnv::open(db_path, DatabaseEnvKind::RW, db_args)?;
+        db.view(|tx| tx.entries::<tables::StageCheckpoints>())??;
+        eyre::Ok(())
+    })();
+
+    if let Err(err) = swapped {
+        remove_if_exists(&lock_file)?;
+        fs::rename(&backup_file, &data_file).wrap_err_with(|| {
+            format!("failed to restore the database from {}", backup_file.display())
+        })?;
+        remove_if_exists(&compacted_file)?;
+        return Err(err.wrap_err("failed to open the compacted database, restored the database"))
+    }
+
+    fs::remove_file(backup_file)?;
+    Ok(())
+}
+
+/// Removes the file, if it exists.
+fn remove_if_exists(path: &Path) -> std::io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+    use reth_db::init_db;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives::B256;
+    use reth_provider::providers::StaticFileProvider;
+
+    const ROWS: u64 = 10_000;
+
+    fn provider_factory(db_path: &Path, static_path: &Path) -> ProviderFactory<Arc<DatabaseEnv>> {
+        let db = init_db(db_path, DatabaseArguments::default()).unwrap();
+        ProviderFactory::new(
+            Arc::new(db),
+            MAINNET.clone(),
+            StaticFileProvider::read_write(static_path).unwrap(),
+        )
+    }
+
+    /// Writes [`ROWS`] canonical hashes, then deletes all but every hundredth, so that most of the
+    /// pages end up on the freelist.
+    fn fill_and_prune(factory: &ProviderFactory<Arc<DatabaseEnv>>) {
+        factory
+            .db_ref()
+            .update(|tx| {
+                for number in 0..ROWS {
+                    tx.put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8))
+                        .unwrap();
+                }
+            })
+            .unwrap();
+        factory
+            .db_ref()
+            .update(|tx| {
+                for number in (0..ROWS).filter(|number| number % 100 != 0) {
+                    tx.delete::<tables::CanonicalHeaders>(number, None).unwrap();
+                }
+            })
+            .unwrap();
+    }
+
+    fn used_pages(db: &DatabaseEnv) -> usize {
+        db.info().unwrap().last_pgno() + 1
+    }
+
+    fn assert_pruned_rows(db_path: &Path) {
+        let db =
+            DatabaseEnv::open(db_path, DatabaseEnvKind::RO, DatabaseArguments::default()).unwrap();
+        db.view(|tx| {
+            assert_eq!(tx.entries::<tables::CanonicalHeaders>().unwrap(), (ROWS / 100) as usize);
+            for number in (0..ROWS).step_by(100) {
+                assert_eq!(
+                    tx.get::<tables::CanonicalHeaders>(number).unwrap(),
+                    Some(B256::with_last_byte(number as u8))
+                );
+            }
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn compacts_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("db");
+        let factory = provider_factory(&db_path, &dir.path().join("static_files"));
+        fill_and_prune(&factory);
+        let pages = used_pages(factory.db_ref());
+
+        Command { dry_run: false }
+            .execute(&db_path, DatabaseArguments::default(), factory)
+            .unwrap();
+
+        let db =
+            DatabaseEnv::open(&db_path, DatabaseEnvKind::RO, DatabaseArguments::default()).unwrap();
+        assert!(used_pages(&db) < pages);
+        drop(db);
+        assert!(!db_path.join(COMPACTED_FILE).exists());
+        assert!(!db_path.join(BACKUP_FILE).exists());
+        assert_pruned_rows(&db_path);
+    }
+
+    #[test]
+    fn restores_database_when_compacted_copy_does_not_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("db");
+        let factory = provider_factory(&db_path, &dir.path().join("static_files"));
+        fill_and_prune(&factory);
+        drop(factory);
+
+        fs::write(db_path.join(COMPACTED_FILE), b"not a database").unwrap();
+        assert!(replace_data_file(&db_path, DatabaseArguments::default()).is_err());
+
+        assert!(!db_path.join(COMPACTED_FILE).exists());
+        assert!(!db_path.join(BACKUP_FILE).exists());
+        assert_pruned_rows(&db_path);
+    }
+}
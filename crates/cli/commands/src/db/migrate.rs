@@ -0,0 +1,77 @@
+use clap::Parser;
+use eyre::WrapErr;
+use reth_db::{mdbx::DatabaseArguments, migration::copy_table, tables_to_generic, Tables};
+use reth_db_api::{
+    database::Database,
+    transaction::{DbTx, DbTxMut},
+};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// The arguments for the `reth db migrate` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Copies the database into a new redb database in the given directory.
+    #[arg(long, value_name = "PATH", required_unless_present = "from_redb")]
+    to_redb: Option<PathBuf>,
+
+    /// Copies the redb database in the given directory into the database, which must be empty.
+    #[arg(long, value_name = "PATH", conflicts_with = "to_redb")]
+    from_redb: Option<PathBuf>,
+
+    /// Number of entries written per transaction.
+    #[arg(long, default_value_t = 100_000)]
+    commit_threshold: usize,
+}
+
+impl Command {
+    /// Returns `true` if a redb database is imported.
+    pub const fn is_import(&self) -> bool {
+        self.from_redb.is_some()
+    }
+
+    /// Execute `db migrate` command
+    ///
+    /// Only the database is copied, static files are left in place. The node must not be running.
+    pub fn execute(self, db_path: &Path, args: DatabaseArguments) -> eyre::Result<()> {
+        if let Some(redb_path) = &self.to_redb {
+            eyre::ensure!(
+                !redb_path.join(reth_db::redb::REDB_FILE_NAME).exists(),
+                "redb database already exists: {redb_path:?}"
+            );
+            let source = reth_db::open_db_read_only(db_path, args)?;
+            let destination = reth_db::redb::init_db(redb_path)?;
+            self.copy_tables(&source, &destination)?;
+        } else if let Some(redb_path) = &self.from_redb {
+            let source = reth_db::redb::open_db(redb_path)?;
+            let destination = reth_db::init_db(db_path, args)?;
+            // opening the database records the client version, it's replaced by the source history
+            destination.update(|tx| tx.clear::<reth_db::VersionHistory>())??;
+            self.copy_tables(&source, &destination)?;
+        }
+
+        Ok(())
+    }
+
+    fn copy_tables<S: Database, D: Database>(
+        &self,
+        source: &S,
+        destination: &D,
+    ) -> eyre::Result<()> {
+        let destination_tx = destination.tx()?;
+        for table in Tables::ALL {
+            let entries = tables_to_generic!(table, |T| destination_tx.entries::<T>())?;
+            eyre::ensure!(entries == 0, "Destination table {table} is not empty");
+        }
+        drop(destination_tx);
+
+        for table in Tables::ALL {
+            info!(target: "reth::cli", %table, "Copying table");
+            let entries = copy_table(source, destination, *table, self.commit_threshold)
+                .wrap_err_with(|| format!("Failed to copy table {table}"))?;
+            info!(target: "reth::cli", %table, entries, "Copied table");
+        }
+
+        Ok(())
+    }
+}
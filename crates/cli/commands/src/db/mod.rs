@@ -6,9 +6,13 @@ use std::io::{self, Write};
 
 mod checksum;
 mod clear;
+#[cfg(unix)]
+mod compact;
 mod diff;
 mod get;
 mod list;
+#[cfg(feature = "redb")]
+mod migrate;
 mod stats;
 /// DB List TUI
 mod tui;
@@ -44,6 +48,16 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Reclaims the free pages of the database by replacing it with a compacted copy.
+    ///
+    /// The node must not be running.
+    #[cfg(unix)]
+    Compact(compact::Command),
+    /// Copies the database into a redb database, or back from one.
+    ///
+    /// The node must not be running.
+    #[cfg(feature = "redb")]
+    Migrate(migrate::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -74,8 +88,13 @@ impl Command {
             data_dir.data_dir()
         );
 
-        // ensure the provided database exist
-        eyre::ensure!(db_path.is_dir(), "Database does not exist: {:?}", db_path);
+        // ensure the provided database exist, unless it's about to be imported
+        #[cfg(feature = "redb")]
+        let is_import =
+            matches!(&self.command, Subcommands::Migrate(command) if command.is_import());
+        #[cfg(not(feature = "redb"))]
+        let is_import = false;
+        eyre::ensure!(is_import || db_path.is_dir(), "Database does not exist: {:?}", db_path);
 
         match self.command {
             // TODO: We'll need to add this on the DB trait.
@@ -128,6 +147,15 @@ impl Command {
                 let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
                 command.execute(provider_factory)?;
             }
+            #[cfg(unix)]
+            Subcommands::Compact(command) => {
+                let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
+                command.execute(&db_path, self.env.db.database_args(), provider_factory)?;
+            }
+            #[cfg(feature = "redb")]
+            Subcommands::Migrate(command) => {
+                command.execute(&db_path, self.env.db.database_args())?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
@@ -0,0 +1,142 @@
+//! Command that exports a range of blocks to a file.
+use crate::{
+    block_file::{BlockFileFormat, BlockFileWriter, BlockRecord},
+    common::{AccessRights, Environment, EnvironmentArgs},
+};
+use clap::Parser;
+use reth_db_api::database::Database;
+use reth_node_core::version::SHORT_VERSION;
+use reth_primitives::BlockNumber;
+use reth_provider::{BlockNumReader, BlockReader, ProviderError, ProviderFactory, ReceiptProvider};
+use std::{io::Write, ops::RangeInclusive, path::PathBuf};
+use tracing::info;
+
+/// Exports a range of blocks, and optionally their receipts, to an RLP or JSONL file.
+#[derive(Debug, Parser)]
+pub struct ExportCommand {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The first block to export.
+    #[arg(long, value_name = "BLOCK", default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block to export. Defaults to the highest block in the database.
+    #[arg(long, value_name = "BLOCK")]
+    to: Option<BlockNumber>,
+
+    /// The format of the file. Defaults to JSONL for files with a `.jsonl` extension, and to RLP
+    /// otherwise.
+    #[arg(long, value_enum)]
+    format: Option<BlockFileFormat>,
+
+    /// Exports the receipts of the blocks.
+    ///
+    /// JSONL files contain the receipts next to their block. For RLP files, they are written to a
+    /// file with the same name and a `.receipts` suffix, so the block file can still be imported
+    /// with `reth import`.
+    #[arg(long, verbatim_doc_comment)]
+    receipts: bool,
+
+    /// The path to the file to export the blocks to.
+    #[arg(value_name = "EXPORT_PATH")]
+    path: PathBuf,
+}
+
+impl ExportCommand {
+    /// Execute `export` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+
+        let to = match self.to {
+            Some(to) => to,
+            None => provider_factory.last_block_number()?,
+        };
+        if to < self.from {
+            eyre::bail!("the range of blocks to export is empty: #{}..=#{to}", self.from)
+        }
+
+        let format = self.format.unwrap_or_else(|| BlockFileFormat::from_path(&self.path));
+        let mut writer = BlockFileWriter::create(&self.path, format, self.receipts)?;
+        let result = export_blocks(&provider_factory, self.from..=to, &mut writer)?;
+
+        info!(target: "reth::cli",
+            path = %self.path.display(),
+            ?format,
+            blocks = result.blocks,
+            transactions = result.transactions,
+            receipts = result.receipts,
+            "Blocks exported"
+        );
+
+        Ok(())
+    }
+}
+
+/// Blocks, transactions and receipts exported to a block file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExportResult {
+    /// Number of exported blocks.
+    pub blocks: u64,
+    /// Number of exported transactions.
+    pub transactions: u64,
+    /// Number of exported receipts.
+    pub receipts: u64,
+}
+
+/// Exports the blocks in the given range to the block file.
+///
+/// The receipts are exported as well if the writer writes receipts, which fails if they are not
+/// available, e.g. because they were pruned or the blocks are not executed yet.
+pub fn export_blocks<DB: Database, W: Write>(
+    provider_factory: &ProviderFactory<DB>,
+    range: RangeInclusive<BlockNumber>,
+    writer: &mut BlockFileWriter<W>,
+) -> eyre::Result<ExportResult> {
+    let provider = provider_factory.provider()?;
+
+    let mut result = ExportResult::default();
+    for number in range {
+        let block = provider
+            .block(number.into())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+        let receipts = if writer.writes_receipts() {
+            let receipts = provider
+                .receipts_by_block(number.into())?
+                .filter(|receipts| receipts.len() == block.body.len())
+                .ok_or_else(|| eyre::eyre!("receipts of block #{number} are not available"))?;
+            result.receipts += receipts.len() as u64;
+            Some(receipts)
+        } else {
+            None
+        };
+
+        result.blocks += 1;
+        result.transactions += block.body.len() as u64;
+        writer.write(&BlockRecord { block, receipts })?;
+    }
+    writer.flush()?;
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_node_core::args::utils::SUPPORTED_CHAINS;
+
+    #[test]
+    fn parse_export_command_chain_args() {
+        for chain in SUPPORTED_CHAINS {
+            let args: ExportCommand =
+                ExportCommand::parse_from(["reth", "--chain", chain, "blocks.rlp"]);
+            assert_eq!(
+                Ok(args.env.chain.chain),
+                chain.parse::<reth_chainspec::Chain>(),
+                "failed to parse chain {chain}"
+            );
+        }
+    }
+}
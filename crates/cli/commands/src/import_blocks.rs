@@ -0,0 +1,531 @@
+//! Command that imports blocks and their receipts from an RLP or JSONL file.
+use crate::{
+    block_file::{BlockFileFormat, BlockFileReader, BlockRecord},
+    common::{AccessRights, Environment, EnvironmentArgs},
+};
+use clap::{Parser, ValueEnum};
+use reth_beacon_consensus::EthBeaconConsensus;
+use reth_consensus::Consensus;
+use reth_db::tables;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    models::{StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_node_core::version::SHORT_VERSION;
+use reth_primitives::{proofs::calculate_receipt_root_no_memo, SealedBlock, SealedHeader, U256};
+use reth_provider::{
+    writer::UnifiedStorageWriter, BlockHashReader, HeaderProvider, ProviderError, ProviderFactory,
+    StageCheckpointReader, StageCheckpointWriter, StaticFileProviderFactory, StaticFileWriter,
+};
+use reth_stages::{StageCheckpoint, StageId};
+use reth_static_file_types::StaticFileSegment;
+use std::{io::BufRead, path::PathBuf};
+use tracing::{info, warn};
+
+/// The number of blocks that are imported in one database transaction.
+const BLOCKS_PER_COMMIT: u64 = 10_000;
+
+/// Imports blocks, and their receipts if the file contains them, from an RLP or JSONL file as
+/// written by `reth export`.
+#[derive(Debug, Parser)]
+pub struct ImportBlocksCommand {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The format of the file. Defaults to JSONL for files with a `.jsonl` extension, and to RLP
+    /// otherwise.
+    #[arg(long, value_enum)]
+    format: Option<BlockFileFormat>,
+
+    /// How the imported blocks are validated.
+    #[arg(long, value_enum, default_value_t = ImportValidation::Full)]
+    validation: ImportValidation,
+
+    /// The path to the file to import.
+    ///
+    /// The headers, bodies and receipts are written directly to the database and static files,
+    /// replacing the online header and body download for the imported blocks. The blocks are
+    /// executed when the node is started.
+    ///
+    /// The blocks must continue the chain in the database. Blocks that are already in the database
+    /// are skipped. The receipts of an RLP file are read from the file with the same name and a
+    /// `.receipts` suffix, if it exists.
+    #[arg(value_name = "IMPORT_PATH", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+impl ImportBlocksCommand {
+    /// Execute `import-blocks` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        let Environment { provider_factory, config, .. } = self.env.init(AccessRights::RW)?;
+
+        let consensus = EthBeaconConsensus::new(self.env.chain.clone());
+        let write_receipts =
+            !config.prune.as_ref().is_some_and(|prune| prune.segments.has_receipts_pruning());
+
+        let format = self.format.unwrap_or_else(|| BlockFileFormat::from_path(&self.path));
+        let mut reader = BlockFileReader::open(&self.path, format)?;
+        let result = import_blocks(
+            &provider_factory,
+            &consensus,
+            &mut reader,
+            self.validation,
+            write_receipts,
+        )?;
+
+        info!(target: "reth::cli",
+            path = %self.path.display(),
+            ?format,
+            total_imported_blocks = result.blocks,
+            total_imported_txns = result.transactions,
+            total_imported_receipts = result.receipts,
+            "Blocks imported"
+        );
+
+        Ok(())
+    }
+}
+
+/// How imported blocks are validated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ImportValidation {
+    /// Validates the headers and bodies against the consensus rules and their parents, and the
+    /// receipts against the receipts root of their headers.
+    #[default]
+    Full,
+    /// Only validates the headers against the consensus rules and their parents.
+    HeaderOnly,
+    /// Imports the blocks as they are, only checking that their numbers are consecutive.
+    None,
+}
+
+/// Blocks, transactions and receipts imported from a block file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportBlocksResult {
+    /// Number of imported blocks.
+    pub blocks: u64,
+    /// Number of imported transactions.
+    pub transactions: u64,
+    /// Number of imported receipts.
+    pub receipts: u64,
+}
+
+impl std::ops::AddAssign for ImportBlocksResult {
+    fn add_assign(&mut self, other: Self) {
+        self.blocks += other.blocks;
+        self.transactions += other.transactions;
+        self.receipts += other.receipts;
+    }
+}
+
+/// Imports the blocks of the block file that follow the highest block in the database.
+///
+/// Blocks are validated according to `validation`. Headers and bodies are written the same way the
+/// headers and bodies stages write them, and their stage checkpoints are advanced to the last
+/// imported block. Blocks are committed in batches, so a failing import keeps the blocks before
+/// the failing batch.
+///
+/// Receipts are only written if `write_receipts` is set, the file contains them and the receipts
+/// static files are at the same height as the headers, i.e. no block is waiting for execution.
+/// Note that executing the imported blocks replaces their receipts.
+pub fn import_blocks<DB: Database, R: BufRead>(
+    provider_factory: &ProviderFactory<DB>,
+    consensus: &dyn Consensus,
+    reader: &mut BlockFileReader<R>,
+    validation: ImportValidation,
+    write_receipts: bool,
+) -> eyre::Result<ImportBlocksResult> {
+    let mut total = ImportBlocksResult::default();
+    let mut write_receipts = write_receipts;
+    loop {
+        let (result, done) =
+            import_batch(provider_factory, consensus, reader, validation, &mut write_receipts)?;
+        total += result;
+        if done {
+            return Ok(total)
+        }
+        info!(target: "reth::cli", blocks = total.blocks, "Imported blocks");
+    }
+}
+
+/// Imports up to [`BLOCKS_PER_COMMIT`] blocks in one database transaction.
+///
+/// Returns `true` if the end of the file was reached.
+fn import_batch<DB: Database, R: BufRead>(
+    provider_factory: &ProviderFactory<DB>,
+    consensus: &dyn Consensus,
+    reader: &mut BlockFileReader<R>,
+    validation: ImportValidation,
+    write_receipts: &mut bool,
+) -> eyre::Result<(ImportBlocksResult, bool)> {
+    let provider = provider_factory.provider_rw()?;
+    let static_file_provider = provider_factory.static_file_provider();
+
+    let highest_header = static_file_provider
+        .get_highest_static_file_block(StaticFileSegment::Headers)
+        .unwrap_or_default();
+
+    for stage_id in [StageId::Headers, StageId::Bodies] {
+        let checkpoint = provider.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+        if checkpoint.block_number != highest_header {
+            eyre::bail!(
+                "{stage_id} stage is at block #{}, but the headers end at block #{highest_header}",
+                checkpoint.block_number
+            )
+        }
+    }
+
+    let mut next_tx_num = provider
+        .tx_ref()
+        .cursor_read::<tables::TransactionBlocks>()?
+        .last()?
+        .map(|(id, _)| id + 1)
+        .unwrap_or_default();
+    let next_static_file_tx_num = static_file_provider
+        .get_highest_static_file_tx(StaticFileSegment::Transactions)
+        .map(|id| id + 1)
+        .unwrap_or_default();
+    if next_static_file_tx_num != next_tx_num {
+        eyre::bail!(
+            "transaction static files end at transaction #{next_static_file_tx_num}, but the database at #{next_tx_num}"
+        )
+    }
+
+    let mut parent = provider
+        .sealed_header(highest_header)?
+        .ok_or(ProviderError::HeaderNotFound(highest_header.into()))?;
+    let mut total_difficulty = provider
+        .header_td_by_number(highest_header)?
+        .ok_or(ProviderError::TotalDifficultyNotFound(highest_header))?;
+    let mut result = ImportBlocksResult::default();
+    let mut done = false;
+    {
+        let mut headers_writer = static_file_provider.latest_writer(StaticFileSegment::Headers)?;
+        let mut transactions_writer =
+            static_file_provider.latest_writer(StaticFileSegment::Transactions)?;
+        let mut receipts_writer = if *write_receipts &&
+            static_file_provider.get_highest_static_file_block(StaticFileSegment::Receipts) ==
+                Some(highest_header)
+        {
+            Some(static_file_provider.latest_writer(StaticFileSegment::Receipts)?)
+        } else {
+            None
+        };
+
+        while result.blocks < BLOCKS_PER_COMMIT {
+            let Some(BlockRecord { block, receipts }) = reader.next_record()? else {
+                done = true;
+                break
+            };
+
+            let number = block.number;
+            if number <= highest_header {
+                let hash = provider
+                    .block_hash(number)?
+                    .ok_or(ProviderError::HeaderNotFound(number.into()))?;
+                if hash != block.hash_slow() {
+                    eyre::bail!("block #{number} does not match the database")
+                }
+                continue
+            }
+            if number != parent.number + 1 {
+                eyre::bail!("expected block #{}, got block #{number}", parent.number + 1)
+            }
+
+            let block = block.seal_slow();
+            total_difficulty += block.difficulty;
+            validate_block(
+                consensus,
+                &block,
+                receipts.as_deref(),
+                &parent,
+                total_difficulty,
+                validation,
+            )?;
+
+            // Headers
+            headers_writer.append_header(&block.header, total_difficulty, &block.hash())?;
+            provider.tx_ref().put::<tables::HeaderNumbers>(block.hash(), number)?;
+
+            // Bodies
+            let block_indices = StoredBlockBodyIndices {
+                first_tx_num: next_tx_num,
+                tx_count: block.body.len() as u64,
+            };
+            transactions_writer.increment_block(number)?;
+            if !block.body.is_empty() {
+                provider
+                    .tx_ref()
+                    .put::<tables::TransactionBlocks>(block_indices.last_tx_num(), number)?;
+            }
+            for transaction in &block.body {
+                transactions_writer.append_transaction(next_tx_num, &transaction.clone().into())?;
+                next_tx_num += 1;
+            }
+            if !block.ommers.is_empty() {
+                provider.tx_ref().put::<tables::BlockOmmers>(
+                    number,
+                    StoredBlockOmmers { ommers: block.ommers.clone() },
+                )?;
+            }
+            if let Some(withdrawals) = block.withdrawals.clone().filter(|w| !w.is_empty()) {
+                provider.tx_ref().put::<tables::BlockWithdrawals>(
+                    number,
+                    StoredBlockWithdrawals { withdrawals },
+                )?;
+            }
+            if let Some(requests) = block.requests.clone().filter(|r| !r.0.is_empty()) {
+                provider.tx_ref().put::<tables::BlockRequests>(number, requests)?;
+            }
+            provider.tx_ref().put::<tables::BlockBodyIndices>(number, block_indices.clone())?;
+
+            // Receipts
+            if let Some(writer) = &mut receipts_writer {
+                if let Some(receipts) = &receipts {
+                    if receipts.len() as u64 != block_indices.tx_count {
+                        eyre::bail!(
+                            "block #{number} has {} transactions, but {} receipts",
+                            block_indices.tx_count,
+                            receipts.len()
+                        )
+                    }
+                    writer.increment_block(number)?;
+                    for (tx_num, receipt) in block_indices.tx_num_range().zip(receipts) {
+                        writer.append_receipt(tx_num, receipt)?;
+                    }
+                    result.receipts += receipts.len() as u64;
+                } else {
+                    warn!(target: "reth::cli", block = number, "Block has no receipts, receipts of the following blocks are not imported");
+                    receipts_writer = None;
+                }
+            }
+
+            result.blocks += 1;
+            result.transactions += block_indices.tx_count;
+            parent = block.header;
+        }
+
+        if receipts_writer.is_none() {
+            *write_receipts = false;
+        }
+    }
+
+    let checkpoint = StageCheckpoint::new(parent.number);
+    provider.save_stage_checkpoint(StageId::Headers, checkpoint)?;
+    provider.save_stage_checkpoint(StageId::Bodies, checkpoint)?;
+
+    UnifiedStorageWriter::commit(provider, static_file_provider)?;
+
+    Ok((result, done))
+}
+
+/// Validates the block according to the validation level.
+fn validate_block(
+    consensus: &dyn Consensus,
+    block: &SealedBlock,
+    receipts: Option<&[reth_primitives::Receipt]>,
+    parent: &SealedHeader,
+    total_difficulty: U256,
+    validation: ImportValidation,
+) -> eyre::Result<()> {
+    if validation == ImportValidation::None {
+        return Ok(())
+    }
+
+    consensus.validate_header(&block.header)?;
+    consensus.validate_header_against_parent(&block.header, parent)?;
+    consensus.validate_header_with_total_difficulty(&block.header, total_difficulty)?;
+    if validation == ImportValidation::HeaderOnly {
+        return Ok(())
+    }
+
+    consensus.validate_block_pre_execution(block)?;
+    if let Some(receipts) = receipts {
+        let receipts_root = calculate_receipt_root_no_memo(&receipts.iter().collect::<Vec<_>>());
+        if receipts_root != block.receipts_root {
+            eyre::bail!(
+                "receipts root {receipts_root} of block #{} does not match the header, expected {}",
+                block.number,
+                block.receipts_root
+            )
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        block_file::BlockFileWriter,
+        export::{export_blocks, ExportResult},
+    };
+    use reth_chainspec::{BaseFeeParams, ChainSpec, ChainSpecBuilder};
+    use reth_db_common::init::init_genesis;
+    use reth_node_core::args::utils::SUPPORTED_CHAINS;
+    use reth_primitives::{
+        constants::{EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH},
+        proofs::calculate_transaction_root,
+        Block, Genesis, Header, Receipt,
+    };
+    use reth_provider::{
+        test_utils::create_test_provider_factory_with_chain_spec, BlockReader, ReceiptProvider,
+    };
+    use reth_testing_utils::generators::{self, random_signed_tx};
+    use std::sync::Arc;
+
+    #[test]
+    fn parse_import_blocks_command_chain_args() {
+        for chain in SUPPORTED_CHAINS {
+            let args: ImportBlocksCommand =
+                ImportBlocksCommand::parse_from(["reth", "--chain", chain, "blocks.rlp"]);
+            assert_eq!(
+                Ok(args.env.chain.chain),
+                chain.parse::<reth_chainspec::Chain>(),
+                "failed to parse chain {chain}"
+            );
+        }
+    }
+
+    fn chain_spec() -> Arc<ChainSpec> {
+        Arc::new(
+            ChainSpecBuilder::mainnet()
+                .genesis(Genesis::default().with_gas_limit(30_000_000))
+                .shanghai_activated()
+                .build(),
+        )
+    }
+
+    /// Returns a chain of blocks on top of the parent, with one transaction and receipt each.
+    fn chain(parent: &SealedHeader, len: u64) -> Vec<BlockRecord> {
+        let mut rng = generators::rng();
+        let mut parent = parent.header().clone();
+        (parent.number + 1..=parent.number + len)
+            .map(|number| {
+                let transactions = vec![random_signed_tx(&mut rng)];
+                let receipts = vec![Receipt {
+                    tx_type: transactions[0].tx_type(),
+                    success: true,
+                    cumulative_gas_used: 21_000,
+                    ..Default::default()
+                }];
+                let header = Header {
+                    parent_hash: parent.hash_slow(),
+                    number,
+                    gas_limit: parent.gas_limit,
+                    timestamp: parent.timestamp + 1,
+                    base_fee_per_gas: parent.next_block_base_fee(BaseFeeParams::ethereum()),
+                    transactions_root: calculate_transaction_root(&transactions),
+                    receipts_root: calculate_receipt_root_no_memo(&[&receipts[0]]),
+                    ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                    withdrawals_root: Some(EMPTY_ROOT_HASH),
+                    ..Default::default()
+                };
+                parent = header.clone();
+                BlockRecord {
+                    block: Block {
+                        header,
+                        body: transactions,
+                        withdrawals: Some(Default::default()),
+                        ..Default::default()
+                    },
+                    receipts: Some(receipts),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn import_exported_blocks() {
+        let chain_spec = chain_spec();
+        let source = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(source.clone()).unwrap();
+        let genesis = source.sealed_header(0).unwrap().unwrap();
+        let records = chain(&genesis, 5);
+
+        // write the blocks to an RLP file and import them into the source
+        let mut blocks = Vec::new();
+        let mut receipts = Vec::new();
+        {
+            let mut writer = BlockFileWriter::new(BlockFileFormat::Rlp, &mut blocks)
+                .with_receipts(Some(&mut receipts));
+            for record in &records {
+                writer.write(record).unwrap();
+            }
+        }
+        let mut reader =
+            BlockFileReader::new(BlockFileFormat::Rlp, &blocks[..], Some(&receipts[..]));
+        let consensus = EthBeaconConsensus::new(chain_spec.clone());
+        let result =
+            import_blocks(&source, &consensus, &mut reader, ImportValidation::Full, true).unwrap();
+        assert_eq!(result, ImportBlocksResult { blocks: 5, transactions: 5, receipts: 5 });
+
+        // export them as JSONL and import them into a fresh database
+        let mut exported = Vec::new();
+        let result = export_blocks(
+            &source,
+            0..=5,
+            &mut BlockFileWriter::new(BlockFileFormat::Jsonl, &mut exported).with_receipts(None),
+        )
+        .unwrap();
+        assert_eq!(result, ExportResult { blocks: 6, transactions: 5, receipts: 5 });
+
+        let target = create_test_provider_factory_with_chain_spec(chain_spec);
+        init_genesis(target.clone()).unwrap();
+        let mut reader = BlockFileReader::new(BlockFileFormat::Jsonl, &exported[..], None);
+        let result =
+            import_blocks(&target, &consensus, &mut reader, ImportValidation::Full, true).unwrap();
+        assert_eq!(result, ImportBlocksResult { blocks: 5, transactions: 5, receipts: 5 });
+
+        for record in records {
+            let number = record.block.number;
+            assert_eq!(target.block(number.into()).unwrap(), Some(record.block));
+            assert_eq!(target.receipts_by_block(number.into()).unwrap(), record.receipts);
+        }
+        let provider = target.provider().unwrap();
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::Bodies).unwrap(),
+            Some(StageCheckpoint::new(5))
+        );
+    }
+
+    #[test]
+    fn validation_levels() {
+        let chain_spec = chain_spec();
+        let consensus = EthBeaconConsensus::new(chain_spec.clone());
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec);
+        init_genesis(factory.clone()).unwrap();
+        let genesis = factory.sealed_header(0).unwrap().unwrap();
+
+        // a block with a wrong transactions root
+        let mut record = chain(&genesis, 1).remove(0);
+        record.block.body.push(record.block.body[0].clone());
+        let block = record.block.clone().seal_slow();
+
+        let receipts = record.receipts.as_deref();
+        let td = U256::ZERO;
+        assert!(validate_block(&consensus, &block, receipts, &genesis, td, ImportValidation::Full)
+            .is_err());
+        validate_block(&consensus, &block, receipts, &genesis, td, ImportValidation::HeaderOnly)
+            .unwrap();
+
+        // a block that doesn't extend its parent
+        let mut record = chain(&genesis, 1).remove(0);
+        record.block.header.parent_hash = Default::default();
+        let block = record.block.seal_slow();
+        assert!(validate_block(
+            &consensus,
+            &block,
+            None,
+            &genesis,
+            td,
+            ImportValidation::HeaderOnly
+        )
+        .is_err());
+        validate_block(&consensus, &block, None, &genesis, td, ImportValidation::None).unwrap();
+    }
+}
@@ -0,0 +1,324 @@
+//! Command that imports pre-merge chain history from era1 files.
+use crate::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use reth_beacon_consensus::EthBeaconConsensus;
+use reth_consensus::Consensus;
+use reth_db::tables;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    models::{StoredBlockBodyIndices, StoredBlockOmmers},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_era::{Era1Block, Era1Reader};
+use reth_node_core::version::SHORT_VERSION;
+use reth_provider::{
+    writer::UnifiedStorageWriter, BlockHashReader, HeaderProvider, ProviderError, ProviderFactory,
+    StageCheckpointReader, StageCheckpointWriter, StaticFileProviderFactory, StaticFileWriter,
+};
+use reth_stages::{StageCheckpoint, StageId};
+use reth_static_file_types::StaticFileSegment;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Imports pre-merge blocks from era1 files.
+#[derive(Debug, Parser)]
+pub struct ImportEraCommand {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The era1 files to import, or directories containing them.
+    ///
+    /// The headers, bodies and receipts are written directly to the database and static files,
+    /// replacing the online header and body download for the imported blocks. The accumulator
+    /// root of each file is verified before its blocks are imported.
+    ///
+    /// Files are imported in order of their blocks and must continue the chain in the database.
+    /// Blocks that are already in the database are skipped.
+    #[arg(value_name = "IMPORT_PATH", required = true, verbatim_doc_comment)]
+    paths: Vec<PathBuf>,
+}
+
+impl ImportEraCommand {
+    /// Execute `import-era` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        let Environment { provider_factory, config, .. } = self.env.init(AccessRights::RW)?;
+
+        let consensus = EthBeaconConsensus::new(self.env.chain.clone());
+        let write_receipts =
+            !config.prune.as_ref().is_some_and(|prune| prune.segments.has_receipts_pruning());
+
+        let mut total = ImportEraResult::default();
+        for path in era1_files(&self.paths)? {
+            let result = import_era1_file(&provider_factory, &consensus, &path, write_receipts)?;
+            info!(target: "reth::cli",
+                path = %path.display(),
+                blocks = result.blocks,
+                transactions = result.transactions,
+                receipts = result.receipts,
+                "Imported era1 file"
+            );
+            total += result;
+        }
+
+        info!(target: "reth::cli",
+            total_imported_blocks = total.blocks,
+            total_imported_txns = total.transactions,
+            total_imported_receipts = total.receipts,
+            "Era1 files imported"
+        );
+
+        Ok(())
+    }
+}
+
+/// Blocks, transactions and receipts imported from era1 files.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportEraResult {
+    /// Number of imported blocks.
+    pub blocks: u64,
+    /// Number of imported transactions.
+    pub transactions: u64,
+    /// Number of imported receipts.
+    pub receipts: u64,
+}
+
+impl std::ops::AddAssign for ImportEraResult {
+    fn add_assign(&mut self, other: Self) {
+        self.blocks += other.blocks;
+        self.transactions += other.transactions;
+        self.receipts += other.receipts;
+    }
+}
+
+/// Returns the era1 files at the given paths, expanding directories, sorted by file name.
+fn era1_files(paths: &[PathBuf]) -> eyre::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            for entry in reth_fs_util::read_dir(path)? {
+                let path = entry?.path();
+                if path.extension().is_some_and(|extension| extension == "era1") {
+                    files.push(path);
+                }
+            }
+        } else {
+            files.push(path.clone());
+        }
+    }
+    // era1 file names start with the network and the zero padded epoch
+    files.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+    Ok(files)
+}
+
+/// Imports the blocks of an era1 file that follow the highest block in the database.
+///
+/// The accumulator root of the file is verified before anything is written. Headers and bodies are
+/// validated against the consensus rules and their parents, and receipts against the receipts root
+/// of their headers. Headers and bodies are written the same way the headers and bodies stages
+/// write them, and their stage checkpoints are advanced to the last imported block.
+///
+/// Receipts are only written if `write_receipts` is set and the receipts static files are at the
+/// same height as the headers, i.e. no block is waiting for execution. Receipts from before
+/// Byzantium can't be imported, since they don't record the status of the transactions. Note that
+/// executing the imported blocks replaces their receipts.
+pub fn import_era1_file<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    consensus: &dyn Consensus,
+    path: &Path,
+    write_receipts: bool,
+) -> eyre::Result<ImportEraResult> {
+    let range = Era1Reader::open(path)?.verify()?;
+
+    let provider = provider_factory.provider_rw()?;
+    let static_file_provider = provider_factory.static_file_provider();
+
+    let highest_header = static_file_provider
+        .get_highest_static_file_block(StaticFileSegment::Headers)
+        .unwrap_or_default();
+    if *range.end() <= highest_header {
+        info!(target: "reth::cli", path = %path.display(), ?range, "Skipping era1 file, blocks already imported");
+        return Ok(ImportEraResult::default())
+    }
+    if *range.start() > highest_header + 1 {
+        eyre::bail!(
+            "era1 file {} starts at block #{}, but the database ends at block #{highest_header}",
+            path.display(),
+            range.start()
+        )
+    }
+
+    for stage_id in [StageId::Headers, StageId::Bodies] {
+        let checkpoint = provider.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+        if checkpoint.block_number != highest_header {
+            eyre::bail!(
+                "{stage_id} stage is at block #{}, but the headers end at block #{highest_header}",
+                checkpoint.block_number
+            )
+        }
+    }
+
+    let mut next_tx_num = provider
+        .tx_ref()
+        .cursor_read::<tables::TransactionBlocks>()?
+        .last()?
+        .map(|(id, _)| id + 1)
+        .unwrap_or_default();
+    let next_static_file_tx_num = static_file_provider
+        .get_highest_static_file_tx(StaticFileSegment::Transactions)
+        .map(|id| id + 1)
+        .unwrap_or_default();
+    if next_static_file_tx_num != next_tx_num {
+        eyre::bail!(
+            "transaction static files end at transaction #{next_static_file_tx_num}, but the database at #{next_tx_num}"
+        )
+    }
+
+    let write_receipts = write_receipts &&
+        static_file_provider.get_highest_static_file_block(StaticFileSegment::Receipts) ==
+            Some(highest_header);
+    if !write_receipts {
+        info!(target: "reth::cli", path = %path.display(), "Receipts are not imported, execution is behind or receipts are pruned");
+    }
+
+    let mut parent = provider
+        .sealed_header(highest_header)?
+        .ok_or(ProviderError::HeaderNotFound(highest_header.into()))?;
+    let mut total_difficulty = provider
+        .header_td_by_number(highest_header)?
+        .ok_or(ProviderError::TotalDifficultyNotFound(highest_header))?;
+    let mut result = ImportEraResult::default();
+    {
+        let mut headers_writer = static_file_provider.latest_writer(StaticFileSegment::Headers)?;
+        let mut transactions_writer =
+            static_file_provider.latest_writer(StaticFileSegment::Transactions)?;
+        let mut receipts_writer = if write_receipts {
+            Some(static_file_provider.latest_writer(StaticFileSegment::Receipts)?)
+        } else {
+            None
+        };
+
+        let mut reader = Era1Reader::open(path)?;
+        while let Some(block) = reader.next_block()? {
+            let number = block.header.number;
+            if number <= highest_header {
+                let hash = provider
+                    .block_hash(number)?
+                    .ok_or(ProviderError::HeaderNotFound(number.into()))?;
+                if hash != block.header.hash() {
+                    eyre::bail!(
+                        "block #{number} of era1 file {} does not match the database",
+                        path.display()
+                    )
+                }
+                continue
+            }
+
+            total_difficulty += block.header.difficulty;
+            if total_difficulty != block.total_difficulty {
+                eyre::bail!(
+                    "total difficulty {} of block #{number} does not match the database, expected {total_difficulty}",
+                    block.total_difficulty
+                )
+            }
+            validate_block(consensus, &block, &parent)?;
+            block.validate_receipts()?;
+
+            let Era1Block { header, body, .. } = &block;
+
+            // Headers
+            headers_writer.append_header(header, total_difficulty, &header.hash())?;
+            provider.tx_ref().put::<tables::HeaderNumbers>(header.hash(), number)?;
+
+            // Bodies
+            let block_indices = StoredBlockBodyIndices {
+                first_tx_num: next_tx_num,
+                tx_count: body.transactions.len() as u64,
+            };
+            transactions_writer.increment_block(number)?;
+            if !body.transactions.is_empty() {
+                provider
+                    .tx_ref()
+                    .put::<tables::TransactionBlocks>(block_indices.last_tx_num(), number)?;
+            }
+            for transaction in &body.transactions {
+                transactions_writer.append_transaction(next_tx_num, &transaction.clone().into())?;
+                next_tx_num += 1;
+            }
+            if !body.ommers.is_empty() {
+                provider.tx_ref().put::<tables::BlockOmmers>(
+                    number,
+                    StoredBlockOmmers { ommers: body.ommers.clone() },
+                )?;
+            }
+            provider.tx_ref().put::<tables::BlockBodyIndices>(number, block_indices.clone())?;
+
+            // Receipts
+            if let Some(writer) = &mut receipts_writer {
+                if let Some(receipts) = block.decode_receipts()? {
+                    if receipts.len() as u64 != block_indices.tx_count {
+                        eyre::bail!(
+                            "block #{number} has {} transactions, but {} receipts",
+                            block_indices.tx_count,
+                            receipts.len()
+                        )
+                    }
+                    writer.increment_block(number)?;
+                    for (tx_num, receipt) in block_indices.tx_num_range().zip(&receipts) {
+                        writer.append_receipt(tx_num, receipt)?;
+                    }
+                    result.receipts += receipts.len() as u64;
+                } else {
+                    warn!(target: "reth::cli", block = number, "Receipts from before Byzantium can't be imported");
+                    receipts_writer = None;
+                }
+            }
+
+            result.blocks += 1;
+            result.transactions += block_indices.tx_count;
+            parent = block.header;
+        }
+    }
+
+    let checkpoint = StageCheckpoint::new(parent.number);
+    provider.save_stage_checkpoint(StageId::Headers, checkpoint)?;
+    provider.save_stage_checkpoint(StageId::Bodies, checkpoint)?;
+
+    UnifiedStorageWriter::commit(provider, static_file_provider)?;
+
+    Ok(result)
+}
+
+/// Validates the block against the consensus rules and its parent.
+fn validate_block(
+    consensus: &dyn Consensus,
+    block: &Era1Block,
+    parent: &reth_primitives::SealedHeader,
+) -> eyre::Result<()> {
+    consensus.validate_header(&block.header)?;
+    consensus.validate_header_against_parent(&block.header, parent)?;
+    consensus.validate_header_with_total_difficulty(&block.header, block.total_difficulty)?;
+    consensus.validate_block_pre_execution(&block.block())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_node_core::args::utils::SUPPORTED_CHAINS;
+
+    #[test]
+    fn parse_import_era_command_chain_args() {
+        for chain in SUPPORTED_CHAINS {
+            let args: ImportEraCommand =
+                ImportEraCommand::parse_from(["reth", "--chain", chain, "."]);
+            assert_eq!(
+                Ok(args.env.chain.chain),
+                chain.parse::<reth_chainspec::Chain>(),
+                "failed to parse chain {chain}"
+            );
+        }
+    }
+}
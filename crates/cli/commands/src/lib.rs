@@ -8,11 +8,16 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod backup;
+pub mod block_file;
 pub mod common;
 pub mod config_cmd;
 pub mod db;
 pub mod dump_genesis;
+pub mod export;
 pub mod import;
+pub mod import_blocks;
+pub mod import_era;
 pub mod init_cmd;
 pub mod init_state;
 pub mod node;
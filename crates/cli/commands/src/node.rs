@@ -1,22 +1,27 @@
 //! Main node command for launching a node
 
 use clap::{value_parser, Args, Parser};
+use reth_backup::{Backup, BackupPaths};
 use reth_chainspec::ChainSpec;
 use reth_cli_runner::CliContext;
-use reth_cli_util::parse_socket_address;
+use reth_cli_util::{parse_key_value, parse_socket_address};
+use reth_config::{Config, ConfigOverrides, MetricsConfig};
 use reth_db::{init_db, DatabaseEnv};
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
 use reth_node_core::{
     args::{
         utils::{chain_help, chain_value_parser, SUPPORTED_CHAINS},
         DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        PruningArgs, RpcServerArgs, SnapshotArgs, TxPoolArgs,
     },
+    dirs::{ChainPath, DataDirPath},
     node_config::NodeConfig,
     version,
 };
-use reth_node_metrics::recorder::install_prometheus_recorder;
+use reth_node_metrics::recorder::install_prometheus_recorder_with;
+use reth_object_store::HttpObjectStore;
 use std::{ffi::OsString, fmt, future::Future, net::SocketAddr, path::PathBuf, sync::Arc};
+use tokio::runtime::Handle;
 
 /// Start the node
 #[derive(Debug, Parser)]
@@ -25,6 +30,21 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
     #[arg(long, value_name = "FILE", verbatim_doc_comment)]
     pub config: Option<PathBuf>,
 
+    /// The profile of the configuration file to apply, e.g. `op-sepolia` for the
+    /// `[profiles.op-sepolia]` table.
+    ///
+    /// Settings that are not overridden by the profile fall back to the top-level values.
+    #[arg(long, value_name = "NAME")]
+    pub config_profile: Option<String>,
+
+    /// Override a setting of the configuration file, e.g.
+    /// `--config.set stages.execution.max_blocks=1000`.
+    ///
+    /// Overrides take precedence over the configuration file and `RETH_` environment variables,
+    /// e.g. `RETH_STAGES_EXECUTION_MAX_BLOCKS`.
+    #[arg(long = "config.set", value_name = "KEY=VALUE", value_parser = parse_key_value)]
+    pub config_overrides: Vec<(String, String)>,
+
     /// The chain this node is running.
     ///
     /// Possible values are either a built-in chain or the path to a chain specification file.
@@ -104,6 +124,10 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
     #[command(flatten)]
     pub pruning: PruningArgs,
 
+    /// All snapshot related arguments with --snapshot prefix
+    #[command(flatten)]
+    pub snapshot: SnapshotArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -140,6 +164,8 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
         let Self {
             datadir,
             config,
+            config_profile,
+            config_overrides,
             chain,
             metrics,
             instance,
@@ -152,6 +178,7 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             db,
             dev,
             pruning,
+            snapshot,
             ext,
         } = self;
 
@@ -159,6 +186,8 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
         let mut node_config = NodeConfig {
             datadir,
             config,
+            config_profile,
+            config_overrides,
             chain,
             metrics,
             instance,
@@ -174,11 +203,13 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
 
         // Register the prometheus recorder before creating the database,
         // because database init needs it to register metrics.
-        let _ = install_prometheus_recorder();
+        install_prometheus_recorder_with(&metrics_config(&node_config)?)?;
 
         let data_dir = node_config.datadir();
         let db_path = data_dir.db();
 
+        bootstrap_from_snapshot(&snapshot, &data_dir).await?;
+
         tracing::info!(target: "reth::cli", path = ?db_path, "Opening database");
         let database = Arc::new(init_db(db_path.clone(), self.db.database_args())?.with_metrics());
 
@@ -194,6 +225,45 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
     }
 }
 
+/// Returns the `[metrics]` section of the config file with the environment and command line
+/// overrides applied.
+///
+/// The section is read before the node is launched, because it must be known when the metrics
+/// recorder is installed.
+fn metrics_config(config: &NodeConfig) -> eyre::Result<MetricsConfig> {
+    let path = config.config.clone().unwrap_or_else(|| config.datadir().config());
+    let file = if path.exists() { Config::from_path(&path)? } else { Config::default() };
+    let layered = ConfigOverrides::from_env()
+        .with_cli_overrides(config.config_overrides.iter().cloned())
+        .apply(&file)?;
+    Ok(layered.config.metrics.unwrap_or_default())
+}
+
+/// Restores the snapshot into the datadir, unless it already has a database.
+///
+/// The remaining blocks are synced normally once the node is launched.
+async fn bootstrap_from_snapshot(
+    snapshot: &SnapshotArgs,
+    data_dir: &ChainPath<DataDirPath>,
+) -> eyre::Result<()> {
+    let (Some(url), Some(signer)) = (&snapshot.url, snapshot.signer) else { return Ok(()) };
+    let paths = BackupPaths { db: data_dir.db(), static_files: data_dir.static_files() };
+    if !paths.needs_restore() {
+        tracing::debug!(target: "reth::cli", "Database exists, skipping snapshot bootstrap");
+        return Ok(())
+    }
+
+    tracing::info!(target: "reth::cli", %url, %signer, "Bootstrapping from snapshot");
+    let backup = Backup::new(HttpObjectStore::new(url.clone(), Handle::current())?)
+        .with_trusted_signer(signer);
+    let id = snapshot.id;
+    // Requests block on the runtime, so they are sent from a blocking task
+    let manifest = tokio::task::spawn_blocking(move || backup.restore(id, &paths)).await??;
+    tracing::info!(target: "reth::cli", id = manifest.id, size = manifest.size(), "Snapshot restored");
+
+    Ok(())
+}
+
 /// No Additional arguments
 #[derive(Debug, Clone, Copy, Default, Args)]
 #[non_exhaustive]
@@ -289,6 +359,37 @@ mod tests {
         assert!(config_path.ends_with(end), "{:?}", cmd.config);
     }
 
+    #[test]
+    fn parse_config_profile() {
+        let cmd = NodeCommand::try_parse_args_from(["reth"]).unwrap();
+        assert_eq!(cmd.config_profile, None);
+
+        let cmd =
+            NodeCommand::try_parse_args_from(["reth", "--config-profile", "op-sepolia"]).unwrap();
+        assert_eq!(cmd.config_profile.as_deref(), Some("op-sepolia"));
+    }
+
+    #[test]
+    fn parse_config_overrides() {
+        let cmd = NodeCommand::try_parse_args_from([
+            "reth",
+            "--config.set",
+            "stages.execution.max_blocks=1000",
+            "--config.set",
+            "prune.block_interval=10",
+        ])
+        .unwrap();
+        assert_eq!(
+            cmd.config_overrides,
+            [
+                ("stages.execution.max_blocks".to_string(), "1000".to_string()),
+                ("prune.block_interval".to_string(), "10".to_string()),
+            ]
+        );
+
+        assert!(NodeCommand::try_parse_args_from(["reth", "--config.set", "prune"]).is_err());
+    }
+
     #[test]
     fn parse_db_path() {
         let cmd = NodeCommand::try_parse_args_from(["reth"]).unwrap();
@@ -1,7 +1,10 @@
 //! Command that runs pruning without any limits.
 use crate::common::{AccessRights, Environment, EnvironmentArgs};
 use clap::Parser;
-use reth_prune::PrunerBuilder;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use reth_provider::StaticFileProviderFactory;
+use reth_prune::{PruneEstimate, PrunerBuilder};
 use reth_static_file::StaticFileProducer;
 use tracing::info;
 
@@ -10,6 +13,13 @@ use tracing::info;
 pub struct PruneCommand {
     #[command(flatten)]
     env: EnvironmentArgs,
+
+    /// Report how many entries and bytes each prune segment would delete, without deleting
+    /// anything.
+    ///
+    /// Only data that was already copied to static files is taken into account.
+    #[arg(long)]
+    dry_run: bool,
 }
 
 impl PruneCommand {
@@ -18,6 +28,22 @@ impl PruneCommand {
         let Environment { config, provider_factory, .. } = self.env.init(AccessRights::RW)?;
         let prune_config = config.prune.unwrap_or_default();
 
+        if self.dry_run {
+            let prune_tip =
+                provider_factory.static_file_provider().get_highest_static_files().min();
+            let Some(prune_tip) = prune_tip else {
+                info!(target: "reth::cli", "No static files to prune up to");
+                return Ok(())
+            };
+
+            info!(target: "reth::cli", ?prune_tip, ?prune_config, "Estimating data to prune...");
+            let estimate = PrunerBuilder::new(prune_config)
+                .build_with_provider_factory(provider_factory)
+                .dry_run(prune_tip)?;
+            println!("{}", estimate_table(&estimate));
+            return Ok(())
+        }
+
         // Copy data from database to static files
         info!(target: "reth::cli", "Copying data from database to static files...");
         let static_file_producer =
@@ -40,3 +66,33 @@ impl PruneCommand {
         Ok(())
     }
 }
+
+fn estimate_table(estimate: &PruneEstimate) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header(["Segment", "Prune Mode", "Up To Block", "Table", "# Entries", "Size"]);
+
+    for segment in &estimate.segments {
+        for table_estimate in &segment.tables {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(segment.segment))
+                .add_cell(Cell::new(format!("{:?}", segment.mode)))
+                .add_cell(Cell::new(segment.to_block))
+                .add_cell(Cell::new(table_estimate.table.name()))
+                .add_cell(Cell::new(table_estimate.entries))
+                .add_cell(Cell::new(human_bytes(table_estimate.bytes as f64)));
+            table.add_row(row);
+        }
+    }
+
+    let mut row = Row::new();
+    row.add_cell(Cell::new("Total"))
+        .add_cell(Cell::new(""))
+        .add_cell(Cell::new(""))
+        .add_cell(Cell::new(""))
+        .add_cell(Cell::new(estimate.entries()))
+        .add_cell(Cell::new(human_bytes(estimate.bytes() as f64)));
+    table.add_row(row);
+
+    table
+}
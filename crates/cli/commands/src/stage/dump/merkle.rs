@@ -13,7 +13,7 @@ use reth_prune::PruneModes;
 use reth_stages::{
     stages::{
         AccountHashingStage, ExecutionStage, MerkleStage, StorageHashingStage,
-        MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD,
+        MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD, MERKLE_STAGE_DEFAULT_COMMIT_THRESHOLD,
     },
     ExecutionStageThresholds, Stage, StageCheckpoint, UnwindInput,
 };
@@ -151,6 +151,7 @@ fn dry_run<DB: Database>(
     let mut stage = MerkleStage::Execution {
         // Forces updating the root instead of calculating from scratch
         clean_threshold: u64::MAX,
+        commit_threshold: MERKLE_STAGE_DEFAULT_COMMIT_THRESHOLD,
     };
 
     loop {
@@ -237,6 +237,7 @@ impl Command {
                 StageEnum::Senders => (
                     Box::new(SenderRecoveryStage::new(SenderRecoveryConfig {
                         commit_threshold: batch_size,
+                        ..config.stages.sender_recovery
                     })),
                     None,
                 ),
@@ -278,7 +279,7 @@ impl Command {
                     None,
                 ),
                 StageEnum::Merkle => (
-                    Box::new(MerkleStage::new_execution(config.stages.merkle.clean_threshold)),
+                    Box::new(MerkleStage::new_execution(config.stages.merkle)),
                     Some(Box::new(MerkleStage::default_unwind())),
                 ),
                 StageEnum::AccountHistory => (
@@ -42,6 +42,10 @@ pub struct Command {
     /// unwound.
     #[arg(long)]
     offline: bool,
+
+    /// Check that the unwind is possible and print what would be reverted, without unwinding.
+    #[arg(long)]
+    dry_run: bool,
 }
 
 impl Command {
@@ -53,6 +57,17 @@ impl Command {
         if *range.start() == 0 {
             eyre::bail!("Cannot unwind genesis block")
         }
+        let target = *range.start() - 1;
+
+        // This will build an offline-only pipeline if the `offline` flag is enabled
+        let mut pipeline = self.build_pipeline(config, provider_factory.clone())?;
+
+        // Fails if the data required to unwind is pruned or moved to the object store.
+        let plan = pipeline.unwind_plan(target)?;
+        if self.dry_run {
+            println!("{plan}");
+            return Ok(())
+        }
 
         let highest_static_file_block = provider_factory
             .static_file_provider()
@@ -77,13 +92,10 @@ impl Command {
                 info!(target: "reth::cli", ?range, "Executing a pipeline unwind.");
             }
 
-            // This will build an offline-only pipeline if the `offline` flag is enabled
-            let mut pipeline = self.build_pipeline(config, provider_factory)?;
-
             // Move all applicable data from database to static files.
             pipeline.move_to_static_files()?;
 
-            pipeline.unwind((*range.start()).saturating_sub(1), None)?;
+            pipeline.unwind_to(target)?;
         } else {
             info!(target: "reth::cli", ?range, "Executing a database unwind.");
             let provider = provider_factory.provider_rw()?;
@@ -111,7 +123,7 @@ impl Command {
     }
 
     fn build_pipeline<DB: Database + 'static>(
-        self,
+        &self,
         config: Config,
         provider_factory: ProviderFactory<Arc<DB>>,
     ) -> Result<Pipeline<Arc<DB>>, eyre::Error> {
@@ -216,5 +228,9 @@ mod tests {
 
         let cmd = Command::parse_from(["reth", "--datadir", "dir", "num-blocks", "100"]);
         assert_eq!(cmd.command, Subcommands::NumBlocks { amount: 100 });
+        assert!(!cmd.dry_run);
+
+        let cmd = Command::parse_from(["reth", "--datadir", "dir", "--dry-run", "to-block", "100"]);
+        assert!(cmd.dry_run);
     }
 }
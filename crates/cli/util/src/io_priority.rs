@@ -0,0 +1,36 @@
+/// The idle I/O scheduling class, see `ioprio_set(2)`.
+#[cfg(target_os = "linux")]
+const IOPRIO_CLASS_IDLE: libc::c_int = 3;
+
+/// The bit offset of the class in an I/O priority value.
+#[cfg(target_os = "linux")]
+const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+
+/// Targets a single thread in `ioprio_set(2)`.
+#[cfg(target_os = "linux")]
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+
+/// Moves the current thread to the idle I/O scheduling class, so its disk I/O is only served when
+/// no other thread needs the disk.
+///
+/// This is only supported on Linux and only has an effect with an I/O scheduler that supports
+/// priorities, e.g. BFQ. On other platforms it does nothing.
+pub fn set_idle_io_priority() -> std::io::Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        // SAFETY: `ioprio_set` doesn't access memory of the caller, and `0` targets the calling
+        // thread.
+        let ret = unsafe {
+            libc::syscall(
+                libc::SYS_ioprio_set,
+                IOPRIO_WHO_PROCESS,
+                0,
+                IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT,
+            )
+        };
+        if ret == -1 {
+            return Err(std::io::Error::last_os_error())
+        }
+    }
+    Ok(())
+}
@@ -16,9 +16,17 @@ pub use load_secret_key::get_secret_key;
 pub mod parsers;
 pub use parsers::{
     hash_or_num_value_parser, parse_duration_from_secs, parse_duration_from_secs_or_ms,
-    parse_socket_address,
+    parse_key_value, parse_socket_address,
 };
 
+/// I/O scheduling priority of the current thread.
+pub mod io_priority;
+pub use io_priority::set_idle_io_priority;
+
+/// CPU affinity and scheduling priority of the current thread.
+pub mod thread_priority;
+pub use thread_priority::{pin_current_thread, set_current_thread_nice};
+
 #[cfg(all(unix, any(target_env = "gnu", target_os = "macos")))]
 pub mod sigsegv_handler;
 
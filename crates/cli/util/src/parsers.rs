@@ -82,6 +82,16 @@ pub fn parse_socket_address(value: &str) -> eyre::Result<SocketAddr, SocketAddre
         .ok_or_else(|| SocketAddressParsingError::Parse(value.to_string()))
 }
 
+/// Parse a `KEY=VALUE` pair, the value may contain `=`.
+pub fn parse_key_value(arg: &str) -> eyre::Result<(String, String)> {
+    let (key, value) =
+        arg.split_once('=').ok_or_else(|| eyre::eyre!("expected KEY=VALUE, got `{arg}`"))?;
+    if key.is_empty() {
+        eyre::bail!("missing key in `{arg}`")
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,4 +134,14 @@ mod tests {
 
         assert!(parse_duration_from_secs_or_ms("5ns").is_err());
     }
+
+    #[test]
+    fn parse_key_values() {
+        let (key, value) = parse_key_value("stages.etl.dir=/data/etl=tmp").unwrap();
+        assert_eq!(key, "stages.etl.dir");
+        assert_eq!(value, "/data/etl=tmp");
+
+        assert!(parse_key_value("stages.etl.dir").is_err());
+        assert!(parse_key_value("=1").is_err());
+    }
 }
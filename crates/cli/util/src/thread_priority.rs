@@ -0,0 +1,50 @@
+/// Pins the current thread to the given CPU cores, see `sched_setaffinity(2)`.
+///
+/// This is only supported on Linux. On other platforms it does nothing.
+pub fn pin_current_thread(cpus: &[usize]) -> std::io::Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        // SAFETY: `cpu_set_t` is a plain bit mask, for which all zeroes is a valid value.
+        let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        for &cpu in cpus {
+            if cpu >= libc::CPU_SETSIZE as usize {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("CPU core {cpu} is out of range"),
+                ))
+            }
+            // SAFETY: `cpu` was checked to be within the bounds of the set.
+            unsafe { libc::CPU_SET(cpu, &mut set) };
+        }
+        // SAFETY: `set` is a valid `cpu_set_t` of the passed size, and `0` targets the calling
+        // thread.
+        let ret = unsafe { libc::sched_setaffinity(0, std::mem::size_of_val(&set), &set) };
+        if ret == -1 {
+            return Err(std::io::Error::last_os_error())
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = cpus;
+    Ok(())
+}
+
+/// Sets the nice value of the current thread, see `setpriority(2)`. Lower values get more CPU
+/// time, negative values usually require elevated privileges.
+///
+/// This is only supported on Linux, where the nice value applies per thread. On other platforms it
+/// does nothing.
+pub fn set_current_thread_nice(nice: i32) -> std::io::Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        // SAFETY: `gettid` has no preconditions.
+        let tid = unsafe { libc::gettid() };
+        // SAFETY: `setpriority` doesn't access memory of the caller.
+        let ret = unsafe { libc::setpriority(libc::PRIO_PROCESS, tid as libc::id_t, nice) };
+        if ret == -1 {
+            return Err(std::io::Error::last_os_error())
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = nice;
+    Ok(())
+}
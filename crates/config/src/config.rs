@@ -1,11 +1,14 @@
 //! Configuration files.
 
+use alloy_primitives::Address;
 use eyre::eyre;
 use reth_network_types::{PeersConfig, SessionsConfig};
-use reth_prune_types::PruneModes;
-use reth_stages_types::ExecutionStageThresholds;
-use serde::{Deserialize, Deserializer, Serialize};
+use reth_prune_types::{PruneModes, PruneWindow};
+use reth_stages_types::{ExecutionStageThresholds, StageProgressThresholds};
+use reth_static_file_types::StaticFileCompression;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
 use std::{
+    collections::BTreeMap,
     ffi::OsStr,
     fs,
     path::{Path, PathBuf},
@@ -28,9 +31,64 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration of the discv4 and discv5 peer discovery protocols.
+    pub discovery: DiscoveryConfig,
+    /// Overrides of the RPC request limits and the gas price oracle.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpc: Option<RpcConfig>,
+    /// Overrides of the EVM settings of the chain, e.g. for devnets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evm: Option<EvmConfig>,
+    /// Export of spans to an OpenTelemetry collector.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracing: Option<TracingConfig>,
+    /// Which metrics are recorded and the cardinality of their labels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<MetricsConfig>,
+    /// Audit log of reorgs, invalid blocks, forkchoice changes and built payloads.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audit_log: Option<AuditLogConfig>,
+    /// Re-execution of sampled historical blocks to detect corrupted data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verifier: Option<BlockVerifierConfig>,
+    /// Background consistency check of the stored chain.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integrity_check: Option<IntegrityCheckConfig>,
+    /// Forecasting of the disk usage and the alarm when a volume is running out of free space.
+    pub disk_alarm: DiskAlarmConfig,
+    /// Dedicated runtimes for the task priority classes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tasks: Option<TasksConfig>,
+    /// Latency budgets of the engine API messages.
+    pub engine: EngineConfig,
+    /// Configuration for static files.
+    pub static_files: StaticFilesConfig,
+    /// Named profiles, e.g. `[profiles.mainnet]`, that override parts of this configuration.
+    ///
+    /// See [`Config::with_profile`].
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, ConfigProfile>,
 }
 
 impl Config {
+    /// Returns the configuration with the overrides of the given profile applied.
+    ///
+    /// Settings that are not overridden by the profile fall back to the top-level values.
+    pub fn with_profile(&self, name: &str) -> eyre::Result<Self> {
+        let profile =
+            self.profiles.get(name).ok_or_else(|| eyre!("Unknown config profile `{name}`"))?;
+
+        let mut config = toml::Table::try_from(Self { profiles: BTreeMap::new(), ..self.clone() })
+            .map_err(|e| eyre!("Failed to serialize config: {e}"))?;
+        profile.apply(&mut config).map_err(|e| eyre!("Invalid config profile `{name}`: {e}"))?;
+
+        let mut config: Self = toml::Value::Table(config)
+            .try_into()
+            .map_err(|e| eyre!("Invalid config profile `{name}`: {e}"))?;
+        config.profiles.clone_from(&self.profiles);
+        Ok(config)
+    }
+
     /// Load a [`Config`] from a specified path.
     ///
     /// A new configuration file is created with default values if none
@@ -91,6 +149,51 @@ impl Config {
     }
 }
 
+/// Overrides of a named config profile, see [`Config::with_profile`].
+///
+/// A profile can override any setting of the `stages`, `peers`, `sessions`, `discovery` and
+/// `prune` sections, nested tables are merged with the top-level values.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct ConfigProfile(pub toml::Table);
+
+impl ConfigProfile {
+    /// The sections of the config a profile can override.
+    pub const SECTIONS: [&'static str; 5] = ["stages", "peers", "sessions", "discovery", "prune"];
+
+    /// Merges the overrides into the given config table.
+    fn apply(&self, config: &mut toml::Table) -> eyre::Result<()> {
+        for (section, value) in &self.0 {
+            if !Self::SECTIONS.contains(&section.as_str()) {
+                return Err(eyre!("section `{section}` can't be overridden"))
+            }
+            let base = config.entry(section.clone()).or_insert_with(|| toml::Table::new().into());
+            merge_toml(base, value);
+        }
+        Ok(())
+    }
+}
+
+// Like `ExtraStageConfig`, profiles only hold plain settings.
+impl Eq for ConfigProfile {}
+
+/// Recursively merges `value` into `base`, tables are merged and all other values are replaced.
+fn merge_toml(base: &mut toml::Value, value: &toml::Value) {
+    match (base, value) {
+        (toml::Value::Table(base), toml::Value::Table(value)) => {
+            for (key, value) in value {
+                match base.get_mut(key) {
+                    Some(base) => merge_toml(base, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, value) => *base = value.clone(),
+    }
+}
+
 /// Configuration for each stage in the pipeline.
 #[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -99,6 +202,8 @@ pub struct StageConfig {
     pub headers: HeadersConfig,
     /// Body stage configuration.
     pub bodies: BodiesConfig,
+    /// Snap sync stage configuration.
+    pub snap: SnapSyncConfig,
     /// Sender Recovery stage configuration.
     pub sender_recovery: SenderRecoveryConfig,
     /// Execution stage configuration.
@@ -119,9 +224,21 @@ pub struct StageConfig {
     pub index_storage_history: IndexHistoryConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
+    /// Throughput measurement and minimum throughput of the stages.
+    pub progress: StageProgressConfig,
+    /// Configuration of custom stages, keyed by the name of their `[stages.extra.<name>]` table.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, ExtraStageConfig>,
 }
 
 impl StageConfig {
+    /// Returns the configuration of the custom stage with the given name.
+    ///
+    /// Returns `Ok(None)` if there is no `[stages.extra.<name>]` table.
+    pub fn extra<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>, toml::de::Error> {
+        self.extra.get(name).map(ExtraStageConfig::deserialize_into).transpose()
+    }
+
     /// The highest threshold (in number of blocks) for switching between incremental and full
     /// calculations across `MerkleStage`, `AccountHashingStage` and `StorageHashingStage`. This is
     /// required to figure out if can prune or not changesets on subsequent pipeline runs during
@@ -134,6 +251,21 @@ impl StageConfig {
     }
 }
 
+/// Configuration table of a custom stage, see [`StageConfig::extra`].
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct ExtraStageConfig(pub toml::Table);
+
+impl ExtraStageConfig {
+    /// Deserializes the table into the config type of the stage.
+    pub fn deserialize_into<T: DeserializeOwned>(&self) -> Result<T, toml::de::Error> {
+        toml::Value::Table(self.0.clone()).try_into()
+    }
+}
+
+// Stage configs are plain settings, so `NaN` floats are not expected in them.
+impl Eq for ExtraStageConfig {}
+
 /// Header stage configuration.
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -206,17 +338,45 @@ impl Default for BodiesConfig {
     }
 }
 
+/// Snap sync stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct SnapSyncConfig {
+    /// Whether to download the state at the sync target from peers with the `snap/1` protocol
+    /// instead of executing all historical blocks.
+    ///
+    /// Requires receipts pruning, since receipts are only created for blocks executed after the
+    /// snap sync.
+    pub enabled: bool,
+    /// The soft limit of the response size of each request, in bytes.
+    ///
+    /// Default: 512KB
+    pub response_bytes: u64,
+}
+
+impl Default for SnapSyncConfig {
+    fn default() -> Self {
+        Self { enabled: false, response_bytes: 512 * 1024 }
+    }
+}
+
 /// Sender recovery stage configuration.
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
 pub struct SenderRecoveryConfig {
     /// The maximum number of transactions to process before committing progress to the database.
     pub commit_threshold: u64,
+    /// The number of dedicated threads that recover senders, or `0` to use the global rayon
+    /// pool, which has a thread per core.
+    pub workers: usize,
+    /// The number of transactions whose senders are recovered in parallel before they are
+    /// written to the database.
+    pub batch_size: usize,
 }
 
 impl Default for SenderRecoveryConfig {
     fn default() -> Self {
-        Self { commit_threshold: 5_000_000 }
+        Self { commit_threshold: 5_000_000, workers: 0, batch_size: 100_000 }
     }
 }
 
@@ -236,6 +396,9 @@ pub struct ExecutionConfig {
         deserialize_with = "deserialize_duration"
     )]
     pub max_duration: Option<Duration>,
+    /// The number of threads that speculatively execute the transactions of the next block in
+    /// parallel to warm up the state it reads, or `0` to disable pre-warming.
+    pub prewarm_workers: usize,
 }
 
 impl Default for ExecutionConfig {
@@ -247,6 +410,7 @@ impl Default for ExecutionConfig {
             max_cumulative_gas: Some(30_000_000 * 50_000),
             // 10 minutes
             max_duration: Some(Duration::from_secs(10 * 60)),
+            prewarm_workers: 0,
         }
     }
 }
@@ -300,14 +464,39 @@ pub struct MerkleConfig {
     /// The threshold (in number of blocks) for switching from incremental trie building of changes
     /// to whole rebuild.
     pub clean_threshold: u64,
+    /// The maximum number of trie updates of a whole rebuild to compute before committing its
+    /// intermediate progress to the database.
+    pub commit_threshold: u64,
+    /// The number of threads that compute the state root of new blocks by partitioning the
+    /// account trie by the first nibble, or `0` to walk the account trie serially.
+    pub workers: usize,
+    /// The trie the state root is computed with.
+    pub backend: TrieBackend,
 }
 
 impl Default for MerkleConfig {
     fn default() -> Self {
-        Self { clean_threshold: 5_000 }
+        Self {
+            clean_threshold: 5_000,
+            commit_threshold: 100_000,
+            workers: 0,
+            backend: TrieBackend::default(),
+        }
     }
 }
 
+/// The trie the state root is computed with.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrieBackend {
+    /// The Merkle Patricia Trie of the Ethereum mainnet.
+    #[default]
+    Mpt,
+    /// The experimental verkle trie of [EIP-6800](https://eips.ethereum.org/EIPS/eip-6800),
+    /// which requires the `verkle` feature.
+    Verkle,
+}
+
 /// Transaction Lookup stage configuration.
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -356,6 +545,33 @@ impl EtlConfig {
     }
 }
 
+/// Configuration of the throughput measurement of the stages, `[stages.progress]`.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct StageProgressConfig {
+    /// The minimum time a stage has to spend executing before its throughput and ETA are
+    /// reported.
+    #[serde(with = "humantime_serde")]
+    pub window: Duration,
+    /// The minimum number of entities per second of a stage, keyed by the stage name, e.g.
+    /// `execution` or `sender_recovery`. Slower stages are logged and counted in the metrics.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub min_throughput: BTreeMap<String, u64>,
+}
+
+impl Default for StageProgressConfig {
+    fn default() -> Self {
+        let StageProgressThresholds { window, min_throughput } = Default::default();
+        Self { window, min_throughput }
+    }
+}
+
+impl From<StageProgressConfig> for StageProgressThresholds {
+    fn from(config: StageProgressConfig) -> Self {
+        Self { window: config.window, min_throughput: config.min_throughput }
+    }
+}
+
 /// History stage configuration.
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -379,18 +595,502 @@ pub struct PruneConfig {
     /// Pruning configuration for every part of the data that can be pruned.
     #[serde(alias = "parts")]
     pub segments: PruneModes,
+    /// Restrictions on when and how fast the pruner runs alongside the node.
+    #[serde(skip_serializing_if = "PruneScheduleConfig::is_empty")]
+    pub schedule: PruneScheduleConfig,
 }
 
 impl Default for PruneConfig {
     fn default() -> Self {
-        Self { block_interval: 5, segments: PruneModes::none() }
+        Self {
+            block_interval: 5,
+            segments: PruneModes::none(),
+            schedule: PruneScheduleConfig::default(),
+        }
+    }
+}
+
+/// Scheduling of the pruner running alongside the node, `[prune.schedule]`.
+///
+/// These settings don't apply to the `reth prune` command.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct PruneScheduleConfig {
+    /// The maximum time a single prune run can take. If unset, a run takes at most 100ms.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "humantime_serde::serialize",
+        deserialize_with = "humantime_serde::deserialize"
+    )]
+    pub max_run_time: Option<Duration>,
+    /// The maximum average number of entries deleted from the database per second.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_entries_per_second: Option<u64>,
+    /// Daily time windows in UTC during which pruning is allowed, e.g. `02:00-06:00`. If empty,
+    /// pruning is allowed at any time.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub windows: Vec<PruneWindow>,
+}
+
+impl PruneScheduleConfig {
+    /// Returns `true` if no restrictions are configured.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
     }
 }
 
 impl PruneConfig {
     /// Returns whether there is any kind of receipt pruning configuration.
     pub fn has_receipts_pruning(&self) -> bool {
-        self.segments.receipts.is_some() || !self.segments.receipts_log_filter.is_empty()
+        self.segments.has_receipts_pruning()
+    }
+}
+
+/// Static files configuration, `[static_files]`.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct StaticFilesConfig {
+    /// Compression codec of newly created static files of each segment.
+    pub compression: StaticFileCompression,
+    /// Whether existing static files are recompressed in the background on startup, if their
+    /// compression codec differs from the configured one.
+    pub recompress: bool,
+    /// Object store that the data of old static files is moved to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<RemoteStaticFilesConfig>,
+}
+
+impl Default for StaticFilesConfig {
+    fn default() -> Self {
+        Self { compression: StaticFileCompression::default(), recompress: true, remote: None }
+    }
+}
+
+/// Remote static files configuration, `[static_files.remote]`.
+///
+/// S3 credentials are read from the `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` environment
+/// variables.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
+pub struct RemoteStaticFilesConfig {
+    /// Location of the object store, either `s3://<bucket>/<prefix>` or a local directory.
+    pub location: String,
+    /// Endpoint of the S3 compatible object store.
+    #[serde(default = "RemoteStaticFilesConfig::default_endpoint")]
+    pub endpoint: String,
+    /// Region of the S3 compatible object store.
+    #[serde(default = "RemoteStaticFilesConfig::default_region")]
+    pub region: String,
+    /// Static files that end below this block are moved to the object store.
+    pub cutoff: u64,
+    /// Maximum size in bytes of the data of remote static files kept on local disk.
+    #[serde(default)]
+    pub cache_size: u64,
+}
+
+impl RemoteStaticFilesConfig {
+    /// Default endpoint of the S3 compatible object store.
+    pub fn default_endpoint() -> String {
+        "https://s3.amazonaws.com".to_string()
+    }
+
+    /// Default region of the S3 compatible object store.
+    pub fn default_region() -> String {
+        "us-east-1".to_string()
+    }
+}
+
+/// Peer discovery configuration, `[discovery]`.
+///
+/// Discv4 and discv5 can run side by side, the command line flags that disable discv4 or enable
+/// discv5 take precedence over these settings.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct DiscoveryConfig {
+    /// Discovery v4 settings, `[discovery.v4]`.
+    pub v4: DiscoveryV4Config,
+    /// Discovery v5 settings, `[discovery.v5]`.
+    pub v5: DiscoveryV5Config,
+}
+
+/// Discovery v4 settings of the [`DiscoveryConfig`].
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct DiscoveryV4Config {
+    /// Whether discv4 is enabled.
+    pub enabled: bool,
+    /// Max number of new peers discovered via discv4 that are used per second. Unlimited if not
+    /// set.
+    pub max_peers_per_second: Option<u32>,
+}
+
+impl Default for DiscoveryV4Config {
+    fn default() -> Self {
+        Self { enabled: true, max_peers_per_second: None }
+    }
+}
+
+/// Discovery v5 settings of the [`DiscoveryConfig`].
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct DiscoveryV5Config {
+    /// Whether discv5 is enabled.
+    pub enabled: bool,
+    /// Whether peers must advertise the fork id of the chain in their node record. Peers without
+    /// it are dropped, peers with an incompatible fork id are not connected to.
+    pub require_fork_id: bool,
+    /// Max number of new peers discovered via discv5 that are used per second. Unlimited if not
+    /// set.
+    pub max_peers_per_second: Option<u32>,
+}
+
+/// RPC settings that override the values of the command line arguments.
+///
+/// Unset values keep the value of the corresponding argument. All of these settings are applied
+/// when the configuration file is reloaded while the node is running.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct RpcConfig {
+    /// Maximum gas limit for `eth_call` and call tracing RPC methods.
+    pub gas_cap: Option<u64>,
+    /// Maximum number of logs that can be returned in a single response.
+    pub max_logs_per_response: Option<usize>,
+    /// Maximum number of concurrent tracing requests.
+    pub max_tracing_requests: Option<usize>,
+    /// Settings of the gas price oracle.
+    pub gas_price_oracle: GasPriceOracleOverrides,
+}
+
+/// Gas price oracle settings of the [`RpcConfig`].
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct GasPriceOracleOverrides {
+    /// Number of recent blocks to check for gas price.
+    pub blocks: Option<u32>,
+    /// Gas price percentile to use for the estimate.
+    pub percentile: Option<u32>,
+    /// Maximum gas price in wei.
+    pub max_price: Option<u64>,
+    /// Gas price in wei below which transactions are ignored.
+    pub ignore_price: Option<u64>,
+}
+
+/// EVM settings that override the values of the chain spec.
+///
+/// Meant for devnets that need EVM changes without defining a new chain spec. The overrides only
+/// change the EVM, consensus rules of the chain spec are not affected.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct EvmConfig {
+    /// The chain id used by the EVM.
+    pub chain_id: Option<u64>,
+    /// The maximum size of deployed contract code in bytes, see EIP-170.
+    pub max_code_size: Option<usize>,
+    /// Spec ids, e.g. `Cancun`, that are active from the given block numbers on.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub spec_activations: Vec<EvmSpecActivation>,
+    /// Additional precompile addresses.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub precompiles: Vec<EvmPrecompileAlias>,
+}
+
+/// Activation of a spec id in the [`EvmConfig`].
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct EvmSpecActivation {
+    /// The block number from which on the spec id is active.
+    pub block: u64,
+    /// The name of the spec id, e.g. `Cancun`.
+    pub spec: String,
+}
+
+/// Additional precompile address in the [`EvmConfig`].
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct EvmPrecompileAlias {
+    /// The address of the new precompile.
+    pub address: Address,
+    /// The address of the existing precompile the new precompile behaves like.
+    pub precompile: Address,
+}
+
+/// Export of spans to an OpenTelemetry collector via OTLP.
+///
+/// Spans of RPC requests, engine API calls, block processing and stage runs are exported if an
+/// endpoint is set.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct TracingConfig {
+    /// The gRPC endpoint of the collector, e.g. `http://localhost:4317`.
+    pub otlp_endpoint: Option<String>,
+    /// The ratio of traces that are sampled, between `0.0` and `1.0`. All traces are sampled if
+    /// not set.
+    #[serde(deserialize_with = "deserialize_sample_ratio")]
+    pub sample_ratio: Option<f64>,
+    /// The service name of exported spans, `reth` if not set.
+    pub service_name: Option<String>,
+    /// Filter directives of the exported spans, e.g. `rpc=debug,engine=trace`.
+    pub filter: Option<String>,
+}
+
+// The sample ratio is never NaN, see `deserialize_sample_ratio`.
+impl Eq for TracingConfig {}
+
+/// Deserializes a sample ratio and rejects values outside of `0.0..=1.0`.
+fn deserialize_sample_ratio<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let ratio = Option::<f64>::deserialize(deserializer)?;
+    if let Some(ratio) = ratio.filter(|ratio| !(0.0..=1.0).contains(ratio)) {
+        return Err(serde::de::Error::custom(format!(
+            "invalid sample ratio {ratio}, expected a value between 0 and 1"
+        )))
+    }
+    Ok(ratio)
+}
+
+/// Controls which metrics are recorded and how they're exported, to keep the number of series
+/// exported to Prometheus bounded.
+///
+/// Metric names are the names of the Prometheus endpoint, e.g. `reth_sync_checkpoint`. A trailing
+/// `*` matches all names with the given prefix, e.g. `reth_network_*`.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    /// Only metrics that match any of these names are recorded. All metrics are recorded if empty.
+    pub allow: Vec<String>,
+    /// Metrics that match any of these names are not recorded, even if they're allowed.
+    pub deny: Vec<String>,
+    /// Maximum number of distinct values of a label per metric, e.g. `peer_id = 20`.
+    ///
+    /// The first values that are recorded keep their own series, all further values are aggregated
+    /// into a single series with the value `other`.
+    pub label_limits: BTreeMap<String, usize>,
+    /// Bucket boundaries of histograms by metric name, in ascending order.
+    ///
+    /// Histograms without buckets are exported as summaries.
+    #[serde(deserialize_with = "deserialize_buckets")]
+    pub buckets: BTreeMap<String, Vec<f64>>,
+}
+
+// Bucket boundaries are never NaN, see `deserialize_buckets`.
+impl Eq for MetricsConfig {}
+
+/// Deserializes histogram buckets and rejects boundaries that aren't finite.
+fn deserialize_buckets<'de, D>(deserializer: D) -> Result<BTreeMap<String, Vec<f64>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let buckets = BTreeMap::<String, Vec<f64>>::deserialize(deserializer)?;
+    for (name, boundaries) in &buckets {
+        if boundaries.iter().any(|boundary| !boundary.is_finite()) {
+            return Err(serde::de::Error::custom(format!(
+                "invalid buckets of `{name}`, boundaries must be finite"
+            )))
+        }
+    }
+    Ok(buckets)
+}
+
+/// Append-only audit log of chain events in JSON lines, for postmortems of e.g. missed proposals.
+///
+/// The log is written if the section is present.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct AuditLogConfig {
+    /// The directory of the log files, `<DATADIR>/<CHAIN_ID>/audit` if not set.
+    pub dir: Option<PathBuf>,
+    /// The maximum size of a log file in bytes before it's rotated.
+    pub max_file_size: u64,
+    /// The maximum number of rotated log files that are kept.
+    pub max_files: usize,
+}
+
+impl Default for AuditLogConfig {
+    fn default() -> Self {
+        Self { dir: None, max_file_size: 100 * 1024 * 1024, max_files: 10 }
+    }
+}
+
+/// Background verification of the stored chain, that re-executes randomly sampled historical
+/// blocks and compares the computed receipts and state root with the stored ones, to detect
+/// corrupted data, e.g. bit rot of the disks of archive nodes.
+///
+/// The verifier runs if the section is present.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct BlockVerifierConfig {
+    /// How often a block is sampled and re-executed.
+    #[serde(with = "humantime_serde")]
+    pub interval: Duration,
+    /// Only blocks at most this many blocks below the tip are sampled, all blocks if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<u64>,
+    /// The state root is only recomputed for blocks at most this many blocks below the tip,
+    /// because the state of a block is reverted from the tip to compute it.
+    pub state_root_depth: u64,
+}
+
+impl Default for BlockVerifierConfig {
+    fn default() -> Self {
+        Self { interval: Duration::from_secs(60), max_depth: None, state_root_depth: 64 }
+    }
+}
+
+/// Background consistency check of the stored chain, that cross-checks the headers, bodies,
+/// transactions, receipts, transaction lookup and history indices of all blocks while the node is
+/// running.
+///
+/// The blocks are scanned in batches with a pause in between to limit the I/O, and the progress is
+/// kept across restarts. The check runs if the section is present.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct IntegrityCheckConfig {
+    /// The number of blocks that are checked in one read transaction.
+    pub batch_size: u64,
+    /// The pause between two batches.
+    #[serde(with = "humantime_serde")]
+    pub pause: Duration,
+    /// The time between the end of a scan of all blocks and the start of the next one.
+    #[serde(with = "humantime_serde")]
+    pub interval: Duration,
+    /// The maximum number of findings that are kept, further findings are only counted.
+    pub max_findings: usize,
+}
+
+impl Default for IntegrityCheckConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            pause: Duration::from_millis(500),
+            interval: Duration::from_secs(24 * 60 * 60),
+            max_findings: 1000,
+        }
+    }
+}
+
+/// Forecasting of the disk usage of the data dir.
+///
+/// The growth of each data class, e.g. the database and the static files, and the free space of
+/// the volumes they are stored on are measured over a window. When a volume is projected to run
+/// out of free space sooner than `min_time_to_full`, a warning is logged and the
+/// `volumes.disk_alarm` metric is set.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct DiskAlarmConfig {
+    /// The window over which the growth is measured.
+    #[serde(with = "humantime_serde")]
+    pub window: Duration,
+    /// The alarm is raised when a volume is projected to be full within this time.
+    #[serde(with = "humantime_serde")]
+    pub min_time_to_full: Duration,
+    /// While the alarm is raised, the configured prune segments are pruned to at most this
+    /// distance from the tip, if that prunes more than their configured mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_prune_distance: Option<u64>,
+}
+
+impl Default for DiskAlarmConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(6 * 60 * 60),
+            min_time_to_full: Duration::from_secs(2 * 24 * 60 * 60),
+            auto_prune_distance: None,
+        }
+    }
+}
+
+/// Latency budgets of the engine API messages, the limits of parallel payload validation and when
+/// canonical blocks kept in memory are persisted.
+///
+/// When processing a `newPayload` or `forkchoiceUpdated` message takes longer than its budget, a
+/// warning with the time spent in each stage, e.g. execution and state root computation, is
+/// logged.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct EngineConfig {
+    /// The latency budget of a `newPayload` message.
+    #[serde(with = "humantime_serde")]
+    pub new_payload_budget: Duration,
+    /// The latency budget of a `forkchoiceUpdated` message.
+    #[serde(with = "humantime_serde")]
+    pub forkchoice_updated_budget: Duration,
+    /// The maximum number of queued payloads of independent blocks, e.g. competing forks, that are
+    /// executed concurrently.
+    pub max_parallel_validations: usize,
+    /// The estimated memory in bytes that blocks executed concurrently may use.
+    pub parallel_validation_memory_budget: usize,
+    /// The maximum number of canonical blocks kept only in memory before they are persisted.
+    pub persistence_threshold: u64,
+    /// The number of most recent canonical blocks kept in memory when blocks are persisted.
+    pub memory_block_buffer_target: u64,
+    /// When canonical blocks are persisted.
+    pub persistence_policy: PersistencePolicy,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            new_payload_budget: Duration::from_secs(1),
+            forkchoice_updated_budget: Duration::from_millis(500),
+            max_parallel_validations: 4,
+            parallel_validation_memory_budget: 1024 * 1024 * 1024, // 1GB
+            persistence_threshold: 3,
+            memory_block_buffer_target: 2,
+            persistence_policy: PersistencePolicy::Depth,
+        }
+    }
+}
+
+/// When canonical blocks kept in memory are persisted to the database.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PersistencePolicy {
+    /// Persist blocks once more than `persistence_threshold` blocks are kept in memory.
+    #[default]
+    Depth,
+    /// Persist blocks once they are finalized, falling back to [`PersistencePolicy::Depth`] if
+    /// finalization stalls.
+    Finalized,
+}
+
+/// Dedicated runtimes for the priority classes of the node's tasks, so that e.g. heavy RPC tracing
+/// can't delay the processing of `newPayload`.
+///
+/// Tasks of a class without a runtime of its own run on the default runtime.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct TasksConfig {
+    /// The runtime of the consensus critical tasks, e.g. the engine.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consensus: Option<TaskRuntimeConfig>,
+    /// The runtime of the RPC servers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpc: Option<TaskRuntimeConfig>,
+    /// The runtime of background maintenance tasks, e.g. pruning and monitoring.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<TaskRuntimeConfig>,
+}
+
+/// The runtime of a task priority class.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct TaskRuntimeConfig {
+    /// The number of worker threads of the runtime.
+    pub worker_threads: usize,
+    /// The CPU cores the threads of the runtime are pinned to, all cores if empty.
+    ///
+    /// This is only supported on Linux.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub cpus: Vec<usize>,
+    /// The nice value of the threads of the runtime, from -20 (highest priority) to 19 (lowest).
+    ///
+    /// This is only supported on Linux.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nice: Option<i32>,
+}
+
+impl Default for TaskRuntimeConfig {
+    fn default() -> Self {
+        Self { worker_threads: 2, cpus: Vec::new(), nice: None }
     }
 }
 
@@ -414,8 +1114,14 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{Config, EXTENSION};
+    use super::{
+        AuditLogConfig, BlockVerifierConfig, Config, DiskAlarmConfig, EngineConfig,
+        IntegrityCheckConfig, PersistencePolicy, RemoteStaticFilesConfig, TaskRuntimeConfig,
+        EXTENSION,
+    };
+    use alloy_primitives::Address;
     use reth_network_peers::TrustedPeer;
+    use reth_static_file_types::CompressionCodec;
     use std::{path::Path, str::FromStr, time::Duration};
 
     fn with_tempdir(filename: &str, proc: fn(&std::path::Path)) {
@@ -564,6 +1270,405 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_extra_stage_config() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct IndexConfig {
+            commit_threshold: u64,
+            enabled: bool,
+        }
+
+        let s = r#"
+[stages.extra.my_index]
+commit_threshold = 1000
+enabled = true
+"#;
+        let config: Config = toml::from_str(s).unwrap();
+        assert_eq!(
+            config.stages.extra::<IndexConfig>("my_index").unwrap(),
+            Some(IndexConfig { commit_threshold: 1000, enabled: true })
+        );
+        assert_eq!(config.stages.extra::<IndexConfig>("other").unwrap(), None);
+        assert!(config.stages.extra::<Duration>("my_index").is_err());
+
+        let roundtrip: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, roundtrip);
+    }
+
+    #[test]
+    fn test_stage_progress_config() {
+        let s = r#"
+[stages.progress]
+window = "5m"
+
+[stages.progress.min_throughput]
+execution = 100
+sender_recovery = 10000
+"#;
+        let config: Config = toml::from_str(s).unwrap();
+        let thresholds =
+            reth_stages_types::StageProgressThresholds::from(config.stages.progress.clone());
+        assert_eq!(thresholds.window, Duration::from_secs(5 * 60));
+        assert_eq!(thresholds.min_throughput(reth_stages_types::StageId::Execution), Some(100));
+        assert_eq!(
+            thresholds.min_throughput(reth_stages_types::StageId::SenderRecovery),
+            Some(10_000)
+        );
+
+        let roundtrip: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, roundtrip);
+    }
+
+    #[test]
+    fn test_prune_schedule_config() {
+        let s = r#"
+[prune.schedule]
+max_run_time = "500ms"
+max_entries_per_second = 10000
+windows = ["02:00-06:00", "22:30-23:30"]
+"#;
+        let config: Config = toml::from_str(s).unwrap();
+        let schedule = &config.prune.as_ref().unwrap().schedule;
+        assert_eq!(schedule.max_run_time, Some(Duration::from_millis(500)));
+        assert_eq!(schedule.max_entries_per_second, Some(10_000));
+        assert_eq!(schedule.windows.len(), 2);
+
+        let roundtrip: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, roundtrip);
+
+        let s = r#"
+[prune.schedule]
+windows = ["06:00-06:00"]
+"#;
+        let err = toml::from_str::<Config>(s).unwrap_err().to_string();
+        assert!(err.contains("invalid prune window `06:00-06:00`"), "{err}");
+    }
+
+    #[test]
+    fn test_config_profiles() {
+        let s = r#"
+[stages.headers]
+downloader_max_concurrent_requests = 100
+commit_threshold = 10000
+
+[sessions]
+session_command_buffer = 32
+
+[profiles.op-sepolia.stages.headers]
+downloader_max_concurrent_requests = 10
+
+[profiles.op-sepolia.peers]
+max_backoff_count = 3
+
+[profiles.op-sepolia.prune]
+block_interval = 10
+
+[profiles.broken.rpc]
+http = true
+"#;
+        let config: Config = toml::from_str(s).unwrap();
+        assert_eq!(config.profiles.len(), 2);
+
+        let profile = config.with_profile("op-sepolia").unwrap();
+        // overridden
+        assert_eq!(profile.stages.headers.downloader_max_concurrent_requests, 10);
+        assert_eq!(profile.peers.max_backoff_count, 3);
+        assert_eq!(profile.prune.as_ref().unwrap().block_interval, 10);
+        // falls back to the top-level values
+        assert_eq!(profile.stages.headers.commit_threshold, 10000);
+        assert_eq!(profile.sessions.session_command_buffer, 32);
+        assert_eq!(profile.stages.bodies, config.stages.bodies);
+        assert_eq!(profile.profiles, config.profiles);
+
+        assert!(config.with_profile("mainnet").is_err());
+        assert!(config.with_profile("broken").is_err());
+
+        let roundtrip: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, roundtrip);
+    }
+
+    #[test]
+    fn test_discovery_config() {
+        let s = r"
+[discovery.v4]
+max_peers_per_second = 20
+
+[discovery.v5]
+enabled = true
+require_fork_id = true
+
+[profiles.devnet.discovery.v4]
+enabled = false
+";
+        let config: Config = toml::from_str(s).unwrap();
+        let discovery = config.discovery;
+        assert!(discovery.v4.enabled);
+        assert_eq!(discovery.v4.max_peers_per_second, Some(20));
+        assert!(discovery.v5.enabled);
+        assert!(discovery.v5.require_fork_id);
+        assert_eq!(discovery.v5.max_peers_per_second, None);
+
+        let profile = config.with_profile("devnet").unwrap();
+        assert!(!profile.discovery.v4.enabled);
+        assert_eq!(profile.discovery.v4.max_peers_per_second, Some(20));
+        assert_eq!(profile.discovery.v5, discovery.v5);
+
+        let roundtrip: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, roundtrip);
+
+        let default = Config::default().discovery;
+        assert!(default.v4.enabled);
+        assert!(!default.v5.enabled);
+    }
+
+    #[test]
+    fn test_rpc_config() {
+        let s = r#"
+[rpc]
+gas_cap = 100000000
+max_logs_per_response = 5000
+
+[rpc.gas_price_oracle]
+percentile = 50
+max_price = 100000000000
+"#;
+        let config: Config = toml::from_str(s).unwrap();
+        let rpc = config.rpc.unwrap();
+        assert_eq!(rpc.gas_cap, Some(100_000_000));
+        assert_eq!(rpc.max_logs_per_response, Some(5000));
+        assert_eq!(rpc.max_tracing_requests, None);
+        assert_eq!(rpc.gas_price_oracle.percentile, Some(50));
+        assert_eq!(rpc.gas_price_oracle.max_price, Some(100_000_000_000));
+        assert_eq!(rpc.gas_price_oracle.blocks, None);
+
+        let roundtrip: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, roundtrip);
+        assert!(Config::default().rpc.is_none());
+    }
+
+    #[test]
+    fn test_tracing_config() {
+        let s = r#"
+[tracing]
+otlp_endpoint = "http://localhost:4317"
+sample_ratio = 0.25
+"#;
+        let config: Config = toml::from_str(s).unwrap();
+        let tracing = config.tracing.clone().unwrap();
+        assert_eq!(tracing.otlp_endpoint.as_deref(), Some("http://localhost:4317"));
+        assert_eq!(tracing.sample_ratio, Some(0.25));
+        assert_eq!(tracing.service_name, None);
+        assert_eq!(tracing.filter, None);
+
+        let roundtrip: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, roundtrip);
+        assert!(Config::default().tracing.is_none());
+
+        let err = toml::from_str::<Config>("[tracing]\nsample_ratio = 1.5").unwrap_err();
+        assert!(err.to_string().contains("invalid sample ratio"));
+    }
+
+    #[test]
+    fn test_metrics_config() {
+        let s = r#"
+[metrics]
+deny = ["reth_jemalloc_*"]
+
+[metrics.label_limits]
+peer_id = 20
+
+[metrics.buckets]
+reth_engine_rpc_new_payload_v3 = [0.01, 0.1, 1.0]
+"#;
+        let config: Config = toml::from_str(s).unwrap();
+        let metrics = config.metrics.clone().unwrap();
+        assert!(metrics.allow.is_empty());
+        assert_eq!(metrics.deny, vec!["reth_jemalloc_*".to_string()]);
+        assert_eq!(metrics.label_limits.get("peer_id"), Some(&20));
+        assert_eq!(
+            metrics.buckets.get("reth_engine_rpc_new_payload_v3"),
+            Some(&vec![0.01, 0.1, 1.0])
+        );
+
+        let roundtrip: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, roundtrip);
+        assert!(Config::default().metrics.is_none());
+
+        let err = toml::from_str::<Config>("[metrics.buckets]\nfoo = [1.0, nan]").unwrap_err();
+        assert!(err.to_string().contains("boundaries must be finite"));
+    }
+
+    #[test]
+    fn test_audit_log_config() {
+        let config: Config = toml::from_str("[audit_log]\nmax_files = 3").unwrap();
+        let audit_log = config.audit_log.clone().unwrap();
+        assert_eq!(audit_log.dir, None);
+        assert_eq!(audit_log.max_files, 3);
+        assert_eq!(audit_log.max_file_size, AuditLogConfig::default().max_file_size);
+
+        let roundtrip: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, roundtrip);
+        assert!(Config::default().audit_log.is_none());
+    }
+
+    #[test]
+    fn test_verifier_config() {
+        let config: Config = toml::from_str("[verifier]\ninterval = \"10s\"").unwrap();
+        let verifier = config.verifier.clone().unwrap();
+        assert_eq!(verifier.interval, Duration::from_secs(10));
+        assert_eq!(verifier.max_depth, None);
+        assert_eq!(verifier.state_root_depth, BlockVerifierConfig::default().state_root_depth);
+
+        let roundtrip: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, roundtrip);
+        assert!(Config::default().verifier.is_none());
+    }
+
+    #[test]
+    fn test_integrity_check_config() {
+        let config: Config =
+            toml::from_str("[integrity_check]\nbatch_size = 10\npause = \"100ms\"").unwrap();
+        let integrity_check = config.integrity_check.clone().unwrap();
+        assert_eq!(integrity_check.batch_size, 10);
+        assert_eq!(integrity_check.pause, Duration::from_millis(100));
+        assert_eq!(integrity_check.interval, IntegrityCheckConfig::default().interval);
+
+        let roundtrip: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, roundtrip);
+        assert!(Config::default().integrity_check.is_none());
+    }
+
+    #[test]
+    fn test_engine_config() {
+        let config: Config = toml::from_str("[engine]\nnew_payload_budget = \"750ms\"").unwrap();
+        assert_eq!(config.engine.new_payload_budget, Duration::from_millis(750));
+        assert_eq!(
+            config.engine.forkchoice_updated_budget,
+            EngineConfig::default().forkchoice_updated_budget
+        );
+        assert_eq!(config.engine.max_parallel_validations, 4);
+
+        let config: Config = toml::from_str(
+            "[engine]\nmax_parallel_validations = 8\nparallel_validation_memory_budget = 1048576",
+        )
+        .unwrap();
+        assert_eq!(config.engine.max_parallel_validations, 8);
+        assert_eq!(config.engine.parallel_validation_memory_budget, 1024 * 1024);
+
+        let config: Config = toml::from_str(
+            "[engine]\npersistence_threshold = 256\npersistence_policy = \"finalized\"",
+        )
+        .unwrap();
+        assert_eq!(config.engine.persistence_threshold, 256);
+        assert_eq!(config.engine.memory_block_buffer_target, 2);
+        assert_eq!(config.engine.persistence_policy, PersistencePolicy::Finalized);
+    }
+
+    #[test]
+    fn test_tasks_config() {
+        let config: Config = toml::from_str(
+            "[tasks.consensus]\ncpus = [0, 1]\nnice = -5\n\n[tasks.rpc]\nworker_threads = 8",
+        )
+        .unwrap();
+        let tasks = config.tasks.unwrap();
+        assert_eq!(
+            tasks.consensus,
+            Some(TaskRuntimeConfig { worker_threads: 2, cpus: vec![0, 1], nice: Some(-5) })
+        );
+        assert_eq!(tasks.rpc.unwrap().worker_threads, 8);
+        assert_eq!(tasks.background, None);
+    }
+
+    #[test]
+    fn test_disk_alarm_config() {
+        let config: Config =
+            toml::from_str("[disk_alarm]\nmin_time_to_full = \"1d\"\nauto_prune_distance = 100000")
+                .unwrap();
+        assert_eq!(config.disk_alarm.window, DiskAlarmConfig::default().window);
+        assert_eq!(config.disk_alarm.min_time_to_full, Duration::from_secs(24 * 60 * 60));
+        assert_eq!(config.disk_alarm.auto_prune_distance, Some(100_000));
+    }
+
+    #[test]
+    fn test_evm_config() {
+        let s = r#"
+[evm]
+chain_id = 1337
+max_code_size = 49152
+
+[[evm.spec_activations]]
+block = 0
+spec = "Shanghai"
+
+[[evm.spec_activations]]
+block = 100
+spec = "Cancun"
+
+[[evm.precompiles]]
+address = "0x0000000000000000000000000000000000000100"
+precompile = "0x0000000000000000000000000000000000000001"
+"#;
+        let config: Config = toml::from_str(s).unwrap();
+        let evm = config.evm.as_ref().unwrap();
+        assert_eq!(evm.chain_id, Some(1337));
+        assert_eq!(evm.max_code_size, Some(49152));
+        assert_eq!(evm.spec_activations.len(), 2);
+        assert_eq!(evm.spec_activations[1].block, 100);
+        assert_eq!(evm.spec_activations[1].spec, "Cancun");
+        assert_eq!(evm.precompiles[0].precompile, Address::with_last_byte(1));
+
+        let roundtrip: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, roundtrip);
+        assert!(Config::default().evm.is_none());
+    }
+
+    #[test]
+    fn test_static_files_config() {
+        let s = r#"
+[static_files]
+recompress = false
+
+[static_files.compression]
+transactions = "lz4"
+receipts = "zstd:19"
+"#;
+        let config: Config = toml::from_str(s).unwrap();
+        let compression = config.static_files.compression;
+        assert_eq!(compression.headers, CompressionCodec::Lz4);
+        assert_eq!(compression.transactions, CompressionCodec::Lz4);
+        assert_eq!(compression.receipts, CompressionCodec::Zstd(19));
+        assert!(!config.static_files.recompress);
+
+        let roundtrip: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, roundtrip);
+        assert!(Config::default().static_files.recompress);
+        assert!(config.static_files.remote.is_none());
+
+        let s = r#"
+[static_files.remote]
+location = "s3://bucket/static_files"
+cutoff = 15000000
+cache_size = 1073741824
+"#;
+        let config: Config = toml::from_str(s).unwrap();
+        let remote = config.static_files.remote.clone().unwrap();
+        assert_eq!(remote.location, "s3://bucket/static_files");
+        assert_eq!(remote.endpoint, RemoteStaticFilesConfig::default_endpoint());
+        assert_eq!(remote.region, RemoteStaticFilesConfig::default_region());
+        assert_eq!(remote.cutoff, 15_000_000);
+        assert_eq!(remote.cache_size, 1024 * 1024 * 1024);
+
+        let roundtrip: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, roundtrip);
+
+        let s = r#"
+[static_files.compression]
+receipts = "zstd:30"
+"#;
+        let err = toml::from_str::<Config>(s).unwrap_err().to_string();
+        assert!(err.contains("invalid compression codec `zstd:30`"), "{err}");
+    }
+
     // ensures config deserialization is backwards compatible
     #[test]
     fn test_backwards_compatibility() {
@@ -0,0 +1,418 @@
+//! Layered overrides of the [`Config`] from environment variables and the command line.
+//!
+//! Settings are resolved in the following order, later layers override earlier ones:
+//!
+//! 1. the configuration file, or the default value if the file doesn't set it
+//! 2. environment variables, e.g. `RETH_STAGES_EXECUTION_MAX_BLOCKS=1000`
+//! 3. command line overrides, e.g. `--config.set stages.execution.max_blocks=1000`
+//!
+//! The environment variable of a setting is its path prefixed with [`ENV_PREFIX`], in uppercase
+//! and with `.` and `-` replaced by `_`. Values are parsed as TOML values, e.g. `10`, `true` or
+//! `[1, 2]`, and otherwise used as strings.
+
+use crate::{
+    config::GasPriceOracleOverrides, AuditLogConfig, BlockVerifierConfig, Config,
+    IntegrityCheckConfig, MetricsConfig, PruneConfig, PruneScheduleConfig, RpcConfig,
+    TaskRuntimeConfig, TasksConfig,
+};
+use eyre::{bail, eyre};
+use reth_prune_types::{PruneMode, PruneModes, PruneWindow, ReceiptsRetentionConfig};
+use std::{collections::BTreeMap, fmt, path::PathBuf, time::Duration};
+
+/// Prefix of the environment variables that override settings of the [`Config`].
+pub const ENV_PREFIX: &str = "RETH_";
+
+/// Where the value of a setting comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// The default value.
+    Default,
+    /// The configuration file.
+    File,
+    /// The environment variable with the given name.
+    Env(String),
+    /// A command line override.
+    Cli,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => f.write_str("default"),
+            Self::File => f.write_str("file"),
+            Self::Env(name) => write!(f, "env {name}"),
+            Self::Cli => f.write_str("cli"),
+        }
+    }
+}
+
+/// Overrides of the settings of a [`Config`] from environment variables and the command line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigOverrides {
+    /// `RETH_` prefixed environment variables.
+    env: Vec<(String, String)>,
+    /// Command line overrides, keyed by the dotted path of the setting.
+    cli: Vec<(String, String)>,
+}
+
+impl ConfigOverrides {
+    /// Creates overrides from the environment variables of the process.
+    pub fn from_env() -> Self {
+        Self::default().with_env_vars(std::env::vars())
+    }
+
+    /// Adds the given environment variables, variables without the [`ENV_PREFIX`] are ignored.
+    pub fn with_env_vars(mut self, vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.env.extend(vars.into_iter().filter(|(name, _)| name.starts_with(ENV_PREFIX)));
+        self
+    }
+
+    /// Adds a command line override for the setting at the given path, e.g.
+    /// `stages.execution.max_blocks`.
+    pub fn with_cli_override(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.cli.push((key.into(), value.into()));
+        self
+    }
+
+    /// Adds command line overrides, see [`ConfigOverrides::with_cli_override`].
+    pub fn with_cli_overrides(
+        mut self,
+        overrides: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        self.cli.extend(overrides);
+        self
+    }
+
+    /// Returns `true` if there are no overrides.
+    pub fn is_empty(&self) -> bool {
+        self.env.is_empty() && self.cli.is_empty()
+    }
+
+    /// Applies the overrides to the given configuration.
+    ///
+    /// Environment variables that don't name a setting are returned in
+    /// [`LayeredConfig::ignored_env`], unknown command line overrides are an error.
+    pub fn apply(&self, config: &Config) -> eyre::Result<LayeredConfig> {
+        let template = template();
+        let defaults = toml::Table::try_from(Config::default())?;
+        let mut table =
+            toml::Table::try_from(Config { profiles: Default::default(), ..config.clone() })?;
+
+        let mut overridden = BTreeMap::new();
+        let mut ignored_env = Vec::new();
+
+        for (name, value) in &self.env {
+            let tokens = name[ENV_PREFIX.len()..].split('_').collect::<Vec<_>>();
+            let Some(path) = resolve_env(&template, &tokens).filter(|path| path.len() > 1) else {
+                ignored_env.push(name.clone());
+                continue
+            };
+            set(&mut table, &template, &path, value);
+            overridden.insert(path.join("."), ConfigSource::Env(name.clone()));
+        }
+
+        for (key, value) in &self.cli {
+            let path = key.split('.').map(str::to_string).collect::<Vec<_>>();
+            if path.len() < 2 || !is_setting(&template, &path) {
+                bail!("Unknown config setting `{key}`")
+            }
+            set(&mut table, &template, &path, value);
+            overridden.insert(key.clone(), ConfigSource::Cli);
+        }
+
+        let mut sources = BTreeMap::new();
+        collect_sources(&table, &defaults, &mut Vec::new(), &overridden, &mut sources);
+
+        let mut layered: Config = toml::Value::Table(table)
+            .try_into()
+            .map_err(|e| eyre!("Invalid config override: {e}"))?;
+        layered.profiles.clone_from(&config.profiles);
+
+        Ok(LayeredConfig { config: layered, sources, ignored_env })
+    }
+}
+
+/// A [`Config`] with the overrides applied, see [`ConfigOverrides::apply`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayeredConfig {
+    /// The merged configuration.
+    pub config: Config,
+    /// Source of every setting, keyed by the dotted path of the setting.
+    pub sources: BTreeMap<String, ConfigSource>,
+    /// `RETH_` prefixed environment variables that don't name a setting.
+    pub ignored_env: Vec<String>,
+}
+
+impl LayeredConfig {
+    /// Returns where the setting at the given path comes from.
+    pub fn source(&self, key: &str) -> Option<&ConfigSource> {
+        self.sources.get(key)
+    }
+
+    /// Returns the settings that are overridden by an environment variable or on the command
+    /// line.
+    pub fn overrides(&self) -> impl Iterator<Item = (&String, &ConfigSource)> {
+        self.sources
+            .iter()
+            .filter(|(_, source)| matches!(source, ConfigSource::Env(_) | ConfigSource::Cli))
+    }
+}
+
+/// Returns a table that contains all settings that can be overridden.
+///
+/// Optional sections are included with their default values, so their settings can be resolved
+/// even if they aren't set in the configuration file.
+fn template() -> toml::Table {
+    let gas_price_oracle = GasPriceOracleOverrides {
+        blocks: Some(0),
+        percentile: Some(0),
+        max_price: Some(0),
+        ignore_price: Some(0),
+    };
+    let rpc = RpcConfig {
+        gas_cap: Some(0),
+        max_logs_per_response: Some(0),
+        max_tracing_requests: Some(0),
+        gas_price_oracle,
+    };
+    let distance = Some(PruneMode::Distance(0));
+    let segments = PruneModes {
+        sender_recovery: distance,
+        transaction_lookup: distance,
+        receipts: distance,
+        account_history: distance,
+        storage_history: distance,
+        receipts_log_filter: Default::default(),
+        receipts_retention: ReceiptsRetentionConfig { mode: distance, ..Default::default() },
+        state_expiry: distance,
+    };
+    let schedule = PruneScheduleConfig {
+        max_run_time: Some(Duration::ZERO),
+        max_entries_per_second: Some(0),
+        windows: PruneWindow::from_minutes(0, 60).into_iter().collect(),
+    };
+
+    let runtime = TaskRuntimeConfig { nice: Some(0), ..Default::default() };
+
+    let mut config = Config {
+        prune: Some(PruneConfig { segments, schedule, ..Default::default() }),
+        rpc: Some(rpc),
+        metrics: Some(MetricsConfig::default()),
+        audit_log: Some(AuditLogConfig { dir: Some(PathBuf::new()), ..Default::default() }),
+        verifier: Some(BlockVerifierConfig { max_depth: Some(0), ..Default::default() }),
+        integrity_check: Some(IntegrityCheckConfig::default()),
+        tasks: Some(TasksConfig {
+            consensus: Some(runtime.clone()),
+            rpc: Some(runtime.clone()),
+            background: Some(runtime),
+        }),
+        ..Default::default()
+    };
+    config.stages.etl.dir = Some(PathBuf::new());
+    config.disk_alarm.auto_prune_distance = Some(0);
+    toml::Table::try_from(config).expect("default config is serializable")
+}
+
+/// Resolves the tokens of an environment variable name, e.g. `STAGES_EXECUTION_MAX_BLOCKS`, to
+/// the path of a setting in the template.
+fn resolve_env(table: &toml::Table, tokens: &[&str]) -> Option<Vec<String>> {
+    for (key, value) in table {
+        let normalized = key.to_uppercase().replace('-', "_");
+        let key_tokens = normalized.split('_').collect::<Vec<_>>();
+        if tokens.len() < key_tokens.len() || tokens[..key_tokens.len()] != key_tokens[..] {
+            continue
+        }
+
+        let rest = &tokens[key_tokens.len()..];
+        // tables can be set as a whole, e.g. `RETH_PRUNE_SEGMENTS_RECEIPTS=full`
+        let path = match value {
+            _ if rest.is_empty() => Some(Vec::new()),
+            toml::Value::Table(table) => resolve_env(table, rest),
+            _ => None,
+        };
+        if let Some(mut path) = path {
+            path.insert(0, key.clone());
+            return Some(path)
+        }
+    }
+    None
+}
+
+/// Returns `true` if the path names a setting of the template.
+fn is_setting(template: &toml::Table, path: &[String]) -> bool {
+    let Some((key, parents)) = path.split_last() else { return false };
+    let mut table = template;
+    for parent in parents {
+        match table.get(parent) {
+            Some(toml::Value::Table(inner)) => table = inner,
+            _ => return false,
+        }
+    }
+    table.contains_key(key)
+}
+
+/// Sets the setting at the given path to the parsed value.
+fn set(table: &mut toml::Table, template: &toml::Table, path: &[String], value: &str) {
+    let (key, parents) = path.split_last().expect("path is not empty");
+
+    let mut table = table;
+    let mut template = template;
+    for parent in parents {
+        template = template.get(parent).and_then(toml::Value::as_table).expect("path is resolved");
+        let entry = table.entry(parent.clone()).or_insert_with(|| toml::Table::new().into());
+        if !entry.is_table() {
+            // e.g. `receipts = "full"` that is overridden with `receipts.distance`
+            *entry = toml::Table::new().into();
+        }
+        table = entry.as_table_mut().expect("entry is a table");
+    }
+
+    let value = match template.get(key) {
+        Some(toml::Value::String(_)) => toml::Value::String(value.to_string()),
+        _ => parse_value(value),
+    };
+    table.insert(key.clone(), value);
+}
+
+/// Parses the value as a TOML value, or returns it as a string.
+fn parse_value(value: &str) -> toml::Value {
+    format!("value = {value}")
+        .parse::<toml::Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()))
+}
+
+/// Records the source of every setting of the table.
+fn collect_sources(
+    table: &toml::Table,
+    defaults: &toml::Table,
+    path: &mut Vec<String>,
+    overridden: &BTreeMap<String, ConfigSource>,
+    sources: &mut BTreeMap<String, ConfigSource>,
+) {
+    for (key, value) in table {
+        path.push(key.clone());
+        let default = defaults.get(key);
+        match value {
+            toml::Value::Table(table) => {
+                let empty = toml::Table::new();
+                let defaults = default.and_then(toml::Value::as_table).unwrap_or(&empty);
+                collect_sources(table, defaults, path, overridden, sources);
+            }
+            value => {
+                let key = path.join(".");
+                let source = overridden.get(&key).cloned().unwrap_or_else(|| {
+                    if default == Some(value) {
+                        ConfigSource::Default
+                    } else {
+                        ConfigSource::File
+                    }
+                });
+                sources.insert(key, source);
+            }
+        }
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> ConfigOverrides {
+        ConfigOverrides::default()
+            .with_env_vars(vars.iter().map(|(name, value)| (name.to_string(), value.to_string())))
+    }
+
+    #[test]
+    fn env_overrides() {
+        let overrides = env(&[
+            ("RETH_STAGES_EXECUTION_MAX_BLOCKS", "1000"),
+            ("RETH_STAGES_ETL_DIR", "/tmp/etl"),
+            ("RETH_PEERS_CONNECTION_INFO_MAX_OUTBOUND", "50"),
+            ("RETH_PRUNE_BLOCK_INTERVAL", "10"),
+            ("RETH_RPC_GAS_PRICE_ORACLE_PERCENTILE", "40"),
+            ("RETH_STAGES_EXECUTION_MAX_DURATION", "1h"),
+            ("RETH_RPC_TEST_NODE_URL", "http://localhost:8545"),
+            ("PATH", "/usr/bin"),
+        ]);
+
+        let mut file = Config::default();
+        file.stages.headers.commit_threshold = 1;
+        let layered = overrides.apply(&file).unwrap();
+        let config = &layered.config;
+
+        assert_eq!(config.stages.execution.max_blocks, Some(1000));
+        assert_eq!(config.stages.etl.dir, Some("/tmp/etl".into()));
+        assert_eq!(config.peers.connection_info.max_outbound, 50);
+        assert_eq!(config.prune.as_ref().unwrap().block_interval, 10);
+        assert_eq!(config.rpc.unwrap().gas_price_oracle.percentile, Some(40));
+        assert_eq!(
+            config.stages.execution.max_duration,
+            Some(std::time::Duration::from_secs(60 * 60))
+        );
+        assert_eq!(layered.ignored_env, ["RETH_RPC_TEST_NODE_URL"]);
+
+        assert_eq!(
+            layered.source("stages.execution.max_blocks"),
+            Some(&ConfigSource::Env("RETH_STAGES_EXECUTION_MAX_BLOCKS".to_string()))
+        );
+        assert_eq!(layered.source("stages.headers.commit_threshold"), Some(&ConfigSource::File));
+        assert_eq!(
+            layered.source("stages.bodies.downloader_request_limit"),
+            Some(&ConfigSource::Default)
+        );
+        assert_eq!(layered.overrides().count(), 6);
+    }
+
+    #[test]
+    fn prune_segment_overrides() {
+        let overrides = env(&[
+            ("RETH_PRUNE_SEGMENTS_RECEIPTS_DISTANCE", "100000"),
+            ("RETH_PRUNE_SCHEDULE_WINDOWS", r#"["02:00-06:00"]"#),
+        ])
+        .with_cli_override("prune.segments.sender_recovery", "\"full\"");
+
+        let file = Config {
+            prune: Some(PruneConfig {
+                segments: PruneModes { receipts: Some(PruneMode::Full), ..Default::default() },
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let config = overrides.apply(&file).unwrap().config;
+        let prune = config.prune.unwrap();
+        assert_eq!(prune.segments.receipts, Some(PruneMode::Distance(100_000)));
+        assert_eq!(prune.segments.sender_recovery, Some(PruneMode::Full));
+        assert_eq!(prune.schedule.windows, ["02:00-06:00".parse().unwrap()]);
+    }
+
+    #[test]
+    fn metrics_overrides() {
+        let overrides = env(&[("RETH_METRICS_DENY", r#"["reth_jemalloc_*"]"#)])
+            .with_cli_override("metrics.label_limits", "{ peer_id = 20 }");
+
+        let config = overrides.apply(&Config::default()).unwrap().config;
+        let metrics = config.metrics.unwrap();
+        assert_eq!(metrics.deny, vec!["reth_jemalloc_*".to_string()]);
+        assert_eq!(metrics.label_limits.get("peer_id"), Some(&20));
+    }
+
+    #[test]
+    fn cli_overrides_env() {
+        let overrides = env(&[("RETH_STAGES_EXECUTION_MAX_BLOCKS", "1000")])
+            .with_cli_override("stages.execution.max_blocks", "2000");
+
+        let layered = overrides.apply(&Config::default()).unwrap();
+        assert_eq!(layered.config.stages.execution.max_blocks, Some(2000));
+        assert_eq!(layered.source("stages.execution.max_blocks"), Some(&ConfigSource::Cli));
+
+        let unknown = ConfigOverrides::default().with_cli_override("stages.execution.unknown", "1");
+        assert!(unknown.apply(&Config::default()).is_err());
+        let section = ConfigOverrides::default().with_cli_override("stages.execution", "1");
+        assert!(section.apply(&Config::default()).is_err());
+        let invalid = ConfigOverrides::default().with_cli_override("prune.block_interval", "ten");
+        assert!(invalid.apply(&Config::default()).is_err());
+    }
+}
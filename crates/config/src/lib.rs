@@ -9,4 +9,15 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 pub mod config;
-pub use config::{BodiesConfig, Config, PruneConfig};
+pub mod layered;
+pub mod validation;
+pub use config::{
+    AuditLogConfig, BlockVerifierConfig, BodiesConfig, Config, ConfigProfile, DiscoveryConfig,
+    DiscoveryV4Config, DiscoveryV5Config, DiskAlarmConfig, EngineConfig, EvmConfig,
+    EvmPrecompileAlias, EvmSpecActivation, ExtraStageConfig, GasPriceOracleOverrides,
+    IntegrityCheckConfig, MetricsConfig, PersistencePolicy, PruneConfig, PruneScheduleConfig,
+    RemoteStaticFilesConfig, RpcConfig, StaticFilesConfig, TaskRuntimeConfig, TasksConfig,
+    TracingConfig,
+};
+pub use layered::{ConfigOverrides, ConfigSource, LayeredConfig, ENV_PREFIX};
+pub use validation::{ConfigIssue, ConfigReport, InvalidConfig, IssueSeverity};
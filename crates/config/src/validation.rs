@@ -0,0 +1,655 @@
+//! Validation of configuration values.
+
+use crate::Config;
+use reth_prune_types::{PruneMode, MINIMUM_PRUNING_DISTANCE};
+use std::fmt;
+
+/// ETL file size in bytes below which data is flushed to disk so often that it slows down the
+/// sync.
+pub const MIN_RECOMMENDED_ETL_FILE_SIZE: usize = 10 * 1024 * 1024;
+
+/// Severity of a [`ConfigIssue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueSeverity {
+    /// The value is valid, but likely not intended.
+    Warning,
+    /// The node can not run with the value.
+    Error,
+}
+
+/// A questionable or invalid value found by [`Config::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// Severity of the issue.
+    pub severity: IssueSeverity,
+    /// Path of the setting, e.g. `stages.headers.commit_threshold`.
+    pub key: String,
+    /// Description of the issue.
+    pub message: String,
+}
+
+impl ConfigIssue {
+    /// Returns `true` if the issue is an error.
+    pub fn is_error(&self) -> bool {
+        self.severity == IssueSeverity::Error
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+/// The issues found by [`Config::validate`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigReport {
+    issues: Vec<ConfigIssue>,
+}
+
+impl ConfigReport {
+    /// Returns all issues.
+    pub fn issues(&self) -> &[ConfigIssue] {
+        &self.issues
+    }
+
+    /// Returns the issues the node can not run with.
+    pub fn errors(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues.iter().filter(|issue| issue.is_error())
+    }
+
+    /// Returns the issues with values that are valid, but likely not intended.
+    pub fn warnings(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues.iter().filter(|issue| !issue.is_error())
+    }
+
+    /// Returns `true` if any error was found.
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// Returns the warnings, or an error with all issues if any error was found.
+    pub fn into_result(self) -> Result<Vec<ConfigIssue>, InvalidConfig> {
+        if self.has_errors() {
+            return Err(InvalidConfig(self.issues))
+        }
+        Ok(self.issues)
+    }
+
+    fn push(&mut self, severity: IssueSeverity, key: &str, message: impl Into<String>) {
+        self.issues.push(ConfigIssue { severity, key: key.to_string(), message: message.into() });
+    }
+
+    fn error(&mut self, key: &str, message: impl Into<String>) {
+        self.push(IssueSeverity::Error, key, message)
+    }
+
+    fn warning(&mut self, key: &str, message: impl Into<String>) {
+        self.push(IssueSeverity::Warning, key, message)
+    }
+
+    fn non_zero(&mut self, key: &str, value: u64) {
+        if value == 0 {
+            self.error(key, "must be greater than zero")
+        }
+    }
+
+    fn concurrency(&mut self, key: &str, min: usize, max: usize) {
+        if max == 0 {
+            self.error(
+                &format!("{key}.downloader_max_concurrent_requests"),
+                "must be greater than zero",
+            )
+        } else if min > max {
+            self.error(
+                &format!("{key}.downloader_min_concurrent_requests"),
+                format!("{min} is greater than the maximum of {max} concurrent requests"),
+            )
+        }
+    }
+
+    fn prune_distance(&mut self, key: &str, mode: Option<&PruneMode>) {
+        let min = MINIMUM_PRUNING_DISTANCE;
+        match mode {
+            Some(PruneMode::Full) => self
+                .error(key, format!("full pruning is not supported, keep at least {min} blocks")),
+            Some(PruneMode::Distance(distance)) if *distance < min => {
+                self.error(key, format!("distance {distance} is below the minimum of {min} blocks"))
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Error returned by [`ConfigReport::into_result`] if the configuration contains errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidConfig(pub Vec<ConfigIssue>);
+
+impl fmt::Display for InvalidConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid configuration")?;
+        for issue in self.0.iter().filter(|issue| issue.is_error()) {
+            write!(f, "\n  {issue}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for InvalidConfig {}
+
+impl Config {
+    /// Cross-checks the configured values and returns the issues that were found.
+    ///
+    /// Errors are values the node can not run with, e.g. commit thresholds of zero or prune
+    /// distances below [`MINIMUM_PRUNING_DISTANCE`]. Warnings are values that are valid, but
+    /// likely not intended.
+    pub fn validate(&self) -> ConfigReport {
+        let mut report = ConfigReport::default();
+        let stages = &self.stages;
+
+        let headers = &stages.headers;
+        report.non_zero("stages.headers.commit_threshold", headers.commit_threshold);
+        report
+            .non_zero("stages.headers.downloader_request_limit", headers.downloader_request_limit);
+        report.non_zero(
+            "stages.headers.downloader_max_buffered_responses",
+            headers.downloader_max_buffered_responses as u64,
+        );
+        report.concurrency(
+            "stages.headers",
+            headers.downloader_min_concurrent_requests,
+            headers.downloader_max_concurrent_requests,
+        );
+
+        let bodies = &stages.bodies;
+        report.non_zero("stages.bodies.downloader_request_limit", bodies.downloader_request_limit);
+        report.non_zero(
+            "stages.bodies.downloader_stream_batch_size",
+            bodies.downloader_stream_batch_size as u64,
+        );
+        report.concurrency(
+            "stages.bodies",
+            bodies.downloader_min_concurrent_requests,
+            bodies.downloader_max_concurrent_requests,
+        );
+
+        let snap = &stages.snap;
+        if snap.enabled {
+            report.non_zero("stages.snap.response_bytes", snap.response_bytes);
+            if !self.prune.as_ref().is_some_and(|prune| prune.segments.has_receipts_pruning()) {
+                report.error(
+                    "stages.snap.enabled",
+                    "requires receipts pruning, receipts of snap synced blocks don't exist",
+                )
+            }
+        }
+
+        report.non_zero(
+            "stages.sender_recovery.commit_threshold",
+            stages.sender_recovery.commit_threshold,
+        );
+        report.non_zero(
+            "stages.sender_recovery.batch_size",
+            stages.sender_recovery.batch_size as u64,
+        );
+        report.non_zero("stages.prune.commit_threshold", stages.prune.commit_threshold as u64);
+        report.non_zero(
+            "stages.account_hashing.commit_threshold",
+            stages.account_hashing.commit_threshold,
+        );
+        report.non_zero(
+            "stages.storage_hashing.commit_threshold",
+            stages.storage_hashing.commit_threshold,
+        );
+        report
+            .non_zero("stages.transaction_lookup.chunk_size", stages.transaction_lookup.chunk_size);
+        report.non_zero("stages.merkle.commit_threshold", stages.merkle.commit_threshold);
+        report.non_zero(
+            "stages.index_account_history.commit_threshold",
+            stages.index_account_history.commit_threshold,
+        );
+        report.non_zero(
+            "stages.index_storage_history.commit_threshold",
+            stages.index_storage_history.commit_threshold,
+        );
+
+        let execution = &stages.execution;
+        for (key, value) in [
+            ("stages.execution.max_blocks", execution.max_blocks),
+            ("stages.execution.max_changes", execution.max_changes),
+            ("stages.execution.max_cumulative_gas", execution.max_cumulative_gas),
+        ] {
+            if value == Some(0) {
+                report.warning(key, "the execution stage commits after every block")
+            }
+        }
+
+        if stages.etl.file_size == 0 {
+            report.error("stages.etl.file_size", "must be greater than zero")
+        } else if stages.etl.file_size < MIN_RECOMMENDED_ETL_FILE_SIZE {
+            report.warning(
+                "stages.etl.file_size",
+                format!(
+                    "{} bytes is below the recommended minimum of {} bytes",
+                    stages.etl.file_size, MIN_RECOMMENDED_ETL_FILE_SIZE
+                ),
+            )
+        }
+
+        if stages.progress.window.is_zero() {
+            report.warning(
+                "stages.progress.window",
+                "the throughput of a stage is reported after every commit",
+            )
+        }
+
+        let connections = &self.peers.connection_info;
+        if connections.max_outbound == 0 {
+            report.warning("peers.connection_info.max_outbound", "no outbound peers are dialed")
+        }
+        if connections.max_concurrent_outbound_dials == 0 {
+            report.warning(
+                "peers.connection_info.max_concurrent_outbound_dials",
+                "no outbound peers are dialed",
+            )
+        }
+
+        let bandwidth = &self.sessions.bandwidth;
+        for (key, limit) in [
+            ("sessions.bandwidth.upload", bandwidth.upload),
+            ("sessions.bandwidth.download", bandwidth.download),
+            ("sessions.bandwidth.peer_upload", bandwidth.peer_upload),
+            ("sessions.bandwidth.peer_download", bandwidth.peer_download),
+        ] {
+            if let Some(limit) = limit {
+                report.non_zero(key, limit);
+            }
+        }
+
+        for (key, max_peers_per_second) in [
+            ("discovery.v4.max_peers_per_second", self.discovery.v4.max_peers_per_second),
+            ("discovery.v5.max_peers_per_second", self.discovery.v5.max_peers_per_second),
+        ] {
+            if let Some(max_peers_per_second) = max_peers_per_second {
+                report.non_zero(key, max_peers_per_second as u64);
+            }
+        }
+
+        if let Some(prune) = &self.prune {
+            report.non_zero("prune.block_interval", prune.block_interval as u64);
+
+            let schedule = &prune.schedule;
+            if let Some(max_run_time) = schedule.max_run_time {
+                report.non_zero("prune.schedule.max_run_time", max_run_time.as_millis() as u64);
+            }
+            if let Some(max_entries_per_second) = schedule.max_entries_per_second {
+                report.non_zero("prune.schedule.max_entries_per_second", max_entries_per_second);
+            }
+
+            let segments = &prune.segments;
+            report.prune_distance("prune.segments.receipts", segments.receipts.as_ref());
+            report.prune_distance(
+                "prune.segments.account_history",
+                segments.account_history.as_ref(),
+            );
+            report.prune_distance(
+                "prune.segments.storage_history",
+                segments.storage_history.as_ref(),
+            );
+            for (address, mode) in &segments.receipts_log_filter.0 {
+                let key = format!("prune.segments.receipts_log_filter.{address}");
+                if mode.is_time_based() {
+                    report.error(&key, "time-based retention is not supported for log filters")
+                }
+                report.prune_distance(&key, Some(mode));
+            }
+
+            let retention = &segments.receipts_retention;
+            report
+                .prune_distance("prune.segments.receipts_retention.mode", retention.mode.as_ref());
+            if retention.mode.is_none() {
+                if !retention.contracts.is_empty() || !retention.senders.is_empty() {
+                    report.warning(
+                        "prune.segments.receipts_retention.mode",
+                        "no receipts are pruned without a prune mode",
+                    )
+                }
+            } else if retention.contracts.is_empty() && retention.senders.is_empty() {
+                report.warning(
+                    "prune.segments.receipts_retention",
+                    "no contracts or senders are configured, all receipts are pruned",
+                )
+            }
+
+            report.prune_distance("prune.segments.state_expiry", segments.state_expiry.as_ref());
+            // state expiry finds the last change of the state in the history, which has to be kept
+            // at least as long
+            if let Some(PruneMode::Distance(expiry)) = segments.state_expiry {
+                for (name, history) in [
+                    ("account_history", segments.account_history),
+                    ("storage_history", segments.storage_history),
+                ] {
+                    if let Some(PruneMode::Distance(distance)) = history {
+                        if distance < expiry {
+                            report.warning(
+                                "prune.segments.state_expiry",
+                                format!(
+                                    "{name} keeps {distance} blocks, state changed before is never expired"
+                                ),
+                            )
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(percentile) = self.rpc.and_then(|rpc| rpc.gas_price_oracle.percentile) {
+            if percentile > 100 {
+                report.warning(
+                    "rpc.gas_price_oracle.percentile",
+                    format!("{percentile} is greater than 100, 100 is used instead"),
+                )
+            }
+        }
+
+        if let Some(evm) = &self.evm {
+            if let Some(max_code_size) = evm.max_code_size {
+                report.non_zero("evm.max_code_size", max_code_size as u64);
+            }
+            let mut blocks = evm.spec_activations.iter().map(|a| a.block).collect::<Vec<_>>();
+            blocks.sort_unstable();
+            if let Some(block) = blocks.windows(2).find(|w| w[0] == w[1]).map(|w| w[0]) {
+                report.error(
+                    "evm.spec_activations",
+                    format!("multiple specs are activated at block {block}"),
+                )
+            }
+        }
+
+        if let Some(metrics) = &self.metrics {
+            for (name, buckets) in &metrics.buckets {
+                let key = format!("metrics.buckets.{name}");
+                if buckets.is_empty() {
+                    report.error(&key, "must not be empty")
+                } else if buckets.windows(2).any(|w| w[0] >= w[1]) {
+                    report.error(&key, "boundaries must be in ascending order")
+                }
+            }
+            for (label, limit) in &metrics.label_limits {
+                if *limit == 0 {
+                    report.warning(
+                        &format!("metrics.label_limits.{label}"),
+                        "all values of the label are aggregated",
+                    )
+                }
+            }
+        }
+
+        if let Some(audit_log) = &self.audit_log {
+            report.non_zero("audit_log.max_file_size", audit_log.max_file_size);
+            report.non_zero("audit_log.max_files", audit_log.max_files as u64);
+        }
+
+        if let Some(verifier) = &self.verifier {
+            if verifier.interval.is_zero() {
+                report.error("verifier.interval", "must be greater than zero")
+            }
+            if verifier.max_depth == Some(0) {
+                report.error("verifier.max_depth", "must be greater than zero")
+            }
+        }
+
+        if let Some(integrity_check) = &self.integrity_check {
+            report.non_zero("integrity_check.batch_size", integrity_check.batch_size);
+            if integrity_check.pause.is_zero() {
+                report.warning(
+                    "integrity_check.pause",
+                    "the I/O of the check is only limited by its priority",
+                )
+            }
+        }
+
+        if self.disk_alarm.window.is_zero() {
+            report.error("disk_alarm.window", "must be greater than zero")
+        }
+        if let Some(distance) = self.disk_alarm.auto_prune_distance {
+            if self.prune.as_ref().map_or(true, |prune| prune.segments.is_empty()) {
+                report.warning(
+                    "disk_alarm.auto_prune_distance",
+                    "no segments are configured to be pruned",
+                )
+            } else if distance < MINIMUM_PRUNING_DISTANCE {
+                report.warning(
+                    "disk_alarm.auto_prune_distance",
+                    format!(
+                        "the history and receipts are kept for at least {MINIMUM_PRUNING_DISTANCE} blocks"
+                    ),
+                )
+            }
+        }
+
+        if self.engine.new_payload_budget.is_zero() {
+            report.warning("engine.new_payload_budget", "every newPayload message is reported")
+        }
+        if self.engine.forkchoice_updated_budget.is_zero() {
+            report.warning(
+                "engine.forkchoice_updated_budget",
+                "every forkchoiceUpdated message is reported",
+            )
+        }
+        report.non_zero(
+            "engine.max_parallel_validations",
+            self.engine.max_parallel_validations as u64,
+        );
+        if self.engine.max_parallel_validations == 1 {
+            report.warning("engine.max_parallel_validations", "payloads are validated serially")
+        }
+        if self.engine.memory_block_buffer_target > self.engine.persistence_threshold {
+            report.error(
+                "engine.memory_block_buffer_target",
+                "must not be greater than engine.persistence_threshold, blocks would never be persisted",
+            )
+        }
+        if self.engine.parallel_validation_memory_budget == 0 {
+            report.warning(
+                "engine.parallel_validation_memory_budget",
+                "payloads are validated serially",
+            )
+        }
+
+        if let Some(tasks) = &self.tasks {
+            let runtimes = [
+                ("tasks.consensus", &tasks.consensus),
+                ("tasks.rpc", &tasks.rpc),
+                ("tasks.background", &tasks.background),
+            ]
+            .into_iter()
+            .filter_map(|(key, runtime)| Some((key, runtime.as_ref()?)))
+            .collect::<Vec<_>>();
+            for (key, runtime) in &runtimes {
+                report.non_zero(&format!("{key}.worker_threads"), runtime.worker_threads as u64);
+                if let Some(nice) = runtime.nice {
+                    if !(-20..=19).contains(&nice) {
+                        report.error(&format!("{key}.nice"), "must be between -20 and 19")
+                    }
+                }
+            }
+            if let Some(consensus) = &tasks.consensus {
+                for (key, runtime) in runtimes.iter().filter(|(key, _)| *key != "tasks.consensus") {
+                    if runtime.cpus.iter().any(|cpu| consensus.cpus.contains(cpu)) {
+                        report.warning(
+                            &format!("{key}.cpus"),
+                            "shares CPU cores with the consensus critical tasks",
+                        )
+                    }
+                }
+            }
+        }
+
+        if let Some(tracing) = &self.tracing {
+            if tracing.otlp_endpoint.is_none() {
+                report.warning("tracing.otlp_endpoint", "no spans are exported without an endpoint")
+            } else if tracing.sample_ratio == Some(0.0) {
+                report.warning("tracing.sample_ratio", "no traces are sampled")
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PruneConfig, PruneScheduleConfig};
+    use reth_prune_types::{PruneModes, ReceiptsRetentionConfig};
+    use std::time::Duration;
+
+    #[test]
+    fn default_config_is_valid() {
+        assert_eq!(Config::default().validate(), ConfigReport::default());
+    }
+
+    #[test]
+    fn reports_issues() {
+        let mut config = Config::default();
+        config.stages.headers.commit_threshold = 0;
+        config.stages.bodies.downloader_min_concurrent_requests = 200;
+        config.stages.etl.file_size = 1024;
+        config.discovery.v5.max_peers_per_second = Some(0);
+        config.sessions.bandwidth.peer_upload = Some(0);
+        config.prune = Some(PruneConfig {
+            block_interval: 5,
+            segments: PruneModes {
+                receipts: Some(PruneMode::keep(Duration::from_secs(30 * 24 * 60 * 60))),
+                account_history: Some(PruneMode::Distance(10)),
+                storage_history: Some(PruneMode::Full),
+                receipts_retention: ReceiptsRetentionConfig {
+                    mode: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            schedule: PruneScheduleConfig { max_entries_per_second: Some(0), ..Default::default() },
+        });
+
+        let report = config.validate();
+        let errors = report.errors().map(|issue| issue.key.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            [
+                "stages.headers.commit_threshold",
+                "stages.bodies.downloader_min_concurrent_requests",
+                "sessions.bandwidth.peer_upload",
+                "discovery.v5.max_peers_per_second",
+                "prune.schedule.max_entries_per_second",
+                "prune.segments.account_history",
+                "prune.segments.storage_history",
+            ]
+        );
+        let warnings = report.warnings().map(|issue| issue.key.as_str()).collect::<Vec<_>>();
+        assert_eq!(warnings, ["stages.etl.file_size", "prune.segments.receipts_retention"]);
+
+        let err = report.into_result().unwrap_err();
+        assert_eq!(err.0.len(), 9);
+        assert!(err
+            .to_string()
+            .contains("stages.headers.commit_threshold: must be greater than zero"));
+        assert!(!err.to_string().contains("stages.etl.file_size"));
+    }
+
+    #[test]
+    fn state_expiry_needs_history() {
+        let config = Config {
+            prune: Some(PruneConfig {
+                segments: PruneModes {
+                    account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    storage_history: Some(PruneMode::Distance(2 * MINIMUM_PRUNING_DISTANCE)),
+                    state_expiry: Some(PruneMode::Distance(2 * MINIMUM_PRUNING_DISTANCE)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let warnings = config.validate().into_result().unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].key, "prune.segments.state_expiry");
+        assert!(warnings[0].message.starts_with("account_history"));
+    }
+
+    #[test]
+    fn snap_sync_requires_receipts_pruning() {
+        let mut config = Config::default();
+        config.stages.snap.enabled = true;
+
+        let errors = config.validate().into_result().unwrap_err();
+        assert_eq!(errors.0.len(), 1);
+        assert_eq!(errors.0[0].key, "stages.snap.enabled");
+
+        config.prune = Some(PruneConfig {
+            segments: PruneModes {
+                receipts: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        assert_eq!(config.validate(), ConfigReport::default());
+    }
+
+    #[test]
+    fn warnings_are_not_errors() {
+        let mut config = Config::default();
+        config.peers.connection_info.max_outbound = 0;
+
+        let warnings = config.validate().into_result().unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].severity, IssueSeverity::Warning);
+        assert_eq!(warnings[0].key, "peers.connection_info.max_outbound");
+    }
+
+    #[test]
+    fn evm_specs_are_activated_at_distinct_blocks() {
+        let activation =
+            |block, spec: &str| crate::EvmSpecActivation { block, spec: spec.to_string() };
+        let config = Config {
+            evm: Some(crate::EvmConfig {
+                spec_activations: vec![
+                    activation(0, "Shanghai"),
+                    activation(10, "Cancun"),
+                    activation(0, "London"),
+                ],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let errors = config.validate().into_result().unwrap_err();
+        assert_eq!(errors.0.len(), 1);
+        assert_eq!(errors.0[0].key, "evm.spec_activations");
+    }
+
+    #[test]
+    fn task_runtimes_are_isolated() {
+        let runtime = |worker_threads, cpus: &[usize], nice| crate::TaskRuntimeConfig {
+            worker_threads,
+            cpus: cpus.to_vec(),
+            nice,
+        };
+        let config = Config {
+            tasks: Some(crate::TasksConfig {
+                consensus: Some(runtime(2, &[0, 1], Some(-30))),
+                rpc: Some(runtime(0, &[1, 2], None)),
+                background: Some(runtime(1, &[3], Some(19))),
+            }),
+            ..Default::default()
+        };
+
+        let report = config.validate();
+        let errors = report.errors().map(|issue| issue.key.as_str()).collect::<Vec<_>>();
+        assert_eq!(errors, ["tasks.consensus.nice", "tasks.rpc.worker_threads"]);
+        let warnings = report.warnings().map(|issue| issue.key.as_str()).collect::<Vec<_>>();
+        assert_eq!(warnings, ["tasks.rpc.cpus"]);
+    }
+}
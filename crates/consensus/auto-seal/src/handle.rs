@@ -0,0 +1,264 @@
+//! A handle to mine blocks on demand, to control the timestamps of mined blocks and to change the
+//! state of the mined chain.
+
+use crate::{DevStateChanges, Storage};
+use reth_evm::state_changes::AccountChange;
+use reth_primitives::{Address, BlockNumber, TransactionSignedEcRecovered};
+use std::{collections::BTreeMap, time::Duration};
+use tokio::sync::{mpsc::UnboundedSender, oneshot};
+
+/// Commands sent to the [`MiningTask`](crate::MiningTask) by a [`MiningHandle`].
+#[derive(Debug)]
+pub(crate) enum MiningCommand {
+    /// Mines the given number of blocks, regardless of the [`MiningMode`](crate::MiningMode), and
+    /// notifies the sender once the last one is canonical.
+    Mine {
+        /// The number of blocks to mine, all ready transactions are included in the first one.
+        blocks: u64,
+        /// Transactions that are included in the first block after the ready transactions of the
+        /// pool.
+        transactions: Vec<TransactionSignedEcRecovered>,
+        /// Notified once the blocks are mined.
+        tx: oneshot::Sender<()>,
+    },
+    /// Unwinds the chain to the given block once the queued blocks are mined, and notifies the
+    /// sender with the outcome.
+    Unwind {
+        /// The block that becomes the best block.
+        block: BlockNumber,
+        /// Notified once the chain is unwound.
+        tx: oneshot::Sender<Result<(), MiningError>>,
+    },
+    /// Mines blocks as soon as transactions are ready if enabled. Disabling it stops mining only
+    /// if blocks are mined as soon as transactions are ready.
+    SetAutomine {
+        /// Whether blocks are mined as soon as transactions are ready.
+        enabled: bool,
+        /// Notified once the mode is changed.
+        tx: oneshot::Sender<()>,
+    },
+    /// Mines blocks at the given interval, or stops mining if there's no interval.
+    SetIntervalMining {
+        /// The interval between mined blocks.
+        interval: Option<Duration>,
+        /// Notified once the mode is changed.
+        tx: oneshot::Sender<()>,
+    },
+    /// Returns whether blocks are mined as soon as transactions are ready.
+    Automine {
+        /// Receives the answer.
+        tx: oneshot::Sender<bool>,
+    },
+}
+
+/// An error returned by a [`MiningHandle`].
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum MiningError {
+    /// The mining task is not running.
+    #[error("mining task is not running")]
+    TaskClosed,
+    /// A block failed to be built or inserted, see the logs of the node.
+    #[error("failed to mine block")]
+    Failed,
+    /// The chain failed to be unwound, see the logs of the node.
+    #[error("failed to unwind the chain")]
+    UnwindFailed,
+    /// The timestamp is not after the timestamp of the latest block.
+    #[error("timestamp {timestamp} is not after the latest block timestamp {latest}")]
+    TimestampTooLow {
+        /// The requested timestamp.
+        timestamp: u64,
+        /// The timestamp of the latest block.
+        latest: u64,
+    },
+    /// The block executor of the node doesn't apply the state changes of the mined chain.
+    #[error("state changes are not supported by the node")]
+    StateChangesUnsupported,
+}
+
+/// A handle to the [`MiningTask`](crate::MiningTask), to mine blocks on demand, to control the
+/// timestamps of the mined blocks and to change the state of the mined chain.
+///
+/// This backs the `evm_` and `anvil_` RPC namespaces in dev mode.
+#[derive(Debug, Clone)]
+pub struct MiningHandle {
+    /// The storage of the mined chain, which tracks the block time.
+    storage: Storage,
+    /// The state changes of the mined chain, if they are supported.
+    state_changes: Option<DevStateChanges>,
+    /// Sends commands to the mining task.
+    to_task: UnboundedSender<MiningCommand>,
+}
+
+impl MiningHandle {
+    pub(crate) const fn new(
+        storage: Storage,
+        state_changes: Option<DevStateChanges>,
+        to_task: UnboundedSender<MiningCommand>,
+    ) -> Self {
+        Self { storage, state_changes, to_task }
+    }
+
+    /// Mines the given number of blocks, even if there are no transactions to include, and waits
+    /// until they are canonical.
+    pub async fn mine(&self, blocks: u64) -> Result<(), MiningError> {
+        if blocks == 0 {
+            return Ok(())
+        }
+        self.mine_with(blocks, Vec::new()).await
+    }
+
+    /// Mines a block that includes the given transaction and waits until it is canonical.
+    ///
+    /// The transaction bypasses the pool, and is executed as its sender even if the sender did not
+    /// sign it. This is used to send transactions of impersonated accounts.
+    pub async fn send_transaction(
+        &self,
+        transaction: TransactionSignedEcRecovered,
+    ) -> Result<(), MiningError> {
+        if transaction.recover_signer() != Some(transaction.signer()) {
+            self.state_changes()?.impersonate(transaction.hash(), transaction.signer());
+        }
+        self.mine_with(1, vec![transaction]).await
+    }
+
+    /// Changes the state of the accounts in the pending state, which is applied before the
+    /// transactions of the next mined block.
+    pub fn set_accounts(&self, changes: Vec<(Address, AccountChange)>) -> Result<(), MiningError> {
+        let state_changes = self.state_changes()?;
+        for (address, change) in changes {
+            state_changes.schedule(address, change);
+        }
+        Ok(())
+    }
+
+    /// Returns the change of the account in the pending state, if any.
+    pub fn pending_change(&self, address: &Address) -> Option<AccountChange> {
+        self.state_changes.as_ref().and_then(|state_changes| state_changes.pending_change(address))
+    }
+
+    /// Returns all changes in the pending state.
+    pub fn pending_changes(&self) -> BTreeMap<Address, AccountChange> {
+        self.state_changes.as_ref().map(DevStateChanges::pending).unwrap_or_default()
+    }
+
+    /// Replaces all changes in the pending state, e.g. to restore the pending state of a snapshot.
+    pub fn set_pending_changes(
+        &self,
+        changes: BTreeMap<Address, AccountChange>,
+    ) -> Result<(), MiningError> {
+        self.state_changes()?.set_pending(changes);
+        Ok(())
+    }
+
+    fn state_changes(&self) -> Result<&DevStateChanges, MiningError> {
+        self.state_changes.as_ref().ok_or(MiningError::StateChangesUnsupported)
+    }
+
+    /// Unwinds the chain to the given block once the queued blocks are mined, and waits until the
+    /// block is the best block.
+    ///
+    /// The unwound blocks are removed from the database, together with their transactions. This
+    /// does nothing if the block is not below the best block.
+    pub async fn unwind(&self, block: BlockNumber) -> Result<(), MiningError> {
+        let (tx, rx) = oneshot::channel();
+        self.send(MiningCommand::Unwind { block, tx })?;
+        rx.await.map_err(|_| MiningError::UnwindFailed)?
+    }
+
+    /// Returns whether blocks are mined as soon as transactions are ready.
+    pub async fn automine(&self) -> Result<bool, MiningError> {
+        let (tx, rx) = oneshot::channel();
+        self.send(MiningCommand::Automine { tx })?;
+        rx.await.map_err(|_| MiningError::TaskClosed)
+    }
+
+    /// Mines blocks as soon as transactions are ready if enabled.
+    ///
+    /// Disabling it stops mining if blocks are mined as soon as transactions are ready, and does
+    /// nothing if they are mined at an interval.
+    pub async fn set_automine(&self, enabled: bool) -> Result<(), MiningError> {
+        let (tx, rx) = oneshot::channel();
+        self.send(MiningCommand::SetAutomine { enabled, tx })?;
+        rx.await.map_err(|_| MiningError::TaskClosed)
+    }
+
+    /// Mines blocks at the given interval, or stops mining if there's no interval.
+    pub async fn set_interval_mining(&self, interval: Option<Duration>) -> Result<(), MiningError> {
+        let (tx, rx) = oneshot::channel();
+        self.send(MiningCommand::SetIntervalMining { interval, tx })?;
+        rx.await.map_err(|_| MiningError::TaskClosed)
+    }
+
+    fn send(&self, command: MiningCommand) -> Result<(), MiningError> {
+        self.to_task.send(command).map_err(|_| MiningError::TaskClosed)
+    }
+
+    async fn mine_with(
+        &self,
+        blocks: u64,
+        transactions: Vec<TransactionSignedEcRecovered>,
+    ) -> Result<(), MiningError> {
+        let (tx, rx) = oneshot::channel();
+        self.send(MiningCommand::Mine { blocks, transactions, tx })?;
+        rx.await.map_err(|_| MiningError::Failed)
+    }
+
+    /// Moves the block time forward by the given number of seconds.
+    ///
+    /// Returns the total offset of the block time from the system time, in seconds.
+    pub async fn increase_time(&self, seconds: u64) -> i64 {
+        let mut storage = self.storage.write().await;
+        storage.time_offset = storage.time_offset.saturating_add_unsigned(seconds);
+        storage.time_offset
+    }
+
+    /// Sets the timestamp of the next mined block. Blocks mined after it continue from this
+    /// timestamp.
+    pub async fn set_next_block_timestamp(&self, timestamp: u64) -> Result<(), MiningError> {
+        let mut storage = self.storage.write().await;
+        let latest = storage.best_timestamp();
+        if timestamp <= latest {
+            return Err(MiningError::TimestampTooLow { timestamp, latest })
+        }
+        storage.set_next_timestamp(timestamp);
+        Ok(())
+    }
+
+    /// Sets the block time to the given timestamp. The timestamps of mined blocks are still
+    /// strictly increasing.
+    ///
+    /// Returns the number of seconds the block time moved forward.
+    pub async fn set_time(&self, timestamp: u64) -> u64 {
+        self.storage.write().await.set_time(timestamp)
+    }
+
+    /// Sets the number of seconds between the timestamps of mined blocks, or removes it so the
+    /// timestamps follow the block time again.
+    ///
+    /// Returns `true` if an interval was set before.
+    pub async fn set_block_timestamp_interval(&self, interval: Option<u64>) -> bool {
+        let mut storage = self.storage.write().await;
+        std::mem::replace(&mut storage.timestamp_interval, interval).is_some()
+    }
+
+    /// Sets the beneficiary of the mined blocks.
+    pub async fn set_coinbase(&self, coinbase: Address) {
+        self.storage.write().await.coinbase = coinbase;
+    }
+
+    /// Sets the gas limit of the mined blocks.
+    pub async fn set_block_gas_limit(&self, gas_limit: u64) {
+        self.storage.write().await.gas_limit = Some(gas_limit);
+    }
+
+    /// Returns the gas limit of the mined blocks, if it's set explicitly.
+    pub async fn block_gas_limit(&self) -> Option<u64> {
+        self.storage.read().await.gas_limit
+    }
+
+    /// Sets the base fee of the next mined block, if the chain has a base fee.
+    pub async fn set_next_block_base_fee(&self, base_fee: u64) {
+        self.storage.write().await.next_base_fee = Some(base_fee);
+    }
+}
@@ -19,14 +19,12 @@ use reth_beacon_consensus::BeaconEngineMessage;
 use reth_chainspec::{ChainSpec, EthereumHardforks};
 use reth_consensus::{Consensus, ConsensusError, PostExecutionInput};
 use reth_engine_primitives::EngineTypes;
-use reth_execution_errors::{
-    BlockExecutionError, BlockValidationError, InternalBlockExecutionError,
-};
+use reth_execution_errors::{BlockExecutionError, InternalBlockExecutionError};
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
-    eip4844::calculate_excess_blob_gas, proofs, Block, BlockBody, BlockHash, BlockHashOrNumber,
-    BlockNumber, BlockWithSenders, Bloom, Header, Requests, SealedBlock, SealedHeader,
-    TransactionSigned, Withdrawals, B256, U256,
+    eip4844::calculate_excess_blob_gas, proofs, Address, Block, BlockBody, BlockHash,
+    BlockHashOrNumber, BlockNumber, BlockWithSenders, Bloom, Header, Requests, SealedBlock,
+    SealedHeader, TransactionSigned, TransactionSignedEcRecovered, Withdrawals, B256, U256,
 };
 use reth_provider::{BlockReaderIdExt, StateProviderFactory, StateRootProvider};
 use reth_revm::database::StateProviderDatabase;
@@ -40,12 +38,16 @@ use tokio::sync::{mpsc::UnboundedSender, RwLock, RwLockReadGuard, RwLockWriteGua
 use tracing::trace;
 
 mod client;
+mod handle;
 mod mode;
+mod state;
 mod task;
 
 pub use crate::client::AutoSealClient;
+pub use handle::{MiningError, MiningHandle};
 pub use mode::{FixedBlockTimeMiner, MiningMode, ReadyTransactionMiner};
 use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor};
+pub use state::DevStateChanges;
 pub use task::MiningTask;
 
 /// A consensus implementation intended for local development and testing purposes.
@@ -107,6 +109,7 @@ pub struct AutoSealBuilder<Client, Pool, Engine: EngineTypes, EvmConfig> {
     storage: Storage,
     to_engine: UnboundedSender<BeaconEngineMessage<Engine>>,
     evm_config: EvmConfig,
+    state_changes: Option<DevStateChanges>,
 }
 
 // === impl AutoSealBuilder ===
@@ -140,6 +143,7 @@ where
             mode,
             to_engine,
             evm_config,
+            state_changes: None,
         }
     }
 
@@ -149,12 +153,22 @@ where
         self
     }
 
+    /// Sets the state changes that are applied to the mined blocks, which allows changing the state
+    /// of the mined chain through the [`MiningHandle`].
+    ///
+    /// The same changes must be installed on the block executor, see [`DevStateChanges`].
+    pub fn with_state_changes(mut self, state_changes: DevStateChanges) -> Self {
+        self.state_changes = Some(state_changes);
+        self
+    }
+
     /// Consumes the type and returns all components
     #[track_caller]
     pub fn build(
         self,
     ) -> (AutoSealConsensus, AutoSealClient, MiningTask<Client, Pool, EvmConfig, Engine>) {
-        let Self { client, consensus, pool, mode, storage, to_engine, evm_config } = self;
+        let Self { client, consensus, pool, mode, storage, to_engine, evm_config, state_changes } =
+            self;
         let auto_client = AutoSealClient::new(storage.clone());
         let task = MiningTask::new(
             Arc::clone(&consensus.chain_spec),
@@ -164,6 +178,7 @@ where
             client,
             pool,
             evm_config,
+            state_changes,
         );
         (consensus, auto_client, task)
     }
@@ -219,6 +234,18 @@ pub(crate) struct StorageInner {
     pub(crate) best_hash: B256,
     /// The total difficulty of the chain until this block
     pub(crate) total_difficulty: U256,
+    /// The offset of the block time from the system time, in seconds
+    pub(crate) time_offset: i64,
+    /// The timestamp of the next block, if set explicitly
+    pub(crate) next_timestamp: Option<u64>,
+    /// The number of seconds between the timestamps of mined blocks, if set explicitly
+    pub(crate) timestamp_interval: Option<u64>,
+    /// The beneficiary of the mined blocks
+    pub(crate) coinbase: Address,
+    /// The gas limit of the mined blocks, if set explicitly
+    pub(crate) gas_limit: Option<u64>,
+    /// The base fee of the next block, if set explicitly
+    pub(crate) next_base_fee: Option<u64>,
 }
 
 // === impl StorageInner ===
@@ -241,6 +268,73 @@ impl StorageInner {
         self.headers.get(&num).cloned()
     }
 
+    /// Returns the timestamp of the best block.
+    pub(crate) fn best_timestamp(&self) -> u64 {
+        self.headers.get(&self.best_block).map(|header| header.timestamp).unwrap_or_default()
+    }
+
+    /// Sets the timestamp of the next block, and moves the block time so the following blocks
+    /// continue from it.
+    pub(crate) fn set_next_timestamp(&mut self, timestamp: u64) {
+        self.time_offset = timestamp as i64 - unix_timestamp() as i64;
+        self.next_timestamp = Some(timestamp);
+    }
+
+    /// Sets the block time, without changing the timestamp of the next block if it's set
+    /// explicitly.
+    ///
+    /// Returns the number of seconds the block time moved forward.
+    pub(crate) fn set_time(&mut self, timestamp: u64) -> u64 {
+        let now = self.block_time();
+        self.time_offset = timestamp as i64 - unix_timestamp() as i64;
+        timestamp.saturating_sub(now)
+    }
+
+    /// Returns the system time moved by the time offset.
+    pub(crate) fn block_time(&self) -> u64 {
+        unix_timestamp().saturating_add_signed(self.time_offset)
+    }
+
+    /// Returns the timestamp of the next block.
+    ///
+    /// This is the explicitly set timestamp if any, the timestamp of the best block plus the
+    /// timestamp interval if it's set, and the block time otherwise. Timestamps are strictly
+    /// increasing, so blocks that are mined in the same second get consecutive timestamps.
+    pub(crate) fn next_block_timestamp(&mut self) -> u64 {
+        let timestamp =
+            self.next_timestamp.take().unwrap_or_else(|| match self.timestamp_interval {
+                Some(interval) => self.best_timestamp().saturating_add(interval),
+                None => self.block_time(),
+            });
+        timestamp.max(self.best_timestamp() + 1)
+    }
+
+    /// Unwinds the chain to the given block, which becomes the best block.
+    ///
+    /// The headers and bodies of the unwound blocks are removed, and the timestamp of the next
+    /// block is reset.
+    pub(crate) fn unwind_to(&mut self, best_block: SealedHeader) {
+        let (header, best_hash) = best_block.split();
+        let unwound: U256 = self
+            .headers
+            .iter()
+            .filter(|(number, _)| **number > header.number)
+            .map(|(_, header)| header.difficulty)
+            .sum();
+        self.headers.retain(|number, _| *number < header.number);
+        self.hash_to_number.retain(|_, number| *number < header.number);
+        let hash_to_number = &self.hash_to_number;
+        self.bodies.retain(|hash, _| hash_to_number.contains_key(hash));
+
+        self.best_hash = best_hash;
+        self.best_block = header.number;
+        self.total_difficulty = self.total_difficulty.saturating_sub(unwound);
+        self.next_timestamp = None;
+        self.bodies.entry(best_hash).or_default();
+        self.hash_to_number.insert(best_hash, header.number);
+        self.headers.insert(header.number, header);
+    }
+
     /// Inserts a new header+body pair
     pub(crate) fn insert_new_block(&mut self, mut header: Header, body: BlockBody) {
         header.number = self.best_block + 1;
@@ -291,7 +385,8 @@ impl StorageInner {
             withdrawals_root: withdrawals.map(|w| proofs::calculate_withdrawals_root(w)),
             difficulty: U256::from(2),
             number: self.best_block + 1,
-            gas_limit: chain_spec.max_gas_limit,
+            beneficiary: self.coinbase,
+            gas_limit: self.gas_limit.unwrap_or(chain_spec.max_gas_limit),
             timestamp,
             base_fee_per_gas,
             blob_gas_used,
@@ -329,7 +424,7 @@ impl StorageInner {
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn build_and_execute<Provider, Executor>(
         &mut self,
-        transactions: Vec<TransactionSigned>,
+        transactions: Vec<TransactionSignedEcRecovered>,
         ommers: Vec<Header>,
         provider: &Provider,
         chain_spec: Arc<ChainSpec>,
@@ -339,7 +434,9 @@ impl StorageInner {
         Executor: BlockExecutorProvider,
         Provider: StateProviderFactory,
     {
-        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let timestamp = self.next_block_timestamp();
+        let (transactions, senders): (Vec<_>, Vec<_>) =
+            transactions.into_iter().map(TransactionSignedEcRecovered::to_components).unzip();
 
         // if shanghai is active, include empty withdrawals
         let withdrawals =
@@ -348,7 +445,7 @@ impl StorageInner {
         let requests =
             chain_spec.is_prague_active_at_timestamp(timestamp).then_some(Requests::default());
 
-        let header = self.build_header_template(
+        let mut header = self.build_header_template(
             timestamp,
             &transactions,
             &ommers,
@@ -356,16 +453,26 @@ impl StorageInner {
             requests.as_ref(),
             &chain_spec,
         );
-
-        let block = Block {
-            header,
-            body: transactions,
-            ommers: ommers.clone(),
-            withdrawals: withdrawals.clone(),
-            requests: requests.clone(),
+        if let Some(base_fee) = self.next_base_fee.take() {
+            // the base fee can only be set once it's part of the header
+            if header.base_fee_per_gas.is_some() {
+                header.base_fee_per_gas = Some(base_fee);
+            }
         }
-        .with_recovered_senders()
-        .ok_or(BlockExecutionError::Validation(BlockValidationError::SenderRecoveryError))?;
+
+        // the senders are not recovered, since transactions of impersonated accounts are executed
+        // as a sender that did not sign them
+        let block = BlockWithSenders::new(
+            Block {
+                header,
+                body: transactions,
+                ommers: ommers.clone(),
+                withdrawals: withdrawals.clone(),
+                requests: requests.clone(),
+            },
+            senders,
+        )
+        .expect("one sender per transaction");
 
         trace!(target: "consensus::auto", transactions=?&block.body, "executing transactions");
 
@@ -432,3 +539,8 @@ impl StorageInner {
         Ok((new_header, execution_outcome))
     }
 }
+
+/// Returns the current system time as a unix timestamp.
+fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
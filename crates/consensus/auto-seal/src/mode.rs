@@ -127,6 +127,11 @@ pub struct ReadyTransactionMiner {
 // === impl ReadyTransactionMiner ===
 
 impl ReadyTransactionMiner {
+    /// Returns how many transactions are mined per block.
+    pub(crate) const fn max_transactions(&self) -> usize {
+        self.max_transactions
+    }
+
     fn poll<Pool>(
         &mut self,
         pool: &Pool,
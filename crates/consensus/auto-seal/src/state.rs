@@ -0,0 +1,125 @@
+//! Irregular state changes of the mined chain, e.g. to set the balance of an account.
+
+use parking_lot::RwLock;
+use reth_evm::state_changes::{AccountChange, BlockStateChanges};
+use reth_primitives::{Address, BlockNumber, TxHash};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+/// The irregular state changes of the mined chain.
+///
+/// Changes are scheduled by a [`MiningHandle`](crate::MiningHandle) and stay pending until the next
+/// block is mined, where they are applied before its transactions. They must be installed on the
+/// block executor of the node as well, so the blocks are executed with the same changes when they
+/// are inserted.
+#[derive(Debug, Clone, Default)]
+pub struct DevStateChanges {
+    inner: Arc<RwLock<DevStateChangesInner>>,
+}
+
+#[derive(Debug, Default)]
+struct DevStateChangesInner {
+    /// Changes that are applied to the next mined block, by account.
+    pending: BTreeMap<Address, AccountChange>,
+    /// Changes of the mined blocks.
+    blocks: BTreeMap<BlockNumber, BTreeMap<Address, AccountChange>>,
+    /// The accounts that impersonated transactions are executed as.
+    senders: HashMap<TxHash, Address>,
+}
+
+impl DevStateChanges {
+    /// Schedules the change of the account for the next mined block.
+    pub(crate) fn schedule(&self, address: Address, change: AccountChange) {
+        self.inner.write().pending.entry(address).or_default().extend(change);
+    }
+
+    /// Returns the pending change of the account, if any.
+    pub(crate) fn pending_change(&self, address: &Address) -> Option<AccountChange> {
+        self.inner.read().pending.get(address).cloned()
+    }
+
+    /// Returns all pending changes.
+    pub(crate) fn pending(&self) -> BTreeMap<Address, AccountChange> {
+        self.inner.read().pending.clone()
+    }
+
+    /// Replaces the pending changes.
+    pub(crate) fn set_pending(&self, pending: BTreeMap<Address, AccountChange>) {
+        self.inner.write().pending = pending;
+    }
+
+    /// Executes the transaction as the given sender instead of its signer.
+    pub(crate) fn impersonate(&self, transaction: TxHash, sender: Address) {
+        self.inner.write().senders.insert(transaction, sender);
+    }
+
+    /// Moves the pending changes to the given block, which is about to be mined.
+    pub(crate) fn apply_pending(&self, block: BlockNumber) {
+        let mut inner = self.inner.write();
+        let pending = std::mem::take(&mut inner.pending);
+        let changes = inner.blocks.entry(block).or_default();
+        for (address, change) in pending {
+            changes.entry(address).or_default().extend(change);
+        }
+    }
+
+    /// Removes the changes of the blocks after the given block, which are unwound.
+    pub(crate) fn unwind(&self, block: BlockNumber) {
+        self.inner.write().blocks.split_off(&(block + 1));
+    }
+}
+
+impl BlockStateChanges for DevStateChanges {
+    fn account_changes(&self, block: BlockNumber) -> Vec<(Address, AccountChange)> {
+        self.inner
+            .read()
+            .blocks
+            .get(&block)
+            .map(|changes| {
+                changes.iter().map(|(address, change)| (*address, change.clone())).collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn sender(&self, transaction: &TxHash) -> Option<Address> {
+        self.inner.read().senders.get(transaction).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::U256;
+
+    #[test]
+    fn pending_changes_are_applied_to_the_next_block() {
+        let changes = DevStateChanges::default();
+        let address = Address::with_last_byte(1);
+        changes.schedule(address, AccountChange::default().with_balance(U256::from(1)));
+        changes.schedule(address, AccountChange::default().with_nonce(2));
+        assert!(changes.account_changes(1).is_empty());
+        assert_eq!(
+            changes.pending_change(&address),
+            Some(AccountChange::default().with_balance(U256::from(1)).with_nonce(2))
+        );
+
+        changes.apply_pending(1);
+        changes.apply_pending(2);
+        assert_eq!(
+            changes.account_changes(1),
+            vec![(address, AccountChange::default().with_balance(U256::from(1)).with_nonce(2))]
+        );
+        assert!(changes.account_changes(2).is_empty());
+        assert_eq!(changes.pending_change(&address), None);
+
+        changes.unwind(0);
+        assert!(changes.account_changes(1).is_empty());
+
+        let transaction = TxHash::with_last_byte(1);
+        assert_eq!(changes.sender(&transaction), None);
+        changes.impersonate(transaction, address);
+        assert_eq!(changes.sender(&transaction), Some(address));
+    }
+}
@@ -1,15 +1,20 @@
-use crate::{mode::MiningMode, Storage};
+use crate::{
+    handle::{MiningCommand, MiningError, MiningHandle},
+    mode::MiningMode,
+    DevStateChanges, Storage, StorageInner,
+};
 use futures_util::{future::BoxFuture, FutureExt};
 use reth_beacon_consensus::{BeaconEngineMessage, ForkchoiceStatus};
+use reth_blockchain_tree_api::{error::CanonicalError, BlockchainTreeEngine};
 use reth_chainspec::ChainSpec;
 use reth_engine_primitives::EngineTypes;
 use reth_evm::execute::BlockExecutorProvider;
-use reth_primitives::IntoRecoveredTransaction;
-use reth_provider::{CanonChainTracker, StateProviderFactory};
+use reth_primitives::{BlockNumber, IntoRecoveredTransaction, TransactionSignedEcRecovered, B256};
+use reth_provider::{CanonChainTracker, HeaderProvider, ProviderError, StateProviderFactory};
 use reth_rpc_types::engine::ForkchoiceState;
 use reth_stages_api::PipelineEvent;
 use reth_tokio_util::EventStream;
-use reth_transaction_pool::{TransactionPool, ValidPoolTransaction};
+use reth_transaction_pool::TransactionPool;
 use std::{
     collections::VecDeque,
     future::Future,
@@ -17,7 +22,10 @@ use std::{
     sync::Arc,
     task::{Context, Poll},
 };
-use tokio::sync::{mpsc::UnboundedSender, oneshot};
+use tokio::sync::{
+    mpsc::{self, UnboundedReceiver, UnboundedSender},
+    oneshot,
+};
 use tracing::{debug, error, warn};
 
 /// A Future that listens for new ready transactions and puts new blocks into storage
@@ -34,8 +42,17 @@ pub struct MiningTask<Client, Pool: TransactionPool, Executor, Engine: EngineTyp
     storage: Storage,
     /// Pool where transactions are stored
     pool: Pool,
-    /// backlog of sets of transactions ready to be mined
-    queued: VecDeque<Vec<Arc<ValidPoolTransaction<<Pool as TransactionPool>::Transaction>>>>,
+    /// How many transactions are mined per block when blocks are mined as soon as transactions
+    /// are ready
+    max_transactions: usize,
+    /// backlog of sets of transactions ready to be mined and of unwinds, which are run in order
+    queued: VecDeque<Job>,
+    /// The state changes that are applied to the mined blocks
+    state_changes: Option<DevStateChanges>,
+    /// Receives the commands of the [`MiningHandle`]s
+    commands: UnboundedReceiver<MiningCommand>,
+    /// The handle that is cloned for callers of [`MiningTask::handle`]
+    handle: MiningHandle,
     // TODO: ideally this would just be a sender of hashes
     to_engine: UnboundedSender<BeaconEngineMessage<Engine>>,
     /// The pipeline events to listen on
@@ -59,21 +76,36 @@ impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes>
         client: Client,
         pool: Pool,
         block_executor: Executor,
+        state_changes: Option<DevStateChanges>,
     ) -> Self {
+        let (to_task, commands) = mpsc::unbounded_channel();
+        let max_transactions = match &miner {
+            MiningMode::Auto(miner) => miner.max_transactions(),
+            _ => 1,
+        };
         Self {
             chain_spec,
             client,
             miner,
+            max_transactions,
             insert_task: None,
+            handle: MiningHandle::new(storage.clone(), state_changes.clone(), to_task),
+            state_changes,
             storage,
             pool,
             to_engine,
             queued: Default::default(),
+            commands,
             pipe_line_events: None,
             block_executor,
         }
     }
 
+    /// Returns a handle to mine blocks on demand and to control the timestamps of mined blocks.
+    pub fn handle(&self) -> MiningHandle {
+        self.handle.clone()
+    }
+
     /// Sets the pipeline events to listen on.
     pub fn set_pipeline_events(&mut self, events: EventStream<PipelineEvent>) {
         self.pipe_line_events = Some(events);
@@ -82,7 +114,13 @@ impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes>
 
 impl<Executor, Client, Pool, Engine> Future for MiningTask<Client, Pool, Executor, Engine>
 where
-    Client: StateProviderFactory + CanonChainTracker + Clone + Unpin + 'static,
+    Client: StateProviderFactory
+        + CanonChainTracker
+        + BlockchainTreeEngine
+        + HeaderProvider
+        + Clone
+        + Unpin
+        + 'static,
     Pool: TransactionPool + Unpin + 'static,
     Engine: EngineTypes,
     Executor: BlockExecutorProvider,
@@ -94,9 +132,54 @@ where
 
         // this drives block production and
         loop {
+            while let Poll::Ready(Some(command)) = this.commands.poll_recv(cx) {
+                match command {
+                    MiningCommand::Mine { blocks, transactions, tx } => {
+                        // the first block includes all ready transactions, the others are empty
+                        let mut ready: Vec<_> = this
+                            .pool
+                            .best_transactions()
+                            .map(|tx| tx.to_recovered_transaction())
+                            .collect();
+                        ready.extend(transactions);
+                        this.queued.push_back(Job::Mine(ready, None));
+                        for _ in 1..blocks {
+                            this.queued.push_back(Job::Mine(Vec::new(), None));
+                        }
+                        if let Some(Job::Mine(_, done)) = this.queued.back_mut() {
+                            *done = Some(tx);
+                        }
+                    }
+                    MiningCommand::Unwind { block, tx } => {
+                        this.queued.push_back(Job::Unwind(block, tx));
+                    }
+                    MiningCommand::SetAutomine { enabled, tx } => {
+                        let automine = matches!(this.miner, MiningMode::Auto(_));
+                        if enabled && !automine {
+                            this.miner = MiningMode::instant(
+                                this.max_transactions,
+                                this.pool.pending_transactions_listener(),
+                            );
+                        } else if !enabled && automine {
+                            this.miner = MiningMode::None;
+                        }
+                        let _ = tx.send(());
+                    }
+                    MiningCommand::SetIntervalMining { interval, tx } => {
+                        this.miner = interval.map_or(MiningMode::None, MiningMode::interval);
+                        let _ = tx.send(());
+                    }
+                    MiningCommand::Automine { tx } => {
+                        let _ = tx.send(matches!(this.miner, MiningMode::Auto(_)));
+                    }
+                }
+            }
+
             if let Poll::Ready(transactions) = this.miner.poll(&this.pool, cx) {
                 // miner returned a set of transaction that we feed to the producer
-                this.queued.push_back(transactions);
+                let transactions =
+                    transactions.into_iter().map(|tx| tx.to_recovered_transaction()).collect();
+                this.queued.push_back(Job::Mine(transactions, None));
             }
 
             if this.insert_task.is_none() {
@@ -107,7 +190,25 @@ where
 
                 // ready to queue in new insert task
                 let storage = this.storage.clone();
-                let transactions = this.queued.pop_front().expect("not empty");
+                let (transactions, done) = match this.queued.pop_front().expect("not empty") {
+                    Job::Mine(transactions, done) => (transactions, done),
+                    Job::Unwind(block, done) => {
+                        let client = this.client.clone();
+                        let state_changes = this.state_changes.clone();
+                        let events = this.pipe_line_events.take();
+                        this.insert_task = Some(Box::pin(async move {
+                            let mut storage = storage.write().await;
+                            let result =
+                                unwind_chain(&client, &mut storage, block, state_changes.as_ref());
+                            if let Err(err) = &result {
+                                error!(target: "consensus::auto", %err, block, "Failed to unwind chain");
+                            }
+                            let _ = done.send(result.map_err(|_| MiningError::UnwindFailed));
+                            events
+                        }));
+                        continue
+                    }
+                };
 
                 let to_engine = this.to_engine.clone();
                 let client = this.client.clone();
@@ -115,23 +216,24 @@ where
                 let pool = this.pool.clone();
                 let events = this.pipe_line_events.take();
                 let executor = this.block_executor.clone();
+                let state_changes = this.state_changes.clone();
 
                 // Create the mining future that creates a block, notifies the engine that drives
                 // the pipeline
                 this.insert_task = Some(Box::pin(async move {
                     let mut storage = storage.write().await;
 
-                    let transactions: Vec<_> = transactions
-                        .into_iter()
-                        .map(|tx| {
-                            let recovered = tx.to_recovered_transaction();
-                            recovered.into_signed()
-                        })
-                        .collect();
+                    // the pending state changes are applied before the transactions of the new
+                    // block
+                    if let Some(state_changes) = &state_changes {
+                        state_changes.apply_pending(storage.best_block + 1);
+                    }
+
+                    let hashes = transactions.iter().map(|tx| tx.hash()).collect();
                     let ommers = vec![];
 
                     match storage.build_and_execute(
-                        transactions.clone(),
+                        transactions,
                         ommers.clone(),
                         &client,
                         chain_spec,
@@ -139,13 +241,12 @@ where
                     ) {
                         Ok((new_header, _bundle_state)) => {
                             // clear all transactions from pool
-                            pool.remove_transactions(
-                                transactions.iter().map(|tx| tx.hash()).collect(),
-                            );
+                            pool.remove_transactions(hashes);
 
+                            // blocks are not finalized, so the chain can be unwound
                             let state = ForkchoiceState {
                                 head_block_hash: new_header.hash(),
-                                finalized_block_hash: new_header.hash(),
+                                finalized_block_hash: B256::ZERO,
                                 safe_block_hash: new_header.hash(),
                             };
                             drop(storage);
@@ -188,7 +289,10 @@ where
                             // update canon chain for rpc
                             client.set_canonical_head(new_header.clone());
                             client.set_safe(new_header.clone());
-                            client.set_finalized(new_header.clone());
+
+                            if let Some(done) = done {
+                                let _ = done.send(());
+                            }
                         }
                         Err(err) => {
                             warn!(target: "consensus::auto", %err, "failed to execute block")
@@ -216,6 +320,43 @@ where
     }
 }
 
+/// A job of the [`MiningTask`], jobs are run one at a time.
+enum Job {
+    /// Mines a block with the transactions, and notifies the sender once it's canonical.
+    Mine(Vec<TransactionSignedEcRecovered>, Option<oneshot::Sender<()>>),
+    /// Unwinds the chain to the block, and notifies the sender with the outcome.
+    Unwind(BlockNumber, oneshot::Sender<Result<(), MiningError>>),
+}
+
+/// Unwinds the canonical chain and the mined chain to the given block, and removes the state
+/// changes of the unwound blocks.
+fn unwind_chain<Client>(
+    client: &Client,
+    storage: &mut StorageInner,
+    block: BlockNumber,
+    state_changes: Option<&DevStateChanges>,
+) -> Result<(), CanonicalError>
+where
+    Client: BlockchainTreeEngine + HeaderProvider + CanonChainTracker,
+{
+    if block >= storage.best_block {
+        return Ok(())
+    }
+
+    client.unwind(block)?;
+    let header =
+        client.sealed_header(block)?.ok_or_else(|| ProviderError::HeaderNotFound(block.into()))?;
+    storage.unwind_to(header.clone());
+    if let Some(state_changes) = state_changes {
+        state_changes.unwind(block);
+    }
+
+    // update canon chain for rpc
+    client.set_canonical_head(header.clone());
+    client.set_safe(header);
+    Ok(())
+}
+
 impl<Client, Pool: TransactionPool, EvmConfig: std::fmt::Debug, Engine: EngineTypes> std::fmt::Debug
     for MiningTask<Client, Pool, EvmConfig, Engine>
 {
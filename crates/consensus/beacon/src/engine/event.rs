@@ -16,6 +16,8 @@ pub enum BeaconConsensusEngineEvent {
     LiveSyncProgress(ConsensusEngineLiveSyncProgress),
     /// A block was added to the fork chain.
     ForkBlockAdded(Arc<SealedBlock>),
+    /// A received block failed validation, and the validation error
+    InvalidBlock(Box<SealedHeader>, String),
 }
 
 impl BeaconConsensusEngineEvent {
@@ -40,6 +40,15 @@ where
         Self { to_engine, event_sender }
     }
 
+    /// Returns a handle that sends messages to the given channel instead, but shares the event
+    /// listeners of this handle.
+    pub fn with_engine_sender(
+        &self,
+        to_engine: UnboundedSender<BeaconEngineMessage<Engine>>,
+    ) -> Self {
+        Self { to_engine, event_sender: self.event_sender.clone() }
+    }
+
     /// Sends a new payload message to the beacon consensus engine and waits for a response.
     ///
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/shanghai.md#engine_newpayloadv2>
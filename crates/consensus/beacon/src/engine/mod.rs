@@ -1663,6 +1663,10 @@ where
                             self.latest_valid_hash_for_invalid_payload(block.parent_hash)?
                         };
                         // keep track of the invalid header
+                        self.event_sender.notify(BeaconConsensusEngineEvent::InvalidBlock(
+                            Box::new(block.header.clone()),
+                            error.to_string(),
+                        ));
                         self.invalid_headers.insert(block.header);
                         PayloadStatus::new(
                             PayloadStatusEnum::Invalid { validation_error: error.to_string() },
@@ -1772,6 +1776,10 @@ where
                             let (block, err) = err.split();
                             warn!(target: "consensus::engine", invalid_number=?block.number, invalid_hash=?block.hash(), %err, "Marking block as invalid");
 
+                            self.event_sender.notify(BeaconConsensusEngineEvent::InvalidBlock(
+                                Box::new(block.header.clone()),
+                                err.to_string(),
+                            ));
                             self.invalid_headers.insert(block.header);
                         }
                     }
@@ -85,11 +85,13 @@ where
 
         let span = span!(Level::INFO, "node", idx);
         let _enter = span.enter();
-        let NodeHandle { node, node_exit_future: _ } = NodeBuilder::new(node_config.clone())
+        // the launch future is boxed, so tests that run the node on their own thread don't
+        // overflow its stack in debug builds
+        let launch = NodeBuilder::new(node_config.clone())
             .testing_node(exec.clone())
             .node(Default::default())
-            .launch()
-            .await?;
+            .launch();
+        let NodeHandle { node, node_exit_future: _ } = Box::pin(launch).await?;
 
         let mut node = NodeTestContext::new(node).await?;
 
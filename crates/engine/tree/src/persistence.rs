@@ -7,6 +7,7 @@ use reth_errors::ProviderError;
 use reth_primitives::B256;
 use reth_provider::{writer::UnifiedStorageWriter, ProviderFactory, StaticFileProviderFactory};
 use reth_prune::{Pruner, PrunerError, PrunerOutput};
+use reth_tasks::TaskPriority;
 use std::{
     sync::mpsc::{Receiver, SendError, Sender},
     time::Instant,
@@ -177,6 +178,7 @@ impl PersistenceHandle {
         std::thread::Builder::new()
             .name("Persistence Service".to_string())
             .spawn(|| {
+                TaskPriority::Consensus.apply_to_current_thread();
                 if let Err(err) = db_service.run() {
                     error!(target: "engine::persistence", ?err, "Persistence service failed");
                 }
@@ -1,5 +1,7 @@
 //! Engine tree configuration.
 
+use std::time::Duration;
+
 const DEFAULT_PERSISTENCE_THRESHOLD: u64 = 3;
 const DEFAULT_MEMORY_BLOCK_BUFFER_TARGET: u64 = 2;
 const DEFAULT_BLOCK_BUFFER_LIMIT: u32 = 256;
@@ -7,6 +9,27 @@ const DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH: u32 = 256;
 
 const DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE: usize = 4;
 
+const DEFAULT_NEW_PAYLOAD_BUDGET: Duration = Duration::from_secs(1);
+const DEFAULT_FORKCHOICE_UPDATED_BUDGET: Duration = Duration::from_millis(500);
+
+const DEFAULT_MAX_PARALLEL_VALIDATIONS: usize = 4;
+const DEFAULT_PARALLEL_VALIDATION_MEMORY_BUDGET: usize = 1024 * 1024 * 1024;
+
+/// When canonical blocks kept in memory are persisted to the database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PersistencePolicy {
+    /// Persist blocks once the canonical chain is more than
+    /// [`TreeConfig::persistence_threshold`] blocks ahead of the last persisted block, keeping the
+    /// [`TreeConfig::memory_block_buffer_target`] most recent blocks in memory.
+    #[default]
+    Depth,
+    /// Persist blocks once they are finalized, so that reorgs never have to unwind the database.
+    ///
+    /// If finalization stalls, blocks are persisted as with [`PersistencePolicy::Depth`] to bound
+    /// the number of blocks kept in memory.
+    Finalized,
+}
+
 /// The configuration of the engine tree.
 #[derive(Debug)]
 pub struct TreeConfig {
@@ -16,6 +39,8 @@ pub struct TreeConfig {
     /// How close to the canonical head we persist blocks. Represents the ideal
     /// number of most recent blocks to keep in memory for quick access and reorgs.
     memory_block_buffer_target: u64,
+    /// When canonical blocks are persisted.
+    persistence_policy: PersistencePolicy,
     /// Number of pending blocks that cannot be executed due to missing parent and
     /// are kept in cache.
     block_buffer_limit: u32,
@@ -23,6 +48,16 @@ pub struct TreeConfig {
     max_invalid_header_cache_length: u32,
     /// Maximum number of blocks to execute sequentially in a batch.
     max_execute_block_batch_size: usize,
+    /// Processing a `newPayload` message longer than this logs a warning with the breakdown of
+    /// the time spent.
+    new_payload_budget: Duration,
+    /// Processing a `forkchoiceUpdated` message longer than this logs a warning with the breakdown
+    /// of the time spent.
+    forkchoice_updated_budget: Duration,
+    /// Maximum number of queued `newPayload` messages whose blocks are executed concurrently.
+    max_parallel_validations: usize,
+    /// Maximum estimated memory in bytes of the blocks that are executed concurrently.
+    parallel_validation_memory_budget: usize,
 }
 
 impl Default for TreeConfig {
@@ -30,9 +65,14 @@ impl Default for TreeConfig {
         Self {
             persistence_threshold: DEFAULT_PERSISTENCE_THRESHOLD,
             memory_block_buffer_target: DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
+            persistence_policy: PersistencePolicy::Depth,
             block_buffer_limit: DEFAULT_BLOCK_BUFFER_LIMIT,
             max_invalid_header_cache_length: DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH,
             max_execute_block_batch_size: DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE,
+            new_payload_budget: DEFAULT_NEW_PAYLOAD_BUDGET,
+            forkchoice_updated_budget: DEFAULT_FORKCHOICE_UPDATED_BUDGET,
+            max_parallel_validations: DEFAULT_MAX_PARALLEL_VALIDATIONS,
+            parallel_validation_memory_budget: DEFAULT_PARALLEL_VALIDATION_MEMORY_BUDGET,
         }
     }
 }
@@ -49,9 +89,14 @@ impl TreeConfig {
         Self {
             persistence_threshold,
             memory_block_buffer_target,
+            persistence_policy: PersistencePolicy::Depth,
             block_buffer_limit,
             max_invalid_header_cache_length,
             max_execute_block_batch_size,
+            new_payload_budget: DEFAULT_NEW_PAYLOAD_BUDGET,
+            forkchoice_updated_budget: DEFAULT_FORKCHOICE_UPDATED_BUDGET,
+            max_parallel_validations: DEFAULT_MAX_PARALLEL_VALIDATIONS,
+            parallel_validation_memory_budget: DEFAULT_PARALLEL_VALIDATION_MEMORY_BUDGET,
         }
     }
 
@@ -65,6 +110,11 @@ impl TreeConfig {
         self.memory_block_buffer_target
     }
 
+    /// Return the persistence policy.
+    pub const fn persistence_policy(&self) -> PersistencePolicy {
+        self.persistence_policy
+    }
+
     /// Return the block buffer limit.
     pub const fn block_buffer_limit(&self) -> u32 {
         self.block_buffer_limit
@@ -80,6 +130,26 @@ impl TreeConfig {
         self.max_execute_block_batch_size
     }
 
+    /// Return the latency budget of a `newPayload` message.
+    pub const fn new_payload_budget(&self) -> Duration {
+        self.new_payload_budget
+    }
+
+    /// Return the latency budget of a `forkchoiceUpdated` message.
+    pub const fn forkchoice_updated_budget(&self) -> Duration {
+        self.forkchoice_updated_budget
+    }
+
+    /// Return the maximum number of blocks that are executed concurrently.
+    pub const fn max_parallel_validations(&self) -> usize {
+        self.max_parallel_validations
+    }
+
+    /// Return the memory budget of the blocks that are executed concurrently.
+    pub const fn parallel_validation_memory_budget(&self) -> usize {
+        self.parallel_validation_memory_budget
+    }
+
     /// Setter for persistence threshold.
     pub const fn with_persistence_threshold(mut self, persistence_threshold: u64) -> Self {
         self.persistence_threshold = persistence_threshold;
@@ -95,6 +165,12 @@ impl TreeConfig {
         self
     }
 
+    /// Setter for persistence policy.
+    pub const fn with_persistence_policy(mut self, persistence_policy: PersistencePolicy) -> Self {
+        self.persistence_policy = persistence_policy;
+        self
+    }
+
     /// Setter for block buffer limit.
     pub const fn with_block_buffer_limit(mut self, block_buffer_limit: u32) -> Self {
         self.block_buffer_limit = block_buffer_limit;
@@ -118,4 +194,34 @@ impl TreeConfig {
         self.max_execute_block_batch_size = max_execute_block_batch_size;
         self
     }
+
+    /// Setter for the latency budget of a `newPayload` message.
+    pub const fn with_new_payload_budget(mut self, new_payload_budget: Duration) -> Self {
+        self.new_payload_budget = new_payload_budget;
+        self
+    }
+
+    /// Setter for the latency budget of a `forkchoiceUpdated` message.
+    pub const fn with_forkchoice_updated_budget(
+        mut self,
+        forkchoice_updated_budget: Duration,
+    ) -> Self {
+        self.forkchoice_updated_budget = forkchoice_updated_budget;
+        self
+    }
+
+    /// Setter for the maximum number of blocks that are executed concurrently.
+    pub const fn with_max_parallel_validations(mut self, max_parallel_validations: usize) -> Self {
+        self.max_parallel_validations = max_parallel_validations;
+        self
+    }
+
+    /// Setter for the memory budget of the blocks that are executed concurrently.
+    pub const fn with_parallel_validation_memory_budget(
+        mut self,
+        parallel_validation_memory_budget: usize,
+    ) -> Self {
+        self.parallel_validation_memory_budget = parallel_validation_memory_budget;
+        self
+    }
 }
@@ -0,0 +1,212 @@
+//! Latency tracking of `newPayload` and `forkchoiceUpdated` messages.
+
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
+use reth_primitives::{BlockNumHash, B256};
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+use tracing::warn;
+
+/// A stage of processing an engine API message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LatencyStage {
+    /// Decoding the payload into a block and recovering the transaction senders.
+    Decode,
+    /// Validating the block or the forkchoice state against the consensus rules.
+    Validation,
+    /// Executing the block, including the post-execution validation.
+    Execution,
+    /// Computing the state root of the block.
+    StateRoot,
+    /// Applying a new canonical chain to the in-memory state.
+    Canonicalization,
+}
+
+impl LatencyStage {
+    /// Returns the name of the stage.
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Decode => "decode",
+            Self::Validation => "validation",
+            Self::Execution => "execution",
+            Self::StateRoot => "state_root",
+            Self::Canonicalization => "canonicalization",
+        }
+    }
+}
+
+/// Tracks how long the stages of the engine API message that is currently processed take.
+///
+/// The durations are recorded as metrics once the message is processed, and a warning with the
+/// breakdown is logged if the message took longer than its budget.
+#[derive(Debug)]
+pub(crate) struct LatencyTracker {
+    /// The budget of a `newPayload` message.
+    new_payload_budget: Duration,
+    /// The budget of a `forkchoiceUpdated` message.
+    forkchoice_updated_budget: Duration,
+    /// When processing of the current message started.
+    start: Instant,
+    /// The durations of the stages of the current message, in the order they were first entered.
+    stages: Vec<(LatencyStage, Duration)>,
+    metrics: LatencyMetrics,
+}
+
+impl LatencyTracker {
+    /// Creates a new tracker with the given budgets.
+    pub(crate) fn new(new_payload_budget: Duration, forkchoice_updated_budget: Duration) -> Self {
+        Self {
+            new_payload_budget,
+            forkchoice_updated_budget,
+            start: Instant::now(),
+            stages: Vec::new(),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Starts tracking a new message.
+    pub(crate) fn start(&mut self) {
+        self.start = Instant::now();
+        self.stages.clear();
+    }
+
+    /// Adds the time since `start` to the duration of the given stage of the current message.
+    pub(crate) fn record(&mut self, stage: LatencyStage, start: Instant) {
+        self.record_duration(stage, start.elapsed())
+    }
+
+    /// Adds `elapsed` to the duration of the given stage of the current message.
+    pub(crate) fn record_duration(&mut self, stage: LatencyStage, elapsed: Duration) {
+        match self.stages.iter_mut().find(|(s, _)| *s == stage) {
+            Some((_, duration)) => *duration += elapsed,
+            None => self.stages.push((stage, elapsed)),
+        }
+    }
+
+    /// Finishes tracking a `newPayload` message for the given block.
+    pub(crate) fn finish_new_payload(&self, block: BlockNumHash) {
+        let total = self.finish();
+        self.metrics.new_payload.record(total);
+        if total > self.new_payload_budget {
+            self.metrics.new_payload_over_budget.increment(1);
+            warn!(
+                target: "engine::latency",
+                number = block.number,
+                hash = %block.hash,
+                ?total,
+                budget = ?self.new_payload_budget,
+                stages = %StageDurations(&self.stages),
+                "newPayload exceeded its latency budget"
+            );
+        }
+    }
+
+    /// Finishes tracking a `forkchoiceUpdated` message for the given head.
+    pub(crate) fn finish_forkchoice_updated(&self, head: B256) {
+        let total = self.finish();
+        self.metrics.forkchoice_updated.record(total);
+        if total > self.forkchoice_updated_budget {
+            self.metrics.forkchoice_updated_over_budget.increment(1);
+            warn!(
+                target: "engine::latency",
+                %head,
+                ?total,
+                budget = ?self.forkchoice_updated_budget,
+                stages = %StageDurations(&self.stages),
+                "forkchoiceUpdated exceeded its latency budget"
+            );
+        }
+    }
+
+    /// Records the stages of the current message and returns its total duration.
+    fn finish(&self) -> Duration {
+        for (stage, duration) in &self.stages {
+            self.metrics.record(*stage, *duration);
+        }
+        self.start.elapsed()
+    }
+}
+
+/// Formats stage durations as `decode=1.2ms execution=80ms ...`.
+struct StageDurations<'a>(&'a [(LatencyStage, Duration)]);
+
+impl fmt::Display for StageDurations<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (stage, duration)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}={duration:?}", stage.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// Latency metrics of the engine API messages.
+#[derive(Metrics)]
+#[metrics(scope = "consensus.engine.latency")]
+struct LatencyMetrics {
+    /// Histogram of the total `newPayload` processing time
+    new_payload: Histogram,
+    /// Histogram of the total `forkchoiceUpdated` processing time
+    forkchoice_updated: Histogram,
+    /// The number of `newPayload` messages that exceeded their budget
+    new_payload_over_budget: Counter,
+    /// The number of `forkchoiceUpdated` messages that exceeded their budget
+    forkchoice_updated_over_budget: Counter,
+    /// Histogram of the time spent decoding payloads and recovering senders
+    decode: Histogram,
+    /// Histogram of the time spent validating blocks and forkchoice states
+    validation: Histogram,
+    /// Histogram of the time spent executing blocks
+    execution: Histogram,
+    /// Histogram of the time spent computing state roots
+    state_root: Histogram,
+    /// Histogram of the time spent applying new canonical chains
+    canonicalization: Histogram,
+}
+
+impl LatencyMetrics {
+    /// Records the duration of a stage.
+    fn record(&self, stage: LatencyStage, duration: Duration) {
+        match stage {
+            LatencyStage::Decode => self.decode.record(duration),
+            LatencyStage::Validation => self.validation.record(duration),
+            LatencyStage::Execution => self.execution.record(duration),
+            LatencyStage::StateRoot => self.state_root.record(duration),
+            LatencyStage::Canonicalization => self.canonicalization.record(duration),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_accumulated_stages() {
+        let mut tracker = LatencyTracker::new(Duration::from_secs(1), Duration::from_secs(1));
+        tracker.start();
+        let start = Instant::now();
+        tracker.record(LatencyStage::Decode, start);
+        tracker.record(LatencyStage::Execution, start);
+        tracker.record(LatencyStage::Decode, start);
+        assert_eq!(
+            tracker.stages.iter().map(|(stage, _)| *stage).collect::<Vec<_>>(),
+            [LatencyStage::Decode, LatencyStage::Execution]
+        );
+
+        tracker.stages = vec![
+            (LatencyStage::Decode, Duration::from_millis(2)),
+            (LatencyStage::StateRoot, Duration::from_millis(150)),
+        ];
+        assert_eq!(StageDurations(&tracker.stages).to_string(), "decode=2ms state_root=150ms");
+
+        tracker.start();
+        assert!(tracker.stages.is_empty());
+    }
+}
@@ -17,5 +17,8 @@ pub(crate) struct EngineApiMetrics {
     pub(crate) new_payload_messages: Counter,
     /// Histogram of persistence operation durations (in seconds)
     pub(crate) persistence_duration: Histogram,
-    // TODO add latency metrics
+    /// Histogram of the number of blocks written by a persistence operation
+    pub(crate) persisted_blocks: Histogram,
+    /// How many canonical blocks are kept in memory and not persisted yet.
+    pub(crate) unpersisted_blocks: Gauge,
 }
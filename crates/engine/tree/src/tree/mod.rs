@@ -4,9 +4,10 @@ use crate::{
     engine::{DownloadRequest, EngineApiEvent, FromEngine},
     persistence::PersistenceHandle,
 };
+use rayon::prelude::*;
 use reth_beacon_consensus::{
-    BeaconConsensusEngineEvent, BeaconEngineMessage, ForkchoiceStateTracker, InvalidHeaderCache,
-    OnForkChoiceUpdated, MIN_BLOCKS_FOR_PIPELINE_RUN,
+    BeaconConsensusEngineEvent, BeaconEngineMessage, BeaconOnNewPayloadError,
+    ForkchoiceStateTracker, InvalidHeaderCache, OnForkChoiceUpdated, MIN_BLOCKS_FOR_PIPELINE_RUN,
 };
 use reth_blockchain_tree::{
     error::{InsertBlockErrorKindTwo, InsertBlockErrorTwo, InsertBlockFatalError},
@@ -18,13 +19,13 @@ use reth_chain_state::{
 use reth_consensus::{Consensus, PostExecutionInput};
 use reth_engine_primitives::EngineTypes;
 use reth_errors::{ConsensusError, ProviderResult};
-use reth_evm::execute::{BlockExecutorProvider, Executor};
+use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{PayloadAttributes, PayloadBuilderAttributes};
 use reth_payload_validator::ExecutionPayloadValidator;
 use reth_primitives::{
-    Block, BlockNumHash, BlockNumber, GotExpected, Header, Receipts, Requests, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, B256, U256,
+    Block, BlockNumHash, BlockNumber, GotExpected, Header, Receipt, Receipts, Requests,
+    SealedBlock, SealedBlockWithSenders, SealedHeader, B256, U256,
 };
 use reth_provider::{
     BlockReader, ExecutionOutcome, ProviderError, StateProviderBox, StateProviderFactory,
@@ -39,15 +40,17 @@ use reth_rpc_types::{
     ExecutionPayload,
 };
 use reth_stages_api::ControlFlow;
-use reth_trie::HashedPostState;
+use reth_tasks::TaskPriority;
+use reth_trie::{updates::TrieUpdates, HashedPostState};
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
     ops::Bound,
+    panic::{self, AssertUnwindSafe},
     sync::{
         mpsc::{Receiver, RecvError, RecvTimeoutError, Sender},
         Arc,
     },
-    time::Instant,
+    time::{Duration, Instant},
 };
 use tokio::sync::{
     mpsc::{UnboundedReceiver, UnboundedSender},
@@ -57,9 +60,16 @@ use tokio::sync::{
 use tracing::*;
 
 mod config;
+mod latency;
 mod metrics;
-use crate::{engine::EngineApiRequest, tree::metrics::EngineApiMetrics};
-pub use config::TreeConfig;
+use crate::{
+    engine::EngineApiRequest,
+    tree::{
+        latency::{LatencyStage, LatencyTracker},
+        metrics::EngineApiMetrics,
+    },
+};
+pub use config::{PersistencePolicy, TreeConfig};
 
 /// Keeps track of the state of the tree.
 ///
@@ -413,6 +423,15 @@ pub struct EngineApiTreeHandler<P, E, T: EngineTypes> {
     config: TreeConfig,
     /// Metrics for the engine api.
     metrics: EngineApiMetrics,
+    /// Tracks the latency of the engine API messages.
+    latency: LatencyTracker,
+    /// A message that was received while collecting queued `newPayload` messages and is handled
+    /// next.
+    queued: Option<FromEngine<EngineApiRequest<T>>>,
+    /// Blocks of the current batch of `newPayload` messages that were executed in parallel.
+    pre_executed: HashMap<B256, PreExecutedBlock>,
+    /// The thread pool the blocks of independent queued `newPayload` messages are executed on.
+    validation_pool: rayon::ThreadPool,
 }
 
 impl<P, E, T> EngineApiTreeHandler<P, E, T>
@@ -437,6 +456,14 @@ where
         config: TreeConfig,
     ) -> Self {
         let (incoming_tx, incoming) = std::sync::mpsc::channel();
+        let latency =
+            LatencyTracker::new(config.new_payload_budget(), config.forkchoice_updated_budget());
+        let validation_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(config.max_parallel_validations().max(1))
+            .thread_name(|i| format!("reth-validation-{i}"))
+            .start_handler(|_| TaskPriority::Consensus.apply_to_current_thread())
+            .build()
+            .expect("failed to create validation thread pool");
         Self {
             provider,
             executor_provider,
@@ -452,6 +479,10 @@ where
             payload_builder,
             config,
             metrics: Default::default(),
+            latency,
+            queued: None,
+            pre_executed: HashMap::new(),
+            validation_pool,
             incoming_tx,
         }
     }
@@ -502,7 +533,13 @@ where
             config,
         );
         let incoming = task.incoming_tx.clone();
-        std::thread::Builder::new().name("Tree Task".to_string()).spawn(|| task.run()).unwrap();
+        std::thread::Builder::new()
+            .name("Tree Task".to_string())
+            .spawn(|| {
+                TaskPriority::Consensus.apply_to_current_thread();
+                task.run()
+            })
+            .unwrap();
         (incoming, outgoing)
     }
 
@@ -615,10 +652,11 @@ where
         //
         // This validation **MUST** be instantly run in all cases even during active sync process.
         let parent_hash = payload.parent_hash();
-        let block = match self
-            .payload_validator
-            .ensure_well_formed_payload(payload, cancun_fields.into())
-        {
+        let decode_start = Instant::now();
+        let block =
+            self.payload_validator.ensure_well_formed_payload(payload, cancun_fields.into());
+        self.latency.record(LatencyStage::Decode, decode_start);
+        let block = match block {
             Ok(block) => block,
             Err(error) => {
                 error!(target: "engine::tree", %error, "Invalid payload");
@@ -696,6 +734,158 @@ where
         Ok(outcome)
     }
 
+    /// Handles a `newPayload` message and sends the outcome to the consensus layer.
+    fn on_new_payload_message(
+        &mut self,
+        payload: ExecutionPayload,
+        cancun_fields: Option<CancunPayloadFields>,
+        tx: oneshot::Sender<Result<PayloadStatus, BeaconOnNewPayloadError>>,
+    ) {
+        let block = BlockNumHash::new(payload.block_number(), payload.block_hash());
+        self.latency.start();
+        let output = self.on_new_payload(payload, cancun_fields);
+        self.latency.finish_new_payload(block);
+        if let Err(err) = tx.send(
+            output.map(|o| o.outcome).map_err(|e| BeaconOnNewPayloadError::Internal(Box::new(e))),
+        ) {
+            error!("Failed to send event: {err:?}");
+        }
+    }
+
+    /// Moves the `newPayload` messages that are queued behind the given ones into the batch, up to
+    /// the configured number of parallel validations.
+    ///
+    /// Stops at the first message of another kind, which is handled after the batch.
+    fn collect_queued_payloads(&mut self, payloads: &mut Vec<NewPayloadMessage>) {
+        while payloads.len() < self.config.max_parallel_validations() && self.queued.is_none() {
+            match self.incoming.try_recv() {
+                Ok(FromEngine::Request(EngineApiRequest::Beacon(
+                    BeaconEngineMessage::NewPayload { payload, cancun_fields, tx },
+                ))) => payloads.push((payload, cancun_fields, tx)),
+                Ok(msg) => self.queued = Some(msg),
+                Err(_) => break,
+            }
+        }
+    }
+
+    /// Executes the blocks of the given payloads that don't depend on each other concurrently and
+    /// keeps the results, so that inserting the blocks afterwards skips the execution and the
+    /// state root computation.
+    ///
+    /// A payload that builds on another payload of the batch, or whose parent state is unknown, is
+    /// left to the regular insertion. Blocks are executed in chunks of at most
+    /// [`TreeConfig::max_parallel_validations`] blocks whose estimated memory stays within
+    /// [`TreeConfig::parallel_validation_memory_budget`] on the validation thread pool. If the
+    /// execution of a block panics, the panic is logged and the block is left to the regular
+    /// insertion as well.
+    fn pre_execute_payloads(&mut self, payloads: &[NewPayloadMessage]) {
+        if payloads.len() < 2 || !self.backfill_sync_state.is_idle() {
+            return
+        }
+
+        let blocks = payloads
+            .iter()
+            .filter_map(|(payload, cancun_fields, _)| {
+                self.payload_validator
+                    .ensure_well_formed_payload(payload.clone(), cancun_fields.clone().into())
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+        let hashes = blocks.iter().map(|block| block.hash()).collect::<HashSet<_>>();
+
+        let mut jobs = Vec::new();
+        for block in blocks {
+            if hashes.contains(&block.parent_hash) ||
+                self.state.tree_state.block_by_hash(block.hash()).is_some()
+            {
+                continue
+            }
+            let Ok(Some(state_provider)) = self.state_provider(block.parent_hash) else { continue };
+            jobs.push((block, state_provider));
+        }
+        if jobs.len() < 2 {
+            return
+        }
+
+        let start = Instant::now();
+        let count = jobs.len();
+        let mut jobs = jobs.into_iter().peekable();
+        while jobs.peek().is_some() {
+            let mut chunk = Vec::new();
+            let mut memory = 0;
+            while let Some((block, _)) = jobs.peek() {
+                let estimate = estimated_validation_memory(block);
+                if !chunk.is_empty() &&
+                    (chunk.len() >= self.config.max_parallel_validations() ||
+                        memory + estimate > self.config.parallel_validation_memory_budget())
+                {
+                    break
+                }
+                memory += estimate;
+                chunk.extend(jobs.next());
+            }
+
+            let executor_provider = &self.executor_provider;
+            let executed: Vec<_> = self.validation_pool.install(|| {
+                chunk
+                    .into_par_iter()
+                    .filter_map(|(block, state_provider)| {
+                        let hash = block.hash();
+                        panic::catch_unwind(AssertUnwindSafe(|| {
+                            Self::pre_execute_block(executor_provider, block, state_provider)
+                        }))
+                        .unwrap_or_else(|_| {
+                            error!(target: "engine", %hash, "Executing block in parallel panicked");
+                            None
+                        })
+                    })
+                    .collect()
+            });
+            for block in executed {
+                self.pre_executed.insert(block.block.hash(), block);
+            }
+        }
+        debug!(target: "engine", count, elapsed = ?start.elapsed(), "Executed payloads in parallel");
+    }
+
+    /// Executes the block on top of the given parent state and computes its state root.
+    ///
+    /// Returns `None` if this fails, in which case the regular insertion reports the error.
+    fn pre_execute_block(
+        executor_provider: &E,
+        block: SealedBlock,
+        state_provider: StateProviderBox,
+    ) -> Option<PreExecutedBlock> {
+        let block = block.try_seal_with_senders().ok()?;
+        let block_number = block.number;
+
+        let start = Instant::now();
+        let executor = executor_provider.executor(StateProviderDatabase::new(&state_provider));
+        let output = match executor.execute((&block.clone().unseal(), U256::MAX).into()) {
+            Ok(output) => output,
+            Err(err) => {
+                debug!(target: "engine", %err, ?block_number, "Failed to execute block in parallel");
+                return None
+            }
+        };
+        let execution_elapsed = start.elapsed();
+
+        let start = Instant::now();
+        let hashed_state = HashedPostState::from_bundle_state(&output.state.state);
+        let (state_root, trie_output) =
+            state_provider.hashed_state_root_with_updates(hashed_state.clone()).ok()?;
+
+        Some(PreExecutedBlock {
+            block,
+            output,
+            hashed_state,
+            state_root,
+            trie_output,
+            execution_elapsed,
+            state_root_elapsed: start.elapsed(),
+        })
+    }
+
     /// Invoked when we receive a new forkchoice update message. Calls into the blockchain tree
     /// to resolve chain forks and ensure that the Execution Layer is working with the latest valid
     /// chain.
@@ -714,7 +904,10 @@ where
         self.metrics.forkchoice_updated_messages.increment(1);
         self.canonical_in_memory_state.on_forkchoice_update_received();
 
-        if let Some(on_updated) = self.pre_validate_forkchoice_update(state)? {
+        let validation_start = Instant::now();
+        let pre_validated = self.pre_validate_forkchoice_update(state)?;
+        self.latency.record(LatencyStage::Validation, validation_start);
+        if let Some(on_updated) = pre_validated {
             return Ok(TreeOutcome::new(on_updated))
         }
 
@@ -839,8 +1032,11 @@ where
     ///
     /// Returns an error if the engine channel is disconnected.
     fn try_recv_engine_message(
-        &self,
+        &mut self,
     ) -> Result<Option<FromEngine<EngineApiRequest<T>>>, RecvError> {
+        if let Some(msg) = self.queued.take() {
+            return Ok(Some(msg))
+        }
         if self.persistence_state.in_progress() {
             // try to receive the next request with a timeout to not block indefinitely
             match self.incoming.recv_timeout(std::time::Duration::from_millis(500)) {
@@ -860,9 +1056,18 @@ where
     /// If we're currently awaiting a response this will try to receive the response (non-blocking)
     /// or send a new persistence action if necessary.
     fn advance_persistence(&mut self) -> Result<(), TryRecvError> {
+        self.metrics.unpersisted_blocks.set(
+            self.state
+                .tree_state
+                .canonical_block_number()
+                .saturating_sub(self.persistence_state.last_persisted_block_number)
+                as f64,
+        );
+
         if self.should_persist() && !self.persistence_state.in_progress() {
             let blocks_to_persist = self.get_canonical_blocks_to_persist();
             if !blocks_to_persist.is_empty() {
+                self.metrics.persisted_blocks.record(blocks_to_persist.len() as f64);
                 let (tx, rx) = oneshot::channel();
                 let _ = self.persistence.save_blocks(blocks_to_persist, tx);
                 self.persistence_state.start(rx);
@@ -923,6 +1128,7 @@ where
                     EngineApiRequest::Beacon(request) => {
                         match request {
                             BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx } => {
+                                self.latency.start();
                                 let mut output = self.on_forkchoice_updated(state, payload_attrs);
 
                                 if let Ok(res) = &mut output {
@@ -940,6 +1146,7 @@ where
                                     // handle the event if any
                                     self.on_maybe_tree_event(res.event.take());
                                 }
+                                self.latency.finish_forkchoice_updated(state.head_block_hash);
 
                                 if let Err(err) =
                                     tx.send(output.map(|o| o.outcome).map_err(Into::into))
@@ -948,14 +1155,13 @@ where
                                 }
                             }
                             BeaconEngineMessage::NewPayload { payload, cancun_fields, tx } => {
-                                let output = self.on_new_payload(payload, cancun_fields);
-                                if let Err(err) = tx.send(output.map(|o| o.outcome).map_err(|e| {
-                                    reth_beacon_consensus::BeaconOnNewPayloadError::Internal(
-                                        Box::new(e),
-                                    )
-                                })) {
-                                    error!("Failed to send event: {err:?}");
+                                let mut payloads = vec![(payload, cancun_fields, tx)];
+                                self.collect_queued_payloads(&mut payloads);
+                                self.pre_execute_payloads(&payloads);
+                                for (payload, cancun_fields, tx) in payloads {
+                                    self.on_new_payload_message(payload, cancun_fields, tx);
                                 }
+                                self.pre_executed.clear();
                             }
                             BeaconEngineMessage::TransitionConfigurationExchanged => {
                                 // triggering this hook will record that we received a request from
@@ -1123,32 +1329,65 @@ where
             .inspect_err(|err| error!("Failed to send internal event: {err:?}"));
     }
 
-    /// Returns true if the canonical chain length minus the last persisted
-    /// block is greater than or equal to the persistence threshold and
-    /// backfill is not running.
-    const fn should_persist(&self) -> bool {
+    /// Returns true if there are canonical blocks to persist according to the
+    /// [`PersistencePolicy`] and backfill is not running.
+    ///
+    /// With [`PersistencePolicy::Depth`] this is the case if the canonical chain length minus the
+    /// last persisted block is greater than the persistence threshold.
+    fn should_persist(&self) -> bool {
         if !self.backfill_sync_state.is_idle() {
             // can't persist if backfill is running
             return false
         }
 
         let min_block = self.persistence_state.last_persisted_block_number;
-        self.state.tree_state.canonical_block_number().saturating_sub(min_block) >
-            self.config.persistence_threshold()
+        match self.config.persistence_policy() {
+            PersistencePolicy::Depth => {
+                self.state.tree_state.canonical_block_number().saturating_sub(min_block) >
+                    self.config.persistence_threshold()
+            }
+            PersistencePolicy::Finalized => self.persistence_target() > min_block,
+        }
+    }
+
+    /// Returns the number of the newest canonical block that should be persisted.
+    ///
+    /// With [`PersistencePolicy::Finalized`] this is the finalized block, unless more than
+    /// persistence threshold blocks are not persisted yet, in which case blocks are persisted up to
+    /// the memory block buffer target as with [`PersistencePolicy::Depth`].
+    fn persistence_target(&self) -> u64 {
+        let canonical_head_number = self.state.tree_state.canonical_block_number();
+        let depth_target =
+            canonical_head_number.saturating_sub(self.config.memory_block_buffer_target());
+
+        match self.config.persistence_policy() {
+            PersistencePolicy::Depth => depth_target,
+            PersistencePolicy::Finalized => {
+                let finalized = self
+                    .canonical_in_memory_state
+                    .get_finalized_num_hash()
+                    .map_or(0, |finalized| finalized.number)
+                    .min(canonical_head_number);
+                let unpersisted = canonical_head_number
+                    .saturating_sub(self.persistence_state.last_persisted_block_number);
+                if unpersisted > self.config.persistence_threshold() {
+                    finalized.max(depth_target)
+                } else {
+                    finalized
+                }
+            }
+        }
     }
 
     /// Returns a batch of consecutive canonical blocks to persist in the range
-    /// `(last_persisted_number .. canonical_head - threshold]` . The expected
+    /// `(last_persisted_number .. persistence_target]` . The expected
     /// order is oldest -> newest.
     fn get_canonical_blocks_to_persist(&self) -> Vec<ExecutedBlock> {
         let mut blocks_to_persist = Vec::new();
         let mut current_hash = self.state.tree_state.canonical_block_hash();
         let last_persisted_number = self.persistence_state.last_persisted_block_number;
 
-        let canonical_head_number = self.state.tree_state.canonical_block_number();
-
-        let target_number =
-            canonical_head_number.saturating_sub(self.config.memory_block_buffer_target());
+        let target_number = self.persistence_target();
 
         while let Some(block) = self.state.tree_state.blocks_by_hash.get(&current_hash) {
             if block.block.number <= last_persisted_number {
@@ -1567,6 +1806,7 @@ where
             Box::new(tip),
             start.elapsed(),
         ));
+        self.latency.record(LatencyStage::Canonicalization, start);
     }
 
     /// This handles downloaded blocks that are shown to be disconnected from the canonical chain.
@@ -1671,7 +1911,16 @@ where
         &mut self,
         block: SealedBlock,
     ) -> Result<InsertPayloadOk2, InsertBlockErrorTwo> {
-        match block.try_seal_with_senders() {
+        if let Some(pre_executed) = self.pre_executed.get(&block.hash()) {
+            // the senders were recovered while executing the block in parallel
+            let block = pre_executed.block.clone();
+            return self.insert_block(block)
+        }
+
+        let recovery_start = Instant::now();
+        let block = block.try_seal_with_senders();
+        self.latency.record(LatencyStage::Decode, recovery_start);
+        match block {
             Ok(block) => self.insert_block(block),
             Err(block) => Err(InsertBlockErrorTwo::sender_recovery_error(block)),
         }
@@ -1726,28 +1975,59 @@ where
             warn!(?block, "Failed to validate header {} against parent: {e}", block.header.hash());
             return Err(e.into())
         }
-
-        let executor = self.executor_provider.executor(StateProviderDatabase::new(&state_provider));
+        self.latency.record(LatencyStage::Validation, start);
 
         let block_number = block.number;
         let block_hash = block.hash();
         let sealed_block = Arc::new(block.block.clone());
         let block = block.unseal();
 
+        // the block may have been executed in parallel with other queued payloads
+        let pre_executed = self.pre_executed.remove(&block_hash);
+
         let exec_time = Instant::now();
-        let output = executor.execute((&block, U256::MAX).into())?;
-        debug!(target: "engine", elapsed=?exec_time.elapsed(), ?block_number, "Executed block");
+        let (output, pre_computed_root) = if let Some(pre_executed) = pre_executed {
+            debug!(target: "engine", elapsed=?pre_executed.execution_elapsed, ?block_number, "Executed block in parallel");
+            self.latency.record_duration(LatencyStage::Execution, pre_executed.execution_elapsed);
+            let PreExecutedBlock {
+                output,
+                hashed_state,
+                state_root,
+                trie_output,
+                state_root_elapsed,
+                ..
+            } = pre_executed;
+            (output, Some((hashed_state, state_root, trie_output, state_root_elapsed)))
+        } else {
+            let executor =
+                self.executor_provider.executor(StateProviderDatabase::new(&state_provider));
+            let output = trace_span!(target: "engine", "execute", ?block_number)
+                .in_scope(|| executor.execute((&block, U256::MAX).into()))?;
+            debug!(target: "engine", elapsed=?exec_time.elapsed(), ?block_number, "Executed block");
+            (output, None)
+        };
 
         self.consensus.validate_block_post_execution(
             &block,
             PostExecutionInput::new(&output.receipts, &output.requests),
         )?;
-
-        let hashed_state = HashedPostState::from_bundle_state(&output.state.state);
+        self.latency.record(LatencyStage::Execution, exec_time);
 
         let root_time = Instant::now();
-        let (state_root, trie_output) =
-            state_provider.hashed_state_root_with_updates(hashed_state.clone())?;
+        let (hashed_state, state_root, trie_output) = match pre_computed_root {
+            Some((hashed_state, state_root, trie_output, elapsed)) => {
+                self.latency.record_duration(LatencyStage::StateRoot, elapsed);
+                (hashed_state, state_root, trie_output)
+            }
+            None => {
+                let hashed_state = HashedPostState::from_bundle_state(&output.state.state);
+                let (state_root, trie_output) =
+                    trace_span!(target: "engine", "state_root", ?block_number).in_scope(|| {
+                        state_provider.hashed_state_root_with_updates(hashed_state.clone())
+                    })?;
+                (hashed_state, state_root, trie_output)
+            }
+        };
         if state_root != block.state_root {
             return Err(ConsensusError::BodyStateRootDiff(
                 GotExpected { got: state_root, expected: block.state_root }.into(),
@@ -1756,6 +2036,7 @@ where
         }
 
         debug!(target: "engine", elapsed=?root_time.elapsed(), ?block_number, "Calculated state root");
+        self.latency.record(LatencyStage::StateRoot, root_time);
 
         let executed = ExecutedBlock {
             block: sealed_block.clone(),
@@ -1817,6 +2098,10 @@ where
         };
 
         // keep track of the invalid header
+        self.emit_event(BeaconConsensusEngineEvent::InvalidBlock(
+            Box::new(block.header.clone()),
+            validation_err.to_string(),
+        ));
         self.state.invalid_headers.insert(block.header);
         Ok(PayloadStatus::new(
             PayloadStatusEnum::Invalid { validation_error: validation_err.to_string() },
@@ -1993,6 +2278,39 @@ where
     }
 }
 
+/// The payload, the cancun fields and the response channel of a `newPayload` message.
+type NewPayloadMessage = (
+    ExecutionPayload,
+    Option<CancunPayloadFields>,
+    oneshot::Sender<Result<PayloadStatus, BeaconOnNewPayloadError>>,
+);
+
+/// A block that was executed in parallel with other queued payloads, see
+/// [`EngineApiTreeHandler::pre_execute_payloads`].
+#[derive(Debug)]
+struct PreExecutedBlock {
+    /// The block with the recovered senders.
+    block: SealedBlockWithSenders,
+    /// The output of executing the block.
+    output: BlockExecutionOutput<Receipt>,
+    /// The hashed state changes of the block.
+    hashed_state: HashedPostState,
+    /// The computed state root.
+    state_root: B256,
+    /// The trie updates of the block.
+    trie_output: TrieUpdates,
+    /// How long the execution took.
+    execution_elapsed: Duration,
+    /// How long the state root computation took.
+    state_root_elapsed: Duration,
+}
+
+/// Estimates the memory needed to execute the block in parallel with others: its size plus one
+/// byte per unit of gas used, as a rough bound of the state changes and trie updates it produces.
+fn estimated_validation_memory(block: &SealedBlock) -> usize {
+    block.size() + block.gas_used as usize
+}
+
 /// The state of the persistence task.
 #[derive(Default, Debug)]
 pub struct PersistenceState {
@@ -2035,6 +2353,7 @@ mod tests {
     use super::*;
     use crate::persistence::PersistenceAction;
     use alloy_rlp::Decodable;
+    use assert_matches::assert_matches;
     use reth_beacon_consensus::{EthBeaconConsensus, ForkchoiceStatus};
     use reth_chain_state::{test_utils::TestBlockBuilder, BlockState};
     use reth_chainspec::{ChainSpec, HOLESKY, MAINNET};
@@ -2814,6 +3133,38 @@ mod tests {
             .any(|b| b.block.number == 4 && b.block.hash() == blocks[4].block.hash()));
     }
 
+    #[tokio::test]
+    async fn test_get_canonical_blocks_to_persist_finalized() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let mut test_block_builder = TestBlockBuilder::default();
+
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(0..10).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+        test_harness.tree.persistence_state.last_persisted_block_number = 3;
+        test_harness.tree.config = TreeConfig::default()
+            .with_persistence_threshold(10)
+            .with_memory_block_buffer_target(2)
+            .with_persistence_policy(PersistencePolicy::Finalized);
+
+        // nothing is persisted without a finalized block
+        assert!(!test_harness.tree.should_persist());
+
+        // blocks are persisted up to the finalized block
+        test_harness.tree.canonical_in_memory_state.set_finalized(blocks[6].block.header.clone());
+        assert!(test_harness.tree.should_persist());
+        let blocks_to_persist = test_harness.tree.get_canonical_blocks_to_persist();
+        assert_eq!(blocks_to_persist.iter().map(|b| b.block.number).collect::<Vec<_>>(), [4, 5, 6]);
+
+        // too many blocks not persisted yet, persist up to the memory block buffer target
+        test_harness.tree.config = TreeConfig::default()
+            .with_persistence_threshold(4)
+            .with_memory_block_buffer_target(2)
+            .with_persistence_policy(PersistencePolicy::Finalized);
+        let blocks_to_persist = test_harness.tree.get_canonical_blocks_to_persist();
+        assert_eq!(blocks_to_persist.last().unwrap().block.number, 7);
+    }
+
     #[tokio::test]
     async fn test_engine_tree_fcu_missing_head() {
         let chain_spec = MAINNET.clone();
@@ -3162,6 +3513,145 @@ mod tests {
         assert!(test_harness.tree.state.tree_state.is_fork(chain_a.last().unwrap().hash()));
     }
 
+    #[tokio::test]
+    async fn test_engine_tree_pre_executes_independent_payloads() {
+        let chain_spec = MAINNET.clone();
+        let mut block_builder = TestBlockBuilder::default().with_chain_spec((*chain_spec).clone());
+        let base_chain: Vec<_> = block_builder.get_executed_blocks(0..1).collect();
+
+        // two competing blocks on top of the head, and a child of the first one. The competing
+        // blocks have the same number of transactions and therefore the same state root, so that
+        // the results of the mocked executor and state root don't depend on the execution order.
+        let parent = base_chain.last().unwrap().block();
+        let fork_a = block_builder.create_fork(parent, 2);
+        let fork_b = loop {
+            let fork_b = block_builder.create_fork(parent, 1);
+            if fork_b[0].body.len() == fork_a[0].body.len() {
+                break fork_b
+            }
+        };
+        let blocks = [fork_a[0].clone(), fork_b[0].clone(), fork_a[1].clone()];
+        let sibling_outcome = block_builder.get_execution_outcome(fork_a[0].clone());
+        let child_outcome = block_builder.get_execution_outcome(fork_a[1].clone());
+
+        let mut harnesses = Vec::new();
+        for max_parallel_validations in [TreeConfig::default().max_parallel_validations(), 1] {
+            let mut test_harness =
+                TestHarness::new(chain_spec.clone()).with_blocks(base_chain.clone());
+            test_harness.tree.config =
+                TreeConfig::default().with_max_parallel_validations(max_parallel_validations);
+            // outcomes and state roots are popped from the back
+            test_harness.extend_execution_outcome([
+                child_outcome.clone(),
+                sibling_outcome.clone(),
+                sibling_outcome.clone(),
+            ]);
+            for block in [&fork_a[1], &fork_a[0], &fork_b[0]] {
+                test_harness.tree.provider.add_state_root(block.state_root);
+            }
+
+            let mut responses = Vec::new();
+            for block in &blocks {
+                let (tx, rx) = oneshot::channel();
+                test_harness
+                    .to_tree_tx
+                    .send(FromEngine::Request(
+                        BeaconEngineMessage::NewPayload {
+                            payload: block_to_payload_v3(block.block.clone()).into(),
+                            cancun_fields: Some(CancunPayloadFields {
+                                parent_beacon_block_root: block.parent_beacon_block_root.unwrap(),
+                                versioned_hashes: vec![],
+                            }),
+                            tx,
+                        }
+                        .into(),
+                    ))
+                    .unwrap();
+                responses.push(rx);
+            }
+            let (tx, _rx) = oneshot::channel();
+            test_harness
+                .to_tree_tx
+                .send(FromEngine::Request(
+                    BeaconEngineMessage::ForkchoiceUpdated {
+                        state: test_harness.fcu_state(fork_b[0].hash()),
+                        payload_attrs: None,
+                        tx,
+                    }
+                    .into(),
+                ))
+                .unwrap();
+
+            let Some(FromEngine::Request(EngineApiRequest::Beacon(
+                BeaconEngineMessage::NewPayload { payload, cancun_fields, tx },
+            ))) = test_harness.tree.try_recv_engine_message().unwrap()
+            else {
+                panic!("expected a new payload message")
+            };
+            let mut payloads = vec![(payload, cancun_fields, tx)];
+            test_harness.tree.collect_queued_payloads(&mut payloads);
+            assert_eq!(payloads.len(), max_parallel_validations.min(blocks.len()));
+
+            // only the blocks that don't depend on another payload are executed upfront
+            test_harness.tree.pre_execute_payloads(&payloads);
+            let pre_executed =
+                test_harness.tree.pre_executed.keys().copied().collect::<HashSet<_>>();
+            if max_parallel_validations > 1 {
+                assert_eq!(pre_executed, HashSet::from([fork_a[0].hash(), fork_b[0].hash()]));
+                // the forkchoice update stays queued behind the payloads
+                assert_matches!(
+                    test_harness.tree.queued,
+                    Some(FromEngine::Request(EngineApiRequest::Beacon(
+                        BeaconEngineMessage::ForkchoiceUpdated { .. }
+                    )))
+                );
+            } else {
+                assert!(pre_executed.is_empty());
+            }
+            for (payload, cancun_fields, tx) in payloads {
+                test_harness.tree.on_new_payload_message(payload, cancun_fields, tx);
+            }
+            test_harness.tree.pre_executed.clear();
+
+            // handle the remaining payloads of the serial run one by one
+            while let Some(msg) = test_harness.tree.try_recv_engine_message().unwrap() {
+                if matches!(
+                    msg,
+                    FromEngine::Request(EngineApiRequest::Beacon(
+                        BeaconEngineMessage::ForkchoiceUpdated { .. }
+                    ))
+                ) {
+                    break
+                }
+                test_harness.tree.on_engine_message(msg);
+            }
+
+            let mut statuses = Vec::new();
+            for rx in responses {
+                let status = rx.await.unwrap().unwrap();
+                assert!(status.is_valid());
+                statuses.push(status);
+            }
+            harnesses.push((test_harness, statuses));
+        }
+
+        // parallel validation has the same outcome as serial validation
+        let (serial, serial_statuses) = harnesses.pop().unwrap();
+        let (parallel, parallel_statuses) = harnesses.pop().unwrap();
+        assert_eq!(parallel_statuses, serial_statuses);
+        for block in &blocks {
+            assert_eq!(
+                parallel.tree.state.tree_state.blocks_by_hash.get(&block.hash()),
+                serial.tree.state.tree_state.blocks_by_hash.get(&block.hash())
+            );
+            assert!(parallel.tree.state.tree_state.blocks_by_hash.contains_key(&block.hash()));
+        }
+        assert_eq!(
+            parallel.tree.state.tree_state.canonical_block_hash(),
+            serial.tree.state.tree_state.canonical_block_hash()
+        );
+    }
+
     #[tokio::test]
     async fn test_engine_tree_buffered_blocks_are_eventually_connected() {
         let chain_spec = MAINNET.clone();
@@ -0,0 +1,95 @@
+//! Stream wrapper that fails over to a backup consensus layer client.
+
+use futures::{stream::Fuse, Stream, StreamExt};
+use reth_beacon_consensus::{BeaconEngineMessage, OnForkChoiceUpdated};
+use reth_engine_primitives::EngineTypes;
+use reth_rpc_types::engine::{PayloadStatus, PayloadStatusEnum};
+use std::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::{Duration, Instant},
+};
+
+/// Engine API stream wrapper that merges the messages of a primary and a backup consensus layer
+/// client.
+///
+/// Messages of the primary are always forwarded. Messages of the backup are only forwarded once
+/// the primary has not sent any message for the configured timeout, otherwise they are answered
+/// with `SYNCING`.
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct EngineFailover<S, B> {
+    /// Messages of the primary consensus layer client.
+    #[pin]
+    primary: S,
+    /// Messages of the backup consensus layer client.
+    #[pin]
+    backup: Fuse<B>,
+    /// How long the primary may be silent before the backup is followed.
+    timeout: Duration,
+    /// When the primary sent its last message.
+    last_primary_message: Instant,
+    /// Whether messages of the backup are currently forwarded.
+    backup_active: bool,
+}
+
+impl<S, B: Stream> EngineFailover<S, B> {
+    /// Creates new [`EngineFailover`] stream wrapper.
+    pub fn new(primary: S, backup: B, timeout: Duration) -> Self {
+        Self {
+            primary,
+            backup: backup.fuse(),
+            timeout,
+            last_primary_message: Instant::now(),
+            backup_active: false,
+        }
+    }
+}
+
+impl<S, B, Engine> Stream for EngineFailover<S, B>
+where
+    S: Stream<Item = BeaconEngineMessage<Engine>>,
+    B: Stream<Item = BeaconEngineMessage<Engine>>,
+    Engine: EngineTypes,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if let Poll::Ready(next) = this.primary.poll_next_unpin(cx) {
+            if next.is_some() {
+                *this.last_primary_message = Instant::now();
+                if *this.backup_active {
+                    *this.backup_active = false;
+                    tracing::info!(target: "engine::stream::failover", "Primary consensus layer client is back, ignoring backup");
+                }
+            }
+            return Poll::Ready(next)
+        }
+
+        loop {
+            let Some(msg) = ready!(this.backup.poll_next_unpin(cx)) else { return Poll::Pending };
+
+            if this.last_primary_message.elapsed() >= *this.timeout {
+                if !*this.backup_active {
+                    *this.backup_active = true;
+                    tracing::warn!(target: "engine::stream::failover", timeout=?this.timeout, "Primary consensus layer client is silent, following backup");
+                }
+                return Poll::Ready(Some(msg))
+            }
+
+            match msg {
+                BeaconEngineMessage::ForkchoiceUpdated { state, tx, .. } => {
+                    tracing::trace!(target: "engine::stream::failover", ?state, "Ignoring FCU of backup");
+                    let _ = tx.send(Ok(OnForkChoiceUpdated::syncing()));
+                }
+                BeaconEngineMessage::NewPayload { payload, tx, .. } => {
+                    tracing::trace!(target: "engine::stream::failover", block_hash = %payload.block_hash(), "Ignoring new payload of backup");
+                    let _ = tx.send(Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing)));
+                }
+                BeaconEngineMessage::TransitionConfigurationExchanged => {}
+            }
+        }
+    }
+}
@@ -4,7 +4,7 @@ use futures::Stream;
 use reth_beacon_consensus::BeaconEngineMessage;
 use reth_engine_primitives::EngineTypes;
 use reth_payload_validator::ExecutionPayloadValidator;
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 use tokio_util::either::Either;
 
 pub mod engine_store;
@@ -19,10 +19,41 @@ use skip_new_payload::EngineSkipNewPayload;
 pub mod reorg;
 use reorg::EngineReorg;
 
+pub mod failover;
+use failover::EngineFailover;
+
 /// The collection of stream extensions for engine API message stream.
 pub trait EngineMessageStreamExt<Engine: EngineTypes>:
     Stream<Item = BeaconEngineMessage<Engine>>
 {
+    /// Merges the messages of a backup consensus layer client, which are only forwarded once this
+    /// stream has not yielded any message for the given timeout.
+    fn failover<B>(self, backup: B, timeout: Duration) -> EngineFailover<Self, B>
+    where
+        Self: Sized,
+        B: Stream<Item = BeaconEngineMessage<Engine>>,
+    {
+        EngineFailover::new(self, backup, timeout)
+    }
+
+    /// If the backup stream is [Some], returns the stream that fails over to the backup stream.
+    /// Otherwise, returns `Self`.
+    fn maybe_failover<B>(
+        self,
+        maybe_backup: Option<B>,
+        timeout: Duration,
+    ) -> Either<EngineFailover<Self, B>, Self>
+    where
+        Self: Sized,
+        B: Stream<Item = BeaconEngineMessage<Engine>>,
+    {
+        if let Some(backup) = maybe_backup {
+            Either::Left(self.failover(backup, timeout))
+        } else {
+            Either::Right(self)
+        }
+    }
+
     /// Skips the specified number of [`BeaconEngineMessage::ForkchoiceUpdated`] messages from the
     /// engine message stream.
     fn skip_fcu(self, count: usize) -> EngineSkipFcu<Self>
@@ -296,6 +296,11 @@ impl ForkFilter {
         self.set_head_priv(head)
     }
 
+    /// Returns the current head.
+    pub const fn head(&self) -> Head {
+        self.head
+    }
+
     /// Return current fork id
     #[must_use]
     pub const fn current(&self) -> ForkId {
@@ -12,6 +12,8 @@ use reth_evm::{
         BatchExecutor, BlockExecutionError, BlockExecutionInput, BlockExecutionOutput,
         BlockExecutorProvider, BlockValidationError, Executor, ProviderError,
     },
+    inspector::{BlockInspectorStack, BlockInspectors},
+    state_changes::{apply_account_changes, BlockStateChanges},
     system_calls::{
         apply_beacon_root_contract_call, apply_consolidation_requests_contract_call,
         apply_withdrawal_requests_contract_call,
@@ -25,7 +27,7 @@ use reth_primitives::{
 use reth_prune_types::PruneModes;
 use reth_revm::{
     batch::BlockBatchRecord,
-    db::states::bundle_state::BundleRetention,
+    db::{states::bundle_state::BundleRetention, BundleState},
     state_change::{apply_blockhashes_update, post_block_balance_increments},
     Evm, State,
 };
@@ -44,6 +46,10 @@ use std::sync::Arc;
 pub struct EthExecutorProvider<EvmConfig = EthEvmConfig> {
     chain_spec: Arc<ChainSpec>,
     evm_config: EvmConfig,
+    /// Inspectors that run on every executed block.
+    inspectors: BlockInspectors,
+    /// Irregular state changes that are applied to the executed blocks.
+    state_changes: Option<Arc<dyn BlockStateChanges>>,
 }
 
 impl EthExecutorProvider {
@@ -61,7 +67,19 @@ impl EthExecutorProvider {
 impl<EvmConfig> EthExecutorProvider<EvmConfig> {
     /// Creates a new executor provider.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig) -> Self {
-        Self { chain_spec, evm_config }
+        Self { chain_spec, evm_config, inspectors: BlockInspectors::new(), state_changes: None }
+    }
+
+    /// Installs the given inspectors on all executors created by this provider.
+    pub fn with_inspectors(mut self, inspectors: BlockInspectors) -> Self {
+        self.inspectors = inspectors;
+        self
+    }
+
+    /// Applies the given irregular state changes to all blocks executed by this provider.
+    pub fn with_state_changes(mut self, state_changes: Arc<dyn BlockStateChanges>) -> Self {
+        self.state_changes = Some(state_changes);
+        self
     }
 }
 
@@ -78,6 +96,8 @@ where
             self.evm_config.clone(),
             State::builder().with_database(db).with_bundle_update().without_state_clear().build(),
         )
+        .with_inspectors(self.inspectors.stack())
+        .with_state_changes(self.state_changes.clone())
     }
 }
 
@@ -122,6 +142,8 @@ struct EthEvmExecutor<EvmConfig> {
     chain_spec: Arc<ChainSpec>,
     /// How to create an EVM.
     evm_config: EvmConfig,
+    /// Irregular state changes that are applied to the executed blocks.
+    state_changes: Option<Arc<dyn BlockStateChanges>>,
 }
 
 impl<EvmConfig> EthEvmExecutor<EvmConfig>
@@ -131,8 +153,8 @@ where
     /// Executes the transactions in the block and returns the receipts of the transactions in the
     /// block, the total gas used and the list of EIP-7685 [requests](Request).
     ///
-    /// This applies the pre-execution and post-execution changes that require an [EVM](Evm), and
-    /// executes the transactions.
+    /// This applies the pre-execution and post-execution changes that require an [EVM](Evm) and the
+    /// configured [`BlockStateChanges`], and executes the transactions.
     ///
     /// # Note
     ///
@@ -141,7 +163,8 @@ where
     fn execute_state_transitions<Ext, DB>(
         &self,
         block: &BlockWithSenders,
-        mut evm: Evm<'_, Ext, &mut State<DB>>,
+        evm: &mut Evm<'_, Ext, &mut State<DB>>,
+        inspectors: impl Fn(&mut Ext) -> Option<&mut BlockInspectorStack>,
     ) -> Result<EthExecuteOutput, BlockExecutionError>
     where
         DB: Database,
@@ -154,7 +177,7 @@ where
             block.timestamp,
             block.number,
             block.parent_beacon_block_root,
-            &mut evm,
+            evm,
         )?;
         apply_blockhashes_update(
             evm.db_mut(),
@@ -163,11 +186,21 @@ where
             block.number,
             block.parent_hash,
         )?;
+        // irregular state changes, e.g. of a dev node
+        if let Some(state_changes) = &self.state_changes {
+            apply_account_changes(evm.db_mut(), state_changes.account_changes(block.number))?;
+        }
 
         // execute transactions
         let mut cumulative_gas_used = 0;
         let mut receipts = Vec::with_capacity(block.body.len());
         for (sender, transaction) in block.transactions_with_sender() {
+            let sender = self
+                .state_changes
+                .as_ref()
+                .and_then(|changes| changes.sender(&transaction.hash))
+                .unwrap_or(*sender);
+
             // The sum of the transaction’s gas limit, Tg, and the gas utilized in this block prior,
             // must be no greater than the block’s gasLimit.
             let block_available_gas = block.header.gas_limit - cumulative_gas_used;
@@ -179,7 +212,10 @@ where
                 .into())
             }
 
-            self.evm_config.fill_tx_env(evm.tx_mut(), transaction, *sender);
+            self.evm_config.fill_tx_env(evm.tx_mut(), transaction, sender);
+            if let Some(inspectors) = inspectors(&mut evm.context.external) {
+                inspectors.on_transaction_start(transaction, sender);
+            }
 
             // Execute transaction.
             let ResultAndState { result, state } = evm.transact().map_err(move |err| {
@@ -196,6 +232,9 @@ where
                     error: Box::new(new_err),
                 }
             })?;
+            if let Some(inspectors) = inspectors(&mut evm.context.external) {
+                inspectors.on_transaction_end(transaction, &result);
+            }
             evm.db_mut().commit(state);
 
             // append gas used
@@ -224,11 +263,11 @@ where
 
             // Collect all EIP-7685 requests
             let withdrawal_requests =
-                apply_withdrawal_requests_contract_call(&self.evm_config, &mut evm)?;
+                apply_withdrawal_requests_contract_call(&self.evm_config, evm)?;
 
             // Collect all EIP-7251 requests
             let consolidation_requests =
-                apply_consolidation_requests_contract_call(&self.evm_config, &mut evm)?;
+                apply_consolidation_requests_contract_call(&self.evm_config, evm)?;
 
             [deposit_requests, withdrawal_requests, consolidation_requests].concat()
         } else {
@@ -250,12 +289,30 @@ pub struct EthBlockExecutor<EvmConfig, DB> {
     executor: EthEvmExecutor<EvmConfig>,
     /// The state to use for execution
     state: State<DB>,
+    /// Inspectors that run on every executed block.
+    inspectors: Option<BlockInspectorStack>,
 }
 
 impl<EvmConfig, DB> EthBlockExecutor<EvmConfig, DB> {
     /// Creates a new Ethereum block executor.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig, state: State<DB>) -> Self {
-        Self { executor: EthEvmExecutor { chain_spec, evm_config }, state }
+        Self {
+            executor: EthEvmExecutor { chain_spec, evm_config, state_changes: None },
+            state,
+            inspectors: None,
+        }
+    }
+
+    /// Runs the given inspectors on every executed block.
+    pub fn with_inspectors(mut self, inspectors: Option<BlockInspectorStack>) -> Self {
+        self.inspectors = inspectors;
+        self
+    }
+
+    /// Applies the given irregular state changes to every executed block.
+    pub fn with_state_changes(mut self, state_changes: Option<Arc<dyn BlockStateChanges>>) -> Self {
+        self.executor.state_changes = state_changes;
+        self
     }
 
     #[inline]
@@ -310,9 +367,25 @@ where
 
         // 2. configure the evm and execute
         let env = self.evm_env_for_block(&block.header, total_difficulty);
-        let output = {
-            let evm = self.executor.evm_config.evm_with_env(&mut self.state, env);
-            self.executor.execute_state_transitions(block, evm)
+        let output = if let Some(mut inspectors) = self.inspectors.take() {
+            inspectors.on_block_start(block);
+            let mut evm = self.executor.evm_config.evm_with_env_and_inspector(
+                &mut self.state,
+                env,
+                inspectors,
+            );
+            let output = self
+                .executor
+                .execute_state_transitions(block, &mut evm, |inspectors| Some(inspectors));
+            let mut inspectors = evm.into_context().external;
+            if let Ok(output) = &output {
+                inspectors.on_block_end(block, &output.receipts);
+            }
+            self.inspectors = Some(inspectors);
+            output
+        } else {
+            let mut evm = self.executor.evm_config.evm_with_env(&mut self.state, env);
+            self.executor.execute_state_transitions(block, &mut evm, |_| None)
         }?;
 
         // 3. apply post execution changes
@@ -433,7 +506,7 @@ where
         self.executor.state.merge_transitions(retention);
 
         // store receipts in the set
-        self.batch_record.save_receipts(receipts)?;
+        self.batch_record.save_receipts(block, receipts)?;
 
         // store requests in the set
         self.batch_record.save_requests(requests);
@@ -461,6 +534,10 @@ where
     fn size_hint(&self) -> Option<usize> {
         Some(self.executor.state.bundle_state.size_hint())
     }
+
+    fn bundle_state(&self) -> Option<&BundleState> {
+        Some(&self.executor.state.bundle_state)
+    }
 }
 
 #[cfg(test)]
@@ -472,17 +549,20 @@ mod tests {
         eip7002::{WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, WITHDRAWAL_REQUEST_PREDEPLOY_CODE},
     };
     use reth_chainspec::{ChainSpecBuilder, ForkCondition};
+    use reth_evm::{inspector::BlockInspector, state_changes::AccountChange};
     use reth_primitives::{
         constants::{EMPTY_ROOT_HASH, ETH_TO_WEI},
-        keccak256, public_key_to_address, Account, Block, Transaction, TxKind, TxLegacy, B256,
+        keccak256, public_key_to_address, Account, Address, Block, BlockNumHash, Receipt,
+        Transaction, TransactionSigned, TxKind, TxLegacy, B256,
     };
     use reth_revm::{
-        database::StateProviderDatabase, test_utils::StateProviderTest, TransitionState,
+        database::StateProviderDatabase, interpreter::CallInputs, test_utils::StateProviderTest,
+        TransitionState,
     };
     use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
     use revm_primitives::{b256, fixed_bytes, Bytes, BLOCKHASH_SERVE_WINDOW};
     use secp256k1::{Keypair, Secp256k1};
-    use std::collections::HashMap;
+    use std::{collections::HashMap, sync::Mutex};
 
     fn create_state_provider_with_beacon_root_contract() -> StateProviderTest {
         let mut db = StateProviderTest::default();
@@ -523,7 +603,7 @@ mod tests {
     }
 
     fn executor_provider(chain_spec: Arc<ChainSpec>) -> EthExecutorProvider<EthEvmConfig> {
-        EthExecutorProvider { chain_spec, evm_config: Default::default() }
+        EthExecutorProvider::new(chain_spec, Default::default())
     }
 
     #[test]
@@ -1406,4 +1486,176 @@ mod tests {
             ),
         }
     }
+
+    #[test]
+    fn block_inspectors() {
+        #[derive(Debug, Default)]
+        struct CountingInspector {
+            transactions: u64,
+            calls: u64,
+        }
+
+        impl BlockInspector for CountingInspector {
+            fn on_transaction_start(&mut self, _transaction: &TransactionSigned, _sender: Address) {
+                self.transactions += 1;
+            }
+
+            fn call(&mut self, _inputs: &CallInputs) {
+                self.calls += 1;
+            }
+
+            fn on_block_end(
+                &mut self,
+                _block: &BlockWithSenders,
+                _receipts: &[Receipt],
+            ) -> Option<Bytes> {
+                Some([self.transactions.to_be_bytes(), self.calls.to_be_bytes()].concat().into())
+            }
+        }
+
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+        let mut db = StateProviderTest::default();
+        let secp = Secp256k1::new();
+        let sender_key_pair = Keypair::new(&secp, &mut generators::rng());
+        let sender_address = public_key_to_address(sender_key_pair.public_key());
+        db.insert_account(
+            sender_address,
+            Account { nonce: 0, balance: U256::from(ETH_TO_WEI), bytecode_hash: None },
+            None,
+            HashMap::new(),
+        );
+
+        let mut header = chain_spec.genesis_header();
+        header.gas_limit = 1_500_000;
+        let tx = sign_tx_with_key_pair(
+            sender_key_pair,
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(chain_spec.chain.id()),
+                nonce: 0,
+                gas_price: header.base_fee_per_gas.unwrap().into(),
+                gas_limit: 21_000,
+                to: TxKind::Call(Address::with_last_byte(1)),
+                value: U256::from(1),
+                input: Bytes::new(),
+            }),
+        );
+
+        let outputs = Arc::new(Mutex::new(Vec::new()));
+        let inspectors = BlockInspectors::new()
+            .with_inspector("counter", CountingInspector::default)
+            .with_sink({
+                let outputs = Arc::clone(&outputs);
+                move |inspector: &str, block: BlockNumHash, output: Bytes| {
+                    outputs.lock().unwrap().push((inspector.to_string(), block.number, output))
+                }
+            });
+        let provider = executor_provider(chain_spec).with_inspectors(inspectors);
+
+        let block =
+            Block { header, body: vec![tx], ommers: vec![], withdrawals: None, requests: None }
+                .with_recovered_senders()
+                .unwrap();
+        provider
+            .executor(StateProviderDatabase::new(&db))
+            .execute((&block, U256::ZERO).into())
+            .unwrap();
+
+        let expected: Bytes = [1u64.to_be_bytes(), 1u64.to_be_bytes()].concat().into();
+        assert_eq!(*outputs.lock().unwrap(), vec![("counter".to_string(), 0, expected)]);
+    }
+
+    #[test]
+    fn block_state_changes() {
+        #[derive(Debug)]
+        struct DevStateChanges {
+            impersonated: (B256, Address),
+            changes: Vec<(Address, AccountChange)>,
+        }
+
+        impl BlockStateChanges for DevStateChanges {
+            fn account_changes(&self, block: BlockNumber) -> Vec<(Address, AccountChange)> {
+                if block == 0 {
+                    self.changes.clone()
+                } else {
+                    Vec::new()
+                }
+            }
+
+            fn sender(&self, transaction: &B256) -> Option<Address> {
+                (*transaction == self.impersonated.0).then_some(self.impersonated.1)
+            }
+        }
+
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+        // the transaction is executed as the impersonated account, which is funded by the changes
+        // of the block
+        let db = StateProviderTest::default();
+        let secp = Secp256k1::new();
+        let signer_key_pair = Keypair::new(&secp, &mut generators::rng());
+        let impersonated = Address::with_last_byte(0xa1);
+
+        let mut header = chain_spec.genesis_header();
+        header.gas_limit = 1_500_000;
+        let tx = sign_tx_with_key_pair(
+            signer_key_pair,
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(chain_spec.chain.id()),
+                nonce: 0,
+                gas_price: header.base_fee_per_gas.unwrap().into(),
+                gas_limit: 21_000,
+                to: TxKind::Call(Address::with_last_byte(0xa2)),
+                value: U256::from(1),
+                input: Bytes::new(),
+            }),
+        );
+
+        let gas_cost = U256::from(21_000 * header.base_fee_per_gas.unwrap());
+        let balance_change = AccountChange::default().with_balance(U256::from(ETH_TO_WEI));
+        let contract = Address::with_last_byte(0xa3);
+        let code = Bytes::from_static(&[0x60, 0x00]);
+        let contract_change = AccountChange::default()
+            .with_nonce(1)
+            .with_code(code.clone())
+            .with_storage(U256::from(1), U256::from(2));
+        let state_changes = DevStateChanges {
+            impersonated: (tx.hash, impersonated),
+            changes: vec![(impersonated, balance_change), (contract, contract_change)],
+        };
+        let provider = executor_provider(chain_spec).with_state_changes(Arc::new(state_changes));
+
+        let block =
+            Block { header, body: vec![tx], ommers: vec![], withdrawals: None, requests: None }
+                .with_recovered_senders()
+                .unwrap();
+        let output = provider
+            .executor(StateProviderDatabase::new(&db))
+            .execute((&block, U256::ZERO).into())
+            .unwrap();
+        assert!(output.receipts[0].success);
+
+        // the changes are applied before the transaction
+        let account = output.state.account(&impersonated).unwrap().info.clone().unwrap();
+        assert_eq!(account.nonce, 1);
+        assert_eq!(account.balance, U256::from(ETH_TO_WEI) - gas_cost - U256::from(1));
+        assert_eq!(
+            output
+                .state
+                .account(&Address::with_last_byte(0xa2))
+                .unwrap()
+                .info
+                .clone()
+                .unwrap()
+                .balance,
+            U256::from(1)
+        );
+
+        let account = output.state.account(&contract).unwrap();
+        let info = account.info.clone().unwrap();
+        assert_eq!(info.nonce, 1);
+        assert_eq!(info.code_hash, keccak256(&code));
+        assert_eq!(account.storage_slot(U256::from(1)), Some(U256::from(2)));
+        assert_eq!(output.state.bytecode(&keccak256(&code)).unwrap().original_bytes(), code);
+    }
 }
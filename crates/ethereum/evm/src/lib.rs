@@ -115,8 +115,11 @@ impl ConfigureEvm for EthEvmConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use reth_chainspec::{Chain, ChainSpec};
-    use reth_evm::execute::ProviderError;
+    use reth_chainspec::{Chain, ChainSpec, MAINNET};
+    use reth_evm::{
+        execute::ProviderError,
+        overrides::{CustomPrecompile, EvmConfigWithOverrides, EvmOverrides},
+    };
     use reth_primitives::{
         revm_primitives::{BlockEnv, CfgEnv, SpecId},
         Genesis, Header, B256, KECCAK_EMPTY, U256,
@@ -126,7 +129,10 @@ mod tests {
         inspectors::NoOpInspector,
         JournaledState,
     };
-    use revm_primitives::{CfgEnvWithHandlerCfg, EnvWithHandlerCfg, HandlerCfg};
+    use revm_primitives::{
+        CfgEnvWithHandlerCfg, EnvWithHandlerCfg, HandlerCfg, Precompile, PrecompileOutput,
+        PrecompileResult,
+    };
     use std::collections::HashSet;
 
     #[test]
@@ -464,4 +470,63 @@ mod tests {
             HandlerCfg { spec_id: SpecId::CONSTANTINOPLE, ..Default::default() }
         );
     }
+
+    #[test]
+    fn test_evm_with_overrides() {
+        let ecrecover = Address::with_last_byte(1);
+        let alias = Address::with_last_byte(0xff);
+        let overrides = EvmOverrides::default()
+            .with_chain_id(1337)
+            .with_spec_activation(0, SpecId::SHANGHAI)
+            .with_spec_activation(10, SpecId::CANCUN)
+            .with_precompile(alias, ecrecover);
+        let evm_config = EvmConfigWithOverrides::new(EthEvmConfig::default(), overrides);
+
+        let mut cfg_env = CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::LATEST);
+        let header = Header { number: 5, ..Default::default() };
+        evm_config.fill_cfg_env(&mut cfg_env, &MAINNET, &header, U256::ZERO);
+        assert_eq!(cfg_env.chain_id, 1337);
+        assert_eq!(cfg_env.handler_cfg.spec_id, SpecId::SHANGHAI);
+
+        let env_with_handler =
+            EnvWithHandlerCfg::new_with_cfg_env(cfg_env, BlockEnv::default(), Default::default());
+        let db = CacheDB::<EmptyDBTyped<ProviderError>>::default();
+        let evm = evm_config.evm_with_env(db, env_with_handler);
+        assert_eq!(evm.handler.spec_id(), SpecId::SHANGHAI);
+
+        // the alias is kept when the spec id changes
+        let precompiles = evm.handler.pre_execution().load_precompiles();
+        assert!(precompiles.contains(&ecrecover));
+        assert!(precompiles.contains(&alias));
+    }
+
+    #[test]
+    fn test_evm_with_custom_precompile() {
+        fn precompile(_input: &Bytes, _gas_limit: u64) -> PrecompileResult {
+            Ok(PrecompileOutput::new(100, Bytes::from_static(b"custom")))
+        }
+
+        let address = Address::with_last_byte(0xff);
+        let overrides = EvmOverrides::default().with_custom_precompile(CustomPrecompile::new(
+            address,
+            10,
+            Precompile::Standard(precompile),
+        ));
+        let evm_config = EvmConfigWithOverrides::new(EthEvmConfig::default(), overrides);
+
+        let call = |block: u64| {
+            let mut env = Env::default();
+            env.block.number = U256::from(block);
+            env.tx.transact_to = TxKind::Call(address);
+            env.tx.gas_price = U256::ZERO;
+            let env = EnvWithHandlerCfg::new_with_spec_id(Box::new(env), SpecId::CANCUN);
+
+            let db = CacheDB::<EmptyDBTyped<ProviderError>>::default();
+            let mut evm = evm_config.evm_with_env(db, env);
+            evm.transact().unwrap().result.into_output().unwrap()
+        };
+
+        assert_eq!(call(9), Bytes::new());
+        assert_eq!(call(10), Bytes::from_static(b"custom"));
+    }
 }
@@ -9,6 +9,7 @@ use reth_chainspec::ChainSpec;
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
+use reth_evm::{inspector::BlockInspectors, overrides::EvmConfigWithOverrides};
 use reth_evm_ethereum::execute::EthExecutorProvider;
 use reth_network::NetworkHandle;
 use reth_node_api::{FullNodeComponents, NodeAddOns};
@@ -21,12 +22,13 @@ use reth_node_builder::{
     BuilderContext, ConfigureEvm, Node, PayloadBuilderConfig, PayloadTypes,
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
+use reth_payload_relay::{RelayClient, RelayConfig, RelayPayloadBuilder, RelayTrustRules};
 use reth_provider::CanonStateSubscriptions;
 use reth_rpc::EthApi;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, TransactionPool,
-    TransactionValidationTaskExecutor,
+    blobstore::{BlobOverflowStore, DiskFileBlobStore},
+    EthTransactionPool, TransactionPool, TransactionValidationTaskExecutor,
 };
 
 use crate::{EthEngineTypes, EthEvmConfig};
@@ -99,15 +101,27 @@ where
 }
 
 /// A regular ethereum evm and executor builder.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct EthereumExecutorBuilder;
+pub struct EthereumExecutorBuilder {
+    /// Inspectors that run on every block executed by the node.
+    inspectors: BlockInspectors,
+}
+
+impl EthereumExecutorBuilder {
+    /// Installs the given inspectors on the block executor, so they run on every block executed
+    /// by the node.
+    pub fn with_inspectors(mut self, inspectors: BlockInspectors) -> Self {
+        self.inspectors = inspectors;
+        self
+    }
+}
 
 impl<Node> ExecutorBuilder<Node> for EthereumExecutorBuilder
 where
     Node: FullNodeTypes,
 {
-    type EVM = EthEvmConfig;
+    type EVM = EvmConfigWithOverrides<EthEvmConfig>;
     type Executor = EthExecutorProvider<Self::EVM>;
 
     async fn build_evm(
@@ -115,8 +129,12 @@ where
         ctx: &BuilderContext<Node>,
     ) -> eyre::Result<(Self::EVM, Self::Executor)> {
         let chain_spec = ctx.chain_spec();
-        let evm_config = EthEvmConfig::default();
-        let executor = EthExecutorProvider::new(chain_spec, evm_config);
+        let evm_config = EvmConfigWithOverrides::new(EthEvmConfig::default(), ctx.evm_overrides()?);
+        let mut executor = EthExecutorProvider::new(chain_spec, evm_config.clone())
+            .with_inspectors(self.inspectors);
+        if let Some(state_changes) = ctx.dev_state_changes() {
+            executor = executor.with_state_changes(state_changes);
+        }
 
         Ok((evm_config, executor))
     }
@@ -140,8 +158,12 @@ where
 
     async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
         let data_dir = ctx.config().datadir();
-        let pool_config = ctx.pool_config();
+        let mut pool_config = ctx.pool_config();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
+        if let Some(overflow_config) = ctx.config().txpool.blob_overflow_config() {
+            pool_config.blob_overflow =
+                Some(BlobOverflowStore::open(data_dir.blobstore_overflow(), overflow_config)?);
+        }
         let validator = TransactionValidationTaskExecutor::eth_builder(ctx.chain_spec())
             .with_head_timestamp(ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
@@ -164,7 +186,9 @@ where
             let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
             let transactions_backup_config =
-                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path)
+                    .with_remote_transactions(ctx.config().txpool.journal_remote_transactions)
+                    .with_journal_interval(ctx.config().txpool.rejournal_interval);
 
             ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
                 "local transactions backup task",
@@ -226,15 +250,36 @@ where
         ctx: &BuilderContext<Node>,
         pool: Pool,
     ) -> eyre::Result<PayloadBuilderHandle<Node::Engine>> {
-        let payload_builder =
-            reth_ethereum_payload_builder::EthereumPayloadBuilder::new(self.evm_config);
         let conf = ctx.payload_builder_config();
+        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
+            EvmConfigWithOverrides::new(self.evm_config, ctx.evm_overrides()?),
+        )
+        .with_local_gas_reserve(conf.local_gas_reserve())
+        .with_blob_selection(
+            reth_ethereum_payload_builder::BlobSelectionConfig::new()
+                .with_max_blobs(conf.max_blobs())
+                .with_min_priority_fee(conf.min_blob_priority_fee()),
+        );
+        let relay = conf.relay_url().map(|url| {
+            info!(target: "reth::cli", %url, "Racing local payloads against external builder");
+            RelayConfig::new(RelayClient::new(url.clone()))
+                .with_rules(
+                    RelayTrustRules::default()
+                        .with_trusted_builders(conf.relay_trusted_builders().iter().copied())
+                        .with_min_value_advantage_bps(conf.relay_min_advantage_bps()),
+                )
+                .with_timeout(conf.relay_timeout())
+        });
+        let payload_builder = RelayPayloadBuilder::new(payload_builder).with_relay(relay);
 
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
             .interval(conf.interval())
             .deadline(conf.deadline())
             .max_payload_tasks(conf.max_payload_tasks())
-            .extradata(conf.extradata_bytes());
+            .extradata(conf.extradata_bytes())
+            .blob_target(conf.blob_target())
+            .min_improvement_bps(conf.min_improvement_bps())
+            .build_time_budget(conf.build_time_budget());
 
         let payload_generator = BasicPayloadJobGenerator::with_builder(
             ctx.provider().clone(),
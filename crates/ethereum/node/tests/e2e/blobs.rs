@@ -35,11 +35,11 @@ async fn can_handle_blobs() -> eyre::Result<()> {
         .with_chain(chain_spec)
         .with_unused_ports()
         .with_rpc(RpcServerArgs::default().with_unused_ports().with_http());
-    let NodeHandle { node, node_exit_future: _ } = NodeBuilder::new(node_config.clone())
+    let launch = NodeBuilder::new(node_config.clone())
         .testing_node(exec.clone())
         .node(EthereumNode::default())
-        .launch()
-        .await?;
+        .launch();
+    let NodeHandle { node, node_exit_future: _ } = Box::pin(launch).await?;
 
     let mut node = NodeTestContext::new(node).await?;
 
@@ -1,12 +1,24 @@
 use std::sync::Arc;
 
 use alloy_genesis::Genesis;
-use alloy_primitives::{b256, hex};
+use alloy_primitives::{address, b256, bytes, hex, B256, U256, U64};
 use futures::StreamExt;
-use reth::core::rpc::eth::helpers::EthTransactions;
-use reth_chainspec::ChainSpec;
-use reth_e2e_test_utils::setup;
-use reth_provider::CanonStateSubscriptions;
+use jsonrpsee::{core::client::ClientT, rpc_params};
+use reth::{
+    args::RpcServerArgs,
+    builder::{NodeBuilder, NodeConfig, NodeHandle},
+    core::rpc::eth::helpers::EthTransactions,
+    rpc::{
+        api::{AnvilApiClient, EvmApiClient},
+        types::anvil::MineOptions,
+    },
+};
+use reth_chainspec::{ChainSpec, DEV};
+use reth_e2e_test_utils::{node::NodeTestContext, setup};
+use reth_node_ethereum::EthereumNode;
+use reth_provider::{
+    AccountReader, BlockNumReader, CanonStateSubscriptions, HeaderProvider, StateProviderFactory,
+};
 
 use crate::utils::EthNode;
 
@@ -19,6 +31,208 @@ async fn can_run_dev_node() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn can_mine_and_set_timestamps() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let (mut nodes, _tasks, _) = setup(1, custom_chain(), true).await?;
+    let node: EthNode = nodes.pop().unwrap();
+    let client = node.inner.rpc_server_handle().http_client().unwrap();
+    let provider = &node.inner.provider;
+
+    // mines empty blocks on request
+    assert_eq!(EvmApiClient::evm_mine(&client, None).await?, "0x0");
+    assert_eq!(provider.best_block_number()?, 1);
+    let options = MineOptions::Options { timestamp: None, blocks: Some(2) };
+    EvmApiClient::evm_mine(&client, Some(options)).await?;
+    assert_eq!(provider.best_block_number()?, 3);
+
+    // timestamps are strictly increasing
+    let timestamp = |number| provider.header_by_number(number).unwrap().unwrap().timestamp;
+    assert!(timestamp(1) < timestamp(2) && timestamp(2) < timestamp(3));
+
+    // the next block has exactly the requested timestamp
+    let next = timestamp(3) + 1_000;
+    EvmApiClient::evm_set_next_block_timestamp(&client, U64::from(next)).await?;
+    EvmApiClient::evm_mine(&client, None).await?;
+    assert_eq!(timestamp(4), next);
+
+    // timestamps in the past are rejected
+    assert!(EvmApiClient::evm_set_next_block_timestamp(&client, U64::from(next)).await.is_err());
+
+    // the time moves forward by the increase
+    let offset = EvmApiClient::evm_increase_time(&client, U256::from(3_600)).await?;
+    assert!(offset >= 1_000 + 3_600 - 5);
+    EvmApiClient::evm_mine(&client, None).await?;
+    assert!(timestamp(5) >= next + 3_600);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_manipulate_state() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let (mut nodes, _tasks, _) = setup(1, DEV.clone(), true).await?;
+    let node: EthNode = nodes.pop().unwrap();
+    let client = node.inner.rpc_server_handle().http_client().unwrap();
+    let provider = &node.inner.provider;
+    let account = |address| provider.basic_account(address).unwrap().unwrap_or_default();
+
+    let alice = address!("00000000000000000000000000000000000a11ce");
+    let bob = address!("0000000000000000000000000000000000000b0b");
+
+    let carol = address!("00000000000000000000000000000000000ca401");
+
+    // changes are made to the pending state, without mining a block
+    let snapshot = AnvilApiClient::anvil_snapshot(&client).await?;
+    let balance = U256::from(10).pow(U256::from(18));
+    AnvilApiClient::anvil_set_balance(&client, alice, balance).await?;
+    AnvilApiClient::anvil_set_nonce(&client, alice, U256::from(5)).await?;
+    AnvilApiClient::anvil_set_code(&client, bob, bytes!("00")).await?;
+    AnvilApiClient::anvil_set_storage_at(&client, bob, U256::from(1), B256::with_last_byte(2))
+        .await?;
+    assert_eq!(provider.best_block_number()?, 0);
+    assert_eq!(account(alice), Default::default());
+    let pending: U256 = client.request("eth_getBalance", rpc_params![alice, "pending"]).await?;
+    assert_eq!(pending, balance);
+    let latest: U256 = client.request("eth_getBalance", rpc_params![alice, "latest"]).await?;
+    assert_eq!(latest, U256::ZERO);
+    let pending: B256 =
+        client.request("eth_getStorageAt", rpc_params![bob, "0x1", "pending"]).await?;
+    assert_eq!(pending, B256::with_last_byte(2));
+
+    // transactions of impersonated accounts are sent without their key, and the pending state is
+    // applied before them
+    AnvilApiClient::anvil_impersonate_account(&client, alice).await?;
+    let transaction = serde_json::json!({
+        "from": alice,
+        "to": bob,
+        "value": "0x1",
+        "maxFeePerGas": "0x77359400",
+        "maxPriorityFeePerGas": "0x1",
+    });
+    let _: B256 = client.request("eth_sendTransaction", rpc_params![transaction]).await?;
+    assert_eq!(provider.best_block_number()?, 1);
+    assert_eq!(account(alice).nonce, 6);
+    assert!(account(alice).balance < balance);
+    assert_eq!(account(bob).balance, U256::from(1));
+    assert!(account(bob).has_bytecode());
+    let storage = provider.latest()?.storage(bob, B256::with_last_byte(1))?;
+    assert_eq!(storage, Some(U256::from(2)));
+
+    // the environment of the mined blocks can be changed
+    AnvilApiClient::anvil_set_coinbase(&client, carol).await?;
+    assert!(AnvilApiClient::anvil_set_block_gas_limit(&client, U256::from(20_000_000)).await?);
+    let blocks = AnvilApiClient::anvil_mine_detailed(&client, None).await?;
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].header.number, Some(2));
+    assert_eq!(blocks[0].header.miner, carol);
+    assert_eq!(blocks[0].header.gas_limit, 20_000_000);
+
+    // automine can be toggled
+    assert!(AnvilApiClient::anvil_get_automine(&client).await?);
+    AnvilApiClient::anvil_set_automine(&client, false).await?;
+    assert!(!AnvilApiClient::anvil_get_automine(&client).await?);
+    AnvilApiClient::anvil_set_automine(&client, true).await?;
+
+    // reverting unwinds the chain to the snapshot
+    assert!(AnvilApiClient::anvil_revert(&client, snapshot).await?);
+    assert_eq!(provider.best_block_number()?, 0);
+    assert_eq!(account(alice), Default::default());
+    assert_eq!(account(bob), Default::default());
+    let storage = provider.latest()?.storage(bob, B256::with_last_byte(1))?;
+    assert_eq!(storage.unwrap_or_default(), U256::ZERO);
+    let pending: U256 = client.request("eth_getBalance", rpc_params![alice, "pending"]).await?;
+    assert_eq!(pending, U256::ZERO);
+
+    // the snapshot is deleted by the revert
+    assert!(!AnvilApiClient::anvil_revert(&client, snapshot).await?);
+
+    // the chain continues from the snapshot
+    EvmApiClient::evm_mine(&client, None).await?;
+    assert_eq!(provider.best_block_number()?, 1);
+
+    // methods that are not supported are rejected
+    assert!(AnvilApiClient::anvil_set_chain_id(&client, 1).await.is_err());
+
+    Ok(())
+}
+
+// the forked node runs in the same runtime, so it must serve state requests while a worker of the
+// forking node is blocked on them
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn can_fork_chain() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let (mut nodes, tasks, _) = setup(1, DEV.clone(), true).await?;
+    let remote: EthNode = nodes.pop().unwrap();
+    let remote_client = remote.inner.rpc_server_handle().http_client().unwrap();
+
+    let alice = address!("00000000000000000000000000000000000a11ce");
+    let bob = address!("0000000000000000000000000000000000000b0b");
+    let balance = U256::from(10).pow(U256::from(18));
+    AnvilApiClient::anvil_set_balance(&remote_client, alice, balance).await?;
+    AnvilApiClient::anvil_set_code(&remote_client, bob, bytes!("00")).await?;
+    AnvilApiClient::anvil_set_storage_at(
+        &remote_client,
+        bob,
+        U256::from(1),
+        B256::with_last_byte(2),
+    )
+    .await?;
+    AnvilApiClient::anvil_mine(&remote_client, None, None).await?;
+
+    let mut config = NodeConfig::test()
+        .with_chain(DEV.clone())
+        .with_unused_ports()
+        .with_rpc(RpcServerArgs::default().with_unused_ports().with_http())
+        .dev();
+    config.dev.fork_url = Some(remote.inner.rpc_server_handle().http_url().unwrap().parse()?);
+    let launch = NodeBuilder::new(config)
+        .testing_node(tasks.executor())
+        .node(EthereumNode::default())
+        .launch();
+    let NodeHandle { node, node_exit_future: _ } = Box::pin(launch).await?;
+    let node: EthNode = NodeTestContext::new(node).await?;
+    let client = node.inner.rpc_server_handle().http_client().unwrap();
+    let provider = &node.inner.provider;
+
+    // state that isn't written locally is read from the forked chain
+    assert_eq!(provider.best_block_number()?, 0);
+    let account = provider.basic_account(alice)?.unwrap();
+    assert_eq!(account.balance, balance);
+    let code = provider.latest()?.account_code(bob)?.unwrap();
+    assert_eq!(code.original_bytes(), bytes!("00"));
+    let storage = provider.latest()?.storage(bob, B256::with_last_byte(1))?;
+    assert_eq!(storage, Some(U256::from(2)));
+
+    // the fork is reported by the node
+    let metadata = AnvilApiClient::anvil_metadata(&client).await?;
+    assert_eq!(metadata.forked_network.map(|network| network.fork_block_number), Some(1));
+
+    // local changes are layered on top, without changing the forked chain
+    AnvilApiClient::anvil_set_storage_at(&client, bob, U256::from(1), B256::ZERO).await?;
+    AnvilApiClient::anvil_impersonate_account(&client, alice).await?;
+    let transaction = serde_json::json!({
+        "from": alice,
+        "to": bob,
+        "value": "0x1",
+        "maxFeePerGas": "0x77359400",
+        "maxPriorityFeePerGas": "0x1",
+    });
+    let _: B256 = client.request("eth_sendTransaction", rpc_params![transaction]).await?;
+    assert_eq!(provider.best_block_number()?, 1);
+    assert_eq!(provider.basic_account(alice)?.unwrap().nonce, 1);
+    assert_eq!(provider.basic_account(bob)?.unwrap().balance, U256::from(1));
+    let storage = provider.latest()?.storage(bob, B256::with_last_byte(1))?;
+    assert_eq!(storage.unwrap_or_default(), U256::ZERO);
+
+    let remote_provider = &remote.inner.provider;
+    assert_eq!(remote_provider.basic_account(alice)?.unwrap().nonce, 0);
+    let storage = remote_provider.latest()?.storage(bob, B256::with_last_byte(1))?;
+    assert_eq!(storage, Some(U256::from(2)));
+
+    Ok(())
+}
+
 async fn assert_chain_advances(node: EthNode) {
     let mut notifications = node.inner.provider.canonical_state_stream();
 
@@ -2,6 +2,7 @@
 
 use std::sync::Arc;
 
+use jsonrpsee::RpcModule;
 use reth_db::{
     test_utils::{create_test_rw_db, TempDatabase},
     DatabaseEnv,
@@ -10,6 +11,7 @@ use reth_node_builder::{EngineNodeLauncher, FullNodeComponents, NodeBuilder, Nod
 use reth_node_ethereum::node::{EthereumAddOns, EthereumNode};
 use reth_provider::providers::BlockchainProvider2;
 use reth_tasks::TaskManager;
+use reth_transaction_pool::policy::{DenySelectors, MinPriorityFee, PolicySet};
 
 #[test]
 fn test_basic_setup() {
@@ -38,6 +40,15 @@ fn test_basic_setup() {
 
             Ok(())
         })
+        .install_rpc_namespace("myapp", |ctx| {
+            let _provider = ctx.provider.clone();
+            let _cache = ctx.cache.clone();
+            Ok(RpcModule::new(()))
+        })
+        .extend_pipeline_stages(|stages, config| {
+            let _ = config.extra.get("my_index");
+            Ok(stages)
+        })
         .check_launch();
 }
 
@@ -57,6 +68,21 @@ async fn test_eth_launcher() {
         });
 }
 
+#[test]
+fn test_pool_policy_setup() {
+    let config = NodeConfig::test();
+    let db = create_test_rw_db();
+    let policy = PolicySet::default()
+        .with_policy(MinPriorityFee(1))
+        .with_policy(DenySelectors::new([[0xa9, 0x05, 0x9c, 0xbb]]));
+    let _builder = NodeBuilder::new(config)
+        .with_database(db)
+        .with_types::<EthereumNode>()
+        .with_components(EthereumNode::components().pool_policy(policy))
+        .with_add_ons::<EthereumAddOns>()
+        .check_launch();
+}
+
 #[test]
 fn test_node_setup() {
     let config = NodeConfig::test();
@@ -27,7 +27,8 @@ use reth_payload_builder::{
 };
 use reth_primitives::{
     constants::{
-        eip4844::MAX_DATA_GAS_PER_BLOCK, BEACON_NONCE, EMPTY_RECEIPTS, EMPTY_TRANSACTIONS,
+        eip4844::{DATA_GAS_PER_BLOB, MAX_DATA_GAS_PER_BLOCK},
+        BEACON_NONCE, EMPTY_RECEIPTS, EMPTY_TRANSACTIONS,
     },
     eip4844::calculate_excess_blob_gas,
     proofs::{self, calculate_requests_root},
@@ -49,12 +50,73 @@ use tracing::{debug, trace, warn};
 pub struct EthereumPayloadBuilder<EvmConfig = EthEvmConfig> {
     /// The type responsible for creating the evm.
     evm_config: EvmConfig,
+    /// Gas of each block that is reserved for local transactions.
+    local_gas_reserve: u64,
+    /// How blob transactions are selected.
+    blob_selection: BlobSelectionConfig,
 }
 
 impl<EvmConfig> EthereumPayloadBuilder<EvmConfig> {
     /// `EthereumPayloadBuilder` constructor.
     pub const fn new(evm_config: EvmConfig) -> Self {
-        Self { evm_config }
+        Self { evm_config, local_gas_reserve: 0, blob_selection: BlobSelectionConfig::new() }
+    }
+
+    /// Reserves the given amount of gas of each block for local transactions.
+    ///
+    /// Non-local transactions are only included as long as this much gas remains available for
+    /// local transactions.
+    pub const fn with_local_gas_reserve(mut self, local_gas_reserve: u64) -> Self {
+        self.local_gas_reserve = local_gas_reserve;
+        self
+    }
+
+    /// Sets how blob transactions are selected for a payload.
+    pub const fn with_blob_selection(mut self, blob_selection: BlobSelectionConfig) -> Self {
+        self.blob_selection = blob_selection;
+        self
+    }
+}
+
+/// Settings for selecting blob transactions when building a payload.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlobSelectionConfig {
+    /// Maximum number of blobs included in a payload.
+    ///
+    /// This is capped at
+    /// [`MAX_BLOBS_PER_BLOCK`](reth_primitives::constants::eip4844::MAX_BLOBS_PER_BLOCK), which
+    /// is also used if not set.
+    pub max_blobs: Option<u64>,
+    /// Minimum priority fee per gas a blob transaction must pay to be included.
+    ///
+    /// Blob gas fees are burned, so blob transactions below this floor don't add any value to the
+    /// payload, but take up blob space.
+    pub min_priority_fee: u128,
+}
+
+impl BlobSelectionConfig {
+    /// Creates a config without any restrictions beyond the protocol limits.
+    pub const fn new() -> Self {
+        Self { max_blobs: None, min_priority_fee: 0 }
+    }
+
+    /// Sets the maximum number of blobs included in a payload.
+    pub const fn with_max_blobs(mut self, max_blobs: Option<u64>) -> Self {
+        self.max_blobs = max_blobs;
+        self
+    }
+
+    /// Sets the minimum priority fee per gas of included blob transactions.
+    pub const fn with_min_priority_fee(mut self, min_priority_fee: u128) -> Self {
+        self.min_priority_fee = min_priority_fee;
+        self
+    }
+
+    /// Returns the maximum blob gas a payload can use.
+    pub fn max_blob_gas(&self) -> u64 {
+        self.max_blobs.map_or(MAX_DATA_GAS_PER_BLOCK, |max_blobs| {
+            (max_blobs * DATA_GAS_PER_BLOB).min(MAX_DATA_GAS_PER_BLOCK)
+        })
     }
 }
 
@@ -78,7 +140,12 @@ where
         &self,
         args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
     ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
-        default_ethereum_payload_builder(self.evm_config.clone(), args)
+        default_ethereum_payload_builder(
+            self.evm_config.clone(),
+            args,
+            self.local_gas_reserve,
+            self.blob_selection,
+        )
     }
 
     fn build_empty_payload(
@@ -251,10 +318,16 @@ where
 /// Given build arguments including an Ethereum client, transaction pool,
 /// and configuration, this function creates a transaction payload. Returns
 /// a result indicating success with the payload or an error in case of failure.
+///
+/// The given `local_gas_reserve` is kept available for local transactions, non-local transactions
+/// can only use the remaining gas of the block. Blob transactions are included according to the
+/// given [`BlobSelectionConfig`].
 #[inline]
 pub fn default_ethereum_payload_builder<EvmConfig, Pool, Client>(
     evm_config: EvmConfig,
     args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
+    local_gas_reserve: u64,
+    blob_selection: BlobSelectionConfig,
 ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
 where
     EvmConfig: ConfigureEvm,
@@ -279,7 +352,9 @@ where
 
     debug!(target: "payload_builder", id=%attributes.id, parent_hash = ?parent_block.hash(), parent_number = parent_block.number, "building new payload");
     let mut cumulative_gas_used = 0;
+    let mut local_gas_used = 0;
     let mut sum_blob_gas_used = 0;
+    let max_blob_gas = blob_selection.max_blob_gas();
     let block_gas_limit: u64 =
         initialized_block_env.gas_limit.try_into().unwrap_or(chain_spec.max_gas_limit);
     let base_fee = initialized_block_env.basefee.to::<u64>();
@@ -327,8 +402,16 @@ where
 
     let mut receipts = Vec::new();
     while let Some(pool_tx) = best_txs.next() {
+        // non-local transactions can't use the gas that is still reserved for local transactions
+        let reserved_gas = if pool_tx.origin.is_local() {
+            0
+        } else {
+            local_gas_reserve.saturating_sub(local_gas_used)
+        };
+
         // ensure we still have capacity for this transaction
-        if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
+        if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit.saturating_sub(reserved_gas)
+        {
             // we can't fit this transaction into the block, so we need to mark it as invalid
             // which also removes all dependent transaction from the iterator before we can
             // continue
@@ -348,12 +431,20 @@ where
         // the EIP-4844 can still fit in the block
         if let Some(blob_tx) = tx.transaction.as_eip4844() {
             let tx_blob_gas = blob_tx.blob_gas();
-            if sum_blob_gas_used + tx_blob_gas > MAX_DATA_GAS_PER_BLOCK {
+            if sum_blob_gas_used + tx_blob_gas > max_blob_gas {
                 // we can't fit this _blob_ transaction into the block, so we mark it as
                 // invalid, which removes its dependent transactions from
                 // the iterator. This is similar to the gas limit condition
                 // for regular transactions above.
-                trace!(target: "payload_builder", tx=?tx.hash, ?sum_blob_gas_used, ?tx_blob_gas, "skipping blob transaction because it would exceed the max data gas per block");
+                trace!(target: "payload_builder", tx=?tx.hash, ?sum_blob_gas_used, ?tx_blob_gas, ?max_blob_gas, "skipping blob transaction because it would exceed the max data gas per block");
+                best_txs.mark_invalid(&pool_tx);
+                continue
+            }
+
+            // blob space is scarce, so it's only used for blob transactions that pay enough
+            let tip = pool_tx.effective_tip_per_gas(base_fee).unwrap_or_default();
+            if tip < blob_selection.min_priority_fee {
+                trace!(target: "payload_builder", tx=?tx.hash, ?tip, min_priority_fee=?blob_selection.min_priority_fee, "skipping blob transaction below the blob priority fee floor");
                 best_txs.mark_invalid(&pool_tx);
                 continue
             }
@@ -403,7 +494,7 @@ where
             sum_blob_gas_used += tx_blob_gas;
 
             // if we've reached the max data gas per block, we can skip blob txs entirely
-            if sum_blob_gas_used == MAX_DATA_GAS_PER_BLOCK {
+            if sum_blob_gas_used >= max_blob_gas {
                 best_txs.skip_blobs();
             }
         }
@@ -412,6 +503,9 @@ where
 
         // add gas used by the transaction to cumulative gas used, before creating the receipt
         cumulative_gas_used += gas_used;
+        if pool_tx.origin.is_local() {
+            local_gas_used += gas_used;
+        }
 
         // Push transaction changeset and calculate header bloom filter for receipt.
         #[allow(clippy::needless_update)] // side-effect of optimism fields
@@ -8,6 +8,7 @@ use reth_execution_types::{BlockExecutionInput, BlockExecutionOutput, ExecutionO
 use reth_primitives::{BlockNumber, BlockWithSenders, Receipt};
 use reth_prune_types::PruneModes;
 use reth_storage_errors::provider::ProviderError;
+use revm::db::BundleState;
 use revm_primitives::db::Database;
 
 // re-export Either
@@ -127,4 +128,11 @@ where
             Self::Right(b) => b.size_hint(),
         }
     }
+
+    fn bundle_state(&self) -> Option<&BundleState> {
+        match self {
+            Self::Left(a) => a.bundle_state(),
+            Self::Right(b) => b.bundle_state(),
+        }
+    }
 }
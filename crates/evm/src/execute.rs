@@ -9,6 +9,7 @@ use core::fmt::Display;
 
 use reth_primitives::{BlockNumber, BlockWithSenders, Receipt};
 use reth_prune_types::PruneModes;
+use revm::db::BundleState;
 use revm_primitives::db::Database;
 
 /// A general purpose executor trait that executes an input (e.g. block) and produces an output
@@ -92,6 +93,11 @@ pub trait BatchExecutor<DB> {
     ///
     /// This is used to optimize DB commits depending on the size of the state.
     fn size_hint(&self) -> Option<usize>;
+
+    /// The state changes of the blocks executed so far, if the executor tracks them.
+    fn bundle_state(&self) -> Option<&BundleState> {
+        None
+    }
 }
 
 /// A type that can create a new executor for block execution.
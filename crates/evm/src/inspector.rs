@@ -0,0 +1,298 @@
+//! Inspectors that run on every block executed by the node.
+//!
+//! Unlike the inspectors used for RPC tracing, [`BlockInspector`]s are installed on the block
+//! executor, and observe all blocks executed by the node, e.g. during live sync or historical
+//! sync. They can only observe the execution, and can't change its outcome.
+
+use core::fmt;
+
+use reth_primitives::{Address, BlockNumHash, BlockWithSenders, Bytes, Receipt, TransactionSigned};
+use revm::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    Database, EvmContext, Inspector,
+};
+use revm_primitives::{ExecutionResult, Log};
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+/// An inspector that observes every block executed by the node.
+///
+/// All hooks have empty default implementations.
+pub trait BlockInspector: Send + fmt::Debug {
+    /// Called before the transactions of a block are executed.
+    fn on_block_start(&mut self, block: &BlockWithSenders) {
+        let _ = block;
+    }
+
+    /// Called before a transaction is executed.
+    fn on_transaction_start(&mut self, transaction: &TransactionSigned, sender: Address) {
+        let _ = transaction;
+        let _ = sender;
+    }
+
+    /// Called after a transaction was executed.
+    fn on_transaction_end(&mut self, transaction: &TransactionSigned, result: &ExecutionResult) {
+        let _ = transaction;
+        let _ = result;
+    }
+
+    /// Called after all transactions of a block were executed.
+    ///
+    /// The returned output is sent to the [`BlockInspectorSink`].
+    fn on_block_end(&mut self, block: &BlockWithSenders, receipts: &[Receipt]) -> Option<Bytes> {
+        let _ = block;
+        let _ = receipts;
+        None
+    }
+
+    /// Called before each instruction of the interpreter.
+    fn step(&mut self, interp: &Interpreter) {
+        let _ = interp;
+    }
+
+    /// Called when a log is emitted.
+    fn log(&mut self, log: &Log) {
+        let _ = log;
+    }
+
+    /// Called before a call is executed.
+    fn call(&mut self, inputs: &CallInputs) {
+        let _ = inputs;
+    }
+
+    /// Called after a call was executed.
+    fn call_end(&mut self, inputs: &CallInputs, outcome: &CallOutcome) {
+        let _ = inputs;
+        let _ = outcome;
+    }
+
+    /// Called before a contract is created.
+    fn create(&mut self, inputs: &CreateInputs) {
+        let _ = inputs;
+    }
+
+    /// Called after a contract was created.
+    fn create_end(&mut self, inputs: &CreateInputs, outcome: &CreateOutcome) {
+        let _ = inputs;
+        let _ = outcome;
+    }
+
+    /// Called when a contract self-destructs.
+    fn selfdestruct(&mut self, contract: Address, target: Address) {
+        let _ = contract;
+        let _ = target;
+    }
+}
+
+/// Receives the outputs of [`BlockInspector`]s.
+pub trait BlockInspectorSink: Send + Sync {
+    /// Called with the output of the inspector with the given ID for the given block.
+    fn on_output(&self, inspector: &str, block: BlockNumHash, output: Bytes);
+}
+
+impl<F> BlockInspectorSink for F
+where
+    F: Fn(&str, BlockNumHash, Bytes) + Send + Sync,
+{
+    fn on_output(&self, inspector: &str, block: BlockNumHash, output: Bytes) {
+        self(inspector, block, output)
+    }
+}
+
+/// Creates a new instance of a [`BlockInspector`].
+pub type BlockInspectorFactory = Arc<dyn Fn() -> Box<dyn BlockInspector> + Send + Sync>;
+
+/// The [`BlockInspector`]s installed on a block executor.
+///
+/// Every executor gets its own instances of the inspectors, created by the registered factories.
+/// Results that should outlive an executor are sent to the [`BlockInspectorSink`].
+#[derive(Clone, Default)]
+pub struct BlockInspectors {
+    /// The factories of the inspectors, with their IDs.
+    factories: Vec<(String, BlockInspectorFactory)>,
+    /// Receives the outputs of the inspectors.
+    sink: Option<Arc<dyn BlockInspectorSink>>,
+}
+
+impl BlockInspectors {
+    /// Creates an empty set of inspectors.
+    pub const fn new() -> Self {
+        Self { factories: Vec::new(), sink: None }
+    }
+
+    /// Registers an inspector with the given ID.
+    pub fn with_inspector<F, I>(mut self, id: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn() -> I + Send + Sync + 'static,
+        I: BlockInspector + 'static,
+    {
+        self.factories.push((id.into(), Arc::new(move || Box::new(factory()))));
+        self
+    }
+
+    /// Sets the sink that receives the outputs of the inspectors.
+    pub fn with_sink(mut self, sink: impl BlockInspectorSink + 'static) -> Self {
+        self.sink = Some(Arc::new(sink));
+        self
+    }
+
+    /// Returns `true` if no inspectors are registered.
+    pub fn is_empty(&self) -> bool {
+        self.factories.is_empty()
+    }
+
+    /// Creates new instances of all inspectors.
+    ///
+    /// Returns `None` if no inspectors are registered.
+    pub fn stack(&self) -> Option<BlockInspectorStack> {
+        if self.is_empty() {
+            return None
+        }
+
+        Some(BlockInspectorStack {
+            inspectors: self
+                .factories
+                .iter()
+                .map(|(id, factory)| (id.clone(), factory()))
+                .collect(),
+            sink: self.sink.clone(),
+        })
+    }
+}
+
+impl fmt::Debug for BlockInspectors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockInspectors")
+            .field("inspectors", &self.factories.iter().map(|(id, _)| id).collect::<Vec<_>>())
+            .field("sink", &self.sink.is_some())
+            .finish()
+    }
+}
+
+/// Instances of [`BlockInspector`]s that are run by a block executor.
+///
+/// Implements [`Inspector`] to be used as the external context of the EVM.
+pub struct BlockInspectorStack {
+    /// The inspectors, with their IDs.
+    inspectors: Vec<(String, Box<dyn BlockInspector>)>,
+    /// Receives the outputs of the inspectors.
+    sink: Option<Arc<dyn BlockInspectorSink>>,
+}
+
+impl BlockInspectorStack {
+    /// Calls [`BlockInspector::on_block_start`] on all inspectors.
+    pub fn on_block_start(&mut self, block: &BlockWithSenders) {
+        for (_, inspector) in &mut self.inspectors {
+            inspector.on_block_start(block);
+        }
+    }
+
+    /// Calls [`BlockInspector::on_transaction_start`] on all inspectors.
+    pub fn on_transaction_start(&mut self, transaction: &TransactionSigned, sender: Address) {
+        for (_, inspector) in &mut self.inspectors {
+            inspector.on_transaction_start(transaction, sender);
+        }
+    }
+
+    /// Calls [`BlockInspector::on_transaction_end`] on all inspectors.
+    pub fn on_transaction_end(
+        &mut self,
+        transaction: &TransactionSigned,
+        result: &ExecutionResult,
+    ) {
+        for (_, inspector) in &mut self.inspectors {
+            inspector.on_transaction_end(transaction, result);
+        }
+    }
+
+    /// Calls [`BlockInspector::on_block_end`] on all inspectors, and sends their outputs to the
+    /// sink.
+    pub fn on_block_end(&mut self, block: &BlockWithSenders, receipts: &[Receipt]) {
+        let num_hash = BlockNumHash::new(block.number, block.header.hash_slow());
+        for (id, inspector) in &mut self.inspectors {
+            if let (Some(output), Some(sink)) =
+                (inspector.on_block_end(block, receipts), &self.sink)
+            {
+                sink.on_output(id, num_hash, output);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for BlockInspectorStack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockInspectorStack")
+            .field("inspectors", &self.inspectors)
+            .field("sink", &self.sink.is_some())
+            .finish()
+    }
+}
+
+impl<DB: Database> Inspector<DB> for BlockInspectorStack {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        for (_, inspector) in &mut self.inspectors {
+            inspector.step(interp);
+        }
+    }
+
+    fn log(&mut self, _interp: &mut Interpreter, _context: &mut EvmContext<DB>, log: &Log) {
+        for (_, inspector) in &mut self.inspectors {
+            inspector.log(log);
+        }
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        for (_, inspector) in &mut self.inspectors {
+            inspector.call(inputs);
+        }
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        for (_, inspector) in &mut self.inspectors {
+            inspector.call_end(inputs, &outcome);
+        }
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        for (_, inspector) in &mut self.inspectors {
+            inspector.create(inputs);
+        }
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        for (_, inspector) in &mut self.inspectors {
+            inspector.create_end(inputs, &outcome);
+        }
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, _value: revm_primitives::U256) {
+        for (_, inspector) in &mut self.inspectors {
+            inspector.selfdestruct(contract, target);
+        }
+    }
+}
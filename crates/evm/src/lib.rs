@@ -25,8 +25,11 @@ use revm_primitives::{
 pub mod builder;
 pub mod either;
 pub mod execute;
+pub mod inspector;
 pub mod noop;
+pub mod overrides;
 pub mod provider;
+pub mod state_changes;
 pub mod system_calls;
 
 #[cfg(any(test, feature = "test-utils"))]
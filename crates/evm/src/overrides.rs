@@ -0,0 +1,360 @@
+//! EVM configuration overrides, e.g. for devnets.
+//!
+//! [`EvmOverrides`] change the EVM settings of a chain without defining a new chain spec: the
+//! chain id, the maximum contract code size, the spec ids that are active from given blocks on,
+//! additional precompile addresses, and custom precompiles that are active from given blocks on.
+//! [`EvmConfigWithOverrides`] applies them on top of any [`ConfigureEvm`].
+//!
+//! The overrides only change the EVM. Consensus rules that are derived from the chain spec, e.g.
+//! the header fields and system calls of a hardfork, are not affected.
+
+use crate::{ConfigureEvm, ConfigureEvmEnv};
+use reth_chainspec::ChainSpec;
+use reth_primitives::{Address, Bytes, Header, TransactionSigned, U256};
+use revm::{
+    precompile::PrecompileOutput, ContextPrecompile, ContextStatefulPrecompileMut, Database, Evm,
+    GetInspector, InnerEvmContext,
+};
+use revm_primitives::{
+    BlockEnv, CfgEnvWithHandlerCfg, Env, Precompile, PrecompileResult, SpecId, TxEnv,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+#[cfg(feature = "std")]
+use std::{collections::BTreeMap, sync::Arc};
+
+/// A precompile that is added to the EVM from the given block on.
+#[derive(Debug, Clone)]
+pub struct CustomPrecompile {
+    /// The address of the precompile.
+    pub address: Address,
+    /// The block number from which on the precompile is active.
+    pub activation_block: u64,
+    /// The implementation of the precompile.
+    pub precompile: Precompile,
+}
+
+impl CustomPrecompile {
+    /// Creates a new precompile at the given address that is active from the given block on.
+    pub fn new(address: Address, activation_block: u64, precompile: impl Into<Precompile>) -> Self {
+        Self { address, activation_block, precompile: precompile.into() }
+    }
+}
+
+/// Precompile implementations can't be compared by value, so two custom precompiles are equal if
+/// they share the same implementation, e.g. the same function or the same [`Arc`].
+impl PartialEq for CustomPrecompile {
+    fn eq(&self, other: &Self) -> bool {
+        let same_precompile = match (&self.precompile, &other.precompile) {
+            (Precompile::Standard(a), Precompile::Standard(b)) => *a as usize == *b as usize,
+            (Precompile::Env(a), Precompile::Env(b)) => *a as usize == *b as usize,
+            (Precompile::Stateful(a), Precompile::Stateful(b)) => Arc::ptr_eq(a, b),
+            (Precompile::StatefulMut(a), Precompile::StatefulMut(b)) => {
+                core::ptr::addr_eq(&**a, &**b)
+            }
+            _ => false,
+        };
+        self.address == other.address &&
+            self.activation_block == other.activation_block &&
+            same_precompile
+    }
+}
+
+impl Eq for CustomPrecompile {}
+
+/// A [`CustomPrecompile`] installed in the EVM.
+///
+/// The block number is read from the environment of the call: before the activation block, calls
+/// are forwarded to the precompile the custom precompile replaces, or return empty output if there
+/// is none.
+#[derive(Debug, Clone)]
+struct ActivatedPrecompile {
+    /// The block number from which on the custom precompile is active.
+    activation_block: u64,
+    /// The custom precompile.
+    precompile: Precompile,
+    /// The precompile at the same address before the activation block, if any.
+    replaced: Option<Precompile>,
+}
+
+impl<DB: Database> ContextStatefulPrecompileMut<DB> for ActivatedPrecompile {
+    fn call_mut(
+        &mut self,
+        bytes: &Bytes,
+        gas_limit: u64,
+        evmctx: &mut InnerEvmContext<DB>,
+    ) -> PrecompileResult {
+        if evmctx.env.block.number >= U256::from(self.activation_block) {
+            return self.precompile.call(bytes, gas_limit, &evmctx.env)
+        }
+        match &mut self.replaced {
+            Some(precompile) => precompile.call(bytes, gas_limit, &evmctx.env),
+            None => Ok(PrecompileOutput::new(0, Bytes::new())),
+        }
+    }
+}
+
+/// Overrides of the EVM settings of a chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EvmOverrides {
+    /// The chain id used by the EVM, e.g. for the `CHAINID` opcode and transaction validation.
+    pub chain_id: Option<u64>,
+    /// The maximum size of deployed contract code, see EIP-170.
+    pub max_code_size: Option<usize>,
+    /// The spec ids that are active from the given block numbers on, replacing the spec id of
+    /// the chain spec.
+    pub spec_activations: BTreeMap<u64, SpecId>,
+    /// Additional precompile addresses, with the address of the existing precompile they
+    /// behave like.
+    pub precompiles: Vec<(Address, Address)>,
+    /// Custom precompiles, e.g. of an L2.
+    pub custom_precompiles: Vec<CustomPrecompile>,
+}
+
+impl EvmOverrides {
+    /// Sets the chain id used by the EVM.
+    pub const fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Sets the maximum size of deployed contract code.
+    pub const fn with_max_code_size(mut self, max_code_size: usize) -> Self {
+        self.max_code_size = Some(max_code_size);
+        self
+    }
+
+    /// Activates the given spec id from the given block on.
+    pub fn with_spec_activation(mut self, block: u64, spec_id: SpecId) -> Self {
+        self.spec_activations.insert(block, spec_id);
+        self
+    }
+
+    /// Adds a precompile at `address` that behaves like the existing precompile at `precompile`.
+    pub fn with_precompile(mut self, address: Address, precompile: Address) -> Self {
+        self.precompiles.push((address, precompile));
+        self
+    }
+
+    /// Adds a custom precompile.
+    ///
+    /// A custom precompile replaces the precompile at the same address once it's active.
+    pub fn with_custom_precompile(mut self, precompile: CustomPrecompile) -> Self {
+        self.custom_precompiles.push(precompile);
+        self
+    }
+
+    /// Returns `true` if nothing is overridden.
+    pub fn is_empty(&self) -> bool {
+        self.chain_id.is_none() &&
+            self.max_code_size.is_none() &&
+            self.spec_activations.is_empty() &&
+            self.precompiles.is_empty() &&
+            self.custom_precompiles.is_empty()
+    }
+
+    /// Returns the overridden spec id of the given block, if any.
+    pub fn spec_id_at(&self, block: u64) -> Option<SpecId> {
+        self.spec_activations.range(..=block).next_back().map(|(_, spec_id)| *spec_id)
+    }
+
+    /// Applies the overrides to the given [`CfgEnvWithHandlerCfg`] of the given block.
+    pub fn apply(&self, cfg_env: &mut CfgEnvWithHandlerCfg, block: u64) {
+        if let Some(chain_id) = self.chain_id {
+            cfg_env.chain_id = chain_id;
+        }
+        if let Some(max_code_size) = self.max_code_size {
+            cfg_env.limit_contract_code_size = Some(max_code_size);
+        }
+        if let Some(spec_id) = self.spec_id_at(block) {
+            cfg_env.handler_cfg.spec_id = spec_id;
+        }
+    }
+
+    /// Adds the custom precompiles and the precompile aliases to the given EVM.
+    fn install_precompiles<'a, EXT, DB: Database>(
+        &self,
+        evm: Evm<'a, EXT, DB>,
+    ) -> Evm<'a, EXT, DB> {
+        if self.precompiles.is_empty() && self.custom_precompiles.is_empty() {
+            return evm
+        }
+
+        let aliases = Arc::new(self.precompiles.clone());
+        let custom = Arc::new(self.custom_precompiles.clone());
+        evm.modify()
+            .append_handler_register_box(Box::new(move |handler| {
+                let aliases = aliases.clone();
+                let custom = custom.clone();
+
+                // precompiles are loaded without access to the environment, so the activation
+                // block is checked against the block of the call when the precompile is called
+                let load_precompiles = handler.pre_execution.load_precompiles.clone();
+                handler.pre_execution.load_precompiles = Arc::new(move || {
+                    let mut precompiles = load_precompiles();
+                    let map = precompiles.to_mut();
+                    for custom in custom.iter() {
+                        let replaced = match map.get(&custom.address) {
+                            Some(ContextPrecompile::Ordinary(precompile)) => {
+                                Some(precompile.clone())
+                            }
+                            _ => None,
+                        };
+                        map.insert(
+                            custom.address,
+                            ContextPrecompile::ContextStatefulMut(Box::new(ActivatedPrecompile {
+                                activation_block: custom.activation_block,
+                                precompile: custom.precompile.clone(),
+                                replaced,
+                            })),
+                        );
+                    }
+                    for (address, precompile) in aliases.iter() {
+                        if let Some(precompile) = map.get(precompile).cloned() {
+                            map.insert(*address, precompile);
+                        }
+                    }
+                    precompiles
+                });
+            }))
+            .build()
+    }
+}
+
+/// Returns the [`SpecId`] with the given name, e.g. `Cancun`.
+pub fn spec_id_by_name(name: &str) -> Option<SpecId> {
+    // unknown names are parsed as `LATEST`
+    let spec_id = SpecId::from(name);
+    (<&str>::from(spec_id) == name).then_some(spec_id)
+}
+
+/// A [`ConfigureEvm`] that applies [`EvmOverrides`] to the EVMs of the inner configuration.
+#[derive(Debug, Clone, Default)]
+pub struct EvmConfigWithOverrides<EvmConfig> {
+    /// The inner EVM configuration.
+    inner: EvmConfig,
+    /// The overrides applied to the inner configuration.
+    overrides: Arc<EvmOverrides>,
+}
+
+impl<EvmConfig> EvmConfigWithOverrides<EvmConfig> {
+    /// Creates a new configuration that applies the given overrides to `inner`.
+    pub fn new(inner: EvmConfig, overrides: EvmOverrides) -> Self {
+        Self { inner, overrides: Arc::new(overrides) }
+    }
+
+    /// Returns the inner EVM configuration.
+    pub const fn inner(&self) -> &EvmConfig {
+        &self.inner
+    }
+
+    /// Returns the overrides.
+    pub fn overrides(&self) -> &EvmOverrides {
+        &self.overrides
+    }
+}
+
+impl<EvmConfig: ConfigureEvmEnv> ConfigureEvmEnv for EvmConfigWithOverrides<EvmConfig> {
+    fn fill_tx_env(&self, tx_env: &mut TxEnv, transaction: &TransactionSigned, sender: Address) {
+        self.inner.fill_tx_env(tx_env, transaction, sender)
+    }
+
+    fn fill_tx_env_system_contract_call(
+        &self,
+        env: &mut Env,
+        caller: Address,
+        contract: Address,
+        data: Bytes,
+    ) {
+        self.inner.fill_tx_env_system_contract_call(env, caller, contract, data)
+    }
+
+    fn fill_cfg_env(
+        &self,
+        cfg_env: &mut CfgEnvWithHandlerCfg,
+        chain_spec: &ChainSpec,
+        header: &Header,
+        total_difficulty: U256,
+    ) {
+        self.inner.fill_cfg_env(cfg_env, chain_spec, header, total_difficulty);
+        self.overrides.apply(cfg_env, header.number);
+    }
+
+    fn fill_block_env(&self, block_env: &mut BlockEnv, header: &Header, after_merge: bool) {
+        self.inner.fill_block_env(block_env, header, after_merge)
+    }
+}
+
+impl<EvmConfig: ConfigureEvm> ConfigureEvm for EvmConfigWithOverrides<EvmConfig> {
+    type DefaultExternalContext<'a> = EvmConfig::DefaultExternalContext<'a>;
+
+    fn evm<DB: Database>(&self, db: DB) -> Evm<'_, Self::DefaultExternalContext<'_>, DB> {
+        self.overrides.install_precompiles(self.inner.evm(db))
+    }
+
+    fn evm_with_inspector<DB, I>(&self, db: DB, inspector: I) -> Evm<'_, I, DB>
+    where
+        DB: Database,
+        I: GetInspector<DB>,
+    {
+        self.overrides.install_precompiles(self.inner.evm_with_inspector(db, inspector))
+    }
+
+    fn default_external_context<'a>(&self) -> Self::DefaultExternalContext<'a> {
+        self.inner.default_external_context()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::CfgEnv;
+
+    #[test]
+    fn spec_activations() {
+        let overrides = EvmOverrides::default()
+            .with_spec_activation(10, SpecId::SHANGHAI)
+            .with_spec_activation(20, SpecId::CANCUN);
+        assert_eq!(overrides.spec_id_at(9), None);
+        assert_eq!(overrides.spec_id_at(10), Some(SpecId::SHANGHAI));
+        assert_eq!(overrides.spec_id_at(19), Some(SpecId::SHANGHAI));
+        assert_eq!(overrides.spec_id_at(25), Some(SpecId::CANCUN));
+
+        let mut cfg_env = CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::LONDON);
+        overrides.clone().with_chain_id(1337).with_max_code_size(0x10000).apply(&mut cfg_env, 15);
+        assert_eq!(cfg_env.handler_cfg.spec_id, SpecId::SHANGHAI);
+        assert_eq!(cfg_env.chain_id, 1337);
+        assert_eq!(cfg_env.limit_contract_code_size, Some(0x10000));
+
+        overrides.apply(&mut cfg_env, 5);
+        assert_eq!(cfg_env.handler_cfg.spec_id, SpecId::SHANGHAI);
+    }
+
+    #[test]
+    fn custom_precompile_eq() {
+        fn precompile(_input: &Bytes, _gas_limit: u64) -> PrecompileResult {
+            Ok(PrecompileOutput::new(0, Bytes::new()))
+        }
+
+        let custom = CustomPrecompile::new(
+            Address::with_last_byte(0xff),
+            10,
+            Precompile::Standard(precompile),
+        );
+        let overrides = EvmOverrides::default().with_custom_precompile(custom.clone());
+        assert_eq!(overrides, overrides.clone());
+        assert_ne!(
+            overrides,
+            EvmOverrides::default()
+                .with_custom_precompile(CustomPrecompile { activation_block: 11, ..custom })
+        );
+    }
+
+    #[test]
+    fn spec_names() {
+        assert_eq!(spec_id_by_name("Cancun"), Some(SpecId::CANCUN));
+        assert_eq!(spec_id_by_name("Latest"), Some(SpecId::LATEST));
+        assert_eq!(spec_id_by_name("cancun"), None);
+    }
+}
@@ -0,0 +1,137 @@
+//! Irregular state changes that are applied to blocks on top of the changes of their transactions.
+//!
+//! Executors that support them, e.g. the Ethereum block executor, apply the
+//! [`BlockStateChanges::account_changes`] of a block after its pre-execution changes and before its
+//! transactions, so they are part of the state transition of the block: they are included in its
+//! state root, the transactions of the block see them, and unwinding the block reverts them. This
+//! is used by dev nodes to manipulate the state, e.g. to set the balance of an account.
+
+use core::fmt;
+
+use reth_execution_errors::BlockExecutionError;
+use reth_primitives::{keccak256, Address, BlockNumber, Bytes, TxHash, U256};
+use reth_storage_errors::provider::ProviderError;
+use revm::{
+    db::State,
+    primitives::{Account, AccountStatus, Bytecode, EvmStorageSlot, HashMap},
+    Database, DatabaseCommit,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// A change of the state of an account.
+///
+/// Fields that are `None` and storage slots that are not set keep their current value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountChange {
+    /// The new balance of the account.
+    pub balance: Option<U256>,
+    /// The new nonce of the account.
+    pub nonce: Option<u64>,
+    /// The new code of the account.
+    pub code: Option<Bytes>,
+    /// The new values of storage slots of the account.
+    pub storage: BTreeMap<U256, U256>,
+}
+
+impl AccountChange {
+    /// Sets the balance of the account.
+    pub const fn with_balance(mut self, balance: U256) -> Self {
+        self.balance = Some(balance);
+        self
+    }
+
+    /// Sets the nonce of the account.
+    pub const fn with_nonce(mut self, nonce: u64) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Sets the code of the account.
+    pub fn with_code(mut self, code: Bytes) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Sets the value of a storage slot of the account.
+    pub fn with_storage(mut self, slot: U256, value: U256) -> Self {
+        self.storage.insert(slot, value);
+        self
+    }
+
+    /// Applies the given change on top of this one.
+    pub fn extend(&mut self, other: Self) {
+        self.balance = other.balance.or(self.balance);
+        self.nonce = other.nonce.or(self.nonce);
+        if other.code.is_some() {
+            self.code = other.code;
+        }
+        self.storage.extend(other.storage);
+    }
+}
+
+/// Irregular state changes of blocks.
+pub trait BlockStateChanges: Send + Sync + fmt::Debug {
+    /// Returns the changes of accounts that are applied before the transactions of the given block.
+    fn account_changes(&self, block: BlockNumber) -> Vec<(Address, AccountChange)>;
+
+    /// Returns the address the given transaction is executed as, if it's not the signer of the
+    /// transaction.
+    fn sender(&self, transaction: &TxHash) -> Option<Address>;
+}
+
+/// Applies the account changes to the state, as a new transition.
+///
+/// Accounts that are empty after the change are removed together with their storage, like empty
+/// accounts that are touched by a transaction (EIP-161).
+pub fn apply_account_changes<DB>(
+    state: &mut State<DB>,
+    changes: Vec<(Address, AccountChange)>,
+) -> Result<(), BlockExecutionError>
+where
+    DB: Database<Error: Into<ProviderError>>,
+{
+    if changes.is_empty() {
+        return Ok(())
+    }
+
+    let mut accounts = HashMap::with_capacity(changes.len());
+    for (address, change) in changes {
+        let mut info = state
+            .basic(address)
+            .map_err(|err| BlockExecutionError::from(err.into()))?
+            .unwrap_or_default();
+
+        if let Some(balance) = change.balance {
+            info.balance = balance;
+        }
+        if let Some(nonce) = change.nonce {
+            info.nonce = nonce;
+        }
+        if let Some(code) = change.code {
+            info.code_hash = keccak256(&code);
+            info.code = Some(Bytecode::new_raw(code));
+        }
+
+        let mut storage = HashMap::with_capacity(change.storage.len());
+        for (slot, value) in change.storage {
+            let original = state
+                .storage(address, slot)
+                .map_err(|err| BlockExecutionError::from(err.into()))?;
+            storage.insert(slot, EvmStorageSlot::new_changed(original, value));
+        }
+
+        let status = if info.is_empty() {
+            AccountStatus::Touched | AccountStatus::SelfDestructed
+        } else {
+            AccountStatus::Touched
+        };
+        accounts.insert(address, Account { info, storage, status });
+    }
+    state.commit(accounts);
+
+    Ok(())
+}
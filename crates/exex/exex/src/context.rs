@@ -2,14 +2,17 @@ use std::fmt::Debug;
 
 use reth_node_api::FullNodeComponents;
 use reth_node_core::node_config::NodeConfig;
-use reth_primitives::Head;
+use reth_primitives::{BlockNumber, Head};
+use reth_provider::{ExExCheckpointReader, ExExCheckpointWriter, ProviderResult};
 use reth_tasks::TaskExecutor;
-use tokio::sync::mpsc::{Receiver, UnboundedSender};
+use tokio::sync::mpsc::{error::SendError, Receiver, UnboundedSender};
 
-use crate::{ExExEvent, ExExNotification};
+use crate::{ExExEvent, ExExNotification, ExExNotificationFilter};
 
 /// Captures the context that an `ExEx` has access to.
 pub struct ExExContext<Node: FullNodeComponents> {
+    /// The ID of the `ExEx`.
+    pub id: String,
     /// The current head of the blockchain at launch.
     pub head: Head,
     /// The config of the node
@@ -39,6 +42,7 @@ pub struct ExExContext<Node: FullNodeComponents> {
 impl<Node: FullNodeComponents> Debug for ExExContext<Node> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ExExContext")
+            .field("id", &self.id)
             .field("head", &self.head)
             .field("config", &self.config)
             .field("reth_config", &self.reth_config)
@@ -84,4 +88,39 @@ impl<Node: FullNodeComponents> ExExContext<Node> {
     pub fn task_executor(&self) -> &TaskExecutor {
         self.components.task_executor()
     }
+
+    /// Only delivers committed chains matching the filter to this `ExEx`.
+    ///
+    /// See [`ExExEvent::NotificationFilter`].
+    pub fn set_notification_filter(
+        &self,
+        filter: ExExNotificationFilter,
+    ) -> Result<(), SendError<ExExEvent>> {
+        self.events.send(ExExEvent::NotificationFilter(Box::new(filter)))
+    }
+
+    /// Delivers all canonical blocks starting at the given historical block to this `ExEx` before
+    /// any further notification.
+    ///
+    /// See [`ExExEvent::BackfillFrom`].
+    pub fn backfill_from(&self, block: BlockNumber) -> Result<(), SendError<ExExEvent>> {
+        self.events.send(ExExEvent::BackfillFrom(block))
+    }
+
+    /// Returns the last block number saved with [`Self::save_checkpoint`], if any.
+    pub fn checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.provider().get_exex_checkpoint(&self.id)
+    }
+
+    /// Saves the given block number as processed by this `ExEx` in the node's database, and emits
+    /// an [`ExExEvent::FinishedHeight`] event.
+    ///
+    /// The checkpoint is committed before the event is emitted, so the node never prunes blocks
+    /// that are after the checkpoint. On restart, the node delivers all canonical blocks after the
+    /// checkpoint before any new notification, see [`ExExEvent::BackfillFrom`].
+    pub fn save_checkpoint(&self, block_number: BlockNumber) -> eyre::Result<()> {
+        self.provider().save_exex_checkpoint(&self.id, block_number)?;
+        self.events.send(ExExEvent::FinishedHeight(block_number))?;
+        Ok(())
+    }
 }
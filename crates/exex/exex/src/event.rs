@@ -1,7 +1,8 @@
+use crate::ExExNotificationFilter;
 use reth_primitives::BlockNumber;
 
 /// Events emitted by an `ExEx`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ExExEvent {
     /// Highest block processed by the `ExEx`.
     ///
@@ -10,4 +11,15 @@ pub enum ExExEvent {
     ///
     /// On reorgs, it's possible for the height to go down.
     FinishedHeight(BlockNumber),
+    /// Only deliver committed chains matching the filter to the `ExEx`.
+    ///
+    /// Reorgs and reverts are always delivered. Skipped chains directly following the finished
+    /// height of the `ExEx` are considered finished.
+    NotificationFilter(Box<ExExNotificationFilter>),
+    /// Deliver all canonical blocks starting at the given historical block to the `ExEx`.
+    ///
+    /// The blocks up to the current tip are re-executed and delivered as committed chains before
+    /// any further notification. This should be emitted before processing any notification, since
+    /// notifications that were already delivered are not trimmed.
+    BackfillFrom(BlockNumber),
 }
@@ -0,0 +1,120 @@
+use reth_primitives::{Address, TxType, B256};
+use reth_provider::Chain;
+use std::collections::HashSet;
+
+/// Declares which committed chains an `ExEx` is interested in.
+///
+/// A committed chain matches the filter if any of its transactions
+/// - has one of the configured transaction types,
+/// - is sent from or to one of the configured addresses, or
+/// - emitted a log from one of the configured addresses or with one of the configured topics.
+///
+/// An empty filter matches every chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExExNotificationFilter {
+    /// Addresses of senders, recipients and log emitters.
+    addresses: HashSet<Address>,
+    /// Log topics.
+    topics: HashSet<B256>,
+    /// Transaction types.
+    tx_types: HashSet<TxType>,
+}
+
+impl ExExNotificationFilter {
+    /// Matches transactions sent from or to the given addresses, and logs emitted by them.
+    pub fn with_addresses(mut self, addresses: impl IntoIterator<Item = Address>) -> Self {
+        self.addresses.extend(addresses);
+        self
+    }
+
+    /// Matches logs with any of the given topics.
+    pub fn with_topics(mut self, topics: impl IntoIterator<Item = B256>) -> Self {
+        self.topics.extend(topics);
+        self
+    }
+
+    /// Matches transactions of the given types.
+    pub fn with_tx_types(mut self, tx_types: impl IntoIterator<Item = TxType>) -> Self {
+        self.tx_types.extend(tx_types);
+        self
+    }
+
+    /// Returns `true` if the filter has no criteria and matches every chain.
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty() && self.topics.is_empty() && self.tx_types.is_empty()
+    }
+
+    /// Returns `true` if the chain contains a matching transaction or log.
+    pub fn matches(&self, chain: &Chain) -> bool {
+        if self.is_empty() {
+            return true
+        }
+
+        chain.blocks_and_receipts().any(|(block, receipts)| {
+            block.transactions_with_sender().any(|(sender, tx)| {
+                self.tx_types.contains(&tx.tx_type()) ||
+                    self.addresses.contains(sender) ||
+                    tx.to().is_some_and(|to| self.addresses.contains(&to))
+            }) || receipts.iter().flatten().flat_map(|receipt| &receipt.logs).any(|log| {
+                self.addresses.contains(&log.address) ||
+                    log.topics().iter().any(|topic| self.topics.contains(topic))
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        Log, Receipt, Receipts, SealedBlockWithSenders, Transaction, TransactionSigned, TxEip1559,
+        TxKind,
+    };
+    use reth_provider::ExecutionOutcome;
+
+    fn chain(to: Address, log: Log) -> Chain {
+        let tx = TransactionSigned::from_transaction_and_signature(
+            Transaction::Eip1559(TxEip1559 { to: TxKind::Call(to), ..Default::default() }),
+            Default::default(),
+        );
+        let mut block = SealedBlockWithSenders::default();
+        block.block.body.push(tx);
+        block.senders.push(Address::with_last_byte(1));
+
+        let receipt = Receipt { tx_type: TxType::Eip1559, logs: vec![log], ..Default::default() };
+        let outcome = ExecutionOutcome {
+            receipts: Receipts::from(vec![vec![Some(receipt)]]),
+            ..Default::default()
+        };
+        Chain::new([block], outcome, None)
+    }
+
+    #[test]
+    fn matches_transactions_and_logs() {
+        let log = Log::new_unchecked(
+            Address::with_last_byte(3),
+            vec![B256::with_last_byte(4)],
+            Default::default(),
+        );
+        let chain = chain(Address::with_last_byte(2), log);
+
+        assert!(ExExNotificationFilter::default().matches(&chain));
+        for filter in [
+            ExExNotificationFilter::default().with_addresses([Address::with_last_byte(1)]),
+            ExExNotificationFilter::default().with_addresses([Address::with_last_byte(2)]),
+            ExExNotificationFilter::default().with_addresses([Address::with_last_byte(3)]),
+            ExExNotificationFilter::default().with_topics([B256::with_last_byte(4)]),
+            ExExNotificationFilter::default().with_tx_types([TxType::Eip1559]),
+        ] {
+            assert!(filter.matches(&chain), "{filter:?}");
+        }
+
+        for filter in [
+            ExExNotificationFilter::default().with_addresses([Address::with_last_byte(4)]),
+            ExExNotificationFilter::default().with_topics([B256::with_last_byte(3)]),
+            ExExNotificationFilter::default().with_tx_types([TxType::Legacy, TxType::Eip4844]),
+        ] {
+            assert!(!filter.matches(&chain), "{filter:?}");
+        }
+    }
+}
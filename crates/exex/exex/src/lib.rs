@@ -23,6 +23,25 @@
 //! event. To clarify: if the `ExEx` emits `ExExEvent::FinishedHeight(0)` it will receive
 //! notifications for any `block_number > 0`.
 //!
+//! # Filtering and backfill
+//!
+//! `ExEx`'s can declare which committed chains they are interested in with an
+//! `ExExEvent::NotificationFilter` event, and request all blocks starting at a historical block
+//! with an `ExExEvent::BackfillFrom` event. Backfilled blocks are re-executed and delivered before
+//! any live notification.
+//!
+//! # Delivery modes
+//!
+//! By default every notification is delivered exactly once, and slow `ExEx`'s apply backpressure
+//! to the node. Stateless `ExEx`'s can opt into [`ExExDeliveryMode::AtMostOnce`] instead, which
+//! buffers notifications in a bounded channel and drops them when it is full.
+//!
+//! # Checkpoints
+//!
+//! `ExEx`'s can save the highest processed block in the node's database with
+//! [`ExExContext::save_checkpoint`]. After a restart or crash, the node delivers all canonical
+//! blocks after the checkpoint before any live notification, so no notification is missed.
+//!
 //! [`Future`]: std::future::Future
 //! [`ExExContext`]: crate::ExExContext
 //! [`CanonStateNotification`]: reth_provider::CanonStateNotification
@@ -43,6 +62,9 @@ pub use context::*;
 mod event;
 pub use event::*;
 
+mod filter;
+pub use filter::*;
+
 mod manager;
 pub use manager::*;
 
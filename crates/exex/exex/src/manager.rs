@@ -1,11 +1,20 @@
-use crate::{ExExEvent, ExExNotification, FinishedExExHeight};
+use crate::{
+    BackfillJobFactory, ExExEvent, ExExNotification, ExExNotificationFilter, FinishedExExHeight,
+};
+use futures::{stream::BoxStream, Stream, StreamExt};
 use metrics::Gauge;
+use reth_evm::execute::{BlockExecutionError, BlockExecutorProvider};
 use reth_metrics::{metrics::Counter, Metrics};
 use reth_primitives::BlockNumber;
-use reth_tracing::tracing::debug;
+use reth_provider::{
+    BlockReader, Chain, ChainSplit, ChainSplitTarget, HeaderProvider, StateProviderFactory,
+};
+use reth_tracing::tracing::{debug, error};
 use std::{
     collections::VecDeque,
+    fmt,
     future::{poll_fn, Future},
+    ops::RangeInclusive,
     pin::Pin,
     sync::{
         atomic::{AtomicUsize, Ordering},
@@ -27,6 +36,102 @@ struct ExExMetrics {
     notifications_sent_total: Counter,
     /// The total number of events an `ExEx` has sent to the manager.
     events_sent_total: Counter,
+    /// The total number of committed chains skipped because they didn't match the filter of an
+    /// `ExEx`.
+    notifications_filtered_total: Counter,
+    /// The total number of backfilled chains sent to an `ExEx`.
+    backfilled_notifications_total: Counter,
+    /// The total number of notifications dropped because the buffer of an at-most-once `ExEx` was
+    /// full.
+    notifications_dropped_total: Counter,
+}
+
+/// How notifications are delivered to an `ExEx`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExExDeliveryMode {
+    /// Every notification is delivered exactly once.
+    ///
+    /// A slow `ExEx` applies backpressure to the node, and blocks are only pruned once the `ExEx`
+    /// has emitted a [`ExExEvent::FinishedHeight`] for them.
+    #[default]
+    ExactlyOnce,
+    /// Notifications are delivered at most once through a bounded in-memory buffer.
+    ///
+    /// Notifications that don't fit into the buffer are dropped, so the `ExEx` never applies
+    /// backpressure to the node and does not hold back pruning. Meant for stateless `ExEx`'s,
+    /// e.g. for monitoring.
+    AtMostOnce {
+        /// The number of notifications that can be buffered for the `ExEx`.
+        buffer: usize,
+    },
+}
+
+impl ExExDeliveryMode {
+    /// Returns `true` if notifications are delivered at most once.
+    pub const fn is_at_most_once(&self) -> bool {
+        matches!(self, Self::AtMostOnce { .. })
+    }
+
+    /// Returns the capacity of the notification channel.
+    const fn channel_capacity(&self) -> usize {
+        match self {
+            Self::ExactlyOnce => 1,
+            Self::AtMostOnce { buffer } => *buffer,
+        }
+    }
+}
+
+/// Historical chains that are delivered to an `ExEx` before any further notification.
+pub struct ExExBackfill {
+    /// Stream of the re-executed chains.
+    chains: BoxStream<'static, Result<Chain, BlockExecutionError>>,
+    /// The last block of the backfill.
+    to: BlockNumber,
+    /// Chain waiting for a free slot in the notification channel.
+    pending: Option<Chain>,
+}
+
+impl ExExBackfill {
+    /// Creates a new backfill from a stream of chains ending at the given block.
+    pub fn new(
+        chains: impl Stream<Item = Result<Chain, BlockExecutionError>> + Send + 'static,
+        to: BlockNumber,
+    ) -> Self {
+        Self { chains: chains.boxed(), to, pending: None }
+    }
+
+    /// Creates a new backfill that re-executes the given range using the [`BackfillJobFactory`].
+    pub fn from_job_factory<E, P>(
+        factory: &BackfillJobFactory<E, P>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Self
+    where
+        E: BlockExecutorProvider + Clone + Send + 'static,
+        P: HeaderProvider + BlockReader + StateProviderFactory + Clone + Send + Unpin + 'static,
+    {
+        let to = *range.end();
+        Self::new(factory.backfill(range).into_stream(), to)
+    }
+}
+
+impl fmt::Debug for ExExBackfill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExExBackfill")
+            .field("to", &self.to)
+            .field("pending", &self.pending.as_ref().map(|chain| chain.range()))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Creates the [`ExExBackfill`] for an `ExEx` starting at the given block.
+///
+/// Returns `None` if there is nothing to backfill.
+struct ExExBackfillFactory(Box<dyn Fn(BlockNumber) -> Option<ExExBackfill> + Send + Sync>);
+
+impl fmt::Debug for ExExBackfillFactory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExExBackfillFactory").finish_non_exhaustive()
+    }
 }
 
 /// A handle to an `ExEx` used by the [`ExExManager`] to communicate with `ExEx`'s.
@@ -40,6 +145,8 @@ pub struct ExExHandle {
     id: String,
     /// Metrics for an `ExEx`.
     metrics: ExExMetrics,
+    /// How notifications are delivered to the `ExEx`.
+    delivery_mode: ExExDeliveryMode,
 
     /// Channel to send [`ExExNotification`]s to the `ExEx`.
     sender: PollSender<ExExNotification>,
@@ -52,6 +159,15 @@ pub struct ExExHandle {
     ///
     /// If this is `None`, the `ExEx` has not emitted a `FinishedHeight` event.
     finished_height: Option<BlockNumber>,
+
+    /// Filter for committed chains set by the `ExEx`.
+    filter: Option<ExExNotificationFilter>,
+    /// Historical chains that are delivered before any further notification.
+    backfill: Option<ExExBackfill>,
+    /// The last block delivered by the most recent backfill.
+    ///
+    /// Committed chains up to this block have already been delivered and are trimmed.
+    backfilled_to: Option<BlockNumber>,
 }
 
 impl ExExHandle {
@@ -60,17 +176,36 @@ impl ExExHandle {
     /// Returns the handle, as well as a [`UnboundedSender`] for [`ExExEvent`]s and a
     /// [`Receiver`] for [`ExExNotification`]s that should be given to the `ExEx`.
     pub fn new(id: String) -> (Self, UnboundedSender<ExExEvent>, Receiver<ExExNotification>) {
-        let (notification_tx, notification_rx) = mpsc::channel(1);
+        Self::with_delivery_mode(id, ExExDeliveryMode::default())
+    }
+
+    /// Create a new handle for the given `ExEx` that delivers notifications with the given
+    /// [`ExExDeliveryMode`].
+    ///
+    /// See also [`ExExHandle::new`].
+    ///
+    /// # Panics
+    ///
+    /// If the buffer of [`ExExDeliveryMode::AtMostOnce`] is zero.
+    pub fn with_delivery_mode(
+        id: String,
+        delivery_mode: ExExDeliveryMode,
+    ) -> (Self, UnboundedSender<ExExEvent>, Receiver<ExExNotification>) {
+        let (notification_tx, notification_rx) = mpsc::channel(delivery_mode.channel_capacity());
         let (event_tx, event_rx) = mpsc::unbounded_channel();
 
         (
             Self {
                 id: id.clone(),
                 metrics: ExExMetrics::new_with_labels(&[("exex", id)]),
+                delivery_mode,
                 sender: PollSender::new(notification_tx),
                 receiver: event_rx,
                 next_notification_id: 0,
                 finished_height: None,
+                filter: None,
+                backfill: None,
+                backfilled_to: None,
             },
             event_tx,
             notification_rx,
@@ -113,6 +248,17 @@ impl ExExHandle {
             }
         }
 
+        let Some(notification) = self.prepare_notification(notification) else {
+            debug!(
+                exex_id = %self.id,
+                %notification_id,
+                "Skipping notification that was backfilled or didn't match the filter"
+            );
+
+            self.next_notification_id = notification_id + 1;
+            return Poll::Ready(Ok(()))
+        };
+
         debug!(
             exex_id = %self.id,
             %notification_id,
@@ -120,6 +266,12 @@ impl ExExHandle {
         );
         match self.sender.poll_reserve(cx) {
             Poll::Ready(Ok(())) => (),
+            Poll::Pending if self.delivery_mode.is_at_most_once() => {
+                // the buffer is full, so the notification is dropped instead of waiting
+                self.sender.abort_send();
+                self.drop_notification(*notification_id);
+                return Poll::Ready(Ok(()))
+            }
             other => return other,
         }
 
@@ -128,7 +280,7 @@ impl ExExHandle {
             %notification_id,
             "Sending notification"
         );
-        match self.sender.send_item(notification.clone()) {
+        match self.sender.send_item(notification) {
             Ok(()) => {
                 self.next_notification_id = notification_id + 1;
                 self.metrics.notifications_sent_total.increment(1);
@@ -137,6 +289,108 @@ impl ExExHandle {
             Err(err) => Poll::Ready(Err(err)),
         }
     }
+
+    /// Drops the notification without delivering it to the `ExEx`.
+    fn drop_notification(&mut self, notification_id: usize) {
+        debug!(exex_id = %self.id, %notification_id, "Dropping notification");
+        self.next_notification_id = notification_id + 1;
+        self.metrics.notifications_dropped_total.increment(1);
+    }
+
+    /// Returns the notification that should be sent to the `ExEx`, or `None` if it should be
+    /// skipped.
+    ///
+    /// Committed blocks that were already backfilled are trimmed, and committed chains that don't
+    /// match the filter are skipped. Reorgs and reverts are always sent.
+    fn prepare_notification(
+        &mut self,
+        notification: &ExExNotification,
+    ) -> Option<ExExNotification> {
+        let ExExNotification::ChainCommitted { new } = notification else {
+            // blocks committed after a reorg or revert have not been backfilled
+            self.backfilled_to = None;
+            return Some(notification.clone())
+        };
+
+        let mut new = new.clone();
+        if let Some(backfilled_to) = self.backfilled_to {
+            if new.tip().number <= backfilled_to {
+                return None
+            }
+            if new.first().number <= backfilled_to {
+                if let ChainSplit::Split { pending, .. } =
+                    new.as_ref().clone().split(ChainSplitTarget::Number(backfilled_to))
+                {
+                    new = Arc::new(pending);
+                }
+            }
+        }
+
+        if self.filter.as_ref().is_some_and(|filter| !filter.matches(&new)) {
+            self.metrics.notifications_filtered_total.increment(1);
+
+            // The skipped blocks are finished if they directly follow the finished height, so
+            // that pruning is not held back by chains the `ExEx` is not interested in.
+            if let Some(finished_height) = &mut self.finished_height {
+                if *finished_height + 1 >= new.first().number {
+                    *finished_height = (*finished_height).max(new.tip().number);
+                }
+            }
+            return None
+        }
+
+        Some(ExExNotification::ChainCommitted { new })
+    }
+
+    /// Sends the chains of an active backfill to the `ExEx`.
+    ///
+    /// Resolves once there is no active backfill. If the backfill fails, it is dropped and the
+    /// `ExEx` continues with live notifications.
+    fn poll_backfill(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), PollSendError<ExExNotification>>> {
+        while let Some(backfill) = &mut self.backfill {
+            let chain = match backfill.pending.take() {
+                Some(chain) => chain,
+                None => match ready!(backfill.chains.poll_next_unpin(cx)) {
+                    Some(Ok(chain)) => chain,
+                    Some(Err(err)) => {
+                        error!(exex_id = %self.id, %err, "Failed to backfill");
+                        self.backfill = None;
+                        break
+                    }
+                    None => {
+                        debug!(exex_id = %self.id, to = %backfill.to, "Finished backfill");
+                        self.backfilled_to = Some(backfill.to);
+                        self.backfill = None;
+                        break
+                    }
+                },
+            };
+
+            if self.filter.as_ref().is_some_and(|filter| !filter.matches(&chain)) {
+                self.metrics.notifications_filtered_total.increment(1);
+                continue
+            }
+
+            match self.sender.poll_reserve(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => {
+                    backfill.pending = Some(chain);
+                    return Poll::Pending
+                }
+            }
+
+            debug!(exex_id = %self.id, range = ?chain.range(), "Sending backfilled chain");
+            self.sender.send_item(ExExNotification::ChainCommitted { new: Arc::new(chain) })?;
+            self.metrics.notifications_sent_total.increment(1);
+            self.metrics.backfilled_notifications_total.increment(1);
+        }
+
+        Poll::Ready(Ok(()))
+    }
 }
 
 /// Metrics for the `ExEx` manager.
@@ -198,6 +452,9 @@ pub struct ExExManager {
     handle: ExExManagerHandle,
     /// Metrics for the `ExEx` manager.
     metrics: ExExManagerMetrics,
+
+    /// Creates the backfills requested by `ExEx`'s.
+    backfill_factory: Option<ExExBackfillFactory>,
 }
 
 impl ExExManager {
@@ -248,9 +505,24 @@ impl ExExManager {
                 finished_height: finished_height_rx,
             },
             metrics,
+
+            backfill_factory: None,
         }
     }
 
+    /// Sets the factory used to create the backfill when an `ExEx` emits
+    /// [`ExExEvent::BackfillFrom`].
+    ///
+    /// The factory is called with the first block to backfill, and returns `None` if there is
+    /// nothing to backfill. Without a factory, backfill requests are ignored.
+    pub fn with_backfill_factory(
+        mut self,
+        factory: impl Fn(BlockNumber) -> Option<ExExBackfill> + Send + Sync + 'static,
+    ) -> Self {
+        self.backfill_factory = Some(ExExBackfillFactory(Box::new(factory)));
+        self
+    }
+
     /// Returns the handle to the manager.
     pub fn handle(&self) -> ExExManagerHandle {
         self.handle.clone()
@@ -304,17 +576,42 @@ impl Future for ExExManager {
         for idx in (0..self.exex_handles.len()).rev() {
             let mut exex = self.exex_handles.swap_remove(idx);
 
-            // it is a logic error for this to ever underflow since the manager manages the
-            // notification IDs
-            let notification_index = exex
-                .next_notification_id
-                .checked_sub(self.min_id)
-                .expect("exex expected notification ID outside the manager's range");
-            if let Some(notification) = self.buffer.get(notification_index) {
+            // backfilled chains are sent before any buffered notification
+            let backfilled = match exex.poll_backfill(cx) {
+                Poll::Ready(Ok(())) => true,
+                // the channel was closed, which is irrecoverable for the manager
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err.into())),
+                Poll::Pending => false,
+            };
+
+            loop {
+                // it is a logic error for this to ever underflow since the manager manages the
+                // notification IDs
+                let notification_index = exex
+                    .next_notification_id
+                    .checked_sub(self.min_id)
+                    .expect("exex expected notification ID outside the manager's range");
+                let Some(notification) = self.buffer.get(notification_index) else { break };
+
+                if !backfilled {
+                    // at-most-once exexs don't hold back the buffer while backfilling
+                    if exex.delivery_mode.is_at_most_once() {
+                        exex.drop_notification(notification.0);
+                        continue
+                    }
+                    break
+                }
+
                 if let Poll::Ready(Err(err)) = exex.send(cx, notification) {
                     // the channel was closed, which is irrecoverable for the manager
                     return Poll::Ready(Err(err.into()))
                 }
+
+                // at-most-once exexs never wait for a slot, so all buffered notifications are
+                // either sent or dropped
+                if !exex.delivery_mode.is_at_most_once() {
+                    break
+                }
             }
             min_id = min_id.min(exex.next_notification_id);
             self.exex_handles.push(exex);
@@ -329,22 +626,45 @@ impl Future for ExExManager {
         self.update_capacity();
 
         // handle incoming exex events
-        for exex in &mut self.exex_handles {
+        let this = &mut *self;
+        for exex in &mut this.exex_handles {
             while let Poll::Ready(Some(event)) = exex.receiver.poll_recv(cx) {
                 debug!(exex_id = %exex.id, ?event, "Received event from exex");
                 exex.metrics.events_sent_total.increment(1);
                 match event {
                     ExExEvent::FinishedHeight(height) => exex.finished_height = Some(height),
+                    ExExEvent::NotificationFilter(filter) => {
+                        exex.filter = (!filter.is_empty()).then_some(*filter)
+                    }
+                    ExExEvent::BackfillFrom(block) => {
+                        // all blocks before the backfill are finished
+                        exex.finished_height = Some(block.saturating_sub(1));
+                        exex.backfill =
+                            this.backfill_factory.as_ref().and_then(|factory| (factory.0)(block));
+                        // poll the new backfill
+                        cx.waker().wake_by_ref();
+                    }
                 }
             }
         }
 
-        // update watch channel block number
-        let finished_height = self.exex_handles.iter_mut().try_fold(u64::MAX, |curr, exex| {
-            exex.finished_height.map_or(Err(()), |height| Ok(height.min(curr)))
-        });
+        // update watch channel block number, at-most-once exexs don't hold back pruning
+        let mut exex_handles = self
+            .exex_handles
+            .iter()
+            .filter(|exex| !exex.delivery_mode.is_at_most_once())
+            .peekable();
+        let finished_height = if exex_handles.peek().is_none() {
+            Ok(FinishedExExHeight::NoExExs)
+        } else {
+            exex_handles
+                .try_fold(u64::MAX, |curr, exex| {
+                    exex.finished_height.map_or(Err(()), |height| Ok(height.min(curr)))
+                })
+                .map(FinishedExExHeight::Height)
+        };
         if let Ok(finished_height) = finished_height {
-            let _ = self.finished_height.send(FinishedExExHeight::Height(finished_height));
+            let _ = self.finished_height.send(finished_height);
         }
 
         Poll::Pending
@@ -473,8 +793,25 @@ impl Clone for ExExManagerHandle {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use reth_primitives::{SealedBlockWithSenders, B256};
-    use reth_provider::Chain;
+    use reth_primitives::{Address, Receipts, SealedBlockWithSenders, B256};
+    use reth_provider::{Chain, ExecutionOutcome};
+
+    fn chain(blocks: RangeInclusive<BlockNumber>) -> Chain {
+        let first_block = *blocks.start();
+        let blocks = blocks.map(|number| {
+            let mut block = SealedBlockWithSenders::default();
+            block.block.header.set_hash(B256::with_last_byte(number as u8));
+            block.block.header.set_block_number(number);
+            block
+        });
+        let blocks = blocks.collect::<Vec<_>>();
+        let outcome = ExecutionOutcome {
+            first_block,
+            receipts: Receipts::from(vec![Vec::new(); blocks.len()]),
+            ..Default::default()
+        };
+        Chain::new(blocks, outcome, None)
+    }
 
     #[tokio::test]
     async fn test_delivers_events() {
@@ -859,4 +1196,131 @@ mod tests {
         // Ensure the notification ID was incremented
         assert_eq!(exex_handle.next_notification_id, 23);
     }
+
+    #[tokio::test]
+    async fn test_skips_notification_not_matching_filter() {
+        let (mut exex_handle, _, mut notification_rx) = ExExHandle::new("test_exex".to_string());
+
+        exex_handle.finished_height = Some(9);
+        exex_handle.filter =
+            Some(ExExNotificationFilter::default().with_addresses([Address::with_last_byte(1)]));
+
+        let notification = ExExNotification::ChainCommitted { new: Arc::new(chain(10..=11)) };
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        // The chain has no transactions, so the notification should be skipped
+        assert!(matches!(exex_handle.send(&mut cx, &(22, notification)), Poll::Ready(Ok(()))));
+        assert!(notification_rx.try_recv().is_err(), "Receiver channel should be empty");
+        assert_eq!(exex_handle.next_notification_id, 23);
+
+        // The skipped blocks directly follow the finished height, so they are finished
+        assert_eq!(exex_handle.finished_height, Some(11));
+
+        // Reorgs are always sent
+        let notification = ExExNotification::ChainReorged {
+            old: Arc::new(chain(11..=11)),
+            new: Arc::new(chain(11..=12)),
+        };
+        assert!(matches!(
+            exex_handle.send(&mut cx, &(23, notification.clone())),
+            Poll::Ready(Ok(()))
+        ));
+        assert_eq!(notification_rx.recv().await.unwrap(), notification);
+    }
+
+    #[tokio::test]
+    async fn test_sends_backfill_before_notifications() {
+        let (mut exex_handle, _, mut notification_rx) = ExExHandle::new("test_exex".to_string());
+
+        exex_handle.backfill =
+            Some(ExExBackfill::new(futures::stream::iter([Ok(chain(5..=5)), Ok(chain(6..=6))]), 6));
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        // The channel has a capacity of one, so the second chain has to wait
+        assert!(exex_handle.poll_backfill(&mut cx).is_pending());
+        assert_eq!(
+            notification_rx.recv().await.unwrap(),
+            ExExNotification::ChainCommitted { new: Arc::new(chain(5..=5)) }
+        );
+
+        assert!(matches!(exex_handle.poll_backfill(&mut cx), Poll::Ready(Ok(()))));
+        assert_eq!(
+            notification_rx.recv().await.unwrap(),
+            ExExNotification::ChainCommitted { new: Arc::new(chain(6..=6)) }
+        );
+        assert!(exex_handle.backfill.is_none());
+        assert_eq!(exex_handle.backfilled_to, Some(6));
+
+        // Blocks that were already backfilled are trimmed
+        let notification = ExExNotification::ChainCommitted { new: Arc::new(chain(6..=7)) };
+        assert!(matches!(exex_handle.send(&mut cx, &(0, notification)), Poll::Ready(Ok(()))));
+        let received = notification_rx.recv().await.unwrap();
+        assert_eq!(received.committed_chain().unwrap().range(), 7..=7);
+
+        // Notifications that were fully backfilled are skipped
+        let notification = ExExNotification::ChainCommitted { new: Arc::new(chain(5..=6)) };
+        assert!(matches!(exex_handle.send(&mut cx, &(1, notification)), Poll::Ready(Ok(()))));
+        assert!(notification_rx.try_recv().is_err(), "Receiver channel should be empty");
+        assert_eq!(exex_handle.next_notification_id, 2);
+    }
+
+    #[tokio::test]
+    async fn test_drops_notifications_if_at_most_once_buffer_is_full() {
+        let (exex_handle, _, mut notification_rx) = ExExHandle::with_delivery_mode(
+            "test_exex".to_string(),
+            ExExDeliveryMode::AtMostOnce { buffer: 2 },
+        );
+        let (slow_exex_handle, _, mut slow_notification_rx) =
+            ExExHandle::new("test_slow_exex".to_string());
+
+        let mut exex_manager = ExExManager::new(vec![exex_handle, slow_exex_handle], 10);
+        for number in 1..=3 {
+            exex_manager.push_notification(ExExNotification::ChainCommitted {
+                new: Arc::new(chain(number..=number)),
+            });
+        }
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+        let mut pinned_manager = std::pin::pin!(exex_manager);
+        let _ = pinned_manager.as_mut().poll(&mut cx);
+
+        // The at-most-once exex received the notifications that fit into its buffer, and the
+        // last one was dropped instead of waiting
+        let exex_handle =
+            pinned_manager.exex_handles.iter().find(|exex| exex.id == "test_exex").unwrap();
+        assert_eq!(exex_handle.next_notification_id, 3);
+        for number in 1..=2 {
+            let notification = notification_rx.recv().await.unwrap();
+            assert_eq!(notification.committed_chain().unwrap().tip().number, number);
+        }
+        assert!(notification_rx.try_recv().is_err(), "Receiver channel should be empty");
+
+        // The exactly-once exex only received the first notification, and holds the buffer
+        assert_eq!(
+            slow_notification_rx.recv().await.unwrap().committed_chain().unwrap().tip().number,
+            1
+        );
+        assert_eq!(pinned_manager.buffer.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_at_most_once_exex_does_not_hold_back_pruning() {
+        let (exex_handle, event_tx, _) = ExExHandle::with_delivery_mode(
+            "test_exex".to_string(),
+            ExExDeliveryMode::AtMostOnce { buffer: 2 },
+        );
+        event_tx.send(ExExEvent::FinishedHeight(10)).unwrap();
+
+        let exex_manager = ExExManager::new(vec![exex_handle], 10);
+        let mut receiver = exex_manager.handle.finished_height();
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+        let mut pinned_manager = std::pin::pin!(exex_manager);
+        let _ = pinned_manager.as_mut().poll(&mut cx);
+
+        receiver.changed().await.unwrap();
+        assert_eq!(*receiver.borrow(), FinishedExExHeight::NoExExs);
+    }
 }
@@ -229,6 +229,12 @@ pub fn create_file(path: impl AsRef<Path>) -> Result<fs::File> {
     File::create(path).map_err(|err| FsPathError::create_file(err, path))
 }
 
+/// Wrapper for `File::open`.
+pub fn open(path: impl AsRef<Path>) -> Result<fs::File> {
+    let path = path.as_ref();
+    File::open(path).map_err(|err| FsPathError::open(err, path))
+}
+
 /// Wrapper for `std::fs::remove_file`
 pub fn remove_file(path: impl AsRef<Path>) -> Result<()> {
     let path = path.as_ref();
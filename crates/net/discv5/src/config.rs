@@ -77,6 +77,9 @@ pub struct ConfigBuilder {
     /// Custom filter rules to apply to a discovered peer in order to determine if it should be
     /// passed up to rlpx or dropped.
     discovered_peer_filter: Option<MustNotIncludeKeys>,
+    /// Whether to drop discovered peers that don't advertise a fork kv-pair under the local fork
+    /// key.
+    require_fork_id: bool,
 }
 
 impl ConfigBuilder {
@@ -92,6 +95,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            require_fork_id,
         } = discv5_config;
 
         Self {
@@ -104,6 +108,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval: Some(bootstrap_lookup_interval),
             bootstrap_lookup_countdown: Some(bootstrap_lookup_countdown),
             discovered_peer_filter: Some(discovered_peer_filter),
+            require_fork_id,
         }
     }
 
@@ -206,6 +211,16 @@ impl ConfigBuilder {
         self
     }
 
+    /// Sets whether discovered peers must advertise a fork kv-pair under the local fork key, see
+    /// [`fork`](Self::fork), in order to be passed to rlpx. The fork id of peers that advertise
+    /// one is then validated against the local fork id, before connecting to them. Peers of
+    /// other networks are thereby dropped early on, which helps finding peers on smaller
+    /// networks.
+    pub const fn require_fork_id(mut self, require_fork_id: bool) -> Self {
+        self.require_fork_id = require_fork_id;
+        self
+    }
+
     /// Returns a new [`Config`].
     pub fn build(self) -> Config {
         let Self {
@@ -218,6 +233,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            require_fork_id,
         } = self;
 
         let mut discv5_config = discv5_config
@@ -247,6 +263,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            require_fork_id,
         }
     }
 }
@@ -280,6 +297,9 @@ pub struct Config {
     /// Custom filter rules to apply to a discovered peer in order to determine if it should be
     /// passed up to rlpx or dropped.
     pub(super) discovered_peer_filter: MustNotIncludeKeys,
+    /// Whether to drop discovered peers that don't advertise a fork kv-pair under the local fork
+    /// key.
+    pub(super) require_fork_id: bool,
 }
 
 impl Config {
@@ -296,6 +316,7 @@ impl Config {
             bootstrap_lookup_interval: None,
             bootstrap_lookup_countdown: None,
             discovered_peer_filter: None,
+            require_fork_id: false,
         }
     }
 
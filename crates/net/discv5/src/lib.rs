@@ -73,6 +73,8 @@ pub struct Discv5 {
     fork_key: Option<&'static [u8]>,
     /// Filter applied to a discovered peers before passing it up to app.
     discovered_peer_filter: MustNotIncludeKeys,
+    /// Whether to drop discovered peers that don't advertise a fork id under the fork key.
+    require_fork_id: bool,
     /// Metrics for underlying [`discv5::Discv5`] node and filtered discovered peers.
     metrics: Discv5Metrics,
 }
@@ -180,6 +182,7 @@ impl Discv5 {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            require_fork_id,
             ..
         } = discv5_config;
 
@@ -215,7 +218,14 @@ impl Discv5 {
         );
 
         Ok((
-            Self { discv5, rlpx_ip_mode, fork_key, discovered_peer_filter, metrics },
+            Self {
+                discv5,
+                rlpx_ip_mode,
+                fork_key,
+                discovered_peer_filter,
+                require_fork_id,
+                metrics,
+            },
             discv5_updates,
             bc_enr,
         ))
@@ -317,10 +327,27 @@ impl Discv5 {
             return None
         }
 
-        // todo: extend for all network stacks in reth-network rlpx logic
-        let fork_id = (self.fork_key == Some(NetworkStackId::ETH))
-            .then(|| self.get_fork_id(enr).ok())
-            .flatten();
+        let fork_id = if self.require_fork_id {
+            match self.get_fork_id(enr) {
+                Ok(fork_id) => Some(fork_id),
+                Err(err) => {
+                    trace!(target: "net::discv5",
+                        %err,
+                        ?enr,
+                        "filtered out discovered peer without fork id"
+                    );
+
+                    self.metrics.discovered_peers.increment_established_sessions_filtered(1);
+
+                    return None
+                }
+            }
+        } else {
+            // todo: extend for all network stacks in reth-network rlpx logic
+            (self.fork_key == Some(NetworkStackId::ETH))
+                .then(|| self.get_fork_id(enr).ok())
+                .flatten()
+        };
 
         trace!(target: "net::discv5",
             ?fork_id,
@@ -671,6 +698,7 @@ mod test {
             rlpx_ip_mode: IpMode::Ip4,
             fork_key: None,
             discovered_peer_filter: MustNotIncludeKeys::default(),
+            require_fork_id: false,
             metrics: Discv5Metrics::default(),
         }
     }
@@ -772,6 +800,36 @@ mod test {
         )
     }
 
+    #[test]
+    fn discovered_peer_without_fork_id_filtered() {
+        reth_tracing::init_test_tracing();
+
+        // rig test
+        let remote_socket = "104.28.44.25:9000".parse().unwrap();
+        let fork_id = MAINNET.latest_fork_id();
+
+        let remote_key = CombinedKey::generate_secp256k1();
+        let remote_enr_no_fork = Enr::builder().tcp4(30303).build(&remote_key).unwrap();
+        let remote_enr_with_fork = Enr::builder()
+            .tcp4(30303)
+            .add_value_rlp(
+                NetworkStackId::ETH,
+                alloy_rlp::encode(EnrForkIdEntry::from(fork_id)).into(),
+            )
+            .build(&remote_key)
+            .unwrap();
+
+        let mut discv5 = discv5_noop();
+        discv5.fork_key = Some(NetworkStackId::ETH);
+        discv5.require_fork_id = true;
+
+        // test
+        assert!(discv5.on_discovered_peer(&remote_enr_no_fork, remote_socket).is_none());
+
+        let peer = discv5.on_discovered_peer(&remote_enr_with_fork, remote_socket).unwrap();
+        assert_eq!(Some(fork_id), peer.fork_id);
+    }
+
     // Copied from sigp/discv5 with slight modification (U256 type)
     // <https://github.com/sigp/discv5/blob/master/src/kbucket/key.rs#L89-L101>
     #[allow(unreachable_pub)]
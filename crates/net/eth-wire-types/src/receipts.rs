@@ -1,8 +1,8 @@
 //! Implements the `GetReceipts` and `Receipts` message types.
 
-use alloy_rlp::{RlpDecodableWrapper, RlpEncodableWrapper};
+use alloy_rlp::{Decodable, Encodable, Header, RlpDecodableWrapper, RlpEncodableWrapper};
 use reth_codecs_derive::add_arbitrary_tests;
-use reth_primitives::{ReceiptWithBloom, B256};
+use reth_primitives::{bytes::BufMut, Log, Receipt, ReceiptWithBloom, TxType, B256};
 
 /// A request for transaction receipts from the given block hashes.
 #[derive(Clone, Debug, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper, Default)]
@@ -25,9 +25,131 @@ pub struct Receipts(
     pub Vec<Vec<ReceiptWithBloom>>,
 );
 
+/// The response to [`GetReceipts`] in `eth/69`, which drops the bloom filter from the receipts, as
+/// defined in [EIP-7642](https://eips.ethereum.org/EIPS/eip-7642).
+///
+/// Each receipt is encoded as `[tx-type, post-state-or-status, cumulative-gas, logs]`.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Receipts69(
+    /// Each receipt hash should correspond to a block hash in the request.
+    pub Vec<Vec<Receipt>>,
+);
+
+impl Receipts69 {
+    /// Converts the receipts into [`Receipts`] by calculating the bloom filter of every receipt.
+    pub fn into_receipts(self) -> Receipts {
+        Receipts(
+            self.0
+                .into_iter()
+                .map(|receipts| receipts.into_iter().map(Receipt::with_bloom).collect())
+                .collect(),
+        )
+    }
+
+    fn encoder(&self) -> Vec<Vec<Receipt69Encoder<'_>>> {
+        self.0.iter().map(|receipts| receipts.iter().map(Receipt69Encoder).collect()).collect()
+    }
+}
+
+impl From<Receipts> for Receipts69 {
+    fn from(receipts: Receipts) -> Self {
+        Self(
+            receipts
+                .0
+                .into_iter()
+                .map(|receipts| receipts.into_iter().map(|receipt| receipt.receipt).collect())
+                .collect(),
+        )
+    }
+}
+
+impl Encodable for Receipts69 {
+    fn encode(&self, out: &mut dyn BufMut) {
+        self.encoder().encode(out)
+    }
+
+    fn length(&self) -> usize {
+        self.encoder().length()
+    }
+}
+
+impl Decodable for Receipts69 {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let receipts = Vec::<Vec<Receipt69Decoder>>::decode(buf)?;
+        Ok(Self(
+            receipts
+                .into_iter()
+                .map(|receipts| receipts.into_iter().map(|receipt| receipt.0).collect())
+                .collect(),
+        ))
+    }
+}
+
+/// Encodes a [`Receipt`] in the `eth/69` format.
+struct Receipt69Encoder<'a>(&'a Receipt);
+
+impl Receipt69Encoder<'_> {
+    fn payload_length(&self) -> usize {
+        let receipt = self.0;
+        receipt.tx_type.length() +
+            receipt.success.length() +
+            receipt.cumulative_gas_used.length() +
+            receipt.logs.length()
+    }
+}
+
+impl Encodable for Receipt69Encoder<'_> {
+    fn encode(&self, out: &mut dyn BufMut) {
+        let receipt = self.0;
+        Header { list: true, payload_length: self.payload_length() }.encode(out);
+        receipt.tx_type.encode(out);
+        receipt.success.encode(out);
+        receipt.cumulative_gas_used.encode(out);
+        receipt.logs.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+/// Decodes a [`Receipt`] in the `eth/69` format.
+struct Receipt69Decoder(Receipt);
+
+impl Decodable for Receipt69Decoder {
+    #[allow(clippy::needless_update)]
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let started_len = buf.len();
+
+        let receipt = Receipt {
+            tx_type: TxType::decode(buf)?,
+            success: bool::decode(buf)?,
+            cumulative_gas_used: u64::decode(buf)?,
+            logs: Vec::<Log>::decode(buf)?,
+            ..Default::default()
+        };
+
+        let consumed = started_len - buf.len();
+        if consumed != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            })
+        }
+
+        Ok(Self(receipt))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{message::RequestPair, GetReceipts, Receipts};
+    use crate::{message::RequestPair, GetReceipts, Receipts, Receipts69};
     use alloy_rlp::{Decodable, Encodable};
     use reth_primitives::{hex, Log, Receipt, ReceiptWithBloom, TxType};
 
@@ -150,4 +272,32 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    #[allow(clippy::needless_update)]
+    fn roundtrip_eth69_receipts() {
+        let receipt = Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used: 21_000,
+            logs: vec![Log::new_unchecked(
+                hex!("0000000000000000000000000000000000000011").into(),
+                vec![
+                    hex!("000000000000000000000000000000000000000000000000000000000000dead").into()
+                ],
+                hex!("0100ff")[..].into(),
+            )],
+            ..Default::default()
+        };
+        let receipts = Receipts69(vec![vec![receipt.clone()], vec![]]);
+
+        let mut out = vec![];
+        receipts.encode(&mut out);
+        assert_eq!(out.len(), receipts.length());
+        assert_eq!(Receipts69::decode(&mut out.as_slice()).unwrap(), receipts);
+
+        let with_bloom = receipts.clone().into_receipts();
+        assert_eq!(with_bloom.0[0][0], receipt.with_bloom());
+        assert_eq!(Receipts69::from(with_bloom), receipts);
+    }
 }
@@ -18,7 +18,8 @@ pub enum Response {
     /// The response to a [`Request::GetBlockBodies`](super::Request::GetBlockBodies) request.
     BlockBodies(RequestPair<BlockBodies>),
 
-    /// The response to a [`Request::GetPooledTransactions`](super::Request::GetPooledTransactions) request.
+    /// The response to a [`Request::GetPooledTransactions`](super::Request::GetPooledTransactions)
+    /// request.
     PooledTransactions(RequestPair<PooledTransactions>),
 
     /// The response to a [`Request::GetNodeData`](super::Request::GetNodeData) request.
@@ -0,0 +1,471 @@
+//! Implements the message types of the `snap/1` protocol, used to download a snapshot of the state
+//! at a recent block.
+//!
+//! See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+
+use alloy_rlp::{Decodable, Encodable, Header, RlpDecodable, RlpEncodable};
+use bytes::{BufMut, BytesMut};
+use reth_codecs_derive::add_arbitrary_tests;
+use reth_primitives::{
+    constants::{EMPTY_ROOT_HASH, KECCAK_EMPTY},
+    Bytes, B256, U256,
+};
+
+/// Requests an ordered range of accounts of the state trie with the given root, starting at the
+/// given account hash.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetAccountRange {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The root of the state trie to serve.
+    pub root_hash: B256,
+    /// The hash of the first account to retrieve.
+    pub starting_hash: B256,
+    /// The hash after which to stop serving accounts.
+    pub limit_hash: B256,
+    /// The soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// An account of an [`AccountRange`].
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+pub struct AccountData {
+    /// The hash of the account address.
+    pub hash: B256,
+    /// The account in the slim RLP format, where the empty storage root and code hash are
+    /// replaced by empty strings.
+    pub body: Bytes,
+}
+
+impl AccountData {
+    /// Decodes the slim RLP encoded account.
+    pub fn account(&self) -> alloy_rlp::Result<SlimAccount> {
+        SlimAccount::decode(&mut &self.body[..])
+    }
+}
+
+/// An account in the slim RLP format of the `snap/1` protocol.
+///
+/// The empty storage root and the code hash of accounts without code are encoded as empty strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct SlimAccount {
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The storage root of the account.
+    pub storage_root: B256,
+    /// The code hash of the account.
+    pub code_hash: B256,
+}
+
+impl SlimAccount {
+    /// Returns the storage root, or `None` if it's the empty root.
+    fn slim_storage_root(&self) -> Option<B256> {
+        (self.storage_root != EMPTY_ROOT_HASH).then_some(self.storage_root)
+    }
+
+    /// Returns the code hash, or `None` if the account has no code.
+    fn slim_code_hash(&self) -> Option<B256> {
+        (self.code_hash != KECCAK_EMPTY).then_some(self.code_hash)
+    }
+
+    fn payload_length(&self) -> usize {
+        self.nonce.length() +
+            self.balance.length() +
+            self.slim_storage_root().map_or(1, |root| root.length()) +
+            self.slim_code_hash().map_or(1, |hash| hash.length())
+    }
+}
+
+impl Encodable for SlimAccount {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.payload_length() }.encode(out);
+        self.nonce.encode(out);
+        self.balance.encode(out);
+        match self.slim_storage_root() {
+            Some(root) => root.encode(out),
+            None => out.put_u8(alloy_rlp::EMPTY_STRING_CODE),
+        }
+        match self.slim_code_hash() {
+            Some(hash) => hash.encode(out),
+            None => out.put_u8(alloy_rlp::EMPTY_STRING_CODE),
+        }
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for SlimAccount {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let started_len = buf.len();
+
+        let nonce = u64::decode(buf)?;
+        let balance = U256::decode(buf)?;
+        let storage_root = decode_optional_hash(buf)?.unwrap_or(EMPTY_ROOT_HASH);
+        let code_hash = decode_optional_hash(buf)?.unwrap_or(KECCAK_EMPTY);
+
+        if started_len - buf.len() != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: started_len - buf.len(),
+            })
+        }
+        Ok(Self { nonce, balance, storage_root, code_hash })
+    }
+}
+
+/// Decodes a hash that is encoded as an empty string if it's the default value.
+fn decode_optional_hash(buf: &mut &[u8]) -> alloy_rlp::Result<Option<B256>> {
+    let bytes = Header::decode_bytes(buf, false)?;
+    match bytes.len() {
+        0 => Ok(None),
+        32 => Ok(Some(B256::from_slice(bytes))),
+        _ => Err(alloy_rlp::Error::UnexpectedLength),
+    }
+}
+
+/// The response to [`GetAccountRange`] with consecutive accounts and the merkle proofs of the first
+/// requested hash and the last returned account.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct AccountRange {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The accounts, ordered by hash.
+    pub accounts: Vec<AccountData>,
+    /// The trie nodes proving the range.
+    pub proof: Vec<Bytes>,
+}
+
+/// Requests the storage slots of the given accounts in the state trie with the given root.
+///
+/// The starting and limit hashes only apply to the first account. They are used to continue the
+/// download of a large storage trie.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetStorageRanges {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The root of the state trie to serve.
+    pub root_hash: B256,
+    /// The hashes of the accounts to retrieve the storage of.
+    pub account_hashes: Vec<B256>,
+    /// The hash of the first storage slot to retrieve.
+    pub starting_hash: B256,
+    /// The hash after which to stop serving storage slots.
+    pub limit_hash: B256,
+    /// The soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// A storage slot of a [`StorageRanges`].
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+pub struct StorageData {
+    /// The hash of the storage slot key.
+    pub hash: B256,
+    /// The RLP encoded value of the storage slot.
+    pub data: Bytes,
+}
+
+/// The response to [`GetStorageRanges`] with the storage slots of the requested accounts.
+///
+/// The proof is only set if the storage of the last account is incomplete.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct StorageRanges {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The storage slots of each account, ordered by hash.
+    pub slots: Vec<Vec<StorageData>>,
+    /// The trie nodes proving the incomplete range of the last account.
+    pub proof: Vec<Bytes>,
+}
+
+/// Requests the bytecodes with the given hashes.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetByteCodes {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The code hashes of the bytecodes to retrieve.
+    pub hashes: Vec<B256>,
+    /// The soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetByteCodes`] with the requested bytecodes in request order. Unavailable
+/// bytecodes are skipped.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct ByteCodes {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The bytecodes.
+    pub codes: Vec<Bytes>,
+}
+
+/// Requests trie nodes by path from the state trie with the given root.
+///
+/// Each path set starts with the compact encoded path of a node in the account trie. Further paths
+/// are the compact encoded paths of nodes in the storage trie of the account, whose hash is given
+/// by the first element instead.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetTrieNodes {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The root of the state trie to serve.
+    pub root_hash: B256,
+    /// The path sets of the requested nodes.
+    pub paths: Vec<Vec<Bytes>>,
+    /// The soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetTrieNodes`] with the RLP encoded trie nodes in request order.
+/// Unavailable nodes end the response.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct TrieNodes {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The trie nodes.
+    pub nodes: Vec<Bytes>,
+}
+
+/// Represents message IDs for `snap/1` protocol messages.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SnapMessageId {
+    /// Requests a range of accounts.
+    GetAccountRange = 0x00,
+    /// Represents a range of accounts.
+    AccountRange = 0x01,
+    /// Requests ranges of storage slots.
+    GetStorageRanges = 0x02,
+    /// Represents ranges of storage slots.
+    StorageRanges = 0x03,
+    /// Requests bytecodes.
+    GetByteCodes = 0x04,
+    /// Represents bytecodes.
+    ByteCodes = 0x05,
+    /// Requests trie nodes.
+    GetTrieNodes = 0x06,
+    /// Represents trie nodes.
+    TrieNodes = 0x07,
+}
+
+impl SnapMessageId {
+    /// The number of messages of the protocol.
+    pub const COUNT: u8 = 8;
+}
+
+impl TryFrom<u8> for SnapMessageId {
+    type Error = alloy_rlp::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0x00 => Self::GetAccountRange,
+            0x01 => Self::AccountRange,
+            0x02 => Self::GetStorageRanges,
+            0x03 => Self::StorageRanges,
+            0x04 => Self::GetByteCodes,
+            0x05 => Self::ByteCodes,
+            0x06 => Self::GetTrieNodes,
+            0x07 => Self::TrieNodes,
+            _ => return Err(alloy_rlp::Error::Custom("invalid snap message id")),
+        })
+    }
+}
+
+/// A message of the `snap/1` protocol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SnapProtocolMessage {
+    /// Represents a [`GetAccountRange`] request.
+    GetAccountRange(GetAccountRange),
+    /// Represents an [`AccountRange`] response.
+    AccountRange(AccountRange),
+    /// Represents a [`GetStorageRanges`] request.
+    GetStorageRanges(GetStorageRanges),
+    /// Represents a [`StorageRanges`] response.
+    StorageRanges(StorageRanges),
+    /// Represents a [`GetByteCodes`] request.
+    GetByteCodes(GetByteCodes),
+    /// Represents a [`ByteCodes`] response.
+    ByteCodes(ByteCodes),
+    /// Represents a [`GetTrieNodes`] request.
+    GetTrieNodes(GetTrieNodes),
+    /// Represents a [`TrieNodes`] response.
+    TrieNodes(TrieNodes),
+}
+
+impl SnapProtocolMessage {
+    /// Returns the id of the message.
+    pub const fn message_id(&self) -> SnapMessageId {
+        match self {
+            Self::GetAccountRange(_) => SnapMessageId::GetAccountRange,
+            Self::AccountRange(_) => SnapMessageId::AccountRange,
+            Self::GetStorageRanges(_) => SnapMessageId::GetStorageRanges,
+            Self::StorageRanges(_) => SnapMessageId::StorageRanges,
+            Self::GetByteCodes(_) => SnapMessageId::GetByteCodes,
+            Self::ByteCodes(_) => SnapMessageId::ByteCodes,
+            Self::GetTrieNodes(_) => SnapMessageId::GetTrieNodes,
+            Self::TrieNodes(_) => SnapMessageId::TrieNodes,
+        }
+    }
+
+    /// Returns the id of the request, or of the request that is answered.
+    pub const fn request_id(&self) -> u64 {
+        match self {
+            Self::GetAccountRange(msg) => msg.request_id,
+            Self::AccountRange(msg) => msg.request_id,
+            Self::GetStorageRanges(msg) => msg.request_id,
+            Self::StorageRanges(msg) => msg.request_id,
+            Self::GetByteCodes(msg) => msg.request_id,
+            Self::ByteCodes(msg) => msg.request_id,
+            Self::GetTrieNodes(msg) => msg.request_id,
+            Self::TrieNodes(msg) => msg.request_id,
+        }
+    }
+
+    /// Returns the message id followed by the RLP encoded message.
+    pub fn encoded(&self) -> BytesMut {
+        let mut buf = BytesMut::new();
+        buf.put_u8(self.message_id() as u8);
+        match self {
+            Self::GetAccountRange(msg) => msg.encode(&mut buf),
+            Self::AccountRange(msg) => msg.encode(&mut buf),
+            Self::GetStorageRanges(msg) => msg.encode(&mut buf),
+            Self::StorageRanges(msg) => msg.encode(&mut buf),
+            Self::GetByteCodes(msg) => msg.encode(&mut buf),
+            Self::ByteCodes(msg) => msg.encode(&mut buf),
+            Self::GetTrieNodes(msg) => msg.encode(&mut buf),
+            Self::TrieNodes(msg) => msg.encode(&mut buf),
+        }
+        buf
+    }
+
+    /// Decodes a message from the message id followed by the RLP encoded message.
+    pub fn decode_message(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let (&id, rest) = buf.split_first().ok_or(alloy_rlp::Error::InputTooShort)?;
+        *buf = rest;
+        Ok(match SnapMessageId::try_from(id)? {
+            SnapMessageId::GetAccountRange => Self::GetAccountRange(Decodable::decode(buf)?),
+            SnapMessageId::AccountRange => Self::AccountRange(Decodable::decode(buf)?),
+            SnapMessageId::GetStorageRanges => Self::GetStorageRanges(Decodable::decode(buf)?),
+            SnapMessageId::StorageRanges => Self::StorageRanges(Decodable::decode(buf)?),
+            SnapMessageId::GetByteCodes => Self::GetByteCodes(Decodable::decode(buf)?),
+            SnapMessageId::ByteCodes => Self::ByteCodes(Decodable::decode(buf)?),
+            SnapMessageId::GetTrieNodes => Self::GetTrieNodes(Decodable::decode(buf)?),
+            SnapMessageId::TrieNodes => Self::TrieNodes(Decodable::decode(buf)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::hex;
+
+    #[test]
+    fn roundtrip_messages() {
+        let messages = [
+            SnapProtocolMessage::GetAccountRange(GetAccountRange {
+                request_id: 1,
+                root_hash: B256::repeat_byte(1),
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: 512 * 1024,
+            }),
+            SnapProtocolMessage::AccountRange(AccountRange {
+                request_id: 1,
+                accounts: vec![AccountData {
+                    hash: B256::repeat_byte(2),
+                    body: hex!("c4018080808080").into(),
+                }],
+                proof: vec![hex!("c20102").into()],
+            }),
+            SnapProtocolMessage::StorageRanges(StorageRanges {
+                request_id: 2,
+                slots: vec![
+                    vec![StorageData { hash: B256::repeat_byte(3), data: hex!("01").into() }],
+                    Vec::new(),
+                ],
+                proof: Vec::new(),
+            }),
+            SnapProtocolMessage::GetTrieNodes(GetTrieNodes {
+                request_id: 3,
+                root_hash: B256::repeat_byte(4),
+                paths: vec![vec![Bytes::new()], vec![hex!("12").into(), hex!("0034").into()]],
+                response_bytes: 1024,
+            }),
+        ];
+
+        for message in messages {
+            let encoded = message.encoded();
+            assert_eq!(encoded[0], message.message_id() as u8);
+            assert_eq!(SnapProtocolMessage::decode_message(&mut &encoded[..]).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn slim_account_roundtrip() {
+        let empty = SlimAccount {
+            nonce: 1,
+            balance: U256::from(2),
+            storage_root: EMPTY_ROOT_HASH,
+            code_hash: KECCAK_EMPTY,
+        };
+        let encoded = alloy_rlp::encode(empty);
+        assert_eq!(encoded, hex!("c401028080"));
+        assert_eq!(encoded.len(), empty.length());
+        assert_eq!(SlimAccount::decode(&mut &encoded[..]).unwrap(), empty);
+
+        let contract = SlimAccount {
+            storage_root: B256::repeat_byte(1),
+            code_hash: B256::repeat_byte(2),
+            ..empty
+        };
+        let encoded = alloy_rlp::encode(contract);
+        assert_eq!(encoded.len(), contract.length());
+        let data = AccountData { hash: B256::ZERO, body: encoded.into() };
+        assert_eq!(data.account().unwrap(), contract);
+    }
+
+    #[test]
+    fn decode_invalid_message_id() {
+        assert!(SnapProtocolMessage::decode_message(&mut &[0x08, 0xc0][..]).is_err());
+        assert!(SnapProtocolMessage::decode_message(&mut &[][..]).is_err());
+    }
+}
@@ -131,6 +131,102 @@ impl Debug for Status {
     }
 }
 
+/// The status message of `eth/69`, as defined in
+/// [EIP-7642](https://eips.ethereum.org/EIPS/eip-7642).
+///
+/// Compared to [`Status`], the total difficulty is dropped and the head is replaced by the range of
+/// blocks the peer can serve.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct StatusEth69 {
+    /// The current protocol version.
+    pub version: u8,
+
+    /// The chain id, as introduced in
+    /// [EIP155](https://eips.ethereum.org/EIPS/eip-155#list-of-chain-ids).
+    pub chain: Chain,
+
+    /// The genesis hash of the peer's chain.
+    pub genesis: B256,
+
+    /// The fork identifier as defined by
+    /// [EIP-2124](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2124.md).
+    pub forkid: ForkId,
+
+    /// The earliest block the peer can serve.
+    pub earliest_block: u64,
+
+    /// The latest block the peer can serve.
+    pub latest_block: u64,
+
+    /// The hash of the latest block.
+    pub latest_block_hash: B256,
+}
+
+impl StatusEth69 {
+    /// Creates the `eth/69` status from the given [`Status`] and the range of blocks that can be
+    /// served.
+    pub const fn new(status: &Status, range: BlockRangeUpdate) -> Self {
+        Self {
+            version: status.version,
+            chain: status.chain,
+            genesis: status.genesis,
+            forkid: status.forkid,
+            earliest_block: range.earliest,
+            latest_block: range.latest,
+            latest_block_hash: range.latest_hash,
+        }
+    }
+
+    /// Returns the range of blocks the peer can serve.
+    pub const fn block_range(&self) -> BlockRangeUpdate {
+        BlockRangeUpdate {
+            earliest: self.earliest_block,
+            latest: self.latest_block,
+            latest_hash: self.latest_block_hash,
+        }
+    }
+}
+
+impl From<StatusEth69> for Status {
+    /// Converts the `eth/69` status into a [`Status`] with the latest block as head.
+    ///
+    /// `eth/69` doesn't advertise a total difficulty, so it's set to zero.
+    fn from(status: StatusEth69) -> Self {
+        Self {
+            version: status.version,
+            chain: status.chain,
+            total_difficulty: U256::ZERO,
+            blockhash: status.latest_block_hash,
+            genesis: status.genesis,
+            forkid: status.forkid,
+        }
+    }
+}
+
+/// Announces the range of blocks a peer can serve, introduced in `eth/69`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct BlockRangeUpdate {
+    /// The earliest block the peer can serve.
+    pub earliest: u64,
+    /// The latest block the peer can serve.
+    pub latest: u64,
+    /// The hash of the latest block.
+    pub latest_hash: B256,
+}
+
+impl BlockRangeUpdate {
+    /// Returns `true` if the earliest block is not after the latest block.
+    pub const fn is_valid(&self) -> bool {
+        self.earliest <= self.latest
+    }
+}
+
 // <https://etherscan.io/block/0>
 impl Default for Status {
     fn default() -> Self {
@@ -228,7 +324,7 @@ impl StatusBuilder {
 
 #[cfg(test)]
 mod tests {
-    use crate::{EthVersion, Status};
+    use crate::{BlockRangeUpdate, EthVersion, Status, StatusEth69};
     use alloy_genesis::Genesis;
     use alloy_rlp::{Decodable, Encodable};
     use rand::Rng;
@@ -404,4 +500,25 @@ mod tests {
         assert_eq!(status.blockhash, head_hash);
         assert_eq!(status.genesis, genesis_hash);
     }
+
+    #[test]
+    fn eth69_status() {
+        let status = Status { version: EthVersion::Eth69 as u8, ..Default::default() };
+        let range = BlockRangeUpdate { earliest: 10, latest: 100, latest_hash: B256::random() };
+        let status69 = StatusEth69::new(&status, range);
+        assert_eq!(status69.block_range(), range);
+
+        let mut encoded = vec![];
+        status69.encode(&mut encoded);
+        assert_eq!(StatusEth69::decode(&mut encoded.as_slice()).unwrap(), status69);
+
+        let converted = Status::from(status69);
+        assert_eq!(converted.version, status.version);
+        assert_eq!(converted.forkid, status.forkid);
+        assert_eq!(converted.blockhash, range.latest_hash);
+        assert_eq!(converted.total_difficulty, U256::ZERO);
+
+        assert!(range.is_valid());
+        assert!(!BlockRangeUpdate { earliest: 101, ..range }.is_valid());
+    }
 }
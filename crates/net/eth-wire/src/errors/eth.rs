@@ -38,6 +38,14 @@ pub enum EthStreamError {
     /// Error when data is not received from peer for a prolonged period.
     #[error("never received data from remote peer")]
     StreamTimeout,
+    #[error("invalid block range update: earliest {earliest}, latest {latest}")]
+    /// Received an `eth/69` block range update whose earliest block is after the latest block.
+    InvalidBlockRangeUpdate {
+        /// The earliest block the peer can serve.
+        earliest: u64,
+        /// The latest block the peer can serve.
+        latest: u64,
+    },
 }
 
 // === impl EthStreamError ===
@@ -99,4 +107,12 @@ pub enum EthHandshakeError {
         /// The maximum allowed bit length for the total difficulty.
         maximum: usize,
     },
+    #[error("invalid block range in status message: earliest {earliest}, latest {latest}")]
+    /// The earliest block of the `eth/69` status message is after the latest block.
+    InvalidBlockRange {
+        /// The earliest block the peer can serve.
+        earliest: u64,
+        /// The latest block the peer can serve.
+        latest: u64,
+    },
 }
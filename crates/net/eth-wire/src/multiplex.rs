@@ -20,7 +20,8 @@ use crate::{
     capability::{SharedCapabilities, SharedCapability, UnsupportedCapabilityError},
     errors::{EthStreamError, P2PStreamError},
     p2pstream::DisconnectP2P,
-    CanDisconnect, Capability, DisconnectReason, EthStream, P2PStream, Status, UnauthedEthStream,
+    BlockRangeUpdate, CanDisconnect, Capability, DisconnectReason, EthStream, P2PStream, Status,
+    UnauthedEthStream,
 };
 use bytes::{Bytes, BytesMut};
 use futures::{Sink, SinkExt, Stream, StreamExt, TryStream, TryStreamExt};
@@ -209,6 +210,22 @@ impl<St> RlpxProtocolMultiplexer<St> {
         status: Status,
         fork_filter: ForkFilter,
     ) -> Result<(RlpxSatelliteStream<St, EthStream<ProtocolProxy>>, Status), EthStreamError>
+    where
+        St: Stream<Item = io::Result<BytesMut>> + Sink<Bytes, Error = io::Error> + Unpin,
+    {
+        self.into_eth_satellite_stream_with_block_range(status, None, fork_filter).await
+    }
+
+    /// Same as [`Self::into_eth_satellite_stream`], but announces the given range of blocks that
+    /// can be served if `eth/69` is negotiated.
+    ///
+    /// See also [`UnauthedEthStream::with_block_range`].
+    pub async fn into_eth_satellite_stream_with_block_range(
+        self,
+        status: Status,
+        block_range: Option<BlockRangeUpdate>,
+        fork_filter: ForkFilter,
+    ) -> Result<(RlpxSatelliteStream<St, EthStream<ProtocolProxy>>, Status), EthStreamError>
     where
         St: Stream<Item = io::Result<BytesMut>> + Sink<Bytes, Error = io::Error> + Unpin,
     {
@@ -216,7 +233,11 @@ impl<St> RlpxProtocolMultiplexer<St> {
         self.into_satellite_stream_with_tuple_handshake(
             &Capability::eth(eth_cap),
             move |proxy| async move {
-                UnauthedEthStream::new(proxy).handshake(status, fork_filter).await
+                let mut stream = UnauthedEthStream::new(proxy);
+                if let Some(block_range) = block_range {
+                    stream = stream.with_block_range(block_range);
+                }
+                stream.handshake(status, fork_filter).await
             },
         )
         .await
@@ -24,7 +24,11 @@ pub mod test_utils;
 pub use alloy_rpc_types_admin::EthProtocolInfo;
 use reth_network_p2p::sync::NetworkSyncUpdater;
 pub use reth_network_p2p::BlockClient;
-pub use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+use reth_network_types::{ConnectionsConfig, InboundFilter};
+pub use reth_network_types::{
+    PeerKind, PropagationEvent, PropagationKind, Reputation, ReputationChangeKind,
+    TransactionPropagation,
+};
 
 pub use downloaders::BlockDownloaderProvider;
 pub use error::NetworkError;
@@ -35,7 +39,10 @@ pub use events::{
 
 use std::{future::Future, net::SocketAddr, sync::Arc, time::Instant};
 
-use reth_eth_wire_types::{capability::Capabilities, DisconnectReason, EthVersion, Status};
+use alloy_primitives::TxHash;
+use reth_eth_wire_types::{
+    capability::Capabilities, BlockRangeUpdate, DisconnectReason, EthVersion, Status,
+};
 use reth_network_peers::NodeRecord;
 
 /// The `PeerId` type.
@@ -192,6 +199,27 @@ pub trait Peers: PeersInfo {
         &self,
         peer_id: PeerId,
     ) -> impl Future<Output = Result<Option<Reputation>, NetworkError>> + Send;
+
+    /// Replaces the limits for inbound and outbound connections.
+    ///
+    /// Established sessions are kept if the new limits are lower.
+    fn set_connection_limits(&self, limits: ConnectionsConfig);
+
+    /// Returns the filter that decides which inbound sessions are accepted.
+    fn inbound_filter(&self) -> impl Future<Output = Result<InboundFilter, NetworkError>> + Send;
+
+    /// Replaces the filter that decides which inbound sessions are accepted.
+    ///
+    /// Established inbound sessions that the new filter rejects by IP or peer id are disconnected.
+    fn set_inbound_filter(&self, filter: InboundFilter);
+
+    /// Returns how the given transaction was received from and forwarded to peers.
+    ///
+    /// Returns `None` if the transaction was not seen recently or tracing is disabled.
+    fn transaction_propagation(
+        &self,
+        hash: TxHash,
+    ) -> impl Future<Output = Result<Option<TransactionPropagation>, NetworkError>> + Send;
 }
 
 /// Info about an active peer session.
@@ -217,6 +245,8 @@ pub struct PeerInfo {
     pub eth_version: EthVersion,
     /// The Status message the peer sent for the `eth` handshake
     pub status: Arc<Status>,
+    /// The latest range of blocks the peer announced it can serve, `eth/69` only.
+    pub block_range: Option<BlockRangeUpdate>,
     /// The timestamp when the session to that peer has been established.
     pub session_established: Instant,
     /// The peer's connection kind
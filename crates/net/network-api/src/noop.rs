@@ -5,11 +5,15 @@
 
 use std::net::{IpAddr, SocketAddr};
 
+use alloy_primitives::TxHash;
 use alloy_rpc_types_admin::EthProtocolInfo;
 use enr::{secp256k1::SecretKey, Enr};
 use reth_eth_wire_types::{DisconnectReason, ProtocolVersion};
 use reth_network_peers::NodeRecord;
-use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+use reth_network_types::{
+    ConnectionsConfig, InboundFilter, PeerKind, Reputation, ReputationChangeKind,
+    TransactionPropagation,
+};
 
 use crate::{NetworkError, NetworkInfo, NetworkStatus, PeerId, PeerInfo, Peers, PeersInfo};
 
@@ -107,4 +111,19 @@ impl Peers for NoopNetwork {
     async fn reputation_by_id(&self, _peer_id: PeerId) -> Result<Option<Reputation>, NetworkError> {
         Ok(None)
     }
+
+    fn set_connection_limits(&self, _limits: ConnectionsConfig) {}
+
+    async fn inbound_filter(&self) -> Result<InboundFilter, NetworkError> {
+        Ok(Default::default())
+    }
+
+    fn set_inbound_filter(&self, _filter: InboundFilter) {}
+
+    async fn transaction_propagation(
+        &self,
+        _hash: TxHash,
+    ) -> Result<Option<TransactionPropagation>, NetworkError> {
+        Ok(None)
+    }
 }
@@ -5,7 +5,7 @@ use std::net::SocketAddr;
 
 use derive_more::Constructor;
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::{Peer, ReputationChangeKind};
+use reth_network_types::{ConnectionsConfig, InboundFilter, Peer, ReputationChangeKind};
 use tokio::sync::{mpsc, oneshot};
 
 /// Provides an API for managing the peers of the network.
@@ -46,6 +46,29 @@ impl PeersHandle {
         self.send(PeerCommand::ReputationChange(peer_id, kind));
     }
 
+    /// Replaces the connection limits of the peer set.
+    ///
+    /// Existing sessions are kept if the new limits are lower, the limits only apply to new
+    /// connections.
+    pub fn set_connection_limits(&self, limits: ConnectionsConfig) {
+        self.send(PeerCommand::SetConnectionLimits(limits));
+    }
+
+    /// Replaces the filter that decides which inbound sessions are accepted.
+    ///
+    /// Established inbound sessions that the new filter rejects by IP or peer id are disconnected.
+    pub fn set_inbound_filter(&self, filter: InboundFilter) {
+        self.send(PeerCommand::SetInboundFilter(filter));
+    }
+
+    /// Returns the filter that decides which inbound sessions are accepted.
+    pub async fn inbound_filter(&self) -> Result<InboundFilter, oneshot::error::RecvError> {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::GetInboundFilter(tx));
+
+        rx.await
+    }
+
     /// Returns a peer by its [`PeerId`], or `None` if the peer is not in the peer set.
     pub async fn peer_by_id(&self, peer_id: PeerId) -> Option<Peer> {
         let (tx, rx) = oneshot::channel();
@@ -74,6 +97,12 @@ pub enum PeerCommand {
     Remove(PeerId),
     /// Apply a reputation change to the given peer.
     ReputationChange(PeerId, ReputationChangeKind),
+    /// Replace the connection limits.
+    SetConnectionLimits(ConnectionsConfig),
+    /// Replace the filter for inbound sessions.
+    SetInboundFilter(InboundFilter),
+    /// Get the filter for inbound sessions.
+    GetInboundFilter(oneshot::Sender<InboundFilter>),
     /// Get information about a peer
     GetPeer(PeerId, oneshot::Sender<Option<Peer>>),
     /// Get node information on all peers
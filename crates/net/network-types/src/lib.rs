@@ -15,6 +15,7 @@
 /// Types related to peering.
 pub mod peers;
 pub mod session;
+pub mod transactions;
 
 /// [`BackoffKind`] definition.
 mod backoff;
@@ -27,6 +28,7 @@ pub use peers::{
     kind::PeerKind,
     reputation::{is_banned_reputation, ReputationChangeOutcome, DEFAULT_REPUTATION},
     state::PeerConnectionState,
-    ConnectionsConfig, Peer, PeersConfig,
+    AsnRange, ConnectionsConfig, InboundFilter, Peer, PeerStats, PeersConfig, PersistedPeer,
 };
-pub use session::{SessionLimits, SessionsConfig};
+pub use session::{BandwidthLimits, SessionLimits, SessionsConfig};
+pub use transactions::{PropagationEvent, PropagationKind, TransactionPropagation};
@@ -1,17 +1,17 @@
 //! Configuration for peering.
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     io::{self, ErrorKind},
     path::Path,
     time::Duration,
 };
 
 use reth_net_banlist::BanList;
-use reth_network_peers::{NodeRecord, TrustedPeer};
+use reth_network_peers::{NodeRecord, PeerId, TrustedPeer};
 use tracing::info;
 
-use crate::{BackoffKind, ReputationChangeWeights};
+use crate::{BackoffKind, InboundFilter, PeerStats, PersistedPeer, ReputationChangeWeights};
 
 /// Maximum number of available slots for outbound sessions.
 pub const DEFAULT_MAX_COUNT_PEERS_OUTBOUND: u32 = 100;
@@ -141,6 +141,9 @@ pub struct PeersConfig {
     /// Basic nodes to connect to.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub basic_nodes: HashSet<NodeRecord>,
+    /// What has been observed about peers in previous runs.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub peer_stats: HashMap<PeerId, PeerStats>,
     /// How long to ban bad peers.
     #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
     pub ban_duration: Duration,
@@ -149,6 +152,8 @@ pub struct PeersConfig {
     pub ban_list: BanList,
     /// Restrictions on connections.
     pub connection_info: ConnectionsConfig,
+    /// Which inbound sessions are accepted.
+    pub inbound_filter: InboundFilter,
     /// How to weigh reputation changes.
     pub reputation_weights: ReputationChangeWeights,
     /// How long to backoff peers that we are failed to connect to for non-fatal reasons.
@@ -162,6 +167,7 @@ impl Default for PeersConfig {
         Self {
             refill_slots_interval: Duration::from_millis(5_000),
             connection_info: Default::default(),
+            inbound_filter: Default::default(),
             reputation_weights: Default::default(),
             ban_list: Default::default(),
             // Ban peers for 12h
@@ -170,6 +176,7 @@ impl Default for PeersConfig {
             trusted_nodes: Default::default(),
             trusted_nodes_only: false,
             basic_nodes: Default::default(),
+            peer_stats: Default::default(),
             max_backoff_count: 5,
         }
     }
@@ -228,6 +235,12 @@ impl PeersConfig {
         self
     }
 
+    /// Configures which inbound sessions are accepted.
+    pub fn with_inbound_filter(mut self, inbound_filter: InboundFilter) -> Self {
+        self.inbound_filter = inbound_filter;
+        self
+    }
+
     /// Connect only to trusted nodes.
     pub const fn with_trusted_nodes_only(mut self, trusted_only: bool) -> Self {
         self.trusted_nodes_only = trusted_only;
@@ -240,6 +253,12 @@ impl PeersConfig {
         self
     }
 
+    /// What has been observed about peers in previous runs.
+    pub fn with_peer_stats(mut self, peer_stats: HashMap<PeerId, PeerStats>) -> Self {
+        self.peer_stats = peer_stats;
+        self
+    }
+
     /// Configures the max allowed backoff count.
     pub const fn with_max_backoff_count(mut self, max_backoff_count: u8) -> Self {
         self.max_backoff_count = max_backoff_count;
@@ -266,7 +285,8 @@ impl PeersConfig {
         self.connection_info.max_outbound + self.connection_info.max_inbound
     }
 
-    /// Read from file nodes available at launch. Ignored if None.
+    /// Read from file nodes available at launch, along with what has been observed about them in
+    /// previous runs. Ignored if None.
     pub fn with_basic_nodes_from_file(
         self,
         optional_file: Option<impl AsRef<Path>>,
@@ -278,8 +298,10 @@ impl PeersConfig {
             Err(e) => Err(e)?,
         };
         info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peers");
-        let nodes: HashSet<NodeRecord> = serde_json::from_reader(reader)?;
-        Ok(self.with_basic_nodes(nodes))
+        let peers: Vec<PersistedPeer> = serde_json::from_reader(reader)?;
+        let nodes = peers.iter().map(|peer| peer.record).collect();
+        let peer_stats = peers.into_iter().map(|peer| (peer.record.id, peer.stats)).collect();
+        Ok(self.with_basic_nodes(nodes).with_peer_stats(peer_stats))
     }
 
     /// Returns settings for testing
@@ -0,0 +1,114 @@
+//! Filters for inbound sessions.
+
+use std::{collections::HashSet, net::IpAddr};
+
+use ipnet::IpNet;
+use reth_network_peers::PeerId;
+
+/// An IP range that is announced by an autonomous system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AsnRange {
+    /// The IP range, e.g. `203.0.113.0/24`.
+    pub net: IpNet,
+    /// The number of the autonomous system.
+    pub asn: u32,
+}
+
+/// Decides which inbound sessions are accepted, by the IP address and the id of the remote peer.
+///
+/// Sessions from denied IP ranges or peers are always rejected. If any IP ranges or peers are
+/// allowed, only sessions from those are accepted, except for trusted peers which don't need to be
+/// allowed by peer id.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct InboundFilter {
+    /// If not empty, only IPs in these ranges are accepted.
+    pub allow_ips: Vec<IpNet>,
+    /// IPs in these ranges are rejected.
+    pub deny_ips: Vec<IpNet>,
+    /// If not empty, only these peers, or trusted peers, are accepted.
+    pub allow_peers: HashSet<PeerId>,
+    /// These peers are rejected.
+    pub deny_peers: HashSet<PeerId>,
+    /// The autonomous systems that IP ranges belong to, the most specific range of an IP applies.
+    pub asn_ranges: Vec<AsnRange>,
+    /// Maximum number of inbound sessions with peers of the same autonomous system.
+    ///
+    /// Does not apply to trusted peers and peers whose IP is in none of the `asn_ranges`.
+    pub max_peers_per_asn: Option<usize>,
+}
+
+impl InboundFilter {
+    /// Returns `true` if sessions from the given IP are accepted.
+    pub fn is_allowed_ip(&self, ip: &IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        !self.deny_ips.iter().any(|net| net.contains(&ip)) &&
+            (self.allow_ips.is_empty() || self.allow_ips.iter().any(|net| net.contains(&ip)))
+    }
+
+    /// Returns `true` if sessions with the given peer are accepted.
+    pub fn is_allowed_peer(&self, peer_id: &PeerId, is_trusted: bool) -> bool {
+        !self.deny_peers.contains(peer_id) &&
+            (is_trusted || self.allow_peers.is_empty() || self.allow_peers.contains(peer_id))
+    }
+
+    /// Returns the autonomous system the given IP belongs to, if known.
+    pub fn asn(&self, ip: &IpAddr) -> Option<u32> {
+        let ip = ip.to_canonical();
+        self.asn_ranges
+            .iter()
+            .filter(|range| range.net.contains(&ip))
+            .max_by_key(|range| range.net.prefix_len())
+            .map(|range| range.asn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_ips() {
+        let filter = InboundFilter {
+            allow_ips: vec!["10.0.0.0/8".parse().unwrap()],
+            deny_ips: vec!["10.1.0.0/16".parse().unwrap()],
+            ..Default::default()
+        };
+        assert!(filter.is_allowed_ip(&"10.0.0.1".parse().unwrap()));
+        assert!(filter.is_allowed_ip(&"::ffff:10.0.0.1".parse().unwrap()));
+        assert!(!filter.is_allowed_ip(&"10.1.0.1".parse().unwrap()));
+        assert!(!filter.is_allowed_ip(&"192.168.0.1".parse().unwrap()));
+        assert!(InboundFilter::default().is_allowed_ip(&"192.168.0.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn filter_peers() {
+        let allowed = PeerId::with_last_byte(1);
+        let denied = PeerId::with_last_byte(2);
+        let other = PeerId::with_last_byte(3);
+        let filter = InboundFilter {
+            allow_peers: HashSet::from([allowed]),
+            deny_peers: HashSet::from([denied]),
+            ..Default::default()
+        };
+        assert!(filter.is_allowed_peer(&allowed, false));
+        assert!(!filter.is_allowed_peer(&other, false));
+        assert!(filter.is_allowed_peer(&other, true));
+        assert!(!filter.is_allowed_peer(&denied, true));
+    }
+
+    #[test]
+    fn most_specific_asn() {
+        let filter = InboundFilter {
+            asn_ranges: vec![
+                AsnRange { net: "10.0.0.0/8".parse().unwrap(), asn: 1 },
+                AsnRange { net: "10.1.0.0/16".parse().unwrap(), asn: 2 },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(filter.asn(&"10.0.0.1".parse().unwrap()), Some(1));
+        assert_eq!(filter.asn(&"10.1.0.1".parse().unwrap()), Some(2));
+        assert_eq!(filter.asn(&"192.168.0.1".parse().unwrap()), None);
+    }
+}
@@ -1,11 +1,15 @@
 pub mod addr;
 pub mod config;
+pub mod filter;
 pub mod kind;
 pub mod reputation;
 pub mod state;
+pub mod stats;
 
 pub use config::{ConnectionsConfig, PeersConfig};
+pub use filter::{AsnRange, InboundFilter};
 pub use reputation::{Reputation, ReputationChange, ReputationChangeKind, ReputationChangeWeights};
+pub use stats::{PeerStats, PersistedPeer};
 
 use reth_ethereum_forks::ForkId;
 use tracing::trace;
@@ -35,6 +39,8 @@ pub struct Peer {
     /// Counts number of times the peer was backed off due to a severe
     /// [`BackoffKind`](crate::BackoffKind).
     pub severe_backoff_counter: u8,
+    /// What has been observed about the peer, including previous runs.
+    pub stats: PeerStats,
 }
 
 // === impl Peer ===
@@ -66,6 +72,7 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            stats: Default::default(),
         }
     }
 
@@ -0,0 +1,133 @@
+//! Statistics about peers that are persisted across restarts.
+
+use std::time::Duration;
+
+use reth_network_peers::NodeRecord;
+use serde::{Deserialize, Serialize};
+
+/// How much a new latency sample contributes to the average latency, in percent.
+const LATENCY_SAMPLE_IMPACT: u64 = 20;
+
+/// What has been observed about a peer in previous sessions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PeerStats {
+    /// Moving average of the latency of the peer's responses, in milliseconds.
+    pub latency_ms: Option<u64>,
+    /// Number of responses that contained the requested data.
+    pub good_responses: u64,
+    /// Number of requests that failed or were answered with an empty response.
+    pub bad_responses: u64,
+    /// The eth protocol version of the last session.
+    pub eth_version: Option<u8>,
+}
+
+impl PeerStats {
+    /// Returns the average latency of the peer's responses, if any.
+    pub fn latency(&self) -> Option<Duration> {
+        self.latency_ms.map(Duration::from_millis)
+    }
+
+    /// Records a response with the given latency, that is good if it contained the requested
+    /// data.
+    pub fn on_response(&mut self, latency: Duration, good: bool) {
+        let sample = latency.as_millis() as u64;
+        self.latency_ms = Some(match self.latency_ms {
+            Some(average) => {
+                (average * (100 - LATENCY_SAMPLE_IMPACT) + sample * LATENCY_SAMPLE_IMPACT) / 100
+            }
+            None => sample,
+        });
+        if good {
+            self.good_responses += 1;
+        } else {
+            self.bad_responses += 1;
+        }
+    }
+
+    /// Records a failed request.
+    pub fn on_failed_request(&mut self) {
+        self.bad_responses += 1;
+    }
+
+    /// Returns `true` if the peer mostly served bad responses.
+    pub const fn is_likely_bad(&self) -> bool {
+        self.bad_responses > self.good_responses
+    }
+}
+
+/// An entry of the peers file.
+///
+/// Peers files that only contain the [`NodeRecord`]s of the peers are also accepted, in which case
+/// the stats are empty.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "PersistedPeerRepr")]
+pub struct PersistedPeer {
+    /// The record of the peer.
+    pub record: NodeRecord,
+    /// What has been observed about the peer.
+    pub stats: PeerStats,
+}
+
+impl From<NodeRecord> for PersistedPeer {
+    fn from(record: NodeRecord) -> Self {
+        Self { record, stats: Default::default() }
+    }
+}
+
+/// The formats a [`PersistedPeer`] can be read from.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PersistedPeerRepr {
+    Record(NodeRecord),
+    Peer {
+        record: NodeRecord,
+        #[serde(default)]
+        stats: PeerStats,
+    },
+}
+
+impl From<PersistedPeerRepr> for PersistedPeer {
+    fn from(repr: PersistedPeerRepr) -> Self {
+        match repr {
+            PersistedPeerRepr::Record(record) => record.into(),
+            PersistedPeerRepr::Peer { record, stats } => Self { record, stats },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PEER: &str = "enode://6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0@10.3.58.6:30303";
+
+    #[test]
+    fn average_latency() {
+        let mut stats = PeerStats::default();
+        stats.on_response(Duration::from_millis(100), true);
+        assert_eq!(stats.latency(), Some(Duration::from_millis(100)));
+        stats.on_response(Duration::from_millis(600), false);
+        assert_eq!(stats.latency(), Some(Duration::from_millis(200)));
+        assert!(!stats.is_likely_bad());
+        stats.on_failed_request();
+        assert!(stats.is_likely_bad());
+    }
+
+    #[test]
+    fn read_legacy_peers_file() {
+        let record: NodeRecord = PEER.parse().unwrap();
+        let peer = PersistedPeer {
+            record,
+            stats: PeerStats { latency_ms: Some(50), eth_version: Some(68), ..Default::default() },
+        };
+
+        let legacy = format!("[\"{PEER}\"]");
+        let peers: Vec<PersistedPeer> = serde_json::from_str(&legacy).unwrap();
+        assert_eq!(peers, vec![record.into()]);
+
+        let json = serde_json::to_string(&vec![peer.clone()]).unwrap();
+        let peers: Vec<PersistedPeer> = serde_json::from_str(&json).unwrap();
+        assert_eq!(peers, vec![peer]);
+    }
+}
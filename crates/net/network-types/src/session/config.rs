@@ -50,6 +50,10 @@ pub struct SessionsConfig {
     pub protocol_breach_request_timeout: Duration,
     /// The timeout after which a pending session attempt is considered failed.
     pub pending_session_timeout: Duration,
+    /// Bandwidth limits to enforce.
+    ///
+    /// By default, no limits will be enforced.
+    pub bandwidth: BandwidthLimits,
 }
 
 impl Default for SessionsConfig {
@@ -69,6 +73,7 @@ impl Default for SessionsConfig {
             initial_internal_request_timeout: INITIAL_REQUEST_TIMEOUT,
             protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
             pending_session_timeout: PENDING_SESSION_TIMEOUT,
+            bandwidth: Default::default(),
         }
     }
 }
@@ -100,6 +105,33 @@ impl SessionsConfig {
         }
         self
     }
+
+    /// Sets the bandwidth limits of the sessions.
+    pub const fn with_bandwidth_limits(mut self, bandwidth: BandwidthLimits) -> Self {
+        self.bandwidth = bandwidth;
+        self
+    }
+}
+
+/// Bandwidth limits for sessions, in bytes per second.
+///
+/// The limits apply to the size of the `eth` messages, before compression. Messages are never
+/// split, a session that exceeds a limit pauses sending or reading messages until it's back
+/// within the limit.
+///
+/// By default, no bandwidth limits will be enforced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct BandwidthLimits {
+    /// Maximum upload rate of all sessions combined.
+    pub upload: Option<u64>,
+    /// Maximum download rate of all sessions combined.
+    pub download: Option<u64>,
+    /// Maximum upload rate of a single session.
+    pub peer_upload: Option<u64>,
+    /// Maximum download rate of a single session.
+    pub peer_download: Option<u64>,
 }
 
 /// Limits for sessions.
@@ -0,0 +1,57 @@
+//! Traces of how transactions propagate between peers.
+
+use alloy_primitives::TxHash;
+use reth_network_peers::PeerId;
+
+/// How a transaction was exchanged with a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum PropagationKind {
+    /// Only the hash of the transaction was announced.
+    Hash,
+    /// The full transaction was sent.
+    Full,
+}
+
+/// An announcement or broadcast of a transaction that was received from or sent to a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct PropagationEvent {
+    /// The peer the transaction was exchanged with.
+    pub peer_id: PeerId,
+    /// Whether the hash or the full transaction was exchanged.
+    pub kind: PropagationKind,
+    /// Milliseconds since the transaction was first seen.
+    pub elapsed_ms: u64,
+}
+
+/// How a transaction was received from and forwarded to peers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct TransactionPropagation {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// Unix timestamp in milliseconds at which the transaction was first seen.
+    pub first_seen: u64,
+    /// The peer the transaction was first received from.
+    ///
+    /// `None` if the transaction was first seen in the local pool, e.g. because it was submitted
+    /// over RPC.
+    pub first_seen_from: Option<PeerId>,
+    /// Announcements and broadcasts of the transaction that were received from peers, in order.
+    pub received: Vec<PropagationEvent>,
+    /// Announcements and broadcasts of the transaction that were sent to peers, in order.
+    pub propagated: Vec<PropagationEvent>,
+}
@@ -6,7 +6,7 @@ use reth_chainspec::{ChainSpec, MAINNET};
 use reth_discv4::{Discv4Config, Discv4ConfigBuilder, NatResolver, DEFAULT_DISCOVERY_ADDRESS};
 use reth_discv5::NetworkStackId;
 use reth_dns_discovery::DnsDiscoveryConfig;
-use reth_eth_wire::{HelloMessage, HelloMessageWithProtocols, Status};
+use reth_eth_wire::{EthVersion, HelloMessage, HelloMessageWithProtocols, Status};
 use reth_network_peers::{mainnet_nodes, pk2id, sepolia_nodes, PeerId, TrustedPeer};
 use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_primitives::{ForkFilter, Head};
@@ -15,8 +15,10 @@ use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use secp256k1::SECP256K1;
 
 use crate::{
+    discovery::DiscoveryRateLimits,
     error::NetworkError,
     import::{BlockImport, ProofOfStakeBlockImport},
+    scoring::PeerScorer,
     transactions::TransactionsManagerConfig,
     NetworkHandle, NetworkManager,
 };
@@ -50,6 +52,8 @@ pub struct NetworkConfig<C> {
     pub discovery_v4_config: Option<Discv4Config>,
     /// How to set up discovery version 5.
     pub discovery_v5_config: Option<reth_discv5::Config>,
+    /// Limits on the rate at which newly discovered peers are passed on, per discovery protocol.
+    pub discovery_rate_limits: DiscoveryRateLimits,
     /// Address to listen for incoming connections
     pub listener_addr: SocketAddr,
     /// How to instantiate peer manager.
@@ -67,6 +71,11 @@ pub struct NetworkConfig<C> {
     pub fork_filter: ForkFilter,
     /// The block importer type.
     pub block_import: Box<dyn BlockImport>,
+    /// How to score peers.
+    ///
+    /// If not set, the [`ReputationChangeWeights`](reth_network_types::ReputationChangeWeights)
+    /// of the [`PeersConfig`] are used.
+    pub peer_scorer: Option<Box<dyn PeerScorer>>,
     /// The default mode of the network.
     pub network_mode: NetworkMode,
     /// The executor to use for spawning tasks.
@@ -160,6 +169,8 @@ pub struct NetworkConfigBuilder {
     discovery_v4_builder: Option<Discv4ConfigBuilder>,
     /// How to set up discovery version 5.
     discovery_v5_builder: Option<reth_discv5::ConfigBuilder>,
+    /// Limits on the rate of newly discovered peers, per discovery protocol.
+    discovery_rate_limits: DiscoveryRateLimits,
     /// All boot nodes to start network discovery with.
     boot_nodes: HashSet<TrustedPeer>,
     /// Address to use for discovery
@@ -178,6 +189,8 @@ pub struct NetworkConfigBuilder {
     executor: Option<Box<dyn TaskSpawner>>,
     /// Sets the hello message for the p2p handshake in `RLPx`
     hello_message: Option<HelloMessageWithProtocols>,
+    /// Whether the `eth/69` protocol is offered to peers.
+    eth69: bool,
     /// The executor to use for spawning tasks.
     extra_protocols: RlpxSubProtocols,
     /// Head used to start set for the fork filter and status.
@@ -186,6 +199,8 @@ pub struct NetworkConfigBuilder {
     tx_gossip_disabled: bool,
     /// The block importer type
     block_import: Option<Box<dyn BlockImport>>,
+    /// How to score peers
+    peer_scorer: Option<Box<dyn PeerScorer>>,
     /// How to instantiate transactions manager.
     transactions_manager_config: TransactionsManagerConfig,
 }
@@ -206,6 +221,7 @@ impl NetworkConfigBuilder {
             dns_discovery_config: Some(Default::default()),
             discovery_v4_builder: Some(Default::default()),
             discovery_v5_builder: None,
+            discovery_rate_limits: Default::default(),
             boot_nodes: Default::default(),
             discovery_addr: None,
             listener_addr: None,
@@ -215,10 +231,12 @@ impl NetworkConfigBuilder {
             network_mode: Default::default(),
             executor: None,
             hello_message: None,
+            eth69: false,
             extra_protocols: Default::default(),
             head: None,
             tx_gossip_disabled: false,
             block_import: None,
+            peer_scorer: None,
             transactions_manager_config: Default::default(),
         }
     }
@@ -278,6 +296,15 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Sets whether the `eth/69` protocol is offered to peers, in addition to the protocols of the
+    /// `HelloMessage`.
+    ///
+    /// Peers that don't support `eth/69` fall back to older versions.
+    pub const fn eth69(mut self, enable: bool) -> Self {
+        self.eth69 = enable;
+        self
+    }
+
     /// Set a custom peer config for how peers are handled
     pub fn peer_config(mut self, config: PeersConfig) -> Self {
         self.peers_config = Some(config);
@@ -370,6 +397,13 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Sets the limits on the rate at which newly discovered peers are passed on to the network,
+    /// per discovery protocol.
+    pub const fn discovery_rate_limits(mut self, limits: DiscoveryRateLimits) -> Self {
+        self.discovery_rate_limits = limits;
+        self
+    }
+
     /// Sets the dns discovery config to use.
     pub fn dns_discovery(mut self, config: DnsDiscoveryConfig) -> Self {
         self.dns_discovery_config = Some(config);
@@ -459,6 +493,15 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Sets the scorer that decides how the reputation of peers changes.
+    ///
+    /// Replaces the [`ReputationChangeWeights`](reth_network_types::ReputationChangeWeights) of
+    /// the peers config.
+    pub fn peer_scorer(mut self, peer_scorer: Box<dyn PeerScorer>) -> Self {
+        self.peer_scorer = Some(peer_scorer);
+        self
+    }
+
     /// Convenience function for creating a [`NetworkConfig`] with a noop provider that does
     /// nothing.
     pub fn build_with_noop_provider(
@@ -480,6 +523,7 @@ impl NetworkConfigBuilder {
             mut dns_discovery_config,
             discovery_v4_builder,
             mut discovery_v5_builder,
+            discovery_rate_limits,
             boot_nodes,
             discovery_addr,
             listener_addr,
@@ -489,10 +533,12 @@ impl NetworkConfigBuilder {
             network_mode,
             executor,
             hello_message,
+            eth69,
             extra_protocols,
             head,
             tx_gossip_disabled,
             block_import,
+            peer_scorer,
             transactions_manager_config,
         } = self;
 
@@ -510,6 +556,10 @@ impl NetworkConfigBuilder {
         let mut hello_message =
             hello_message.unwrap_or_else(|| HelloMessage::builder(peer_id).build());
         hello_message.port = listener_addr.port();
+        if eth69 {
+            // fails if already offered
+            let _ = hello_message.try_add_protocol(EthVersion::Eth69.into());
+        }
 
         let head = head.unwrap_or_else(|| Head {
             hash: chain_spec.genesis_hash(),
@@ -543,12 +593,14 @@ impl NetworkConfigBuilder {
             dns_discovery_config,
             discovery_v4_config: discovery_v4_builder.map(|builder| builder.build()),
             discovery_v5_config: discovery_v5_builder.map(|builder| builder.build()),
+            discovery_rate_limits,
             discovery_v4_addr: discovery_addr.unwrap_or(DEFAULT_DISCOVERY_ADDRESS),
             listener_addr,
             peers_config: peers_config.unwrap_or_default(),
             sessions_config: sessions_config.unwrap_or_default(),
             chain_spec,
             block_import: block_import.unwrap_or_else(|| Box::<ProofOfStakeBlockImport>::default()),
+            peer_scorer,
             network_mode,
             executor: executor.unwrap_or_else(|| Box::<TokioTaskExecutor>::default()),
             status,
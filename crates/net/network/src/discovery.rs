@@ -6,6 +6,7 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
+    time::{Duration, Instant},
 };
 
 use enr::Enr;
@@ -34,6 +35,19 @@ use crate::{
 /// Default is 10 000 peers.
 pub const DEFAULT_MAX_CAPACITY_DISCOVERED_PEERS_CACHE: u32 = 10_000;
 
+/// Limits on the number of newly discovered peers per second that are passed on to the network,
+/// per discovery protocol.
+///
+/// When discv4 and discv5 run side by side, this keeps one protocol from crowding out the peers
+/// found by the other. Peers discovered over DNS are never limited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiscoveryRateLimits {
+    /// Max number of new peers discovered via discv4 per second. Unlimited if not set.
+    pub discv4: Option<u32>,
+    /// Max number of new peers discovered via discv5 per second. Unlimited if not set.
+    pub discv5: Option<u32>,
+}
+
 /// An abstraction over the configured discovery protocol.
 ///
 /// Listens for new discovered nodes and emits events for discovered nodes and their
@@ -66,6 +80,10 @@ pub struct Discovery {
     queued_events: VecDeque<DiscoveryEvent>,
     /// List of listeners subscribed to discovery events.
     discovery_listeners: Vec<mpsc::UnboundedSender<DiscoveryEvent>>,
+    /// Limits the rate of new peers discovered via discv4.
+    discv4_rate_limiter: Option<DiscoveredPeersRateLimiter>,
+    /// Limits the rate of new peers discovered via discv5.
+    discv5_rate_limiter: Option<DiscoveredPeersRateLimiter>,
 }
 
 impl Discovery {
@@ -136,9 +154,20 @@ impl Discovery {
             _dns_disc_service,
             _dns_discovery,
             dns_discovery_updates,
+            discv4_rate_limiter: None,
+            discv5_rate_limiter: None,
         })
     }
 
+    /// Sets the limits on the rate at which newly discovered peers are passed on, per discovery
+    /// protocol.
+    pub fn with_rate_limits(mut self, limits: DiscoveryRateLimits) -> Self {
+        let DiscoveryRateLimits { discv4, discv5 } = limits;
+        self.discv4_rate_limiter = discv4.map(DiscoveredPeersRateLimiter::new);
+        self.discv5_rate_limiter = discv5.map(DiscoveredPeersRateLimiter::new);
+        self
+    }
+
     /// Registers a listener for receiving [`DiscoveryEvent`] updates.
     pub(crate) fn add_listener(&mut self, tx: mpsc::UnboundedSender<DiscoveryEvent>) {
         self.discovery_listeners.push(tx);
@@ -150,13 +179,20 @@ impl Discovery {
         self.discovery_listeners.retain_mut(|listener| listener.send(event.clone()).is_ok());
     }
 
-    /// Updates the `eth:ForkId` field in discv4.
+    /// Updates the `eth:ForkId` field in discv4, and the fork kv-pair in discv5.
     pub(crate) fn update_fork_id(&self, fork_id: ForkId) {
         if let Some(discv4) = &self.discv4 {
             // use forward-compatible forkid entry
             discv4.set_eip868_rlp(b"eth".to_vec(), EnrForkIdEntry::from(fork_id))
         }
-        // todo: update discv5 enr
+        if let Some(discv5) = &self.discv5 {
+            if let Some(fork_key) = discv5.fork_key() {
+                discv5.encode_and_set_eip868_in_local_enr(
+                    fork_key.to_vec(),
+                    EnrForkIdEntry::from(fork_id),
+                )
+            }
+        }
     }
 
     /// Bans the [`IpAddr`] in the discovery service.
@@ -221,10 +257,35 @@ impl Discovery {
             })
     }
 
+    /// Returns `true` if a peer discovered via the given protocol should be passed on, w.r.t. the
+    /// configured rate limits. Peers that have been discovered before always pass.
+    fn admit_discovered_peer(&mut self, peer_id: PeerId, protocol: DiscoveryProtocol) -> bool {
+        let rate_limiter = match protocol {
+            DiscoveryProtocol::V4 => self.discv4_rate_limiter.as_mut(),
+            DiscoveryProtocol::V5 => self.discv5_rate_limiter.as_mut(),
+        };
+        let Some(rate_limiter) = rate_limiter else { return true };
+
+        if self.discovered_nodes.peek(&peer_id).is_some() || rate_limiter.try_admit(Instant::now())
+        {
+            return true
+        }
+
+        trace!(target: "net::discovery",
+            ?peer_id,
+            ?protocol,
+            "dropped discovered peer, rate limit exceeded"
+        );
+
+        false
+    }
+
     fn on_discv4_update(&mut self, update: DiscoveryUpdate) {
         match update {
             DiscoveryUpdate::Added(record) | DiscoveryUpdate::DiscoveredAtCapacity(record) => {
-                self.on_node_record_update(record, None);
+                if self.admit_discovered_peer(record.id, DiscoveryProtocol::V4) {
+                    self.on_node_record_update(record, None);
+                }
             }
             DiscoveryUpdate::EnrForkId(node, fork_id) => {
                 self.queued_events.push_back(DiscoveryEvent::EnrForkId(node.id, fork_id))
@@ -263,7 +324,9 @@ impl Discovery {
                     if let Some(DiscoveredPeer { node_record, fork_id }) =
                         discv5.on_discv5_update(update)
                     {
-                        self.on_node_record_update(node_record, fork_id);
+                        if self.admit_discovered_peer(node_record.id, DiscoveryProtocol::V5) {
+                            self.on_node_record_update(node_record, fork_id);
+                        }
                     }
                 }
             }
@@ -289,6 +352,44 @@ impl Discovery {
     }
 }
 
+/// Discovery protocol via which a peer was discovered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiscoveryProtocol {
+    V4,
+    V5,
+}
+
+/// Limits the number of newly discovered peers that pass per second.
+#[derive(Debug)]
+struct DiscoveredPeersRateLimiter {
+    /// Max number of peers that pass per second.
+    max_per_second: u32,
+    /// Start of the current one second window.
+    window_start: Instant,
+    /// Number of peers that passed in the current window.
+    admitted: u32,
+}
+
+impl DiscoveredPeersRateLimiter {
+    fn new(max_per_second: u32) -> Self {
+        Self { max_per_second, window_start: Instant::now(), admitted: 0 }
+    }
+
+    /// Returns `true` if another peer may pass at the given point in time.
+    fn try_admit(&mut self, now: Instant) -> bool {
+        if now.saturating_duration_since(self.window_start) >= Duration::from_secs(1) {
+            self.window_start = now;
+            self.admitted = 0;
+        }
+        if self.admitted >= self.max_per_second {
+            return false
+        }
+        self.admitted += 1;
+
+        true
+    }
+}
+
 impl Stream for Discovery {
     type Item = DiscoveryEvent;
 
@@ -324,6 +425,8 @@ impl Discovery {
             dns_discovery_updates: None,
             _dns_disc_service: None,
             discovery_listeners: Default::default(),
+            discv4_rate_limiter: None,
+            discv5_rate_limiter: None,
         }
     }
 }
@@ -352,6 +455,34 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn test_discv4_rate_limit() {
+        let mut discovery = Discovery::noop()
+            .with_rate_limits(DiscoveryRateLimits { discv4: Some(2), discv5: None });
+
+        for port in 30303..30306 {
+            let record = NodeRecord {
+                address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                tcp_port: port,
+                udp_port: port,
+                id: PeerId::random(),
+            };
+            discovery.on_discv4_update(DiscoveryUpdate::Added(record));
+        }
+
+        assert_eq!(discovery.queued_events.len(), 2);
+    }
+
+    #[test]
+    fn test_discovered_peers_rate_limiter_window() {
+        let mut limiter = DiscoveredPeersRateLimiter::new(1);
+        let now = Instant::now();
+
+        assert!(limiter.try_admit(now));
+        assert!(!limiter.try_admit(now));
+        assert!(limiter.try_admit(now + Duration::from_secs(1)));
+    }
+
     use reth_discv4::Discv4ConfigBuilder;
     use reth_discv5::{enr::EnrCombinedKeyWrapper, enr_to_discv4_id};
     use tracing::trace;
@@ -11,6 +11,7 @@ use std::{
         Arc,
     },
     task::{Context, Poll},
+    time::Duration,
 };
 
 use futures::StreamExt;
@@ -22,7 +23,7 @@ use reth_network_p2p::{
     priority::Priority,
 };
 use reth_network_peers::PeerId;
-use reth_network_types::ReputationChangeKind;
+use reth_network_types::{PeerStats, ReputationChangeKind};
 use reth_primitives::{BlockBody, Header, B256};
 use tokio::sync::{mpsc, mpsc::UnboundedSender, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -81,6 +82,7 @@ impl StateFetcher {
         best_hash: B256,
         best_number: u64,
         timeout: Arc<AtomicU64>,
+        stats: PeerStats,
     ) {
         self.peers.insert(
             peer_id,
@@ -89,7 +91,8 @@ impl StateFetcher {
                 best_hash,
                 best_number,
                 timeout,
-                last_response_likely_bad: false,
+                previous_latency: stats.latency(),
+                last_response_likely_bad: stats.is_likely_bad(),
             },
         );
     }
@@ -146,8 +149,9 @@ impl StateFetcher {
                 continue
             }
 
-            // replace best peer if this peer has better rtt
-            if maybe_better.1.timeout() < best_peer.1.timeout() &&
+            // replace best peer if this peer has better rtt, or was faster in previous sessions
+            // if the rtt is the same, e.g. because neither peer has responded yet
+            if maybe_better.1.rank() < best_peer.1.rank() &&
                 !maybe_better.1.last_response_likely_bad
             {
                 best_peer = maybe_better;
@@ -343,6 +347,8 @@ struct Peer {
     best_number: u64,
     /// Tracks the current timeout value we use for the peer.
     timeout: Arc<AtomicU64>,
+    /// The latency of the peer observed in previous sessions, if any.
+    previous_latency: Option<Duration>,
     /// Tracks whether the peer has recently responded with a likely bad response.
     ///
     /// This is used to de-rank the peer if there are other peers available.
@@ -356,6 +362,11 @@ impl Peer {
     fn timeout(&self) -> u64 {
         self.timeout.load(Ordering::Relaxed)
     }
+
+    /// Returns the rank of the peer, lower is better.
+    fn rank(&self) -> (u64, Duration) {
+        (self.timeout(), self.previous_latency.unwrap_or(Duration::MAX))
+    }
 }
 
 /// Tracks the state of an individual peer
@@ -503,8 +514,20 @@ mod tests {
         // Add a few random peers
         let peer1 = B512::random();
         let peer2 = B512::random();
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(1)));
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(1)));
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            1,
+            Arc::new(AtomicU64::new(1)),
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer2,
+            B256::random(),
+            2,
+            Arc::new(AtomicU64::new(1)),
+            Default::default(),
+        );
 
         let first_peer = fetcher.next_best_peer().unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
@@ -530,9 +553,27 @@ mod tests {
 
         let peer2_timeout = Arc::new(AtomicU64::new(300));
 
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(30)));
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::clone(&peer2_timeout));
-        fetcher.new_active_peer(peer3, B256::random(), 3, Arc::new(AtomicU64::new(50)));
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            1,
+            Arc::new(AtomicU64::new(30)),
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer2,
+            B256::random(),
+            2,
+            Arc::clone(&peer2_timeout),
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer3,
+            B256::random(),
+            3,
+            Arc::new(AtomicU64::new(50)),
+            Default::default(),
+        );
 
         // Must always get peer1 (lowest timeout)
         assert_eq!(fetcher.next_best_peer(), Some(peer1));
@@ -544,6 +585,29 @@ mod tests {
         assert_eq!(fetcher.next_best_peer(), Some(peer2));
     }
 
+    #[tokio::test]
+    async fn test_peer_prioritization_by_previous_sessions() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+        let peer1 = B512::random();
+        let peer2 = B512::random();
+        let peer3 = B512::random();
+
+        let stats = |latency_ms, bad_responses| PeerStats {
+            latency_ms: Some(latency_ms),
+            bad_responses,
+            ..Default::default()
+        };
+        let timeout = || Arc::new(AtomicU64::new(100));
+        fetcher.new_active_peer(peer1, B256::random(), 1, timeout(), stats(300, 0));
+        fetcher.new_active_peer(peer2, B256::random(), 2, timeout(), stats(50, 0));
+        fetcher.new_active_peer(peer3, B256::random(), 3, timeout(), stats(10, 1));
+
+        // peer3 was faster, but mostly served bad responses
+        assert_eq!(fetcher.next_best_peer(), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(), Some(peer2));
+    }
+
     #[tokio::test]
     async fn test_on_block_headers_response() {
         let manager = PeersManager::new(PeersConfig::default());
@@ -600,6 +664,7 @@ mod tests {
             Default::default(),
             Default::default(),
             Default::default(),
+            Default::default(),
         );
 
         let (req, header) = request_pair();
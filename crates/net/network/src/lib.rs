@@ -122,6 +122,8 @@ pub mod import;
 pub mod message;
 pub mod peers;
 pub mod protocol;
+pub mod scoring;
+pub mod snap;
 pub mod transactions;
 
 mod budget;
@@ -143,7 +145,7 @@ pub use reth_network_api::{
     NetworkEventListenerProvider, NetworkInfo, PeerRequest, PeerRequestSender, Peers, PeersInfo,
 };
 pub use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState};
-pub use reth_network_types::{PeersConfig, SessionsConfig};
+pub use reth_network_types::{BandwidthLimits, ConnectionsConfig, PeersConfig, SessionsConfig};
 pub use session::{
     ActiveSessionHandle, ActiveSessionMessage, Direction, EthRlpxConnection, PeerInfo,
     PendingSessionEvent, PendingSessionHandle, PendingSessionHandshakeError, SessionCommand,
@@ -152,7 +154,7 @@ pub use session::{
 
 pub use builder::NetworkBuilder;
 pub use config::{NetworkConfig, NetworkConfigBuilder};
-pub use discovery::Discovery;
+pub use discovery::{Discovery, DiscoveryRateLimits};
 pub use fetch::FetchClient;
 pub use flattened_response::FlattenedResponse;
 pub use manager::NetworkManager;
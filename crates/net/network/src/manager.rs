@@ -175,11 +175,13 @@ impl NetworkManager {
             discovery_v4_addr,
             mut discovery_v4_config,
             mut discovery_v5_config,
+            discovery_rate_limits,
             listener_addr,
             peers_config,
             sessions_config,
             chain_spec,
             block_import,
+            peer_scorer,
             network_mode,
             boot_nodes,
             executor,
@@ -192,7 +194,10 @@ impl NetworkManager {
             transactions_manager_config: _,
         } = config;
 
-        let peers_manager = PeersManager::new(peers_config);
+        let mut peers_manager = PeersManager::new(peers_config);
+        if let Some(peer_scorer) = peer_scorer {
+            peers_manager = peers_manager.with_scorer(peer_scorer);
+        }
         let peers_handle = peers_manager.handle();
 
         let incoming = ConnectionListener::bind(listener_addr).await.map_err(|err| {
@@ -225,7 +230,8 @@ impl NetworkManager {
             discovery_v5_config,
             dns_discovery_config,
         )
-        .await?;
+        .await?
+        .with_rate_limits(discovery_rate_limits);
         // need to retrieve the addr here since provided port could be `0`
         let local_peer_id = discovery.local_id();
         let discv4 = discovery.discv4();
@@ -355,10 +361,10 @@ impl NetworkManager {
         self.swarm.state().peers().handle()
     }
 
-    /// Collect the peers from the [`NetworkManager`] and write them to the given
-    /// `persistent_peers_file`.
+    /// Collect the peers from the [`NetworkManager`], along with what has been observed about
+    /// them, and write them to the given `persistent_peers_file`.
     pub fn write_peers_to_file(&self, persistent_peers_file: &Path) -> Result<(), FsPathError> {
-        let known_peers = self.all_peers().collect::<Vec<_>>();
+        let known_peers = self.swarm.state().peers().iter_persisted_peers().collect::<Vec<_>>();
         persistent_peers_file.parent().map(fs::create_dir_all).transpose()?;
         reth_fs_util::write_json_file(persistent_peers_file, &known_peers)?;
         Ok(())
@@ -539,6 +545,9 @@ impl NetworkManager {
                     msg,
                 });
             }
+            PeerMessage::BlockRangeUpdate(range) => {
+                self.swarm.sessions_mut().on_block_range_update(&peer_id, range);
+            }
             PeerMessage::SendTransactions(_) => {
                 unreachable!("Not emitted by session")
             }
@@ -624,6 +633,9 @@ impl NetworkManager {
                     self.swarm.state_mut().update_fork_id(transition.current);
                 }
             }
+            NetworkHandleMessage::EarliestBlockUpdate { earliest } => {
+                self.swarm.sessions_mut().update_earliest_block(earliest);
+            }
             NetworkHandleMessage::GetPeerInfos(tx) => {
                 let _ = tx.send(self.get_peer_infos());
             }
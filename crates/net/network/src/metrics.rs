@@ -87,6 +87,42 @@ pub struct SessionManagerMetrics {
     pub(crate) total_outgoing_peer_messages_dropped: Counter,
 }
 
+/// Metrics for the bandwidth limits of sessions.
+#[derive(Metrics)]
+#[metrics(scope = "network.bandwidth")]
+pub struct BandwidthMetrics {
+    /// Number of times a session was throttled before sending a message.
+    pub(crate) throttled_uploads: Counter,
+    /// Number of times a session was throttled before reading a message.
+    pub(crate) throttled_downloads: Counter,
+}
+
+/// Metrics for sessions rejected by the inbound filter.
+#[derive(Metrics)]
+#[metrics(scope = "network.inbound_filter")]
+pub struct InboundFilterMetrics {
+    /// Number of incoming connections rejected because of their ip address.
+    pub(crate) rejected_ips: Counter,
+    /// Number of incoming sessions rejected because of their peer id.
+    pub(crate) rejected_peers: Counter,
+    /// Number of incoming sessions rejected because the per-ASN limit was reached.
+    pub(crate) rejected_asn_limit: Counter,
+}
+
+/// Propagation delays of the transactions sampled by the
+/// [`TransactionsManager`](crate::transactions::TransactionsManager).
+#[derive(Metrics)]
+#[metrics(scope = "network.transaction_propagation")]
+pub struct TransactionPropagationMetrics {
+    /// Time in seconds from first receiving a transaction from a peer until forwarding it.
+    pub(crate) forward_delay: Histogram,
+    /// Time in seconds from first receiving a transaction until another peer announces or sends
+    /// it.
+    pub(crate) announcement_delay: Histogram,
+    /// Number of transactions that were sampled.
+    pub(crate) sampled_transactions: Counter,
+}
+
 /// Metrics for the [`TransactionsManager`](crate::transactions::TransactionsManager).
 #[derive(Metrics)]
 #[metrics(scope = "network")]
@@ -94,6 +130,8 @@ pub struct TransactionsManagerMetrics {
     /* ================ BROADCAST ================ */
     /// Total number of propagated transactions
     pub(crate) propagated_transactions: Counter,
+    /// Total number of local transactions that were rebroadcast
+    pub(crate) rebroadcast_local_transactions: Counter,
     /// Total number of reported bad transactions
     pub(crate) reported_bad_transactions: Counter,
 
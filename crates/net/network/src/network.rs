@@ -21,8 +21,11 @@ use reth_network_p2p::{
     BlockClient,
 };
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::{PeerAddr, PeerKind, Reputation, ReputationChangeKind};
-use reth_primitives::{Head, TransactionSigned, B256};
+use reth_network_types::{
+    ConnectionsConfig, InboundFilter, PeerAddr, PeerKind, Reputation, ReputationChangeKind,
+    TransactionPropagation,
+};
+use reth_primitives::{BlockNumber, Head, TransactionSigned, TxHash, B256};
 use reth_tokio_util::{EventSender, EventStream};
 use secp256k1::SecretKey;
 use tokio::sync::{
@@ -105,6 +108,11 @@ impl NetworkHandle {
         self.send_message(NetworkHandleMessage::StatusUpdate { head });
     }
 
+    /// Update the earliest block the node can serve, announced to `eth/69` peers.
+    pub fn update_earliest_block(&self, earliest: BlockNumber) {
+        self.send_message(NetworkHandleMessage::EarliestBlockUpdate { earliest });
+    }
+
     /// Announce a block over devp2p
     ///
     /// Caution: in `PoS` this is a noop because new blocks are no longer announced over devp2p.
@@ -311,6 +319,26 @@ impl Peers for NetworkHandle {
         let _ = self.manager().send(NetworkHandleMessage::GetReputationById(peer_id, tx));
         Ok(rx.await?)
     }
+
+    fn set_connection_limits(&self, limits: ConnectionsConfig) {
+        self.inner.peers.set_connection_limits(limits);
+    }
+
+    async fn inbound_filter(&self) -> Result<InboundFilter, NetworkError> {
+        Ok(self.inner.peers.inbound_filter().await?)
+    }
+
+    fn set_inbound_filter(&self, filter: InboundFilter) {
+        self.inner.peers.set_inbound_filter(filter);
+    }
+
+    async fn transaction_propagation(
+        &self,
+        hash: TxHash,
+    ) -> Result<Option<TransactionPropagation>, NetworkError> {
+        let Some(transactions) = self.transactions_handle().await else { return Ok(None) };
+        Ok(transactions.transaction_propagation(hash).await?)
+    }
 }
 
 impl PeersHandleProvider for NetworkHandle {
@@ -459,6 +487,11 @@ pub(crate) enum NetworkHandleMessage {
         /// The head status to apply.
         head: Head,
     },
+    /// Updates the earliest block the node can serve.
+    EarliestBlockUpdate {
+        /// The earliest available block.
+        earliest: BlockNumber,
+    },
     /// Retrieves the current status via a oneshot sender.
     GetStatus(oneshot::Sender<NetworkStatus>),
     /// Gets `PeerInfo` for the specified peer IDs.
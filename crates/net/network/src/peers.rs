@@ -19,8 +19,8 @@ use reth_network_types::{
         config::PeerBackoffDurations,
         reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
     },
-    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
-    ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
+    ConnectionsConfig, InboundFilter, Peer, PeerAddr, PeerConnectionState, PeerKind, PeerStats,
+    PeersConfig, PersistedPeer, ReputationChangeKind, ReputationChangeOutcome,
 };
 use reth_primitives::ForkId;
 use thiserror::Error;
@@ -29,10 +29,12 @@ use tokio::{
     time::{Instant, Interval},
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
-use tracing::{trace, warn};
+use tracing::{debug, trace, warn};
 
 use crate::{
     error::SessionError,
+    metrics::InboundFilterMetrics,
+    scoring::{PeerScorer, ScoredPeer, ScoredResponse},
     session::{Direction, PendingSessionHandshakeError},
     swarm::NetworkConnectionState,
 };
@@ -61,7 +63,7 @@ pub struct PeersManager {
     /// Interval for triggering connections if there are free slots.
     refill_slots_interval: Interval,
     /// How to weigh reputation changes
-    reputation_weights: ReputationChangeWeights,
+    scorer: Box<dyn PeerScorer>,
     /// Tracks current slot stats.
     connection_info: ConnectionInfo,
     /// Tracks unwanted ips/peer ids.
@@ -84,6 +86,10 @@ pub struct PeersManager {
     max_backoff_count: u8,
     /// Tracks the connection state of the node
     net_connection_state: NetworkConnectionState,
+    /// Allow and deny rules applied to incoming sessions.
+    inbound_filter: InboundFilter,
+    /// Tracks sessions rejected by the inbound filter.
+    inbound_filter_metrics: InboundFilterMetrics,
 }
 
 impl PeersManager {
@@ -99,7 +105,9 @@ impl PeersManager {
             trusted_nodes,
             trusted_nodes_only,
             basic_nodes,
+            peer_stats,
             max_backoff_count,
+            inbound_filter,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
         let now = Instant::now();
@@ -130,13 +138,19 @@ impl PeersManager {
             });
         }
 
+        for (peer_id, stats) in peer_stats {
+            if let Some(peer) = peers.get_mut(&peer_id) {
+                peer.stats = stats;
+            }
+        }
+
         Self {
             peers,
             trusted_peer_ids,
             manager_tx,
             handle_rx: UnboundedReceiverStream::new(handle_rx),
             queued_actions: Default::default(),
-            reputation_weights,
+            scorer: Box::new(reputation_weights),
             refill_slots_interval: tokio::time::interval(refill_slots_interval),
             release_interval: tokio::time::interval_at(now + unban_interval, unban_interval),
             connection_info: ConnectionInfo::new(connection_info),
@@ -148,9 +162,18 @@ impl PeersManager {
             last_tick: Instant::now(),
             max_backoff_count,
             net_connection_state: NetworkConnectionState::default(),
+            inbound_filter,
+            inbound_filter_metrics: Default::default(),
         }
     }
 
+    /// Sets the scorer that decides how the reputation of peers changes, replacing the
+    /// configured [`ReputationChangeWeights`](reth_network_types::ReputationChangeWeights).
+    pub fn with_scorer(mut self, scorer: Box<dyn PeerScorer>) -> Self {
+        self.scorer = scorer;
+        self
+    }
+
     /// Returns a new [`PeersHandle`] that can send commands to this type.
     pub(crate) fn handle(&self) -> PeersHandle {
         PeersHandle::new(self.manager_tx.clone())
@@ -174,6 +197,31 @@ impl PeersManager {
         })
     }
 
+    /// Returns an iterator over all peers, along with what has been observed about them.
+    pub(crate) fn iter_persisted_peers(&self) -> impl Iterator<Item = PersistedPeer> + '_ {
+        self.peers.iter().map(|(peer_id, v)| PersistedPeer {
+            record: NodeRecord::new_with_ports(
+                v.addr.tcp().ip(),
+                v.addr.tcp().port(),
+                v.addr.udp().map(|addr| addr.port()),
+                *peer_id,
+            ),
+            stats: v.stats,
+        })
+    }
+
+    /// Returns what has been observed about the given peer, including previous runs.
+    pub(crate) fn peer_stats(&self, peer_id: &PeerId) -> Option<&PeerStats> {
+        self.peers.get(peer_id).map(|peer| &peer.stats)
+    }
+
+    /// Records the eth protocol version of an established session with the peer.
+    pub(crate) fn on_eth_version(&mut self, peer_id: &PeerId, version: u8) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.stats.eth_version = Some(version);
+        }
+    }
+
     /// Returns the `NodeRecord` and `PeerKind` for the given peer id
     pub(crate) fn peer_by_id(&self, peer_id: PeerId) -> Option<(NodeRecord, PeerKind)> {
         self.peers.get(&peer_id).map(|v| {
@@ -220,7 +268,8 @@ impl PeersManager {
 
     /// Invoked when a new _incoming_ tcp connection is accepted.
     ///
-    /// returns an error if the inbound ip address is on the ban list
+    /// returns an error if the inbound ip address is on the ban list or rejected by the
+    /// [`InboundFilter`]
     pub(crate) fn on_incoming_pending_session(
         &mut self,
         addr: IpAddr,
@@ -229,6 +278,11 @@ impl PeersManager {
             return Err(InboundConnectionError::IpBanned)
         }
 
+        if !self.inbound_filter.is_allowed_ip(&addr) {
+            self.inbound_filter_metrics.rejected_ips.increment(1);
+            return Err(InboundConnectionError::IpNotAllowed)
+        }
+
         if !self.connection_info.has_in_capacity() && self.trusted_peer_ids.is_empty() {
             // if we don't have any inbound slots and no trusted peers, we don't accept any new
             // connections
@@ -330,6 +384,74 @@ impl PeersManager {
                 peer_id,
                 reason: Some(DisconnectReason::TooManyPeers),
             });
+        } else if let Some(reason) = self.inbound_filter_rejection(peer_id, addr.ip(), is_trusted) {
+            self.queued_actions.push_back(PeerAction::Disconnect { peer_id, reason: Some(reason) });
+        }
+    }
+
+    /// Checks an established incoming session against the [`InboundFilter`].
+    ///
+    /// Returns the reason the peer should be disconnected with if it is rejected, either because
+    /// its peer id is not allowed or because too many untrusted inbound peers from the same ASN
+    /// are already connected.
+    fn inbound_filter_rejection(
+        &self,
+        peer_id: PeerId,
+        ip: IpAddr,
+        is_trusted: bool,
+    ) -> Option<DisconnectReason> {
+        if !self.inbound_filter.is_allowed_peer(&peer_id, is_trusted) {
+            debug!(target: "net::peers", ?peer_id, "Peer rejected by the inbound filter");
+            self.inbound_filter_metrics.rejected_peers.increment(1);
+            return Some(DisconnectReason::DisconnectRequested)
+        }
+
+        if is_trusted {
+            return None
+        }
+
+        let max_peers_per_asn = self.inbound_filter.max_peers_per_asn?;
+        let asn = self.inbound_filter.asn(&ip)?;
+        // this includes the peer itself, which is already marked as inbound
+        let peers_in_asn = self
+            .peers
+            .values()
+            .filter(|peer| {
+                peer.state == PeerConnectionState::In &&
+                    !peer.is_trusted() &&
+                    self.inbound_filter.asn(&peer.addr.tcp().ip()) == Some(asn)
+            })
+            .count();
+        if peers_in_asn > max_peers_per_asn {
+            debug!(target: "net::peers", ?peer_id, asn, "Inbound peer limit for ASN reached");
+            self.inbound_filter_metrics.rejected_asn_limit.increment(1);
+            return Some(DisconnectReason::TooManyPeers)
+        }
+
+        None
+    }
+
+    /// Replaces the [`InboundFilter`].
+    ///
+    /// Established incoming sessions that are rejected by the new filter because of their ip
+    /// address or peer id are disconnected.
+    pub(crate) fn set_inbound_filter(&mut self, filter: InboundFilter) {
+        trace!(target: "net::peers", ?filter, "Updating inbound filter");
+        self.inbound_filter = filter;
+
+        for (peer_id, peer) in &mut self.peers {
+            if peer.state != PeerConnectionState::In {
+                continue
+            }
+            if !self.inbound_filter.is_allowed_ip(&peer.addr.tcp().ip()) ||
+                !self.inbound_filter.is_allowed_peer(peer_id, peer.is_trusted())
+            {
+                peer.state.disconnect();
+                self.queued_actions.push_back(PeerAction::Disconnect {
+                    peer_id: *peer_id,
+                    reason: Some(DisconnectReason::DisconnectRequested),
+                });
+            }
         }
     }
 
@@ -408,7 +530,8 @@ impl PeersManager {
             if rep.is_reset() {
                 peer.reset_reputation()
             } else {
-                let mut reputation_change = self.reputation_weights.change(rep).as_i32();
+                let mut reputation_change =
+                    self.scorer.reputation_change(&scored_peer(*peer_id, peer), rep).as_i32();
                 if peer.is_trusted() || peer.is_static() {
                     // exempt trusted and static peers from reputation slashing for
                     if matches!(
@@ -433,6 +556,42 @@ impl PeersManager {
             return
         };
 
+        self.on_reputation_change_outcome(peer_id, outcome);
+    }
+
+    /// Invoked when a request to the peer failed.
+    pub(crate) fn on_failed_peer_request(&mut self, peer_id: &PeerId) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.stats.on_failed_request();
+        }
+    }
+
+    /// Invoked when the peer successfully responded to a request.
+    ///
+    /// Records the response in the peer's stats and applies the reputation change the scorer
+    /// assigns to the response, if any.
+    pub(crate) fn on_peer_response(&mut self, peer_id: &PeerId, response: ScoredResponse) {
+        let Some(peer) = self.peers.get_mut(peer_id) else { return };
+        peer.stats.on_response(response.latency, response.items > 0);
+        let Some(reputation_change) =
+            self.scorer.on_response(&scored_peer(*peer_id, peer), &response)
+        else {
+            return
+        };
+
+        let mut reputation_change = reputation_change.as_i32();
+        if (peer.is_trusted() || peer.is_static()) &&
+            reputation_change < MAX_TRUSTED_PEER_REPUTATION_CHANGE
+        {
+            reputation_change = MAX_TRUSTED_PEER_REPUTATION_CHANGE;
+        }
+        let outcome = peer.apply_reputation(reputation_change);
+
+        self.on_reputation_change_outcome(peer_id, outcome);
+    }
+
+    /// Bans, unbans or disconnects the peer depending on the outcome of a reputation change.
+    fn on_reputation_change_outcome(&mut self, peer_id: &PeerId, outcome: ReputationChangeOutcome) {
         match outcome {
             ReputationChangeOutcome::None => {}
             ReputationChangeOutcome::Ban => {
@@ -584,7 +743,9 @@ impl PeersManager {
                     backoff_until = Some(backoff_time);
                 } else {
                     // If the error was not a backoff error, we reduce the peer's reputation
-                    let reputation_change = self.reputation_weights.change(reputation_change);
+                    let reputation_change = self
+                        .scorer
+                        .reputation_change(&scored_peer(*peer_id, peer), reputation_change);
                     peer.reputation = peer.reputation.saturating_add(reputation_change.as_i32());
                 };
 
@@ -677,6 +838,12 @@ impl PeersManager {
         match self.peers.entry(peer_id) {
             Entry::Occupied(mut entry) => {
                 let peer = entry.get_mut();
+                if peer.addr.tcp() != addr.tcp() && peer.is_backed_off() {
+                    // the peer moved, e.g. its DNS name resolves to a new address, so the new
+                    // address can be dialed right away
+                    peer.backed_off = false;
+                    self.backed_off_peers.remove(&peer_id);
+                }
                 peer.kind = kind;
                 peer.fork_id = fork_id;
                 peer.addr = addr;
@@ -742,6 +909,14 @@ impl PeersManager {
         self.trusted_peer_ids.remove(&peer_id);
     }
 
+    /// Replaces the connection limits.
+    ///
+    /// Established sessions are not disconnected if the new limits are lower.
+    pub(crate) fn set_connection_limits(&mut self, limits: ConnectionsConfig) {
+        trace!(target: "net::peers", ?limits, "updating connection limits");
+        self.connection_info.config = limits;
+    }
+
     /// Returns the idle peer with the highest reputation.
     ///
     /// Peers that are `trusted` or `static`, see [`PeerKind`], are prioritized as long as they're
@@ -848,12 +1023,17 @@ impl PeersManager {
                     PeerCommand::ReputationChange(peer_id, rep) => {
                         self.apply_reputation_change(&peer_id, rep)
                     }
+                    PeerCommand::SetConnectionLimits(limits) => self.set_connection_limits(limits),
                     PeerCommand::GetPeer(peer, tx) => {
                         let _ = tx.send(self.peers.get(&peer).cloned());
                     }
                     PeerCommand::GetPeers(tx) => {
                         let _ = tx.send(self.iter_peers().collect());
                     }
+                    PeerCommand::SetInboundFilter(filter) => self.set_inbound_filter(filter),
+                    PeerCommand::GetInboundFilter(tx) => {
+                        let _ = tx.send(self.inbound_filter.clone());
+                    }
                 }
             }
 
@@ -1037,6 +1217,8 @@ pub enum InboundConnectionError {
     IpBanned,
     /// No capacity for new inbound connections
     ExceedsCapacity,
+    /// The remote's ip address is rejected by the inbound filter
+    IpNotAllowed,
 }
 
 impl Display for InboundConnectionError {
@@ -1045,10 +1227,15 @@ impl Display for InboundConnectionError {
     }
 }
 
+/// Returns the [`ScoredPeer`] the scorer is consulted with.
+const fn scored_peer(peer_id: PeerId, peer: &Peer) -> ScoredPeer {
+    ScoredPeer { peer_id, addr: peer.addr.tcp(), kind: peer.kind, reputation: peer.reputation }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
-        collections::HashSet,
+        collections::{HashMap, HashSet},
         future::{poll_fn, Future},
         io,
         net::{IpAddr, Ipv4Addr, SocketAddr},
@@ -1063,9 +1250,11 @@ mod tests {
     };
     use reth_net_banlist::BanList;
     use reth_network_api::Direction;
-    use reth_network_peers::{PeerId, TrustedPeer};
+    use reth_network_peers::{NodeRecord, PeerId, TrustedPeer};
     use reth_network_types::{
-        peers::reputation::DEFAULT_REPUTATION, BackoffKind, ReputationChangeKind,
+        peers::reputation::{ReputationChange, DEFAULT_REPUTATION},
+        AsnRange, BackoffKind, ConnectionsConfig, InboundFilter, PeerStats, PersistedPeer,
+        ReputationChangeKind, ReputationChangeWeights,
     };
     use reth_primitives::B512;
     use url::Host;
@@ -1076,6 +1265,7 @@ mod tests {
             ConnectionInfo, InboundConnectionError, PeerAction, PeerAddr, PeerBackoffDurations,
             PeerConnectionState,
         },
+        scoring::{PeerScorer, ScoredPeer, ScoredRequestKind, ScoredResponse},
         session::PendingSessionHandshakeError,
         PeersConfig,
     };
@@ -1326,6 +1516,29 @@ mod tests {
         assert!(!peers.peers.get(&peer).unwrap().is_backed_off());
     }
 
+    #[tokio::test]
+    async fn test_address_change_clears_backoff() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::new(PeersConfig::test());
+        peers.add_trusted_peer(peer, PeerAddr::from_tcp(socket_addr));
+
+        let until = std::time::Instant::now() + peers.backoff_durations.high;
+        peers.backoff_peer_until(peer, until);
+
+        // same address
+        peers.add_trusted_peer(peer, PeerAddr::from_tcp(socket_addr));
+        assert!(peers.backed_off_peers.contains_key(&peer));
+        assert!(peers.peers.get(&peer).unwrap().is_backed_off());
+
+        let new_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 3)), 8008);
+        peers.add_trusted_peer(peer, PeerAddr::from_tcp(new_addr));
+        assert!(!peers.backed_off_peers.contains_key(&peer));
+        let peer = peers.peers.get(&peer).unwrap();
+        assert!(!peer.is_backed_off());
+        assert_eq!(peer.addr.tcp(), new_addr);
+    }
+
     #[tokio::test]
     async fn test_low_backoff() {
         let peer = PeerId::random();
@@ -1805,6 +2018,101 @@ mod tests {
         assert_eq!(peers.get_reputation(&peer), Some(0));
     }
 
+    #[tokio::test]
+    async fn test_custom_peer_scorer() {
+        /// Doubles penalties of peers outside of `127.0.1.0/24` and rewards fast responses.
+        #[derive(Debug)]
+        struct TestScorer;
+
+        impl PeerScorer for TestScorer {
+            fn reputation_change(
+                &self,
+                peer: &ScoredPeer,
+                kind: ReputationChangeKind,
+            ) -> ReputationChange {
+                let change = ReputationChangeWeights::default().change(kind).as_i32();
+                match peer.addr.ip() {
+                    IpAddr::V4(ip) if ip.octets()[..3] == [127, 0, 1] => change.into(),
+                    _ => change.saturating_mul(2).into(),
+                }
+            }
+
+            fn on_response(
+                &self,
+                _peer: &ScoredPeer,
+                response: &ScoredResponse,
+            ) -> Option<ReputationChange> {
+                (response.latency < Duration::from_millis(100)).then_some(100.into())
+            }
+        }
+
+        let local = PeerId::random();
+        let remote = PeerId::random();
+        let mut peers = PeersManager::default().with_scorer(Box::new(TestScorer));
+        peers.add_peer(
+            local,
+            PeerAddr::from_tcp(SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008)),
+            None,
+        );
+        peers.add_peer(
+            remote,
+            PeerAddr::from_tcp(SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 8008)),
+            None,
+        );
+
+        let penalty = ReputationChangeWeights::default().timeout;
+        peers.apply_reputation_change(&local, ReputationChangeKind::Timeout);
+        peers.apply_reputation_change(&remote, ReputationChangeKind::Timeout);
+        assert_eq!(peers.get_reputation(&local), Some(penalty));
+        assert_eq!(peers.get_reputation(&remote), Some(penalty * 2));
+
+        let response =
+            |latency| ScoredResponse { kind: ScoredRequestKind::BlockHeaders, latency, items: 1 };
+        peers.on_peer_response(&local, response(Duration::from_millis(10)));
+        peers.on_peer_response(&remote, response(Duration::from_secs(1)));
+        assert_eq!(peers.get_reputation(&local), Some(penalty + 100));
+        assert_eq!(peers.get_reputation(&remote), Some(penalty * 2));
+    }
+
+    #[tokio::test]
+    async fn test_persisted_peer_stats() {
+        let record = NodeRecord::new(
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 30303),
+            PeerId::random(),
+        );
+        let stats = PeerStats { latency_ms: Some(100), good_responses: 1, ..Default::default() };
+        let config = PeersConfig::default()
+            .with_basic_nodes(HashSet::from([record]))
+            .with_peer_stats(HashMap::from([(record.id, stats), (PeerId::random(), stats)]));
+        let mut peers = PeersManager::new(config);
+        assert_eq!(peers.peer_stats(&record.id), Some(&stats));
+
+        peers.on_eth_version(&record.id, 68);
+        peers.on_failed_peer_request(&record.id);
+        peers.on_peer_response(
+            &record.id,
+            ScoredResponse {
+                kind: ScoredRequestKind::BlockBodies,
+                latency: Duration::from_millis(600),
+                items: 1,
+            },
+        );
+
+        let persisted = peers.iter_persisted_peers().collect::<Vec<_>>();
+        assert_eq!(
+            persisted,
+            vec![PersistedPeer {
+                record,
+                stats: PeerStats {
+                    latency_ms: Some(200),
+                    good_responses: 2,
+                    bad_responses: 1,
+                    eth_version: Some(68),
+                },
+            }]
+        );
+    }
+
     #[tokio::test]
     async fn test_remove_discovered_active() {
         let peer = PeerId::random();
@@ -2127,6 +2435,99 @@ mod tests {
         assert!(!peers.peers.contains_key(&basic_peer));
     }
 
+    #[tokio::test]
+    async fn test_inbound_filter_rejects_ip() {
+        let filter =
+            InboundFilter { deny_ips: vec!["127.0.1.0/24".parse().unwrap()], ..Default::default() };
+        let mut peers = PeersManager::new(PeersConfig::test().with_inbound_filter(filter));
+
+        let denied = IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2));
+        assert_eq!(
+            peers.on_incoming_pending_session(denied),
+            Err(InboundConnectionError::IpNotAllowed)
+        );
+        assert_eq!(peers.connection_info.num_pending_in, 0);
+
+        let allowed = IpAddr::V4(Ipv4Addr::new(127, 0, 2, 2));
+        assert!(peers.on_incoming_pending_session(allowed).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_inbound_filter_rejects_peer() {
+        let denied_peer = PeerId::random();
+        let filter = InboundFilter { deny_peers: [denied_peer].into(), ..Default::default() };
+        let mut peers = PeersManager::new(PeersConfig::test().with_inbound_filter(filter));
+
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        assert!(peers.on_incoming_pending_session(socket_addr.ip()).is_ok());
+        peers.on_incoming_session_established(denied_peer, socket_addr);
+
+        assert!(matches!(peers.queued_actions.pop_front(), Some(PeerAction::PeerAdded(_))));
+        let Some(PeerAction::Disconnect { peer_id, reason }) = peers.queued_actions.pop_front()
+        else {
+            panic!()
+        };
+        assert_eq!(peer_id, denied_peer);
+        assert_eq!(reason, Some(DisconnectReason::DisconnectRequested));
+    }
+
+    #[tokio::test]
+    async fn test_inbound_filter_asn_limit() {
+        let filter = InboundFilter {
+            asn_ranges: vec![AsnRange { net: "127.0.1.0/24".parse().unwrap(), asn: 64496 }],
+            max_peers_per_asn: Some(1),
+            ..Default::default()
+        };
+        let mut peers = PeersManager::new(PeersConfig::test().with_inbound_filter(filter));
+
+        let first = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        assert!(peers.on_incoming_pending_session(first.ip()).is_ok());
+        peers.on_incoming_session_established(PeerId::random(), first);
+        assert!(matches!(peers.queued_actions.pop_front(), Some(PeerAction::PeerAdded(_))));
+        assert!(peers.queued_actions.is_empty());
+
+        // a second peer of the same ASN exceeds the limit
+        let second_peer = PeerId::random();
+        let second = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 3)), 8008);
+        assert!(peers.on_incoming_pending_session(second.ip()).is_ok());
+        peers.on_incoming_session_established(second_peer, second);
+        assert!(matches!(peers.queued_actions.pop_front(), Some(PeerAction::PeerAdded(_))));
+        let Some(PeerAction::Disconnect { peer_id, reason }) = peers.queued_actions.pop_front()
+        else {
+            panic!()
+        };
+        assert_eq!(peer_id, second_peer);
+        assert_eq!(reason, Some(DisconnectReason::TooManyPeers));
+
+        // peers outside of the ASN are not limited
+        let other = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 2, 2)), 8008);
+        assert!(peers.on_incoming_pending_session(other.ip()).is_ok());
+        peers.on_incoming_session_established(PeerId::random(), other);
+        assert!(matches!(peers.queued_actions.pop_front(), Some(PeerAction::PeerAdded(_))));
+        assert!(peers.queued_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_set_inbound_filter_disconnects_peers() {
+        let mut peers = PeersManager::new(PeersConfig::test());
+
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        assert!(peers.on_incoming_pending_session(socket_addr.ip()).is_ok());
+        peers.on_incoming_session_established(peer, socket_addr);
+        peers.queued_actions.clear();
+
+        peers.set_inbound_filter(InboundFilter {
+            deny_ips: vec!["127.0.1.2/32".parse().unwrap()],
+            ..Default::default()
+        });
+        let Some(PeerAction::Disconnect { peer_id, .. }) = peers.queued_actions.pop_front() else {
+            panic!()
+        };
+        assert_eq!(peer_id, peer);
+        assert_eq!(peers.peers.get(&peer).unwrap().state, PeerConnectionState::DisconnectingIn);
+    }
+
     #[tokio::test]
     async fn test_incoming_without_trusted_nodes_only() {
         let trusted_peer = PeerId::random();
@@ -2381,4 +2782,27 @@ mod tests {
         // no more pending outbound connections
         assert_eq!(peer_manager.connection_info.num_pending_out, 0);
     }
+
+    #[tokio::test]
+    async fn test_set_connection_limits() {
+        let mut peers = PeersManager::new(PeersConfig::test().with_max_inbound(1));
+        let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2));
+
+        assert!(peers.on_incoming_pending_session(ip).is_ok());
+        let peer_id = PeerId::random();
+        peers.on_incoming_session_established(peer_id, SocketAddr::new(ip, 8008));
+        assert!(matches!(event!(peers), PeerAction::PeerAdded(id) if id == peer_id));
+        assert!(!peers.connection_info.has_in_capacity());
+
+        let limits = ConnectionsConfig { max_inbound: 2, ..peers.connection_info.config };
+        peers.handle().set_connection_limits(limits);
+        poll_fn(|cx| {
+            let _ = peers.poll(cx);
+            Poll::Ready(())
+        })
+        .await;
+
+        assert_eq!(peers.connection_info.config.max_inbound, 2);
+        assert!(peers.connection_info.has_in_capacity());
+    }
 }
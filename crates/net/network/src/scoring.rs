@@ -0,0 +1,82 @@
+//! This module provides an abstraction over peer reputation scoring in the form of the
+//! `PeerScorer` trait.
+
+use std::{net::SocketAddr, time::Duration};
+
+use reth_network_peers::PeerId;
+use reth_network_types::{
+    peers::reputation::ReputationChange, PeerKind, Reputation, ReputationChangeKind,
+    ReputationChangeWeights,
+};
+
+/// Abstraction over how the reputation of peers changes.
+///
+/// The [`PeersManager`](crate::peers::PeersManager) consults the scorer whenever it changes the
+/// reputation of a peer, and bans peers whose reputation drops below the banned threshold.
+/// Trusted and static peers remain exempt from penalties that can be attributed to network
+/// conditions, and their penalties are capped, regardless of the scorer.
+///
+/// By default, the [`ReputationChangeWeights`] of the
+/// [`PeersConfig`](reth_network_types::PeersConfig) are used.
+pub trait PeerScorer: std::fmt::Debug + Send + Sync {
+    /// Returns the reputation change to apply to the peer for the given kind of change.
+    ///
+    /// This is never invoked for [`ReputationChangeKind::Reset`].
+    fn reputation_change(&self, peer: &ScoredPeer, kind: ReputationChangeKind) -> ReputationChange;
+
+    /// Invoked when the peer successfully responded to a request, returns the reputation change to
+    /// apply, if any.
+    ///
+    /// This can be used to reward fast peers or penalize slow ones.
+    fn on_response(
+        &self,
+        peer: &ScoredPeer,
+        response: &ScoredResponse,
+    ) -> Option<ReputationChange> {
+        let _ = (peer, response);
+        None
+    }
+}
+
+impl PeerScorer for ReputationChangeWeights {
+    fn reputation_change(
+        &self,
+        _peer: &ScoredPeer,
+        kind: ReputationChangeKind,
+    ) -> ReputationChange {
+        self.change(kind)
+    }
+}
+
+/// The peer whose reputation changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoredPeer {
+    /// Id of the peer.
+    pub peer_id: PeerId,
+    /// The `RLPx` address of the peer.
+    pub addr: SocketAddr,
+    /// The kind of peer.
+    pub kind: PeerKind,
+    /// Current reputation of the peer.
+    pub reputation: Reputation,
+}
+
+/// A response the peer sent to a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoredResponse {
+    /// The kind of request the peer responded to.
+    pub kind: ScoredRequestKind,
+    /// Time between sending the request and receiving the response.
+    pub latency: Duration,
+    /// Number of items in the response, e.g. headers.
+    pub items: usize,
+}
+
+/// The kind of request a [`ScoredResponse`] responds to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoredRequestKind {
+    /// A `GetBlockHeaders` request.
+    BlockHeaders,
+    /// A `GetBlockBodies` request.
+    BlockBodies,
+}
@@ -11,11 +11,12 @@ use std::{
     time::{Duration, Instant},
 };
 
+use alloy_rlp::Encodable;
 use futures::{stream::Fuse, SinkExt, StreamExt};
 use reth_eth_wire::{
     errors::{EthHandshakeError, EthStreamError, P2PStreamError},
     message::{EthBroadcastMessage, RequestPair},
-    Capabilities, DisconnectP2P, DisconnectReason, EthMessage,
+    Capabilities, DisconnectP2P, DisconnectReason, EthMessage, Receipts69,
 };
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_api::PeerRequest;
@@ -34,6 +35,7 @@ use tracing::{debug, trace};
 use crate::{
     message::{NewBlockMessage, PeerMessage, PeerResponse, PeerResponseResult},
     session::{
+        bandwidth::SessionBandwidth,
         conn::EthRlpxConnection,
         handle::{ActiveSessionMessage, SessionCommand},
         SessionId,
@@ -97,6 +99,8 @@ pub(crate) struct ActiveSession {
     pub(crate) protocol_breach_request_timeout: Duration,
     /// Used to reserve a slot to guarantee that the termination message is delivered
     pub(crate) terminate_message: Option<(PollSender<ActiveSessionMessage>, ActiveSessionMessage)>,
+    /// Enforces the upload and download limits of the session.
+    pub(crate) bandwidth: SessionBandwidth,
 }
 
 impl ActiveSession {
@@ -172,10 +176,14 @@ impl ActiveSession {
         }
 
         match msg {
-            message @ EthMessage::Status(_) => OnIncomingMessageOutcome::BadMessage {
-                error: EthStreamError::EthHandshakeError(EthHandshakeError::StatusNotInHandshake),
-                message,
-            },
+            message @ (EthMessage::Status(_) | EthMessage::Status69(_)) => {
+                OnIncomingMessageOutcome::BadMessage {
+                    error: EthStreamError::EthHandshakeError(
+                        EthHandshakeError::StatusNotInHandshake,
+                    ),
+                    message,
+                }
+            }
             EthMessage::NewBlockHashes(msg) => {
                 self.try_emit_broadcast(PeerMessage::NewBlockHashes(msg)).into()
             }
@@ -233,6 +241,23 @@ impl ActiveSession {
             EthMessage::Receipts(resp) => {
                 on_response!(resp, GetReceipts)
             }
+            EthMessage::Receipts69(resp) => {
+                let resp = resp.map(Receipts69::into_receipts);
+                on_response!(resp, GetReceipts)
+            }
+            EthMessage::BlockRangeUpdate(update) => {
+                trace!(target: "net::session", ?update, remote_peer_id=?self.remote_peer_id, "received block range update");
+                if !update.is_valid() {
+                    return OnIncomingMessageOutcome::BadMessage {
+                        error: EthStreamError::InvalidBlockRangeUpdate {
+                            earliest: update.earliest,
+                            latest: update.latest,
+                        },
+                        message: EthMessage::BlockRangeUpdate(update),
+                    }
+                }
+                self.try_emit_broadcast(PeerMessage::BlockRangeUpdate(update)).into()
+            }
         }
     }
 
@@ -270,6 +295,11 @@ impl ActiveSession {
             PeerMessage::SendTransactions(msg) => {
                 self.queued_outgoing.push_back(EthBroadcastMessage::Transactions(msg).into());
             }
+            PeerMessage::BlockRangeUpdate(update) => {
+                if self.conn.version().is_eth69() {
+                    self.queued_outgoing.push_back(EthMessage::BlockRangeUpdate(update).into());
+                }
+            }
             PeerMessage::ReceivedTransaction(_) => {
                 unreachable!("Not emitted by network")
             }
@@ -291,6 +321,13 @@ impl ActiveSession {
     fn handle_outgoing_response(&mut self, id: u64, resp: PeerResponseResult) {
         match resp.try_into_message(id) {
             Ok(msg) => {
+                let msg = match msg {
+                    // eth/69 receipts don't include the bloom filter
+                    EthMessage::Receipts(resp) if self.conn.version().is_eth69() => {
+                        EthMessage::Receipts69(resp.map(Into::into))
+                    }
+                    msg => msg,
+                };
                 self.queued_outgoing.push_back(msg.into());
             }
             Err(err) => {
@@ -552,7 +589,13 @@ impl Future for ActiveSession {
             // Send messages by advancing the sink and queuing in buffered messages
             while this.conn.poll_ready_unpin(cx).is_ready() {
                 if let Some(msg) = this.queued_outgoing.pop_front() {
+                    if !this.bandwidth.poll_upload(cx) {
+                        // upload limit reached, we're woken up again once we can send
+                        this.queued_outgoing.push_front(msg);
+                        break
+                    }
                     progress = true;
+                    this.bandwidth.on_upload(msg.length());
                     let res = match msg {
                         OutgoingMessage::Eth(msg) => this.conn.start_send_unpin(msg),
                         OutgoingMessage::Broadcast(msg) => this.conn.start_send_broadcast(msg),
@@ -594,6 +637,11 @@ impl Future for ActiveSession {
                     };
                 }
 
+                if !this.bandwidth.poll_download(cx) {
+                    // download limit reached, we're woken up again once we can read
+                    break 'receive
+                }
+
                 match this.conn.poll_next_unpin(cx) {
                     Poll::Pending => break,
                     Poll::Ready(None) => {
@@ -608,6 +656,7 @@ impl Future for ActiveSession {
                         match res {
                             Ok(msg) => {
                                 trace!(target: "net::session", msg_id=?msg.message_id(), remote_peer_id=?this.remote_peer_id, "received eth message");
+                                this.bandwidth.on_download(msg.length());
                                 // decode and handle message
                                 match this.on_incoming_message(msg) {
                                     OnIncomingMessageOutcome::Ok => {
@@ -736,6 +785,16 @@ pub(crate) enum OutgoingMessage {
     Broadcast(EthBroadcastMessage),
 }
 
+impl OutgoingMessage {
+    /// Returns the length of the encoded message.
+    fn length(&self) -> usize {
+        match self {
+            Self::Eth(msg) => msg.length(),
+            Self::Broadcast(msg) => msg.length(),
+        }
+    }
+}
+
 impl From<EthMessage> for OutgoingMessage {
     fn from(value: EthMessage) -> Self {
         Self::Eth(value)
@@ -765,12 +824,13 @@ mod tests {
     use reth_chainspec::MAINNET;
     use reth_ecies::stream::ECIESStream;
     use reth_eth_wire::{
-        EthStream, GetBlockBodies, HelloMessageWithProtocols, P2PStream, Status, StatusBuilder,
-        UnauthedEthStream, UnauthedP2PStream,
+        protocol::Protocol, BlockRangeUpdate, EthStream, EthVersion, GetBlockBodies,
+        HelloMessageWithProtocols, P2PStream, Status, StatusBuilder, UnauthedEthStream,
+        UnauthedP2PStream,
     };
     use reth_network_peers::pk2id;
     use reth_network_types::session::config::PROTOCOL_BREACH_REQUEST_TIMEOUT;
-    use reth_primitives::{EthereumHardfork, ForkFilter};
+    use reth_primitives::{EthereumHardfork, ForkFilter, B256};
     use secp256k1::{SecretKey, SECP256K1};
     use tokio::{
         net::{TcpListener, TcpStream},
@@ -848,6 +908,7 @@ mod tests {
                 self.hello.clone(),
                 self.status,
                 self.fork_filter.clone(),
+                BlockRangeUpdate::default(),
                 Default::default(),
             ));
 
@@ -893,6 +954,7 @@ mod tests {
                         )),
                         protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
                         terminate_message: None,
+                        bandwidth: Default::default(),
                     }
                 }
                 ev => {
@@ -1054,6 +1116,40 @@ mod tests {
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_invalid_block_range_update() {
+        reth_tracing::init_test_tracing();
+
+        let mut builder = SessionBuilder::default();
+        builder.hello = HelloMessageWithProtocols::builder(builder.local_peer_id)
+            .protocol(Protocol::eth_69())
+            .build();
+        builder.status.set_eth_version(EthVersion::Eth69);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let fut = builder.with_client_stream(local_addr, move |mut client_stream| async move {
+            let update = BlockRangeUpdate { earliest: 11, latest: 10, latest_hash: B256::ZERO };
+            client_stream.send(EthMessage::BlockRangeUpdate(update)).await.unwrap();
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+        tokio::task::spawn(fut);
+
+        let (incoming, _) = listener.accept().await.unwrap();
+        let session = builder.connect_incoming(incoming).await;
+        tokio::spawn(session);
+
+        let msg = builder.active_session_rx.next().await.unwrap();
+        match msg {
+            ActiveSessionMessage::ClosedOnConnectionError {
+                error: EthStreamError::InvalidBlockRangeUpdate { earliest: 11, latest: 10 },
+                ..
+            } => {}
+            ev => unreachable!("{ev:?}"),
+        }
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_keep_alive() {
         let mut builder = SessionBuilder::default();
@@ -0,0 +1,216 @@
+//! Bandwidth limits of sessions.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::Context,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+use reth_network_types::BandwidthLimits;
+use tokio::time::Sleep;
+
+use crate::metrics::BandwidthMetrics;
+
+/// A token bucket that refills at a fixed rate of bytes per second, up to one second worth of
+/// bytes.
+///
+/// The bucket can go into debt, so that messages larger than the bucket can pass. Once in debt, no
+/// more bytes pass until the debt has been paid off.
+#[derive(Debug)]
+pub(crate) struct TokenBucket {
+    /// Bytes per second.
+    rate: u64,
+    /// Available bytes, negative if in debt.
+    tokens: f64,
+    /// When the bucket was last refilled.
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Returns a full bucket with the given rate in bytes per second.
+    pub(crate) fn new(rate: u64) -> Self {
+        // a zero rate would never pay off any debt
+        let rate = rate.max(1);
+        Self { rate, tokens: rate as f64, last_refill: Instant::now() }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = elapsed.mul_add(self.rate as f64, self.tokens).min(self.rate as f64);
+        self.last_refill = now;
+    }
+
+    /// Returns how long to wait until the debt has been paid off, or `None` if bytes can pass.
+    pub(crate) fn delay(&mut self, now: Instant) -> Option<Duration> {
+        self.refill(now);
+        if self.tokens >= 0. {
+            return None
+        }
+        Some(Duration::from_secs_f64(-self.tokens / self.rate as f64))
+    }
+
+    /// Takes the given number of bytes from the bucket.
+    pub(crate) fn consume(&mut self, bytes: usize) {
+        self.tokens -= bytes as f64;
+    }
+}
+
+/// A bucket shared by all sessions.
+pub(crate) type SharedTokenBucket = Arc<Mutex<TokenBucket>>;
+
+/// Limits the bandwidth of a single direction of a session, to the global limit shared with all
+/// other sessions and the limit of the session itself.
+#[derive(Debug, Default)]
+struct DirectionLimiter {
+    global: Option<SharedTokenBucket>,
+    peer: Option<TokenBucket>,
+}
+
+impl DirectionLimiter {
+    fn new(global: Option<SharedTokenBucket>, peer: Option<u64>) -> Self {
+        Self { global, peer: peer.map(TokenBucket::new) }
+    }
+
+    fn delay(&mut self, now: Instant) -> Option<Duration> {
+        let global = self.global.as_ref().and_then(|bucket| bucket.lock().delay(now));
+        let peer = self.peer.as_mut().and_then(|bucket| bucket.delay(now));
+        global.max(peer)
+    }
+
+    fn consume(&mut self, bytes: usize) {
+        if let Some(global) = &self.global {
+            global.lock().consume(bytes)
+        }
+        if let Some(peer) = &mut self.peer {
+            peer.consume(bytes)
+        }
+    }
+}
+
+/// The global bandwidth limits that are shared by all sessions.
+#[derive(Debug, Clone)]
+pub(crate) struct SharedBandwidth {
+    limits: BandwidthLimits,
+    upload: Option<SharedTokenBucket>,
+    download: Option<SharedTokenBucket>,
+}
+
+impl SharedBandwidth {
+    pub(crate) fn new(limits: BandwidthLimits) -> Self {
+        let bucket = |rate: u64| Arc::new(Mutex::new(TokenBucket::new(rate)));
+        Self { limits, upload: limits.upload.map(bucket), download: limits.download.map(bucket) }
+    }
+
+    /// Returns the bandwidth limiter for a new session.
+    pub(crate) fn session(&self) -> SessionBandwidth {
+        SessionBandwidth {
+            upload: DirectionLimiter::new(self.upload.clone(), self.limits.peer_upload),
+            download: DirectionLimiter::new(self.download.clone(), self.limits.peer_download),
+            timer: None,
+            metrics: Default::default(),
+        }
+    }
+}
+
+/// Enforces the bandwidth limits of a session.
+#[derive(Debug, Default)]
+pub(crate) struct SessionBandwidth {
+    upload: DirectionLimiter,
+    download: DirectionLimiter,
+    /// Wakes up the session once it can continue after being throttled.
+    timer: Option<Pin<Box<Sleep>>>,
+    metrics: BandwidthMetrics,
+}
+
+impl SessionBandwidth {
+    /// Returns `true` if a message can be sent, otherwise the session is woken up once it can.
+    pub(crate) fn poll_upload(&mut self, cx: &mut Context<'_>) -> bool {
+        let Some(delay) = self.upload.delay(Instant::now()) else { return true };
+        self.metrics.throttled_uploads.increment(1);
+        self.throttle(delay, cx);
+        false
+    }
+
+    /// Returns `true` if a message can be read, otherwise the session is woken up once it can.
+    pub(crate) fn poll_download(&mut self, cx: &mut Context<'_>) -> bool {
+        let Some(delay) = self.download.delay(Instant::now()) else { return true };
+        self.metrics.throttled_downloads.increment(1);
+        self.throttle(delay, cx);
+        false
+    }
+
+    /// Records a message of the given size that was sent.
+    pub(crate) fn on_upload(&mut self, bytes: usize) {
+        self.upload.consume(bytes)
+    }
+
+    /// Records a message of the given size that was read.
+    pub(crate) fn on_download(&mut self, bytes: usize) {
+        self.download.consume(bytes)
+    }
+
+    fn throttle(&mut self, delay: Duration, cx: &mut Context<'_>) {
+        let deadline = tokio::time::Instant::now() + delay;
+        let timer = match &mut self.timer {
+            Some(timer) => {
+                timer.as_mut().reset(deadline);
+                timer
+            }
+            None => self.timer.insert(Box::pin(tokio::time::sleep_until(deadline))),
+        };
+        if timer.as_mut().poll(cx).is_ready() {
+            cx.waker().wake_by_ref();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_bucket() {
+        let mut bucket = TokenBucket::new(1_000);
+        let now = bucket.last_refill;
+
+        assert_eq!(bucket.delay(now), None);
+        bucket.consume(1_500);
+        assert_eq!(bucket.delay(now), Some(Duration::from_millis(500)));
+
+        // refilled, but never above the rate
+        assert_eq!(bucket.delay(now + Duration::from_millis(500)), None);
+        assert_eq!(bucket.delay(now + Duration::from_secs(10)), None);
+        bucket.consume(1_000);
+        assert_eq!(bucket.delay(now + Duration::from_secs(10)), None);
+        bucket.consume(1);
+        assert!(bucket.delay(now + Duration::from_secs(10)).is_some());
+    }
+
+    #[tokio::test]
+    async fn global_limit_is_shared() {
+        let shared = SharedBandwidth::new(BandwidthLimits {
+            upload: Some(1_000),
+            peer_download: Some(100),
+            ..Default::default()
+        });
+        let mut session_1 = shared.session();
+        let mut session_2 = shared.session();
+
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(session_1.poll_upload(&mut cx));
+        session_1.on_upload(2_000);
+        assert!(!session_1.poll_upload(&mut cx));
+        assert!(!session_2.poll_upload(&mut cx));
+
+        // per session limit
+        assert!(session_1.poll_download(&mut cx));
+        session_1.on_download(200);
+        assert!(!session_1.poll_download(&mut cx));
+        assert!(session_2.poll_download(&mut cx));
+    }
+}
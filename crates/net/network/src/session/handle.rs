@@ -4,8 +4,8 @@ use std::{io, net::SocketAddr, sync::Arc, time::Instant};
 
 use reth_ecies::ECIESError;
 use reth_eth_wire::{
-    capability::CapabilityMessage, errors::EthStreamError, Capabilities, DisconnectReason,
-    EthVersion, Status,
+    capability::CapabilityMessage, errors::EthStreamError, BlockRangeUpdate, Capabilities,
+    DisconnectReason, EthVersion, Status,
 };
 use reth_network_api::PeerInfo;
 use reth_network_peers::{NodeRecord, PeerId};
@@ -77,6 +77,8 @@ pub struct ActiveSessionHandle {
     pub(crate) local_addr: Option<SocketAddr>,
     /// The Status message the peer sent for the `eth` handshake
     pub(crate) status: Arc<Status>,
+    /// The latest range of blocks the peer announced it can serve, `eth/69` only.
+    pub(crate) block_range: Option<BlockRangeUpdate>,
 }
 
 // === impl ActiveSessionHandle ===
@@ -150,6 +152,7 @@ impl ActiveSessionHandle {
             client_version: self.client_version.clone(),
             eth_version: self.version,
             status: self.status.clone(),
+            block_range: self.block_range,
             session_established: self.established,
             kind,
         }
@@ -1,6 +1,7 @@
 //! Support for handling peer sessions.
 
 mod active;
+mod bandwidth;
 mod conn;
 mod counter;
 mod handle;
@@ -27,14 +28,14 @@ use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
     capability::CapabilityMessage, errors::EthStreamError, multiplex::RlpxProtocolMultiplexer,
-    Capabilities, DisconnectReason, EthVersion, HelloMessageWithProtocols, Status,
-    UnauthedEthStream, UnauthedP2PStream,
+    BlockRangeUpdate, Capabilities, DisconnectReason, EthVersion, HelloMessageWithProtocols,
+    Status, UnauthedEthStream, UnauthedP2PStream,
 };
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_api::PeerRequestSender;
 use reth_network_peers::PeerId;
 use reth_network_types::SessionsConfig;
-use reth_primitives::{ForkFilter, ForkId, ForkTransition, Head};
+use reth_primitives::{BlockNumber, ForkFilter, ForkId, ForkTransition, Head};
 use reth_tasks::TaskSpawner;
 use rustc_hash::FxHashMap;
 use secp256k1::SecretKey;
@@ -51,7 +52,7 @@ use crate::{
     message::PeerMessage,
     metrics::SessionManagerMetrics,
     protocol::{IntoRlpxSubProtocol, RlpxSubProtocolHandlers, RlpxSubProtocols},
-    session::active::ActiveSession,
+    session::{active::ActiveSession, bandwidth::SharedBandwidth},
 };
 
 /// Internal identifier for active sessions.
@@ -82,6 +83,8 @@ pub struct SessionManager {
     hello_message: HelloMessageWithProtocols,
     /// The [`ForkFilter`] used to validate the peer's `Status` message.
     fork_filter: ForkFilter,
+    /// The earliest block the local node can serve, announced to `eth/69` peers.
+    earliest_block: BlockNumber,
     /// Size of the command buffer per session.
     session_command_buffer: usize,
     /// The executor for spawned tasks.
@@ -111,6 +114,8 @@ pub struct SessionManager {
     extra_protocols: RlpxSubProtocols,
     /// Metrics for the session manager.
     metrics: SessionManagerMetrics,
+    /// The bandwidth limits shared by all sessions.
+    bandwidth: SharedBandwidth,
 }
 
 // === impl SessionManager ===
@@ -141,6 +146,7 @@ impl SessionManager {
             status,
             hello_message,
             fork_filter,
+            earliest_block: 0,
             session_command_buffer: config.session_command_buffer,
             executor,
             pending_sessions: Default::default(),
@@ -151,6 +157,7 @@ impl SessionManager {
             active_session_rx: ReceiverStream::new(active_session_rx),
             extra_protocols,
             metrics: Default::default(),
+            bandwidth: SharedBandwidth::new(config.bandwidth),
         }
     }
 
@@ -216,9 +223,50 @@ impl SessionManager {
         self.status.total_difficulty = head.total_difficulty;
         let transition = self.fork_filter.set_head(head);
         self.status.forkid = self.fork_filter.current();
+        self.broadcast_block_range();
         transition
     }
 
+    /// Updates the earliest block the local node can serve.
+    ///
+    /// If it changed, the new range is announced to all `eth/69` peers.
+    pub(crate) fn update_earliest_block(&mut self, earliest: BlockNumber) {
+        if self.earliest_block != earliest {
+            self.earliest_block = earliest;
+            self.broadcast_block_range();
+        }
+    }
+
+    /// Returns the range of blocks the local node can serve.
+    pub(crate) fn block_range(&self) -> BlockRangeUpdate {
+        BlockRangeUpdate {
+            earliest: self.earliest_block.min(self.fork_filter.head().number),
+            latest: self.fork_filter.head().number,
+            latest_hash: self.status.blockhash,
+        }
+    }
+
+    /// Announces the current block range to all `eth/69` peers.
+    fn broadcast_block_range(&mut self) {
+        let range = self.block_range();
+        let peers = self
+            .active_sessions
+            .values()
+            .filter(|session| session.version.is_eth69())
+            .map(|session| session.remote_id)
+            .collect::<Vec<_>>();
+        for peer_id in peers {
+            self.send_message(&peer_id, PeerMessage::BlockRangeUpdate(range));
+        }
+    }
+
+    /// Records the block range the peer announced it can serve.
+    pub(crate) fn on_block_range_update(&mut self, peer_id: &PeerId, range: BlockRangeUpdate) {
+        if let Some(session) = self.active_sessions.get_mut(peer_id) {
+            session.block_range = Some(range);
+        }
+    }
+
     /// An incoming TCP connection was received. This starts the authentication process to turn this
     /// stream into an active peer session.
     ///
@@ -245,6 +293,7 @@ impl SessionManager {
         let hello_message = self.hello_message.clone();
         let status = self.status;
         let fork_filter = self.fork_filter.clone();
+        let block_range = self.block_range();
         let extra_handlers = self.extra_protocols.on_incoming(remote_addr);
         self.spawn(pending_session_with_timeout(
             self.pending_session_timeout,
@@ -262,6 +311,7 @@ impl SessionManager {
                 hello_message,
                 status,
                 fork_filter,
+                block_range,
                 extra_handlers,
             ),
         ));
@@ -286,6 +336,7 @@ impl SessionManager {
             let hello_message = self.hello_message.clone();
             let fork_filter = self.fork_filter.clone();
             let status = self.status;
+            let block_range = self.block_range();
             let extra_handlers = self.extra_protocols.on_outgoing(remote_addr, remote_peer_id);
             self.spawn(pending_session_with_timeout(
                 self.pending_session_timeout,
@@ -303,6 +354,7 @@ impl SessionManager {
                     hello_message,
                     status,
                     fork_filter,
+                    block_range,
                     extra_handlers,
                 ),
             ));
@@ -503,6 +555,7 @@ impl SessionManager {
                     internal_request_timeout: Arc::clone(&timeout),
                     protocol_breach_request_timeout: self.protocol_breach_request_timeout,
                     terminate_message: None,
+                    bandwidth: self.bandwidth.session(),
                 };
 
                 self.spawn(session);
@@ -520,6 +573,7 @@ impl SessionManager {
                     client_version: Arc::clone(&client_version),
                     remote_addr,
                     local_addr,
+                    block_range: None,
                 };
 
                 self.active_sessions.insert(peer_id, handle);
@@ -784,6 +838,7 @@ pub(crate) async fn start_pending_incoming_session(
     hello: HelloMessageWithProtocols,
     status: Status,
     fork_filter: ForkFilter,
+    block_range: BlockRangeUpdate,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
     authenticate(
@@ -797,6 +852,7 @@ pub(crate) async fn start_pending_incoming_session(
         hello,
         status,
         fork_filter,
+        block_range,
         extra_handlers,
     )
     .await
@@ -815,6 +871,7 @@ async fn start_pending_outbound_session(
     hello: HelloMessageWithProtocols,
     status: Status,
     fork_filter: ForkFilter,
+    block_range: BlockRangeUpdate,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
     let stream = match TcpStream::connect(remote_addr).await {
@@ -847,6 +904,7 @@ async fn start_pending_outbound_session(
         hello,
         status,
         fork_filter,
+        block_range,
         extra_handlers,
     )
     .await
@@ -865,6 +923,7 @@ async fn authenticate(
     hello: HelloMessageWithProtocols,
     status: Status,
     fork_filter: ForkFilter,
+    block_range: BlockRangeUpdate,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
     let local_addr = stream.local_addr().ok();
@@ -894,6 +953,7 @@ async fn authenticate(
         hello,
         status,
         fork_filter,
+        block_range,
         extra_handlers,
     )
     .boxed();
@@ -946,6 +1006,7 @@ async fn authenticate_stream(
     mut hello: HelloMessageWithProtocols,
     mut status: Status,
     fork_filter: ForkFilter,
+    block_range: BlockRangeUpdate,
     mut extra_handlers: RlpxSubProtocolHandlers,
 ) -> PendingSessionEvent {
     // Add extra protocols to the hello message
@@ -977,12 +1038,12 @@ async fn authenticate_stream(
         }
     };
 
+    // Before trying status handshake, set up the version to negotiated shared version
+    status.set_eth_version(eth_version);
+
     let (conn, their_status) = if p2p_stream.shared_capabilities().len() == 1 {
         // if the hello handshake was successful we can try status handshake
-        //
-        // Before trying status handshake, set up the version to negotiated shared version
-        status.set_eth_version(eth_version);
-        let eth_unauthed = UnauthedEthStream::new(p2p_stream);
+        let eth_unauthed = UnauthedEthStream::new(p2p_stream).with_block_range(block_range);
         let (eth_stream, their_status) = match eth_unauthed.handshake(status, fork_filter).await {
             Ok(stream_res) => stream_res,
             Err(err) => {
@@ -1010,18 +1071,20 @@ async fn authenticate_stream(
                 .ok();
         }
 
-        let (multiplex_stream, their_status) =
-            match multiplex_stream.into_eth_satellite_stream(status, fork_filter).await {
-                Ok((multiplex_stream, their_status)) => (multiplex_stream, their_status),
-                Err(err) => {
-                    return PendingSessionEvent::Disconnected {
-                        remote_addr,
-                        session_id,
-                        direction,
-                        error: Some(PendingSessionHandshakeError::Eth(err)),
-                    }
+        let (multiplex_stream, their_status) = match multiplex_stream
+            .into_eth_satellite_stream_with_block_range(status, Some(block_range), fork_filter)
+            .await
+        {
+            Ok((multiplex_stream, their_status)) => (multiplex_stream, their_status),
+            Err(err) => {
+                return PendingSessionEvent::Disconnected {
+                    remote_addr,
+                    session_id,
+                    direction,
+                    error: Some(PendingSessionHandshakeError::Eth(err)),
                 }
-            };
+            }
+        };
 
         (multiplex_stream.into(), their_status)
     };
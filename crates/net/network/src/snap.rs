@@ -0,0 +1,386 @@
+//! Client support for the `snap/1` protocol.
+//!
+//! The protocol runs as an additional `RLPx` subprotocol next to `eth`, see
+//! [`SnapProtocolHandler`]. Requests are sent with the [`SnapClientHandle`] to the connected peer
+//! with the fewest requests in flight. Requests sent while no peer is connected are queued until
+//! the next peer connects.
+//!
+//! The node does not serve the state snapshot, incoming requests are answered with empty
+//! responses.
+//!
+//! See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+
+use crate::protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler};
+use futures::{Stream, StreamExt};
+use parking_lot::Mutex;
+use reth_eth_wire::{
+    capability::SharedCapabilities,
+    multiplex::ProtocolConnection,
+    protocol::Protocol,
+    snap::{
+        AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges, GetTrieNodes,
+        SnapProtocolMessage, StorageRanges, TrieNodes,
+    },
+};
+use reth_network_api::{Direction, PeerId};
+use reth_network_p2p::{
+    download::DownloadClient,
+    error::{PeerRequestResult, RequestError},
+    snap::client::{SnapClient, SnapFut},
+};
+use reth_network_peers::WithPeerId;
+use reth_primitives::BytesMut;
+use std::{
+    collections::{HashMap, VecDeque},
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::trace;
+
+/// The default timeout of `snap/1` requests.
+pub const SNAP_REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// The [`ProtocolHandler`] of the `snap/1` protocol.
+///
+/// Add it to the network with
+/// [`NetworkProtocols::add_rlpx_sub_protocol`](crate::NetworkProtocols::add_rlpx_sub_protocol)
+/// and use [`SnapProtocolHandler::client`] to send requests to the connected peers.
+#[derive(Debug, Clone, Default)]
+pub struct SnapProtocolHandler {
+    peers: Arc<Mutex<SnapPeers>>,
+}
+
+impl SnapProtocolHandler {
+    /// Returns a client for the peers connected with this protocol.
+    pub fn client(&self) -> SnapClientHandle {
+        SnapClientHandle { peers: self.peers.clone(), request_timeout: SNAP_REQUEST_TIMEOUT }
+    }
+}
+
+impl ProtocolHandler for SnapProtocolHandler {
+    type ConnectionHandler = SnapConnectionHandler;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(SnapConnectionHandler { peers: self.peers.clone() })
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(SnapConnectionHandler { peers: self.peers.clone() })
+    }
+}
+
+/// The [`ConnectionHandler`] of the `snap/1` protocol.
+#[derive(Debug)]
+pub struct SnapConnectionHandler {
+    peers: Arc<Mutex<SnapPeers>>,
+}
+
+impl ConnectionHandler for SnapConnectionHandler {
+    type Connection = SnapConnection;
+
+    fn protocol(&self) -> Protocol {
+        Protocol::snap_1()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let connection_id = self.peers.lock().on_connection(peer_id, tx);
+        SnapConnection {
+            peer_id,
+            connection_id,
+            conn,
+            requests: UnboundedReceiverStream::new(rx),
+            inflight: HashMap::new(),
+            peers: self.peers,
+        }
+    }
+}
+
+/// A request sent to a peer.
+#[derive(Debug)]
+struct SnapRequest {
+    message: SnapProtocolMessage,
+    response: oneshot::Sender<PeerRequestResult<SnapProtocolMessage>>,
+}
+
+/// A connected peer.
+#[derive(Debug)]
+struct SnapPeer {
+    connection_id: u64,
+    to_connection: mpsc::UnboundedSender<SnapRequest>,
+    inflight: usize,
+}
+
+/// The peers connected with the `snap/1` protocol.
+#[derive(Debug, Default)]
+struct SnapPeers {
+    peers: HashMap<PeerId, SnapPeer>,
+    /// Requests waiting for a peer to connect.
+    queued: VecDeque<SnapRequest>,
+    next_request_id: u64,
+    next_connection_id: u64,
+}
+
+impl SnapPeers {
+    /// Registers a new connection, sends it the queued requests and returns its id.
+    fn on_connection(
+        &mut self,
+        peer_id: PeerId,
+        to_connection: mpsc::UnboundedSender<SnapRequest>,
+    ) -> u64 {
+        let connection_id = self.next_connection_id;
+        self.next_connection_id += 1;
+
+        let mut peer = SnapPeer { connection_id, to_connection, inflight: 0 };
+        for request in self.queued.drain(..) {
+            if !request.response.is_closed() && peer.to_connection.send(request).is_ok() {
+                peer.inflight += 1;
+            }
+        }
+        self.peers.insert(peer_id, peer);
+        connection_id
+    }
+
+    /// Sends the request to the peer with the fewest requests in flight, or queues it.
+    fn send(&mut self, mut request: SnapRequest) {
+        set_request_id(&mut request.message, self.next_request_id);
+        self.next_request_id += 1;
+
+        match self.peers.iter_mut().min_by_key(|(_, peer)| peer.inflight) {
+            Some((peer_id, peer)) => {
+                trace!(target: "net::snap", %peer_id, message = ?request.message.message_id(), "Sending request");
+                peer.inflight += 1;
+                // the connection is closing if this fails, which drops the response sender
+                let _ = peer.to_connection.send(request);
+            }
+            None => self.queued.push_back(request),
+        }
+    }
+}
+
+/// Sets the request id of a request message.
+fn set_request_id(message: &mut SnapProtocolMessage, id: u64) {
+    match message {
+        SnapProtocolMessage::GetAccountRange(msg) => msg.request_id = id,
+        SnapProtocolMessage::GetStorageRanges(msg) => msg.request_id = id,
+        SnapProtocolMessage::GetByteCodes(msg) => msg.request_id = id,
+        SnapProtocolMessage::GetTrieNodes(msg) => msg.request_id = id,
+        _ => {}
+    }
+}
+
+/// The `snap/1` connection to a peer.
+///
+/// The connection is closed if the peer sends an invalid message or is reported with
+/// [`DownloadClient::report_bad_message`].
+#[derive(Debug)]
+pub struct SnapConnection {
+    peer_id: PeerId,
+    connection_id: u64,
+    conn: ProtocolConnection,
+    requests: UnboundedReceiverStream<SnapRequest>,
+    inflight: HashMap<u64, oneshot::Sender<PeerRequestResult<SnapProtocolMessage>>>,
+    peers: Arc<Mutex<SnapPeers>>,
+}
+
+impl SnapConnection {
+    /// Removes the requests that are no longer awaited, e.g. because they timed out.
+    fn remove_abandoned_requests(&mut self) {
+        let before = self.inflight.len();
+        self.inflight.retain(|_, response| !response.is_closed());
+        self.on_requests_finished(before - self.inflight.len());
+    }
+
+    fn on_requests_finished(&self, count: usize) {
+        if count == 0 {
+            return
+        }
+        if let Some(peer) = self
+            .peers
+            .lock()
+            .peers
+            .get_mut(&self.peer_id)
+            .filter(|peer| peer.connection_id == self.connection_id)
+        {
+            peer.inflight = peer.inflight.saturating_sub(count);
+        }
+    }
+}
+
+impl Stream for SnapConnection {
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            match this.requests.poll_next_unpin(cx) {
+                Poll::Ready(Some(request)) => {
+                    this.remove_abandoned_requests();
+                    this.inflight.insert(request.message.request_id(), request.response);
+                    return Poll::Ready(Some(request.message.encoded()))
+                }
+                // the peer was removed
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => {}
+            }
+
+            let Some(msg) = ready!(this.conn.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+            let msg = match SnapProtocolMessage::decode_message(&mut &msg[..]) {
+                Ok(msg) => msg,
+                Err(err) => {
+                    trace!(target: "net::snap", peer_id = %this.peer_id, %err, "Invalid message");
+                    return Poll::Ready(None)
+                }
+            };
+
+            let response = match msg {
+                SnapProtocolMessage::GetAccountRange(request) => {
+                    SnapProtocolMessage::AccountRange(AccountRange {
+                        request_id: request.request_id,
+                        ..Default::default()
+                    })
+                }
+                SnapProtocolMessage::GetStorageRanges(request) => {
+                    SnapProtocolMessage::StorageRanges(StorageRanges {
+                        request_id: request.request_id,
+                        ..Default::default()
+                    })
+                }
+                SnapProtocolMessage::GetByteCodes(request) => {
+                    SnapProtocolMessage::ByteCodes(ByteCodes {
+                        request_id: request.request_id,
+                        ..Default::default()
+                    })
+                }
+                SnapProtocolMessage::GetTrieNodes(request) => {
+                    SnapProtocolMessage::TrieNodes(TrieNodes {
+                        request_id: request.request_id,
+                        ..Default::default()
+                    })
+                }
+                response => {
+                    if let Some(tx) = this.inflight.remove(&response.request_id()) {
+                        let _ = tx.send(Ok(WithPeerId::new(this.peer_id, response)));
+                        this.on_requests_finished(1);
+                    }
+                    continue
+                }
+            };
+            return Poll::Ready(Some(response.encoded()))
+        }
+    }
+}
+
+impl Drop for SnapConnection {
+    fn drop(&mut self) {
+        let mut peers = self.peers.lock();
+        if peers
+            .peers
+            .get(&self.peer_id)
+            .is_some_and(|peer| peer.connection_id == self.connection_id)
+        {
+            peers.peers.remove(&self.peer_id);
+        }
+    }
+}
+
+/// A [`SnapClient`] that sends requests to the peers connected with the [`SnapProtocolHandler`].
+#[derive(Debug, Clone)]
+pub struct SnapClientHandle {
+    peers: Arc<Mutex<SnapPeers>>,
+    request_timeout: Duration,
+}
+
+impl SnapClientHandle {
+    /// Sets the timeout of requests, including the time a request waits for a peer to connect.
+    pub const fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
+    /// Sends the request and returns the response, converted with the given function.
+    fn request<T: Send + 'static>(
+        &self,
+        message: SnapProtocolMessage,
+        convert: fn(SnapProtocolMessage) -> Option<T>,
+    ) -> SnapFut<T> {
+        let (response, rx) = oneshot::channel();
+        self.peers.lock().send(SnapRequest { message, response });
+        let request_timeout = self.request_timeout;
+        Box::pin(async move {
+            let response = tokio::time::timeout(request_timeout, rx)
+                .await
+                .map_err(|_| RequestError::Timeout)?
+                .map_err(|_| RequestError::ConnectionDropped)??;
+            let (peer_id, message) = response.split();
+            convert(message)
+                .map(|message| WithPeerId::new(peer_id, message))
+                .ok_or(RequestError::BadResponse)
+        })
+    }
+}
+
+impl DownloadClient for SnapClientHandle {
+    fn report_bad_message(&self, peer_id: PeerId) {
+        // dropping the sender closes the connection
+        self.peers.lock().peers.remove(&peer_id);
+    }
+
+    fn num_connected_peers(&self) -> usize {
+        self.peers.lock().peers.len()
+    }
+}
+
+impl SnapClient for SnapClientHandle {
+    fn get_account_range(&self, request: GetAccountRange) -> SnapFut<AccountRange> {
+        self.request(SnapProtocolMessage::GetAccountRange(request), |message| match message {
+            SnapProtocolMessage::AccountRange(response) => Some(response),
+            _ => None,
+        })
+    }
+
+    fn get_storage_ranges(&self, request: GetStorageRanges) -> SnapFut<StorageRanges> {
+        self.request(SnapProtocolMessage::GetStorageRanges(request), |message| match message {
+            SnapProtocolMessage::StorageRanges(response) => Some(response),
+            _ => None,
+        })
+    }
+
+    fn get_byte_codes(&self, request: GetByteCodes) -> SnapFut<ByteCodes> {
+        self.request(SnapProtocolMessage::GetByteCodes(request), |message| match message {
+            SnapProtocolMessage::ByteCodes(response) => Some(response),
+            _ => None,
+        })
+    }
+
+    fn get_trie_nodes(&self, request: GetTrieNodes) -> SnapFut<TrieNodes> {
+        self.request(SnapProtocolMessage::GetTrieNodes(request), |message| match message {
+            SnapProtocolMessage::TrieNodes(response) => Some(response),
+            _ => None,
+        })
+    }
+}
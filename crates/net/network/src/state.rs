@@ -10,6 +10,7 @@ use std::{
         Arc,
     },
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 
 use rand::seq::SliceRandom;
@@ -27,6 +28,7 @@ use crate::{
     fetch::{BlockResponseOutcome, FetchAction, StateFetcher},
     message::{BlockRequest, NewBlockMessage, PeerResponse, PeerResponseResult},
     peers::{PeerAction, PeersManager},
+    scoring::{ScoredRequestKind, ScoredResponse},
     FetchClient,
 };
 
@@ -151,7 +153,9 @@ impl NetworkState {
         // find the corresponding block number
         let block_number =
             self.client.block_number(status.blockhash).ok().flatten().unwrap_or_default();
-        self.state_fetcher.new_active_peer(peer, status.blockhash, block_number, timeout);
+        self.peers_manager.on_eth_version(&peer, status.version);
+        let stats = self.peers_manager.peer_stats(&peer).copied().unwrap_or_default();
+        self.state_fetcher.new_active_peer(peer, status.blockhash, block_number, timeout, stats);
 
         self.active_peers.insert(
             peer,
@@ -371,7 +375,7 @@ impl NetworkState {
                 }
             };
             let _ = peer.request_tx.to_session_tx.try_send(request);
-            peer.pending_response = Some(response);
+            peer.pending_response = Some((response, Instant::now()));
         }
     }
 
@@ -393,13 +397,42 @@ impl NetworkState {
     /// Delegates the response result to the fetcher which may return an outcome specific
     /// instruction that needs to be handled in [`Self::on_block_response_outcome`]. This could be
     /// a follow-up request or an instruction to slash the peer's reputation.
-    fn on_eth_response(&mut self, peer: PeerId, resp: PeerResponseResult) -> Option<StateAction> {
+    ///
+    /// Successful responses are also reported to the peers manager, which scores them.
+    fn on_eth_response(
+        &mut self,
+        peer: PeerId,
+        resp: PeerResponseResult,
+        latency: Duration,
+    ) -> Option<StateAction> {
         match resp {
             PeerResponseResult::BlockHeaders(res) => {
+                match &res {
+                    Ok(headers) => self.peers_manager.on_peer_response(
+                        &peer,
+                        ScoredResponse {
+                            kind: ScoredRequestKind::BlockHeaders,
+                            latency,
+                            items: headers.len(),
+                        },
+                    ),
+                    Err(_) => self.peers_manager.on_failed_peer_request(&peer),
+                }
                 let outcome = self.state_fetcher.on_block_headers_response(peer, res)?;
                 self.on_block_response_outcome(outcome)
             }
             PeerResponseResult::BlockBodies(res) => {
+                match &res {
+                    Ok(bodies) => self.peers_manager.on_peer_response(
+                        &peer,
+                        ScoredResponse {
+                            kind: ScoredRequestKind::BlockBodies,
+                            latency,
+                            items: bodies.len(),
+                        },
+                    ),
+                    Err(_) => self.peers_manager.on_failed_peer_request(&peer),
+                }
                 let outcome = self.state_fetcher.on_block_bodies_response(peer, res)?;
                 self.on_block_response_outcome(outcome)
             }
@@ -433,7 +466,7 @@ impl NetworkState {
 
             // poll all connected peers for responses
             for (id, peer) in &mut self.active_peers {
-                if let Some(mut response) = peer.pending_response.take() {
+                if let Some((mut response, sent_at)) = peer.pending_response.take() {
                     match response.poll(cx) {
                         Poll::Ready(res) => {
                             // check if the error is due to a closed channel to the session
@@ -449,12 +482,12 @@ impl NetworkState {
                                 // connection dropped error
                                 closed_sessions.push(*id);
                             } else {
-                                received_responses.push((*id, res));
+                                received_responses.push((*id, res, sent_at.elapsed()));
                             }
                         }
                         Poll::Pending => {
                             // not ready yet, store again.
-                            peer.pending_response = Some(response);
+                            peer.pending_response = Some((response, sent_at));
                         }
                     };
                 }
@@ -464,8 +497,8 @@ impl NetworkState {
                 self.on_session_closed(peer)
             }
 
-            for (peer_id, resp) in received_responses {
-                if let Some(action) = self.on_eth_response(peer_id, resp) {
+            for (peer_id, resp, latency) in received_responses {
+                if let Some(action) = self.on_eth_response(peer_id, resp, latency) {
                     self.queued_messages.push_back(action);
                 }
             }
@@ -494,8 +527,9 @@ pub(crate) struct ActivePeer {
     pub(crate) capabilities: Arc<Capabilities>,
     /// A communication channel directly to the session task.
     pub(crate) request_tx: PeerRequestSender,
-    /// The response receiver for a currently active request to that peer.
-    pub(crate) pending_response: Option<PeerResponse>,
+    /// The response receiver for a currently active request to that peer, and when the request
+    /// was sent.
+    pub(crate) pending_response: Option<(PeerResponse, Instant)>,
     /// Blocks we know the peer has.
     pub(crate) blocks: LruCache<B256>,
 }
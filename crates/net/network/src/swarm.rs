@@ -202,6 +202,9 @@ impl Swarm {
                         InboundConnectionError::ExceedsCapacity => {
                             trace!(target: "net", ?remote_addr, "No capacity for incoming connection");
                         }
+                        InboundConnectionError::IpNotAllowed => {
+                            trace!(target: "net", ?remote_addr, "The incoming ip address is rejected by the inbound filter");
+                        }
                     }
                     return None
                 }
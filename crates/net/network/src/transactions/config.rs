@@ -1,7 +1,9 @@
+use std::time::Duration;
+
 use derive_more::Constructor;
 
 use super::{
-    DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
+    DEFAULT_MAX_COUNT_TRACED_TRANSACTIONS, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
     DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
     SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
 };
@@ -17,6 +19,19 @@ pub struct TransactionsManagerConfig {
     pub transaction_fetcher_config: TransactionFetcherConfig,
     /// Max number of seen transactions to store for each peer.
     pub max_transactions_seen_by_peer_history: u32,
+    /// Interval at which local pending transactions are re-announced to all connected peers.
+    ///
+    /// Rebroadcasting is disabled if this is `None`.
+    pub local_transactions_rebroadcast_interval: Option<Duration>,
+    /// Max number of recently seen transactions to trace the propagation of.
+    ///
+    /// Tracing is disabled if this is `0`.
+    pub max_traced_transactions: u32,
+    /// Ratio of transactions, between `0.0` and `1.0`, for which propagation delays are recorded
+    /// as metrics.
+    ///
+    /// Transactions are sampled by hash, so that all nodes sample the same transactions.
+    pub propagation_metrics_sample_ratio: f64,
 }
 
 impl Default for TransactionsManagerConfig {
@@ -24,6 +39,9 @@ impl Default for TransactionsManagerConfig {
         Self {
             transaction_fetcher_config: TransactionFetcherConfig::default(),
             max_transactions_seen_by_peer_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
+            local_transactions_rebroadcast_interval: None,
+            max_traced_transactions: DEFAULT_MAX_COUNT_TRACED_TRANSACTIONS,
+            propagation_metrics_sample_ratio: 0.0,
         }
     }
 }
@@ -53,6 +53,14 @@ pub mod tx_manager {
     ///
     /// Default is 100 KiB, i.e. 3 200 transaction hashes.
     pub const DEFAULT_MAX_COUNT_BAD_IMPORTS: u32 = 100 * 1024 / 32;
+
+    /// Default limit for number of transactions to trace the propagation of.
+    ///
+    /// Default is 4096 transactions, the most recently seen are kept.
+    pub const DEFAULT_MAX_COUNT_TRACED_TRANSACTIONS: u32 = 4096;
+
+    /// Max number of announcements and broadcasts recorded per direction for a traced transaction.
+    pub const MAX_COUNT_PROPAGATION_EVENTS_PER_TRANSACTION: usize = 32;
 }
 
 /// Constants used by [`TransactionFetcher`](super::TransactionFetcher).
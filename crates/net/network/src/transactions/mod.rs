@@ -6,6 +6,8 @@ pub mod config;
 pub mod constants;
 /// Component responsible for fetching transactions from [`NewPooledTransactionHashes`].
 pub mod fetcher;
+/// Tracing of how transactions propagate between peers.
+mod propagation;
 pub mod validation;
 
 pub use self::constants::{
@@ -17,6 +19,8 @@ pub use validation::*;
 
 pub(crate) use fetcher::{FetchEvent, TransactionFetcher};
 
+use propagation::PropagationTracker;
+
 use self::constants::{tx_manager::*, DEFAULT_SOFT_LIMIT_BYTE_SIZE_TRANSACTIONS_BROADCAST_MESSAGE};
 use constants::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE;
 
@@ -46,7 +50,7 @@ use reth_network_p2p::{
     sync::SyncStateProvider,
 };
 use reth_network_peers::PeerId;
-use reth_network_types::ReputationChangeKind;
+use reth_network_types::{PropagationKind, ReputationChangeKind, TransactionPropagation};
 use reth_primitives::{PooledTransactionsElement, TransactionSigned, TxHash, B256};
 use reth_tokio_util::EventStream;
 use reth_transaction_pool::{
@@ -54,7 +58,10 @@ use reth_transaction_pool::{
     GetPooledTransactionLimit, PoolTransaction, PropagateKind, PropagatedTransactions,
     TransactionPool, ValidPoolTransaction,
 };
-use tokio::sync::{mpsc, oneshot, oneshot::error::RecvError};
+use tokio::{
+    sync::{mpsc, oneshot, oneshot::error::RecvError},
+    time::{Interval, MissedTickBehavior},
+};
 use tokio_stream::wrappers::{ReceiverStream, UnboundedReceiverStream};
 use tracing::{debug, trace};
 
@@ -170,6 +177,18 @@ impl TransactionsHandle {
         let res = self.get_transaction_hashes(vec![peer]).await?;
         Ok(res.into_values().next().unwrap_or_default())
     }
+
+    /// Request how a transaction was received from and forwarded to peers.
+    ///
+    /// Returns `None` if the transaction was not seen recently or tracing is disabled.
+    pub async fn transaction_propagation(
+        &self,
+        hash: TxHash,
+    ) -> Result<Option<TransactionPropagation>, RecvError> {
+        let (tx, rx) = oneshot::channel();
+        self.send(TransactionsCommand::GetTransactionPropagation { hash, tx });
+        rx.await
+    }
 }
 
 /// Manages transactions on top of the p2p network.
@@ -247,6 +266,10 @@ pub struct TransactionsManager<Pool> {
     transaction_events: UnboundedMeteredReceiver<NetworkTransactionEvent>,
     /// Max number of seen transactions to store for each peer.
     max_transactions_seen_by_peer_history: u32,
+    /// Interval at which local pending transactions are rebroadcast, if enabled.
+    local_rebroadcast_interval: Option<Interval>,
+    /// Traces when recently seen transactions were received and forwarded.
+    propagation_tracker: PropagationTracker,
     /// `TransactionsManager` metrics
     metrics: TransactionsManagerMetrics,
 }
@@ -274,6 +297,14 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
         let pending = pool.pending_transactions_listener();
         let pending_pool_imports_info = PendingPoolImportsInfo::default();
         let metrics = TransactionsManagerMetrics::default();
+
+        let local_rebroadcast_interval =
+            transactions_manager_config.local_transactions_rebroadcast_interval.map(|period| {
+                let mut interval =
+                    tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+                interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                interval
+            });
         metrics
             .capacity_pending_pool_imports
             .increment(pending_pool_imports_info.max_pending_pool_imports as u64);
@@ -299,6 +330,11 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
             ),
             max_transactions_seen_by_peer_history: transactions_manager_config
                 .max_transactions_seen_by_peer_history,
+            local_rebroadcast_interval,
+            propagation_tracker: PropagationTracker::new(
+                transactions_manager_config.max_traced_transactions,
+                transactions_manager_config.propagation_metrics_sample_ratio,
+            ),
             metrics,
         }
     }
@@ -404,7 +440,47 @@ where
             self.pool.get_all(hashes).into_iter().map(PropagateTransaction::new).collect(),
         );
 
-        // notify pool so events get fired
+        self.on_propagated(propagated);
+    }
+
+    /// Re-announces all local pending transactions to the connected peers.
+    ///
+    /// Local transactions are announced again even to peers that have already seen them, since
+    /// they may have been evicted from the peer's pool in the meantime.
+    fn rebroadcast_local_transactions(&mut self) {
+        if self.network.is_initially_syncing() || self.network.tx_gossip_disabled() {
+            return
+        }
+
+        let to_propagate = self
+            .pool
+            .get_local_pending_transactions()
+            .into_iter()
+            .filter(|tx| tx.propagate)
+            .map(PropagateTransaction::new)
+            .collect::<Vec<_>>();
+        if to_propagate.is_empty() {
+            return
+        }
+
+        trace!(target: "net::tx", num_txs=?to_propagate.len(), "Rebroadcasting local transactions");
+
+        for peer in self.peers.values_mut() {
+            for tx in &to_propagate {
+                peer.seen_transactions.remove(&tx.hash());
+            }
+        }
+
+        self.metrics.rebroadcast_local_transactions.increment(to_propagate.len() as u64);
+
+        let propagated = self.propagate_transactions(to_propagate);
+
+        self.on_propagated(propagated);
+    }
+
+    /// Records the propagated transactions and notifies the pool so events get fired.
+    fn on_propagated(&mut self, propagated: PropagatedTransactions) {
+        self.propagation_tracker.on_propagated(&propagated);
         self.pool.on_propagated(propagated);
     }
 
@@ -604,8 +680,7 @@ where
             propagated
         };
 
-        // notify pool so events get fired
-        self.pool.on_propagated(propagated);
+        self.on_propagated(propagated);
     }
 
     /// Request handler for an incoming `NewPooledTransactionHashes`
@@ -637,6 +712,7 @@ where
         // keep track of the transactions the peer knows
         let mut count_txns_already_seen_by_peer = 0;
         for tx in msg.iter_hashes().copied() {
+            self.propagation_tracker.on_received(tx, peer_id, PropagationKind::Hash);
             if !peer.seen_transactions.insert(tx) {
                 count_txns_already_seen_by_peer += 1;
             }
@@ -875,7 +951,7 @@ where
             }
             TransactionsCommand::PropagateTransactionsTo(txs, _peer) => {
                 if let Some(propagated) = self.propagate_full_transactions_to_peer(txs, _peer) {
-                    self.pool.on_propagated(propagated);
+                    self.on_propagated(propagated);
                 }
             }
             TransactionsCommand::GetTransactionHashes { peers, tx } => {
@@ -894,6 +970,9 @@ where
                 let sender = self.peers.get(&peer_id).map(|peer| peer.request_tx.clone());
                 peer_request_sender.send(sender).ok();
             }
+            TransactionsCommand::GetTransactionPropagation { hash, tx } => {
+                tx.send(self.propagation_tracker.get(&hash)).ok();
+            }
         }
     }
 
@@ -978,8 +1057,11 @@ where
         // recorded the hashes as seen by this peer in `Self::on_new_pooled_transaction_hashes`.
         let mut num_already_seen_by_peer = 0;
         for tx in &transactions {
-            if source.is_broadcast() && !peer.seen_transactions.insert(*tx.hash()) {
-                num_already_seen_by_peer += 1;
+            if source.is_broadcast() {
+                self.propagation_tracker.on_received(*tx.hash(), peer_id, PropagationKind::Full);
+                if !peer.seen_transactions.insert(*tx.hash()) {
+                    num_already_seen_by_peer += 1;
+                }
             }
         }
 
@@ -1349,6 +1431,15 @@ where
             poll_durations.acc_pending_fetch
         );
 
+        // Rebroadcast local pending transactions, if the interval elapsed.
+        if this
+            .local_rebroadcast_interval
+            .as_mut()
+            .is_some_and(|interval| interval.poll_tick(cx).is_ready())
+        {
+            this.rebroadcast_local_transactions();
+        }
+
         // Advance commands (propagate/fetch/serve txns).
         let maybe_more_commands = metered_poll_nested_stream_with_budget!(
             poll_durations.acc_cmds,
@@ -1576,7 +1667,7 @@ impl PooledTransactionsHashesBuilder {
     fn new(version: EthVersion) -> Self {
         match version {
             EthVersion::Eth66 | EthVersion::Eth67 => Self::Eth66(Default::default()),
-            EthVersion::Eth68 => Self::Eth68(Default::default()),
+            EthVersion::Eth68 | EthVersion::Eth69 => Self::Eth68(Default::default()),
         }
     }
 
@@ -1658,6 +1749,8 @@ enum TransactionsCommand {
         peer_id: PeerId,
         peer_request_sender: oneshot::Sender<Option<PeerRequestSender>>,
     },
+    /// Requests the propagation trace of a transaction.
+    GetTransactionPropagation { hash: TxHash, tx: oneshot::Sender<Option<TransactionPropagation>> },
 }
 
 /// All events related to transactions emitted by the network.
@@ -1744,8 +1837,9 @@ mod tests {
     };
     use reth_primitives::hex;
     use reth_provider::test_utils::NoopProvider;
-    use reth_transaction_pool::test_utils::{
-        testing_pool, MockTransaction, MockTransactionFactory, TestPool,
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction, MockTransactionFactory, TestPool},
+        TransactionEvent, TransactionOrigin,
     };
     use secp256k1::SecretKey;
     use std::{
@@ -2385,4 +2479,55 @@ mod tests {
         let propagated = tx_manager.propagate_transactions(propagate);
         assert!(propagated.0.is_empty());
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_rebroadcast_local_transactions() {
+        reth_tracing::init_test_tracing();
+
+        let (mut tx_manager, network) = new_tx_manager().await;
+        let peer_id = PeerId::random();
+
+        // ensure not syncing
+        network.handle().update_sync_state(SyncState::Idle);
+
+        // mock a peer
+        let (tx, _rx) = mpsc::channel(1);
+        tx_manager.on_network_event(NetworkEvent::SessionEstablished {
+            peer_id,
+            remote_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+            client_version: Arc::from(""),
+            capabilities: Arc::new(vec![].into()),
+            messages: PeerRequestSender::new(peer_id, tx),
+            status: Arc::new(Default::default()),
+            version: EthVersion::Eth68,
+        });
+
+        let local_hash = tx_manager
+            .pool
+            .add_transaction(TransactionOrigin::Local, MockTransaction::eip1559())
+            .await
+            .unwrap();
+        let external_hash = tx_manager
+            .pool
+            .add_transaction(TransactionOrigin::External, MockTransaction::eip1559())
+            .await
+            .unwrap();
+
+        // the peer has already seen both transactions
+        let peer = tx_manager.peers.get_mut(&peer_id).unwrap();
+        peer.seen_transactions.insert(local_hash);
+        peer.seen_transactions.insert(external_hash);
+
+        let mut local_events = tx_manager.pool.transaction_event_listener(local_hash).unwrap();
+        let mut external_events =
+            tx_manager.pool.transaction_event_listener(external_hash).unwrap();
+
+        // only the local transaction is announced to the peer again
+        tx_manager.rebroadcast_local_transactions();
+        assert!(matches!(
+            local_events.next().await,
+            Some(TransactionEvent::Propagated(kinds)) if kinds.len() == 1
+        ));
+        assert!(external_events.next().now_or_never().is_none());
+    }
 }
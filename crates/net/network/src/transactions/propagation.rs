@@ -0,0 +1,230 @@
+//! Tracing of how transactions propagate between peers.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use reth_network_peers::PeerId;
+use reth_network_types::{PropagationEvent, PropagationKind, TransactionPropagation};
+use reth_primitives::TxHash;
+use reth_transaction_pool::{PropagateKind, PropagatedTransactions};
+
+use super::constants::tx_manager::MAX_COUNT_PROPAGATION_EVENTS_PER_TRANSACTION;
+use crate::{cache::LruMap, metrics::TransactionPropagationMetrics};
+
+/// Records when recently seen transactions were received from peers and forwarded to peers.
+///
+/// Delays of a sample of the transactions are recorded as metrics.
+#[derive(Debug)]
+pub(super) struct PropagationTracker {
+    /// Traces of the most recently seen transactions, `None` if tracing is disabled.
+    traces: Option<LruMap<TxHash, PropagationTrace>>,
+    /// Ratio of transactions for which metrics are recorded.
+    metrics_sample_ratio: f64,
+    /// Metrics of the sampled transactions.
+    metrics: TransactionPropagationMetrics,
+}
+
+impl PropagationTracker {
+    /// Returns a new tracker that traces up to `max_transactions` transactions.
+    pub(super) fn new(max_transactions: u32, metrics_sample_ratio: f64) -> Self {
+        Self {
+            traces: (max_transactions > 0).then(|| LruMap::new(max_transactions)),
+            metrics_sample_ratio,
+            metrics: TransactionPropagationMetrics::default(),
+        }
+    }
+
+    /// Records that the peer announced or sent the transaction.
+    pub(super) fn on_received(&mut self, hash: TxHash, peer_id: PeerId, kind: PropagationKind) {
+        let Some(traces) = self.traces.as_mut() else { return };
+
+        let mut is_new = false;
+        let Some(trace) = traces.get_or_insert(hash, || {
+            is_new = true;
+            PropagationTrace::new(Some(peer_id), is_sampled(&hash, self.metrics_sample_ratio))
+        }) else {
+            return
+        };
+
+        let elapsed = trace.first_seen_at.elapsed();
+        if trace.sampled {
+            if is_new {
+                self.metrics.sampled_transactions.increment(1);
+            } else {
+                self.metrics.announcement_delay.record(elapsed);
+            }
+        }
+        trace.received.push(peer_id, kind, elapsed);
+    }
+
+    /// Records the peers the transactions were announced or sent to.
+    pub(super) fn on_propagated(&mut self, propagated: &PropagatedTransactions) {
+        let Some(traces) = self.traces.as_mut() else { return };
+
+        for (hash, kinds) in &propagated.0 {
+            // transactions that weren't received from a peer first show up here
+            let mut is_new = false;
+            let Some(trace) = traces.get_or_insert(*hash, || {
+                is_new = true;
+                PropagationTrace::new(None, is_sampled(hash, self.metrics_sample_ratio))
+            }) else {
+                continue
+            };
+
+            let elapsed = trace.first_seen_at.elapsed();
+            if trace.sampled {
+                if is_new {
+                    self.metrics.sampled_transactions.increment(1);
+                } else if trace.first_seen_from.is_some() && trace.propagated.events.is_empty() {
+                    self.metrics.forward_delay.record(elapsed);
+                }
+            }
+            for kind in kinds {
+                let (peer_id, kind) = match kind {
+                    PropagateKind::Full(peer_id) => (*peer_id, PropagationKind::Full),
+                    PropagateKind::Hash(peer_id) => (*peer_id, PropagationKind::Hash),
+                };
+                trace.propagated.push(peer_id, kind, elapsed);
+            }
+        }
+    }
+
+    /// Returns the trace of the transaction, if it was seen recently.
+    pub(super) fn get(&self, hash: &TxHash) -> Option<TransactionPropagation> {
+        let trace = self.traces.as_ref()?.peek(hash)?;
+
+        Some(TransactionPropagation {
+            hash: *hash,
+            first_seen: trace.first_seen.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis()
+                as u64,
+            first_seen_from: trace.first_seen_from,
+            received: trace.received.events.clone(),
+            propagated: trace.propagated.events.clone(),
+        })
+    }
+}
+
+/// Returns `true` if metrics should be recorded for the transaction.
+///
+/// Transactions are sampled by their hash, so that all nodes sample the same transactions and
+/// their delays can be compared.
+fn is_sampled(hash: &TxHash, ratio: f64) -> bool {
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&hash[..8]);
+    ratio >= 1.0 || (u64::from_be_bytes(prefix) as f64) < ratio * u64::MAX as f64
+}
+
+/// The propagation of a single transaction.
+#[derive(Debug)]
+struct PropagationTrace {
+    /// When the transaction was first seen.
+    first_seen: SystemTime,
+    /// When the transaction was first seen, used to measure delays.
+    first_seen_at: Instant,
+    /// The peer the transaction was first received from, `None` if it was local.
+    first_seen_from: Option<PeerId>,
+    /// Whether metrics are recorded for the transaction.
+    sampled: bool,
+    /// Announcements and broadcasts received from peers.
+    received: PropagationEvents,
+    /// Announcements and broadcasts sent to peers.
+    propagated: PropagationEvents,
+}
+
+impl PropagationTrace {
+    fn new(first_seen_from: Option<PeerId>, sampled: bool) -> Self {
+        Self {
+            first_seen: SystemTime::now(),
+            first_seen_at: Instant::now(),
+            first_seen_from,
+            sampled,
+            received: Default::default(),
+            propagated: Default::default(),
+        }
+    }
+}
+
+/// Bounded list of [`PropagationEvent`]s, later events are dropped once it's full.
+#[derive(Debug, Default)]
+struct PropagationEvents {
+    events: Vec<PropagationEvent>,
+}
+
+impl PropagationEvents {
+    fn push(&mut self, peer_id: PeerId, kind: PropagationKind, elapsed: Duration) {
+        if self.events.len() < MAX_COUNT_PROPAGATION_EVENTS_PER_TRANSACTION {
+            self.events.push(PropagationEvent {
+                peer_id,
+                kind,
+                elapsed_ms: elapsed.as_millis() as u64,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::B256;
+
+    #[test]
+    fn traces_received_and_propagated() {
+        let mut tracker = PropagationTracker::new(2, 0.0);
+        let hash = B256::with_last_byte(1);
+        let (first, second, third) = (PeerId::random(), PeerId::random(), PeerId::random());
+
+        tracker.on_received(hash, first, PropagationKind::Hash);
+        tracker.on_received(hash, second, PropagationKind::Full);
+
+        let mut propagated = PropagatedTransactions::default();
+        propagated.0.insert(hash, vec![PropagateKind::Full(third)]);
+        tracker.on_propagated(&propagated);
+
+        let trace = tracker.get(&hash).unwrap();
+        assert_eq!(trace.first_seen_from, Some(first));
+        assert_eq!(
+            trace.received.iter().map(|e| (e.peer_id, e.kind)).collect::<Vec<_>>(),
+            vec![(first, PropagationKind::Hash), (second, PropagationKind::Full)]
+        );
+        assert_eq!(trace.propagated.len(), 1);
+        assert_eq!(trace.propagated[0].peer_id, third);
+        assert_eq!(trace.propagated[0].kind, PropagationKind::Full);
+    }
+
+    #[test]
+    fn traces_local_transactions_and_evicts_oldest() {
+        let mut tracker = PropagationTracker::new(1, 0.0);
+        let (local, remote) = (B256::with_last_byte(1), B256::with_last_byte(2));
+
+        let mut propagated = PropagatedTransactions::default();
+        propagated.0.insert(local, vec![PropagateKind::Hash(PeerId::random())]);
+        tracker.on_propagated(&propagated);
+
+        let trace = tracker.get(&local).unwrap();
+        assert_eq!(trace.first_seen_from, None);
+        assert!(trace.received.is_empty());
+
+        tracker.on_received(remote, PeerId::random(), PropagationKind::Hash);
+        assert!(tracker.get(&local).is_none());
+        assert!(tracker.get(&remote).is_some());
+    }
+
+    #[test]
+    fn disabled_tracker_records_nothing() {
+        let mut tracker = PropagationTracker::new(0, 1.0);
+        let hash = B256::with_last_byte(1);
+
+        tracker.on_received(hash, PeerId::random(), PropagationKind::Full);
+        assert!(tracker.get(&hash).is_none());
+    }
+
+    #[test]
+    fn samples_by_hash() {
+        let low = B256::with_last_byte(1);
+        let high = B256::repeat_byte(0xff);
+
+        assert!(!is_sampled(&low, 0.0));
+        assert!(is_sampled(&low, 0.5));
+        assert!(!is_sampled(&high, 0.5));
+        assert!(is_sampled(&high, 1.0));
+    }
+}
@@ -3,6 +3,7 @@ mod connect;
 mod multiplex;
 mod requests;
 mod session;
+mod snap;
 mod startup;
 mod txgossip;
 
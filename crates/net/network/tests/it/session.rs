@@ -1,13 +1,17 @@
 //! Session tests
 
+use std::time::Duration;
+
 use futures::StreamExt;
-use reth_eth_wire::EthVersion;
+use reth_eth_wire::{BlockRangeUpdate, EthVersion, GetReceipts};
 use reth_network::{
-    test_utils::{PeerConfig, Testnet},
-    NetworkEvent, NetworkEventListenerProvider,
+    test_utils::{NetworkEventStream, PeerConfig, Testnet},
+    NetworkEvent, NetworkEventListenerProvider, PeerRequest,
 };
-use reth_network_api::{NetworkInfo, Peers};
+use reth_network_api::{NetworkInfo, Peers, PeersInfo};
+use reth_primitives::{Head, B256};
 use reth_provider::test_utils::NoopProvider;
+use tokio::sync::oneshot;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_session_established_with_highest_version() {
@@ -81,3 +85,151 @@ async fn test_session_established_with_different_capability() {
 
     handle.terminate().await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_session_established_with_eth69() {
+    reth_tracing::init_test_tracing();
+
+    let mut net = Testnet::create_with(0, NoopProvider::default()).await;
+    for _ in 0..2 {
+        let peer = PeerConfig::with_protocols(
+            NoopProvider::default(),
+            [EthVersion::Eth69.into(), EthVersion::Eth68.into()],
+        );
+        net.add_peer_with_config(peer).await.unwrap();
+    }
+    net.for_each_mut(|peer| peer.install_request_handler());
+
+    let handle0 = net.peers()[0].handle();
+    let handle1 = net.peers()[1].handle();
+    let mut events0 = NetworkEventStream::new(handle0.event_listener());
+
+    let handle = net.spawn();
+
+    handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
+    let connected = events0.next_session_established().await.unwrap();
+    assert_eq!(connected, *handle1.peer_id());
+
+    let info = handle0.get_peer_by_id(*handle1.peer_id()).await.unwrap().unwrap();
+    assert_eq!(info.status.version, EthVersion::Eth69 as u8);
+
+    // receipts are exchanged without bloom filter
+    let (tx, rx) = oneshot::channel();
+    handle0.send_request(
+        *handle1.peer_id(),
+        PeerRequest::GetReceipts { request: GetReceipts(vec![B256::random()]), response: tx },
+    );
+    let receipts = rx.await.unwrap().unwrap();
+    assert!(receipts.0.is_empty());
+
+    handle.terminate().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_session_eth69_falls_back_to_eth68() {
+    reth_tracing::init_test_tracing();
+
+    let mut net = Testnet::create(1).await;
+
+    let p1 = PeerConfig::with_protocols(
+        NoopProvider::default(),
+        [EthVersion::Eth69.into(), EthVersion::Eth68.into()],
+    );
+    net.add_peer_with_config(p1).await.unwrap();
+
+    let handle0 = net.peers()[0].handle();
+    let handle1 = net.peers()[1].handle();
+    let mut events0 = NetworkEventStream::new(handle0.event_listener());
+
+    let handle = net.spawn();
+
+    handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
+    let connected = events0.next_session_established().await.unwrap();
+    assert_eq!(connected, *handle1.peer_id());
+
+    let info = handle0.get_peer_by_id(*handle1.peer_id()).await.unwrap().unwrap();
+    assert_eq!(info.status.version, EthVersion::Eth68 as u8);
+
+    handle.terminate().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_session_eth69_block_range_update() {
+    reth_tracing::init_test_tracing();
+
+    let mut net = Testnet::create_with(0, NoopProvider::default()).await;
+    for _ in 0..2 {
+        let peer = PeerConfig::with_protocols(
+            NoopProvider::default(),
+            [EthVersion::Eth69.into(), EthVersion::Eth68.into()],
+        );
+        net.add_peer_with_config(peer).await.unwrap();
+    }
+
+    let handle0 = net.peers()[0].handle();
+    let handle1 = net.peers()[1].handle();
+    let mut events0 = NetworkEventStream::new(handle0.event_listener());
+
+    let handle = net.spawn();
+
+    handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
+    let connected = events0.next_session_established().await.unwrap();
+    assert_eq!(connected, *handle1.peer_id());
+
+    // nothing was announced after the handshake yet
+    let info = handle0.get_peer_by_id(*handle1.peer_id()).await.unwrap().unwrap();
+    assert_eq!(info.block_range, None);
+
+    // the pruned history and the new head are announced to the peer
+    let head = Head { number: 10, hash: B256::random(), ..Default::default() };
+    handle1.update_earliest_block(5);
+    handle1.update_status(head);
+
+    let expected = BlockRangeUpdate { earliest: 5, latest: 10, latest_hash: head.hash };
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let info = handle0.get_peer_by_id(*handle1.peer_id()).await.unwrap().unwrap();
+            if info.block_range == Some(expected) {
+                break
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("block range update not received");
+
+    handle.terminate().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_session_eth68_no_block_range_update() {
+    reth_tracing::init_test_tracing();
+
+    let mut net = Testnet::create(1).await;
+
+    let p1 = PeerConfig::with_protocols(
+        NoopProvider::default(),
+        [EthVersion::Eth69.into(), EthVersion::Eth68.into()],
+    );
+    net.add_peer_with_config(p1).await.unwrap();
+
+    let handle0 = net.peers()[0].handle();
+    let handle1 = net.peers()[1].handle();
+    let mut events0 = NetworkEventStream::new(handle0.event_listener());
+
+    let handle = net.spawn();
+
+    handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
+    let connected = events0.next_session_established().await.unwrap();
+    assert_eq!(connected, *handle1.peer_id());
+
+    // an eth/68 session must not receive the eth/69 only message
+    handle1.update_status(Head { number: 10, hash: B256::random(), ..Default::default() });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let info = handle0.get_peer_by_id(*handle1.peer_id()).await.unwrap().unwrap();
+    assert_eq!(info.block_range, None);
+    assert_eq!(handle0.num_connected_peers(), 1);
+
+    handle.terminate().await;
+}
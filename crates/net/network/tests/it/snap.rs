@@ -0,0 +1,42 @@
+//! Tests for the `snap/1` subprotocol.
+
+use reth_eth_wire::snap::{AccountRange, GetAccountRange, GetByteCodes};
+use reth_network::{snap::SnapProtocolHandler, test_utils::Testnet};
+use reth_network_p2p::{download::DownloadClient, snap::client::SnapClient};
+use reth_primitives::B256;
+use reth_provider::test_utils::MockEthProvider;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_snap_requests() {
+    reth_tracing::init_test_tracing();
+    let provider = MockEthProvider::default();
+    let mut net = Testnet::create_with(2, provider.clone()).await;
+
+    let snap = SnapProtocolHandler::default();
+    let client = snap.client();
+    net.peers_mut()[0].add_rlpx_sub_protocol(snap);
+    net.peers_mut()[1].add_rlpx_sub_protocol(SnapProtocolHandler::default());
+
+    // requests are queued until a peer connects
+    let request = client.get_account_range(GetAccountRange {
+        root_hash: B256::repeat_byte(1),
+        limit_hash: B256::repeat_byte(0xff),
+        response_bytes: 1024,
+        ..Default::default()
+    });
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+
+    // the node does not serve the state, so the responses are empty
+    let response = request.await.unwrap();
+    assert_eq!(response.peer_id(), *handle.peers()[1].peer_id());
+    assert_eq!(response.into_data(), AccountRange { request_id: 0, ..Default::default() });
+    assert_eq!(client.num_connected_peers(), 1);
+
+    let response = client
+        .get_byte_codes(GetByteCodes { hashes: vec![B256::repeat_byte(2)], ..Default::default() })
+        .await
+        .unwrap();
+    assert!(response.into_data().codes.is_empty());
+}
@@ -41,6 +41,9 @@ pub mod priority;
 /// Syncing related traits.
 pub mod sync;
 
+/// Traits for implementing `snap/1` state clients.
+pub mod snap;
+
 /// Common test helpers for mocking out Consensus, Downloaders and Header Clients.
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
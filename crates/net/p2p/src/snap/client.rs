@@ -0,0 +1,28 @@
+use crate::{download::DownloadClient, error::PeerRequestResult};
+use futures::Future;
+use reth_eth_wire_types::snap::{
+    AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges, GetTrieNodes,
+    StorageRanges, TrieNodes,
+};
+use std::pin::Pin;
+
+/// The future type of [`SnapClient`] requests.
+pub type SnapFut<T> = Pin<Box<dyn Future<Output = PeerRequestResult<T>> + Send + Sync>>;
+
+/// A client capable of downloading the state with the `snap/1` protocol.
+///
+/// The request id of the requests is assigned by the client.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait SnapClient: DownloadClient {
+    /// Fetches a range of accounts.
+    fn get_account_range(&self, request: GetAccountRange) -> SnapFut<AccountRange>;
+
+    /// Fetches ranges of storage slots.
+    fn get_storage_ranges(&self, request: GetStorageRanges) -> SnapFut<StorageRanges>;
+
+    /// Fetches bytecodes by their hashes.
+    fn get_byte_codes(&self, request: GetByteCodes) -> SnapFut<ByteCodes>;
+
+    /// Fetches trie nodes by their paths.
+    fn get_trie_nodes(&self, request: GetTrieNodes) -> SnapFut<TrieNodes>;
+}
@@ -0,0 +1,2 @@
+/// Trait definition for [`SnapClient`](client::SnapClient).
+pub mod client;
@@ -0,0 +1,26 @@
+#![allow(missing_docs)]
+
+use tonic_build::manual::{Builder, Method, Service};
+
+fn main() {
+    let method = |name: &str, route: &str, input: &str, output: &str| {
+        Method::builder()
+            .name(name)
+            .route_name(route)
+            .input_type(format!("crate::proto::{input}"))
+            .output_type(format!("crate::proto::{output}"))
+            .codec_path("tonic::codec::ProstCodec")
+            .build()
+    };
+
+    let sentry = Service::builder()
+        .name("Sentry")
+        .package("reth.sentry")
+        .method(method("get_block_headers", "GetBlockHeaders", "HeadersRequest", "PeerResponse"))
+        .method(method("get_block_bodies", "GetBlockBodies", "BodiesRequest", "PeerResponse"))
+        .method(method("report_bad_message", "ReportBadMessage", "ReportRequest", "Empty"))
+        .method(method("peer_count", "PeerCount", "Empty", "PeerCountReply"))
+        .build();
+
+    Builder::new().compile(&[sentry]);
+}
@@ -0,0 +1,242 @@
+//! A download client backed by sentries.
+
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use alloy_rlp::Decodable;
+use futures::future;
+use reth_network_p2p::{
+    bodies::client::{BodiesClient, BodiesFut},
+    download::DownloadClient,
+    error::{PeerRequestResult, RequestError},
+    headers::client::{HeadersClient, HeadersFut, HeadersRequest},
+    priority::Priority,
+};
+use reth_network_peers::{PeerId, WithPeerId};
+use reth_primitives::B256;
+use tonic::{
+    transport::{Channel, Endpoint},
+    Response, Status,
+};
+use tracing::debug;
+
+use crate::{
+    codec::{decode_response, encode_bodies_request, encode_headers_request, status_to_error},
+    proto::{self, sentry_client::SentryClient},
+};
+
+/// A sentry the client is connected to.
+#[derive(Debug)]
+struct RemoteSentry {
+    client: SentryClient<Channel>,
+    /// Number of peers the sentry was connected to when it last responded.
+    num_peers: AtomicUsize,
+}
+
+/// A download client that sends requests to the networks of one or more sentries.
+///
+/// Requests are distributed over the sentries that are connected to peers in round robin order.
+#[derive(Debug, Clone)]
+pub struct SentryFetchClient {
+    sentries: Arc<[RemoteSentry]>,
+    /// Index of the sentry that is asked next.
+    next: Arc<AtomicUsize>,
+}
+
+impl SentryFetchClient {
+    /// Creates a new client for the sentries at the given channels.
+    ///
+    /// The sentries are assumed to have no peers until they respond, see
+    /// [`SentryFetchClient::update_peer_counts`].
+    pub fn new(channels: impl IntoIterator<Item = Channel>) -> Self {
+        let sentries = channels
+            .into_iter()
+            .map(|channel| RemoteSentry {
+                client: SentryClient::new(channel),
+                num_peers: AtomicUsize::new(0),
+            })
+            .collect();
+        Self { sentries, next: Default::default() }
+    }
+
+    /// Connects to the sentries at the given endpoints.
+    pub async fn connect(
+        endpoints: impl IntoIterator<Item = Endpoint>,
+    ) -> Result<Self, tonic::transport::Error> {
+        let mut channels = Vec::new();
+        for endpoint in endpoints {
+            channels.push(endpoint.connect().await?);
+        }
+        let client = Self::new(channels);
+        client.update_peer_counts().await;
+        Ok(client)
+    }
+
+    /// Asks all sentries how many peers they are connected to.
+    pub async fn update_peer_counts(&self) {
+        for sentry in self.sentries.iter() {
+            match sentry.client.clone().peer_count(proto::Empty {}).await {
+                Ok(reply) => {
+                    sentry.num_peers.store(reply.into_inner().num_peers as usize, Ordering::Relaxed)
+                }
+                Err(status) => {
+                    debug!(target: "net::sentry", %status, "Failed to get peer count of sentry");
+                    sentry.num_peers.store(0, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// Returns the index of the sentry the next request is sent to, preferring sentries that are
+    /// connected to peers.
+    fn next_sentry(&self) -> Option<usize> {
+        let len = self.sentries.len();
+        if len == 0 {
+            return None
+        }
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let index = (start..start + len)
+            .map(|index| index % len)
+            .find(|index| self.sentries[*index].num_peers.load(Ordering::Relaxed) > 0)
+            .unwrap_or(start % len);
+        Some(index)
+    }
+
+    /// Sends a request to the next sentry and decodes the items of the response.
+    ///
+    /// The request is sent on a new task, so that the returned future is `Sync`.
+    fn request<T, F, Fut>(
+        &self,
+        send: F,
+    ) -> std::pin::Pin<Box<dyn Future<Output = PeerRequestResult<Vec<T>>> + Send + Sync>>
+    where
+        T: Decodable + Send + Sync + 'static,
+        F: FnOnce(SentryClient<Channel>) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Response<proto::PeerResponse>, Status>> + Send,
+    {
+        let Some(index) = self.next_sentry() else {
+            return Box::pin(future::err(RequestError::ChannelClosed))
+        };
+        let sentries = Arc::clone(&self.sentries);
+        let request = tokio::spawn(async move {
+            let sentry = &sentries[index];
+            let response = send(sentry.client.clone())
+                .await
+                .map_err(|status| status_to_error(&status))?
+                .into_inner();
+            sentry.num_peers.store(response.num_peers as usize, Ordering::Relaxed);
+            let (peer_id, items) = decode_response(&response)?;
+            Ok(WithPeerId::new(peer_id, items))
+        });
+        Box::pin(async move { request.await.unwrap_or(Err(RequestError::ChannelClosed)) })
+    }
+}
+
+impl DownloadClient for SentryFetchClient {
+    fn report_bad_message(&self, peer_id: PeerId) {
+        // the peer is reported to all sentries, only the one that is connected to it applies it
+        for sentry in self.sentries.iter() {
+            let mut client = sentry.client.clone();
+            let request = proto::ReportRequest { peer_id: peer_id.to_vec() };
+            tokio::spawn(async move {
+                if let Err(status) = client.report_bad_message(request).await {
+                    debug!(target: "net::sentry", %status, %peer_id, "Failed to report peer to sentry");
+                }
+            });
+        }
+    }
+
+    fn num_connected_peers(&self) -> usize {
+        self.sentries.iter().map(|sentry| sentry.num_peers.load(Ordering::Relaxed)).sum()
+    }
+}
+
+impl HeadersClient for SentryFetchClient {
+    type Output = HeadersFut;
+
+    fn get_headers_with_priority(
+        &self,
+        request: HeadersRequest,
+        priority: Priority,
+    ) -> Self::Output {
+        let request = encode_headers_request(&request, priority);
+        self.request(|mut client| async move { client.get_block_headers(request).await })
+    }
+}
+
+impl BodiesClient for SentryFetchClient {
+    type Output = BodiesFut;
+
+    fn get_block_bodies_with_priority(
+        &self,
+        hashes: Vec<B256>,
+        priority: Priority,
+    ) -> Self::Output {
+        let request = encode_bodies_request(&hashes, priority);
+        self.request(|mut client| async move { client.get_block_bodies(request).await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SentryService;
+    use reth_network_p2p::test_utils::TestFullBlockClient;
+    use reth_primitives::{BlockBody, Header, SealedHeader};
+    use tokio::net::TcpListener;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tonic::transport::Server;
+
+    async fn spawn_sentry(client: TestFullBlockClient) -> Endpoint {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(SentryService::new(client).into_server())
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        Endpoint::from_shared(format!("http://{addr}")).unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn fetch_through_sentries() {
+        let network = TestFullBlockClient::default();
+        let header = SealedHeader::new(Header { number: 1, ..Default::default() }, B256::random());
+        let body = BlockBody::default();
+        network.insert(header.clone(), body.clone());
+
+        let endpoints = [spawn_sentry(network.clone()).await, spawn_sentry(network).await];
+        let client = SentryFetchClient::connect(endpoints).await.unwrap();
+        assert_eq!(client.num_connected_peers(), 2);
+
+        for _ in 0..2 {
+            let headers = client.get_headers(HeadersRequest {
+                start: header.hash().into(),
+                limit: 1,
+                direction: Default::default(),
+            });
+            assert_eq!(headers.await.unwrap().into_data(), vec![header.clone().unseal()]);
+        }
+
+        let bodies = client.get_block_bodies(vec![header.hash()]).await.unwrap();
+        assert_eq!(bodies.into_data(), vec![body]);
+
+        let unknown = client.get_block_bodies(vec![B256::random()]).await.unwrap();
+        assert!(unknown.into_data().is_empty());
+    }
+
+    #[tokio::test]
+    async fn no_sentries() {
+        let client = SentryFetchClient::new([]);
+        assert_eq!(client.num_connected_peers(), 0);
+        assert_eq!(
+            client.get_block_bodies(vec![B256::random()]).await.unwrap_err(),
+            RequestError::ChannelClosed
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//! Conversions between the download client types and the messages of the gRPC interface.
+
+use alloy_rlp::{Decodable, Encodable};
+use reth_network_p2p::{
+    error::RequestError,
+    headers::client::{HeadersDirection, HeadersRequest},
+    priority::Priority,
+};
+use reth_network_peers::PeerId;
+use reth_primitives::{BlockHashOrNumber, B256};
+use tonic::{Code, Status};
+
+use crate::proto;
+
+const fn priority(high_priority: bool) -> Priority {
+    if high_priority {
+        Priority::High
+    } else {
+        Priority::Normal
+    }
+}
+
+pub(crate) fn encode_headers_request(
+    request: &HeadersRequest,
+    priority: Priority,
+) -> proto::HeadersRequest {
+    let (start_hash, start_number) = match request.start {
+        BlockHashOrNumber::Hash(hash) => (hash.to_vec(), 0),
+        BlockHashOrNumber::Number(number) => (Vec::new(), number),
+    };
+    proto::HeadersRequest {
+        start_hash,
+        start_number,
+        limit: request.limit,
+        falling: request.direction.is_falling(),
+        high_priority: priority.is_high(),
+    }
+}
+
+pub(crate) fn decode_headers_request(
+    request: proto::HeadersRequest,
+) -> Result<(HeadersRequest, Priority), Status> {
+    let start = if request.start_hash.is_empty() {
+        request.start_number.into()
+    } else {
+        decode_hash(&request.start_hash)?.into()
+    };
+    let headers_request = HeadersRequest {
+        start,
+        limit: request.limit,
+        direction: HeadersDirection::new(request.falling),
+    };
+    Ok((headers_request, priority(request.high_priority)))
+}
+
+pub(crate) fn encode_bodies_request(hashes: &[B256], priority: Priority) -> proto::BodiesRequest {
+    proto::BodiesRequest {
+        hashes: hashes.iter().map(|hash| hash.to_vec()).collect(),
+        high_priority: priority.is_high(),
+    }
+}
+
+pub(crate) fn decode_bodies_request(
+    request: proto::BodiesRequest,
+) -> Result<(Vec<B256>, Priority), Status> {
+    let hashes = request.hashes.iter().map(|hash| decode_hash(hash)).collect::<Result<_, _>>()?;
+    Ok((hashes, priority(request.high_priority)))
+}
+
+fn decode_hash(hash: &[u8]) -> Result<B256, Status> {
+    B256::try_from(hash).map_err(|_| Status::invalid_argument("invalid block hash"))
+}
+
+pub(crate) fn decode_peer_id(peer_id: &[u8]) -> Result<PeerId, Status> {
+    PeerId::try_from(peer_id).map_err(|_| Status::invalid_argument("invalid peer id"))
+}
+
+/// Encodes the response of a peer.
+pub(crate) fn encode_response<T: Encodable>(
+    peer_id: PeerId,
+    items: &[T],
+    num_peers: usize,
+) -> proto::PeerResponse {
+    let mut data = Vec::new();
+    alloy_rlp::encode_list(items, &mut data);
+    proto::PeerResponse { peer_id: peer_id.to_vec(), data, num_peers: num_peers as u64 }
+}
+
+/// Decodes the response of a peer, a response that can't be decoded is a bad response.
+pub(crate) fn decode_response<T: Decodable>(
+    response: &proto::PeerResponse,
+) -> Result<(PeerId, Vec<T>), RequestError> {
+    let peer_id =
+        PeerId::try_from(response.peer_id.as_slice()).map_err(|_| RequestError::BadResponse)?;
+    let items =
+        Vec::<T>::decode(&mut response.data.as_slice()).map_err(|_| RequestError::BadResponse)?;
+    Ok((peer_id, items))
+}
+
+/// Returns the status a failed request is served with.
+pub(crate) fn error_to_status(err: RequestError) -> Status {
+    let code = match err {
+        RequestError::ChannelClosed => Code::Unavailable,
+        RequestError::ConnectionDropped => Code::Aborted,
+        RequestError::UnsupportedCapability => Code::Unimplemented,
+        RequestError::Timeout => Code::DeadlineExceeded,
+        RequestError::BadResponse => Code::DataLoss,
+    };
+    Status::new(code, err.to_string())
+}
+
+/// Returns the error of a request that failed with the given status.
+///
+/// If the sentry can't be reached, this is the same as a closed channel to the network.
+pub(crate) fn status_to_error(status: &Status) -> RequestError {
+    match status.code() {
+        Code::Aborted => RequestError::ConnectionDropped,
+        Code::Unimplemented => RequestError::UnsupportedCapability,
+        Code::DeadlineExceeded => RequestError::Timeout,
+        Code::DataLoss => RequestError::BadResponse,
+        _ => RequestError::ChannelClosed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_roundtrip() {
+        let request = HeadersRequest {
+            start: B256::random().into(),
+            limit: 10,
+            direction: HeadersDirection::Falling,
+        };
+        let (decoded, priority) =
+            decode_headers_request(encode_headers_request(&request, Priority::High)).unwrap();
+        assert_eq!(decoded.start, request.start);
+        assert_eq!(decoded.limit, request.limit);
+        assert_eq!(decoded.direction, request.direction);
+        assert!(priority.is_high());
+
+        let hashes = vec![B256::random(), B256::random()];
+        let decoded = decode_bodies_request(encode_bodies_request(&hashes, Priority::Normal));
+        assert_eq!(decoded.unwrap(), (hashes, Priority::Normal));
+
+        let invalid = proto::BodiesRequest { hashes: vec![vec![1]], high_priority: false };
+        assert_eq!(decode_bodies_request(invalid).unwrap_err().code(), Code::InvalidArgument);
+    }
+
+    #[test]
+    fn error_roundtrip() {
+        for err in [
+            RequestError::ChannelClosed,
+            RequestError::ConnectionDropped,
+            RequestError::UnsupportedCapability,
+            RequestError::Timeout,
+            RequestError::BadResponse,
+        ] {
+            assert_eq!(status_to_error(&error_to_status(err.clone())), err);
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! A standalone p2p sentry.
+//!
+//! A sentry runs the p2p network on behalf of a node and serves it to the node over gRPC, so the
+//! p2p surface can run in a separate, unprivileged process, and a node can fan out its requests
+//! through multiple sentries, e.g. in different regions.
+//!
+//! - [`SentryService`] serves any download client, e.g. the network's `FetchClient`, to nodes.
+//! - [`SentryFetchClient`] is the download client of a node that is backed by one or more sentries,
+//!   and can be used by the pipeline's downloaders in place of the network.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod client;
+mod codec;
+pub mod proto;
+mod service;
+
+pub use client::SentryFetchClient;
+pub use service::SentryService;
@@ -0,0 +1,71 @@
+//! Messages of the sentry gRPC interface.
+//!
+//! Headers and bodies are exchanged in their RLP encoding, the same as on the wire.
+
+#![allow(missing_docs, clippy::missing_const_for_fn)]
+
+include!(concat!(env!("OUT_DIR"), "/reth.sentry.Sentry.rs"));
+
+/// A `GetBlockHeaders` request.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct HeadersRequest {
+    /// Hash of the first header, empty if the request starts at `start_number`.
+    #[prost(bytes = "vec", tag = "1")]
+    pub start_hash: Vec<u8>,
+    /// Number of the first header.
+    #[prost(uint64, tag = "2")]
+    pub start_number: u64,
+    /// Maximum number of headers.
+    #[prost(uint64, tag = "3")]
+    pub limit: u64,
+    /// Whether the headers are returned in falling order.
+    #[prost(bool, tag = "4")]
+    pub falling: bool,
+    /// Whether the request has high priority.
+    #[prost(bool, tag = "5")]
+    pub high_priority: bool,
+}
+
+/// A `GetBlockBodies` request.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct BodiesRequest {
+    /// Hashes of the blocks.
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub hashes: Vec<Vec<u8>>,
+    /// Whether the request has high priority.
+    #[prost(bool, tag = "2")]
+    pub high_priority: bool,
+}
+
+/// The response of a peer of the sentry.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct PeerResponse {
+    /// Id of the peer that responded.
+    #[prost(bytes = "vec", tag = "1")]
+    pub peer_id: Vec<u8>,
+    /// The RLP encoded list of headers or bodies.
+    #[prost(bytes = "vec", tag = "2")]
+    pub data: Vec<u8>,
+    /// Number of peers the sentry is connected to.
+    #[prost(uint64, tag = "3")]
+    pub num_peers: u64,
+}
+
+/// Reports a peer that responded with a bad message.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct ReportRequest {
+    /// Id of the peer.
+    #[prost(bytes = "vec", tag = "1")]
+    pub peer_id: Vec<u8>,
+}
+
+/// Number of peers the sentry is connected to.
+#[derive(Clone, Copy, PartialEq, Eq, prost::Message)]
+pub struct PeerCountReply {
+    #[prost(uint64, tag = "1")]
+    pub num_peers: u64,
+}
+
+/// An empty message.
+#[derive(Clone, Copy, PartialEq, Eq, prost::Message)]
+pub struct Empty {}
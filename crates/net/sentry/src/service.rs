@@ -0,0 +1,100 @@
+//! The gRPC service of the sentry.
+
+use std::net::SocketAddr;
+
+use reth_network_p2p::{bodies::client::BodiesClient, headers::client::HeadersClient};
+use tonic::{transport::Server, Request, Response, Status};
+use tracing::trace;
+
+use crate::{
+    codec::{
+        decode_bodies_request, decode_headers_request, decode_peer_id, encode_response,
+        error_to_status,
+    },
+    proto::{
+        self,
+        sentry_server::{Sentry, SentryServer},
+    },
+};
+
+/// Serves the network of the sentry to nodes.
+///
+/// The requests of the nodes are forwarded to the given download client, which picks the peer that
+/// serves the request.
+#[derive(Debug, Clone)]
+pub struct SentryService<C> {
+    client: C,
+}
+
+impl<C> SentryService<C>
+where
+    C: HeadersClient + BodiesClient + Clone + 'static,
+{
+    /// Creates a new service that serves requests with the given download client.
+    pub const fn new(client: C) -> Self {
+        Self { client }
+    }
+
+    /// Returns the gRPC server of the service, that can be added to a [`Server`].
+    pub fn into_server(self) -> SentryServer<Self> {
+        SentryServer::new(self)
+    }
+
+    /// Serves the service on the given address until the future is dropped or fails.
+    pub async fn serve(self, addr: SocketAddr) -> Result<(), tonic::transport::Error> {
+        Server::builder().add_service(self.into_server()).serve(addr).await
+    }
+}
+
+#[tonic::async_trait]
+impl<C> Sentry for SentryService<C>
+where
+    C: HeadersClient + BodiesClient + Clone + 'static,
+{
+    async fn get_block_headers(
+        &self,
+        request: Request<proto::HeadersRequest>,
+    ) -> Result<Response<proto::PeerResponse>, Status> {
+        let (request, priority) = decode_headers_request(request.into_inner())?;
+        trace!(target: "net::sentry", ?request, "Serving headers request");
+        let response = self
+            .client
+            .get_headers_with_priority(request, priority)
+            .await
+            .map_err(error_to_status)?;
+        let (peer_id, headers) = response.split();
+        Ok(Response::new(encode_response(peer_id, &headers, self.client.num_connected_peers())))
+    }
+
+    async fn get_block_bodies(
+        &self,
+        request: Request<proto::BodiesRequest>,
+    ) -> Result<Response<proto::PeerResponse>, Status> {
+        let (hashes, priority) = decode_bodies_request(request.into_inner())?;
+        trace!(target: "net::sentry", len = hashes.len(), "Serving bodies request");
+        let response = self
+            .client
+            .get_block_bodies_with_priority(hashes, priority)
+            .await
+            .map_err(error_to_status)?;
+        let (peer_id, bodies) = response.split();
+        Ok(Response::new(encode_response(peer_id, &bodies, self.client.num_connected_peers())))
+    }
+
+    async fn report_bad_message(
+        &self,
+        request: Request<proto::ReportRequest>,
+    ) -> Result<Response<proto::Empty>, Status> {
+        let peer_id = decode_peer_id(&request.into_inner().peer_id)?;
+        self.client.report_bad_message(peer_id);
+        Ok(Response::new(proto::Empty {}))
+    }
+
+    async fn peer_count(
+        &self,
+        _request: Request<proto::Empty>,
+    ) -> Result<Response<proto::PeerCountReply>, Status> {
+        let num_peers = self.client.num_connected_peers() as u64;
+        Ok(Response::new(proto::PeerCountReply { num_peers }))
+    }
+}
@@ -1,10 +1,17 @@
 //! Node add-ons. Depend on core [`NodeComponents`](crate::NodeComponents).
 
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc};
 
-use reth_node_api::{EthApiTypes, FullNodeComponents, NodeAddOns};
+use reth_exex::ExExDeliveryMode;
+use reth_node_api::{EthApiTypes, FullNodeComponents, FullNodeTypes, NodeAddOns};
+use reth_rpc_builder::EthMiddleware;
 
-use crate::{exex::BoxedLaunchExEx, hooks::NodeHooks, rpc::RpcHooks};
+use crate::{
+    exex::BoxedLaunchExEx,
+    hooks::NodeHooks,
+    rpc::{RpcHooks, RpcNamespaces},
+    setup::ExtendPipelineStages,
+};
 
 /// Additional node extensions.
 ///
@@ -12,10 +19,13 @@ use crate::{exex::BoxedLaunchExEx, hooks::NodeHooks, rpc::RpcHooks};
 pub struct AddOns<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> {
     /// Additional `NodeHooks` that are called at specific points in the node's launch lifecycle.
     pub hooks: NodeHooks<Node, AddOns>,
-    /// The `ExExs` (execution extensions) of the node.
-    pub exexs: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    /// The `ExExs` (execution extensions) of the node, with the delivery mode of their
+    /// notifications.
+    pub exexs: Vec<(String, ExExDeliveryMode, Box<dyn BoxedLaunchExEx<Node>>)>,
     /// Additional RPC add-ons.
     pub rpc: RpcAddOns<Node, AddOns::EthApi>,
+    /// Hooks that configure the stages of the sync pipeline, applied in order.
+    pub pipeline_stages: Vec<Box<dyn ExtendPipelineStages<<Node as FullNodeTypes>::DB>>>,
 }
 
 /// Captures node specific addons that can be installed on top of the type configured node and are
@@ -26,4 +36,8 @@ pub struct RpcAddOns<Node: FullNodeComponents, EthApi: EthApiTypes> {
     pub _eth_api: PhantomData<EthApi>,
     /// Additional RPC hooks.
     pub hooks: RpcHooks<Node, EthApi>,
+    /// Middleware that intercepts all `eth_` methods.
+    pub eth_middleware: Option<Arc<dyn EthMiddleware>>,
+    /// Custom RPC namespaces to install on the RPC server.
+    pub namespaces: RpcNamespaces<Node>,
 }
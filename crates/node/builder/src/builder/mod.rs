@@ -9,16 +9,24 @@ pub use states::*;
 
 use std::sync::Arc;
 
-use futures::Future;
+use futures::{Future, StreamExt};
+use jsonrpsee::Methods;
+use reth_auto_seal_consensus::DevStateChanges;
 use reth_chainspec::ChainSpec;
 use reth_cli_util::get_secret_key;
+use reth_config::config::StageConfig;
 use reth_db_api::{
     database::Database,
     database_metrics::{DatabaseMetadata, DatabaseMetrics},
 };
-use reth_exex::ExExContext;
+use reth_evm::{
+    overrides::{spec_id_by_name, CustomPrecompile, EvmOverrides},
+    state_changes::BlockStateChanges,
+};
+use reth_exex::{ExExContext, ExExDeliveryMode};
 use reth_network::{
-    NetworkBuilder, NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager,
+    snap::SnapProtocolHandler, NetworkBuilder, NetworkConfig, NetworkConfigBuilder, NetworkHandle,
+    NetworkManager,
 };
 use reth_node_api::{FullNodeTypes, FullNodeTypesAdapter, NodeAddOns, NodeTypes};
 use reth_node_core::{
@@ -28,10 +36,19 @@ use reth_node_core::{
     primitives::Head,
     rpc::eth::{helpers::AddDevSigners, FullEthApiServer},
 };
-use reth_primitives::revm_primitives::EnvKzgSettings;
-use reth_provider::{providers::BlockchainProvider, ChainSpecProvider, FullProvider};
+use reth_primitives::{revm_primitives::EnvKzgSettings, BlockNumber};
+use reth_provider::{
+    providers::BlockchainProvider, CanonStateSubscriptions, ChainSpecProvider, FullProvider,
+    ProviderResult, PruneCheckpointReader,
+};
+use reth_prune::PruneSegment;
+use reth_rpc_builder::EthMiddleware;
+use reth_stages::StageSetBuilder;
 use reth_tasks::TaskExecutor;
-use reth_transaction_pool::{PoolConfig, TransactionPool};
+use reth_transaction_pool::{
+    policy::{ReplacementPolicy, TransactionPolicy},
+    PoolConfig, TransactionPool,
+};
 use secp256k1::SecretKey;
 use tracing::{info, trace, warn};
 
@@ -40,7 +57,7 @@ use crate::{
     components::NodeComponentsBuilder,
     node::FullNode,
     rpc::{EthApiBuilderProvider, RethRpcServerHandles, RpcContext},
-    DefaultNodeLauncher, LaunchNode, Node, NodeHandle,
+    DefaultNodeLauncher, EthApiBuilderCtx, LaunchNode, Node, NodeHandle,
 };
 
 /// The adapter type for a reth node with the builtin provider type
@@ -98,8 +115,11 @@ pub type RethFullAdapter<DB, Types> = FullNodeTypesAdapter<Types, DB, Blockchain
 /// is launched [`NodeBuilderWithComponents::on_component_initialized`], or once the rpc server(s)
 /// are launched [`NodeBuilderWithComponents::on_rpc_started`]. The
 /// [`NodeBuilderWithComponents::extend_rpc_modules`] can be used to inject custom rpc modules into
-/// the rpc server before it is launched. See also [`RpcContext`] All hooks accept a closure that is
-/// then invoked at the appropriate time in the node's launch process.
+/// the rpc server before it is launched. See also [`RpcContext`]. Custom namespaces that only need
+/// the shared node components can be registered with
+/// [`NodeBuilderWithComponents::install_rpc_namespace`], and custom stages can be inserted into the
+/// sync pipeline with [`NodeBuilderWithComponents::extend_pipeline_stages`]. All hooks accept a
+/// closure that is then invoked at the appropriate time in the node's launch process.
 ///
 /// ## Flow
 ///
@@ -187,6 +207,8 @@ impl<DB> NodeBuilder<DB> {
         );
         self.config = self.config.with_datadir_args(reth_node_core::args::DatadirArgs {
             datadir: path.clone(),
+            // don't pause syncing on machines running low on disk space
+            min_free_space_mb: 0,
             ..Default::default()
         });
 
@@ -429,6 +451,33 @@ where
         Self { builder: self.builder.extend_rpc_modules(hook), task_executor: self.task_executor }
     }
 
+    /// Installs a custom RPC namespace, e.g. `myapp`, on all configured transports.
+    ///
+    /// See also [`NodeBuilderWithComponents::install_rpc_namespace`].
+    pub fn install_rpc_namespace<F, M>(self, name: impl Into<String>, builder: F) -> Self
+    where
+        F: FnOnce(&EthApiBuilderCtx<NodeAdapter<T, CB::Components>>) -> eyre::Result<M>
+            + Send
+            + 'static,
+        M: Into<Methods>,
+    {
+        Self {
+            builder: self.builder.install_rpc_namespace(name, builder),
+            task_executor: self.task_executor,
+        }
+    }
+
+    /// Installs a middleware that intercepts all `eth_` methods of the RPC server.
+    pub fn with_eth_middleware<M>(self, middleware: M) -> Self
+    where
+        M: EthMiddleware,
+    {
+        Self {
+            builder: self.builder.with_eth_middleware(middleware),
+            task_executor: self.task_executor,
+        }
+    }
+
     /// Installs an `ExEx` (Execution Extension) in the node.
     ///
     /// # Note
@@ -446,6 +495,44 @@ where
         }
     }
 
+    /// Installs an `ExEx` (Execution Extension) in the node, that receives notifications with the
+    /// given [`ExExDeliveryMode`].
+    ///
+    /// # Note
+    ///
+    /// The `ExEx` ID must be unique.
+    pub fn install_exex_with_delivery_mode<F, R, E>(
+        self,
+        exex_id: impl Into<String>,
+        delivery_mode: ExExDeliveryMode,
+        exex: F,
+    ) -> Self
+    where
+        F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
+        R: Future<Output = eyre::Result<E>> + Send,
+        E: Future<Output = eyre::Result<()>> + Send,
+    {
+        Self {
+            builder: self.builder.install_exex_with_delivery_mode(exex_id, delivery_mode, exex),
+            task_executor: self.task_executor,
+        }
+    }
+
+    /// Installs a hook that configures the stages of the sync pipeline.
+    ///
+    /// See also [`NodeBuilderWithComponents::extend_pipeline_stages`].
+    pub fn extend_pipeline_stages<F>(self, hook: F) -> Self
+    where
+        F: FnOnce(StageSetBuilder<T::DB>, &StageConfig) -> eyre::Result<StageSetBuilder<T::DB>>
+            + Send
+            + 'static,
+    {
+        Self {
+            builder: self.builder.extend_pipeline_stages(hook),
+            task_executor: self.task_executor,
+        }
+    }
+
     /// Launches the node with the given launcher.
     pub async fn launch_with<L>(self, launcher: L) -> eyre::Result<L::Node>
     where
@@ -504,6 +591,16 @@ pub struct BuilderContext<Node: FullNodeTypes> {
     pub(crate) executor: TaskExecutor,
     /// Config container
     pub(crate) config_container: WithConfigs,
+    /// Admission policy for the transaction pool.
+    pub(crate) pool_policy: Option<Arc<dyn TransactionPolicy>>,
+    /// Replacement policy for the transaction pool.
+    pub(crate) pool_replacement_policy: Option<Arc<dyn ReplacementPolicy>>,
+    /// The `snap/1` protocol added to the network, if snap sync is enabled.
+    pub(crate) snap_protocol: Option<SnapProtocolHandler>,
+    /// Custom precompiles that are added to the EVM overrides.
+    pub(crate) precompiles: Vec<CustomPrecompile>,
+    /// The state changes of the mined chain, in dev mode.
+    pub(crate) dev_state_changes: Option<DevStateChanges>,
 }
 
 impl<Node: FullNodeTypes> BuilderContext<Node> {
@@ -514,7 +611,64 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         executor: TaskExecutor,
         config_container: WithConfigs,
     ) -> Self {
-        Self { head, provider, executor, config_container }
+        Self {
+            head,
+            provider,
+            executor,
+            config_container,
+            pool_policy: None,
+            pool_replacement_policy: None,
+            snap_protocol: None,
+            precompiles: Vec::new(),
+            dev_state_changes: None,
+        }
+    }
+
+    /// Sets the `snap/1` protocol that is added to the network.
+    pub(crate) fn with_snap_protocol(mut self, protocol: SnapProtocolHandler) -> Self {
+        self.snap_protocol = Some(protocol);
+        self
+    }
+
+    /// Sets the state changes of the mined chain in dev mode.
+    pub(crate) fn with_dev_state_changes(mut self, state_changes: DevStateChanges) -> Self {
+        self.dev_state_changes = Some(state_changes);
+        self
+    }
+
+    /// Returns a copy of the context with the given transaction pool admission and replacement
+    /// policies.
+    pub(crate) fn with_pool_policy(
+        &self,
+        policy: Option<Arc<dyn TransactionPolicy>>,
+        replacement_policy: Option<Arc<dyn ReplacementPolicy>>,
+    ) -> Self {
+        Self {
+            head: self.head,
+            provider: self.provider.clone(),
+            executor: self.executor.clone(),
+            config_container: self.config_container.clone(),
+            pool_policy: policy,
+            pool_replacement_policy: replacement_policy,
+            snap_protocol: self.snap_protocol.clone(),
+            precompiles: self.precompiles.clone(),
+            dev_state_changes: self.dev_state_changes.clone(),
+        }
+    }
+
+    /// Returns a copy of the context with the given custom precompiles.
+    pub(crate) fn with_precompiles(&self, precompiles: Vec<CustomPrecompile>) -> Self {
+        Self {
+            head: self.head,
+            provider: self.provider.clone(),
+            executor: self.executor.clone(),
+            config_container: self.config_container.clone(),
+            pool_policy: self.pool_policy.clone(),
+            pool_replacement_policy: self.pool_replacement_policy.clone(),
+            snap_protocol: self.snap_protocol.clone(),
+            precompiles,
+            dev_state_changes: self.dev_state_changes.clone(),
+        }
     }
 
     /// Returns the configured provider to interact with the blockchain.
@@ -537,6 +691,28 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         &self.config_container.toml_config
     }
 
+    /// Returns the EVM overrides of the `[evm]` section of the reth.toml config, with the custom
+    /// precompiles registered on the components builder.
+    ///
+    /// Returns an error if a configured spec id is unknown.
+    pub fn evm_overrides(&self) -> eyre::Result<EvmOverrides> {
+        let mut overrides =
+            EvmOverrides { custom_precompiles: self.precompiles.clone(), ..Default::default() };
+        let Some(config) = &self.reth_config().evm else { return Ok(overrides) };
+
+        overrides.chain_id = config.chain_id;
+        overrides.max_code_size = config.max_code_size;
+        for activation in &config.spec_activations {
+            let spec_id = spec_id_by_name(&activation.spec)
+                .ok_or_else(|| eyre::eyre!("Unknown EVM spec `{}`", activation.spec))?;
+            overrides.spec_activations.insert(activation.block, spec_id);
+        }
+        overrides.precompiles =
+            config.precompiles.iter().map(|alias| (alias.address, alias.precompile)).collect();
+
+        Ok(overrides)
+    }
+
     /// Returns the executor of the node.
     ///
     /// This can be used to execute async tasks or functions during the setup.
@@ -554,9 +730,30 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().dev.dev
     }
 
+    /// Returns the state changes of the mined chain in dev mode, which the block executor should
+    /// apply to the executed blocks, so the state of the chain can be changed over RPC, e.g. with
+    /// `anvil_setBalance`.
+    pub fn dev_state_changes(&self) -> Option<Arc<dyn BlockStateChanges>> {
+        self.dev_state_changes
+            .clone()
+            .map(|state_changes| Arc::new(state_changes) as Arc<dyn BlockStateChanges>)
+    }
+
     /// Returns the transaction pool config of the node.
+    ///
+    /// This includes the admission policy configured with
+    /// [`ComponentsBuilder::pool_policy`](crate::components::ComponentsBuilder::pool_policy) and
+    /// the replacement policy configured with
+    /// [`ComponentsBuilder::pool_replacement_policy`](crate::components::ComponentsBuilder::pool_replacement_policy).
     pub fn pool_config(&self) -> PoolConfig {
-        self.config().txpool.pool_config()
+        let mut config = self.config().txpool.pool_config();
+        if let Some(policy) = &self.pool_policy {
+            config.admission_policy = Some(policy.clone());
+        }
+        if let Some(policy) = &self.pool_replacement_policy {
+            config.replacement_policy = Some(policy.clone());
+        }
+        config
     }
 
     /// Loads `EnvKzgSettings::Default`.
@@ -592,6 +789,24 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.executor.spawn_critical("p2p txpool", txpool);
         self.executor.spawn_critical("p2p eth request handler", eth);
 
+        // keeps the range of blocks announced to eth/69 peers in sync with the pruned history
+        let provider = self.provider().clone();
+        let network_handle = handle.clone();
+        self.executor.spawn(async move {
+            let mut canon_state = provider.canonical_state_stream();
+            loop {
+                match earliest_available_block(&provider) {
+                    Ok(earliest) => network_handle.update_earliest_block(earliest),
+                    Err(err) => {
+                        warn!(target: "reth::cli", %err, "Failed to read the earliest available block")
+                    }
+                }
+                if canon_state.next().await.is_none() {
+                    break
+                }
+            }
+        });
+
         let default_peers_path = self.config().datadir().known_peers();
         let known_peers_file = self.config().network.persistent_peers_file(default_peers_path);
         self.executor.spawn_critical_with_graceful_shutdown_signal(
@@ -627,7 +842,7 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
     pub fn network_config_builder(&self) -> eyre::Result<NetworkConfigBuilder> {
         let secret_key = self.network_secret(&self.config().datadir())?;
         let default_peers_path = self.config().datadir().known_peers();
-        let builder = self
+        let mut builder = self
             .config()
             .network
             .network_config(
@@ -639,6 +854,10 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
             .with_task_executor(Box::new(self.executor.clone()))
             .set_head(self.head);
 
+        if let Some(protocol) = &self.snap_protocol {
+            builder = builder.add_rlpx_sub_protocol(protocol.clone());
+        }
+
         Ok(builder)
     }
 
@@ -659,6 +878,19 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
     }
 }
 
+/// Returns the earliest block whose header, body and receipts haven't been pruned.
+fn earliest_available_block(provider: &impl PruneCheckpointReader) -> ProviderResult<BlockNumber> {
+    let mut earliest = 0;
+    for segment in [PruneSegment::Headers, PruneSegment::Transactions, PruneSegment::Receipts] {
+        if let Some(pruned_to) =
+            provider.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+        {
+            earliest = earliest.max(pruned_to + 1);
+        }
+    }
+    Ok(earliest)
+}
+
 impl<Node: FullNodeTypes> std::fmt::Debug for BuilderContext<Node> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("BuilderContext")
@@ -666,6 +898,8 @@ impl<Node: FullNodeTypes> std::fmt::Debug for BuilderContext<Node> {
             .field("provider", &std::any::type_name::<Node::Provider>())
             .field("executor", &self.executor)
             .field("config", &self.config())
+            .field("pool_policy", &self.pool_policy)
+            .field("pool_replacement_policy", &self.pool_replacement_policy)
             .finish()
     }
 }
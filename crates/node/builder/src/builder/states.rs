@@ -5,23 +5,27 @@
 //! The node builder process is essentially a state machine that transitions through various states
 //! before the node can be launched.
 
-use std::{fmt, future::Future, marker::PhantomData};
+use std::{fmt, future::Future, marker::PhantomData, sync::Arc};
 
-use reth_exex::ExExContext;
+use jsonrpsee::Methods;
+use reth_config::config::StageConfig;
+use reth_exex::{ExExContext, ExExDeliveryMode};
 use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypes};
 use reth_node_core::{
     node_config::NodeConfig,
     rpc::eth::{helpers::AddDevSigners, FullEthApiServer},
 };
 use reth_payload_builder::PayloadBuilderHandle;
+use reth_rpc_builder::EthMiddleware;
+use reth_stages::StageSetBuilder;
 use reth_tasks::TaskExecutor;
 
 use crate::{
     components::{NodeComponents, NodeComponentsBuilder},
     hooks::NodeHooks,
     launch::LaunchNode,
-    rpc::{EthApiBuilderProvider, RethRpcServerHandles, RpcContext, RpcHooks},
-    AddOns, FullNode, RpcAddOns,
+    rpc::{EthApiBuilderProvider, RethRpcServerHandles, RpcContext, RpcHooks, RpcNamespaces},
+    AddOns, EthApiBuilderCtx, FullNode, RpcAddOns,
 };
 
 /// A node builder that also has the configured types.
@@ -51,8 +55,14 @@ impl<T: FullNodeTypes> NodeBuilderWithTypes<T> {
             components_builder,
             add_ons: AddOns {
                 hooks: NodeHooks::default(),
-                rpc: RpcAddOns { _eth_api: PhantomData::<()>, hooks: RpcHooks::default() },
+                rpc: RpcAddOns {
+                    _eth_api: PhantomData::<()>,
+                    hooks: RpcHooks::default(),
+                    eth_middleware: None,
+                    namespaces: RpcNamespaces::default(),
+                },
                 exexs: Vec::new(),
+                pipeline_stages: Vec::new(),
             },
         }
     }
@@ -181,8 +191,14 @@ where
             components_builder,
             add_ons: AddOns {
                 hooks: NodeHooks::default(),
-                rpc: RpcAddOns { _eth_api: PhantomData::<AO::EthApi>, hooks: RpcHooks::default() },
+                rpc: RpcAddOns {
+                    _eth_api: PhantomData::<AO::EthApi>,
+                    hooks: RpcHooks::default(),
+                    eth_middleware: None,
+                    namespaces: RpcNamespaces::default(),
+                },
                 exexs: Vec::new(),
+                pipeline_stages: Vec::new(),
             },
         }
     }
@@ -239,18 +255,78 @@ where
         self
     }
 
+    /// Installs a custom RPC namespace, e.g. `myapp`, on all configured transports.
+    ///
+    /// The builder is invoked with the same [`EthApiBuilderCtx`] the `eth` handlers are built
+    /// with, and all returned methods must be prefixed with `<name>_`.
+    ///
+    /// Replaces the builder if the namespace is already installed.
+    pub fn install_rpc_namespace<F, M>(mut self, name: impl Into<String>, builder: F) -> Self
+    where
+        F: FnOnce(&EthApiBuilderCtx<NodeAdapter<T, CB::Components>>) -> eyre::Result<M>
+            + Send
+            + 'static,
+        M: Into<Methods>,
+    {
+        self.add_ons.rpc.namespaces.register(name, builder);
+        self
+    }
+
+    /// Installs a middleware that intercepts all `eth_` methods of the RPC server.
+    pub fn with_eth_middleware<M>(mut self, middleware: M) -> Self
+    where
+        M: EthMiddleware,
+    {
+        self.add_ons.rpc.eth_middleware = Some(Arc::new(middleware));
+        self
+    }
+
     /// Installs an `ExEx` (Execution Extension) in the node.
     ///
     /// # Note
     ///
     /// The `ExEx` ID must be unique.
-    pub fn install_exex<F, R, E>(mut self, exex_id: impl Into<String>, exex: F) -> Self
+    pub fn install_exex<F, R, E>(self, exex_id: impl Into<String>, exex: F) -> Self
     where
         F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
         R: Future<Output = eyre::Result<E>> + Send,
         E: Future<Output = eyre::Result<()>> + Send,
     {
-        self.add_ons.exexs.push((exex_id.into(), Box::new(exex)));
+        self.install_exex_with_delivery_mode(exex_id, ExExDeliveryMode::default(), exex)
+    }
+
+    /// Installs an `ExEx` (Execution Extension) in the node, that receives notifications with the
+    /// given [`ExExDeliveryMode`].
+    ///
+    /// # Note
+    ///
+    /// The `ExEx` ID must be unique.
+    pub fn install_exex_with_delivery_mode<F, R, E>(
+        mut self,
+        exex_id: impl Into<String>,
+        delivery_mode: ExExDeliveryMode,
+        exex: F,
+    ) -> Self
+    where
+        F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
+        R: Future<Output = eyre::Result<E>> + Send,
+        E: Future<Output = eyre::Result<()>> + Send,
+    {
+        self.add_ons.exexs.push((exex_id.into(), delivery_mode, Box::new(exex)));
+        self
+    }
+
+    /// Installs a hook that configures the stages of the sync pipeline.
+    ///
+    /// This can be used to insert custom stages at defined positions. Hooks are applied in the
+    /// order they are installed.
+    pub fn extend_pipeline_stages<F>(mut self, hook: F) -> Self
+    where
+        F: FnOnce(StageSetBuilder<T::DB>, &StageConfig) -> eyre::Result<StageSetBuilder<T::DB>>
+            + Send
+            + 'static,
+    {
+        self.add_ons.pipeline_stages.push(Box::new(hook));
         self
     }
 
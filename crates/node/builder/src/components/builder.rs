@@ -1,10 +1,14 @@
 //! A generic [`NodeComponentsBuilder`]
 
-use std::{future::Future, marker::PhantomData};
+use std::{future::Future, marker::PhantomData, sync::Arc};
 
 use reth_consensus::Consensus;
-use reth_evm::execute::BlockExecutorProvider;
-use reth_transaction_pool::TransactionPool;
+use reth_evm::{execute::BlockExecutorProvider, overrides::CustomPrecompile};
+use reth_primitives::{revm_primitives::Precompile, Address};
+use reth_transaction_pool::{
+    policy::{ReplacementPolicy, TransactionPolicy},
+    TransactionPool,
+};
 
 use crate::{
     components::{
@@ -31,6 +35,10 @@ use crate::{
 /// ## Builder lifecycle:
 ///
 /// First all standalone components are built. Then the service components are spawned.
+/// An optional admission policy for the transaction pool can be configured with
+/// [`ComponentsBuilder::pool_policy`], and a custom replacement rule with
+/// [`ComponentsBuilder::pool_replacement_policy`]. Custom precompiles can be registered with
+/// [`ComponentsBuilder::precompile`].
 /// All component builders are captured in the builder state and will be consumed once the node is
 /// launched.
 #[derive(Debug)]
@@ -40,6 +48,9 @@ pub struct ComponentsBuilder<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB> {
     network_builder: NetworkB,
     executor_builder: ExecB,
     consensus_builder: ConsB,
+    pool_policy: Option<Arc<dyn TransactionPolicy>>,
+    pool_replacement_policy: Option<Arc<dyn ReplacementPolicy>>,
+    precompiles: Vec<CustomPrecompile>,
     _marker: PhantomData<Node>,
 }
 
@@ -59,6 +70,9 @@ impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
             network_builder,
             executor_builder: evm_builder,
             consensus_builder,
+            pool_policy,
+            pool_replacement_policy,
+            precompiles,
             _marker,
         } = self;
         ComponentsBuilder {
@@ -67,6 +81,9 @@ impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
             payload_builder,
             network_builder,
             consensus_builder,
+            pool_policy,
+            pool_replacement_policy,
+            precompiles,
             _marker: Default::default(),
         }
     }
@@ -79,6 +96,9 @@ impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
             network_builder: self.network_builder,
             executor_builder: self.executor_builder,
             consensus_builder: self.consensus_builder,
+            pool_policy: self.pool_policy,
+            pool_replacement_policy: self.pool_replacement_policy,
+            precompiles: self.precompiles,
             _marker: self._marker,
         }
     }
@@ -91,6 +111,9 @@ impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
             network_builder: self.network_builder,
             executor_builder: self.executor_builder,
             consensus_builder: self.consensus_builder,
+            pool_policy: self.pool_policy,
+            pool_replacement_policy: self.pool_replacement_policy,
+            precompiles: self.precompiles,
             _marker: self._marker,
         }
     }
@@ -103,6 +126,9 @@ impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
             network_builder: f(self.network_builder),
             executor_builder: self.executor_builder,
             consensus_builder: self.consensus_builder,
+            pool_policy: self.pool_policy,
+            pool_replacement_policy: self.pool_replacement_policy,
+            precompiles: self.precompiles,
             _marker: self._marker,
         }
     }
@@ -115,6 +141,9 @@ impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
             network_builder: self.network_builder,
             executor_builder: f(self.executor_builder),
             consensus_builder: self.consensus_builder,
+            pool_policy: self.pool_policy,
+            pool_replacement_policy: self.pool_replacement_policy,
+            precompiles: self.precompiles,
             _marker: self._marker,
         }
     }
@@ -127,9 +156,51 @@ impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
             network_builder: self.network_builder,
             executor_builder: self.executor_builder,
             consensus_builder: f(self.consensus_builder),
+            pool_policy: self.pool_policy,
+            pool_replacement_policy: self.pool_replacement_policy,
+            precompiles: self.precompiles,
             _marker: self._marker,
         }
     }
+
+    /// Configures the admission policy of the transaction pool.
+    ///
+    /// The policy is installed via [`BuilderContext::pool_config`], so it applies to any pool
+    /// builder that is configured with it, and is checked before a transaction is validated.
+    pub fn pool_policy<P>(mut self, policy: P) -> Self
+    where
+        P: TransactionPolicy + 'static,
+    {
+        self.pool_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Configures the replacement policy of the transaction pool.
+    ///
+    /// The policy decides whether a transaction may replace a pooled transaction with the same
+    /// sender and nonce, and takes precedence over the configured price bumps.
+    pub fn pool_replacement_policy<P>(mut self, policy: P) -> Self
+    where
+        P: ReplacementPolicy + 'static,
+    {
+        self.pool_replacement_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Registers a custom precompile at the given address that is active from the given block on.
+    ///
+    /// The precompile is installed via [`BuilderContext::evm_overrides`], so it applies to the
+    /// block executor, the payload builder and RPC calls of any component builder that uses the
+    /// overrides.
+    pub fn precompile(
+        mut self,
+        address: Address,
+        activation_block: u64,
+        precompile: impl Into<Precompile>,
+    ) -> Self {
+        self.precompiles.push(CustomPrecompile::new(address, activation_block, precompile));
+        self
+    }
 }
 
 impl<Node, PoolB, PayloadB, NetworkB, ExecB, ConsB>
@@ -154,6 +225,9 @@ where
             network_builder,
             executor_builder: evm_builder,
             consensus_builder,
+            pool_policy,
+            pool_replacement_policy,
+            precompiles,
             _marker,
         } = self;
         ComponentsBuilder {
@@ -162,6 +236,9 @@ where
             network_builder,
             executor_builder: evm_builder,
             consensus_builder,
+            pool_policy,
+            pool_replacement_policy,
+            precompiles,
             _marker,
         }
     }
@@ -190,6 +267,9 @@ where
             network_builder: _,
             executor_builder: evm_builder,
             consensus_builder,
+            pool_policy,
+            pool_replacement_policy,
+            precompiles,
             _marker,
         } = self;
         ComponentsBuilder {
@@ -198,6 +278,9 @@ where
             network_builder,
             executor_builder: evm_builder,
             consensus_builder,
+            pool_policy,
+            pool_replacement_policy,
+            precompiles,
             _marker,
         }
     }
@@ -219,6 +302,9 @@ where
             network_builder,
             executor_builder: evm_builder,
             consensus_builder,
+            pool_policy,
+            pool_replacement_policy,
+            precompiles,
             _marker,
         } = self;
         ComponentsBuilder {
@@ -227,6 +313,9 @@ where
             network_builder,
             executor_builder: evm_builder,
             consensus_builder,
+            pool_policy,
+            pool_replacement_policy,
+            precompiles,
             _marker,
         }
     }
@@ -248,6 +337,9 @@ where
             network_builder,
             executor_builder: _,
             consensus_builder,
+            pool_policy,
+            pool_replacement_policy,
+            precompiles,
             _marker,
         } = self;
         ComponentsBuilder {
@@ -256,6 +348,9 @@ where
             network_builder,
             executor_builder,
             consensus_builder,
+            pool_policy,
+            pool_replacement_policy,
+            precompiles,
             _marker,
         }
     }
@@ -277,6 +372,9 @@ where
             network_builder,
             executor_builder,
             consensus_builder: _,
+            pool_policy,
+            pool_replacement_policy,
+            precompiles,
             _marker,
         } = self;
         ComponentsBuilder {
@@ -285,6 +383,9 @@ where
             network_builder,
             executor_builder,
             consensus_builder,
+            pool_policy,
+            pool_replacement_policy,
+            precompiles,
             _marker,
         }
     }
@@ -312,11 +413,28 @@ where
             network_builder,
             executor_builder: evm_builder,
             consensus_builder,
+            pool_policy,
+            pool_replacement_policy,
+            precompiles,
             _marker,
         } = self;
 
+        let context_with_precompiles;
+        let context = if precompiles.is_empty() {
+            context
+        } else {
+            context_with_precompiles = context.with_precompiles(precompiles);
+            &context_with_precompiles
+        };
+
         let (evm_config, executor) = evm_builder.build_evm(context).await?;
-        let pool = pool_builder.build_pool(context).await?;
+        let pool = if pool_policy.is_some() || pool_replacement_policy.is_some() {
+            pool_builder
+                .build_pool(&context.with_pool_policy(pool_policy, pool_replacement_policy))
+                .await?
+        } else {
+            pool_builder.build_pool(context).await?
+        };
         let network = network_builder.build_network(context, pool.clone()).await?;
         let payload_builder = payload_builder.spawn_payload_service(context, pool.clone()).await?;
         let consensus = consensus_builder.build_consensus(context).await?;
@@ -340,6 +458,9 @@ impl Default for ComponentsBuilder<(), (), (), (), (), ()> {
             network_builder: (),
             executor_builder: (),
             consensus_builder: (),
+            pool_policy: None,
+            pool_replacement_policy: None,
+            precompiles: Vec::new(),
             _marker: Default::default(),
         }
     }
@@ -0,0 +1,369 @@
+//! Reloads the configuration file of a running node.
+
+use std::{
+    fmt, fs,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use eyre::{eyre, WrapErr};
+use futures::{stream::BoxStream, StreamExt};
+use reth_config::{Config, ConfigOverrides, RpcConfig};
+use reth_network::{ConnectionsConfig, Peers};
+use reth_node_core::rpc::eth::helpers::LoadFee;
+use reth_primitives::U256;
+use reth_prune::PruneIntervalHandle;
+use reth_rpc_eth_types::{EthConfig, GasPriceOracleConfig, RpcLimits};
+use tracing::{debug, info, warn};
+
+/// The default interval in which the configuration file is checked for modifications.
+pub const DEFAULT_CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Function that applies a reloaded setting.
+type ApplyFn<T> = Box<dyn Fn(T) + Send + Sync>;
+
+/// Re-reads the configuration file when it is modified or the process receives `SIGHUP`, and
+/// applies the settings that can be changed while the node is running:
+///
+/// - the peer limits, `[peers.connection_info]`
+/// - the pruning interval, `prune.block_interval`
+/// - the RPC request limits and gas price oracle settings, `[rpc]`
+///
+/// Changes to all other settings are logged and take effect after a restart.
+pub struct ConfigWatcher {
+    /// Path of the configuration file.
+    path: PathBuf,
+    /// The profile that is applied to the configuration file, if any.
+    profile: Option<String>,
+    /// Environment and command line overrides that take precedence over the file.
+    overrides: ConfigOverrides,
+    /// The settings the node is running with.
+    config: Config,
+    /// How often the modification time of the file is checked.
+    poll_interval: Duration,
+    /// Applies new peer limits.
+    connection_limits: Option<ApplyFn<ConnectionsConfig>>,
+    /// The pruning interval of the running pruner.
+    prune_interval: Option<PruneIntervalHandle>,
+    /// The RPC request limits and the configured values they fall back to.
+    rpc_limits: Option<(RpcLimits, EthConfig)>,
+    /// Applies new gas price oracle settings.
+    gas_oracle: Option<ApplyFn<GasPriceOracleConfig>>,
+}
+
+impl ConfigWatcher {
+    /// Creates a new watcher for the configuration file at the given path.
+    ///
+    /// `config` is the configuration the node was launched with, including the `profile`.
+    pub fn new(path: impl Into<PathBuf>, profile: Option<String>, config: Config) -> Self {
+        Self {
+            path: path.into(),
+            profile,
+            overrides: ConfigOverrides::default(),
+            config,
+            poll_interval: DEFAULT_CONFIG_POLL_INTERVAL,
+            connection_limits: None,
+            prune_interval: None,
+            rpc_limits: None,
+            gas_oracle: None,
+        }
+    }
+
+    /// Sets how often the configuration file is checked for modifications.
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets the overrides that are applied on top of the reloaded file.
+    pub fn with_overrides(mut self, overrides: ConfigOverrides) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Applies peer limits to the given network.
+    pub fn with_network<N>(mut self, network: N) -> Self
+    where
+        N: Peers + 'static,
+    {
+        self.connection_limits =
+            Some(Box::new(move |limits| network.set_connection_limits(limits)));
+        self
+    }
+
+    /// Applies the pruning interval to the pruner of the given handle.
+    pub fn with_pruner(mut self, prune_interval: PruneIntervalHandle) -> Self {
+        self.prune_interval = Some(prune_interval);
+        self
+    }
+
+    /// Applies the `[rpc]` section to the given limits.
+    ///
+    /// Settings that are not part of the section fall back to the values of `eth_config`.
+    pub fn with_rpc_limits(mut self, limits: RpcLimits, eth_config: EthConfig) -> Self {
+        self.rpc_limits = Some((limits, eth_config));
+        self
+    }
+
+    /// Applies the `[rpc.gas_price_oracle]` section to the gas price oracle of the given API.
+    pub fn with_gas_oracle<EthApi>(mut self, eth_api: EthApi) -> Self
+    where
+        EthApi: LoadFee + 'static,
+    {
+        self.gas_oracle = Some(Box::new(move |config| eth_api.gas_oracle().set_config(config)));
+        self
+    }
+
+    /// Applies the `[rpc]` section of the current configuration and then reloads the
+    /// configuration file whenever it is modified or `SIGHUP` is received.
+    pub async fn run(mut self) {
+        if let Some(rpc) = self.config.rpc {
+            self.apply_rpc(&rpc);
+        }
+
+        let mut hangup = hangup_signals();
+        let mut interval = tokio::time::interval(self.poll_interval);
+        let mut modified = self.modified();
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    let current = self.modified();
+                    if current == modified {
+                        continue
+                    }
+                    modified = current;
+                    debug!(target: "reth::cli", path = ?self.path, "Configuration file modified");
+                }
+                Some(()) = hangup.next() => {
+                    debug!(target: "reth::cli", "Received SIGHUP");
+                }
+            }
+
+            if let Err(err) = self.reload() {
+                warn!(target: "reth::cli", %err, path = ?self.path, "Failed to reload configuration");
+            }
+        }
+    }
+
+    /// Re-reads the configuration file and applies the changed settings.
+    pub fn reload(&mut self) -> eyre::Result<()> {
+        let content = fs::read_to_string(&self.path)
+            .wrap_err_with(|| format!("Could not read config file {:?}", self.path))?;
+        let mut config: Config =
+            toml::from_str(&content).map_err(|e| eyre!("Failed to parse TOML: {e}"))?;
+        if let Some(profile) = &self.profile {
+            config = config.with_profile(profile)?;
+        }
+        let config = self.overrides.apply(&config)?.config;
+
+        let restart_required = self.apply(config);
+        if restart_required.is_empty() {
+            info!(target: "reth::cli", path = ?self.path, "Configuration reloaded");
+        } else {
+            warn!(target: "reth::cli", path = ?self.path, settings = ?restart_required, "Configuration reloaded, changed settings require a restart");
+        }
+        Ok(())
+    }
+
+    /// Applies the settings of `config` that can be changed live, and returns the sections with
+    /// changes that require a restart.
+    fn apply(&mut self, mut config: Config) -> Vec<&'static str> {
+        // command line arguments that override the configuration file
+        config.peers.trusted_nodes_only = self.config.peers.trusted_nodes_only;
+
+        if config.peers.connection_info != self.config.peers.connection_info {
+            if let Some(apply) = &self.connection_limits {
+                info!(target: "reth::cli", limits = ?config.peers.connection_info, "Updating peer limits");
+                apply(config.peers.connection_info.clone());
+                self.config.peers.connection_info = config.peers.connection_info.clone();
+            }
+        }
+
+        let block_interval = config.prune.as_ref().map(|prune| prune.block_interval);
+        if block_interval != self.config.prune.as_ref().map(|prune| prune.block_interval) {
+            if let (Some(handle), Some(block_interval), Some(prune)) =
+                (&self.prune_interval, block_interval, &mut self.config.prune)
+            {
+                info!(target: "reth::cli", block_interval, "Updating pruning interval");
+                handle.set(block_interval);
+                prune.block_interval = block_interval;
+            }
+        }
+
+        if config.rpc != self.config.rpc && self.rpc_limits.is_some() {
+            self.apply_rpc(&config.rpc.unwrap_or_default());
+            self.config.rpc = config.rpc;
+        }
+
+        let mut restart_required = Vec::new();
+        if config.stages != self.config.stages {
+            restart_required.push("stages");
+        }
+        if config.peers != self.config.peers {
+            restart_required.push("peers");
+        }
+        if config.sessions != self.config.sessions {
+            restart_required.push("sessions");
+        }
+        if config.prune != self.config.prune {
+            restart_required.push("prune");
+        }
+        if config.rpc != self.config.rpc {
+            restart_required.push("rpc");
+        }
+        if config.disk_alarm != self.config.disk_alarm {
+            restart_required.push("disk_alarm");
+        }
+        if config.tasks != self.config.tasks {
+            restart_required.push("tasks");
+        }
+        if config.engine != self.config.engine {
+            restart_required.push("engine");
+        }
+        restart_required
+    }
+
+    /// Applies the RPC settings, unset values fall back to the configured values.
+    fn apply_rpc(&self, rpc: &RpcConfig) {
+        let Some((limits, eth_config)) = &self.rpc_limits else { return };
+        info!(target: "reth::cli", ?rpc, "Updating RPC settings");
+
+        limits.set_gas_cap(rpc.gas_cap.unwrap_or(eth_config.rpc_gas_cap));
+        limits.set_max_logs_per_response(
+            rpc.max_logs_per_response.unwrap_or(eth_config.max_logs_per_response),
+        );
+        limits.set_max_tracing_requests(
+            rpc.max_tracing_requests.unwrap_or(eth_config.max_tracing_requests),
+        );
+
+        if let Some(apply) = &self.gas_oracle {
+            let overrides = rpc.gas_price_oracle;
+            let defaults = eth_config.gas_oracle;
+            apply(GasPriceOracleConfig {
+                blocks: overrides.blocks.unwrap_or(defaults.blocks),
+                percentile: overrides.percentile.unwrap_or(defaults.percentile),
+                max_price: overrides.max_price.map(U256::from).or(defaults.max_price),
+                ignore_price: overrides.ignore_price.map(U256::from).or(defaults.ignore_price),
+                ..defaults
+            });
+        }
+    }
+
+    /// Returns the modification time of the configuration file.
+    fn modified(&self) -> Option<SystemTime> {
+        fs::metadata(&self.path).and_then(|metadata| metadata.modified()).ok()
+    }
+}
+
+impl fmt::Debug for ConfigWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigWatcher")
+            .field("path", &self.path)
+            .field("profile", &self.profile)
+            .field("overrides", &self.overrides)
+            .field("poll_interval", &self.poll_interval)
+            .field("network", &self.connection_limits.is_some())
+            .field("prune_interval", &self.prune_interval)
+            .field("rpc_limits", &self.rpc_limits.is_some())
+            .field("gas_oracle", &self.gas_oracle.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns a stream that yields whenever the process receives `SIGHUP`.
+#[cfg(unix)]
+fn hangup_signals() -> BoxStream<'static, ()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::hangup()) {
+        Ok(signal) => futures::stream::unfold(signal, |mut signal| async move {
+            signal.recv().await.map(|()| ((), signal))
+        })
+        .boxed(),
+        Err(err) => {
+            warn!(target: "reth::cli", %err, "Failed to listen for SIGHUP");
+            futures::stream::pending().boxed()
+        }
+    }
+}
+
+/// Returns a stream that yields whenever the process receives `SIGHUP`.
+#[cfg(not(unix))]
+fn hangup_signals() -> BoxStream<'static, ()> {
+    futures::stream::pending().boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_config::PruneConfig;
+    use std::sync::{Arc, Mutex};
+
+    fn watcher(config: Config) -> (ConfigWatcher, Arc<Mutex<Vec<ConnectionsConfig>>>) {
+        let applied = Arc::new(Mutex::new(Vec::new()));
+        let mut watcher = ConfigWatcher::new("reth.toml", None, config);
+        let limits = applied.clone();
+        watcher.connection_limits = Some(Box::new(move |l| limits.lock().unwrap().push(l)));
+        (watcher, applied)
+    }
+
+    #[test]
+    fn applies_peer_limits() {
+        let (mut watcher, applied) = watcher(Config::default());
+
+        let mut config = Config::default();
+        config.peers.connection_info.max_inbound = 100;
+        assert!(watcher.apply(config.clone()).is_empty());
+        assert_eq!(applied.lock().unwrap().as_slice(), &[config.peers.connection_info.clone()]);
+        assert_eq!(watcher.config, config);
+
+        // unchanged
+        assert!(watcher.apply(config).is_empty());
+        assert_eq!(applied.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn applies_rpc_limits() {
+        let limits = RpcLimits::default();
+        let eth_config = EthConfig::default();
+        let (watcher, _) = watcher(Config::default());
+        let mut watcher = watcher.with_rpc_limits(limits.clone(), eth_config.clone());
+
+        let rpc =
+            RpcConfig { gas_cap: Some(1_000), max_tracing_requests: Some(3), ..Default::default() };
+        let mut config = Config { rpc: Some(rpc), ..Default::default() };
+        assert!(watcher.apply(config.clone()).is_empty());
+        assert_eq!(limits.gas_cap(), 1_000);
+        assert_eq!(limits.max_tracing_requests(), 3);
+        assert_eq!(limits.max_logs_per_response(), eth_config.max_logs_per_response);
+
+        // removing the section restores the configured values
+        config.rpc = None;
+        assert!(watcher.apply(config).is_empty());
+        assert_eq!(limits.gas_cap(), eth_config.rpc_gas_cap);
+        assert_eq!(limits.max_tracing_requests(), eth_config.max_tracing_requests);
+    }
+
+    #[test]
+    fn reports_restart_required() {
+        let running = Config { prune: Some(PruneConfig::default()), ..Default::default() };
+        let (mut watcher, _) = watcher(running.clone());
+
+        let mut config = running.clone();
+        config.stages.headers.commit_threshold += 1;
+        config.sessions.session_command_buffer += 1;
+        config.peers.max_backoff_count += 1;
+        config.prune.as_mut().unwrap().block_interval = 10;
+        config.rpc = Some(RpcConfig { gas_cap: Some(1_000), ..Default::default() });
+        assert_eq!(watcher.apply(config.clone()), ["stages", "peers", "sessions", "prune", "rpc"]);
+        // nothing is applied without handles
+        assert_eq!(watcher.config, running);
+
+        let (watcher, _) = self::watcher(running);
+        let handle = PruneIntervalHandle::new(5);
+        let mut watcher = watcher.with_pruner(handle.clone());
+        assert_eq!(watcher.apply(config), ["stages", "peers", "sessions", "rpc"]);
+        assert_eq!(handle.get(), 10);
+    }
+}
@@ -1,21 +1,29 @@
 //! Helper types that can be used by launchers.
 
-use std::{marker::PhantomData, sync::Arc, thread::available_parallelism};
+use std::{
+    marker::PhantomData,
+    path::PathBuf,
+    sync::{Arc, Once},
+    thread::available_parallelism,
+};
 
 use eyre::Context;
 use rayon::ThreadPoolBuilder;
-use reth_auto_seal_consensus::MiningMode;
+use reth_auto_seal_consensus::{DevStateChanges, MiningMode};
 use reth_beacon_consensus::EthBeaconConsensus;
 use reth_blockchain_tree::{
     BlockchainTree, BlockchainTreeConfig, ShareableBlockchainTree, TreeExternals,
 };
 use reth_chainspec::{Chain, ChainSpec};
-use reth_config::{config::EtlConfig, PruneConfig};
+use reth_cli_util::{pin_current_thread, set_current_thread_nice};
+use reth_config::{PruneConfig, TaskRuntimeConfig};
 use reth_consensus::Consensus;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_db_common::init::{init_genesis, InitDatabaseError};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_evm::noop::NoopBlockExecutorProvider;
+use reth_forked_state::{ForkPoint, RpcForkedState};
+use reth_network::snap::{SnapClientHandle, SnapProtocolHandler};
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_node_api::FullNodeTypes;
 use reth_node_core::{
@@ -31,9 +39,12 @@ use reth_node_metrics::{
     server::{MetricServer, MetricServerConfig},
     version::VersionInfo,
 };
+use reth_object_store::{
+    parse_s3_location, LocalObjectStore, ObjectStore, S3Config, S3ObjectStore,
+};
 use reth_primitives::{BlockNumber, Head, B256};
 use reth_provider::{
-    providers::{BlockchainProvider, BlockchainProvider2, StaticFileProvider},
+    providers::{BlockchainProvider, BlockchainProvider2, RemoteStaticFiles, StaticFileProvider},
     BlockHashReader, CanonStateNotificationSender, FullProvider, ProviderFactory, ProviderResult,
     StageCheckpointReader, StaticFileProviderFactory, TreeViewer,
 };
@@ -42,12 +53,17 @@ use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_layer::JwtSecret;
 use reth_stages::{sets::DefaultStages, MetricEvent, Pipeline, PipelineTarget, StageId};
 use reth_static_file::StaticFileProducer;
-use reth_tasks::TaskExecutor;
-use reth_tracing::tracing::{debug, error, info, warn};
+use reth_tasks::{TaskExecutor, TaskPriority};
+use reth_tracing::{
+    install_otlp_exporter, shutdown_otlp_exporter,
+    tracing::{debug, error, info, warn},
+    OtlpConfig,
+};
 use tokio::sync::{
     mpsc::{unbounded_channel, Receiver, UnboundedSender},
     oneshot, watch,
 };
+use url::Url;
 
 use crate::{
     components::{NodeComponents, NodeComponentsBuilder},
@@ -122,11 +138,35 @@ impl LaunchContext {
 
         Self::save_pruning_config_if_full_node(&mut toml_config, config, &config_path)?;
 
-        info!(target: "reth::cli", path = ?config_path, "Configuration loaded");
+        if let Some(profile) = &config.config_profile {
+            toml_config = toml_config.with_profile(profile)?;
+            info!(target: "reth::cli", path = ?config_path, %profile, "Configuration loaded");
+        } else {
+            info!(target: "reth::cli", path = ?config_path, "Configuration loaded");
+        }
+
+        let layered = reth_config::ConfigOverrides::from_env()
+            .with_cli_overrides(config.config_overrides.iter().cloned())
+            .apply(&toml_config)?;
+        for name in &layered.ignored_env {
+            debug!(target: "reth::cli", %name, "Ignoring environment variable that does not name a config setting");
+        }
+        for (key, source) in layered.overrides() {
+            info!(target: "reth::cli", %key, %source, "Config setting overridden");
+        }
+        toml_config = layered.config;
 
         // Update the config with the command line arguments
         toml_config.peers.trusted_nodes_only = config.network.trusted_only;
 
+        for issue in toml_config
+            .validate()
+            .into_result()
+            .wrap_err_with(|| format!("Invalid config file {config_path:?}"))?
+        {
+            warn!(target: "reth::cli", %issue, "Questionable configuration value");
+        }
+
         Ok(toml_config)
     }
 
@@ -248,6 +288,73 @@ impl LaunchContextWith<WithConfigs> {
         }
         Ok(self)
     }
+
+    /// Starts exporting spans to the OpenTelemetry collector of the `[tracing]` section of the
+    /// toml config, if an endpoint is configured.
+    ///
+    /// Pending spans are exported on shutdown.
+    pub fn with_tracing_export(self) -> eyre::Result<Self> {
+        let Some(tracing) = &self.attachment.toml_config.tracing else { return Ok(self) };
+        let Some(endpoint) = &tracing.otlp_endpoint else { return Ok(self) };
+
+        let mut otlp = OtlpConfig::new(endpoint.clone());
+        if let Some(sample_ratio) = tracing.sample_ratio {
+            otlp.sample_ratio = sample_ratio;
+        }
+        if let Some(service_name) = &tracing.service_name {
+            otlp.service_name.clone_from(service_name);
+        }
+        if let Some(filter) = &tracing.filter {
+            otlp.filter.clone_from(filter);
+        }
+        install_otlp_exporter(&otlp).wrap_err("Failed to install the OTLP span exporter")?;
+        info!(target: "reth::cli", %endpoint, sample_ratio = otlp.sample_ratio, "Exporting spans via OTLP");
+
+        self.task_executor().spawn_with_graceful_shutdown_signal(|shutdown| async move {
+            let _guard = shutdown.await;
+            let _ = tokio::task::spawn_blocking(shutdown_otlp_exporter).await;
+        });
+        Ok(self)
+    }
+
+    /// Starts the dedicated runtimes of the task priority classes configured in the `[tasks]`
+    /// section of the toml config, see [`TaskExecutor::with_priority`].
+    ///
+    /// The threads of each class are pinned to its CPU cores and get its nice value, if configured.
+    /// Besides the threads of the runtime, this applies to the engine's threads for the consensus
+    /// class and the RPC blocking pool for the RPC class, see
+    /// [`TaskPriority::apply_to_current_thread`].
+    pub fn with_task_runtimes(self) -> eyre::Result<Self> {
+        let Some(tasks) = &self.attachment.toml_config.tasks else { return Ok(self) };
+
+        let runtimes = [
+            (TaskPriority::Consensus, &tasks.consensus),
+            (TaskPriority::Rpc, &tasks.rpc),
+            (TaskPriority::Background, &tasks.background),
+        ];
+        for (priority, runtime) in runtimes {
+            let Some(TaskRuntimeConfig { worker_threads, cpus, nice }) = runtime.clone() else {
+                continue
+            };
+            info!(target: "reth::cli", %priority, worker_threads, ?cpus, ?nice, "Starting task runtime");
+
+            // threads are started on demand, so failures are only logged once
+            let warned = Once::new();
+            priority.set_thread_hook(move || {
+                let pinned = if cpus.is_empty() { Ok(()) } else { pin_current_thread(&cpus) };
+                let result = pinned.and_then(|_| nice.map_or(Ok(()), set_current_thread_nice));
+                if let Err(err) = result {
+                    warned.call_once(|| {
+                        warn!(target: "reth::cli", %priority, %err, "Failed to set the CPU cores or the nice value of the task priority class");
+                    });
+                }
+            });
+            self.task_executor()
+                .start_priority_runtime(priority, worker_threads)
+                .wrap_err_with(|| format!("Failed to start the {priority} task runtime"))?;
+        }
+        Ok(self)
+    }
 }
 
 impl<L, R> LaunchContextWith<Attached<L, R>> {
@@ -282,10 +389,13 @@ impl<R> LaunchContextWith<Attached<WithConfigs, R>> {
     }
 
     /// Make sure ETL doesn't default to /tmp/, but to whatever datadir is set to
+    ///
+    /// `--datadir.etl` takes precedence over the config file.
     pub fn ensure_etl_datadir(mut self) -> Self {
-        if self.toml_config_mut().stages.etl.dir.is_none() {
-            self.toml_config_mut().stages.etl.dir =
-                Some(EtlConfig::from_datadir(self.data_dir().data_dir()))
+        if self.node_config().datadir.etl_path.is_some() ||
+            self.toml_config_mut().stages.etl.dir.is_none()
+        {
+            self.toml_config_mut().stages.etl.dir = Some(self.data_dir().etl())
         }
 
         self
@@ -322,6 +432,11 @@ impl<R> LaunchContextWith<Attached<WithConfigs, R>> {
         &mut self.left_mut().toml_config
     }
 
+    /// Returns the path of the toml config file.
+    pub fn toml_config_path(&self) -> PathBuf {
+        self.node_config().config.clone().unwrap_or_else(|| self.data_dir().config())
+    }
+
     /// Returns the configured chain spec.
     pub fn chain_spec(&self) -> Arc<ChainSpec> {
         self.node_config().chain.clone()
@@ -354,9 +469,11 @@ impl<R> LaunchContextWith<Attached<WithConfigs, R>> {
 
     /// Returns an initialized [`PrunerBuilder`] based on the configured [`PruneConfig`]
     pub fn pruner_builder(&self) -> PrunerBuilder {
-        PrunerBuilder::new(self.prune_config().unwrap_or_default())
+        let prune_config = self.prune_config().unwrap_or_default();
+        let timeout = prune_config.schedule.max_run_time.unwrap_or(PrunerBuilder::DEFAULT_TIMEOUT);
+        PrunerBuilder::new(prune_config)
             .delete_limit(self.chain_spec().prune_delete_limit)
-            .timeout(PrunerBuilder::DEFAULT_TIMEOUT)
+            .timeout(timeout)
     }
 
     /// Loads the JWT secret for the engine API
@@ -368,7 +485,9 @@ impl<R> LaunchContextWith<Attached<WithConfigs, R>> {
 
     /// Returns the [`MiningMode`] intended for --dev mode.
     pub fn dev_mining_mode(&self, pending_transactions_listener: Receiver<B256>) -> MiningMode {
-        if let Some(interval) = self.node_config().dev.block_time {
+        if self.node_config().dev.manual_mining {
+            MiningMode::None
+        } else if let Some(interval) = self.node_config().dev.block_time {
             MiningMode::interval(interval)
         } else if let Some(max_transactions) = self.node_config().dev.block_max_transactions {
             MiningMode::instant(max_transactions, pending_transactions_listener)
@@ -386,13 +505,20 @@ where
     /// between the database and static files. **It may execute a pipeline unwind if it fails this
     /// check.**
     pub async fn create_provider_factory(&self) -> eyre::Result<ProviderFactory<DB>> {
-        let factory = ProviderFactory::new(
-            self.right().clone(),
-            self.chain_spec(),
-            StaticFileProvider::read_write(self.data_dir().static_files())?,
-        )
-        .with_prune_modes(self.prune_modes())
-        .with_static_files_metrics();
+        let mut static_file_provider =
+            StaticFileProvider::read_write(self.data_dir().static_files())?
+                .with_compression(self.toml_config().static_files.compression);
+        if let Some(remote) = self.remote_static_files()? {
+            static_file_provider = static_file_provider.with_remote(remote);
+        }
+
+        let mut factory =
+            ProviderFactory::new(self.right().clone(), self.chain_spec(), static_file_provider)
+                .with_prune_modes(self.prune_modes())
+                .with_static_files_metrics();
+        if let Some(forked_state) = self.forked_state(&factory)? {
+            factory = factory.with_forked_state(forked_state);
+        }
 
         let has_receipt_pruning =
             self.toml_config().prune.as_ref().map_or(false, |a| a.has_receipts_pruning());
@@ -442,9 +568,124 @@ where
             rx.await??;
         }
 
+        let recompress = self.toml_config().static_files.recompress;
+        if recompress || factory.static_file_provider().remote().is_some() {
+            let static_file_provider = factory.static_file_provider();
+            self.task_executor().spawn_blocking(async move {
+                // Recompressed first, so the recompressed static files are uploaded
+                if recompress {
+                    match static_file_provider.recompress_static_files() {
+                        Ok(0) => {}
+                        Ok(recompressed) => {
+                            info!(target: "reth::cli", %recompressed, "Recompressed static files")
+                        }
+                        Err(err) => {
+                            error!(target: "reth::cli", %err, "Failed to recompress static files")
+                        }
+                    }
+                }
+
+                match static_file_provider.offload_static_files() {
+                    Ok(0) => {}
+                    Ok(uploaded) => {
+                        info!(target: "reth::cli", %uploaded, "Uploaded static files to the object store")
+                    }
+                    Err(err) => {
+                        error!(target: "reth::cli", %err, "Failed to upload static files to the object store")
+                    }
+                }
+            });
+        }
+
         Ok(factory)
     }
 
+    /// Returns the object store that the data of old static files is moved to, if configured.
+    ///
+    /// S3 credentials are read from the `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY`
+    /// environment variables.
+    fn remote_static_files(&self) -> eyre::Result<Option<RemoteStaticFiles>> {
+        let Some(config) = &self.toml_config().static_files.remote else { return Ok(None) };
+
+        let store: Arc<dyn ObjectStore> = if config.location.starts_with("s3://") {
+            let (bucket, prefix) = parse_s3_location(&config.location)
+                .ok_or_else(|| eyre::eyre!("S3 location is missing the bucket"))?;
+            let s3_config = S3Config {
+                endpoint: Url::parse(&config.endpoint)
+                    .wrap_err("invalid endpoint of remote static files")?,
+                region: config.region.clone(),
+                bucket,
+                prefix,
+                access_key_id: std::env::var("AWS_ACCESS_KEY_ID")
+                    .wrap_err("AWS_ACCESS_KEY_ID is required for remote static files")?,
+                secret_access_key: std::env::var("AWS_SECRET_ACCESS_KEY")
+                    .wrap_err("AWS_SECRET_ACCESS_KEY is required for remote static files")?,
+            };
+            Arc::new(S3ObjectStore::new(s3_config, self.task_executor().handle().clone()))
+        } else {
+            Arc::new(LocalObjectStore::new(PathBuf::from(&config.location)))
+        };
+
+        info!(target: "reth::cli", location = %config.location, cutoff = config.cutoff, "Moving old static files to the object store");
+        Ok(Some(RemoteStaticFiles::new(store, config.cutoff, config.cache_size)))
+    }
+
+    /// Returns the state of the network the dev chain is forked from, if it's forked.
+    ///
+    /// The fork point is stored in the datadir when the chain is forked, which must be new then.
+    /// Afterwards, the chain can only be started forked at the same block.
+    fn forked_state(
+        &self,
+        factory: &ProviderFactory<DB>,
+    ) -> eyre::Result<Option<Arc<RpcForkedState>>> {
+        let fork_dir = self.data_dir().fork();
+        let fork_file = fork_dir.join("fork.json");
+        let stored = ForkPoint::load(&fork_file)?;
+        let Some(url) = self.node_config().dev.fork_url.clone() else {
+            if stored.is_some() {
+                eyre::bail!("datadir is of a forked chain, --fork-url is required");
+            }
+            return Ok(None)
+        };
+
+        let block_number = match (self.node_config().dev.fork_block_number, stored) {
+            (Some(block_number), Some(stored)) if block_number != stored.block_number => {
+                eyre::bail!(
+                    "datadir is forked at block {}, not at block {block_number}",
+                    stored.block_number
+                )
+            }
+            (block_number, stored) => {
+                block_number.or_else(|| stored.map(|stored| stored.block_number))
+            }
+        };
+
+        let forked_state = RpcForkedState::connect(
+            url.clone(),
+            block_number,
+            self.task_executor().handle().clone(),
+        )
+        .wrap_err_with(|| format!("failed to fork {url}"))?;
+        let fork_point = forked_state.fork_point();
+        match stored {
+            Some(stored) if stored != fork_point => {
+                eyre::bail!("datadir is forked at {stored:?}, but {url} has {fork_point:?}")
+            }
+            Some(_) => {}
+            None => {
+                let checkpoint = factory.get_stage_checkpoint(StageId::Finish)?;
+                if checkpoint.unwrap_or_default().block_number > 0 {
+                    eyre::bail!("only a new datadir can be forked");
+                }
+                fork_point.save(&fork_file)?;
+            }
+        }
+
+        info!(target: "reth::cli", %url, chain_id = fork_point.chain_id, block_number = fork_point.block_number, block_hash = %fork_point.block_hash, "Forking chain");
+        let forked_state = forked_state.with_cache_file(&fork_dir.join("cache.jsonl"))?;
+        Ok(Some(Arc::new(forked_state)))
+    }
+
     /// Creates a new [`ProviderFactory`] and attaches it to the launch context.
     pub async fn with_provider_factory(
         self,
@@ -653,23 +894,43 @@ where
         // fetch the head block from the database
         let head = self.lookup_head()?;
 
-        let builder_ctx = BuilderContext::new(
+        let mut builder_ctx = BuilderContext::new(
             head,
             self.blockchain_db().clone(),
             self.task_executor().clone(),
             self.configs().clone(),
         );
 
+        let mut snap_client = None;
+        if self.toml_config().stages.snap.enabled {
+            let snap_protocol = SnapProtocolHandler::default();
+            snap_client = Some(snap_protocol.client());
+            builder_ctx = builder_ctx.with_snap_protocol(snap_protocol);
+        }
+
+        let dev_state_changes = self.is_dev().then(DevStateChanges::default);
+        if let Some(state_changes) = &dev_state_changes {
+            builder_ctx = builder_ctx.with_dev_state_changes(state_changes.clone());
+        }
+
         debug!(target: "reth::cli", "creating components");
         let components = components_builder.build_components(&builder_ctx).await?;
 
         let consensus: Arc<dyn Consensus> = Arc::new(components.consensus().clone());
 
-        let tree_externals = TreeExternals::new(
+        let mut tree_externals = TreeExternals::new(
             self.provider_factory().clone(),
             consensus.clone(),
             components.block_executor().clone(),
         );
+        let state_root_workers = self.toml_config().stages.merkle.workers;
+        if state_root_workers > 0 {
+            let state_root_pool = ThreadPoolBuilder::new()
+                .num_threads(state_root_workers)
+                .thread_name(|i| format!("state-root-{i:02}"))
+                .build()?;
+            tree_externals = tree_externals.with_state_root_pool(Arc::new(state_root_pool));
+        }
         let tree = BlockchainTree::new(tree_externals, *self.tree_config(), self.prune_modes())?
             .with_sync_metrics_tx(self.sync_metrics_tx())
             // Note: This is required because we need to ensure that both the components and the
@@ -703,6 +964,8 @@ where
             node_adapter,
             head,
             consensus,
+            snap_client,
+            dev_state_changes,
         };
 
         let ctx = LaunchContextWith {
@@ -825,6 +1088,16 @@ where
         self.right().consensus.clone()
     }
 
+    /// Returns the client of the `snap/1` protocol, if snap sync is enabled.
+    pub fn snap_client(&self) -> Option<SnapClientHandle> {
+        self.right().snap_client.clone()
+    }
+
+    /// Returns the state changes of the mined chain, in dev mode.
+    pub fn dev_state_changes(&self) -> Option<DevStateChanges> {
+        self.right().dev_state_changes.clone()
+    }
+
     /// Returns the metrics sender.
     pub fn sync_metrics_tx(&self) -> UnboundedSender<MetricEvent> {
         self.right().db_provider_container.metrics_sender.clone()
@@ -940,6 +1213,8 @@ where
     node_adapter: NodeAdapter<T, CB::Components>,
     head: Head,
     consensus: Arc<dyn Consensus>,
+    snap_client: Option<SnapClientHandle>,
+    dev_state_changes: Option<DevStateChanges>,
 }
 
 #[cfg(test)]
@@ -7,15 +7,19 @@ use reth_beacon_consensus::{
 };
 use reth_blockchain_tree::BlockchainTreeConfig;
 use reth_chainspec::ChainSpec;
+use reth_cli_util::set_idle_io_priority;
+use reth_config::ConfigOverrides;
+use reth_db_common::integrity::IntegrityChecker;
 use reth_engine_service::service::{ChainEvent, EngineService};
 use reth_engine_tree::{
     engine::{EngineApiRequest, EngineRequestHandler},
-    tree::TreeConfig,
+    tree::{PersistencePolicy, TreeConfig},
 };
 use reth_engine_util::EngineMessageStreamExt;
 use reth_exex::ExExManagerHandle;
 use reth_network::{NetworkSyncUpdater, SyncState};
 use reth_network_api::{BlockDownloaderProvider, NetworkEventListenerProvider};
+use reth_network_sentry::SentryService;
 use reth_node_api::{BuiltPayload, FullNodeTypes, NodeAddOns};
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
@@ -24,20 +28,29 @@ use reth_node_core::{
     rpc::eth::{helpers::AddDevSigners, FullEthApiServer},
     version::{CARGO_PKG_VERSION, CLIENT_CODE, NAME_CLIENT, VERGEN_GIT_SHA},
 };
-use reth_node_events::{cl::ConsensusLayerHealthEvents, node};
-use reth_provider::providers::BlockchainProvider2;
+use reth_node_events::{
+    audit::{handle_audit_events, AuditLog},
+    cl::ConsensusLayerHealthEvents,
+    node,
+};
+use reth_provider::{providers::BlockchainProvider2, CanonStateSubscriptions};
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
 use reth_rpc_types::engine::ClientVersionV1;
-use reth_tasks::TaskExecutor;
+use reth_stages::track_sync_progress;
+use reth_tasks::{TaskExecutor, TaskPriority};
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info};
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use crate::{
+    config_watcher::ConfigWatcher,
     hooks::NodeHooks,
     rpc::{launch_rpc_servers, EthApiBuilderProvider},
-    setup::build_networked_pipeline,
+    setup::{build_networked_pipeline, snap_sync_stage_hook},
+    trusted_peers::TrustedPeersWatcher,
+    verifier::BlockVerifier,
+    volumes::VolumeMonitor,
     AddOns, ExExLauncher, FullNode, LaunchContext, LaunchNode, NodeAdapter,
     NodeBuilderWithComponents, NodeComponents, NodeComponentsBuilder, NodeHandle, NodeTypesAdapter,
 };
@@ -81,7 +94,7 @@ where
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: AddOns { hooks, rpc, exexs: installed_exex },
+            add_ons: AddOns { hooks, rpc, exexs: installed_exex, pipeline_stages },
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
@@ -100,6 +113,10 @@ where
             .with_configured_globals()
             // load the toml config
             .with_loaded_toml_config(config)?
+            // export spans if configured
+            .with_tracing_export()?
+            // start the runtimes of the task priority classes
+            .with_task_runtimes()?
             // add resolved peers
             .with_resolved_peers().await?
             // attach the database
@@ -139,10 +156,28 @@ where
 
         // create pipeline
         let network_client = ctx.components().network().fetch_client().await?;
-        let (consensus_engine_tx, consensus_engine_rx) = unbounded_channel();
 
+        // serve the network to other nodes that use this node as sentry
+        if let Some(addr) = ctx.node_config().network.sentry_addr {
+            info!(target: "reth::cli", %addr, "Serving the network as sentry");
+            let sentry = SentryService::new(network_client.clone());
+            ctx.task_executor().spawn(Box::pin(async move {
+                if let Err(err) = sentry.serve(addr).await {
+                    error!(target: "reth::cli", %err, "Sentry service failed");
+                }
+            }));
+        }
+        let (consensus_engine_tx, consensus_engine_rx) = unbounded_channel();
         let node_config = ctx.node_config();
+        // messages of a backup consensus layer client are served by a separate auth server
+        let (backup_engine_tx, backup_engine_rx) =
+            node_config.rpc.auth_backup_port.map(|_| unbounded_channel()).unzip();
+
         let consensus_engine_stream = UnboundedReceiverStream::from(consensus_engine_rx)
+            .maybe_failover(
+                backup_engine_rx.map(UnboundedReceiverStream::from),
+                node_config.rpc.auth_failover_timeout,
+            )
             .maybe_skip_fcu(node_config.debug.skip_fcu)
             .maybe_skip_new_payload(node_config.debug.skip_new_payload)
             .maybe_reorg(
@@ -163,13 +198,22 @@ where
         let static_file_producer_events = static_file_producer.lock().events();
         hooks.add(StaticFileHook::new(
             static_file_producer.clone(),
-            Box::new(ctx.task_executor().clone()),
+            Box::new(ctx.task_executor().with_priority(TaskPriority::Background)),
         ));
         info!(target: "reth::cli", "StaticFileProducer initialized");
 
         // Configure the pipeline
         let pipeline_exex_handle =
             exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty);
+        let mut pipeline_stages = pipeline_stages;
+        if let Some(snap_client) = ctx.snap_client() {
+            pipeline_stages.insert(0, snap_sync_stage_hook(snap_client));
+        }
+        let volume_monitor = VolumeMonitor::new(
+            ctx.data_dir().data_class_dirs(),
+            ctx.node_config().datadir.min_free_space(),
+        )
+        .with_alarm(ctx.toml_config().disk_alarm.clone());
         let pipeline = build_networked_pipeline(
             &ctx.toml_config().stages,
             network_client.clone(),
@@ -182,12 +226,15 @@ where
             static_file_producer,
             ctx.components().block_executor().clone(),
             pipeline_exex_handle,
+            pipeline_stages,
+            Some(volume_monitor.pause_receiver()),
         )?;
 
         // The new engine writes directly to static files. This ensures that they're up to the tip.
         pipeline.move_to_static_files()?;
 
         let pipeline_events = pipeline.events();
+        let sync_progress_events = pipeline.events();
 
         let mut pruner_builder = ctx.pruner_builder();
         if let Some(exex_manager_handle) = &exex_manager_handle {
@@ -197,6 +244,11 @@ where
         let pruner = pruner_builder.build_with_provider_factory(ctx.provider_factory().clone());
 
         let pruner_events = pruner.events();
+        let prune_interval = pruner.block_interval_handle();
+        let volume_monitor = volume_monitor.with_prune_distance(pruner.distance_handle());
+        ctx.task_executor()
+            .with_priority(TaskPriority::Background)
+            .spawn(Box::pin(volume_monitor.run()));
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
 
         // Configure the consensus engine
@@ -212,7 +264,21 @@ where
             ctx.blockchain_db().clone(),
             pruner,
             ctx.components().payload_builder().clone(),
-            TreeConfig::default(),
+            TreeConfig::default()
+                .with_new_payload_budget(ctx.toml_config().engine.new_payload_budget)
+                .with_forkchoice_updated_budget(ctx.toml_config().engine.forkchoice_updated_budget)
+                .with_max_parallel_validations(ctx.toml_config().engine.max_parallel_validations)
+                .with_parallel_validation_memory_budget(
+                    ctx.toml_config().engine.parallel_validation_memory_budget,
+                )
+                .with_persistence_threshold(ctx.toml_config().engine.persistence_threshold)
+                .with_memory_block_buffer_target(
+                    ctx.toml_config().engine.memory_block_buffer_target,
+                )
+                .with_persistence_policy(match ctx.toml_config().engine.persistence_policy {
+                    reth_config::PersistencePolicy::Depth => PersistencePolicy::Depth,
+                    reth_config::PersistencePolicy::Finalized => PersistencePolicy::Finalized,
+                }),
         );
 
         let event_sender = EventSender::default();
@@ -246,18 +312,57 @@ where
             ),
         );
 
+        if let Some(audit_log) = ctx.toml_config().audit_log.clone() {
+            let dir = audit_log.dir.unwrap_or_else(|| ctx.data_dir().audit_log());
+            let log = AuditLog::new(&dir, audit_log.max_file_size, audit_log.max_files)?;
+            let payload_events = ctx.components().payload_builder().subscribe().await.ok();
+            info!(target: "reth::cli", dir = %dir.display(), "Writing audit log");
+            ctx.task_executor().with_priority(TaskPriority::Background).spawn(Box::pin(
+                handle_audit_events(
+                    log,
+                    beacon_engine_handle.event_listener(),
+                    ctx.blockchain_db().canonical_state_stream(),
+                    payload_events,
+                ),
+            ));
+        }
+
+        if let Some(verifier) = ctx.toml_config().verifier.clone() {
+            info!(target: "reth::cli", interval = ?verifier.interval, max_depth = ?verifier.max_depth, "Verifying sampled historical blocks");
+            let verifier = BlockVerifier::new(
+                ctx.blockchain_db().clone(),
+                ctx.components().block_executor().clone(),
+                ctx.consensus(),
+                verifier,
+            );
+            ctx.task_executor()
+                .with_priority(TaskPriority::Background)
+                .spawn_blocking(Box::pin(verifier.run()));
+        }
+
         let client = ClientVersionV1 {
             code: CLIENT_CODE,
             name: NAME_CLIENT.to_string(),
             version: CARGO_PKG_VERSION.to_string(),
             commit: VERGEN_GIT_SHA.to_string(),
         };
+        let backup_engine_api = backup_engine_tx.map(|tx| {
+            EngineApi::new(
+                ctx.blockchain_db().clone(),
+                ctx.chain_spec(),
+                beacon_engine_handle.with_engine_sender(tx),
+                ctx.components().payload_builder().clone().into(),
+                Box::new(ctx.task_executor().with_priority(TaskPriority::Consensus)),
+                client.clone(),
+                EngineCapabilities::default(),
+            )
+        });
         let engine_api = EngineApi::new(
             ctx.blockchain_db().clone(),
             ctx.chain_spec(),
             beacon_engine_handle,
             ctx.components().payload_builder().clone().into(),
-            Box::new(ctx.task_executor().clone()),
+            Box::new(ctx.task_executor().with_priority(TaskPriority::Consensus)),
             client,
             EngineCapabilities::default(),
         );
@@ -270,12 +375,71 @@ where
         let (rpc_server_handles, rpc_registry) = launch_rpc_servers(
             ctx.node_adapter().clone(),
             engine_api,
+            backup_engine_api,
+            None,
             ctx.node_config(),
+            ctx.prune_modes(),
             jwt_secret,
             rpc,
         )
         .await?;
 
+        // report the progress of the sync stages via `eth_syncing`
+        ctx.task_executor().spawn(Box::pin(track_sync_progress(
+            rpc_registry.sync_progress().clone(),
+            sync_progress_events,
+        )));
+
+        // check the consistency of the stored chain in the background
+        if let Some(integrity_check) = ctx.toml_config().integrity_check.clone() {
+            let checker = IntegrityChecker::new(
+                ctx.provider_factory().clone(),
+                ctx.prune_modes(),
+                integrity_check,
+                ctx.data_dir().integrity_check(),
+            )
+            .with_handle(rpc_registry.integrity_check().clone());
+            ctx.task_executor().with_priority(TaskPriority::Background).spawn_blocking(Box::pin(async move {
+                // the task keeps its blocking thread until the node shuts down
+                if let Err(err) = set_idle_io_priority() {
+                    debug!(target: "reth::cli", %err, "Failed to lower the I/O priority of the integrity check");
+                }
+                checker.run()
+            }));
+        }
+
+        // reload the config file while the node is running
+        let config_watcher = ConfigWatcher::new(
+            ctx.toml_config_path(),
+            ctx.node_config().config_profile.clone(),
+            ctx.toml_config().clone(),
+        )
+        .with_overrides(
+            ConfigOverrides::from_env()
+                .with_cli_overrides(ctx.node_config().config_overrides.iter().cloned()),
+        )
+        .with_network(ctx.components().network().clone())
+        .with_pruner(prune_interval)
+        .with_rpc_limits(rpc_registry.limits().clone(), rpc_registry.eth_config().clone())
+        .with_gas_oracle(rpc_registry.eth_api().clone());
+        ctx.task_executor()
+            .with_priority(TaskPriority::Background)
+            .spawn(Box::pin(config_watcher.run()));
+
+        // keep trusted peers given by DNS name or by URL up to date
+        let network_args = &ctx.node_config().network;
+        let mut trusted_peers = TrustedPeersWatcher::new(
+            ctx.components().network().clone(),
+            ctx.toml_config().peers.trusted_nodes.clone(),
+            network_args.trusted_peers_refresh_interval,
+        );
+        if let Some(url) = network_args.trusted_peers_url.clone() {
+            trusted_peers = trusted_peers.with_url(url);
+        }
+        if trusted_peers.needs_refresh() {
+            ctx.task_executor().spawn(Box::pin(trusted_peers.run()));
+        }
+
         // Run consensus engine to completion
         let initial_target = ctx.initial_backfill_target()?;
         let network_handle = ctx.components().network().clone();
@@ -290,7 +454,7 @@ where
         let chainspec = ctx.chain_spec();
         let (exit, rx) = oneshot::channel();
         info!(target: "reth::cli", "Starting consensus engine");
-        ctx.task_executor().spawn_critical("consensus engine", async move {
+        ctx.task_executor().with_priority(TaskPriority::Consensus).spawn_critical("consensus engine", async move {
             if let Some(initial_target) = initial_target {
                 debug!(target: "reth::cli", %initial_target,  "start backfill sync");
                 eth_service.orchestrator_mut().start_backfill_sync(initial_target);
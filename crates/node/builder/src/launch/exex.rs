@@ -3,11 +3,14 @@
 use std::{fmt, fmt::Debug};
 
 use futures::future;
-use reth_exex::{ExExContext, ExExHandle, ExExManager, ExExManagerHandle};
+use reth_exex::{
+    BackfillJobFactory, ExExBackfill, ExExContext, ExExDeliveryMode, ExExEvent, ExExHandle,
+    ExExManager, ExExManagerHandle,
+};
 use reth_node_api::FullNodeComponents;
 use reth_primitives::Head;
-use reth_provider::CanonStateSubscriptions;
-use reth_tracing::tracing::{debug, info};
+use reth_provider::{BlockNumReader, CanonStateSubscriptions, ExExCheckpointReader};
+use reth_tracing::tracing::{debug, error, info};
 use tracing::Instrument;
 
 use crate::{common::WithConfigs, exex::BoxedLaunchExEx};
@@ -15,7 +18,7 @@ use crate::{common::WithConfigs, exex::BoxedLaunchExEx};
 /// Can launch execution extensions.
 pub struct ExExLauncher<Node: FullNodeComponents> {
     head: Head,
-    extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    extensions: Vec<(String, ExExDeliveryMode, Box<dyn BoxedLaunchExEx<Node>>)>,
     components: Node,
     config_container: WithConfigs,
 }
@@ -25,7 +28,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     pub const fn new(
         head: Head,
         components: Node,
-        extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+        extensions: Vec<(String, ExExDeliveryMode, Box<dyn BoxedLaunchExEx<Node>>)>,
         config_container: WithConfigs,
     ) -> Self {
         Self { head, extensions, components, config_container }
@@ -46,13 +49,25 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
         let mut exex_handles = Vec::with_capacity(extensions.len());
         let mut exexes = Vec::with_capacity(extensions.len());
 
-        for (id, exex) in extensions {
+        for (id, delivery_mode, exex) in extensions {
             // create a new exex handle
-            let (handle, events, notifications) = ExExHandle::new(id.clone());
+            let (handle, events, notifications) =
+                ExExHandle::with_delivery_mode(id.clone(), delivery_mode);
             exex_handles.push(handle);
 
+            // replay all blocks after the checkpoint saved by the exex before the last shutdown
+            match components.provider().get_exex_checkpoint(&id) {
+                Ok(Some(checkpoint)) => {
+                    debug!(target: "reth::cli", id, checkpoint, "backfilling exex from checkpoint");
+                    let _ = events.send(ExExEvent::BackfillFrom(checkpoint + 1));
+                }
+                Ok(None) => {}
+                Err(err) => error!(target: "reth::cli", id, %err, "failed to read exex checkpoint"),
+            }
+
             // create the launch context for the exex
             let context = ExExContext {
+                id: id.clone(),
                 head,
                 config: config_container.config.clone(),
                 reth_config: config_container.toml_config.clone(),
@@ -89,7 +104,19 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
         // spawn exex manager
         debug!(target: "reth::cli", "spawning exex manager");
         // todo(onbjerg): rm magic number
-        let exex_manager = ExExManager::new(exex_handles, 1024);
+        let provider = components.provider().clone();
+        let backfill_job_factory = BackfillJobFactory::new_from_components(components.clone());
+        let exex_manager = ExExManager::new(exex_handles, 1024).with_backfill_factory(
+            move |from| {
+                let to = provider
+                    .best_block_number()
+                    .inspect_err(|err| {
+                        error!(target: "reth::cli", %err, "Failed to get the best block to backfill to")
+                    })
+                    .ok()?;
+                (from <= to).then(|| ExExBackfill::from_job_factory(&backfill_job_factory, from..=to))
+            },
+        );
         let exex_manager_handle = exex_manager.handle();
         components.task_executor().spawn_critical("exex manager", async move {
             exex_manager.await.expect("exex manager crashed");
@@ -120,7 +147,7 @@ impl<Node: FullNodeComponents> Debug for ExExLauncher<Node> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ExExLauncher")
             .field("head", &self.head)
-            .field("extensions", &self.extensions.iter().map(|(id, _)| id).collect::<Vec<_>>())
+            .field("extensions", &self.extensions.iter().map(|(id, _, _)| id).collect::<Vec<_>>())
             .field("components", &"...")
             .field("config_container", &self.config_container)
             .finish()
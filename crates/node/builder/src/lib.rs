@@ -35,6 +35,18 @@ pub mod rpc;
 
 pub mod setup;
 
+/// Reloading of the configuration file.
+pub mod config_watcher;
+
+/// Re-resolution and reconciliation of the trusted peers.
+pub mod trusted_peers;
+
+/// Re-execution of sampled historical blocks.
+pub mod verifier;
+
+/// Monitoring of the free space on the volumes of the data dir.
+pub mod volumes;
+
 /// Support for installing the ExExs (execution extensions) in a node.
 pub mod exex;
 
@@ -3,26 +3,39 @@
 use std::{
     fmt,
     ops::{Deref, DerefMut},
+    path::PathBuf,
 };
 
 use futures::TryFutureExt;
+use jsonrpsee::Methods;
+use reth_auto_seal_consensus::MiningHandle;
+use reth_forked_state::ForkPoint;
 use reth_node_api::{BuilderProvider, FullNodeComponents};
 use reth_node_core::{
     node_config::NodeConfig,
     rpc::{
-        api::EngineApiServer,
-        eth::{EthApiTypes, FullEthApiServer},
+        api::{AnvilApiServer, EngineApiServer, EvmApiServer},
+        eth::{helpers::AddDevSigners, EthApiTypes, FullEthApiServer},
     },
 };
 use reth_payload_builder::PayloadBuilderHandle;
+use reth_prune::PruneModes;
+use reth_rpc::{eth::DevSigner, AnvilApi, AnvilFork, EvmApi};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
+    error::RpcError,
+    health::{HealthCheck, NodeSyncStatusProvider},
     RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
 };
+use reth_rpc_eth_types::{
+    cache::warm::{read_cached_blocks, write_cached_blocks},
+    EthStateCache,
+};
 use reth_rpc_layer::JwtSecret;
-use reth_tasks::TaskExecutor;
-use reth_tracing::tracing::{debug, info};
+use reth_rpc_types::anvil::ForkedNetwork;
+use reth_tasks::{TaskExecutor, TaskPriority};
+use reth_tracing::tracing::{debug, info, warn};
 
 use crate::{EthApiBuilderCtx, RpcAddOns};
 
@@ -35,6 +48,8 @@ pub struct RethRpcServerHandles {
     pub rpc: RpcServerHandle,
     /// The handle to the auth server (engine API)
     pub auth: AuthServerHandle,
+    /// The handle to the auth server for a backup consensus layer client, if configured.
+    pub backup_auth: Option<AuthServerHandle>,
 }
 
 /// Contains hooks that are called during the rpc setup.
@@ -178,6 +193,98 @@ where
     }
 }
 
+/// Builds the methods of a custom rpc namespace.
+///
+/// The builder has access to the same [`EthApiBuilderCtx`] the core `eth` handlers are built with.
+pub trait RpcNamespaceBuilder<Node: FullNodeComponents>: Send {
+    /// Returns the methods of the namespace.
+    fn build_namespace(self: Box<Self>, ctx: &EthApiBuilderCtx<Node>) -> eyre::Result<Methods>;
+}
+
+impl<Node, F, M> RpcNamespaceBuilder<Node> for F
+where
+    F: FnOnce(&EthApiBuilderCtx<Node>) -> eyre::Result<M> + Send,
+    M: Into<Methods>,
+    Node: FullNodeComponents,
+{
+    fn build_namespace(self: Box<Self>, ctx: &EthApiBuilderCtx<Node>) -> eyre::Result<Methods> {
+        (*self)(ctx).map(Into::into)
+    }
+}
+
+/// Registry of custom rpc namespaces that are installed on all configured transports.
+///
+/// Every namespace is keyed by its name, e.g. `myapp`, and all of its methods must be prefixed
+/// with `<name>_`.
+pub struct RpcNamespaces<Node: FullNodeComponents> {
+    namespaces: Vec<(String, Box<dyn RpcNamespaceBuilder<Node>>)>,
+}
+
+impl<Node: FullNodeComponents> Default for RpcNamespaces<Node> {
+    fn default() -> Self {
+        Self { namespaces: Vec::new() }
+    }
+}
+
+impl<Node: FullNodeComponents> RpcNamespaces<Node> {
+    /// Registers the builder for the given namespace.
+    ///
+    /// Replaces the builder if the namespace is already registered.
+    pub fn register<F>(&mut self, name: impl Into<String>, builder: F) -> &mut Self
+    where
+        F: RpcNamespaceBuilder<Node> + 'static,
+    {
+        let name = name.into();
+        let builder: Box<dyn RpcNamespaceBuilder<Node>> = Box::new(builder);
+        if let Some((_, existing)) = self.namespaces.iter_mut().find(|(n, _)| *n == name) {
+            *existing = builder;
+        } else {
+            self.namespaces.push((name, builder));
+        }
+        self
+    }
+
+    /// Returns the names of all registered namespaces.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.namespaces.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns `true` if no namespace is registered.
+    pub fn is_empty(&self) -> bool {
+        self.namespaces.is_empty()
+    }
+
+    /// Builds all registered namespaces and merges them into the configured transports.
+    pub(crate) fn install(
+        self,
+        ctx: &EthApiBuilderCtx<Node>,
+        modules: &mut TransportRpcModules,
+    ) -> eyre::Result<()> {
+        for (name, builder) in self.namespaces {
+            let methods = builder.build_namespace(ctx)?;
+            ensure_namespace(&name, &methods)?;
+            modules.merge_configured(methods)?;
+            debug!(target: "reth::cli", namespace=%name, "Installed custom RPC namespace");
+        }
+        Ok(())
+    }
+}
+
+impl<Node: FullNodeComponents> fmt::Debug for RpcNamespaces<Node> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+/// Ensures that all methods belong to the given namespace.
+fn ensure_namespace(name: &str, methods: &Methods) -> eyre::Result<()> {
+    let prefix = format!("{name}_");
+    if let Some(method) = methods.method_names().find(|method| !method.starts_with(&prefix)) {
+        eyre::bail!("method `{method}` is not part of the `{name}` rpc namespace")
+    }
+    Ok(())
+}
+
 /// Helper wrapper type to encapsulate the [`RpcRegistryInner`] over components trait.
 #[derive(Debug, Clone)]
 #[allow(clippy::type_complexity)]
@@ -288,10 +395,20 @@ where
 }
 
 /// Launch the rpc servers.
+///
+/// If a `backup_engine_api` is given, it is served by a separate auth server for a backup consensus
+/// layer client. If a `dev_mining` handle is given, the `evm` and `anvil` namespaces are served on
+/// all configured transports, and the `eth` methods that send transactions and read the state are
+/// replaced to support impersonated accounts and the pending state of the dev chain. In dev mode,
+/// the dev-signer accounts are added to the `eth` API.
+#[allow(clippy::too_many_arguments)]
 pub async fn launch_rpc_servers<Node, Engine, EthApi>(
     node: Node,
     engine_api: Engine,
+    backup_engine_api: Option<Engine>,
+    dev_mining: Option<MiningHandle>,
     config: &NodeConfig,
+    prune_modes: PruneModes,
     jwt_secret: JwtSecret,
     add_ons: RpcAddOns<Node, EthApi>,
 ) -> eyre::Result<(RethRpcServerHandles, RpcRegistry<Node, EthApi>)>
@@ -301,22 +418,93 @@ where
     EthApi: EthApiBuilderProvider<Node>
         + FullEthApiServer<
             NetworkTypes: alloy_network::Network<TransactionResponse = reth_rpc_types::Transaction>,
-        >,
+        > + AddDevSigners,
 {
     let auth_config = config.rpc.auth_server_config(jwt_secret)?;
+    let backup_auth_config = config.rpc.backup_auth_server_config(jwt_secret);
     let module_config = config.rpc.transport_rpc_module_config();
     debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
+    let rpc_executor = node.task_executor().with_priority(TaskPriority::Rpc);
     let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
         .with_provider(node.provider().clone())
         .with_pool(node.pool().clone())
         .with_network(node.network().clone())
         .with_events(node.provider().clone())
-        .with_executor(node.task_executor().clone())
+        .with_executor(rpc_executor.clone())
         .with_evm_config(node.evm_config().clone())
+        .with_eth_middleware(add_ons.eth_middleware)
+        .with_prune_modes(prune_modes)
+        .with_historical_fallback(config.rpc.historical_fallback())
         .build_with_auth_server(module_config, engine_api, EthApi::eth_api_builder());
 
     let mut registry = RpcRegistry { registry };
+
+    let warm_blocks = config.rpc.rpc_state_cache.warm_blocks;
+    if warm_blocks > 0 {
+        spawn_cache_warming(
+            &rpc_executor,
+            registry.eth_cache().clone(),
+            config.datadir().rpc_cache_blocks(),
+            warm_blocks,
+        );
+    }
+
+    // in dev mode we add the dev-signer accounts, by default the 20 prefunded accounts of the dev
+    // chain
+    if config.dev.dev {
+        let signers = DevSigner::from_config(&config.dev.signer_config())?;
+        registry.eth_api().with_dev_signers(signers);
+    }
+
+    if let Some(mining) = dev_mining {
+        modules.merge_configured(EvmApi::new(mining.clone()).into_rpc())?;
+
+        let fork = match &config.dev.fork_url {
+            Some(url) => {
+                ForkPoint::load(&config.datadir().fork().join("fork.json"))?.map(|fork_point| {
+                    AnvilFork {
+                        url: url.to_string(),
+                        network: ForkedNetwork {
+                            chain_id: fork_point.chain_id,
+                            fork_block_number: fork_point.block_number,
+                            fork_block_hash: fork_point.block_hash,
+                        },
+                    }
+                })
+            }
+            None => None,
+        };
+        let anvil =
+            AnvilApi::new(node.provider().clone(), registry.eth_api().clone(), mining, fork);
+        modules.replace_configured(anvil.eth_module())?;
+        let mut anvil = anvil.into_rpc();
+        // the snapshot methods of Hardhat and Ganache
+        anvil.register_alias("evm_snapshot", "anvil_snapshot")?;
+        anvil.register_alias("evm_revert", "anvil_revert")?;
+        modules.merge_configured(anvil)?;
+    }
+
+    let RpcAddOns { hooks, namespaces, .. } = add_ons;
+    let RpcHooks { on_rpc_started, extend_rpc_modules } = hooks;
+
+    if !namespaces.is_empty() {
+        let eth_ctx = EthApiBuilderCtx::<Node> {
+            provider: node.provider().clone(),
+            pool: node.pool().clone(),
+            network: node.network().clone(),
+            evm_config: node.evm_config().clone(),
+            config: registry.eth_config().clone(),
+            executor: rpc_executor,
+            events: node.provider().clone(),
+            cache: registry.eth_cache().clone(),
+            limits: registry.limits().clone(),
+            sync_progress: registry.sync_progress().clone(),
+            historical_fallback: config.rpc.historical_fallback(),
+        };
+        namespaces.install(&eth_ctx, &mut modules)?;
+    }
+
     let ctx = RpcContext {
         node: node.clone(),
         config,
@@ -325,12 +513,12 @@ where
         auth_module: &mut auth_module,
     };
 
-    let RpcAddOns { hooks, .. } = add_ons;
-    let RpcHooks { on_rpc_started, extend_rpc_modules } = hooks;
-
     extend_rpc_modules.extend_rpc_modules(ctx)?;
 
-    let server_config = config.rpc.rpc_server_config();
+    let server_config = config.rpc.rpc_server_config().with_health_check(Some(HealthCheck::new(
+        config.rpc.health_config(),
+        NodeSyncStatusProvider::new(node.provider().clone(), node.network().clone()),
+    )));
     let cloned_modules = modules.clone();
     let launch_rpc = server_config.start(&cloned_modules).map_ok(|handle| {
         if let Some(path) = handle.ipc_endpoint() {
@@ -355,9 +543,19 @@ where
         handle
     });
 
+    let launch_backup_auth = async move {
+        let (Some(engine_api), Some(config)) = (backup_engine_api, backup_auth_config) else {
+            return Ok(None)
+        };
+        let handle = AuthRpcModule::new(engine_api).start_server(config).await?;
+        info!(target: "reth::cli", url=%handle.local_addr(), "RPC backup auth server started");
+        Ok::<_, RpcError>(Some(handle))
+    };
+
     // launch servers concurrently
-    let (rpc, auth) = futures::future::try_join(launch_rpc, launch_auth).await?;
-    let handles = RethRpcServerHandles { rpc, auth };
+    let (rpc, auth, backup_auth) =
+        futures::future::try_join3(launch_rpc, launch_auth, launch_backup_auth).await?;
+    let handles = RethRpcServerHandles { rpc, auth, backup_auth };
 
     let ctx = RpcContext {
         node,
@@ -372,6 +570,53 @@ where
     Ok((handles, registry))
 }
 
+/// Spawns a task that loads the blocks that were cached before the last shutdown into the
+/// [`EthStateCache`], and records the `max_blocks` most recently used blocks on shutdown.
+fn spawn_cache_warming(
+    executor: &TaskExecutor,
+    cache: EthStateCache,
+    path: PathBuf,
+    max_blocks: usize,
+) {
+    executor.spawn_critical_with_graceful_shutdown_signal("rpc cache warming", |shutdown| {
+        async move {
+            match read_cached_blocks(&path) {
+                Ok(mut block_hashes) => {
+                    block_hashes.truncate(max_blocks);
+                    match cache.warm(block_hashes).await {
+                        Ok(warmed) => {
+                            info!(target: "reth::cli", blocks = warmed, "Warmed RPC state cache")
+                        }
+                        Err(err) => {
+                            warn!(target: "reth::cli", %err, "Failed to warm RPC state cache")
+                        }
+                    }
+                }
+                Err(err) => {
+                    warn!(target: "reth::cli", %err, ?path, "Failed to read cached RPC blocks")
+                }
+            }
+
+            let _guard = shutdown.await;
+            let block_hashes = match cache.cached_block_hashes(max_blocks).await {
+                Ok(block_hashes) => block_hashes,
+                Err(err) => {
+                    warn!(target: "reth::cli", %err, "Failed to get cached RPC blocks");
+                    return
+                }
+            };
+            match write_cached_blocks(&path, &block_hashes) {
+                Ok(()) => {
+                    debug!(target: "reth::cli", blocks = block_hashes.len(), ?path, "Wrote cached RPC blocks")
+                }
+                Err(err) => {
+                    warn!(target: "reth::cli", %err, ?path, "Failed to write cached RPC blocks")
+                }
+            }
+        }
+    });
+}
+
 /// Provides builder for the core `eth` API type.
 pub trait EthApiBuilderProvider<N: FullNodeComponents>: BuilderProvider<N> + EthApiTypes {
     /// Returns the eth api builder.
@@ -388,3 +633,21 @@ where
         F::builder()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::RpcModule;
+
+    #[test]
+    fn namespace_methods_must_be_prefixed() {
+        let mut module = RpcModule::new(());
+        module.register_method("myapp_status", |_, _, _| "ok").unwrap();
+        assert!(ensure_namespace("myapp", &module.clone().into()).is_ok());
+        assert!(ensure_namespace("my", &module.clone().into()).is_err());
+
+        module.register_method("eth_status", |_, _, _| "ok").unwrap();
+        let err = ensure_namespace("myapp", &module.into()).unwrap_err();
+        assert_eq!(err.to_string(), "method `eth_status` is not part of the `myapp` rpc namespace");
+    }
+}
@@ -12,16 +12,61 @@ use reth_downloaders::{
 use reth_evm::execute::BlockExecutorProvider;
 use reth_exex::ExExManagerHandle;
 use reth_network_p2p::{
-    bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader, BlockClient,
+    bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader,
+    snap::client::SnapClient, BlockClient,
 };
 use reth_node_core::primitives::{BlockNumber, B256};
 use reth_provider::ProviderFactory;
-use reth_stages::{prelude::DefaultStages, stages::ExecutionStage, Pipeline, StageSet};
+use reth_stages::{
+    prelude::DefaultStages,
+    stages::{ExecutionStage, SnapSyncStage},
+    Pipeline, StageId, StageSet, StageSetBuilder,
+};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::debug;
 use tokio::sync::watch;
 
+/// A hook that is run to configure the stages of the sync pipeline.
+///
+/// This can be used to insert custom stages at defined positions, e.g. with
+/// [`StageSetBuilder::add_after`]. Their configuration can be read from the
+/// `[stages.extra.<name>]` tables of the config file, see [`StageConfig::extra`].
+pub trait ExtendPipelineStages<DB: Database>: Send {
+    /// Returns the configured stages.
+    fn extend_stages(
+        self: Box<Self>,
+        stages: StageSetBuilder<DB>,
+        config: &StageConfig,
+    ) -> eyre::Result<StageSetBuilder<DB>>;
+}
+
+impl<DB, F> ExtendPipelineStages<DB> for F
+where
+    DB: Database,
+    F: FnOnce(StageSetBuilder<DB>, &StageConfig) -> eyre::Result<StageSetBuilder<DB>> + Send,
+{
+    fn extend_stages(
+        self: Box<Self>,
+        stages: StageSetBuilder<DB>,
+        config: &StageConfig,
+    ) -> eyre::Result<StageSetBuilder<DB>> {
+        (*self)(stages, config)
+    }
+}
+
+/// Returns the hook that adds the [`SnapSyncStage`] with the given client before the sender
+/// recovery stage.
+pub fn snap_sync_stage_hook<DB, C>(client: C) -> Box<dyn ExtendPipelineStages<DB>>
+where
+    DB: Database,
+    C: SnapClient + 'static,
+{
+    Box::new(move |stages: StageSetBuilder<DB>, config: &StageConfig| {
+        Ok(stages.add_before(SnapSyncStage::new(client, config.snap), StageId::SenderRecovery))
+    })
+}
+
 /// Constructs a [Pipeline] that's wired to the network
 #[allow(clippy::too_many_arguments)]
 pub fn build_networked_pipeline<DB, Client, Executor>(
@@ -36,6 +81,8 @@ pub fn build_networked_pipeline<DB, Client, Executor>(
     static_file_producer: StaticFileProducer<DB>,
     executor: Executor,
     exex_manager_handle: ExExManagerHandle,
+    stage_hooks: Vec<Box<dyn ExtendPipelineStages<DB>>>,
+    pause_rx: Option<watch::Receiver<bool>>,
 ) -> eyre::Result<Pipeline<DB>>
 where
     DB: Database + Unpin + Clone + 'static,
@@ -63,12 +110,17 @@ where
         static_file_producer,
         executor,
         exex_manager_handle,
+        stage_hooks,
+        pause_rx,
     )?;
 
     Ok(pipeline)
 }
 
 /// Builds the [Pipeline] with the given [`ProviderFactory`] and downloaders.
+///
+/// The default stages are passed through the given hooks in order before they are added to the
+/// pipeline. The pipeline is paused while `true` is received from `pause_rx`.
 #[allow(clippy::too_many_arguments)]
 pub fn build_pipeline<DB, H, B, Executor>(
     provider_factory: ProviderFactory<DB>,
@@ -82,6 +134,8 @@ pub fn build_pipeline<DB, H, B, Executor>(
     static_file_producer: StaticFileProducer<DB>,
     executor: Executor,
     exex_manager_handle: ExExManagerHandle,
+    stage_hooks: Vec<Box<dyn ExtendPipelineStages<DB>>>,
+    pause_rx: Option<watch::Receiver<bool>>,
 ) -> eyre::Result<Pipeline<DB>>
 where
     DB: Database + Clone + 'static,
@@ -96,35 +150,44 @@ where
         builder = builder.with_max_block(max_block)
     }
 
+    if let Some(pause_rx) = pause_rx {
+        builder = builder.with_pause_receiver(pause_rx)
+    }
+
     let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
 
     let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
+    let mut stages = DefaultStages::new(
+        provider_factory.clone(),
+        tip_rx,
+        Arc::clone(&consensus),
+        header_downloader,
+        body_downloader,
+        executor.clone(),
+        stage_config.clone(),
+        prune_modes.clone(),
+    )
+    .set(
+        ExecutionStage::new(
+            executor,
+            stage_config.execution.into(),
+            stage_config.execution_external_clean_threshold(),
+            prune_modes,
+            exex_manager_handle,
+        )
+        .with_metrics_tx(metrics_tx.clone())
+        .with_prewarming(provider_factory.clone(), stage_config.execution.prewarm_workers),
+    );
+    for hook in stage_hooks {
+        stages = hook.extend_stages(stages, stage_config)?;
+    }
+
     let pipeline = builder
         .with_tip_sender(tip_tx)
-        .with_metrics_tx(metrics_tx.clone())
-        .add_stages(
-            DefaultStages::new(
-                provider_factory.clone(),
-                tip_rx,
-                Arc::clone(&consensus),
-                header_downloader,
-                body_downloader,
-                executor.clone(),
-                stage_config.clone(),
-                prune_modes.clone(),
-            )
-            .set(
-                ExecutionStage::new(
-                    executor,
-                    stage_config.execution.into(),
-                    stage_config.execution_external_clean_threshold(),
-                    prune_modes,
-                    exex_manager_handle,
-                )
-                .with_metrics_tx(metrics_tx),
-            ),
-        )
+        .with_metrics_tx(metrics_tx)
+        .with_progress_thresholds(stage_config.progress.clone().into())
+        .add_stages(stages)
         .build(provider_factory, static_file_producer);
 
     Ok(pipeline)
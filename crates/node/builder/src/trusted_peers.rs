@@ -0,0 +1,220 @@
+//! Keeps the trusted peers of a running node up to date.
+
+use std::{collections::HashMap, fmt, time::Duration};
+
+use eyre::{eyre, WrapErr};
+use futures::future::join_all;
+use reth_network_api::{PeerKind, Peers};
+use reth_network_peers::{NodeRecord, PeerId, TrustedPeer};
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, info, warn};
+use url::Url;
+
+/// Periodically re-resolves the trusted peers that are given by DNS name and re-fetches the list
+/// of trusted peers at a URL, and reconciles the trusted peers of the network with the result:
+///
+/// - peers that are new or whose address changed are (re-)added as trusted peers, a backed off peer
+///   is dialed again right away if its address changed
+/// - peers that were removed from the list are no longer trusted
+///
+/// If a name can't be resolved or the list can't be fetched, the last known addresses are kept.
+pub struct TrustedPeersWatcher<N> {
+    /// The network the trusted peers are applied to.
+    network: N,
+    /// The configured trusted peers.
+    configured: Vec<TrustedPeer>,
+    /// URL of a list of trusted peers, one enode URL per line.
+    url: Option<Url>,
+    /// How often the peers are refreshed.
+    interval: Duration,
+    /// Client to fetch the list with.
+    client: reqwest::Client,
+    /// The peers of the most recently fetched list.
+    fetched: Vec<TrustedPeer>,
+    /// The trusted peers that have been applied to the network.
+    applied: HashMap<PeerId, NodeRecord>,
+}
+
+impl<N> TrustedPeersWatcher<N>
+where
+    N: Peers,
+{
+    /// Creates a new watcher for the given trusted peers, that refreshes them in the given
+    /// interval.
+    pub fn new(network: N, configured: Vec<TrustedPeer>, interval: Duration) -> Self {
+        Self {
+            network,
+            configured,
+            url: None,
+            interval,
+            client: reqwest::Client::new(),
+            fetched: Vec::new(),
+            applied: HashMap::new(),
+        }
+    }
+
+    /// Sets the URL of a list of trusted peers that is re-fetched on every refresh.
+    pub fn with_url(mut self, url: Url) -> Self {
+        self.url = Some(url);
+        self
+    }
+
+    /// Returns `true` if there is anything to refresh, i.e. a URL is set or any of the trusted
+    /// peers is given by DNS name.
+    pub fn needs_refresh(&self) -> bool {
+        self.url.is_some() ||
+            self.configured.iter().any(|peer| matches!(peer.host, url::Host::Domain(_)))
+    }
+
+    /// Refreshes the trusted peers in the configured interval, starting right away.
+    pub async fn run(mut self) {
+        // a zero interval would make the timer panic
+        let mut interval = tokio::time::interval(self.interval.max(Duration::from_secs(1)));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            self.refresh().await;
+        }
+    }
+
+    /// Re-fetches the list, re-resolves all trusted peers and applies the changes to the network.
+    pub async fn refresh(&mut self) {
+        if let Some(url) = &self.url {
+            match fetch_trusted_peers(&self.client, url).await {
+                Ok(peers) => self.fetched = peers,
+                Err(err) => {
+                    warn!(target: "reth::cli", %err, %url, "Failed to fetch trusted peers, keeping the previous list")
+                }
+            }
+        }
+
+        let peers = self.configured.iter().chain(&self.fetched);
+        let resolved =
+            join_all(peers.map(|peer| async move { (peer, peer.resolve().await) })).await;
+
+        let mut records = HashMap::with_capacity(resolved.len());
+        for (peer, result) in resolved {
+            match result {
+                Ok(record) => {
+                    records.insert(record.id, record);
+                }
+                Err(err) => {
+                    debug!(target: "reth::cli", %err, %peer, "Failed to resolve trusted peer");
+                    if let Some(record) = self.applied.get(&peer.id) {
+                        records.insert(peer.id, *record);
+                    }
+                }
+            }
+        }
+
+        self.apply(records);
+    }
+
+    /// Applies the resolved trusted peers to the network.
+    fn apply(&mut self, records: HashMap<PeerId, NodeRecord>) {
+        for record in records.values() {
+            if self.applied.get(&record.id) != Some(record) {
+                info!(target: "reth::cli", peer_id = %record.id, addr = %record.tcp_addr(), "Updating trusted peer");
+                self.network.add_trusted_peer_with_udp(
+                    record.id,
+                    record.tcp_addr(),
+                    record.udp_addr(),
+                );
+            }
+        }
+
+        for peer_id in self.applied.keys() {
+            if !records.contains_key(peer_id) {
+                info!(target: "reth::cli", %peer_id, "Removing trusted peer");
+                self.network.remove_peer(*peer_id, PeerKind::Trusted);
+            }
+        }
+
+        self.applied = records;
+    }
+}
+
+impl<N> fmt::Debug for TrustedPeersWatcher<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrustedPeersWatcher")
+            .field("configured", &self.configured)
+            .field("url", &self.url)
+            .field("interval", &self.interval)
+            .field("fetched", &self.fetched)
+            .field("applied", &self.applied)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Fetches the list of trusted peers at the given URL.
+async fn fetch_trusted_peers(
+    client: &reqwest::Client,
+    url: &Url,
+) -> eyre::Result<Vec<TrustedPeer>> {
+    let list = client
+        .get(url.clone())
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .wrap_err("Request failed")?
+        .text()
+        .await
+        .wrap_err("Failed to read response")?;
+
+    parse_trusted_peers(&list)
+}
+
+/// Parses a list of trusted peers, one enode URL per line.
+///
+/// Empty lines and lines starting with `#` are ignored.
+fn parse_trusted_peers(list: &str) -> eyre::Result<Vec<TrustedPeer>> {
+    list.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.parse().map_err(|err| eyre!("Invalid trusted peer `{line}`: {err}")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_network_api::noop::NoopNetwork;
+
+    const PEER_1: &str = "enode://6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0@10.3.58.6:30303";
+    const PEER_2: &str = "enode://d860a01f9722d78051619d1e2351aba3f43f943f6f00718d1b9baa4101932a1f5011f16bb2b1bb35db20d6fe28fa0bf09636d26a87d31de9ec6203eeedb1f666@18.138.108.67:30303";
+
+    #[test]
+    fn parse_list() {
+        let list = format!("# trusted peers\n{PEER_1}\n\n  {PEER_2}  \n");
+        let peers = parse_trusted_peers(&list).unwrap();
+        assert_eq!(peers, vec![PEER_1.parse().unwrap(), PEER_2.parse().unwrap()]);
+
+        assert!(parse_trusted_peers("enode://invalid").is_err());
+    }
+
+    #[tokio::test]
+    async fn reconciles_trusted_peers() {
+        let peer_1: TrustedPeer = PEER_1.parse().unwrap();
+        let peer_2: TrustedPeer = PEER_2.parse().unwrap();
+        let mut watcher =
+            TrustedPeersWatcher::new(NoopNetwork::default(), vec![peer_1.clone()], Duration::ZERO);
+        assert!(!watcher.needs_refresh());
+
+        // a peer of the fetched list
+        watcher.fetched = vec![peer_2.clone()];
+        watcher.refresh().await;
+        assert_eq!(watcher.applied.len(), 2);
+
+        // removed from the fetched list
+        watcher.fetched.clear();
+        watcher.refresh().await;
+        assert_eq!(watcher.applied.keys().collect::<Vec<_>>(), vec![&peer_1.id]);
+
+        let dns_peer =
+            TrustedPeer::new(url::Host::Domain("localhost".to_string()), 30303, peer_2.id);
+        let watcher =
+            TrustedPeersWatcher::new(NoopNetwork::default(), vec![dns_peer], Duration::ZERO);
+        assert!(watcher.needs_refresh());
+    }
+}
@@ -0,0 +1,288 @@
+//! Re-executes sampled historical blocks of a running node to detect corrupted data.
+
+use std::{
+    fmt,
+    ops::RangeInclusive,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use rand::Rng;
+use reth_config::BlockVerifierConfig;
+use reth_consensus::{Consensus, ConsensusError, PostExecutionInput};
+use reth_evm::execute::{BlockExecutorProvider, Executor};
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
+use reth_primitives::{BlockNumber, B256};
+use reth_provider::{
+    BlockExecutionOutput, BlockReader, HeaderProvider, ProviderError, ProviderResult,
+    StateProviderFactory, TransactionVariant,
+};
+use reth_revm::database::StateProviderDatabase;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, error, warn};
+
+/// A difference between a re-executed block and the stored chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockMismatch {
+    /// The block failed to execute on the stored state of its parent.
+    Execution(String),
+    /// The execution outcome doesn't match the header, e.g. its receipts root, logs bloom or gas
+    /// used.
+    Header(ConsensusError),
+    /// The stored receipt of the transaction at the given index differs from the re-executed one.
+    Receipt(usize),
+    /// The number of stored receipts differs from the number of transactions.
+    ReceiptCount {
+        /// The number of stored receipts.
+        stored: usize,
+        /// The number of re-executed receipts.
+        executed: usize,
+    },
+    /// The state root of the re-executed state differs from the state root of the header.
+    StateRoot {
+        /// The computed state root.
+        got: B256,
+        /// The state root of the header.
+        expected: B256,
+    },
+}
+
+impl fmt::Display for BlockMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Execution(err) => write!(f, "execution failed: {err}"),
+            Self::Header(err) => write!(f, "{err}"),
+            Self::Receipt(index) => write!(f, "stored receipt {index} differs"),
+            Self::ReceiptCount { stored, executed } => {
+                write!(f, "{stored} receipts are stored, but the block has {executed}")
+            }
+            Self::StateRoot { got, expected } => {
+                write!(f, "state root mismatch: got {got}, expected {expected}")
+            }
+        }
+    }
+}
+
+/// The result of the verification of a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockVerification {
+    /// The re-executed block matches the stored chain.
+    Valid {
+        /// Whether the state root was recomputed.
+        state_root: bool,
+    },
+    /// The re-executed block differs from the stored chain.
+    Mismatch(Vec<BlockMismatch>),
+    /// The block or the state of its parent is not available, e.g. because it was pruned.
+    Unavailable,
+}
+
+/// Periodically re-executes a randomly sampled historical block on the stored state of its parent
+/// and compares the outcome with the stored chain:
+///
+/// - the receipts root, logs bloom and gas used with the header
+/// - the receipts with the stored receipts, unless they were pruned
+/// - the state root with the header, for blocks close to the tip
+///
+/// Mismatches are logged as errors and counted in the `block_verifier` metrics. Nothing is
+/// repaired, a mismatch means that the database is corrupted and should be restored or re-synced.
+pub struct BlockVerifier<P, E> {
+    /// The provider of the stored chain.
+    provider: P,
+    /// The executor the blocks are re-executed with.
+    executor: E,
+    /// Validates the execution outcome against the header.
+    consensus: Arc<dyn Consensus>,
+    /// The configuration of the verifier.
+    config: BlockVerifierConfig,
+    /// The metrics of the verifier.
+    metrics: BlockVerifierMetrics,
+}
+
+impl<P, E> BlockVerifier<P, E>
+where
+    P: BlockReader + HeaderProvider + StateProviderFactory,
+    E: BlockExecutorProvider,
+{
+    /// Creates a new verifier of the blocks of the given provider.
+    pub fn new(
+        provider: P,
+        executor: E,
+        consensus: Arc<dyn Consensus>,
+        config: BlockVerifierConfig,
+    ) -> Self {
+        Self { provider, executor, consensus, config, metrics: BlockVerifierMetrics::default() }
+    }
+
+    /// Verifies a sampled block in the configured interval, forever.
+    ///
+    /// Blocks are executed on the current thread, so this should be spawned as a blocking task.
+    pub async fn run(self) {
+        // a zero interval would make the timer panic
+        let mut interval = tokio::time::interval(self.config.interval.max(Duration::from_secs(1)));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.verify_sample() {
+                self.metrics.errors.increment(1);
+                warn!(target: "reth::verifier", %err, "Failed to verify block");
+            }
+        }
+    }
+
+    /// Verifies a block that is randomly sampled from the configured range.
+    pub fn verify_sample(&self) -> ProviderResult<()> {
+        let tip = self.provider.last_block_number()?;
+        let Some(range) = sample_range(tip, self.config.max_depth) else { return Ok(()) };
+        let number = rand::thread_rng().gen_range(range);
+
+        let start = Instant::now();
+        let verification = self.verify_block(number, tip)?;
+        self.metrics.duration_seconds.record(start.elapsed());
+
+        match verification {
+            BlockVerification::Valid { state_root } => {
+                self.metrics.blocks_verified.increment(1);
+                if state_root {
+                    self.metrics.state_roots_verified.increment(1);
+                }
+                debug!(target: "reth::verifier", number, state_root, "Verified block");
+            }
+            BlockVerification::Mismatch(mismatches) => {
+                self.metrics.blocks_verified.increment(1);
+                self.metrics.mismatches.increment(1);
+                self.metrics.last_mismatch_block.set(number as f64);
+                for mismatch in mismatches {
+                    error!(target: "reth::verifier", number, %mismatch, "Re-executed block differs from the stored chain, the database may be corrupted");
+                }
+            }
+            BlockVerification::Unavailable => {
+                self.metrics.blocks_unavailable.increment(1);
+                debug!(target: "reth::verifier", number, "Block or its parent state is not available");
+            }
+        }
+        Ok(())
+    }
+
+    /// Re-executes the block and compares the outcome with the stored chain.
+    ///
+    /// The state root is only recomputed if the block is at most
+    /// [`BlockVerifierConfig::state_root_depth`] blocks below the tip.
+    pub fn verify_block(
+        &self,
+        number: BlockNumber,
+        tip: BlockNumber,
+    ) -> ProviderResult<BlockVerification> {
+        // the genesis block isn't executed
+        let Some(parent) = number.checked_sub(1) else { return Ok(BlockVerification::Unavailable) };
+        let Some(block) =
+            self.provider.block_with_senders(number.into(), TransactionVariant::WithHash)?
+        else {
+            return Ok(BlockVerification::Unavailable)
+        };
+        let Some(parent_td) = self.provider.header_td_by_number(parent)? else {
+            return Ok(BlockVerification::Unavailable)
+        };
+        let state = match self.provider.history_by_block_number(parent) {
+            Ok(state) => state,
+            Err(ProviderError::StateAtBlockPruned(_)) => return Ok(BlockVerification::Unavailable),
+            Err(err) => return Err(err),
+        };
+
+        let executor = self.executor.executor(StateProviderDatabase::new(&state));
+        let total_difficulty = parent_td + block.difficulty;
+        let BlockExecutionOutput { state: bundle, receipts, requests, .. } =
+            match executor.execute((&block, total_difficulty).into()) {
+                Ok(output) => output,
+                Err(err) => {
+                    return Ok(BlockVerification::Mismatch(vec![BlockMismatch::Execution(
+                        err.to_string(),
+                    )]))
+                }
+            };
+
+        let mut mismatches = Vec::new();
+        if let Err(err) = self
+            .consensus
+            .validate_block_post_execution(&block, PostExecutionInput::new(&receipts, &requests))
+        {
+            mismatches.push(BlockMismatch::Header(err));
+        }
+
+        if let Some(stored) = self.provider.receipts_by_block(number.into())? {
+            if stored.len() != receipts.len() {
+                mismatches.push(BlockMismatch::ReceiptCount {
+                    stored: stored.len(),
+                    executed: receipts.len(),
+                });
+            } else if let Some(index) = stored.iter().zip(&receipts).position(|(a, b)| a != b) {
+                mismatches.push(BlockMismatch::Receipt(index));
+            }
+        }
+
+        let verify_state_root = tip.saturating_sub(number) <= self.config.state_root_depth;
+        if verify_state_root {
+            let state_root = state.state_root(&bundle)?;
+            if state_root != block.state_root {
+                mismatches
+                    .push(BlockMismatch::StateRoot { got: state_root, expected: block.state_root });
+            }
+        }
+
+        if mismatches.is_empty() {
+            Ok(BlockVerification::Valid { state_root: verify_state_root })
+        } else {
+            Ok(BlockVerification::Mismatch(mismatches))
+        }
+    }
+}
+
+impl<P, E> fmt::Debug for BlockVerifier<P, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockVerifier").field("config", &self.config).finish_non_exhaustive()
+    }
+}
+
+/// Returns the range of blocks that are sampled, the genesis block is never verified.
+fn sample_range(tip: BlockNumber, max_depth: Option<u64>) -> Option<RangeInclusive<BlockNumber>> {
+    let start = max_depth.map_or(1, |depth| tip.saturating_sub(depth.saturating_sub(1)).max(1));
+    (tip >= start).then_some(start..=tip)
+}
+
+/// Metrics of the [`BlockVerifier`].
+#[derive(Metrics)]
+#[metrics(scope = "block_verifier")]
+struct BlockVerifierMetrics {
+    /// The number of re-executed blocks
+    blocks_verified: Counter,
+    /// The number of re-executed blocks whose state root was recomputed
+    state_roots_verified: Counter,
+    /// The number of re-executed blocks that differ from the stored chain
+    mismatches: Counter,
+    /// The number of the last block that differed from the stored chain
+    last_mismatch_block: Gauge,
+    /// The number of sampled blocks that weren't available, e.g. because they were pruned
+    blocks_unavailable: Counter,
+    /// The number of verifications that failed with a database error
+    errors: Counter,
+    /// The time it took to verify a block
+    duration_seconds: Histogram,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sampled_blocks() {
+        assert_eq!(sample_range(0, None), None);
+        assert_eq!(sample_range(100, None), Some(1..=100));
+        assert_eq!(sample_range(100, Some(10)), Some(91..=100));
+        assert_eq!(sample_range(5, Some(10)), Some(1..=5));
+        assert_eq!(sample_range(100, Some(1)), Some(100..=100));
+    }
+}
@@ -0,0 +1,443 @@
+//! Monitors the free space on the volumes the data dir is stored on, and forecasts when they run
+//! out of it.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use reth_config::DiskAlarmConfig;
+use reth_metrics::{metrics::Gauge, Metrics};
+use reth_node_core::dirs::DataClass;
+use reth_prune::PruneDistanceHandle;
+use sysinfo::{Disk, Disks};
+use tokio::{sync::watch, time::MissedTickBehavior};
+use tracing::{info, warn};
+
+/// The default interval in which the free space of the volumes is checked.
+pub const DEFAULT_VOLUME_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Periodically checks the free space on the volumes of the data classes, e.g. when the database
+/// and the static files are stored on different disks, and records it in the metrics.
+///
+/// While any volume has less free space than the configured minimum, syncing is paused through
+/// the receivers of [`Self::pause_receiver`], so the pipeline doesn't fail in the middle of a
+/// write because the disk is full. Syncing resumes once space is freed.
+///
+/// The growth of the data classes and the shrinking of the free space are measured over the window
+/// of the [`DiskAlarmConfig`]. When a volume is projected to be full sooner than configured, the
+/// alarm is raised: a warning is logged, the `volumes.disk_alarm` metric is set and, if configured,
+/// pruning is tightened until the alarm is cleared.
+pub struct VolumeMonitor {
+    /// The directories of the data classes.
+    dirs: Vec<(DataClass, PathBuf)>,
+    /// The minimum free space in bytes, or 0 if syncing is never paused.
+    min_free_space: u64,
+    /// How often the free space is checked.
+    interval: Duration,
+    /// The disks of the system.
+    disks: Disks,
+    /// Sends whether syncing is paused.
+    paused: watch::Sender<bool>,
+    /// When the alarm is raised.
+    alarm: DiskAlarmConfig,
+    /// Whether the alarm is raised.
+    alarm_raised: bool,
+    /// Tightens pruning while the alarm is raised.
+    prune_distance: Option<PruneDistanceHandle>,
+    /// The sizes of the data classes within the window.
+    sizes: HashMap<DataClass, Samples>,
+    /// The free space of the volumes within the window, by mount point.
+    free_space: HashMap<PathBuf, Samples>,
+    /// Metrics of the volumes by data class.
+    metrics: HashMap<DataClass, VolumeMetrics>,
+    /// Metrics of the monitor.
+    monitor_metrics: VolumeMonitorMetrics,
+}
+
+impl VolumeMonitor {
+    /// Creates a new monitor for the directories of the data classes, that pauses syncing while a
+    /// volume has less than `min_free_space` bytes of free space.
+    pub fn new(dirs: impl IntoIterator<Item = (DataClass, PathBuf)>, min_free_space: u64) -> Self {
+        let dirs = dirs.into_iter().collect::<Vec<_>>();
+        let metrics = dirs
+            .iter()
+            .map(|(class, _)| {
+                (*class, VolumeMetrics::new_with_labels(&[("data_class", class.as_str())]))
+            })
+            .collect();
+        Self {
+            dirs,
+            min_free_space,
+            interval: DEFAULT_VOLUME_CHECK_INTERVAL,
+            disks: Disks::new(),
+            paused: watch::channel(false).0,
+            alarm: DiskAlarmConfig::default(),
+            alarm_raised: false,
+            prune_distance: None,
+            sizes: HashMap::new(),
+            free_space: HashMap::new(),
+            metrics,
+            monitor_metrics: VolumeMonitorMetrics::default(),
+        }
+    }
+
+    /// Sets how often the free space is checked.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets when the alarm is raised.
+    pub const fn with_alarm(mut self, alarm: DiskAlarmConfig) -> Self {
+        self.alarm = alarm;
+        self
+    }
+
+    /// Sets the handle that tightens pruning to the `auto_prune_distance` of the
+    /// [`DiskAlarmConfig`] while the alarm is raised.
+    pub fn with_prune_distance(mut self, prune_distance: PruneDistanceHandle) -> Self {
+        self.prune_distance = Some(prune_distance);
+        self
+    }
+
+    /// Returns a receiver for whether syncing is paused, see
+    /// [`PipelineBuilder::with_pause_receiver`](reth_stages::PipelineBuilder::with_pause_receiver).
+    pub fn pause_receiver(&self) -> watch::Receiver<bool> {
+        self.paused.subscribe()
+    }
+
+    /// Checks the free space in the configured interval, starting right away.
+    pub async fn run(mut self) {
+        // a zero interval would make the timer panic
+        let mut interval = tokio::time::interval(self.interval.max(Duration::from_secs(1)));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            self.check();
+        }
+    }
+
+    /// Checks the free space on the volumes, pauses or resumes syncing, and raises or clears the
+    /// alarm.
+    ///
+    /// Returns whether syncing is paused.
+    pub fn check(&mut self) -> bool {
+        let now = Instant::now();
+        let window = self.alarm.window;
+        self.disks.refresh_list();
+
+        let mut volumes = HashMap::<PathBuf, Volume>::new();
+        let mut growth = HashMap::new();
+        for (class, dir) in &self.dirs {
+            let size = dir_size(dir);
+            let rate = self.sizes.entry(*class).or_default().push(now, size, window);
+            growth.insert(*class, rate);
+            if let Some(metrics) = self.metrics.get(class) {
+                metrics.size.set(size as f64);
+                metrics.growth_rate.set(rate.unwrap_or_default());
+            }
+
+            let Some(disk) = disk_of(&self.disks, dir) else { continue };
+            volumes
+                .entry(disk.mount_point().to_path_buf())
+                .or_insert_with(|| Volume {
+                    free_space: disk.available_space(),
+                    total_space: disk.total_space(),
+                    classes: Vec::new(),
+                })
+                .classes
+                .push(*class);
+        }
+        self.free_space.retain(|mount_point, _| volumes.contains_key(mount_point));
+
+        let mut low = Vec::new();
+        let mut full_soon = Vec::new();
+        for (mount_point, volume) in &volumes {
+            let rate = self.free_space.entry(mount_point.clone()).or_default().push(
+                now,
+                volume.free_space,
+                window,
+            );
+            let time_to_full = time_to_full(volume.free_space, rate);
+            for class in &volume.classes {
+                if let Some(metrics) = self.metrics.get(class) {
+                    metrics.free_space.set(volume.free_space as f64);
+                    metrics.total_space.set(volume.total_space as f64);
+                    metrics
+                        .time_to_full
+                        .set(time_to_full.map_or(f64::INFINITY, |time| time.as_secs_f64()));
+                }
+            }
+
+            if volume.free_space < self.min_free_space {
+                low.push((mount_point, volume));
+            }
+            if let Some(time_to_full) =
+                time_to_full.filter(|time_to_full| *time_to_full < self.alarm.min_time_to_full)
+            {
+                full_soon.push((mount_point, volume, time_to_full));
+            }
+        }
+
+        let paused = !low.is_empty();
+        let was_paused = self.paused.send_replace(paused);
+        self.monitor_metrics.sync_paused.set(paused as u8 as f64);
+
+        if paused && !was_paused {
+            for (mount_point, volume) in low {
+                warn!(
+                    target: "reth::cli",
+                    volume = ?mount_point,
+                    data_classes = ?volume.classes,
+                    free_space = volume.free_space,
+                    min_free_space = self.min_free_space,
+                    "Volume is low on free space, pausing sync"
+                );
+            }
+        } else if !paused && was_paused {
+            info!(target: "reth::cli", "Volumes have enough free space again, resuming sync");
+        }
+
+        let alarm = !full_soon.is_empty();
+        self.monitor_metrics.disk_alarm.set(alarm as u8 as f64);
+
+        if alarm && !self.alarm_raised {
+            for (mount_point, volume, time_to_full) in full_soon {
+                // bytes per hour, as measured over the window
+                let growth_per_hour = volume
+                    .classes
+                    .iter()
+                    .map(|class| {
+                        let rate = growth.get(class).copied().flatten().unwrap_or_default();
+                        (*class, (rate * 3600.0) as i64)
+                    })
+                    .collect::<Vec<_>>();
+                warn!(
+                    target: "reth::cli",
+                    volume = ?mount_point,
+                    free_space = volume.free_space,
+                    ?time_to_full,
+                    ?growth_per_hour,
+                    "Volume is projected to run out of free space"
+                );
+            }
+            if let (Some(handle), Some(distance)) =
+                (&self.prune_distance, self.alarm.auto_prune_distance)
+            {
+                warn!(target: "reth::cli", distance, "Tightening pruning until the volumes have enough free space");
+                handle.set(Some(distance));
+            }
+        } else if !alarm && self.alarm_raised {
+            info!(target: "reth::cli", "Volumes are no longer projected to run out of free space");
+            if let Some(handle) = &self.prune_distance {
+                handle.set(None);
+            }
+        }
+        self.alarm_raised = alarm;
+
+        paused
+    }
+}
+
+impl fmt::Debug for VolumeMonitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VolumeMonitor")
+            .field("dirs", &self.dirs)
+            .field("min_free_space", &self.min_free_space)
+            .field("interval", &self.interval)
+            .field("paused", &*self.paused.borrow())
+            .field("alarm", &self.alarm)
+            .field("alarm_raised", &self.alarm_raised)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A volume that data classes are stored on.
+#[derive(Debug)]
+struct Volume {
+    /// The free space in bytes.
+    free_space: u64,
+    /// The total space in bytes.
+    total_space: u64,
+    /// The data classes stored on the volume.
+    classes: Vec<DataClass>,
+}
+
+/// Samples of a value within a window, to measure its rate of change.
+#[derive(Debug, Default)]
+struct Samples(VecDeque<(Instant, u64)>);
+
+impl Samples {
+    /// Adds a sample, drops the samples that are older than the window, and returns the rate of
+    /// change per second over the window.
+    ///
+    /// The rate is only returned once the samples span at least a quarter of the window, so it
+    /// isn't skewed by short bursts.
+    fn push(&mut self, now: Instant, value: u64, window: Duration) -> Option<f64> {
+        self.0.push_back((now, value));
+        while self.0.front().is_some_and(|(at, _)| now.duration_since(*at) > window) {
+            self.0.pop_front();
+        }
+
+        let (first_at, first) = self.0.front()?;
+        let elapsed = now.duration_since(*first_at);
+        (!elapsed.is_zero() && elapsed >= window / 4)
+            .then(|| (value as f64 - *first as f64) / elapsed.as_secs_f64())
+    }
+}
+
+/// Returns the time until the free space is used up, if it's shrinking at the given rate per
+/// second.
+fn time_to_full(free_space: u64, rate: Option<f64>) -> Option<Duration> {
+    let shrinking = -rate?;
+    (shrinking > 0.0).then(|| Duration::from_secs_f64(free_space as f64 / shrinking))
+}
+
+/// Returns the total size of the files in the directory and its subdirectories.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else { return 0 };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Returns the disk the path is stored on, i.e. the disk with the longest mount point the path
+/// starts with.
+///
+/// The path doesn't need to exist yet, then the disk of its closest existing ancestor is returned.
+fn disk_of<'a>(disks: &'a Disks, path: &Path) -> Option<&'a Disk> {
+    let path = path.ancestors().find_map(|ancestor| ancestor.canonicalize().ok())?;
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+}
+
+/// Metrics of the volume a data class is stored on.
+#[derive(Metrics)]
+#[metrics(scope = "volumes")]
+struct VolumeMetrics {
+    /// The size of the data class in bytes
+    size: Gauge,
+    /// The growth of the data class in bytes per second, measured over the window of the disk
+    /// alarm
+    growth_rate: Gauge,
+    /// The free space on the volume in bytes
+    free_space: Gauge,
+    /// The total space of the volume in bytes
+    total_space: Gauge,
+    /// The projected time in seconds until the volume is full, infinite if its free space isn't
+    /// shrinking
+    time_to_full: Gauge,
+}
+
+/// Metrics of the [`VolumeMonitor`].
+#[derive(Metrics)]
+#[metrics(scope = "volumes")]
+struct VolumeMonitorMetrics {
+    /// Whether syncing is paused because a volume is low on free space
+    sync_paused: Gauge,
+    /// Whether a volume is projected to run out of free space soon
+    disk_alarm: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pauses_below_min_free_space() {
+        let dir = tempfile::tempdir().unwrap();
+        let dirs = [
+            (DataClass::Database, dir.path().join("db")),
+            (DataClass::Etl, dir.path().join("etl-tmp")),
+        ];
+
+        let mut monitor = VolumeMonitor::new(dirs.clone(), 0);
+        let paused = monitor.pause_receiver();
+        assert!(!monitor.check());
+        assert!(!*paused.borrow());
+
+        // no volume has this much free space
+        let mut monitor = VolumeMonitor::new(dirs, u64::MAX);
+        let paused = monitor.pause_receiver();
+        if disk_of(&Disks::new_with_refreshed_list(), dir.path()).is_none() {
+            // the file system of the temp dir isn't listed, e.g. in some containers
+            return
+        }
+        assert!(monitor.check());
+        assert!(*paused.borrow());
+
+        monitor.min_free_space = 0;
+        assert!(!monitor.check());
+        assert!(!*paused.borrow());
+    }
+
+    #[test]
+    fn samples_rate() {
+        let window = Duration::from_secs(100);
+        let start = Instant::now();
+        let mut samples = Samples::default();
+
+        assert_eq!(samples.push(start, 1000, window), None);
+        // less than a quarter of the window
+        assert_eq!(samples.push(start + Duration::from_secs(10), 900, window), None);
+        assert_eq!(samples.push(start + Duration::from_secs(50), 500, window), Some(-10.0));
+        // the first samples are out of the window
+        assert_eq!(samples.push(start + Duration::from_secs(150), 700, window), Some(2.0));
+        assert_eq!(samples.0.len(), 2);
+    }
+
+    #[test]
+    fn projected_time_to_full() {
+        assert_eq!(time_to_full(1000, None), None);
+        assert_eq!(time_to_full(1000, Some(0.0)), None);
+        assert_eq!(time_to_full(1000, Some(5.0)), None);
+        assert_eq!(time_to_full(1000, Some(-10.0)), Some(Duration::from_secs(100)));
+    }
+
+    #[test]
+    fn alarm_tightens_pruning() {
+        let dir = tempfile::tempdir().unwrap();
+        let prune_distance = PruneDistanceHandle::default();
+        let mut monitor = VolumeMonitor::new([(DataClass::Database, dir.path().to_path_buf())], 0)
+            .with_alarm(DiskAlarmConfig {
+                window: Duration::from_secs(100),
+                min_time_to_full: Duration::from_secs(3600),
+                auto_prune_distance: Some(10_064),
+            })
+            .with_prune_distance(prune_distance.clone());
+        let disks = Disks::new_with_refreshed_list();
+        let Some(disk) = disk_of(&disks, dir.path()) else {
+            // the file system of the temp dir isn't listed, e.g. in some containers
+            return
+        };
+        let mount_point = disk.mount_point().to_path_buf();
+
+        // the free space shrank by the whole disk within the window
+        let now = Instant::now();
+        monitor.free_space.insert(
+            mount_point.clone(),
+            Samples(VecDeque::from([(now - Duration::from_secs(50), u64::MAX)])),
+        );
+        monitor.check();
+        assert!(monitor.alarm_raised);
+        assert_eq!(prune_distance.get(), Some(10_064));
+
+        // the free space is stable
+        monitor.free_space.insert(mount_point, Samples::default());
+        monitor.check();
+        assert!(!monitor.alarm_raised);
+        assert_eq!(prune_distance.get(), None);
+    }
+}
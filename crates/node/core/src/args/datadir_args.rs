@@ -5,8 +5,12 @@ use clap::Args;
 use reth_chainspec::Chain;
 use std::path::PathBuf;
 
+/// Default minimum free space in megabytes on the volumes of the data dir, see
+/// [`DatadirArgs::min_free_space_mb`].
+pub const DEFAULT_MIN_FREE_SPACE_MB: u64 = 1024;
+
 /// Parameters for datadir configuration
-#[derive(Debug, Args, PartialEq, Eq, Default, Clone)]
+#[derive(Debug, Args, PartialEq, Eq, Clone)]
 #[command(next_help_heading = "Datadir")]
 pub struct DatadirArgs {
     /// The path to the data dir for all reth files and subdirectories.
@@ -22,9 +26,53 @@ pub struct DatadirArgs {
     /// The absolute path to store static files in.
     #[arg(long = "datadir.static_files", verbatim_doc_comment, value_name = "PATH")]
     pub static_files_path: Option<PathBuf>,
+
+    /// The absolute path to store the database in.
+    #[arg(long = "datadir.db", verbatim_doc_comment, value_name = "PATH")]
+    pub db_path: Option<PathBuf>,
+
+    /// The absolute path to store the blobs of unfinalized transactions in.
+    #[arg(long = "datadir.blobstore", verbatim_doc_comment, value_name = "PATH")]
+    pub blobstore_path: Option<PathBuf>,
+
+    /// The absolute path to store temporary ETL files in during sync.
+    ///
+    /// Takes precedence over `stages.etl.dir` of the config file.
+    #[arg(long = "datadir.etl", verbatim_doc_comment, value_name = "PATH")]
+    pub etl_path: Option<PathBuf>,
+
+    /// The minimum free space in megabytes on each volume of the data dir.
+    ///
+    /// Syncing is paused while a volume has less free space, and resumed once space is freed.
+    /// Set to 0 to disable.
+    #[arg(
+        long = "datadir.min-free-space",
+        verbatim_doc_comment,
+        value_name = "MB",
+        default_value_t = DEFAULT_MIN_FREE_SPACE_MB
+    )]
+    pub min_free_space_mb: u64,
+}
+
+impl Default for DatadirArgs {
+    fn default() -> Self {
+        Self {
+            datadir: Default::default(),
+            static_files_path: None,
+            db_path: None,
+            blobstore_path: None,
+            etl_path: None,
+            min_free_space_mb: DEFAULT_MIN_FREE_SPACE_MB,
+        }
+    }
 }
 
 impl DatadirArgs {
+    /// Returns the minimum free space in bytes on each volume of the data dir.
+    pub const fn min_free_space(&self) -> u64 {
+        self.min_free_space_mb * 1024 * 1024
+    }
+
     /// Resolves the final datadir path.
     pub fn resolve_datadir(self, chain: Chain) -> ChainPath<DataDirPath> {
         let datadir = self.datadir.clone();
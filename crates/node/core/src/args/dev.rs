@@ -1,18 +1,25 @@
 //! clap [Args](clap::Args) for Dev testnet configuration
 
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use clap::Args;
 use humantime::parse_duration;
+use reth_primitives::BlockNumber;
+use reth_rpc_eth_types::DevSignerConfig;
+use reth_rpc_server_types::constants::{DEFAULT_DEV_ACCOUNTS, DEFAULT_DEV_MNEMONIC};
+use url::Url;
 
 /// Parameters for Dev testnet configuration
-#[derive(Debug, Args, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Debug, Args, PartialEq, Eq, Clone)]
 #[command(next_help_heading = "Dev testnet")]
 pub struct DevArgs {
     /// Start the node in dev mode
     ///
-    /// This mode uses a local proof-of-authority consensus engine with either fixed block times
-    /// or automatically mined blocks.
+    /// This mode uses a local proof-of-authority consensus engine with either fixed block times,
+    /// automatically mined blocks or blocks that are only mined on request.
+    /// Blocks can always be mined with `evm_mine`, and their timestamps controlled with
+    /// `evm_increaseTime` and `evm_setNextBlockTimestamp`.
+    /// With `--fork-url`, the chain is started on top of the state of another network.
     /// Disables network discovery and enables local http server.
     /// Prefunds 20 accounts derived by mnemonic "test test test test test test test test test test
     /// test junk" with 10 000 ETH each.
@@ -23,7 +30,7 @@ pub struct DevArgs {
     #[arg(
         long = "dev.block-max-transactions",
         help_heading = "Dev testnet",
-        conflicts_with = "block_time"
+        conflicts_with_all = ["block_time", "manual_mining"]
     )]
     pub block_max_transactions: Option<usize>,
 
@@ -34,11 +41,87 @@ pub struct DevArgs {
     #[arg(
         long = "dev.block-time",
         help_heading = "Dev testnet",
-        conflicts_with = "block_max_transactions",
+        conflicts_with_all = ["block_max_transactions", "manual_mining"],
         value_parser = parse_duration,
         verbatim_doc_comment
     )]
     pub block_time: Option<Duration>,
+
+    /// Only mine blocks on request, with `evm_mine`.
+    #[arg(long = "dev.manual-mining", help_heading = "Dev testnet")]
+    pub manual_mining: bool,
+
+    /// Mnemonic the accounts of the dev signer are derived from.
+    ///
+    /// The dev signer backs `eth_accounts`, `eth_sign`, `eth_signTransaction` and
+    /// `eth_sendTransaction` in dev mode.
+    #[arg(
+        long = "dev.mnemonic",
+        help_heading = "Dev testnet",
+        value_name = "PHRASE",
+        default_value = DEFAULT_DEV_MNEMONIC
+    )]
+    pub mnemonic: String,
+
+    /// Number of dev signer accounts derived from the mnemonic.
+    #[arg(
+        long = "dev.accounts",
+        help_heading = "Dev testnet",
+        value_name = "COUNT",
+        default_value_t = DEFAULT_DEV_ACCOUNTS
+    )]
+    pub accounts: u32,
+
+    /// File with additional hex encoded private keys of dev signer accounts, one per line.
+    ///
+    /// Can be specified multiple times.
+    #[arg(long = "dev.key-file", help_heading = "Dev testnet", value_name = "PATH")]
+    pub key_files: Vec<PathBuf>,
+
+    /// RPC endpoint of a network to fork.
+    ///
+    /// Accounts and storage that the dev chain hasn't written are read from the forked network at
+    /// the fork block, and cached in the datadir. The datadir must be new when forking, and is
+    /// bound to the fork block afterwards.
+    #[arg(long = "fork-url", help_heading = "Dev testnet", value_name = "URL", requires = "dev")]
+    pub fork_url: Option<Url>,
+
+    /// Block of the forked network to fork at. Defaults to its latest block.
+    #[arg(
+        long = "fork-block-number",
+        help_heading = "Dev testnet",
+        value_name = "NUMBER",
+        requires = "fork_url"
+    )]
+    pub fork_block_number: Option<BlockNumber>,
+}
+
+impl DevArgs {
+    /// Returns the accounts of the dev signer.
+    pub fn signer_config(&self) -> DevSignerConfig {
+        DevSignerConfig {
+            mnemonic: self.mnemonic.clone(),
+            accounts: self.accounts,
+            key_files: self.key_files.clone(),
+        }
+    }
+}
+
+impl Default for DevArgs {
+    fn default() -> Self {
+        let DevSignerConfig { mnemonic, accounts, key_files } = DevSignerConfig::default();
+        Self {
+            dev: false,
+            block_max_transactions: None,
+            block_time: None,
+            manual_mining: false,
+            mnemonic,
+            accounts,
+            key_files,
+            fork_url: None,
+            fork_block_number: None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -56,13 +139,13 @@ mod tests {
     #[test]
     fn test_parse_dev_args() {
         let args = CommandParser::<DevArgs>::parse_from(["reth"]).args;
-        assert_eq!(args, DevArgs { dev: false, block_max_transactions: None, block_time: None });
+        assert_eq!(args, DevArgs::default());
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--dev"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(args, DevArgs { dev: true, ..Default::default() });
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--auto-mine"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(args, DevArgs { dev: true, ..Default::default() });
 
         let args = CommandParser::<DevArgs>::parse_from([
             "reth",
@@ -71,7 +154,10 @@ mod tests {
             "2",
         ])
         .args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: Some(2), block_time: None });
+        assert_eq!(
+            args,
+            DevArgs { dev: true, block_max_transactions: Some(2), ..Default::default() }
+        );
 
         let args =
             CommandParser::<DevArgs>::parse_from(["reth", "--dev", "--dev.block-time", "1s"]).args;
@@ -79,8 +165,31 @@ mod tests {
             args,
             DevArgs {
                 dev: true,
-                block_max_transactions: None,
-                block_time: Some(std::time::Duration::from_secs(1))
+                block_time: Some(std::time::Duration::from_secs(1)),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_dev_signer_args() {
+        let args = CommandParser::<DevArgs>::parse_from([
+            "reth",
+            "--dev",
+            "--dev.accounts",
+            "5",
+            "--dev.key-file",
+            "a.keys",
+            "--dev.key-file",
+            "b.keys",
+        ])
+        .args;
+        assert_eq!(
+            args.signer_config(),
+            DevSignerConfig {
+                mnemonic: DEFAULT_DEV_MNEMONIC.to_string(),
+                accounts: 5,
+                key_files: vec![PathBuf::from("a.keys"), PathBuf::from("b.keys")],
             }
         );
     }
@@ -96,6 +205,52 @@ mod tests {
             "1s",
         ]);
         assert!(args.is_err());
+
+        let args = CommandParser::<DevArgs>::try_parse_from([
+            "reth",
+            "--dev",
+            "--dev.manual-mining",
+            "--dev.block-time",
+            "1s",
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_parse_dev_manual_mining() {
+        let args =
+            CommandParser::<DevArgs>::parse_from(["reth", "--dev", "--dev.manual-mining"]).args;
+        assert_eq!(args, DevArgs { dev: true, manual_mining: true, ..Default::default() });
+    }
+
+    #[test]
+    fn test_parse_fork_args() {
+        let args = CommandParser::<DevArgs>::parse_from([
+            "reth",
+            "--dev",
+            "--fork-url",
+            "http://localhost:8545",
+            "--fork-block-number",
+            "100",
+        ])
+        .args;
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: true,
+                fork_url: Some("http://localhost:8545".parse().unwrap()),
+                fork_block_number: Some(100),
+                ..Default::default()
+            }
+        );
+
+        let args =
+            CommandParser::<DevArgs>::try_parse_from(["reth", "--fork-url", "http://localhost"]);
+        assert!(args.is_err());
+
+        let args =
+            CommandParser::<DevArgs>::try_parse_from(["reth", "--dev", "--fork-block-number", "1"]);
+        assert!(args.is_err());
     }
 
     #[test]
@@ -2,7 +2,7 @@
 
 /// NetworkArg struct for configuring the network
 mod network;
-pub use network::{DiscoveryArgs, NetworkArgs};
+pub use network::{DiscoveryArgs, NetworkArgs, DEFAULT_TRUSTED_PEERS_REFRESH_INTERVAL};
 
 /// RpcServerArg struct for configuring the RPC
 mod rpc_server;
@@ -48,9 +48,13 @@ pub use dev::DevArgs;
 mod pruning;
 pub use pruning::PruningArgs;
 
+/// SnapshotArgs for bootstrapping the node from a snapshot
+mod snapshot;
+pub use snapshot::SnapshotArgs;
+
 /// DatadirArgs for configuring data storage paths
 mod datadir_args;
-pub use datadir_args::DatadirArgs;
+pub use datadir_args::{DatadirArgs, DEFAULT_MIN_FREE_SPACE_MB};
 
 /// BenchmarkArgs struct for configuring the benchmark to run
 mod benchmark_args;
@@ -5,11 +5,12 @@ use std::{
     ops::Not,
     path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 
 use clap::Args;
 use reth_chainspec::ChainSpec;
-use reth_config::Config;
+use reth_config::{Config, DiscoveryConfig};
 use reth_discv4::{NodeRecord, DEFAULT_DISCOVERY_ADDR, DEFAULT_DISCOVERY_PORT};
 use reth_discv5::{
     discv5::ListenConfig, DEFAULT_COUNT_BOOTSTRAP_LOOKUPS, DEFAULT_DISCOVERY_V5_PORT,
@@ -24,20 +25,25 @@ use reth_network::{
                 DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
             },
             tx_manager::{
-                DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
+                DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS, DEFAULT_MAX_COUNT_TRACED_TRANSACTIONS,
+                DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             },
         },
         TransactionFetcherConfig, TransactionsManagerConfig,
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
-    HelloMessageWithProtocols, NetworkConfigBuilder, SessionsConfig,
+    DiscoveryRateLimits, HelloMessageWithProtocols, NetworkConfigBuilder, SessionsConfig,
 };
 use reth_network_peers::{mainnet_nodes, TrustedPeer};
 use secp256k1::SecretKey;
+use url::Url;
 
 use crate::version::P2P_CLIENT_VERSION;
 
+/// Default interval in which trusted peers given by DNS name are re-resolved.
+pub const DEFAULT_TRUSTED_PEERS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Parameters for configuring the network more granularity via CLI
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "Networking")]
@@ -53,6 +59,18 @@ pub struct NetworkArgs {
     #[arg(long, value_delimiter = ',')]
     pub trusted_peers: Vec<TrustedPeer>,
 
+    /// URL of a list of trusted peers, one enode URL per line.
+    ///
+    /// The list is re-fetched periodically, peers that are removed from the list are no longer
+    /// trusted.
+    #[arg(long = "trusted-peers.url", value_name = "URL")]
+    pub trusted_peers_url: Option<Url>,
+
+    /// Interval in which trusted peers given by DNS name are re-resolved and the list of
+    /// `--trusted-peers.url` is re-fetched.
+    #[arg(long = "trusted-peers.refresh-interval", value_name = "DURATION", value_parser = humantime::parse_duration, default_value = "60s")]
+    pub trusted_peers_refresh_interval: Duration,
+
     /// Connect to or accept from trusted peers only
     #[arg(long)]
     pub trusted_only: bool,
@@ -121,6 +139,26 @@ pub struct NetworkArgs {
     #[arg(long = "max-seen-tx-history", value_name = "COUNT", default_value_t = DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER, verbatim_doc_comment)]
     pub max_seen_tx_history: u32,
 
+    /// Interval at which local pending transactions are re-announced to all peers, e.g. `30s`.
+    ///
+    /// Local transactions are only announced once if not set.
+    #[arg(long = "local-tx-rebroadcast-interval", value_name = "DURATION", value_parser = humantime::parse_duration)]
+    pub local_tx_rebroadcast_interval: Option<Duration>,
+
+    /// Max number of recently seen transactions to trace the propagation of, see
+    /// `reth_traceTransactionPropagation`.
+    ///
+    /// Tracing is disabled if set to 0.
+    #[arg(long = "tx-propagation-history", value_name = "COUNT", default_value_t = DEFAULT_MAX_COUNT_TRACED_TRANSACTIONS)]
+    pub tx_propagation_history: u32,
+
+    /// Records propagation delays as metrics for one in every COUNT transactions.
+    ///
+    /// Transactions are sampled by hash, so all nodes sample the same transactions. No metrics
+    /// are recorded if not set.
+    #[arg(long = "tx-propagation-sample", value_name = "COUNT", value_parser = clap::value_parser!(u32).range(1..))]
+    pub tx_propagation_sample: Option<u32>,
+
     #[arg(long = "max-pending-imports", value_name = "COUNT", default_value_t = DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS, verbatim_doc_comment)]
     /// Max number of transactions to import concurrently.
     pub max_pending_pool_imports: usize,
@@ -144,6 +182,16 @@ pub struct NetworkArgs {
     /// Default is 128 KiB.
     #[arg(long = "pooled-tx-pack-soft-limit", value_name = "BYTES", default_value_t = DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ, verbatim_doc_comment)]
     pub soft_limit_byte_size_pooled_transactions_response_on_pack_request: usize,
+
+    /// Offer the `eth/69` protocol to peers, falling back to `eth/68` for peers that don't
+    /// support it.
+    #[arg(long)]
+    pub eth69: bool,
+
+    /// Serve the network to other nodes over gRPC on this address, so that they can download
+    /// blocks through this node as sentry.
+    #[arg(long = "sentry.addr", value_name = "ADDR")]
+    pub sentry_addr: Option<SocketAddr>,
 }
 
 impl NetworkArgs {
@@ -193,6 +241,11 @@ impl NetworkArgs {
                 self.soft_limit_byte_size_pooled_transactions_response_on_pack_request,
             ),
             max_transactions_seen_by_peer_history: self.max_seen_tx_history,
+            local_transactions_rebroadcast_interval: self.local_tx_rebroadcast_interval,
+            max_traced_transactions: self.tx_propagation_history,
+            propagation_metrics_sample_ratio: self
+                .tx_propagation_sample
+                .map_or(0.0, |count| 1.0 / count as f64),
         };
 
         // Configure basic network stack
@@ -202,12 +255,15 @@ impl NetworkArgs {
             ))
             .external_ip_resolver(self.nat)
             .sessions_config(
-                SessionsConfig::default().with_upscaled_event_buffer(peers_config.max_peers()),
+                SessionsConfig::default()
+                    .with_upscaled_event_buffer(peers_config.max_peers())
+                    .with_bandwidth_limits(config.sessions.bandwidth),
             )
             .peer_config(peers_config)
             .boot_nodes(chain_bootnodes.clone())
             .chain_spec(chain_spec)
             .transactions_manager_config(transactions_manager_config)
+            .eth69(self.eth69)
             // Configure node identity
             .apply(|builder| {
                 let peer_id = builder.get_peer_id();
@@ -220,7 +276,12 @@ impl NetworkArgs {
             // apply discovery settings
             .apply(|builder| {
                 let rlpx_socket = (self.addr, self.port).into();
-                self.discovery.apply_to_builder(builder, rlpx_socket, chain_bootnodes)
+                self.discovery.apply_to_builder_with_config(
+                    builder,
+                    &config.discovery,
+                    rlpx_socket,
+                    chain_bootnodes,
+                )
             })
             .listener_addr(SocketAddr::new(
                 self.addr, // set discovery port based on instance number
@@ -278,6 +339,8 @@ impl Default for NetworkArgs {
         Self {
             discovery: DiscoveryArgs::default(),
             trusted_peers: vec![],
+            trusted_peers_url: None,
+            trusted_peers_refresh_interval: DEFAULT_TRUSTED_PEERS_REFRESH_INTERVAL,
             trusted_only: false,
             bootnodes: None,
             dns_retries: 0,
@@ -295,8 +358,13 @@ impl Default for NetworkArgs {
             soft_limit_byte_size_pooled_transactions_response:
                 SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
             soft_limit_byte_size_pooled_transactions_response_on_pack_request: DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
+            eth69: false,
+            sentry_addr: None,
             max_pending_pool_imports: DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS,
             max_seen_tx_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
+            local_tx_rebroadcast_interval: None,
+            tx_propagation_history: DEFAULT_MAX_COUNT_TRACED_TRANSACTIONS,
+            tx_propagation_sample: None,
         }
     }
 }
@@ -370,8 +438,28 @@ pub struct DiscoveryArgs {
 impl DiscoveryArgs {
     /// Apply the discovery settings to the given [`NetworkConfigBuilder`]
     pub fn apply_to_builder(
+        &self,
+        network_config_builder: NetworkConfigBuilder,
+        rlpx_tcp_socket: SocketAddr,
+        boot_nodes: impl IntoIterator<Item = NodeRecord>,
+    ) -> NetworkConfigBuilder {
+        self.apply_to_builder_with_config(
+            network_config_builder,
+            &DiscoveryConfig::default(),
+            rlpx_tcp_socket,
+            boot_nodes,
+        )
+    }
+
+    /// Apply the discovery settings to the given [`NetworkConfigBuilder`], merged with the
+    /// `[discovery]` section of the config file.
+    ///
+    /// Discv4 is disabled if either the arguments or the config disable it, discv5 is enabled if
+    /// either of them enables it.
+    pub fn apply_to_builder_with_config(
         &self,
         mut network_config_builder: NetworkConfigBuilder,
+        config: &DiscoveryConfig,
         rlpx_tcp_socket: SocketAddr,
         boot_nodes: impl IntoIterator<Item = NodeRecord>,
     ) -> NetworkConfigBuilder {
@@ -379,16 +467,21 @@ impl DiscoveryArgs {
             network_config_builder = network_config_builder.disable_dns_discovery();
         }
 
-        if self.disable_discovery || self.disable_discv4_discovery {
+        if self.disable_discovery || self.disable_discv4_discovery || !config.v4.enabled {
             network_config_builder = network_config_builder.disable_discv4_discovery();
         }
 
-        if !self.disable_discovery && self.enable_discv5_discovery {
-            network_config_builder = network_config_builder
-                .discovery_v5(self.discovery_v5_builder(rlpx_tcp_socket, boot_nodes));
+        if !self.disable_discovery && (self.enable_discv5_discovery || config.v5.enabled) {
+            network_config_builder = network_config_builder.discovery_v5(
+                self.discovery_v5_builder(rlpx_tcp_socket, boot_nodes)
+                    .require_fork_id(config.v5.require_fork_id),
+            );
         }
 
-        network_config_builder
+        network_config_builder.discovery_rate_limits(DiscoveryRateLimits {
+            discv4: config.v4.max_peers_per_second,
+            discv5: config.v5.max_peers_per_second,
+        })
     }
 
     /// Creates a [`reth_discv5::ConfigBuilder`] filling it with the values from this struct.
@@ -531,6 +624,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_trusted_peers_url_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--trusted-peers.url",
+            "https://example.com/peers.txt",
+            "--trusted-peers.refresh-interval",
+            "5m",
+        ])
+        .args;
+        assert_eq!(args.trusted_peers_url, Some("https://example.com/peers.txt".parse().unwrap()));
+        assert_eq!(args.trusted_peers_refresh_interval, Duration::from_secs(300));
+    }
+
     #[test]
     fn parse_retry_strategy_args() {
         let tests = vec![0, 10];
@@ -547,6 +654,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn discovery_config() {
+        let mut config = Config::default();
+        config.discovery.v4.enabled = false;
+        config.discovery.v5.enabled = true;
+        config.discovery.v5.max_peers_per_second = Some(10);
+
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth"]).args;
+        let network_config = args
+            .network_config(
+                &config,
+                reth_chainspec::MAINNET.clone(),
+                SecretKey::from_slice(&[1; 32]).unwrap(),
+                PathBuf::from("known-peers.json"),
+            )
+            .build_with_noop_provider();
+
+        assert!(network_config.discovery_v4_config.is_none());
+        assert!(network_config.discovery_v5_config.is_some());
+        assert_eq!(
+            network_config.discovery_rate_limits,
+            DiscoveryRateLimits { discv4: None, discv5: Some(10) }
+        );
+
+        // discv5 is enabled by either the config or the arguments
+        config.discovery.v5.enabled = false;
+        let args =
+            CommandParser::<NetworkArgs>::parse_from(["reth", "--enable-discv5-discovery"]).args;
+        let network_config = args
+            .network_config(
+                &config,
+                reth_chainspec::MAINNET.clone(),
+                SecretKey::from_slice(&[1; 32]).unwrap(),
+                PathBuf::from("known-peers.json"),
+            )
+            .build_with_noop_provider();
+        assert!(network_config.discovery_v5_config.is_some());
+    }
+
     #[cfg(not(feature = "optimism"))]
     #[test]
     fn network_args_default_sanity_test() {
@@ -5,9 +5,11 @@ use clap::{
 };
 use reth_cli_util::{parse_duration_from_secs, parse_duration_from_secs_or_ms};
 use reth_primitives::constants::{
-    ETHEREUM_BLOCK_GAS_LIMIT, MAXIMUM_EXTRA_DATA_SIZE, SLOT_DURATION,
+    eip4844::MAX_BLOBS_PER_BLOCK, ETHEREUM_BLOCK_GAS_LIMIT, MAXIMUM_EXTRA_DATA_SIZE, SLOT_DURATION,
 };
+use reth_rpc_types::beacon::BlsPublicKey;
 use std::{borrow::Cow, ffi::OsStr, time::Duration};
+use url::Url;
 
 /// Parameters for configuring the Payload Builder
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
@@ -36,6 +38,65 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// Gas of each built block that is reserved for transactions submitted to this node.
+    ///
+    /// Transactions received from the network are only included as long as this much gas
+    /// remains available for local transactions.
+    #[arg(long = "builder.local-gas-reserve", default_value = "0", value_name = "GAS")]
+    pub local_gas_reserve: u64,
+
+    /// Maximum number of blobs included in built blocks.
+    #[arg(long = "builder.max-blobs", value_name = "COUNT", value_parser = RangedU64ValueParser::<u64>::new().range(0..=MAX_BLOBS_PER_BLOCK as u64))]
+    pub max_blobs: Option<u64>,
+
+    /// Number of blobs built blocks should contain.
+    ///
+    /// While the best block built so far contains fewer blobs, a new block is built as soon as new
+    /// blob transactions arrive, instead of waiting for the next `--builder.interval`.
+    #[arg(long = "builder.blob-target", value_name = "COUNT", value_parser = RangedU64ValueParser::<u64>::new().range(1..=MAX_BLOBS_PER_BLOCK as u64))]
+    pub blob_target: Option<u64>,
+
+    /// Minimum priority fee per gas (in wei) of blob transactions included in built blocks.
+    #[arg(long = "builder.min-blob-tip", default_value = "0", value_name = "WEI")]
+    pub min_blob_priority_fee: u128,
+
+    /// Minimum fee improvement (in basis points) a newly built payload must have over the best
+    /// payload so far to replace it.
+    #[arg(long = "builder.min-improvement-bps", default_value = "0", value_name = "BPS")]
+    pub min_improvement_bps: u64,
+
+    /// Total time a payload job may spend building payloads.
+    ///
+    /// The payload is improved until the deadline, but no new build attempts are started once
+    /// this much time was spent building. Specified in seconds or in milliseconds if the value
+    /// ends with `ms`.
+    #[arg(long = "builder.build-time-budget", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION")]
+    pub build_time_budget: Option<Duration>,
+
+    /// URL of an external block builder to race local payloads against.
+    ///
+    /// For every build attempt, the best block submission of the builder for the payload's parent
+    /// is fetched and served instead of the local payload if it pays more.
+    #[arg(long = "builder.relay-url", value_name = "URL")]
+    pub relay_url: Option<Url>,
+
+    /// Comma separated BLS public keys of external builders whose payloads are accepted.
+    ///
+    /// If empty, payloads of any builder are accepted.
+    #[arg(long = "builder.relay-trusted-builders", value_delimiter = ',', value_name = "PUBKEYS")]
+    pub relay_trusted_builders: Vec<BlsPublicKey>,
+
+    /// Minimum value advantage (in basis points) an external payload must have over the local
+    /// payload to be served.
+    #[arg(long = "builder.relay-min-advantage-bps", default_value = "0", value_name = "BPS")]
+    pub relay_min_advantage_bps: u64,
+
+    /// How long to wait for the external builder once the local payload was built.
+    ///
+    /// Specified in seconds or in milliseconds if the value ends with `ms`.
+    #[arg(long = "builder.relay-timeout", value_parser = parse_duration_from_secs_or_ms, default_value = "500ms", value_name = "DURATION")]
+    pub relay_timeout: Duration,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -46,6 +107,16 @@ impl Default for PayloadBuilderArgs {
             interval: Duration::from_secs(1),
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            local_gas_reserve: 0,
+            max_blobs: None,
+            blob_target: None,
+            min_blob_priority_fee: 0,
+            min_improvement_bps: 0,
+            build_time_budget: None,
+            relay_url: None,
+            relay_trusted_builders: Vec::new(),
+            relay_min_advantage_bps: 0,
+            relay_timeout: Duration::from_millis(500),
         }
     }
 }
@@ -70,6 +141,46 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
     fn max_payload_tasks(&self) -> usize {
         self.max_payload_tasks
     }
+
+    fn local_gas_reserve(&self) -> u64 {
+        self.local_gas_reserve
+    }
+
+    fn max_blobs(&self) -> Option<u64> {
+        self.max_blobs
+    }
+
+    fn blob_target(&self) -> Option<u64> {
+        self.blob_target
+    }
+
+    fn min_blob_priority_fee(&self) -> u128 {
+        self.min_blob_priority_fee
+    }
+
+    fn min_improvement_bps(&self) -> u64 {
+        self.min_improvement_bps
+    }
+
+    fn build_time_budget(&self) -> Option<Duration> {
+        self.build_time_budget
+    }
+
+    fn relay_url(&self) -> Option<&Url> {
+        self.relay_url.as_ref()
+    }
+
+    fn relay_trusted_builders(&self) -> &[BlsPublicKey] {
+        &self.relay_trusted_builders
+    }
+
+    fn relay_min_advantage_bps(&self) -> u64 {
+        self.relay_min_advantage_bps
+    }
+
+    fn relay_timeout(&self) -> Duration {
+        self.relay_timeout
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -174,4 +285,74 @@ mod tests {
                 .args;
         assert_eq!(args.interval, Duration::from_millis(50));
     }
+
+    #[test]
+    fn test_args_with_local_gas_reserve() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.local-gas-reserve",
+            "1000000",
+        ])
+        .args;
+        assert_eq!(args.local_gas_reserve(), 1_000_000);
+    }
+
+    #[test]
+    fn test_args_with_blob_selection() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.max-blobs",
+            "4",
+            "--builder.blob-target",
+            "3",
+            "--builder.min-blob-tip",
+            "1000000000",
+        ])
+        .args;
+        assert_eq!(args.max_blobs(), Some(4));
+        assert_eq!(args.blob_target(), Some(3));
+        assert_eq!(args.min_blob_priority_fee(), 1_000_000_000);
+
+        assert!(CommandParser::<PayloadBuilderArgs>::try_parse_from([
+            "reth",
+            "--builder.max-blobs",
+            "7"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_args_with_improvement_iterations() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.min-improvement-bps",
+            "50",
+            "--builder.build-time-budget",
+            "500ms",
+        ])
+        .args;
+        assert_eq!(args.min_improvement_bps(), 50);
+        assert_eq!(args.build_time_budget(), Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_args_with_relay() {
+        let builder = BlsPublicKey::with_last_byte(1);
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.relay-url",
+            "http://localhost:18550",
+            "--builder.relay-trusted-builders",
+            &format!("{builder},{}", BlsPublicKey::with_last_byte(2)),
+            "--builder.relay-min-advantage-bps",
+            "100",
+            "--builder.relay-timeout",
+            "200ms",
+        ])
+        .args;
+        assert_eq!(args.relay_url().map(Url::as_str), Some("http://localhost:18550/"));
+        assert_eq!(args.relay_trusted_builders(), &[builder, BlsPublicKey::with_last_byte(2)]);
+        assert_eq!(args.relay_min_advantage_bps(), 100);
+        assert_eq!(args.relay_timeout(), Duration::from_millis(200));
+    }
 }
@@ -43,7 +43,10 @@ impl PruningArgs {
                         .into_iter()
                         .collect(),
                 ),
+                receipts_retention: Default::default(),
+                state_expiry: None,
             },
+            schedule: Default::default(),
         })
     }
 }
@@ -4,6 +4,7 @@ use std::{
     ffi::OsStr,
     net::{IpAddr, Ipv4Addr},
     path::PathBuf,
+    time::Duration,
 };
 
 use alloy_rpc_types_engine::JwtSecret;
@@ -11,17 +12,23 @@ use clap::{
     builder::{PossibleValue, RangedU64ValueParser, TypedValueParser},
     Arg, Args, Command,
 };
+use humantime::parse_duration;
 use rand::Rng;
-use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+use reth_rpc_server_types::{
+    constants, RethRpcModule, RpcModuleSelection, SubscriptionBackpressure,
+};
 
 use crate::args::{
-    types::{MaxU32, ZeroAsNoneU64},
+    types::{MaxU32, ZeroAsNoneU32, ZeroAsNoneU64},
     GasPriceOracleArgs, RpcStateCacheArgs,
 };
 
 /// Default max number of subscriptions per connection.
 pub(crate) const RPC_DEFAULT_MAX_SUBS_PER_CONN: u32 = 1024;
 
+/// Default number of messages buffered per connection before backpressure is applied.
+pub(crate) const RPC_DEFAULT_MESSAGE_BUFFER_CAPACITY: u32 = 1024;
+
 /// Default max request size in MB.
 pub(crate) const RPC_DEFAULT_MAX_REQUEST_SIZE_MB: u32 = 15;
 
@@ -57,6 +64,23 @@ pub struct RpcServerArgs {
     #[arg(long = "http.corsdomain")]
     pub http_corsdomain: Option<String>,
 
+    /// Maximum number of blocks the node may be behind the highest known block to be reported as
+    /// ready on the `/ready` endpoint of the http server.
+    #[arg(long = "http.ready.max-blocks-behind", value_name = "COUNT", default_value_t = constants::DEFAULT_READY_MAX_BLOCKS_BEHIND)]
+    pub http_ready_max_blocks_behind: u64,
+
+    /// Minimum number of connected peers for the node to be reported as ready on the `/ready`
+    /// endpoint of the http server.
+    #[arg(long = "http.ready.min-peers", value_name = "COUNT", default_value_t = 0)]
+    pub http_ready_min_peers: usize,
+
+    /// Maximum time since the last forkchoice update of the consensus layer for the node to be
+    /// reported as ready on the `/ready` endpoint of the http server, e.g. `60s`.
+    ///
+    /// If not set, the activity of the engine API is not checked.
+    #[arg(long = "http.ready.max-engine-idle", value_name = "DURATION", value_parser = parse_duration)]
+    pub http_ready_max_engine_idle: Option<Duration>,
+
     /// Enable the WS-RPC server
     #[arg(long)]
     pub ws: bool,
@@ -85,6 +109,12 @@ pub struct RpcServerArgs {
     #[arg(long, default_value_t = constants::DEFAULT_IPC_ENDPOINT.to_string())]
     pub ipcpath: String,
 
+    /// Unix permission mode of the IPC socket file in octal, e.g. `600`.
+    ///
+    /// If not set, the mode is derived from the umask of the process.
+    #[arg(long = "ipc.permissions", value_name = "MODE", value_parser = parse_socket_permissions)]
+    pub ipc_socket_permissions: Option<u32>,
+
     /// Auth server address to listen on
     #[arg(long = "authrpc.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
     pub auth_addr: IpAddr,
@@ -110,6 +140,19 @@ pub struct RpcServerArgs {
     #[arg(long = "auth-ipc.path", default_value_t = constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string())]
     pub auth_ipc_path: String,
 
+    /// Auth server port for a backup consensus layer client.
+    ///
+    /// Forkchoice updates and payloads received on this port are only honored once the primary
+    /// consensus layer client connected to `--authrpc.port` has been silent for
+    /// `--authrpc.failover-timeout`. Until then they are answered with `SYNCING`.
+    #[arg(long = "authrpc.backup-port", value_name = "PORT")]
+    pub auth_backup_port: Option<u16>,
+
+    /// How long the primary consensus layer client may be silent before the backup consensus
+    /// layer client is followed, see `--authrpc.backup-port`.
+    #[arg(long = "authrpc.failover-timeout", value_name = "DURATION", value_parser = parse_duration, default_value = "24s")]
+    pub auth_failover_timeout: Duration,
+
     /// Hex encoded JWT secret to authenticate the regular RPC server(s), see `--http.api` and
     /// `--ws.api`.
     ///
@@ -130,6 +173,20 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-subscriptions-per-connection", alias = "rpc-max-subscriptions-per-connection", default_value_t = RPC_DEFAULT_MAX_SUBS_PER_CONN.into())]
     pub rpc_max_subscriptions_per_connection: MaxU32,
 
+    /// Maximum number of requests in a batch for all transports. (0 = no limit)
+    #[arg(long = "rpc.max-batch-size", value_name = "COUNT", default_value_t = ZeroAsNoneU32(None))]
+    pub rpc_max_batch_size: ZeroAsNoneU32,
+
+    /// Maximum number of messages buffered per WS and IPC connection, including subscription
+    /// messages, before the server stops reading new requests until the client caught up.
+    #[arg(
+        long = "rpc.message-buffer-capacity",
+        value_name = "COUNT",
+        value_parser = RangedU64ValueParser::<u32>::new().range(1..),
+        default_value_t = RPC_DEFAULT_MESSAGE_BUFFER_CAPACITY
+    )]
+    pub rpc_message_buffer_capacity: u32,
+
     /// Maximum number of RPC server connections.
     #[arg(long = "rpc.max-connections", alias = "rpc-max-connections", value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_CONNECTIONS.into())]
     pub rpc_max_connections: MaxU32,
@@ -146,6 +203,11 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-logs-per-response", alias = "rpc-max-logs-per-response", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64))]
     pub rpc_max_logs_per_response: ZeroAsNoneU64,
 
+    /// Maximum number of chunks of a finalized block range that are scanned concurrently per
+    /// `eth_getLogs` request.
+    #[arg(long = "rpc.max-concurrent-getlogs-chunks", value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_CONCURRENT_GETLOGS_CHUNKS)]
+    pub rpc_max_concurrent_getlogs_chunks: usize,
+
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     #[arg(
         long = "rpc.gascap",
@@ -156,6 +218,11 @@ pub struct RpcServerArgs {
     )]
     pub rpc_gas_cap: u64,
 
+    /// Wall-clock timeout of the EVM execution of `eth_call` and `eth_estimateGas`, e.g. `5s`.
+    /// (0s = no timeout)
+    #[arg(long = "rpc.call-timeout", value_name = "DURATION", value_parser = parse_duration, default_value = "5s")]
+    pub rpc_call_timeout: Duration,
+
     /// The maximum proof window for historical proof generation.
     /// This value allows for generating historical proofs up to
     /// configured number of blocks from current tip (up to `tip - window`).
@@ -170,6 +237,44 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
 
+    /// Policy for `eth_subscribe` messages if a client can't keep up: `buffer:<n>`,
+    /// `drop-oldest` or `disconnect`.
+    #[arg(long = "rpc.subscription-backpressure", value_name = "POLICY", default_value_t = SubscriptionBackpressure::default())]
+    pub rpc_subscription_backpressure: SubscriptionBackpressure,
+
+    /// Maximum number of RPC methods that are tracked with their own metrics label, the metrics
+    /// of all other methods are labeled `method="other"`.
+    #[arg(long = "rpc.metrics.max-methods", value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_METRICS_METHODS)]
+    pub rpc_metrics_max_methods: usize,
+
+    /// Maximum number of error codes per RPC method that are tracked with their own metrics
+    /// label, all other error codes are labeled `code="other"`.
+    #[arg(long = "rpc.metrics.max-error-codes", value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_METRICS_ERROR_CODES)]
+    pub rpc_metrics_max_error_codes: usize,
+
+    /// Handle every request in a span with a unique correlation id, which is included in all log
+    /// events of the request.
+    #[arg(long = "rpc.correlation-ids")]
+    pub rpc_correlation_ids: bool,
+
+    /// HTTP URL of an archive node that requests for pruned history are forwarded to.
+    #[arg(long = "rpc.historical-fallback", value_name = "URL")]
+    pub rpc_historical_fallback: Option<String>,
+
+    /// Serve receipts of transactions included in the locally built pending block from
+    /// `eth_getTransactionReceipt`, tagged with `"pending": true`.
+    #[arg(long = "rpc.pending-receipts")]
+    pub rpc_pending_receipts: bool,
+
+    /// Time after which a state snapshot created by `reth_createStateSnapshot` is released,
+    /// e.g. `60s`.
+    #[arg(long = "rpc.snapshot-ttl", value_name = "DURATION", value_parser = parse_duration, default_value = "60s")]
+    pub rpc_snapshot_ttl: Duration,
+
+    /// Maximum number of state snapshots that can be alive at the same time.
+    #[arg(long = "rpc.max-snapshots", value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_STATE_SNAPSHOTS)]
+    pub rpc_max_snapshots: usize,
+
     /// State cache configuration.
     #[command(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -199,7 +304,7 @@ impl RpcServerArgs {
     }
 
     /// Change rpc port numbers based on the instance number.
-    /// * The `auth_port` is scaled by a factor of `instance * 100`
+    /// * The `auth_port` and `auth_backup_port` are scaled by a factor of `instance * 100`
     /// * The `http_port` is scaled by a factor of `-instance`
     /// * The `ws_port` is scaled by a factor of `instance * 2`
     /// * The `ipcpath` is appended with the instance number: `/tmp/reth.ipc-<instance>`
@@ -216,6 +321,9 @@ impl RpcServerArgs {
         debug_assert_ne!(instance, 0, "instance must be non-zero");
         // auth port is scaled by a factor of instance * 100
         self.auth_port += instance * 100 - 100;
+        if let Some(port) = &mut self.auth_backup_port {
+            *port += instance * 100 - 100;
+        }
         // http port is scaled by a factor of -instance
         self.http_port -= instance - 1;
         // ws port is scaled by a factor of instance * 2
@@ -279,6 +387,9 @@ impl Default for RpcServerArgs {
             http_port: constants::DEFAULT_HTTP_RPC_PORT,
             http_api: None,
             http_corsdomain: None,
+            http_ready_max_blocks_behind: constants::DEFAULT_READY_MAX_BLOCKS_BEHIND,
+            http_ready_min_peers: 0,
+            http_ready_max_engine_idle: None,
             ws: false,
             ws_addr: Ipv4Addr::LOCALHOST.into(),
             ws_port: constants::DEFAULT_WS_RPC_PORT,
@@ -286,28 +397,52 @@ impl Default for RpcServerArgs {
             ws_api: None,
             ipcdisable: false,
             ipcpath: constants::DEFAULT_IPC_ENDPOINT.to_string(),
+            ipc_socket_permissions: None,
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
             auth_jwtsecret: None,
             auth_ipc: false,
             auth_ipc_path: constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string(),
+            auth_backup_port: None,
+            auth_failover_timeout: Duration::from_secs(24),
             rpc_jwtsecret: None,
             rpc_max_request_size: RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into(),
             rpc_max_response_size: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB.into(),
             rpc_max_subscriptions_per_connection: RPC_DEFAULT_MAX_SUBS_PER_CONN.into(),
+            rpc_max_batch_size: ZeroAsNoneU32(None),
+            rpc_message_buffer_capacity: RPC_DEFAULT_MESSAGE_BUFFER_CAPACITY,
             rpc_max_connections: RPC_DEFAULT_MAX_CONNECTIONS.into(),
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
+            rpc_max_concurrent_getlogs_chunks: constants::DEFAULT_MAX_CONCURRENT_GETLOGS_CHUNKS,
             rpc_gas_cap: constants::gas_oracle::RPC_DEFAULT_GAS_CAP,
+            rpc_call_timeout: constants::DEFAULT_RPC_CALL_TIMEOUT,
             rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
+            rpc_subscription_backpressure: SubscriptionBackpressure::default(),
+            rpc_metrics_max_methods: constants::DEFAULT_MAX_METRICS_METHODS,
+            rpc_metrics_max_error_codes: constants::DEFAULT_MAX_METRICS_ERROR_CODES,
+            rpc_correlation_ids: false,
+            rpc_historical_fallback: None,
+            rpc_pending_receipts: false,
+            rpc_snapshot_ttl: constants::DEFAULT_STATE_SNAPSHOT_TTL,
+            rpc_max_snapshots: constants::DEFAULT_MAX_STATE_SNAPSHOTS,
         }
     }
 }
 
+/// Parses an octal unix permission mode, e.g. `600` or `0o600`.
+fn parse_socket_permissions(value: &str) -> Result<u32, String> {
+    let digits = value.strip_prefix("0o").unwrap_or(value);
+    match u32::from_str_radix(digits, 8) {
+        Ok(mode) if mode <= 0o777 => Ok(mode),
+        _ => Err(format!("invalid permission mode `{value}`, expected octal like `600`")),
+    }
+}
+
 /// clap value parser for [`RpcModuleSelection`].
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
@@ -389,6 +524,54 @@ mod tests {
         assert_eq!(apis, expected);
     }
 
+    #[test]
+    fn test_rpc_server_ipc_permissions() {
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--ipc.permissions", "660"]).args;
+        assert_eq!(args.ipc_socket_permissions, Some(0o660));
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--ipc.permissions", "0o600"]).args;
+        assert_eq!(args.ipc_socket_permissions, Some(0o600));
+
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--ipc.permissions",
+            "800"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_rpc_server_pending_receipts() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(!args.rpc_pending_receipts);
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc.pending-receipts"]).args;
+        assert!(args.rpc_pending_receipts);
+    }
+
+    #[test]
+    fn test_rpc_server_auth_backup() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.auth_backup_port, None);
+
+        let mut args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--authrpc.backup-port",
+            "8552",
+            "--authrpc.failover-timeout",
+            "36s",
+        ])
+        .args;
+        assert_eq!(args.auth_backup_port, Some(8552));
+        assert_eq!(args.auth_failover_timeout, Duration::from_secs(36));
+
+        args.adjust_instance_ports(2);
+        assert_eq!(args.auth_backup_port, Some(8652));
+    }
+
     #[test]
     fn rpc_server_args_default_sanity_test() {
         let default_args = RpcServerArgs::default();
@@ -1,7 +1,9 @@
 use clap::Args;
 use reth_rpc_server_types::constants::cache::{
-    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_ENV_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_BLOCK_CACHE_SIZE_BYTES_MB, DEFAULT_CONCURRENT_DB_REQUESTS,
+    DEFAULT_ENV_CACHE_MAX_LEN, DEFAULT_ENV_CACHE_SIZE_BYTES_MB, DEFAULT_MIN_AVAILABLE_MEMORY_MB,
+    DEFAULT_RECEIPT_CACHE_MAX_LEN, DEFAULT_RECEIPT_CACHE_SIZE_BYTES_MB,
+    DEFAULT_TRACE_CACHE_MAX_LEN,
 };
 
 /// Parameters to configure RPC state cache.
@@ -29,12 +31,64 @@ pub struct RpcStateCacheArgs {
     )]
     pub max_envs: u32,
 
+    /// Max number of blocks with cached `trace_block` and `debug_traceBlock*` traces.
+    ///
+    /// Disabled if set to 0.
+    #[arg(
+        long = "rpc-cache.max-traces",
+        default_value_t = DEFAULT_TRACE_CACHE_MAX_LEN,
+    )]
+    pub max_traces: u32,
+
     /// Max number of concurrent database requests.
     #[arg(
         long = "rpc-cache.max-concurrent-db-requests",
         default_value_t = DEFAULT_CONCURRENT_DB_REQUESTS,
     )]
     pub max_concurrent_db_requests: usize,
+
+    /// Size the caches by estimated memory usage instead of number of entries.
+    ///
+    /// If enabled, the `--rpc-cache.max-*-mb` budgets are used instead of the entry limits.
+    #[arg(long = "rpc-cache.adaptive", default_value_t = false)]
+    pub adaptive: bool,
+
+    /// Max megabytes of cached blocks, if adaptive sizing is enabled.
+    #[arg(
+        long = "rpc-cache.max-blocks-mb",
+        default_value_t = DEFAULT_BLOCK_CACHE_SIZE_BYTES_MB,
+    )]
+    pub max_blocks_mb: usize,
+
+    /// Max megabytes of cached receipts, if adaptive sizing is enabled.
+    #[arg(
+        long = "rpc-cache.max-receipts-mb",
+        default_value_t = DEFAULT_RECEIPT_CACHE_SIZE_BYTES_MB,
+    )]
+    pub max_receipts_mb: usize,
+
+    /// Max megabytes of cached env data, if adaptive sizing is enabled.
+    #[arg(
+        long = "rpc-cache.max-envs-mb",
+        default_value_t = DEFAULT_ENV_CACHE_SIZE_BYTES_MB,
+    )]
+    pub max_envs_mb: usize,
+
+    /// Megabytes of system memory that should stay available, if adaptive sizing is enabled.
+    ///
+    /// The cache budgets are reduced while less memory is available. Disabled if set to 0.
+    #[arg(
+        long = "rpc-cache.min-available-memory-mb",
+        default_value_t = DEFAULT_MIN_AVAILABLE_MEMORY_MB,
+    )]
+    pub min_available_memory_mb: usize,
+
+    /// Number of most recently used blocks that are recorded on shutdown and loaded into the
+    /// cache again on startup.
+    ///
+    /// Disabled if set to 0.
+    #[arg(long = "rpc-cache.warm-blocks", default_value_t = 0)]
+    pub warm_blocks: usize,
 }
 
 impl Default for RpcStateCacheArgs {
@@ -43,7 +97,14 @@ impl Default for RpcStateCacheArgs {
             max_blocks: DEFAULT_BLOCK_CACHE_MAX_LEN,
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_envs: DEFAULT_ENV_CACHE_MAX_LEN,
+            max_traces: DEFAULT_TRACE_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
+            adaptive: false,
+            max_blocks_mb: DEFAULT_BLOCK_CACHE_SIZE_BYTES_MB,
+            max_receipts_mb: DEFAULT_RECEIPT_CACHE_SIZE_BYTES_MB,
+            max_envs_mb: DEFAULT_ENV_CACHE_SIZE_BYTES_MB,
+            min_available_memory_mb: DEFAULT_MIN_AVAILABLE_MEMORY_MB,
+            warm_blocks: 0,
         }
     }
 }
@@ -0,0 +1,68 @@
+//! clap [Args](clap::Args) for bootstrapping a node from a snapshot
+
+use clap::Args;
+use reth_primitives::Address;
+use url::Url;
+
+/// Parameters for bootstrapping the node from a trusted snapshot.
+///
+/// Snapshots are backups created with `reth backup create --signer-key` and published on a web
+/// server.
+#[derive(Debug, Clone, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Snapshot")]
+pub struct SnapshotArgs {
+    /// URL of a signed snapshot to bootstrap the node from, e.g.
+    /// `https://snapshots.example.com/mainnet/`.
+    ///
+    /// If the datadir has no database yet, the snapshot is downloaded and restored before the
+    /// node starts, and the remaining blocks are synced normally. An interrupted download is
+    /// resumed on the next start.
+    #[arg(long = "snapshot.url", value_name = "URL", requires = "signer")]
+    pub url: Option<Url>,
+
+    /// Address the manifest of the snapshot must be signed by.
+    #[arg(long = "snapshot.signer", value_name = "ADDRESS")]
+    pub signer: Option<Address>,
+
+    /// Id of the snapshot to restore. Defaults to the latest snapshot.
+    #[arg(long = "snapshot.id", value_name = "ID", requires = "url")]
+    pub id: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn parse_snapshot_args() {
+        let args = CommandParser::<SnapshotArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, SnapshotArgs::default());
+
+        let args = CommandParser::<SnapshotArgs>::parse_from([
+            "reth",
+            "--snapshot.url",
+            "https://snapshots.example.com/mainnet/",
+            "--snapshot.signer",
+            "0x000000000000000000000000000000000000dEaD",
+        ])
+        .args;
+        assert_eq!(args.url.unwrap().as_str(), "https://snapshots.example.com/mainnet/");
+        assert!(args.signer.is_some());
+
+        // Snapshots must be signed
+        assert!(CommandParser::<SnapshotArgs>::try_parse_from([
+            "reth",
+            "--snapshot.url",
+            "https://snapshots.example.com/mainnet/"
+        ])
+        .is_err());
+    }
+}
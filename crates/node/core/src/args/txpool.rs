@@ -4,14 +4,19 @@ use crate::cli::config::RethTransactionPoolConfig;
 use clap::Args;
 use reth_primitives::Address;
 use reth_transaction_pool::{
-    blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
+    blobstore::{
+        disk::DEFAULT_MAX_CACHED_BLOBS,
+        overflow::{DEFAULT_BLOB_OVERFLOW_MAX_SIZE_MB, DEFAULT_BLOB_OVERFLOW_MAX_TXS},
+        BlobOverflowConfig, BlobOverflowEviction,
+    },
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-    DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, REPLACE_BLOB_PRICE_BUMP,
-    TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
-    TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+    EvictionPolicy, LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit,
+    DEFAULT_PRICE_BUMP, DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, REPLACE_BLOB_PRICE_BUMP,
+    TXPOOL_FAIR_ACCOUNT_SLOTS_DEFAULT, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+    TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
+use std::time::Duration;
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "TxPool")]
@@ -37,18 +42,59 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.queued-max-size", alias = "txpool.queued_max_size", default_value_t = TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT)]
     pub queued_max_size: usize,
 
+    /// Max combined size of all sub-pools in megabytes.
+    ///
+    /// Only the limits of the individual sub-pools apply if not set.
+    #[arg(long = "txpool.max-total-size")]
+    pub max_total_size: Option<usize>,
+
     /// Max number of executable transaction slots guaranteed per account
     #[arg(long = "txpool.max-account-slots", alias = "txpool.max_account_slots", default_value_t = TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER)]
     pub max_account_slots: usize,
 
+    /// Number of transactions an account can have in the pool before its transactions are
+    /// evicted first with `--txpool.eviction-policy sender-overage`.
+    #[arg(long = "txpool.fair-account-slots", default_value_t = TXPOOL_FAIR_ACCOUNT_SLOTS_DEFAULT)]
+    pub fair_account_slots: usize,
+
+    /// Which transactions are evicted first once the pool is full: `fee` evicts the worst
+    /// transactions by fee, `sender-overage` first evicts the transactions of the accounts that
+    /// exceed `--txpool.fair-account-slots` the most.
+    #[arg(long = "txpool.eviction-policy", default_value_t = EvictionPolicy::Fee)]
+    pub eviction_policy: EvictionPolicy,
+
     /// Price bump (in %) for the transaction pool underpriced check.
     #[arg(long = "txpool.pricebump", default_value_t = DEFAULT_PRICE_BUMP)]
     pub price_bump: u128,
 
+    /// Price bump (in %) to replace legacy and EIP-2930 transactions, defaults to
+    /// `--txpool.pricebump`.
+    #[arg(long = "txpool.legacy-pricebump")]
+    pub legacy_price_bump: Option<u128>,
+
+    /// Price bump (in %) to replace EIP-1559 transactions, defaults to `--txpool.pricebump`.
+    #[arg(long = "txpool.eip1559-pricebump")]
+    pub eip1559_price_bump: Option<u128>,
+
     /// Price bump percentage to replace an already existing blob transaction
     #[arg(long = "blobpool.pricebump", default_value_t = REPLACE_BLOB_PRICE_BUMP)]
     pub blob_transaction_price_bump: u128,
 
+    /// Keep blob transactions that are discarded because the blob sub-pool is full on disk, and
+    /// re-admit them once there's room again.
+    #[arg(long = "blobpool.overflow")]
+    pub blob_overflow: bool,
+    /// Max number of transactions kept on disk with `--blobpool.overflow`.
+    #[arg(long = "blobpool.overflow-max-count", default_value_t = DEFAULT_BLOB_OVERFLOW_MAX_TXS)]
+    pub blob_overflow_max_count: usize,
+    /// Max size of the transactions kept on disk with `--blobpool.overflow` in megabytes.
+    #[arg(long = "blobpool.overflow-max-size", default_value_t = DEFAULT_BLOB_OVERFLOW_MAX_SIZE_MB)]
+    pub blob_overflow_max_size: usize,
+    /// Which transactions are evicted once the limits of `--blobpool.overflow` are reached:
+    /// `lowest-fee` or `oldest`.
+    #[arg(long = "blobpool.overflow-eviction", default_value_t = BlobOverflowEviction::LowestFee)]
+    pub blob_overflow_eviction: BlobOverflowEviction,
+
     /// Max size in bytes of a single transaction allowed to enter the pool
     #[arg(long = "txpool.max-tx-input-bytes", alias = "txpool.max_tx_input_bytes", default_value_t = DEFAULT_MAX_TX_INPUT_BYTES)]
     pub max_tx_input_bytes: usize,
@@ -66,6 +112,14 @@ pub struct TxPoolArgs {
     /// Flag to toggle local transaction propagation.
     #[arg(long = "txpool.no-local-transactions-propagation")]
     pub no_local_transactions_propagation: bool,
+    /// Save the pending and queued transactions received from the network on shutdown as well,
+    /// and revalidate them on startup. Blob transactions are not saved.
+    #[arg(long = "txpool.journal-remotes")]
+    pub journal_remote_transactions: bool,
+    /// Interval at which the transactions of the pool are saved to disk while the node is
+    /// running, e.g. `1h`. Without this they are only saved on shutdown.
+    #[arg(long = "txpool.rejournal", value_name = "DURATION", value_parser = humantime::parse_duration)]
+    pub rejournal_interval: Option<Duration>,
     /// Number of additional transaction validation tasks to spawn.
     #[arg(long = "txpool.additional-validation-tasks", alias = "txpool.additional_validation_tasks", default_value_t = DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS)]
     pub additional_validation_tasks: usize,
@@ -88,14 +142,25 @@ impl Default for TxPoolArgs {
             basefee_max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
             queued_max_count: TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
             queued_max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+            max_total_size: None,
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            fair_account_slots: TXPOOL_FAIR_ACCOUNT_SLOTS_DEFAULT,
+            eviction_policy: EvictionPolicy::Fee,
             price_bump: DEFAULT_PRICE_BUMP,
+            legacy_price_bump: None,
+            eip1559_price_bump: None,
             blob_transaction_price_bump: REPLACE_BLOB_PRICE_BUMP,
+            blob_overflow: false,
+            blob_overflow_max_count: DEFAULT_BLOB_OVERFLOW_MAX_TXS,
+            blob_overflow_max_size: DEFAULT_BLOB_OVERFLOW_MAX_SIZE_MB,
+            blob_overflow_eviction: BlobOverflowEviction::LowestFee,
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
             max_cached_entries: DEFAULT_MAX_CACHED_BLOBS,
             no_locals: false,
             locals: Default::default(),
             no_local_transactions_propagation: false,
+            journal_remote_transactions: false,
+            rejournal_interval: None,
             additional_validation_tasks: DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS,
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
@@ -103,6 +168,20 @@ impl Default for TxPoolArgs {
     }
 }
 
+impl TxPoolArgs {
+    /// Returns the configuration of the blob overflow store if `--blobpool.overflow` is enabled.
+    pub const fn blob_overflow_config(&self) -> Option<BlobOverflowConfig> {
+        if !self.blob_overflow {
+            return None
+        }
+        Some(BlobOverflowConfig {
+            max_txs: self.blob_overflow_max_count,
+            max_size: self.blob_overflow_max_size * 1024 * 1024,
+            eviction: self.blob_overflow_eviction,
+        })
+    }
+}
+
 impl RethTransactionPoolConfig for TxPoolArgs {
     /// Returns transaction pool configuration.
     fn pool_config(&self) -> PoolConfig {
@@ -128,13 +207,21 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 max_txs: self.queued_max_count,
                 max_size: self.queued_max_size * 1024 * 1024,
             },
+            max_total_size: self.max_total_size.map(|size| size * 1024 * 1024),
             max_account_slots: self.max_account_slots,
+            fair_account_slots: self.fair_account_slots,
+            eviction_policy: self.eviction_policy,
             price_bumps: PriceBumpConfig {
                 default_price_bump: self.price_bump,
                 replace_blob_tx_price_bump: self.blob_transaction_price_bump,
+                legacy_price_bump: self.legacy_price_bump,
+                eip1559_price_bump: self.eip1559_price_bump,
             },
             pending_tx_listener_buffer_size: self.pending_tx_listener_buffer_size,
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
+            admission_policy: None,
+            replacement_policy: None,
+            blob_overflow: None,
         }
     }
 }
@@ -157,4 +244,81 @@ mod tests {
         let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn txpool_parse_eviction_budgets() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.max-total-size",
+            "64",
+            "--txpool.fair-account-slots",
+            "2",
+            "--txpool.eviction-policy",
+            "sender-overage",
+        ])
+        .args;
+        let config = args.pool_config();
+        assert_eq!(config.max_total_size, Some(64 * 1024 * 1024));
+        assert_eq!(config.fair_account_slots, 2);
+        assert_eq!(config.eviction_policy, EvictionPolicy::SenderOverage);
+
+        assert!(CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.eviction-policy",
+            "random"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn txpool_parse_price_bumps_by_type() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.pricebump",
+            "15",
+            "--txpool.eip1559-pricebump",
+            "5",
+        ])
+        .args;
+        let price_bumps = args.pool_config().price_bumps;
+        assert_eq!(price_bumps.default_price_bump, 15);
+        assert_eq!(price_bumps.legacy_price_bump, None);
+        assert_eq!(price_bumps.eip1559_price_bump, Some(5));
+        assert_eq!(price_bumps.replace_blob_tx_price_bump, REPLACE_BLOB_PRICE_BUMP);
+    }
+
+    #[test]
+    fn txpool_parse_blob_overflow() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--blobpool.overflow",
+            "--blobpool.overflow-max-count",
+            "100",
+            "--blobpool.overflow-eviction",
+            "oldest",
+        ])
+        .args;
+        assert_eq!(
+            args.blob_overflow_config(),
+            Some(BlobOverflowConfig {
+                max_txs: 100,
+                max_size: DEFAULT_BLOB_OVERFLOW_MAX_SIZE_MB * 1024 * 1024,
+                eviction: BlobOverflowEviction::Oldest,
+            })
+        );
+        assert_eq!(TxPoolArgs::default().blob_overflow_config(), None);
+    }
+
+    #[test]
+    fn txpool_parse_journal() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.journal-remotes",
+            "--txpool.rejournal",
+            "1h",
+        ])
+        .args;
+        assert!(args.journal_remote_transactions);
+        assert_eq!(args.rejournal_interval, Some(Duration::from_secs(3600)));
+    }
 }
@@ -1,7 +1,7 @@
 //! Clap parser utilities
 
 use alloy_genesis::Genesis;
-use reth_chainspec::ChainSpec;
+use reth_chainspec::{ChainSpec, ChainSpecFile};
 use reth_fs_util as fs;
 use std::{path::PathBuf, sync::Arc};
 
@@ -27,8 +27,9 @@ pub fn chain_help() -> String {
 
 /// Clap value parser for [`ChainSpec`]s.
 ///
-/// The value parser matches either a known chain, the path
-/// to a json file, or a json formatted string in-memory. The json needs to be a Genesis struct.
+/// The value parser matches either a known chain, the path to a TOML chain file (see
+/// [`ChainSpecFile`]), the path to a json file, or a json formatted string in-memory. The json
+/// needs to be a Genesis struct.
 pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error> {
     Ok(match s {
         #[cfg(not(feature = "optimism"))]
@@ -47,8 +48,15 @@ pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error>
         #[cfg(feature = "optimism")]
         "base_sepolia" | "base-sepolia" => BASE_SEPOLIA.clone(),
         _ => {
+            let path = PathBuf::from(shellexpand::full(s)?.into_owned());
+
+            // chain files define the genesis and the hardfork schedule
+            if path.extension().is_some_and(|extension| extension == "toml") {
+                return Ok(Arc::new(ChainSpecFile::load(&path)?.chain_spec()?))
+            }
+
             // try to read json from path first
-            let raw = match fs::read_to_string(PathBuf::from(shellexpand::full(s)?.into_owned())) {
+            let raw = match fs::read_to_string(path) {
                 Ok(raw) => raw,
                 Err(io_err) => {
                     // valid json may start with "\n", but must contain "{"
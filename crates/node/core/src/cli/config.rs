@@ -2,8 +2,10 @@
 
 use reth_network::protocol::IntoRlpxSubProtocol;
 use reth_primitives::Bytes;
+use reth_rpc_types::beacon::BlsPublicKey;
 use reth_transaction_pool::PoolConfig;
 use std::{borrow::Cow, time::Duration};
+use url::Url;
 
 /// A trait that provides payload builder settings.
 ///
@@ -29,6 +31,40 @@ pub trait PayloadBuilderConfig {
 
     /// Maximum number of tasks to spawn for building a payload.
     fn max_payload_tasks(&self) -> usize;
+
+    /// Gas of each built block that is reserved for local transactions.
+    fn local_gas_reserve(&self) -> u64;
+
+    /// Maximum number of blobs included in built blocks.
+    fn max_blobs(&self) -> Option<u64>;
+
+    /// Number of blobs built blocks should contain.
+    fn blob_target(&self) -> Option<u64>;
+
+    /// Minimum priority fee per gas of blob transactions included in built blocks.
+    fn min_blob_priority_fee(&self) -> u128;
+
+    /// Minimum fee improvement in basis points a new payload must have to replace the best
+    /// payload.
+    fn min_improvement_bps(&self) -> u64;
+
+    /// Total time a payload job may spend building payloads.
+    fn build_time_budget(&self) -> Option<Duration>;
+
+    /// URL of an external block builder to race local payloads against.
+    fn relay_url(&self) -> Option<&Url>;
+
+    /// BLS public keys of external builders whose payloads are accepted.
+    ///
+    /// If empty, payloads of any builder are accepted.
+    fn relay_trusted_builders(&self) -> &[BlsPublicKey];
+
+    /// Minimum value advantage in basis points an external payload must have over the local
+    /// payload.
+    fn relay_min_advantage_bps(&self) -> u64;
+
+    /// How long to wait for the external builder once the local payload was built.
+    fn relay_timeout(&self) -> Duration;
 }
 
 /// A trait that represents the configured network and can be used to apply additional configuration
@@ -2,6 +2,7 @@
 
 use crate::{args::DatadirArgs, utils::parse_path};
 use reth_chainspec::Chain;
+use reth_config::config::EtlConfig;
 use std::{
     env::VarError,
     fmt::{Debug, Display, Formatter},
@@ -256,6 +257,38 @@ impl<D> From<PathBuf> for MaybePlatformPath<D> {
     }
 }
 
+/// A class of data in the data dir that can be stored on a separate volume, see
+/// [`ChainPath::data_class_dirs`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DataClass {
+    /// The MDBX database.
+    Database,
+    /// The static files.
+    StaticFiles,
+    /// The blobs of unfinalized transactions.
+    Blobstore,
+    /// The temporary ETL files.
+    Etl,
+}
+
+impl DataClass {
+    /// Returns the name of the data class.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Database => "db",
+            Self::StaticFiles => "static_files",
+            Self::Blobstore => "blobstore",
+            Self::Etl => "etl",
+        }
+    }
+}
+
+impl Display for DataClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Wrapper type around `PlatformPath` that includes a `Chain`, used for separating reth data for
 /// different networks.
 ///
@@ -286,7 +319,12 @@ impl<D> ChainPath<D> {
     ///
     /// `<DIR>/<CHAIN_ID>/db`
     pub fn db(&self) -> PathBuf {
-        self.data_dir().join("db")
+        let datadir_args = &self.2;
+        if let Some(db_path) = &datadir_args.db_path {
+            db_path.to_path_buf()
+        } else {
+            self.data_dir().join("db")
+        }
     }
 
     /// Returns the path to the static files directory for this chain.
@@ -320,7 +358,43 @@ impl<D> ChainPath<D> {
     ///
     /// `<DIR>/<CHAIN_ID>/blobstore`
     pub fn blobstore(&self) -> PathBuf {
-        self.data_dir().join("blobstore")
+        let datadir_args = &self.2;
+        if let Some(blobstore_path) = &datadir_args.blobstore_path {
+            blobstore_path.to_path_buf()
+        } else {
+            self.data_dir().join("blobstore")
+        }
+    }
+
+    /// Returns the path to the directory for this chain where blob transactions are stored that
+    /// didn't fit into the transaction pool.
+    ///
+    /// `<DIR>/<CHAIN_ID>/blobstore-overflow`
+    pub fn blobstore_overflow(&self) -> PathBuf {
+        self.data_dir().join("blobstore-overflow")
+    }
+
+    /// Returns the path to the directory for this chain where temporary ETL files are created
+    /// during sync.
+    ///
+    /// `<DIR>/<CHAIN_ID>/etl-tmp`
+    pub fn etl(&self) -> PathBuf {
+        let datadir_args = &self.2;
+        if let Some(etl_path) = &datadir_args.etl_path {
+            etl_path.to_path_buf()
+        } else {
+            EtlConfig::from_datadir(self.data_dir())
+        }
+    }
+
+    /// Returns the directories of the data classes that can be stored on separate volumes.
+    pub fn data_class_dirs(&self) -> [(DataClass, PathBuf); 4] {
+        [
+            (DataClass::Database, self.db()),
+            (DataClass::StaticFiles, self.static_files()),
+            (DataClass::Blobstore, self.blobstore()),
+            (DataClass::Etl, self.etl()),
+        ]
     }
 
     /// Returns the path to the local transactions backup file
@@ -330,6 +404,37 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the file with the hashes of the blocks that were cached by the RPC
+    /// state cache before shutdown.
+    ///
+    /// `<DIR>/<CHAIN_ID>/rpc-cache-blocks.json`
+    pub fn rpc_cache_blocks(&self) -> PathBuf {
+        self.data_dir().join("rpc-cache-blocks.json")
+    }
+
+    /// Returns the path to the file with the progress and findings of the background integrity
+    /// check of the database.
+    ///
+    /// `<DIR>/<CHAIN_ID>/integrity-check.json`
+    pub fn integrity_check(&self) -> PathBuf {
+        self.data_dir().join("integrity-check.json")
+    }
+
+    /// Returns the path to the directory with the fork point and the state cache of a forked dev
+    /// chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/fork`
+    pub fn fork(&self) -> PathBuf {
+        self.data_dir().join("fork")
+    }
+
+    /// Returns the path to the audit log directory for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/audit`
+    pub fn audit_log(&self) -> PathBuf {
+        self.data_dir().join("audit")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -391,4 +496,25 @@ mod tests {
         let path = path.unwrap_or_chain_default(Chain::sepolia(), DatadirArgs::default());
         assert!(path.as_ref().ends_with("reth/sepolia"), "{path:?}");
     }
+
+    #[test]
+    fn test_data_class_dirs() {
+        let path = MaybePlatformPath::<DataDirPath>::from_str("/data/reth").unwrap();
+        let args = DatadirArgs {
+            db_path: Some("/mnt/nvme/db".into()),
+            etl_path: Some("/mnt/hdd/etl".into()),
+            ..Default::default()
+        };
+        let path = path.unwrap_or_chain_default(Chain::mainnet(), args);
+
+        assert_eq!(
+            path.data_class_dirs(),
+            [
+                (DataClass::Database, PathBuf::from("/mnt/nvme/db")),
+                (DataClass::StaticFiles, PathBuf::from("/data/reth/static_files")),
+                (DataClass::Blobstore, PathBuf::from("/data/reth/blobstore")),
+                (DataClass::Etl, PathBuf::from("/mnt/hdd/etl")),
+            ]
+        );
+    }
 }
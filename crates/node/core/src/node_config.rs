@@ -78,6 +78,14 @@ pub struct NodeConfig {
     /// The path to the configuration file to use.
     pub config: Option<PathBuf>,
 
+    /// The profile of the configuration file to apply, see
+    /// [`Config::with_profile`](reth_config::Config::with_profile).
+    pub config_profile: Option<String>,
+
+    /// Overrides of settings of the configuration file, keyed by the path of the setting, see
+    /// [`ConfigOverrides`](reth_config::ConfigOverrides).
+    pub config_overrides: Vec<(String, String)>,
+
     /// The chain this node is running.
     ///
     /// Possible values are either a built-in chain or the path to a chain specification file.
@@ -168,6 +176,23 @@ impl NodeConfig {
         self
     }
 
+    /// Set the config profile for the node
+    pub fn with_config_profile(mut self, profile: impl Into<String>) -> Self {
+        self.config_profile = Some(profile.into());
+        self
+    }
+
+    /// Add an override for a setting of the configuration file, e.g.
+    /// `stages.execution.max_blocks`
+    pub fn with_config_override(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.config_overrides.push((key.into(), value.into()));
+        self
+    }
+
     /// Set the [`ChainSpec`] for the node
     pub fn with_chain(mut self, chain: impl Into<Arc<ChainSpec>>) -> Self {
         self.chain = chain.into();
@@ -223,7 +248,7 @@ impl NodeConfig {
     }
 
     /// Set the dev args for the node
-    pub const fn with_dev(mut self, dev: DevArgs) -> Self {
+    pub fn with_dev(mut self, dev: DevArgs) -> Self {
         self.dev = dev;
         self
     }
@@ -401,6 +426,8 @@ impl Default for NodeConfig {
     fn default() -> Self {
         Self {
             config: None,
+            config_profile: None,
+            config_overrides: Vec::new(),
             chain: MAINNET.clone(),
             metrics: None,
             instance: 1,
@@ -0,0 +1,342 @@
+//! Append-only audit log of chain events in JSON lines.
+//!
+//! Records reorgs, invalid blocks, forkchoice changes and built payloads with their timings, so
+//! e.g. a missed proposal can be reconstructed without searching the logs.
+
+use alloy_rpc_types_engine::{ForkchoiceState, PayloadId};
+use futures::{stream, Stream, StreamExt};
+use reth_beacon_consensus::{BeaconConsensusEngineEvent, ForkchoiceStatus};
+use reth_payload_builder::{Events, PayloadEvents};
+use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes, PayloadTypes};
+use reth_primitives::{BlockNumber, B256, U256};
+use reth_provider::CanonStateNotification;
+use rolling_file::{RollingConditionBasic, RollingFileAppender};
+use serde::Serialize;
+use std::{
+    io::{self, Write},
+    path::Path,
+    pin::pin,
+    time::{Duration, Instant, SystemTime},
+};
+use tracing::warn;
+
+/// The file name of the current audit log, rotated files have a numeric suffix.
+pub const AUDIT_LOG_FILE_NAME: &str = "audit.jsonl";
+
+/// How long payload attributes are kept to measure the build time of their payload.
+const PAYLOAD_ATTRIBUTES_TTL: Duration = Duration::from_secs(60);
+
+/// An event of the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// The forkchoice state changed.
+    ForkchoiceUpdated {
+        /// The head block of the new forkchoice state.
+        head: B256,
+        /// The safe block of the new forkchoice state.
+        safe: B256,
+        /// The finalized block of the new forkchoice state.
+        finalized: B256,
+        /// The status of the forkchoice update: `valid`, `invalid` or `syncing`.
+        status: &'static str,
+    },
+    /// Canonical blocks were reverted, and replaced by the blocks of a new chain if any.
+    Reorg {
+        /// The number of the last block both chains have in common.
+        fork_number: BlockNumber,
+        /// The hash of the last block both chains have in common.
+        fork_hash: B256,
+        /// The number of the reverted tip.
+        old_tip_number: BlockNumber,
+        /// The hash of the reverted tip.
+        old_tip_hash: B256,
+        /// The number of the new tip, if blocks were added.
+        new_tip_number: Option<BlockNumber>,
+        /// The hash of the new tip, if blocks were added.
+        new_tip_hash: Option<B256>,
+        /// The number of reverted blocks.
+        reverted: usize,
+        /// The number of added blocks.
+        added: usize,
+    },
+    /// A received block failed validation.
+    InvalidBlock {
+        /// The number of the block.
+        number: BlockNumber,
+        /// The hash of the block.
+        hash: B256,
+        /// The parent hash of the block.
+        parent_hash: B256,
+        /// The validation error.
+        error: String,
+    },
+    /// A payload was built and returned to the consensus layer.
+    PayloadBuilt {
+        /// The id of the payload, if its attributes were received.
+        payload_id: Option<PayloadId>,
+        /// The number of the built block.
+        number: BlockNumber,
+        /// The hash of the built block.
+        hash: B256,
+        /// The parent hash of the built block.
+        parent_hash: B256,
+        /// The number of transactions in the block.
+        transactions: usize,
+        /// The gas used by the block.
+        gas_used: u64,
+        /// The fees collected by the block.
+        fees: U256,
+        /// Milliseconds from receiving the payload attributes until the payload was returned, if
+        /// the attributes were received.
+        elapsed_ms: Option<u64>,
+    },
+}
+
+/// A line of the audit log.
+#[derive(Serialize)]
+struct AuditRecord<'a> {
+    /// RFC 3339 timestamp of the event.
+    timestamp: String,
+    #[serde(flatten)]
+    event: &'a AuditEvent,
+}
+
+/// Writer of the audit log, which rotates the log file once it reaches the maximum size.
+pub struct AuditLog {
+    writer: RollingFileAppender<RollingConditionBasic>,
+}
+
+impl AuditLog {
+    /// Opens the audit log in the given directory, appending to an existing log.
+    ///
+    /// Once the log file reaches `max_file_size` bytes it's rotated, and at most `max_files`
+    /// rotated files are kept.
+    pub fn new(dir: &Path, max_file_size: u64, max_files: usize) -> io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        let writer = RollingFileAppender::new(
+            dir.join(AUDIT_LOG_FILE_NAME),
+            RollingConditionBasic::new().max_size(max_file_size),
+            max_files,
+        )?;
+        Ok(Self { writer })
+    }
+
+    /// Appends the event to the log.
+    pub fn write(&mut self, event: &AuditEvent) -> io::Result<()> {
+        let record = AuditRecord {
+            timestamp: humantime::format_rfc3339_millis(SystemTime::now()).to_string(),
+            event,
+        };
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+        self.writer.write_all(&line)?;
+        self.writer.flush()
+    }
+}
+
+impl std::fmt::Debug for AuditLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditLog").finish_non_exhaustive()
+    }
+}
+
+/// Received payload attributes, to measure the build time of their payload.
+#[derive(Debug)]
+struct PendingPayload {
+    id: PayloadId,
+    parent: B256,
+    timestamp: u64,
+    received_at: Instant,
+}
+
+/// Turns the events of the node into [`AuditEvent`]s.
+#[derive(Debug, Default)]
+struct AuditState {
+    /// The last logged forkchoice state and status.
+    forkchoice: Option<(ForkchoiceState, ForkchoiceStatus)>,
+    /// Payload attributes whose payload wasn't returned yet.
+    pending_payloads: Vec<PendingPayload>,
+}
+
+impl AuditState {
+    fn on_engine_event(&mut self, event: BeaconConsensusEngineEvent) -> Option<AuditEvent> {
+        match event {
+            BeaconConsensusEngineEvent::ForkchoiceUpdated(state, status) => {
+                // consecutive updates with the same state, e.g. to build a payload, are logged once
+                if self.forkchoice == Some((state, status)) {
+                    return None
+                }
+                self.forkchoice = Some((state, status));
+                Some(AuditEvent::ForkchoiceUpdated {
+                    head: state.head_block_hash,
+                    safe: state.safe_block_hash,
+                    finalized: state.finalized_block_hash,
+                    status: match status {
+                        ForkchoiceStatus::Valid => "valid",
+                        ForkchoiceStatus::Invalid => "invalid",
+                        ForkchoiceStatus::Syncing => "syncing",
+                    },
+                })
+            }
+            BeaconConsensusEngineEvent::InvalidBlock(header, error) => {
+                Some(AuditEvent::InvalidBlock {
+                    number: header.number,
+                    hash: header.hash(),
+                    parent_hash: header.parent_hash,
+                    error,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn on_canon_state_notification(notification: CanonStateNotification) -> Option<AuditEvent> {
+        let CanonStateNotification::Reorg { old, new } = notification else { return None };
+        let fork = old.fork_block();
+        let old_tip = old.tip();
+        let new_tip = (new.len() > 0).then(|| new.tip());
+        Some(AuditEvent::Reorg {
+            fork_number: fork.number,
+            fork_hash: fork.hash,
+            old_tip_number: old_tip.number,
+            old_tip_hash: old_tip.hash(),
+            new_tip_number: new_tip.map(|tip| tip.number),
+            new_tip_hash: new_tip.map(|tip| tip.hash()),
+            reverted: old.len(),
+            added: new.len(),
+        })
+    }
+
+    fn on_payload_event<T: PayloadTypes>(&mut self, event: Events<T>) -> Option<AuditEvent> {
+        let now = Instant::now();
+        self.pending_payloads
+            .retain(|pending| now.duration_since(pending.received_at) < PAYLOAD_ATTRIBUTES_TTL);
+
+        match event {
+            Events::Attributes(attributes) => {
+                self.pending_payloads.push(PendingPayload {
+                    id: attributes.payload_id(),
+                    parent: attributes.parent(),
+                    timestamp: attributes.timestamp(),
+                    received_at: now,
+                });
+                None
+            }
+            Events::BuiltPayload(payload) => {
+                let block = payload.block();
+                let (parent, timestamp) = (block.parent_hash, block.timestamp);
+                let pending = self
+                    .pending_payloads
+                    .iter()
+                    .position(|pending| pending.parent == parent && pending.timestamp == timestamp)
+                    .map(|idx| self.pending_payloads.swap_remove(idx));
+                Some(AuditEvent::PayloadBuilt {
+                    payload_id: pending.as_ref().map(|pending| pending.id),
+                    number: block.number,
+                    hash: block.hash(),
+                    parent_hash: block.parent_hash,
+                    transactions: block.body.len(),
+                    gas_used: block.gas_used,
+                    fees: payload.fees(),
+                    elapsed_ms: pending
+                        .map(|pending| now.duration_since(pending.received_at).as_millis() as u64),
+                })
+            }
+        }
+    }
+}
+
+/// An event of one of the streams the audit log consumes.
+enum AuditInput<T: PayloadTypes> {
+    Engine(BeaconConsensusEngineEvent),
+    CanonState(CanonStateNotification),
+    Payload(Events<T>),
+}
+
+/// Writes the audit events of the given event streams to the audit log until all streams end.
+pub async fn handle_audit_events<T, E, C>(
+    mut log: AuditLog,
+    engine_events: E,
+    canon_state_notifications: C,
+    payload_events: Option<PayloadEvents<T>>,
+) where
+    T: PayloadTypes + 'static,
+    E: Stream<Item = BeaconConsensusEngineEvent> + Unpin,
+    C: Stream<Item = CanonStateNotification> + Unpin,
+{
+    let payload_events = match payload_events {
+        // lagging behind only drops events, which is logged by the stream
+        Some(events) => {
+            events.into_stream().filter_map(|event| async move { event.ok() }).left_stream()
+        }
+        None => stream::empty().right_stream(),
+    };
+    let mut inputs = pin!(stream::select(
+        engine_events.map(AuditInput::<T>::Engine),
+        stream::select(
+            canon_state_notifications.map(AuditInput::CanonState),
+            payload_events.map(AuditInput::Payload),
+        ),
+    ));
+
+    let mut state = AuditState::default();
+    while let Some(input) = inputs.next().await {
+        let event = match input {
+            AuditInput::Engine(event) => state.on_engine_event(event),
+            AuditInput::CanonState(notification) => {
+                AuditState::on_canon_state_notification(notification)
+            }
+            AuditInput::Payload(event) => state.on_payload_event(event),
+        };
+        if let Some(event) = event {
+            if let Err(err) = log.write(&event) {
+                warn!(target: "reth::audit", %err, "Failed to write audit log");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::SealedHeader;
+
+    #[test]
+    fn dedups_forkchoice_updates() {
+        let mut state = AuditState::default();
+        let fcu =
+            ForkchoiceState { head_block_hash: B256::with_last_byte(1), ..Default::default() };
+
+        let event = BeaconConsensusEngineEvent::ForkchoiceUpdated(fcu, ForkchoiceStatus::Valid);
+        assert!(state.on_engine_event(event.clone()).is_some());
+        assert!(state.on_engine_event(event).is_none());
+        let syncing = BeaconConsensusEngineEvent::ForkchoiceUpdated(fcu, ForkchoiceStatus::Syncing);
+        assert!(state.on_engine_event(syncing).is_some());
+    }
+
+    #[test]
+    fn writes_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = AuditLog::new(dir.path(), 1024 * 1024, 2).unwrap();
+
+        let header = SealedHeader::default();
+        let event = AuditState::default()
+            .on_engine_event(BeaconConsensusEngineEvent::InvalidBlock(
+                Box::new(header.clone()),
+                "invalid state root".to_string(),
+            ))
+            .unwrap();
+        log.write(&event).unwrap();
+        log.write(&event).unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join(AUDIT_LOG_FILE_NAME)).unwrap();
+        let lines = content.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        let line: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(line["event"], "invalid_block");
+        assert_eq!(line["hash"], serde_json::to_value(header.hash()).unwrap());
+        assert_eq!(line["error"], "invalid state root");
+        assert!(line["timestamp"].is_string());
+    }
+}
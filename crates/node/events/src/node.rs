@@ -197,6 +197,31 @@ impl NodeState {
                     self.current_stage = None;
                 }
             }
+            PipelineEvent::Throughput { stage_id, throughput } => {
+                let entities_per_second = throughput.entities_per_second;
+                let stage_eta = OptionalField(
+                    throughput
+                        .eta
+                        .map(|eta| humantime::format_duration(Duration::from_secs(eta.as_secs()))),
+                );
+
+                if let Some(min_throughput) = throughput.below_threshold {
+                    warn!(
+                        stage = %stage_id,
+                        entities_per_second,
+                        min_throughput,
+                        %stage_eta,
+                        "Stage is slower than the configured minimum throughput",
+                    );
+                } else {
+                    debug!(
+                        stage = %stage_id,
+                        entities_per_second,
+                        %stage_eta,
+                        "Measured stage throughput",
+                    );
+                }
+            }
             PipelineEvent::Unwind { stage_id, input } => {
                 let current_stage = CurrentStage {
                     stage_id,
@@ -278,6 +303,9 @@ impl NodeState {
             BeaconConsensusEngineEvent::ForkBlockAdded(block) => {
                 info!(number=block.number, hash=?block.hash(), "Block added to fork chain");
             }
+            BeaconConsensusEngineEvent::InvalidBlock(..) => {
+                // already logged by the engine
+            }
         }
     }
 
@@ -503,7 +531,7 @@ where
             } else if let Some(latest_block) = this.state.latest_block {
                 let now =
                     SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
-                if now - this.state.latest_block_time.unwrap_or(0) > 60 {
+                if now.saturating_sub(this.state.latest_block_time.unwrap_or(0)) > 60 {
                     // Once we start receiving consensus nodes, don't emit status unless stalled for
                     // 1 minute
                     info!(
@@ -0,0 +1,193 @@
+//! Recorder layer that limits which metrics are recorded and the cardinality of their labels.
+
+use metrics::{
+    Counter, Gauge, Histogram, Key, KeyName, Label, Metadata, Recorder, SharedString, Unit,
+};
+use metrics_exporter_prometheus::formatting::sanitize_metric_name;
+use metrics_util::layers::Layer;
+use reth_config::MetricsConfig;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{Mutex, PoisonError},
+};
+
+/// The label value of the series that aggregates the values above the limit of a label.
+pub const AGGREGATED_LABEL_VALUE: &str = "other";
+
+/// A metric name of the [`MetricsConfig`], where a trailing `*` matches all names with the given
+/// prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum NamePattern {
+    /// Matches the name exactly.
+    Full(String),
+    /// Matches all names with the prefix.
+    Prefix(String),
+}
+
+impl NamePattern {
+    fn new(pattern: &str) -> Self {
+        match pattern.strip_suffix('*') {
+            Some(prefix) => Self::Prefix(prefix.to_string()),
+            None => Self::Full(pattern.to_string()),
+        }
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            Self::Full(full) => name == full,
+            Self::Prefix(prefix) => name.starts_with(prefix),
+        }
+    }
+}
+
+/// Layer that drops metrics that are not allowed by the [`MetricsConfig`] and aggregates the
+/// values of labels above their limit.
+///
+/// Names are matched against the name of the metric on the Prometheus endpoint, so the layer must
+/// wrap the recorder below the prefix layer.
+#[derive(Debug, Clone, Default)]
+pub struct CardinalityLayer {
+    allow: Vec<NamePattern>,
+    deny: Vec<NamePattern>,
+    label_limits: BTreeMap<String, usize>,
+}
+
+impl CardinalityLayer {
+    /// Creates a new layer from the given config.
+    pub fn new(config: &MetricsConfig) -> Self {
+        Self {
+            allow: config.allow.iter().map(|pattern| NamePattern::new(pattern)).collect(),
+            deny: config.deny.iter().map(|pattern| NamePattern::new(pattern)).collect(),
+            label_limits: config.label_limits.clone(),
+        }
+    }
+}
+
+impl<R> Layer<R> for CardinalityLayer {
+    type Output = Cardinality<R>;
+
+    fn layer(&self, inner: R) -> Self::Output {
+        Cardinality { inner, layer: self.clone(), seen: Default::default() }
+    }
+}
+
+/// Recorder created by [`CardinalityLayer`].
+#[derive(Debug)]
+pub struct Cardinality<R> {
+    inner: R,
+    layer: CardinalityLayer,
+    /// The label values that keep their own series, by metric name and label key.
+    seen: Mutex<HashMap<(String, String), HashSet<String>>>,
+}
+
+impl<R> Cardinality<R> {
+    /// Returns `true` if the metric is recorded.
+    fn is_recorded(&self, name: &str) -> bool {
+        let name = sanitize_metric_name(name);
+        (self.layer.allow.is_empty() || self.layer.allow.iter().any(|p| p.matches(&name))) &&
+            !self.layer.deny.iter().any(|p| p.matches(&name))
+    }
+
+    /// Replaces the values of labels above their limit with [`AGGREGATED_LABEL_VALUE`].
+    fn limit_labels(&self, key: &Key) -> Key {
+        if self.layer.label_limits.is_empty() ||
+            !key.labels().any(|label| self.layer.label_limits.contains_key(label.key()))
+        {
+            return key.clone()
+        }
+
+        let mut seen = self.seen.lock().unwrap_or_else(PoisonError::into_inner);
+        let labels = key
+            .labels()
+            .map(|label| {
+                let Some(limit) = self.layer.label_limits.get(label.key()) else {
+                    return label.clone()
+                };
+                let values =
+                    seen.entry((key.name().to_string(), label.key().to_string())).or_default();
+                if values.contains(label.value()) || values.len() < *limit {
+                    values.insert(label.value().to_string());
+                    label.clone()
+                } else {
+                    Label::new(label.key().to_string(), AGGREGATED_LABEL_VALUE)
+                }
+            })
+            .collect::<Vec<_>>();
+        Key::from_parts(key.name().to_string(), labels)
+    }
+}
+
+impl<R: Recorder> Recorder for Cardinality<R> {
+    fn describe_counter(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        if self.is_recorded(key.as_str()) {
+            self.inner.describe_counter(key, unit, description)
+        }
+    }
+
+    fn describe_gauge(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        if self.is_recorded(key.as_str()) {
+            self.inner.describe_gauge(key, unit, description)
+        }
+    }
+
+    fn describe_histogram(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        if self.is_recorded(key.as_str()) {
+            self.inner.describe_histogram(key, unit, description)
+        }
+    }
+
+    fn register_counter(&self, key: &Key, metadata: &Metadata<'_>) -> Counter {
+        if !self.is_recorded(key.name()) {
+            return Counter::noop()
+        }
+        self.inner.register_counter(&self.limit_labels(key), metadata)
+    }
+
+    fn register_gauge(&self, key: &Key, metadata: &Metadata<'_>) -> Gauge {
+        if !self.is_recorded(key.name()) {
+            return Gauge::noop()
+        }
+        self.inner.register_gauge(&self.limit_labels(key), metadata)
+    }
+
+    fn register_histogram(&self, key: &Key, metadata: &Metadata<'_>) -> Histogram {
+        if !self.is_recorded(key.name()) {
+            return Histogram::noop()
+        }
+        self.inner.register_histogram(&self.limit_labels(key), metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metrics_exporter_prometheus::PrometheusBuilder;
+
+    #[test]
+    fn limits_metrics_and_labels() {
+        let config = MetricsConfig {
+            allow: vec!["reth_network_*".to_string()],
+            deny: vec!["reth_network_denied".to_string()],
+            label_limits: BTreeMap::from([("peer_id".to_string(), 2)]),
+            ..Default::default()
+        };
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let recorder = CardinalityLayer::new(&config).layer(recorder);
+
+        metrics::with_local_recorder(&recorder, || {
+            for peer in ["a", "b", "c", "d", "a"] {
+                metrics::counter!("reth.network.messages", "peer_id" => peer).increment(1);
+            }
+            metrics::counter!("reth.network.denied").increment(1);
+            metrics::counter!("reth.sync.blocks").increment(1);
+        });
+
+        let rendered = handle.render();
+        assert!(rendered.contains(r#"reth_network_messages{peer_id="a"} 2"#), "{rendered}");
+        assert!(rendered.contains(r#"reth_network_messages{peer_id="b"} 1"#), "{rendered}");
+        assert!(rendered.contains(r#"reth_network_messages{peer_id="other"} 2"#), "{rendered}");
+        assert!(!rendered.contains("reth_network_denied"), "{rendered}");
+        assert!(!rendered.contains("reth_sync_blocks"), "{rendered}");
+    }
+}
@@ -7,6 +7,7 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod cardinality;
 /// The metrics hooks for prometheus.
 pub mod hooks;
 pub mod recorder;
@@ -1,19 +1,34 @@
 //! Prometheus recorder
 
+use crate::cardinality::CardinalityLayer;
 use eyre::WrapErr;
-use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
 use metrics_util::layers::{PrefixLayer, Stack};
-use std::sync::LazyLock;
+use reth_config::MetricsConfig;
+use std::sync::OnceLock;
 
 /// Installs the Prometheus recorder as the global recorder.
 pub fn install_prometheus_recorder() -> &'static PrometheusHandle {
-    &PROMETHEUS_RECORDER_HANDLE
+    PROMETHEUS_RECORDER_HANDLE
+        .get_or_init(|| PrometheusRecorder::install(&MetricsConfig::default()).unwrap())
+}
+
+/// Installs the Prometheus recorder with the given [`MetricsConfig`] as the global recorder.
+///
+/// The config is ignored if the recorder is already installed.
+pub fn install_prometheus_recorder_with(
+    config: &MetricsConfig,
+) -> eyre::Result<&'static PrometheusHandle> {
+    if let Some(handle) = PROMETHEUS_RECORDER_HANDLE.get() {
+        return Ok(handle)
+    }
+    let handle = PrometheusRecorder::install(config)?;
+    Ok(PROMETHEUS_RECORDER_HANDLE.get_or_init(|| handle))
 }
 
 /// The default Prometheus recorder handle. We use a global static to ensure that it is only
 /// installed once.
-static PROMETHEUS_RECORDER_HANDLE: LazyLock<PrometheusHandle> =
-    LazyLock::new(|| PrometheusRecorder::install().unwrap());
+static PROMETHEUS_RECORDER_HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
 
 /// Prometheus recorder installer
 #[derive(Debug)]
@@ -21,12 +36,26 @@ pub struct PrometheusRecorder;
 
 impl PrometheusRecorder {
     /// Installs Prometheus as the metrics recorder.
-    pub fn install() -> eyre::Result<PrometheusHandle> {
-        let recorder = PrometheusBuilder::new().build_recorder();
+    ///
+    /// Metrics that are not allowed by the config are dropped and the values of labels above
+    /// their limit are aggregated, see [`CardinalityLayer`].
+    pub fn install(config: &MetricsConfig) -> eyre::Result<PrometheusHandle> {
+        let mut builder = PrometheusBuilder::new();
+        for (name, buckets) in &config.buckets {
+            let matcher = match name.strip_suffix('*') {
+                Some(prefix) => Matcher::Prefix(prefix.to_string()),
+                None => Matcher::Full(name.clone()),
+            };
+            builder = builder
+                .set_buckets_for_metric(matcher, buckets)
+                .wrap_err_with(|| format!("Invalid buckets of metric `{name}`"))?;
+        }
+        let recorder = builder.build_recorder();
         let handle = recorder.handle();
 
         // Build metrics stack
         Stack::new(recorder)
+            .push(CardinalityLayer::new(config))
             .push(PrefixLayer::new("reth"))
             .install()
             .wrap_err("Couldn't set metrics recorder.")?;
@@ -45,14 +74,13 @@ mod tests {
     // `metrics-exporter-prometheus` dependency version.
     #[test]
     fn process_metrics() {
-        // initialize the lazy handle
-        let _ = &*PROMETHEUS_RECORDER_HANDLE;
+        let handle = install_prometheus_recorder();
 
         let process = metrics_process::Collector::default();
         process.describe();
         process.collect();
 
-        let metrics = PROMETHEUS_RECORDER_HANDLE.render();
+        let metrics = handle.render();
         assert!(metrics.contains("process_cpu_seconds_total"), "{metrics:?}");
     }
 }
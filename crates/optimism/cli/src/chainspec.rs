@@ -1,13 +1,16 @@
 use alloy_genesis::Genesis;
 use clap::{builder::TypedValueParser, error::Result, Arg, Command};
-use reth_chainspec::{ChainSpec, BASE_MAINNET, BASE_SEPOLIA, DEV, OP_MAINNET, OP_SEPOLIA};
+use reth_chainspec::{
+    ChainSpec, ChainSpecFile, BASE_MAINNET, BASE_SEPOLIA, DEV, OP_MAINNET, OP_SEPOLIA,
+};
 use reth_cli::chainspec::ChainSpecParser;
 use std::{ffi::OsStr, fs, path::PathBuf, sync::Arc};
 
 /// Clap value parser for [`ChainSpec`]s.
 ///
-/// The value parser matches either a known chain, the path
-/// to a json file, or a json formatted string in-memory. The json needs to be a Genesis struct.
+/// The value parser matches either a known chain, the path to a TOML chain file (see
+/// [`ChainSpecFile`]), the path to a json file, or a json formatted string in-memory. The json
+/// needs to be a Genesis struct.
 fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error> {
     Ok(match s {
         "dev" => DEV.clone(),
@@ -16,8 +19,15 @@ fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error> {
         "base" => BASE_MAINNET.clone(),
         "base_sepolia" | "base-sepolia" => BASE_SEPOLIA.clone(),
         _ => {
+            let path = PathBuf::from(shellexpand::full(s)?.into_owned());
+
+            // chain files define the genesis and the hardfork schedule
+            if path.extension().is_some_and(|extension| extension == "toml") {
+                return Ok(Arc::new(ChainSpecFile::load(&path)?.chain_spec()?))
+            }
+
             // try to read json from path first
-            let raw = match fs::read_to_string(PathBuf::from(shellexpand::full(s)?.into_owned())) {
+            let raw = match fs::read_to_string(path) {
                 Ok(raw) => raw,
                 Err(io_err) => {
                     // valid json may start with "\n", but must contain "{"
@@ -15,8 +15,10 @@ use reth_optimism_consensus::validate_block_post_execution;
 use reth_primitives::{BlockNumber, BlockWithSenders, Header, Receipt, Receipts, TxType, U256};
 use reth_prune_types::PruneModes;
 use reth_revm::{
-    batch::BlockBatchRecord, db::states::bundle_state::BundleRetention,
-    state_change::post_block_balance_increments, Evm, State,
+    batch::BlockBatchRecord,
+    db::{states::bundle_state::BundleRetention, BundleState},
+    state_change::post_block_balance_increments,
+    Evm, State,
 };
 use revm_primitives::{
     db::{Database, DatabaseCommit},
@@ -411,7 +413,7 @@ where
         self.executor.state.merge_transitions(retention);
 
         // store receipts in the set
-        self.batch_record.save_receipts(receipts)?;
+        self.batch_record.save_receipts(block, receipts)?;
 
         Ok(())
     }
@@ -436,6 +438,10 @@ where
     fn size_hint(&self) -> Option<usize> {
         Some(self.executor.state.bundle_state.size_hint())
     }
+
+    fn bundle_state(&self) -> Option<&BundleState> {
+        Some(&self.executor.state.bundle_state)
+    }
 }
 
 #[cfg(test)]
@@ -2,14 +2,35 @@
 
 //! clap [Args](clap::Args) for optimism rollup configuration
 
+use crate::forwarder::SequencerForwarderConfig;
+use reth_cli_util::parse_duration_from_secs_or_ms;
+use std::time::Duration;
+
 /// Parameters for rollup configuration
-#[derive(Debug, Clone, Default, PartialEq, Eq, clap::Args)]
+#[derive(Debug, Clone, PartialEq, Eq, clap::Args)]
 #[command(next_help_heading = "Rollup")]
 pub struct RollupArgs {
     /// HTTP endpoint for the sequencer mempool
     #[arg(long = "rollup.sequencer-http", value_name = "HTTP_URL")]
     pub sequencer_http: Option<String>,
 
+    /// Maximum number of transactions waiting to be forwarded to the sequencer.
+    ///
+    /// Once the queue is full, `eth_sendRawTransaction` returns an error.
+    #[arg(long = "rollup.sequencer-queue-size", default_value_t = 1024, value_name = "COUNT")]
+    pub sequencer_queue_size: usize,
+
+    /// Maximum number of retries of a transaction that could not be forwarded to the sequencer.
+    #[arg(long = "rollup.sequencer-max-retries", default_value_t = 3, value_name = "COUNT")]
+    pub sequencer_max_retries: u32,
+
+    /// Interval at which the sequencer endpoint is probed.
+    ///
+    /// While the sequencer is unreachable, `eth_sendRawTransaction` returns an error. Specified in
+    /// seconds or in milliseconds if the value ends with `ms`.
+    #[arg(long = "rollup.sequencer-health-interval", value_parser = parse_duration_from_secs_or_ms, default_value = "10", value_name = "DURATION")]
+    pub sequencer_health_interval: Duration,
+
     /// Disable transaction pool gossip
     #[arg(long = "rollup.disable-tx-pool-gossip")]
     pub disable_txpool_gossip: bool,
@@ -39,6 +60,34 @@ pub struct RollupArgs {
     pub experimental: bool,
 }
 
+impl RollupArgs {
+    /// Returns the settings for forwarding transactions to the sequencer.
+    pub fn sequencer_forwarder_config(&self) -> SequencerForwarderConfig {
+        SequencerForwarderConfig {
+            queue_size: self.sequencer_queue_size,
+            max_retries: self.sequencer_max_retries,
+            health_check_interval: self.sequencer_health_interval,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for RollupArgs {
+    fn default() -> Self {
+        Self {
+            sequencer_http: None,
+            sequencer_queue_size: 1024,
+            sequencer_max_retries: 3,
+            sequencer_health_interval: Duration::from_secs(10),
+            disable_txpool_gossip: false,
+            enable_genesis_walkback: false,
+            compute_pending_block: false,
+            discovery_v4: false,
+            experimental: false,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -57,4 +106,24 @@ mod tests {
         let args = CommandParser::<RollupArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_sequencer_forwarder_args() {
+        let args = CommandParser::<RollupArgs>::parse_from([
+            "reth",
+            "--rollup.sequencer-http",
+            "http://localhost:8545",
+            "--rollup.sequencer-queue-size",
+            "64",
+            "--rollup.sequencer-max-retries",
+            "5",
+            "--rollup.sequencer-health-interval",
+            "500ms",
+        ])
+        .args;
+        let config = args.sequencer_forwarder_config();
+        assert_eq!(config.queue_size, 64);
+        assert_eq!(config.max_retries, 5);
+        assert_eq!(config.health_check_interval, Duration::from_millis(500));
+    }
 }
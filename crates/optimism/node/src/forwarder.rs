@@ -0,0 +1,229 @@
+//! Queued forwarding of raw transactions to the sequencer.
+
+use crate::rpc::{SequencerClient, SequencerRpcError};
+use futures::{stream::FuturesUnordered, StreamExt};
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
+use reth_primitives::Bytes;
+use reth_rpc_eth_api::RawTransactionForwarder;
+use reth_rpc_eth_types::error::EthResult;
+use reth_tasks::TaskSpawner;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tokio::sync::mpsc;
+use tracing::{debug, info, trace, warn};
+
+/// Settings for forwarding transactions to the sequencer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequencerForwarderConfig {
+    /// Maximum number of transactions waiting to be forwarded.
+    pub queue_size: usize,
+    /// Maximum number of transactions that are forwarded concurrently.
+    pub max_concurrent_forwards: usize,
+    /// Maximum number of retries of a failed forward.
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for every further retry.
+    pub initial_backoff: Duration,
+    /// Maximum delay between retries.
+    pub max_backoff: Duration,
+    /// Interval at which the sequencer endpoint is probed.
+    pub health_check_interval: Duration,
+}
+
+impl Default for SequencerForwarderConfig {
+    fn default() -> Self {
+        Self {
+            queue_size: 1024,
+            max_concurrent_forwards: 32,
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(3),
+            health_check_interval: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Forwards raw transactions to the sequencer from a bounded queue.
+///
+/// Transactions are forwarded by a background task that retries failed forwards with exponential
+/// backoff. Another task periodically probes the sequencer endpoint. While the sequencer is
+/// unreachable or the queue is full, forwarding fails right away, so that the error is returned to
+/// the sender of the transaction instead of dropping the transaction later.
+#[derive(Debug, Clone)]
+pub struct SequencerForwarder {
+    /// Sender half of the forwarding queue.
+    to_forwarder: mpsc::Sender<Bytes>,
+    /// Result of the last health check.
+    healthy: Arc<AtomicBool>,
+    /// Forwarding metrics.
+    metrics: SequencerForwarderMetrics,
+}
+
+impl SequencerForwarder {
+    /// Spawns the forwarding and health check tasks for the given client.
+    pub fn spawn(
+        client: SequencerClient,
+        config: SequencerForwarderConfig,
+        executor: &dyn TaskSpawner,
+    ) -> Self {
+        let (to_forwarder, queue) = mpsc::channel(config.queue_size.max(1));
+        let healthy = Arc::new(AtomicBool::new(true));
+        let metrics = SequencerForwarderMetrics::default();
+        metrics.healthy.set(1.0);
+
+        executor.spawn(Box::pin(check_health(
+            client.clone(),
+            config.health_check_interval,
+            healthy.clone(),
+            metrics.clone(),
+        )));
+        executor.spawn(Box::pin(forward_transactions(client, config, queue, metrics.clone())));
+
+        Self { to_forwarder, healthy, metrics }
+    }
+
+    /// Returns `true` if the last health check of the sequencer succeeded.
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    /// Queues the transaction for forwarding to the sequencer.
+    pub fn queue_transaction(&self, tx: Bytes) -> Result<(), SequencerRpcError> {
+        if !self.is_healthy() {
+            self.metrics.dropped_transactions.increment(1);
+            return Err(SequencerRpcError::Unavailable)
+        }
+        self.to_forwarder.try_send(tx).map_err(|err| {
+            self.metrics.dropped_transactions.increment(1);
+            match err {
+                mpsc::error::TrySendError::Full(_) => SequencerRpcError::QueueFull,
+                mpsc::error::TrySendError::Closed(_) => SequencerRpcError::Unavailable,
+            }
+        })?;
+        self.metrics.queued_transactions.increment(1.0);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl RawTransactionForwarder for SequencerForwarder {
+    async fn forward_raw_transaction(&self, tx: &[u8]) -> EthResult<()> {
+        self.queue_transaction(Bytes::copy_from_slice(tx))?;
+        Ok(())
+    }
+}
+
+/// Forwards queued transactions until the queue is closed.
+async fn forward_transactions(
+    client: SequencerClient,
+    config: SequencerForwarderConfig,
+    mut queue: mpsc::Receiver<Bytes>,
+    metrics: SequencerForwarderMetrics,
+) {
+    let mut in_flight = FuturesUnordered::new();
+    loop {
+        tokio::select! {
+            tx = queue.recv(), if in_flight.len() < config.max_concurrent_forwards.max(1) => {
+                let Some(tx) = tx else { break };
+                metrics.queued_transactions.decrement(1.0);
+                in_flight.push(forward_with_retries(&client, &config, &metrics, tx));
+            }
+            Some(()) = in_flight.next() => {}
+        }
+    }
+    while in_flight.next().await.is_some() {}
+}
+
+/// Forwards the transaction, retrying failed forwards with exponential backoff.
+async fn forward_with_retries(
+    client: &SequencerClient,
+    config: &SequencerForwarderConfig,
+    metrics: &SequencerForwarderMetrics,
+    tx: Bytes,
+) {
+    let started_at = Instant::now();
+    let mut backoff = config.initial_backoff;
+    let mut retries = 0;
+    loop {
+        match client.forward_raw_transaction(&tx).await {
+            Ok(()) => {
+                metrics.forwarded_transactions.increment(1);
+                metrics.forward_duration_seconds.record(started_at.elapsed());
+                return
+            }
+            Err(err) if !err.is_retryable() => {
+                debug!(target: "rpc::eth", %err, "Sequencer rejected forwarded transaction");
+                metrics.rejected_transactions.increment(1);
+                return
+            }
+            Err(err) if retries < config.max_retries => {
+                retries += 1;
+                trace!(target: "rpc::eth", %err, retries, ?backoff, "Retrying forward to sequencer");
+                metrics.retried_forwards.increment(1);
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(config.max_backoff);
+            }
+            Err(err) => {
+                warn!(target: "rpc::eth", %err, retries, "Dropping transaction that could not be forwarded to sequencer");
+                metrics.failed_forwards.increment(1);
+                return
+            }
+        }
+    }
+}
+
+/// Periodically probes the sequencer endpoint.
+async fn check_health(
+    client: SequencerClient,
+    interval: Duration,
+    healthy: Arc<AtomicBool>,
+    metrics: SequencerForwarderMetrics,
+) {
+    let mut interval = tokio::time::interval(interval);
+    loop {
+        interval.tick().await;
+        let result = client.check_health().await;
+        let is_healthy = result.is_ok();
+        if healthy.swap(is_healthy, Ordering::Relaxed) != is_healthy {
+            match result {
+                Ok(()) => {
+                    info!(target: "rpc::eth", endpoint = client.endpoint(), "Sequencer is reachable again")
+                }
+                Err(err) => {
+                    warn!(target: "rpc::eth", %err, endpoint = client.endpoint(), "Sequencer is unreachable, rejecting transactions")
+                }
+            }
+        }
+        metrics.healthy.set(is_healthy as u8 as f64);
+    }
+}
+
+/// Metrics for forwarding transactions to the sequencer.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "optimism.sequencer")]
+struct SequencerForwarderMetrics {
+    /// Number of transactions waiting to be forwarded
+    queued_transactions: Gauge,
+    /// Number of transactions forwarded to the sequencer
+    forwarded_transactions: Counter,
+    /// Number of transactions the sequencer responded to with an error
+    rejected_transactions: Counter,
+    /// Number of transactions that could not be forwarded after all retries
+    failed_forwards: Counter,
+    /// Number of retried forwards
+    retried_forwards: Counter,
+    /// Number of transactions that were not queued, because the queue was full or the sequencer
+    /// was unreachable
+    dropped_transactions: Counter,
+    /// Whether the last health check of the sequencer succeeded
+    healthy: Gauge,
+    /// Time to forward a transaction to the sequencer, including retries
+    forward_duration_seconds: Histogram,
+}
@@ -24,6 +24,8 @@ pub mod txpool;
 
 pub mod rpc;
 
+pub mod forwarder;
+
 pub use reth_optimism_payload_builder::{
     OptimismBuiltPayload, OptimismPayloadBuilder, OptimismPayloadBuilderAttributes,
 };
@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
 use reth_chainspec::ChainSpec;
-use reth_evm::ConfigureEvm;
+use reth_evm::{overrides::EvmConfigWithOverrides, ConfigureEvm};
 use reth_evm_optimism::{OpExecutorProvider, OptimismEvmConfig};
 use reth_network::{NetworkHandle, NetworkManager};
 use reth_node_api::{FullNodeComponents, NodeAddOns};
@@ -121,7 +121,7 @@ impl<Node> ExecutorBuilder<Node> for OptimismExecutorBuilder
 where
     Node: FullNodeTypes,
 {
-    type EVM = OptimismEvmConfig;
+    type EVM = EvmConfigWithOverrides<OptimismEvmConfig>;
     type Executor = OpExecutorProvider<Self::EVM>;
 
     async fn build_evm(
@@ -129,8 +129,9 @@ where
         ctx: &BuilderContext<Node>,
     ) -> eyre::Result<(Self::EVM, Self::Executor)> {
         let chain_spec = ctx.chain_spec();
-        let evm_config = OptimismEvmConfig::default();
-        let executor = OpExecutorProvider::new(chain_spec, evm_config);
+        let evm_config =
+            EvmConfigWithOverrides::new(OptimismEvmConfig::default(), ctx.evm_overrides()?);
+        let executor = OpExecutorProvider::new(chain_spec, evm_config.clone());
 
         Ok((evm_config, executor))
     }
@@ -185,7 +186,9 @@ where
             let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
             let transactions_backup_config =
-                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path)
+                    .with_remote_transactions(ctx.config().txpool.journal_remote_transactions)
+                    .with_journal_interval(ctx.config().txpool.rejournal_interval);
 
             ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
                 "local transactions backup task",
@@ -250,17 +253,21 @@ where
         ctx: &BuilderContext<Node>,
         pool: Pool,
     ) -> eyre::Result<PayloadBuilderHandle<Node::Engine>> {
-        let payload_builder =
-            reth_optimism_payload_builder::OptimismPayloadBuilder::new(self.evm_config)
-                .set_compute_pending_block(self.compute_pending_block);
         let conf = ctx.payload_builder_config();
+        let payload_builder = reth_optimism_payload_builder::OptimismPayloadBuilder::new(
+            EvmConfigWithOverrides::new(self.evm_config, ctx.evm_overrides()?),
+        )
+        .set_compute_pending_block(self.compute_pending_block)
+        .set_local_gas_reserve(conf.local_gas_reserve());
 
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
             .interval(conf.interval())
             .deadline(conf.deadline())
             .max_payload_tasks(conf.max_payload_tasks())
             // no extradata for OP
-            .extradata(Default::default());
+            .extradata(Default::default())
+            .min_improvement_bps(conf.min_improvement_bps())
+            .build_time_budget(conf.build_time_budget());
 
         let payload_generator = BasicPayloadJobGenerator::with_builder(
             ctx.provider().clone(),
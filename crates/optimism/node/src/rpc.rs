@@ -17,6 +17,22 @@ pub enum SequencerRpcError {
     /// Thrown when serializing transaction to forward to sequencer
     #[error("invalid sequencer transaction")]
     InvalidSequencerTransaction,
+    /// The sequencer responded with an error.
+    #[error("sequencer rejected transaction: {0}")]
+    Rejected(String),
+    /// The sequencer is currently unreachable.
+    #[error("sequencer unavailable")]
+    Unavailable,
+    /// Too many transactions are waiting to be forwarded to the sequencer.
+    #[error("sequencer forwarding queue is full")]
+    QueueFull,
+}
+
+impl SequencerRpcError {
+    /// Returns `true` if forwarding the transaction again may succeed.
+    pub const fn is_retryable(&self) -> bool {
+        matches!(self, Self::HttpError(_) | Self::Unavailable)
+    }
 }
 
 impl ToRpcError for SequencerRpcError {
@@ -70,6 +86,8 @@ impl SequencerClient {
     }
 
     /// Forwards a transaction to the sequencer endpoint.
+    ///
+    /// Returns [`SequencerRpcError::Rejected`] if the sequencer responded with an error.
     pub async fn forward_raw_transaction(&self, tx: &[u8]) -> Result<(), SequencerRpcError> {
         let body = serde_json::to_string(&serde_json::json!({
             "jsonrpc": "2.0",
@@ -85,20 +103,46 @@ impl SequencerClient {
             SequencerRpcError::InvalidSequencerTransaction
         })?;
 
-        self.http_client()
+        self.send_request(body).await.inspect_err(|err| {
+            tracing::warn!(
+                target = "rpc::eth",
+                %err,
+                "Failed to forward transaction to sequencer",
+            );
+        })
+    }
+
+    /// Checks whether the sequencer endpoint responds to requests.
+    pub async fn check_health(&self) -> Result<(), SequencerRpcError> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "eth_chainId",
+            "params": [],
+            "id": self.next_request_id()
+        })
+        .to_string();
+        self.send_request(body).await
+    }
+
+    /// Sends the JSON-RPC request to the sequencer endpoint and checks the response for errors.
+    async fn send_request(&self, body: String) -> Result<(), SequencerRpcError> {
+        let response = self
+            .http_client()
             .post(self.endpoint())
             .header(reqwest::header::CONTENT_TYPE, "application/json")
             .body(body)
             .send()
-            .await
-            .inspect_err(|err| {
-                tracing::warn!(
-                    target = "rpc::eth",
-                    %err,
-                    "Failed to forward transaction to sequencer",
-                );
-            })
-            .map_err(SequencerRpcError::HttpError)?;
+            .await?
+            .error_for_status()?
+            .json::<serde_json::Value>()
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            let message = error.get("message").and_then(|msg| msg.as_str());
+            return Err(SequencerRpcError::Rejected(
+                message.map_or_else(|| error.to_string(), str::to_string),
+            ))
+        }
 
         Ok(())
     }
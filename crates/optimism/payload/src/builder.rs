@@ -35,12 +35,14 @@ pub struct OptimismPayloadBuilder<EvmConfig> {
     compute_pending_block: bool,
     /// The type responsible for creating the evm.
     evm_config: EvmConfig,
+    /// Gas of each block that is reserved for local transactions.
+    local_gas_reserve: u64,
 }
 
 impl<EvmConfig> OptimismPayloadBuilder<EvmConfig> {
     /// `OptimismPayloadBuilder` constructor.
     pub const fn new(evm_config: EvmConfig) -> Self {
-        Self { compute_pending_block: true, evm_config }
+        Self { compute_pending_block: true, evm_config, local_gas_reserve: 0 }
     }
 
     /// Sets the rollup's compute pending block configuration option.
@@ -58,6 +60,12 @@ impl<EvmConfig> OptimismPayloadBuilder<EvmConfig> {
     pub const fn is_compute_pending_block(&self) -> bool {
         self.compute_pending_block
     }
+
+    /// Sets the amount of gas of each block that is reserved for local transactions.
+    pub const fn set_local_gas_reserve(mut self, local_gas_reserve: u64) -> Self {
+        self.local_gas_reserve = local_gas_reserve;
+        self
+    }
 }
 
 /// Implementation of the [`PayloadBuilder`] trait for [`OptimismPayloadBuilder`].
@@ -74,7 +82,12 @@ where
         &self,
         args: BuildArguments<Pool, Client, OptimismPayloadBuilderAttributes, OptimismBuiltPayload>,
     ) -> Result<BuildOutcome<OptimismBuiltPayload>, PayloadBuilderError> {
-        optimism_payload_builder(self.evm_config.clone(), args, self.compute_pending_block)
+        optimism_payload_builder(
+            self.evm_config.clone(),
+            args,
+            self.compute_pending_block,
+            self.local_gas_reserve,
+        )
     }
 
     fn on_missing_payload(
@@ -235,6 +248,7 @@ pub(crate) fn optimism_payload_builder<EvmConfig, Pool, Client>(
     evm_config: EvmConfig,
     args: BuildArguments<Pool, Client, OptimismPayloadBuilderAttributes, OptimismBuiltPayload>,
     _compute_pending_block: bool,
+    local_gas_reserve: u64,
 ) -> Result<BuildOutcome<OptimismBuiltPayload>, PayloadBuilderError>
 where
     EvmConfig: ConfigureEvm,
@@ -413,9 +427,20 @@ where
     }
 
     if !attributes.no_tx_pool {
+        let mut local_gas_used = 0;
         while let Some(pool_tx) = best_txs.next() {
+            // non-local transactions can't use the gas that is still reserved for local
+            // transactions
+            let reserved_gas = if pool_tx.origin.is_local() {
+                0
+            } else {
+                local_gas_reserve.saturating_sub(local_gas_used)
+            };
+
             // ensure we still have capacity for this transaction
-            if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
+            if cumulative_gas_used + pool_tx.gas_limit() >
+                block_gas_limit.saturating_sub(reserved_gas)
+            {
                 // we can't fit this transaction into the block, so we need to mark it as
                 // invalid which also removes all dependent transaction from
                 // the iterator before we can continue
@@ -479,6 +504,9 @@ where
             // add gas used by the transaction to cumulative gas used, before creating the
             // receipt
             cumulative_gas_used += gas_used;
+            if pool_tx.origin.is_local() {
+                local_gas_used += gas_used;
+            }
 
             // Push transaction changeset and calculate header bloom filter for receipt.
             receipts.push(Some(Receipt {
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use reth_evm::ConfigureEvm;
 use reth_node_api::FullNodeComponents;
 use reth_primitives::{
@@ -31,6 +33,11 @@ where
         self.inner.gas_cap()
     }
 
+    #[inline]
+    fn call_timeout(&self) -> Option<Duration> {
+        self.inner.limits().call_timeout()
+    }
+
     #[inline]
     fn evm_config(&self) -> &impl ConfigureEvm {
         self.inner.evm_config()
@@ -30,9 +30,10 @@ use reth_rpc_eth_api::{
     EthApiTypes,
 };
 use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle};
+use reth_stages_types::SyncProgress;
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
-    TaskSpawner,
+    TaskPriority, TaskSpawner,
 };
 use reth_transaction_pool::TransactionPool;
 
@@ -66,8 +67,8 @@ impl<N: FullNodeComponents> OpEthApi<N> {
     /// Creates a new instance for given context.
     #[allow(clippy::type_complexity)]
     pub fn with_spawner(ctx: &EthApiBuilderCtx<N>) -> Self {
-        let blocking_task_pool =
-            BlockingTaskPool::build().expect("failed to build blocking task pool");
+        let blocking_task_pool = BlockingTaskPool::build_with_priority(TaskPriority::Rpc)
+            .expect("failed to build blocking task pool");
 
         let inner = EthApiInner::new(
             ctx.provider.clone(),
@@ -75,7 +76,7 @@ impl<N: FullNodeComponents> OpEthApi<N> {
             ctx.network.clone(),
             ctx.cache.clone(),
             ctx.new_gas_price_oracle(),
-            ctx.config.rpc_gas_cap,
+            ctx.limits.clone(),
             ctx.config.eth_proof_window,
             blocking_task_pool,
             ctx.new_fee_history_cache(),
@@ -83,7 +84,8 @@ impl<N: FullNodeComponents> OpEthApi<N> {
             ctx.executor.clone(),
             None,
             ctx.config.proof_permits,
-        );
+        )
+        .with_sync_progress(ctx.sync_progress.clone());
 
         Self { inner: Arc::new(inner) }
     }
@@ -125,6 +127,11 @@ where
     fn signers(&self) -> &parking_lot::RwLock<Vec<Box<dyn EthSigner>>> {
         self.inner.signers()
     }
+
+    #[inline]
+    fn sync_progress(&self) -> &SyncProgress {
+        self.inner.sync_progress()
+    }
 }
 
 impl<N> SpawnBlocking for OpEthApi<N>
@@ -230,6 +237,10 @@ impl<N: FullNodeComponents> AddDevSigners for OpEthApi<N> {
     fn with_dev_accounts(&self) {
         *self.signers().write() = DevSigner::random_signers(20)
     }
+
+    fn with_dev_signers(&self, signers: Vec<Box<dyn EthSigner>>) {
+        *self.signers().write() = signers
+    }
 }
 
 impl<N> BuilderProvider<N> for OpEthApi<N>
@@ -18,7 +18,9 @@ use reth_payload_builder::{
 };
 use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes};
 use reth_primitives::{
-    constants::{EMPTY_WITHDRAWALS, RETH_CLIENT_VERSION, SLOT_DURATION},
+    constants::{
+        eip4844::DATA_GAS_PER_BLOB, EMPTY_WITHDRAWALS, RETH_CLIENT_VERSION, SLOT_DURATION,
+    },
     proofs, BlockNumberOrTag, Bytes, SealedBlock, Withdrawals, B256, U256,
 };
 use reth_provider::{
@@ -26,7 +28,7 @@ use reth_provider::{
 };
 use reth_revm::state_change::post_block_withdrawals_balance_increments;
 use reth_tasks::TaskSpawner;
-use reth_transaction_pool::TransactionPool;
+use reth_transaction_pool::{NewBlobSidecar, TransactionPool};
 use revm::{
     primitives::{BlockEnv, CfgEnvWithHandlerCfg},
     Database, State,
@@ -36,12 +38,15 @@ use std::{
     future::Future,
     ops::Deref,
     pin::Pin,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
     task::{Context, Poll},
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::{
-    sync::{oneshot, Semaphore},
+    sync::{mpsc, oneshot, Semaphore},
     time::{Interval, Sleep},
 };
 use tracing::{debug, trace, warn};
@@ -192,6 +197,17 @@ where
             payload_task_guard: self.payload_task_guard.clone(),
             metrics: Default::default(),
             builder: self.builder.clone(),
+            blob_target: self.config.blob_target,
+            blob_sidecars: self
+                .config
+                .blob_target
+                .map(|_| self.pool.blob_transaction_sidecars_listener()),
+            rebuild_for_blobs: false,
+            build_attempts: 0,
+            better_payloads: 0,
+            min_improvement_bps: self.config.min_improvement_bps,
+            build_time_budget: self.config.build_time_budget,
+            build_time: Default::default(),
         };
 
         // start the first job right away
@@ -265,6 +281,13 @@ pub struct BasicPayloadJobGeneratorConfig {
     deadline: Duration,
     /// Maximum number of tasks to spawn for building a payload.
     max_payload_tasks: usize,
+    /// Number of blobs a payload should contain, see
+    /// [`BasicPayloadJobGeneratorConfig::blob_target`].
+    blob_target: Option<u64>,
+    /// Minimum fee improvement in basis points for a new payload to replace the best payload.
+    min_improvement_bps: u64,
+    /// Total time a job may spend building payloads.
+    build_time_budget: Option<Duration>,
 }
 
 // === impl BasicPayloadJobGeneratorConfig ===
@@ -300,6 +323,35 @@ impl BasicPayloadJobGeneratorConfig {
         self.extradata = extradata;
         self
     }
+
+    /// Sets the number of blobs a payload should contain.
+    ///
+    /// If set, a job rebuilds its payload as soon as new blob transactions arrive in the pool
+    /// while the best payload so far contains fewer blobs, instead of waiting for the next
+    /// interval.
+    pub const fn blob_target(mut self, blob_target: Option<u64>) -> Self {
+        self.blob_target = blob_target;
+        self
+    }
+
+    /// Sets the minimum fee improvement, in basis points of the best payload's fees, a newly built
+    /// payload must have to replace the best payload.
+    ///
+    /// By default any payload with higher fees replaces the best payload.
+    pub const fn min_improvement_bps(mut self, min_improvement_bps: u64) -> Self {
+        self.min_improvement_bps = min_improvement_bps;
+        self
+    }
+
+    /// Sets the total time a job may spend building payloads.
+    ///
+    /// A job keeps improving its payload until the deadline, but no new build attempts are
+    /// started once the build attempts of the job took this long in total. By default there is no
+    /// limit.
+    pub const fn build_time_budget(mut self, build_time_budget: Option<Duration>) -> Self {
+        self.build_time_budget = build_time_budget;
+        self
+    }
 }
 
 impl Default for BasicPayloadJobGeneratorConfig {
@@ -310,6 +362,9 @@ impl Default for BasicPayloadJobGeneratorConfig {
             // 12s slot time
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            blob_target: None,
+            min_improvement_bps: 0,
+            build_time_budget: None,
         }
     }
 }
@@ -349,6 +404,22 @@ where
     ///
     /// See [`PayloadBuilder`]
     builder: Builder,
+    /// Number of blobs the payload should contain.
+    blob_target: Option<u64>,
+    /// Receives the sidecars of new blob transactions, if a `blob_target` is configured.
+    blob_sidecars: Option<mpsc::Receiver<NewBlobSidecar>>,
+    /// Whether new blob transactions arrived that warrant a new build attempt.
+    rebuild_for_blobs: bool,
+    /// Number of build attempts of this job.
+    build_attempts: u64,
+    /// Number of build attempts of this job that produced a better payload.
+    better_payloads: u64,
+    /// Minimum fee improvement in basis points for a new payload to replace the best payload.
+    min_improvement_bps: u64,
+    /// Total time this job may spend building payloads.
+    build_time_budget: Option<Duration>,
+    /// Total time in nanoseconds the build attempts of this job took so far.
+    build_time: Arc<AtomicU64>,
 }
 
 impl<Client, Pool, Tasks, Builder> BasicPayloadJob<Client, Pool, Tasks, Builder>
@@ -372,8 +443,10 @@ where
         let payload_config = self.config.clone();
         let best_payload = self.best_payload.clone();
         self.metrics.inc_initiated_payload_builds();
+        self.build_attempts += 1;
         let cached_reads = self.cached_reads.take().unwrap_or_default();
         let builder = self.builder.clone();
+        let build_time = self.build_time.clone();
         self.executor.spawn_blocking(Box::pin(async move {
             // acquire the permit for executing the task
             let _permit = guard.acquire().await;
@@ -385,12 +458,37 @@ where
                 cancel,
                 best_payload,
             };
+            let started_at = Instant::now();
             let result = builder.try_build(args);
+            build_time.fetch_add(started_at.elapsed().as_nanos() as u64, Ordering::Relaxed);
             let _ = tx.send(result);
         }));
 
         self.pending_block = Some(PendingPayload { _cancel, payload: rx });
     }
+
+    /// Returns `true` if the build attempts of this job used up the configured build time budget.
+    fn is_build_time_budget_exhausted(&self) -> bool {
+        self.build_time_budget.is_some_and(|budget| {
+            Duration::from_nanos(self.build_time.load(Ordering::Relaxed)) >= budget
+        })
+    }
+
+    /// Returns `true` if the given payload improves on the best payload so far by at least the
+    /// configured minimum improvement.
+    fn is_sufficient_improvement(&self, payload: &Builder::BuiltPayload) -> bool {
+        let Some(best_payload) = &self.best_payload else { return true };
+        let best_fees = best_payload.fees();
+        let min_improvement = best_fees * U256::from(self.min_improvement_bps) / U256::from(10_000);
+        payload.fees() > best_fees && payload.fees() - best_fees >= min_improvement
+    }
+
+    /// Returns the number of blobs in the best payload so far.
+    fn best_payload_blobs(&self) -> u64 {
+        self.best_payload.as_ref().map_or(0, |payload| {
+            payload.block().blob_gas_used.unwrap_or_default() / DATA_GAS_PER_BLOB
+        })
+    }
 }
 
 impl<Client, Pool, Tasks, Builder> Future for BasicPayloadJob<Client, Pool, Tasks, Builder>
@@ -417,18 +515,54 @@ where
         while this.interval.poll_tick(cx).is_ready() {
             // start a new job if there is no pending block and we haven't reached the deadline
             if this.pending_block.is_none() {
-                this.spawn_build_job();
+                if this.is_build_time_budget_exhausted() {
+                    trace!(target: "payload_builder", "payload build time budget exhausted");
+                } else {
+                    this.spawn_build_job();
+                }
             }
         }
 
+        // check if new blob transactions arrived while the payload is below the blob target
+        if let Some(sidecars) = this.blob_sidecars.as_mut() {
+            let mut new_blobs = false;
+            while let Poll::Ready(Some(_)) = sidecars.poll_recv(cx) {
+                new_blobs = true;
+            }
+            if new_blobs &&
+                this.blob_target.is_some_and(|target| this.best_payload_blobs() < target)
+            {
+                this.rebuild_for_blobs = true;
+            }
+        }
+        if this.rebuild_for_blobs &&
+            this.pending_block.is_none() &&
+            !this.is_build_time_budget_exhausted()
+        {
+            trace!(target: "payload_builder", "new blob transactions arrived, rebuilding payload");
+            this.rebuild_for_blobs = false;
+            this.metrics.inc_blob_triggered_payload_builds();
+            this.spawn_build_job();
+        }
+
         // poll the pending block
         if let Some(mut fut) = this.pending_block.take() {
-            match fut.poll_unpin(cx) {
+            let poll = fut.poll_unpin(cx);
+            if poll.is_ready() && this.rebuild_for_blobs {
+                // a rebuild is due as soon as this attempt finished
+                cx.waker().wake_by_ref();
+            }
+            match poll {
                 Poll::Ready(Ok(outcome)) => match outcome {
                     BuildOutcome::Better { payload, cached_reads } => {
                         this.cached_reads = Some(cached_reads);
-                        debug!(target: "payload_builder", value = %payload.fees(), "built better payload");
-                        this.best_payload = Some(payload);
+                        if this.is_sufficient_improvement(&payload) {
+                            debug!(target: "payload_builder", value = %payload.fees(), "built better payload");
+                            this.better_payloads += 1;
+                            this.best_payload = Some(payload);
+                        } else {
+                            trace!(target: "payload_builder", value = %payload.fees(), "skipped payload below the minimum improvement");
+                        }
                     }
                     BuildOutcome::Aborted { fees, cached_reads } => {
                         this.cached_reads = Some(cached_reads);
@@ -485,6 +619,11 @@ where
     }
 
     fn resolve(&mut self) -> (Self::ResolvePayloadFuture, KeepPayloadJobAlive) {
+        self.metrics.record_resolved_job(
+            self.build_attempts,
+            self.better_payloads,
+            self.best_payload_blobs(),
+        );
         let best_payload = self.best_payload.take();
 
         if best_payload.is_none() && self.pending_block.is_none() {
@@ -660,13 +799,13 @@ pub struct Cancelled(Arc<AtomicBool>);
 impl Cancelled {
     /// Returns true if the job was cancelled.
     pub fn is_cancelled(&self) -> bool {
-        self.0.load(std::sync::atomic::Ordering::Relaxed)
+        self.0.load(Ordering::Relaxed)
     }
 }
 
 impl Drop for Cancelled {
     fn drop(&mut self) {
-        self.0.store(true, std::sync::atomic::Ordering::Relaxed);
+        self.0.store(true, Ordering::Relaxed);
     }
 }
 
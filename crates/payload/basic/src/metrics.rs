@@ -1,6 +1,9 @@
 //! Metrics for the payload builder impl
 
-use reth_metrics::{metrics::Counter, Metrics};
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
 
 /// Transaction pool metrics
 #[derive(Metrics)]
@@ -12,6 +15,14 @@ pub(crate) struct PayloadBuilderMetrics {
     pub(crate) initiated_payload_builds: Counter,
     /// Total number of failed payload build attempts
     pub(crate) failed_payload_builds: Counter,
+    /// Total number of payload build attempts triggered by new blob transactions
+    pub(crate) blob_triggered_payload_builds: Counter,
+    /// Number of build attempts per resolved payload job
+    pub(crate) payload_builds_per_job: Histogram,
+    /// Number of build attempts that produced a better payload per resolved payload job
+    pub(crate) better_payloads_per_job: Histogram,
+    /// Number of blobs in the best payload of a resolved payload job
+    pub(crate) resolved_payload_blobs: Histogram,
 }
 
 impl PayloadBuilderMetrics {
@@ -26,4 +37,14 @@ impl PayloadBuilderMetrics {
     pub(crate) fn inc_failed_payload_builds(&self) {
         self.failed_payload_builds.increment(1);
     }
+
+    pub(crate) fn inc_blob_triggered_payload_builds(&self) {
+        self.blob_triggered_payload_builds.increment(1);
+    }
+
+    pub(crate) fn record_resolved_job(&self, builds: u64, better_payloads: u64, blobs: u64) {
+        self.payload_builds_per_job.record(builds as f64);
+        self.better_payloads_per_job.record(better_payloads as f64);
+        self.resolved_payload_blobs.record(blobs as f64);
+    }
 }
@@ -0,0 +1,172 @@
+//! Payload builder that races the local payload against an external builder.
+
+use crate::{metrics::RelayPayloadBuilderMetrics, RelayClient, RelayClientError, RelayTrustRules};
+use reth_basic_payload_builder::{
+    BuildArguments, BuildOutcome, MissingPayloadBehaviour, PayloadBuilder, PayloadConfig,
+};
+use reth_payload_builder::{
+    error::PayloadBuilderError, EthBuiltPayload, EthPayloadBuilderAttributes,
+};
+use reth_primitives::U256;
+use std::{
+    sync::{mpsc, Arc},
+    time::Duration,
+};
+use tracing::{debug, trace};
+
+/// Default time to wait for the external builder after the local payload was built.
+pub const DEFAULT_RELAY_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Settings for fetching payloads of an external builder.
+#[derive(Debug, Clone)]
+pub struct RelayConfig {
+    /// Client for the external builder.
+    pub client: RelayClient,
+    /// Rules external payloads must satisfy.
+    pub rules: RelayTrustRules,
+    /// How long to wait for the external builder once the local payload was built.
+    pub timeout: Duration,
+}
+
+impl RelayConfig {
+    /// Creates a new config for the given client that accepts payloads of any builder.
+    pub fn new(client: RelayClient) -> Self {
+        Self { client, rules: Default::default(), timeout: DEFAULT_RELAY_TIMEOUT }
+    }
+
+    /// Sets the rules external payloads must satisfy.
+    pub fn with_rules(mut self, rules: RelayTrustRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Sets how long to wait for the external builder once the local payload was built.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// A [`PayloadBuilder`] that serves the payload of an external builder instead of the locally
+/// built payload if it pays more.
+///
+/// Every build attempt fetches the best block submission of the external builder while the local
+/// payload is built. Since the job keeps the best payload across build attempts, local payloads
+/// also need to beat an external payload that was served before.
+///
+/// Without a [`RelayConfig`] this only builds local payloads.
+#[derive(Debug, Clone)]
+pub struct RelayPayloadBuilder<B> {
+    /// The local payload builder.
+    inner: B,
+    /// The external builder, if configured.
+    relay: Option<Arc<RelayConfig>>,
+    /// Metrics for external payloads.
+    metrics: RelayPayloadBuilderMetrics,
+}
+
+impl<B> RelayPayloadBuilder<B> {
+    /// Wraps the given local payload builder.
+    pub fn new(inner: B) -> Self {
+        Self { inner, relay: None, metrics: Default::default() }
+    }
+
+    /// Sets the external builder to race against.
+    pub fn with_relay(mut self, relay: Option<RelayConfig>) -> Self {
+        self.relay = relay.map(Arc::new);
+        self
+    }
+}
+
+impl<B, Pool, Client> PayloadBuilder<Pool, Client> for RelayPayloadBuilder<B>
+where
+    B: PayloadBuilder<
+        Pool,
+        Client,
+        Attributes = EthPayloadBuilderAttributes,
+        BuiltPayload = EthBuiltPayload,
+    >,
+{
+    type Attributes = EthPayloadBuilderAttributes;
+    type BuiltPayload = EthBuiltPayload;
+
+    fn try_build(
+        &self,
+        args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
+    ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
+        let Some(relay) = &self.relay else { return self.inner.try_build(args) };
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return self.inner.try_build(args)
+        };
+
+        let attributes = args.config.attributes.clone();
+        let best_value = args.best_payload.as_ref().map(|payload| payload.fees());
+
+        // fetch the external payload while the local payload is built
+        let (tx, rx) = mpsc::channel();
+        let client = relay.client.clone();
+        let parent_hash = attributes.parent;
+        handle.spawn(async move {
+            let _ = tx.send(client.get_block_submission(parent_hash).await);
+        });
+
+        let outcome = self.inner.try_build(args)?;
+        let local_value = match &outcome {
+            BuildOutcome::Better { payload, .. } => payload.fees(),
+            BuildOutcome::Aborted { .. } => best_value.unwrap_or(U256::ZERO),
+            BuildOutcome::Cancelled => return Ok(outcome),
+        };
+
+        let submission =
+            rx.recv_timeout(relay.timeout).unwrap_or(Err(RelayClientError::Timeout(relay.timeout)));
+        let submission = match submission {
+            Ok(Some(submission)) => submission,
+            Ok(None) => {
+                trace!(target: "payload_builder", ?parent_hash, "no external payload available");
+                return Ok(outcome)
+            }
+            Err(err) => {
+                debug!(target: "payload_builder", %err, url=%relay.client.url(), "failed to fetch external payload");
+                self.metrics.failed_requests.increment(1);
+                return Ok(outcome)
+            }
+        };
+
+        let value = submission.message.value;
+        match relay.rules.check(submission, &attributes, local_value) {
+            Ok(block) => {
+                debug!(target: "payload_builder", block_hash=%block.hash(), %value, %local_value, "serving external payload");
+                self.metrics.selected_external_payloads.increment(1);
+                let cached_reads = match outcome {
+                    BuildOutcome::Better { cached_reads, .. } |
+                    BuildOutcome::Aborted { cached_reads, .. } => cached_reads,
+                    BuildOutcome::Cancelled => Default::default(),
+                };
+                Ok(BuildOutcome::Better {
+                    payload: EthBuiltPayload::new(attributes.id, block, value),
+                    cached_reads,
+                })
+            }
+            Err(err) => {
+                debug!(target: "payload_builder", %err, %value, %local_value, "rejected external payload");
+                self.metrics.rejected_external_payloads.increment(1);
+                Ok(outcome)
+            }
+        }
+    }
+
+    fn on_missing_payload(
+        &self,
+        args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
+    ) -> MissingPayloadBehaviour<EthBuiltPayload> {
+        self.inner.on_missing_payload(args)
+    }
+
+    fn build_empty_payload(
+        &self,
+        client: &Client,
+        config: PayloadConfig<EthPayloadBuilderAttributes>,
+    ) -> Result<EthBuiltPayload, PayloadBuilderError> {
+        self.inner.build_empty_payload(client, config)
+    }
+}
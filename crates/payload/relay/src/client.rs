@@ -0,0 +1,57 @@
+//! Client for fetching block submissions of an external builder.
+
+use reqwest::StatusCode;
+use reth_primitives::B256;
+use reth_rpc_types::beacon::relay::SubmitBlockRequest;
+use url::Url;
+
+/// Fetches the best block submission of an external builder.
+///
+/// The endpoint is queried with the parent block hash as `parent_hash` query parameter and
+/// responds with a [`SubmitBlockRequest`], or `204 No Content` if the builder has no block for the
+/// parent.
+#[derive(Debug, Clone)]
+pub struct RelayClient {
+    client: reqwest::Client,
+    url: Url,
+}
+
+impl RelayClient {
+    /// Creates a new client for the given endpoint.
+    pub fn new(url: Url) -> Self {
+        Self { client: reqwest::Client::new(), url }
+    }
+
+    /// Returns the endpoint of the external builder.
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Fetches the best block submission that builds on the given parent block.
+    pub async fn get_block_submission(
+        &self,
+        parent_hash: B256,
+    ) -> Result<Option<SubmitBlockRequest>, RelayClientError> {
+        let response = self
+            .client
+            .get(self.url.clone())
+            .query(&[("parent_hash", parent_hash.to_string())])
+            .send()
+            .await?;
+        if response.status() == StatusCode::NO_CONTENT {
+            return Ok(None)
+        }
+        Ok(Some(response.error_for_status()?.json().await?))
+    }
+}
+
+/// Errors that can occur when fetching a block submission.
+#[derive(Debug, thiserror::Error)]
+pub enum RelayClientError {
+    /// The request failed or the response couldn't be decoded.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// No response within the configured timeout.
+    #[error("no response from the external builder within {0:?}")]
+    Timeout(std::time::Duration),
+}
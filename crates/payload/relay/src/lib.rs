@@ -0,0 +1,28 @@
+//! Payload builder that races the locally built payload against payloads of an external builder.
+//!
+//! The [`RelayPayloadBuilder`] wraps the local payload builder. For every build attempt it fetches
+//! the best block submission of an external builder with the [`RelayClient`] and serves it instead
+//! of the local payload if it passes the configured [`RelayTrustRules`] and pays more.
+//!
+//! Block submissions use the format builders submit to relays (`SubmitBlockRequest`), because
+//! relays only release full payloads of bids that were signed by the proposer, which requires the
+//! validator key.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod builder;
+pub use builder::{RelayConfig, RelayPayloadBuilder, DEFAULT_RELAY_TIMEOUT};
+
+mod client;
+pub use client::{RelayClient, RelayClientError};
+
+mod trust;
+pub use trust::{RejectedPayload, RelayTrustRules};
+
+mod metrics;
@@ -0,0 +1,15 @@
+//! Metrics for the relay payload builder.
+
+use reth_metrics::{metrics::Counter, Metrics};
+
+/// Relay payload builder metrics
+#[derive(Metrics, Clone)]
+#[metrics(scope = "payloads.relay")]
+pub(crate) struct RelayPayloadBuilderMetrics {
+    /// Number of external payloads that were chosen over the local payload
+    pub(crate) selected_external_payloads: Counter,
+    /// Number of external payloads that were rejected
+    pub(crate) rejected_external_payloads: Counter,
+    /// Number of failed requests to the external builder
+    pub(crate) failed_requests: Counter,
+}
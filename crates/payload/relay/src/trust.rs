@@ -0,0 +1,242 @@
+//! Rules for accepting payloads of an external builder.
+
+use reth_payload_builder::EthPayloadBuilderAttributes;
+use reth_primitives::{Address, SealedBlock, B256, U256};
+use reth_rpc_types::{
+    beacon::{relay::SubmitBlockRequest, BlsPublicKey},
+    engine::PayloadError,
+};
+use reth_rpc_types_compat::engine::payload::try_into_sealed_block;
+use std::collections::HashSet;
+
+/// Rules an external payload must satisfy to be served instead of the local payload.
+///
+/// Regardless of the configuration, the payload must be a valid block for the requested payload
+/// attributes that pays the proposer's fee recipient, and must not contain blob transactions,
+/// because block submissions don't include the blobs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RelayTrustRules {
+    /// Builders whose payloads are accepted, payloads of any builder are accepted if empty.
+    pub trusted_builders: HashSet<BlsPublicKey>,
+    /// Minimum value advantage, in basis points of the local payload's value, the external
+    /// payload must have.
+    pub min_value_advantage_bps: u64,
+}
+
+impl RelayTrustRules {
+    /// Only accepts payloads of the given builders.
+    pub fn with_trusted_builders(
+        mut self,
+        trusted_builders: impl IntoIterator<Item = BlsPublicKey>,
+    ) -> Self {
+        self.trusted_builders = trusted_builders.into_iter().collect();
+        self
+    }
+
+    /// Sets the minimum value advantage in basis points of the local payload's value.
+    pub const fn with_min_value_advantage_bps(mut self, min_value_advantage_bps: u64) -> Self {
+        self.min_value_advantage_bps = min_value_advantage_bps;
+        self
+    }
+
+    /// Checks the given block submission against the payload attributes and the value of the
+    /// local payload, and returns the block of the submission if it should be served instead.
+    pub fn check(
+        &self,
+        submission: SubmitBlockRequest,
+        attributes: &EthPayloadBuilderAttributes,
+        local_value: U256,
+    ) -> Result<SealedBlock, RejectedPayload> {
+        let SubmitBlockRequest { message: bid, execution_payload, .. } = submission;
+
+        if !self.trusted_builders.is_empty() && !self.trusted_builders.contains(&bid.builder_pubkey)
+        {
+            return Err(RejectedPayload::UntrustedBuilder(bid.builder_pubkey))
+        }
+        if bid.proposer_fee_recipient != attributes.suggested_fee_recipient {
+            return Err(RejectedPayload::FeeRecipientMismatch {
+                got: bid.proposer_fee_recipient,
+                expected: attributes.suggested_fee_recipient,
+            })
+        }
+
+        let min_value = local_value +
+            local_value * U256::from(self.min_value_advantage_bps) / U256::from(10_000);
+        if bid.value <= local_value || bid.value < min_value {
+            return Err(RejectedPayload::InsufficientValue { value: bid.value, local_value })
+        }
+
+        let block = try_into_sealed_block(execution_payload, attributes.parent_beacon_block_root)?;
+        if block.hash() != bid.block_hash {
+            return Err(RejectedPayload::BlockHashMismatch {
+                got: block.hash(),
+                bid: bid.block_hash,
+            })
+        }
+        if block.parent_hash != attributes.parent ||
+            bid.parent_hash != attributes.parent ||
+            block.timestamp != attributes.timestamp ||
+            block.mix_hash != attributes.prev_randao ||
+            block.withdrawals.as_ref().is_some_and(|w| *w != attributes.withdrawals)
+        {
+            return Err(RejectedPayload::AttributesMismatch)
+        }
+        if block.blob_gas_used.unwrap_or_default() > 0 {
+            return Err(RejectedPayload::BlobTransactions)
+        }
+
+        Ok(block)
+    }
+}
+
+/// Reasons for not serving an external payload.
+#[derive(Debug, thiserror::Error)]
+pub enum RejectedPayload {
+    /// The payload was built by a builder that isn't trusted.
+    #[error("builder {0} is not trusted")]
+    UntrustedBuilder(BlsPublicKey),
+    /// The payload pays a different fee recipient.
+    #[error("payload pays fee recipient {got}, expected {expected}")]
+    FeeRecipientMismatch {
+        /// The fee recipient of the bid.
+        got: Address,
+        /// The fee recipient of the payload attributes.
+        expected: Address,
+    },
+    /// The payload doesn't pay enough more than the local payload.
+    #[error("payload value {value} doesn't beat the local payload value {local_value}")]
+    InsufficientValue {
+        /// The value of the bid.
+        value: U256,
+        /// The value of the local payload.
+        local_value: U256,
+    },
+    /// The block hash of the bid doesn't match the payload.
+    #[error("block hash {got} doesn't match the bid's block hash {bid}")]
+    BlockHashMismatch {
+        /// The hash of the payload.
+        got: B256,
+        /// The block hash of the bid.
+        bid: B256,
+    },
+    /// The payload doesn't match the payload attributes.
+    #[error("payload doesn't match the payload attributes")]
+    AttributesMismatch,
+    /// The payload contains blob transactions.
+    #[error("payload contains blob transactions")]
+    BlobTransactions,
+    /// The payload is not a valid block.
+    #[error(transparent)]
+    InvalidPayload(#[from] PayloadError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_payload_builder::PayloadId;
+    use reth_primitives::{constants::EMPTY_ROOT_HASH, Block, Header, Withdrawals};
+    use reth_rpc_types::beacon::relay::BidTrace;
+    use reth_rpc_types_compat::engine::payload::block_to_payload;
+
+    fn attributes() -> EthPayloadBuilderAttributes {
+        EthPayloadBuilderAttributes {
+            id: PayloadId::new([0; 8]),
+            parent: B256::with_last_byte(1),
+            timestamp: 12,
+            suggested_fee_recipient: Address::with_last_byte(2),
+            prev_randao: B256::with_last_byte(3),
+            withdrawals: Withdrawals::default(),
+            parent_beacon_block_root: None,
+        }
+    }
+
+    fn submission(attributes: &EthPayloadBuilderAttributes, value: u64) -> SubmitBlockRequest {
+        let block = Block {
+            header: Header {
+                parent_hash: attributes.parent,
+                timestamp: attributes.timestamp,
+                mix_hash: attributes.prev_randao,
+                base_fee_per_gas: Some(7),
+                withdrawals_root: Some(EMPTY_ROOT_HASH),
+                ..Default::default()
+            },
+            withdrawals: Some(Withdrawals::default()),
+            ..Default::default()
+        }
+        .seal_slow();
+        SubmitBlockRequest {
+            message: BidTrace {
+                parent_hash: attributes.parent,
+                block_hash: block.hash(),
+                proposer_fee_recipient: attributes.suggested_fee_recipient,
+                value: U256::from(value),
+                ..Default::default()
+            },
+            execution_payload: block_to_payload(block),
+            signature: Default::default(),
+        }
+    }
+
+    #[test]
+    fn accepts_valid_payload() {
+        let attributes = attributes();
+        let submission = submission(&attributes, 100);
+        let block_hash = submission.message.block_hash;
+        let block =
+            RelayTrustRules::default().check(submission, &attributes, U256::from(99)).unwrap();
+        assert_eq!(block.hash(), block_hash);
+    }
+
+    #[test]
+    fn rejects_insufficient_value() {
+        let attributes = attributes();
+        let rules = RelayTrustRules::default().with_min_value_advantage_bps(1_000);
+        assert!(matches!(
+            rules.check(submission(&attributes, 100), &attributes, U256::from(100)),
+            Err(RejectedPayload::InsufficientValue { .. })
+        ));
+        assert!(matches!(
+            rules.check(submission(&attributes, 109), &attributes, U256::from(100)),
+            Err(RejectedPayload::InsufficientValue { .. })
+        ));
+        assert!(rules.check(submission(&attributes, 110), &attributes, U256::from(100)).is_ok());
+    }
+
+    #[test]
+    fn rejects_untrusted_builder() {
+        let attributes = attributes();
+        let rules =
+            RelayTrustRules::default().with_trusted_builders([BlsPublicKey::with_last_byte(1)]);
+        assert!(matches!(
+            rules.check(submission(&attributes, 100), &attributes, U256::ZERO),
+            Err(RejectedPayload::UntrustedBuilder(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_mismatching_payload() {
+        let attributes = attributes();
+        let rules = RelayTrustRules::default();
+
+        let mut wrong_recipient = submission(&attributes, 100);
+        wrong_recipient.message.proposer_fee_recipient = Address::ZERO;
+        assert!(matches!(
+            rules.check(wrong_recipient, &attributes, U256::ZERO),
+            Err(RejectedPayload::FeeRecipientMismatch { .. })
+        ));
+
+        let mut wrong_hash = submission(&attributes, 100);
+        wrong_hash.message.block_hash = B256::ZERO;
+        assert!(matches!(
+            rules.check(wrong_hash, &attributes, U256::ZERO),
+            Err(RejectedPayload::BlockHashMismatch { .. })
+        ));
+
+        let mut other_attributes = self::attributes();
+        other_attributes.timestamp += 1;
+        assert!(matches!(
+            rules.check(submission(&other_attributes, 100), &attributes, U256::ZERO),
+            Err(RejectedPayload::AttributesMismatch)
+        ));
+    }
+}
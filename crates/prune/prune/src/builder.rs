@@ -1,4 +1,4 @@
-use crate::{segments::SegmentSet, Pruner};
+use crate::{segments::SegmentSet, PruneSchedule, Pruner};
 use reth_chainspec::MAINNET;
 use reth_config::PruneConfig;
 use reth_db_api::database::Database;
@@ -21,6 +21,8 @@ pub struct PrunerBuilder {
     timeout: Option<Duration>,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    /// Time windows and rate limit restricting when pruning is needed.
+    schedule: PruneSchedule,
 }
 
 impl PrunerBuilder {
@@ -32,6 +34,10 @@ impl PrunerBuilder {
         Self::default()
             .block_interval(pruner_config.block_interval)
             .segments(pruner_config.segments)
+            .schedule(PruneSchedule::new(
+                pruner_config.schedule.windows,
+                pruner_config.schedule.max_entries_per_second,
+            ))
     }
 
     /// Sets the minimum pruning interval measured in blocks.
@@ -70,6 +76,12 @@ impl PrunerBuilder {
         self
     }
 
+    /// Sets the [`PruneSchedule`] restricting when pruning is needed.
+    pub fn schedule(mut self, schedule: PruneSchedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
     /// Builds a [Pruner] from the current configuration with the given provider factory.
     pub fn build_with_provider_factory<DB: Database>(
         self,
@@ -88,6 +100,7 @@ impl PrunerBuilder {
             self.timeout,
             self.finished_exex_height,
         )
+        .with_schedule(self.schedule)
     }
 
     /// Builds a [Pruner] from the current configuration with the given static file provider.
@@ -101,6 +114,7 @@ impl PrunerBuilder {
             self.timeout,
             self.finished_exex_height,
         )
+        .with_schedule(self.schedule)
     }
 }
 
@@ -112,6 +126,7 @@ impl Default for PrunerBuilder {
             delete_limit: MAINNET.prune_delete_limit,
             timeout: None,
             finished_exex_height: watch::channel(FinishedExExHeight::NoExExs).1,
+            schedule: PruneSchedule::default(),
         }
     }
 }
@@ -0,0 +1,127 @@
+use alloy_primitives::BlockNumber;
+use reth_db::{DatabaseError, TableViewer, Tables};
+use reth_db_api::{database_metrics::DatabaseMetrics, table::Table, transaction::DbTx};
+use reth_prune_types::{PruneMode, PruneSegment};
+use std::collections::HashMap;
+
+/// The data the [`Pruner`](crate::Pruner) would delete for a tip block number, see
+/// [`Pruner::dry_run`](crate::Pruner::dry_run).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PruneEstimate {
+    /// The tip block number the estimate was made for.
+    pub tip_block_number: BlockNumber,
+    /// The segments that have data to prune.
+    pub segments: Vec<SegmentEstimate>,
+}
+
+impl PruneEstimate {
+    /// Returns the total number of entries that would be deleted.
+    pub fn entries(&self) -> usize {
+        self.segments.iter().map(SegmentEstimate::entries).sum()
+    }
+
+    /// Returns the estimated total number of bytes that would be freed.
+    pub fn bytes(&self) -> u64 {
+        self.segments.iter().map(SegmentEstimate::bytes).sum()
+    }
+}
+
+/// The data a prune segment would delete.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentEstimate {
+    /// The prune segment.
+    pub segment: PruneSegment,
+    /// The prune mode of the segment.
+    pub mode: PruneMode,
+    /// The block number up to which the segment would be pruned, inclusive.
+    pub to_block: BlockNumber,
+    /// The entries that would be deleted from each table of the segment.
+    pub tables: Vec<TableEstimate>,
+}
+
+impl SegmentEstimate {
+    /// Returns the number of entries that would be deleted.
+    pub fn entries(&self) -> usize {
+        self.tables.iter().map(|table| table.entries).sum()
+    }
+
+    /// Returns the estimated number of bytes that would be freed.
+    pub fn bytes(&self) -> u64 {
+        self.tables.iter().map(|table| table.bytes).sum()
+    }
+}
+
+/// The entries that would be deleted from a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableEstimate {
+    /// The table.
+    pub table: Tables,
+    /// The number of entries that would be deleted.
+    pub entries: usize,
+    /// The estimated number of bytes that would be freed, based on the average size of an entry
+    /// of the table.
+    pub bytes: u64,
+}
+
+/// Returns the tables the given segment deletes entries from.
+pub(crate) const fn segment_tables(segment: PruneSegment) -> &'static [Tables] {
+    match segment {
+        PruneSegment::SenderRecovery => &[Tables::TransactionSenders],
+        PruneSegment::TransactionLookup => &[Tables::TransactionHashNumbers],
+        PruneSegment::Receipts | PruneSegment::ContractLogs | PruneSegment::ReceiptsRetention => {
+            &[Tables::Receipts]
+        }
+        PruneSegment::AccountHistory => &[Tables::AccountChangeSets, Tables::AccountsHistory],
+        PruneSegment::StorageHistory => &[Tables::StorageChangeSets, Tables::StoragesHistory],
+        PruneSegment::Headers => {
+            &[Tables::Headers, Tables::HeaderTerminalDifficulties, Tables::CanonicalHeaders]
+        }
+        PruneSegment::Transactions => &[Tables::Transactions],
+        // expired state is moved to the cold state tables, so only the plain state shrinks
+        PruneSegment::StateExpiry => &[Tables::PlainAccountState, Tables::PlainStorageState],
+    }
+}
+
+/// The average size in bytes of an entry of each table, as reported by the table size metrics of
+/// the database.
+#[derive(Debug, Default)]
+pub(crate) struct EntrySizes(HashMap<String, f64>);
+
+impl EntrySizes {
+    pub(crate) fn from_metrics(db: &impl DatabaseMetrics) -> Self {
+        let mut tables = HashMap::<String, (f64, f64)>::new();
+        for (name, value, labels) in db.gauge_metrics() {
+            let Some(table) = labels.iter().find(|label| label.key() == "table") else { continue };
+            let (size, entries) = tables.entry(table.value().to_string()).or_default();
+            match name {
+                "db.table_size" => *size = value,
+                "db.table_entries" => *entries = value,
+                _ => {}
+            }
+        }
+
+        Self(
+            tables
+                .into_iter()
+                .filter(|(_, (_, entries))| *entries > 0.0)
+                .map(|(table, (size, entries))| (table, size / entries))
+                .collect(),
+        )
+    }
+
+    /// Returns the estimated size of the given number of entries of the table.
+    pub(crate) fn estimate(&self, table: Tables, entries: usize) -> u64 {
+        self.0.get(table.name()).map_or(0, |size| (size * entries as f64) as u64)
+    }
+}
+
+/// Returns the number of entries of a table in the given transaction.
+pub(crate) struct TableEntries<'a, TX>(pub(crate) &'a TX);
+
+impl<TX: DbTx> TableViewer<usize> for TableEntries<'_, TX> {
+    type Error = DatabaseError;
+
+    fn view<T: Table>(&self) -> Result<usize, Self::Error> {
+        self.0.entries::<T>()
+    }
+}
@@ -11,16 +11,22 @@
 
 mod builder;
 mod error;
+mod estimate;
 mod event;
 mod metrics;
 mod pruner;
+mod schedule;
 pub mod segments;
 
 use crate::metrics::Metrics;
 pub use builder::PrunerBuilder;
 pub use error::PrunerError;
+pub use estimate::{PruneEstimate, SegmentEstimate, TableEstimate};
 pub use event::PrunerEvent;
-pub use pruner::{Pruner, PrunerResult, PrunerWithResult};
+pub use pruner::{
+    PruneDistanceHandle, PruneIntervalHandle, Pruner, PrunerResult, PrunerWithResult,
+};
+pub use schedule::PruneSchedule;
 
 // Re-export prune types
 #[doc(inline)]
@@ -1,16 +1,24 @@
 //! Support for pruning.
 
 use crate::{
-    segments::{PruneInput, Segment},
-    Metrics, PrunerError, PrunerEvent,
+    estimate::{segment_tables, EntrySizes, TableEntries},
+    segments::{prune_target_block, PruneInput, Segment},
+    Metrics, PruneEstimate, PruneSchedule, PrunerError, PrunerEvent, SegmentEstimate,
+    TableEstimate,
 };
 use alloy_primitives::BlockNumber;
-use reth_db_api::database::Database;
+use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_exex_types::FinishedExExHeight;
 use reth_provider::{DatabaseProviderRW, ProviderFactory, PruneCheckpointReader};
 use reth_prune_types::{PruneLimiter, PruneProgress, PruneSegment, PrunerOutput};
 use reth_tokio_util::{EventSender, EventStream};
-use std::time::{Duration, Instant};
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant, SystemTime},
+};
 use tokio::sync::watch;
 use tracing::debug;
 
@@ -22,6 +30,64 @@ pub type PrunerWithResult<S, DB> = (Pruner<S, DB>, PrunerResult);
 
 type PrunerStats = Vec<(PruneSegment, usize, PruneProgress)>;
 
+/// Shared minimum pruning interval of a [`Pruner`], measured in blocks.
+///
+/// Clones share the same value, changes are picked up by the next check of the pruner.
+#[derive(Debug, Clone)]
+pub struct PruneIntervalHandle(Arc<AtomicUsize>);
+
+impl PruneIntervalHandle {
+    /// Creates a new handle with the given interval.
+    pub fn new(block_interval: usize) -> Self {
+        Self(Arc::new(AtomicUsize::new(block_interval)))
+    }
+
+    /// Returns the minimum pruning interval.
+    pub fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Sets the minimum pruning interval.
+    pub fn set(&self, block_interval: usize) {
+        self.0.store(block_interval, Ordering::Relaxed)
+    }
+}
+
+/// Shared distance from the tip that tightens the configured prune modes of a [`Pruner`] while
+/// it's set, e.g. when the disk is running out of free space.
+///
+/// The segments of the sender recovery, transaction lookup, receipts and account and storage
+/// history are pruned as if they were configured with [`PruneMode::Distance`] of the given
+/// distance, if that prunes more than their configured mode. Segments that aren't configured to
+/// be pruned are never pruned. The distance is raised to the minimum distance of each segment.
+///
+/// Clones share the same value, changes are picked up by the next run of the pruner.
+#[derive(Debug, Clone)]
+pub struct PruneDistanceHandle(Arc<AtomicU64>);
+
+impl PruneDistanceHandle {
+    /// Creates a new handle without a distance.
+    pub fn new() -> Self {
+        Self(Arc::new(AtomicU64::new(u64::MAX)))
+    }
+
+    /// Returns the distance, if set.
+    pub fn get(&self) -> Option<u64> {
+        Some(self.0.load(Ordering::Relaxed)).filter(|distance| *distance != u64::MAX)
+    }
+
+    /// Sets the distance, or removes it if `None`.
+    pub fn set(&self, distance: Option<u64>) {
+        self.0.store(distance.unwrap_or(u64::MAX), Ordering::Relaxed)
+    }
+}
+
+impl Default for PruneDistanceHandle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Pruning routine. Main pruning logic happens in [`Pruner::run`].
 #[derive(Debug)]
 pub struct Pruner<DB, PF> {
@@ -30,7 +96,9 @@ pub struct Pruner<DB, PF> {
     segments: Vec<Box<dyn Segment<DB>>>,
     /// Minimum pruning interval measured in blocks. All prune segments are checked and, if needed,
     /// pruned, when the chain advances by the specified number of blocks.
-    min_block_interval: usize,
+    min_block_interval: PruneIntervalHandle,
+    /// Distance from the tip that tightens the configured prune modes while it's set.
+    max_distance: PruneDistanceHandle,
     /// Previous tip block number when the pruner was run. Even if no data was pruned, this block
     /// number is updated with the tip block number the pruner was called with. It's used in
     /// conjunction with `min_block_interval` to determine when the pruning needs to be initiated.
@@ -41,6 +109,8 @@ pub struct Pruner<DB, PF> {
     timeout: Option<Duration>,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    /// Time windows and rate limit restricting when pruning is needed.
+    schedule: PruneSchedule,
     #[doc(hidden)]
     metrics: Metrics,
     event_sender: EventSender<PrunerEvent>,
@@ -58,11 +128,13 @@ impl<DB> Pruner<DB, ()> {
         Self {
             provider_factory: (),
             segments,
-            min_block_interval,
+            min_block_interval: PruneIntervalHandle::new(min_block_interval),
+            max_distance: PruneDistanceHandle::new(),
             previous_tip_block_number: None,
             delete_limit,
             timeout,
             finished_exex_height,
+            schedule: PruneSchedule::default(),
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
@@ -82,11 +154,13 @@ impl<DB: Database> Pruner<DB, ProviderFactory<DB>> {
         Self {
             provider_factory,
             segments,
-            min_block_interval,
+            min_block_interval: PruneIntervalHandle::new(min_block_interval),
+            max_distance: PruneDistanceHandle::new(),
             previous_tip_block_number: None,
             delete_limit,
             timeout,
             finished_exex_height,
+            schedule: PruneSchedule::default(),
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
@@ -99,6 +173,22 @@ impl<DB: Database, S> Pruner<DB, S> {
         self.event_sender.new_listener()
     }
 
+    /// Sets the [`PruneSchedule`] that restricts when pruning is needed.
+    pub fn with_schedule(mut self, schedule: PruneSchedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    /// Returns a handle that can change the minimum pruning interval while the pruner is running.
+    pub fn block_interval_handle(&self) -> PruneIntervalHandle {
+        self.min_block_interval.clone()
+    }
+
+    /// Returns a handle that can tighten the configured prune modes while the pruner is running.
+    pub fn distance_handle(&self) -> PruneDistanceHandle {
+        self.max_distance.clone()
+    }
+
     fn run_with_provider(
         &mut self,
         provider: &DatabaseProviderRW<DB>,
@@ -130,6 +220,7 @@ impl<DB: Database, S> Pruner<DB, S> {
             self.prune_segments(provider, tip_block_number, &mut limiter)?;
 
         self.previous_tip_block_number = Some(tip_block_number);
+        self.schedule.on_run_finished(start, deleted_entries);
 
         let elapsed = start.elapsed();
         self.metrics.duration_seconds.record(elapsed);
@@ -178,14 +269,12 @@ impl<DB: Database, S> Pruner<DB, S> {
                 break
             }
 
-            if let Some((to_block, prune_mode)) = segment
-                .mode()
-                .map(|mode| {
-                    mode.prune_target_block(tip_block_number, segment.segment(), segment.purpose())
-                })
-                .transpose()?
-                .flatten()
-            {
+            if let Some((to_block, prune_mode)) = prune_target_block(
+                provider,
+                segment.as_ref(),
+                tip_block_number,
+                self.max_distance.get(),
+            )? {
                 debug!(
                     target: "pruner",
                     segment = ?segment.segment(),
@@ -245,7 +334,8 @@ impl<DB: Database, S> Pruner<DB, S> {
     }
 
     /// Returns `true` if the pruning is needed at the provided tip block number.
-    /// This determined by the check against minimum pruning interval and last pruned block number.
+    /// This determined by the check against minimum pruning interval and last pruned block number,
+    /// and by the [`PruneSchedule`] of the pruner.
     pub fn is_pruning_needed(&self, tip_block_number: BlockNumber) -> bool {
         let Some(tip_block_number) =
             self.adjust_tip_block_number_to_finished_exex_height(tip_block_number)
@@ -256,19 +346,29 @@ impl<DB: Database, S> Pruner<DB, S> {
         // Saturating subtraction is needed for the case when the chain was reverted, meaning
         // current block number might be less than the previous tip block number.
         // If that's the case, no pruning is needed as outdated data is also reverted.
-        if tip_block_number.saturating_sub(self.previous_tip_block_number.unwrap_or_default()) >=
-            self.min_block_interval as u64
+        if tip_block_number.saturating_sub(self.previous_tip_block_number.unwrap_or_default()) <
+            self.min_block_interval.get() as u64
         {
-            debug!(
-                target: "pruner",
-                previous_tip_block_number = ?self.previous_tip_block_number,
-                %tip_block_number,
-                "Minimum pruning interval reached"
-            );
-            true
-        } else {
-            false
+            return false
         }
+
+        if !self.schedule.is_within_windows(SystemTime::now()) {
+            debug!(target: "pruner", %tip_block_number, "Outside of the pruning time windows");
+            return false
+        }
+
+        if !self.schedule.is_within_rate_limit(Instant::now()) {
+            debug!(target: "pruner", %tip_block_number, "Pruning rate limit reached");
+            return false
+        }
+
+        debug!(
+            target: "pruner",
+            previous_tip_block_number = ?self.previous_tip_block_number,
+            %tip_block_number,
+            "Minimum pruning interval reached"
+        );
+        true
     }
 
     /// Adjusts the tip block number to the finished `ExEx` height. This is needed to not prune more
@@ -327,11 +427,93 @@ impl<DB: Database> Pruner<DB, ProviderFactory<DB>> {
     }
 }
 
+impl<DB: Database + DatabaseMetrics> Pruner<DB, ProviderFactory<DB>> {
+    /// Estimates the data that [`Pruner::run`] would delete for the given tip block number,
+    /// without deleting anything.
+    ///
+    /// The segments are pruned without any limits in a database transaction that is aborted
+    /// afterwards, so this takes as long as pruning itself. The number of bytes is estimated from
+    /// the average size of an entry of each table.
+    pub fn dry_run(&self, tip_block_number: BlockNumber) -> Result<PruneEstimate, PrunerError> {
+        let mut estimate = PruneEstimate { tip_block_number, segments: Vec::new() };
+        let Some(tip_block_number) =
+            self.adjust_tip_block_number_to_finished_exex_height(tip_block_number)
+        else {
+            return Ok(estimate)
+        };
+
+        let entry_sizes = EntrySizes::from_metrics(self.provider_factory.db_ref());
+        // Dropped without committing
+        let provider = self.provider_factory.provider_rw()?;
+
+        for segment in &self.segments {
+            let Some((to_block, mode)) = prune_target_block(
+                &provider,
+                segment.as_ref(),
+                tip_block_number,
+                self.max_distance.get(),
+            )?
+            else {
+                continue
+            };
+
+            let tables = segment_tables(segment.segment());
+            let entries_before = tables
+                .iter()
+                .map(|table| table.view(&TableEntries(provider.tx_ref())))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let previous_checkpoint = provider.get_prune_checkpoint(segment.segment())?;
+            let output = segment.prune(
+                &provider,
+                PruneInput { previous_checkpoint, to_block, limiter: PruneLimiter::default() },
+            )?;
+            if output.pruned == 0 {
+                continue
+            }
+            // Later segments, e.g. receipts by logs, continue from the checkpoint
+            if let Some(checkpoint) = output.checkpoint {
+                segment.save_checkpoint(&provider, checkpoint.as_prune_checkpoint(mode))?;
+            }
+
+            let tables = tables
+                .iter()
+                .zip(entries_before)
+                .map(|(table, before)| {
+                    let after = table.view(&TableEntries(provider.tx_ref()))?;
+                    let entries = before.saturating_sub(after);
+                    Ok(TableEstimate {
+                        table: *table,
+                        entries,
+                        bytes: entry_sizes.estimate(*table, entries),
+                    })
+                })
+                .collect::<Result<Vec<_>, PrunerError>>()?;
+            estimate.segments.push(SegmentEstimate {
+                segment: segment.segment(),
+                mode,
+                to_block,
+                tables,
+            });
+        }
+
+        Ok(estimate)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::Pruner;
+    use crate::{PruneSchedule, Pruner, PrunerBuilder};
+    use alloy_primitives::B256;
+    use reth_db::{tables, Tables};
     use reth_exex_types::FinishedExExHeight;
-    use reth_provider::{test_utils::create_test_provider_factory, ProviderFactory};
+    use reth_provider::{
+        test_utils::create_test_provider_factory, ProviderFactory, PruneCheckpointReader,
+    };
+    use reth_prune_types::{PruneMode, PruneModes, PruneSegment};
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_testing_utils::{generators, generators::random_block_range};
+    use std::time::{Duration, Instant};
 
     #[test]
     fn is_pruning_needed() {
@@ -355,7 +537,7 @@ mod tests {
         pruner.previous_tip_block_number = Some(first_block_number);
 
         // Tip block number delta is >= than min block interval
-        let second_block_number = first_block_number + pruner.min_block_interval as u64;
+        let second_block_number = first_block_number + pruner.min_block_interval.get() as u64;
         assert!(pruner.is_pruning_needed(second_block_number));
         pruner.previous_tip_block_number = Some(second_block_number);
 
@@ -363,9 +545,14 @@ mod tests {
         assert!(!pruner.is_pruning_needed(second_block_number));
 
         // Tip block number delta is >= than min block interval
-        let third_block_number = second_block_number + pruner.min_block_interval as u64;
+        let third_block_number = second_block_number + pruner.min_block_interval.get() as u64;
         assert!(pruner.is_pruning_needed(third_block_number));
 
+        // A larger interval set through the handle is applied to the next check
+        pruner.block_interval_handle().set(10);
+        assert!(!pruner.is_pruning_needed(third_block_number));
+        pruner.block_interval_handle().set(5);
+
         // Not all ExExs have emitted a `FinishedHeight` event yet
         finished_exex_height_tx.send(FinishedExExHeight::NotReady).unwrap();
         assert!(!pruner.is_pruning_needed(third_block_number));
@@ -377,5 +564,146 @@ mod tests {
         // Adjust tip block number to the finished ExEx height that reaches the threshold
         finished_exex_height_tx.send(FinishedExExHeight::Height(third_block_number)).unwrap();
         assert!(pruner.is_pruning_needed(third_block_number));
+
+        // The previous run deleted more entries than the rate limit allows until now
+        let mut schedule = PruneSchedule::new(Vec::new(), Some(1));
+        schedule.on_run_finished(Instant::now(), 3600);
+        let pruner = pruner.with_schedule(schedule);
+        assert!(!pruner.is_pruning_needed(third_block_number));
+    }
+
+    #[test]
+    fn dry_run() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 1..=10, B256::ZERO, 2..3, None, None);
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+        let transaction_senders = blocks
+            .iter()
+            .flat_map(|block| &block.body)
+            .enumerate()
+            .map(|(tx_number, transaction)| {
+                (tx_number as u64, transaction.recover_signer().expect("recover signer"))
+            })
+            .collect::<Vec<_>>();
+        db.insert_transaction_senders(transaction_senders.clone())
+            .expect("insert transaction senders");
+
+        let pruner = PrunerBuilder::default()
+            .segments(PruneModes {
+                sender_recovery: Some(PruneMode::Before(6)),
+                ..PruneModes::none()
+            })
+            .build_with_provider_factory(db.factory.clone());
+        let estimate = pruner.dry_run(10).unwrap();
+
+        let expected = blocks[..5].iter().map(|block| block.body.len()).sum::<usize>();
+        let segment = estimate
+            .segments
+            .iter()
+            .find(|segment| segment.segment == PruneSegment::SenderRecovery)
+            .unwrap();
+        assert_eq!(segment.to_block, 5);
+        assert_eq!(segment.tables.len(), 1);
+        assert_eq!(segment.tables[0].table, Tables::TransactionSenders);
+        assert_eq!(segment.tables[0].entries, expected);
+        assert!(segment.bytes() > 0);
+
+        // Nothing was deleted
+        assert_eq!(
+            db.table::<tables::TransactionSenders>().unwrap().len(),
+            transaction_senders.len()
+        );
+        assert_eq!(
+            db.factory.provider().unwrap().get_prune_checkpoint(PruneSegment::SenderRecovery),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn tightened_prune_mode() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 1..=10, B256::ZERO, 1..2, None, None);
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+        let transaction_senders = blocks
+            .iter()
+            .flat_map(|block| &block.body)
+            .enumerate()
+            .map(|(tx_number, transaction)| {
+                (tx_number as u64, transaction.recover_signer().expect("recover signer"))
+            })
+            .collect::<Vec<_>>();
+        db.insert_transaction_senders(transaction_senders).expect("insert transaction senders");
+
+        let pruner = PrunerBuilder::default()
+            .segments(PruneModes {
+                sender_recovery: Some(PruneMode::Before(3)),
+                ..PruneModes::none()
+            })
+            .build_with_provider_factory(db.factory);
+        let target = |pruner: &Pruner<_, _>| {
+            let estimate = pruner.dry_run(10).unwrap();
+            assert_eq!(estimate.segments.len(), 1);
+            (estimate.segments[0].to_block, estimate.segments[0].mode)
+        };
+        assert_eq!(target(&pruner), (2, PruneMode::Before(3)));
+
+        pruner.distance_handle().set(Some(4));
+        assert_eq!(target(&pruner), (6, PruneMode::Distance(4)));
+
+        // The distance doesn't loosen the configured mode
+        pruner.distance_handle().set(Some(9));
+        assert_eq!(target(&pruner), (2, PruneMode::Before(3)));
+
+        pruner.distance_handle().set(None);
+        assert_eq!(target(&pruner), (2, PruneMode::Before(3)));
+    }
+
+    #[test]
+    fn time_based_prune_mode() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        // One block every 12 seconds
+        let mut blocks = random_block_range(&mut rng, 0..=100, B256::ZERO, 1..2, None, None);
+        for block in &mut blocks {
+            let mut header = block.header.clone().unseal();
+            header.timestamp = header.number * 12;
+            block.header = header.seal_slow();
+        }
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+        let transaction_senders = blocks
+            .iter()
+            .flat_map(|block| &block.body)
+            .enumerate()
+            .map(|(tx_number, transaction)| {
+                (tx_number as u64, transaction.recover_signer().expect("recover signer"))
+            })
+            .collect::<Vec<_>>();
+        db.insert_transaction_senders(transaction_senders).expect("insert transaction senders");
+
+        let mode = PruneMode::keep(Duration::from_secs(40 * 12));
+        let pruner = PrunerBuilder::default()
+            .segments(PruneModes { sender_recovery: Some(mode), ..PruneModes::none() })
+            .build_with_provider_factory(db.factory.clone());
+        let estimate = pruner.dry_run(100).unwrap();
+
+        // Blocks 60..=100 are within the last 480 seconds
+        assert_eq!(estimate.segments.len(), 1);
+        assert_eq!(estimate.segments[0].segment, PruneSegment::SenderRecovery);
+        assert_eq!(estimate.segments[0].mode, mode);
+        assert_eq!(estimate.segments[0].to_block, 59);
+
+        // Nothing is older than the retention period yet
+        let pruner = PrunerBuilder::default()
+            .segments(PruneModes {
+                sender_recovery: Some(PruneMode::keep(Duration::from_secs(100 * 12))),
+                ..PruneModes::none()
+            })
+            .build_with_provider_factory(db.factory);
+        assert!(pruner.dry_run(100).unwrap().segments.is_empty());
     }
 }
@@ -0,0 +1,82 @@
+use reth_prune_types::PruneWindow;
+use std::{
+    num::NonZeroU64,
+    time::{Duration, Instant, SystemTime},
+};
+
+/// Restricts when the [`Pruner`](crate::Pruner) is allowed to run, so that pruning doesn't
+/// compete with block processing or RPC.
+#[derive(Debug, Clone, Default)]
+pub struct PruneSchedule {
+    /// Daily time windows in UTC during which pruning is allowed. If empty, pruning is allowed at
+    /// any time.
+    windows: Vec<PruneWindow>,
+    /// Maximum average number of entries deleted per second.
+    max_entries_per_second: Option<NonZeroU64>,
+    /// Earliest time of the next run according to `max_entries_per_second`.
+    next_run_at: Option<Instant>,
+}
+
+impl PruneSchedule {
+    /// Creates a new schedule with the given time windows and rate limit of deleted entries per
+    /// second. A rate limit of zero disables rate limiting.
+    pub fn new(windows: Vec<PruneWindow>, max_entries_per_second: Option<u64>) -> Self {
+        Self {
+            windows,
+            max_entries_per_second: max_entries_per_second.and_then(NonZeroU64::new),
+            next_run_at: None,
+        }
+    }
+
+    /// Returns `true` if the given time lies within one of the time windows.
+    pub fn is_within_windows(&self, time: SystemTime) -> bool {
+        self.windows.is_empty() || self.windows.iter().any(|window| window.contains_time(time))
+    }
+
+    /// Returns `true` if a run at the given instant doesn't exceed the rate limit.
+    pub fn is_within_rate_limit(&self, now: Instant) -> bool {
+        self.next_run_at.map_or(true, |next_run_at| now >= next_run_at)
+    }
+
+    /// Records a run that started at `started_at` and deleted `deleted_entries` entries, delaying
+    /// the next run until the average rate drops to the rate limit.
+    pub fn on_run_finished(&mut self, started_at: Instant, deleted_entries: usize) {
+        if let Some(max_entries_per_second) = self.max_entries_per_second {
+            let delay = Duration::from_secs_f64(
+                deleted_entries as f64 / max_entries_per_second.get() as f64,
+            );
+            self.next_run_at = Some(started_at + delay);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn windows() {
+        let schedule = PruneSchedule::default();
+        assert!(schedule.is_within_windows(SystemTime::now()));
+
+        let schedule = PruneSchedule::new(vec!["02:00-06:00".parse().unwrap()], None);
+        assert!(schedule.is_within_windows(UNIX_EPOCH + Duration::from_secs(3 * 3600)));
+        assert!(!schedule.is_within_windows(UNIX_EPOCH + Duration::from_secs(12 * 3600)));
+    }
+
+    #[test]
+    fn rate_limit() {
+        let now = Instant::now();
+
+        let mut schedule = PruneSchedule::default();
+        schedule.on_run_finished(now, 1000);
+        assert!(schedule.is_within_rate_limit(now));
+
+        let mut schedule = PruneSchedule::new(Vec::new(), Some(100));
+        assert!(schedule.is_within_rate_limit(now));
+        schedule.on_run_finished(now, 1000);
+        assert!(!schedule.is_within_rate_limit(now + Duration::from_secs(9)));
+        assert!(schedule.is_within_rate_limit(now + Duration::from_secs(10)));
+    }
+}
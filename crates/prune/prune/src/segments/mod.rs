@@ -7,7 +7,8 @@ use crate::PrunerError;
 use alloy_primitives::{BlockNumber, TxNumber};
 use reth_db_api::database::Database;
 use reth_provider::{
-    errors::provider::ProviderResult, BlockReader, DatabaseProviderRW, PruneCheckpointWriter,
+    errors::provider::ProviderResult, BlockReader, DatabaseProviderRW, HeaderProvider,
+    PruneCheckpointWriter,
 };
 use reth_prune_types::{
     PruneCheckpoint, PruneLimiter, PruneMode, PrunePurpose, PruneSegment, SegmentOutput,
@@ -20,8 +21,8 @@ pub use static_file::{
 use std::{fmt::Debug, ops::RangeInclusive};
 use tracing::error;
 pub use user::{
-    AccountHistory, Receipts as UserReceipts, ReceiptsByLogs, SenderRecovery, StorageHistory,
-    TransactionLookup,
+    AccountHistory, Receipts as UserReceipts, ReceiptsByLogs, ReceiptsRetention, SenderRecovery,
+    StateExpiry, StorageHistory, TransactionLookup,
 };
 
 /// A segment represents a pruning of some portion of the data.
@@ -58,6 +59,95 @@ pub trait Segment<DB: Database>: Debug + Send + Sync {
     }
 }
 
+/// Returns the block up to which the segment needs to be pruned, inclusive, together with the
+/// prune mode of the segment.
+///
+/// A time-based [`PruneMode::Keep`] is resolved to the first block within the retention period
+/// using the header timestamps. At least [`PruneSegment::min_blocks`] blocks are kept.
+///
+/// If `max_distance` is set and the segment is tightened by a
+/// [`PruneDistanceHandle`](crate::PruneDistanceHandle), [`PruneMode::Distance`] is returned if it
+/// prunes more than the configured mode.
+pub(crate) fn prune_target_block<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    segment: &dyn Segment<DB>,
+    tip_block_number: BlockNumber,
+    max_distance: Option<u64>,
+) -> Result<Option<(BlockNumber, PruneMode)>, PrunerError> {
+    let Some(mode) = segment.mode() else { return Ok(None) };
+    let min_blocks = segment.segment().min_blocks(segment.purpose());
+
+    let target_mode = match mode {
+        PruneMode::Keep(seconds) => {
+            let first_kept_block = first_block_since(provider, tip_block_number, seconds)?
+                .min(tip_block_number.saturating_sub(min_blocks));
+            // Nothing to prune yet if the first kept block is 0
+            (first_kept_block > 0).then_some(PruneMode::Before(first_kept_block))
+        }
+        mode => Some(mode),
+    };
+    let target = match target_mode {
+        Some(target_mode) => target_mode
+            .prune_target_block(tip_block_number, segment.segment(), segment.purpose())?
+            .map(|(to_block, _)| (to_block, mode)),
+        None => None,
+    };
+
+    let Some(max_distance) = max_distance.filter(|_| is_tightened(segment)) else {
+        return Ok(target)
+    };
+    let tightened = PruneMode::Distance(max_distance.max(min_blocks));
+    Ok(
+        match tightened.prune_target_block(
+            tip_block_number,
+            segment.segment(),
+            segment.purpose(),
+        )? {
+            Some((to_block, _)) if target.map_or(true, |(target, _)| to_block > target) => {
+                Some((to_block, tightened))
+            }
+            _ => target,
+        },
+    )
+}
+
+/// Returns `true` if the prune mode of the segment is tightened by a
+/// [`PruneDistanceHandle`](crate::PruneDistanceHandle).
+fn is_tightened<DB: Database>(segment: &dyn Segment<DB>) -> bool {
+    segment.purpose().is_user() &&
+        matches!(
+            segment.segment(),
+            PruneSegment::SenderRecovery |
+                PruneSegment::TransactionLookup |
+                PruneSegment::Receipts |
+                PruneSegment::AccountHistory |
+                PruneSegment::StorageHistory
+        )
+}
+
+/// Returns the first block with a timestamp within `seconds` of the timestamp of the tip.
+fn first_block_since<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    tip_block_number: BlockNumber,
+    seconds: u64,
+) -> ProviderResult<BlockNumber> {
+    let Some(tip) = provider.header_by_number(tip_block_number)? else { return Ok(0) };
+    let cutoff = tip.timestamp.saturating_sub(seconds);
+
+    let (mut low, mut high) = (0, tip_block_number);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let timestamp = provider.header_by_number(mid)?.map(|header| header.timestamp);
+        if timestamp.map_or(true, |timestamp| timestamp < cutoff) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    Ok(low)
+}
+
 /// Segment pruning input, see [`Segment::prune`].
 #[derive(Debug)]
 #[cfg_attr(test, derive(Clone))]
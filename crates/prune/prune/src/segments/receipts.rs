@@ -66,9 +66,10 @@ pub(crate) fn save_checkpoint<DB: Database>(
 ) -> ProviderResult<()> {
     provider.save_prune_checkpoint(PruneSegment::Receipts, checkpoint)?;
 
-    // `PruneSegment::Receipts` overrides `PruneSegment::ContractLogs`, so we can preemptively
-    // limit their pruning start point.
+    // `PruneSegment::Receipts` overrides `PruneSegment::ContractLogs` and
+    // `PruneSegment::ReceiptsRetention`, so we can preemptively limit their pruning start point.
     provider.save_prune_checkpoint(PruneSegment::ContractLogs, checkpoint)?;
+    provider.save_prune_checkpoint(PruneSegment::ReceiptsRetention, checkpoint)?;
 
     Ok(())
 }
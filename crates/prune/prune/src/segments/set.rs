@@ -1,6 +1,6 @@
 use crate::segments::{
-    AccountHistory, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory, TransactionLookup,
-    UserReceipts,
+    AccountHistory, ReceiptsByLogs, ReceiptsRetention, Segment, SenderRecovery, StateExpiry,
+    StorageHistory, TransactionLookup, UserReceipts,
 };
 use reth_db_api::database::Database;
 use reth_provider::providers::StaticFileProvider;
@@ -52,6 +52,8 @@ impl<DB: Database> SegmentSet<DB> {
             account_history,
             storage_history,
             receipts_log_filter,
+            receipts_retention,
+            state_expiry,
         } = prune_modes;
 
         Self::default()
@@ -61,6 +63,8 @@ impl<DB: Database> SegmentSet<DB> {
             .segment(StaticFileTransactions::new(static_file_provider.clone()))
             // Static file receipts
             .segment(StaticFileReceipts::new(static_file_provider))
+            // State expiry, before account and storage history prune the changesets it reads
+            .segment_opt(state_expiry.map(StateExpiry::new))
             // Account history
             .segment_opt(account_history.map(AccountHistory::new))
             // Storage history
@@ -72,6 +76,11 @@ impl<DB: Database> SegmentSet<DB> {
                 (!receipts_log_filter.is_empty())
                     .then(|| ReceiptsByLogs::new(receipts_log_filter.clone())),
             )
+            // Receipts retained by transaction recipient and sender
+            .segment_opt(
+                (!receipts_retention.is_empty())
+                    .then(|| ReceiptsRetention::new(receipts_retention)),
+            )
             // Transaction lookup
             .segment_opt(transaction_lookup.map(TransactionLookup::new))
             // Sender recovery
@@ -2,13 +2,17 @@ mod account_history;
 mod history;
 mod receipts;
 mod receipts_by_logs;
+mod receipts_retention;
 mod sender_recovery;
+mod state_expiry;
 mod storage_history;
 mod transaction_lookup;
 
 pub use account_history::AccountHistory;
 pub use receipts::Receipts;
 pub use receipts_by_logs::ReceiptsByLogs;
+pub use receipts_retention::ReceiptsRetention;
 pub use sender_recovery::SenderRecovery;
+pub use state_expiry::StateExpiry;
 pub use storage_history::StorageHistory;
 pub use transaction_lookup::TransactionLookup;
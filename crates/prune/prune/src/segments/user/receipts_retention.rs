@@ -0,0 +1,227 @@
+use crate::{
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use rayon::prelude::*;
+use reth_db::tables;
+use reth_db_api::database::Database;
+use reth_provider::{DatabaseProviderRW, TransactionsProvider};
+use reth_prune_types::{
+    PruneInterruptReason, PruneMode, PruneProgress, PrunePurpose, PruneSegment,
+    ReceiptsRetentionConfig, SegmentOutput, SegmentOutputCheckpoint,
+};
+use tracing::{instrument, trace};
+
+#[derive(Debug)]
+pub struct ReceiptsRetention {
+    config: ReceiptsRetentionConfig,
+}
+
+impl ReceiptsRetention {
+    pub const fn new(config: ReceiptsRetentionConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl<DB: Database> Segment<DB> for ReceiptsRetention {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::ReceiptsRetention
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        self.config.mode
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<SegmentOutput, PrunerError> {
+        let (start, end) = match input.get_next_tx_num_range(provider)? {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No receipts to prune");
+                return Ok(SegmentOutput::done())
+            }
+        }
+        .into_inner();
+        // Every transaction in the range is loaded to decide whether its receipt is retained, so
+        // the range is bounded by the number of entries we're still allowed to delete.
+        let tx_range = start..=
+            input
+                .limiter
+                .deleted_entries_limit_left()
+                .map_or(end, |left| end.min(start + left as u64 - 1));
+        let tx_range_end = *tx_range.end();
+
+        let transactions = provider.transactions_by_tx_range(tx_range.clone())?;
+
+        // Number of transactions retrieved from the database should match the tx range count
+        if transactions.len() != tx_range.clone().count() {
+            return Err(PrunerError::InconsistentData(
+                "Unexpected number of transactions retrieved by transaction number range",
+            ))
+        }
+
+        // Senders are recovered from the signatures, because the `TransactionSenders` table might
+        // have been pruned already.
+        let senders = if self.config.needs_senders() {
+            transactions
+                .par_iter()
+                .map(|transaction| transaction.recover_signer())
+                .collect::<Option<Vec<_>>>()
+                .ok_or(PrunerError::InconsistentData("Failed to recover transaction sender"))?
+        } else {
+            Vec::new()
+        };
+
+        let mut limiter = input.limiter;
+
+        let mut last_checked_transaction = None;
+        let (pruned, done) = provider.prune_table_with_range::<tables::Receipts>(
+            tx_range,
+            &mut limiter,
+            |(tx_num, _)| {
+                last_checked_transaction = Some(*tx_num);
+                let index = (tx_num - start) as usize;
+                let sender = senders.get(index).copied().unwrap_or_default();
+                self.config.retains(transactions[index].to(), sender)
+            },
+            |_| {},
+        )?;
+
+        // The limit can be reached right after the last receipt of the range was checked, in which
+        // case there's nothing left to prune either.
+        let done = (done && tx_range_end == end) || last_checked_transaction == Some(end);
+        trace!(target: "pruner", %pruned, %done, "Pruned receipts");
+
+        let last_pruned_transaction = if done {
+            tx_range_end
+        } else {
+            match last_checked_transaction {
+                Some(tx_num) => tx_num,
+                None => {
+                    return Ok(SegmentOutput::not_done(
+                        PruneInterruptReason::new(&limiter),
+                        input
+                            .previous_checkpoint
+                            .map(SegmentOutputCheckpoint::from_prune_checkpoint),
+                    ))
+                }
+            }
+        };
+
+        let last_pruned_block = provider
+            .transaction_block(last_pruned_transaction)?
+            .ok_or(PrunerError::InconsistentData("Block for transaction is not found"))?
+            // If there's more receipts to prune, set the checkpoint block number to previous,
+            // so we could finish pruning its receipts on the next run.
+            .checked_sub(if done { 0 } else { 1 });
+
+        let progress = PruneProgress::new(done, &limiter);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: last_pruned_block,
+                tx_number: Some(last_pruned_transaction),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{PruneInput, ReceiptsRetention, Segment};
+    use alloy_primitives::B256;
+    use reth_db::tables;
+    use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
+    use reth_provider::{PruneCheckpointReader, TransactionsProvider};
+    use reth_prune_types::{PruneLimiter, PruneMode, PruneSegment, ReceiptsRetentionConfig};
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_testing_utils::{
+        generators,
+        generators::{random_block_range, random_receipt},
+    };
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn prune_receipts_retention() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=100, B256::ZERO, 2..5, None, None);
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let mut receipts = Vec::new();
+        for block in &blocks {
+            for transaction in &block.body {
+                receipts
+                    .push((receipts.len() as u64, random_receipt(&mut rng, transaction, Some(0))));
+            }
+        }
+        db.insert_receipts(receipts).expect("insert receipts");
+
+        let contract = blocks[10].body[0].to().unwrap();
+        let sender = blocks[20].body[0].recover_signer().unwrap();
+        let to_block = 49;
+        let prune_mode = PruneMode::Before(to_block + 1);
+        let segment = ReceiptsRetention::new(ReceiptsRetentionConfig {
+            mode: Some(prune_mode),
+            contracts: BTreeSet::from([contract]),
+            senders: BTreeSet::from([sender]),
+        });
+
+        let run_prune = || {
+            let provider = db.factory.provider_rw().unwrap();
+
+            let input = PruneInput {
+                previous_checkpoint: provider
+                    .get_prune_checkpoint(PruneSegment::ReceiptsRetention)
+                    .unwrap(),
+                to_block,
+                limiter: PruneLimiter::default().set_deleted_entries_limit(10),
+            };
+            let output = segment.prune(&provider, input).unwrap();
+            if let Some(checkpoint) = output.checkpoint {
+                segment
+                    .save_checkpoint(&provider, checkpoint.as_prune_checkpoint(prune_mode))
+                    .unwrap();
+            }
+            provider.commit().expect("commit");
+
+            output.progress.is_finished()
+        };
+
+        while !run_prune() {}
+
+        let provider = db.factory.provider().unwrap();
+        let mut cursor = provider.tx_ref().cursor_read::<tables::Receipts>().unwrap();
+        let mut retained = 0;
+        for entry in cursor.walk(None).unwrap() {
+            let (tx_num, _) = entry.unwrap();
+            if provider.transaction_block(tx_num).unwrap().unwrap() > to_block {
+                continue
+            }
+
+            // Receipts up to the target block are only kept if they belong to a transaction sent
+            // to the contract or by the sender
+            let transaction = provider.transaction_by_id(tx_num).unwrap().unwrap();
+            assert!(
+                transaction.to() == Some(contract) || transaction.recover_signer() == Some(sender)
+            );
+            retained += 1;
+        }
+        assert!(retained >= 2);
+
+        let checkpoint =
+            provider.get_prune_checkpoint(PruneSegment::ReceiptsRetention).unwrap().unwrap();
+        assert_eq!(checkpoint.block_number, Some(to_block));
+    }
+}
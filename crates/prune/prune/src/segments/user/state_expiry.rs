@@ -0,0 +1,290 @@
+use crate::{
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use alloy_primitives::{Address, BlockNumber};
+use reth_db::{tables, BlockNumberList};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO},
+    database::Database,
+    models::{storage_sharded_key::StorageShardedKey, BlockNumberAddress, ShardedKey},
+    table::Table,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_provider::DatabaseProviderRW;
+use reth_prune_types::{
+    PruneInterruptReason, PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutput,
+    SegmentOutputCheckpoint,
+};
+use tracing::{instrument, trace};
+
+/// Moves accounts and storage slots that weren't changed after the pruned blocks from the plain
+/// state to the cold state.
+///
+/// The last change of an account or a storage slot is found using the changesets of the pruned
+/// blocks and the history indices, so this segment has to run before
+/// [`AccountHistory`](super::AccountHistory) and [`StorageHistory`](super::StorageHistory) prune
+/// them.
+#[derive(Debug)]
+pub struct StateExpiry {
+    mode: PruneMode,
+}
+
+impl StateExpiry {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for StateExpiry {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::StateExpiry
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<SegmentOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No state to expire");
+                return Ok(SegmentOutput::done())
+            }
+        };
+
+        let mut limiter = input.limiter;
+        let tx = provider.tx_ref();
+        let mut account_changesets = tx.cursor_dup_read::<tables::AccountChangeSets>()?;
+        let mut storage_changesets = tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+        let mut accounts_history = tx.cursor_read::<tables::AccountsHistory>()?;
+        let mut storages_history = tx.cursor_read::<tables::StoragesHistory>()?;
+        let mut plain_accounts = tx.cursor_write::<tables::PlainAccountState>()?;
+        let mut plain_storages = tx.cursor_dup_write::<tables::PlainStorageState>()?;
+        let mut cold_accounts = tx.cursor_write::<tables::ColdAccountState>()?;
+        let mut cold_storages = tx.cursor_dup_write::<tables::ColdStorageState>()?;
+
+        let mut last_expired_block = None;
+        let mut expired = 0;
+        let mut done = true;
+        // Blocks are processed as a whole, so that the checkpoint can be set to the last one.
+        for block_number in range {
+            if limiter.is_limit_reached() {
+                done = false;
+                break
+            }
+
+            for entry in account_changesets.walk_dup(Some(block_number), None)? {
+                let (_, account) = entry?;
+                let address = account.address;
+                if changed_after(
+                    &mut accounts_history,
+                    ShardedKey::new(address, block_number + 1),
+                    |key| key.key == address,
+                    block_number,
+                )? {
+                    continue
+                }
+
+                if let Some((_, account)) = plain_accounts.seek_exact(address)? {
+                    plain_accounts.delete_current()?;
+                    cold_accounts.upsert(address, account)?;
+                    limiter.increment_deleted_entries_count();
+                    expired += 1;
+                }
+            }
+
+            for entry in storage_changesets
+                .walk_range(BlockNumberAddress::range(block_number..=block_number))?
+            {
+                let (block_address, storage) = entry?;
+                let address: Address = block_address.address();
+                if changed_after(
+                    &mut storages_history,
+                    StorageShardedKey::new(address, storage.key, block_number + 1),
+                    |key| key.address == address && key.sharded_key.key == storage.key,
+                    block_number,
+                )? {
+                    continue
+                }
+
+                if let Some(entry) = plain_storages
+                    .seek_by_key_subkey(address, storage.key)?
+                    .filter(|entry| entry.key == storage.key)
+                {
+                    plain_storages.delete_current()?;
+                    cold_storages.upsert(address, entry)?;
+                    limiter.increment_deleted_entries_count();
+                    expired += 1;
+                }
+            }
+
+            last_expired_block = Some(block_number);
+        }
+        trace!(target: "pruner", %expired, %done, ?last_expired_block, "Expired state");
+
+        let progress = PruneProgress::new(done, &limiter);
+        let Some(block_number) = last_expired_block else {
+            return Ok(SegmentOutput::not_done(
+                PruneInterruptReason::new(&limiter),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        };
+
+        Ok(SegmentOutput {
+            progress,
+            pruned: expired,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: Some(block_number),
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+/// Returns `true` if the history index has a change of the key after `block_number`.
+///
+/// `start_key` is the sharded key of the key with `block_number + 1` as the highest block number,
+/// which is the first shard that may contain changes after `block_number`.
+fn changed_after<T, C>(
+    cursor: &mut C,
+    start_key: T::Key,
+    is_same_key: impl Fn(&T::Key) -> bool,
+    block_number: BlockNumber,
+) -> Result<bool, PrunerError>
+where
+    T: Table<Value = BlockNumberList>,
+    C: DbCursorRO<T>,
+{
+    Ok(cursor
+        .seek(start_key)?
+        .filter(|(key, _)| is_same_key(key))
+        .is_some_and(|(_, blocks)| blocks.max().is_some_and(|max| max > block_number)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{PruneInput, Segment, SegmentOutput, StateExpiry};
+    use alloy_primitives::{Address, B256, U256};
+    use assert_matches::assert_matches;
+    use reth_db::tables;
+    use reth_primitives::{Account, StorageEntry};
+    use reth_provider::{AccountReader, PruneCheckpointReader, StateProvider};
+    use reth_prune_types::{
+        PruneCheckpoint, PruneInterruptReason, PruneLimiter, PruneMode, PruneProgress, PruneSegment,
+    };
+    use reth_stages::test_utils::TestStageDB;
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+
+        let account = |nonce| Account { nonce, ..Default::default() };
+        let slot = |address: &Address| StorageEntry {
+            key: B256::with_last_byte(address.0[19]),
+            value: U256::from(address.0[19]),
+        };
+        let [first, second, third] = [1, 2, 3].map(Address::with_last_byte);
+
+        // `first` is changed in block 0, `second` in blocks 0 and 1, `third` in blocks 0 and 3.
+        let changesets = vec![
+            vec![
+                (first, account(0), vec![slot(&first)]),
+                (second, account(0), vec![slot(&second)]),
+                (third, account(0), vec![]),
+            ],
+            vec![(second, account(1), vec![slot(&second)])],
+            vec![],
+            vec![(third, account(1), vec![])],
+        ];
+        db.insert_changesets(changesets.clone(), None).expect("insert changesets");
+        db.insert_history(changesets, None).expect("insert history");
+        db.insert_accounts_and_storages([
+            (first, (account(1), vec![slot(&first)])),
+            (second, (account(2), vec![slot(&second)])),
+            (third, (account(2), vec![])),
+        ])
+        .expect("insert state");
+
+        let test_prune = |to_block, limit, expected_progress, expected_checkpoint| {
+            let prune_mode = PruneMode::Before(to_block + 1);
+            let input = PruneInput {
+                previous_checkpoint: db
+                    .factory
+                    .provider()
+                    .unwrap()
+                    .get_prune_checkpoint(PruneSegment::StateExpiry)
+                    .unwrap(),
+                to_block,
+                limiter: PruneLimiter::default().set_deleted_entries_limit(limit),
+            };
+            let segment = StateExpiry::new(prune_mode);
+
+            let provider = db.factory.provider_rw().unwrap();
+            let result = segment.prune(&provider, input).unwrap();
+            assert_matches!(
+                result,
+                SegmentOutput { progress, checkpoint: Some(checkpoint), .. }
+                    if progress == expected_progress &&
+                        checkpoint.block_number == Some(expected_checkpoint)
+            );
+            segment
+                .save_checkpoint(
+                    &provider,
+                    result.checkpoint.unwrap().as_prune_checkpoint(prune_mode),
+                )
+                .unwrap();
+            provider.commit().expect("commit");
+
+            assert_eq!(
+                db.factory
+                    .provider()
+                    .unwrap()
+                    .get_prune_checkpoint(PruneSegment::StateExpiry)
+                    .unwrap(),
+                Some(PruneCheckpoint {
+                    block_number: Some(expected_checkpoint),
+                    tx_number: None,
+                    prune_mode
+                })
+            );
+        };
+
+        // Block 0 is processed as a whole even though it reaches the limit
+        test_prune(
+            1,
+            1,
+            PruneProgress::HasMoreData(PruneInterruptReason::DeletedEntriesLimitReached),
+            0,
+        );
+        assert_eq!(db.table::<tables::ColdAccountState>().unwrap(), vec![(first, account(1))]);
+        assert_eq!(db.table::<tables::ColdStorageState>().unwrap(), vec![(first, slot(&first))]);
+
+        test_prune(2, 10, PruneProgress::Finished, 2);
+        assert_eq!(
+            db.table::<tables::ColdAccountState>().unwrap(),
+            vec![(first, account(1)), (second, account(2))]
+        );
+        assert_eq!(
+            db.table::<tables::ColdStorageState>().unwrap(),
+            vec![(first, slot(&first)), (second, slot(&second))]
+        );
+        assert_eq!(db.table::<tables::PlainAccountState>().unwrap(), vec![(third, account(2))]);
+        assert!(db.table_is_empty::<tables::PlainStorageState>().unwrap());
+
+        // Expired state is still readable
+        let provider = db.factory.latest().unwrap();
+        assert_eq!(provider.basic_account(second).unwrap(), Some(account(2)));
+        assert_eq!(provider.storage(first, slot(&first).key).unwrap(), Some(slot(&first).value));
+    }
+}
@@ -50,9 +50,10 @@ impl<DB: Database> Segment<DB> for TransactionLookup {
         }
         .into_inner();
         let tx_range = start..=
-            Some(end)
-                .min(input.limiter.deleted_entries_limit_left().map(|left| start + left as u64 - 1))
-                .unwrap();
+            input
+                .limiter
+                .deleted_entries_limit_left()
+                .map_or(end, |left| end.min(start + left as u64 - 1));
         let tx_range_end = *tx_range.end();
 
         // Retrieve transactions in the range and calculate their hashes in parallel
@@ -12,8 +12,10 @@ mod checkpoint;
 mod limiter;
 mod mode;
 mod pruner;
+mod retention;
 mod segment;
 mod target;
+mod window;
 
 pub use checkpoint::PruneCheckpoint;
 pub use limiter::PruneLimiter;
@@ -21,10 +23,12 @@ pub use mode::PruneMode;
 pub use pruner::{
     PruneInterruptReason, PruneProgress, PrunerOutput, SegmentOutput, SegmentOutputCheckpoint,
 };
+pub use retention::ReceiptsRetentionConfig;
 pub use segment::{PrunePurpose, PruneSegment, PruneSegmentError};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 pub use target::{PruneModes, MINIMUM_PRUNING_DISTANCE};
+pub use window::{ParsePruneWindowError, PruneWindow};
 
 use alloy_primitives::{Address, BlockNumber};
 
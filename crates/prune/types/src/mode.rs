@@ -2,6 +2,7 @@ use crate::{segment::PrunePurpose, PruneSegment, PruneSegmentError};
 use alloy_primitives::BlockNumber;
 use reth_codecs::{add_arbitrary_tests, Compact};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Prune mode.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Compact)]
@@ -15,6 +16,12 @@ pub enum PruneMode {
     Distance(u64),
     /// Prune blocks before the specified block number. The specified block number is not pruned.
     Before(BlockNumber),
+    /// Prune blocks older than the specified number of seconds, relative to the timestamp of the
+    /// tip. Configured as a duration, e.g. `{ keep = "30d" }`.
+    ///
+    /// The pruner resolves it to a block number using the header timestamps, stages don't prune
+    /// segments with a time-based mode while syncing.
+    Keep(#[serde(with = "keep_duration")] u64),
 }
 
 impl PruneMode {
@@ -25,6 +32,11 @@ impl PruneMode {
         Self::Before(block_number + 1)
     }
 
+    /// Keep blocks of the last `duration`, see [`PruneMode::Keep`].
+    pub const fn keep(duration: Duration) -> Self {
+        Self::Keep(duration.as_secs())
+    }
+
     /// Returns block up to which variant pruning needs to be done, inclusive, according to the
     /// provided tip.
     pub fn prune_target_block(
@@ -44,6 +56,8 @@ impl PruneMode {
             Self::Before(n) if tip - n >= segment.min_blocks(purpose) => {
                 Some(((*n).saturating_sub(1), *self))
             }
+            // Needs to be resolved to a block number first
+            Self::Keep(_) => None,
             _ => return Err(PruneSegmentError::Configuration(segment)),
         };
         Ok(result)
@@ -60,6 +74,7 @@ impl PruneMode {
                 block < tip - *distance
             }
             Self::Before(n) => *n > block,
+            Self::Keep(_) => false,
         }
     }
 
@@ -67,6 +82,31 @@ impl PruneMode {
     pub const fn is_full(&self) -> bool {
         matches!(self, Self::Full)
     }
+
+    /// Returns true if the prune mode is the time-based [`PruneMode::Keep`].
+    pub const fn is_time_based(&self) -> bool {
+        matches!(self, Self::Keep(_))
+    }
+}
+
+/// (De)serializes the seconds of [`PruneMode::Keep`] as a human readable duration.
+mod keep_duration {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub(super) fn serialize<S: Serializer>(
+        seconds: &u64,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&humantime::format_duration(Duration::from_secs(*seconds)))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        let duration = String::deserialize(deserializer)?;
+        humantime::parse_duration(&duration)
+            .map(|duration| duration.as_secs())
+            .map_err(Error::custom)
+    }
 }
 
 #[cfg(test)]
@@ -109,6 +149,8 @@ mod tests {
                 Ok(Some(tip - MINIMUM_PRUNING_DISTANCE - 2)),
             ),
             (PruneMode::Before(tip - 1), Err(PruneSegmentError::Configuration(segment))),
+            // Time-based modes are resolved by the pruner
+            (PruneMode::Keep(86400), Ok(None)),
         ];
 
         for (index, (mode, expected_result)) in tests.into_iter().enumerate() {
@@ -146,6 +188,7 @@ mod tests {
             ),
             (PruneMode::Before(tip + 1), 1, should_prune),
             (PruneMode::Before(tip + 1), tip + 1, !should_prune),
+            (PruneMode::Keep(86400), 1, !should_prune),
         ];
 
         for (index, (mode, block, expected_result)) in tests.into_iter().enumerate() {
@@ -161,12 +204,14 @@ mod tests {
             b: Option<PruneMode>,
             c: Option<PruneMode>,
             d: Option<PruneMode>,
+            e: Option<PruneMode>,
         }
 
         let toml_str = r#"
         a = "full"
         b = { distance = 10 }
         c = { before = 20 }
+        e = { keep = "30d" }
     "#;
 
         assert_matches!(
@@ -175,8 +220,13 @@ mod tests {
                 a: Some(PruneMode::Full),
                 b: Some(PruneMode::Distance(10)),
                 c: Some(PruneMode::Before(20)),
-                d: None
+                d: None,
+                e: Some(PruneMode::Keep(2_592_000)),
             })
         );
+
+        let mode = PruneMode::keep(std::time::Duration::from_secs(30 * 24 * 60 * 60));
+        assert_eq!(toml::to_string(&mode).unwrap(), "keep = \"30days\"\n");
+        assert_eq!(toml::from_str::<PruneMode>("keep = \"30days\"").unwrap(), mode);
     }
 }
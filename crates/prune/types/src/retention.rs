@@ -0,0 +1,82 @@
+use crate::{
+    target::deserialize_opt_prune_mode_with_min_blocks, PruneMode, MINIMUM_PRUNING_DISTANCE,
+};
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Configuration for pruning all receipts, except the ones of transactions sent to the specified
+/// contracts or by the specified senders.
+///
+/// Unlike [`ReceiptsLogPruneConfig`](crate::ReceiptsLogPruneConfig), the retained receipts don't
+/// need to contain any logs, so the status and gas used of every transaction that interacted with
+/// a contract are kept.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReceiptsRetentionConfig {
+    /// Pruning configuration for the receipts that are not retained. Nothing is pruned if unset.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<MINIMUM_PRUNING_DISTANCE, _>"
+    )]
+    pub mode: Option<PruneMode>,
+    /// Receipts of transactions sent to these addresses are retained.
+    pub contracts: BTreeSet<Address>,
+    /// Receipts of transactions sent by these addresses are retained.
+    pub senders: BTreeSet<Address>,
+}
+
+impl ReceiptsRetentionConfig {
+    /// Returns `true` if no receipts are pruned by this configuration.
+    pub const fn is_empty(&self) -> bool {
+        self.mode.is_none()
+    }
+
+    /// Returns `true` if the senders of the transactions are needed to decide which receipts are
+    /// retained.
+    pub fn needs_senders(&self) -> bool {
+        !self.senders.is_empty()
+    }
+
+    /// Returns `true` if the receipt of a transaction sent by `sender` to `to` is retained.
+    pub fn retains(&self, to: Option<Address>, sender: Address) -> bool {
+        to.map_or(false, |to| self.contracts.contains(&to)) || self.senders.contains(&sender)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retains() {
+        let config = ReceiptsRetentionConfig {
+            mode: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+            contracts: BTreeSet::from([Address::with_last_byte(1)]),
+            senders: BTreeSet::from([Address::with_last_byte(2)]),
+        };
+
+        assert!(config.retains(Some(Address::with_last_byte(1)), Address::with_last_byte(3)));
+        assert!(config.retains(Some(Address::with_last_byte(3)), Address::with_last_byte(2)));
+        assert!(config.retains(None, Address::with_last_byte(2)));
+        assert!(!config.retains(None, Address::with_last_byte(1)));
+        assert!(!config.retains(Some(Address::with_last_byte(3)), Address::with_last_byte(3)));
+    }
+
+    #[test]
+    fn deserialize() {
+        let config: ReceiptsRetentionConfig = toml::from_str(
+            r#"
+            mode = { distance = 100000 }
+            contracts = ["0xdac17f958d2ee523a2206206994597c13d831ec7"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.mode, Some(PruneMode::Distance(100000)));
+        assert_eq!(config.contracts.len(), 1);
+        assert!(config.senders.is_empty());
+
+        assert!(toml::from_str::<ReceiptsRetentionConfig>(r#"mode = "full""#).is_err());
+        assert!(toml::from_str::<ReceiptsRetentionConfig>("").unwrap().is_empty());
+    }
+}
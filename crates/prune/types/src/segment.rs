@@ -39,6 +39,13 @@ pub enum PruneSegment {
     Headers,
     /// Prune segment responsible for the `Transactions` table.
     Transactions,
+    /// Prune segment responsible for some rows in `Receipts` table filtered by transaction
+    /// recipient and sender.
+    ReceiptsRetention,
+    /// Prune segment responsible for moving state that wasn't changed for a number of blocks from
+    /// the `PlainAccountState` and `PlainStorageState` tables to the `ColdAccountState` and
+    /// `ColdStorageState` tables.
+    StateExpiry,
 }
 
 impl PruneSegment {
@@ -49,10 +56,12 @@ impl PruneSegment {
                 0
             }
             Self::Receipts if purpose.is_static_file() => 0,
-            Self::ContractLogs | Self::AccountHistory | Self::StorageHistory => {
-                MINIMUM_PRUNING_DISTANCE
-            }
-            Self::Receipts => MINIMUM_PRUNING_DISTANCE,
+            Self::ContractLogs |
+            Self::AccountHistory |
+            Self::StorageHistory |
+            Self::Receipts |
+            Self::ReceiptsRetention |
+            Self::StateExpiry => MINIMUM_PRUNING_DISTANCE,
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::{PruneMode, ReceiptsLogPruneConfig};
+use crate::{PruneMode, ReceiptsLogPruneConfig, ReceiptsRetentionConfig};
 use serde::{Deserialize, Deserializer, Serialize};
 
 /// Minimum distance from the tip necessary for the node to work correctly:
@@ -43,6 +43,17 @@ pub struct PruneModes {
     /// The [`BlockNumber`](`crate::BlockNumber`) represents the starting block from which point
     /// onwards the receipts are preserved.
     pub receipts_log_filter: ReceiptsLogPruneConfig,
+    /// Receipts pruning configuration by retaining only those receipts of transactions sent to or
+    /// by the specified addresses, discarding others. This setting is overridden by `receipts`.
+    pub receipts_retention: ReceiptsRetentionConfig,
+    /// State expiry configuration. State that wasn't changed in the blocks pruned by this mode is
+    /// moved to the cold state tables, from where it's read back transparently and moved back once
+    /// it's changed. Experimental.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<MINIMUM_PRUNING_DISTANCE, _>"
+    )]
+    pub state_expiry: Option<PruneMode>,
 }
 
 impl PruneModes {
@@ -60,12 +71,16 @@ impl PruneModes {
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
+            receipts_retention: Default::default(),
+            state_expiry: None,
         }
     }
 
     /// Returns whether there is any kind of receipt pruning configuration.
     pub fn has_receipts_pruning(&self) -> bool {
-        self.receipts.is_some() || !self.receipts_log_filter.is_empty()
+        self.receipts.is_some() ||
+            !self.receipts_log_filter.is_empty() ||
+            !self.receipts_retention.is_empty()
     }
 
     /// Returns true if all prune modes are set to [`None`].
@@ -82,7 +97,11 @@ impl PruneModes {
 /// 2. For [`PruneMode::Distance(distance`)], it fails if `distance < MIN_BLOCKS + 1`. `+ 1` is
 ///    needed because `PruneMode::Distance(0)` means that we leave zero blocks from the latest,
 ///    meaning we have one block in the database.
-fn deserialize_opt_prune_mode_with_min_blocks<'de, const MIN_BLOCKS: u64, D: Deserializer<'de>>(
+pub(crate) fn deserialize_opt_prune_mode_with_min_blocks<
+    'de,
+    const MIN_BLOCKS: u64,
+    D: Deserializer<'de>,
+>(
     deserializer: D,
 ) -> Result<Option<PruneMode>, D::Error> {
     let prune_mode = Option::<PruneMode>::deserialize(deserializer)?;
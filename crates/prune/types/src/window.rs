@@ -0,0 +1,127 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use thiserror::Error;
+
+const SECONDS_PER_MINUTE: u32 = 60;
+const SECONDS_PER_DAY: u32 = 24 * 60 * SECONDS_PER_MINUTE;
+
+/// A daily time window in UTC, e.g. `02:00-06:00`.
+///
+/// The end of the window is exclusive. A window whose end is before its start spans midnight,
+/// e.g. `22:00-04:00`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PruneWindow {
+    /// Start of the window, in seconds since midnight.
+    start: u32,
+    /// End of the window, in seconds since midnight.
+    end: u32,
+}
+
+impl PruneWindow {
+    /// Creates a new window from the given start and end, in minutes since midnight.
+    ///
+    /// Returns `None` if the window is empty or any of the values is not within a day.
+    pub const fn from_minutes(start: u32, end: u32) -> Option<Self> {
+        if start == end || start >= 24 * 60 || end > 24 * 60 {
+            return None
+        }
+        Some(Self { start: start * SECONDS_PER_MINUTE, end: end * SECONDS_PER_MINUTE })
+    }
+
+    /// Returns `true` if the given number of seconds since midnight lies within the window.
+    pub const fn contains(&self, seconds_of_day: u32) -> bool {
+        if self.start < self.end {
+            self.start <= seconds_of_day && seconds_of_day < self.end
+        } else {
+            self.start <= seconds_of_day || seconds_of_day < self.end
+        }
+    }
+
+    /// Returns `true` if the given time lies within the window.
+    pub fn contains_time(&self, time: SystemTime) -> bool {
+        let seconds = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        self.contains((seconds % SECONDS_PER_DAY as u64) as u32)
+    }
+}
+
+impl fmt::Display for PruneWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [start, end] = [self.start, self.end].map(|seconds| seconds / SECONDS_PER_MINUTE);
+        write!(f, "{:02}:{:02}-{:02}:{:02}", start / 60, start % 60, end / 60, end % 60)
+    }
+}
+
+/// Error returned when parsing a [`PruneWindow`] fails.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid prune window `{0}`, expected a non-empty range such as `02:00-06:00`")]
+pub struct ParsePruneWindowError(String);
+
+impl FromStr for PruneWindow {
+    type Err = ParsePruneWindowError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_time = |time: &str| {
+            let (hours, minutes) = time.trim().split_once(':')?;
+            let (hours, minutes) = (hours.parse::<u32>().ok()?, minutes.parse::<u32>().ok()?);
+            // `24:00` is allowed as the end of a window
+            (minutes < 60 && (hours < 24 || (hours == 24 && minutes == 0)))
+                .then_some(hours * 60 + minutes)
+        };
+
+        s.split_once('-')
+            .and_then(|(start, end)| Self::from_minutes(parse_time(start)?, parse_time(end)?))
+            .ok_or_else(|| ParsePruneWindowError(s.to_string()))
+    }
+}
+
+impl Serialize for PruneWindow {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PruneWindow {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn parse() {
+        let window = "02:00-06:30".parse::<PruneWindow>().unwrap();
+        assert_eq!(window, PruneWindow::from_minutes(120, 390).unwrap());
+        assert_eq!(window.to_string(), "02:00-06:30");
+        assert_eq!("22:00-24:00".parse::<PruneWindow>().unwrap().to_string(), "22:00-24:00");
+
+        for invalid in ["", "02:00", "02:00-02:00", "24:00-02:00", "02:60-03:00", "2-3"] {
+            assert!(invalid.parse::<PruneWindow>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn contains() {
+        let window = "02:00-06:00".parse::<PruneWindow>().unwrap();
+        assert!(!window.contains(2 * 3600 - 1));
+        assert!(window.contains(2 * 3600));
+        assert!(!window.contains(6 * 3600));
+
+        let midnight = "22:00-04:00".parse::<PruneWindow>().unwrap();
+        assert!(midnight.contains(23 * 3600));
+        assert!(midnight.contains(3600));
+        assert!(!midnight.contains(12 * 3600));
+
+        // 1970-01-02 03:00 UTC
+        let time = UNIX_EPOCH + Duration::from_secs(SECONDS_PER_DAY as u64 + 3 * 3600);
+        assert!(window.contains_time(time));
+        assert!(!window.contains_time(time + Duration::from_secs(3 * 3600)));
+    }
+}
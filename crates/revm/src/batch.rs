@@ -5,7 +5,7 @@ use crate::{
     primitives::alloy_primitives::BlockNumber,
 };
 use reth_execution_errors::{BlockExecutionError, InternalBlockExecutionError};
-use reth_primitives::{Receipt, Receipts, Request, Requests};
+use reth_primitives::{BlockWithSenders, Receipt, Receipts, Request, Requests};
 use reth_prune_types::{PruneMode, PruneModes, PruneSegmentError, MINIMUM_PRUNING_DISTANCE};
 use revm::db::states::bundle_state::BundleRetention;
 
@@ -114,11 +114,15 @@ impl BlockBatchRecord {
         }
     }
 
-    /// Save receipts to the executor.
-    pub fn save_receipts(&mut self, receipts: Vec<Receipt>) -> Result<(), BlockExecutionError> {
+    /// Save receipts of the transactions of the given block to the executor.
+    pub fn save_receipts(
+        &mut self,
+        block: &BlockWithSenders,
+        receipts: Vec<Receipt>,
+    ) -> Result<(), BlockExecutionError> {
         let mut receipts = receipts.into_iter().map(Some).collect();
         // Prune receipts if necessary.
-        self.prune_receipts(&mut receipts).map_err(InternalBlockExecutionError::from)?;
+        self.prune_receipts(block, &mut receipts).map_err(InternalBlockExecutionError::from)?;
         // Save receipts.
         self.receipts.push(receipts);
         Ok(())
@@ -127,6 +131,7 @@ impl BlockBatchRecord {
     /// Prune receipts according to the pruning configuration.
     fn prune_receipts(
         &mut self,
+        block: &BlockWithSenders,
         receipts: &mut Vec<Option<Receipt>>,
     ) -> Result<(), PruneSegmentError> {
         let (Some(first_block), Some(tip)) = (self.first_block, self.tip) else { return Ok(()) };
@@ -135,7 +140,8 @@ impl BlockBatchRecord {
 
         // Block receipts should not be retained
         if self.prune_modes.receipts == Some(PruneMode::Full) ||
-            // [`PruneSegment::Receipts`] takes priority over [`PruneSegment::ContractLogs`]
+            // [`PruneSegment::Receipts`] takes priority over [`PruneSegment::ContractLogs`] and
+            // [`PruneSegment::ReceiptsRetention`]
             self.prune_modes.receipts.map_or(false, |mode| mode.should_prune(block_number, tip))
         {
             receipts.clear();
@@ -171,6 +177,19 @@ impl BlockBatchRecord {
             }
         }
 
+        // Receipts of transactions that weren't sent to or by one of the configured addresses are
+        // removed, on top of the ones removed by [`PruneSegment::ContractLogs`].
+        let retention = &self.prune_modes.receipts_retention;
+        if retention.mode.map_or(false, |mode| mode.should_prune(block_number, tip)) {
+            for (receipt, (sender, transaction)) in
+                receipts.iter_mut().zip(block.transactions_with_sender())
+            {
+                if !retention.retains(transaction.to(), *sender) {
+                    receipt.take();
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -183,14 +202,16 @@ impl BlockBatchRecord {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use reth_primitives::{Address, Log, Receipt};
-    use reth_prune_types::{PruneMode, ReceiptsLogPruneConfig};
+    use reth_primitives::{
+        Address, Block, Log, Receipt, Transaction, TransactionSigned, TxKind, TxLegacy,
+    };
+    use reth_prune_types::{PruneMode, ReceiptsLogPruneConfig, ReceiptsRetentionConfig};
     #[cfg(feature = "std")]
-    use std::collections::BTreeMap;
+    use std::collections::{BTreeMap, BTreeSet};
     #[cfg(not(feature = "std"))]
     extern crate alloc;
     #[cfg(not(feature = "std"))]
-    use alloc::collections::BTreeMap;
+    use alloc::collections::{BTreeMap, BTreeSet};
 
     #[test]
     fn test_save_receipts_empty() {
@@ -199,7 +220,7 @@ mod tests {
         let receipts = vec![];
 
         // Verify that saving receipts completes without error
-        assert!(recorder.save_receipts(receipts).is_ok());
+        assert!(recorder.save_receipts(&BlockWithSenders::default(), receipts).is_ok());
         // Verify that the saved receipts are equal to a nested empty vector
         assert_eq!(*recorder.receipts(), vec![vec![]].into());
     }
@@ -210,7 +231,7 @@ mod tests {
         let receipts = vec![Receipt::default()];
 
         // Verify that saving receipts completes without error
-        assert!(recorder.save_receipts(receipts).is_ok());
+        assert!(recorder.save_receipts(&BlockWithSenders::default(), receipts).is_ok());
         // Verify that there is one block of receipts
         assert_eq!(recorder.receipts().len(), 1);
         // Verify that the first block contains one receipt
@@ -232,7 +253,7 @@ mod tests {
         let receipts = vec![Receipt::default()];
 
         // Verify that saving receipts completes without error
-        assert!(recorder.save_receipts(receipts).is_ok());
+        assert!(recorder.save_receipts(&BlockWithSenders::default(), receipts).is_ok());
         // Verify that there is one block of receipts
         assert_eq!(recorder.receipts().len(), 1);
         // Verify that the first block contains one receipt
@@ -254,7 +275,7 @@ mod tests {
         let receipts = vec![Receipt::default()];
 
         // Verify that saving receipts completes without error
-        assert!(recorder.save_receipts(receipts).is_ok());
+        assert!(recorder.save_receipts(&BlockWithSenders::default(), receipts).is_ok());
         // Verify that there is one block of receipts
         assert_eq!(recorder.receipts().len(), 1);
         // Verify that the first block contains one receipt
@@ -278,7 +299,7 @@ mod tests {
         let receipts = vec![Receipt::default()];
 
         // Verify that saving receipts completes without error
-        assert!(recorder.save_receipts(receipts).is_ok());
+        assert!(recorder.save_receipts(&BlockWithSenders::default(), receipts).is_ok());
         // Verify that there is one block of receipts
         assert_eq!(recorder.receipts().len(), 1);
         // Verify that the first block contains one receipt
@@ -305,7 +326,7 @@ mod tests {
         let receipts = vec![Receipt::default()];
 
         // Verify that saving receipts completes without error
-        assert!(recorder.save_receipts(receipts).is_ok());
+        assert!(recorder.save_receipts(&BlockWithSenders::default(), receipts).is_ok());
         // Verify that there is one block of receipts
         assert_eq!(recorder.receipts().len(), 1);
         // Verify that the receipts are pruned (empty)
@@ -337,7 +358,7 @@ mod tests {
         let mut receipt = Receipt::default();
         receipt.logs.push(Log { address: Address::with_last_byte(4), ..Default::default() });
         let receipts = vec![receipt.clone()];
-        assert!(recorder.save_receipts(receipts).is_ok());
+        assert!(recorder.save_receipts(&BlockWithSenders::default(), receipts).is_ok());
         // Verify that the receipts are pruned (empty)
         assert_eq!(recorder.receipts().len(), 1);
         assert_eq!(recorder.receipts()[0], vec![None]);
@@ -346,7 +367,7 @@ mod tests {
         let mut receipt1 = Receipt::default();
         receipt1.logs.push(Log { address: Address::with_last_byte(1), ..Default::default() });
         let receipts = vec![receipt1.clone()];
-        assert!(recorder.save_receipts(receipts).is_ok());
+        assert!(recorder.save_receipts(&BlockWithSenders::default(), receipts).is_ok());
         // Verify that the second block of receipts contains the receipt
         assert_eq!(recorder.receipts().len(), 2);
         assert_eq!(recorder.receipts()[1][0], Some(receipt1));
@@ -355,7 +376,7 @@ mod tests {
         let mut receipt2 = Receipt::default();
         receipt2.logs.push(Log { address: Address::with_last_byte(2), ..Default::default() });
         let receipts = vec![receipt2.clone()];
-        assert!(recorder.save_receipts(receipts).is_ok());
+        assert!(recorder.save_receipts(&BlockWithSenders::default(), receipts).is_ok());
         // Verify that the third block of receipts contains the receipt
         assert_eq!(recorder.receipts().len(), 3);
         assert_eq!(recorder.receipts()[2][0], Some(receipt2));
@@ -364,9 +385,60 @@ mod tests {
         let mut receipt3 = Receipt::default();
         receipt3.logs.push(Log { address: Address::with_last_byte(3), ..Default::default() });
         let receipts = vec![receipt3.clone()];
-        assert!(recorder.save_receipts(receipts).is_ok());
+        assert!(recorder.save_receipts(&BlockWithSenders::default(), receipts).is_ok());
         // Verify that the fourth block of receipts contains the receipt
         assert_eq!(recorder.receipts().len(), 4);
         assert_eq!(recorder.receipts()[3][0], Some(receipt3));
     }
+
+    #[test]
+    fn test_save_receipts_with_receipts_retention() {
+        let prune_modes = PruneModes {
+            receipts_retention: ReceiptsRetentionConfig {
+                mode: Some(PruneMode::Before(1000)),
+                contracts: BTreeSet::from([Address::with_last_byte(1)]),
+                senders: BTreeSet::from([Address::with_last_byte(2)]),
+            },
+            ..Default::default()
+        };
+        let mut recorder = BlockBatchRecord::new(prune_modes);
+        recorder.set_first_block(999);
+        recorder.set_tip(1300000);
+
+        // Transactions from the sender to the contract, from the sender to another address, and
+        // from another sender to another address
+        let transaction = |to: u8| {
+            TransactionSigned::from_transaction_and_signature(
+                Transaction::Legacy(TxLegacy {
+                    to: TxKind::Call(Address::with_last_byte(to)),
+                    ..Default::default()
+                }),
+                Default::default(),
+            )
+        };
+        let block = BlockWithSenders {
+            block: Block {
+                body: vec![transaction(1), transaction(3), transaction(3)],
+                ..Default::default()
+            },
+            senders: vec![
+                Address::with_last_byte(3),
+                Address::with_last_byte(2),
+                Address::with_last_byte(3),
+            ],
+        };
+        let receipts = vec![Receipt::default(); 3];
+
+        // Only the receipt of the transaction that neither was sent to a contract nor by a sender
+        // from the list is pruned
+        assert!(recorder.save_receipts(&block, receipts.clone()).is_ok());
+        assert_eq!(
+            recorder.receipts()[0],
+            vec![Some(Receipt::default()), Some(Receipt::default()), None]
+        );
+
+        // Receipts from block 1000 onwards are not pruned
+        assert!(recorder.save_receipts(&block, receipts).is_ok());
+        assert_eq!(recorder.receipts()[1], vec![Some(Receipt::default()); 3]);
+    }
 }
@@ -8,7 +8,7 @@ use reth_storage_api::{
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     prefix_set::TriePrefixSetsMut, updates::TrieUpdates, AccountProof, HashedPostState,
-    HashedStorage,
+    HashedStorage, MultiProof,
 };
 
 #[cfg(not(feature = "std"))]
@@ -119,6 +119,14 @@ impl StateProofProvider for StateProviderTest {
         unimplemented!("proof generation is not supported")
     }
 
+    fn multiproof(
+        &self,
+        _hashed_state: HashedPostState,
+        _targets: HashMap<B256, Vec<B256>>,
+    ) -> ProviderResult<MultiProof> {
+        unimplemented!("proof generation is not supported")
+    }
+
     fn witness(
         &self,
         _overlay: HashedPostState,
@@ -12,8 +12,8 @@ use jsonrpsee::{
     core::TEN_MB_SIZE_BYTES,
     server::{
         middleware::rpc::{RpcLoggerLayer, RpcServiceT},
-        stop_channel, ConnectionGuard, ConnectionPermit, IdProvider, RandomIntegerIdProvider,
-        ServerHandle, StopHandle,
+        stop_channel, BatchRequestConfig, ConnectionGuard, ConnectionPermit, IdProvider,
+        RandomIntegerIdProvider, ServerHandle, StopHandle,
     },
     BoundedSubscriptions, MethodSink, Methods,
 };
@@ -119,6 +119,21 @@ where
         Ok(server_handle)
     }
 
+    /// Applies the configured permission mode to the socket file.
+    #[cfg(unix)]
+    fn set_socket_permissions(&self) -> io::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let Some(mode) = self.cfg.socket_permissions else { return Ok(()) };
+        std::fs::set_permissions(&self.endpoint, std::fs::Permissions::from_mode(mode))
+    }
+
+    /// Socket permissions are only supported on unix.
+    #[cfg(not(unix))]
+    fn set_socket_permissions(&self) -> io::Result<()> {
+        Ok(())
+    }
+
     async fn start_inner(
         self,
         methods: Methods,
@@ -149,6 +164,13 @@ where
             }
         };
 
+        if let Err(err) = self.set_socket_permissions() {
+            on_ready
+                .send(Err(IpcServerStartError { endpoint: self.endpoint.clone(), source: err }))
+                .ok();
+            return;
+        }
+
         // signal that we're ready to accept connections
         on_ready.send(Ok(())).ok();
 
@@ -388,6 +410,7 @@ where
 
         let max_response_body_size = self.inner.server_cfg.max_response_body_size as usize;
         let max_request_body_size = self.inner.server_cfg.max_request_body_size as usize;
+        let batch_requests_config = self.inner.server_cfg.batch_requests_config;
         let conn = self.inner.conn_permit.clone();
         let rpc_service = self.rpc_middleware.service(RpcService::new(
             self.inner.methods.clone(),
@@ -407,6 +430,7 @@ where
                 rpc_service,
                 max_response_body_size,
                 max_request_body_size,
+                batch_requests_config,
                 conn,
             )
             .await
@@ -548,6 +572,10 @@ pub struct Settings {
     max_subscriptions_per_connection: u32,
     /// Number of messages that server is allowed `buffer` until backpressure kicks in.
     message_buffer_capacity: u32,
+    /// Configuration of batch requests.
+    batch_requests_config: BatchRequestConfig,
+    /// Unix permission mode of the socket file.
+    socket_permissions: Option<u32>,
     /// Custom tokio runtime to run the server on.
     tokio_runtime: Option<tokio::runtime::Handle>,
 }
@@ -561,6 +589,8 @@ impl Default for Settings {
             max_connections: 100,
             max_subscriptions_per_connection: 1024,
             message_buffer_capacity: 1024,
+            batch_requests_config: BatchRequestConfig::Unlimited,
+            socket_permissions: None,
             tokio_runtime: None,
         }
     }
@@ -640,6 +670,21 @@ impl<HttpMiddleware, RpcMiddleware> Builder<HttpMiddleware, RpcMiddleware> {
         self
     }
 
+    /// Configure how batch requests are handled. Default is [`BatchRequestConfig::Unlimited`].
+    pub const fn set_batch_request_config(mut self, cfg: BatchRequestConfig) -> Self {
+        self.settings.batch_requests_config = cfg;
+        self
+    }
+
+    /// Set the unix permission mode of the socket file, e.g. `0o600` to only allow access by the
+    /// owner. By default the mode is derived from the process umask.
+    ///
+    /// This has no effect on windows.
+    pub const fn set_socket_permissions(mut self, mode: u32) -> Self {
+        self.settings.socket_permissions = Some(mode);
+        self
+    }
+
     /// Configure a custom [`tokio::runtime::Handle`] to run the server on.
     ///
     /// Default: [`tokio::spawn`]
@@ -904,6 +949,45 @@ mod tests {
         assert!(response.is_err());
     }
 
+    #[tokio::test]
+    async fn can_set_the_max_batch_size() {
+        let endpoint = &dummy_name();
+        let server = Builder::default()
+            .set_batch_request_config(BatchRequestConfig::Limit(2))
+            .build(endpoint.clone());
+        let mut module = RpcModule::new(());
+        module.register_method("anything", |_, _, _| "succeed").unwrap();
+        let handle = server.start(module).await.unwrap();
+        tokio::spawn(handle.stopped());
+
+        let client = IpcClientBuilder::default().build(endpoint).await.unwrap();
+        let mut batch_request_builder = BatchRequestBuilder::new();
+        let _ = batch_request_builder.insert("anything", rpc_params![]);
+        let _ = batch_request_builder.insert("anything", rpc_params![]);
+        let response: client::BatchResponse<'_, String> =
+            client.batch_request(batch_request_builder.clone()).await.unwrap();
+        assert_eq!(response.num_successful_calls(), 2);
+
+        let _ = batch_request_builder.insert("anything", rpc_params![]);
+        let response: Result<client::BatchResponse<'_, String>, Error> =
+            client.batch_request(batch_request_builder).await;
+        assert!(response.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn can_set_socket_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let endpoint = &dummy_name();
+        let server = Builder::default().set_socket_permissions(0o600).build(endpoint.clone());
+        let handle = server.start(RpcModule::new(())).await.unwrap();
+        tokio::spawn(handle.stopped());
+
+        let mode = std::fs::metadata(endpoint).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
     #[tokio::test]
     async fn can_set_max_connections() {
         init_test_tracing();
@@ -1,6 +1,13 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_network_peers::{AnyNode, NodeRecord};
-use reth_rpc_types::admin::{NodeInfo, PeerInfo};
+use reth_network_types::InboundFilter;
+use reth_rpc_server_types::{
+    integrity::IntegrityCheckStatus,
+    log_levels::LogLevelOverride,
+    peers::AdminPeerInfo,
+    runtime::{RpcConfigUpdate, RpcRuntimeConfig},
+};
+use reth_rpc_types::admin::NodeInfo;
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -10,7 +17,10 @@ pub trait AdminApi {
     #[method(name = "addPeer")]
     fn add_peer(&self, record: NodeRecord) -> RpcResult<bool>;
 
-    /// Disconnects from a remote node if the connection exists.
+    /// Removes a remote node from the peer set and disconnects from it if the connection exists.
+    ///
+    /// Trusted nodes stay in the peer set until they're removed with `admin_removeTrustedPeer`, but
+    /// their session is closed as well.
     ///
     /// Returns true if the peer was successfully removed.
     #[method(name = "removePeer")]
@@ -31,8 +41,10 @@ pub trait AdminApi {
     /// connected remote nodes at the networking granularity. These include general information
     /// about the nodes themselves as participants of the devp2p P2P overlay protocol, as well as
     /// specialized information added by each of the running application protocols
+    ///
+    /// Besides the standard peer info, the details of the session with each peer are returned.
     #[method(name = "peers")]
-    async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
+    async fn peers(&self) -> RpcResult<Vec<AdminPeerInfo>>;
 
     /// Creates an RPC subscription which serves events received from the network.
     #[subscription(
@@ -45,4 +57,58 @@ pub trait AdminApi {
     /// Returns the ENR of the node.
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
+
+    /// Returns the filter that incoming sessions are checked against.
+    #[method(name = "inboundFilter")]
+    async fn inbound_filter(&self) -> RpcResult<InboundFilter>;
+
+    /// Replaces the filter that incoming sessions are checked against.
+    ///
+    /// Connected inbound peers that are rejected by the new filter are disconnected.
+    #[method(name = "setInboundFilter")]
+    fn set_inbound_filter(&self, filter: InboundFilter) -> RpcResult<bool>;
+
+    /// Returns the RPC configuration that can be changed while the node is running.
+    #[method(name = "rpcConfig")]
+    fn rpc_config(&self) -> RpcResult<RpcRuntimeConfig>;
+
+    /// Enables or disables served RPC namespaces and changes request limits without restarting
+    /// the node.
+    ///
+    /// Changes apply to all requests that are handled afterwards, open connections and
+    /// subscriptions are kept. Returns the updated configuration.
+    #[method(name = "updateRpcConfig")]
+    fn update_rpc_config(&self, update: RpcConfigUpdate) -> RpcResult<RpcRuntimeConfig>;
+
+    /// Returns the log level overrides of all targets.
+    #[method(name = "logLevels")]
+    fn log_levels(&self) -> RpcResult<Vec<LogLevelOverride>>;
+
+    /// Sets the log level of a target, e.g. `debug` for `reth::network`, without restarting the
+    /// node.
+    ///
+    /// If a duration in seconds is given, the override is removed again once it elapsed. Returns
+    /// the log level overrides of all targets.
+    #[method(name = "setLogLevel")]
+    fn set_log_level(
+        &self,
+        target: String,
+        level: String,
+        duration: Option<u64>,
+    ) -> RpcResult<Vec<LogLevelOverride>>;
+
+    /// Removes the log level override of the target, or of all targets if none is given.
+    ///
+    /// Returns the log level overrides of all remaining targets.
+    #[method(name = "resetLogLevel")]
+    fn reset_log_level(&self, target: Option<String>) -> RpcResult<Vec<LogLevelOverride>>;
+
+    /// Returns the progress and findings of the background integrity check of the database.
+    #[method(name = "integrityCheck")]
+    fn integrity_check(&self) -> RpcResult<IntegrityCheckStatus>;
+
+    /// Discards the findings of the background integrity check and starts it over from the
+    /// genesis block.
+    #[method(name = "resetIntegrityCheck")]
+    fn reset_integrity_check(&self) -> RpcResult<bool>;
 }
@@ -7,6 +7,7 @@ use alloy_json_rpc::RpcObject;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_engine_primitives::EngineTypes;
 use reth_primitives::{Address, BlockHash, BlockId, BlockNumberOrTag, Bytes, B256, U256, U64};
+use reth_rpc_server_types::{proof::AccountProofResponse, sync::SyncStatus};
 use reth_rpc_types::{
     engine::{
         ClientVersionV1, ExecutionPayloadBodiesV1, ExecutionPayloadBodiesV2,
@@ -14,8 +15,7 @@ use reth_rpc_types::{
         ForkchoiceState, ForkchoiceUpdated, PayloadId, PayloadStatus, TransitionConfiguration,
     },
     state::StateOverride,
-    BlockOverrides, EIP1186AccountProofResponse, Filter, JsonStorageKey, Log, SyncStatus,
-    TransactionRequest,
+    BlockOverrides, Filter, JsonStorageKey, Log, TransactionRequest,
 };
 // NOTE: We can't use associated types in the `EngineApi` trait because of jsonrpsee, so we use a
 // generic here. It would be nice if the rpc macro would understand which types need to have serde.
@@ -265,11 +265,14 @@ pub trait EngineEthApi<T: RpcObject, B: RpcObject> {
 
     /// Returns the account and storage values of the specified account including the Merkle-proof.
     /// This call can be used to verify that the data you are pulling from is not tampered with.
+    ///
+    /// With the experimental verkle trie backend, the verkle trie leaves of the account are
+    /// returned with a verkle proof instead.
     #[method(name = "getProof")]
     async fn get_proof(
         &self,
         address: Address,
         keys: Vec<JsonStorageKey>,
         block_number: Option<BlockId>,
-    ) -> RpcResult<EIP1186AccountProofResponse>;
+    ) -> RpcResult<AccountProofResponse>;
 }
@@ -0,0 +1,29 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_primitives::{U256, U64};
+use reth_rpc_types::anvil::MineOptions;
+
+/// The `evm` namespace of a dev node, to mine blocks on demand and to control their timestamps.
+///
+/// These are the mining and time related methods that Anvil, Hardhat and Ganache share.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "evm"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "evm"))]
+pub trait EvmApi {
+    /// Mines a block, or the given number of blocks, independent of the mining mode. Mines empty
+    /// blocks if there are no transactions to include.
+    ///
+    /// Returns "0x0" once the blocks are mined.
+    #[method(name = "mine")]
+    async fn evm_mine(&self, opts: Option<MineOptions>) -> RpcResult<String>;
+
+    /// Jumps forward in time by the given amount of seconds. The next mined block includes the
+    /// adjustment.
+    ///
+    /// Returns the total time adjustment, in seconds.
+    #[method(name = "increaseTime")]
+    async fn evm_increase_time(&self, seconds: U256) -> RpcResult<i64>;
+
+    /// Sets the timestamp of the next mined block, which must be after the timestamp of the latest
+    /// block. The blocks after it continue from this timestamp.
+    #[method(name = "setNextBlockTimestamp")]
+    async fn evm_set_next_block_timestamp(&self, timestamp: U64) -> RpcResult<()>;
+}
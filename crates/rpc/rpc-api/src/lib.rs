@@ -18,12 +18,14 @@ mod admin;
 mod anvil;
 mod debug;
 mod engine;
+mod evm;
 mod ganache;
 mod hardhat;
 mod mev;
 mod net;
 mod otterscan;
 mod reth;
+mod reth_db;
 mod rpc;
 mod trace;
 mod txpool;
@@ -37,12 +39,15 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
+        anvil::AnvilApiServer,
         debug::DebugApiServer,
         engine::{EngineApiServer, EngineEthApiServer},
+        evm::EvmApiServer,
         mev::MevApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
         reth::RethApiServer,
+        reth_db::RethDbApiServer,
         rpc::RpcApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
@@ -67,11 +72,14 @@ pub mod clients {
         anvil::AnvilApiClient,
         debug::DebugApiClient,
         engine::{EngineApiClient, EngineEthApiClient},
+        evm::EvmApiClient,
         ganache::GanacheApiClient,
         hardhat::HardhatApiClient,
         mev::MevApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
+        reth::RethApiClient,
+        reth_db::RethDbApiClient,
         rpc::RpcApiServer,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
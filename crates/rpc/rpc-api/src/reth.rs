@@ -1,5 +1,18 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockId, U256};
+use reth_network_types::TransactionPropagation;
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, TxHash, U256, U64};
+use reth_rpc_server_types::{
+    blob::BlobSidecarItem,
+    capabilities::NodeCapabilities,
+    proof::{MultiProofResponse, MultiProofTarget},
+    snapshot::StateSnapshotInfo,
+    state_changes::BlockStateChanges,
+    state_history::{AccountHistoryEntry, StateHistory, StorageHistoryEntry},
+    witness::CallWitness,
+};
+use reth_rpc_types::{
+    serde_helpers::JsonStorageKey, state::StateOverride, BlockOverrides, TransactionRequest,
+};
 use std::collections::HashMap;
 
 /// Reth API namespace for reth-specific methods
@@ -12,4 +25,106 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Returns the accounts that were created, updated or deleted by a block, with the storage
+    /// slots they changed and the code they deployed, read from the changesets of the block.
+    #[method(name = "getStateChanges")]
+    async fn reth_get_state_changes(&self, block_id: BlockId) -> RpcResult<BlockStateChanges>;
+
+    /// Returns the balance, nonce and code hash of an account after each block in the given range
+    /// that changed it, read from the account history index.
+    ///
+    /// At most `limit` entries are returned, the query can be continued from `nextBlock`.
+    #[method(name = "getBalanceHistory")]
+    async fn reth_get_balance_history(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        limit: Option<usize>,
+    ) -> RpcResult<StateHistory<AccountHistoryEntry>>;
+
+    /// Returns the value of a storage slot after each block in the given range that changed it,
+    /// read from the storage history index.
+    ///
+    /// At most `limit` entries are returned, the query can be continued from `nextBlock`.
+    #[method(name = "getStorageHistory")]
+    async fn reth_get_storage_history(
+        &self,
+        address: Address,
+        slot: JsonStorageKey,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        limit: Option<usize>,
+    ) -> RpcResult<StateHistory<StorageHistoryEntry>>;
+
+    /// Returns the active chain spec, hardfork schedule, enabled RPC namespaces, request limits
+    /// and prune configuration of the node.
+    #[method(name = "config")]
+    async fn reth_config(&self) -> RpcResult<NodeCapabilities>;
+
+    /// Executes a new message call like `eth_call` and returns its result together with the
+    /// minimal state witness of the call: all accessed accounts, storage slots and bytecodes, and
+    /// the trie nodes required to prove them against the state root of the block.
+    #[method(name = "callWithWitness")]
+    async fn reth_call_with_witness(
+        &self,
+        request: TransactionRequest,
+        block_number: Option<BlockId>,
+    ) -> RpcResult<CallWitness>;
+
+    /// Returns the values of the given accounts and storage slots, with a single Merkle-proof
+    /// like `eth_getProof` in which trie nodes shared by multiple accounts and slots are only
+    /// included once.
+    #[method(name = "getMultiProof")]
+    async fn reth_get_multi_proof(
+        &self,
+        targets: Vec<MultiProofTarget>,
+        block_number: Option<BlockId>,
+    ) -> RpcResult<MultiProofResponse>;
+
+    /// Pins the state at the given block, so that calls executed with `reth_callOnSnapshot` see
+    /// the same state even if the chain advances or reorgs in between.
+    ///
+    /// The snapshot is released after the configured TTL or with `reth_dropStateSnapshot`.
+    #[method(name = "createStateSnapshot")]
+    async fn reth_create_state_snapshot(&self, block_id: BlockId) -> RpcResult<StateSnapshotInfo>;
+
+    /// Executes a new message call like `eth_call` on the state of a snapshot created with
+    /// `reth_createStateSnapshot`.
+    #[method(name = "callOnSnapshot")]
+    async fn reth_call_on_snapshot(
+        &self,
+        snapshot_id: U64,
+        request: TransactionRequest,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> RpcResult<Bytes>;
+
+    /// Releases a snapshot created with `reth_createStateSnapshot`.
+    ///
+    /// Returns `false` if the snapshot does not exist or already expired.
+    #[method(name = "dropStateSnapshot")]
+    async fn reth_drop_state_snapshot(&self, snapshot_id: U64) -> RpcResult<bool>;
+
+    /// Returns the sidecars of the blob transactions in the given block that are still stored in
+    /// the transaction pool's blob store.
+    ///
+    /// Sidecars are only retained until the block that includes them is finalized. Returns `None`
+    /// if the block does not exist.
+    #[method(name = "getBlobSidecarsByBlock")]
+    async fn reth_get_blob_sidecars_by_block(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<BlobSidecarItem>>>;
+
+    /// Returns when the transaction was first seen and from which peer, and the peers it was
+    /// received from and forwarded to since.
+    ///
+    /// Only recently seen transactions are traced. Returns `None` if the transaction is unknown.
+    #[method(name = "traceTransactionPropagation")]
+    async fn reth_trace_transaction_propagation(
+        &self,
+        hash: TxHash,
+    ) -> RpcResult<Option<TransactionPropagation>>;
 }
@@ -0,0 +1,30 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_server_types::db_stats::{
+    DatabaseStats, PruneCheckpointInfo, StageCheckpointInfo, StaticFileSegmentInfo, TableInfo,
+};
+
+/// Reth database namespace to inspect the composition of the node's storage.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth_db"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth_db"))]
+pub trait RethDbApi {
+    /// Returns the tables, static files, prune checkpoints and stage checkpoints at once.
+    #[method(name = "stats")]
+    fn stats(&self) -> RpcResult<DatabaseStats>;
+
+    /// Returns the size and number of entries of each database table.
+    #[method(name = "tables")]
+    fn tables(&self) -> RpcResult<Vec<TableInfo>>;
+
+    /// Returns the files, entries, size and the block and transaction ranges of each static file
+    /// segment.
+    #[method(name = "staticFiles")]
+    fn static_files(&self) -> RpcResult<Vec<StaticFileSegmentInfo>>;
+
+    /// Returns the prune checkpoints of all pruned segments.
+    #[method(name = "pruneCheckpoints")]
+    fn prune_checkpoints(&self) -> RpcResult<Vec<PruneCheckpointInfo>>;
+
+    /// Returns the checkpoints of all stages.
+    #[method(name = "stageCheckpoints")]
+    fn stage_checkpoints(&self) -> RpcResult<Vec<StageCheckpointInfo>>;
+}
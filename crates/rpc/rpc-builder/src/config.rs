@@ -1,16 +1,19 @@
 use std::{net::SocketAddr, path::PathBuf};
 
-use jsonrpsee::server::ServerBuilder;
+use jsonrpsee::server::{BatchRequestConfig, ServerBuilder};
 use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_path};
-use reth_rpc_eth_types::{EthConfig, EthStateCacheConfig, GasPriceOracleConfig};
+use reth_rpc_eth_types::{
+    cache::config::EthStateCacheMemoryBudget, EthConfig, EthStateCacheConfig, GasPriceOracleConfig,
+    HistoricalRpcFallback,
+};
 use reth_rpc_layer::{JwtError, JwtSecret};
 use reth_rpc_server_types::RpcModuleSelection;
 use tower::layer::util::Identity;
 use tracing::debug;
 
 use crate::{
-    auth::AuthServerConfig, error::RpcError, IpcServerBuilder, RpcModuleConfig, RpcServerConfig,
-    TransportRpcModuleConfig,
+    auth::AuthServerConfig, error::RpcError, HealthConfig, IpcServerBuilder, RpcMetricsConfig,
+    RpcModuleConfig, RpcServerConfig, TransportRpcModuleConfig,
 };
 
 /// A trait that provides a configured RPC server.
@@ -27,6 +30,9 @@ pub trait RethRpcServerConfig {
     /// The configured ethereum RPC settings.
     fn eth_config(&self) -> EthConfig;
 
+    /// Returns the archive node that requests for pruned history are forwarded to, if any.
+    fn historical_fallback(&self) -> Option<HistoricalRpcFallback>;
+
     /// Returns state cache configuration.
     fn state_cache_config(&self) -> EthStateCacheConfig;
 
@@ -36,6 +42,9 @@ pub trait RethRpcServerConfig {
     /// Returns the max response size in bytes.
     fn rpc_max_response_size_bytes(&self) -> u32;
 
+    /// Returns how batch requests are limited.
+    fn batch_request_config(&self) -> BatchRequestConfig;
+
     /// Extracts the gas price oracle config from the args.
     fn gas_price_oracle_config(&self) -> GasPriceOracleConfig;
 
@@ -72,10 +81,20 @@ pub trait RethRpcServerConfig {
     /// jwt secret in case the `auth_jwtsecret` argument is not provided.
     fn auth_jwt_secret(&self, default_jwt_path: PathBuf) -> Result<JwtSecret, JwtError>;
 
+    /// Creates the [`AuthServerConfig`] of the auth server for a backup consensus layer client, if
+    /// a backup port is configured.
+    ///
+    /// The backup auth server only listens on the configured address and uses the same jwt
+    /// secret as the primary auth server.
+    fn backup_auth_server_config(&self, jwt_secret: JwtSecret) -> Option<AuthServerConfig>;
+
     /// Returns the configured jwt secret key for the regular rpc servers, if any.
     ///
     /// Note: this is not used for the auth server (engine API).
     fn rpc_secret_key(&self) -> Option<JwtSecret>;
+
+    /// Returns the readiness criteria of the `/ready` endpoint of the http server.
+    fn health_config(&self) -> HealthConfig;
 }
 
 impl RethRpcServerConfig for RpcServerArgs {
@@ -93,11 +112,21 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_tracing_requests(self.rpc_max_tracing_requests)
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
+            .max_concurrent_getlogs_chunks(self.rpc_max_concurrent_getlogs_chunks)
             .eth_proof_window(self.rpc_eth_proof_window)
             .rpc_gas_cap(self.rpc_gas_cap)
+            .rpc_call_timeout((!self.rpc_call_timeout.is_zero()).then_some(self.rpc_call_timeout))
+            .pending_receipts(self.rpc_pending_receipts)
+            .state_snapshot_ttl(self.rpc_snapshot_ttl)
+            .max_state_snapshots(self.rpc_max_snapshots)
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
             .proof_permits(self.rpc_proof_permits)
+            .subscription_backpressure(self.rpc_subscription_backpressure)
+    }
+
+    fn historical_fallback(&self) -> Option<HistoricalRpcFallback> {
+        self.rpc_historical_fallback.clone().map(HistoricalRpcFallback::new)
     }
 
     fn state_cache_config(&self) -> EthStateCacheConfig {
@@ -105,7 +134,16 @@ impl RethRpcServerConfig for RpcServerArgs {
             max_blocks: self.rpc_state_cache.max_blocks,
             max_receipts: self.rpc_state_cache.max_receipts,
             max_envs: self.rpc_state_cache.max_envs,
+            max_traces: self.rpc_state_cache.max_traces,
             max_concurrent_db_requests: self.rpc_state_cache.max_concurrent_db_requests,
+            memory_budget: self.rpc_state_cache.adaptive.then(|| EthStateCacheMemoryBudget {
+                max_blocks_bytes: self.rpc_state_cache.max_blocks_mb * 1024 * 1024,
+                max_receipts_bytes: self.rpc_state_cache.max_receipts_mb * 1024 * 1024,
+                max_envs_bytes: self.rpc_state_cache.max_envs_mb * 1024 * 1024,
+                min_available_memory_bytes: self.rpc_state_cache.min_available_memory_mb *
+                    1024 *
+                    1024,
+            }),
         }
     }
 
@@ -117,6 +155,10 @@ impl RethRpcServerConfig for RpcServerArgs {
         self.rpc_max_response_size.get().saturating_mul(1024 * 1024)
     }
 
+    fn batch_request_config(&self) -> BatchRequestConfig {
+        self.rpc_max_batch_size.0.map_or(BatchRequestConfig::Unlimited, BatchRequestConfig::Limit)
+    }
+
     fn gas_price_oracle_config(&self) -> GasPriceOracleConfig {
         self.gas_price_oracle.gas_price_oracle_config()
     }
@@ -154,18 +196,33 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_request_body_size(self.rpc_max_request_size_bytes())
             .max_response_body_size(self.rpc_max_response_size_bytes())
             .max_subscriptions_per_connection(self.rpc_max_subscriptions_per_connection.get())
+            .set_message_buffer_capacity(self.rpc_message_buffer_capacity)
+            .set_batch_request_config(self.batch_request_config())
     }
 
     fn ipc_server_builder(&self) -> IpcServerBuilder<Identity, Identity> {
-        IpcServerBuilder::default()
+        let builder = IpcServerBuilder::default()
             .max_subscriptions_per_connection(self.rpc_max_subscriptions_per_connection.get())
             .max_request_body_size(self.rpc_max_request_size_bytes())
             .max_response_body_size(self.rpc_max_response_size_bytes())
             .max_connections(self.rpc_max_connections.get())
+            .set_message_buffer_capacity(self.rpc_message_buffer_capacity)
+            .set_batch_request_config(self.batch_request_config());
+        match self.ipc_socket_permissions {
+            Some(mode) => builder.set_socket_permissions(mode),
+            None => builder,
+        }
     }
 
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let mut config = RpcServerConfig::default()
+            .with_jwt_secret(self.rpc_secret_key())
+            .with_correlation_ids(self.rpc_correlation_ids)
+            .with_metrics_config(
+                RpcMetricsConfig::default()
+                    .with_max_methods(self.rpc_metrics_max_methods)
+                    .with_max_error_codes(self.rpc_metrics_max_error_codes),
+            );
 
         if self.http {
             let socket_address = SocketAddr::new(self.http_addr, self.http_port);
@@ -201,6 +258,12 @@ impl RethRpcServerConfig for RpcServerArgs {
         Ok(builder.build())
     }
 
+    fn backup_auth_server_config(&self, jwt_secret: JwtSecret) -> Option<AuthServerConfig> {
+        let port = self.auth_backup_port?;
+        let address = SocketAddr::new(self.auth_addr, port);
+        Some(AuthServerConfig::builder(jwt_secret).socket_addr(address).build())
+    }
+
     fn auth_jwt_secret(&self, default_jwt_path: PathBuf) -> Result<JwtSecret, JwtError> {
         match self.auth_jwtsecret.as_ref() {
             Some(fpath) => {
@@ -214,6 +277,13 @@ impl RethRpcServerConfig for RpcServerArgs {
     fn rpc_secret_key(&self) -> Option<JwtSecret> {
         self.rpc_jwtsecret
     }
+
+    fn health_config(&self) -> HealthConfig {
+        HealthConfig::default()
+            .with_max_blocks_behind(self.http_ready_max_blocks_behind)
+            .with_min_peers(self.http_ready_min_peers)
+            .with_max_engine_idle(self.http_ready_max_engine_idle)
+    }
 }
 
 #[cfg(test)]
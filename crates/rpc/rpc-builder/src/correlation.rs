@@ -0,0 +1,62 @@
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+use tower::Layer;
+use tracing::{instrument::Instrumented, Instrument};
+
+/// Layer that attaches a unique correlation id to all log events emitted while a request is
+/// handled.
+///
+/// Every request is handled in a `request` span with the `correlation_id` and the `method` of the
+/// request. If enabled, the span is created at `INFO` level and included in every log event of the
+/// request, e.g. in the `span` field of the JSON log format. Otherwise it's created at `DEBUG`
+/// level, so it's only recorded if enabled by a filter, e.g. for span export.
+#[derive(Debug, Clone, Default)]
+pub struct RpcCorrelationLayer {
+    enabled: bool,
+    next_id: Arc<AtomicU64>,
+}
+
+impl RpcCorrelationLayer {
+    /// Creates a new instance of `RpcCorrelationLayer`.
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled, next_id: Default::default() }
+    }
+}
+
+impl<S> Layer<S> for RpcCorrelationLayer {
+    type Service = RpcCorrelationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcCorrelationService { layer: self.clone(), inner }
+    }
+}
+
+/// Service created by [`RpcCorrelationLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcCorrelationService<S> {
+    layer: RpcCorrelationLayer,
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcCorrelationService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = Instrumented<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let correlation_id = self.layer.next_id.fetch_add(1, Ordering::Relaxed);
+        let method = req.method_name();
+        let span = if self.layer.enabled {
+            tracing::info_span!(target: "rpc", "request", correlation_id, method)
+        } else {
+            tracing::debug_span!(target: "rpc", "request", correlation_id, method)
+        };
+        // synchronous methods are executed by `call` directly
+        let fut = span.in_scope(|| self.inner.call(req));
+        fut.instrument(span)
+    }
+}
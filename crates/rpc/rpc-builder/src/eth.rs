@@ -1,15 +1,51 @@
+use std::{fmt, sync::Arc};
+
+use jsonrpsee::{
+    core::server::{Extensions, MethodCallback, MethodResponse, RpcModule},
+    types::{ErrorObjectOwned, Params},
+};
 use reth_evm::ConfigureEvm;
 use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider, StateProviderFactory};
 use reth_rpc::{EthFilter, EthPubSub};
 use reth_rpc_eth_types::{
     cache::cache_new_blocks_task, EthApiBuilderCtx, EthConfig, EthStateCache,
+    HistoricalRpcFallback, RpcLimits,
 };
+use reth_stages_types::SyncProgress;
 use reth_tasks::TaskSpawner;
 
 /// Alias for `eth` namespace API builder.
 pub type DynEthApiBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events, EthApi> =
     Box<dyn Fn(&EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events>) -> EthApi>;
 
+/// Middleware that intercepts every `eth_` namespace method call, for example for auditing,
+/// rewriting requests or enforcing quotas without reimplementing the `eth` API.
+///
+/// The middleware is installed with [`EthHandlersBuilder::with_middleware`] and applies to the
+/// core, filter and pubsub `eth` handlers.
+pub trait EthMiddleware: fmt::Debug + Send + Sync + 'static {
+    /// Invoked before the method is called.
+    ///
+    /// Returns the params the method is called with, or an error that is returned to the caller
+    /// without calling the method. The request extensions, e.g. the HTTP headers, can be used to
+    /// identify the caller.
+    fn on_request<'a>(
+        &self,
+        method: &'static str,
+        params: Params<'a>,
+        extensions: &Extensions,
+    ) -> Result<Params<'a>, ErrorObjectOwned> {
+        let _ = (method, extensions);
+        Ok(params)
+    }
+
+    /// Invoked with the response of the method, returns the response that is sent to the caller.
+    fn on_response(&self, method: &'static str, response: MethodResponse) -> MethodResponse {
+        let _ = method;
+        response
+    }
+}
+
 /// Handlers for core, filter and pubsub `eth` namespace APIs.
 #[derive(Debug, Clone)]
 pub struct EthHandlers<Provider, Pool, Network, Events, EthApi> {
@@ -21,6 +57,8 @@ pub struct EthHandlers<Provider, Pool, Network, Events, EthApi> {
     pub filter: EthFilter<Provider, Pool>,
     /// Handler for subscriptions only available for transports that support it (ws, ipc)
     pub pubsub: EthPubSub<Provider, Pool, Events, Network>,
+    /// Middleware applied to all `eth_` methods
+    pub middleware: Option<Arc<dyn EthMiddleware>>,
 }
 
 impl<Provider, Pool, Network, Events, EthApi> EthHandlers<Provider, Pool, Network, Events, EthApi> {
@@ -53,8 +91,84 @@ impl<Provider, Pool, Network, Events, EthApi> EthHandlers<Provider, Pool, Networ
             executor,
             events,
             eth_api_builder,
+            middleware: None,
+            limits: None,
+            sync_progress: Default::default(),
+            historical_fallback: None,
         }
     }
+
+    /// Applies the configured [`EthMiddleware`], if any, to all methods of the given module.
+    pub fn apply_middleware(&self, module: RpcModule<()>) -> RpcModule<()> {
+        match &self.middleware {
+            Some(middleware) => with_eth_middleware(module, middleware.clone()),
+            None => module,
+        }
+    }
+}
+
+/// Wraps all methods of the module with the [`EthMiddleware`].
+pub fn with_eth_middleware(
+    mut module: RpcModule<()>,
+    middleware: Arc<dyn EthMiddleware>,
+) -> RpcModule<()> {
+    let methods = module.method_names().collect::<Vec<_>>();
+    for name in methods {
+        let Some(callback) = module.remove_method(name) else { continue };
+        let mw = middleware.clone();
+        let callback = match callback {
+            MethodCallback::Sync(f) => {
+                MethodCallback::Sync(Arc::new(move |id, params, max_response_size, ext| {
+                    match mw.on_request(name, params, &ext) {
+                        Ok(params) => mw.on_response(name, f(id, params, max_response_size, ext)),
+                        Err(err) => MethodResponse::error(id, err),
+                    }
+                }))
+            }
+            MethodCallback::Async(f) => MethodCallback::Async(Arc::new(
+                move |id, params, conn_id, max_response_size, ext| match mw
+                    .on_request(name, params, &ext)
+                {
+                    Ok(params) => {
+                        let fut = f(id, params, conn_id, max_response_size, ext);
+                        let mw = mw.clone();
+                        Box::pin(async move { mw.on_response(name, fut.await) })
+                    }
+                    Err(err) => {
+                        let response = MethodResponse::error(id, err);
+                        Box::pin(async move { response })
+                    }
+                },
+            )),
+            MethodCallback::Subscription(f) => {
+                MethodCallback::Subscription(Arc::new(move |id, params, sink, state, ext| match mw
+                    .on_request(name, params, &ext)
+                {
+                    Ok(params) => {
+                        let fut = f(id, params, sink, state, ext);
+                        let mw = mw.clone();
+                        Box::pin(async move { mw.on_response(name, fut.await) })
+                    }
+                    Err(err) => {
+                        let response = MethodResponse::error(id, err);
+                        Box::pin(async move { response })
+                    }
+                }))
+            }
+            MethodCallback::Unsubscription(f) => MethodCallback::Unsubscription(Arc::new(
+                move |id, params, conn_id, max_response_size, ext| match mw
+                    .on_request(name, params, &ext)
+                {
+                    Ok(params) => {
+                        mw.on_response(name, f(id, params, conn_id, max_response_size, ext))
+                    }
+                    Err(err) => MethodResponse::error(id, err),
+                },
+            )),
+        };
+        module.verify_and_insert(name, callback).expect("method was removed");
+    }
+    module
 }
 
 /// Builds [`EthHandlers`] for core, filter, and pubsub `eth_` apis.
@@ -68,6 +182,44 @@ pub struct EthHandlersBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig,
     executor: Tasks,
     events: Events,
     eth_api_builder: DynEthApiBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events, EthApi>,
+    middleware: Option<Arc<dyn EthMiddleware>>,
+    limits: Option<RpcLimits>,
+    sync_progress: SyncProgress,
+    historical_fallback: Option<HistoricalRpcFallback>,
+}
+
+impl<Provider, Pool, Network, Tasks, Events, EvmConfig, EthApi>
+    EthHandlersBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, EthApi>
+{
+    /// Configures the [`EthMiddleware`] that intercepts all `eth_` methods.
+    pub fn with_middleware(mut self, middleware: Option<Arc<dyn EthMiddleware>>) -> Self {
+        self.middleware = middleware;
+        self
+    }
+
+    /// Configures the [`RpcLimits`] shared with other handlers.
+    ///
+    /// If not set, new limits are created from the [`EthConfig`].
+    pub fn with_limits(mut self, limits: RpcLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Configures the [`SyncProgress`] of the sync stages reported by the `eth` handlers.
+    pub fn with_sync_progress(mut self, sync_progress: SyncProgress) -> Self {
+        self.sync_progress = sync_progress;
+        self
+    }
+
+    /// Configures the archive node that `eth_getLogs` requests for pruned receipts are forwarded
+    /// to.
+    pub fn with_historical_fallback(
+        mut self,
+        historical_fallback: Option<HistoricalRpcFallback>,
+    ) -> Self {
+        self.historical_fallback = historical_fallback;
+        self
+    }
 }
 
 impl<Provider, Pool, Network, Tasks, Events, EvmConfig, EthApi>
@@ -83,8 +235,21 @@ where
 {
     /// Returns a new instance with handlers for `eth` namespace.
     pub fn build(self) -> EthHandlers<Provider, Pool, Network, Events, EthApi> {
-        let Self { provider, pool, network, evm_config, config, executor, events, eth_api_builder } =
-            self;
+        let Self {
+            provider,
+            pool,
+            network,
+            evm_config,
+            config,
+            executor,
+            events,
+            eth_api_builder,
+            middleware,
+            limits,
+            sync_progress,
+            historical_fallback,
+        } = self;
+        let limits = limits.unwrap_or_else(|| config.limits());
 
         let cache = EthStateCache::spawn_with(
             provider.clone(),
@@ -111,6 +276,9 @@ where
             executor,
             events,
             cache,
+            limits,
+            sync_progress,
+            historical_fallback,
         };
 
         let api = eth_api_builder(&ctx);
@@ -119,7 +287,7 @@ where
 
         let pubsub = EthPubSubApiBuilder::build(&ctx);
 
-        EthHandlers { api, cache: ctx.cache, filter, pubsub }
+        EthHandlers { api, cache: ctx.cache, filter, pubsub, middleware }
     }
 }
 
@@ -141,7 +309,10 @@ impl EthFilterApiBuilder {
             ctx.provider.clone(),
             ctx.pool.clone(),
             ctx.cache.clone(),
-            ctx.config.filter_config(),
+            ctx.config
+                .filter_config()
+                .limits(ctx.limits.clone())
+                .historical_fallback(ctx.historical_fallback.clone()),
             Box::new(ctx.executor.clone()),
         )
     }
@@ -170,5 +341,7 @@ impl EthPubSubApiBuilder {
             ctx.network.clone(),
             Box::new(ctx.executor.clone()),
         )
+        .with_backpressure(ctx.config.subscription_backpressure)
+        .with_sync_progress(ctx.sync_progress.clone())
     }
 }
@@ -0,0 +1,392 @@
+//! `/health` is a liveness probe: it responds with `200 OK` as long as the node can read its
+//! sync state. `/ready` is a readiness probe: it responds with `200 OK` only if the node meets all
+//! criteria of the [`HealthConfig`], and with `503 Service Unavailable` otherwise. Both respond
+//! with a JSON encoded [`HealthReport`].
+
+use http::{header, Method, StatusCode};
+use jsonrpsee::server::{HttpBody, HttpRequest, HttpResponse};
+use pin_project::pin_project;
+use reth_network_api::{NetworkInfo, PeersInfo};
+use reth_provider::{BlockNumReader, CanonChainTracker, ProviderResult, StageCheckpointReader};
+use reth_rpc_server_types::constants::DEFAULT_READY_MAX_BLOCKS_BEHIND;
+use reth_stages_types::StageId;
+use serde::Serialize;
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::{Layer, Service};
+
+/// Path of the liveness endpoint.
+pub const HEALTH_PATH: &str = "/health";
+
+/// Path of the readiness endpoint.
+pub const READY_PATH: &str = "/ready";
+
+/// Criteria the node must meet to be reported as ready on [`READY_PATH`].
+///
+/// A node that is still performing its initial sync is never ready.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HealthConfig {
+    /// Maximum number of blocks the node may be behind the highest known block.
+    pub max_blocks_behind: u64,
+    /// Minimum number of connected peers.
+    pub min_peers: usize,
+    /// Maximum time since the last forkchoice update of the consensus layer, if the engine API
+    /// must be active.
+    pub max_engine_idle: Option<Duration>,
+}
+
+impl HealthConfig {
+    /// Sets the maximum number of blocks the node may be behind the highest known block.
+    pub const fn with_max_blocks_behind(mut self, max_blocks_behind: u64) -> Self {
+        self.max_blocks_behind = max_blocks_behind;
+        self
+    }
+
+    /// Sets the minimum number of connected peers.
+    pub const fn with_min_peers(mut self, min_peers: usize) -> Self {
+        self.min_peers = min_peers;
+        self
+    }
+
+    /// Sets the maximum time since the last forkchoice update of the consensus layer.
+    pub const fn with_max_engine_idle(mut self, max_engine_idle: Option<Duration>) -> Self {
+        self.max_engine_idle = max_engine_idle;
+        self
+    }
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            max_blocks_behind: DEFAULT_READY_MAX_BLOCKS_BEHIND,
+            min_peers: 0,
+            max_engine_idle: None,
+        }
+    }
+}
+
+/// A snapshot of the node's sync state the [`HealthConfig`] criteria are evaluated against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NodeSyncStatus {
+    /// Whether the node is performing its initial sync.
+    pub initially_syncing: bool,
+    /// The best block of the node.
+    pub best_block: u64,
+    /// The highest block known to the node.
+    pub highest_block: u64,
+    /// The number of connected peers.
+    pub peers: usize,
+    /// The time since the last forkchoice update of the consensus layer, if any was received.
+    pub engine_idle: Option<Duration>,
+}
+
+/// Provides the current [`NodeSyncStatus`].
+pub trait SyncStatusProvider: Send + Sync + 'static {
+    /// Returns the current sync status of the node.
+    fn sync_status(&self) -> ProviderResult<NodeSyncStatus>;
+}
+
+/// [`SyncStatusProvider`] backed by the node's provider and network.
+///
+/// The highest known block is the target of the `Headers` stage, which is ahead of the best
+/// block while the pipeline is syncing.
+#[derive(Debug, Clone)]
+pub struct NodeSyncStatusProvider<Provider, Network> {
+    provider: Provider,
+    network: Network,
+}
+
+impl<Provider, Network> NodeSyncStatusProvider<Provider, Network> {
+    /// Creates a new instance of `NodeSyncStatusProvider`.
+    pub const fn new(provider: Provider, network: Network) -> Self {
+        Self { provider, network }
+    }
+}
+
+impl<Provider, Network> SyncStatusProvider for NodeSyncStatusProvider<Provider, Network>
+where
+    Provider: BlockNumReader + StageCheckpointReader + CanonChainTracker + 'static,
+    Network: NetworkInfo + PeersInfo + 'static,
+{
+    fn sync_status(&self) -> ProviderResult<NodeSyncStatus> {
+        let best_block = self.provider.best_block_number()?;
+        let headers_target = self
+            .provider
+            .get_stage_checkpoint(StageId::Headers)?
+            .map(|checkpoint| checkpoint.block_number)
+            .unwrap_or_default();
+
+        Ok(NodeSyncStatus {
+            initially_syncing: self.network.is_initially_syncing(),
+            best_block,
+            highest_block: headers_target.max(best_block),
+            peers: self.network.num_connected_peers(),
+            engine_idle: self
+                .provider
+                .last_received_update_timestamp()
+                .map(|timestamp| timestamp.elapsed()),
+        })
+    }
+}
+
+/// The response body of the health endpoints.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthReport {
+    /// Whether the node meets all readiness criteria.
+    pub ready: bool,
+    /// Whether the node is performing its initial sync.
+    pub syncing: bool,
+    /// The best block of the node.
+    pub best_block: u64,
+    /// The highest block known to the node.
+    pub highest_block: u64,
+    /// The number of blocks the node is behind the highest known block.
+    pub blocks_behind: u64,
+    /// The number of connected peers.
+    pub peers: usize,
+    /// Seconds since the last forkchoice update of the consensus layer, if any was received.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub engine_idle_secs: Option<u64>,
+    /// The readiness criteria the node does not meet.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<String>,
+}
+
+impl HealthReport {
+    /// Evaluates the [`HealthConfig`] criteria against the given status.
+    pub fn new(config: &HealthConfig, status: NodeSyncStatus) -> Self {
+        let blocks_behind = status.highest_block.saturating_sub(status.best_block);
+
+        let mut failures = Vec::new();
+        if status.initially_syncing {
+            failures.push("initial sync in progress".to_string());
+        }
+        if blocks_behind > config.max_blocks_behind {
+            failures.push(format!(
+                "{blocks_behind} blocks behind, at most {} allowed",
+                config.max_blocks_behind
+            ));
+        }
+        if status.peers < config.min_peers {
+            failures.push(format!(
+                "{} peers connected, at least {} required",
+                status.peers, config.min_peers
+            ));
+        }
+        if let Some(max_engine_idle) = config.max_engine_idle {
+            match status.engine_idle {
+                None => failures.push("no forkchoice update received".to_string()),
+                Some(idle) if idle > max_engine_idle => failures.push(format!(
+                    "last forkchoice update {}s ago, at most {}s allowed",
+                    idle.as_secs(),
+                    max_engine_idle.as_secs()
+                )),
+                Some(_) => {}
+            }
+        }
+
+        Self {
+            ready: failures.is_empty(),
+            syncing: status.initially_syncing,
+            best_block: status.best_block,
+            highest_block: status.highest_block,
+            blocks_behind,
+            peers: status.peers,
+            engine_idle_secs: status.engine_idle.map(|idle| idle.as_secs()),
+            failures,
+        }
+    }
+}
+
+/// Evaluates the [`HealthConfig`] criteria against the node's current sync status.
+#[derive(Clone)]
+pub struct HealthCheck {
+    config: HealthConfig,
+    status: Arc<dyn SyncStatusProvider>,
+}
+
+impl HealthCheck {
+    /// Creates a new instance of `HealthCheck`.
+    pub fn new(config: HealthConfig, status: impl SyncStatusProvider) -> Self {
+        Self { config, status: Arc::new(status) }
+    }
+
+    /// Returns the configured criteria.
+    pub const fn config(&self) -> &HealthConfig {
+        &self.config
+    }
+
+    /// Returns the [`HealthReport`] for the node's current sync status.
+    pub fn report(&self) -> ProviderResult<HealthReport> {
+        Ok(HealthReport::new(&self.config, self.status.sync_status()?))
+    }
+
+    /// Returns the response to a request on the given health endpoint.
+    fn response(&self, path: &str) -> HttpResponse {
+        match self.report() {
+            Ok(report) => {
+                let status = if path == READY_PATH && !report.ready {
+                    StatusCode::SERVICE_UNAVAILABLE
+                } else {
+                    StatusCode::OK
+                };
+                json_response(status, serde_json::to_string(&report).unwrap_or_default())
+            }
+            Err(err) => json_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                serde_json::json!({ "error": err.to_string() }).to_string(),
+            ),
+        }
+    }
+}
+
+impl fmt::Debug for HealthCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HealthCheck").field("config", &self.config).finish_non_exhaustive()
+    }
+}
+
+fn json_response(status: StatusCode, body: String) -> HttpResponse {
+    HttpResponse::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(HttpBody::from(body))
+        .expect("This should never happen")
+}
+
+/// A layer that answers `GET` requests on [`HEALTH_PATH`] and [`READY_PATH`] and passes all
+/// other requests to the inner service.
+#[derive(Clone, Debug)]
+pub struct HealthLayer {
+    check: HealthCheck,
+}
+
+impl HealthLayer {
+    /// Creates a new instance of `HealthLayer`.
+    pub const fn new(check: HealthCheck) -> Self {
+        Self { check }
+    }
+}
+
+impl<S> Layer<S> for HealthLayer {
+    type Service = HealthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HealthService { check: self.check.clone(), inner }
+    }
+}
+
+/// Service created by [`HealthLayer`].
+#[derive(Clone, Debug)]
+pub struct HealthService<S> {
+    check: HealthCheck,
+    inner: S,
+}
+
+impl<S> Service<HttpRequest> for HealthService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse>,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = HealthResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: HttpRequest) -> Self::Future {
+        let path = req.uri().path();
+        if req.method() == Method::GET && (path == HEALTH_PATH || path == READY_PATH) {
+            return HealthResponseFuture::Ready { response: Some(self.check.response(path)) }
+        }
+        HealthResponseFuture::Inner { future: self.inner.call(req) }
+    }
+}
+
+/// Response future of [`HealthService`].
+#[pin_project(project = HealthResponseFutureProj)]
+#[allow(missing_debug_implementations)]
+pub enum HealthResponseFuture<F> {
+    /// The response of the inner service.
+    Inner {
+        /// The future of the inner service.
+        #[pin]
+        future: F,
+    },
+    /// The response of a health endpoint.
+    Ready {
+        /// The response, taken on completion.
+        response: Option<HttpResponse>,
+    },
+}
+
+impl<F, E> Future for HealthResponseFuture<F>
+where
+    F: Future<Output = Result<HttpResponse, E>>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            HealthResponseFutureProj::Inner { future } => future.poll(cx),
+            HealthResponseFutureProj::Ready { response } => {
+                Poll::Ready(Ok(response.take().expect("polled after completion")))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn synced() -> NodeSyncStatus {
+        NodeSyncStatus {
+            initially_syncing: false,
+            best_block: 100,
+            highest_block: 100,
+            peers: 5,
+            engine_idle: Some(Duration::from_secs(2)),
+        }
+    }
+
+    #[test]
+    fn ready_when_synced() {
+        let report = HealthReport::new(&HealthConfig::default(), synced());
+        assert!(report.ready);
+        assert!(report.failures.is_empty());
+    }
+
+    #[test]
+    fn not_ready_while_initially_syncing() {
+        let status = NodeSyncStatus { initially_syncing: true, ..synced() };
+        let report = HealthReport::new(&HealthConfig::default(), status);
+        assert!(!report.ready);
+        assert_eq!(report.failures, vec!["initial sync in progress".to_string()]);
+    }
+
+    #[test]
+    fn not_ready_when_criteria_fail() {
+        let config = HealthConfig::default()
+            .with_max_blocks_behind(10)
+            .with_min_peers(8)
+            .with_max_engine_idle(Some(Duration::from_secs(1)));
+        let status = NodeSyncStatus { highest_block: 111, ..synced() };
+
+        let report = HealthReport::new(&config, status);
+        assert!(!report.ready);
+        assert_eq!(report.blocks_behind, 11);
+        assert_eq!(report.failures.len(), 3);
+
+        let report = HealthReport::new(&config, NodeSyncStatus { engine_idle: None, ..status });
+        assert!(report.failures.contains(&"no forkchoice update received".to_string()));
+    }
+}
@@ -152,6 +152,7 @@ use jsonrpsee::{
     Methods, RpcModule,
 };
 use reth_chainspec::ChainSpec;
+use reth_db_common::integrity::IntegrityCheckHandle;
 use reth_engine_primitives::EngineTypes;
 use reth_evm::ConfigureEvm;
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
@@ -159,20 +160,27 @@ use reth_provider::{
     AccountReader, BlockReader, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
     EvmEnvProvider, FullRpcProvider, StateProviderFactory,
 };
+use reth_prune_types::PruneModes;
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi,
-    TxPoolApi, Web3Api,
+    AdminApi, DebugApi, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi, RethApi,
+    RethApiConfig, RethDbApi, RpcConfigHandle, TraceApi, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
     helpers::{
-        Call, EthApiSpec, EthTransactions, LoadPendingBlock, TraceExt, UpdateRawTxForwarder,
+        Call, EthApiSpec, EthState, EthTransactions, LoadPendingBlock, TraceExt,
+        UpdateRawTxForwarder,
     },
     EthApiServer, FullEthApiServer, RawTransactionForwarder,
 };
-use reth_rpc_eth_types::{EthConfig, EthStateCache, EthSubscriptionIdProvider};
+use reth_rpc_eth_types::{
+    EthConfig, EthStateCache, EthSubscriptionIdProvider, HistoricalRpcClient,
+    HistoricalRpcFallback, RpcLimits,
+};
 use reth_rpc_layer::{AuthLayer, Claims, JwtAuthValidator, JwtSecret};
+use reth_stages_types::SyncProgress;
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
+use reth_tracing::LogFilterHandle;
 use reth_transaction_pool::{noop::NoopTransactionPool, TransactionPool};
 use serde::{Deserialize, Serialize};
 use tower::Layer;
@@ -187,7 +195,9 @@ pub use jsonrpsee::server::ServerBuilder;
 pub use reth_ipc::server::{
     Builder as IpcServerBuilder, RpcServiceBuilder as IpcRpcServiceBuilder,
 };
-pub use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+pub use reth_rpc_server_types::{
+    constants, runtime::RpcModuleToggles, RethRpcModule, RpcModuleSelection,
+};
 pub use tower::layer::util::{Identity, Stack};
 
 /// Auth server utilities.
@@ -199,16 +209,26 @@ pub mod config;
 /// Cors utilities.
 mod cors;
 
+/// Correlation ids of requests in log events.
+mod correlation;
+pub use correlation::{RpcCorrelationLayer, RpcCorrelationService};
+
 /// Rpc error utilities.
 pub mod error;
 
+/// `/health` and `/ready` endpoints of the http server.
+pub mod health;
+pub use health::{HealthCheck, HealthConfig};
+
 /// Eth utils
 pub mod eth;
-pub use eth::EthHandlers;
+pub use eth::{EthHandlers, EthMiddleware};
 
 // Rpc server metrics
 mod metrics;
-pub use metrics::{MeteredRequestFuture, RpcRequestMetricsService};
+
+mod runtime;
+pub use metrics::{MeteredRequestFuture, RpcMetricsConfig, RpcRequestMetricsService};
 
 /// Convenience function for starting a server in one step.
 #[allow(clippy::too_many_arguments)]
@@ -259,6 +279,12 @@ pub struct RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig> {
     events: Events,
     /// Defines how the EVM should be configured before execution.
     evm_config: EvmConfig,
+    /// Middleware that intercepts all `eth_` methods.
+    eth_middleware: Option<Arc<dyn EthMiddleware>>,
+    /// The prune modes of the node, reported by `reth_config`.
+    prune_modes: Option<PruneModes>,
+    /// Archive node that requests for pruned history are forwarded to.
+    historical_fallback: Option<HistoricalRpcFallback>,
 }
 
 // === impl RpcBuilder ===
@@ -275,7 +301,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
         events: Events,
         evm_config: EvmConfig,
     ) -> Self {
-        Self { provider, pool, network, executor, events, evm_config }
+        Self {
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            eth_middleware: None,
+            prune_modes: None,
+            historical_fallback: None,
+        }
     }
 
     /// Configure the provider instance.
@@ -286,8 +322,28 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     where
         P: BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     {
-        let Self { pool, network, executor, events, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self {
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+            ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+        }
     }
 
     /// Configure the transaction pool instance.
@@ -298,8 +354,28 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     where
         P: TransactionPool + 'static,
     {
-        let Self { provider, network, executor, events, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self {
+            provider,
+            network,
+            executor,
+            events,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+            ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+        }
     }
 
     /// Configure a [`NoopTransactionPool`] instance.
@@ -310,7 +386,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     pub fn with_noop_pool(
         self,
     ) -> RpcModuleBuilder<Provider, NoopTransactionPool, Network, Tasks, Events, EvmConfig> {
-        let Self { provider, executor, events, network, evm_config, .. } = self;
+        let Self {
+            provider,
+            executor,
+            events,
+            network,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             executor,
@@ -318,6 +404,9 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
             network,
             evm_config,
             pool: NoopTransactionPool::default(),
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
         }
     }
 
@@ -329,8 +418,28 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     where
         N: NetworkInfo + Peers + 'static,
     {
-        let Self { provider, pool, executor, events, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self {
+            provider,
+            pool,
+            executor,
+            events,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+            ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+        }
     }
 
     /// Configure a [`NoopNetwork`] instance.
@@ -341,7 +450,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     pub fn with_noop_network(
         self,
     ) -> RpcModuleBuilder<Provider, Pool, NoopNetwork, Tasks, Events, EvmConfig> {
-        let Self { provider, pool, executor, events, evm_config, .. } = self;
+        let Self {
+            provider,
+            pool,
+            executor,
+            events,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             pool,
@@ -349,6 +468,9 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
             events,
             network: NoopNetwork::default(),
             evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
         }
     }
 
@@ -360,8 +482,28 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     where
         T: TaskSpawner + 'static,
     {
-        let Self { pool, network, provider, events, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self {
+            pool,
+            network,
+            provider,
+            events,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+            ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+        }
     }
 
     /// Configure [`TokioTaskExecutor`] as the task executor to use for additional tasks.
@@ -371,7 +513,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     pub fn with_tokio_executor(
         self,
     ) -> RpcModuleBuilder<Provider, Pool, Network, TokioTaskExecutor, Events, EvmConfig> {
-        let Self { pool, network, provider, events, evm_config, .. } = self;
+        let Self {
+            pool,
+            network,
+            provider,
+            events,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -379,6 +531,9 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
             events,
             executor: TokioTaskExecutor::default(),
             evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
         }
     }
 
@@ -390,8 +545,28 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     where
         E: CanonStateSubscriptions + 'static,
     {
-        let Self { provider, pool, executor, network, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self {
+            provider,
+            pool,
+            executor,
+            network,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+            ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+        }
     }
 
     /// Configure the evm configuration type
@@ -402,8 +577,51 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     where
         E: ConfigureEvm + 'static,
     {
-        let Self { provider, pool, executor, network, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self {
+            provider,
+            pool,
+            executor,
+            network,
+            events,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+            ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+        }
+    }
+
+    /// Configures the [`EthMiddleware`] that intercepts all `eth_` methods.
+    pub fn with_eth_middleware(mut self, eth_middleware: Option<Arc<dyn EthMiddleware>>) -> Self {
+        self.eth_middleware = eth_middleware;
+        self
+    }
+
+    /// Configures the prune modes of the node that are reported by `reth_config`.
+    pub fn with_prune_modes(mut self, prune_modes: PruneModes) -> Self {
+        self.prune_modes = Some(prune_modes);
+        self
+    }
+
+    /// Configures the archive node that requests for pruned history are forwarded to.
+    ///
+    /// If `None`, such requests fail with a pruned history error.
+    pub fn with_historical_fallback(
+        mut self,
+        historical_fallback: Option<HistoricalRpcFallback>,
+    ) -> Self {
+        self.historical_fallback = historical_fallback;
+        self
     }
 }
 
@@ -439,13 +657,34 @@ where
         EngineApi: EngineApiServer<EngineT>,
         EthApi: FullEthApiServer,
     {
-        let Self { provider, pool, network, executor, events, evm_config } = self;
+        let Self {
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+        } = self;
 
         let config = module_config.config.clone().unwrap_or_default();
 
         let mut registry = RpcRegistryInner::new(
-            provider, pool, network, executor, events, config, evm_config, eth,
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            config,
+            historical_fallback,
+            evm_config,
+            eth,
         );
+        registry
+            .set_eth_middleware(eth_middleware)
+            .set_prune_modes(prune_modes.unwrap_or_default());
 
         let modules = registry.create_transport_rpc_modules(module_config);
 
@@ -491,8 +730,32 @@ where
     where
         EthApi: 'static,
     {
-        let Self { provider, pool, network, executor, events, evm_config } = self;
-        RpcRegistryInner::new(provider, pool, network, executor, events, config, evm_config, eth)
+        let Self {
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+        } = self;
+        let mut registry = RpcRegistryInner::new(
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            config,
+            historical_fallback,
+            evm_config,
+            eth,
+        );
+        registry
+            .set_eth_middleware(eth_middleware)
+            .set_prune_modes(prune_modes.unwrap_or_default());
+        registry
     }
 
     /// Configures all [`RpcModule`]s specific to the given [`TransportRpcModuleConfig`] which can
@@ -507,7 +770,17 @@ where
     {
         let mut modules = TransportRpcModules::default();
 
-        let Self { provider, pool, network, executor, events, evm_config } = self;
+        let Self {
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            eth_middleware,
+            prune_modes,
+            historical_fallback,
+        } = self;
 
         if !module_config.is_empty() {
             let TransportRpcModuleConfig { http, ws, ipc, config } = module_config.clone();
@@ -519,9 +792,13 @@ where
                 executor,
                 events,
                 config.unwrap_or_default(),
+                historical_fallback,
                 evm_config,
                 eth,
             );
+            registry
+                .set_eth_middleware(eth_middleware)
+                .set_prune_modes(prune_modes.unwrap_or_default());
 
             modules.config = module_config;
             modules.http = registry.maybe_module(http.as_ref());
@@ -617,8 +894,22 @@ pub struct RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi> {
     events: Events,
     /// Holds a all `eth_` namespace handlers
     eth: EthHandlers<Provider, Pool, Network, Events, EthApi>,
+    /// The `eth` namespace configuration the handlers were built with
+    eth_config: EthConfig,
+    /// The prune modes of the node
+    prune_modes: PruneModes,
+    /// Archive node that requests for pruned history are forwarded to
+    historical_fallback: Option<HistoricalRpcClient>,
     /// to put trace calls behind semaphore
     blocking_pool_guard: BlockingTaskGuard,
+    /// Request limits that can be changed at runtime
+    limits: RpcLimits,
+    /// Progress of the sync stages reported by the `eth` handlers
+    sync_progress: SyncProgress,
+    /// State of the background integrity check of the database reported by the `admin` handlers
+    integrity_check: IntegrityCheckHandle,
+    /// Tracks which of the served namespaces are enabled
+    module_toggles: RpcModuleToggles,
     /// Contains the [Methods] of a module
     modules: HashMap<RethRpcModule, Methods>,
 }
@@ -644,6 +935,7 @@ where
         executor: Tasks,
         events: Events,
         config: RpcModuleConfig,
+        historical_fallback: Option<HistoricalRpcFallback>,
         evm_config: EvmConfig,
         eth_api_builder: DynEthApiBuilder<
             Provider,
@@ -658,7 +950,10 @@ where
     where
         EvmConfig: ConfigureEvm,
     {
-        let blocking_pool_guard = BlockingTaskGuard::new(config.eth.max_tracing_requests);
+        let limits = config.eth.limits();
+        let blocking_pool_guard = limits.tracing_guard().clone();
+        let eth_config = config.eth.clone();
+        let sync_progress = SyncProgress::default();
 
         let eth = EthHandlers::builder(
             provider.clone(),
@@ -670,6 +965,9 @@ where
             events.clone(),
             eth_api_builder,
         )
+        .with_limits(limits.clone())
+        .with_sync_progress(sync_progress.clone())
+        .with_historical_fallback(historical_fallback.clone())
         .build();
 
         Self {
@@ -677,9 +975,16 @@ where
             pool,
             network,
             eth,
+            eth_config,
+            prune_modes: PruneModes::none(),
+            historical_fallback: historical_fallback.and_then(|fallback| fallback.client()),
             executor,
             modules: Default::default(),
             blocking_pool_guard,
+            limits,
+            sync_progress,
+            integrity_check: Default::default(),
+            module_toggles: Default::default(),
             events,
         }
     }
@@ -693,11 +998,51 @@ impl<Provider, Pool, Network, Tasks, Events, EthApi>
         &self.eth.api
     }
 
+    /// Returns the configuration reported by `reth_config` for the given namespaces.
+    fn reth_api_config(&self, namespaces: &[RethRpcModule]) -> RethApiConfig {
+        RethApiConfig {
+            // ordered like the variants, so the reported namespaces don't depend on the order
+            // they were installed in
+            namespaces: RethRpcModule::all_variants()
+                .iter()
+                .copied()
+                .filter(|namespace| namespaces.contains(namespace))
+                .collect(),
+            eth: self.eth_config.clone(),
+            limits: self.limits.clone(),
+            modules: self.module_toggles.clone(),
+            prune_modes: self.prune_modes.clone(),
+        }
+    }
+
     /// Returns a reference to the installed [`EthHandlers`].
     pub const fn eth_handlers(&self) -> &EthHandlers<Provider, Pool, Network, Events, EthApi> {
         &self.eth
     }
 
+    /// Configures the [`EthMiddleware`] that intercepts all `eth_` methods.
+    ///
+    /// This only applies to `eth` modules that are registered afterwards.
+    pub fn set_eth_middleware(&mut self, middleware: Option<Arc<dyn EthMiddleware>>) -> &mut Self {
+        self.eth.middleware = middleware;
+        self
+    }
+
+    /// Configures the prune modes of the node that are reported by `reth_config`.
+    ///
+    /// This only applies to `reth` modules that are registered afterwards.
+    pub fn set_prune_modes(&mut self, prune_modes: PruneModes) -> &mut Self {
+        self.prune_modes = prune_modes;
+        self
+    }
+
+    /// Returns a handle to change the RPC configuration while the node is running.
+    ///
+    /// Namespaces can only be toggled once they are served, see [`Self::reth_methods`].
+    pub fn rpc_config_handle(&self) -> RpcConfigHandle {
+        RpcConfigHandle::new(self.module_toggles.clone(), self.limits.clone())
+    }
+
     /// Returns the [`EthStateCache`] frontend
     ///
     /// This will spawn exactly one [`EthStateCache`] service if this is the first time the cache is
@@ -726,6 +1071,36 @@ impl<Provider, Pool, Network, Tasks, Events, EthApi>
         &self.provider
     }
 
+    /// Returns a reference to the network
+    pub const fn network(&self) -> &Network {
+        &self.network
+    }
+
+    /// Returns the `eth` namespace configuration the handlers were built with
+    pub const fn eth_config(&self) -> &EthConfig {
+        &self.eth_config
+    }
+
+    /// Returns the request limits shared with the rpc handlers
+    pub const fn limits(&self) -> &RpcLimits {
+        &self.limits
+    }
+
+    /// Returns the [`SyncProgress`] reported by `eth_syncing` and the `syncing` subscription.
+    ///
+    /// The progress of the sync stages is only reported once it's recorded, e.g. from the events
+    /// of the pipeline.
+    pub const fn sync_progress(&self) -> &SyncProgress {
+        &self.sync_progress
+    }
+
+    /// Returns the [`IntegrityCheckHandle`] that `admin_integrityCheck` reports.
+    ///
+    /// The integrity check is only reported once a checker is started with this handle.
+    pub const fn integrity_check(&self) -> &IntegrityCheckHandle {
+        &self.integrity_check
+    }
+
     /// Returns all installed methods
     pub fn methods(&self) -> Vec<Methods> {
         self.modules.values().cloned().collect()
@@ -767,6 +1142,9 @@ where
         Network: Peers,
     {
         AdminApi::new(self.network.clone(), self.provider.chain_spec())
+            .with_rpc_config(self.rpc_config_handle())
+            .with_log_filters(LogFilterHandle::global())
+            .with_integrity_check(self.integrity_check.clone())
     }
 
     /// Instantiates `Web3Api`
@@ -810,7 +1188,8 @@ where
         EthApi: EthApiServer<reth_rpc_types::Transaction, reth_rpc_types::Block>,
     {
         let eth_api = self.eth_api().clone();
-        self.modules.insert(RethRpcModule::Eth, eth_api.into_rpc().into());
+        let module = self.eth.apply_middleware(eth_api.into_rpc().remove_context());
+        self.modules.insert(RethRpcModule::Eth, module.into());
         self
     }
 
@@ -879,12 +1258,22 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime.
-    pub fn register_reth(&mut self) -> &mut Self {
+    pub fn register_reth(&mut self) -> &mut Self
+    where
+        EthApi: Call + LoadPendingBlock + EthState + EthApiSpec,
+    {
         let rethapi = self.reth_api();
         self.modules.insert(RethRpcModule::Reth, rethapi.into_rpc().into());
         self
     }
 
+    /// Register Reth database namespace
+    pub fn register_reth_db(&mut self) -> &mut Self {
+        let reth_db_api = self.reth_db_api();
+        self.modules.insert(RethRpcModule::RethDb, reth_db_api.into_rpc().into());
+        self
+    }
+
     /// Instantiates `TraceApi`
     ///
     /// # Panics
@@ -894,10 +1283,11 @@ where
     where
         EthApi: TraceExt,
     {
-        TraceApi::new(
+        TraceApi::with_historical_fallback(
             self.provider.clone(),
             self.eth_api().clone(),
             self.blocking_pool_guard.clone(),
+            self.historical_fallback.clone(),
         )
     }
 
@@ -954,8 +1344,20 @@ where
     }
 
     /// Instantiates `RethApi`
-    pub fn reth_api(&self) -> RethApi<Provider> {
-        RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+    pub fn reth_api(&self) -> RethApi<Provider, EthApi, Network> {
+        let config = self.reth_api_config(&self.modules.keys().copied().collect::<Vec<_>>());
+        RethApi::with_config(
+            self.provider.clone(),
+            self.eth_api().clone(),
+            self.network.clone(),
+            Box::new(self.executor.clone()),
+            config,
+        )
+    }
+
+    /// Instantiates `RethDbApi`
+    pub fn reth_db_api(&self) -> RethDbApi<Provider> {
+        RethDbApi::new(self.provider.clone())
     }
 }
 
@@ -1032,6 +1434,9 @@ where
     /// If this is the first time the namespace is requested, a new instance of API implementation
     /// will be created.
     ///
+    /// The namespaces are marked as served, the returned methods fail while the namespace is
+    /// disabled in the [`RpcModuleToggles`].
+    ///
     /// # Panics
     ///
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
@@ -1039,20 +1444,29 @@ where
         &mut self,
         namespaces: impl Iterator<Item = RethRpcModule>,
     ) -> Vec<Methods> {
+        let eth_handlers = self.eth_handlers().clone();
         let EthHandlers { api: eth_api, filter: eth_filter, pubsub: eth_pubsub, .. } =
-            self.eth_handlers().clone();
+            &eth_handlers;
 
         // Create a copy, so we can list out all the methods for rpc_ api
         let namespaces: Vec<_> = namespaces.collect();
+        let reth_api_config = self.reth_api_config(&namespaces);
         namespaces
             .iter()
             .copied()
             .map(|namespace| {
-                self.modules
+                let methods = self
+                    .modules
                     .entry(namespace)
                     .or_insert_with(|| match namespace {
                         RethRpcModule::Admin => {
                             AdminApi::new(self.network.clone(), self.provider.chain_spec())
+                                .with_rpc_config(RpcConfigHandle::new(
+                                    self.module_toggles.clone(),
+                                    self.limits.clone(),
+                                ))
+                                .with_log_filters(LogFilterHandle::global())
+                                .with_integrity_check(self.integrity_check.clone())
                                 .into_rpc()
                                 .into()
                         }
@@ -1069,15 +1483,16 @@ where
                             module.merge(eth_filter.clone().into_rpc()).expect("No conflicts");
                             module.merge(eth_pubsub.clone().into_rpc()).expect("No conflicts");
 
-                            module.into()
+                            eth_handlers.apply_middleware(module.remove_context()).into()
                         }
                         RethRpcModule::Net => {
                             NetApi::new(self.network.clone(), eth_api.clone()).into_rpc().into()
                         }
-                        RethRpcModule::Trace => TraceApi::new(
+                        RethRpcModule::Trace => TraceApi::with_historical_fallback(
                             self.provider.clone(),
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
+                            self.historical_fallback.clone(),
                         )
                         .into_rpc()
                         .into(),
@@ -1094,18 +1509,28 @@ where
                         .into_rpc()
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
-                        RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
-                                .into_rpc()
-                                .into()
-                        }
+                        RethRpcModule::Reth => RethApi::with_config(
+                            self.provider.clone(),
+                            eth_api.clone(),
+                            self.network.clone(),
+                            Box::new(self.executor.clone()),
+                            reth_api_config.clone(),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::EthCallBundle => {
                             EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
                                 .into_rpc()
                                 .into()
                         }
+                        RethRpcModule::RethDb => {
+                            RethDbApi::new(self.provider.clone()).into_rpc().into()
+                        }
                     })
-                    .clone()
+                    .clone();
+
+                self.module_toggles.serve(namespace);
+                runtime::toggleable_methods(namespace, methods, &self.module_toggles)
             })
             .collect::<Vec<_>>()
     }
@@ -1142,6 +1567,12 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// Health check served on `/health` and `/ready` by the http server
+    health_check: Option<HealthCheck>,
+    /// Whether requests are handled in a span with a unique correlation id
+    correlation_ids: bool,
+    /// Cardinality limits of the per-method metrics
+    metrics_config: RpcMetricsConfig,
     /// Configurable RPC middleware
     rpc_middleware: RpcServiceBuilder<RpcMiddleware>,
 }
@@ -1161,6 +1592,9 @@ impl Default for RpcServerConfig<Identity> {
             ipc_server_config: None,
             ipc_endpoint: None,
             jwt_secret: None,
+            health_check: None,
+            correlation_ids: false,
+            metrics_config: RpcMetricsConfig::default(),
             rpc_middleware: RpcServiceBuilder::new(),
         }
     }
@@ -1224,6 +1658,9 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             ipc_server_config: self.ipc_server_config,
             ipc_endpoint: self.ipc_endpoint,
             jwt_secret: self.jwt_secret,
+            health_check: self.health_check,
+            correlation_ids: self.correlation_ids,
+            metrics_config: self.metrics_config,
             rpc_middleware,
         }
     }
@@ -1297,6 +1734,25 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configures the health check served on `/health` and `/ready` by the http server.
+    pub fn with_health_check(mut self, check: Option<HealthCheck>) -> Self {
+        self.health_check = check;
+        self
+    }
+
+    /// Configures whether requests are handled in a span with a unique correlation id, see
+    /// [`RpcCorrelationLayer`].
+    pub const fn with_correlation_ids(mut self, enabled: bool) -> Self {
+        self.correlation_ids = enabled;
+        self
+    }
+
+    /// Configures the cardinality limits of the per-method metrics.
+    pub const fn with_metrics_config(mut self, config: RpcMetricsConfig) -> Self {
+        self.metrics_config = config;
+        self
+    }
+
     /// Returns true if any server is configured.
     ///
     /// If no server is configured, no server will be launched on [`RpcServerConfig::start`].
@@ -1331,6 +1787,11 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
     }
 
+    /// Creates the [`HealthLayer`](health::HealthLayer) if any
+    fn maybe_health_layer(check: Option<HealthCheck>) -> Option<health::HealthLayer> {
+        check.map(health::HealthLayer::new)
+    }
+
     /// Builds and starts the configured server(s): http, ws, ipc.
     ///
     /// If both http and ws are on the same port, they are combined into one server.
@@ -1338,9 +1799,13 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
     /// Returns the [`RpcServerHandle`] with the handle to the started servers.
     pub async fn start(self, modules: &TransportRpcModules) -> Result<RpcServerHandle, RpcError>
     where
-        RpcMiddleware: Layer<RpcRequestMetricsService<RpcService>> + Clone + Send + 'static,
-        for<'a> <RpcMiddleware as Layer<RpcRequestMetricsService<RpcService>>>::Service:
-            Send + Sync + 'static + RpcServiceT<'a>,
+        RpcMiddleware: Layer<RpcRequestMetricsService<RpcCorrelationService<RpcService>>>
+            + Clone
+            + Send
+            + 'static,
+        for<'a> <RpcMiddleware as Layer<
+            RpcRequestMetricsService<RpcCorrelationService<RpcService>>,
+        >>::Service: Send + Sync + 'static + RpcServiceT<'a>,
     {
         let mut http_handle = None;
         let mut ws_handle = None;
@@ -1356,13 +1821,21 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             constants::DEFAULT_WS_RPC_PORT,
         )));
 
-        let metrics = modules.ipc.as_ref().map(RpcRequestMetrics::ipc).unwrap_or_default();
+        let metrics_config = self.metrics_config;
+        let correlation = RpcCorrelationLayer::new(self.correlation_ids);
+        let metrics = modules
+            .ipc
+            .as_ref()
+            .map(|module| RpcRequestMetrics::ipc(module, metrics_config))
+            .unwrap_or_default();
         let ipc_path =
             self.ipc_endpoint.clone().unwrap_or_else(|| constants::DEFAULT_IPC_ENDPOINT.into());
 
         if let Some(builder) = self.ipc_server_config {
             let ipc = builder
-                .set_rpc_middleware(IpcRpcServiceBuilder::new().layer(metrics))
+                .set_rpc_middleware(
+                    IpcRpcServiceBuilder::new().layer(metrics).layer(correlation.clone()),
+                )
                 .build(ipc_path);
             ipc_handle = Some(ipc.start(modules.ipc.clone().expect("ipc server error")).await?);
         }
@@ -1395,17 +1868,23 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                     .set_http_middleware(
                         tower::ServiceBuilder::new()
                             .option_layer(Self::maybe_cors_layer(cors)?)
+                            .option_layer(Self::maybe_health_layer(self.health_check.clone()))
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
                     )
                     .set_rpc_middleware(
-                        self.rpc_middleware.clone().layer(
-                            modules
-                                .http
-                                .as_ref()
-                                .or(modules.ws.as_ref())
-                                .map(RpcRequestMetrics::same_port)
-                                .unwrap_or_default(),
-                        ),
+                        self.rpc_middleware
+                            .clone()
+                            .layer(
+                                modules
+                                    .http
+                                    .as_ref()
+                                    .or(modules.ws.as_ref())
+                                    .map(|module| {
+                                        RpcRequestMetrics::same_port(module, metrics_config)
+                                    })
+                                    .unwrap_or_default(),
+                            )
+                            .layer(correlation.clone()),
                     )
                     .build(http_socket_addr)
                     .await
@@ -1448,7 +1927,14 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 .set_rpc_middleware(
                     self.rpc_middleware
                         .clone()
-                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default()),
+                        .layer(
+                            modules
+                                .ws
+                                .as_ref()
+                                .map(|module| RpcRequestMetrics::ws(module, metrics_config))
+                                .unwrap_or_default(),
+                        )
+                        .layer(correlation.clone()),
                 )
                 .build(ws_socket_addr)
                 .await
@@ -1468,12 +1954,20 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
+                        .option_layer(Self::maybe_health_layer(self.health_check))
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
                 )
                 .set_rpc_middleware(
-                    self.rpc_middleware.clone().layer(
-                        modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
-                    ),
+                    self.rpc_middleware
+                        .clone()
+                        .layer(
+                            modules
+                                .http
+                                .as_ref()
+                                .map(|module| RpcRequestMetrics::http(module, metrics_config))
+                                .unwrap_or_default(),
+                        )
+                        .layer(correlation.clone()),
                 )
                 .build(http_socket_addr)
                 .await
@@ -1705,6 +2199,22 @@ impl TransportRpcModules {
         self.merge_ipc(other)?;
         Ok(())
     }
+
+    /// Merge the given [Methods] in all configured methods, replacing the methods with the same
+    /// names that are present already.
+    pub fn replace_configured(
+        &mut self,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        for module in [&mut self.http, &mut self.ws, &mut self.ipc].into_iter().flatten() {
+            for name in other.method_names() {
+                module.remove_method(name);
+            }
+            module.merge(other.clone())?;
+        }
+        Ok(())
+    }
 }
 
 /// A handle to the spawned servers.
@@ -1918,6 +2428,7 @@ mod tests {
                 "rpc" => RethRpcModule::Rpc,
                 "ots" => RethRpcModule::Ots,
                 "reth" => RethRpcModule::Reth,
+                "reth-db" => RethRpcModule::RethDb,
             );
     }
 
@@ -1954,6 +2465,30 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_replace_configured() {
+        let mut existing = RpcModule::new(());
+        existing.register_method("eth_chainId", |_, _, _| "existing").unwrap();
+        existing.register_method("eth_syncing", |_, _, _| false).unwrap();
+        let mut modules = TransportRpcModules {
+            config: TransportRpcModuleConfig::default(),
+            http: Some(existing),
+            ws: None,
+            ipc: None,
+        };
+
+        let mut replacement = RpcModule::new(());
+        replacement.register_method("eth_chainId", |_, _, _| "replaced").unwrap();
+        assert!(modules.merge_configured(replacement.clone()).is_err());
+        modules.replace_configured(replacement).unwrap();
+
+        let http = modules.http.unwrap();
+        let mut names = http.method_names().collect::<Vec<_>>();
+        names.sort_unstable();
+        assert_eq!(names, ["eth_chainId", "eth_syncing"]);
+        assert!(modules.ws.is_none());
+    }
+
     #[test]
     fn test_configure_transport_config_none() {
         let config = TransportRpcModuleConfig::default().with_http(Vec::<RethRpcModule>::new());
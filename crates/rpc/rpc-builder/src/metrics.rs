@@ -1,18 +1,69 @@
 use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse, RpcModule};
 use reth_metrics::{
-    metrics::{Counter, Histogram},
+    metrics::{self, Counter, Histogram},
     Metrics,
 };
+use reth_rpc_server_types::constants::{
+    DEFAULT_MAX_METRICS_ERROR_CODES, DEFAULT_MAX_METRICS_METHODS,
+};
 use std::{
     collections::HashMap,
     future::Future,
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, Mutex, PoisonError},
     task::{Context, Poll},
     time::Instant,
 };
 use tower::Layer;
 
+/// The label of methods and error codes that exceed the cardinality limits of the
+/// [`RpcMetricsConfig`].
+const OTHER_LABEL: &str = "other";
+
+/// Limits the cardinality of the per-method RPC metrics.
+///
+/// Methods beyond [`Self::max_methods`] share the metrics labeled `method="other"`, error codes
+/// beyond [`Self::max_error_codes`] of a method are counted as `code="other"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpcMetricsConfig {
+    max_methods: usize,
+    max_error_codes: usize,
+}
+
+impl RpcMetricsConfig {
+    /// Sets the maximum number of methods that are tracked with their own label.
+    pub const fn with_max_methods(mut self, max_methods: usize) -> Self {
+        self.max_methods = max_methods;
+        self
+    }
+
+    /// Sets the maximum number of error codes that are tracked with their own label per method.
+    pub const fn with_max_error_codes(mut self, max_error_codes: usize) -> Self {
+        self.max_error_codes = max_error_codes;
+        self
+    }
+
+    /// Returns the maximum number of methods that are tracked with their own label.
+    pub const fn max_methods(&self) -> usize {
+        self.max_methods
+    }
+
+    /// Returns the maximum number of error codes that are tracked with their own label per
+    /// method.
+    pub const fn max_error_codes(&self) -> usize {
+        self.max_error_codes
+    }
+}
+
+impl Default for RpcMetricsConfig {
+    fn default() -> Self {
+        Self {
+            max_methods: DEFAULT_MAX_METRICS_METHODS,
+            max_error_codes: DEFAULT_MAX_METRICS_ERROR_CODES,
+        }
+    }
+}
+
 /// Metrics for the RPC server.
 ///
 /// Metrics are divided into two categories:
@@ -26,38 +77,57 @@ pub(crate) struct RpcRequestMetrics {
 }
 
 impl RpcRequestMetrics {
-    pub(crate) fn new(module: &RpcModule<()>, transport: RpcTransport) -> Self {
+    pub(crate) fn new(
+        module: &RpcModule<()>,
+        transport: RpcTransport,
+        config: RpcMetricsConfig,
+    ) -> Self {
+        let mut methods = module.method_names().collect::<Vec<_>>();
+        methods.sort_unstable();
+
+        let mut call_metrics = HashMap::with_capacity(methods.len());
+        let mut other = None;
+        for (idx, method) in methods.into_iter().enumerate() {
+            let metrics = if idx < config.max_methods {
+                Arc::new(MethodMetrics::new(method, config.max_error_codes))
+            } else {
+                other
+                    .get_or_insert_with(|| {
+                        Arc::new(MethodMetrics::new(OTHER_LABEL, config.max_error_codes))
+                    })
+                    .clone()
+            };
+            call_metrics.insert(method, metrics);
+        }
+
         Self {
             inner: Arc::new(RpcServerMetricsInner {
                 connection_metrics: transport.connection_metrics(),
-                call_metrics: HashMap::from_iter(module.method_names().map(|method| {
-                    (method, RpcServerCallMetrics::new_with_labels(&[("method", method)]))
-                })),
+                call_metrics,
             }),
         }
     }
 
     /// Creates a new instance of the metrics layer for HTTP.
-    pub(crate) fn http(module: &RpcModule<()>) -> Self {
-        Self::new(module, RpcTransport::Http)
+    pub(crate) fn http(module: &RpcModule<()>, config: RpcMetricsConfig) -> Self {
+        Self::new(module, RpcTransport::Http, config)
     }
 
     /// Creates a new instance of the metrics layer for same port.
     ///
     /// Note: currently it's not possible to track transport specific metrics for a server that runs http and ws on the same port: <https://github.com/paritytech/jsonrpsee/issues/1345> until we have this feature we will use the http metrics for this case.
-    pub(crate) fn same_port(module: &RpcModule<()>) -> Self {
-        Self::http(module)
+    pub(crate) fn same_port(module: &RpcModule<()>, config: RpcMetricsConfig) -> Self {
+        Self::http(module, config)
     }
 
     /// Creates a new instance of the metrics layer for Ws.
-    pub(crate) fn ws(module: &RpcModule<()>) -> Self {
-        Self::new(module, RpcTransport::WebSocket)
+    pub(crate) fn ws(module: &RpcModule<()>, config: RpcMetricsConfig) -> Self {
+        Self::new(module, RpcTransport::WebSocket, config)
     }
 
-    /// Creates a new instance of the metrics layer for Ws.
-    #[allow(unused)]
-    pub(crate) fn ipc(module: &RpcModule<()>) -> Self {
-        Self::new(module, RpcTransport::Ipc)
+    /// Creates a new instance of the metrics layer for Ipc.
+    pub(crate) fn ipc(module: &RpcModule<()>, config: RpcMetricsConfig) -> Self {
+        Self::new(module, RpcTransport::Ipc, config)
     }
 }
 
@@ -74,8 +144,58 @@ impl<S> Layer<S> for RpcRequestMetrics {
 struct RpcServerMetricsInner {
     /// Connection metrics per transport type
     connection_metrics: RpcServerConnectionMetrics,
-    /// Call metrics per RPC method
-    call_metrics: HashMap<&'static str, RpcServerCallMetrics>,
+    /// Call metrics per RPC method, methods beyond the cardinality limit share the same metrics
+    call_metrics: HashMap<&'static str, Arc<MethodMetrics>>,
+}
+
+/// Metrics of a single RPC method, or of all methods beyond the cardinality limit.
+struct MethodMetrics {
+    /// The label of the method
+    method: &'static str,
+    /// Call metrics of the method
+    calls: RpcServerCallMetrics,
+    /// The maximum number of error codes with their own label
+    max_error_codes: usize,
+    /// Error counters per error code, `None` for the error codes beyond the limit
+    errors: Mutex<HashMap<Option<i32>, Counter>>,
+}
+
+impl std::fmt::Debug for MethodMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MethodMetrics")
+            .field("method", &self.method)
+            .field("max_error_codes", &self.max_error_codes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MethodMetrics {
+    fn new(method: &'static str, max_error_codes: usize) -> Self {
+        Self {
+            method,
+            calls: RpcServerCallMetrics::new_with_labels(&[("method", method)]),
+            max_error_codes,
+            errors: Default::default(),
+        }
+    }
+
+    /// Increments the error counter of the given error code.
+    fn record_error(&self, code: i32) {
+        let mut errors = self.errors.lock().unwrap_or_else(PoisonError::into_inner);
+        let known_codes = errors.len() - usize::from(errors.contains_key(&None));
+        let key = if errors.contains_key(&Some(code)) || known_codes < self.max_error_codes {
+            Some(code)
+        } else {
+            None
+        };
+        errors
+            .entry(key)
+            .or_insert_with(|| {
+                let code = key.map_or_else(|| OTHER_LABEL.to_string(), |code| code.to_string());
+                metrics::counter!("rpc_server.calls.errors_total", "method" => self.method, "code" => code)
+            })
+            .increment(1);
+    }
 }
 
 /// A [`RpcServiceT`] middleware that captures RPC metrics for the server.
@@ -105,15 +225,17 @@ where
 
     fn call(&self, req: Request<'a>) -> Self::Future {
         self.metrics.inner.connection_metrics.requests_started_total.increment(1);
-        let call_metrics = self.metrics.inner.call_metrics.get_key_value(req.method.as_ref());
-        if let Some((_, call_metrics)) = &call_metrics {
-            call_metrics.started_total.increment(1);
+        let call_metrics = self.metrics.inner.call_metrics.get(req.method.as_ref()).cloned();
+        if let Some(call_metrics) = &call_metrics {
+            call_metrics.calls.started_total.increment(1);
+            let request_size = req.params.as_ref().map_or(0, |params| params.get().len());
+            call_metrics.calls.request_size_bytes.record(request_size as f64);
         }
         MeteredRequestFuture {
             fut: self.inner.call(req),
             started_at: Instant::now(),
             metrics: self.metrics.clone(),
-            method: call_metrics.map(|(method, _)| *method),
+            call_metrics,
         }
     }
 }
@@ -134,8 +256,8 @@ pub struct MeteredRequestFuture<F> {
     started_at: Instant,
     /// metrics for the method call
     metrics: RpcRequestMetrics,
-    /// the method name if known
-    method: Option<&'static str>,
+    /// the metrics of the method if known
+    call_metrics: Option<Arc<MethodMetrics>>,
 }
 
 impl<F> std::fmt::Debug for MeteredRequestFuture<F> {
@@ -159,14 +281,16 @@ impl<F: Future<Output = MethodResponse>> Future for MeteredRequestFuture<F> {
             this.metrics.inner.connection_metrics.request_time_seconds.record(elapsed);
 
             // update call metrics
-            if let Some(call_metrics) =
-                this.method.and_then(|method| this.metrics.inner.call_metrics.get(method))
-            {
-                call_metrics.time_seconds.record(elapsed);
+            if let Some(call_metrics) = this.call_metrics {
+                call_metrics.calls.time_seconds.record(elapsed);
+                call_metrics.calls.response_size_bytes.record(resp.as_result().len() as f64);
                 if resp.is_success() {
-                    call_metrics.successful_total.increment(1);
+                    call_metrics.calls.successful_total.increment(1);
                 } else {
-                    call_metrics.failed_total.increment(1);
+                    call_metrics.calls.failed_total.increment(1);
+                    if let Some(code) = resp.as_error_code() {
+                        call_metrics.record_error(code);
+                    }
                 }
             }
         }
@@ -179,7 +303,6 @@ impl<F: Future<Output = MethodResponse>> Future for MeteredRequestFuture<F> {
 pub(crate) enum RpcTransport {
     Http,
     WebSocket,
-    #[allow(unused)]
     Ipc,
 }
 
@@ -227,4 +350,37 @@ struct RpcServerCallMetrics {
     failed_total: Counter,
     /// Response for a single call
     time_seconds: Histogram,
+    /// Size of the request parameters in bytes
+    request_size_bytes: Histogram,
+    /// Size of the response in bytes
+    response_size_bytes: Histogram,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics_cardinality_limits() {
+        let mut module = RpcModule::new(());
+        module.register_method("a_method", |_, _, _| "a").unwrap();
+        module.register_method("b_method", |_, _, _| "b").unwrap();
+        module.register_method("c_method", |_, _, _| "c").unwrap();
+
+        let config = RpcMetricsConfig::default().with_max_methods(1).with_max_error_codes(1);
+        let metrics = RpcRequestMetrics::new(&module, RpcTransport::Http, config);
+        let call_metrics = &metrics.inner.call_metrics;
+        assert_eq!(call_metrics["a_method"].method, "a_method");
+        assert_eq!(call_metrics["b_method"].method, OTHER_LABEL);
+        assert!(Arc::ptr_eq(&call_metrics["b_method"], &call_metrics["c_method"]));
+
+        let method_metrics = &call_metrics["a_method"];
+        method_metrics.record_error(-32000);
+        method_metrics.record_error(-32001);
+        method_metrics.record_error(-32000);
+        let errors = method_metrics.errors.lock().unwrap();
+        assert!(errors.contains_key(&Some(-32000)));
+        assert!(errors.contains_key(&None));
+        assert_eq!(errors.len(), 2);
+    }
 }
@@ -0,0 +1,68 @@
+//! Support for disabling served namespaces while the node is running.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+    core::server::{MethodCallback, MethodResponse, Methods, RpcModule},
+    types::{error::ErrorCode, ErrorObjectOwned},
+};
+use reth_rpc_server_types::{runtime::RpcModuleToggles, RethRpcModule};
+
+/// Wraps all methods of the namespace so that calls fail as if the method did not exist while the
+/// namespace is disabled in the [`RpcModuleToggles`].
+///
+/// Unsubscribing is always possible, so that subscriptions created before the namespace was
+/// disabled can be closed.
+pub(crate) fn toggleable_methods(
+    namespace: RethRpcModule,
+    methods: Methods,
+    toggles: &RpcModuleToggles,
+) -> Methods {
+    let mut module = RpcModule::new(());
+    module.merge(methods).expect("module is empty");
+
+    let names = module.method_names().collect::<Vec<_>>();
+    for name in names {
+        let Some(callback) = module.remove_method(name) else { continue };
+        let toggles = toggles.clone();
+        let callback = match callback {
+            MethodCallback::Sync(f) => {
+                MethodCallback::Sync(Arc::new(move |id, params, max_response_size, ext| {
+                    if toggles.is_enabled(namespace) {
+                        f(id, params, max_response_size, ext)
+                    } else {
+                        MethodResponse::error(id, method_not_found())
+                    }
+                }))
+            }
+            MethodCallback::Async(f) => MethodCallback::Async(Arc::new(
+                move |id, params, conn_id, max_response_size, ext| {
+                    if toggles.is_enabled(namespace) {
+                        f(id, params, conn_id, max_response_size, ext)
+                    } else {
+                        let response = MethodResponse::error(id, method_not_found());
+                        Box::pin(async move { response })
+                    }
+                },
+            )),
+            MethodCallback::Subscription(f) => {
+                MethodCallback::Subscription(Arc::new(move |id, params, sink, state, ext| {
+                    if toggles.is_enabled(namespace) {
+                        f(id, params, sink, state, ext)
+                    } else {
+                        let response = MethodResponse::error(id, method_not_found());
+                        Box::pin(async move { response })
+                    }
+                }))
+            }
+            callback @ MethodCallback::Unsubscription(_) => callback,
+        };
+        module.verify_and_insert(name, callback).expect("method was removed");
+    }
+    module.into()
+}
+
+/// The error returned for methods of disabled namespaces.
+fn method_not_found() -> ErrorObjectOwned {
+    ErrorCode::MethodNotFound.into()
+}
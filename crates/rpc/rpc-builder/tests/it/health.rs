@@ -0,0 +1,91 @@
+//! Health endpoint tests
+
+use reth_provider::ProviderResult;
+use reth_rpc::EthApi;
+use reth_rpc_builder::{
+    health::{HealthCheck, HealthConfig, NodeSyncStatus, SyncStatusProvider},
+    RpcServerConfig, RpcServerHandle, TransportRpcModuleConfig,
+};
+use reth_rpc_layer::JwtSecret;
+use reth_rpc_server_types::RethRpcModule;
+use std::net::SocketAddr;
+
+use crate::utils::{test_address, test_rpc_builder};
+
+struct StaticSyncStatus(NodeSyncStatus);
+
+impl SyncStatusProvider for StaticSyncStatus {
+    fn sync_status(&self) -> ProviderResult<NodeSyncStatus> {
+        Ok(self.0)
+    }
+}
+
+async fn launch_http_with_health(
+    status: NodeSyncStatus,
+    secret: Option<JwtSecret>,
+) -> RpcServerHandle {
+    let builder = test_rpc_builder();
+    let server = builder.build(
+        TransportRpcModuleConfig::set_http(vec![RethRpcModule::Web3]),
+        Box::new(EthApi::with_spawner),
+    );
+    RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .with_jwt_secret(secret)
+        .with_health_check(Some(HealthCheck::new(
+            HealthConfig::default().with_min_peers(1),
+            StaticSyncStatus(status),
+        )))
+        .start(&server)
+        .await
+        .unwrap()
+}
+
+async fn get(addr: SocketAddr, path: &str) -> (reqwest::StatusCode, serde_json::Value) {
+    let response = reqwest::get(format!("http://{addr}{path}")).await.unwrap();
+    let status = response.status();
+    (status, serde_json::from_str(&response.text().await.unwrap()).unwrap())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_health_endpoints() {
+    reth_tracing::init_test_tracing();
+
+    let synced =
+        NodeSyncStatus { best_block: 10, highest_block: 10, peers: 3, ..Default::default() };
+    let handle = launch_http_with_health(synced, None).await;
+    let addr = handle.http_local_addr().unwrap();
+
+    let (status, body) = get(addr, "/health").await;
+    assert_eq!(status, reqwest::StatusCode::OK);
+    assert_eq!(body["bestBlock"], 10);
+
+    let (status, body) = get(addr, "/ready").await;
+    assert_eq!(status, reqwest::StatusCode::OK);
+    assert_eq!(body["ready"], true);
+
+    let syncing = NodeSyncStatus { initially_syncing: true, ..synced };
+    let handle = launch_http_with_health(syncing, None).await;
+    let addr = handle.http_local_addr().unwrap();
+
+    let (status, _) = get(addr, "/health").await;
+    assert_eq!(status, reqwest::StatusCode::OK);
+
+    let (status, body) = get(addr, "/ready").await;
+    assert_eq!(status, reqwest::StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["ready"], false);
+    assert_eq!(body["failures"][0], "initial sync in progress");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_health_endpoints_skip_jwt_auth() {
+    reth_tracing::init_test_tracing();
+
+    let status = NodeSyncStatus { peers: 0, ..Default::default() };
+    let handle = launch_http_with_health(status, Some(JwtSecret::random())).await;
+    let addr = handle.http_local_addr().unwrap();
+
+    let (status, body) = get(addr, "/ready").await;
+    assert_eq!(status, reqwest::StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["failures"][0], "0 peers connected, at least 1 required");
+}
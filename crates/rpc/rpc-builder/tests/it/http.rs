@@ -1,7 +1,7 @@
 #![allow(unreachable_pub)]
 //! Standalone http tests
 
-use crate::utils::{launch_http, launch_http_ws, launch_ws};
+use crate::utils::{launch_http, launch_http_ws, launch_ws, test_address, test_rpc_builder};
 use jsonrpsee::{
     core::{
         client::{ClientT, SubscriptionClientT},
@@ -15,12 +15,19 @@ use reth_network_peers::NodeRecord;
 use reth_primitives::{
     hex_literal::hex, Address, BlockId, BlockNumberOrTag, Bytes, TxHash, B256, B64, U256, U64,
 };
+use reth_prune_types::{PruneMode, PruneModes, PruneSegment};
+use reth_rpc::EthApi;
 use reth_rpc_api::{
     clients::{AdminApiClient, EthApiClient},
-    DebugApiClient, EthFilterApiClient, NetApiClient, OtterscanClient, TraceApiClient,
-    Web3ApiClient,
+    DebugApiClient, EthFilterApiClient, NetApiClient, OtterscanClient, RethApiClient,
+    RethDbApiClient, TraceApiClient, Web3ApiClient,
+};
+use reth_rpc_builder::{RpcServerConfig, TransportRpcModuleConfig};
+use reth_rpc_server_types::{
+    capabilities::PruneSegmentInfo,
+    runtime::{RpcConfigUpdate, RpcRuntimeConfig},
+    RethRpcModule,
 };
-use reth_rpc_server_types::RethRpcModule;
 use reth_rpc_types::{
     trace::filter::TraceFilter, FeeHistory, Filter, Index, Log, PendingTransactionFilterKind,
     RichBlock, SyncStatus, Transaction, TransactionReceipt, TransactionRequest,
@@ -145,6 +152,10 @@ where
     AdminApiClient::add_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::remove_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::node_info(client).await.unwrap();
+    AdminApiClient::rpc_config(client).await.unwrap();
+    // no integrity check is running
+    assert!(AdminApiClient::integrity_check(client).await.is_err());
+    assert!(AdminApiClient::reset_integrity_check(client).await.is_err());
 }
 
 async fn test_basic_eth_calls<C>(client: &C)
@@ -223,6 +234,9 @@ where
     EthApiClient::<Transaction, RichBlock>::sign_typed_data(client, address, typed_data)
         .await
         .unwrap_err();
+    EthApiClient::<Transaction, RichBlock>::sign_transaction(client, call_request.clone())
+        .await
+        .unwrap_err();
     EthApiClient::<Transaction, RichBlock>::transaction_by_hash(client, tx_hash).await.unwrap();
     EthApiClient::<Transaction, RichBlock>::transaction_by_block_hash_and_index(
         client, hash, index,
@@ -248,6 +262,7 @@ where
         call_request.clone(),
         Some(block_number.into()),
         None,
+        None,
     )
     .await
     .unwrap();
@@ -257,6 +272,7 @@ where
         Some(block_number.into()),
         None,
         None,
+        None,
     )
     .await
     .unwrap();
@@ -297,12 +313,6 @@ where
         .err()
         .unwrap()
     ));
-    assert!(is_unimplemented(
-        EthApiClient::<Transaction, RichBlock>::sign_transaction(client, call_request.clone())
-            .await
-            .err()
-            .unwrap()
-    ));
 }
 
 async fn test_basic_debug_calls<C>(client: &C)
@@ -618,6 +628,24 @@ async fn test_call_otterscan_functions_http_and_ws() {
     test_basic_otterscan_calls(&client).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_reth_db_functions_http() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_http(vec![RethRpcModule::RethDb]).await;
+    let client = handle.http_client().unwrap();
+
+    let stats = RethDbApiClient::stats(&client).await.unwrap();
+    assert_eq!(stats, Default::default());
+    assert!(RethDbApiClient::tables(&client).await.unwrap().is_empty());
+    assert!(RethDbApiClient::static_files(&client).await.unwrap().is_empty());
+    assert!(RethDbApiClient::prune_checkpoints(&client).await.unwrap().is_empty());
+    assert!(RethDbApiClient::stage_checkpoints(&client).await.unwrap().is_empty());
+
+    let stats = client.request::<Value, _>("reth_db_stats", rpc_params![]).await.unwrap();
+    assert!(stats["staticFiles"].is_array());
+}
+
 // <https://github.com/paradigmxyz/reth/issues/5830>
 #[tokio::test(flavor = "multi_thread")]
 async fn test_eth_logs_args() {
@@ -1517,3 +1545,93 @@ async fn test_eth_fee_history_raw() {
     )
     .await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_admin_update_rpc_config() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_http(vec![RethRpcModule::Admin, RethRpcModule::Web3]).await;
+    let client = handle.http_client().unwrap();
+
+    let config = AdminApiClient::update_rpc_config(
+        &client,
+        RpcConfigUpdate {
+            disable: vec![RethRpcModule::Web3],
+            gas_cap: Some(1_000_000),
+            max_logs_per_response: Some(0),
+            max_tracing_requests: Some(4),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(config.enabled, vec![RethRpcModule::Admin]);
+    assert_eq!(config.disabled, vec![RethRpcModule::Web3]);
+    assert_eq!(config.gas_cap, 1_000_000);
+    assert_eq!(config.max_logs_per_response, None);
+    assert_eq!(config.max_tracing_requests, 4);
+
+    let err = Web3ApiClient::client_version(&client).await.unwrap_err();
+    assert!(
+        matches!(err, jsonrpsee::core::client::Error::Call(ref err) if err.code() == ErrorCode::MethodNotFound.code()),
+        "{err:?}"
+    );
+
+    let update = RpcConfigUpdate { enable: vec![RethRpcModule::Web3], ..Default::default() };
+    AdminApiClient::update_rpc_config(&client, update).await.unwrap();
+    Web3ApiClient::client_version(&client).await.unwrap();
+
+    // namespaces that are not served and the admin namespace can't be toggled
+    let update = RpcConfigUpdate { enable: vec![RethRpcModule::Trace], ..Default::default() };
+    AdminApiClient::update_rpc_config(&client, update).await.unwrap_err();
+    let update = RpcConfigUpdate { disable: vec![RethRpcModule::Admin], ..Default::default() };
+    AdminApiClient::update_rpc_config(&client, update).await.unwrap_err();
+    assert_eq!(
+        AdminApiClient::rpc_config(&client).await.unwrap(),
+        RpcRuntimeConfig {
+            enabled: vec![RethRpcModule::Admin, RethRpcModule::Web3],
+            disabled: vec![],
+            gas_cap: 1_000_000,
+            max_logs_per_response: None,
+            max_tracing_requests: 4,
+        }
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_reth_config() {
+    reth_tracing::init_test_tracing();
+
+    let prune_modes =
+        PruneModes { receipts: Some(PruneMode::Distance(10_064)), ..PruneModes::none() };
+    let config = TransportRpcModuleConfig::set_http(vec![
+        RethRpcModule::Reth,
+        RethRpcModule::Web3,
+        RethRpcModule::Eth,
+    ]);
+    let server = test_rpc_builder()
+        .with_prune_modes(prune_modes)
+        .build(config, Box::new(EthApi::with_spawner));
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .start(&server)
+        .await
+        .unwrap();
+    let client = handle.http_client().unwrap();
+
+    let capabilities = RethApiClient::reth_config(&client).await.unwrap();
+    // ordered like the variants, not like the configured selection
+    assert_eq!(
+        capabilities.namespaces,
+        vec![RethRpcModule::Eth, RethRpcModule::Web3, RethRpcModule::Reth]
+    );
+    // the configured modes are reported, although nothing was pruned yet
+    assert_eq!(
+        capabilities.prune,
+        vec![PruneSegmentInfo {
+            segment: PruneSegment::Receipts,
+            mode: PruneMode::Distance(10_064),
+            pruned_to_block: None,
+        }]
+    );
+}
@@ -1,4 +1,5 @@
 mod auth;
+mod health;
 mod http;
 mod middleware;
 mod serde;
@@ -1,11 +1,13 @@
 use crate::utils::{test_address, test_rpc_builder};
 use jsonrpsee::{
+    core::server::Extensions,
     server::{middleware::rpc::RpcServiceT, RpcServiceBuilder},
-    types::Request,
+    types::{ErrorObjectOwned, Params, Request},
     MethodResponse,
 };
 use reth_rpc::EthApi;
-use reth_rpc_builder::{RpcServerConfig, TransportRpcModuleConfig};
+use reth_rpc_api::clients::NetApiClient;
+use reth_rpc_builder::{EthMiddleware, RpcServerConfig, TransportRpcModuleConfig};
 use reth_rpc_eth_api::EthApiClient;
 use reth_rpc_server_types::RpcModuleSelection;
 use reth_rpc_types::{RichBlock, Transaction};
@@ -79,3 +81,53 @@ async fn test_rpc_middleware() {
     let count = mylayer.count.load(Ordering::Relaxed);
     assert_eq!(count, 1);
 }
+
+#[derive(Debug, Default)]
+struct BlockChainIdMiddleware {
+    responses: AtomicUsize,
+}
+
+impl EthMiddleware for BlockChainIdMiddleware {
+    fn on_request<'a>(
+        &self,
+        method: &'static str,
+        params: Params<'a>,
+        _extensions: &Extensions,
+    ) -> Result<Params<'a>, ErrorObjectOwned> {
+        if method == "eth_chainId" {
+            return Err(ErrorObjectOwned::owned(-32000, "blocked", None::<()>))
+        }
+        Ok(params)
+    }
+
+    fn on_response(&self, _method: &'static str, response: MethodResponse) -> MethodResponse {
+        self.responses.fetch_add(1, Ordering::Relaxed);
+        response
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_eth_middleware() {
+    let middleware = Arc::new(BlockChainIdMiddleware::default());
+    let builder = test_rpc_builder().with_eth_middleware(Some(middleware.clone()));
+    let modules = builder.build(
+        TransportRpcModuleConfig::set_http(RpcModuleSelection::All),
+        Box::new(EthApi::with_spawner),
+    );
+
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .start(&modules)
+        .await
+        .unwrap();
+
+    let client = handle.http_client().unwrap();
+    let err = EthApiClient::<Transaction, RichBlock>::chain_id(&client).await.unwrap_err();
+    assert!(err.to_string().contains("blocked"), "{err}");
+    EthApiClient::<Transaction, RichBlock>::protocol_version(&client).await.unwrap();
+    assert_eq!(middleware.responses.load(Ordering::Relaxed), 1);
+
+    // methods of other namespaces are not intercepted
+    NetApiClient::version(&client).await.unwrap();
+    assert_eq!(middleware.responses.load(Ordering::Relaxed), 1);
+}
@@ -29,7 +29,7 @@ use reth_storage_api::{BlockReader, HeaderProvider, StateProviderFactory};
 use reth_tasks::TaskSpawner;
 use std::{sync::Arc, time::Instant};
 use tokio::sync::oneshot;
-use tracing::{trace, warn};
+use tracing::{instrument, trace, warn};
 
 /// The Engine API response sender.
 pub type EngineApiSender<Ok> = oneshot::Sender<EngineApiResult<Ok>>;
@@ -112,6 +112,7 @@ where
 
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/paris.md#engine_newpayloadv1>
     /// Caution: This should not accept the `withdrawals` field
+    #[instrument(level = "debug", skip_all, fields(block_hash = %payload.block_hash, block_num = payload.block_number), target = "rpc::engine")]
     pub async fn new_payload_v1(
         &self,
         payload: ExecutionPayloadV1,
@@ -130,6 +131,7 @@ where
     }
 
     /// See also <https://github.com/ethereum/execution-apis/blob/584905270d8ad665718058060267061ecfd79ca5/src/engine/shanghai.md#engine_newpayloadv2>
+    #[instrument(level = "debug", skip_all, fields(block_hash = %payload.execution_payload.block_hash, block_num = payload.execution_payload.block_number), target = "rpc::engine")]
     pub async fn new_payload_v2(
         &self,
         payload: ExecutionPayloadInputV2,
@@ -148,6 +150,7 @@ where
     }
 
     /// See also <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#engine_newpayloadv3>
+    #[instrument(level = "debug", skip_all, fields(block_hash = %payload.payload_inner.payload_inner.block_hash, block_num = payload.payload_inner.payload_inner.block_number), target = "rpc::engine")]
     pub async fn new_payload_v3(
         &self,
         payload: ExecutionPayloadV3,
@@ -172,6 +175,7 @@ where
     }
 
     /// See also <https://github.com/ethereum/execution-apis/blob/7907424db935b93c2fe6a3c0faab943adebe8557/src/engine/prague.md#engine_newpayloadv4>
+    #[instrument(level = "debug", skip_all, fields(block_hash = %payload.payload_inner.payload_inner.payload_inner.block_hash, block_num = payload.payload_inner.payload_inner.payload_inner.block_number), target = "rpc::engine")]
     pub async fn new_payload_v4(
         &self,
         payload: ExecutionPayloadV4,
@@ -201,6 +205,7 @@ where
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/paris.md#engine_forkchoiceUpdatedV1>
     ///
     /// Caution: This should not accept the `withdrawals` field
+    #[instrument(level = "debug", skip_all, fields(head = %state.head_block_hash, has_attributes = payload_attrs.is_some()), target = "rpc::engine")]
     pub async fn fork_choice_updated_v1(
         &self,
         state: ForkchoiceState,
@@ -214,6 +219,7 @@ where
     /// but only _after_ shanghai.
     ///
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/shanghai.md#engine_forkchoiceupdatedv2>
+    #[instrument(level = "debug", skip_all, fields(head = %state.head_block_hash, has_attributes = payload_attrs.is_some()), target = "rpc::engine")]
     pub async fn fork_choice_updated_v2(
         &self,
         state: ForkchoiceState,
@@ -227,6 +233,7 @@ where
     /// but only _after_ cancun.
     ///
     /// See also  <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#engine_forkchoiceupdatedv3>
+    #[instrument(level = "debug", skip_all, fields(head = %state.head_block_hash, has_attributes = payload_attrs.is_some()), target = "rpc::engine")]
     pub async fn fork_choice_updated_v3(
         &self,
         state: ForkchoiceState,
@@ -245,6 +252,7 @@ where
     ///
     /// Note:
     /// > Provider software MAY stop the corresponding build process after serving this call.
+    #[instrument(level = "debug", skip(self), target = "rpc::engine")]
     pub async fn get_payload_v1(
         &self,
         payload_id: PayloadId,
@@ -269,6 +277,7 @@ where
     ///
     /// Note:
     /// > Provider software MAY stop the corresponding build process after serving this call.
+    #[instrument(level = "debug", skip(self), target = "rpc::engine")]
     pub async fn get_payload_v2(
         &self,
         payload_id: PayloadId,
@@ -304,6 +313,7 @@ where
     ///
     /// Note:
     /// > Provider software MAY stop the corresponding build process after serving this call.
+    #[instrument(level = "debug", skip(self), target = "rpc::engine")]
     pub async fn get_payload_v3(
         &self,
         payload_id: PayloadId,
@@ -339,6 +349,7 @@ where
     ///
     /// Note:
     /// > Provider software MAY stop the corresponding build process after serving this call.
+    #[instrument(level = "debug", skip(self), target = "rpc::engine")]
     pub async fn get_payload_v4(
         &self,
         payload_id: PayloadId,
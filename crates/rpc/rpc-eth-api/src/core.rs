@@ -7,13 +7,16 @@ use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::{
     transaction::AccessListResult, Address, BlockId, BlockNumberOrTag, Bytes, B256, B64, U256, U64,
 };
-use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_rpc_server_types::{
+    call::CallOptions, proof::AccountProofResponse, result::internal_rpc_err, sync::SyncStatus,
+    ToRpcResult,
+};
 use reth_rpc_types::{
     serde_helpers::JsonStorageKey,
     simulate::{SimBlock, SimulatedBlock},
     state::{EvmOverrides, StateOverride},
-    AnyTransactionReceipt, BlockOverrides, Bundle, EIP1186AccountProofResponse, EthCallResponse,
-    FeeHistory, Header, Index, StateContext, SyncStatus, TransactionRequest, Work,
+    AnyTransactionReceipt, BlockOverrides, Bundle, EthCallResponse, FeeHistory, Header, Index,
+    StateContext, TransactionRequest, Work,
 };
 use tracing::trace;
 
@@ -211,6 +214,8 @@ pub trait EthApi<T: RpcObject, B: RpcObject> {
     ) -> RpcResult<Vec<SimulatedBlock>>;
 
     /// Executes a new message call immediately without creating a transaction on the block chain.
+    ///
+    /// The reth specific [`CallOptions`] can lower the execution timeout of the call.
     #[method(name = "call")]
     async fn call(
         &self,
@@ -218,6 +223,7 @@ pub trait EthApi<T: RpcObject, B: RpcObject> {
         block_number: Option<BlockId>,
         state_overrides: Option<StateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
+        call_options: Option<CallOptions>,
     ) -> RpcResult<Bytes>;
 
     /// Simulate arbitrary number of transactions at an arbitrary blockchain index, with the
@@ -253,12 +259,15 @@ pub trait EthApi<T: RpcObject, B: RpcObject> {
 
     /// Generates and returns an estimate of how much gas is necessary to allow the transaction to
     /// complete.
+    ///
+    /// The reth specific [`CallOptions`] can lower the execution timeout of the estimation.
     #[method(name = "estimateGas")]
     async fn estimate_gas(
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
         state_override: Option<StateOverride>,
+        call_options: Option<CallOptions>,
     ) -> RpcResult<U256>;
 
     /// Returns the current price per gas in wei.
@@ -346,13 +355,16 @@ pub trait EthApi<T: RpcObject, B: RpcObject> {
 
     /// Returns the account and storage values of the specified account including the Merkle-proof.
     /// This call can be used to verify that the data you are pulling from is not tampered with.
+    ///
+    /// With the experimental verkle trie backend, the verkle trie leaves of the account are
+    /// returned with a verkle proof instead.
     #[method(name = "getProof")]
     async fn get_proof(
         &self,
         address: Address,
         keys: Vec<JsonStorageKey>,
         block_number: Option<BlockId>,
-    ) -> RpcResult<EIP1186AccountProofResponse>;
+    ) -> RpcResult<AccountProofResponse>;
 }
 
 #[async_trait::async_trait]
@@ -608,13 +620,16 @@ where
         block_number: Option<BlockId>,
         state_overrides: Option<StateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
+        call_options: Option<CallOptions>,
     ) -> RpcResult<Bytes> {
-        trace!(target: "rpc::eth", ?request, ?block_number, ?state_overrides, ?block_overrides, "Serving eth_call");
-        Ok(EthCall::call(
+        trace!(target: "rpc::eth", ?request, ?block_number, ?state_overrides, ?block_overrides, ?call_options, "Serving eth_call");
+        let timeout = call_options.unwrap_or_default().effective_timeout(self.call_timeout());
+        Ok(EthCall::call_with_timeout(
             self,
             request,
             block_number,
             EvmOverrides::new(state_overrides, block_overrides),
+            timeout,
         )
         .await?)
     }
@@ -646,13 +661,16 @@ where
         request: TransactionRequest,
         block_number: Option<BlockId>,
         state_override: Option<StateOverride>,
+        call_options: Option<CallOptions>,
     ) -> RpcResult<U256> {
-        trace!(target: "rpc::eth", ?request, ?block_number, "Serving eth_estimateGas");
-        Ok(EthCall::estimate_gas_at(
+        trace!(target: "rpc::eth", ?request, ?block_number, ?call_options, "Serving eth_estimateGas");
+        let timeout = call_options.unwrap_or_default().effective_timeout(self.call_timeout());
+        Ok(EthCall::estimate_gas_at_with_timeout(
             self,
             request,
             block_number.unwrap_or_default(),
             state_override,
+            timeout,
         )
         .await?)
     }
@@ -753,8 +771,9 @@ where
     }
 
     /// Handler for: `eth_signTransaction`
-    async fn sign_transaction(&self, _transaction: TransactionRequest) -> RpcResult<Bytes> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn sign_transaction(&self, transaction: TransactionRequest) -> RpcResult<Bytes> {
+        trace!(target: "rpc::eth", ?transaction, "Serving eth_signTransaction");
+        Ok(EthTransactions::sign_transaction(self, transaction).await?.envelope_encoded())
     }
 
     /// Handler for: `eth_signTypedData`
@@ -769,7 +788,7 @@ where
         address: Address,
         keys: Vec<JsonStorageKey>,
         block_number: Option<BlockId>,
-    ) -> RpcResult<EIP1186AccountProofResponse> {
+    ) -> RpcResult<AccountProofResponse> {
         trace!(target: "rpc::eth", ?address, ?keys, ?block_number, "Serving eth_getProof");
         Ok(EthState::get_proof(self, address, keys, block_number)?.await?)
     }
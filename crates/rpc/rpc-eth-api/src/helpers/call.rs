@@ -19,7 +19,7 @@ use reth_rpc_eth_types::{
     error::ensure_success,
     revm_utils::{
         apply_block_overrides, apply_state_overrides, caller_gas_allowance,
-        cap_tx_gas_limit_with_caller_allowance, get_precompiles, CallFees,
+        cap_tx_gas_limit_with_caller_allowance, get_precompiles, CallFees, CallTimeoutInspector,
     },
     EthApiError, RevertError, RpcInvalidTransactionError, StateCacheDb,
 };
@@ -33,6 +33,7 @@ use reth_rpc_types::{
 };
 use revm::{Database, DatabaseCommit};
 use revm_inspectors::access_list::AccessListInspector;
+use std::time::Duration;
 use tracing::trace;
 
 use super::{LoadBlock, LoadPendingBlock, LoadState, LoadTransaction, SpawnBlocking, Trace};
@@ -50,6 +51,18 @@ pub trait EthCall: Call + LoadPendingBlock {
         Call::estimate_gas_at(self, request, at, state_override)
     }
 
+    /// Same as [`EthCall::estimate_gas_at`] but aborts the execution after the given timeout
+    /// instead of [`Call::call_timeout`].
+    fn estimate_gas_at_with_timeout(
+        &self,
+        request: TransactionRequest,
+        at: BlockId,
+        state_override: Option<StateOverride>,
+        timeout: Option<Duration>,
+    ) -> impl Future<Output = Result<U256, Self::Error>> + Send {
+        Call::estimate_gas_at_with_timeout(self, request, at, state_override, timeout)
+    }
+
     /// `eth_simulateV1` executes an arbitrary number of transactions on top of the requested state.
     /// The transactions are packed into individual blocks. Overrides can be provided.
     ///
@@ -68,10 +81,32 @@ pub trait EthCall: Call + LoadPendingBlock {
         request: TransactionRequest,
         block_number: Option<BlockId>,
         overrides: EvmOverrides,
+    ) -> impl Future<Output = Result<Bytes, Self::Error>> + Send {
+        self.call_with_timeout(request, block_number, overrides, self.call_timeout())
+    }
+
+    /// Same as [`EthCall::call`] but aborts the execution after the given timeout instead of
+    /// [`Call::call_timeout`].
+    fn call_with_timeout(
+        &self,
+        request: TransactionRequest,
+        block_number: Option<BlockId>,
+        overrides: EvmOverrides,
+        timeout: Option<Duration>,
     ) -> impl Future<Output = Result<Bytes, Self::Error>> + Send {
         async move {
-            let (res, _env) =
-                self.transact_call_at(request, block_number.unwrap_or_default(), overrides).await?;
+            let this = self.clone();
+            let (res, _env) = self
+                .spawn_with_call_at(
+                    request,
+                    block_number.unwrap_or_default(),
+                    overrides,
+                    move |db, env| {
+                        let mut deadline = timeout.map(CallTimeoutInspector::new);
+                        this.transact_with_deadline(db, env, deadline.as_mut())
+                    },
+                )
+                .await?;
 
             ensure_success(res.result).map_err(Self::Error::from_eth_err)
         }
@@ -294,6 +329,11 @@ pub trait Call: LoadState + SpawnBlocking {
     /// Data access in default trait method implementations.
     fn call_gas_limit(&self) -> u64;
 
+    /// Returns the wall-clock timeout of the EVM execution of `eth_call` and `eth_estimateGas`.
+    ///
+    /// Data access in default trait method implementations.
+    fn call_timeout(&self) -> Option<Duration>;
+
     /// Returns a handle for reading evm config.
     ///
     /// Data access in default (L1) trait method implementations.
@@ -325,6 +365,27 @@ pub trait Call: LoadState + SpawnBlocking {
         Ok((res, env))
     }
 
+    /// Same as [`Call::transact`] but aborts the execution once the deadline of the
+    /// [`CallTimeoutInspector`] passed and returns [`EthApiError::ExecutionTimedOut`].
+    fn transact_with_deadline<DB>(
+        &self,
+        db: DB,
+        env: EnvWithHandlerCfg,
+        deadline: Option<&mut CallTimeoutInspector>,
+    ) -> Result<(ResultAndState, EnvWithHandlerCfg), Self::Error>
+    where
+        DB: Database,
+        EthApiError: From<DB::Error>,
+    {
+        let Some(deadline) = deadline else { return self.transact(db, env) };
+
+        let mut evm = self.evm_config().evm_with_env_and_inspector(db, env, &mut *deadline);
+        let res = evm.transact().map_err(Self::Error::from_evm_err)?;
+        let (_, env) = evm.into_db_and_env_with_handler_cfg();
+        deadline.ensure_not_timed_out().map_err(Self::Error::from_eth_err::<EthApiError>)?;
+        Ok((res, env))
+    }
+
     /// Executes the call request at the given [`BlockId`].
     fn transact_call_at(
         &self,
@@ -503,6 +564,21 @@ pub trait Call: LoadState + SpawnBlocking {
         at: BlockId,
         state_override: Option<StateOverride>,
     ) -> impl Future<Output = Result<U256, Self::Error>> + Send
+    where
+        Self: LoadPendingBlock,
+    {
+        self.estimate_gas_at_with_timeout(request, at, state_override, self.call_timeout())
+    }
+
+    /// Same as [`Call::estimate_gas_at`] but aborts the estimation after the given timeout instead
+    /// of [`Call::call_timeout`].
+    fn estimate_gas_at_with_timeout(
+        &self,
+        request: TransactionRequest,
+        at: BlockId,
+        state_override: Option<StateOverride>,
+        timeout: Option<Duration>,
+    ) -> impl Future<Output = Result<U256, Self::Error>> + Send
     where
         Self: LoadPendingBlock,
     {
@@ -511,7 +587,14 @@ pub trait Call: LoadState + SpawnBlocking {
 
             self.spawn_blocking_io(move |this| {
                 let state = this.state_at_block_id(at)?;
-                this.estimate_gas_with(cfg, block_env, request, state, state_override)
+                this.estimate_gas_with_deadline(
+                    cfg,
+                    block_env,
+                    request,
+                    state,
+                    state_override,
+                    timeout.map(CallTimeoutInspector::new),
+                )
             })
             .await
         }
@@ -529,12 +612,29 @@ pub trait Call: LoadState + SpawnBlocking {
     ///  - `disable_base_fee` is set to `true`
     ///  - `nonce` is set to `None`
     fn estimate_gas_with<S>(
+        &self,
+        cfg: CfgEnvWithHandlerCfg,
+        block: BlockEnv,
+        request: TransactionRequest,
+        state: S,
+        state_override: Option<StateOverride>,
+    ) -> Result<U256, Self::Error>
+    where
+        S: StateProvider,
+    {
+        self.estimate_gas_with_deadline(cfg, block, request, state, state_override, None)
+    }
+
+    /// Same as [`Call::estimate_gas_with`] but aborts the estimation once the deadline of the
+    /// [`CallTimeoutInspector`] passed.
+    fn estimate_gas_with_deadline<S>(
         &self,
         mut cfg: CfgEnvWithHandlerCfg,
         block: BlockEnv,
         mut request: TransactionRequest,
         state: S,
         state_override: Option<StateOverride>,
+        mut deadline: Option<CallTimeoutInspector>,
     ) -> Result<U256, Self::Error>
     where
         S: StateProvider,
@@ -584,7 +684,9 @@ pub trait Call: LoadState + SpawnBlocking {
                         // with the minimum gas limit to make sure.
                         let mut env = env.clone();
                         env.tx.gas_limit = MIN_TRANSACTION_GAS;
-                        if let Ok((res, _)) = self.transact(&mut db, env) {
+                        if let Ok((res, _)) =
+                            self.transact_with_deadline(&mut db, env, deadline.as_mut())
+                        {
                             if res.result.is_success() {
                                 return Ok(U256::from(MIN_TRANSACTION_GAS))
                             }
@@ -613,20 +715,21 @@ pub trait Call: LoadState + SpawnBlocking {
         trace!(target: "rpc::eth::estimate", ?env, "Starting gas estimation");
 
         // Execute the transaction with the highest possible gas limit.
-        let (mut res, mut env) = match self.transact(&mut db, env.clone()) {
-            // Handle the exceptional case where the transaction initialization uses too much gas.
-            // If the gas price or gas limit was specified in the request, retry the transaction
-            // with the block's gas limit to determine if the failure was due to
-            // insufficient gas.
-            Err(err)
-                if err.is_gas_too_high() &&
-                    (tx_request_gas_limit.is_some() || tx_request_gas_price.is_some()) =>
-            {
-                return Err(self.map_out_of_gas_err(block_env_gas_limit, env, &mut db))
-            }
-            // Propagate other results (successful or other errors).
-            ethres => ethres?,
-        };
+        let (mut res, mut env) =
+            match self.transact_with_deadline(&mut db, env.clone(), deadline.as_mut()) {
+                // Handle the exceptional case where the transaction initialization uses too much
+                // gas. If the gas price or gas limit was specified in the request,
+                // retry the transaction with the block's gas limit to determine if
+                // the failure was due to insufficient gas.
+                Err(err)
+                    if err.is_gas_too_high() &&
+                        (tx_request_gas_limit.is_some() || tx_request_gas_price.is_some()) =>
+                {
+                    return Err(self.map_out_of_gas_err(block_env_gas_limit, env, &mut db))
+                }
+                // Propagate other results (successful or other errors).
+                ethres => ethres?,
+            };
 
         let gas_refund = match res.result {
             ExecutionResult::Success { gas_refunded, .. } => gas_refunded,
@@ -672,7 +775,7 @@ pub trait Call: LoadState + SpawnBlocking {
             env.tx.gas_limit = optimistic_gas_limit;
             // Re-execute the transaction with the new gas limit and update the result and
             // environment.
-            (res, env) = self.transact(&mut db, env)?;
+            (res, env) = self.transact_with_deadline(&mut db, env, deadline.as_mut())?;
             // Update the gas used based on the new result.
             gas_used = res.result.gas_used();
             // Update the gas limit estimates (highest and lowest) based on the execution result.
@@ -707,7 +810,7 @@ pub trait Call: LoadState + SpawnBlocking {
             env.tx.gas_limit = mid_gas_limit;
 
             // Execute transaction and handle potential gas errors, adjusting limits accordingly.
-            match self.transact(&mut db, env.clone()) {
+            match self.transact_with_deadline(&mut db, env.clone(), deadline.as_mut()) {
                 Err(err) if err.is_gas_too_high() => {
                     // Increase the lowest gas limit if gas is too high
                     lowest_gas_limit = mid_gas_limit;
@@ -1,7 +1,10 @@
 //! Loads a pending block from database. Helper trait for `eth_` block, transaction, call and trace
 //! RPC methods.
 
-use std::time::{Duration, Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use futures::Future;
 use reth_chainspec::{ChainSpec, EthereumHardforks};
@@ -67,6 +70,14 @@ pub trait LoadPendingBlock: EthApiTypes {
     /// Data access in default (L1) trait method implementations.
     fn evm_config(&self) -> &impl ConfigureEvm;
 
+    /// Returns `true` if `eth_getTransactionReceipt` may return receipts for transactions that
+    /// are only included in the locally built pending block.
+    ///
+    /// Disabled by default.
+    fn pending_receipts_enabled(&self) -> bool {
+        false
+    }
+
     /// Configures the [`CfgEnvWithHandlerCfg`] and [`BlockEnv`] for the pending block
     ///
     /// If no pending block is available, this will derive it from the `latest` block
@@ -126,13 +137,36 @@ pub trait LoadPendingBlock: EthApiTypes {
     fn local_pending_block(
         &self,
     ) -> impl Future<Output = Result<Option<SealedBlockWithSenders>, Self::Error>> + Send
+    where
+        Self: SpawnBlocking,
+    {
+        async move { Ok(self.local_pending_block_with_receipts().await?.map(|(block, _)| block)) }
+    }
+
+    /// Returns the locally built pending block together with the receipts of its transactions.
+    ///
+    /// Receipts are in block order, i.e. the receipt at index `i` belongs to the `i`-th
+    /// transaction of the block.
+    fn local_pending_block_with_receipts(
+        &self,
+    ) -> impl Future<Output = Result<Option<(SealedBlockWithSenders, Arc<Vec<Receipt>>)>, Self::Error>>
+           + Send
     where
         Self: SpawnBlocking,
     {
         async move {
             let pending = self.pending_block_env_and_cfg()?;
             if pending.origin.is_actual_pending() {
-                return Ok(pending.origin.into_actual_pending())
+                let receipts = self
+                    .provider()
+                    .pending_block_and_receipts()
+                    .map_err(Self::Error::from_eth_err)?
+                    .map(|(_, receipts)| receipts)
+                    .unwrap_or_default();
+                return Ok(pending
+                    .origin
+                    .into_actual_pending()
+                    .map(|block| (block, Arc::new(receipts))))
             }
 
             let mut lock = self.pending_block().lock().await;
@@ -146,12 +180,12 @@ pub trait LoadPendingBlock: EthApiTypes {
                     pending.origin.header().hash() == pending_block.block.parent_hash &&
                     now <= pending_block.expires_at
                 {
-                    return Ok(Some(pending_block.block.clone()))
+                    return Ok(Some((pending_block.block.clone(), pending_block.receipts.clone())))
                 }
             }
 
             // no pending block from the CL yet, so we need to build it ourselves via txpool
-            let pending_block = match self
+            let (pending_block, receipts) = match self
                 .spawn_blocking_io(move |this| {
                     // we rebuild the block
                     this.build_block(pending)
@@ -165,10 +199,15 @@ pub trait LoadPendingBlock: EthApiTypes {
                 }
             };
 
+            let receipts = Arc::new(receipts);
             let now = Instant::now();
-            *lock = Some(PendingBlock::new(pending_block.clone(), now + Duration::from_secs(1)));
+            *lock = Some(PendingBlock::new(
+                pending_block.clone(),
+                receipts.clone(),
+                now + Duration::from_secs(1),
+            ));
 
-            Ok(Some(pending_block))
+            Ok(Some((pending_block, receipts)))
         }
     }
 
@@ -203,11 +242,16 @@ pub trait LoadPendingBlock: EthApiTypes {
 
     /// Builds a pending block using the configured provider and pool.
     ///
+    /// Returns the sealed block and the receipts of its transactions, in block order.
+    ///
     /// If the origin is the actual pending block, the block is built with withdrawals.
     ///
     /// After Cancun, if the origin is the actual pending block, the block includes the EIP-4788 pre
     /// block contract call using the parent beacon block root received from the CL.
-    fn build_block(&self, env: PendingBlockEnv) -> Result<SealedBlockWithSenders, Self::Error>
+    fn build_block(
+        &self,
+        env: PendingBlockEnv,
+    ) -> Result<(SealedBlockWithSenders, Vec<Receipt>), Self::Error>
     where
         EthApiError: From<ProviderError>,
     {
@@ -382,7 +426,7 @@ pub trait LoadPendingBlock: EthApiTypes {
 
         let execution_outcome = ExecutionOutcome::new(
             db.take_bundle(),
-            vec![receipts].into(),
+            vec![receipts.clone()].into(),
             block_number,
             Vec::new(),
         );
@@ -440,6 +484,7 @@ pub trait LoadPendingBlock: EthApiTypes {
 
         // seal the block
         let block = Block { header, body: executed_txs, ommers: vec![], withdrawals, requests };
-        Ok(SealedBlockWithSenders { block: block.seal_slow(), senders })
+        let receipts = receipts.into_iter().flatten().collect();
+        Ok((SealedBlockWithSenders { block: block.seal_slow(), senders }, receipts))
     }
 }
@@ -38,10 +38,14 @@ pub trait EthSigner: Send + Sync + DynClone {
 
 dyn_clone::clone_trait_object!(EthSigner);
 
-/// Adds 20 random dev signers for access via the API. Used in dev mode.
+/// Adds dev signers for access via the API. Used in dev mode.
 #[auto_impl::auto_impl(&)]
 pub trait AddDevSigners {
     /// Generates 20 random developer accounts.
     /// Used in DEV mode.
     fn with_dev_accounts(&self);
+
+    /// Replaces the configured signers with the given developer signers.
+    /// Used in DEV mode.
+    fn with_dev_signers(&self, signers: Vec<Box<dyn EthSigner>>);
 }
@@ -8,7 +8,8 @@ use reth_errors::{RethError, RethResult};
 use reth_network_api::NetworkInfo;
 use reth_primitives::{Address, U256, U64};
 use reth_provider::{BlockNumReader, ChainSpecProvider, StageCheckpointReader};
-use reth_rpc_types::{Stage, SyncInfo, SyncStatus};
+use reth_rpc_server_types::sync::{StageProgress, SyncInfo, SyncStatus};
+use reth_stages_types::{StageSyncProgress, SyncProgress};
 
 use super::EthSigner;
 
@@ -31,6 +32,9 @@ pub trait EthApiSpec: Send + Sync {
     /// Returns a handle to the signers owned by provider.
     fn signers(&self) -> &parking_lot::RwLock<Vec<Box<dyn EthSigner>>>;
 
+    /// Returns the progress of the sync stages, recorded from the pipeline events.
+    fn sync_progress(&self) -> &SyncProgress;
+
     /// Returns the current ethereum protocol version.
     fn protocol_version(&self) -> impl Future<Output = RethResult<U64>> + Send {
         async move {
@@ -60,27 +64,50 @@ pub trait EthApiSpec: Send + Sync {
     }
 
     /// Returns the [`SyncStatus`] of the network
+    ///
+    /// Includes the progress of the sync stages. If no progress was recorded, the stage
+    /// checkpoints are read from the database instead.
     fn sync_status(&self) -> RethResult<SyncStatus> {
         let status = if self.is_syncing() {
-            let current_block = U256::from(
-                self.provider().chain_info().map(|info| info.best_number).unwrap_or_default(),
-            );
-
-            let stages = self
-                .provider()
-                .get_all_checkpoints()
+            let current_block =
+                self.provider().chain_info().map(|info| info.best_number).unwrap_or_default();
+
+            let progress = self.sync_progress().stages();
+            let stages: Vec<_> = if progress.is_empty() {
+                self.provider()
+                    .get_all_checkpoints()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(name, checkpoint)| {
+                        let entities = checkpoint.entities();
+                        StageProgress {
+                            name,
+                            block: checkpoint.block_number,
+                            target: None,
+                            processed: entities.map(|entities| entities.processed),
+                            total: entities.map(|entities| entities.total),
+                            eta: None,
+                        }
+                    })
+                    .collect()
+            } else {
+                progress.into_iter().map(stage_progress).collect()
+            };
+            let highest_block = stages
+                .iter()
+                .filter_map(|stage| stage.target)
+                .max()
                 .unwrap_or_default()
-                .into_iter()
-                .map(|(name, checkpoint)| Stage { name, block: checkpoint.block_number })
-                .collect();
+                .max(current_block);
 
             SyncStatus::Info(Box::new(SyncInfo {
-                starting_block: self.starting_block(),
-                current_block,
-                highest_block: current_block,
-                warp_chunks_amount: None,
-                warp_chunks_processed: None,
-                stages: Some(stages),
+                info: reth_rpc_types::SyncInfo {
+                    starting_block: self.starting_block(),
+                    current_block: U256::from(current_block),
+                    highest_block: U256::from(highest_block),
+                    ..Default::default()
+                },
+                stages,
             }))
         } else {
             SyncStatus::None
@@ -93,3 +120,16 @@ pub trait EthApiSpec: Send + Sync {
         self.provider().chain_spec()
     }
 }
+
+/// Converts the recorded progress of a stage into its RPC representation.
+pub fn stage_progress(progress: StageSyncProgress) -> StageProgress {
+    let entities = progress.checkpoint.entities();
+    StageProgress {
+        name: progress.stage_id.to_string(),
+        block: progress.checkpoint.block_number,
+        target: progress.target,
+        processed: entities.map(|entities| entities.processed),
+        total: entities.map(|entities| entities.total),
+        eta: progress.eta.map(|eta| eta.as_secs()),
+    }
+}
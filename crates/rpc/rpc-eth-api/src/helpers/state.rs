@@ -5,17 +5,23 @@ use futures::Future;
 use reth_chainspec::ChainSpec;
 use reth_errors::RethError;
 use reth_evm::ConfigureEvmEnv;
-use reth_primitives::{Address, BlockId, Bytes, Header, B256, KECCAK_EMPTY, U256};
+use reth_primitives::{keccak256, Address, BlockId, Bytes, Header, B256, KECCAK_EMPTY, U256};
 use reth_provider::{
-    BlockIdReader, ChainSpecProvider, StateProvider, StateProviderBox, StateProviderFactory,
-    StateRootProvider,
+    BlockIdReader, ChainSpecProvider, ProviderError, StateProofProvider, StateProvider,
+    StateProviderBox, StateProviderFactory, StateRootProvider,
 };
 use reth_rpc_eth_types::{EthApiError, EthStateCache, PendingBlockEnv, RpcInvalidTransactionError};
-use reth_rpc_types::{serde_helpers::JsonStorageKey, Account, EIP1186AccountProofResponse};
+use reth_rpc_server_types::proof::{
+    AccountProofResponse, MultiProofAccount, MultiProofResponse, MultiProofStorageSlot,
+    MultiProofTarget,
+};
+use reth_rpc_types::{serde_helpers::JsonStorageKey, Account};
 use reth_rpc_types_compat::proof::from_primitive_account_proof;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use reth_trie::HashedPostState;
 use revm::db::BundleState;
 use revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg, SpecId};
+use std::collections::{HashMap, HashSet};
 
 use crate::{EthApiTypes, FromEthApiError};
 
@@ -88,15 +94,15 @@ pub trait EthState: LoadState + SpawnBlocking {
     }
 
     /// Returns values stored of given account, with Merkle-proof, at given blocknumber.
+    ///
+    /// If the state is stored in a verkle trie, the verkle trie leaves of the account are returned
+    /// with a verkle proof instead, which is only available for the latest block.
     fn get_proof(
         &self,
         address: Address,
         keys: Vec<JsonStorageKey>,
         block_id: Option<BlockId>,
-    ) -> Result<
-        impl Future<Output = Result<EIP1186AccountProofResponse, Self::Error>> + Send,
-        Self::Error,
-    >
+    ) -> Result<impl Future<Output = Result<AccountProofResponse, Self::Error>> + Send, Self::Error>
     where
         Self: EthApiSpec,
     {
@@ -119,12 +125,111 @@ pub trait EthState: LoadState + SpawnBlocking {
                 .await
                 .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
             self.spawn_blocking_io(move |this| {
-                let state = this.state_at_block_id(block_id)?;
                 let storage_keys = keys.iter().map(|key| key.0).collect::<Vec<_>>();
+                let latest = this.latest_state()?;
+                if let Some(proof) = latest
+                    .verkle_proof(address, &storage_keys)
+                    .map_err(Self::Error::from_eth_err)?
+                {
+                    if block_number != chain_info.best_number {
+                        return Err(EthApiError::InvalidParams(
+                            "verkle proofs are only available for the latest block".to_string(),
+                        )
+                        .into())
+                    }
+                    return Ok(AccountProofResponse::Verkle(proof.into()))
+                }
+
+                let state = this.state_at_block_id(block_id)?;
                 let proof = state
                     .proof(&BundleState::default(), address, &storage_keys)
                     .map_err(Self::Error::from_eth_err)?;
-                Ok(from_primitive_account_proof(proof))
+                Ok(AccountProofResponse::Merkle(from_primitive_account_proof(proof)))
+            })
+            .await
+        })
+    }
+
+    /// Returns the values of the given accounts and storage slots at the given block, with a
+    /// single Merkle-proof that includes every trie node shared by the targets only once.
+    fn get_multi_proof(
+        &self,
+        targets: Vec<MultiProofTarget>,
+        block_id: Option<BlockId>,
+    ) -> Result<impl Future<Output = Result<MultiProofResponse, Self::Error>> + Send, Self::Error>
+    where
+        Self: EthApiSpec,
+    {
+        let chain_info = self.chain_info().map_err(Self::Error::from_eth_err)?;
+        let block_id = block_id.unwrap_or_default();
+
+        // Check whether the distance to the block exceeds the maximum configured window.
+        let block_number = LoadState::provider(self)
+            .block_number_for_id(block_id)
+            .map_err(Self::Error::from_eth_err)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let max_window = self.max_proof_window();
+        if chain_info.best_number.saturating_sub(block_number) > max_window {
+            return Err(EthApiError::ExceedsMaxProofWindow.into())
+        }
+
+        Ok(async move {
+            let _permit = self
+                .acquire_owned()
+                .await
+                .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+            self.spawn_blocking_io(move |this| {
+                let state = this.state_at_block_id(block_id)?;
+
+                let mut proof_targets = HashMap::<B256, Vec<B256>>::with_capacity(targets.len());
+                for target in &targets {
+                    proof_targets
+                        .entry(keccak256(target.address))
+                        .or_default()
+                        .extend(target.storage_keys.iter().map(|key| keccak256(key.0)));
+                }
+                let multiproof = state
+                    .multiproof(HashedPostState::default(), proof_targets)
+                    .map_err(Self::Error::from_eth_err)?;
+
+                let mut accounts = Vec::with_capacity(targets.len());
+                for target in targets {
+                    let storage_keys =
+                        target.storage_keys.iter().map(|key| key.0).collect::<Vec<_>>();
+                    let proof = multiproof
+                        .account_proof(target.address, &storage_keys)
+                        .map_err(|err| Self::Error::from_eth_err(ProviderError::from(err)))?;
+                    let info = proof.info.unwrap_or_default();
+                    accounts.push(MultiProofAccount {
+                        address: target.address,
+                        balance: info.balance,
+                        nonce: info.nonce,
+                        code_hash: info.get_bytecode_hash(),
+                        storage_hash: proof.storage_root,
+                        storage: target
+                            .storage_keys
+                            .into_iter()
+                            .zip(proof.storage_proofs)
+                            .map(|(key, proof)| MultiProofStorageSlot { key, value: proof.value })
+                            .collect(),
+                    });
+                }
+
+                // Deduplicate the nodes of all proofs, starting with the account trie.
+                let mut seen = HashSet::new();
+                let nodes = multiproof
+                    .account_subtree
+                    .into_values()
+                    .chain(
+                        multiproof
+                            .storages
+                            .into_values()
+                            .flat_map(|storage| storage.subtree.into_values()),
+                    )
+                    .filter(|node| seen.insert(node.clone()))
+                    .collect();
+
+                Ok(MultiProofResponse { accounts, nodes })
             })
             .await
         })
@@ -11,15 +11,15 @@ use reth_primitives::{
 };
 use reth_provider::{BlockReaderIdExt, ReceiptProvider, TransactionsProvider};
 use reth_rpc_eth_types::{
-    utils::recover_raw_transaction, EthApiError, EthResult, EthStateCache, SignError,
-    TransactionSource,
+    utils::recover_raw_transaction, EthApiError, EthResult, EthStateCache, ReceiptBuilder,
+    SignError, TransactionSource,
 };
 use reth_rpc_types::{
     transaction::{
         EIP1559TransactionRequest, EIP2930TransactionRequest, EIP4844TransactionRequest,
         LegacyTransactionRequest,
     },
-    AnyTransactionReceipt, TransactionRequest, TypedTransactionRequest,
+    AnyTransactionReceipt, OtherFields, TransactionRequest, TypedTransactionRequest,
 };
 use reth_rpc_types_compat::transaction::from_recovered_with_block_context;
 use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
@@ -138,24 +138,69 @@ pub trait EthTransactions: LoadTransaction {
     /// Returns the transaction receipt for the given hash.
     ///
     /// Returns None if the transaction does not exist or is pending
-    /// Note: The tx receipt is not available for pending transactions.
+    /// Note: The tx receipt is not available for pending transactions, unless
+    /// [`LoadPendingBlock::pending_receipts_enabled`] is set, in which case the receipt is
+    /// computed from the locally built pending block.
     fn transaction_receipt(
         &self,
         hash: B256,
     ) -> impl Future<Output = Result<Option<AnyTransactionReceipt>, Self::Error>> + Send
     where
-        Self: LoadReceipt + 'static,
+        Self: LoadReceipt + LoadPendingBlock + 'static,
     {
         async move {
             match self.load_transaction_and_receipt(hash).await? {
                 Some((tx, meta, receipt)) => {
                     self.build_transaction_receipt(tx, meta, receipt).await.map(Some)
                 }
+                None if self.pending_receipts_enabled() => {
+                    self.pending_transaction_receipt(hash).await
+                }
                 None => Ok(None),
             }
         }
     }
 
+    /// Returns the receipt of a transaction that is included in the locally built pending block.
+    ///
+    /// The receipt is tagged with `"pending": true`. Returns `None` if the transaction is not part
+    /// of the pending block.
+    fn pending_transaction_receipt(
+        &self,
+        hash: B256,
+    ) -> impl Future<Output = Result<Option<AnyTransactionReceipt>, Self::Error>> + Send
+    where
+        Self: LoadPendingBlock + 'static,
+    {
+        async move {
+            let Some((block, receipts)) = self.local_pending_block_with_receipts().await? else {
+                return Ok(None)
+            };
+            let Some((index, tx)) = block.body.iter().enumerate().find(|(_, tx)| tx.hash() == hash)
+            else {
+                return Ok(None)
+            };
+            let Some(receipt) = receipts.get(index) else { return Ok(None) };
+
+            let meta = TransactionMeta {
+                tx_hash: hash,
+                index: index as u64,
+                block_hash: block.hash(),
+                block_number: block.number,
+                base_fee: block.base_fee_per_gas,
+                excess_blob_gas: block.excess_blob_gas,
+                timestamp: block.timestamp,
+            };
+
+            let receipt = ReceiptBuilder::new(tx, meta, receipt, &receipts)
+                .map_err(Self::Error::from_eth_err)?
+                .add_other_fields(OtherFields::from_iter([("pending", true.into())]))
+                .build();
+
+            Ok(Some(receipt))
+        }
+    }
+
     /// Helper method that loads a transaction and its receipt.
     fn load_transaction_and_receipt(
         &self,
@@ -254,12 +299,13 @@ pub trait EthTransactions: LoadTransaction {
                 <Self::Pool as TransactionPool>::Transaction::from_pooled(recovered);
 
             // On optimism, transactions are forwarded directly to the sequencer to be included in
-            // blocks that it builds.
+            // blocks that it builds. If forwarding fails, the error is returned, since the
+            // transaction would otherwise only be included in the local pool.
             if let Some(client) = self.raw_tx_forwarder().as_ref() {
                 tracing::debug!( target: "rpc::eth",  "forwarding raw transaction to");
-                let _ = client.forward_raw_transaction(&tx).await.inspect_err(|err| {
+                client.forward_raw_transaction(&tx).await.inspect_err(|err| {
                     tracing::debug!(target: "rpc::eth", %err, hash=% *pool_transaction.hash(), "failed to forward raw transaction");
-                });
+                })?;
             }
 
             // submit the transaction to the pool with a `Local` origin
@@ -277,8 +323,38 @@ pub trait EthTransactions: LoadTransaction {
     /// Returns the hash of the signed transaction.
     fn send_transaction(
         &self,
-        mut request: TransactionRequest,
+        request: TransactionRequest,
     ) -> impl Future<Output = Result<B256, Self::Error>> + Send
+    where
+        Self: EthApiSpec + LoadBlock + LoadPendingBlock + LoadFee + Call,
+    {
+        async move {
+            let signed_tx = self.sign_transaction(request).await?;
+
+            let recovered =
+                signed_tx.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?;
+
+            let pool_transaction = match recovered.try_into() {
+                Ok(converted) => converted,
+                Err(_) => return Err(EthApiError::TransactionConversionError.into()),
+            };
+
+            // submit the transaction to the pool with a `Local` origin
+            let hash = LoadTransaction::pool(self)
+                .add_transaction(TransactionOrigin::Local, pool_transaction)
+                .await
+                .map_err(Self::Error::from_eth_err)?;
+
+            Ok(hash)
+        }
+    }
+
+    /// Fills the missing nonce, gas limit and fee fields of the transaction request and signs it
+    /// with a matching signer, if any.
+    fn sign_transaction(
+        &self,
+        mut request: TransactionRequest,
+    ) -> impl Future<Output = Result<TransactionSigned, Self::Error>> + Send
     where
         Self: EthApiSpec + LoadBlock + LoadPendingBlock + LoadFee + Call,
     {
@@ -301,9 +377,10 @@ pub trait EthTransactions: LoadTransaction {
 
             let chain_id = self.chain_id();
 
-            let estimated_gas =
-                self.estimate_gas_at(request.clone(), BlockId::pending(), None).await?;
-            let gas_limit = estimated_gas;
+            let gas_limit = match request.gas {
+                Some(gas) => U256::from(gas),
+                None => self.estimate_gas_at(request.clone(), BlockId::pending(), None).await?,
+            };
 
             let TransactionRequest {
                 to,
@@ -465,23 +542,7 @@ pub trait EthTransactions: LoadTransaction {
                 None => return Err(EthApiError::ConflictingFeeFieldsInRequest.into()),
             };
 
-            let signed_tx = self.sign_request(&from, transaction)?;
-
-            let recovered =
-                signed_tx.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?;
-
-            let pool_transaction = match recovered.try_into() {
-                Ok(converted) => converted,
-                Err(_) => return Err(EthApiError::TransactionConversionError.into()),
-            };
-
-            // submit the transaction to the pool with a `Local` origin
-            let hash = LoadTransaction::pool(self)
-                .add_transaction(TransactionOrigin::Local, pool_transaction)
-                .await
-                .map_err(Self::Error::from_eth_err)?;
-
-            Ok(hash)
+            self.sign_request(&from, transaction)
         }
     }
 
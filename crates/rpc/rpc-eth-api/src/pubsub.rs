@@ -2,12 +2,17 @@
 
 use alloy_json_rpc::RpcObject;
 use jsonrpsee::proc_macros::rpc;
-use reth_rpc_types::pubsub::{Params, SubscriptionKind};
+use reth_rpc_server_types::RethSubscriptionKind;
+use reth_rpc_types::pubsub::Params;
 
 /// Ethereum pub-sub rpc interface.
 #[rpc(server, namespace = "eth")]
 pub trait EthPubSubApi<T: RpcObject> {
     /// Create an ethereum subscription for the given params
+    ///
+    /// Besides the standard subscription kinds, `blobSidecars` subscribes to the sidecars of blob
+    /// transactions that are added to the transaction pool, and `stateChanges` to the state changes
+    /// of new canonical blocks.
     #[subscription(
         name = "subscribe" => "subscription",
         unsubscribe = "unsubscribe",
@@ -15,7 +20,7 @@ pub trait EthPubSubApi<T: RpcObject> {
     )]
     async fn subscribe(
         &self,
-        kind: SubscriptionKind,
+        kind: RethSubscriptionKind,
         params: Option<Params>,
     ) -> jsonrpsee::core::SubscriptionResult;
 }
@@ -1,13 +1,20 @@
 //! Configuration for `eth` namespace APIs.
 
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use crate::{
-    EthStateCacheConfig, FeeHistoryCacheConfig, GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP,
+    EthStateCacheConfig, FeeHistoryCacheConfig, GasPriceOracleConfig, HistoricalRpcFallback,
+    RpcLimits, RPC_DEFAULT_GAS_CAP,
 };
-use reth_rpc_server_types::constants::{
-    default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER,
-    DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_PROOF_PERMITS,
+use reth_rpc_server_types::{
+    constants::{
+        default_max_tracing_requests, DEFAULT_DEV_ACCOUNTS, DEFAULT_DEV_MNEMONIC,
+        DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER,
+        DEFAULT_MAX_CONCURRENT_GETLOGS_CHUNKS, DEFAULT_MAX_LOGS_PER_RESPONSE,
+        DEFAULT_MAX_STATE_SNAPSHOTS, DEFAULT_PROOF_PERMITS, DEFAULT_RPC_CALL_TIMEOUT,
+        DEFAULT_STATE_SNAPSHOT_TTL,
+    },
+    SubscriptionBackpressure,
 };
 use serde::{Deserialize, Serialize};
 
@@ -29,10 +36,17 @@ pub struct EthConfig {
     pub max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
     pub max_logs_per_response: usize,
+    /// Maximum number of chunks of a finalized block range that are scanned concurrently in
+    /// `eth_getLogs` calls.
+    pub max_concurrent_getlogs_chunks: usize,
     /// Gas limit for `eth_call` and call tracing RPC methods.
     ///
     /// Defaults to [`RPC_DEFAULT_GAS_CAP`]
     pub rpc_gas_cap: u64,
+    /// Wall-clock timeout of the EVM execution of `eth_call` and `eth_estimateGas`.
+    ///
+    /// Defaults to [`DEFAULT_RPC_CALL_TIMEOUT`], `None` disables the timeout.
+    pub rpc_call_timeout: Option<Duration>,
     ///
     /// Sets TTL for stale filters
     pub stale_filter_ttl: Duration,
@@ -40,6 +54,15 @@ pub struct EthConfig {
     pub fee_history_cache: FeeHistoryCacheConfig,
     /// The maximum number of getproof calls that can be executed concurrently.
     pub proof_permits: usize,
+    /// What to do with `eth_subscribe` messages if a client can't keep up.
+    pub subscription_backpressure: SubscriptionBackpressure,
+    /// Whether `eth_getTransactionReceipt` may return receipts computed from the locally built
+    /// pending block for transactions that are not yet mined.
+    pub pending_receipts: bool,
+    /// Time after which a state snapshot created by `reth_createStateSnapshot` is released.
+    pub state_snapshot_ttl: Duration,
+    /// Maximum number of state snapshots that can be alive at the same time.
+    pub max_state_snapshots: usize,
 }
 
 impl EthConfig {
@@ -48,8 +71,17 @@ impl EthConfig {
         EthFilterConfig::default()
             .max_blocks_per_filter(self.max_blocks_per_filter)
             .max_logs_per_response(self.max_logs_per_response)
+            .max_concurrent_getlogs_chunks(self.max_concurrent_getlogs_chunks)
             .stale_filter_ttl(self.stale_filter_ttl)
     }
+
+    /// Returns new [`RpcLimits`] with the configured request limits.
+    pub fn limits(&self) -> RpcLimits {
+        let limits =
+            RpcLimits::new(self.rpc_gas_cap, self.max_logs_per_response, self.max_tracing_requests);
+        limits.set_call_timeout(self.rpc_call_timeout);
+        limits
+    }
 }
 
 impl Default for EthConfig {
@@ -61,10 +93,16 @@ impl Default for EthConfig {
             max_tracing_requests: default_max_tracing_requests(),
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
+            max_concurrent_getlogs_chunks: DEFAULT_MAX_CONCURRENT_GETLOGS_CHUNKS,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            rpc_call_timeout: Some(DEFAULT_RPC_CALL_TIMEOUT),
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
             proof_permits: DEFAULT_PROOF_PERMITS,
+            subscription_backpressure: SubscriptionBackpressure::default(),
+            pending_receipts: false,
+            state_snapshot_ttl: DEFAULT_STATE_SNAPSHOT_TTL,
+            max_state_snapshots: DEFAULT_MAX_STATE_SNAPSHOTS,
         }
     }
 }
@@ -100,12 +138,42 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum number of concurrently scanned chunks per `eth_getLogs` request
+    pub const fn max_concurrent_getlogs_chunks(mut self, max_chunks: usize) -> Self {
+        self.max_concurrent_getlogs_chunks = max_chunks;
+        self
+    }
+
     /// Configures the maximum gas limit for `eth_call` and call tracing RPC methods
     pub const fn rpc_gas_cap(mut self, rpc_gas_cap: u64) -> Self {
         self.rpc_gas_cap = rpc_gas_cap;
         self
     }
 
+    /// Configures the wall-clock timeout of the EVM execution of `eth_call` and `eth_estimateGas`
+    pub const fn rpc_call_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.rpc_call_timeout = timeout;
+        self
+    }
+
+    /// Configures whether receipts may be served from the locally built pending block.
+    pub const fn pending_receipts(mut self, enabled: bool) -> Self {
+        self.pending_receipts = enabled;
+        self
+    }
+
+    /// Configures the time after which a state snapshot is released.
+    pub const fn state_snapshot_ttl(mut self, ttl: Duration) -> Self {
+        self.state_snapshot_ttl = ttl;
+        self
+    }
+
+    /// Configures the maximum number of state snapshots that can be alive at the same time.
+    pub const fn max_state_snapshots(mut self, max: usize) -> Self {
+        self.max_state_snapshots = max;
+        self
+    }
+
     /// Configures the maximum proof window for historical proof generation.
     pub const fn eth_proof_window(mut self, window: u64) -> Self {
         self.eth_proof_window = window;
@@ -117,6 +185,12 @@ impl EthConfig {
         self.proof_permits = permits;
         self
     }
+
+    /// Configures the backpressure policy for `eth_subscribe` subscriptions
+    pub const fn subscription_backpressure(mut self, policy: SubscriptionBackpressure) -> Self {
+        self.subscription_backpressure = policy;
+        self
+    }
 }
 
 /// Config for the filter
@@ -130,11 +204,23 @@ pub struct EthFilterConfig {
     ///
     /// If `None` then no limit is enforced.
     pub max_logs_per_response: Option<usize>,
+    /// Maximum number of chunks of a finalized block range that are scanned concurrently in
+    /// `eth_getLogs` calls.
+    ///
+    /// If `None` or `1`, ranges are scanned sequentially.
+    pub max_concurrent_getlogs_chunks: Option<usize>,
     /// How long a filter remains valid after the last poll.
     ///
     /// A filter is considered stale if it has not been polled for longer than this duration and
     /// will be removed.
     pub stale_filter_ttl: Duration,
+    /// Archive node that `eth_getLogs` requests for pruned receipts are forwarded to.
+    pub historical_fallback: Option<HistoricalRpcFallback>,
+    /// Request limits shared with other handlers.
+    ///
+    /// If set, the maximum number of logs per response is read from these limits instead of
+    /// `max_logs_per_response`, so that it can be changed while the node is running.
+    pub limits: Option<RpcLimits>,
 }
 
 impl EthFilterConfig {
@@ -151,11 +237,30 @@ impl EthFilterConfig {
         self
     }
 
+    /// Sets the maximum number of chunks of a finalized block range that are scanned
+    /// concurrently in `eth_getLogs` calls.
+    pub const fn max_concurrent_getlogs_chunks(mut self, num: usize) -> Self {
+        self.max_concurrent_getlogs_chunks = Some(num);
+        self
+    }
+
     /// Sets how long a filter remains valid after the last poll before it will be removed.
     pub const fn stale_filter_ttl(mut self, duration: Duration) -> Self {
         self.stale_filter_ttl = duration;
         self
     }
+
+    /// Sets the archive node that `eth_getLogs` requests for pruned receipts are forwarded to.
+    pub fn historical_fallback(mut self, fallback: Option<HistoricalRpcFallback>) -> Self {
+        self.historical_fallback = fallback;
+        self
+    }
+
+    /// Sets the request limits shared with other handlers.
+    pub fn limits(mut self, limits: RpcLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
 }
 
 impl Default for EthFilterConfig {
@@ -163,8 +268,33 @@ impl Default for EthFilterConfig {
         Self {
             max_blocks_per_filter: None,
             max_logs_per_response: None,
+            max_concurrent_getlogs_chunks: None,
             // 5min
             stale_filter_ttl: Duration::from_secs(5 * 60),
+            historical_fallback: None,
+            limits: None,
+        }
+    }
+}
+
+/// Accounts of the signer that is installed in dev mode and used by `eth_accounts`, `eth_sign`,
+/// `eth_signTransaction` and `eth_sendTransaction`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DevSignerConfig {
+    /// The mnemonic the accounts are derived from.
+    pub mnemonic: String,
+    /// The number of accounts derived from the mnemonic.
+    pub accounts: u32,
+    /// Files with additional hex encoded private keys, one per line.
+    pub key_files: Vec<PathBuf>,
+}
+
+impl Default for DevSignerConfig {
+    fn default() -> Self {
+        Self {
+            mnemonic: DEFAULT_DEV_MNEMONIC.to_string(),
+            accounts: DEFAULT_DEV_ACCOUNTS,
+            key_files: Vec::new(),
         }
     }
 }
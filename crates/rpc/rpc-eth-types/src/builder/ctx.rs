@@ -2,12 +2,13 @@
 
 use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::ChainSpecProvider;
+use reth_stages_types::SyncProgress;
 use reth_storage_api::BlockReaderIdExt;
 use reth_tasks::TaskSpawner;
 
 use crate::{
     fee_history::fee_history_cache_new_blocks_task, EthConfig, EthStateCache, FeeHistoryCache,
-    GasPriceOracle,
+    GasPriceOracle, HistoricalRpcFallback, RpcLimits,
 };
 
 /// Context for building the `eth` namespace API.
@@ -29,6 +30,12 @@ pub struct EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events> {
     pub events: Events,
     /// RPC cache handle.
     pub cache: EthStateCache,
+    /// Request limits shared with the other RPC handlers.
+    pub limits: RpcLimits,
+    /// Progress of the sync stages, reported by `eth_syncing`.
+    pub sync_progress: SyncProgress,
+    /// Archive node that requests for pruned history are forwarded to.
+    pub historical_fallback: Option<HistoricalRpcFallback>,
 }
 
 impl<Provider, Pool, EvmConfig, Network, Tasks, Events>
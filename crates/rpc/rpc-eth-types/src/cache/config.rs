@@ -3,10 +3,14 @@
 use serde::{Deserialize, Serialize};
 
 use reth_rpc_server_types::constants::cache::{
-    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_ENV_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_BLOCK_CACHE_SIZE_BYTES_MB, DEFAULT_CONCURRENT_DB_REQUESTS,
+    DEFAULT_ENV_CACHE_MAX_LEN, DEFAULT_ENV_CACHE_SIZE_BYTES_MB, DEFAULT_MIN_AVAILABLE_MEMORY_MB,
+    DEFAULT_RECEIPT_CACHE_MAX_LEN, DEFAULT_RECEIPT_CACHE_SIZE_BYTES_MB,
+    DEFAULT_TRACE_CACHE_MAX_LEN,
 };
 
+use super::limiter::CacheLimiter;
+
 /// Settings for the [`EthStateCache`](super::EthStateCache).
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -23,10 +27,50 @@ pub struct EthStateCacheConfig {
     ///
     /// Default is 1000.
     pub max_envs: u32,
+    /// Max number of blocks with cached traces of `trace_block` and `debug_traceBlock*`, or `0`
+    /// to disable the trace cache.
+    ///
+    /// Default is 256.
+    #[serde(default = "default_max_traces")]
+    pub max_traces: u32,
     /// Max number of concurrent database requests.
     ///
     /// Default is 512.
     pub max_concurrent_db_requests: usize,
+    /// Memory budget of the caches.
+    ///
+    /// If set, the caches are sized by the estimated memory usage of the cached data instead of
+    /// by number of entries, and the entry limits above are ignored.
+    ///
+    /// Default is `None`.
+    #[serde(default)]
+    pub memory_budget: Option<EthStateCacheMemoryBudget>,
+}
+
+impl EthStateCacheConfig {
+    /// Returns the limiter for the block cache.
+    pub const fn blocks_limiter(&self) -> CacheLimiter {
+        match self.memory_budget {
+            Some(budget) => CacheLimiter::by_memory(budget.max_blocks_bytes),
+            None => CacheLimiter::by_length(self.max_blocks),
+        }
+    }
+
+    /// Returns the limiter for the receipts cache.
+    pub const fn receipts_limiter(&self) -> CacheLimiter {
+        match self.memory_budget {
+            Some(budget) => CacheLimiter::by_memory(budget.max_receipts_bytes),
+            None => CacheLimiter::by_length(self.max_receipts),
+        }
+    }
+
+    /// Returns the limiter for the env cache.
+    pub const fn envs_limiter(&self) -> CacheLimiter {
+        match self.memory_budget {
+            Some(budget) => CacheLimiter::by_memory(budget.max_envs_bytes),
+            None => CacheLimiter::by_length(self.max_envs),
+        }
+    }
 }
 
 impl Default for EthStateCacheConfig {
@@ -35,7 +79,58 @@ impl Default for EthStateCacheConfig {
             max_blocks: DEFAULT_BLOCK_CACHE_MAX_LEN,
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_envs: DEFAULT_ENV_CACHE_MAX_LEN,
+            max_traces: DEFAULT_TRACE_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
+            memory_budget: None,
         }
     }
 }
+
+const fn default_max_traces() -> u32 {
+    DEFAULT_TRACE_CACHE_MAX_LEN
+}
+
+/// Memory budget for the [`EthStateCache`](super::EthStateCache) in adaptive mode.
+///
+/// Each cache evicts its least recently used entries once the estimated memory usage of the cached
+/// data exceeds its budget, so the number of cached entries adapts to their size.
+///
+/// While less than [`min_available_memory_bytes`](Self::min_available_memory_bytes) of system
+/// memory is available, the budgets are halved step by step and the caches evict their least
+/// recently used entries until they fit again.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthStateCacheMemoryBudget {
+    /// Max number of bytes for cached blocks.
+    ///
+    /// Default is 500MB.
+    pub max_blocks_bytes: usize,
+    /// Max number of bytes for cached receipts.
+    ///
+    /// Default is 500MB.
+    pub max_receipts_bytes: usize,
+    /// Max number of bytes for cached env data.
+    ///
+    /// Default is 1MB.
+    pub max_envs_bytes: usize,
+    /// Number of bytes of system memory that should stay available.
+    ///
+    /// Default is 1GB, disabled if set to 0.
+    #[serde(default = "default_min_available_memory_bytes")]
+    pub min_available_memory_bytes: usize,
+}
+
+impl Default for EthStateCacheMemoryBudget {
+    fn default() -> Self {
+        Self {
+            max_blocks_bytes: DEFAULT_BLOCK_CACHE_SIZE_BYTES_MB * 1024 * 1024,
+            max_receipts_bytes: DEFAULT_RECEIPT_CACHE_SIZE_BYTES_MB * 1024 * 1024,
+            max_envs_bytes: DEFAULT_ENV_CACHE_SIZE_BYTES_MB * 1024 * 1024,
+            min_available_memory_bytes: default_min_available_memory_bytes(),
+        }
+    }
+}
+
+const fn default_min_available_memory_bytes() -> usize {
+    DEFAULT_MIN_AVAILABLE_MEMORY_MB * 1024 * 1024
+}
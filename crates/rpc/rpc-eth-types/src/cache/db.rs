@@ -69,6 +69,14 @@ impl<'a> reth_storage_api::StateProofProvider for StateProviderTraitObjWrapper<'
         self.0.hashed_proof(hashed_state, address, slots)
     }
 
+    fn multiproof(
+        &self,
+        hashed_state: reth_trie::HashedPostState,
+        targets: std::collections::HashMap<B256, Vec<B256>>,
+    ) -> reth_errors::ProviderResult<reth_trie::MultiProof> {
+        self.0.multiproof(hashed_state, targets)
+    }
+
     fn witness(
         &self,
         overlay: reth_trie::HashedPostState,
@@ -76,6 +84,14 @@ impl<'a> reth_storage_api::StateProofProvider for StateProviderTraitObjWrapper<'
     ) -> reth_errors::ProviderResult<std::collections::HashMap<B256, reth_primitives::Bytes>> {
         self.0.witness(overlay, target)
     }
+
+    fn verkle_proof(
+        &self,
+        address: Address,
+        slots: &[B256],
+    ) -> reth_errors::ProviderResult<Option<reth_trie::VerkleAccountProof>> {
+        self.0.verkle_proof(address, slots)
+    }
 }
 
 impl<'a> reth_storage_api::AccountReader for StateProviderTraitObjWrapper<'a> {
@@ -0,0 +1,234 @@
+//! Limiters for the RPC cache.
+
+use std::sync::Arc;
+
+use reth_primitives::{Address, BlockWithSenders, Log, Receipt};
+use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
+use schnellru::Limiter;
+
+use super::pressure::MAX_PRESSURE_LEVEL;
+
+/// Heuristic for the in-memory size of a cached value.
+pub trait CacheSize {
+    /// Returns the estimated number of bytes the value occupies in memory, including heap
+    /// allocations.
+    fn cache_size(&self) -> usize;
+}
+
+impl CacheSize for BlockWithSenders {
+    fn cache_size(&self) -> usize {
+        self.block.size() + self.senders.capacity() * core::mem::size_of::<Address>()
+    }
+}
+
+impl CacheSize for Arc<Vec<Receipt>> {
+    fn cache_size(&self) -> usize {
+        self.iter()
+            .map(|receipt| {
+                core::mem::size_of::<Receipt>() +
+                    receipt
+                        .logs
+                        .iter()
+                        .map(|log| {
+                            core::mem::size_of::<Log>() +
+                                core::mem::size_of_val(log.topics()) +
+                                log.data.data.len()
+                        })
+                        .sum::<usize>()
+            })
+            .sum::<usize>() +
+            core::mem::size_of::<Vec<Receipt>>()
+    }
+}
+
+impl CacheSize for (CfgEnvWithHandlerCfg, BlockEnv) {
+    fn cache_size(&self) -> usize {
+        core::mem::size_of::<Self>()
+    }
+}
+
+/// The bound applied by a [`CacheLimiter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheLimit {
+    /// Bounds the cache by the number of entries.
+    Length(u32),
+    /// Bounds the cache by the estimated memory usage of the cached values, in bytes.
+    ///
+    /// The number of entries adapts to the size of the cached values: large values evict more
+    /// of the least recently used entries.
+    Memory(usize),
+}
+
+/// A limiter for the [`MultiConsumerLruCache`](super::MultiConsumerLruCache) that bounds the cache
+/// either by number of entries or by the estimated memory usage of the cached values.
+#[derive(Debug, Clone, Copy)]
+pub struct CacheLimiter {
+    /// The configured bound.
+    limit: CacheLimit,
+    /// Estimated number of bytes used by the cached values.
+    used_bytes: usize,
+    /// The memory budget is halved for every level of memory pressure.
+    pressure_level: u32,
+}
+
+impl CacheLimiter {
+    /// Creates a new limiter that bounds the cache by the given number of entries.
+    pub const fn by_length(max_len: u32) -> Self {
+        Self { limit: CacheLimit::Length(max_len), used_bytes: 0, pressure_level: 0 }
+    }
+
+    /// Creates a new limiter that bounds the cache by the given memory budget in bytes.
+    pub const fn by_memory(max_bytes: usize) -> Self {
+        Self { limit: CacheLimit::Memory(max_bytes), used_bytes: 0, pressure_level: 0 }
+    }
+
+    /// Sets the memory pressure level, which halves the memory budget for every level, up to
+    /// [`MAX_PRESSURE_LEVEL`]. This has no effect on limiters that bound the number of entries.
+    pub fn set_pressure_level(&mut self, level: u32) {
+        self.pressure_level = level.min(MAX_PRESSURE_LEVEL);
+    }
+
+    /// Returns the memory budget in bytes after applying the memory pressure level, if the cache
+    /// is bounded by memory.
+    pub const fn memory_budget(&self) -> Option<usize> {
+        match self.limit {
+            CacheLimit::Length(_) => None,
+            CacheLimit::Memory(max_bytes) => Some(max_bytes >> self.pressure_level),
+        }
+    }
+
+    /// Returns the configured bound.
+    pub const fn limit(&self) -> CacheLimit {
+        self.limit
+    }
+
+    /// Returns the estimated number of bytes used by the cached values.
+    pub const fn used_bytes(&self) -> usize {
+        self.used_bytes
+    }
+
+    /// Returns `true` if a value of the given size can be cached at all.
+    const fn fits(&self, size: usize) -> bool {
+        match self.limit {
+            CacheLimit::Length(max_len) => max_len > 0,
+            CacheLimit::Memory(max_bytes) => size <= max_bytes >> self.pressure_level,
+        }
+    }
+}
+
+impl<K, V: CacheSize> Limiter<K, V> for CacheLimiter {
+    type KeyToInsert<'a> = K;
+    type LinkType = u32;
+
+    fn is_over_the_limit(&self, length: usize) -> bool {
+        match self.limit {
+            CacheLimit::Length(max_len) => length > max_len as usize,
+            CacheLimit::Memory(max_bytes) => self.used_bytes > max_bytes >> self.pressure_level,
+        }
+    }
+
+    fn on_insert(
+        &mut self,
+        _length: usize,
+        key: Self::KeyToInsert<'_>,
+        value: V,
+    ) -> Option<(K, V)> {
+        let size = value.cache_size();
+        if !self.fits(size) {
+            return None
+        }
+        self.used_bytes += size;
+        Some((key, value))
+    }
+
+    fn on_replace(
+        &mut self,
+        _length: usize,
+        _old_key: &mut K,
+        _new_key: Self::KeyToInsert<'_>,
+        old_value: &mut V,
+        new_value: &mut V,
+    ) -> bool {
+        let new_size = new_value.cache_size();
+        if !self.fits(new_size) {
+            // the old value is removed from the map and accounted for in `on_removed`
+            return false
+        }
+        self.used_bytes = self.used_bytes.saturating_sub(old_value.cache_size()) + new_size;
+        true
+    }
+
+    fn on_removed(&mut self, _key: &mut K, value: &mut V) {
+        self.used_bytes = self.used_bytes.saturating_sub(value.cache_size());
+    }
+
+    fn on_cleared(&mut self) {
+        self.used_bytes = 0;
+    }
+
+    fn on_grow(&mut self, _new_memory_usage: usize) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schnellru::LruMap;
+
+    impl CacheSize for Vec<u8> {
+        fn cache_size(&self) -> usize {
+            self.len()
+        }
+    }
+
+    #[test]
+    fn memory_limiter_evicts_oldest() {
+        let mut cache = LruMap::<u8, Vec<u8>, _>::new(CacheLimiter::by_memory(100));
+        assert!(cache.insert(1, vec![0; 40]));
+        assert!(cache.insert(2, vec![0; 40]));
+        assert_eq!(cache.limiter().used_bytes(), 80);
+
+        // exceeds the budget, evicts the oldest entry
+        assert!(cache.insert(3, vec![0; 40]));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.peek(&1).is_none());
+        assert_eq!(cache.limiter().used_bytes(), 80);
+
+        // larger than the entire budget
+        assert!(!cache.insert(4, vec![0; 101]));
+        assert_eq!(cache.len(), 2);
+
+        cache.remove(&2);
+        assert_eq!(cache.limiter().used_bytes(), 40);
+    }
+
+    #[test]
+    fn memory_limiter_shrinks_under_pressure() {
+        let mut cache = LruMap::<u8, Vec<u8>, _>::new(CacheLimiter::by_memory(100));
+        assert!(cache.insert(1, vec![0; 40]));
+        assert!(cache.insert(2, vec![0; 40]));
+
+        cache.limiter_mut().set_pressure_level(1);
+        assert_eq!(cache.limiter().memory_budget(), Some(50));
+        assert!(Limiter::<u8, Vec<u8>>::is_over_the_limit(cache.limiter(), cache.len()));
+
+        // values larger than the reduced budget are rejected
+        assert!(!cache.insert(3, vec![0; 60]));
+
+        cache.limiter_mut().set_pressure_level(10);
+        assert_eq!(cache.limiter().memory_budget(), Some(100 >> MAX_PRESSURE_LEVEL));
+        cache.limiter_mut().set_pressure_level(0);
+        assert_eq!(cache.limiter().memory_budget(), Some(100));
+    }
+
+    #[test]
+    fn length_limiter_tracks_bytes() {
+        let mut cache = LruMap::<u8, Vec<u8>, _>::new(CacheLimiter::by_length(2));
+        cache.insert(1, vec![0; 10]);
+        cache.insert(2, vec![0; 10]);
+        cache.insert(3, vec![0; 10]);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.limiter().used_bytes(), 20);
+    }
+}
@@ -8,10 +8,16 @@ use reth_metrics::{metrics::Gauge, Metrics};
 pub(crate) struct CacheMetrics {
     /// The number of entities in the cache.
     pub(crate) cached_count: Gauge,
+    /// The estimated memory usage of the cached entities in bytes.
+    pub(crate) cached_bytes: Gauge,
+    /// The memory budget of the cache in bytes, reduced while the system is low on memory.
+    pub(crate) budget_bytes: Gauge,
     /// The number of queued consumers.
     pub(crate) queued_consumers_count: Gauge,
     /// The number of cache hits.
     pub(crate) hits_total: Counter,
     /// The number of cache misses.
     pub(crate) misses_total: Counter,
+    /// The number of entities evicted from the cache to stay within its limit.
+    pub(crate) evictions_total: Counter,
 }
@@ -12,7 +12,7 @@ use reth_primitives::{
 use reth_storage_api::{BlockReader, StateProviderFactory, TransactionVariant};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use revm::primitives::{BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, SpecId};
-use schnellru::{ByLength, Limiter};
+use schnellru::Limiter;
 use std::{
     future::Future,
     pin::Pin,
@@ -26,11 +26,18 @@ use tokio::sync::{
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use super::{EthStateCacheConfig, MultiConsumerLruCache};
+use limiter::CacheLimiter;
+use pressure::MemoryPressureMonitor;
+use trace::TraceCache;
 
 pub mod config;
 pub mod db;
+pub mod limiter;
 pub mod metrics;
 pub mod multi_consumer;
+pub mod pressure;
+pub mod trace;
+pub mod warm;
 
 /// The type that can send the response to a requested [`Block`]
 type BlockTransactionsResponseSender =
@@ -45,6 +52,9 @@ type ReceiptsResponseSender = oneshot::Sender<ProviderResult<Option<Arc<Vec<Rece
 /// The type that can send the response to a requested env
 type EnvResponseSender = oneshot::Sender<ProviderResult<(CfgEnvWithHandlerCfg, BlockEnv)>>;
 
+/// The type that can send the hashes of the cached blocks.
+type CachedBlocksResponseSender = oneshot::Sender<Vec<B256>>;
+
 type BlockLruCache<L> = MultiConsumerLruCache<
     B256,
     BlockWithSenders,
@@ -65,6 +75,7 @@ type EnvLruCache<L> =
 #[derive(Debug, Clone)]
 pub struct EthStateCache {
     to_service: UnboundedSender<CacheAction>,
+    traces: TraceCache,
 }
 
 impl EthStateCache {
@@ -73,24 +84,25 @@ impl EthStateCache {
         provider: Provider,
         action_task_spawner: Tasks,
         evm_config: EvmConfig,
-        max_blocks: u32,
-        max_receipts: u32,
-        max_envs: u32,
-        max_concurrent_db_operations: usize,
+        config: EthStateCacheConfig,
     ) -> (Self, EthStateCacheService<Provider, Tasks, EvmConfig>) {
         let (to_service, rx) = unbounded_channel();
         let service = EthStateCacheService {
             provider,
-            full_block_cache: BlockLruCache::new(max_blocks, "blocks"),
-            receipts_cache: ReceiptsLruCache::new(max_receipts, "receipts"),
-            evm_env_cache: EnvLruCache::new(max_envs, "evm_env"),
+            full_block_cache: BlockLruCache::with_limiter(config.blocks_limiter(), "blocks"),
+            receipts_cache: ReceiptsLruCache::with_limiter(config.receipts_limiter(), "receipts"),
+            evm_env_cache: EnvLruCache::with_limiter(config.envs_limiter(), "evm_env"),
             action_tx: to_service.clone(),
             action_rx: UnboundedReceiverStream::new(rx),
             action_task_spawner,
-            rate_limiter: Arc::new(Semaphore::new(max_concurrent_db_operations)),
+            rate_limiter: Arc::new(Semaphore::new(config.max_concurrent_db_requests)),
             evm_config,
+            memory_pressure: config
+                .memory_budget
+                .filter(|budget| budget.min_available_memory_bytes > 0)
+                .map(|budget| MemoryPressureMonitor::new(budget.min_available_memory_bytes)),
         };
-        let cache = Self { to_service };
+        let cache = Self { to_service, traces: TraceCache::new(config.max_traces) };
         (cache, service)
     }
 
@@ -113,7 +125,8 @@ impl EthStateCache {
     /// Creates a new async LRU backed cache service task and spawns it to a new task via the given
     /// spawner.
     ///
-    /// The cache is memory limited by the given max bytes values.
+    /// The cache is limited either by the configured number of entries or, if a memory budget is
+    /// configured, by the estimated memory usage of the cached data.
     pub fn spawn_with<Provider, Tasks, EvmConfig>(
         provider: Provider,
         config: EthStateCacheConfig,
@@ -125,21 +138,16 @@ impl EthStateCache {
         Tasks: TaskSpawner + Clone + 'static,
         EvmConfig: ConfigureEvm,
     {
-        let EthStateCacheConfig { max_blocks, max_receipts, max_envs, max_concurrent_db_requests } =
-            config;
-        let (this, service) = Self::create(
-            provider,
-            executor.clone(),
-            evm_config,
-            max_blocks,
-            max_receipts,
-            max_envs,
-            max_concurrent_db_requests,
-        );
+        let (this, service) = Self::create(provider, executor.clone(), evm_config, config);
         executor.spawn_critical("eth state cache", Box::pin(service));
         this
     }
 
+    /// Returns the cache of block traces.
+    pub const fn traces(&self) -> &TraceCache {
+        &self.traces
+    }
+
     /// Requests the [`Block`] for the block hash
     ///
     /// Returns `None` if the block does not exist.
@@ -260,6 +268,31 @@ impl EthStateCache {
         let _ = self.to_service.send(CacheAction::GetEnv { block_hash, response_tx });
         rx.await.map_err(|_| ProviderError::CacheServiceUnavailable)?
     }
+
+    /// Returns the hashes of at most `max` cached blocks, from the most to the least recently
+    /// used.
+    pub async fn cached_block_hashes(&self, max: usize) -> ProviderResult<Vec<B256>> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetCachedBlocks { max, response_tx });
+        rx.await.map_err(|_| ProviderError::CacheServiceUnavailable)
+    }
+
+    /// Loads the blocks and receipts of the given block hashes into the cache.
+    ///
+    /// The hashes are expected from the most to the least recently used, as returned by
+    /// [`Self::cached_block_hashes`], and are loaded in reverse order to keep that order in the
+    /// cache. Returns the number of blocks that were found.
+    pub async fn warm(&self, block_hashes: Vec<B256>) -> ProviderResult<usize> {
+        let mut warmed = 0;
+        for block_hash in block_hashes.into_iter().rev() {
+            let block = self.get_block_with_senders(block_hash);
+            let receipts = self.get_receipts(block_hash);
+            if let (Some(_), _) = futures::try_join!(block, receipts)? {
+                warmed += 1;
+            }
+        }
+        Ok(warmed)
+    }
 }
 
 /// A task than manages caches for data required by the `eth` rpc implementation.
@@ -283,9 +316,9 @@ pub(crate) struct EthStateCacheService<
     Provider,
     Tasks,
     EvmConfig,
-    LimitBlocks = ByLength,
-    LimitReceipts = ByLength,
-    LimitEnvs = ByLength,
+    LimitBlocks = CacheLimiter,
+    LimitReceipts = CacheLimiter,
+    LimitEnvs = CacheLimiter,
 > where
     LimitBlocks: Limiter<B256, BlockWithSenders>,
     LimitReceipts: Limiter<B256, Arc<Vec<Receipt>>>,
@@ -309,6 +342,8 @@ pub(crate) struct EthStateCacheService<
     rate_limiter: Arc<Semaphore>,
     /// The type that determines how to configure the EVM.
     evm_config: EvmConfig,
+    /// Reduces the memory budgets of the caches while the system is low on memory, if enabled.
+    memory_pressure: Option<MemoryPressureMonitor>,
 }
 
 impl<Provider, Tasks, EvmConfig> EthStateCacheService<Provider, Tasks, EvmConfig>
@@ -393,8 +428,11 @@ where
 
     fn update_cached_metrics(&self) {
         self.full_block_cache.update_cached_metrics();
+        self.full_block_cache.update_memory_metrics();
         self.receipts_cache.update_cached_metrics();
+        self.receipts_cache.update_memory_metrics();
         self.evm_env_cache.update_cached_metrics();
+        self.evm_env_cache.update_memory_metrics();
     }
 }
 
@@ -409,6 +447,14 @@ where
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
 
+        if let Some(monitor) = &mut this.memory_pressure {
+            while let Poll::Ready(level) = monitor.poll_level(cx) {
+                this.full_block_cache.set_pressure_level(level);
+                this.receipts_cache.set_pressure_level(level);
+                this.evm_env_cache.set_pressure_level(level);
+            }
+        }
+
         loop {
             match ready!(this.action_rx.poll_next_unpin(cx)) {
                 None => {
@@ -532,6 +578,10 @@ where
                                 }));
                             }
                         }
+                        CacheAction::GetCachedBlocks { max, response_tx } => {
+                            let _ = response_tx
+                                .send(this.full_block_cache.keys().take(max).copied().collect());
+                        }
                         CacheAction::ReceiptsResult { block_hash, res } => {
                             this.on_new_receipts(block_hash, res);
                         }
@@ -602,6 +652,7 @@ enum CacheAction {
     GetBlockTransactions { block_hash: B256, response_tx: BlockTransactionsResponseSender },
     GetEnv { block_hash: B256, response_tx: EnvResponseSender },
     GetReceipts { block_hash: B256, response_tx: ReceiptsResponseSender },
+    GetCachedBlocks { max: usize, response_tx: CachedBlocksResponseSender },
     BlockWithSendersResult { block_hash: B256, res: ProviderResult<Option<BlockWithSenders>> },
     ReceiptsResult { block_hash: B256, res: ProviderResult<Option<Arc<Vec<Receipt>>>> },
     EnvResult { block_hash: B256, res: Box<ProviderResult<(CfgEnvWithHandlerCfg, BlockEnv)>> },
@@ -644,6 +695,10 @@ where
 {
     while let Some(event) = events.next().await {
         if let Some(reverted) = event.reverted() {
+            eth_state_cache
+                .traces
+                .remove_blocks(reverted.blocks().values().map(|block| block.hash()));
+
             let chain_change = ChainChange::new(reverted);
 
             let _ =
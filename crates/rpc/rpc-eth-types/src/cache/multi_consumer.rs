@@ -9,7 +9,7 @@ use std::{
 
 use schnellru::{ByLength, Limiter, LruMap};
 
-use super::metrics::CacheMetrics;
+use super::{limiter::CacheLimiter, metrics::CacheMetrics};
 
 /// A multi-consumer LRU cache.
 pub struct MultiConsumerLruCache<K, V, L, S>
@@ -85,12 +85,29 @@ where
     ///
     /// Can fail if the element is rejected by the limiter or if we fail to grow an empty map.
     ///
+    /// Entries evicted to make room for the new element are recorded in the cache metrics.
+    ///
     /// See [`Schnellru::insert`](LruMap::insert) for more info.
     pub fn insert<'a>(&mut self, key: L::KeyToInsert<'a>, value: V) -> bool
     where
         L::KeyToInsert<'a>: Hash + PartialEq<K>,
     {
-        self.cache.insert(key, value)
+        let is_new = self.cache.peek(&key).is_none();
+        let len = self.cache.len();
+        let inserted = self.cache.insert(key, value);
+
+        let expected_len = len + (is_new && inserted) as usize;
+        let evicted = expected_len.saturating_sub(self.cache.len());
+        if evicted > 0 {
+            self.metrics.evictions_total.increment(evicted as u64);
+        }
+
+        inserted
+    }
+
+    /// Returns the keys of the cached elements, from the most to the least recently used.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.cache.iter().map(|(key, _)| key)
     }
 
     /// Update metrics for the inner cache.
@@ -100,16 +117,60 @@ where
     }
 }
 
-impl<K, V, S> MultiConsumerLruCache<K, V, ByLength, S>
+impl<K, V, L, S> MultiConsumerLruCache<K, V, L, S>
 where
     K: Hash + Eq,
+    L: Limiter<K, V>,
 {
-    /// Creates a new empty map with a given `max_len` and metric label.
-    pub fn new(max_len: u32, cache_id: &str) -> Self {
+    /// Creates a new empty map with the given limiter and metric label.
+    pub fn with_limiter(limiter: L, cache_id: &str) -> Self {
         Self {
-            cache: LruMap::new(ByLength::new(max_len)),
+            cache: LruMap::new(limiter),
             queued: Default::default(),
             metrics: CacheMetrics::new_with_labels(&[("cache", cache_id.to_string())]),
         }
     }
 }
+
+impl<K, V, S> MultiConsumerLruCache<K, V, ByLength, S>
+where
+    K: Hash + Eq,
+{
+    /// Creates a new empty map with a given `max_len` and metric label.
+    pub fn new(max_len: u32, cache_id: &str) -> Self {
+        Self::with_limiter(ByLength::new(max_len), cache_id)
+    }
+}
+
+impl<K, V, S> MultiConsumerLruCache<K, V, CacheLimiter, S>
+where
+    K: Hash + Eq,
+    CacheLimiter: Limiter<K, V>,
+{
+    /// Update memory metrics for the inner cache.
+    #[inline]
+    pub fn update_memory_metrics(&self) {
+        self.metrics.cached_bytes.set(self.cache.limiter().used_bytes() as f64);
+        if let Some(budget) = self.cache.limiter().memory_budget() {
+            self.metrics.budget_bytes.set(budget as f64);
+        }
+    }
+
+    /// Sets the memory pressure level of the limiter and evicts the least recently used entries
+    /// until the cache fits into the reduced budget, see [`CacheLimiter::set_pressure_level`].
+    pub fn set_pressure_level(&mut self, level: u32) {
+        self.cache.limiter_mut().set_pressure_level(level);
+
+        let mut evicted = 0;
+        while Limiter::<K, V>::is_over_the_limit(self.cache.limiter(), self.cache.len()) &&
+            self.cache.pop_oldest().is_some()
+        {
+            evicted += 1;
+        }
+        if evicted > 0 {
+            self.metrics.evictions_total.increment(evicted);
+        }
+        self.metrics.cached_count.set(self.cache.len() as f64);
+        self.update_memory_metrics();
+    }
+}
@@ -0,0 +1,101 @@
+//! Shrinks the memory budget of the RPC cache while the system is low on memory.
+
+use std::{
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use sysinfo::{MemoryRefreshKind, RefreshKind, System};
+use tokio::time::{Interval, MissedTickBehavior};
+
+/// How often the available memory is checked.
+const MEMORY_PRESSURE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The maximum pressure level, at which the caches use `1 / 2^MAX_PRESSURE_LEVEL` of their budget.
+pub const MAX_PRESSURE_LEVEL: u32 = 3;
+
+/// Periodically checks the memory available on the system and derives the pressure level the
+/// memory budgets of the caches are reduced by, see
+/// [`CacheLimiter::set_pressure_level`](super::limiter::CacheLimiter::set_pressure_level).
+#[derive(Debug)]
+pub(crate) struct MemoryPressureMonitor {
+    /// Ticks when the available memory should be checked.
+    interval: Interval,
+    /// Reads the available memory.
+    system: System,
+    /// The memory that should stay available, in bytes.
+    min_available_bytes: u64,
+    /// The current pressure level.
+    level: u32,
+}
+
+impl MemoryPressureMonitor {
+    /// Creates a new monitor that raises the pressure level while less than `min_available_bytes`
+    /// are available.
+    pub(crate) fn new(min_available_bytes: usize) -> Self {
+        let mut interval = tokio::time::interval(MEMORY_PRESSURE_CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Self {
+            interval,
+            system: System::new_with_specifics(
+                RefreshKind::new().with_memory(MemoryRefreshKind::new().with_ram()),
+            ),
+            min_available_bytes: min_available_bytes as u64,
+            level: 0,
+        }
+    }
+
+    /// Checks the available memory once the check interval elapsed and returns the new pressure
+    /// level if it changed.
+    pub(crate) fn poll_level(&mut self, cx: &mut Context<'_>) -> Poll<u32> {
+        while self.interval.poll_tick(cx).is_ready() {
+            self.system.refresh_memory_specifics(MemoryRefreshKind::new().with_ram());
+            let level = next_pressure_level(
+                self.level,
+                self.system.available_memory(),
+                self.min_available_bytes,
+            );
+            if level != self.level {
+                self.level = level;
+                return Poll::Ready(level)
+            }
+        }
+        Poll::Pending
+    }
+}
+
+/// Returns the pressure level after observing `available` bytes of available memory.
+///
+/// The level is raised by one while less than `min_available` bytes are available, halving the
+/// budgets each time, and lowered by one once twice as much is available again.
+pub(crate) fn next_pressure_level(level: u32, available: u64, min_available: u64) -> u32 {
+    if available < min_available {
+        (level + 1).min(MAX_PRESSURE_LEVEL)
+    } else if available >= min_available.saturating_mul(2) {
+        level.saturating_sub(1)
+    } else {
+        level
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pressure_level_follows_available_memory() {
+        let min = 1_000;
+        assert_eq!(next_pressure_level(0, 2_000, min), 0);
+
+        // raised while below the minimum, up to the maximum level
+        let mut level = 0;
+        for _ in 0..5 {
+            level = next_pressure_level(level, 999, min);
+        }
+        assert_eq!(level, MAX_PRESSURE_LEVEL);
+
+        // kept until twice the minimum is available again
+        assert_eq!(next_pressure_level(level, 1_500, min), level);
+        assert_eq!(next_pressure_level(level, 2_000, min), level - 1);
+    }
+}
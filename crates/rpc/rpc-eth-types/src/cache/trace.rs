@@ -0,0 +1,141 @@
+//! Cache of the traces of whole blocks.
+
+use parking_lot::Mutex;
+use reth_primitives::B256;
+use reth_rpc_types::trace::{
+    geth::{GethDebugTracingOptions, TraceResult},
+    parity::LocalizedTransactionTrace,
+};
+use schnellru::{ByLength, LruMap};
+use std::{collections::HashMap, sync::Arc};
+
+/// The cached traces of a block.
+#[derive(Debug, Default)]
+struct BlockTraces {
+    /// The parity style traces of `trace_block`, including the reward traces.
+    parity: Option<Arc<Vec<LocalizedTransactionTrace>>>,
+    /// The geth style traces of `debug_traceBlock*`, keyed by the serialized tracing options.
+    geth: HashMap<String, Arc<Vec<TraceResult>>>,
+}
+
+/// A bounded cache of block traces keyed by block hash, so that repeated requests for the traces
+/// of the same block don't re-execute it.
+///
+/// The traces of a block only depend on the block and its parent state, so they never change for a
+/// block hash. Blocks are removed when they are reorged out of the canonical chain, and the least
+/// recently used blocks are evicted once the configured number of blocks is cached.
+#[derive(Debug, Clone)]
+pub struct TraceCache {
+    inner: Option<Arc<Mutex<LruMap<B256, BlockTraces>>>>,
+}
+
+impl TraceCache {
+    /// Creates a cache of the traces of at most `max_blocks` blocks, disabled if `0`.
+    pub fn new(max_blocks: u32) -> Self {
+        Self {
+            inner: (max_blocks > 0)
+                .then(|| Arc::new(Mutex::new(LruMap::new(ByLength::new(max_blocks))))),
+        }
+    }
+
+    /// Returns the cached parity traces of the block.
+    pub fn parity_traces(&self, block_hash: B256) -> Option<Arc<Vec<LocalizedTransactionTrace>>> {
+        self.inner.as_ref()?.lock().get(&block_hash)?.parity.clone()
+    }
+
+    /// Caches the parity traces of the block.
+    pub fn insert_parity_traces(
+        &self,
+        block_hash: B256,
+        traces: Arc<Vec<LocalizedTransactionTrace>>,
+    ) {
+        if let Some(inner) = &self.inner {
+            if let Some(block) = inner.lock().get_or_insert(block_hash, BlockTraces::default) {
+                block.parity = Some(traces);
+            }
+        }
+    }
+
+    /// Returns the cached geth traces of the block that were traced with the given options.
+    pub fn geth_traces(
+        &self,
+        block_hash: B256,
+        opts: &GethDebugTracingOptions,
+    ) -> Option<Arc<Vec<TraceResult>>> {
+        let key = serde_json::to_string(opts).ok()?;
+        self.inner.as_ref()?.lock().get(&block_hash)?.geth.get(&key).cloned()
+    }
+
+    /// Caches the geth traces of the block that were traced with the given options.
+    pub fn insert_geth_traces(
+        &self,
+        block_hash: B256,
+        opts: &GethDebugTracingOptions,
+        traces: Arc<Vec<TraceResult>>,
+    ) {
+        let (Some(inner), Ok(key)) = (&self.inner, serde_json::to_string(opts)) else { return };
+        if let Some(block) = inner.lock().get_or_insert(block_hash, BlockTraces::default) {
+            block.geth.insert(key, traces);
+        }
+    }
+
+    /// Removes the traces of the given blocks, e.g. because they were reorged out.
+    pub fn remove_blocks(&self, block_hashes: impl IntoIterator<Item = B256>) {
+        if let Some(inner) = &self.inner {
+            let mut inner = inner.lock();
+            for block_hash in block_hashes {
+                inner.remove(&block_hash);
+            }
+        }
+    }
+
+    /// Returns the number of blocks with cached traces.
+    pub fn len(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.lock().len())
+    }
+
+    /// Returns `true` if no traces are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_types::trace::geth::{GethDebugBuiltInTracerType, GethDebugTracerType};
+
+    #[test]
+    fn cache_traces() {
+        let cache = TraceCache::new(2);
+        let (first, second, third) =
+            (B256::with_last_byte(1), B256::with_last_byte(2), B256::with_last_byte(3));
+        let default = GethDebugTracingOptions::default();
+        let call_tracer = GethDebugTracingOptions {
+            tracer: Some(GethDebugTracerType::BuiltInTracer(
+                GethDebugBuiltInTracerType::CallTracer,
+            )),
+            ..Default::default()
+        };
+
+        cache.insert_parity_traces(first, Arc::new(Vec::new()));
+        cache.insert_geth_traces(first, &default, Arc::new(Vec::new()));
+        assert!(cache.parity_traces(first).is_some());
+        assert!(cache.geth_traces(first, &default).is_some());
+        assert!(cache.geth_traces(first, &call_tracer).is_none());
+
+        // The least recently used block is evicted.
+        cache.insert_parity_traces(second, Arc::new(Vec::new()));
+        cache.insert_parity_traces(third, Arc::new(Vec::new()));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.parity_traces(first).is_none());
+
+        cache.remove_blocks([second]);
+        assert!(cache.parity_traces(second).is_none());
+        assert!(cache.parity_traces(third).is_some());
+
+        let disabled = TraceCache::new(0);
+        disabled.insert_parity_traces(first, Arc::new(Vec::new()));
+        assert!(disabled.is_empty());
+    }
+}
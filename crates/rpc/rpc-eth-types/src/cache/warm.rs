@@ -0,0 +1,48 @@
+//! Persistence of the cached blocks of the [`EthStateCache`](super::EthStateCache) across
+//! restarts.
+//!
+//! The hashes of the most recently used blocks are written to a file on shutdown, and the blocks
+//! are loaded into the cache again on startup with
+//! [`EthStateCache::warm`](super::EthStateCache::warm), which avoids the latency of a cold cache
+//! for the first RPC calls after a restart.
+
+use reth_primitives::B256;
+use std::{fs, io, path::Path};
+
+/// Reads the block hashes written by [`write_cached_blocks`].
+///
+/// Returns an empty list if the file doesn't exist.
+pub fn read_cached_blocks(path: &Path) -> io::Result<Vec<B256>> {
+    match fs::read(path) {
+        Ok(contents) => Ok(serde_json::from_slice(&contents)?),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Writes the given block hashes to the file at the given path.
+pub fn write_cached_blocks(path: &Path, block_hashes: &[B256]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_vec(block_hashes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_blocks_roundtrip() {
+        let path = std::env::temp_dir()
+            .join(format!("reth-rpc-cache-{}", std::process::id()))
+            .join("rpc-cache-blocks.json");
+        assert!(read_cached_blocks(&path).unwrap().is_empty());
+
+        let block_hashes = vec![B256::with_last_byte(2), B256::with_last_byte(1)];
+        write_cached_blocks(&path, &block_hashes).unwrap();
+        assert_eq!(read_cached_blocks(&path).unwrap(), block_hashes);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
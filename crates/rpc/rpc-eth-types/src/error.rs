@@ -4,7 +4,7 @@ use std::time::Duration;
 
 use alloy_sol_types::decode_revert_reason;
 use reth_errors::RethError;
-use reth_primitives::{revm_primitives::InvalidHeader, Address, Bytes};
+use reth_primitives::{revm_primitives::InvalidHeader, Address, BlockNumber, Bytes};
 use reth_rpc_server_types::result::{
     internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code,
 };
@@ -19,6 +19,8 @@ use revm::primitives::{EVMError, ExecutionResult, HaltReason, OutOfGasError};
 use revm_inspectors::tracing::MuxError;
 use tracing::error;
 
+use crate::history::pruned_history_rpc_err;
+
 /// Result alias
 pub type EthResult<T> = Result<T, EthApiError>;
 
@@ -55,6 +57,14 @@ pub enum EthApiError {
     /// When an invalid block range is provided
     #[error("invalid block range")]
     InvalidBlockRange,
+    /// Thrown when the requested history is pruned.
+    ///
+    /// Contains the earliest block that is still available, if known.
+    #[error("pruned history unavailable")]
+    PrunedHistory {
+        /// The earliest block that is still available.
+        earliest_available: Option<BlockNumber>,
+    },
     /// Thrown when the target block for proof computation exceeds the maximum configured window.
     #[error("distance to target block exceeds maximum proof window")]
     ExceedsMaxProofWindow,
@@ -174,6 +184,9 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             EthApiError::UnknownSafeOrFinalizedBlock => {
                 rpc_error_with_code(EthRpcErrorCode::UnknownBlock.code(), error.to_string())
             }
+            EthApiError::PrunedHistory { earliest_available } => {
+                pruned_history_rpc_err(earliest_available)
+            }
             EthApiError::Unsupported(msg) => internal_rpc_err(msg),
             EthApiError::InternalJsTracerError(msg) => internal_rpc_err(msg),
             EthApiError::InvalidParams(msg) => invalid_params_rpc_err(msg),
@@ -225,6 +238,9 @@ impl From<reth_errors::ProviderError> for EthApiError {
             ProviderError::FinalizedBlockNotFound | ProviderError::SafeBlockNotFound => {
                 Self::UnknownSafeOrFinalizedBlock
             }
+            ProviderError::StateAtBlockPruned(_) => {
+                Self::PrunedHistory { earliest_available: None }
+            }
             err => Self::Internal(err.into()),
         }
     }
@@ -4,6 +4,7 @@
 use std::fmt::{self, Debug, Formatter};
 
 use derive_more::{Deref, DerefMut, From, Into};
+use parking_lot::RwLock;
 use reth_primitives::{constants::GWEI_TO_WEI, BlockNumberOrTag, B256, U256};
 use reth_rpc_server_types::constants;
 use reth_storage_api::BlockReaderIdExt;
@@ -70,10 +71,8 @@ pub struct GasPriceOracle<Provider> {
     provider: Provider,
     /// The cache for blocks
     cache: EthStateCache,
-    /// The config for the oracle
-    oracle_config: GasPriceOracleConfig,
-    /// The price under which the sample will be ignored.
-    ignore_price: Option<u128>,
+    /// The config for the oracle, can be replaced with [`GasPriceOracle::set_config`]
+    oracle_config: RwLock<GasPriceOracleConfig>,
     /// Stores the latest calculated price and its block hash and Cache stores the lowest effective
     /// tip values of recent blocks
     inner: Mutex<GasPriceOracleInner>,
@@ -86,31 +85,36 @@ where
     /// Creates and returns the [`GasPriceOracle`].
     pub fn new(
         provider: Provider,
-        mut oracle_config: GasPriceOracleConfig,
+        oracle_config: GasPriceOracleConfig,
         cache: EthStateCache,
     ) -> Self {
-        // sanitize the percentile to be less than 100
-        if oracle_config.percentile > 100 {
-            warn!(prev_percentile = ?oracle_config.percentile, "Invalid configured gas price percentile, assuming 100.");
-            oracle_config.percentile = 100;
-        }
-        let ignore_price = oracle_config.ignore_price.map(|price| price.saturating_to());
+        let oracle_config = sanitize_config(oracle_config);
 
         // this is the number of blocks that we will cache the values for
         let cached_values = (oracle_config.blocks * 5).max(oracle_config.max_block_history as u32);
         let inner = Mutex::new(GasPriceOracleInner {
+            config: oracle_config,
             last_price: Default::default(),
             lowest_effective_tip_cache: EffectiveTipLruCache(LruMap::new(ByLength::new(
                 cached_values,
             ))),
         });
 
-        Self { provider, oracle_config, cache, ignore_price, inner }
+        Self { provider, oracle_config: RwLock::new(oracle_config), cache, inner }
     }
 
     /// Returns the configuration of the gas price oracle.
-    pub const fn config(&self) -> &GasPriceOracleConfig {
-        &self.oracle_config
+    pub fn config(&self) -> GasPriceOracleConfig {
+        *self.oracle_config.read()
+    }
+
+    /// Replaces the configuration of the gas price oracle.
+    ///
+    /// The last price and all cached tip values are discarded with the next estimate, so it is
+    /// computed with the new settings. The size of the tip cache is fixed when the oracle is
+    /// created.
+    pub fn set_config(&self, config: GasPriceOracleConfig) {
+        *self.oracle_config.write() = sanitize_config(config);
     }
 
     /// Suggests a gas price estimate based on recent blocks, using the configured percentile.
@@ -121,6 +125,14 @@ where
             .ok_or(EthApiError::UnknownBlockNumber)?;
 
         let mut inner = self.inner.lock().await;
+        let oracle_config = self.config();
+
+        // the cached values are outdated if the config was replaced
+        if inner.config != oracle_config {
+            inner.config = oracle_config;
+            inner.last_price = Default::default();
+            inner.lowest_effective_tip_cache.clear();
+        }
 
         // if we have stored a last price, then we check whether or not it was for the same head
         if inner.last_price.block_hash == header.hash() {
@@ -137,10 +149,10 @@ where
         let mut populated_blocks = 0;
 
         // we only check a maximum of 2 * max_block_history, or the number of blocks in the chain
-        let max_blocks = if self.oracle_config.max_block_history * 2 > header.number {
+        let max_blocks = if oracle_config.max_block_history * 2 > header.number {
             header.number
         } else {
-            self.oracle_config.max_block_history * 2
+            oracle_config.max_block_history * 2
         };
 
         for _ in 0..max_blocks {
@@ -151,7 +163,7 @@ where
                 } else {
                     // Otherwise we fetch it using get_block_values
                     let (parent_hash, block_values) = self
-                        .get_block_values(current_hash, SAMPLE_NUMBER)
+                        .get_block_values(current_hash, SAMPLE_NUMBER, oracle_config.ignore_price)
                         .await?
                         .ok_or(EthApiError::UnknownBlockNumber)?;
                     inner
@@ -168,7 +180,7 @@ where
             }
 
             // break when we have enough populated blocks
-            if populated_blocks >= self.oracle_config.blocks {
+            if populated_blocks >= oracle_config.blocks {
                 break
             }
 
@@ -178,7 +190,7 @@ where
         // sort results then take the configured percentile result
         let mut price = if !results.is_empty() {
             results.sort_unstable();
-            *results.get((results.len() - 1) * self.oracle_config.percentile as usize / 100).expect(
+            *results.get((results.len() - 1) * oracle_config.percentile as usize / 100).expect(
                 "gas price index is a percent of nonzero array length, so a value always exists",
             )
         } else {
@@ -186,7 +198,7 @@ where
         };
 
         // constrain to the max price
-        if let Some(max_price) = self.oracle_config.max_price {
+        if let Some(max_price) = oracle_config.max_price {
             if price > max_price {
                 price = max_price;
             }
@@ -208,7 +220,9 @@ where
         &self,
         block_hash: B256,
         limit: usize,
+        ignore_price: Option<U256>,
     ) -> EthResult<Option<(B256, Vec<U256>)>> {
+        let ignore_price: Option<u128> = ignore_price.map(|price| price.saturating_to());
         // check the cache (this will hit the disk if the block is not cached)
         let mut block = match self.cache.get_block(block_hash).await? {
             Some(block) => block,
@@ -226,7 +240,7 @@ where
         for tx in &block.body {
             let mut effective_gas_tip = None;
             // ignore transactions with a tip under the configured threshold
-            if let Some(ignore_under) = self.ignore_price {
+            if let Some(ignore_under) = ignore_price {
                 let tip = tx.effective_tip_per_gas(base_fee_per_gas);
                 effective_gas_tip = Some(tip);
                 if tip < Some(ignore_under) {
@@ -259,9 +273,20 @@ where
     }
 }
 
+/// Caps the percentile of the config at 100.
+fn sanitize_config(mut config: GasPriceOracleConfig) -> GasPriceOracleConfig {
+    if config.percentile > 100 {
+        warn!(prev_percentile = ?config.percentile, "Invalid configured gas price percentile, assuming 100.");
+        config.percentile = 100;
+    }
+    config
+}
+
 /// Container type for mutable inner state of the [`GasPriceOracle`]
 #[derive(Debug)]
 struct GasPriceOracleInner {
+    /// The config the cached values were computed with.
+    config: GasPriceOracleConfig,
     last_price: GasPriceOracleResult,
     lowest_effective_tip_cache: EffectiveTipLruCache,
 }
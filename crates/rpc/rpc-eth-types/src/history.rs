@@ -0,0 +1,255 @@
+//! Handling of requests for pruned history.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use jsonrpsee_core::{
+    client::{ClientT, Error as ClientError},
+    traits::ToRpcParams,
+    JsonRawValue,
+};
+use jsonrpsee_http_client::{HttpClient, HttpClientBuilder};
+use jsonrpsee_types::ErrorObject;
+use parking_lot::Mutex;
+use reth_errors::ProviderResult;
+use reth_primitives::BlockNumber;
+use reth_prune_types::PruneSegment;
+use reth_rpc_server_types::result::internal_rpc_err;
+use reth_storage_api::PruneCheckpointReader;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tracing::warn;
+
+/// JSON-RPC error code returned if the requested history is pruned.
+///
+/// This is the same code geth uses for pruned history.
+pub const PRUNED_HISTORY_CODE: i32 = 4444;
+
+/// Default timeout for requests forwarded to the [`HistoricalRpcFallback`].
+pub const DEFAULT_HISTORICAL_RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default time after which a cached prune checkpoint is read from the database again.
+///
+/// The pruner runs at most once per block, so this keeps the cached checkpoints at most about a
+/// block behind.
+pub const DEFAULT_PRUNE_CHECKPOINT_CACHE_TTL: Duration = Duration::from_secs(12);
+
+/// Caches the earliest block that is still available for pruned segments, so that requests don't
+/// read the prune checkpoints from the database every time.
+///
+/// Prune checkpoints only move forward, so a block before a cached earliest available block is
+/// always pruned. Blocks that were pruned after the checkpoint was cached are only detected once
+/// the entry expires.
+#[derive(Debug)]
+pub struct PruneCheckpointCache {
+    ttl: Duration,
+    /// Earliest available block per segment and when it was read from the database.
+    entries: Mutex<HashMap<PruneSegment, (Instant, Option<BlockNumber>)>>,
+}
+
+impl PruneCheckpointCache {
+    /// Creates a new cache that reads checkpoints again after the given time.
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, entries: Default::default() }
+    }
+
+    /// Returns the earliest block for which the data of the segment is available, or `None` if
+    /// the segment was never pruned.
+    pub fn earliest_available<Provider>(
+        &self,
+        provider: &Provider,
+        segment: PruneSegment,
+    ) -> ProviderResult<Option<BlockNumber>>
+    where
+        Provider: PruneCheckpointReader,
+    {
+        let now = Instant::now();
+        if let Some((read_at, earliest_available)) = self.entries.lock().get(&segment) {
+            if now.duration_since(*read_at) < self.ttl {
+                return Ok(*earliest_available)
+            }
+        }
+
+        let earliest_available = provider
+            .get_prune_checkpoint(segment)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map(|pruned_to| pruned_to + 1);
+        self.entries.lock().insert(segment, (now, earliest_available));
+        Ok(earliest_available)
+    }
+}
+
+impl Default for PruneCheckpointCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_PRUNE_CHECKPOINT_CACHE_TTL)
+    }
+}
+
+/// Returns the JSON-RPC error for a request for pruned history.
+///
+/// If known, the earliest block that is still available is attached as
+/// `{"earliestAvailableBlock": <number>}` error data.
+pub fn pruned_history_rpc_err(earliest_available: Option<BlockNumber>) -> ErrorObject<'static> {
+    /// Error data of [`pruned_history_rpc_err`].
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct PrunedHistoryData {
+        earliest_available_block: BlockNumber,
+    }
+
+    ErrorObject::owned(
+        PRUNED_HISTORY_CODE,
+        "pruned history unavailable",
+        earliest_available
+            .map(|earliest_available_block| PrunedHistoryData { earliest_available_block }),
+    )
+}
+
+/// Returns `true` if the error was returned for a request for pruned history.
+pub fn is_pruned_history_rpc_err(err: &ErrorObject<'_>) -> bool {
+    err.code() == PRUNED_HISTORY_CODE
+}
+
+/// Settings for an archive node that requests for pruned history are forwarded to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoricalRpcFallback {
+    /// HTTP URL of the archive node.
+    pub url: String,
+    /// Timeout for forwarded requests.
+    pub request_timeout: Duration,
+}
+
+impl HistoricalRpcFallback {
+    /// Creates a new fallback to the archive node at the given HTTP URL.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into(), request_timeout: DEFAULT_HISTORICAL_RPC_TIMEOUT }
+    }
+
+    /// Configures the timeout for forwarded requests.
+    pub const fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
+    /// Creates the client that forwards requests to the archive node.
+    ///
+    /// Returns `None` and logs a warning if the client can't be created, e.g. due to an invalid
+    /// URL, in which case requests for pruned history fail.
+    pub fn client(&self) -> Option<HistoricalRpcClient> {
+        match HttpClientBuilder::default().request_timeout(self.request_timeout).build(&self.url) {
+            Ok(client) => Some(HistoricalRpcClient { client }),
+            Err(err) => {
+                warn!(target: "rpc::eth", %err, url = %self.url, "Invalid historical RPC fallback");
+                None
+            }
+        }
+    }
+}
+
+/// Client that forwards requests for pruned history to an archive node.
+///
+/// See also [`HistoricalRpcFallback`].
+#[derive(Debug, Clone)]
+pub struct HistoricalRpcClient {
+    client: HttpClient,
+}
+
+impl HistoricalRpcClient {
+    /// Forwards the request to the archive node.
+    ///
+    /// The params are serialized as JSON array, e.g. `(block_id, trace_types)`. Errors returned by
+    /// the archive node are passed through as is.
+    pub async fn request<R, P>(&self, method: &str, params: P) -> Result<R, ErrorObject<'static>>
+    where
+        R: DeserializeOwned,
+        P: Serialize + Send,
+    {
+        self.client.request(method, ForwardedParams(params)).await.map_err(|err| match err {
+            ClientError::Call(err) => err,
+            err => internal_rpc_err(format!("historical RPC fallback failed: {err}")),
+        })
+    }
+}
+
+/// Params of a forwarded request.
+struct ForwardedParams<P>(P);
+
+impl<P: Serialize> ToRpcParams for ForwardedParams<P> {
+    fn to_rpc_params(self) -> Result<Option<Box<JsonRawValue>>, serde_json::Error> {
+        jsonrpsee_core::to_json_raw_value(&self.0).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_prune_types::{PruneCheckpoint, PruneMode};
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    /// Returns a checkpoint of the receipts segment and counts the reads.
+    #[derive(Default)]
+    struct CountingProvider {
+        pruned_to: AtomicU64,
+        reads: AtomicUsize,
+    }
+
+    impl PruneCheckpointReader for CountingProvider {
+        fn get_prune_checkpoint(
+            &self,
+            segment: PruneSegment,
+        ) -> ProviderResult<Option<PruneCheckpoint>> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            Ok((segment == PruneSegment::Receipts).then(|| PruneCheckpoint {
+                block_number: Some(self.pruned_to.load(Ordering::Relaxed)),
+                tx_number: None,
+                prune_mode: PruneMode::Full,
+            }))
+        }
+
+        fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn prune_checkpoint_cache() {
+        let provider = CountingProvider { pruned_to: AtomicU64::new(99), ..Default::default() };
+        let cache = PruneCheckpointCache::default();
+
+        for _ in 0..3 {
+            let earliest_available =
+                cache.earliest_available(&provider, PruneSegment::Receipts).unwrap();
+            assert_eq!(earliest_available, Some(100));
+        }
+        assert_eq!(
+            cache.earliest_available(&provider, PruneSegment::AccountHistory).unwrap(),
+            None
+        );
+        assert_eq!(provider.reads.load(Ordering::Relaxed), 2);
+
+        // expired entries are read again
+        let cache = PruneCheckpointCache::new(Duration::ZERO);
+        assert_eq!(cache.earliest_available(&provider, PruneSegment::Receipts).unwrap(), Some(100));
+        provider.pruned_to.store(199, Ordering::Relaxed);
+        assert_eq!(cache.earliest_available(&provider, PruneSegment::Receipts).unwrap(), Some(200));
+        assert_eq!(provider.reads.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn pruned_history_err_data() {
+        let err = pruned_history_rpc_err(Some(100));
+        assert!(is_pruned_history_rpc_err(&err));
+        assert_eq!(err.data().unwrap().get(), r#"{"earliestAvailableBlock":100}"#);
+
+        let err = pruned_history_rpc_err(None);
+        assert!(is_pruned_history_rpc_err(&err));
+        assert!(err.data().is_none());
+    }
+
+    #[test]
+    fn forwarded_params_are_array() {
+        let params = ForwardedParams((1u64, "latest")).to_rpc_params().unwrap().unwrap();
+        assert_eq!(params.get(), r#"[1,"latest"]"#);
+    }
+}
@@ -13,28 +13,33 @@ pub mod cache;
 pub mod error;
 pub mod fee_history;
 pub mod gas_oracle;
+pub mod history;
 pub mod id_provider;
+pub mod limits;
 pub mod logs_utils;
 pub mod pending_block;
 pub mod receipt;
 pub mod revm_utils;
+pub mod state_changes;
 pub mod transaction;
 pub mod utils;
 
 pub use builder::{
-    config::{EthConfig, EthFilterConfig},
+    config::{DevSignerConfig, EthConfig, EthFilterConfig},
     ctx::EthApiBuilderCtx,
 };
 pub use cache::{
     config::EthStateCacheConfig, db::StateCacheDb, multi_consumer::MultiConsumerLruCache,
-    EthStateCache,
+    trace::TraceCache, EthStateCache,
 };
 pub use error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError, SignError};
 pub use fee_history::{FeeHistoryCache, FeeHistoryCacheConfig, FeeHistoryEntry};
 pub use gas_oracle::{
     GasCap, GasPriceOracle, GasPriceOracleConfig, GasPriceOracleResult, RPC_DEFAULT_GAS_CAP,
 };
+pub use history::{HistoricalRpcClient, HistoricalRpcFallback, PruneCheckpointCache};
 pub use id_provider::EthSubscriptionIdProvider;
+pub use limits::RpcLimits;
 pub use logs_utils::EthFilterError;
 pub use pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
 pub use receipt::ReceiptBuilder;
@@ -0,0 +1,159 @@
+//! Request limits that can be changed while the node is running.
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use reth_tasks::pool::BlockingTaskGuard;
+
+use crate::{EthConfig, GasCap};
+
+/// Request limits shared by the RPC handlers.
+///
+/// Clones share the same limits, changes apply to all requests that are handled afterwards. This
+/// allows changing the limits without restarting the node.
+#[derive(Debug, Clone)]
+pub struct RpcLimits {
+    inner: Arc<RpcLimitsInner>,
+}
+
+impl RpcLimits {
+    /// Creates new limits.
+    ///
+    /// `usize::MAX` logs per response is effectively no limit.
+    pub fn new(gas_cap: u64, max_logs_per_response: usize, max_tracing_requests: usize) -> Self {
+        let inner = RpcLimitsInner {
+            gas_cap: AtomicU64::new(gas_cap),
+            max_logs_per_response: AtomicUsize::new(max_logs_per_response),
+            tracing_guard: BlockingTaskGuard::new(max_tracing_requests),
+            call_timeout_ms: AtomicU64::new(0),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Returns the gas limit for `eth_call` and call tracing RPC methods.
+    pub fn gas_cap(&self) -> u64 {
+        self.inner.gas_cap.load(Ordering::Relaxed)
+    }
+
+    /// Changes the gas limit for `eth_call` and call tracing RPC methods.
+    pub fn set_gas_cap(&self, gas_cap: u64) {
+        self.inner.gas_cap.store(gas_cap, Ordering::Relaxed);
+    }
+
+    /// Returns the maximum number of logs that can be returned in a single `eth_getLogs`
+    /// response.
+    pub fn max_logs_per_response(&self) -> usize {
+        self.inner.max_logs_per_response.load(Ordering::Relaxed)
+    }
+
+    /// Changes the maximum number of logs that can be returned in a single `eth_getLogs`
+    /// response.
+    pub fn set_max_logs_per_response(&self, max_logs: usize) {
+        self.inner.max_logs_per_response.store(max_logs, Ordering::Relaxed);
+    }
+
+    /// Returns the wall-clock timeout of the EVM execution of `eth_call` and `eth_estimateGas`.
+    pub fn call_timeout(&self) -> Option<Duration> {
+        match self.inner.call_timeout_ms.load(Ordering::Relaxed) {
+            0 => None,
+            ms => Some(Duration::from_millis(ms)),
+        }
+    }
+
+    /// Changes the wall-clock timeout of the EVM execution of `eth_call` and `eth_estimateGas`.
+    ///
+    /// Timeouts are tracked with millisecond precision, `None` disables the timeout.
+    pub fn set_call_timeout(&self, timeout: Option<Duration>) {
+        let ms = timeout.map_or(0, |timeout| (timeout.as_millis() as u64).max(1));
+        self.inner.call_timeout_ms.store(ms, Ordering::Relaxed);
+    }
+
+    /// Returns the guard that limits the number of concurrent tracing requests.
+    pub fn tracing_guard(&self) -> &BlockingTaskGuard {
+        &self.inner.tracing_guard
+    }
+
+    /// Returns the maximum number of tracing requests that are executed concurrently.
+    pub fn max_tracing_requests(&self) -> usize {
+        self.inner.tracing_guard.max_blocking_tasks()
+    }
+
+    /// Changes the maximum number of tracing requests that are executed concurrently.
+    ///
+    /// See also [`BlockingTaskGuard::set_max_blocking_tasks`].
+    pub fn set_max_tracing_requests(&self, max_requests: usize) {
+        self.inner.tracing_guard.set_max_blocking_tasks(max_requests);
+    }
+}
+
+impl Default for RpcLimits {
+    fn default() -> Self {
+        EthConfig::default().limits()
+    }
+}
+
+impl From<GasCap> for RpcLimits {
+    fn from(gas_cap: GasCap) -> Self {
+        let limits = Self::default();
+        limits.set_gas_cap(gas_cap.into());
+        limits
+    }
+}
+
+/// Two [`RpcLimits`] are equal if they are shared.
+impl PartialEq for RpcLimits {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for RpcLimits {}
+
+/// Container type for [`RpcLimits`].
+#[derive(Debug)]
+struct RpcLimitsInner {
+    gas_cap: AtomicU64,
+    max_logs_per_response: AtomicUsize,
+    tracing_guard: BlockingTaskGuard,
+    /// `0` if calls don't time out.
+    call_timeout_ms: AtomicU64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_are_shared() {
+        let limits = EthConfig::default().rpc_gas_cap(1_000).max_logs_per_response(10).limits();
+        assert_eq!(limits, limits.clone());
+        assert_ne!(limits, RpcLimits::default());
+
+        let shared = limits.clone();
+        std::thread::spawn(move || {
+            shared.set_gas_cap(2_000);
+            shared.set_max_logs_per_response(20);
+        })
+        .join()
+        .unwrap();
+        assert_eq!(limits.gas_cap(), 2_000);
+        assert_eq!(limits.max_logs_per_response(), 20);
+    }
+
+    #[test]
+    fn call_timeout() {
+        let limits = EthConfig::default().rpc_call_timeout(None).limits();
+        assert_eq!(limits.call_timeout(), None);
+
+        limits.set_call_timeout(Some(Duration::from_millis(1500)));
+        assert_eq!(limits.call_timeout(), Some(Duration::from_millis(1500)));
+
+        limits.set_call_timeout(Some(Duration::from_micros(10)));
+        assert_eq!(limits.call_timeout(), Some(Duration::from_millis(1)));
+    }
+}
@@ -7,12 +7,16 @@ use reth_rpc_types::{
 };
 use revm::{
     db::CacheDB,
+    interpreter::{InstructionResult, Interpreter},
     precompile::{PrecompileSpecId, Precompiles},
     primitives::{db::DatabaseRef, Bytecode, SpecId, TxEnv},
-    Database,
+    Database, EvmContext, Inspector,
 };
 use revm_primitives::BlockEnv;
-use std::cmp::min;
+use std::{
+    cmp::min,
+    time::{Duration, Instant},
+};
 
 use super::{EthApiError, EthResult, RpcInvalidTransactionError};
 
@@ -202,6 +206,64 @@ impl CallFees {
     }
 }
 
+/// Number of interpreter steps between two checks of the deadline by [`CallTimeoutInspector`].
+const CALL_TIMEOUT_CHECK_INTERVAL: u32 = 1024;
+
+/// An [`Inspector`] that aborts the execution once the timeout elapsed.
+///
+/// All frames are halted as soon as the deadline passed, so the execution returns promptly. The
+/// result of an aborted execution is meaningless, callers should check [`Self::timed_out`] and
+/// return [`EthApiError::ExecutionTimedOut`] instead.
+#[derive(Debug, Clone, Copy)]
+pub struct CallTimeoutInspector {
+    timeout: Duration,
+    deadline: Instant,
+    steps: u32,
+    timed_out: bool,
+}
+
+impl CallTimeoutInspector {
+    /// Creates a new inspector, the timeout starts now.
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout, deadline: Instant::now() + timeout, steps: 0, timed_out: false }
+    }
+
+    /// Returns the configured timeout.
+    pub const fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns `true` if the execution was aborted.
+    pub const fn timed_out(&self) -> bool {
+        self.timed_out
+    }
+
+    /// Returns [`EthApiError::ExecutionTimedOut`] if the execution was aborted.
+    pub const fn ensure_not_timed_out(&self) -> EthResult<()> {
+        if self.timed_out {
+            return Err(EthApiError::ExecutionTimedOut(self.timeout))
+        }
+        Ok(())
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CallTimeoutInspector {
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if !self.timed_out {
+            self.steps += 1;
+            if self.steps < CALL_TIMEOUT_CHECK_INTERVAL {
+                return
+            }
+            self.steps = 0;
+            if Instant::now() < self.deadline {
+                return
+            }
+            self.timed_out = true;
+        }
+        interp.instruction_result = InstructionResult::OutOfGas;
+    }
+}
+
 /// Applies the given block overrides to the env
 pub fn apply_block_overrides(overrides: BlockOverrides, env: &mut BlockEnv) {
     let BlockOverrides {
@@ -423,4 +485,43 @@ mod tests {
         );
         assert!(call_fees.is_err());
     }
+
+    #[test]
+    fn call_timeout_aborts_endless_loop() {
+        use revm::{
+            db::EmptyDB,
+            inspector_handle_register,
+            primitives::{AccountInfo, TransactTo},
+            Evm,
+        };
+
+        let contract = Address::repeat_byte(0x42);
+        let mut db = CacheDB::new(EmptyDB::default());
+        // JUMPDEST PUSH1 0 JUMP
+        let code =
+            Bytecode::new_raw(reth_primitives::Bytes::from_static(&[0x5b, 0x60, 0x00, 0x56]));
+        db.insert_account_info(contract, AccountInfo::from_bytecode(code));
+
+        let mut inspector = CallTimeoutInspector::new(Duration::from_millis(50));
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(&mut inspector)
+            .modify_tx_env(|tx| {
+                tx.transact_to = TransactTo::Call(contract);
+                tx.gas_limit = u64::MAX;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let started = Instant::now();
+        let res = evm.transact().unwrap();
+        drop(evm);
+
+        assert!(!res.result.is_success());
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(matches!(
+            inspector.ensure_not_timed_out(),
+            Err(EthApiError::ExecutionTimedOut(timeout)) if timeout == Duration::from_millis(50)
+        ));
+    }
 }
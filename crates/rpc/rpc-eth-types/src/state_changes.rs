@@ -0,0 +1,239 @@
+//! Helpers to collect the [`BlockStateChanges`] of blocks, for `reth_getStateChanges` and the
+//! `stateChanges` subscription.
+
+use std::collections::{btree_map::Entry, BTreeMap, HashMap};
+
+use reth_errors::ProviderResult;
+use reth_execution_types::Chain;
+use reth_primitives::{
+    revm_primitives::AccountInfo, Address, BlockNumber, B256, KECCAK_EMPTY, U256,
+};
+use reth_revm::db::{states::PlainStorageRevert, BundleState};
+use reth_rpc_server_types::state_changes::{
+    AccountState, AccountStateChange, BlockStateChanges, StorageChange,
+};
+use reth_storage_api::{ChangeSetReader, StateProviderFactory};
+
+/// Returns the state changes of the given block, read from its changesets.
+pub fn block_state_changes<Provider>(
+    provider: &Provider,
+    block_number: BlockNumber,
+    block_hash: B256,
+) -> ProviderResult<BlockStateChanges>
+where
+    Provider: ChangeSetReader + StateProviderFactory,
+{
+    // the changesets contain the values before the block, the state at the end of the block the
+    // values after it
+    let state = provider.history_by_block_number(block_number)?;
+
+    let mut accounts = BTreeMap::new();
+    for account_before in provider.account_block_changeset(block_number)? {
+        let after = state.basic_account(account_before.address)?;
+        accounts.insert(
+            account_before.address,
+            AccountStateChange::new(account_before.info.map(Into::into), after.map(Into::into)),
+        );
+    }
+
+    for (address, storage_before) in provider.storage_block_changeset(block_number)? {
+        let change = match accounts.entry(address) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                // the account itself didn't change
+                let account = state.basic_account(address)?.map(AccountState::from);
+                entry.insert(AccountStateChange::new(account, account))
+            }
+        };
+        let after = state.storage(address, storage_before.key)?.unwrap_or_default();
+        change
+            .storage
+            .insert(storage_before.key, StorageChange { before: storage_before.value, after });
+    }
+
+    for change in accounts.values_mut() {
+        if let Some(code_hash) = deployed_code_hash(change) {
+            change.code = state.bytecode_by_hash(code_hash)?.map(|code| code.original_bytes());
+        }
+    }
+
+    Ok(finish(block_hash, block_number, accounts))
+}
+
+/// Returns the state changes of all blocks of the given chain, read from the reverts of its
+/// execution outcome.
+///
+/// Slots that were cleared by a selfdestruct without being accessed are not included, since they
+/// are only known once the chain is written to the database.
+pub fn chain_state_changes(chain: &Chain) -> Vec<BlockStateChanges> {
+    let outcome = chain.execution_outcome();
+    let bundle = &outcome.bundle;
+    let reverts = bundle.reverts.clone().into_plain_state_reverts();
+
+    // the values after the block that is processed, starting with the values after the last block
+    let mut accounts_after: HashMap<Address, Option<AccountInfo>> = HashMap::new();
+    let mut storage_after: HashMap<(Address, U256), U256> = HashMap::new();
+
+    let mut changes = Vec::with_capacity(chain.len());
+    for (index, (account_reverts, storage_reverts)) in
+        reverts.accounts.into_iter().zip(reverts.storage).enumerate().rev()
+    {
+        let block_number = outcome.first_block() + index as u64;
+        let Some(block) = chain.blocks().get(&block_number) else { continue };
+
+        let mut accounts = BTreeMap::new();
+        for (address, before) in account_reverts {
+            let after = account_after(&mut accounts_after, bundle, address);
+            let mut change = AccountStateChange::new(
+                before.as_ref().map(account_info_state),
+                after.as_ref().map(account_info_state),
+            );
+            if let Some(code_hash) = deployed_code_hash(&change) {
+                change.code = after
+                    .as_ref()
+                    .and_then(|info| info.code.clone())
+                    .or_else(|| outcome.bytecode(&code_hash).map(|code| code.0))
+                    .map(|code| code.original_bytes());
+            }
+            *after = before;
+            accounts.insert(address, change);
+        }
+
+        for PlainStorageRevert { address, storage_revert, .. } in storage_reverts {
+            let change = accounts.entry(address).or_insert_with(|| {
+                // the account itself didn't change
+                let account = account_after(&mut accounts_after, bundle, address)
+                    .as_ref()
+                    .map(account_info_state);
+                AccountStateChange::new(account, account)
+            });
+            for (slot, revert) in storage_revert {
+                let after = storage_after.entry((address, slot)).or_insert_with(|| {
+                    bundle
+                        .account(&address)
+                        .and_then(|account| account.storage.get(&slot))
+                        .map(|slot| slot.present_value)
+                        .unwrap_or_default()
+                });
+                let before = revert.to_previous_value();
+                change.storage.insert(slot.into(), StorageChange { before, after: *after });
+                *after = before;
+            }
+        }
+
+        changes.push(finish(block.hash(), block_number, accounts));
+    }
+
+    changes.reverse();
+    changes
+}
+
+/// Returns the account after the processed block, which is the account after the last block of the
+/// bundle until it's replaced by the value before a block.
+fn account_after<'a>(
+    accounts_after: &'a mut HashMap<Address, Option<AccountInfo>>,
+    bundle: &BundleState,
+    address: Address,
+) -> &'a mut Option<AccountInfo> {
+    accounts_after
+        .entry(address)
+        .or_insert_with(|| bundle.account(&address).and_then(|account| account.info.clone()))
+}
+
+/// Returns the hash of the code deployed by the change, if any.
+fn deployed_code_hash(change: &AccountStateChange) -> Option<B256> {
+    let code_hash = change.after?.code_hash;
+    (code_hash != KECCAK_EMPTY && change.before.map(|before| before.code_hash) != Some(code_hash))
+        .then_some(code_hash)
+}
+
+/// Drops the accounts and slots that were touched by the block without being changed.
+fn finish(
+    block_hash: B256,
+    block_number: BlockNumber,
+    mut accounts: BTreeMap<Address, AccountStateChange>,
+) -> BlockStateChanges {
+    accounts.retain(|_, change| {
+        change.storage.retain(|_, slot| slot.before != slot.after);
+        !change.is_unchanged()
+    });
+    BlockStateChanges { block_hash, block_number, accounts }
+}
+
+const fn account_info_state(info: &AccountInfo) -> AccountState {
+    AccountState { nonce: info.nonce, balance: info.balance, code_hash: info.code_hash }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_execution_types::ExecutionOutcome;
+    use reth_primitives::{
+        BlockBody, Bytecode, Bytes, Header, SealedBlock, SealedBlockWithSenders, SealedHeader,
+    };
+    use reth_rpc_server_types::state_changes::AccountChangeKind;
+
+    fn block(number: BlockNumber) -> SealedBlockWithSenders {
+        let header = SealedHeader::new(Header { number, ..Default::default() }, B256::ZERO);
+        SealedBlockWithSenders::new(SealedBlock::new(header, BlockBody::default()), Vec::new())
+            .unwrap()
+    }
+
+    #[test]
+    fn chain_state_changes_per_block() {
+        let address = Address::with_last_byte(1);
+        let untouched = Address::with_last_byte(2);
+        let slot = U256::from(1);
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00]));
+        let created = AccountInfo {
+            nonce: 1,
+            balance: U256::from(10),
+            code_hash: code.hash_slow(),
+            code: Some(code.0.clone()),
+        };
+        let updated = AccountInfo { balance: U256::from(20), ..created.clone() };
+        let unchanged = AccountInfo { nonce: 1, ..Default::default() };
+
+        // the account is created with its code and a storage slot in block 1, and updated in
+        // block 2
+        let bundle = BundleState::builder(1..=2)
+            .state_present_account_info(address, updated.clone())
+            .state_storage(address, HashMap::from([(slot, (U256::ZERO, U256::from(7)))]))
+            .revert_account_info(1, address, Some(None))
+            .revert_storage(1, address, vec![(slot, U256::ZERO)])
+            .revert_account_info(2, address, Some(Some(created.clone())))
+            .revert_storage(2, address, vec![(slot, U256::from(5))])
+            .state_present_account_info(untouched, unchanged.clone())
+            .revert_account_info(2, untouched, Some(Some(unchanged)))
+            .build();
+        let outcome = ExecutionOutcome::new(bundle, Default::default(), 1, Vec::new());
+        let chain = Chain::new([block(1), block(2)], outcome, None);
+
+        let changes = chain_state_changes(&chain);
+        assert_eq!(changes.len(), 2);
+
+        let first = &changes[0].accounts[&address];
+        assert_eq!(changes[0].block_number, 1);
+        assert_eq!(first.kind, AccountChangeKind::Created);
+        assert_eq!(first.after, Some(account_info_state(&created)));
+        assert_eq!(first.code, Some(code.original_bytes()));
+        assert_eq!(
+            first.storage[&B256::from(slot)],
+            StorageChange { before: U256::ZERO, after: U256::from(5) }
+        );
+
+        let second = &changes[1].accounts[&address];
+        assert_eq!(changes[1].block_number, 2);
+        assert_eq!(second.kind, AccountChangeKind::Updated);
+        assert_eq!(second.before, Some(account_info_state(&created)));
+        assert_eq!(second.after, Some(account_info_state(&updated)));
+        assert_eq!(second.code, None);
+        assert_eq!(
+            second.storage[&B256::from(slot)],
+            StorageChange { before: U256::from(5), after: U256::from(7) }
+        );
+
+        // accounts that were touched without being changed are omitted
+        assert!(!changes[1].accounts.contains_key(&untouched));
+    }
+}
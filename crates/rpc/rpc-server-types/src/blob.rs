@@ -0,0 +1,27 @@
+//! Blob sidecars returned by `reth_getBlobSidecarsByBlock` and the `blobSidecars` subscription.
+
+use alloy_primitives::B256;
+use reth_primitives::BlobTransactionSidecar;
+use serde::{Deserialize, Serialize};
+
+/// The sidecar of an EIP-4844 transaction, as stored in the transaction pool's blob store.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobSidecarItem {
+    /// Hash of the EIP-4844 transaction.
+    pub tx_hash: B256,
+    /// Hash of the block that includes the transaction.
+    ///
+    /// `None` if the transaction is not included in a block yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<B256>,
+    /// Number of the block that includes the transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// Index of the transaction in the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_index: Option<u64>,
+    /// The blobs, commitments and proofs of the transaction.
+    #[serde(flatten)]
+    pub sidecar: BlobTransactionSidecar,
+}
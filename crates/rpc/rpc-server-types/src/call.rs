@@ -0,0 +1,54 @@
+//! Reth specific options of `eth_call` and `eth_estimateGas`.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Additional options of `eth_call` and `eth_estimateGas`, accepted as an optional parameter after
+/// the standard parameters.
+///
+/// # Example
+///
+/// ```
+/// use reth_rpc_server_types::call::CallOptions;
+/// use std::time::Duration;
+///
+/// let opts: CallOptions = serde_json::from_str(r#"{"timeout":"500ms"}"#).unwrap();
+/// assert_eq!(opts.timeout, Some(Duration::from_millis(500)));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallOptions {
+    /// Wall-clock timeout of the EVM execution, e.g. `"2s"`.
+    ///
+    /// This can only lower the timeout configured on the node.
+    #[serde(default, with = "humantime_serde", skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<Duration>,
+}
+
+impl CallOptions {
+    /// Returns the timeout of the call, given the timeout configured on the node.
+    pub fn effective_timeout(&self, configured: Option<Duration>) -> Option<Duration> {
+        match (self.timeout, configured) {
+            (Some(requested), Some(configured)) => Some(requested.min(configured)),
+            (requested, configured) => requested.or(configured),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requested_timeout_is_capped() {
+        let configured = Some(Duration::from_secs(5));
+        let opts = CallOptions { timeout: Some(Duration::from_secs(1)) };
+        assert_eq!(opts.effective_timeout(configured), Some(Duration::from_secs(1)));
+
+        let opts = CallOptions { timeout: Some(Duration::from_secs(10)) };
+        assert_eq!(opts.effective_timeout(configured), configured);
+        assert_eq!(opts.effective_timeout(None), Some(Duration::from_secs(10)));
+        assert_eq!(CallOptions::default().effective_timeout(configured), configured);
+    }
+}
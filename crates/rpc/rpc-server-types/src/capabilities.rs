@@ -0,0 +1,63 @@
+//! Node capabilities reported by `reth_config`.
+
+use alloy_primitives::B256;
+use reth_primitives::ForkCondition;
+use reth_prune_types::{PruneMode, PruneSegment};
+use serde::{Deserialize, Serialize};
+
+use crate::RethRpcModule;
+
+/// The active chain and RPC configuration of a node.
+///
+/// This allows tooling to adapt to the node, e.g. to the supported namespaces and request limits,
+/// instead of probing it with failing calls.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeCapabilities {
+    /// The chain id.
+    pub chain_id: u64,
+    /// The hash of the genesis block.
+    pub genesis_hash: B256,
+    /// All hardforks of the chain with their activation condition, ordered by activation.
+    pub hardforks: Vec<HardforkActivation>,
+    /// The enabled RPC namespaces.
+    pub namespaces: Vec<RethRpcModule>,
+    /// Gas limit for `eth_call` and call tracing RPC methods.
+    pub gas_cap: u64,
+    /// Maximum number of blocks that can be scanned per `eth_getLogs` request.
+    ///
+    /// `None` if unlimited.
+    pub max_blocks_per_filter: Option<u64>,
+    /// Maximum number of logs that can be returned in a single `eth_getLogs` response.
+    ///
+    /// `None` if unlimited.
+    pub max_logs_per_response: Option<usize>,
+    /// The segments the node is configured to prune.
+    ///
+    /// Receipts that are only kept for the logs of certain contracts are not reported.
+    pub prune: Vec<PruneSegmentInfo>,
+}
+
+/// The activation condition of a hardfork.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HardforkActivation {
+    /// The name of the hardfork.
+    pub name: String,
+    /// The condition at which the hardfork is activated.
+    pub condition: ForkCondition,
+}
+
+/// Pruning configuration and progress of a single segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneSegmentInfo {
+    /// The pruned segment.
+    pub segment: PruneSegment,
+    /// The configured prune mode of the segment.
+    pub mode: PruneMode,
+    /// The highest block the segment is pruned up to.
+    ///
+    /// `None` if the segment was not pruned yet.
+    pub pruned_to_block: Option<u64>,
+}
@@ -1,4 +1,4 @@
-use std::cmp::max;
+use std::{cmp::max, time::Duration};
 
 /// The default port for the http server
 pub const DEFAULT_HTTP_RPC_PORT: u16 = 8545;
@@ -15,6 +15,10 @@ pub const DEFAULT_MAX_BLOCKS_PER_FILTER: u64 = 100_000;
 /// The default maximum of logs in a single response.
 pub const DEFAULT_MAX_LOGS_PER_RESPONSE: usize = 20_000;
 
+/// The default maximum number of chunks of a finalized `eth_getLogs` range that are scanned
+/// concurrently.
+pub const DEFAULT_MAX_CONCURRENT_GETLOGS_CHUNKS: usize = 4;
+
 /// The default maximum number tracing requests we're allowing concurrently.
 /// Tracing is mostly CPU bound so we're limiting the number of concurrent requests to something
 /// lower that the number of cores, in order to minimize the impact on the rest of the system.
@@ -29,6 +33,35 @@ pub fn default_max_tracing_requests() -> usize {
 /// The default number of getproof calls we are allowing to run concurrently.
 pub const DEFAULT_PROOF_PERMITS: usize = 25;
 
+/// The default wall-clock timeout of the EVM execution of `eth_call` and `eth_estimateGas`.
+pub const DEFAULT_RPC_CALL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The default maximum number of RPC methods that are tracked with their own metrics label.
+pub const DEFAULT_MAX_METRICS_METHODS: usize = 512;
+
+/// The default maximum number of error codes per RPC method that are tracked with their own metrics
+/// label.
+pub const DEFAULT_MAX_METRICS_ERROR_CODES: usize = 32;
+
+/// The default time after which a state snapshot created by `reth_createStateSnapshot` expires.
+pub const DEFAULT_STATE_SNAPSHOT_TTL: Duration = Duration::from_secs(60);
+
+/// The default maximum number of state snapshots that can be alive at the same time.
+pub const DEFAULT_MAX_STATE_SNAPSHOTS: usize = 16;
+
+/// The default maximum number of blocks a node may be behind the highest known block to be
+/// reported as ready.
+pub const DEFAULT_READY_MAX_BLOCKS_BEHIND: u64 = 64;
+
+/// The default mnemonic the accounts of the dev signer are derived from.
+///
+/// The dev chain prefunds the first 20 accounts derived from this mnemonic.
+pub const DEFAULT_DEV_MNEMONIC: &str =
+    "test test test test test test test test test test test junk";
+
+/// The default number of accounts of the dev signer.
+pub const DEFAULT_DEV_ACCOUNTS: u32 = 20;
+
 /// The default IPC endpoint
 #[cfg(windows)]
 pub const DEFAULT_IPC_ENDPOINT: &str = r"\\.\pipe\reth.ipc";
@@ -94,7 +127,6 @@ pub mod gas_oracle {
 
 /// Cache specific constants
 pub mod cache {
-    // TODO: memory based limiter is currently disabled pending <https://github.com/paradigmxyz/reth/issues/3503>
     /// Default cache size for the block cache: 500MB
     ///
     /// With an average block size of ~100kb this should be able to cache ~5000 blocks.
@@ -106,6 +138,10 @@ pub mod cache {
     /// Default cache size for the env cache: 1MB
     pub const DEFAULT_ENV_CACHE_SIZE_BYTES_MB: usize = 1;
 
+    /// Default amount of system memory that should stay available before the memory budgets of
+    /// the caches are reduced: 1GB
+    pub const DEFAULT_MIN_AVAILABLE_MEMORY_MB: usize = 1024;
+
     /// Default cache size for the block cache: 5000 blocks.
     pub const DEFAULT_BLOCK_CACHE_MAX_LEN: u32 = 5000;
 
@@ -115,6 +151,9 @@ pub mod cache {
     /// Default cache size for the env cache: 1000 envs.
     pub const DEFAULT_ENV_CACHE_MAX_LEN: u32 = 1000;
 
+    /// Default cache size for the trace cache: the traces of 256 blocks.
+    pub const DEFAULT_TRACE_CACHE_MAX_LEN: u32 = 256;
+
     /// Default number of concurrent database requests.
     pub const DEFAULT_CONCURRENT_DB_REQUESTS: usize = 512;
 }
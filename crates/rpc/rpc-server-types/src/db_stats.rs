@@ -0,0 +1,100 @@
+//! Storage composition returned by the `reth_db` namespace.
+
+use reth_primitives::{static_file::SegmentRangeInclusive, StaticFileSegment};
+use reth_prune_types::{PruneMode, PruneSegment};
+use serde::{Deserialize, Serialize};
+
+/// The composition of the node's storage.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseStats {
+    /// The tables of the database.
+    pub tables: Vec<TableInfo>,
+    /// The static file segments.
+    pub static_files: Vec<StaticFileSegmentInfo>,
+    /// The prune checkpoints of all pruned segments.
+    pub prune_checkpoints: Vec<PruneCheckpointInfo>,
+    /// The checkpoints of all stages.
+    pub stage_checkpoints: Vec<StageCheckpointInfo>,
+}
+
+/// The size and number of entries of a database table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableInfo {
+    /// The name of the table.
+    pub name: String,
+    /// The number of entries in the table.
+    pub entries: usize,
+    /// The size of the table in bytes.
+    pub size: u64,
+    /// The number of leaf pages.
+    pub leaf_pages: usize,
+    /// The number of branch pages.
+    pub branch_pages: usize,
+    /// The number of overflow pages.
+    pub overflow_pages: usize,
+}
+
+/// The files, entries and size of a static file segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaticFileSegmentInfo {
+    /// The segment.
+    pub segment: StaticFileSegment,
+    /// The number of static files of the segment.
+    pub files: usize,
+    /// The number of rows in all static files of the segment.
+    pub entries: usize,
+    /// The size of all static files of the segment in bytes.
+    pub size: u64,
+    /// The blocks stored in the static files of the segment.
+    pub block_range: Option<InclusiveRange>,
+    /// The transactions stored in the static files of the segment.
+    pub tx_range: Option<InclusiveRange>,
+}
+
+/// An inclusive range of blocks or transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusiveRange {
+    /// The first number of the range.
+    pub start: u64,
+    /// The last number of the range.
+    pub end: u64,
+}
+
+impl From<SegmentRangeInclusive> for InclusiveRange {
+    fn from(range: SegmentRangeInclusive) -> Self {
+        Self { start: range.start(), end: range.end() }
+    }
+}
+
+/// The prune checkpoint of a segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneCheckpointInfo {
+    /// The pruned segment.
+    pub segment: PruneSegment,
+    /// The highest pruned block, if any.
+    pub block_number: Option<u64>,
+    /// The highest pruned transaction, if any.
+    pub tx_number: Option<u64>,
+    /// The prune mode the segment was pruned with.
+    pub prune_mode: PruneMode,
+}
+
+/// The checkpoint of a stage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StageCheckpointInfo {
+    /// The name of the stage.
+    pub stage: String,
+    /// The highest block processed by the stage.
+    pub block_number: u64,
+    /// The number of entities processed by the stage, if the stage reports its progress.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub processed: Option<u64>,
+    /// The total number of entities the stage processes, if the stage reports its progress.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+}
@@ -0,0 +1,35 @@
+//! Background integrity check of the database, see `admin_integrityCheck`.
+
+use serde::{Deserialize, Serialize};
+
+/// The progress and findings of the background integrity check of the database.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityCheckStatus {
+    /// The next block that is checked.
+    pub next_block: u64,
+    /// The highest fully synced block when the last batch was checked.
+    pub tip: u64,
+    /// The number of completed scans of all blocks.
+    pub completed_scans: u64,
+    /// The UNIX timestamp in seconds of the end of the last completed scan.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_scan_completed_at: Option<u64>,
+    /// The number of findings since the check was started or reset, including the ones that
+    /// aren't returned.
+    pub total_findings: u64,
+    /// The first findings since the check was started or reset.
+    pub findings: Vec<IntegrityFindingInfo>,
+}
+
+/// An inconsistency of the database that was found by the integrity check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityFindingInfo {
+    /// The block the inconsistency was found in.
+    pub block: u64,
+    /// The check that found the inconsistency, e.g. `transaction_lookup`.
+    pub check: String,
+    /// What is inconsistent.
+    pub message: String,
+}
@@ -8,11 +8,27 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+pub mod blob;
+pub mod call;
+pub mod capabilities;
 /// Common RPC constants.
 pub mod constants;
+pub mod db_stats;
+pub mod integrity;
+pub mod log_levels;
+pub mod peers;
+pub mod proof;
+pub mod pubsub;
 pub mod result;
+pub mod runtime;
+pub mod snapshot;
+pub mod state_changes;
+pub mod state_history;
+pub mod sync;
+pub mod witness;
 
 mod module;
 pub use module::{RethRpcModule, RpcModuleSelection};
+pub use pubsub::{RethSubscriptionKind, SubscriptionBackpressure};
 
 pub use result::ToRpcResult;
@@ -0,0 +1,16 @@
+//! Log levels that can be changed while the node is running, see `admin_setLogLevel`.
+
+use serde::{Deserialize, Serialize};
+
+/// A log level override of a target.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLevelOverride {
+    /// The target the override applies to, e.g. `reth::network`.
+    pub target: String,
+    /// The maximum level of events of the target that are logged, e.g. `debug`.
+    pub level: String,
+    /// Seconds until the override is removed again, if it is temporary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_in: Option<u64>,
+}
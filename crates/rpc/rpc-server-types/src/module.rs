@@ -260,6 +260,8 @@ pub enum RethRpcModule {
     /// This is separate from [`RethRpcModule::Eth`] because it is a non standardized call that
     /// should be opt-in.
     EthCallBundle,
+    /// `reth_db_` module
+    RethDb,
 }
 
 // === impl RethRpcModule ===
@@ -309,6 +311,7 @@ impl FromStr for RethRpcModule {
             "reth" => Self::Reth,
             "ots" => Self::Ots,
             "eth-call-bundle" | "eth_callBundle" => Self::EthCallBundle,
+            "reth-db" | "reth_db" => Self::RethDb,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
@@ -0,0 +1,87 @@
+//! Connected peers reported by `admin_peers`.
+
+use alloy_primitives::{FixedBytes, B256, U256};
+use reth_rpc_types::admin::PeerInfo;
+use serde::{Deserialize, Serialize};
+
+/// A connected peer reported by `admin_peers`.
+///
+/// Extends the standard peer info with the details of the peer's session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminPeerInfo {
+    /// The standard peer info.
+    #[serde(flatten)]
+    pub info: PeerInfo,
+    /// The details of the session with the peer.
+    pub session: PeerSessionInfo,
+}
+
+/// The details of the session with a peer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerSessionInfo {
+    /// The chain id the peer sent in the `eth` handshake.
+    pub network: u64,
+    /// The genesis hash the peer sent in the `eth` handshake.
+    pub genesis: B256,
+    /// The head hash the peer sent in the `eth` handshake.
+    pub head: B256,
+    /// The total difficulty the peer sent in the `eth` handshake, zero for `eth/69`.
+    pub difficulty: U256,
+    /// The fork id the peer sent in the `eth` handshake.
+    pub fork_id: PeerForkId,
+    /// For how long the session has been established, in seconds.
+    pub duration: u64,
+}
+
+/// The fork id of a peer, see [EIP-2124](https://eips.ethereum.org/EIPS/eip-2124).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerForkId {
+    /// The CRC32 checksum of the genesis hash and the passed fork blocks.
+    pub hash: FixedBytes<4>,
+    /// The next fork block or timestamp, zero if none is known.
+    pub next: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_types::admin::{PeerNetworkInfo, PeerProtocolInfo};
+
+    #[test]
+    fn session_is_a_dedicated_field() {
+        let peer = AdminPeerInfo {
+            info: PeerInfo {
+                enr: None,
+                enode: "enode://1@127.0.0.1:30303".to_string(),
+                id: "1".to_string(),
+                name: "reth".to_string(),
+                caps: vec!["eth/68".to_string()],
+                network: PeerNetworkInfo {
+                    local_address: "127.0.0.1:30303".parse().unwrap(),
+                    remote_address: "127.0.0.1:30304".parse().unwrap(),
+                    inbound: false,
+                    trusted: false,
+                    static_node: false,
+                },
+                protocols: PeerProtocolInfo { eth: None, snap: None, other: Default::default() },
+            },
+            session: PeerSessionInfo {
+                network: 1,
+                genesis: B256::with_last_byte(1),
+                head: B256::with_last_byte(2),
+                difficulty: U256::from(3),
+                fork_id: PeerForkId { hash: FixedBytes::from([0xfc, 0x64, 0xec, 0x04]), next: 4 },
+                duration: 5,
+            },
+        };
+
+        let json = serde_json::to_value(&peer).unwrap();
+        assert_eq!(json["name"], "reth");
+        // the session isn't reported as one of the protocols
+        assert_eq!(json["protocols"], serde_json::json!({}));
+        assert_eq!(json["session"]["forkId"], serde_json::json!({"hash": "0xfc64ec04", "next": 4}));
+        assert_eq!(json["session"]["duration"], 5);
+        assert_eq!(serde_json::from_value::<AdminPeerInfo>(json).unwrap(), peer);
+    }
+}
@@ -0,0 +1,107 @@
+//! Proof types of `eth_getProof` and `reth_getMultiProof`.
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use reth_rpc_types::{serde_helpers::JsonStorageKey, EIP1186AccountProofResponse};
+use reth_trie_common::{VerkleAccountProof, VerkleProof};
+use serde::{Deserialize, Serialize};
+
+/// An account and its storage slots to prove.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiProofTarget {
+    /// The address of the account.
+    pub address: Address,
+    /// The storage slots of the account.
+    #[serde(default)]
+    pub storage_keys: Vec<JsonStorageKey>,
+}
+
+/// The values of multiple accounts and storage slots with a combined Merkle-proof.
+///
+/// Unlike multiple `eth_getProof` responses, every trie node shared by the proofs of multiple
+/// targets is only included once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiProofResponse {
+    /// The proven accounts, in the order of the requested targets.
+    pub accounts: Vec<MultiProofAccount>,
+    /// All account and storage trie nodes required to verify the accounts and storage slots
+    /// against the state root of the block, starting with the root node of the account trie.
+    pub nodes: Vec<Bytes>,
+}
+
+/// The values of a proven account, see also [`MultiProofResponse`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiProofAccount {
+    /// The address of the account.
+    pub address: Address,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The code hash of the account.
+    pub code_hash: B256,
+    /// The storage root of the account.
+    pub storage_hash: B256,
+    /// The requested storage slots of the account.
+    pub storage: Vec<MultiProofStorageSlot>,
+}
+
+/// The value of a proven storage slot, see also [`MultiProofResponse`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiProofStorageSlot {
+    /// The storage slot.
+    pub key: JsonStorageKey,
+    /// The value of the storage slot.
+    pub value: U256,
+}
+
+/// The response of `eth_getProof`, which depends on the trie the state is stored in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AccountProofResponse {
+    /// An [EIP-1186](https://eips.ethereum.org/EIPS/eip-1186) Merkle-proof.
+    Merkle(EIP1186AccountProofResponse),
+    /// A proof of the leaves of the account in the experimental verkle trie.
+    Verkle(VerkleAccountProofResponse),
+}
+
+/// The verkle trie leaves of an account and its requested storage slots, with a proof of their
+/// values against the state root.
+///
+/// The leaves are the basic data and code hash leaves of the account followed by the requested
+/// storage slots, as specified by [EIP-6800](https://eips.ethereum.org/EIPS/eip-6800).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerkleAccountProofResponse {
+    /// The address of the account.
+    pub address: Address,
+    /// The proven leaves.
+    pub leaves: Vec<VerkleLeaf>,
+    /// The proof of the leaves.
+    pub proof: VerkleProof,
+}
+
+/// A proven leaf of the verkle trie, see also [`VerkleAccountProofResponse`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerkleLeaf {
+    /// The tree key of the leaf.
+    pub key: B256,
+    /// The value of the leaf, or `null` if it doesn't exist.
+    pub value: Option<B256>,
+}
+
+impl From<VerkleAccountProof> for VerkleAccountProofResponse {
+    fn from(proof: VerkleAccountProof) -> Self {
+        Self {
+            address: proof.address,
+            leaves: proof
+                .leaves
+                .into_iter()
+                .map(|(key, value)| VerkleLeaf { key, value })
+                .collect(),
+            proof: proof.proof,
+        }
+    }
+}
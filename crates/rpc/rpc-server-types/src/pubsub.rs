@@ -0,0 +1,190 @@
+//! Subscription related types.
+
+use std::{fmt, str::FromStr};
+
+use reth_rpc_types::pubsub::SubscriptionKind;
+use serde::{de::IntoDeserializer, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The default number of messages buffered per subscription before the backpressure policy kicks
+/// in.
+pub const DEFAULT_SUBSCRIPTION_BUFFER: usize = 1024;
+
+/// Determines what happens to subscription messages if a client can't keep up with them.
+///
+/// # Example
+///
+/// ```
+/// use reth_rpc_server_types::SubscriptionBackpressure;
+/// let policy: SubscriptionBackpressure = "buffer:64".parse().unwrap();
+/// assert_eq!(policy, SubscriptionBackpressure::Buffer(64));
+/// let policy: SubscriptionBackpressure = "drop-oldest".parse().unwrap();
+/// assert_eq!(policy, SubscriptionBackpressure::DropOldest);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SubscriptionBackpressure {
+    /// Buffers up to the given number of messages and stops reading new events until the client
+    /// caught up.
+    Buffer(usize),
+    /// Buffers up to the capacity of the subscription and drops the oldest buffered messages if
+    /// the client can't keep up.
+    ///
+    /// The client is notified about the number of dropped messages with a separate
+    /// [`DROPPED_SUBSCRIPTION_MESSAGES_METHOD`] notification.
+    DropOldest,
+    /// Closes the subscription with an error as soon as the client can't keep up.
+    Disconnect,
+}
+
+impl Default for SubscriptionBackpressure {
+    fn default() -> Self {
+        Self::Buffer(DEFAULT_SUBSCRIPTION_BUFFER)
+    }
+}
+
+impl FromStr for SubscriptionBackpressure {
+    type Err = ParseSubscriptionBackpressureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "drop-oldest" | "drop_oldest" => Ok(Self::DropOldest),
+            "disconnect" => Ok(Self::Disconnect),
+            "buffer" => Ok(Self::default()),
+            _ => s
+                .strip_prefix("buffer:")
+                .and_then(|n| n.parse().ok())
+                .map(Self::Buffer)
+                .ok_or_else(|| ParseSubscriptionBackpressureError(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for SubscriptionBackpressure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Buffer(n) => write!(f, "buffer:{n}"),
+            Self::DropOldest => f.write_str("drop-oldest"),
+            Self::Disconnect => f.write_str("disconnect"),
+        }
+    }
+}
+
+/// Error returned when parsing an invalid [`SubscriptionBackpressure`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSubscriptionBackpressureError(String);
+
+impl fmt::Display for ParseSubscriptionBackpressureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid subscription backpressure policy `{}`, expected one of `buffer:<n>`, `drop-oldest`, `disconnect`",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseSubscriptionBackpressureError {}
+
+/// Method of the notification that is sent to a subscriber if messages were dropped because it
+/// couldn't keep up.
+///
+/// The notification is sent in place of the dropped messages, its result is a
+/// [`DroppedSubscriptionMessages`].
+pub const DROPPED_SUBSCRIPTION_MESSAGES_METHOD: &str = "reth_subscriptionDropped";
+
+/// Result of the [`DROPPED_SUBSCRIPTION_MESSAGES_METHOD`] notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DroppedSubscriptionMessages {
+    /// Number of messages that were dropped since the last delivered message.
+    pub dropped: usize,
+}
+
+/// The kind of an `eth_subscribe` subscription.
+///
+/// Extends the standard [`SubscriptionKind`]s with reth specific kinds.
+///
+/// # Example
+///
+/// ```
+/// use reth_rpc_server_types::RethSubscriptionKind;
+/// let kind: RethSubscriptionKind = serde_json::from_str("\"blobSidecars\"").unwrap();
+/// assert_eq!(kind, RethSubscriptionKind::BlobSidecars);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RethSubscriptionKind {
+    /// A standard `eth_subscribe` kind.
+    Eth(SubscriptionKind),
+    /// Sidecars of blob transactions that are added to the transaction pool's blob store.
+    BlobSidecars,
+    /// The state changes of new canonical blocks.
+    StateChanges,
+}
+
+impl RethSubscriptionKind {
+    const BLOB_SIDECARS: &'static str = "blobSidecars";
+    const STATE_CHANGES: &'static str = "stateChanges";
+}
+
+impl From<SubscriptionKind> for RethSubscriptionKind {
+    fn from(kind: SubscriptionKind) -> Self {
+        Self::Eth(kind)
+    }
+}
+
+impl Serialize for RethSubscriptionKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Eth(kind) => kind.serialize(serializer),
+            Self::BlobSidecars => serializer.serialize_str(Self::BLOB_SIDECARS),
+            Self::StateChanges => serializer.serialize_str(Self::STATE_CHANGES),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RethSubscriptionKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let kind = String::deserialize(deserializer)?;
+        match kind.as_str() {
+            Self::BLOB_SIDECARS => return Ok(Self::BlobSidecars),
+            Self::STATE_CHANGES => return Ok(Self::StateChanges),
+            _ => {}
+        }
+        SubscriptionKind::deserialize(kind.into_deserializer()).map(Self::Eth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backpressure_roundtrip() {
+        for policy in [
+            SubscriptionBackpressure::Buffer(10),
+            SubscriptionBackpressure::DropOldest,
+            SubscriptionBackpressure::Disconnect,
+        ] {
+            assert_eq!(policy.to_string().parse::<SubscriptionBackpressure>().unwrap(), policy);
+        }
+        assert!("buffer:x".parse::<SubscriptionBackpressure>().is_err());
+    }
+
+    #[test]
+    fn subscription_kind_serde() {
+        for kind in [
+            RethSubscriptionKind::Eth(SubscriptionKind::NewHeads),
+            RethSubscriptionKind::Eth(SubscriptionKind::NewPendingTransactions),
+            RethSubscriptionKind::BlobSidecars,
+            RethSubscriptionKind::StateChanges,
+        ] {
+            let json = serde_json::to_string(&kind).unwrap();
+            assert_eq!(serde_json::from_str::<RethSubscriptionKind>(&json).unwrap(), kind);
+        }
+        assert_eq!(
+            serde_json::from_str::<RethSubscriptionKind>("\"logs\"").unwrap(),
+            RethSubscriptionKind::Eth(SubscriptionKind::Logs)
+        );
+        assert!(serde_json::from_str::<RethSubscriptionKind>("\"blobs\"").is_err());
+    }
+}
@@ -0,0 +1,157 @@
+//! RPC configuration that can be changed while the node is running, see
+//! `admin_updateRpcConfig`.
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::RethRpcModule;
+
+/// Tracks which of the served RPC namespaces are enabled.
+///
+/// A namespace is served if it is configured on any transport. Served namespaces can be disabled
+/// and enabled again at runtime, calls to methods of a disabled namespace fail as if the method
+/// did not exist.
+///
+/// Clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct RpcModuleToggles {
+    inner: Arc<RwLock<ModuleStates>>,
+}
+
+impl RpcModuleToggles {
+    /// Marks the namespace as served.
+    pub fn serve(&self, module: RethRpcModule) {
+        self.inner.write().unwrap_or_else(PoisonError::into_inner).served.insert(module);
+    }
+
+    /// Returns `true` if the namespace is served by any transport.
+    pub fn is_served(&self, module: RethRpcModule) -> bool {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner).served.contains(&module)
+    }
+
+    /// Returns `true` if the namespace was not disabled.
+    pub fn is_enabled(&self, module: RethRpcModule) -> bool {
+        !self.inner.read().unwrap_or_else(PoisonError::into_inner).disabled.contains(&module)
+    }
+
+    /// Enables or disables the namespace.
+    pub fn set_enabled(&self, module: RethRpcModule, enabled: bool) {
+        let mut states = self.inner.write().unwrap_or_else(PoisonError::into_inner);
+        if enabled {
+            states.disabled.remove(&module);
+        } else {
+            states.disabled.insert(module);
+        }
+    }
+
+    /// Returns all served namespaces that are enabled.
+    pub fn enabled(&self) -> Vec<RethRpcModule> {
+        let states = self.inner.read().unwrap_or_else(PoisonError::into_inner);
+        RethRpcModule::all_variants()
+            .iter()
+            .copied()
+            .filter(|module| states.served.contains(module) && !states.disabled.contains(module))
+            .collect()
+    }
+
+    /// Returns all served namespaces that are disabled.
+    pub fn disabled(&self) -> Vec<RethRpcModule> {
+        let states = self.inner.read().unwrap_or_else(PoisonError::into_inner);
+        RethRpcModule::all_variants()
+            .iter()
+            .copied()
+            .filter(|module| states.served.contains(module) && states.disabled.contains(module))
+            .collect()
+    }
+}
+
+/// Container type for [`RpcModuleToggles`].
+#[derive(Debug, Default)]
+struct ModuleStates {
+    served: HashSet<RethRpcModule>,
+    disabled: HashSet<RethRpcModule>,
+}
+
+/// Changes to the RPC configuration, applied by `admin_updateRpcConfig`.
+///
+/// Fields that are not set are left unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcConfigUpdate {
+    /// Served namespaces to enable again.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enable: Vec<RethRpcModule>,
+    /// Served namespaces to disable.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disable: Vec<RethRpcModule>,
+    /// Gas limit for `eth_call` and call tracing RPC methods.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_cap: Option<u64>,
+    /// Maximum number of logs that can be returned in a single `eth_getLogs` response.
+    ///
+    /// `0` removes the limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_logs_per_response: Option<usize>,
+    /// Maximum number of tracing requests that are executed concurrently.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tracing_requests: Option<usize>,
+}
+
+/// The RPC configuration that can be changed while the node is running.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcRuntimeConfig {
+    /// The served namespaces that are enabled.
+    pub enabled: Vec<RethRpcModule>,
+    /// The served namespaces that are disabled.
+    pub disabled: Vec<RethRpcModule>,
+    /// Gas limit for `eth_call` and call tracing RPC methods.
+    pub gas_cap: u64,
+    /// Maximum number of logs that can be returned in a single `eth_getLogs` response.
+    ///
+    /// `None` if unlimited.
+    pub max_logs_per_response: Option<usize>,
+    /// Maximum number of tracing requests that are executed concurrently.
+    pub max_tracing_requests: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_served_modules() {
+        let toggles = RpcModuleToggles::default();
+        toggles.serve(RethRpcModule::Eth);
+        toggles.serve(RethRpcModule::Debug);
+        assert!(!toggles.is_served(RethRpcModule::Trace));
+
+        let shared = toggles.clone();
+        std::thread::spawn(move || shared.set_enabled(RethRpcModule::Debug, false)).join().unwrap();
+        assert!(!toggles.is_enabled(RethRpcModule::Debug));
+        assert_eq!(toggles.enabled(), vec![RethRpcModule::Eth]);
+        assert_eq!(toggles.disabled(), vec![RethRpcModule::Debug]);
+
+        toggles.set_enabled(RethRpcModule::Debug, true);
+        assert_eq!(toggles.enabled(), vec![RethRpcModule::Debug, RethRpcModule::Eth]);
+        assert!(toggles.disabled().is_empty());
+    }
+
+    #[test]
+    fn update_serde() {
+        let update: RpcConfigUpdate =
+            serde_json::from_str(r#"{"disable":["debug"],"gasCap":1000}"#).unwrap();
+        assert_eq!(
+            update,
+            RpcConfigUpdate {
+                disable: vec![RethRpcModule::Debug],
+                gas_cap: Some(1000),
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,20 @@
+//! State snapshots created by `reth_createStateSnapshot`.
+
+use alloy_primitives::{B256, U64};
+use serde::{Deserialize, Serialize};
+
+/// A state view that is pinned by the node, so that calls executed on it with
+/// `reth_callOnSnapshot` see the same state, even if the chain advances or reorgs in between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateSnapshotInfo {
+    /// Identifier of the snapshot.
+    pub id: U64,
+    /// Number of the block whose environment the calls are executed in.
+    pub block_number: u64,
+    /// Hash of the block whose state is pinned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<B256>,
+    /// Unix timestamp in seconds after which the snapshot is released.
+    pub expires_at: u64,
+}
@@ -0,0 +1,117 @@
+//! State changes returned by `reth_getStateChanges` and the `stateChanges` subscription.
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use reth_primitives::{Account, KECCAK_EMPTY};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The accounts, storage slots and code changed by a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStateChanges {
+    /// Hash of the block.
+    pub block_hash: B256,
+    /// Number of the block.
+    pub block_number: u64,
+    /// The changed accounts.
+    pub accounts: BTreeMap<Address, AccountStateChange>,
+}
+
+/// How an account was changed by a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AccountChangeKind {
+    /// The account didn't exist before the block.
+    Created,
+    /// The account existed before and after the block.
+    Updated,
+    /// The account doesn't exist after the block.
+    Deleted,
+}
+
+/// The state of an account before or after a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountState {
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The hash of the code of the account.
+    pub code_hash: B256,
+}
+
+impl From<Account> for AccountState {
+    fn from(account: Account) -> Self {
+        Self {
+            nonce: account.nonce,
+            balance: account.balance,
+            code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+        }
+    }
+}
+
+/// The change of a single account by a block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStateChange {
+    /// How the account was changed.
+    pub kind: AccountChangeKind,
+    /// The account before the block, `None` if it didn't exist.
+    pub before: Option<AccountState>,
+    /// The account after the block, `None` if it doesn't exist.
+    pub after: Option<AccountState>,
+    /// The changed storage slots.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<B256, StorageChange>,
+    /// The code deployed to the account by the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+}
+
+impl AccountStateChange {
+    /// Creates the change of an account from its state before and after the block.
+    pub const fn new(before: Option<AccountState>, after: Option<AccountState>) -> Self {
+        let kind = match (&before, &after) {
+            (None, Some(_)) => AccountChangeKind::Created,
+            (Some(_), None) => AccountChangeKind::Deleted,
+            _ => AccountChangeKind::Updated,
+        };
+        Self { kind, before, after, storage: BTreeMap::new(), code: None }
+    }
+
+    /// Returns `true` if neither the account nor its storage changed.
+    pub fn is_unchanged(&self) -> bool {
+        self.before == self.after && self.storage.is_empty() && self.code.is_none()
+    }
+}
+
+/// The change of a single storage slot by a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageChange {
+    /// The value before the block.
+    pub before: U256,
+    /// The value after the block.
+    pub after: U256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_change_kind() {
+        let account = AccountState { nonce: 1, balance: U256::from(2), code_hash: B256::ZERO };
+        assert_eq!(AccountStateChange::new(None, Some(account)).kind, AccountChangeKind::Created);
+        assert_eq!(AccountStateChange::new(Some(account), None).kind, AccountChangeKind::Deleted);
+
+        let change = AccountStateChange::new(Some(account), Some(account));
+        assert_eq!(change.kind, AccountChangeKind::Updated);
+        assert!(change.is_unchanged());
+
+        let json = serde_json::to_value(AccountStateChange::new(None, Some(account))).unwrap();
+        assert_eq!(json["kind"], "created");
+        assert!(json.get("storage").is_none());
+    }
+}
@@ -0,0 +1,43 @@
+//! Value history returned by `reth_getBalanceHistory` and `reth_getStorageHistory`.
+
+use crate::state_changes::AccountState;
+use alloy_primitives::U256;
+use serde::{Deserialize, Serialize};
+
+/// The number of entries returned by a history query if no limit is given.
+pub const DEFAULT_STATE_HISTORY_LIMIT: usize = 100;
+
+/// The maximum number of entries returned by a history query.
+pub const MAX_STATE_HISTORY_LIMIT: usize = 1000;
+
+/// A page of the value history of an account or a storage slot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateHistory<T> {
+    /// The blocks that changed the value, in ascending order, with the value after each block.
+    pub entries: Vec<T>,
+    /// The block to continue the query from, if the requested range contains more entries than
+    /// returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_block: Option<u64>,
+}
+
+/// An account after a block that changed it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountHistoryEntry {
+    /// The number of the block.
+    pub block_number: u64,
+    /// The account after the block, `None` if it doesn't exist.
+    pub account: Option<AccountState>,
+}
+
+/// A storage slot after a block that changed it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageHistoryEntry {
+    /// The number of the block.
+    pub block_number: u64,
+    /// The value of the slot after the block.
+    pub value: U256,
+}
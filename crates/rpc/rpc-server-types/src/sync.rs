@@ -0,0 +1,141 @@
+//! Sync status returned by `eth_syncing` and the `syncing` subscription.
+//!
+//! These extend the standard responses with the progress of the individual sync stages, and are
+//! otherwise compatible with them.
+
+use reth_rpc_types::{pubsub, quantity};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The progress of a sync stage.
+///
+/// Extends [`reth_rpc_types::Stage`] with the target and the progress of the stage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StageProgress {
+    /// The name of the stage.
+    pub name: String,
+    /// The highest block processed by the stage.
+    #[serde(with = "quantity")]
+    pub block: u64,
+    /// The block the stage is syncing to, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "quantity::opt")]
+    pub target: Option<u64>,
+    /// The number of entities processed by the stage, if the stage reports its progress.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub processed: Option<u64>,
+    /// The total number of entities the stage processes, if the stage reports its progress.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+    /// The estimated number of seconds until the stage reaches its target, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eta: Option<u64>,
+}
+
+/// Sync info returned by `eth_syncing` while the node is syncing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncInfo {
+    /// The standard sync info.
+    ///
+    /// Its stages are replaced by [`Self::stages`].
+    #[serde(flatten)]
+    pub info: reth_rpc_types::SyncInfo,
+    /// The progress of the sync stages.
+    #[serde(default)]
+    pub stages: Vec<StageProgress>,
+}
+
+/// The response of `eth_syncing`, `false` if the node isn't syncing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncStatus {
+    /// The node is syncing.
+    Info(Box<SyncInfo>),
+    /// The node isn't syncing.
+    None,
+}
+
+impl Serialize for SyncStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Info(info) => info.serialize(serializer),
+            Self::None => serializer.serialize_bool(false),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SyncStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Status {
+            Info(Box<SyncInfo>),
+            Syncing(bool),
+        }
+
+        match Status::deserialize(deserializer)? {
+            Status::Info(info) => Ok(Self::Info(info)),
+            Status::Syncing(false) => Ok(Self::None),
+            Status::Syncing(true) => {
+                Err(serde::de::Error::custom("expected sync info or `false`, got `true`"))
+            }
+        }
+    }
+}
+
+/// Sync status sent by the `syncing` subscription while the node is syncing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncStatusMetadata {
+    /// The standard sync status.
+    #[serde(flatten)]
+    pub metadata: pubsub::SyncStatusMetadata,
+    /// The progress of the sync stages.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stages: Vec<StageProgress>,
+}
+
+/// A message of the `syncing` subscription.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PubSubSyncStatus {
+    /// Whether the node is syncing, sent once the node is done syncing.
+    Simple(bool),
+    /// The sync status while the node is syncing.
+    Detailed(SyncStatusMetadata),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_sync_status() {
+        assert_eq!(serde_json::to_string(&SyncStatus::None).unwrap(), "false");
+        assert_eq!(serde_json::from_str::<SyncStatus>("false").unwrap(), SyncStatus::None);
+
+        let status = SyncStatus::Info(Box::new(SyncInfo {
+            info: reth_rpc_types::SyncInfo {
+                current_block: alloy_primitives::U256::from(10),
+                highest_block: alloy_primitives::U256::from(100),
+                ..Default::default()
+            },
+            stages: vec![StageProgress {
+                name: "Execution".to_string(),
+                block: 10,
+                target: Some(100),
+                processed: Some(1000),
+                total: Some(5000),
+                eta: Some(60),
+            }],
+        }));
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["currentBlock"], "0xa");
+        assert_eq!(json["stages"][0]["block"], "0xa");
+        assert_eq!(json["stages"][0]["target"], "0x64");
+        assert_eq!(json["stages"][0]["eta"], 60);
+        assert_eq!(serde_json::from_value::<SyncStatus>(json).unwrap(), status);
+
+        // the stages are compatible with the standard sync info
+        let info: reth_rpc_types::SyncInfo =
+            serde_json::from_str(&serde_json::to_string(&status).unwrap()).unwrap();
+        assert_eq!(info.stages.unwrap()[0].block, 10);
+    }
+}
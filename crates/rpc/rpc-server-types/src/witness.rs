@@ -0,0 +1,41 @@
+//! Execution witness returned by `reth_callWithWitness`.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use alloy_primitives::{Address, Bytes, B256};
+use serde::{Deserialize, Serialize};
+
+/// Result of a call together with the minimal state witness required to re-execute it.
+///
+/// This allows stateless clients and provers to execute the call without access to the full
+/// state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallWitness {
+    /// Return data of the call, or the revert data if the call reverted.
+    pub output: Bytes,
+    /// Gas used by the call.
+    pub gas_used: u64,
+    /// The error if the call reverted or halted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// All accounts accessed by the call.
+    pub accounts: BTreeMap<Address, AccountWitness>,
+    /// All bytecodes loaded by the call, keyed by code hash.
+    pub codes: BTreeMap<B256, Bytes>,
+    /// Preimages of all trie nodes required to access the accounts and storage slots, keyed by
+    /// node hash.
+    pub state: HashMap<B256, Bytes>,
+}
+
+/// An account accessed by a call.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountWitness {
+    /// The code hash of the account.
+    ///
+    /// `None` if the account does not exist.
+    pub code_hash: Option<B256>,
+    /// The storage slots of the account accessed by the call.
+    pub storage: BTreeSet<B256>,
+}
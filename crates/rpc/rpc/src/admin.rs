@@ -1,19 +1,30 @@
-use std::sync::Arc;
+use std::{str::FromStr, sync::Arc, time::Duration};
 
 use alloy_genesis::ChainConfig;
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
 use reth_chainspec::ChainSpec;
+use reth_db_common::integrity::{IntegrityCheckHandle, IntegrityCheckState};
 use reth_network_api::{NetworkInfo, Peers};
 use reth_network_peers::{id2pk, AnyNode, NodeRecord};
-use reth_network_types::PeerKind;
+use reth_network_types::{InboundFilter, PeerKind};
 use reth_primitives::EthereumHardfork;
 use reth_rpc_api::AdminApiServer;
-use reth_rpc_server_types::ToRpcResult;
+use reth_rpc_eth_types::RpcLimits;
+use reth_rpc_server_types::{
+    integrity::{IntegrityCheckStatus, IntegrityFindingInfo},
+    log_levels::LogLevelOverride,
+    peers::{AdminPeerInfo, PeerForkId, PeerSessionInfo},
+    result::{internal_rpc_err, invalid_params_rpc_err},
+    runtime::{RpcConfigUpdate, RpcModuleToggles, RpcRuntimeConfig},
+    RethRpcModule, ToRpcResult,
+};
 use reth_rpc_types::admin::{
     EthInfo, EthPeerInfo, EthProtocolInfo, NodeInfo, PeerInfo, PeerNetworkInfo, PeerProtocolInfo,
-    Ports, ProtocolInfo,
+    Ports, ProtocolInfo, SnapInfo, SnapPeerInfo,
 };
+use reth_tracing::{tracing::level_filters::LevelFilter, LogFilterHandle};
+use serde_json::json;
 
 /// `admin` API implementation.
 ///
@@ -23,12 +34,73 @@ pub struct AdminApi<N> {
     network: N,
     /// The specification of the blockchain's configuration.
     chain_spec: Arc<ChainSpec>,
+    /// Handle to change the RPC configuration at runtime, if supported.
+    rpc_config: Option<RpcConfigHandle>,
+    /// Handle to change the log levels at runtime, if supported.
+    log_filters: Option<LogFilterHandle>,
+    /// Handle to the background integrity check of the database, if supported.
+    integrity_check: Option<IntegrityCheckHandle>,
 }
 
 impl<N> AdminApi<N> {
     /// Creates a new instance of `AdminApi`.
     pub const fn new(network: N, chain_spec: Arc<ChainSpec>) -> Self {
-        Self { network, chain_spec }
+        Self { network, chain_spec, rpc_config: None, log_filters: None, integrity_check: None }
+    }
+
+    /// Configures the handle that `admin_updateRpcConfig` changes the RPC configuration with.
+    pub fn with_rpc_config(mut self, rpc_config: RpcConfigHandle) -> Self {
+        self.rpc_config = Some(rpc_config);
+        self
+    }
+
+    /// Configures the handle that `admin_setLogLevel` changes the log levels with, if any.
+    pub fn with_log_filters(mut self, log_filters: Option<LogFilterHandle>) -> Self {
+        self.log_filters = log_filters;
+        self
+    }
+
+    /// Configures the handle that `admin_integrityCheck` reads the state of the background
+    /// integrity check from.
+    pub fn with_integrity_check(mut self, integrity_check: IntegrityCheckHandle) -> Self {
+        self.integrity_check = Some(integrity_check);
+        self
+    }
+
+    /// Returns the [`RpcConfigHandle`] or an error if changing the RPC configuration is not
+    /// supported.
+    fn rpc_config_handle(&self) -> RpcResult<&RpcConfigHandle> {
+        self.rpc_config
+            .as_ref()
+            .ok_or_else(|| internal_rpc_err("runtime RPC configuration is not supported"))
+    }
+
+    /// Returns the [`LogFilterHandle`] or an error if changing the log levels is not supported.
+    fn log_filter_handle(&self) -> RpcResult<&LogFilterHandle> {
+        self.log_filters
+            .as_ref()
+            .ok_or_else(|| internal_rpc_err("runtime log levels are not supported"))
+    }
+
+    /// Returns the [`IntegrityCheckHandle`] or an error if the integrity check isn't supported.
+    fn integrity_check_handle(&self) -> RpcResult<&IntegrityCheckHandle> {
+        self.integrity_check
+            .as_ref()
+            .ok_or_else(|| internal_rpc_err("the integrity check is not supported"))
+    }
+
+    /// Returns the log level overrides of all targets.
+    fn log_level_overrides(&self) -> RpcResult<Vec<LogLevelOverride>> {
+        Ok(self
+            .log_filter_handle()?
+            .target_levels()
+            .into_iter()
+            .map(|target| LogLevelOverride {
+                expires_in: target.remaining().map(|remaining| remaining.as_secs()),
+                target: target.target,
+                level: target.level.to_string(),
+            })
+            .collect())
     }
 }
 
@@ -45,7 +117,10 @@ where
 
     /// Handler for `admin_removePeer`
     fn remove_peer(&self, record: AnyNode) -> RpcResult<bool> {
-        self.network.remove_peer(record.peer_id(), PeerKind::Basic);
+        let peer_id = record.peer_id();
+        self.network.remove_peer(peer_id, PeerKind::Basic);
+        // removing the peer from the peer set doesn't close an active session
+        self.network.disconnect_peer(peer_id);
         Ok(true)
     }
 
@@ -65,13 +140,15 @@ where
     }
 
     /// Handler for `admin_peers`
-    async fn peers(&self) -> RpcResult<Vec<PeerInfo>> {
+    async fn peers(&self) -> RpcResult<Vec<AdminPeerInfo>> {
         let peers = self.network.get_all_peers().await.to_rpc_result()?;
         let mut infos = Vec::with_capacity(peers.len());
 
         for peer in peers {
             if let Ok(pk) = id2pk(peer.remote_id) {
-                infos.push(PeerInfo {
+                let protocols = peer_protocols(&peer);
+                let session = peer_session(&peer);
+                let info = PeerInfo {
                     id: pk.to_string(),
                     name: peer.client_version.to_string(),
                     enode: peer.enode,
@@ -89,14 +166,9 @@ where
                         trusted: peer.kind.is_trusted(),
                         static_node: peer.kind.is_static(),
                     },
-                    protocols: PeerProtocolInfo {
-                        eth: Some(EthPeerInfo::Info(EthInfo {
-                            version: peer.status.version as u64,
-                        })),
-                        snap: None,
-                        other: Default::default(),
-                    },
-                })
+                    protocols,
+                };
+                infos.push(AdminPeerInfo { info, session })
             }
         }
 
@@ -176,6 +248,75 @@ where
         })
     }
 
+    /// Handler for `admin_inboundFilter`
+    async fn inbound_filter(&self) -> RpcResult<InboundFilter> {
+        self.network.inbound_filter().await.to_rpc_result()
+    }
+
+    /// Handler for `admin_setInboundFilter`
+    fn set_inbound_filter(&self, filter: InboundFilter) -> RpcResult<bool> {
+        self.network.set_inbound_filter(filter);
+        Ok(true)
+    }
+
+    /// Handler for `admin_rpcConfig`
+    fn rpc_config(&self) -> RpcResult<RpcRuntimeConfig> {
+        Ok(self.rpc_config_handle()?.config())
+    }
+
+    /// Handler for `admin_updateRpcConfig`
+    fn update_rpc_config(&self, update: RpcConfigUpdate) -> RpcResult<RpcRuntimeConfig> {
+        self.rpc_config_handle()?.update(update)
+    }
+
+    /// Handler for `admin_logLevels`
+    fn log_levels(&self) -> RpcResult<Vec<LogLevelOverride>> {
+        self.log_level_overrides()
+    }
+
+    /// Handler for `admin_setLogLevel`
+    fn set_log_level(
+        &self,
+        target: String,
+        level: String,
+        duration: Option<u64>,
+    ) -> RpcResult<Vec<LogLevelOverride>> {
+        let level = LevelFilter::from_str(&level)
+            .map_err(|_| invalid_params_rpc_err(format!("invalid log level `{level}`")))?;
+        self.log_filter_handle()?
+            .set_target_level(&target, level, duration.map(Duration::from_secs))
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))?;
+        self.log_level_overrides()
+    }
+
+    /// Handler for `admin_resetLogLevel`
+    fn reset_log_level(&self, target: Option<String>) -> RpcResult<Vec<LogLevelOverride>> {
+        let log_filters = self.log_filter_handle()?;
+        match target {
+            Some(target) => log_filters.reset_target(&target).map(drop),
+            None => log_filters.reset(),
+        }
+        .map_err(|err| internal_rpc_err(err.to_string()))?;
+        self.log_level_overrides()
+    }
+
+    /// Handler for `admin_integrityCheck`
+    fn integrity_check(&self) -> RpcResult<IntegrityCheckStatus> {
+        let state = self
+            .integrity_check_handle()?
+            .state()
+            .ok_or_else(|| internal_rpc_err("the integrity check is not enabled"))?;
+        Ok(integrity_check_status(state))
+    }
+
+    /// Handler for `admin_resetIntegrityCheck`
+    fn reset_integrity_check(&self) -> RpcResult<bool> {
+        if !self.integrity_check_handle()?.reset() {
+            return Err(internal_rpc_err("the integrity check is not enabled"))
+        }
+        Ok(true)
+    }
+
     /// Handler for `admin_peerEvents`
     async fn subscribe_peer_events(
         &self,
@@ -185,8 +326,399 @@ where
     }
 }
 
+/// Converts the state of the integrity check to its RPC representation.
+fn integrity_check_status(state: IntegrityCheckState) -> IntegrityCheckStatus {
+    IntegrityCheckStatus {
+        next_block: state.next_block,
+        tip: state.tip,
+        completed_scans: state.completed_scans,
+        last_scan_completed_at: state.last_scan_completed_at,
+        total_findings: state.total_findings,
+        findings: state
+            .findings
+            .into_iter()
+            .map(|finding| IntegrityFindingInfo {
+                block: finding.block,
+                check: finding.check.to_string(),
+                message: finding.message,
+            })
+            .collect(),
+    }
+}
+
+/// Returns the protocols of the peer's session.
+///
+/// Besides the negotiated `eth` and `snap` versions, this includes the versions of all other
+/// capabilities.
+fn peer_protocols(peer: &reth_network_api::PeerInfo) -> PeerProtocolInfo {
+    let mut protocols = PeerProtocolInfo {
+        eth: Some(EthPeerInfo::Info(EthInfo { version: peer.eth_version as u64 })),
+        snap: None,
+        other: Default::default(),
+    };
+
+    for cap in peer.capabilities.capabilities() {
+        let version = cap.version as u64;
+        match cap.name.as_ref() {
+            "eth" => {}
+            "snap" => {
+                if !matches!(protocols.snap, Some(SnapPeerInfo::Info(info)) if info.version >= version)
+                {
+                    protocols.snap = Some(SnapPeerInfo::Info(SnapInfo { version }));
+                }
+            }
+            name => {
+                let entry = protocols.other.entry(name.to_string()).or_insert_with(|| json!({}));
+                if entry["version"].as_u64().map_or(true, |known| known < version) {
+                    entry["version"] = version.into();
+                }
+            }
+        }
+    }
+
+    protocols
+}
+
+/// Returns the details of the peer's session, i.e. the `eth` status the peer sent during the
+/// handshake and for how long the session has been established.
+fn peer_session(peer: &reth_network_api::PeerInfo) -> PeerSessionInfo {
+    let status = &peer.status;
+    PeerSessionInfo {
+        network: status.chain.id(),
+        genesis: status.genesis,
+        head: status.blockhash,
+        difficulty: status.total_difficulty,
+        fork_id: PeerForkId { hash: status.forkid.hash.0.into(), next: status.forkid.next },
+        duration: peer.session_established.elapsed().as_secs(),
+    }
+}
+
 impl<N> std::fmt::Debug for AdminApi<N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AdminApi").finish_non_exhaustive()
     }
 }
+
+/// Handle to change the RPC configuration of a running node.
+///
+/// This shares the namespace toggles and request limits with the RPC handlers, so that changes
+/// apply to all requests that are handled afterwards.
+#[derive(Debug, Clone)]
+pub struct RpcConfigHandle {
+    modules: RpcModuleToggles,
+    limits: RpcLimits,
+}
+
+impl RpcConfigHandle {
+    /// Creates a new handle for the given namespace toggles and request limits.
+    pub const fn new(modules: RpcModuleToggles, limits: RpcLimits) -> Self {
+        Self { modules, limits }
+    }
+
+    /// Returns the namespace toggles.
+    pub const fn modules(&self) -> &RpcModuleToggles {
+        &self.modules
+    }
+
+    /// Returns the request limits.
+    pub const fn limits(&self) -> &RpcLimits {
+        &self.limits
+    }
+
+    /// Returns the current configuration.
+    pub fn config(&self) -> RpcRuntimeConfig {
+        let max_logs_per_response = self.limits.max_logs_per_response();
+        RpcRuntimeConfig {
+            enabled: self.modules.enabled(),
+            disabled: self.modules.disabled(),
+            gas_cap: self.limits.gas_cap(),
+            max_logs_per_response: (max_logs_per_response != usize::MAX)
+                .then_some(max_logs_per_response),
+            max_tracing_requests: self.limits.max_tracing_requests(),
+        }
+    }
+
+    /// Applies the update and returns the updated configuration.
+    ///
+    /// The update is validated first, if it is invalid nothing is changed.
+    pub fn update(&self, update: RpcConfigUpdate) -> Result<RpcRuntimeConfig, ErrorObjectOwned> {
+        let RpcConfigUpdate {
+            enable,
+            disable,
+            gas_cap,
+            max_logs_per_response,
+            max_tracing_requests,
+        } = update;
+
+        for module in enable.iter().chain(&disable) {
+            if !self.modules.is_served(*module) {
+                return Err(invalid_params_rpc_err(format!(
+                    "namespace {module} is not served, serving it requires a restart"
+                )))
+            }
+        }
+        if let Some(module) = enable.iter().find(|module| disable.contains(module)) {
+            return Err(invalid_params_rpc_err(format!(
+                "namespace {module} can't be enabled and disabled at once"
+            )))
+        }
+        if disable.contains(&RethRpcModule::Admin) {
+            return Err(invalid_params_rpc_err("the admin namespace can't be disabled"))
+        }
+        if max_tracing_requests == Some(0) {
+            return Err(invalid_params_rpc_err("max tracing requests must be at least 1"))
+        }
+
+        for module in enable {
+            self.modules.set_enabled(module, true);
+        }
+        for module in disable {
+            self.modules.set_enabled(module, false);
+        }
+        if let Some(gas_cap) = gas_cap {
+            self.limits.set_gas_cap(gas_cap);
+        }
+        if let Some(max_logs) = max_logs_per_response {
+            // 0 removes the limit, same as on the command line
+            self.limits.set_max_logs_per_response(if max_logs == 0 {
+                usize::MAX
+            } else {
+                max_logs
+            });
+        }
+        if let Some(max_requests) = max_tracing_requests {
+            self.limits.set_max_tracing_requests(max_requests);
+        }
+
+        Ok(self.config())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{B256, U256};
+    use reth_chainspec::MAINNET;
+    use reth_eth_wire_types::{capability::Capability, EthVersion, Status};
+    use reth_network_api::{
+        noop::NoopNetwork, Direction, NetworkError, NetworkStatus, PeerId, PeersInfo,
+    };
+    use reth_network_types::{ConnectionsConfig, Reputation, ReputationChangeKind};
+    use reth_primitives::{ForkHash, ForkId, TxHash};
+    use std::{
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+        time::Instant,
+    };
+
+    /// A network that serves a fixed set of peers and records the calls that change the peer set.
+    #[derive(Debug, Default)]
+    struct TestNetwork {
+        peers: Vec<reth_network_api::PeerInfo>,
+        removed: Mutex<Vec<(PeerId, PeerKind)>>,
+        disconnected: Mutex<Vec<PeerId>>,
+    }
+
+    impl NetworkInfo for TestNetwork {
+        fn local_addr(&self) -> SocketAddr {
+            NoopNetwork::default().local_addr()
+        }
+
+        async fn network_status(&self) -> Result<NetworkStatus, NetworkError> {
+            NoopNetwork::default().network_status().await
+        }
+
+        fn chain_id(&self) -> u64 {
+            1
+        }
+
+        fn is_syncing(&self) -> bool {
+            false
+        }
+
+        fn is_initially_syncing(&self) -> bool {
+            false
+        }
+    }
+
+    impl PeersInfo for TestNetwork {
+        fn num_connected_peers(&self) -> usize {
+            self.peers.len()
+        }
+
+        fn local_node_record(&self) -> NodeRecord {
+            NoopNetwork::default().local_node_record()
+        }
+
+        fn local_enr(&self) -> reth_network_peers::Enr<secp256k1::SecretKey> {
+            NoopNetwork::default().local_enr()
+        }
+    }
+
+    impl Peers for TestNetwork {
+        fn add_trusted_peer_id(&self, _peer: PeerId) {}
+
+        fn add_peer_kind(
+            &self,
+            _peer: PeerId,
+            _kind: PeerKind,
+            _tcp_addr: SocketAddr,
+            _udp_addr: Option<SocketAddr>,
+        ) {
+        }
+
+        async fn get_peers_by_kind(
+            &self,
+            kind: PeerKind,
+        ) -> Result<Vec<reth_network_api::PeerInfo>, NetworkError> {
+            Ok(self.peers.iter().filter(|peer| peer.kind == kind).cloned().collect())
+        }
+
+        async fn get_all_peers(&self) -> Result<Vec<reth_network_api::PeerInfo>, NetworkError> {
+            Ok(self.peers.clone())
+        }
+
+        async fn get_peer_by_id(
+            &self,
+            peer_id: PeerId,
+        ) -> Result<Option<reth_network_api::PeerInfo>, NetworkError> {
+            Ok(self.peers.iter().find(|peer| peer.remote_id == peer_id).cloned())
+        }
+
+        async fn get_peers_by_id(
+            &self,
+            peer_ids: Vec<PeerId>,
+        ) -> Result<Vec<reth_network_api::PeerInfo>, NetworkError> {
+            Ok(self
+                .peers
+                .iter()
+                .filter(|peer| peer_ids.contains(&peer.remote_id))
+                .cloned()
+                .collect())
+        }
+
+        fn remove_peer(&self, peer: PeerId, kind: PeerKind) {
+            self.removed.lock().unwrap().push((peer, kind));
+        }
+
+        fn disconnect_peer(&self, peer: PeerId) {
+            self.disconnected.lock().unwrap().push(peer);
+        }
+
+        fn disconnect_peer_with_reason(
+            &self,
+            peer: PeerId,
+            _reason: reth_eth_wire_types::DisconnectReason,
+        ) {
+            self.disconnected.lock().unwrap().push(peer);
+        }
+
+        fn reputation_change(&self, _peer_id: PeerId, _kind: ReputationChangeKind) {}
+
+        async fn reputation_by_id(
+            &self,
+            _peer_id: PeerId,
+        ) -> Result<Option<Reputation>, NetworkError> {
+            Ok(None)
+        }
+
+        fn set_connection_limits(&self, _limits: ConnectionsConfig) {}
+
+        async fn inbound_filter(&self) -> Result<InboundFilter, NetworkError> {
+            Ok(Default::default())
+        }
+
+        fn set_inbound_filter(&self, _filter: InboundFilter) {}
+
+        async fn transaction_propagation(
+            &self,
+            _hash: TxHash,
+        ) -> Result<Option<reth_network_types::TransactionPropagation>, NetworkError> {
+            Ok(None)
+        }
+    }
+
+    fn peer(peer_id: PeerId, capabilities: Vec<Capability>) -> reth_network_api::PeerInfo {
+        let status = Status {
+            total_difficulty: U256::from(17u64),
+            blockhash: B256::repeat_byte(0xbb),
+            genesis: B256::repeat_byte(0xaa),
+            forkid: ForkId { hash: ForkHash([0xfc, 0x64, 0xec, 0x04]), next: 1_150_000 },
+            ..Default::default()
+        };
+        reth_network_api::PeerInfo {
+            capabilities: Arc::new(capabilities.into()),
+            remote_id: peer_id,
+            client_version: "reth/v1.0.0".into(),
+            enode: format!("enode://{peer_id:x}@127.0.0.1:30303"),
+            enr: None,
+            remote_addr: "127.0.0.1:30303".parse().unwrap(),
+            local_addr: None,
+            direction: Direction::Incoming,
+            eth_version: EthVersion::Eth68,
+            status: Arc::new(status),
+            block_range: None,
+            session_established: Instant::now(),
+            kind: PeerKind::Basic,
+        }
+    }
+
+    #[test]
+    fn protocols_include_the_highest_version_of_each_capability() {
+        let peer = peer(
+            PeerId::random(),
+            vec![
+                Capability::new_static("eth", 67),
+                Capability::new_static("eth", 68),
+                Capability::new_static("snap", 1),
+                Capability::new_static("bsc", 2),
+                Capability::new_static("bsc", 1),
+            ],
+        );
+
+        let protocols = peer_protocols(&peer);
+        assert_eq!(protocols.eth, Some(EthPeerInfo::Info(EthInfo { version: 68 })));
+        assert_eq!(protocols.snap, Some(SnapPeerInfo::Info(SnapInfo { version: 1 })));
+        assert_eq!(protocols.other.len(), 1);
+        assert_eq!(protocols.other["bsc"], json!({ "version": 2 }));
+    }
+
+    #[test]
+    fn session_reports_the_status_of_the_handshake() {
+        let peer = peer(PeerId::random(), vec![Capability::new_static("eth", 68)]);
+
+        let session = peer_session(&peer);
+        assert_eq!(session.network, 1);
+        assert_eq!(session.genesis, B256::repeat_byte(0xaa));
+        assert_eq!(session.head, B256::repeat_byte(0xbb));
+        assert_eq!(session.difficulty, U256::from(17u64));
+        assert_eq!(session.fork_id.hash.0, [0xfc, 0x64, 0xec, 0x04]);
+        assert_eq!(session.fork_id.next, 1_150_000);
+    }
+
+    #[tokio::test]
+    async fn peers_report_session_separately() {
+        let sk = secp256k1::SecretKey::from_slice(&[0xcd; 32]).unwrap();
+        let peer_id = reth_network_peers::pk2id(&sk.public_key(secp256k1::SECP256K1));
+        let network = TestNetwork {
+            peers: vec![peer(peer_id, vec![Capability::new_static("eth", 68)])],
+            ..Default::default()
+        };
+        let api = AdminApi::new(network, MAINNET.clone());
+
+        let peers = AdminApiServer::peers(&api).await.unwrap();
+        assert_eq!(peers.len(), 1);
+        assert!(peers[0].info.protocols.other.is_empty());
+        assert_eq!(peers[0].session.head, B256::repeat_byte(0xbb));
+    }
+
+    #[test]
+    fn remove_peer_disconnects() {
+        let peer_id = PeerId::random();
+        let api = AdminApi::new(TestNetwork::default(), MAINNET.clone());
+
+        assert!(AdminApiServer::remove_peer(&api, AnyNode::PeerId(peer_id)).unwrap());
+        assert_eq!(*api.network.removed.lock().unwrap(), vec![(peer_id, PeerKind::Basic)]);
+        assert_eq!(*api.network.disconnected.lock().unwrap(), vec![peer_id]);
+    }
+}
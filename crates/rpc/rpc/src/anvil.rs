@@ -0,0 +1,792 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use alloy_dyn_abi::TypedData;
+use async_trait::async_trait;
+use jsonrpsee::{
+    core::RpcResult,
+    types::{error::ErrorCode, ErrorObjectOwned},
+    RpcModule,
+};
+use parking_lot::{Mutex, RwLock};
+use reth_auto_seal_consensus::MiningHandle;
+use reth_chainspec::{ChainSpecProvider, Head};
+use reth_evm::state_changes::AccountChange;
+use reth_network_api::NetworkInfo;
+use reth_primitives::{
+    Address, BlockId, BlockNumHash, BlockNumberOrTag, Bytes, Signature, TransactionSigned, B256,
+    U256,
+};
+use reth_provider::BlockReaderIdExt;
+use reth_rpc_api::AnvilApiServer;
+use reth_rpc_eth_api::helpers::{
+    signer::Result as SignResult, EthApiSpec, EthBlocks, EthCall, EthFees, EthSigner, EthState,
+    EthTransactions, FullEthApi, LoadTransaction,
+};
+use reth_rpc_eth_types::{utils::recover_raw_transaction, SignError};
+use reth_rpc_server_types::result::{
+    internal_rpc_err, invalid_params_rpc_err, rpc_error_with_code, ToRpcResult,
+};
+use reth_rpc_types::{
+    anvil::{
+        ForkedNetwork, Forking, Metadata, MineOptions, NodeEnvironment, NodeForkConfig, NodeInfo,
+    },
+    serde_helpers::JsonStorageKey,
+    state::{AccountOverride, StateOverride},
+    Block, TransactionRequest, TypedTransactionRequest,
+};
+use reth_rpc_types_compat::transaction::to_primitive_transaction;
+use reth_transaction_pool::TransactionPool;
+use secp256k1::SecretKey;
+
+use crate::evm::mining_rpc_err;
+
+/// `anvil` API implementation.
+///
+/// This type changes the state of the chain of a dev node, so that test suites written for Anvil or
+/// Hardhat run against it. State changes are made to the pending state, which is applied before
+/// the transactions of the next mined block, and reverting to a snapshot unwinds the chain.
+///
+/// Methods that depend on features a dev node doesn't have are not supported: changing the chain
+/// ID, the fork or the minimum gas price, resetting to a fork, dumping and loading the state,
+/// toggling logging and enabling traces.
+pub struct AnvilApi<Provider, Eth> {
+    inner: Arc<AnvilApiInner<Provider, Eth>>,
+}
+
+impl<Provider, Eth> AnvilApi<Provider, Eth>
+where
+    Eth: EthApiSpec,
+{
+    /// Creates a new instance of `AnvilApi`.
+    ///
+    /// This adds a signer for the impersonated accounts to the `eth` API, which signs their
+    /// transactions with a throwaway key.
+    pub fn new(
+        provider: Provider,
+        eth_api: Eth,
+        mining: MiningHandle,
+        fork: Option<AnvilFork>,
+    ) -> Self {
+        let impersonated = ImpersonatedAccounts::default();
+        eth_api.signers().write().push(Box::new(ImpersonationSigner::new(impersonated.clone())));
+        let inner = AnvilApiInner {
+            provider,
+            eth_api,
+            mining,
+            fork,
+            instance_id: B256::random(),
+            impersonated,
+            snapshots: Default::default(),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+}
+
+impl<Provider, Eth> AnvilApi<Provider, Eth>
+where
+    Provider: BlockReaderIdExt + 'static,
+    Eth: FullEthApi + 'static,
+{
+    /// Handler for `eth_sendTransaction`.
+    ///
+    /// Transactions that are sent for impersonated accounts, or for accounts that are changed in
+    /// the pending state, bypass the pool and are mined right away. All other transactions are sent
+    /// by the `eth` API.
+    async fn send_transaction(&self, mut request: TransactionRequest) -> RpcResult<B256> {
+        let from = match request.from {
+            Some(from) if self.bypasses_pool(&from) => from,
+            _ => {
+                return EthTransactions::send_transaction(&self.inner.eth_api, request)
+                    .await
+                    .map_err(Into::into)
+            }
+        };
+
+        // the nonce and the gas limit are filled from the pending state, which the `eth` API
+        // doesn't see
+        let change = self.inner.mining.pending_change(&from);
+        if request.nonce.is_none() {
+            request.nonce = change.and_then(|change| change.nonce);
+        }
+        if request.gas.is_none() {
+            let gas = EthCall::estimate_gas_at(
+                &self.inner.eth_api,
+                request.clone(),
+                BlockId::pending(),
+                Some(self.pending_state_override()),
+            )
+            .await
+            .map_err(Into::into)?;
+            request.gas = Some(gas.saturating_to());
+        }
+
+        let transaction = EthTransactions::sign_transaction(&self.inner.eth_api, request)
+            .await
+            .map_err(Into::into)?;
+        let hash = transaction.hash();
+        self.inner
+            .mining
+            .send_transaction(transaction.with_signer(from))
+            .await
+            .map_err(mining_rpc_err)?;
+        Ok(hash)
+    }
+
+    /// Handler for `eth_sendRawTransaction`.
+    ///
+    /// Transactions of accounts that are changed in the pending state bypass the pool and are mined
+    /// right away, all other transactions are sent by the `eth` API.
+    async fn send_raw_transaction(&self, tx: Bytes) -> RpcResult<B256> {
+        let recovered = recover_raw_transaction(tx.clone())?;
+        if self.inner.mining.pending_change(&recovered.signer()).is_none() {
+            return EthTransactions::send_raw_transaction(&self.inner.eth_api, tx)
+                .await
+                .map_err(Into::into)
+        }
+
+        let transaction = recovered.into_ecrecovered_transaction();
+        let hash = transaction.hash();
+        self.inner.mining.send_transaction(transaction).await.map_err(mining_rpc_err)?;
+        Ok(hash)
+    }
+
+    /// Handler for `eth_getBalance`, which includes the pending state.
+    async fn balance(&self, address: Address, block_id: Option<BlockId>) -> RpcResult<U256> {
+        if let Some(balance) = self.pending_change(&address, block_id).and_then(|c| c.balance) {
+            return Ok(balance)
+        }
+        EthState::balance(&self.inner.eth_api, address, block_id).await.map_err(Into::into)
+    }
+
+    /// Handler for `eth_getTransactionCount`, which includes the pending state.
+    async fn transaction_count(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<U256> {
+        if let Some(nonce) = self.pending_change(&address, block_id).and_then(|c| c.nonce) {
+            return Ok(U256::from(nonce))
+        }
+        EthState::transaction_count(&self.inner.eth_api, address, block_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Handler for `eth_getCode`, which includes the pending state.
+    async fn code(&self, address: Address, block_id: Option<BlockId>) -> RpcResult<Bytes> {
+        if let Some(code) = self.pending_change(&address, block_id).and_then(|c| c.code) {
+            return Ok(code)
+        }
+        EthState::get_code(&self.inner.eth_api, address, block_id).await.map_err(Into::into)
+    }
+
+    /// Handler for `eth_getStorageAt`, which includes the pending state.
+    async fn storage_at(
+        &self,
+        address: Address,
+        index: JsonStorageKey,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<B256> {
+        let slot = U256::from_be_bytes(index.0 .0);
+        let pending = self.pending_change(&address, block_id);
+        if let Some(value) = pending.and_then(|change| change.storage.get(&slot).copied()) {
+            return Ok(B256::from(value))
+        }
+        EthState::storage_at(&self.inner.eth_api, address, index, block_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Returns the `eth` methods that include the pending state and send the transactions of
+    /// impersonated accounts, to replace the methods of the `eth` namespace.
+    pub fn eth_module(&self) -> RpcModule<Self> {
+        let mut module = RpcModule::new(self.clone());
+        module
+            .register_async_method("eth_sendTransaction", |params, api, _| async move {
+                let request = params.one::<TransactionRequest>()?;
+                api.send_transaction(request).await
+            })
+            .expect("method is not registered yet");
+        module
+            .register_async_method("eth_sendRawTransaction", |params, api, _| async move {
+                let tx = params.one::<Bytes>()?;
+                api.send_raw_transaction(tx).await
+            })
+            .expect("method is not registered yet");
+        module
+            .register_async_method("eth_getBalance", |params, api, _| async move {
+                let mut params = params.sequence();
+                let address = params.next::<Address>()?;
+                api.balance(address, params.optional_next()?).await
+            })
+            .expect("method is not registered yet");
+        module
+            .register_async_method("eth_getTransactionCount", |params, api, _| async move {
+                let mut params = params.sequence();
+                let address = params.next::<Address>()?;
+                api.transaction_count(address, params.optional_next()?).await
+            })
+            .expect("method is not registered yet");
+        module
+            .register_async_method("eth_getCode", |params, api, _| async move {
+                let mut params = params.sequence();
+                let address = params.next::<Address>()?;
+                api.code(address, params.optional_next()?).await
+            })
+            .expect("method is not registered yet");
+        module
+            .register_async_method("eth_getStorageAt", |params, api, _| async move {
+                let mut params = params.sequence();
+                let address = params.next::<Address>()?;
+                let index = params.next::<JsonStorageKey>()?;
+                api.storage_at(address, index, params.optional_next()?).await
+            })
+            .expect("method is not registered yet");
+        module
+    }
+
+    /// Returns `true` if the transactions of the account bypass the pool, because it's impersonated
+    /// or the pool would validate them without the pending state.
+    fn bypasses_pool(&self, address: &Address) -> bool {
+        self.inner.impersonated.contains(address) ||
+            self.inner.mining.pending_change(address).is_some()
+    }
+
+    /// Returns the change of the account in the pending state, if the block is the pending block.
+    fn pending_change(
+        &self,
+        address: &Address,
+        block_id: Option<BlockId>,
+    ) -> Option<AccountChange> {
+        if block_id != Some(BlockId::Number(BlockNumberOrTag::Pending)) {
+            return None
+        }
+        self.inner.mining.pending_change(address)
+    }
+
+    /// Returns the pending state as overrides of a call.
+    fn pending_state_override(&self) -> StateOverride {
+        self.inner
+            .mining
+            .pending_changes()
+            .into_iter()
+            .map(|(address, change)| {
+                let state_diff = (!change.storage.is_empty()).then(|| {
+                    change
+                        .storage
+                        .iter()
+                        .map(|(slot, value)| (B256::from(*slot), B256::from(*value)))
+                        .collect::<HashMap<_, _>>()
+                });
+                let account = AccountOverride {
+                    balance: change.balance,
+                    nonce: change.nonce,
+                    code: change.code,
+                    state: None,
+                    state_diff,
+                };
+                (address, account)
+            })
+            .collect()
+    }
+
+    /// Changes the state of the account in the pending state.
+    fn set_account(&self, address: Address, change: AccountChange) -> RpcResult<()> {
+        self.inner.mining.set_accounts(vec![(address, change)]).map_err(mining_rpc_err)
+    }
+
+    /// Returns the number and hash of the latest block.
+    fn latest_block(&self) -> RpcResult<BlockNumHash> {
+        let header = self
+            .inner
+            .provider
+            .latest_header()
+            .to_rpc_result()?
+            .ok_or_else(|| internal_rpc_err("latest block not found"))?;
+        Ok(header.num_hash())
+    }
+}
+
+impl<Provider, Eth> Clone for AnvilApi<Provider, Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<Provider, Eth> std::fmt::Debug for AnvilApi<Provider, Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnvilApi").finish_non_exhaustive()
+    }
+}
+
+/// The network the dev chain is forked from, reported by `anvil_nodeInfo` and `anvil_metadata`.
+#[derive(Debug, Clone)]
+pub struct AnvilFork {
+    /// The URL of the forked network.
+    pub url: String,
+    /// The chain ID of the forked network and the block it's forked at.
+    pub network: ForkedNetwork,
+}
+
+struct AnvilApiInner<Provider, Eth> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// The implementation of the `eth` API.
+    eth_api: Eth,
+    /// The handle to the mining task of the dev node.
+    mining: MiningHandle,
+    /// The network the chain is forked from, if it's forked.
+    fork: Option<AnvilFork>,
+    /// The random ID of this node instance.
+    instance_id: B256,
+    /// The accounts that transactions can be sent for without their key.
+    impersonated: ImpersonatedAccounts,
+    /// The snapshots of the chain, by their ID.
+    snapshots: Mutex<Snapshots>,
+}
+
+/// The snapshots of the chain, taken with `anvil_snapshot`.
+#[derive(Debug, Default)]
+struct Snapshots {
+    /// The ID of the next snapshot.
+    next_id: u64,
+    /// The snapshots by their ID.
+    snapshots: BTreeMap<u64, Snapshot>,
+}
+
+/// A snapshot of the chain.
+#[derive(Debug)]
+struct Snapshot {
+    /// The best block when the snapshot was taken.
+    block: BlockNumHash,
+    /// The pending state when the snapshot was taken.
+    pending: BTreeMap<Address, AccountChange>,
+}
+
+#[async_trait]
+impl<Provider, Eth> AnvilApiServer for AnvilApi<Provider, Eth>
+where
+    Provider: BlockReaderIdExt + 'static,
+    Eth: FullEthApi<
+            NetworkTypes: alloy_network::Network<TransactionResponse = reth_rpc_types::Transaction>,
+        > + 'static,
+{
+    /// Handler for `anvil_impersonateAccount`
+    async fn anvil_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        self.inner.impersonated.accounts.write().insert(address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_stopImpersonatingAccount`
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        self.inner.impersonated.accounts.write().remove(&address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_autoImpersonateAccount`
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> RpcResult<()> {
+        self.inner.impersonated.all.store(enabled, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Handler for `anvil_getAutomine`
+    async fn anvil_get_automine(&self) -> RpcResult<bool> {
+        self.inner.mining.automine().await.map_err(mining_rpc_err)
+    }
+
+    /// Handler for `anvil_mine`
+    async fn anvil_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        let blocks = blocks
+            .map_or(Ok(1), u64::try_from)
+            .map_err(|_| invalid_params_rpc_err("number of blocks is too large"))?;
+        let Some(interval) = interval else {
+            return self.inner.mining.mine(blocks).await.map_err(mining_rpc_err)
+        };
+
+        let interval =
+            u64::try_from(interval).map_err(|_| invalid_params_rpc_err("interval is too large"))?;
+        for _ in 0..blocks {
+            let latest = self
+                .inner
+                .provider
+                .latest_header()
+                .to_rpc_result()?
+                .ok_or_else(|| internal_rpc_err("latest block not found"))?;
+            self.inner
+                .mining
+                .set_next_block_timestamp(latest.timestamp.saturating_add(interval.max(1)))
+                .await
+                .map_err(mining_rpc_err)?;
+            self.inner.mining.mine(1).await.map_err(mining_rpc_err)?;
+        }
+        Ok(())
+    }
+
+    /// Handler for `anvil_setAutomine`
+    async fn anvil_set_automine(&self, enabled: bool) -> RpcResult<()> {
+        self.inner.mining.set_automine(enabled).await.map_err(mining_rpc_err)
+    }
+
+    /// Handler for `anvil_setIntervalMining`
+    async fn anvil_set_interval_mining(&self, interval: u64) -> RpcResult<()> {
+        // an interval of zero disables interval mining
+        let interval = (interval > 0).then(|| Duration::from_secs(interval));
+        self.inner.mining.set_interval_mining(interval).await.map_err(mining_rpc_err)
+    }
+
+    /// Handler for `anvil_dropTransaction`
+    async fn anvil_drop_transaction(&self, tx_hash: B256) -> RpcResult<Option<B256>> {
+        let pool = LoadTransaction::pool(&self.inner.eth_api);
+        Ok(pool.remove_transactions(vec![tx_hash]).first().map(|tx| *tx.hash()))
+    }
+
+    /// Handler for `anvil_reset`
+    async fn anvil_reset(&self, fork: Option<Forking>) -> RpcResult<()> {
+        if fork.is_some_and(|fork| fork.json_rpc_url.is_some() || fork.block_number.is_some()) {
+            return Err(unsupported("anvil_reset with a fork"))
+        }
+
+        self.inner.mining.unwind(0).await.map_err(mining_rpc_err)?;
+        self.inner.mining.set_pending_changes(BTreeMap::new()).map_err(mining_rpc_err)?;
+        self.inner.snapshots.lock().snapshots.clear();
+        Ok(())
+    }
+
+    /// Handler for `anvil_setRpcUrl`
+    async fn anvil_set_rpc_url(&self, _url: String) -> RpcResult<()> {
+        Err(unsupported("anvil_setRpcUrl"))
+    }
+
+    /// Handler for `anvil_setBalance`
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        self.set_account(address, AccountChange::default().with_balance(balance))
+    }
+
+    /// Handler for `anvil_setCode`
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        self.set_account(address, AccountChange::default().with_code(code))
+    }
+
+    /// Handler for `anvil_setNonce`
+    async fn anvil_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        let nonce =
+            u64::try_from(nonce).map_err(|_| invalid_params_rpc_err("nonce is too large"))?;
+        self.set_account(address, AccountChange::default().with_nonce(nonce))
+    }
+
+    /// Handler for `anvil_setStorageAt`
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<bool> {
+        let change = AccountChange::default().with_storage(slot, U256::from_be_bytes(value.0));
+        self.set_account(address, change)?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_setCoinbase`
+    async fn anvil_set_coinbase(&self, address: Address) -> RpcResult<()> {
+        self.inner.mining.set_coinbase(address).await;
+        Ok(())
+    }
+
+    /// Handler for `anvil_setChainId`
+    async fn anvil_set_chain_id(&self, _chain_id: u64) -> RpcResult<()> {
+        Err(unsupported("anvil_setChainId"))
+    }
+
+    /// Handler for `anvil_setLoggingEnabled`
+    async fn anvil_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        Err(unsupported("anvil_setLoggingEnabled"))
+    }
+
+    /// Handler for `anvil_setMinGasPrice`
+    async fn anvil_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        Err(unsupported("anvil_setMinGasPrice"))
+    }
+
+    /// Handler for `anvil_setNextBlockBaseFeePerGas`
+    async fn anvil_set_next_block_base_fee_per_gas(&self, base_fee: U256) -> RpcResult<()> {
+        let base_fee =
+            u64::try_from(base_fee).map_err(|_| invalid_params_rpc_err("base fee is too large"))?;
+        self.inner.mining.set_next_block_base_fee(base_fee).await;
+        Ok(())
+    }
+
+    /// Handler for `anvil_setTime`
+    async fn anvil_set_time(&self, timestamp: u64) -> RpcResult<u64> {
+        Ok(self.inner.mining.set_time(timestamp).await)
+    }
+
+    /// Handler for `anvil_dumpState`
+    async fn anvil_dump_state(&self) -> RpcResult<Bytes> {
+        Err(unsupported("anvil_dumpState"))
+    }
+
+    /// Handler for `anvil_loadState`
+    async fn anvil_load_state(&self, _state: Bytes) -> RpcResult<bool> {
+        Err(unsupported("anvil_loadState"))
+    }
+
+    /// Handler for `anvil_nodeInfo`
+    async fn anvil_node_info(&self) -> RpcResult<NodeInfo> {
+        let provider = &self.inner.provider;
+        let header = provider
+            .latest_header()
+            .to_rpc_result()?
+            .ok_or_else(|| internal_rpc_err("latest block not found"))?;
+        let total_difficulty =
+            provider.header_td_by_number(header.number).to_rpc_result()?.unwrap_or_default();
+        let head = Head {
+            number: header.number,
+            hash: header.hash(),
+            difficulty: header.difficulty,
+            total_difficulty,
+            timestamp: header.timestamp,
+        };
+
+        let chain_spec = EthApiSpec::provider(&self.inner.eth_api).chain_spec();
+        let hard_fork = chain_spec
+            .hardforks
+            .forks_iter()
+            .filter(|(_, condition)| condition.active_at_head(&head))
+            .last()
+            .map(|(fork, _)| fork.name().to_lowercase())
+            .unwrap_or_default();
+        let base_fee = header
+            .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(header.timestamp + 1))
+            .unwrap_or_default();
+        let gas_limit =
+            self.inner.mining.block_gas_limit().await.unwrap_or(chain_spec.max_gas_limit);
+        let gas_price = EthFees::gas_price(&self.inner.eth_api).await.map_err(Into::into)?;
+
+        let fork = self.inner.fork.as_ref();
+        Ok(NodeInfo {
+            current_block_number: header.number,
+            current_block_timestamp: header.timestamp,
+            current_block_hash: header.hash(),
+            hard_fork,
+            transaction_order: "fees".to_string(),
+            environment: NodeEnvironment {
+                base_fee: U256::from(base_fee),
+                chain_id: chain_spec.chain.id(),
+                gas_limit: U256::from(gas_limit),
+                gas_price,
+            },
+            fork_config: NodeForkConfig {
+                fork_url: fork.map(|fork| fork.url.clone()),
+                fork_block_number: fork.map(|fork| fork.network.fork_block_number),
+                fork_retry_backoff: None,
+            },
+        })
+    }
+
+    /// Handler for `anvil_metadata`
+    async fn anvil_metadata(&self) -> RpcResult<Metadata> {
+        let status = self
+            .inner
+            .eth_api
+            .network()
+            .network_status()
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
+        let latest = self.latest_block()?;
+        let snapshots = self
+            .inner
+            .snapshots
+            .lock()
+            .snapshots
+            .iter()
+            .map(|(id, snapshot)| (U256::from(*id), (snapshot.block.number, snapshot.block.hash)))
+            .collect();
+
+        Ok(Metadata {
+            client_version: status.client_version,
+            chain_id: self.inner.eth_api.chain_id().to(),
+            instance_id: self.inner.instance_id,
+            latest_block_number: latest.number,
+            latest_block_hash: latest.hash,
+            forked_network: self.inner.fork.as_ref().map(|fork| fork.network),
+            snapshots,
+        })
+    }
+
+    /// Handler for `anvil_snapshot`
+    async fn anvil_snapshot(&self) -> RpcResult<U256> {
+        let block = self.latest_block()?;
+        let pending = self.inner.mining.pending_changes();
+        let mut snapshots = self.inner.snapshots.lock();
+        let id = snapshots.next_id;
+        snapshots.next_id += 1;
+        snapshots.snapshots.insert(id, Snapshot { block, pending });
+        Ok(U256::from(id))
+    }
+
+    /// Handler for `anvil_revert`
+    async fn anvil_revert(&self, id: U256) -> RpcResult<bool> {
+        let Ok(id) = u64::try_from(id) else { return Ok(false) };
+        let snapshot = {
+            // reverting deletes the snapshot and all snapshots taken after it
+            let mut snapshots = self.inner.snapshots.lock();
+            let mut reverted = snapshots.snapshots.split_off(&id);
+            match reverted.remove(&id) {
+                Some(snapshot) => snapshot,
+                None => return Ok(false),
+            }
+        };
+
+        self.inner.mining.unwind(snapshot.block.number).await.map_err(mining_rpc_err)?;
+        self.inner.mining.set_pending_changes(snapshot.pending).map_err(mining_rpc_err)?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_increaseTime`
+    async fn anvil_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        let seconds = u64::try_from(seconds)
+            .map_err(|_| invalid_params_rpc_err("time increase is too large"))?;
+        Ok(self.inner.mining.increase_time(seconds).await)
+    }
+
+    /// Handler for `anvil_setNextBlockTimestamp`
+    async fn anvil_set_next_block_timestamp(&self, seconds: u64) -> RpcResult<()> {
+        self.inner.mining.set_next_block_timestamp(seconds).await.map_err(mining_rpc_err)
+    }
+
+    /// Handler for `anvil_setBlockGasLimit`
+    async fn anvil_set_block_gas_limit(&self, gas_limit: U256) -> RpcResult<bool> {
+        let gas_limit = u64::try_from(gas_limit)
+            .map_err(|_| invalid_params_rpc_err("gas limit is too large"))?;
+        self.inner.mining.set_block_gas_limit(gas_limit).await;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_setBlockTimestampInterval`
+    async fn anvil_set_block_timestamp_interval(&self, seconds: u64) -> RpcResult<()> {
+        self.inner.mining.set_block_timestamp_interval(Some(seconds)).await;
+        Ok(())
+    }
+
+    /// Handler for `anvil_removeBlockTimestampInterval`
+    async fn anvil_remove_block_timestamp_interval(&self) -> RpcResult<bool> {
+        Ok(self.inner.mining.set_block_timestamp_interval(None).await)
+    }
+
+    /// Handler for `anvil_mine_detailed`
+    async fn anvil_mine_detailed(&self, opts: Option<MineOptions>) -> RpcResult<Vec<Block>> {
+        let (timestamp, blocks) = match opts.unwrap_or_default() {
+            MineOptions::Options { timestamp, blocks } => (timestamp, blocks.unwrap_or(1)),
+            MineOptions::Timestamp(timestamp) => (timestamp, 1),
+        };
+        if let Some(timestamp) = timestamp {
+            self.inner.mining.set_next_block_timestamp(timestamp).await.map_err(mining_rpc_err)?;
+        }
+
+        let best = self.latest_block()?.number;
+        self.inner.mining.mine(blocks).await.map_err(mining_rpc_err)?;
+
+        let mut mined = Vec::new();
+        for number in best + 1..=best + blocks {
+            let block = EthBlocks::rpc_block(&self.inner.eth_api, number.into(), true)
+                .await
+                .map_err(Into::into)?
+                .ok_or_else(|| internal_rpc_err(format!("mined block {number} not found")))?;
+            mined.push(block);
+        }
+        Ok(mined)
+    }
+
+    /// Handler for `anvil_enableTraces`
+    async fn anvil_enable_traces(&self) -> RpcResult<()> {
+        Err(unsupported("anvil_enableTraces"))
+    }
+
+    /// Handler for `anvil_removePoolTransactions`
+    async fn anvil_remove_pool_transactions(&self, address: Address) -> RpcResult<()> {
+        let pool = LoadTransaction::pool(&self.inner.eth_api);
+        let hashes = pool.get_transactions_by_sender(address).iter().map(|tx| *tx.hash()).collect();
+        pool.remove_transactions(hashes);
+        Ok(())
+    }
+}
+
+/// The accounts that are impersonated with `anvil_impersonateAccount`.
+#[derive(Debug, Clone, Default)]
+struct ImpersonatedAccounts {
+    /// The impersonated accounts.
+    accounts: Arc<RwLock<HashSet<Address>>>,
+    /// Whether all accounts are impersonated.
+    all: Arc<AtomicBool>,
+}
+
+impl ImpersonatedAccounts {
+    /// Returns `true` if the account is impersonated.
+    fn contains(&self, address: &Address) -> bool {
+        self.all.load(Ordering::Relaxed) || self.accounts.read().contains(address)
+    }
+}
+
+/// Signs the transactions of impersonated accounts with a throwaway key.
+///
+/// The signatures of these transactions don't recover to the impersonated account, the mining task
+/// executes them as the impersonated account instead.
+#[derive(Debug, Clone)]
+struct ImpersonationSigner {
+    accounts: ImpersonatedAccounts,
+    key: SecretKey,
+}
+
+impl ImpersonationSigner {
+    fn new(accounts: ImpersonatedAccounts) -> Self {
+        let (key, _) = secp256k1::generate_keypair(&mut rand::thread_rng());
+        Self { accounts, key }
+    }
+}
+
+#[async_trait]
+impl EthSigner for ImpersonationSigner {
+    fn accounts(&self) -> Vec<Address> {
+        // impersonated accounts are not listed by `eth_accounts`
+        Vec::new()
+    }
+
+    fn is_signer_for(&self, addr: &Address) -> bool {
+        self.accounts.contains(addr)
+    }
+
+    async fn sign(&self, _address: Address, _message: &[u8]) -> SignResult<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+
+    fn sign_transaction(
+        &self,
+        request: TypedTransactionRequest,
+        _address: &Address,
+    ) -> SignResult<TransactionSigned> {
+        let transaction =
+            to_primitive_transaction(request).ok_or(SignError::InvalidTransactionRequest)?;
+        let signature = reth_primitives::sign_message(
+            B256::from_slice(self.key.as_ref()),
+            transaction.signature_hash(),
+        )
+        .map_err(|_| SignError::CouldNotSign)?;
+        Ok(TransactionSigned::from_transaction_and_signature(transaction, signature))
+    }
+
+    fn sign_typed_data(&self, _address: Address, _payload: &TypedData) -> SignResult<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+}
+
+/// Returns the error of a method that is not supported by a dev node.
+fn unsupported(method: &str) -> ErrorObjectOwned {
+    rpc_error_with_code(ErrorCode::MethodNotFound.code(), format!("{method} is not supported"))
+}
@@ -13,7 +13,7 @@ use reth_provider::{
 use reth_revm::{database::StateProviderDatabase, state_change::apply_blockhashes_update};
 use reth_rpc_api::DebugApiServer;
 use reth_rpc_eth_api::{
-    helpers::{Call, EthApiSpec, EthTransactions, TraceExt},
+    helpers::{Call, EthApiSpec, EthTransactions, LoadBlock, TraceExt},
     EthApiTypes, FromEthApiError,
 };
 use reth_rpc_eth_types::{EthApiError, StateCacheDb};
@@ -182,6 +182,8 @@ where
     }
 
     /// Replays a block and returns the trace of each transaction.
+    ///
+    /// The traces are cached by block hash and tracing options, except for the pending block.
     pub async fn debug_trace_block(
         &self,
         block_id: BlockId,
@@ -194,9 +196,20 @@ where
             .map_err(Eth::Error::from_eth_err)?
             .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
 
+        let cache = LoadBlock::cache(&self.inner.eth_api).traces();
+        if !block_id.is_pending() {
+            if let Some(traces) = cache.geth_traces(block_hash, &opts) {
+                return Ok(traces.to_vec())
+            }
+        }
+
         let ((cfg, block_env, _), block) = futures::try_join!(
             self.inner.eth_api.evm_env_at(block_hash.into()),
-            self.inner.eth_api.block_with_senders(block_id),
+            self.inner.eth_api.block_with_senders(if block_id.is_pending() {
+                block_id
+            } else {
+                block_hash.into()
+            }),
         )?;
 
         let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
@@ -204,14 +217,19 @@ where
         // its parent block's state
         let state_at = block.parent_hash;
 
-        self.trace_block(
-            state_at.into(),
-            block.into_transactions_ecrecovered().collect(),
-            cfg,
-            block_env,
-            opts,
-        )
-        .await
+        let traces = self
+            .trace_block(
+                state_at.into(),
+                block.into_transactions_ecrecovered().collect(),
+                cfg,
+                block_env,
+                opts.clone(),
+            )
+            .await?;
+        if !block_id.is_pending() {
+            cache.insert_geth_traces(block_hash, &opts, Arc::new(traces.clone()));
+        }
+        Ok(traces)
     }
 
     /// Trace the transaction according to the provided options.
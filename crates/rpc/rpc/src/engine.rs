@@ -5,9 +5,9 @@ use reth_rpc_api::{EngineEthApiServer, EthApiServer, EthFilterApiServer};
 /// Re-export for convenience
 pub use reth_rpc_engine_api::EngineApi;
 use reth_rpc_eth_api::{EthApiTypes, RpcBlock, RpcTransaction};
+use reth_rpc_server_types::{proof::AccountProofResponse, sync::SyncStatus};
 use reth_rpc_types::{
-    state::StateOverride, BlockOverrides, EIP1186AccountProofResponse, Filter, JsonStorageKey, Log,
-    SyncStatus, TransactionRequest,
+    state::StateOverride, BlockOverrides, Filter, JsonStorageKey, Log, TransactionRequest,
 };
 use tracing_futures::Instrument;
 
@@ -73,7 +73,7 @@ where
         block_overrides: Option<Box<BlockOverrides>>,
     ) -> Result<Bytes> {
         self.eth
-            .call(request, block_number, state_overrides, block_overrides)
+            .call(request, block_number, state_overrides, block_overrides, None)
             .instrument(engine_span!())
             .await
     }
@@ -117,7 +117,7 @@ where
         address: Address,
         keys: Vec<JsonStorageKey>,
         block_number: Option<BlockId>,
-    ) -> Result<EIP1186AccountProofResponse> {
+    ) -> Result<AccountProofResponse> {
         self.eth.get_proof(address, keys, block_number).instrument(engine_span!()).await
     }
 }
@@ -14,11 +14,12 @@ use reth_rpc_eth_api::{
 };
 use reth_rpc_eth_types::{
     EthApiBuilderCtx, EthApiError, EthStateCache, FeeHistoryCache, GasCap, GasPriceOracle,
-    PendingBlock,
+    PendingBlock, RpcLimits,
 };
+use reth_stages_types::SyncProgress;
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
-    TaskExecutor, TaskSpawner, TokioTaskExecutor,
+    TaskExecutor, TaskPriority, TaskSpawner, TokioTaskExecutor,
 };
 use tokio::sync::Mutex;
 
@@ -69,7 +70,7 @@ where
             network,
             eth_cache,
             gas_oracle,
-            gas_cap,
+            RpcLimits::from(gas_cap.into()),
             eth_proof_window,
             blocking_task_pool,
             fee_history_cache,
@@ -98,8 +99,8 @@ where
         Tasks: TaskSpawner + Clone + 'static,
         Events: CanonStateSubscriptions,
     {
-        let blocking_task_pool =
-            BlockingTaskPool::build().expect("failed to build blocking task pool");
+        let blocking_task_pool = BlockingTaskPool::build_with_priority(TaskPriority::Rpc)
+            .expect("failed to build blocking task pool");
 
         let inner = EthApiInner::new(
             ctx.provider.clone(),
@@ -107,7 +108,7 @@ where
             ctx.network.clone(),
             ctx.cache.clone(),
             ctx.new_gas_price_oracle(),
-            ctx.config.rpc_gas_cap,
+            ctx.limits.clone(),
             ctx.config.eth_proof_window,
             blocking_task_pool,
             ctx.new_fee_history_cache(),
@@ -115,7 +116,9 @@ where
             ctx.executor.clone(),
             None,
             ctx.config.proof_permits,
-        );
+        )
+        .with_pending_receipts(ctx.config.pending_receipts)
+        .with_sync_progress(ctx.sync_progress.clone());
 
         Self { inner: Arc::new(inner) }
     }
@@ -186,8 +189,8 @@ pub struct EthApiInner<Provider, Pool, Network, EvmConfig> {
     eth_cache: EthStateCache,
     /// The async gas oracle frontend for gas price suggestions
     gas_oracle: GasPriceOracle<Provider>,
-    /// Maximum gas limit for `eth_call` and call tracing RPC methods.
-    gas_cap: u64,
+    /// Request limits, including the gas limit for `eth_call` and call tracing RPC methods.
+    limits: RpcLimits,
     /// The maximum number of blocks into the past for generating state proofs.
     eth_proof_window: u64,
     /// The block number at which the node started
@@ -206,6 +209,10 @@ pub struct EthApiInner<Provider, Pool, Network, EvmConfig> {
     raw_transaction_forwarder: parking_lot::RwLock<Option<Arc<dyn RawTransactionForwarder>>>,
     /// Guard for getproof calls
     blocking_task_guard: BlockingTaskGuard,
+    /// Whether receipts may be served from the locally built pending block
+    pending_receipts: bool,
+    /// Progress of the sync stages
+    sync_progress: SyncProgress,
 }
 
 impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, EvmConfig>
@@ -220,7 +227,7 @@ where
         network: Network,
         eth_cache: EthStateCache,
         gas_oracle: GasPriceOracle<Provider>,
-        gas_cap: impl Into<GasCap>,
+        limits: RpcLimits,
         eth_proof_window: u64,
         blocking_task_pool: BlockingTaskPool,
         fee_history_cache: FeeHistoryCache,
@@ -247,7 +254,7 @@ where
             signers,
             eth_cache,
             gas_oracle,
-            gas_cap: gas_cap.into().into(),
+            limits,
             eth_proof_window,
             starting_block,
             task_spawner: Box::new(task_spawner),
@@ -257,6 +264,8 @@ where
             evm_config,
             raw_transaction_forwarder: parking_lot::RwLock::new(raw_transaction_forwarder),
             blocking_task_guard: BlockingTaskGuard::new(proof_permits),
+            pending_receipts: false,
+            sync_progress: Default::default(),
         }
     }
 }
@@ -312,8 +321,14 @@ impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, Ev
 
     /// Returns the gas cap.
     #[inline]
-    pub const fn gas_cap(&self) -> u64 {
-        self.gas_cap
+    pub fn gas_cap(&self) -> u64 {
+        self.limits.gas_cap()
+    }
+
+    /// Returns a handle to the request limits.
+    #[inline]
+    pub const fn limits(&self) -> &RpcLimits {
+        &self.limits
     }
 
     /// Returns a handle to the gas oracle.
@@ -357,6 +372,31 @@ impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, Ev
     pub const fn blocking_task_guard(&self) -> &BlockingTaskGuard {
         &self.blocking_task_guard
     }
+
+    /// Returns `true` if receipts may be served from the locally built pending block.
+    #[inline]
+    pub const fn pending_receipts(&self) -> bool {
+        self.pending_receipts
+    }
+
+    /// Configures whether `eth_getTransactionReceipt` may return receipts for transactions that
+    /// are only included in the locally built pending block.
+    pub const fn with_pending_receipts(mut self, enabled: bool) -> Self {
+        self.pending_receipts = enabled;
+        self
+    }
+
+    /// Returns the progress of the sync stages.
+    #[inline]
+    pub const fn sync_progress(&self) -> &SyncProgress {
+        &self.sync_progress
+    }
+
+    /// Configures the [`SyncProgress`] of the sync stages reported by `eth_syncing`.
+    pub fn with_sync_progress(mut self, sync_progress: SyncProgress) -> Self {
+        self.sync_progress = sync_progress;
+        self
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> UpdateRawTxForwarder
@@ -10,14 +10,19 @@ use std::{
 };
 
 use async_trait::async_trait;
+use futures::StreamExt;
 use jsonrpsee::{core::RpcResult, server::IdProvider};
 use reth_chainspec::ChainInfo;
 use reth_primitives::{IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, ProviderError};
+use reth_provider::{
+    BlockIdReader, BlockReader, EvmEnvProvider, ProviderError, PruneCheckpointReader,
+};
+use reth_prune_types::PruneSegment;
 use reth_rpc_eth_api::EthFilterApiServer;
 use reth_rpc_eth_types::{
     logs_utils::{self, append_matching_block_logs},
     EthApiError, EthFilterConfig, EthFilterError, EthStateCache, EthSubscriptionIdProvider,
+    HistoricalRpcClient, PruneCheckpointCache, RpcLimits,
 };
 use reth_rpc_server_types::ToRpcResult;
 use reth_rpc_types::{
@@ -27,7 +32,7 @@ use reth_rpc_types::{
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
 use tokio::{
-    sync::{mpsc::Receiver, Mutex},
+    sync::{mpsc::Receiver, oneshot, Mutex},
     time::MissedTickBehavior,
 };
 use tracing::trace;
@@ -61,8 +66,15 @@ where
         config: EthFilterConfig,
         task_spawner: Box<dyn TaskSpawner>,
     ) -> Self {
-        let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
-            config;
+        let EthFilterConfig {
+            max_blocks_per_filter,
+            max_logs_per_response,
+            max_concurrent_getlogs_chunks,
+            stale_filter_ttl,
+            historical_fallback,
+            limits,
+        } = config;
+        let historical_fallback = historical_fallback.and_then(|fallback| fallback.client());
         let inner = EthFilterInner {
             provider,
             active_filters: Default::default(),
@@ -75,6 +87,11 @@ where
             // if not set, use the max value, which is effectively no limit
             max_blocks_per_filter: max_blocks_per_filter.unwrap_or(u64::MAX),
             max_logs_per_response: max_logs_per_response.unwrap_or(usize::MAX),
+            limits,
+            // if not set, scan ranges sequentially
+            max_concurrent_getlogs_chunks: max_concurrent_getlogs_chunks.unwrap_or(1).max(1),
+            historical_fallback,
+            prune_checkpoints: Default::default(),
         };
 
         let eth_filter = Self { inner: Arc::new(inner) };
@@ -124,7 +141,7 @@ where
 
 impl<Provider, Pool> EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + PruneCheckpointReader + 'static,
     Pool: TransactionPool + 'static,
     <Pool as TransactionPool>::Transaction: 'static,
 {
@@ -221,7 +238,7 @@ where
 #[async_trait]
 impl<Provider, Pool> EthFilterApiServer<reth_rpc_types::Transaction> for EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + PruneCheckpointReader + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Handler for `eth_newFilter`
@@ -297,7 +314,17 @@ where
     /// Handler for `eth_getLogs`
     async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>> {
         trace!(target: "rpc::eth", "Serving eth_getLogs");
-        Ok(self.inner.logs_for_filter(filter).await?)
+        let Some(fallback) = &self.inner.historical_fallback else {
+            return Ok(self.inner.logs_for_filter(filter).await?)
+        };
+
+        match self.inner.logs_for_filter(filter.clone()).await {
+            Err(EthFilterError::EthAPIError(EthApiError::PrunedHistory { .. })) => {
+                trace!(target: "rpc::eth", "Forwarding eth_getLogs for pruned receipts");
+                fallback.request("eth_getLogs", (filter,)).await
+            }
+            res => Ok(res?),
+        }
     }
 }
 
@@ -328,6 +355,10 @@ struct EthFilterInner<Provider, Pool> {
     max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a response
     max_logs_per_response: usize,
+    /// Shared limits that take precedence over `max_logs_per_response`
+    limits: Option<RpcLimits>,
+    /// Maximum number of chunks of a finalized range that are scanned concurrently
+    max_concurrent_getlogs_chunks: usize,
     /// The async cache frontend for eth related data
     eth_cache: EthStateCache,
     /// maximum number of headers to read at once for range filter
@@ -336,15 +367,24 @@ struct EthFilterInner<Provider, Pool> {
     task_spawner: Box<dyn TaskSpawner>,
     /// Duration since the last filter poll, after which the filter is considered stale
     stale_filter_ttl: Duration,
+    /// Archive node that `eth_getLogs` requests for pruned receipts are forwarded to
+    historical_fallback: Option<HistoricalRpcClient>,
+    /// Earliest blocks with available receipts
+    prune_checkpoints: PruneCheckpointCache,
 }
 
 impl<Provider, Pool> EthFilterInner<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + PruneCheckpointReader + 'static,
     Pool: TransactionPool + 'static,
 {
+    /// Returns the maximum number of logs that can be returned in a response.
+    fn max_logs_per_response(&self) -> usize {
+        self.limits.as_ref().map_or(self.max_logs_per_response, RpcLimits::max_logs_per_response)
+    }
+
     /// Returns logs matching given filter object.
-    async fn logs_for_filter(&self, filter: Filter) -> Result<Vec<Log>, EthFilterError> {
+    async fn logs_for_filter(self: &Arc<Self>, filter: Filter) -> Result<Vec<Log>, EthFilterError> {
         match filter.block_option {
             FilterBlockOption::AtBlockHash(block_hash) => {
                 // for all matching logs in the block
@@ -353,6 +393,7 @@ where
                     .provider
                     .header_by_hash_or_number(block_hash.into())?
                     .ok_or(ProviderError::HeaderNotFound(block_hash.into()))?;
+                self.ensure_receipts_available(block.number)?;
 
                 // we also need to ensure that the receipts are available and return an error if
                 // not, in case the block hash been reorged
@@ -398,6 +439,23 @@ where
         }
     }
 
+    /// Returns an error if the receipts of the given block are pruned.
+    ///
+    /// Only considers the [`PruneSegment::Receipts`] segment, receipts pruned by contract log
+    /// filters may still contain the logs of the retained addresses.
+    fn ensure_receipts_available(&self, block: u64) -> Result<(), EthFilterError> {
+        let earliest_available =
+            self.prune_checkpoints.earliest_available(&self.provider, PruneSegment::Receipts)?;
+
+        match earliest_available {
+            Some(earliest_available) if block < earliest_available => {
+                Err(EthApiError::PrunedHistory { earliest_available: Some(earliest_available) }
+                    .into())
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Installs a new filter and returns the new identifier.
     async fn install_filter(&self, kind: FilterKind) -> RpcResult<FilterId> {
         let last_poll_block_number = self.provider.best_block_number().to_rpc_result()?;
@@ -420,7 +478,7 @@ where
     ///  - underlying database error
     ///  - amount of matches exceeds configured limit
     async fn get_logs_in_block_range(
-        &self,
+        self: &Arc<Self>,
         filter: &Filter,
         from_block: u64,
         to_block: u64,
@@ -437,6 +495,8 @@ where
             return Err(EthFilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter))
         }
 
+        self.ensure_receipts_available(from_block)?;
+
         let mut all_logs = Vec::new();
         let filter_params = FilteredParams::new(Some(filter.clone()));
 
@@ -461,6 +521,16 @@ where
             return Ok(all_logs)
         }
 
+        // finalized blocks can't be reorged, so large finalized ranges can be split into chunks
+        // that are scanned concurrently
+        if self.max_concurrent_getlogs_chunks > 1 && to_block - from_block >= self.max_headers_range
+        {
+            let finalized = self.provider.finalized_block_number()?;
+            if finalized.is_some_and(|finalized| to_block <= finalized) {
+                return self.get_logs_in_finalized_range(filter, from_block, to_block).await
+            }
+        }
+
         // derive bloom filters from filter input, so we can check headers for matching logs
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
@@ -501,10 +571,9 @@ where
                         // size check but only if range is multiple blocks, so we always return all
                         // logs of a single block
                         let is_multi_block_range = from_block != to_block;
-                        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
-                            return Err(EthFilterError::QueryExceedsMaxResults(
-                                self.max_logs_per_response,
-                            ))
+                        let max_logs = self.max_logs_per_response();
+                        if is_multi_block_range && all_logs.len() > max_logs {
+                            return Err(EthFilterError::QueryExceedsMaxResults(max_logs))
                         }
                     }
                 }
@@ -513,6 +582,104 @@ where
 
         Ok(all_logs)
     }
+
+    /// Returns all logs in the given _inclusive_ finalized range that match the filter.
+    ///
+    /// The range is split into chunks of `max_headers_range` blocks which are scanned on the
+    /// blocking pool, with at most `max_concurrent_getlogs_chunks` chunks in flight. The logs are
+    /// returned in block order.
+    async fn get_logs_in_finalized_range(
+        self: &Arc<Self>,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<Log>, EthFilterError> {
+        trace!(target: "rpc::eth::filter", from=from_block, to=to_block, chunks=self.max_concurrent_getlogs_chunks, "scanning finalized range concurrently");
+
+        let filter = Arc::new(filter.clone());
+        let mut chunks = futures::stream::iter(BlockRangeInclusiveIter::new(
+            from_block..=to_block,
+            self.max_headers_range,
+        ))
+        .map(|(from, to)| {
+            let this = Arc::clone(self);
+            let filter = Arc::clone(&filter);
+            let (tx, rx) = oneshot::channel();
+            self.task_spawner.spawn_blocking(Box::pin(async move {
+                let _ = tx.send(this.get_logs_in_chunk(&filter, from, to));
+            }));
+            rx
+        })
+        .buffered(self.max_concurrent_getlogs_chunks);
+
+        let mut all_logs = Vec::new();
+        while let Some(res) = chunks.next().await {
+            let logs = res.map_err(|_| EthFilterError::InternalError)??;
+            all_logs.extend(logs);
+
+            let max_logs = self.max_logs_per_response();
+            if all_logs.len() > max_logs {
+                return Err(EthFilterError::QueryExceedsMaxResults(max_logs))
+            }
+        }
+
+        Ok(all_logs)
+    }
+
+    /// Returns all logs in the given _inclusive_ range that match the filter.
+    ///
+    /// Receipts are read from the provider directly, so that scanning historical ranges doesn't
+    /// evict recent blocks from the cache.
+    ///
+    /// Note: this does blocking IO and should be spawned on the blocking pool.
+    fn get_logs_in_chunk(
+        &self,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<Log>, EthFilterError> {
+        let mut logs = Vec::new();
+        let filter_params = FilteredParams::new(Some(filter.clone()));
+        let address_filter = FilteredParams::address_filter(&filter.address);
+        let topics_filter = FilteredParams::topics_filter(&filter.topics);
+
+        let headers = self.provider.headers_range(from_block..=to_block)?;
+        for (idx, header) in headers.iter().enumerate() {
+            if !FilteredParams::matches_address(header.logs_bloom, &address_filter) ||
+                !FilteredParams::matches_topics(header.logs_bloom, &topics_filter)
+            {
+                continue
+            }
+
+            let block_hash = match headers.get(idx + 1) {
+                Some(parent) => parent.parent_hash,
+                None => self
+                    .provider
+                    .block_hash(header.number)?
+                    .ok_or(ProviderError::HeaderNotFound(header.number.into()))?,
+            };
+
+            if let Some(receipts) = self.provider.receipts_by_block(block_hash.into())? {
+                append_matching_block_logs(
+                    &mut logs,
+                    &self.provider,
+                    &filter_params,
+                    BlockNumHash::new(header.number, block_hash),
+                    &receipts,
+                    false,
+                    header.timestamp,
+                )?;
+
+                // no need to scan the rest of the chunk if it alone exceeds the limit
+                let max_logs = self.max_logs_per_response();
+                if logs.len() > max_logs {
+                    return Err(EthFilterError::QueryExceedsMaxResults(max_logs))
+                }
+            }
+        }
+
+        Ok(logs)
+    }
 }
 
 /// All active filters
@@ -659,7 +826,247 @@ impl Iterator for BlockRangeInclusiveIter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use jsonrpsee::{server::Server, RpcModule};
     use rand::{thread_rng, Rng};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{
+        Address, Block, BlockNumHash, Bytes, Header, Log as PrimitiveLog, Receipt,
+        TransactionSigned, B256,
+    };
+    use reth_provider::{test_utils::MockEthProvider, BlockHashReader, HeaderProvider};
+    use reth_prune_types::{PruneCheckpoint, PruneMode};
+    use reth_rpc_eth_types::{history::PRUNED_HISTORY_CODE, HistoricalRpcFallback};
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::noop::NoopTransactionPool;
+
+    /// Returns a filter for a chain of 10 blocks whose receipts are pruned up to block 4.
+    fn pruned_receipts_filter(
+        historical_fallback: Option<HistoricalRpcFallback>,
+    ) -> EthFilter<MockEthProvider, NoopTransactionPool> {
+        let provider = MockEthProvider::default();
+        for number in 0..=10 {
+            let header = Header { number, ..Default::default() };
+            provider.add_header(header.hash_slow(), header);
+        }
+        provider.add_prune_checkpoint(
+            PruneSegment::Receipts,
+            PruneCheckpoint {
+                block_number: Some(4),
+                tx_number: None,
+                prune_mode: PruneMode::Distance(6),
+            },
+        );
+        let cache =
+            EthStateCache::spawn(provider.clone(), Default::default(), EthEvmConfig::default());
+        EthFilter::new(
+            provider,
+            NoopTransactionPool::default(),
+            cache,
+            EthFilterConfig::default().historical_fallback(historical_fallback),
+            Box::new(TokioTaskExecutor::default()),
+        )
+    }
+
+    #[tokio::test]
+    async fn logs_of_pruned_receipts() {
+        let filter = pruned_receipts_filter(None);
+
+        let err = EthFilterApiServer::logs(&filter, Filter::new().from_block(2).to_block(8))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), PRUNED_HISTORY_CODE);
+        assert_eq!(err.data().unwrap().get(), r#"{"earliestAvailableBlock":5}"#);
+    }
+
+    #[tokio::test]
+    async fn logs_of_pruned_receipts_are_forwarded() {
+        let archive_log = Log { block_number: Some(3), ..Default::default() };
+        let mut archive = RpcModule::new(archive_log.clone());
+        archive
+            .register_method("eth_getLogs", |params, log, _| {
+                let (filter,) = params.parse::<(Filter,)>().unwrap();
+                assert_eq!(filter.address, Address::with_last_byte(1).into());
+                vec![log.clone()]
+            })
+            .unwrap();
+        let server = Server::builder().build("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap();
+        let _handle = server.start(archive);
+
+        let filter =
+            pruned_receipts_filter(Some(HistoricalRpcFallback::new(format!("http://{addr}"))));
+        let logs = EthFilterApiServer::logs(
+            &filter,
+            Filter::new().from_block(2).to_block(8).address(Address::with_last_byte(1)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(logs, vec![archive_log]);
+    }
+
+    /// The last block of the chain of [`chain_with_logs`], which spans three chunks.
+    const LAST_BLOCK: u64 = 2 * (MAX_HEADERS_RANGE + 1) + 500;
+
+    /// Returns a filter for a chain of blocks up to [`LAST_BLOCK`], where each of the given blocks
+    /// contains a transaction that emits a log with the block number as data.
+    fn chain_with_logs(
+        log_blocks: &[u64],
+        finalized: Option<u64>,
+        config: EthFilterConfig,
+    ) -> EthFilter<MockEthProvider, NoopTransactionPool> {
+        let provider = MockEthProvider::default();
+        let mut parent_hash = B256::ZERO;
+        for number in 0..=LAST_BLOCK {
+            let mut header = Header { number, parent_hash, ..Default::default() };
+            let mut block = Block::default();
+            let mut receipts = Vec::new();
+            if log_blocks.contains(&number) {
+                let log = PrimitiveLog::new_unchecked(
+                    Address::with_last_byte(1),
+                    vec![],
+                    Bytes::copy_from_slice(&number.to_be_bytes()),
+                );
+                let receipt = Receipt { success: true, logs: vec![log], ..Default::default() };
+                header.logs_bloom = receipt.bloom_slow();
+                block.body.push(TransactionSigned::default());
+                receipts.push(receipt);
+            }
+            block.header = header;
+            let hash = block.header.hash_slow();
+            provider.add_block(hash, block);
+            provider.add_receipts(hash, receipts);
+            if finalized == Some(number) {
+                provider.set_finalized_block(BlockNumHash::new(number, hash));
+            }
+            parent_hash = hash;
+        }
+
+        let cache =
+            EthStateCache::spawn(provider.clone(), Default::default(), EthEvmConfig::default());
+        EthFilter::new(
+            provider,
+            NoopTransactionPool::default(),
+            cache,
+            config,
+            Box::new(TokioTaskExecutor::default()),
+        )
+    }
+
+    /// Returns the block numbers the logs were emitted in, asserting that the logs are ordered.
+    fn log_blocks(logs: &[Log]) -> Vec<u64> {
+        let blocks = logs.iter().map(|log| log.block_number.unwrap()).collect::<Vec<_>>();
+        for (log, block) in logs.iter().zip(&blocks) {
+            assert_eq!(log.inner.data.data.as_ref(), block.to_be_bytes());
+        }
+        assert!(blocks.windows(2).all(|pair| pair[0] < pair[1]));
+        blocks
+    }
+
+    /// Blocks with logs at the first and last block of the chain and around the chunk boundaries.
+    const LOG_BLOCKS: [u64; 7] = [
+        0,
+        MAX_HEADERS_RANGE,
+        MAX_HEADERS_RANGE + 1,
+        MAX_HEADERS_RANGE + 2,
+        2 * (MAX_HEADERS_RANGE + 1) - 1,
+        2 * (MAX_HEADERS_RANGE + 1),
+        LAST_BLOCK,
+    ];
+
+    #[tokio::test]
+    async fn logs_in_chunk() {
+        let filter = chain_with_logs(&LOG_BLOCKS, None, EthFilterConfig::default());
+
+        // the hash of the last block of a chunk isn't known from the next header
+        let logs = filter
+            .inner
+            .get_logs_in_chunk(&Filter::new(), MAX_HEADERS_RANGE - 1, MAX_HEADERS_RANGE + 1)
+            .unwrap();
+        assert_eq!(log_blocks(&logs), [MAX_HEADERS_RANGE, MAX_HEADERS_RANGE + 1]);
+
+        let header = filter.inner.provider.header_by_number(MAX_HEADERS_RANGE + 1).unwrap();
+        let hash = filter.inner.provider.block_hash(MAX_HEADERS_RANGE + 1).unwrap();
+        assert_eq!(logs[1].block_hash, hash);
+        assert_eq!(logs[1].block_timestamp, header.map(|header| header.timestamp));
+
+        // logs of other addresses are filtered out
+        let logs = filter
+            .inner
+            .get_logs_in_chunk(&Filter::new().address(Address::with_last_byte(2)), 0, LAST_BLOCK)
+            .unwrap();
+        assert!(logs.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn logs_in_finalized_range_are_scanned_in_chunks() {
+        let config = EthFilterConfig::default().max_concurrent_getlogs_chunks(2);
+        let filter = chain_with_logs(&LOG_BLOCKS, Some(LAST_BLOCK), config);
+
+        // the chunks are scanned concurrently, but the logs are returned in block order
+        let logs = filter.inner.get_logs_in_finalized_range(&Filter::new(), 0, LAST_BLOCK).await;
+        assert_eq!(log_blocks(&logs.unwrap()), LOG_BLOCKS);
+
+        // a range that starts and ends at chunk boundaries
+        let logs = filter
+            .inner
+            .get_logs_in_finalized_range(&Filter::new(), MAX_HEADERS_RANGE + 1, LAST_BLOCK)
+            .await;
+        assert_eq!(log_blocks(&logs.unwrap()), LOG_BLOCKS[2..]);
+
+        // same logs as scanning the range sequentially
+        let sequential = chain_with_logs(&LOG_BLOCKS, Some(LAST_BLOCK), EthFilterConfig::default());
+        let filter_logs = Filter::new().from_block(0).to_block(LAST_BLOCK);
+        assert_eq!(
+            EthFilterApiServer::logs(&filter, filter_logs.clone()).await.unwrap(),
+            EthFilterApiServer::logs(&sequential, filter_logs).await.unwrap()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn logs_in_range_crossing_finality() {
+        let config = EthFilterConfig::default().max_concurrent_getlogs_chunks(2);
+        let finalized = MAX_HEADERS_RANGE + 1;
+        let filter = chain_with_logs(&LOG_BLOCKS, Some(finalized), config);
+
+        // blocks after the finalized block may be reorged, so the range is scanned sequentially
+        // and includes the logs on both sides of the finalized block
+        let logs =
+            EthFilterApiServer::logs(&filter, Filter::new().from_block(0).to_block(LAST_BLOCK))
+                .await
+                .unwrap();
+        assert_eq!(log_blocks(&logs), LOG_BLOCKS);
+
+        // a range that ends at the finalized block is scanned in chunks
+        let logs =
+            EthFilterApiServer::logs(&filter, Filter::new().from_block(0).to_block(finalized))
+                .await
+                .unwrap();
+        assert_eq!(log_blocks(&logs), LOG_BLOCKS[..3]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn logs_in_finalized_range_exceeding_limit() {
+        let config = EthFilterConfig::default()
+            .max_concurrent_getlogs_chunks(2)
+            .max_logs_per_response(LOG_BLOCKS.len() - 1);
+        let filter = chain_with_logs(&LOG_BLOCKS, Some(LAST_BLOCK), config);
+
+        // each chunk is within the limit, all chunks together exceed it
+        let err = filter
+            .inner
+            .get_logs_in_finalized_range(&Filter::new(), 0, LAST_BLOCK)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, EthFilterError::QueryExceedsMaxResults(max) if max == LOG_BLOCKS.len() - 1)
+        );
+
+        // a chunk that alone exceeds the limit
+        let config = EthFilterConfig::default().max_logs_per_response(1);
+        let filter = chain_with_logs(&LOG_BLOCKS, Some(LAST_BLOCK), config);
+        let err = filter.inner.get_logs_in_chunk(&Filter::new(), 0, MAX_HEADERS_RANGE).unwrap_err();
+        assert!(matches!(err, EthFilterError::QueryExceedsMaxResults(1)));
+    }
 
     #[test]
     fn test_block_range_iter() {
@@ -1,5 +1,7 @@
 //! Contains RPC handler implementations specific to endpoints that call/execute within evm.
 
+use std::time::Duration;
+
 use reth_evm::ConfigureEvm;
 use reth_rpc_eth_api::helpers::{Call, EthCall, LoadPendingBlock, LoadState, SpawnBlocking};
 
@@ -20,6 +22,11 @@ where
         self.inner.gas_cap()
     }
 
+    #[inline]
+    fn call_timeout(&self) -> Option<Duration> {
+        self.inner.limits().call_timeout()
+    }
+
     #[inline]
     fn evm_config(&self) -> &impl ConfigureEvm {
         self.inner.evm_config()
@@ -44,4 +44,9 @@ where
     fn evm_config(&self) -> &impl ConfigureEvm {
         self.inner.evm_config()
     }
+
+    #[inline]
+    fn pending_receipts_enabled(&self) -> bool {
+        self.inner.pending_receipts()
+    }
 }
@@ -1,16 +1,22 @@
 //! An abstraction over ethereum signers.
 
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use alloy_dyn_abi::TypedData;
+use alloy_signer_local::{coins_bip39::English, MnemonicBuilder};
+use reth_fs_util::FsPathError;
 use reth_primitives::{
     eip191_hash_message, sign_message, Address, Signature, TransactionSigned, B256,
 };
 use reth_rpc_eth_api::helpers::{signer::Result, AddDevSigners, EthSigner};
-use reth_rpc_eth_types::SignError;
+use reth_rpc_eth_types::{DevSignerConfig, SignError};
 use reth_rpc_types::TypedTransactionRequest;
 use reth_rpc_types_compat::transaction::to_primitive_transaction;
-use secp256k1::SecretKey;
+use secp256k1::{SecretKey, SECP256K1};
 
 use crate::EthApi;
 
@@ -20,6 +26,29 @@ impl<Provider, Pool, Network, EvmConfig> AddDevSigners
     fn with_dev_accounts(&self) {
         *self.inner.signers().write() = DevSigner::random_signers(20)
     }
+
+    fn with_dev_signers(&self, signers: Vec<Box<dyn EthSigner>>) {
+        *self.inner.signers().write() = signers
+    }
+}
+
+/// Errors when loading the accounts of a [`DevSigner`].
+#[derive(Debug, thiserror::Error)]
+pub enum DevSignerError {
+    /// The accounts could not be derived from the mnemonic.
+    #[error("invalid dev signer mnemonic: {0}")]
+    Mnemonic(String),
+    /// A key file could not be read.
+    #[error(transparent)]
+    KeyFile(#[from] FsPathError),
+    /// A key file contains an invalid private key.
+    #[error("invalid private key in {path:?} at line {line}")]
+    InvalidKey {
+        /// The key file.
+        path: PathBuf,
+        /// The line of the invalid key.
+        line: usize,
+    },
 }
 
 /// Holds developer keys
@@ -52,6 +81,73 @@ impl DevSigner {
         signers
     }
 
+    /// Creates a dev signer for each of the given private keys.
+    pub fn from_secret_keys(
+        keys: impl IntoIterator<Item = SecretKey>,
+    ) -> Vec<Box<dyn EthSigner + 'static>> {
+        keys.into_iter()
+            .map(|sk| {
+                let address = reth_primitives::public_key_to_address(sk.public_key(SECP256K1));
+                let addresses = vec![address];
+                let accounts = HashMap::from([(address, sk)]);
+                Box::new(Self { addresses, accounts }) as Box<dyn EthSigner>
+            })
+            .collect()
+    }
+
+    /// Derives the given number of dev signers from the mnemonic, using the default derivation
+    /// path `m/44'/60'/0'/0/{index}`.
+    pub fn from_mnemonic(
+        mnemonic: &str,
+        num: u32,
+    ) -> std::result::Result<Vec<Box<dyn EthSigner + 'static>>, DevSignerError> {
+        let keys = (0..num)
+            .map(|index| {
+                let signer = MnemonicBuilder::<English>::default()
+                    .phrase(mnemonic)
+                    .index(index)
+                    .and_then(|builder| builder.build())
+                    .map_err(|err| DevSignerError::Mnemonic(err.to_string()))?;
+                SecretKey::from_slice(&signer.credential().to_bytes())
+                    .map_err(|err| DevSignerError::Mnemonic(err.to_string()))
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(Self::from_secret_keys(keys))
+    }
+
+    /// Reads hex encoded private keys from the file, one per line, and creates a dev signer for
+    /// each of them.
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn from_key_file(
+        path: &Path,
+    ) -> std::result::Result<Vec<Box<dyn EthSigner + 'static>>, DevSignerError> {
+        let contents = reth_fs_util::read_to_string(path)?;
+        let keys = contents
+            .lines()
+            .map(str::trim)
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(index, line)| {
+                SecretKey::from_str(line.trim_start_matches("0x")).map_err(|_| {
+                    DevSignerError::InvalidKey { path: path.to_path_buf(), line: index + 1 }
+                })
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(Self::from_secret_keys(keys))
+    }
+
+    /// Creates the dev signers for all accounts of the [`DevSignerConfig`].
+    pub fn from_config(
+        config: &DevSignerConfig,
+    ) -> std::result::Result<Vec<Box<dyn EthSigner + 'static>>, DevSignerError> {
+        let mut signers = Self::from_mnemonic(&config.mnemonic, config.accounts)?;
+        for path in &config.key_files {
+            signers.extend(Self::from_key_file(path)?);
+        }
+        Ok(signers)
+    }
+
     fn get_key(&self, account: Address) -> Result<&SecretKey> {
         self.accounts.get(&account).ok_or(SignError::NoAccount)
     }
@@ -102,9 +198,7 @@ impl EthSigner for DevSigner {
 
 #[cfg(test)]
 mod tests {
-    use std::str::FromStr;
-
-    use reth_primitives::U256;
+    use reth_primitives::{address, U256};
 
     use super::*;
 
@@ -222,4 +316,44 @@ mod tests {
         };
         assert_eq!(sig, expected)
     }
+
+    #[test]
+    fn test_dev_signers_from_mnemonic() {
+        let signers =
+            DevSigner::from_mnemonic(reth_rpc_server_types::constants::DEFAULT_DEV_MNEMONIC, 2)
+                .unwrap();
+        let accounts = signers.iter().flat_map(|signer| signer.accounts()).collect::<Vec<_>>();
+        assert_eq!(
+            accounts,
+            vec![
+                address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266"),
+                address!("70997970C51812dc3A010C7d01b50e0d17dc79C8"),
+            ]
+        );
+
+        assert!(DevSigner::from_mnemonic("not a mnemonic", 1).is_err());
+    }
+
+    #[test]
+    fn test_dev_signers_from_key_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys");
+        std::fs::write(
+            &path,
+            "# dev keys\n\n0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80\n",
+        )
+        .unwrap();
+        let signers = DevSigner::from_key_file(&path).unwrap();
+        assert_eq!(signers.len(), 1);
+        assert_eq!(
+            signers[0].accounts(),
+            vec![address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266")]
+        );
+
+        std::fs::write(&path, "0xac09\n").unwrap();
+        assert!(matches!(
+            DevSigner::from_key_file(&path),
+            Err(DevSignerError::InvalidKey { line: 1, .. })
+        ));
+    }
 }
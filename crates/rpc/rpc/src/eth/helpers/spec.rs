@@ -3,6 +3,7 @@ use reth_network_api::NetworkInfo;
 use reth_primitives::U256;
 use reth_provider::{BlockNumReader, ChainSpecProvider, StageCheckpointReader};
 use reth_rpc_eth_api::helpers::EthApiSpec;
+use reth_stages_types::SyncProgress;
 use reth_transaction_pool::TransactionPool;
 
 use crate::EthApi;
@@ -33,4 +34,8 @@ where
     fn signers(&self) -> &parking_lot::RwLock<Vec<Box<dyn reth_rpc_eth_api::helpers::EthSigner>>> {
         self.inner.signers()
     }
+
+    fn sync_progress(&self) -> &SyncProgress {
+        self.inner.sync_progress()
+    }
 }
@@ -1,31 +1,42 @@
 //! `eth_` `PubSub` RPC handler implementation
 
-use std::sync::Arc;
+use std::{collections::VecDeque, sync::Arc, time::Duration};
 
 use futures::StreamExt;
 use jsonrpsee::{
-    server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink, SubscriptionSink,
+    core::server::TrySendError, server::SubscriptionMessage, types::ErrorObject,
+    PendingSubscriptionSink, SubscriptionSink,
 };
 use reth_network_api::NetworkInfo;
 use reth_primitives::{IntoRecoveredTransaction, TxHash};
 use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider};
-use reth_rpc_eth_api::pubsub::EthPubSubApiServer;
-use reth_rpc_eth_types::logs_utils;
-use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_rpc_eth_api::{helpers::spec::stage_progress, pubsub::EthPubSubApiServer};
+use reth_rpc_eth_types::{logs_utils, state_changes};
+use reth_rpc_server_types::{
+    blob::BlobSidecarItem,
+    pubsub::{DroppedSubscriptionMessages, DROPPED_SUBSCRIPTION_MESSAGES_METHOD},
+    result::{internal_rpc_err, invalid_params_rpc_err},
+    state_changes::BlockStateChanges,
+    sync::{PubSubSyncStatus, SyncStatusMetadata},
+    RethSubscriptionKind, SubscriptionBackpressure,
+};
 use reth_rpc_types::{
-    pubsub::{
-        Params, PubSubSyncStatus, SubscriptionKind, SubscriptionResult as EthSubscriptionResult,
-        SyncStatusMetadata,
-    },
+    pubsub::{Params, SubscriptionKind, SubscriptionResult as EthSubscriptionResult},
     FilteredParams, Header, Log,
 };
+use reth_stages_types::SyncProgress;
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
-use reth_transaction_pool::{NewTransactionEvent, TransactionPool};
+use reth_transaction_pool::{NewBlobSidecar, NewTransactionEvent, TransactionPool};
 use serde::Serialize;
+use tokio::{sync::oneshot, time::MissedTickBehavior};
 use tokio_stream::{
     wrappers::{BroadcastStream, ReceiverStream},
     Stream,
 };
+use tracing::trace;
+
+/// How often the `syncing` subscription checks for progress of the sync stages.
+const SYNC_PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
 
 /// `Eth` pubsub RPC implementation.
 ///
@@ -36,6 +47,10 @@ pub struct EthPubSub<Provider, Pool, Events, Network> {
     inner: Arc<EthPubSubInner<Provider, Pool, Events, Network>>,
     /// The type that's used to spawn subscription tasks.
     subscription_task_spawner: Box<dyn TaskSpawner>,
+    /// What to do with messages if a subscriber can't keep up.
+    backpressure: SubscriptionBackpressure,
+    /// Progress of the sync stages reported by the `syncing` subscription.
+    sync_progress: SyncProgress,
 }
 
 // === impl EthPubSub ===
@@ -63,7 +78,24 @@ impl<Provider, Pool, Events, Network> EthPubSub<Provider, Pool, Events, Network>
         subscription_task_spawner: Box<dyn TaskSpawner>,
     ) -> Self {
         let inner = EthPubSubInner { provider, pool, chain_events, network };
-        Self { inner: Arc::new(inner), subscription_task_spawner }
+        Self {
+            inner: Arc::new(inner),
+            subscription_task_spawner,
+            backpressure: SubscriptionBackpressure::default(),
+            sync_progress: SyncProgress::default(),
+        }
+    }
+
+    /// Sets the [`SubscriptionBackpressure`] policy applied to all subscriptions.
+    pub const fn with_backpressure(mut self, backpressure: SubscriptionBackpressure) -> Self {
+        self.backpressure = backpressure;
+        self
+    }
+
+    /// Sets the [`SyncProgress`] of the sync stages reported by the `syncing` subscription.
+    pub fn with_sync_progress(mut self, sync_progress: SyncProgress) -> Self {
+        self.sync_progress = sync_progress;
+        self
     }
 }
 
@@ -80,16 +112,26 @@ where
     async fn subscribe(
         &self,
         pending: PendingSubscriptionSink,
-        kind: SubscriptionKind,
+        kind: RethSubscriptionKind,
         params: Option<Params>,
     ) -> jsonrpsee::core::SubscriptionResult {
         let sink = pending.accept().await?;
         let pubsub = self.inner.clone();
+        let backpressure = self.backpressure;
+        let sync_progress = self.sync_progress.clone();
+        let (tx, rx) = oneshot::channel();
         self.subscription_task_spawner.spawn(Box::pin(async move {
-            let _ = handle_accepted(pubsub, sink, kind, params).await;
+            let _ = tx.send(
+                handle_accepted(pubsub, sink, kind, params, backpressure, sync_progress).await,
+            );
         }));
 
-        Ok(())
+        // closes the subscription with an error notification if it failed, for example because
+        // the subscriber couldn't keep up
+        match rx.await {
+            Ok(Err(err)) => Err(err.message().into()),
+            _ => Ok(()),
+        }
     }
 }
 
@@ -97,8 +139,10 @@ where
 async fn handle_accepted<Provider, Pool, Events, Network>(
     pubsub: Arc<EthPubSubInner<Provider, Pool, Events, Network>>,
     accepted_sink: SubscriptionSink,
-    kind: SubscriptionKind,
+    kind: RethSubscriptionKind,
     params: Option<Params>,
+    backpressure: SubscriptionBackpressure,
+    sync_progress: SyncProgress,
 ) -> Result<(), ErrorObject<'static>>
 where
     Provider: BlockReader + EvmEnvProvider + Clone + 'static,
@@ -106,12 +150,30 @@ where
     Events: CanonStateSubscriptions + Clone + 'static,
     Network: NetworkInfo + Clone + 'static,
 {
+    let kind = match kind {
+        RethSubscriptionKind::Eth(kind) => kind,
+        RethSubscriptionKind::BlobSidecars => {
+            if !matches!(params, None | Some(Params::None)) {
+                return Err(invalid_params_rpc_err("Invalid params for blobSidecars"))
+            }
+            let stream = pubsub.blob_sidecar_stream();
+            return pipe_from_stream(accepted_sink, stream, backpressure).await
+        }
+        RethSubscriptionKind::StateChanges => {
+            if !matches!(params, None | Some(Params::None)) {
+                return Err(invalid_params_rpc_err("Invalid params for stateChanges"))
+            }
+            let stream = pubsub.state_changes_stream();
+            return pipe_from_stream(accepted_sink, stream, backpressure).await
+        }
+    };
+
     match kind {
         SubscriptionKind::NewHeads => {
             let stream = pubsub
                 .new_headers_stream()
                 .map(|block| EthSubscriptionResult::Header(Box::new(block.into())));
-            pipe_from_stream(accepted_sink, stream).await
+            pipe_from_stream(accepted_sink, stream, backpressure).await
         }
         SubscriptionKind::Logs => {
             // if no params are provided, used default filter params
@@ -124,7 +186,7 @@ where
             };
             let stream =
                 pubsub.log_stream(filter).map(|log| EthSubscriptionResult::Log(Box::new(log)));
-            pipe_from_stream(accepted_sink, stream).await
+            pipe_from_stream(accepted_sink, stream, backpressure).await
         }
         SubscriptionKind::NewPendingTransactions => {
             if let Some(params) = params {
@@ -138,7 +200,7 @@ where
                                 ),
                             ))
                         });
-                        return pipe_from_stream(accepted_sink, stream).await
+                        return pipe_from_stream(accepted_sink, stream, backpressure).await
                     }
                     Params::Bool(false) | Params::None => {
                         // only hashes requested
@@ -154,41 +216,11 @@ where
             let stream = pubsub
                 .pending_transaction_hashes_stream()
                 .map(EthSubscriptionResult::TransactionHash);
-            pipe_from_stream(accepted_sink, stream).await
+            pipe_from_stream(accepted_sink, stream, backpressure).await
         }
         SubscriptionKind::Syncing => {
-            // get new block subscription
-            let mut canon_state =
-                BroadcastStream::new(pubsub.chain_events.subscribe_to_canonical_state());
-            // get current sync status
-            let mut initial_sync_status = pubsub.network.is_syncing();
-            let current_sub_res = pubsub.sync_status(initial_sync_status);
-
-            // send the current status immediately
-            let msg = SubscriptionMessage::from_json(&current_sub_res)
-                .map_err(SubscriptionSerializeError::new)?;
-            if accepted_sink.send(msg).await.is_err() {
-                return Ok(())
-            }
-
-            while canon_state.next().await.is_some() {
-                let current_syncing = pubsub.network.is_syncing();
-                // Only send a new response if the sync status has changed
-                if current_syncing != initial_sync_status {
-                    // Update the sync status on each new block
-                    initial_sync_status = current_syncing;
-
-                    // send a new message now that the status changed
-                    let sync_status = pubsub.sync_status(current_syncing);
-                    let msg = SubscriptionMessage::from_json(&sync_status)
-                        .map_err(SubscriptionSerializeError::new)?;
-                    if accepted_sink.send(msg).await.is_err() {
-                        break
-                    }
-                }
-            }
-
-            Ok(())
+            let stream = pubsub.sync_status_stream(sync_progress);
+            pipe_from_stream(accepted_sink, stream, backpressure).await
         }
     }
 }
@@ -210,9 +242,100 @@ impl From<SubscriptionSerializeError> for ErrorObject<'static> {
     }
 }
 
+/// Returns the error the subscription is closed with if the subscriber can't keep up with the
+/// [`SubscriptionBackpressure::Disconnect`] policy.
+fn subscriber_lagging_err() -> ErrorObject<'static> {
+    internal_rpc_err("subscriber couldn't keep up, subscription closed")
+}
+
 /// Pipes all stream items to the subscription sink.
+///
+/// If the subscriber can't keep up, the given [`SubscriptionBackpressure`] policy is applied to the
+/// buffered messages.
 async fn pipe_from_stream<T, St>(
     sink: SubscriptionSink,
+    stream: St,
+    backpressure: SubscriptionBackpressure,
+) -> Result<(), ErrorObject<'static>>
+where
+    St: Stream<Item = T>,
+    T: Serialize,
+{
+    let mut stream = std::pin::pin!(stream);
+    if backpressure == SubscriptionBackpressure::Disconnect {
+        return pipe_or_disconnect(sink, stream).await
+    }
+
+    let capacity = match backpressure {
+        SubscriptionBackpressure::Buffer(capacity) => capacity.max(1),
+        _ => sink.max_capacity(),
+    };
+    let mut buffered = VecDeque::<SubscriptionMessage>::new();
+    // number of messages dropped since the last delivered message
+    let mut dropped = 0;
+    let mut stream_ended = false;
+
+    loop {
+        // the next message to deliver, a pending drop notification always goes first
+        let next = if dropped > 0 {
+            Some(
+                SubscriptionMessage::new(
+                    DROPPED_SUBSCRIPTION_MESSAGES_METHOD,
+                    sink.subscription_id(),
+                    &DroppedSubscriptionMessages { dropped },
+                )
+                .map_err(SubscriptionSerializeError::new)?,
+            )
+        } else {
+            buffered.front().cloned()
+        };
+
+        if next.is_none() && stream_ended {
+            break Ok(())
+        }
+
+        // only read new items if there's room for them or old items can be dropped
+        let can_read = !stream_ended &&
+            (buffered.len() < capacity || backpressure == SubscriptionBackpressure::DropOldest);
+
+        tokio::select! {
+            biased;
+            _ = sink.closed() => {
+                // connection dropped
+                break Ok(())
+            },
+            res = async { sink.send(next.expect("checked above")).await }, if next.is_some() => {
+                if res.is_err() {
+                    break Ok(())
+                }
+                if dropped > 0 {
+                    dropped = 0;
+                } else {
+                    buffered.pop_front();
+                }
+            },
+            maybe_item = stream.next(), if can_read => {
+                let Some(item) = maybe_item else {
+                    // stream ended, deliver what's left
+                    stream_ended = true;
+                    continue
+                };
+                let msg = SubscriptionMessage::from_json(&item).map_err(SubscriptionSerializeError::new)?;
+                if buffered.len() >= capacity {
+                    buffered.pop_front();
+                    dropped += 1;
+                    trace!(target: "rpc::eth", dropped, "subscriber lagging, dropped oldest message");
+                }
+                buffered.push_back(msg);
+            }
+        }
+    }
+}
+
+/// Pipes all stream items to the subscription sink and closes the subscription as soon as the
+/// subscriber can't keep up.
+async fn pipe_or_disconnect<T, St>(
+    mut sink: SubscriptionSink,
     mut stream: St,
 ) -> Result<(), ErrorObject<'static>>
 where
@@ -226,16 +349,18 @@ where
                 break Ok(())
             },
             maybe_item = stream.next() => {
-                let item = match maybe_item {
-                    Some(item) => item,
-                    None => {
-                        // stream ended
-                        break  Ok(())
-                    },
+                let Some(item) = maybe_item else {
+                    // stream ended
+                    break Ok(())
                 };
                 let msg = SubscriptionMessage::from_json(&item).map_err(SubscriptionSerializeError::new)?;
-                if sink.send(msg).await.is_err() {
-                    break Ok(());
+                match sink.try_send(msg) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(_)) => {
+                        trace!(target: "rpc::eth", "subscriber lagging, closing subscription");
+                        break Err(subscriber_lagging_err())
+                    }
+                    Err(TrySendError::Closed(_)) => break Ok(()),
                 }
             }
         }
@@ -270,22 +395,79 @@ where
     Provider: BlockReader + 'static,
 {
     /// Returns the current sync status for the `syncing` subscription
-    fn sync_status(&self, is_syncing: bool) -> EthSubscriptionResult {
+    fn sync_status(&self, is_syncing: bool, sync_progress: &SyncProgress) -> PubSubSyncStatus {
         if is_syncing {
             let current_block =
                 self.provider.chain_info().map(|info| info.best_number).unwrap_or_default();
-            EthSubscriptionResult::SyncState(PubSubSyncStatus::Detailed(SyncStatusMetadata {
-                syncing: true,
-                starting_block: 0,
-                current_block,
-                highest_block: Some(current_block),
-            }))
+            let stages: Vec<_> = sync_progress.stages().into_iter().map(stage_progress).collect();
+            let highest_block = stages
+                .iter()
+                .filter_map(|stage| stage.target)
+                .max()
+                .unwrap_or_default()
+                .max(current_block);
+            PubSubSyncStatus::Detailed(SyncStatusMetadata {
+                metadata: reth_rpc_types::pubsub::SyncStatusMetadata {
+                    syncing: true,
+                    starting_block: 0,
+                    current_block,
+                    highest_block: Some(highest_block),
+                },
+                stages,
+            })
         } else {
-            EthSubscriptionResult::SyncState(PubSubSyncStatus::Simple(false))
+            PubSubSyncStatus::Simple(false)
         }
     }
 }
 
+impl<Provider, Pool, Events, Network> EthPubSubInner<Provider, Pool, Events, Network>
+where
+    Provider: BlockReader + 'static,
+    Events: CanonStateSubscriptions,
+    Network: NetworkInfo,
+{
+    /// Returns a stream that yields the current sync status and then every change of it.
+    fn sync_status_stream(
+        self: Arc<Self>,
+        sync_progress: SyncProgress,
+    ) -> impl Stream<Item = PubSubSyncStatus> {
+        let canon_state = BroadcastStream::new(self.chain_events.subscribe_to_canonical_state());
+        // the pipeline doesn't emit new blocks while it's syncing, so its progress is polled
+        let mut interval = tokio::time::interval(SYNC_PROGRESS_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        futures::stream::unfold(
+            (self, canon_state, interval, None),
+            move |(pubsub, mut canon_state, mut interval, last_sync_status)| {
+                let sync_progress = sync_progress.clone();
+                async move {
+                    loop {
+                        // the current status is sent immediately
+                        if last_sync_status.is_some() {
+                            let ended = tokio::select! {
+                                block = canon_state.next() => block.is_none(),
+                                _ = interval.tick() => false,
+                            };
+                            if ended {
+                                return None
+                            }
+                        }
+
+                        // only send a new message if the sync status has changed
+                        let sync_status =
+                            pubsub.sync_status(pubsub.network.is_syncing(), &sync_progress);
+                        if last_sync_status.as_ref() != Some(&sync_status) {
+                            let state = (pubsub, canon_state, interval, Some(sync_status.clone()));
+                            return Some((sync_status, state))
+                        }
+                    }
+                }
+            },
+        )
+    }
+}
+
 impl<Provider, Pool, Events, Network> EthPubSubInner<Provider, Pool, Events, Network>
 where
     Pool: TransactionPool + 'static,
@@ -301,6 +483,20 @@ where
     ) -> impl Stream<Item = NewTransactionEvent<<Pool as TransactionPool>::Transaction>> {
         self.pool.new_pending_pool_transactions_listener()
     }
+
+    /// Returns a stream that yields the sidecars of all blob transactions added to the blob store
+    /// of the txpool.
+    fn blob_sidecar_stream(&self) -> impl Stream<Item = BlobSidecarItem> {
+        ReceiverStream::new(self.pool.blob_transaction_sidecars_listener()).map(
+            |NewBlobSidecar { tx_hash, sidecar }| BlobSidecarItem {
+                tx_hash,
+                block_hash: None,
+                block_number: None,
+                transaction_index: None,
+                sidecar: Arc::unwrap_or_clone(sidecar),
+            },
+        )
+    }
 }
 
 impl<Provider, Pool, Events, Network> EthPubSubInner<Provider, Pool, Events, Network>
@@ -320,6 +516,13 @@ where
         })
     }
 
+    /// Returns a stream that yields the state changes of all new canonical blocks.
+    fn state_changes_stream(&self) -> impl Stream<Item = BlockStateChanges> {
+        self.chain_events.canonical_state_stream().flat_map(|new_chain| {
+            futures::stream::iter(state_changes::chain_state_changes(&new_chain.committed()))
+        })
+    }
+
     /// Returns a stream that yields all logs that match the given filter.
     fn log_stream(&self, filter: FilteredParams) -> impl Stream<Item = Log> {
         BroadcastStream::new(self.chain_events.subscribe_to_canonical_state())
@@ -338,3 +541,115 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::{core::server::Subscription, RpcModule};
+    use parking_lot::Mutex;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        task::Poll,
+    };
+    use tokio::sync::Notify;
+
+    /// Capacity of the test subscriber's buffer.
+    const SUBSCRIBER_CAPACITY: usize = 2;
+
+    /// Shared state of a subscription in the tests.
+    #[derive(Default)]
+    struct TestSubscription {
+        /// Notified when the subscriber is ready.
+        subscribed: Notify,
+        /// Number of items read from the stream.
+        read: AtomicUsize,
+        /// Notified when the stream ended.
+        ended: Notify,
+        /// Result of [`pipe_from_stream`].
+        result: Mutex<Option<Result<(), ErrorObject<'static>>>>,
+    }
+
+    /// Subscribes to the items `0..10` with the given policy.
+    ///
+    /// The subscriber doesn't read any messages yet, so at most [`SUBSCRIBER_CAPACITY`] messages
+    /// are delivered.
+    async fn subscribe(
+        backpressure: SubscriptionBackpressure,
+    ) -> (Subscription, Arc<TestSubscription>) {
+        let state = Arc::new(TestSubscription::default());
+        let mut module = RpcModule::new(state.clone());
+        module
+            .register_subscription(
+                "sub",
+                "notif",
+                "unsub",
+                move |_, pending, state, _| async move {
+                    let sink = pending.accept().await?;
+                    state.subscribed.notified().await;
+                    let ended = state.clone();
+                    let stream = futures::stream::iter(0..10u64)
+                        .inspect(|_| {
+                            state.read.fetch_add(1, Ordering::Relaxed);
+                        })
+                        .chain(futures::stream::poll_fn(move |_| {
+                            ended.ended.notify_one();
+                            Poll::Ready(None)
+                        }));
+                    let result = pipe_from_stream(sink, stream, backpressure).await;
+                    *state.result.lock() = Some(result.clone());
+                    result.map_err(|err| err.message().to_string().into())
+                },
+            )
+            .unwrap();
+        let subscription =
+            module.subscribe("sub", jsonrpsee::rpc_params![], SUBSCRIBER_CAPACITY).await.unwrap();
+        state.subscribed.notify_one();
+        (subscription, state)
+    }
+
+    /// Receives the next message of the subscription.
+    async fn next(subscription: &mut Subscription) -> Option<serde_json::Value> {
+        subscription.next::<serde_json::Value>().await.map(|res| res.unwrap().0)
+    }
+
+    #[tokio::test]
+    async fn buffer_is_bounded() {
+        let capacity = 3;
+        let (mut subscription, state) = subscribe(SubscriptionBackpressure::Buffer(capacity)).await;
+
+        // the stream isn't read any further once the buffer is full
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(state.read.load(Ordering::Relaxed), SUBSCRIBER_CAPACITY + capacity);
+
+        // all messages are delivered once the subscriber catches up
+        for item in 0..10u64 {
+            assert_eq!(next(&mut subscription).await, Some(item.into()));
+        }
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_notifies_subscriber() {
+        let (mut subscription, state) = subscribe(SubscriptionBackpressure::DropOldest).await;
+        state.ended.notified().await;
+
+        // the delivered messages are followed by the drop notification and the newest messages
+        assert_eq!(next(&mut subscription).await, Some(0u64.into()));
+        assert_eq!(next(&mut subscription).await, Some(1u64.into()));
+        assert_eq!(next(&mut subscription).await, Some(serde_json::json!({ "dropped": 6 })));
+        assert_eq!(next(&mut subscription).await, Some(8u64.into()));
+        assert_eq!(next(&mut subscription).await, Some(9u64.into()));
+        assert_eq!(state.result.lock().take(), Some(Ok(())));
+    }
+
+    #[tokio::test]
+    async fn disconnect_on_overflow() {
+        let (mut subscription, state) = subscribe(SubscriptionBackpressure::Disconnect).await;
+
+        // the subscription is closed with an error once the subscriber's buffer is full
+        assert_eq!(next(&mut subscription).await, Some(0u64.into()));
+        assert_eq!(next(&mut subscription).await, Some(1u64.into()));
+        assert_eq!(next(&mut subscription).await, None);
+        assert_eq!(state.result.lock().take(), Some(Err(subscriber_lagging_err())));
+        assert_eq!(state.read.load(Ordering::Relaxed), SUBSCRIBER_CAPACITY + 1);
+    }
+}
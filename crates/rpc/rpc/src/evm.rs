@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
+use reth_auto_seal_consensus::{MiningError, MiningHandle};
+use reth_primitives::{U256, U64};
+use reth_rpc_api::EvmApiServer;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_rpc_types::anvil::MineOptions;
+
+/// `evm` API implementation.
+///
+/// This type mines blocks on demand and controls the timestamps of mined blocks in dev mode.
+#[derive(Debug, Clone)]
+pub struct EvmApi {
+    /// The handle to the mining task of the dev node.
+    mining: MiningHandle,
+}
+
+impl EvmApi {
+    /// Creates a new instance of `EvmApi`.
+    pub const fn new(mining: MiningHandle) -> Self {
+        Self { mining }
+    }
+}
+
+#[async_trait]
+impl EvmApiServer for EvmApi {
+    /// Handler for `evm_mine`
+    async fn evm_mine(&self, opts: Option<MineOptions>) -> RpcResult<String> {
+        let (timestamp, blocks) = match opts.unwrap_or_default() {
+            MineOptions::Options { timestamp, blocks } => (timestamp, blocks),
+            MineOptions::Timestamp(timestamp) => (timestamp, None),
+        };
+        if let Some(timestamp) = timestamp {
+            self.mining.set_next_block_timestamp(timestamp).await.map_err(mining_rpc_err)?;
+        }
+        self.mining.mine(blocks.unwrap_or(1)).await.map_err(mining_rpc_err)?;
+        Ok("0x0".to_string())
+    }
+
+    /// Handler for `evm_increaseTime`
+    async fn evm_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        let seconds = u64::try_from(seconds)
+            .map_err(|_| invalid_params_rpc_err("time increase is too large"))?;
+        Ok(self.mining.increase_time(seconds).await)
+    }
+
+    /// Handler for `evm_setNextBlockTimestamp`
+    async fn evm_set_next_block_timestamp(&self, timestamp: U64) -> RpcResult<()> {
+        self.mining.set_next_block_timestamp(timestamp.to()).await.map_err(mining_rpc_err)
+    }
+}
+
+/// Converts a [`MiningError`] into an RPC error.
+pub(crate) fn mining_rpc_err(err: MiningError) -> ErrorObjectOwned {
+    match err {
+        MiningError::TimestampTooLow { .. } => invalid_params_rpc_err(err.to_string()),
+        MiningError::TaskClosed |
+        MiningError::Failed |
+        MiningError::UnwindFailed |
+        MiningError::StateChangesUnsupported => internal_rpc_err(err.to_string()),
+    }
+}
@@ -33,23 +33,29 @@ use pin_project as _;
 use tower as _;
 
 mod admin;
+mod anvil;
 mod debug;
 mod engine;
 pub mod eth;
+mod evm;
 mod net;
 mod otterscan;
 mod reth;
+mod reth_db;
 mod rpc;
 mod trace;
 mod txpool;
 mod web3;
-pub use admin::AdminApi;
+pub use admin::{AdminApi, RpcConfigHandle};
+pub use anvil::{AnvilApi, AnvilFork};
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{EthApi, EthBundle, EthFilter, EthPubSub};
+pub use evm::EvmApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
-pub use reth::RethApi;
+pub use reth::{RethApi, RethApiConfig};
+pub use reth_db::RethDbApi;
 pub use rpc::RPCApi;
 pub use trace::TraceApi;
 pub use txpool::TxPoolApi;
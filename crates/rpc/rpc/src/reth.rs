@@ -1,40 +1,142 @@
-use std::{collections::HashMap, future::Future, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    ops::RangeInclusive,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
 
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
-use reth_errors::RethResult;
-use reth_primitives::{Address, BlockId, U256};
-use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use parking_lot::Mutex;
+use reth_chainspec::ChainSpec;
+use reth_errors::{ProviderResult, RethError, RethResult};
+use reth_network_api::{Peers, TransactionPropagation};
+use reth_primitives::{
+    keccak256, Address, BlockId, BlockNumber, BlockNumberOrTag, Bytes, TxHash, B256, U256, U64,
+};
+use reth_provider::{
+    BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, PruneCheckpointReader,
+    StateHistoryReader, StateProofProvider, StateProviderBox, StateProviderFactory,
+};
+use reth_prune_types::{PruneModes, PruneSegment};
+use reth_revm::{database::StateProviderDatabase, db::CacheDB};
 use reth_rpc_api::RethApiServer;
-use reth_rpc_eth_types::{EthApiError, EthResult};
+use reth_rpc_eth_api::{
+    helpers::{Call, EthApiSpec, EthState, LoadPendingBlock},
+    FromEthApiError,
+};
+use reth_rpc_eth_types::{
+    cache::db::{StateCacheDb, StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
+    error::ensure_success,
+    revm_utils::CallTimeoutInspector,
+    state_changes::block_state_changes,
+    EthApiError, EthConfig, EthResult, RevertError, RpcInvalidTransactionError, RpcLimits,
+};
+use reth_rpc_server_types::{
+    blob::BlobSidecarItem,
+    capabilities::{HardforkActivation, NodeCapabilities, PruneSegmentInfo},
+    proof::{MultiProofResponse, MultiProofTarget},
+    runtime::RpcModuleToggles,
+    snapshot::StateSnapshotInfo,
+    state_changes::BlockStateChanges,
+    state_history::{
+        AccountHistoryEntry, StateHistory, StorageHistoryEntry, DEFAULT_STATE_HISTORY_LIMIT,
+        MAX_STATE_HISTORY_LIMIT,
+    },
+    witness::{AccountWitness, CallWitness},
+    RethRpcModule, ToRpcResult,
+};
+use reth_rpc_types::{
+    serde_helpers::JsonStorageKey,
+    state::{EvmOverrides, StateOverride},
+    BlockOverrides, TransactionRequest,
+};
 use reth_tasks::TaskSpawner;
+use reth_transaction_pool::TransactionPool;
+use reth_trie::{HashedPostState, HashedStorage};
+use revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg, ExecutionResult};
 use tokio::sync::oneshot;
 
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
-pub struct RethApi<Provider> {
-    inner: Arc<RethApiInner<Provider>>,
+pub struct RethApi<Provider, Eth = (), Network = ()> {
+    inner: Arc<RethApiInner<Provider, Eth, Network>>,
 }
 
 // === impl RethApi ===
 
-impl<Provider> RethApi<Provider> {
+impl<Provider, Eth, Network> RethApi<Provider, Eth, Network> {
     /// The provider that can interact with the chain.
     pub fn provider(&self) -> &Provider {
         &self.inner.provider
     }
 
+    /// Access the underlying `Eth` API.
+    pub fn eth_api(&self) -> &Eth {
+        &self.inner.eth_api
+    }
+
+    /// Access the network.
+    pub fn network(&self) -> &Network {
+        &self.inner.network
+    }
+
+    /// The configuration reported by `reth_config`.
+    pub fn config(&self) -> &RethApiConfig {
+        &self.inner.config
+    }
+
+    /// Create a new instance of the [`RethApi`] with the given `eth` API and network, which are
+    /// required to serve all `reth_` methods.
+    pub fn with_eth_api(
+        provider: Provider,
+        eth_api: Eth,
+        network: Network,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        Self::with_config(provider, eth_api, network, task_spawner, RethApiConfig::default())
+    }
+
+    /// Create a new instance of the [`RethApi`] with the given configuration.
+    pub fn with_config(
+        provider: Provider,
+        eth_api: Eth,
+        network: Network,
+        task_spawner: Box<dyn TaskSpawner>,
+        config: RethApiConfig,
+    ) -> Self {
+        let inner = Arc::new(RethApiInner {
+            provider,
+            eth_api,
+            network,
+            task_spawner,
+            config,
+            snapshots: Default::default(),
+        });
+        Self { inner }
+    }
+}
+
+impl<Provider> RethApi<Provider> {
     /// Create a new instance of the [`RethApi`]
+    ///
+    /// This instance only provides the methods that are served from the provider, e.g.
+    /// [`RethApi::balance_changes_in_block`]. See [`RethApi::with_eth_api`] for all methods.
     pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = Arc::new(RethApiInner { provider, task_spawner });
-        Self { inner }
+        Self::with_eth_api(provider, (), (), task_spawner)
     }
 }
 
-impl<Provider> RethApi<Provider>
+impl<Provider, Eth, Network> RethApi<Provider, Eth, Network>
 where
     Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Eth: Send + Sync + 'static,
+    Network: Send + Sync + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -82,12 +184,408 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the accounts, storage slots and code changed by a particular block.
+    pub async fn state_changes(&self, block_id: BlockId) -> EthResult<BlockStateChanges> {
+        self.on_blocking_task(|this| async move { this.try_state_changes(block_id) }).await
+    }
+
+    fn try_state_changes(&self, block_id: BlockId) -> EthResult<BlockStateChanges> {
+        let Some(block_number) = self.provider().block_number_for_id(block_id)? else {
+            return Err(EthApiError::UnknownBlockNumber)
+        };
+        let Some(block_hash) = self.provider().block_hash(block_number)? else {
+            return Err(EthApiError::UnknownBlockNumber)
+        };
+
+        Ok(block_state_changes(self.provider(), block_number, block_hash)?)
+    }
+
+    /// Resolves the block range and the page size of a history query.
+    fn history_range(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        limit: Option<usize>,
+    ) -> EthResult<(RangeInclusive<BlockNumber>, usize)> {
+        let from_block = self
+            .provider()
+            .convert_block_number(from_block)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let to_block = self
+            .provider()
+            .convert_block_number(to_block)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        if from_block > to_block {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+
+        let limit = limit.unwrap_or(DEFAULT_STATE_HISTORY_LIMIT);
+        if limit == 0 || limit > MAX_STATE_HISTORY_LIMIT {
+            return Err(EthApiError::InvalidParams(format!(
+                "limit must be between 1 and {MAX_STATE_HISTORY_LIMIT}"
+            )))
+        }
+
+        Ok((from_block..=to_block, limit))
+    }
 }
 
-#[async_trait]
-impl<Provider> RethApiServer for RethApi<Provider>
+impl<Provider, Eth, Network> RethApi<Provider, Eth, Network>
+where
+    Provider:
+        BlockReaderIdExt + ChangeSetReader + StateProviderFactory + StateHistoryReader + 'static,
+    Eth: Send + Sync + 'static,
+    Network: Send + Sync + 'static,
+{
+    /// Returns the account after each block in the given range that changed it.
+    pub async fn balance_history(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        limit: Option<usize>,
+    ) -> EthResult<StateHistory<AccountHistoryEntry>> {
+        self.on_blocking_task(|this| async move {
+            let (range, limit) = this.history_range(from_block, to_block, limit)?;
+            // one more entry than requested tells whether there is a next page
+            let history = this.provider().account_history(address, range, limit + 1)?;
+            Ok(history_page(history, limit, |(block_number, account)| AccountHistoryEntry {
+                block_number,
+                account: account.map(Into::into),
+            }))
+        })
+        .await
+    }
+
+    /// Returns the value of the storage slot after each block in the given range that changed
+    /// it.
+    pub async fn storage_history(
+        &self,
+        address: Address,
+        slot: B256,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        limit: Option<usize>,
+    ) -> EthResult<StateHistory<StorageHistoryEntry>> {
+        self.on_blocking_task(|this| async move {
+            let (range, limit) = this.history_range(from_block, to_block, limit)?;
+            let history = this.provider().storage_history(address, slot, range, limit + 1)?;
+            Ok(history_page(history, limit, |(block_number, value)| StorageHistoryEntry {
+                block_number,
+                value,
+            }))
+        })
+        .await
+    }
+}
+
+/// Returns the first `limit` entries of the history, and the block of the next entry if there is
+/// one.
+fn history_page<T, E>(
+    mut history: Vec<(BlockNumber, T)>,
+    limit: usize,
+    entry: impl Fn((BlockNumber, T)) -> E,
+) -> StateHistory<E> {
+    let next_block = (history.len() > limit).then(|| history[limit].0);
+    history.truncate(limit);
+    StateHistory { entries: history.into_iter().map(entry).collect(), next_block }
+}
+
+impl<Provider, Eth, Network> RethApi<Provider, Eth, Network>
 where
     Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Eth: LoadPendingBlock + 'static,
+    Network: Send + Sync + 'static,
+{
+    /// Returns the sidecars of the blob transactions in the given block that are still available
+    /// in the blob store of the transaction pool.
+    pub async fn blob_sidecars_by_block(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<BlobSidecarItem>>> {
+        self.on_blocking_task(|this| async move { this.try_blob_sidecars_by_block(block_id) }).await
+    }
+
+    fn try_blob_sidecars_by_block(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<BlobSidecarItem>>> {
+        let Some(block_hash) = self.provider().block_hash_for_id(block_id)? else {
+            return Ok(None)
+        };
+        let Some(block) = self.provider().block_by_hash(block_hash)? else { return Ok(None) };
+
+        let blob_txs = block
+            .body
+            .iter()
+            .enumerate()
+            .filter(|(_, tx)| tx.is_eip4844())
+            .map(|(index, tx)| (tx.hash(), index as u64))
+            .collect::<HashMap<_, _>>();
+        if blob_txs.is_empty() {
+            return Ok(Some(Vec::new()))
+        }
+
+        let sidecars = LoadPendingBlock::pool(self.eth_api())
+            .get_all_blobs(blob_txs.keys().copied().collect())
+            .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+
+        let mut items = sidecars
+            .into_iter()
+            .map(|(tx_hash, sidecar)| BlobSidecarItem {
+                tx_hash,
+                block_hash: Some(block_hash),
+                block_number: Some(block.number),
+                transaction_index: blob_txs.get(&tx_hash).copied(),
+                sidecar,
+            })
+            .collect::<Vec<_>>();
+        items.sort_unstable_by_key(|item| item.transaction_index);
+
+        Ok(Some(items))
+    }
+}
+
+impl<Provider, Eth, Network> RethApi<Provider, Eth, Network>
+where
+    Provider: ChainSpecProvider<ChainSpec = ChainSpec> + PruneCheckpointReader + 'static,
+{
+    /// Returns the active chain spec, RPC configuration and prune configuration of the node.
+    pub fn node_capabilities(&self) -> EthResult<NodeCapabilities> {
+        let chain_spec = self.provider().chain_spec();
+        let RethApiConfig { namespaces, eth, limits, modules, prune_modes } = &self.inner.config;
+        let max_logs_per_response = limits.max_logs_per_response();
+
+        let hardforks = chain_spec
+            .hardforks
+            .forks_iter()
+            .map(|(fork, condition)| HardforkActivation {
+                name: fork.name().to_string(),
+                condition,
+            })
+            .collect();
+
+        let checkpoints =
+            self.provider().get_prune_checkpoints()?.into_iter().collect::<HashMap<_, _>>();
+        let prune = [
+            (PruneSegment::SenderRecovery, prune_modes.sender_recovery),
+            (PruneSegment::TransactionLookup, prune_modes.transaction_lookup),
+            (PruneSegment::Receipts, prune_modes.receipts),
+            (PruneSegment::AccountHistory, prune_modes.account_history),
+            (PruneSegment::StorageHistory, prune_modes.storage_history),
+        ]
+        .into_iter()
+        .filter_map(|(segment, mode)| {
+            Some(PruneSegmentInfo {
+                segment,
+                mode: mode?,
+                pruned_to_block: checkpoints
+                    .get(&segment)
+                    .and_then(|checkpoint| checkpoint.block_number),
+            })
+        })
+        .collect();
+
+        Ok(NodeCapabilities {
+            chain_id: chain_spec.chain().id(),
+            genesis_hash: chain_spec.genesis_hash(),
+            hardforks,
+            namespaces: namespaces
+                .iter()
+                .copied()
+                .filter(|namespace| modules.is_enabled(*namespace))
+                .collect(),
+            gas_cap: limits.gas_cap(),
+            max_blocks_per_filter: (eth.max_blocks_per_filter != u64::MAX)
+                .then_some(eth.max_blocks_per_filter),
+            max_logs_per_response: (max_logs_per_response != usize::MAX)
+                .then_some(max_logs_per_response),
+            prune,
+        })
+    }
+}
+
+impl<Provider, Eth, Network> RethApi<Provider, Eth, Network>
+where
+    Eth: Call + LoadPendingBlock + 'static,
+{
+    /// Executes the call request at the given block and returns its result together with the
+    /// state witness of the call.
+    pub async fn call_with_witness(
+        &self,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
+    ) -> Result<CallWitness, Eth::Error> {
+        let eth_api = self.eth_api().clone();
+        self.eth_api()
+            .spawn_with_call_at(
+                request,
+                block_id.unwrap_or_default(),
+                EvmOverrides::default(),
+                move |mut db, env| {
+                    let (res, _) = eth_api.transact(&mut db, env)?;
+                    call_witness(db.0, res.result).map_err(Eth::Error::from_eth_err)
+                },
+            )
+            .await
+    }
+
+    /// Pins the state at the given block, so that calls executed on it with
+    /// [`RethApi::call_on_snapshot`] see the same state until the snapshot expires.
+    pub async fn create_state_snapshot(
+        &self,
+        block_id: BlockId,
+    ) -> Result<StateSnapshotInfo, Eth::Error> {
+        let ttl = self.inner.config.eth.state_snapshot_ttl;
+        let max_snapshots = self.inner.config.eth.max_state_snapshots;
+
+        let (cfg, block_env, at) = self.eth_api().evm_env_at(block_id).await?;
+        let state = self.eth_api().state_at_block_id(at)?;
+
+        let expires_at = SystemTime::now() + ttl;
+        let info = self
+            .inner
+            .snapshots
+            .insert(max_snapshots, |id| StateSnapshot {
+                info: StateSnapshotInfo {
+                    id,
+                    block_number: block_env.number.saturating_to(),
+                    block_hash: at.as_block_hash(),
+                    expires_at: expires_at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+                },
+                cfg,
+                block_env,
+                state,
+                expires_at: Instant::now() + ttl,
+            })
+            .map_err(Eth::Error::from_eth_err)?;
+
+        // release the snapshot once it expired, so it doesn't keep the database transaction open
+        // until the next snapshot request
+        let snapshots = Arc::clone(&self.inner.snapshots);
+        self.inner.task_spawner.spawn(Box::pin(async move {
+            tokio::time::sleep(ttl).await;
+            snapshots.remove_expired();
+        }));
+
+        Ok(info)
+    }
+
+    /// Executes the call request on the state of the given snapshot.
+    pub async fn call_on_snapshot(
+        &self,
+        snapshot_id: U64,
+        request: TransactionRequest,
+        overrides: EvmOverrides,
+    ) -> Result<Bytes, Eth::Error> {
+        let snapshot = self.inner.snapshots.get(snapshot_id).ok_or_else(|| {
+            Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                "unknown state snapshot {snapshot_id}"
+            )))
+        })?;
+
+        let (res, _) = self
+            .eth_api()
+            .spawn_tracing(move |this| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(
+                    StateProviderTraitObjWrapper(&snapshot.state),
+                ));
+                let env = this.prepare_call_env(
+                    snapshot.cfg.clone(),
+                    snapshot.block_env.clone(),
+                    request,
+                    this.call_gas_limit(),
+                    &mut db,
+                    overrides,
+                )?;
+                let mut deadline = this.call_timeout().map(CallTimeoutInspector::new);
+                this.transact_with_deadline(
+                    StateCacheDbRefMutWrapper(&mut db),
+                    env,
+                    deadline.as_mut(),
+                )
+            })
+            .await?;
+
+        ensure_success(res.result).map_err(Eth::Error::from_eth_err)
+    }
+
+    /// Releases the given snapshot.
+    ///
+    /// Returns `false` if the snapshot does not exist or already expired.
+    pub fn drop_state_snapshot(&self, snapshot_id: U64) -> bool {
+        self.inner.snapshots.remove(snapshot_id)
+    }
+}
+
+/// Creates the [`CallWitness`] from the state that was loaded into the database while executing
+/// the call.
+///
+/// The changes of the call are not committed to the database, so it still contains the values
+/// of the accessed accounts and storage slots before the call.
+fn call_witness(db: &StateCacheDb<'_>, result: ExecutionResult) -> ProviderResult<CallWitness> {
+    let (output, gas_used, error) = match result {
+        ExecutionResult::Success { output, gas_used, .. } => (output.into_data(), gas_used, None),
+        ExecutionResult::Revert { output, gas_used } => {
+            let error = RevertError::new(output.clone()).to_string();
+            (output, gas_used, Some(error))
+        }
+        ExecutionResult::Halt { reason, gas_used } => {
+            let error = RpcInvalidTransactionError::halt(reason, gas_used).to_string();
+            (Bytes::new(), gas_used, Some(error))
+        }
+    };
+
+    let mut accounts = BTreeMap::new();
+    let mut hashed_state = HashedPostState::default();
+    for (address, account) in &db.accounts {
+        let info = account.info();
+        let hashed_address = keccak256(address);
+        hashed_state.accounts.insert(hashed_address, info.clone().map(Into::into));
+
+        let storage = hashed_state
+            .storages
+            .entry(hashed_address)
+            .or_insert_with(|| HashedStorage::new(false));
+        for (slot, value) in &account.storage {
+            storage.storage.insert(keccak256(B256::from(*slot)), *value);
+        }
+
+        accounts.insert(
+            *address,
+            AccountWitness {
+                code_hash: info.map(|info| info.code_hash),
+                storage: account.storage.keys().map(|slot| B256::from(*slot)).collect(),
+            },
+        );
+    }
+
+    // Only bytecodes that were actually loaded during execution are cached.
+    let codes = db
+        .contracts
+        .iter()
+        .filter(|(_, code)| !code.is_empty())
+        .map(|(hash, code)| (*hash, code.original_bytes()))
+        .collect();
+
+    let state = db.db.witness(HashedPostState::default(), hashed_state)?;
+
+    Ok(CallWitness { output, gas_used, error, accounts, codes, state })
+}
+
+#[async_trait]
+impl<Provider, Eth, Network> RethApiServer for RethApi<Provider, Eth, Network>
+where
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StateProviderFactory
+        + StateHistoryReader
+        + ChainSpecProvider<ChainSpec = ChainSpec>
+        + PruneCheckpointReader
+        + 'static,
+    Eth: Call + LoadPendingBlock + EthState + EthApiSpec + 'static,
+    Network: Peers + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -96,23 +594,345 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_getStateChanges`
+    async fn reth_get_state_changes(&self, block_id: BlockId) -> RpcResult<BlockStateChanges> {
+        Ok(Self::state_changes(self, block_id).await?)
+    }
+
+    /// Handler for `reth_getBalanceHistory`
+    async fn reth_get_balance_history(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        limit: Option<usize>,
+    ) -> RpcResult<StateHistory<AccountHistoryEntry>> {
+        Ok(Self::balance_history(self, address, from_block, to_block, limit).await?)
+    }
+
+    /// Handler for `reth_getStorageHistory`
+    async fn reth_get_storage_history(
+        &self,
+        address: Address,
+        slot: JsonStorageKey,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        limit: Option<usize>,
+    ) -> RpcResult<StateHistory<StorageHistoryEntry>> {
+        Ok(Self::storage_history(self, address, slot.0, from_block, to_block, limit).await?)
+    }
+
+    /// Handler for `reth_config`
+    async fn reth_config(&self) -> RpcResult<NodeCapabilities> {
+        Ok(self.node_capabilities()?)
+    }
+
+    /// Handler for `reth_callWithWitness`
+    async fn reth_call_with_witness(
+        &self,
+        request: TransactionRequest,
+        block_number: Option<BlockId>,
+    ) -> RpcResult<CallWitness> {
+        Self::call_with_witness(self, request, block_number).await.map_err(Into::into)
+    }
+
+    /// Handler for `reth_getMultiProof`
+    async fn reth_get_multi_proof(
+        &self,
+        targets: Vec<MultiProofTarget>,
+        block_number: Option<BlockId>,
+    ) -> RpcResult<MultiProofResponse> {
+        let res = self.eth_api().get_multi_proof(targets, block_number).map_err(Into::into)?;
+        res.await.map_err(Into::into)
+    }
+
+    /// Handler for `reth_getBlobSidecarsByBlock`
+    async fn reth_get_blob_sidecars_by_block(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<BlobSidecarItem>>> {
+        Ok(Self::blob_sidecars_by_block(self, block_id).await?)
+    }
+
+    /// Handler for `reth_createStateSnapshot`
+    async fn reth_create_state_snapshot(&self, block_id: BlockId) -> RpcResult<StateSnapshotInfo> {
+        Self::create_state_snapshot(self, block_id).await.map_err(Into::into)
+    }
+
+    /// Handler for `reth_callOnSnapshot`
+    async fn reth_call_on_snapshot(
+        &self,
+        snapshot_id: U64,
+        request: TransactionRequest,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> RpcResult<Bytes> {
+        Self::call_on_snapshot(
+            self,
+            snapshot_id,
+            request,
+            EvmOverrides::new(state_overrides, block_overrides),
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    /// Handler for `reth_dropStateSnapshot`
+    async fn reth_drop_state_snapshot(&self, snapshot_id: U64) -> RpcResult<bool> {
+        Ok(self.drop_state_snapshot(snapshot_id))
+    }
+
+    /// Handler for `reth_traceTransactionPropagation`
+    async fn reth_trace_transaction_propagation(
+        &self,
+        hash: TxHash,
+    ) -> RpcResult<Option<TransactionPropagation>> {
+        self.network().transaction_propagation(hash).await.to_rpc_result()
+    }
 }
 
-impl<Provider> std::fmt::Debug for RethApi<Provider> {
+impl<Provider, Eth, Network> std::fmt::Debug for RethApi<Provider, Eth, Network> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RethApi").finish_non_exhaustive()
     }
 }
 
-impl<Provider> Clone for RethApi<Provider> {
+impl<Provider, Eth, Network> Clone for RethApi<Provider, Eth, Network> {
     fn clone(&self) -> Self {
         Self { inner: Arc::clone(&self.inner) }
     }
 }
 
-struct RethApiInner<Provider> {
+struct RethApiInner<Provider, Eth, Network> {
     /// The provider that can interact with the chain.
     provider: Provider,
+    /// The implementation of `eth` API
+    eth_api: Eth,
+    /// An interface to interact with the network
+    network: Network,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
+    /// The configuration reported by `reth_config`.
+    config: RethApiConfig,
+    /// State snapshots created with `reth_createStateSnapshot`.
+    snapshots: Arc<StateSnapshots>,
+}
+
+/// A pinned state view created with `reth_createStateSnapshot`.
+struct StateSnapshot {
+    /// The info returned to the client.
+    info: StateSnapshotInfo,
+    /// The EVM configuration of the block the snapshot was created at.
+    cfg: CfgEnvWithHandlerCfg,
+    /// The block environment of the block the snapshot was created at.
+    block_env: BlockEnv,
+    /// The pinned state.
+    state: StateProviderBox,
+    /// When the snapshot is released.
+    expires_at: Instant,
+}
+
+/// The alive state snapshots.
+#[derive(Default)]
+struct StateSnapshots {
+    /// The identifier of the next snapshot.
+    next_id: AtomicU64,
+    /// All snapshots that have not been released yet.
+    snapshots: Mutex<HashMap<U64, Arc<StateSnapshot>>>,
+}
+
+impl StateSnapshots {
+    /// Adds a new snapshot, unless `max_snapshots` are already alive.
+    fn insert(
+        &self,
+        max_snapshots: usize,
+        snapshot: impl FnOnce(U64) -> StateSnapshot,
+    ) -> EthResult<StateSnapshotInfo> {
+        let mut snapshots = self.snapshots.lock();
+        let now = Instant::now();
+        snapshots.retain(|_, snapshot| snapshot.expires_at > now);
+        if snapshots.len() >= max_snapshots {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many state snapshots, at most {max_snapshots} can be alive"
+            )))
+        }
+
+        let id = U64::from(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let snapshot = snapshot(id);
+        let info = snapshot.info;
+        snapshots.insert(id, Arc::new(snapshot));
+        Ok(info)
+    }
+
+    /// Returns the snapshot if it exists and has not expired yet.
+    fn get(&self, id: U64) -> Option<Arc<StateSnapshot>> {
+        let mut snapshots = self.snapshots.lock();
+        let snapshot = snapshots.get(&id)?;
+        if snapshot.expires_at <= Instant::now() {
+            snapshots.remove(&id);
+            return None
+        }
+        Some(Arc::clone(snapshot))
+    }
+
+    /// Releases the snapshot, returns `false` if it does not exist or already expired.
+    fn remove(&self, id: U64) -> bool {
+        self.snapshots
+            .lock()
+            .remove(&id)
+            .is_some_and(|snapshot| snapshot.expires_at > Instant::now())
+    }
+
+    /// Releases all expired snapshots.
+    fn remove_expired(&self) {
+        let now = Instant::now();
+        self.snapshots.lock().retain(|_, snapshot| snapshot.expires_at > now);
+    }
+}
+
+/// Configuration of the node reported by the `reth_config` endpoint.
+#[derive(Debug, Clone, Default)]
+pub struct RethApiConfig {
+    /// The configured RPC namespaces.
+    pub namespaces: Vec<RethRpcModule>,
+    /// The `eth` namespace configuration, for the request limits.
+    pub eth: EthConfig,
+    /// The request limits, which take precedence over the `eth` configuration since they can be
+    /// changed at runtime.
+    pub limits: RpcLimits,
+    /// Tracks which of the namespaces were disabled at runtime.
+    pub modules: RpcModuleToggles,
+    /// The configured prune modes of the node.
+    pub prune_modes: PruneModes,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_genesis::{Genesis, GenesisAccount};
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_db_common::init::init_genesis;
+    use reth_primitives::{hex, TxKind};
+    use reth_provider::test_utils::{create_test_provider_factory_with_chain_spec, NoopProvider};
+    use reth_trie::root::storage_root_unhashed;
+    use revm::Evm;
+    use revm_primitives::SpecId;
+    use std::{collections::BTreeSet, time::Duration};
+
+    fn snapshot(id: U64, ttl: Duration) -> StateSnapshot {
+        StateSnapshot {
+            info: StateSnapshotInfo { id, block_number: 0, block_hash: None, expires_at: 0 },
+            cfg: CfgEnvWithHandlerCfg::new_with_spec_id(Default::default(), SpecId::LATEST),
+            block_env: Default::default(),
+            state: Box::new(NoopProvider::default()),
+            expires_at: Instant::now() + ttl,
+        }
+    }
+
+    #[test]
+    fn state_snapshots_lifecycle() {
+        let snapshots = StateSnapshots::default();
+        let ttl = Duration::from_secs(60);
+
+        let first = snapshots.insert(2, |id| snapshot(id, ttl)).unwrap();
+        let second = snapshots.insert(2, |id| snapshot(id, ttl)).unwrap();
+        assert_ne!(first.id, second.id);
+        assert!(snapshots.insert(2, |id| snapshot(id, ttl)).is_err());
+
+        assert!(snapshots.get(first.id).is_some());
+        assert!(snapshots.remove(first.id));
+        assert!(!snapshots.remove(first.id));
+        assert!(snapshots.get(first.id).is_none());
+
+        // expired snapshots are released and no longer count towards the limit
+        let expired = snapshots.insert(2, |id| snapshot(id, Duration::ZERO)).unwrap();
+        assert!(snapshots.get(expired.id).is_none());
+        snapshots.insert(2, |id| snapshot(id, Duration::ZERO)).unwrap();
+        snapshots.remove_expired();
+        assert_eq!(snapshots.snapshots.lock().len(), 1);
+    }
+
+    #[test]
+    fn call_witness_of_executed_call() {
+        // returns the value of storage slot 1
+        let code = Bytes::from_static(&hex!("60015460005260206000f3"));
+        let contract = Address::with_last_byte(0xc0);
+        let caller = Address::with_last_byte(0xca);
+        let storage = BTreeMap::from([
+            (B256::with_last_byte(1), B256::with_last_byte(0x2a)),
+            (B256::with_last_byte(2), B256::with_last_byte(0x2b)),
+        ]);
+        let genesis = Genesis::default().extend_accounts([
+            (
+                contract,
+                GenesisAccount::default()
+                    .with_code(Some(code.clone()))
+                    .with_storage(Some(storage.clone())),
+            ),
+            (caller, GenesisAccount::default().with_balance(U256::from(1_000_000_000))),
+        ]);
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().genesis(genesis).build());
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(factory.clone()).unwrap();
+
+        let state = factory.latest().unwrap();
+        let mut db = CacheDB::new(StateProviderDatabase::new(StateProviderTraitObjWrapper(&state)));
+        let result = Evm::builder()
+            .with_db(&mut db)
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(contract);
+                tx.gas_limit = 100_000;
+                tx.gas_price = U256::ZERO;
+            })
+            .build()
+            .transact()
+            .unwrap()
+            .result;
+        let gas_used = result.gas_used();
+        let witness = call_witness(&db, result).unwrap();
+
+        assert_eq!(witness.output, Bytes::from(B256::with_last_byte(0x2a)));
+        assert_eq!(witness.gas_used, gas_used);
+        assert_eq!(witness.error, None);
+
+        // only the slot read by the call is part of the witness
+        let code_hash = keccak256(&code);
+        assert_eq!(
+            witness.accounts[&contract],
+            AccountWitness {
+                code_hash: Some(code_hash),
+                storage: BTreeSet::from([B256::with_last_byte(1)]),
+            }
+        );
+        assert!(witness.accounts.contains_key(&caller));
+        assert_eq!(witness.codes, BTreeMap::from([(code_hash, code)]));
+
+        // the trie nodes are keyed by their hash and lead from the state root to the accessed
+        // storage
+        assert!(witness.state.iter().all(|(hash, node)| keccak256(node) == *hash));
+        assert!(witness.state.contains_key(&chain_spec.genesis_header().state_root));
+        let storage_root = storage_root_unhashed(
+            storage.into_iter().map(|(slot, value)| (slot, U256::from_be_bytes(value.0))),
+        );
+        assert!(witness.state.contains_key(&storage_root));
+    }
+
+    #[test]
+    fn history_pages() {
+        let history = vec![(2, U256::from(1)), (5, U256::from(2)), (9, U256::from(3))];
+        let entry = |(block_number, value)| StorageHistoryEntry { block_number, value };
+
+        let page = history_page(history.clone(), 2, entry);
+        assert_eq!(page.entries.len(), 2);
+        assert_eq!(page.next_block, Some(9));
+
+        let page = history_page(history, 3, entry);
+        assert_eq!(
+            page.entries.last(),
+            Some(&StorageHistoryEntry { block_number: 9, value: U256::from(3) })
+        );
+        assert_eq!(page.next_block, None);
+    }
 }
@@ -0,0 +1,127 @@
+use jsonrpsee::core::RpcResult;
+use reth_errors::ProviderResult;
+use reth_provider::{PruneCheckpointReader, StageCheckpointReader, StorageStatsReader};
+use reth_rpc_api::RethDbApiServer;
+use reth_rpc_server_types::{
+    db_stats::{
+        DatabaseStats, PruneCheckpointInfo, StageCheckpointInfo, StaticFileSegmentInfo, TableInfo,
+    },
+    ToRpcResult,
+};
+
+/// `reth_db` API implementation.
+///
+/// This type provides the composition of the node's storage: the database tables, the static
+/// files and the prune and stage checkpoints.
+#[derive(Debug, Clone)]
+pub struct RethDbApi<Provider> {
+    provider: Provider,
+}
+
+impl<Provider> RethDbApi<Provider>
+where
+    Provider: StorageStatsReader + PruneCheckpointReader + StageCheckpointReader,
+{
+    /// Creates a new instance of `RethDbApi`.
+    pub const fn new(provider: Provider) -> Self {
+        Self { provider }
+    }
+
+    fn try_tables(&self) -> ProviderResult<Vec<TableInfo>> {
+        Ok(self
+            .provider
+            .table_stats()?
+            .into_iter()
+            .map(|stats| TableInfo {
+                name: stats.table.to_string(),
+                entries: stats.entries,
+                size: stats.size() as u64,
+                leaf_pages: stats.leaf_pages,
+                branch_pages: stats.branch_pages,
+                overflow_pages: stats.overflow_pages,
+            })
+            .collect())
+    }
+
+    fn try_static_files(&self) -> ProviderResult<Vec<StaticFileSegmentInfo>> {
+        Ok(self
+            .provider
+            .static_file_stats()?
+            .into_iter()
+            .map(|stats| StaticFileSegmentInfo {
+                segment: stats.segment,
+                files: stats.files,
+                entries: stats.entries,
+                size: stats.size,
+                block_range: stats.block_range.map(Into::into),
+                tx_range: stats.tx_range.map(Into::into),
+            })
+            .collect())
+    }
+
+    fn try_prune_checkpoints(&self) -> ProviderResult<Vec<PruneCheckpointInfo>> {
+        Ok(self
+            .provider
+            .get_prune_checkpoints()?
+            .into_iter()
+            .map(|(segment, checkpoint)| PruneCheckpointInfo {
+                segment,
+                block_number: checkpoint.block_number,
+                tx_number: checkpoint.tx_number,
+                prune_mode: checkpoint.prune_mode,
+            })
+            .collect())
+    }
+
+    fn try_stage_checkpoints(&self) -> ProviderResult<Vec<StageCheckpointInfo>> {
+        Ok(self
+            .provider
+            .get_all_checkpoints()?
+            .into_iter()
+            .map(|(stage, checkpoint)| {
+                let entities = checkpoint.entities();
+                StageCheckpointInfo {
+                    stage,
+                    block_number: checkpoint.block_number,
+                    processed: entities.map(|entities| entities.processed),
+                    total: entities.map(|entities| entities.total),
+                }
+            })
+            .collect())
+    }
+}
+
+impl<Provider> RethDbApiServer for RethDbApi<Provider>
+where
+    Provider: StorageStatsReader + PruneCheckpointReader + StageCheckpointReader + 'static,
+{
+    /// Handler for `reth_db_stats`
+    fn stats(&self) -> RpcResult<DatabaseStats> {
+        Ok(DatabaseStats {
+            tables: self.try_tables().to_rpc_result()?,
+            static_files: self.try_static_files().to_rpc_result()?,
+            prune_checkpoints: self.try_prune_checkpoints().to_rpc_result()?,
+            stage_checkpoints: self.try_stage_checkpoints().to_rpc_result()?,
+        })
+    }
+
+    /// Handler for `reth_db_tables`
+    fn tables(&self) -> RpcResult<Vec<TableInfo>> {
+        self.try_tables().to_rpc_result()
+    }
+
+    /// Handler for `reth_db_staticFiles`
+    fn static_files(&self) -> RpcResult<Vec<StaticFileSegmentInfo>> {
+        self.try_static_files().to_rpc_result()
+    }
+
+    /// Handler for `reth_db_pruneCheckpoints`
+    fn prune_checkpoints(&self) -> RpcResult<Vec<PruneCheckpointInfo>> {
+        self.try_prune_checkpoints().to_rpc_result()
+    }
+
+    /// Handler for `reth_db_stageCheckpoints`
+    fn stage_checkpoints(&self) -> RpcResult<Vec<StageCheckpointInfo>> {
+        self.try_stage_checkpoints().to_rpc_result()
+    }
+}
@@ -7,15 +7,24 @@ use reth_consensus_common::calc::{
     base_block_reward, base_block_reward_pre_merge, block_reward, ommer_reward,
 };
 use reth_evm::ConfigureEvmEnv;
-use reth_primitives::{BlockId, Bytes, Header, B256, U256};
-use reth_provider::{BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
+use reth_primitives::{BlockId, BlockNumber, Bytes, Header, B256, U256};
+use reth_provider::{
+    BlockReader, ChainSpecProvider, EvmEnvProvider, ProviderResult, PruneCheckpointReader,
+    StateProviderFactory,
+};
+use reth_prune_types::PruneSegment;
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_api::TraceApiServer;
 use reth_rpc_eth_api::{
-    helpers::{Call, TraceExt},
+    helpers::{Call, LoadBlock, TraceExt},
     FromEthApiError,
 };
-use reth_rpc_eth_types::{error::EthApiError, utils::recover_raw_transaction};
+use reth_rpc_eth_types::{
+    error::EthApiError,
+    history::{is_pruned_history_rpc_err, pruned_history_rpc_err},
+    utils::recover_raw_transaction,
+    HistoricalRpcClient, PruneCheckpointCache,
+};
 use reth_rpc_types::{
     state::{EvmOverrides, StateOverride},
     trace::{
@@ -35,6 +44,7 @@ use revm_inspectors::{
     opcode::OpcodeGasInspector,
     tracing::{parity::populate_state_diff, TracingInspector, TracingInspectorConfig},
 };
+use serde::{de::DeserializeOwned, Serialize};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
 /// `trace` API implementation.
@@ -54,7 +64,24 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
 
     /// Create a new instance of the [`TraceApi`]
     pub fn new(provider: Provider, eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(TraceApiInner { provider, eth_api, blocking_task_guard });
+        Self::with_historical_fallback(provider, eth_api, blocking_task_guard, None)
+    }
+
+    /// Create a new instance of the [`TraceApi`] that forwards requests for pruned state to the
+    /// given archive node.
+    pub fn with_historical_fallback(
+        provider: Provider,
+        eth_api: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        historical_fallback: Option<HistoricalRpcClient>,
+    ) -> Self {
+        let inner = Arc::new(TraceApiInner {
+            provider,
+            eth_api,
+            blocking_task_guard,
+            historical_fallback,
+            prune_checkpoints: Default::default(),
+        });
         Self { inner }
     }
 
@@ -342,6 +369,23 @@ where
         &self,
         hash: B256,
     ) -> Result<Option<Vec<LocalizedTransactionTrace>>, Eth::Error> {
+        // Serve the traces from the cached traces of the block, if it was traced before.
+        if let Some((_, meta)) =
+            self.provider().transaction_by_hash_with_meta(hash).map_err(Eth::Error::from_eth_err)?
+        {
+            if let Some(traces) =
+                LoadBlock::cache(&self.inner.eth_api).traces().parity_traces(meta.block_hash)
+            {
+                return Ok(Some(
+                    traces
+                        .iter()
+                        .filter(|trace| trace.transaction_hash == Some(hash))
+                        .cloned()
+                        .collect(),
+                ))
+            }
+        }
+
         self.inner
             .eth_api
             .spawn_trace_transaction_in_block(
@@ -357,10 +401,24 @@ where
     }
 
     /// Returns traces created at given block.
+    ///
+    /// The traces are cached by block hash, except for the pending block.
     pub async fn trace_block(
         &self,
         block_id: BlockId,
     ) -> Result<Option<Vec<LocalizedTransactionTrace>>, Eth::Error> {
+        let block_hash = if block_id.is_pending() {
+            None
+        } else {
+            self.provider().block_hash_for_id(block_id).map_err(Eth::Error::from_eth_err)?
+        };
+        let cache = LoadBlock::cache(&self.inner.eth_api).traces();
+        if let Some(traces) = block_hash.and_then(|hash| cache.parity_traces(hash)) {
+            return Ok(Some(traces.to_vec()))
+        }
+        // Trace the block by hash, so that it is the same block if the chain changed meanwhile.
+        let block_id = block_hash.map_or(block_id, Into::into);
+
         let traces = self.inner.eth_api.trace_block_with(
             block_id,
             TracingInspectorConfig::default_parity(),
@@ -387,6 +445,10 @@ where
             }
         }
 
+        if let (Some(block_hash), Some(traces)) = (block_hash, maybe_traces.as_ref()) {
+            cache.insert_parity_traces(block_hash, Arc::new(traces.clone()));
+        }
+
         Ok(maybe_traces)
     }
 
@@ -548,6 +610,55 @@ where
     }
 }
 
+impl<Provider, Eth> TraceApi<Provider, Eth>
+where
+    Provider: PruneCheckpointReader + 'static,
+    Eth: TraceExt + 'static,
+{
+    /// Handles the result of a request that failed because the required state is pruned.
+    ///
+    /// The request is forwarded to the historical RPC fallback if configured, otherwise the
+    /// earliest block with available state is attached to the error.
+    ///
+    /// Callers must release their tracing permit before, waiting for the archive node doesn't use
+    /// the blocking pool and would only hold back other tracing requests.
+    async fn on_pruned_history<R, P>(
+        &self,
+        res: Result<R, Eth::Error>,
+        method: &str,
+        params: P,
+    ) -> RpcResult<R>
+    where
+        R: DeserializeOwned,
+        P: Serialize + Send,
+    {
+        let err = match res {
+            Ok(res) => return Ok(res),
+            Err(err) => err.into(),
+        };
+        if !is_pruned_history_rpc_err(&err) {
+            return Err(err)
+        }
+
+        if let Some(fallback) = &self.inner.historical_fallback {
+            return fallback.request(method, params).await
+        }
+
+        let earliest_available = self.earliest_available_state().map_err(EthApiError::from)?;
+        Err(pruned_history_rpc_err(earliest_available))
+    }
+
+    /// Returns the earliest block at which account and storage history are available, if pruned.
+    fn earliest_available_state(&self) -> ProviderResult<Option<BlockNumber>> {
+        let mut earliest_available = None;
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            earliest_available = earliest_available
+                .max(self.inner.prune_checkpoints.earliest_available(self.provider(), segment)?);
+        }
+        Ok(earliest_available)
+    }
+}
+
 #[async_trait]
 impl<Provider, Eth> TraceApiServer for TraceApi<Provider, Eth>
 where
@@ -555,6 +666,7 @@ where
         + StateProviderFactory
         + EvmEnvProvider
         + ChainSpecProvider<ChainSpec = ChainSpec>
+        + PruneCheckpointReader
         + 'static,
     Eth: TraceExt + 'static,
 {
@@ -569,10 +681,20 @@ where
         state_overrides: Option<StateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<TraceResults> {
-        let _permit = self.acquire_trace_permit().await;
+        let params = (
+            call.clone(),
+            trace_types.clone(),
+            block_id,
+            state_overrides.clone(),
+            block_overrides.clone(),
+        );
         let request =
             TraceCallRequest { call, trace_types, block_id, state_overrides, block_overrides };
-        Ok(Self::trace_call(self, request).await.map_err(Into::into)?)
+        let res = {
+            let _permit = self.acquire_trace_permit().await;
+            Self::trace_call(self, request).await
+        };
+        self.on_pruned_history(res, "trace_call", params).await
     }
 
     /// Handler for `trace_callMany`
@@ -581,8 +703,12 @@ where
         calls: Vec<(TransactionRequest, HashSet<TraceType>)>,
         block_id: Option<BlockId>,
     ) -> RpcResult<Vec<TraceResults>> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::trace_call_many(self, calls, block_id).await.map_err(Into::into)?)
+        let params = (calls.clone(), block_id);
+        let res = {
+            let _permit = self.acquire_trace_permit().await;
+            Self::trace_call_many(self, calls, block_id).await
+        };
+        self.on_pruned_history(res, "trace_callMany", params).await
     }
 
     /// Handler for `trace_rawTransaction`
@@ -592,10 +718,12 @@ where
         trace_types: HashSet<TraceType>,
         block_id: Option<BlockId>,
     ) -> RpcResult<TraceResults> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::trace_raw_transaction(self, data, trace_types, block_id)
-            .await
-            .map_err(Into::into)?)
+        let params = (data.clone(), trace_types.clone(), block_id);
+        let res = {
+            let _permit = self.acquire_trace_permit().await;
+            Self::trace_raw_transaction(self, data, trace_types, block_id).await
+        };
+        self.on_pruned_history(res, "trace_rawTransaction", params).await
     }
 
     /// Handler for `trace_replayBlockTransactions`
@@ -604,10 +732,12 @@ where
         block_id: BlockId,
         trace_types: HashSet<TraceType>,
     ) -> RpcResult<Option<Vec<TraceResultsWithTransactionHash>>> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::replay_block_transactions(self, block_id, trace_types)
-            .await
-            .map_err(Into::into)?)
+        let params = (block_id, trace_types.clone());
+        let res = {
+            let _permit = self.acquire_trace_permit().await;
+            Self::replay_block_transactions(self, block_id, trace_types).await
+        };
+        self.on_pruned_history(res, "trace_replayBlockTransactions", params).await
     }
 
     /// Handler for `trace_replayTransaction`
@@ -616,8 +746,12 @@ where
         transaction: B256,
         trace_types: HashSet<TraceType>,
     ) -> RpcResult<TraceResults> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::replay_transaction(self, transaction, trace_types).await.map_err(Into::into)?)
+        let params = (transaction, trace_types.clone());
+        let res = {
+            let _permit = self.acquire_trace_permit().await;
+            Self::replay_transaction(self, transaction, trace_types).await
+        };
+        self.on_pruned_history(res, "trace_replayTransaction", params).await
     }
 
     /// Handler for `trace_block`
@@ -625,8 +759,11 @@ where
         &self,
         block_id: BlockId,
     ) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::trace_block(self, block_id).await.map_err(Into::into)?)
+        let res = {
+            let _permit = self.acquire_trace_permit().await;
+            Self::trace_block(self, block_id).await
+        };
+        self.on_pruned_history(res, "trace_block", (block_id,)).await
     }
 
     /// Handler for `trace_filter`
@@ -636,7 +773,9 @@ where
     /// # Limitations
     /// This currently requires block filter fields, since reth does not have address indices yet.
     async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTransactionTrace>> {
-        Ok(Self::trace_filter(self, filter).await.map_err(Into::into)?)
+        let params = (filter.clone(),);
+        let res = Self::trace_filter(self, filter).await;
+        self.on_pruned_history(res, "trace_filter", params).await
     }
 
     /// Returns transaction trace at given index.
@@ -646,10 +785,12 @@ where
         hash: B256,
         indices: Vec<Index>,
     ) -> RpcResult<Option<LocalizedTransactionTrace>> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::trace_get(self, hash, indices.into_iter().map(Into::into).collect())
-            .await
-            .map_err(Into::into)?)
+        let params = (hash, indices.clone());
+        let res = {
+            let _permit = self.acquire_trace_permit().await;
+            Self::trace_get(self, hash, indices.into_iter().map(Into::into).collect()).await
+        };
+        self.on_pruned_history(res, "trace_get", params).await
     }
 
     /// Handler for `trace_transaction`
@@ -657,8 +798,11 @@ where
         &self,
         hash: B256,
     ) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>> {
-        let _permit = self.acquire_trace_permit().await;
-        Ok(Self::trace_transaction(self, hash).await.map_err(Into::into)?)
+        let res = {
+            let _permit = self.acquire_trace_permit().await;
+            Self::trace_transaction(self, hash).await
+        };
+        self.on_pruned_history(res, "trace_transaction", (hash,)).await
     }
 
     /// Handler for `trace_transactionOpcodeGas`
@@ -695,6 +839,10 @@ struct TraceApiInner<Provider, Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to `trace_*`
     blocking_task_guard: BlockingTaskGuard,
+    /// Archive node that requests for pruned state are forwarded to
+    historical_fallback: Option<HistoricalRpcClient>,
+    /// Earliest blocks with available state
+    prune_checkpoints: PruneCheckpointCache,
 }
 
 /// Helper to construct a [`LocalizedTransactionTrace`] that describes a reward to the block
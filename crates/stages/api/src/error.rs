@@ -185,4 +185,38 @@ pub enum PipelineError {
     /// Internal error
     #[error(transparent)]
     Internal(#[from] RethError),
+    /// The pipeline can't be unwound to the requested block.
+    #[error(transparent)]
+    UnwindCheck(#[from] UnwindCheckError),
+}
+
+/// A pre-flight check of a pipeline unwind failed, see
+/// [`Pipeline::unwind_plan`](crate::Pipeline::unwind_plan).
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum UnwindCheckError {
+    /// The changesets needed to revert the state were pruned.
+    #[error(
+        "cannot unwind to block #{target}: {segment} changesets are pruned up to block #{pruned_block}"
+    )]
+    PrunedChangeSets {
+        /// The prune segment that deleted the changesets.
+        segment: PruneSegment,
+        /// The highest pruned block.
+        pruned_block: BlockNumber,
+        /// The block to unwind to.
+        target: BlockNumber,
+    },
+    /// The static files to truncate were moved to the object store.
+    #[error(
+        "cannot unwind to block #{target}: {segment} static files ending below block #{cutoff} \
+         are moved to the object store"
+    )]
+    RemoteStaticFiles {
+        /// The static file segment.
+        segment: StaticFileSegment,
+        /// The block that static files ending below it are moved to the object store.
+        cutoff: BlockNumber,
+        /// The block to unwind to.
+        target: BlockNumber,
+    },
 }
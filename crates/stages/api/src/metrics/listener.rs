@@ -1,4 +1,4 @@
-use crate::{metrics::SyncMetrics, StageCheckpoint, StageId};
+use crate::{metrics::SyncMetrics, StageCheckpoint, StageId, StageThroughput};
 use alloy_primitives::BlockNumber;
 use reth_primitives_traits::constants::MEGAGAS;
 use std::{
@@ -30,6 +30,13 @@ pub enum MetricEvent {
         /// If specified, `entities_total` metric is updated.
         max_block_number: Option<BlockNumber>,
     },
+    /// Stage throughput was measured.
+    StageThroughput {
+        /// Stage ID.
+        stage_id: StageId,
+        /// The measured throughput.
+        throughput: StageThroughput,
+    },
     /// Execution stage processed some amount of gas.
     ExecutionStageGas {
         /// Gas processed.
@@ -82,6 +89,15 @@ impl MetricsListener {
                     stage_metrics.entities_total.set(total as f64);
                 }
             }
+            MetricEvent::StageThroughput { stage_id, throughput } => {
+                let stage_metrics = self.sync_metrics.get_stage_metrics(stage_id);
+
+                stage_metrics.entities_per_second.set(throughput.entities_per_second as f64);
+                stage_metrics.eta_seconds.set(throughput.eta.map_or(0.0, |eta| eta.as_secs_f64()));
+                if throughput.is_below_threshold() {
+                    stage_metrics.below_threshold_total.increment(1);
+                }
+            }
             MetricEvent::ExecutionStageGas { gas } => {
                 self.sync_metrics.execution_stage.mgas_processed_total.increment(gas / MEGAGAS)
             }
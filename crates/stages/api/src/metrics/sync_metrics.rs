@@ -30,6 +30,13 @@ pub(crate) struct StageMetrics {
     pub(crate) entities_processed: Gauge,
     /// The number of total entities of the last commit for a stage, if applicable.
     pub(crate) entities_total: Gauge,
+    /// The number of entities processed per second, measured over the configured window.
+    pub(crate) entities_per_second: Gauge,
+    /// The estimated number of seconds until the stage reaches its target, 0 if unknown.
+    pub(crate) eta_seconds: Gauge,
+    /// The number of measurements in which the stage fell below the configured minimum
+    /// throughput.
+    pub(crate) below_threshold_total: Counter,
 }
 
 /// Execution stage metrics.
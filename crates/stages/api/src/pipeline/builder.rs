@@ -1,4 +1,7 @@
-use crate::{pipeline::BoxedStage, MetricEventsSender, Pipeline, Stage, StageId, StageSet};
+use crate::{
+    pipeline::{BoxedStage, ThroughputTracker},
+    MetricEventsSender, Pipeline, Stage, StageId, StageProgressThresholds, StageSet,
+};
 use alloy_primitives::{BlockNumber, B256};
 use reth_db_api::database::Database;
 use reth_provider::ProviderFactory;
@@ -18,6 +21,10 @@ where
     /// A receiver for the current chain tip to sync to.
     tip_tx: Option<watch::Sender<B256>>,
     metrics_tx: Option<MetricEventsSender>,
+    /// Thresholds for the throughput of the stages.
+    progress_thresholds: StageProgressThresholds,
+    /// A receiver for whether the pipeline is paused.
+    pause_rx: Option<watch::Receiver<bool>>,
 }
 
 impl<DB> PipelineBuilder<DB>
@@ -67,13 +74,28 @@ where
         self
     }
 
+    /// Set the thresholds below which the throughput of a stage is reported as too low.
+    pub fn with_progress_thresholds(mut self, thresholds: StageProgressThresholds) -> Self {
+        self.progress_thresholds = thresholds;
+        self
+    }
+
+    /// Set the receiver for whether the pipeline is paused.
+    ///
+    /// While `true` is received, the pipeline waits before executing the next batch of a stage, so
+    /// it doesn't write to the database until it's resumed.
+    pub fn with_pause_receiver(mut self, pause_rx: watch::Receiver<bool>) -> Self {
+        self.pause_rx = Some(pause_rx);
+        self
+    }
+
     /// Builds the final [`Pipeline`] using the given database.
     pub fn build(
         self,
         provider_factory: ProviderFactory<DB>,
         static_file_producer: StaticFileProducer<DB>,
     ) -> Pipeline<DB> {
-        let Self { stages, max_block, tip_tx, metrics_tx } = self;
+        let Self { stages, max_block, tip_tx, metrics_tx, progress_thresholds, pause_rx } = self;
         Pipeline {
             provider_factory,
             stages,
@@ -82,14 +104,23 @@ where
             tip_tx,
             event_sender: Default::default(),
             progress: Default::default(),
+            throughput: ThroughputTracker::new(progress_thresholds),
             metrics_tx,
+            pause_rx,
         }
     }
 }
 
 impl<DB: Database> Default for PipelineBuilder<DB> {
     fn default() -> Self {
-        Self { stages: Vec::new(), max_block: None, tip_tx: None, metrics_tx: None }
+        Self {
+            stages: Vec::new(),
+            max_block: None,
+            tip_tx: None,
+            metrics_tx: None,
+            progress_thresholds: StageProgressThresholds::default(),
+            pause_rx: None,
+        }
     }
 }
 
@@ -1,6 +1,6 @@
 use crate::{
     stage::{ExecOutput, UnwindInput, UnwindOutput},
-    StageCheckpoint, StageId,
+    StageCheckpoint, StageId, StageThroughput,
 };
 use alloy_primitives::BlockNumber;
 use std::fmt::{Display, Formatter};
@@ -45,6 +45,14 @@ pub enum PipelineEvent {
         /// The result of executing the stage.
         result: ExecOutput,
     },
+    /// Emitted when the throughput of a stage was measured, see
+    /// [`StageProgressThresholds`](crate::StageProgressThresholds).
+    Throughput {
+        /// The stage that was measured.
+        stage_id: StageId,
+        /// The throughput of the stage and its estimated time to completion.
+        throughput: StageThroughput,
+    },
     /// Emitted when a stage is about to be unwound.
     Unwind {
         /// The stage that is about to be unwound.
@@ -8,28 +8,36 @@ use futures_util::Future;
 use reth_db_api::database::Database;
 use reth_primitives_traits::constants::BEACON_CONSENSUS_REORG_UNWIND_DEPTH;
 use reth_provider::{
-    writer::UnifiedStorageWriter, FinalizedBlockReader, FinalizedBlockWriter, ProviderFactory,
-    StageCheckpointReader, StageCheckpointWriter, StaticFileProviderFactory,
+    writer::UnifiedStorageWriter, BlockReader, FinalizedBlockReader, FinalizedBlockWriter,
+    ProviderFactory, PruneCheckpointReader, StageCheckpointReader, StageCheckpointWriter,
+    StaticFileProviderFactory,
 };
-use reth_prune::PrunerBuilder;
+use reth_prune::{PruneSegment, PrunerBuilder};
 use reth_static_file::StaticFileProducer;
+use reth_static_file_types::{find_fixed_range, StaticFileSegment};
 use reth_tokio_util::{EventSender, EventStream};
-use std::pin::Pin;
+use std::{pin::Pin, time::Instant};
 use tokio::sync::watch;
 use tracing::*;
 
 mod builder;
 mod progress;
 mod set;
+mod sync_progress;
+mod throughput;
+mod unwind;
 
 use crate::{
     BlockErrorKind, ExecInput, ExecOutput, MetricEvent, MetricEventsSender, PipelineError, Stage,
-    StageError, StageExt, UnwindInput,
+    StageError, StageExt, UnwindCheckError, UnwindInput,
 };
 pub use builder::*;
 use progress::*;
 use reth_errors::RethResult;
 pub use set::*;
+pub use sync_progress::track_sync_progress;
+use throughput::ThroughputTracker;
+pub use unwind::UnwindPlan;
 
 /// A container for a queued stage.
 pub(crate) type BoxedStage<DB> = Box<dyn Stage<DB>>;
@@ -75,9 +83,13 @@ pub struct Pipeline<DB: Database> {
     event_sender: EventSender<PipelineEvent>,
     /// Keeps track of the progress of the pipeline.
     progress: PipelineProgress,
+    /// Measures the throughput of the stages.
+    throughput: ThroughputTracker,
     /// A receiver for the current chain tip to sync to.
     tip_tx: Option<watch::Sender<B256>>,
     metrics_tx: Option<MetricEventsSender>,
+    /// A receiver for whether the pipeline is paused.
+    pause_rx: Option<watch::Receiver<bool>>,
 }
 
 impl<DB> Pipeline<DB>
@@ -204,7 +216,10 @@ where
             let stage_id = stage.id();
 
             trace!(target: "sync::pipeline", stage = %stage_id, "Executing stage");
-            let next = self.execute_stage_to_completion(previous_stage, stage_index).await?;
+            let next = self
+                .execute_stage_to_completion(previous_stage, stage_index)
+                .instrument(debug_span!(target: "sync::pipeline", "stage", stage = %stage_id))
+                .await?;
 
             trace!(target: "sync::pipeline", stage = %stage_id, ?next, "Completed stage");
 
@@ -265,6 +280,89 @@ where
         Ok(())
     }
 
+    /// Returns the plan of unwinding the stages to the target block, without unwinding them.
+    ///
+    /// Fails if the unwind can't be performed, i.e. if the changesets needed to revert the state
+    /// were pruned, or if the static files to truncate were moved to the object store.
+    pub fn unwind_plan(&self, to: BlockNumber) -> Result<UnwindPlan, PipelineError> {
+        let provider = self.provider_factory.provider()?;
+        let mut plan = UnwindPlan::new(to);
+
+        for stage in self.stages.iter().rev() {
+            let stage_id = stage.id();
+            let checkpoint = provider.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+            if checkpoint.block_number > to {
+                plan.stages.push((stage_id, checkpoint));
+            }
+        }
+
+        if plan.stages.iter().any(|(stage_id, _)| unwind::reverts_changesets(*stage_id)) {
+            for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+                let pruned_block = provider
+                    .get_prune_checkpoint(segment)?
+                    .and_then(|checkpoint| checkpoint.block_number);
+                if let Some(pruned_block) = pruned_block.filter(|block| *block > to) {
+                    return Err(UnwindCheckError::PrunedChangeSets {
+                        segment,
+                        pruned_block,
+                        target: to,
+                    }
+                    .into())
+                }
+            }
+        }
+
+        // Static files are truncated by the stages that write them.
+        let static_file_provider = self.provider_factory.static_file_provider();
+        for (segment, stage_id) in [
+            (StaticFileSegment::Headers, StageId::Headers),
+            (StaticFileSegment::Transactions, StageId::Bodies),
+            (StaticFileSegment::Receipts, StageId::Execution),
+        ] {
+            let Some(highest_block) = static_file_provider
+                .get_highest_static_file_block(segment)
+                .filter(|block| *block > to && plan.contains(stage_id))
+            else {
+                continue
+            };
+
+            if let Some(remote) = static_file_provider.remote() {
+                let fixed_block_range_end = find_fixed_range(to + 1).end();
+                if fixed_block_range_end < remote.cutoff() && fixed_block_range_end < highest_block
+                {
+                    return Err(UnwindCheckError::RemoteStaticFiles {
+                        segment,
+                        cutoff: remote.cutoff(),
+                        target: to,
+                    }
+                    .into())
+                }
+            }
+
+            plan.static_files.push((segment, highest_block));
+        }
+
+        if let Some(tip) = plan.tip() {
+            if let Some((first, last)) =
+                provider.block_body_indices(to + 1)?.zip(provider.block_body_indices(tip)?)
+            {
+                plan.transactions = last.next_tx_num().saturating_sub(first.first_tx_num());
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Unwind the stages to the target block after checking that it's possible, see
+    /// [`Pipeline::unwind_plan`].
+    ///
+    /// Returns the plan of the performed unwind.
+    pub fn unwind_to(&mut self, to: BlockNumber) -> Result<UnwindPlan, PipelineError> {
+        let plan = self.unwind_plan(to)?;
+        self.unwind(to, None)?;
+        Ok(plan)
+    }
+
     /// Unwind the stages to the target block.
     ///
     /// If the unwind is due to a bad block the number of that block should be specified.
@@ -403,6 +501,7 @@ where
             }
 
             let exec_input = ExecInput { target, checkpoint: prev_checkpoint };
+            let started_at = Instant::now();
 
             self.event_sender.notify(PipelineEvent::Prepare {
                 pipeline_stages_progress: PipelineStagesProgress {
@@ -423,6 +522,10 @@ where
                 };
             }
 
+            if let Some(pause_rx) = &mut self.pause_rx {
+                wait_until_resumed(pause_rx, stage_id).await;
+            }
+
             let provider_rw = self.provider_factory.provider_rw()?;
 
             self.event_sender.notify(PipelineEvent::Run {
@@ -435,7 +538,13 @@ where
                 target,
             });
 
-            match stage.execute(&provider_rw, exec_input) {
+            let span = debug_span!(
+                target: "sync::pipeline",
+                "execute",
+                checkpoint = prev_checkpoint.unwrap_or_default().block_number,
+                ?target
+            );
+            match span.in_scope(|| stage.execute(&provider_rw, exec_input)) {
                 Ok(out @ ExecOutput { checkpoint, done }) => {
                     made_progress |=
                         checkpoint.block_number != prev_checkpoint.unwrap_or_default().block_number;
@@ -458,6 +567,22 @@ where
                         result: out.clone(),
                     });
 
+                    if let Some(throughput) = self.throughput.record(
+                        stage_id,
+                        started_at.elapsed(),
+                        prev_checkpoint,
+                        checkpoint,
+                        target,
+                        done,
+                    ) {
+                        if let Some(metrics_tx) = &mut self.metrics_tx {
+                            let _ = metrics_tx
+                                .send(MetricEvent::StageThroughput { stage_id, throughput });
+                        }
+                        self.event_sender
+                            .notify(PipelineEvent::Throughput { stage_id, throughput });
+                    }
+
                     UnifiedStorageWriter::commit(
                         provider_rw,
                         self.provider_factory.static_file_provider(),
@@ -487,6 +612,18 @@ where
     }
 }
 
+/// Waits until the pipeline is no longer paused, see [`PipelineBuilder::with_pause_receiver`].
+async fn wait_until_resumed(pause_rx: &mut watch::Receiver<bool>, stage_id: StageId) {
+    if !*pause_rx.borrow_and_update() {
+        return
+    }
+
+    warn!(target: "sync::pipeline", stage = %stage_id, "Pipeline paused");
+    // if the sender is dropped, the pipeline can't be resumed anymore, so it continues
+    let _ = pause_rx.wait_for(|paused| !paused).await;
+    info!(target: "sync::pipeline", stage = %stage_id, "Pipeline resumed");
+}
+
 fn on_stage_error<DB: Database>(
     factory: &ProviderFactory<DB>,
     stage_id: StageId,
@@ -591,8 +728,8 @@ mod tests {
     use assert_matches::assert_matches;
     use reth_consensus::ConsensusError;
     use reth_errors::ProviderError;
-    use reth_provider::test_utils::create_test_provider_factory;
-    use reth_prune::PruneModes;
+    use reth_provider::{test_utils::create_test_provider_factory, PruneCheckpointWriter};
+    use reth_prune::{PruneCheckpoint, PruneMode, PruneModes};
     use reth_testing_utils::{generators, generators::random_header};
     use tokio_stream::StreamExt;
 
@@ -691,6 +828,40 @@ mod tests {
         );
     }
 
+    /// Stages aren't executed while the pipeline is paused.
+    #[tokio::test]
+    async fn pause_pipeline() {
+        let provider_factory = create_test_provider_factory();
+        let (pause_tx, pause_rx) = watch::channel(true);
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .with_max_block(10)
+            .with_pause_receiver(pause_rx)
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+            );
+        let mut events = pipeline.events();
+
+        tokio::spawn(async move {
+            pipeline.run().await.unwrap();
+        });
+
+        assert_matches!(events.next().await, Some(PipelineEvent::Prepare { .. }));
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        assert_matches!(futures_util::FutureExt::now_or_never(events.next()), None);
+
+        pause_tx.send(false).unwrap();
+        assert_matches!(events.next().await, Some(PipelineEvent::Run { .. }));
+        assert_matches!(events.next().await, Some(PipelineEvent::Ran { .. }));
+    }
+
     /// Unwinds a simple pipeline.
     #[tokio::test]
     async fn unwind_pipeline() {
@@ -825,6 +996,57 @@ mod tests {
         );
     }
 
+    /// Checks the unwind plan of a pipeline before unwinding it.
+    #[test]
+    fn unwind_plan() {
+        let provider_factory = create_test_provider_factory();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Headers)
+                    .add_unwind(Ok(UnwindOutput { checkpoint: StageCheckpoint::new(10) })),
+            )
+            .add_stage(TestStage::new(StageId::Execution))
+            .add_stage(TestStage::new(StageId::Other("A")))
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+            );
+
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(20)).unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(10)).unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Other("A"), StageCheckpoint::new(5)).unwrap();
+        provider_rw
+            .save_prune_checkpoint(
+                PruneSegment::AccountHistory,
+                PruneCheckpoint {
+                    block_number: Some(8),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(9),
+                },
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        assert_matches!(
+            pipeline.unwind_plan(7),
+            Err(PipelineError::UnwindCheck(UnwindCheckError::PrunedChangeSets {
+                segment: PruneSegment::AccountHistory,
+                pruned_block: 8,
+                target: 7
+            }))
+        );
+        assert_matches!(pipeline.unwind_to(7), Err(PipelineError::UnwindCheck(_)));
+
+        // Execution is not unwound, so the changesets are not needed.
+        let mut expected = UnwindPlan::new(10);
+        expected.stages = vec![(StageId::Headers, StageCheckpoint::new(20))];
+        assert_eq!(pipeline.unwind_plan(10).unwrap(), expected);
+        assert_eq!(pipeline.unwind_to(10).unwrap(), expected);
+        assert!(pipeline.unwind_plan(10).unwrap().is_empty());
+    }
+
     /// Unwinds a pipeline with intermediate progress.
     #[tokio::test]
     async fn unwind_pipeline_with_intermediate_progress() {
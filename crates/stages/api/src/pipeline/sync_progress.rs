@@ -0,0 +1,90 @@
+use crate::{PipelineEvent, SyncProgress};
+use futures_util::{Stream, StreamExt};
+
+/// Records the progress of the pipeline stages from the pipeline events, until the event stream
+/// ends.
+///
+/// The events are usually obtained via [`Pipeline::events`](crate::Pipeline::events).
+pub async fn track_sync_progress<St>(progress: SyncProgress, mut events: St)
+where
+    St: Stream<Item = PipelineEvent> + Unpin,
+{
+    while let Some(event) = events.next().await {
+        record_event(&progress, event);
+    }
+}
+
+fn record_event(progress: &SyncProgress, event: PipelineEvent) {
+    match event {
+        PipelineEvent::Run { stage_id, checkpoint, target, .. } => {
+            progress.update_checkpoint(stage_id, checkpoint.unwrap_or_default(), target);
+        }
+        PipelineEvent::Ran { stage_id, result, .. } => {
+            progress.update_checkpoint(stage_id, result.checkpoint, None);
+            if result.done {
+                progress.update_eta(stage_id, None);
+            }
+        }
+        PipelineEvent::Throughput { stage_id, throughput } => {
+            progress.update_eta(stage_id, throughput.eta);
+        }
+        PipelineEvent::Unwound { stage_id, result } => {
+            progress.update_checkpoint(stage_id, result.checkpoint, None);
+            progress.update_eta(stage_id, None);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ExecOutput, PipelineStagesProgress, StageCheckpoint, StageId, StageThroughput};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn tracks_stage_progress() {
+        let stages_progress = PipelineStagesProgress { current: 1, total: 1 };
+        let events = futures_util::stream::iter([
+            PipelineEvent::Run {
+                pipeline_stages_progress: stages_progress.clone(),
+                stage_id: StageId::Headers,
+                checkpoint: None,
+                target: Some(100),
+            },
+            PipelineEvent::Ran {
+                pipeline_stages_progress: stages_progress.clone(),
+                stage_id: StageId::Headers,
+                result: ExecOutput { checkpoint: StageCheckpoint::new(50), done: false },
+            },
+            PipelineEvent::Throughput {
+                stage_id: StageId::Headers,
+                throughput: StageThroughput {
+                    entities_per_second: 10,
+                    eta: Some(Duration::from_secs(5)),
+                    below_threshold: None,
+                },
+            },
+        ]);
+
+        let progress = SyncProgress::default();
+        track_sync_progress(progress.clone(), events).await;
+
+        let stages = progress.stages();
+        assert_eq!(stages.len(), 1);
+        assert_eq!(stages[0].stage_id, StageId::Headers);
+        assert_eq!(stages[0].checkpoint.block_number, 50);
+        assert_eq!(stages[0].target, Some(100));
+        assert_eq!(stages[0].eta, Some(Duration::from_secs(5)));
+
+        record_event(
+            &progress,
+            PipelineEvent::Ran {
+                pipeline_stages_progress: stages_progress,
+                stage_id: StageId::Headers,
+                result: ExecOutput { checkpoint: StageCheckpoint::new(100), done: true },
+            },
+        );
+        assert_eq!(progress.stages()[0].eta, None);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{StageCheckpoint, StageId, StageProgressThresholds, StageThroughput};
+use alloy_primitives::BlockNumber;
+use std::{collections::HashMap, time::Duration};
+
+/// Measures the throughput of the stages of the pipeline over
+/// [`StageProgressThresholds::window`].
+#[derive(Debug, Default)]
+pub(crate) struct ThroughputTracker {
+    thresholds: StageProgressThresholds,
+    /// The runs of each stage that are not reported yet.
+    samples: HashMap<StageId, Sample>,
+}
+
+/// Runs of a stage accumulated since the last report.
+#[derive(Debug, Default)]
+struct Sample {
+    /// Time spent executing the stage.
+    elapsed: Duration,
+    /// The number of entities processed.
+    processed: u64,
+}
+
+impl ThroughputTracker {
+    pub(crate) fn new(thresholds: StageProgressThresholds) -> Self {
+        Self { thresholds, samples: HashMap::new() }
+    }
+
+    /// Records a run of the stage that moved it from `prev_checkpoint` to `checkpoint` in
+    /// `elapsed`.
+    ///
+    /// Returns the throughput of the stage once it was executing for at least the configured
+    /// window. Runs that finish the stage before the window is reached are discarded.
+    pub(crate) fn record(
+        &mut self,
+        stage_id: StageId,
+        elapsed: Duration,
+        prev_checkpoint: Option<StageCheckpoint>,
+        checkpoint: StageCheckpoint,
+        target: Option<BlockNumber>,
+        done: bool,
+    ) -> Option<StageThroughput> {
+        let (processed, remaining) =
+            match (prev_checkpoint.and_then(|prev| prev.entities()), checkpoint.entities()) {
+                (Some(prev), Some(current)) => (
+                    current.processed.saturating_sub(prev.processed),
+                    Some(current.total.saturating_sub(current.processed)),
+                ),
+                (None, Some(current)) => {
+                    (current.processed, Some(current.total.saturating_sub(current.processed)))
+                }
+                _ => (
+                    checkpoint
+                        .block_number
+                        .saturating_sub(prev_checkpoint.unwrap_or_default().block_number),
+                    target.map(|target| target.saturating_sub(checkpoint.block_number)),
+                ),
+            };
+
+        let sample = self.samples.entry(stage_id).or_default();
+        sample.elapsed += elapsed;
+        sample.processed += processed;
+
+        if sample.elapsed < self.thresholds.window {
+            if done {
+                self.samples.remove(&stage_id);
+            }
+            return None
+        }
+
+        let Sample { elapsed, processed } =
+            self.samples.remove(&stage_id).expect("sample was just inserted");
+        let per_second = processed as f64 / elapsed.as_secs_f64();
+        let entities_per_second = per_second as u64;
+        let eta = remaining
+            .filter(|_| per_second > 0.0)
+            .and_then(|remaining| Duration::try_from_secs_f64(remaining as f64 / per_second).ok());
+        let below_threshold =
+            self.thresholds.min_throughput(stage_id).filter(|min| entities_per_second < *min);
+
+        Some(StageThroughput { entities_per_second, eta, below_threshold })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EntitiesCheckpoint, StageUnitCheckpoint};
+    use std::collections::BTreeMap;
+
+    fn tracker() -> ThroughputTracker {
+        ThroughputTracker::new(StageProgressThresholds {
+            window: Duration::from_secs(10),
+            min_throughput: BTreeMap::from([("execution".to_string(), 100)]),
+        })
+    }
+
+    #[test]
+    fn reports_after_window() {
+        let mut tracker = tracker();
+        let secs = Duration::from_secs;
+
+        assert_eq!(
+            tracker.record(
+                StageId::Execution,
+                secs(6),
+                Some(StageCheckpoint::new(0)),
+                StageCheckpoint::new(600),
+                Some(3000),
+                false
+            ),
+            None
+        );
+        // 1200 blocks in 12 seconds, 1800 blocks remaining
+        assert_eq!(
+            tracker.record(
+                StageId::Execution,
+                secs(6),
+                Some(StageCheckpoint::new(600)),
+                StageCheckpoint::new(1200),
+                Some(3000),
+                false
+            ),
+            Some(StageThroughput {
+                entities_per_second: 100,
+                eta: Some(secs(18)),
+                below_threshold: None
+            })
+        );
+
+        // the window starts over
+        assert_eq!(
+            tracker.record(
+                StageId::Execution,
+                secs(2),
+                Some(StageCheckpoint::new(1200)),
+                StageCheckpoint::new(1300),
+                Some(3000),
+                false
+            ),
+            None
+        );
+        assert_eq!(
+            tracker.record(
+                StageId::Execution,
+                secs(8),
+                Some(StageCheckpoint::new(1300)),
+                StageCheckpoint::new(1400),
+                Some(3000),
+                false
+            ),
+            Some(StageThroughput {
+                entities_per_second: 20,
+                eta: Some(secs(80)),
+                below_threshold: Some(100)
+            })
+        );
+    }
+
+    #[test]
+    fn entities_checkpoint() {
+        let mut tracker = tracker();
+        let checkpoint = |processed| StageCheckpoint {
+            block_number: 0,
+            stage_checkpoint: Some(StageUnitCheckpoint::Entities(EntitiesCheckpoint {
+                processed,
+                total: 1000,
+            })),
+        };
+
+        assert_eq!(
+            tracker.record(
+                StageId::SenderRecovery,
+                Duration::from_secs(10),
+                Some(checkpoint(100)),
+                checkpoint(600),
+                None,
+                false
+            ),
+            Some(StageThroughput {
+                entities_per_second: 50,
+                eta: Some(Duration::from_secs(8)),
+                below_threshold: None
+            })
+        );
+    }
+
+    #[test]
+    fn discards_short_runs() {
+        let mut tracker = tracker();
+
+        assert_eq!(
+            tracker.record(
+                StageId::Execution,
+                Duration::from_secs(1),
+                Some(StageCheckpoint::new(0)),
+                StageCheckpoint::new(1),
+                Some(1),
+                true
+            ),
+            None
+        );
+        assert!(tracker.samples.is_empty());
+    }
+}
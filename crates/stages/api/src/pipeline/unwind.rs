@@ -0,0 +1,79 @@
+use crate::{StageCheckpoint, StageId};
+use alloy_primitives::BlockNumber;
+use reth_static_file_types::StaticFileSegment;
+use std::fmt::{Display, Formatter};
+
+/// Report of the data that is reverted by unwinding the pipeline to the target block.
+///
+/// See [`Pipeline::unwind_plan`](super::Pipeline::unwind_plan).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnwindPlan {
+    /// The block the pipeline is unwound to.
+    pub target: BlockNumber,
+    /// The stages that are unwound along with their current checkpoints, in unwind order.
+    pub stages: Vec<(StageId, StageCheckpoint)>,
+    /// The static file segments that are truncated along with their highest blocks.
+    pub static_files: Vec<(StaticFileSegment, BlockNumber)>,
+    /// The number of transactions in the reverted blocks.
+    pub transactions: u64,
+}
+
+impl UnwindPlan {
+    /// Creates an empty plan for unwinding to the target block.
+    pub const fn new(target: BlockNumber) -> Self {
+        Self { target, stages: Vec::new(), static_files: Vec::new(), transactions: 0 }
+    }
+
+    /// Returns `true` if no stage is unwound.
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Returns the highest block that is reverted, if any.
+    pub fn tip(&self) -> Option<BlockNumber> {
+        self.stages.iter().map(|(_, checkpoint)| checkpoint.block_number).max()
+    }
+
+    /// Returns `true` if the stage is unwound.
+    pub fn contains(&self, stage_id: StageId) -> bool {
+        self.stages.iter().any(|(id, _)| *id == stage_id)
+    }
+}
+
+impl Display for UnwindPlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Some(tip) = self.tip() else {
+            return write!(f, "Nothing to unwind to block #{}", self.target)
+        };
+
+        writeln!(
+            f,
+            "Unwind to block #{} reverts blocks #{}..=#{} ({} blocks, {} transactions)",
+            self.target,
+            self.target + 1,
+            tip,
+            tip - self.target,
+            self.transactions
+        )?;
+        for (stage_id, checkpoint) in &self.stages {
+            writeln!(f, "  stage {stage_id}: #{} -> #{}", checkpoint.block_number, self.target)?;
+        }
+        for (segment, highest_block) in &self.static_files {
+            writeln!(f, "  static files {segment}: #{highest_block} -> #{}", self.target)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns `true` if unwinding the stage reverts state using the account and storage changesets.
+pub(crate) const fn reverts_changesets(stage_id: StageId) -> bool {
+    matches!(
+        stage_id,
+        StageId::Execution |
+            StageId::AccountHashing |
+            StageId::StorageHashing |
+            StageId::MerkleUnwind |
+            StageId::IndexAccountHistory |
+            StageId::IndexStorageHistory
+    )
+}
@@ -2,7 +2,7 @@
 use criterion::{criterion_main, measurement::WallTime, BenchmarkGroup, Criterion};
 #[cfg(not(target_os = "windows"))]
 use pprof::criterion::{Output, PProfProfiler};
-use reth_config::config::{EtlConfig, TransactionLookupConfig};
+use reth_config::config::{EtlConfig, SenderRecoveryConfig, TransactionLookupConfig};
 use reth_db::{test_utils::TempDatabase, DatabaseEnv};
 
 use reth_primitives::BlockNumber;
@@ -71,7 +71,10 @@ fn senders(c: &mut Criterion, runtime: &Runtime) {
 
     let db = setup::txs_testdata(DEFAULT_NUM_BLOCKS);
 
-    let stage = SenderRecoveryStage { commit_threshold: DEFAULT_NUM_BLOCKS };
+    let stage = SenderRecoveryStage::new(SenderRecoveryConfig {
+        commit_threshold: DEFAULT_NUM_BLOCKS,
+        ..Default::default()
+    });
 
     measure_stage(
         runtime,
@@ -114,7 +117,8 @@ fn merkle(c: &mut Criterion, runtime: &Runtime) {
 
     let db = setup::txs_testdata(DEFAULT_NUM_BLOCKS);
 
-    let stage = MerkleStage::Both { clean_threshold: u64::MAX };
+    let stage =
+        MerkleStage::Both { clean_threshold: u64::MAX, commit_threshold: DEFAULT_NUM_BLOCKS };
     measure_stage(
         runtime,
         &mut group,
@@ -125,7 +129,7 @@ fn merkle(c: &mut Criterion, runtime: &Runtime) {
         "Merkle-incremental".to_string(),
     );
 
-    let stage = MerkleStage::Both { clean_threshold: 0 };
+    let stage = MerkleStage::Both { clean_threshold: 0, commit_threshold: DEFAULT_NUM_BLOCKS };
     measure_stage(
         runtime,
         &mut group,
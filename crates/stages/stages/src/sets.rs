@@ -37,11 +37,11 @@ use crate::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, FinishStage, HeaderStage,
         IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, PruneSenderRecoveryStage,
-        PruneStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
+        PruneStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage, VerkleStage,
     },
     StageSet, StageSetBuilder,
 };
-use reth_config::config::StageConfig;
+use reth_config::config::{StageConfig, TrieBackend};
 use reth_consensus::Consensus;
 use reth_db_api::database::Database;
 use reth_evm::execute::BlockExecutorProvider;
@@ -355,8 +355,10 @@ pub struct HashingStages {
 
 impl<DB: Database> StageSet<DB> for HashingStages {
     fn builder(self) -> StageSetBuilder<DB> {
+        let verkle = self.stages_config.merkle.backend == TrieBackend::Verkle;
         StageSetBuilder::default()
-            .add_stage(MerkleStage::default_unwind())
+            .add_stage_opt(verkle.not().then(MerkleStage::default_unwind))
+            .add_stage_opt(verkle.then_some(VerkleStage::Unwind))
             .add_stage(AccountHashingStage::new(
                 self.stages_config.account_hashing,
                 self.stages_config.etl.clone(),
@@ -365,7 +367,10 @@ impl<DB: Database> StageSet<DB> for HashingStages {
                 self.stages_config.storage_hashing,
                 self.stages_config.etl.clone(),
             ))
-            .add_stage(MerkleStage::new_execution(self.stages_config.merkle.clean_threshold))
+            .add_stage_opt(
+                verkle.not().then(|| MerkleStage::new_execution(self.stages_config.merkle)),
+            )
+            .add_stage_opt(verkle.then_some(VerkleStage::Execution))
     }
 }
 
@@ -3,19 +3,26 @@ use num_traits::Zero;
 use reth_config::config::ExecutionConfig;
 use reth_db::{static_file::HeaderMask, tables};
 use reth_db_api::{cursor::DbCursorRO, database::Database, transaction::DbTx};
-use reth_evm::execute::{BatchExecutor, BlockExecutorProvider};
+use reth_evm::execute::{BatchExecutor, BlockExecutorProvider, Executor};
 use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_exex::{ExExManagerHandle, ExExNotification};
-use reth_primitives::{BlockNumber, Header, StaticFileSegment};
+use reth_primitives::{
+    Address, Block, BlockNumber, BlockWithSenders, Header, StaticFileSegment, U256,
+};
 use reth_primitives_traits::format_gas_throughput;
 use reth_provider::{
-    providers::{StaticFileProvider, StaticFileProviderRWRefMut, StaticFileWriter},
+    providers::{is_snap_synced, StaticFileProvider, StaticFileProviderRWRefMut, StaticFileWriter},
     writer::UnifiedStorageWriter,
     BlockReader, DatabaseProviderRW, HeaderProvider, LatestStateProviderRef, OriginalValuesKnown,
-    ProviderError, StateWriter, StatsReader, TransactionVariant,
+    ProviderError, ProviderFactory, ProviderResult, StateChangeWriter, StateProviderBox,
+    StateWriter, StatsReader, TransactionVariant,
 };
 use reth_prune_types::PruneModes;
-use reth_revm::database::StateProviderDatabase;
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::{AccountState, BundleState, CacheDB, DbAccount},
+    primitives::HashMap,
+};
 use reth_stages_api::{
     BlockErrorKind, CheckpointBlockRange, EntitiesCheckpoint, ExecInput, ExecOutput,
     ExecutionCheckpoint, ExecutionStageThresholds, MetricEvent, MetricEventsSender, Stage,
@@ -24,7 +31,10 @@ use reth_stages_api::{
 use std::{
     cmp::Ordering,
     ops::RangeInclusive,
-    sync::Arc,
+    sync::{
+        atomic::{self, AtomicBool, AtomicU64, AtomicUsize},
+        Arc,
+    },
     task::{ready, Context, Poll},
     time::{Duration, Instant},
 };
@@ -83,6 +93,8 @@ pub struct ExecutionStage<E> {
     post_unwind_commit_input: Option<Chain>,
     /// Handle to communicate with `ExEx` manager.
     exex_manager_handle: ExExManagerHandle,
+    /// Speculative execution of the next block, if enabled.
+    prewarm: Option<Prewarm>,
 }
 
 impl<E> ExecutionStage<E> {
@@ -103,6 +115,7 @@ impl<E> ExecutionStage<E> {
             post_execute_commit_input: None,
             post_unwind_commit_input: None,
             exex_manager_handle,
+            prewarm: None,
         }
     }
 
@@ -141,6 +154,29 @@ impl<E> ExecutionStage<E> {
         self
     }
 
+    /// Speculatively execute the transactions of the next block on the given number of threads
+    /// while the current block is executed, so that the state they read is already cached.
+    ///
+    /// Pre-warming is disabled if `workers` is `0`.
+    pub fn with_prewarming<DB>(
+        mut self,
+        provider_factory: ProviderFactory<DB>,
+        workers: usize,
+    ) -> Self
+    where
+        DB: Database + 'static,
+    {
+        self.prewarm = (workers > 0).then(|| Prewarm {
+            latest: Box::new(move || provider_factory.latest()),
+            workers,
+            pool: None,
+            executing: Arc::default(),
+            cancelled: Arc::default(),
+            prewarmed: Arc::default(),
+        });
+        self
+    }
+
     /// Adjusts the prune modes related to changesets.
     ///
     /// This function verifies whether the [`super::MerkleStage`] or Hashing stages will run from
@@ -207,9 +243,7 @@ where
         let static_file_provider = provider.static_file_provider();
 
         // We only use static files for Receipts, if there is no receipt pruning of any kind.
-        let static_file_producer = if self.prune_modes.receipts.is_none() &&
-            self.prune_modes.receipts_log_filter.is_empty()
-        {
+        let static_file_producer = if !self.prune_modes.has_receipts_pruning() {
             debug!(target: "sync::stages::execution", start = start_block, "Preparing static file producer");
             let mut producer = prepare_static_file_producer(provider, start_block)?;
             // Since there might be a database <-> static file inconsistency (read
@@ -220,10 +254,15 @@ where
             None
         };
 
-        let db = StateProviderDatabase(LatestStateProviderRef::new(
-            provider.tx_ref(),
-            provider.static_file_provider().clone(),
-        ));
+        // The plain state of a snap synced database is incomplete, so the hashed state has to be
+        // read and kept up to date as well. Likewise, the state of a fork is layered on top of the
+        // state of the network it's forked from.
+        let snap_synced = is_snap_synced(provider.tx_ref())?;
+        let db = StateProviderDatabase(
+            LatestStateProviderRef::new(provider.tx_ref(), provider.static_file_provider().clone())
+                .with_hashed_state_fallback(snap_synced)
+                .with_forked_state(provider.forked_state()),
+        );
         let mut executor = self.executor_provider.batch_executor(db);
         executor.set_tip(max_block);
         executor.set_prune_modes(prune_modes);
@@ -248,19 +287,29 @@ where
         let mut cumulative_gas = 0;
         let batch_start = Instant::now();
 
+        // Pending pre-warming of the batch is cancelled once it's done, successfully or not
+        let _prewarm_guard = self.prewarm.as_mut().map(Prewarm::start);
+
         let mut blocks = Vec::new();
+        let mut next_block = None;
         for block_number in start_block..=max_block {
-            // Fetch the block
+            // Fetch the block, unless it was already fetched for pre-warming
             let fetch_block_start = Instant::now();
 
-            let td = provider
-                .header_td_by_number(block_number)?
-                .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+            let (td, block) = match next_block.take() {
+                Some(next_block) => next_block,
+                None => fetch_block(provider, block_number)?,
+            };
 
-            // we need the block's transactions but we don't need the transaction hashes
-            let block = provider
-                .block_with_senders(block_number.into(), TransactionVariant::NoHash)?
-                .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+            // Pre-warm the state of the next block while this one is executed
+            if let Some(prewarm) = &mut self.prewarm {
+                prewarm.set_executing(block_number);
+                if block_number < max_block {
+                    let (td, block) = fetch_block(provider, block_number + 1)?;
+                    prewarm.spawn(&self.executor_provider, executor.bundle_state(), &block, td)?;
+                    next_block = Some((td, block));
+                }
+            }
 
             fetch_block_duration += fetch_block_start.elapsed();
 
@@ -361,6 +410,9 @@ where
         let time = Instant::now();
 
         // write output
+        if snap_synced {
+            provider.write_hashed_state(&state.hash_state_slow().into_sorted())?;
+        }
         let mut writer = UnifiedStorageWriter::new(provider, static_file_producer);
         writer.write_to_storage(state, OriginalValuesKnown::Yes)?;
 
@@ -371,6 +423,7 @@ where
             execution = ?execution_duration,
             write_preparation = ?write_preparation_duration,
             write = ?db_write_duration,
+            prewarmed_txs = self.prewarm.as_ref().map(Prewarm::take_prewarmed),
             "Execution time"
         );
 
@@ -433,7 +486,7 @@ where
         }
 
         // Unwind all receipts for transactions in the block range
-        if self.prune_modes.receipts.is_none() && self.prune_modes.receipts_log_filter.is_empty() {
+        if !self.prune_modes.has_receipts_pruning() {
             // We only use static files for Receipts, if there is no receipt pruning of any kind.
 
             // prepare_static_file_producer does a consistency check that will unwind static files
@@ -479,6 +532,185 @@ where
     }
 }
 
+/// Fetches the block with senders and its total difficulty.
+fn fetch_block<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    block_number: BlockNumber,
+) -> Result<(U256, BlockWithSenders), StageError> {
+    let td = provider
+        .header_td_by_number(block_number)?
+        .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+
+    // we need the block's transactions but we don't need the transaction hashes
+    let block = provider
+        .block_with_senders(block_number.into(), TransactionVariant::NoHash)?
+        .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+
+    Ok((td, block))
+}
+
+/// Speculative execution of the transactions of the next block.
+///
+/// Every transaction is executed on its own on top of a snapshot of the latest committed state and
+/// the result is discarded. The only purpose is to load the accounts, storage slots and bytecode
+/// that the block reads into the database page cache before the block is executed.
+struct Prewarm {
+    /// Opens a provider for the latest committed state.
+    latest: Box<dyn Fn() -> ProviderResult<StateProviderBox> + Send + Sync>,
+    /// The number of threads executing transactions.
+    workers: usize,
+    /// The thread pool the transactions are executed on, built on first use.
+    pool: Option<rayon::ThreadPool>,
+    /// The number of the block that is currently executed. Transactions of blocks up to it are
+    /// no longer worth pre-warming and are skipped.
+    executing: Arc<AtomicU64>,
+    /// Set once the batch the transactions were spawned for is done, so that the transactions
+    /// still pending in the pool are skipped.
+    cancelled: Arc<AtomicBool>,
+    /// The number of transactions that were pre-warmed.
+    prewarmed: Arc<AtomicUsize>,
+}
+
+impl Prewarm {
+    /// Starts pre-warming a new batch.
+    ///
+    /// The returned guard cancels the pending transactions of the batch when dropped.
+    fn start(&mut self) -> CancelOnDrop {
+        self.cancelled = Arc::default();
+        CancelOnDrop(self.cancelled.clone())
+    }
+
+    /// Marks the block as executed.
+    fn set_executing(&self, block_number: BlockNumber) {
+        self.executing.store(block_number, atomic::Ordering::Relaxed);
+    }
+
+    /// Returns the number of transactions pre-warmed since the last call.
+    fn take_prewarmed(&self) -> usize {
+        self.prewarmed.swap(0, atomic::Ordering::Relaxed)
+    }
+
+    /// Spawns the speculative execution of each transaction of the block.
+    ///
+    /// All transactions share one snapshot of the latest committed state. The accounts they're
+    /// sent from and to are taken from the state changes of the batch executed so far, so that
+    /// nonces, balances and code match what the block will see. Storage is read from the snapshot.
+    fn spawn<E: BlockExecutorProvider>(
+        &mut self,
+        executor_provider: &E,
+        batch_state: Option<&BundleState>,
+        block: &BlockWithSenders,
+        td: U256,
+    ) -> Result<(), StageError> {
+        if self.pool.is_none() {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(self.workers)
+                .thread_name(|i| format!("execution-prewarm-{i:02}"))
+                .build()
+                .map_err(|err| StageError::Fatal(Box::new(err)))?;
+            self.pool = Some(pool);
+        }
+        let pool = self.pool.as_ref().expect("pool is built");
+
+        let state = Arc::new((self.latest)()?);
+        let batch_accounts = Arc::new(batch_state.map(|bundle| batch_accounts(bundle, block)));
+
+        for (transaction, sender) in block.body.iter().zip(&block.senders) {
+            let accounts = [Some(*sender), transaction.to(), Some(block.beneficiary)];
+            let block = BlockWithSenders {
+                block: Block {
+                    header: block.header.clone(),
+                    body: vec![transaction.clone()],
+                    ..Default::default()
+                },
+                senders: vec![*sender],
+            };
+            let state = state.clone();
+            let batch_accounts = batch_accounts.clone();
+            let executing = self.executing.clone();
+            let cancelled = self.cancelled.clone();
+            let prewarmed = self.prewarmed.clone();
+            let executor_provider = executor_provider.clone();
+
+            pool.spawn(move || {
+                if cancelled.load(atomic::Ordering::Relaxed) ||
+                    executing.load(atomic::Ordering::Relaxed) >= block.number
+                {
+                    return
+                }
+
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                for address in accounts.into_iter().flatten() {
+                    if let Some(account) =
+                        batch_accounts.as_ref().as_ref().and_then(|batch| batch.get(&address))
+                    {
+                        let mut account = account.clone();
+                        db.insert_contract(&mut account.info);
+                        db.accounts.insert(address, account);
+                    }
+                }
+
+                match executor_provider.executor(db).execute((&block, td).into()) {
+                    Ok(_) => {
+                        prewarmed.fetch_add(1, atomic::Ordering::Relaxed);
+                    }
+                    Err(err) => {
+                        trace!(target: "sync::stages::execution", number = block.number, %err, "Pre-warming transaction failed");
+                    }
+                }
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Cancels the pending pre-warming of a batch when dropped.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, atomic::Ordering::Relaxed);
+    }
+}
+
+/// Returns the state of the accounts the transactions of the block are sent from and to, as left
+/// by the blocks executed earlier in the batch.
+fn batch_accounts(bundle: &BundleState, block: &BlockWithSenders) -> HashMap<Address, DbAccount> {
+    let addresses = block
+        .senders
+        .iter()
+        .copied()
+        .chain(block.body.iter().filter_map(|transaction| transaction.to()))
+        .chain(std::iter::once(block.beneficiary));
+
+    let mut accounts = HashMap::default();
+    for address in addresses {
+        let Some(account) = bundle.account(&address) else { continue };
+        let entry = match account.account_info() {
+            Some(mut info) => {
+                if info.code.is_none() {
+                    info.code = bundle.bytecode(&info.code_hash);
+                }
+                let mut entry = DbAccount::from(info);
+                if account.was_destroyed() {
+                    // the storage of the committed state no longer applies
+                    entry.account_state = AccountState::StorageCleared;
+                    entry.storage = account
+                        .storage
+                        .iter()
+                        .map(|(slot, value)| (*slot, value.present_value))
+                        .collect();
+                }
+                entry
+            }
+            None => DbAccount::new_not_existing(),
+        };
+        accounts.insert(address, entry);
+    }
+    accounts
+}
+
 fn execution_checkpoint(
     provider: &StaticFileProvider,
     start_block: BlockNumber,
@@ -668,6 +900,7 @@ mod tests {
         StaticFileProviderFactory,
     };
     use reth_prune_types::{PruneMode, ReceiptsLogPruneConfig};
+    use reth_revm::primitives::AccountInfo;
     use reth_stages_api::StageUnitCheckpoint;
     use std::collections::BTreeMap;
 
@@ -980,6 +1213,176 @@ mod tests {
         }
     }
 
+    /// Returns a provider factory with the genesis, an empty block and a block with a transaction
+    /// inserted, and the pre-state of the transaction's accounts. Also returns the block with the
+    /// transaction.
+    fn prewarm_fixture(
+    ) -> (ProviderFactory<Arc<reth_db::test_utils::TempDatabase<reth_db::DatabaseEnv>>>, SealedBlock)
+    {
+        let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
+        let genesis = SealedBlock::decode(&mut genesis_rlp).unwrap();
+        let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
+        let block = SealedBlock::decode(&mut block_rlp).unwrap().unseal();
+
+        // An empty block precedes the block with the transaction, so that the transaction is
+        // pre-warmed while the empty block is executed.
+        let empty_block = SealedBlock::new(
+            Header {
+                parent_hash: genesis.hash(),
+                number: 1,
+                gas_limit: genesis.gas_limit,
+                timestamp: block.timestamp - 1,
+                ..Default::default()
+            }
+            .seal_slow(),
+            Default::default(),
+        );
+        let mut block = block;
+        block.header.number = 2;
+        block.header.parent_hash = empty_block.hash();
+        let block = block.seal_slow();
+
+        let acc1 = address!("1000000000000000000000000000000000000000");
+        let acc2 = address!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+        let code = hex!("5a465a905090036002900360015500");
+        let code_hash = keccak256(code);
+
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        for block in [&genesis, &empty_block, &block] {
+            provider
+                .insert_historical_block(block.clone().try_seal_with_senders().unwrap())
+                .unwrap();
+        }
+        provider
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        {
+            let mut receipts_writer =
+                provider.static_file_provider().latest_writer(StaticFileSegment::Receipts).unwrap();
+            receipts_writer.increment_block(0).unwrap();
+            receipts_writer.commit().unwrap();
+        }
+
+        // insert pre state
+        let db_tx = provider.tx_ref();
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc1,
+                Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+            )
+            .unwrap();
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc2,
+                Account {
+                    nonce: 0,
+                    balance: U256::from(0x3635c9adc5dea00000u128),
+                    bytecode_hash: None,
+                },
+            )
+            .unwrap();
+        db_tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into())).unwrap();
+        provider.commit().unwrap();
+
+        (factory, block)
+    }
+
+    #[tokio::test]
+    async fn execution_with_prewarming() {
+        let mut post_states = Vec::new();
+        for workers in [0, 2] {
+            let (factory, _) = prewarm_fixture();
+            let provider = factory.provider_rw().unwrap();
+            let mut execution_stage = stage().with_prewarming(factory.clone(), workers);
+            let output = execution_stage
+                .execute(&provider, ExecInput { target: Some(2), checkpoint: None })
+                .unwrap();
+            provider.commit().unwrap();
+            assert_matches!(
+                output,
+                ExecOutput { checkpoint: StageCheckpoint { block_number: 2, .. }, done: true }
+            );
+            // the transaction of the last block was spawned for pre-warming, see
+            // `prewarm_transactions` for its execution
+            assert_eq!(
+                execution_stage.prewarm.as_ref().map(|prewarm| prewarm.pool.is_some()),
+                (workers > 0).then_some(true)
+            );
+
+            let provider = factory.provider().unwrap();
+            let accounts = provider
+                .tx_ref()
+                .cursor_read::<tables::PlainAccountState>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            let storage = provider
+                .tx_ref()
+                .cursor_read::<tables::PlainStorageState>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            post_states.push((accounts, storage));
+        }
+
+        assert_eq!(post_states[0], post_states[1]);
+    }
+
+    #[test]
+    fn prewarm_transactions() {
+        let (factory, block) = prewarm_fixture();
+        let block = block.try_seal_with_senders().unwrap().unseal();
+        let sender = block.senders[0];
+
+        let mut execution_stage = stage().with_prewarming(factory, 1);
+        let executor_provider = execution_stage.executor_provider.clone();
+        let prewarm = execution_stage.prewarm.as_mut().unwrap();
+        // the single worker handles the transactions in order, so once this returns the
+        // transactions spawned before were handled
+        let wait = |prewarm: &Prewarm| prewarm.pool.as_ref().unwrap().install(|| ());
+
+        let guard = prewarm.start();
+        prewarm.spawn(&executor_provider, None, &block, U256::ZERO).unwrap();
+        wait(prewarm);
+        assert_eq!(prewarm.take_prewarmed(), 1);
+
+        // the sender's nonce is taken from the state of the batch, so the transaction is invalid
+        let batch_state = BundleState::builder(0..=0)
+            .state_present_account_info(
+                sender,
+                AccountInfo {
+                    nonce: 1,
+                    balance: U256::from(0x3635c9adc5dea00000u128),
+                    ..Default::default()
+                },
+            )
+            .build();
+        prewarm.spawn(&executor_provider, Some(&batch_state), &block, U256::ZERO).unwrap();
+        wait(prewarm);
+        assert_eq!(prewarm.take_prewarmed(), 0);
+
+        // transactions of blocks that are already executed are skipped
+        prewarm.set_executing(block.number);
+        prewarm.spawn(&executor_provider, None, &block, U256::ZERO).unwrap();
+        wait(prewarm);
+        assert_eq!(prewarm.take_prewarmed(), 0);
+
+        // pending transactions of a finished batch are skipped
+        prewarm.set_executing(0);
+        drop(guard);
+        prewarm.spawn(&executor_provider, None, &block, U256::ZERO).unwrap();
+        wait(prewarm);
+        assert_eq!(prewarm.take_prewarmed(), 0);
+    }
+
     #[tokio::test]
     async fn sanity_execute_unwind() {
         let factory = create_test_provider_factory();
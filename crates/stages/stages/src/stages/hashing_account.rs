@@ -151,12 +151,19 @@ impl<DB: Database> Stage<DB> for AccountHashingStage {
             tx.clear::<tables::HashedAccounts>()?;
 
             let mut accounts_cursor = tx.cursor_read::<RawTable<tables::PlainAccountState>>()?;
+            // accounts moved out of the plain state by state expiry are still part of the state
+            let mut cold_accounts_cursor =
+                tx.cursor_read::<RawTable<tables::ColdAccountState>>()?;
             let mut collector =
                 Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
             let mut channels = Vec::with_capacity(MAXIMUM_CHANNELS);
 
             // channels used to return result of account hashing
-            for chunk in &accounts_cursor.walk(None)?.chunks(WORKER_CHUNK_SIZE) {
+            for chunk in &accounts_cursor
+                .walk(None)?
+                .chain(cold_accounts_cursor.walk(None)?)
+                .chunks(WORKER_CHUNK_SIZE)
+            {
                 // An _unordered_ channel to receive results from a rayon job
                 let (tx, rx) = mpsc::channel();
                 channels.push(rx);
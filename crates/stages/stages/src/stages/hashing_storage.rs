@@ -90,11 +90,17 @@ impl<DB: Database> Stage<DB> for StorageHashingStage {
             tx.clear::<tables::HashedStorages>()?;
 
             let mut storage_cursor = tx.cursor_read::<tables::PlainStorageState>()?;
+            // storage moved out of the plain state by state expiry is still part of the state
+            let mut cold_storage_cursor = tx.cursor_read::<tables::ColdStorageState>()?;
             let mut collector =
                 Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
             let mut channels = Vec::with_capacity(MAXIMUM_CHANNELS);
 
-            for chunk in &storage_cursor.walk(None)?.chunks(WORKER_CHUNK_SIZE) {
+            for chunk in &storage_cursor
+                .walk(None)?
+                .chain(cold_storage_cursor.walk(None)?)
+                .chunks(WORKER_CHUNK_SIZE)
+            {
                 // An _unordered_ channel to receive results from a rayon job
                 let (tx, rx) = mpsc::channel();
                 channels.push(rx);
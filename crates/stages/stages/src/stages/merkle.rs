@@ -1,4 +1,5 @@
 use reth_codecs::Compact;
+use reth_config::config::MerkleConfig;
 use reth_consensus::ConsensusError;
 use reth_db::tables;
 use reth_db_api::{
@@ -41,6 +42,10 @@ Once you have this information, please submit a github issue at https://github.c
 /// of changes to whole rebuild.
 pub const MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD: u64 = 5_000;
 
+/// The default maximum number of trie updates of a whole rebuild to compute before committing its
+/// intermediate progress.
+pub const MERKLE_STAGE_DEFAULT_COMMIT_THRESHOLD: u64 = 100_000;
+
 /// The merkle hashing stage uses input from
 /// [`AccountHashingStage`][crate::stages::AccountHashingStage] and
 /// [`StorageHashingStage`][crate::stages::AccountHashingStage] to calculate intermediate hashes
@@ -56,6 +61,11 @@ pub const MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD: u64 = 5_000;
 /// stages. The order of these two variants is important. The unwind variant should be added to the
 /// pipeline before the execution variant.
 ///
+/// A whole rebuild of the trie is split into chunks of at most `commit_threshold` trie updates.
+/// The position of the rebuild is saved as a [`MerkleCheckpoint`] after each chunk, so an
+/// interrupted rebuild continues where it left off as long as the target block doesn't change, and
+/// its progress is reported through the entities checkpoint of the stage.
+///
 /// An example pipeline to only hash state would be:
 ///
 /// - [`MerkleStage::Unwind`]
@@ -69,6 +79,9 @@ pub enum MerkleStage {
         /// The threshold (in number of blocks) for switching from incremental trie building
         /// of changes to whole rebuild.
         clean_threshold: u64,
+        /// The maximum number of trie updates of a whole rebuild to compute before committing
+        /// its intermediate progress.
+        commit_threshold: u64,
     },
     /// The unwind portion of the merkle stage.
     Unwind,
@@ -78,13 +91,19 @@ pub enum MerkleStage {
         /// The threshold (in number of blocks) for switching from incremental trie building
         /// of changes to whole rebuild.
         clean_threshold: u64,
+        /// The maximum number of trie updates of a whole rebuild to compute before committing
+        /// its intermediate progress.
+        commit_threshold: u64,
     },
 }
 
 impl MerkleStage {
     /// Stage default for the [`MerkleStage::Execution`].
     pub const fn default_execution() -> Self {
-        Self::Execution { clean_threshold: MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD }
+        Self::Execution {
+            clean_threshold: MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD,
+            commit_threshold: MERKLE_STAGE_DEFAULT_COMMIT_THRESHOLD,
+        }
     }
 
     /// Stage default for the [`MerkleStage::Unwind`].
@@ -93,8 +112,11 @@ impl MerkleStage {
     }
 
     /// Create new instance of [`MerkleStage::Execution`].
-    pub const fn new_execution(clean_threshold: u64) -> Self {
-        Self::Execution { clean_threshold }
+    pub const fn new_execution(config: MerkleConfig) -> Self {
+        Self::Execution {
+            clean_threshold: config.clean_threshold,
+            commit_threshold: config.commit_threshold,
+        }
     }
 
     /// Gets the hashing progress
@@ -149,14 +171,18 @@ impl<DB: Database> Stage<DB> for MerkleStage {
         provider: &DatabaseProviderRW<DB>,
         input: ExecInput,
     ) -> Result<ExecOutput, StageError> {
-        let threshold = match self {
+        let (threshold, commit_threshold) = match self {
             Self::Unwind => {
                 info!(target: "sync::stages::merkle::unwind", "Stage is always skipped");
                 return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
             }
-            Self::Execution { clean_threshold } => *clean_threshold,
+            Self::Execution { clean_threshold, commit_threshold } => {
+                (*clean_threshold, *commit_threshold)
+            }
             #[cfg(any(test, feature = "test-utils"))]
-            Self::Both { clean_threshold } => *clean_threshold,
+            Self::Both { clean_threshold, commit_threshold } => {
+                (*clean_threshold, *commit_threshold)
+            }
         };
 
         let range = input.next_block_range();
@@ -210,6 +236,7 @@ impl<DB: Database> Stage<DB> for MerkleStage {
 
             let tx = provider.tx_ref();
             let progress = StateRoot::from_tx(tx)
+                .with_threshold(commit_threshold)
                 .with_intermediate_state(checkpoint.map(IntermediateStateRootState::from))
                 .root_with_progress()
                 .map_err(|e| {
@@ -229,6 +256,12 @@ impl<DB: Database> Stage<DB> for MerkleStage {
                     self.save_execution_checkpoint(provider, Some(checkpoint))?;
 
                     entities_checkpoint.processed += hashed_entries_walked as u64;
+                    debug!(
+                        target: "sync::stages::merkle::exec",
+                        target = ?to_block,
+                        progress = ?entities_checkpoint.fmt_percentage(),
+                        "Committing intermediate trie rebuild progress"
+                    );
 
                     return Ok(ExecOutput {
                         checkpoint: input
@@ -340,7 +373,7 @@ impl<DB: Database> Stage<DB> for MerkleStage {
 
 /// Check that the computed state root matches the root in the expected header.
 #[inline]
-fn validate_state_root(
+pub(crate) fn validate_state_root(
     got: B256,
     expected: SealedHeader,
     target_block: BlockNumber,
@@ -422,6 +455,46 @@ mod tests {
         assert!(runner.validate_execution(input, result.ok()).is_ok(), "execution validation");
     }
 
+    /// Rebuild the whole trie in chunks, committing the intermediate progress after each one
+    #[tokio::test]
+    async fn execute_clean_merkle_in_chunks() {
+        let (previous_stage, stage_progress) = (500, 0);
+
+        let mut runner = MerkleTestRunner { commit_threshold: 1, ..Default::default() };
+        let mut input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+
+        runner.seed_execution(input).expect("failed to seed execution");
+        let total = (runner.db.table::<tables::HashedAccounts>().unwrap().len() +
+            runner.db.table::<tables::HashedStorages>().unwrap().len()) as u64;
+
+        let mut chunks = 0;
+        let mut processed = 0;
+        let output = loop {
+            let output = runner.execute(input).await.unwrap().unwrap();
+            let entities = output.checkpoint.entities_stage_checkpoint().unwrap();
+            assert!(entities.processed > processed);
+            assert_eq!(entities.total, total);
+            processed = entities.processed;
+            chunks += 1;
+
+            if output.done {
+                break output
+            }
+
+            // The stage checkpoint stays at the previous block until the rebuild is finished
+            assert_eq!(output.checkpoint.block_number, stage_progress);
+            input.checkpoint = Some(output.checkpoint);
+        };
+
+        assert!(chunks > 1);
+        assert_eq!(output.checkpoint.block_number, previous_stage);
+        assert_eq!(processed, total);
+        assert!(runner.validate_execution(input, Some(output)).is_ok(), "execution validation");
+    }
+
     /// Update small trie
     #[tokio::test]
     async fn execute_small_merkle() {
@@ -465,11 +538,16 @@ mod tests {
     struct MerkleTestRunner {
         db: TestStageDB,
         clean_threshold: u64,
+        commit_threshold: u64,
     }
 
     impl Default for MerkleTestRunner {
         fn default() -> Self {
-            Self { db: TestStageDB::default(), clean_threshold: 10000 }
+            Self {
+                db: TestStageDB::default(),
+                clean_threshold: 10000,
+                commit_threshold: MERKLE_STAGE_DEFAULT_COMMIT_THRESHOLD,
+            }
         }
     }
 
@@ -481,7 +559,10 @@ mod tests {
         }
 
         fn stage(&self) -> Self::S {
-            Self::S::Both { clean_threshold: self.clean_threshold }
+            Self::S::Both {
+                clean_threshold: self.clean_threshold,
+                commit_threshold: self.commit_threshold,
+            }
         }
     }
 
@@ -19,8 +19,12 @@ mod merkle;
 mod prune;
 /// The sender recovery stage.
 mod sender_recovery;
+/// The snap sync stage.
+mod snap_sync;
 /// The transaction lookup stage
 mod tx_lookup;
+/// Stage for computing the state root with the verkle trie.
+mod verkle;
 
 pub use bodies::*;
 pub use execution::*;
@@ -33,7 +37,9 @@ pub use index_storage_history::*;
 pub use merkle::*;
 pub use prune::*;
 pub use sender_recovery::*;
+pub use snap_sync::*;
 pub use tx_lookup::*;
+pub use verkle::*;
 
 mod utils;
 use utils::*;
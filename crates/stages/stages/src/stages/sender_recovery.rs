@@ -8,46 +8,81 @@ use reth_db_api::{
 };
 use reth_primitives::{Address, StaticFileSegment, TransactionSignedNoHash, TxNumber};
 use reth_provider::{
-    BlockReader, DatabaseProviderRW, HeaderProvider, ProviderError, PruneCheckpointReader,
-    StatsReader,
+    providers::StaticFileProvider, BlockReader, DatabaseProviderRW, HeaderProvider, ProviderError,
+    PruneCheckpointReader, StatsReader,
 };
 use reth_prune_types::PruneSegment;
 use reth_stages_api::{
     BlockErrorKind, EntitiesCheckpoint, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError,
     StageId, UnwindInput, UnwindOutput,
 };
-use std::{fmt::Debug, ops::Range, sync::mpsc};
+use std::{
+    fmt::Debug,
+    ops::Range,
+    sync::{mpsc, Arc},
+};
 use thiserror::Error;
 use tracing::*;
 
-/// Maximum amount of transactions to read from disk at one time before we flush their senders to
-/// disk. Since each rayon worker will hold at most 100 transactions (`WORKER_CHUNK_SIZE`), we
-/// effectively max limit each batch to 1000 channels in memory.
-const BATCH_SIZE: usize = 100_000;
-
 /// Maximum number of senders to recover per rayon worker job.
 const WORKER_CHUNK_SIZE: usize = 100;
 
+/// Recovered senders of a worker chunk, or the error that stopped its recovery.
+type RecoveredChunk = Result<Vec<(TxNumber, Address)>, Box<SenderRecoveryStageError>>;
+
 /// The sender recovery stage iterates over existing transactions,
 /// recovers the transaction signer and stores them
 /// in [`TransactionSenders`][reth_db::tables::TransactionSenders] table.
+///
+/// Transactions are read and their senders recovered in chunks by a pool of rayon workers, and the
+/// recovered senders are written to the database in batches.
 #[derive(Clone, Debug)]
 pub struct SenderRecoveryStage {
     /// The size of inserted items after which the control
     /// flow will be returned to the pipeline for commit
     pub commit_threshold: u64,
+    /// The number of transactions whose senders are recovered in parallel before they are
+    /// written to the database.
+    pub batch_size: usize,
+    /// The number of dedicated worker threads, or `0` to use the global rayon pool.
+    pub workers: usize,
+    /// Dedicated worker pool, created on the first execution.
+    pool: Option<Arc<rayon::ThreadPool>>,
 }
 
 impl SenderRecoveryStage {
     /// Create new instance of [`SenderRecoveryStage`].
     pub const fn new(config: SenderRecoveryConfig) -> Self {
-        Self { commit_threshold: config.commit_threshold }
+        Self {
+            commit_threshold: config.commit_threshold,
+            batch_size: config.batch_size,
+            workers: config.workers,
+            pool: None,
+        }
+    }
+
+    /// Returns the dedicated worker pool, if configured.
+    fn pool(&mut self) -> Result<Option<Arc<rayon::ThreadPool>>, StageError> {
+        if self.workers == 0 {
+            return Ok(None)
+        }
+
+        if self.pool.is_none() {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(self.workers)
+                .thread_name(|i| format!("sender-recovery-{i:02}"))
+                .build()
+                .map_err(|err| StageError::Fatal(Box::new(err)))?;
+            self.pool = Some(Arc::new(pool));
+        }
+
+        Ok(self.pool.clone())
     }
 }
 
 impl Default for SenderRecoveryStage {
     fn default() -> Self {
-        Self { commit_threshold: 5_000_000 }
+        Self::new(SenderRecoveryConfig::default())
     }
 }
 
@@ -84,20 +119,23 @@ impl<DB: Database> Stage<DB> for SenderRecoveryStage {
             })
         }
 
+        let pool = self.pool()?;
+
         // Acquire the cursor for inserting elements
         let mut senders_cursor = provider.tx_ref().cursor_write::<tables::TransactionSenders>()?;
 
-        info!(target: "sync::stages::sender_recovery", ?tx_range, "Recovering senders");
+        info!(target: "sync::stages::sender_recovery", ?tx_range, workers = self.workers, "Recovering senders");
 
         // Iterate over transactions in batches, recover the senders and append them
+        let batch_size = self.batch_size.max(1) as u64;
         let batch = tx_range
             .clone()
-            .step_by(BATCH_SIZE)
-            .map(|start| start..std::cmp::min(start + BATCH_SIZE as u64, tx_range.end))
+            .step_by(batch_size as usize)
+            .map(|start| start..std::cmp::min(start + batch_size, tx_range.end))
             .collect::<Vec<Range<u64>>>();
 
         for range in batch {
-            recover_range(range, provider, &mut senders_cursor)?;
+            recover_range(range, provider, pool.as_deref(), &mut senders_cursor)?;
         }
 
         Ok(ExecOutput {
@@ -132,6 +170,7 @@ impl<DB: Database> Stage<DB> for SenderRecoveryStage {
 fn recover_range<DB, CURSOR>(
     tx_range: Range<u64>,
     provider: &DatabaseProviderRW<DB>,
+    pool: Option<&rayon::ThreadPool>,
     senders_cursor: &mut CURSOR,
 ) -> Result<(), StageError>
 where
@@ -140,82 +179,60 @@ where
 {
     debug!(target: "sync::stages::sender_recovery", ?tx_range, "Recovering senders batch");
 
-    // Preallocate channels
-    let (chunks, receivers): (Vec<_>, Vec<_>) = tx_range
+    // Each worker reads its chunk of transactions from the static files, recovers the senders and
+    // sends them back through its own channel, so they're appended in order.
+    let receivers = tx_range
         .clone()
         .step_by(WORKER_CHUNK_SIZE)
         .map(|start| {
-            let range = start..std::cmp::min(start + WORKER_CHUNK_SIZE as u64, tx_range.end);
-            let (tx, rx) = mpsc::channel();
-            // Range and channel sender will be sent to rayon worker
-            ((range, tx), rx)
+            let chunk_range = start..std::cmp::min(start + WORKER_CHUNK_SIZE as u64, tx_range.end);
+            let (recovered_senders_tx, rx) = mpsc::sync_channel(1);
+            let static_file_provider = provider.static_file_provider().clone();
+            let job = move || {
+                let _ =
+                    recovered_senders_tx.send(recover_chunk(&static_file_provider, chunk_range));
+            };
+            match pool {
+                Some(pool) => pool.spawn(job),
+                None => rayon::spawn(job),
+            }
+            rx
         })
-        .unzip();
-
-    let static_file_provider = provider.static_file_provider().clone();
-    tokio::task::spawn_blocking(move || {
-        for (chunk_range, recovered_senders_tx) in chunks {
-            // Read the raw value, and let the rayon worker to decompress & decode.
-            let chunk = static_file_provider
-                .fetch_range_with_predicate(
-                    StaticFileSegment::Transactions,
-                    chunk_range.clone(),
-                    |cursor, number| {
-                        Ok(cursor
-                            .get_one::<TransactionMask<RawValue<TransactionSignedNoHash>>>(
-                                number.into(),
-                            )?
-                            .map(|tx| (number, tx)))
-                    },
-                    |_| true,
-                )
-                .expect("failed to fetch range");
-
-            // Spawn the task onto the global rayon pool
-            // This task will send the results through the channel after it has read the transaction
-            // and calculated the sender.
-            rayon::spawn(move || {
-                let mut rlp_buf = Vec::with_capacity(128);
-                for (number, tx) in chunk {
-                    rlp_buf.clear();
-                    let tx = tx.value().expect("decode error");
-                    let _ = recovered_senders_tx.send(recover_sender((number, tx), &mut rlp_buf));
-                }
-            });
-        }
-    });
+        .collect::<Vec<_>>();
 
     debug!(target: "sync::stages::sender_recovery", ?tx_range, "Appending recovered senders to the database");
     for channel in receivers {
-        while let Ok(recovered) = channel.recv() {
-            let (tx_id, sender) = match recovered {
-                Ok(result) => result,
-                Err(error) => {
-                    return match *error {
-                        SenderRecoveryStageError::FailedRecovery(err) => {
-                            // get the block number for the bad transaction
-                            let block_number = provider
-                                .tx_ref()
-                                .get::<tables::TransactionBlocks>(err.tx)?
-                                .ok_or(ProviderError::BlockNumberForTransactionIndexNotFound)?;
-
-                            // fetch the sealed header so we can use it in the sender recovery
-                            // unwind
-                            let sealed_header = provider
-                                .sealed_header(block_number)?
-                                .ok_or(ProviderError::HeaderNotFound(block_number.into()))?;
-                            Err(StageError::Block {
-                                block: Box::new(sealed_header),
-                                error: BlockErrorKind::Validation(
-                                    ConsensusError::TransactionSignerRecoveryError,
-                                ),
-                            })
-                        }
-                        SenderRecoveryStageError::StageError(err) => Err(err),
+        let recovered = channel.recv().map_err(|err| StageError::Fatal(Box::new(err)))?;
+        match recovered {
+            Ok(senders) => {
+                for (tx_id, sender) in senders {
+                    senders_cursor.append(tx_id, sender)?;
+                }
+            }
+            Err(error) => {
+                return match *error {
+                    SenderRecoveryStageError::FailedRecovery(err) => {
+                        // get the block number for the bad transaction
+                        let block_number = provider
+                            .tx_ref()
+                            .get::<tables::TransactionBlocks>(err.tx)?
+                            .ok_or(ProviderError::BlockNumberForTransactionIndexNotFound)?;
+
+                        // fetch the sealed header so we can use it in the sender recovery
+                        // unwind
+                        let sealed_header = provider
+                            .sealed_header(block_number)?
+                            .ok_or(ProviderError::HeaderNotFound(block_number.into()))?;
+                        Err(StageError::Block {
+                            block: Box::new(sealed_header),
+                            error: BlockErrorKind::Validation(
+                                ConsensusError::TransactionSignerRecoveryError,
+                            ),
+                        })
                     }
+                    SenderRecoveryStageError::StageError(err) => Err(err),
                 }
-            };
-            senders_cursor.append(tx_id, sender)?;
+            }
         }
     }
     debug!(target: "sync::stages::sender_recovery", ?tx_range, "Finished recovering senders batch");
@@ -223,6 +240,37 @@ where
     Ok(())
 }
 
+/// Reads a chunk of transactions from the static files and recovers their senders.
+fn recover_chunk(
+    static_file_provider: &StaticFileProvider,
+    chunk_range: Range<TxNumber>,
+) -> RecoveredChunk {
+    let chunk = static_file_provider
+        .fetch_range_with_predicate(
+            StaticFileSegment::Transactions,
+            chunk_range,
+            |cursor, number| {
+                Ok(cursor
+                    .get_one::<TransactionMask<RawValue<TransactionSignedNoHash>>>(number.into())?
+                    .map(|tx| (number, tx)))
+            },
+            |_| true,
+        )
+        .map_err(|err| Box::new(SenderRecoveryStageError::StageError(err.into())))?;
+
+    let mut rlp_buf = Vec::with_capacity(128);
+    chunk
+        .into_iter()
+        .map(|(number, tx)| {
+            rlp_buf.clear();
+            let tx = tx.value().map_err(|err| {
+                Box::new(SenderRecoveryStageError::StageError(StageError::Fatal(Box::new(err))))
+            })?;
+            recover_sender((number, tx), &mut rlp_buf)
+        })
+        .collect()
+}
+
 #[inline]
 fn recover_sender(
     (tx_id, tx): (TxNumber, TransactionSignedNoHash),
@@ -437,6 +485,50 @@ mod tests {
         assert!(runner.validate_execution(first_input, result.ok()).is_ok(), "validation failed");
     }
 
+    /// Execute the stage on a dedicated worker pool with batches that don't align with the worker
+    /// chunks
+    #[tokio::test]
+    async fn execute_worker_pool() {
+        let mut rng = generators::rng();
+
+        let mut runner = SenderRecoveryTestRunner::default();
+        runner.config.workers = 2;
+        runner.config.batch_size = 150;
+        let (stage_progress, previous_stage) = (1000, 1100);
+
+        let seed = random_block_range(
+            &mut rng,
+            stage_progress + 1..=previous_stage,
+            B256::ZERO,
+            0..8,
+            None,
+            None,
+        );
+        runner
+            .db
+            .insert_blocks(seed.iter(), StorageKind::Static)
+            .expect("failed to seed execution");
+        let total_transactions = seed.iter().map(|block| block.body.len() as u64).sum::<u64>();
+        assert!(total_transactions > runner.config.batch_size as u64);
+
+        let input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+        let result = runner.execute(input).await.unwrap();
+        assert_eq!(
+            result.as_ref().unwrap(),
+            &ExecOutput {
+                checkpoint: StageCheckpoint::new(previous_stage).with_entities_stage_checkpoint(
+                    EntitiesCheckpoint { processed: total_transactions, total: total_transactions }
+                ),
+                done: true
+            }
+        );
+
+        assert!(runner.validate_execution(input, result.ok()).is_ok(), "validation failed");
+    }
+
     #[test]
     fn stage_checkpoint_pruned() {
         let db = TestStageDB::default();
@@ -494,18 +586,21 @@ mod tests {
 
     struct SenderRecoveryTestRunner {
         db: TestStageDB,
-        threshold: u64,
+        config: SenderRecoveryConfig,
     }
 
     impl Default for SenderRecoveryTestRunner {
         fn default() -> Self {
-            Self { threshold: 1000, db: TestStageDB::default() }
+            Self {
+                config: SenderRecoveryConfig { commit_threshold: 1000, ..Default::default() },
+                db: TestStageDB::default(),
+            }
         }
     }
 
     impl SenderRecoveryTestRunner {
         fn set_threshold(&mut self, threshold: u64) {
-            self.threshold = threshold;
+            self.config.commit_threshold = threshold;
         }
 
         /// # Panics
@@ -543,7 +638,7 @@ mod tests {
         }
 
         fn stage(&self) -> Self::S {
-            SenderRecoveryStage { commit_threshold: self.threshold }
+            SenderRecoveryStage::new(self.config)
         }
     }
 
@@ -0,0 +1,1318 @@
+use alloy_rlp::Decodable;
+use futures_util::FutureExt;
+use reth_config::config::SnapSyncConfig;
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+    database::Database,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_eth_wire_types::snap::{
+    GetAccountRange, GetByteCodes, GetStorageRanges, GetTrieNodes, SlimAccount, SnapProtocolMessage,
+};
+use reth_network_p2p::{
+    error::{PeerRequestResult, RequestError},
+    snap::client::{SnapClient, SnapFut},
+};
+use reth_network_peers::WithPeerId;
+use reth_primitives::{
+    constants::{EMPTY_ROOT_HASH, KECCAK_EMPTY},
+    keccak256, Account, Bytecode, Bytes, SealedHeader, StorageEntry, B256, U256,
+};
+use reth_provider::{
+    DatabaseProviderRW, HeaderProvider, ProviderError, PruneCheckpointWriter,
+    StageCheckpointReader, StageCheckpointWriter, TrieWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, SnapSyncCheckpoint, Stage, StageCheckpoint, StageError, StageId,
+    UnwindInput, UnwindOutput,
+};
+use reth_trie::{
+    prefix_set::TriePrefixSetsMut, proof::Proof, verify_range_proof, BranchNode, Nibbles,
+    StateRoot, TrieAccount, TrieNode,
+};
+use reth_trie_db::{DatabaseProof, DatabaseStateRoot};
+use std::{
+    collections::{BTreeSet, HashMap, VecDeque},
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tracing::*;
+
+/// The maximum number of accounts to request the storage of at once.
+const MAX_STORAGE_ACCOUNTS_PER_REQUEST: usize = 64;
+
+/// The maximum number of bytecodes to request at once.
+const MAX_BYTECODES_PER_REQUEST: usize = 64;
+
+/// The maximum number of trie nodes to request at once.
+const MAX_TRIE_NODES_PER_REQUEST: usize = 128;
+
+/// The number of consecutive empty responses after which the state of the pivot block is
+/// considered to be no longer served by peers.
+const MAX_EMPTY_RESPONSES: usize = 16;
+
+/// The future of an in-flight `snap/1` request.
+type SnapResponseFut =
+    Pin<Box<dyn Future<Output = PeerRequestResult<SnapProtocolMessage>> + Send + Sync>>;
+
+/// The snap sync stage downloads the state of a recent block from peers with the `snap/1`
+/// protocol, instead of building it by executing all blocks since genesis.
+///
+/// The stage picks the block of its target as the pivot and downloads all accounts, storage slots
+/// and bytecodes of its state into the hashed state tables. Each downloaded range is verified
+/// against the state root of the pivot, or the storage root of its account, and peers serving
+/// invalid ranges are reported. Once everything is downloaded, the account trie is computed and,
+/// if its root doesn't match the state root of the pivot because the pivot moved during the
+/// download, the mismatching subtries are healed by comparing the trie nodes of peers with the
+/// local ones and downloading the differing account ranges again.
+///
+/// When the state root matches, the accounts and storage slots of the plain state are updated to
+/// their downloaded values, and the checkpoints of the execution, hashing, merkle and history index
+/// stages are moved to the pivot block. State providers fall back to the hashed state for accounts
+/// and storage slots that are missing from the plain state.
+///
+/// The stage does nothing if any block was executed already.
+///
+/// # Tables
+///
+/// - [`HashedAccounts`][reth_db::tables::HashedAccounts]
+/// - [`HashedStorages`][reth_db::tables::HashedStorages]
+/// - [`Bytecodes`][reth_db::tables::Bytecodes]
+/// - [`AccountsTrie`][reth_db::tables::AccountsTrie]
+/// - [`StoragesTrie`][reth_db::tables::StoragesTrie]
+pub struct SnapSyncStage<C> {
+    /// The client to download the state with.
+    client: C,
+    /// The snap sync configuration.
+    config: SnapSyncConfig,
+    /// The state of an ongoing snap sync.
+    state: Option<SnapSyncState>,
+    /// The in-flight request.
+    inflight: Option<(SnapRequest, SnapResponseFut)>,
+    /// The validated response waiting to be written.
+    response: Option<(SnapRequest, SnapResponse)>,
+}
+
+impl<C> SnapSyncStage<C> {
+    /// Create new snap sync stage from the client and the configuration.
+    pub const fn new(client: C, config: SnapSyncConfig) -> Self {
+        Self { client, config, state: None, inflight: None, response: None }
+    }
+
+    /// Drops the in-memory progress, so the download restarts from the committed progress.
+    fn reset(&mut self) {
+        self.state = None;
+        self.inflight = None;
+        self.response = None;
+    }
+}
+
+impl<C: fmt::Debug> fmt::Debug for SnapSyncStage<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapSyncStage")
+            .field("client", &self.client)
+            .field("config", &self.config)
+            .field("state", &self.state)
+            .field("inflight", &self.inflight.as_ref().map(|(request, _)| request))
+            .finish_non_exhaustive()
+    }
+}
+
+impl<DB: Database, C: SnapClient + 'static> Stage<DB> for SnapSyncStage<C> {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::SnapSync
+    }
+
+    fn poll_execute_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+        _input: ExecInput,
+    ) -> Poll<Result<(), StageError>> {
+        loop {
+            let Some(state) = self.state.as_mut() else { return Poll::Ready(Ok(())) };
+            if self.response.is_some() || state.pivot_unavailable {
+                return Poll::Ready(Ok(()))
+            }
+
+            let (request, mut fut) = match self.inflight.take() {
+                Some(inflight) => inflight,
+                None => {
+                    // Nothing left to download, the state root is checked next.
+                    let Some(request) = state.next_request() else { return Poll::Ready(Ok(())) };
+                    let fut = send_request(
+                        &self.client,
+                        &request,
+                        state.pivot.state_root,
+                        self.config.response_bytes,
+                    );
+                    (request, fut)
+                }
+            };
+
+            let response = match fut.poll_unpin(cx) {
+                Poll::Ready(Ok(response)) => response,
+                Poll::Ready(Err(err)) => {
+                    state.requeue(request);
+                    return Poll::Ready(Err(StageError::Recoverable(Box::new(err))))
+                }
+                Poll::Pending => {
+                    self.inflight = Some((request, fut));
+                    return Poll::Pending
+                }
+            };
+
+            let peer_id = response.peer_id();
+            match decode_response(&request, response.into_data(), state.pivot.state_root) {
+                Ok(Some(response)) => {
+                    state.empty_responses = 0;
+                    self.response = Some((request, response));
+                    return Poll::Ready(Ok(()))
+                }
+                Ok(None) => {
+                    trace!(target: "sync::stages::snap_sync", ?peer_id, ?request, "Peer did not serve the pivot state");
+                    state.requeue(request);
+                    state.empty_responses += 1;
+                    if state.empty_responses >= MAX_EMPTY_RESPONSES {
+                        state.pivot_unavailable = true;
+                    }
+                }
+                Err(err) => {
+                    self.client.report_bad_message(peer_id);
+                    state.requeue(request);
+                    return Poll::Ready(Err(StageError::Recoverable(Box::new(err))))
+                }
+            }
+        }
+    }
+
+    /// Download the state of the pivot block, or heal it if the downloaded state doesn't match
+    /// the state root of the pivot.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let checkpoint = input.checkpoint();
+        if checkpoint.snap_sync_stage_checkpoint().is_some() ||
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number >
+                0
+        {
+            // The state was either downloaded already or is built by executing blocks.
+            return Ok(ExecOutput::done(checkpoint.with_block_number(input.target())))
+        }
+
+        let result = self.execute_inner(provider, input);
+        if result.is_err() {
+            // The writes of this execution are discarded.
+            self.reset();
+        }
+        result
+    }
+
+    fn unwind(
+        &mut self,
+        _provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        if let Some(SnapSyncCheckpoint { pivot_block }) =
+            input.checkpoint.snap_sync_stage_checkpoint()
+        {
+            // The state before the pivot block was never downloaded.
+            if input.unwind_to < pivot_block {
+                return Err(ProviderError::StateAtBlockPruned(input.unwind_to).into())
+            }
+        }
+
+        self.reset();
+        Ok(UnwindOutput { checkpoint: input.checkpoint.with_block_number(input.unwind_to) })
+    }
+}
+
+impl<C> SnapSyncStage<C> {
+    fn execute_inner<DB: Database>(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        let in_progress = ExecOutput { checkpoint: input.checkpoint(), done: false };
+
+        let Some(state) = self.state.as_mut() else {
+            let state = SnapSyncState::new(provider, input.target())?;
+            info!(target: "sync::stages::snap_sync", pivot = state.pivot.number, "Starting snap sync");
+            self.state = Some(state);
+            return Ok(in_progress)
+        };
+
+        if state.pivot_unavailable {
+            if input.target() <= state.pivot.number {
+                // End the pipeline run without progress, the next run has a newer target.
+                warn!(target: "sync::stages::snap_sync", pivot = state.pivot.number, "Peers stopped serving the pivot state");
+                return Ok(ExecOutput::done(input.checkpoint()))
+            }
+
+            // The downloaded state is kept, the differences to the new pivot are healed.
+            state.pivot = pivot_header(provider, input.target())?;
+            state.pivot_unavailable = false;
+            state.empty_responses = 0;
+            info!(target: "sync::stages::snap_sync", pivot = state.pivot.number, "Moved snap sync pivot");
+        }
+
+        if let Some((request, response)) = self.response.take() {
+            state.write_response(provider, request, response)?;
+            return Ok(in_progress)
+        }
+
+        let state_root = state.state_root(provider)?;
+        if state_root != state.pivot.state_root {
+            debug!(target: "sync::stages::snap_sync", got = ?state_root, expected = ?state.pivot.state_root, "Healing state");
+            state.trie_nodes.push_back((Nibbles::default(), state.pivot.state_root));
+            return Ok(in_progress)
+        }
+
+        let pivot_block = state.pivot.number;
+        finish(provider, pivot_block)?;
+        self.reset();
+        info!(target: "sync::stages::snap_sync", pivot = pivot_block, "Finished snap sync");
+
+        Ok(ExecOutput::done(
+            StageCheckpoint::new(input.target())
+                .with_snap_sync_stage_checkpoint(SnapSyncCheckpoint { pivot_block }),
+        ))
+    }
+}
+
+/// A request of the stage, taken out of the download queues while in-flight.
+#[derive(Debug)]
+enum SnapRequest {
+    /// The accounts between the hashes, inclusive.
+    AccountRange { starting_hash: B256, limit_hash: B256 },
+    /// The storage of the accounts, starting at the hash for the first account.
+    StorageRanges { accounts: Vec<AccountStorage>, starting_hash: B256 },
+    /// The bytecodes with the hashes.
+    ByteCodes(Vec<B256>),
+    /// The account trie nodes at the paths, with their expected hashes.
+    TrieNodes(Vec<(Nibbles, B256)>),
+}
+
+/// A validated and decoded response to a [`SnapRequest`].
+#[derive(Debug)]
+enum SnapResponse {
+    /// The accounts of the range, and whether there are no more accounts in the range.
+    Accounts { accounts: Vec<(B256, SlimAccount)>, complete: bool },
+    /// The storage slots of the served accounts, and whether the storage of the last served
+    /// account is complete.
+    Storages { slots: Vec<Vec<StorageEntry>>, complete: bool },
+    /// The bytecodes by their hashes.
+    ByteCodes(Vec<(B256, Bytes)>),
+    /// The trie nodes of the served paths.
+    TrieNodes(Vec<TrieNode>),
+}
+
+/// An account to download the storage of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AccountStorage {
+    /// The hash of the address of the account.
+    hashed_address: B256,
+    /// The storage root the downloaded storage is verified against.
+    storage_root: B256,
+}
+
+/// The in-memory state of an ongoing snap sync.
+#[derive(Debug)]
+struct SnapSyncState {
+    /// The header of the block whose state is downloaded.
+    pivot: SealedHeader,
+    /// Whether peers stopped serving the state of the pivot.
+    pivot_unavailable: bool,
+    /// The number of consecutive responses without data.
+    empty_responses: usize,
+    /// Whether the trie tables were rebuilt from the downloaded state.
+    trie_built: bool,
+    /// The ranges of accounts to download.
+    account_ranges: VecDeque<(B256, B256)>,
+    /// The accounts to download the storage of, with the hash of the slot to start at.
+    storages: VecDeque<(AccountStorage, B256)>,
+    /// The hashes of the bytecodes to download.
+    bytecodes: BTreeSet<B256>,
+    /// The paths of the account trie nodes to heal, with their expected hashes.
+    trie_nodes: VecDeque<(Nibbles, B256)>,
+    /// The keys changed since the state root was last computed.
+    prefix_sets: TriePrefixSetsMut,
+}
+
+impl SnapSyncState {
+    /// Creates the state for the given pivot block, continuing the account download from the last
+    /// committed progress.
+    fn new<DB: Database>(
+        provider: &DatabaseProviderRW<DB>,
+        pivot_block: u64,
+    ) -> Result<Self, StageError> {
+        let mut account_ranges = VecDeque::new();
+        match provider.get_stage_checkpoint_progress(StageId::SnapSync)? {
+            // All accounts were downloaded.
+            Some(progress) if progress.is_empty() => {}
+            Some(progress) if progress.len() == B256::len_bytes() => {
+                account_ranges.push_back((B256::from_slice(&progress), B256::repeat_byte(0xff)))
+            }
+            _ => account_ranges.push_back((B256::ZERO, B256::repeat_byte(0xff))),
+        }
+
+        Ok(Self {
+            pivot: pivot_header(provider, pivot_block)?,
+            pivot_unavailable: false,
+            empty_responses: 0,
+            trie_built: false,
+            account_ranges,
+            storages: VecDeque::new(),
+            bytecodes: BTreeSet::new(),
+            trie_nodes: VecDeque::new(),
+            prefix_sets: TriePrefixSetsMut::default(),
+        })
+    }
+
+    /// Takes the next request out of the queues, or returns `None` if everything is downloaded.
+    ///
+    /// Storage and bytecodes are downloaded before more accounts, which are downloaded before the
+    /// trie nodes to heal.
+    fn next_request(&mut self) -> Option<SnapRequest> {
+        if let Some((account, starting_hash)) = self.storages.pop_front() {
+            let mut accounts = vec![account];
+            // Only the first account can continue the download of a partially downloaded storage.
+            while starting_hash.is_zero() && accounts.len() < MAX_STORAGE_ACCOUNTS_PER_REQUEST {
+                match self.storages.front() {
+                    Some((account, starting_hash)) if starting_hash.is_zero() => {
+                        accounts.push(*account);
+                        self.storages.pop_front();
+                    }
+                    _ => break,
+                }
+            }
+            return Some(SnapRequest::StorageRanges { accounts, starting_hash })
+        }
+
+        if !self.bytecodes.is_empty() {
+            let hashes = std::iter::from_fn(|| self.bytecodes.pop_first())
+                .take(MAX_BYTECODES_PER_REQUEST)
+                .collect();
+            return Some(SnapRequest::ByteCodes(hashes))
+        }
+
+        if let Some((starting_hash, limit_hash)) = self.account_ranges.pop_front() {
+            return Some(SnapRequest::AccountRange { starting_hash, limit_hash })
+        }
+
+        if !self.trie_nodes.is_empty() {
+            let len = self.trie_nodes.len().min(MAX_TRIE_NODES_PER_REQUEST);
+            return Some(SnapRequest::TrieNodes(self.trie_nodes.drain(..len).collect()))
+        }
+
+        None
+    }
+
+    /// Puts the unserved part of a request back into the queues.
+    fn requeue(&mut self, request: SnapRequest) {
+        match request {
+            SnapRequest::AccountRange { starting_hash, limit_hash } => {
+                self.account_ranges.push_front((starting_hash, limit_hash))
+            }
+            SnapRequest::StorageRanges { accounts, starting_hash } => {
+                for (index, account) in accounts.into_iter().enumerate().rev() {
+                    let starting_hash = if index == 0 { starting_hash } else { B256::ZERO };
+                    self.storages.push_front((account, starting_hash));
+                }
+            }
+            SnapRequest::ByteCodes(hashes) => self.bytecodes.extend(hashes),
+            SnapRequest::TrieNodes(nodes) => {
+                for node in nodes.into_iter().rev() {
+                    self.trie_nodes.push_front(node);
+                }
+            }
+        }
+    }
+
+    /// Writes a response to the database and queues the follow-up requests.
+    fn write_response<DB: Database>(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        request: SnapRequest,
+        response: SnapResponse,
+    ) -> Result<(), StageError> {
+        let tx = provider.tx_ref();
+        match (request, response) {
+            (
+                SnapRequest::AccountRange { starting_hash, limit_hash },
+                SnapResponse::Accounts { accounts, complete },
+            ) => {
+                let last_hash = match accounts.last() {
+                    Some((hash, _)) if !complete => *hash,
+                    _ => limit_hash,
+                };
+                self.write_accounts(tx, starting_hash, last_hash, accounts)?;
+
+                let next_hash = if complete { None } else { next_hash(last_hash) };
+                if let Some(next_hash) = next_hash {
+                    self.account_ranges.push_front((next_hash, limit_hash));
+                }
+
+                // Ranges downloaded after the trie was built are healed, they don't continue
+                // the initial download.
+                if !self.trie_built {
+                    provider.save_stage_checkpoint_progress(
+                        StageId::SnapSync,
+                        next_hash.map(|hash| hash.to_vec()).unwrap_or_default(),
+                    )?;
+                }
+            }
+            (
+                SnapRequest::StorageRanges { accounts, starting_hash },
+                SnapResponse::Storages { slots, complete },
+            ) => {
+                let mut storage_cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+                let served = slots.len();
+                let mut last_slot = None;
+                for (AccountStorage { hashed_address, .. }, slots) in accounts.iter().zip(slots) {
+                    let prefix_set =
+                        self.prefix_sets.storage_prefix_sets.entry(*hashed_address).or_default();
+                    last_slot = slots.last().map(|entry| entry.key);
+                    for entry in slots {
+                        if storage_cursor
+                            .seek_by_key_subkey(*hashed_address, entry.key)?
+                            .filter(|e| e.key == entry.key)
+                            .is_some()
+                        {
+                            storage_cursor.delete_current()?;
+                        }
+                        if !entry.value.is_zero() {
+                            storage_cursor.upsert(*hashed_address, entry)?;
+                        }
+                        prefix_set.insert(Nibbles::unpack(entry.key));
+                    }
+                    self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+                }
+
+                let mut unserved = accounts[served..].to_vec();
+                if !complete {
+                    // Continue the storage of the last served account.
+                    let first_slot = if served == 1 { starting_hash } else { B256::ZERO };
+                    let next_slot = last_slot.map_or(Some(first_slot), next_hash);
+                    if let Some(next_hash) = next_slot {
+                        unserved.insert(0, accounts[served - 1]);
+                        self.requeue(SnapRequest::StorageRanges {
+                            accounts: unserved,
+                            starting_hash: next_hash,
+                        });
+                        return Ok(())
+                    }
+                }
+                let starting_hash = if served == 0 { starting_hash } else { B256::ZERO };
+                self.requeue(SnapRequest::StorageRanges { accounts: unserved, starting_hash });
+            }
+            (SnapRequest::ByteCodes(mut hashes), SnapResponse::ByteCodes(codes)) => {
+                for (hash, code) in codes {
+                    tx.put::<tables::Bytecodes>(hash, Bytecode::new_raw(code))?;
+                    hashes.retain(|requested| *requested != hash);
+                }
+                self.requeue(SnapRequest::ByteCodes(hashes));
+            }
+            (SnapRequest::TrieNodes(mut paths), SnapResponse::TrieNodes(nodes)) => {
+                let unserved = paths.split_off(nodes.len());
+                for ((path, _), node) in paths.into_iter().zip(nodes) {
+                    self.heal_node(tx, path, node)?;
+                }
+                self.requeue(SnapRequest::TrieNodes(unserved));
+            }
+            (request, _) => {
+                // Responses are matched to their requests when decoded.
+                unreachable!("response does not match request {request:?}")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the local accounts between the hashes with the downloaded ones, and queues the
+    /// download of their storage and bytecodes.
+    fn write_accounts<TX: DbTx + DbTxMut>(
+        &mut self,
+        tx: &TX,
+        starting_hash: B256,
+        last_hash: B256,
+        accounts: Vec<(B256, SlimAccount)>,
+    ) -> Result<(), StageError> {
+        let mut account_cursor = tx.cursor_write::<tables::HashedAccounts>()?;
+        let mut removed = Vec::new();
+        let mut walker = account_cursor.walk_range(starting_hash..=last_hash)?;
+        while let Some((hashed_address, _)) = walker.next().transpose()? {
+            walker.delete_current()?;
+            removed.push(hashed_address);
+        }
+        for hashed_address in removed {
+            wipe_storage(tx, hashed_address)?;
+            self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+        }
+
+        for (hashed_address, account) in accounts {
+            wipe_storage(tx, hashed_address)?;
+            if account.storage_root != EMPTY_ROOT_HASH {
+                let storage_root = account.storage_root;
+                self.storages
+                    .push_back((AccountStorage { hashed_address, storage_root }, B256::ZERO));
+            }
+            if account.code_hash != KECCAK_EMPTY &&
+                tx.get::<tables::Bytecodes>(account.code_hash)?.is_none()
+            {
+                self.bytecodes.insert(account.code_hash);
+            }
+
+            account_cursor.upsert(
+                hashed_address,
+                Account {
+                    nonce: account.nonce,
+                    balance: account.balance,
+                    bytecode_hash: (account.code_hash != KECCAK_EMPTY).then_some(account.code_hash),
+                },
+            )?;
+            self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+        }
+
+        Ok(())
+    }
+
+    /// Compares the trie node of a peer with the local node at the same path.
+    ///
+    /// The differing children of branch nodes are healed recursively, any other difference is
+    /// healed by downloading the accounts below the path again.
+    fn heal_node<TX: DbTx>(
+        &mut self,
+        tx: &TX,
+        path: Nibbles,
+        node: TrieNode,
+    ) -> Result<(), StageError> {
+        let local = Proof::from_tx(tx)
+            .with_targets(HashMap::from([(pad_path(&path, 0), Vec::new())]))
+            .multiproof()
+            .map_err(|err| StageError::Fatal(Box::new(err)))?
+            .account_subtree
+            .remove(&path)
+            .map(|node| TrieNode::decode(&mut &node[..]))
+            .transpose()
+            .map_err(|err| StageError::Fatal(Box::new(err)))?;
+
+        match (node, local) {
+            (node, Some(local)) if node == local => {}
+            (TrieNode::Branch(branch), Some(TrieNode::Branch(local))) => {
+                for nibble in 0..16 {
+                    let child = branch_child(&branch, nibble);
+                    if child == branch_child(&local, nibble) {
+                        continue
+                    }
+
+                    let mut child_path = path.clone();
+                    child_path.push(nibble);
+                    match child.and_then(child_hash) {
+                        Some(hash) => self.trie_nodes.push_back((child_path, hash)),
+                        None => self.account_ranges.push_back(prefix_range(&child_path)),
+                    }
+                }
+            }
+            _ => self.account_ranges.push_back(prefix_range(&path)),
+        }
+
+        Ok(())
+    }
+
+    /// Computes the state root of the downloaded state and writes the trie updates.
+    ///
+    /// The first computation rebuilds the trie tables, later ones only update the changed keys.
+    fn state_root<DB: Database>(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+    ) -> Result<B256, StageError> {
+        let tx = provider.tx_ref();
+        let prefix_sets = std::mem::take(&mut self.prefix_sets);
+        let (state_root, updates) = if self.trie_built {
+            StateRoot::from_tx(tx).with_prefix_sets(prefix_sets.freeze()).root_with_updates()
+        } else {
+            tx.clear::<tables::AccountsTrie>()?;
+            tx.clear::<tables::StoragesTrie>()?;
+            StateRoot::from_tx(tx).root_with_updates()
+        }
+        .map_err(|err| StageError::Fatal(Box::new(err)))?;
+
+        provider.write_trie_updates(&updates)?;
+        self.trie_built = true;
+        Ok(state_root)
+    }
+}
+
+/// Sends the request with the client.
+fn send_request<C: SnapClient>(
+    client: &C,
+    request: &SnapRequest,
+    root_hash: B256,
+    response_bytes: u64,
+) -> SnapResponseFut {
+    match request {
+        SnapRequest::AccountRange { starting_hash, limit_hash } => boxed(
+            client.get_account_range(GetAccountRange {
+                request_id: 0,
+                root_hash,
+                starting_hash: *starting_hash,
+                limit_hash: *limit_hash,
+                response_bytes,
+            }),
+            SnapProtocolMessage::AccountRange,
+        ),
+        SnapRequest::StorageRanges { accounts, starting_hash } => boxed(
+            client.get_storage_ranges(GetStorageRanges {
+                request_id: 0,
+                root_hash,
+                account_hashes: accounts.iter().map(|account| account.hashed_address).collect(),
+                starting_hash: *starting_hash,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes,
+            }),
+            SnapProtocolMessage::StorageRanges,
+        ),
+        SnapRequest::ByteCodes(hashes) => boxed(
+            client.get_byte_codes(GetByteCodes {
+                request_id: 0,
+                hashes: hashes.clone(),
+                response_bytes,
+            }),
+            SnapProtocolMessage::ByteCodes,
+        ),
+        SnapRequest::TrieNodes(nodes) => boxed(
+            client.get_trie_nodes(GetTrieNodes {
+                request_id: 0,
+                root_hash,
+                paths: nodes
+                    .iter()
+                    .map(|(path, _)| vec![Bytes::from(path.encode_path_leaf(false).to_vec())])
+                    .collect(),
+                response_bytes,
+            }),
+            SnapProtocolMessage::TrieNodes,
+        ),
+    }
+}
+
+/// Maps the response of a [`SnapFut`] to a [`SnapProtocolMessage`].
+fn boxed<T: 'static>(fut: SnapFut<T>, f: fn(T) -> SnapProtocolMessage) -> SnapResponseFut {
+    Box::pin(fut.map(move |result| {
+        result.map(|response| WithPeerId::new(response.peer_id(), f(response.into_data())))
+    }))
+}
+
+/// Validates and decodes the response to a request.
+///
+/// The ranges of accounts are verified against the state root of the pivot, and the ranges of
+/// storage slots against the storage roots of their accounts. Only the last range of a response
+/// may be incomplete, and be proven with the proof of the response.
+///
+/// Returns `None` if the response is empty, i.e. the peer doesn't serve the requested state.
+fn decode_response(
+    request: &SnapRequest,
+    message: SnapProtocolMessage,
+    state_root: B256,
+) -> Result<Option<SnapResponse>, RequestError> {
+    let response = match (request, message) {
+        (
+            SnapRequest::AccountRange { starting_hash, limit_hash },
+            SnapProtocolMessage::AccountRange(range),
+        ) => {
+            if range.accounts.is_empty() && range.proof.is_empty() {
+                return Ok(None)
+            }
+
+            let mut accounts = range
+                .accounts
+                .into_iter()
+                .map(|data| Ok((data.hash, data.account()?)))
+                .collect::<alloy_rlp::Result<Vec<_>>>()
+                .map_err(|_| RequestError::BadResponse)?;
+            let leaves = accounts
+                .iter()
+                .map(|(hash, account)| {
+                    let account = TrieAccount {
+                        nonce: account.nonce,
+                        balance: account.balance,
+                        storage_root: account.storage_root,
+                        code_hash: account.code_hash,
+                    };
+                    (*hash, alloy_rlp::encode(account))
+                })
+                .collect::<Vec<_>>();
+            let more = verify_range_proof(state_root, *starting_hash, &leaves, &range.proof)
+                .map_err(|_| RequestError::BadResponse)?;
+
+            let complete = !more || accounts.last().is_some_and(|(hash, _)| hash >= limit_hash);
+            accounts.retain(|(hash, _)| hash <= limit_hash);
+            SnapResponse::Accounts { accounts, complete }
+        }
+        (
+            SnapRequest::StorageRanges { accounts, starting_hash },
+            SnapProtocolMessage::StorageRanges(ranges),
+        ) => {
+            if ranges.slots.is_empty() {
+                return Ok(None)
+            }
+            if ranges.slots.len() > accounts.len() {
+                return Err(RequestError::BadResponse)
+            }
+
+            let served = ranges.slots.len();
+            let mut complete = true;
+            let mut storages = Vec::with_capacity(served);
+            for (index, (slots, account)) in ranges.slots.into_iter().zip(accounts).enumerate() {
+                let slots = slots
+                    .into_iter()
+                    .map(|slot| {
+                        Ok(StorageEntry {
+                            key: slot.hash,
+                            value: U256::decode(&mut &slot.data[..])?,
+                        })
+                    })
+                    .collect::<alloy_rlp::Result<Vec<_>>>()
+                    .map_err(|_| RequestError::BadResponse)?;
+                let leaves = slots
+                    .iter()
+                    .map(|entry| (entry.key, alloy_rlp::encode(entry.value)))
+                    .collect::<Vec<_>>();
+
+                let first_key = if index == 0 { *starting_hash } else { B256::ZERO };
+                let proof = if index + 1 == served { &ranges.proof[..] } else { &[] };
+                let more = verify_range_proof(account.storage_root, first_key, &leaves, proof)
+                    .map_err(|_| RequestError::BadResponse)?;
+                complete = !more;
+                storages.push(slots);
+            }
+            SnapResponse::Storages { slots: storages, complete }
+        }
+        (SnapRequest::ByteCodes(hashes), SnapProtocolMessage::ByteCodes(codes)) => {
+            if codes.codes.is_empty() {
+                return Ok(None)
+            }
+
+            let codes =
+                codes.codes.into_iter().map(|code| (keccak256(&code), code)).collect::<Vec<_>>();
+            if codes.iter().any(|(hash, _)| !hashes.contains(hash)) {
+                return Err(RequestError::BadResponse)
+            }
+            SnapResponse::ByteCodes(codes)
+        }
+        (SnapRequest::TrieNodes(paths), SnapProtocolMessage::TrieNodes(nodes)) => {
+            if nodes.nodes.is_empty() {
+                return Ok(None)
+            }
+            if nodes.nodes.len() > paths.len() ||
+                nodes.nodes.iter().zip(paths).any(|(node, (_, hash))| keccak256(node) != *hash)
+            {
+                return Err(RequestError::BadResponse)
+            }
+
+            let nodes = nodes
+                .nodes
+                .iter()
+                .map(|node| TrieNode::decode(&mut &node[..]))
+                .collect::<alloy_rlp::Result<Vec<_>>>()
+                .map_err(|_| RequestError::BadResponse)?;
+            SnapResponse::TrieNodes(nodes)
+        }
+        _ => return Err(RequestError::BadResponse),
+    };
+
+    Ok(Some(response))
+}
+
+/// Moves the stages that build the state to the pivot block.
+fn finish<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    pivot_block: u64,
+) -> Result<(), StageError> {
+    let tx = provider.tx_ref();
+
+    // The plain state only contains the genesis state, which would shadow the downloaded state.
+    refresh_plain_state(tx)?;
+
+    for stage_id in [
+        StageId::Execution,
+        StageId::AccountHashing,
+        StageId::StorageHashing,
+        StageId::MerkleExecute,
+        StageId::IndexAccountHistory,
+        StageId::IndexStorageHistory,
+    ] {
+        provider.save_stage_checkpoint(stage_id, StageCheckpoint::new(pivot_block))?;
+    }
+
+    // There is no history before the pivot block.
+    for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+        provider.save_prune_checkpoint(
+            segment,
+            PruneCheckpoint {
+                block_number: Some(pivot_block),
+                tx_number: None,
+                prune_mode: PruneMode::Before(pivot_block + 1),
+            },
+        )?;
+    }
+
+    provider.save_stage_checkpoint_progress(StageId::SnapSync, Vec::new())?;
+    Ok(())
+}
+
+/// Updates the accounts and storage slots of the plain state to their values in the downloaded
+/// hashed state, and removes the ones that don't exist anymore.
+///
+/// The downloaded state can only be written to the plain state for the addresses and storage keys
+/// that are known, the rest of it is only available as hashed state.
+fn refresh_plain_state<TX: DbTx + DbTxMut>(tx: &TX) -> Result<(), StageError> {
+    let mut accounts = Vec::new();
+    for entry in tx.cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+        let (address, _) = entry?;
+        if let Some(account) = tx.get::<tables::HashedAccounts>(keccak256(address))? {
+            accounts.push((address, account));
+        }
+    }
+
+    let mut slots = Vec::new();
+    let mut hashed_storage_cursor = tx.cursor_dup_read::<tables::HashedStorages>()?;
+    for entry in tx.cursor_dup_read::<tables::PlainStorageState>()?.walk(None)? {
+        let (address, StorageEntry { key, .. }) = entry?;
+        let hashed_key = keccak256(key);
+        if let Some(entry) = hashed_storage_cursor
+            .seek_by_key_subkey(keccak256(address), hashed_key)?
+            .filter(|entry| entry.key == hashed_key)
+        {
+            slots.push((address, StorageEntry { key, value: entry.value }));
+        }
+    }
+
+    tx.clear::<tables::PlainAccountState>()?;
+    let mut account_cursor = tx.cursor_write::<tables::PlainAccountState>()?;
+    for (address, account) in accounts {
+        account_cursor.append(address, account)?;
+    }
+    tx.clear::<tables::PlainStorageState>()?;
+    let mut storage_cursor = tx.cursor_dup_write::<tables::PlainStorageState>()?;
+    for (address, entry) in slots {
+        storage_cursor.append_dup(address, entry)?;
+    }
+    Ok(())
+}
+
+/// Returns the sealed header of the pivot block.
+fn pivot_header<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    pivot_block: u64,
+) -> Result<SealedHeader, StageError> {
+    Ok(provider
+        .sealed_header(pivot_block)?
+        .ok_or(ProviderError::HeaderNotFound(pivot_block.into()))?)
+}
+
+/// Removes the storage and the storage trie of an account.
+fn wipe_storage<TX: DbTx + DbTxMut>(tx: &TX, hashed_address: B256) -> Result<(), StageError> {
+    let mut storage_cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+    if storage_cursor.seek_exact(hashed_address)?.is_some() {
+        storage_cursor.delete_current_duplicates()?;
+    }
+    let mut trie_cursor = tx.cursor_dup_write::<tables::StoragesTrie>()?;
+    if trie_cursor.seek_exact(hashed_address)?.is_some() {
+        trie_cursor.delete_current_duplicates()?;
+    }
+    Ok(())
+}
+
+/// Returns the hash following the given one, or `None` if it's the last hash.
+fn next_hash(hash: B256) -> Option<B256> {
+    U256::from_be_bytes(hash.0)
+        .checked_add(U256::from(1))
+        .map(|next| B256::from(next.to_be_bytes()))
+}
+
+/// Returns the hash of the path padded with the given nibble.
+fn pad_path(path: &Nibbles, nibble: u8) -> B256 {
+    let mut nibbles = path.to_vec();
+    nibbles.resize(B256::len_bytes() * 2, nibble);
+    B256::from_slice(&Nibbles::from_nibbles_unchecked(nibbles).pack())
+}
+
+/// Returns the range of account hashes below the path.
+fn prefix_range(path: &Nibbles) -> (B256, B256) {
+    (pad_path(path, 0), pad_path(path, 0xf))
+}
+
+/// Returns the RLP encoded child of a branch node at the nibble.
+fn branch_child(branch: &BranchNode, nibble: u8) -> Option<&[u8]> {
+    branch.state_mask.is_bit_set(nibble).then(|| {
+        let index = (branch.state_mask.get() & ((1 << nibble) - 1)).count_ones() as usize;
+        branch.stack[index].as_slice()
+    })
+}
+
+/// Returns the hash of a child node, or `None` if the child is embedded in its parent.
+fn child_hash(child: &[u8]) -> Option<B256> {
+    (child.len() == B256::len_bytes() + 1 &&
+        child[0] == alloy_rlp::EMPTY_STRING_CODE + B256::len_bytes() as u8)
+        .then(|| B256::from_slice(&child[1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use reth_db::{test_utils::TempDatabase, DatabaseEnv};
+    use reth_eth_wire_types::snap::{
+        AccountData, AccountRange, ByteCodes, StorageData, StorageRanges, TrieNodes,
+    };
+    use reth_network_p2p::download::DownloadClient;
+    use reth_network_peers::PeerId;
+    use reth_primitives::Address;
+    use reth_provider::{providers::is_snap_synced, ProviderFactory};
+    use reth_stages_api::StageExt;
+    use reth_testing_utils::generators::{self, random_header_range};
+    use reth_trie::StorageRoot;
+    use reth_trie_db::DatabaseStorageRoot;
+    use std::sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    };
+
+    /// Serves the state of a database, in small responses.
+    #[derive(Debug)]
+    struct TestSnapClient {
+        factory: ProviderFactory<Arc<TempDatabase<DatabaseEnv>>>,
+        /// Whether the next account range serves a wrong balance for its first account.
+        serve_invalid_account: AtomicBool,
+        /// The number of trie nodes requests.
+        trie_nodes_requests: AtomicUsize,
+        /// The number of reported bad messages.
+        bad_messages: AtomicUsize,
+    }
+
+    impl TestSnapClient {
+        fn respond<T: Send + Sync + 'static>(response: T) -> SnapFut<T> {
+            Box::pin(std::future::ready(Ok(WithPeerId::new(PeerId::ZERO, response))))
+        }
+    }
+
+    impl DownloadClient for TestSnapClient {
+        fn report_bad_message(&self, _peer_id: PeerId) {
+            self.bad_messages.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn num_connected_peers(&self) -> usize {
+            1
+        }
+    }
+
+    impl SnapClient for TestSnapClient {
+        fn get_account_range(&self, request: GetAccountRange) -> SnapFut<AccountRange> {
+            let provider = self.factory.provider().unwrap();
+            let tx = provider.tx_ref();
+            let mut accounts = Vec::new();
+            let mut cursor = tx.cursor_read::<tables::HashedAccounts>().unwrap();
+            for entry in cursor.walk(Some(request.starting_hash)).unwrap().take(5) {
+                let (hash, account) = entry.unwrap();
+                let mut account = SlimAccount {
+                    nonce: account.nonce,
+                    balance: account.balance,
+                    storage_root: StorageRoot::from_tx_hashed(tx, hash).root().unwrap(),
+                    code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+                };
+                if self.serve_invalid_account.swap(false, Ordering::Relaxed) {
+                    account.balance += U256::from(1);
+                }
+                accounts.push(AccountData { hash, body: alloy_rlp::encode(account).into() });
+                if hash >= request.limit_hash {
+                    break
+                }
+            }
+
+            let mut targets = HashMap::from([(request.starting_hash, Vec::new())]);
+            targets.extend(accounts.last().map(|account| (account.hash, Vec::new())));
+            let proof = Proof::from_tx(tx)
+                .with_targets(targets)
+                .multiproof()
+                .unwrap()
+                .account_subtree
+                .into_values()
+                .collect();
+            Self::respond(AccountRange { request_id: request.request_id, accounts, proof })
+        }
+
+        fn get_storage_ranges(&self, request: GetStorageRanges) -> SnapFut<StorageRanges> {
+            let provider = self.factory.provider().unwrap();
+            let mut cursor = provider.tx_ref().cursor_dup_read::<tables::HashedStorages>().unwrap();
+            let mut response =
+                StorageRanges { request_id: request.request_id, ..Default::default() };
+            let mut remaining = 3;
+            for (index, hashed_address) in request.account_hashes.into_iter().enumerate() {
+                if remaining == 0 {
+                    break
+                }
+                let starting_hash = if index == 0 { request.starting_hash } else { B256::ZERO };
+                let mut slots = Vec::new();
+                let mut truncated = false;
+                for entry in cursor.walk_dup(Some(hashed_address), Some(starting_hash)).unwrap() {
+                    if remaining == 0 {
+                        truncated = true;
+                        break
+                    }
+                    let (_, entry) = entry.unwrap();
+                    slots.push(StorageData {
+                        hash: entry.key,
+                        data: alloy_rlp::encode(entry.value).into(),
+                    });
+                    remaining -= 1;
+                }
+
+                // Incomplete ranges and ranges not starting at the first slot are proven.
+                if truncated || !starting_hash.is_zero() {
+                    let mut targets = vec![starting_hash];
+                    targets.extend(slots.last().map(|slot| slot.hash));
+                    response.proof = Proof::from_tx(provider.tx_ref())
+                        .with_targets(HashMap::from([(hashed_address, targets)]))
+                        .storage_multiproof(hashed_address)
+                        .unwrap()
+                        .subtree
+                        .into_values()
+                        .collect();
+                }
+                response.slots.push(slots);
+                if !response.proof.is_empty() {
+                    break
+                }
+            }
+            Self::respond(response)
+        }
+
+        fn get_byte_codes(&self, request: GetByteCodes) -> SnapFut<ByteCodes> {
+            let provider = self.factory.provider().unwrap();
+            let codes = request
+                .hashes
+                .into_iter()
+                .filter_map(|hash| provider.tx_ref().get::<tables::Bytecodes>(hash).unwrap())
+                .map(|code| code.original_bytes())
+                .collect();
+            Self::respond(ByteCodes { request_id: request.request_id, codes })
+        }
+
+        fn get_trie_nodes(&self, request: GetTrieNodes) -> SnapFut<TrieNodes> {
+            self.trie_nodes_requests.fetch_add(1, Ordering::Relaxed);
+            let provider = self.factory.provider().unwrap();
+            let mut nodes = Vec::new();
+            for paths in request.paths {
+                // Decode the compact encoded path.
+                let compact = &paths[0];
+                let mut nibbles = Nibbles::unpack(&compact[1..]);
+                if compact[0] & 0x10 != 0 {
+                    nibbles = Nibbles::from_nibbles_unchecked(
+                        std::iter::once(compact[0] & 0x0f)
+                            .chain(nibbles.iter().copied())
+                            .collect::<Vec<_>>(),
+                    );
+                }
+
+                let Some(node) = Proof::from_tx(provider.tx_ref())
+                    .with_targets(HashMap::from([(pad_path(&nibbles, 0), Vec::new())]))
+                    .multiproof()
+                    .unwrap()
+                    .account_subtree
+                    .remove(&nibbles)
+                else {
+                    break
+                };
+                nodes.push(node);
+            }
+            Self::respond(TrieNodes { request_id: request.request_id, nodes })
+        }
+    }
+
+    #[tokio::test]
+    async fn snap_sync_heals_state() {
+        let mut rng = generators::rng();
+
+        // Build the state served by peers, with storage and bytecodes.
+        let remote = TestStageDB::default();
+        let accounts = (0..30u64)
+            .map(|index| {
+                let bytecode = Bytecode::new_raw(Bytes::from(index.to_be_bytes().to_vec()));
+                let account = Account {
+                    nonce: index,
+                    balance: U256::from(index + 1),
+                    bytecode_hash: (index % 3 == 0).then(|| bytecode.hash_slow()),
+                };
+                let storage = (0..index % 7)
+                    .map(|slot| StorageEntry {
+                        key: B256::with_last_byte(slot as u8),
+                        value: U256::from(slot + 1),
+                    })
+                    .collect::<Vec<_>>();
+                (Address::random(), (account, storage, bytecode))
+            })
+            .collect::<Vec<_>>();
+        remote
+            .insert_accounts_and_storages(
+                accounts.iter().map(|(address, (account, storage, _))| {
+                    (*address, (*account, storage.clone()))
+                }),
+            )
+            .unwrap();
+        remote
+            .commit(|tx| {
+                for (_, (account, _, bytecode)) in &accounts {
+                    if let Some(hash) = account.bytecode_hash {
+                        tx.put::<tables::Bytecodes>(hash, bytecode.clone())?;
+                    }
+                }
+                Ok(())
+            })
+            .unwrap();
+        let provider = remote.factory.provider_rw().unwrap();
+        let (state_root, updates) =
+            StateRoot::from_tx(provider.tx_ref()).root_with_updates().unwrap();
+        provider.write_trie_updates(&updates).unwrap();
+        provider.commit().unwrap();
+
+        // The local database has the headers up to the pivot, and a stale state.
+        let local = TestStageDB::default();
+        let target = 10;
+        let mut headers = random_header_range(&mut rng, 0..target + 1, B256::ZERO);
+        let mut pivot = headers.pop().unwrap().unseal();
+        pivot.state_root = state_root;
+        headers.push(pivot.seal_slow());
+        local.insert_headers(headers.iter()).unwrap();
+        local
+            .insert_accounts_and_storages([(
+                Address::random(),
+                (Account::default(), vec![StorageEntry { key: B256::ZERO, value: U256::from(1) }]),
+            )])
+            .unwrap();
+
+        // The first account was downloaded for an older pivot, with a balance that changed since.
+        let (address, (account, storage, _)) =
+            accounts.iter().min_by_key(|(address, _)| keccak256(address)).unwrap();
+        let stale = Account { balance: account.balance + U256::from(1), ..*account };
+        local.insert_accounts_and_storages([(*address, (stale, storage.clone()))]).unwrap();
+        let provider = local.factory.provider_rw().unwrap();
+        provider
+            .save_stage_checkpoint_progress(
+                StageId::SnapSync,
+                next_hash(keccak256(address)).unwrap().to_vec(),
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        let client = Arc::new(TestSnapClient {
+            factory: remote.factory.clone(),
+            serve_invalid_account: AtomicBool::new(true),
+            trie_nodes_requests: AtomicUsize::new(0),
+            bad_messages: AtomicUsize::new(0),
+        });
+        let mut stage = SnapSyncStage::new(
+            client.clone(),
+            SnapSyncConfig { enabled: true, response_bytes: 1024 },
+        );
+
+        let mut output = None;
+        for _ in 0..1000 {
+            let input = ExecInput {
+                target: Some(target),
+                checkpoint: local
+                    .factory
+                    .provider()
+                    .unwrap()
+                    .get_stage_checkpoint(StageId::SnapSync)
+                    .unwrap(),
+            };
+            if let Err(err) =
+                StageExt::<Arc<TempDatabase<DatabaseEnv>>>::execute_ready(&mut stage, input).await
+            {
+                // The invalid account range is rejected, and requested again.
+                assert!(matches!(err, StageError::Recoverable(_)));
+                continue
+            }
+            let provider = local.factory.provider_rw().unwrap();
+            let result = stage.execute(&provider, input).unwrap();
+            provider.save_stage_checkpoint(StageId::SnapSync, result.checkpoint).unwrap();
+            provider.commit().unwrap();
+            if result.done {
+                output = Some(result);
+                break
+            }
+        }
+
+        let checkpoint = StageCheckpoint::new(target)
+            .with_snap_sync_stage_checkpoint(SnapSyncCheckpoint { pivot_block: target });
+        assert_eq!(output, Some(ExecOutput::done(checkpoint)));
+        assert!(client.trie_nodes_requests.load(Ordering::Relaxed) > 0);
+        assert_eq!(client.bad_messages.load(Ordering::Relaxed), 1);
+
+        assert_eq!(
+            local.table::<tables::HashedAccounts>().unwrap(),
+            remote.table::<tables::HashedAccounts>().unwrap()
+        );
+        assert_eq!(
+            local.table::<tables::HashedStorages>().unwrap(),
+            remote.table::<tables::HashedStorages>().unwrap()
+        );
+        assert_eq!(
+            local.table::<tables::Bytecodes>().unwrap(),
+            remote.table::<tables::Bytecodes>().unwrap()
+        );
+
+        // The plain state is updated for the known addresses only.
+        assert_eq!(local.table::<tables::PlainAccountState>().unwrap(), vec![(*address, *account)]);
+        assert_eq!(
+            local.table::<tables::PlainStorageState>().unwrap(),
+            storage.iter().map(|entry| (*address, *entry)).collect::<Vec<_>>()
+        );
+
+        let provider = local.factory.provider_rw().unwrap();
+        assert!(is_snap_synced(provider.tx_ref()).unwrap());
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::Execution).unwrap(),
+            Some(StageCheckpoint::new(target))
+        );
+
+        // Blocks before the pivot can't be unwound to.
+        let input = UnwindInput { checkpoint, unwind_to: target - 1, bad_block: None };
+        assert!(stage.unwind(&provider, input).is_err());
+        let input = UnwindInput { checkpoint, unwind_to: target, bad_block: None };
+        assert_eq!(stage.unwind(&provider, input).unwrap(), UnwindOutput { checkpoint });
+
+        // The stage is done once snap synced.
+        let input = ExecInput { target: Some(target + 1), checkpoint: Some(checkpoint) };
+        assert_eq!(
+            stage.execute(&provider, input).unwrap(),
+            ExecOutput::done(checkpoint.with_block_number(target + 1))
+        );
+    }
+
+    #[test]
+    fn prefix_ranges() {
+        let path = Nibbles::from_nibbles([0x1, 0x2, 0x3]);
+        let (start, end) = prefix_range(&path);
+        assert_eq!(&start[..2], &[0x12, 0x30]);
+        assert!(start[2..].iter().all(|byte| *byte == 0));
+        assert_eq!(&end[..2], &[0x12, 0x3f]);
+        assert!(end[2..].iter().all(|byte| *byte == 0xff));
+        assert_eq!(
+            next_hash(start),
+            Some(B256::from(U256::from_be_bytes(start.0) + U256::from(1)))
+        );
+        assert_eq!(next_hash(B256::repeat_byte(0xff)), None);
+    }
+}
@@ -0,0 +1,181 @@
+use reth_db_api::database::Database;
+use reth_provider::DatabaseProviderRW;
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use tracing::*;
+
+/// The verkle trie stage computes the state root with the experimental verkle trie of
+/// [EIP-6800](https://eips.ethereum.org/EIPS/eip-6800) instead of the Merkle Patricia Trie, and
+/// replaces the [`MerkleStage`][crate::stages::MerkleStage] when the `verkle` trie backend is
+/// configured.
+///
+/// The leaves of the trie are derived from the account and storage changesets and the plain
+/// state, so unlike the merkle stage it doesn't read the hashed state. The trie is built from the
+/// state before the first executed range, and then updated incrementally. The values of the
+/// leaves before each block that changed them are kept for
+/// [`MINIMUM_PRUNING_DISTANCE`](reth_prune_types::MINIMUM_PRUNING_DISTANCE) blocks, which bounds
+/// how far the trie can be unwound.
+///
+/// Like the merkle stage, it is split in an execution and an unwind stage, and the unwind stage
+/// should be added to the pipeline before the execution stage.
+///
+/// Without the `verkle` feature, both stages fail when they have work to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerkleStage {
+    /// The execution portion of the verkle trie stage.
+    Execution,
+    /// The unwind portion of the verkle trie stage.
+    Unwind,
+}
+
+impl<DB: Database> Stage<DB> for VerkleStage {
+    fn id(&self) -> StageId {
+        match self {
+            Self::Execution => StageId::MerkleExecute,
+            Self::Unwind => StageId::MerkleUnwind,
+        }
+    }
+
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if *self == Self::Unwind || input.target_reached() {
+            info!(target: "sync::stages::verkle", ?self, "Stage is always skipped");
+            return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+        }
+        imp::execute(provider, input)
+    }
+
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        if *self == Self::Execution {
+            info!(target: "sync::stages::verkle", ?self, "Stage is always skipped");
+            return Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+        }
+        imp::unwind(provider, input)
+    }
+}
+
+#[cfg(feature = "verkle")]
+mod imp {
+    use crate::stages::validate_state_root;
+    use reth_db::tables;
+    use reth_db_api::{
+        database::Database,
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_provider::{DatabaseProviderRW, HeaderProvider, ProviderError};
+    use reth_prune_types::MINIMUM_PRUNING_DISTANCE;
+    use reth_stages_api::{
+        EntitiesCheckpoint, ExecInput, ExecOutput, StageCheckpoint, StageError, UnwindInput,
+        UnwindOutput,
+    };
+    use reth_trie_verkle::{
+        db::{
+            has_verkle_trie, prune_verkle_changesets, take_verkle_changesets, verkle_trie_reader,
+            write_verkle_changesets, write_verkle_updates,
+        },
+        state::{verkle_leaf_changes, verkle_state_leaves},
+        trie::update_verkle_trie,
+    };
+    use tracing::*;
+
+    pub(super) fn execute<DB: Database>(
+        provider: &DatabaseProviderRW<DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        let tx = provider.tx_ref();
+        let range = input.next_block_range();
+        let to_block = *range.end();
+        let target_block = provider
+            .header_by_number(to_block)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(to_block.into()))?;
+
+        if !has_verkle_trie(tx)? {
+            debug!(target: "sync::stages::verkle::exec", ?range, "Building verkle trie of the state before the range");
+            let leaves = verkle_state_leaves(tx, range.clone())?;
+            let (_, updates) = update_verkle_trie(&mut verkle_trie_reader(tx)?, leaves)?;
+            write_verkle_updates(tx, &updates)?;
+        }
+
+        debug!(target: "sync::stages::verkle::exec", ?range, "Updating verkle trie");
+        let changes = verkle_leaf_changes(tx, range)?;
+        let (root, updates) = update_verkle_trie(&mut verkle_trie_reader(tx)?, changes.leaves)?;
+        validate_state_root(root, target_block.seal_slow(), to_block)?;
+
+        write_verkle_updates(tx, &updates)?;
+        write_verkle_changesets(tx, changes.changesets)?;
+        if let Some(prune_to) = to_block.checked_sub(MINIMUM_PRUNING_DISTANCE) {
+            prune_verkle_changesets(tx, prune_to)?;
+        }
+
+        let leaves = tx.entries::<tables::VerkleLeaves>()? as u64;
+        Ok(ExecOutput {
+            checkpoint: StageCheckpoint::new(to_block).with_entities_stage_checkpoint(
+                EntitiesCheckpoint { processed: leaves, total: leaves },
+            ),
+            done: true,
+        })
+    }
+
+    pub(super) fn unwind<DB: Database>(
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let tx = provider.tx_ref();
+        if input.unwind_to == 0 {
+            // The trie is rebuilt from the genesis state on the next execution.
+            tx.clear::<tables::VerkleNodes>()?;
+            tx.clear::<tables::VerkleLeaves>()?;
+            tx.clear::<tables::VerkleLeafChangeSets>()?;
+            return Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+        }
+
+        let range = input.unwind_block_range();
+        if range.is_empty() {
+            info!(target: "sync::stages::verkle::unwind", "Nothing to unwind");
+            return Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+        }
+
+        let leaves = take_verkle_changesets(tx, range)?;
+        let (root, updates) = update_verkle_trie(&mut verkle_trie_reader(tx)?, leaves)?;
+        let target = provider
+            .header_by_number(input.unwind_to)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(input.unwind_to.into()))?;
+        validate_state_root(root, target.seal_slow(), input.unwind_to)?;
+        write_verkle_updates(tx, &updates)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+#[cfg(not(feature = "verkle"))]
+mod imp {
+    use reth_db_api::database::Database;
+    use reth_provider::DatabaseProviderRW;
+    use reth_stages_api::{ExecInput, ExecOutput, StageError, UnwindInput, UnwindOutput};
+
+    fn disabled() -> StageError {
+        StageError::Fatal("the verkle trie backend requires the `verkle` feature".into())
+    }
+
+    pub(super) fn execute<DB: Database>(
+        _provider: &DatabaseProviderRW<DB>,
+        _input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        Err(disabled())
+    }
+
+    pub(super) fn unwind<DB: Database>(
+        _provider: &DatabaseProviderRW<DB>,
+        _input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        Err(disabled())
+    }
+}
@@ -135,6 +135,15 @@ pub struct IndexHistoryCheckpoint {
     pub progress: EntitiesCheckpoint,
 }
 
+/// Saves the block at which the state was downloaded by the snap sync stage.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Compact)]
+#[cfg_attr(test, derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct SnapSyncCheckpoint {
+    /// The block of the downloaded state. No state is available for earlier blocks.
+    pub pivot_block: BlockNumber,
+}
+
 /// Saves the progress of abstract stage iterating over or downloading entities.
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Compact)]
 #[cfg_attr(test, derive(arbitrary::Arbitrary))]
@@ -248,6 +257,7 @@ impl StageCheckpoint {
                 progress: entities,
                 ..
             }) => Some(entities),
+            StageUnitCheckpoint::SnapSync(_) => None,
         }
     }
 }
@@ -271,6 +281,8 @@ pub enum StageUnitCheckpoint {
     Headers(HeadersCheckpoint),
     /// Saves the progress of Index History stage.
     IndexHistory(IndexHistoryCheckpoint),
+    /// Saves the pivot block of SnapSync stage.
+    SnapSync(SnapSyncCheckpoint),
 }
 
 impl StageUnitCheckpoint {
@@ -379,6 +391,15 @@ stage_unit_checkpoints!(
         index_history_stage_checkpoint,
         /// Sets the stage checkpoint to index history.
         with_index_history_stage_checkpoint
+    ),
+    (
+        6,
+        SnapSync,
+        SnapSyncCheckpoint,
+        /// Returns the snap sync stage checkpoint, if any.
+        snap_sync_stage_checkpoint,
+        /// Sets the stage checkpoint to snap sync.
+        with_snap_sync_stage_checkpoint
     )
 );
 
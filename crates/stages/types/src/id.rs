@@ -10,6 +10,11 @@ pub enum StageId {
     StaticFile,
     Headers,
     Bodies,
+    /// Downloads the state at the pipeline target with the `snap/1` protocol instead of executing
+    /// the historical blocks.
+    ///
+    /// The stage is optional and therefore not part of [`StageId::ALL`].
+    SnapSync,
     SenderRecovery,
     Execution,
     PruneSenderRecovery,
@@ -65,6 +70,7 @@ impl StageId {
             Self::StaticFile => "StaticFile",
             Self::Headers => "Headers",
             Self::Bodies => "Bodies",
+            Self::SnapSync => "SnapSync",
             Self::SenderRecovery => "SenderRecovery",
             Self::Execution => "Execution",
             Self::PruneSenderRecovery => "PruneSenderRecovery",
@@ -111,6 +117,7 @@ mod tests {
     fn stage_id_as_string() {
         assert_eq!(StageId::Headers.to_string(), "Headers");
         assert_eq!(StageId::Bodies.to_string(), "Bodies");
+        assert_eq!(StageId::SnapSync.to_string(), "SnapSync");
         assert_eq!(StageId::SenderRecovery.to_string(), "SenderRecovery");
         assert_eq!(StageId::Execution.to_string(), "Execution");
         assert_eq!(StageId::MerkleUnwind.to_string(), "MerkleUnwind");
@@ -0,0 +1,140 @@
+use crate::{StageCheckpoint, StageId};
+use alloy_primitives::BlockNumber;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, PoisonError, RwLock},
+    time::Duration,
+};
+
+/// The default time over which the throughput of a stage is measured.
+pub const DEFAULT_THROUGHPUT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Configures how the pipeline measures the throughput of its stages, and the thresholds below
+/// which a stage is reported as slow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageProgressThresholds {
+    /// The minimum time a stage has to spend executing before its throughput and ETA are
+    /// reported.
+    ///
+    /// Short runs, e.g. when following the tip of the chain, don't give meaningful estimates.
+    pub window: Duration,
+    /// The minimum number of entities per second, keyed by the stage name.
+    ///
+    /// Stage names are matched case-insensitively and ignoring underscores, so both
+    /// `sender_recovery` and `SenderRecovery` refer to [`StageId::SenderRecovery`].
+    pub min_throughput: BTreeMap<String, u64>,
+}
+
+impl Default for StageProgressThresholds {
+    fn default() -> Self {
+        Self { window: DEFAULT_THROUGHPUT_WINDOW, min_throughput: BTreeMap::new() }
+    }
+}
+
+impl StageProgressThresholds {
+    /// Returns the minimum number of entities per second configured for the given stage.
+    pub fn min_throughput(&self, stage_id: StageId) -> Option<u64> {
+        let name = normalize_stage_name(stage_id.as_str());
+        self.min_throughput
+            .iter()
+            .find_map(|(stage, min)| (normalize_stage_name(stage) == name).then_some(*min))
+    }
+}
+
+fn normalize_stage_name(name: &str) -> String {
+    name.chars().filter(|c| *c != '_').flat_map(char::to_lowercase).collect()
+}
+
+/// The throughput of a stage, measured over at least [`StageProgressThresholds::window`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StageThroughput {
+    /// The number of entities processed per second.
+    pub entities_per_second: u64,
+    /// The estimated time until the stage reaches its target, if known.
+    pub eta: Option<Duration>,
+    /// The configured minimum throughput, if the stage fell below it.
+    pub below_threshold: Option<u64>,
+}
+
+impl StageThroughput {
+    /// Returns `true` if the stage processed fewer entities per second than configured.
+    pub const fn is_below_threshold(&self) -> bool {
+        self.below_threshold.is_some()
+    }
+}
+
+/// The progress of a stage while the pipeline is syncing, see [`SyncProgress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StageSyncProgress {
+    /// The stage.
+    pub stage_id: StageId,
+    /// The last checkpoint of the stage.
+    pub checkpoint: StageCheckpoint,
+    /// The block the stage is syncing to, if known.
+    pub target: Option<BlockNumber>,
+    /// The estimated time until the stage reaches its target, if known.
+    pub eta: Option<Duration>,
+}
+
+/// The progress of the stages of a syncing pipeline, shared with e.g. the RPC.
+///
+/// Clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct SyncProgress {
+    stages: Arc<RwLock<Vec<StageSyncProgress>>>,
+}
+
+impl SyncProgress {
+    /// Records the checkpoint of a stage, and the block it's syncing to if known.
+    pub fn update_checkpoint(
+        &self,
+        stage_id: StageId,
+        checkpoint: StageCheckpoint,
+        target: Option<BlockNumber>,
+    ) {
+        let mut stages = self.stages.write().unwrap_or_else(PoisonError::into_inner);
+        match stages.iter_mut().find(|stage| stage.stage_id == stage_id) {
+            Some(stage) => {
+                stage.checkpoint = checkpoint;
+                if target.is_some() {
+                    stage.target = target;
+                }
+            }
+            None => stages.push(StageSyncProgress { stage_id, checkpoint, target, eta: None }),
+        }
+    }
+
+    /// Records the estimated time until the stage reaches its target.
+    pub fn update_eta(&self, stage_id: StageId, eta: Option<Duration>) {
+        let mut stages = self.stages.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(stage) = stages.iter_mut().find(|stage| stage.stage_id == stage_id) {
+            stage.eta = eta;
+        }
+    }
+
+    /// Returns the progress of all stages that reported a checkpoint, in the order they first
+    /// reported one.
+    pub fn stages(&self) -> Vec<StageSyncProgress> {
+        self.stages.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_throughput_by_stage_name() {
+        let thresholds = StageProgressThresholds {
+            min_throughput: BTreeMap::from([
+                ("sender_recovery".to_string(), 1000),
+                ("Execution".to_string(), 50),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(thresholds.min_throughput(StageId::SenderRecovery), Some(1000));
+        assert_eq!(thresholds.min_throughput(StageId::Execution), Some(50));
+        assert_eq!(thresholds.min_throughput(StageId::Headers), None);
+    }
+}
@@ -1,3 +1,6 @@
+use crate::StaticFileSegment;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, str::FromStr};
 use strum::AsRefStr;
 
 /// Static File compression types.
@@ -18,3 +21,138 @@ pub enum Compression {
     #[default]
     Uncompressed,
 }
+
+/// Compression codec applied to the rows of a static file.
+///
+/// Parsed from and displayed as `none`, `lz4`, `zstd` or `zstd:<level>`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CompressionCodec {
+    /// No compression.
+    None,
+    /// LZ4 compression, fast but with a lower compression ratio.
+    Lz4,
+    /// Zstandard compression with the given level.
+    Zstd(i32),
+}
+
+impl CompressionCodec {
+    /// Zstandard compression level used if none is specified.
+    pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+    /// Maximum Zstandard compression level.
+    pub const MAX_ZSTD_LEVEL: i32 = 22;
+}
+
+impl fmt::Display for CompressionCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => f.write_str("none"),
+            Self::Lz4 => f.write_str("lz4"),
+            Self::Zstd(level) => write!(f, "zstd:{level}"),
+        }
+    }
+}
+
+/// Error returned when parsing a [`CompressionCodec`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCompressionCodecError(String);
+
+impl fmt::Display for ParseCompressionCodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid compression codec `{}`, expected `none`, `lz4`, `zstd` or `zstd:<level>` with a level from 1 to {}",
+            self.0,
+            CompressionCodec::MAX_ZSTD_LEVEL
+        )
+    }
+}
+
+impl std::error::Error for ParseCompressionCodecError {}
+
+impl FromStr for CompressionCodec {
+    type Err = ParseCompressionCodecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "none" => Ok(Self::None),
+            None if s == "lz4" => Ok(Self::Lz4),
+            None if s == "zstd" => Ok(Self::Zstd(Self::DEFAULT_ZSTD_LEVEL)),
+            Some(("zstd", level)) => level
+                .parse()
+                .ok()
+                .filter(|level| (1..=Self::MAX_ZSTD_LEVEL).contains(level))
+                .map(Self::Zstd)
+                .ok_or_else(|| ParseCompressionCodecError(s.to_string())),
+            _ => Err(ParseCompressionCodecError(s.to_string())),
+        }
+    }
+}
+
+impl Serialize for CompressionCodec {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for CompressionCodec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Compression codec of the static files of each [`StaticFileSegment`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StaticFileCompression {
+    /// Compression codec of the headers static files.
+    pub headers: CompressionCodec,
+    /// Compression codec of the transactions static files.
+    pub transactions: CompressionCodec,
+    /// Compression codec of the receipts static files.
+    pub receipts: CompressionCodec,
+}
+
+impl StaticFileCompression {
+    /// Returns the compression codec of the given segment.
+    pub const fn codec(&self, segment: StaticFileSegment) -> CompressionCodec {
+        match segment {
+            StaticFileSegment::Headers => self.headers,
+            StaticFileSegment::Transactions => self.transactions,
+            StaticFileSegment::Receipts => self.receipts,
+        }
+    }
+}
+
+impl Default for StaticFileCompression {
+    /// Transactions and receipts are not compressed by default, because their encoding already
+    /// compresses them with zstd dictionaries.
+    fn default() -> Self {
+        Self {
+            headers: CompressionCodec::Lz4,
+            transactions: CompressionCodec::None,
+            receipts: CompressionCodec::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_codec() {
+        assert_eq!("none".parse(), Ok(CompressionCodec::None));
+        assert_eq!("lz4".parse(), Ok(CompressionCodec::Lz4));
+        assert_eq!(
+            "zstd".parse(),
+            Ok(CompressionCodec::Zstd(CompressionCodec::DEFAULT_ZSTD_LEVEL))
+        );
+        assert_eq!("zstd:19".parse(), Ok(CompressionCodec::Zstd(19)));
+        assert_eq!(CompressionCodec::Zstd(19).to_string(), "zstd:19");
+
+        for invalid in ["", "lz4:1", "zstd:", "zstd:0", "zstd:23", "gzip"] {
+            assert!(invalid.parse::<CompressionCodec>().is_err(), "{invalid}");
+        }
+    }
+}
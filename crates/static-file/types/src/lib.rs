@@ -13,7 +13,9 @@ mod filters;
 mod segment;
 
 use alloy_primitives::BlockNumber;
-pub use compression::Compression;
+pub use compression::{
+    Compression, CompressionCodec, ParseCompressionCodecError, StaticFileCompression,
+};
 pub use filters::{Filters, InclusionFilter, PerfectHashingFunction};
 pub use segment::{SegmentConfig, SegmentHeader, SegmentRangeInclusive, StaticFileSegment};
 
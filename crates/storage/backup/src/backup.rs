@@ -0,0 +1,689 @@
+use crate::{
+    signature::{recover_manifest_signer, sign_manifest, signer_address},
+    BackupError, BackupFile, BackupFileKind, BackupManifest,
+};
+use alloy_primitives::{Address, B256};
+use reth_libmdbx::Environment;
+use reth_object_store::ObjectStore;
+use secp256k1::SecretKey;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashSet,
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, info};
+
+/// Default size of the chunks files are split into.
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Key of the object that holds the id of the latest backup.
+const LATEST_KEY: &str = "LATEST";
+
+/// Name of the MDBX data file.
+const DATABASE_FILE: &str = "mdbx.dat";
+
+/// Name of the file that marks an unfinished restore and holds the id of the restored backup.
+const RESTORE_MARKER: &str = "restore.pending";
+
+/// Lock files that are never backed up.
+const LOCK_FILES: [&str; 2] = ["mdbx.lck", "lock"];
+
+/// Directories that are backed up.
+#[derive(Debug, Clone)]
+pub struct BackupPaths {
+    /// The database directory.
+    pub db: PathBuf,
+    /// The static files directory.
+    pub static_files: PathBuf,
+}
+
+impl BackupPaths {
+    /// Returns the directory of the given kind.
+    pub fn dir(&self, kind: BackupFileKind) -> &Path {
+        match kind {
+            BackupFileKind::Database => &self.db,
+            BackupFileKind::StaticFiles => &self.static_files,
+        }
+    }
+
+    /// Returns `true` if there's no database to restore into yet, or a previous restore was
+    /// interrupted.
+    pub fn needs_restore(&self) -> bool {
+        !self.db.join(DATABASE_FILE).exists() || self.db.join(RESTORE_MARKER).exists()
+    }
+}
+
+/// Statistics of a created backup.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BackupStats {
+    /// Number of chunks uploaded to the storage.
+    pub uploaded_chunks: usize,
+    /// Total size of the uploaded chunks.
+    pub uploaded_bytes: u64,
+    /// Number of chunks that were already in the storage.
+    pub reused_chunks: usize,
+}
+
+/// Creates and restores incremental backups of the database and static files.
+///
+/// Files are split into chunks of a fixed size that are stored under their SHA-256 hash, so only
+/// chunks that changed since a previous backup are uploaded. The database is copied from a
+/// read-only transaction, which makes it possible to back up a database that is in use.
+///
+/// Static files are backed up after the database, so they're never behind it. Their configuration
+/// files are copied before their data, and data written after the configuration is truncated when
+/// the restored static files are opened.
+///
+/// Manifests can be signed, so backups published by a third party can be verified before they're
+/// restored. Chunks are always verified against their hash.
+#[derive(Debug)]
+pub struct Backup<S> {
+    storage: S,
+    chunk_size: usize,
+    /// Key that signs the manifests of created backups.
+    signer: Option<SecretKey>,
+    /// Address that manifests must be signed by.
+    trusted_signer: Option<Address>,
+}
+
+impl<S: ObjectStore> Backup<S> {
+    /// Creates a new [`Backup`] that keeps backups in the given storage.
+    pub const fn new(storage: S) -> Self {
+        Self { storage, chunk_size: DEFAULT_CHUNK_SIZE, signer: None, trusted_signer: None }
+    }
+
+    /// Sets the size of the chunks files are split into.
+    ///
+    /// Chunks of backups with a different chunk size can't be reused.
+    ///
+    /// # Panics
+    ///
+    /// If the chunk size is zero.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must not be zero");
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Signs the manifests of created backups with the given key.
+    pub const fn with_signer(mut self, signer: SecretKey) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Only accepts manifests that are signed by the given address.
+    ///
+    /// Reading an unsigned manifest, or one signed by another key, fails.
+    pub const fn with_trusted_signer(mut self, signer: Address) -> Self {
+        self.trusted_signer = Some(signer);
+        self
+    }
+
+    /// Returns the storage backups are kept in.
+    pub const fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Returns the manifest of the latest backup, if any.
+    pub fn latest_manifest(&self) -> Result<Option<BackupManifest>, BackupError> {
+        let Some(latest) = self.storage.get(LATEST_KEY)? else { return Ok(None) };
+        let id = String::from_utf8(latest)
+            .ok()
+            .and_then(|id| id.trim().parse().ok())
+            .ok_or_else(|| BackupError::InvalidBackup("invalid latest backup id".to_string()))?;
+        self.manifest(id)?.map(Some).ok_or(BackupError::BackupNotFound(id))
+    }
+
+    /// Returns the manifest of the backup with the given id, if any.
+    ///
+    /// If a trusted signer is set, the signature of the manifest is verified.
+    pub fn manifest(&self, id: u64) -> Result<Option<BackupManifest>, BackupError> {
+        let Some(manifest) = self.storage.get(&manifest_key(id))? else { return Ok(None) };
+
+        if let Some(trusted_signer) = self.trusted_signer {
+            let signature =
+                self.storage.get(&signature_key(id))?.ok_or(BackupError::MissingSignature(id))?;
+            let signer = recover_manifest_signer(&manifest, &signature)
+                .ok_or(BackupError::InvalidSignature(id))?;
+            if signer != trusted_signer {
+                return Err(BackupError::UntrustedSigner { id, signer })
+            }
+        }
+
+        Ok(Some(serde_json::from_slice(&manifest)?))
+    }
+
+    /// Creates a backup of the database and static files, and makes it the latest one.
+    ///
+    /// Only chunks that aren't in the storage yet are uploaded.
+    pub fn create(
+        &self,
+        env: &Environment,
+        paths: &BackupPaths,
+    ) -> Result<(BackupManifest, BackupStats), BackupError> {
+        let previous = self.latest_manifest()?;
+        let id = now_millis().max(previous.as_ref().map_or(0, |previous| previous.id + 1));
+        let mut uploader = ChunkUploader {
+            storage: &self.storage,
+            known: previous
+                .iter()
+                .flat_map(|previous| &previous.files)
+                .flat_map(|file| file.chunks.iter().copied())
+                .collect(),
+            stats: BackupStats::default(),
+        };
+
+        let mut files = vec![self.backup_database(env, &mut uploader)?];
+
+        for (kind, path) in [
+            (BackupFileKind::Database, &paths.db),
+            (BackupFileKind::StaticFiles, &paths.static_files),
+        ] {
+            for name in backed_up_files(kind, path)? {
+                let path = path.join(&name);
+                let metadata = reth_fs_util::metadata(&path)?;
+                let modified = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                    .map(|modified| modified.as_nanos() as u64);
+
+                // Unchanged files don't need to be read again
+                if let Some(file) = previous
+                    .as_ref()
+                    .filter(|previous| previous.chunk_size == self.chunk_size)
+                    .and_then(|previous| previous.file(kind, &name))
+                    .filter(|file| {
+                        file.size == metadata.len() &&
+                            modified.is_some() &&
+                            file.modified == modified
+                    })
+                {
+                    debug!(target: "backup", ?path, "Reusing unchanged file");
+                    uploader.stats.reused_chunks += file.chunks.len();
+                    files.push(file.clone());
+                    continue
+                }
+
+                debug!(target: "backup", ?path, "Backing up file");
+                let file =
+                    File::open(&path).map_err(|err| reth_fs_util::FsPathError::open(err, &path))?;
+                // The file may grow while it's read, so only the data present up to now is copied
+                let (size, chunks) =
+                    self.upload_chunks(&mut file.take(metadata.len()), &mut uploader)?;
+                files.push(BackupFile { kind, name, size, modified, chunks });
+            }
+        }
+
+        let manifest = BackupManifest { id, chunk_size: self.chunk_size, files };
+        let encoded = serde_json::to_vec_pretty(&manifest)?;
+        self.storage.put(&manifest_key(id), &encoded)?;
+        if let Some(signer) = &self.signer {
+            debug!(target: "backup", signer = %signer_address(signer), "Signing manifest");
+            self.storage.put(&signature_key(id), sign_manifest(signer, &encoded).as_bytes())?;
+        }
+        self.storage.put(LATEST_KEY, id.to_string().as_bytes())?;
+
+        let stats = uploader.stats;
+        info!(
+            target: "backup",
+            id,
+            size = manifest.size(),
+            uploaded_chunks = stats.uploaded_chunks,
+            uploaded_bytes = stats.uploaded_bytes,
+            reused_chunks = stats.reused_chunks,
+            "Created backup"
+        );
+
+        Ok((manifest, stats))
+    }
+
+    /// Restores the backup with the given id, or the latest one if `None`.
+    ///
+    /// The target directories must not contain a database or static files yet, unless a previous
+    /// restore was interrupted. In that case, the restore of the same backup is resumed: restored
+    /// chunks are verified and kept, and only the remaining ones are fetched.
+    pub fn restore(
+        &self,
+        id: Option<u64>,
+        paths: &BackupPaths,
+    ) -> Result<BackupManifest, BackupError> {
+        let marker = paths.db.join(RESTORE_MARKER);
+        let pending = if marker.exists() {
+            let pending = reth_fs_util::read_to_string(&marker)?
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| BackupError::InvalidBackup("invalid pending restore".to_string()))?;
+            if id.is_some_and(|id| id != pending) {
+                return Err(BackupError::RestoreInProgress(pending))
+            }
+            Some(pending)
+        } else {
+            None
+        };
+
+        let manifest = match pending.or(id) {
+            Some(id) => self.manifest(id)?.ok_or(BackupError::BackupNotFound(id))?,
+            None => self.latest_manifest()?.ok_or(BackupError::NoBackup)?,
+        };
+        if pending.is_some() {
+            info!(target: "backup", id = manifest.id, "Resuming interrupted restore");
+        } else {
+            if paths.db.join(DATABASE_FILE).exists() {
+                return Err(BackupError::TargetExists(paths.db.clone()))
+            }
+            if paths.static_files.exists() &&
+                reth_fs_util::read_dir(&paths.static_files)?.next().is_some()
+            {
+                return Err(BackupError::TargetExists(paths.static_files.clone()))
+            }
+            reth_fs_util::create_dir_all(&paths.db)?;
+            reth_fs_util::write(&marker, manifest.id.to_string())?;
+        }
+
+        for backup_file in &manifest.files {
+            let dir = paths.dir(backup_file.kind);
+            reth_fs_util::create_dir_all(dir)?;
+            let path = dir.join(&backup_file.name);
+
+            let mut file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(&path)
+                .map_err(|err| reth_fs_util::FsPathError::open(err, &path))?;
+            let (restored_chunks, mut size) =
+                verify_restored_chunks(&mut file, manifest.chunk_size, &backup_file.chunks)?;
+            // Anything after the last intact chunk is written again
+            file.set_len(size)?;
+            file.seek(SeekFrom::Start(size))?;
+            if restored_chunks == backup_file.chunks.len() {
+                debug!(target: "backup", ?path, "File already restored");
+            } else {
+                debug!(target: "backup", ?path, restored_chunks, "Restoring file");
+            }
+
+            // Runs of identical chunks, e.g. unused database pages, are only fetched once
+            let mut previous: Option<(B256, Vec<u8>)> = None;
+            for hash in &backup_file.chunks[restored_chunks..] {
+                let chunk = match previous {
+                    Some((previous_hash, ref chunk)) if previous_hash == *hash => chunk,
+                    _ => {
+                        let chunk = self
+                            .storage
+                            .get(&chunk_key(*hash))?
+                            .ok_or(BackupError::MissingChunk(*hash))?;
+                        if chunk_hash(&chunk) != *hash {
+                            return Err(BackupError::CorruptedChunk(*hash))
+                        }
+                        &previous.insert((*hash, chunk)).1
+                    }
+                };
+                file.write_all(chunk)?;
+                size += chunk.len() as u64;
+            }
+
+            if size != backup_file.size {
+                return Err(BackupError::InvalidBackup(format!(
+                    "restored {size} bytes of {} instead of {}",
+                    backup_file.name, backup_file.size
+                )))
+            }
+            file.sync_all()?;
+        }
+
+        reth_fs_util::remove_file(&marker)?;
+        info!(target: "backup", id = manifest.id, size = manifest.size(), "Restored backup");
+
+        Ok(manifest)
+    }
+
+    /// Backs up a consistent snapshot of the database, streamed from a copy of the environment.
+    #[cfg(unix)]
+    fn backup_database(
+        &self,
+        env: &Environment,
+        uploader: &mut ChunkUploader<'_, S>,
+    ) -> Result<BackupFile, BackupError> {
+        use std::os::{fd::AsRawFd, unix::net::UnixStream};
+
+        debug!(target: "backup", "Backing up database");
+        let (mut reader, writer) = UnixStream::pair()?;
+        let (copied, uploaded) = std::thread::scope(|scope| {
+            let copy = scope.spawn(move || {
+                let copied = env.copy_to_fd(writer.as_raw_fd(), false);
+                // Closing the socket signals the end of the copy to the reader
+                drop(writer);
+                copied
+            });
+            let uploaded = self.upload_chunks(&mut reader, uploader);
+            // Closing the socket unblocks the copy if the upload failed
+            drop(reader);
+            (copy.join(), uploaded)
+        });
+        copied.unwrap_or_else(|payload| std::panic::resume_unwind(payload))?;
+        let (size, chunks) = uploaded?;
+
+        Ok(BackupFile {
+            kind: BackupFileKind::Database,
+            name: DATABASE_FILE.to_string(),
+            size,
+            modified: None,
+            chunks,
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn backup_database(
+        &self,
+        _env: &Environment,
+        _uploader: &mut ChunkUploader<'_, S>,
+    ) -> Result<BackupFile, BackupError> {
+        Err(BackupError::Unsupported)
+    }
+
+    /// Splits the data into chunks and uploads them, returning the size of the data and the
+    /// hashes of the chunks.
+    fn upload_chunks(
+        &self,
+        reader: &mut impl Read,
+        uploader: &mut ChunkUploader<'_, S>,
+    ) -> Result<(u64, Vec<B256>), BackupError> {
+        let mut size = 0;
+        let mut chunks = Vec::new();
+        let mut chunk = Vec::with_capacity(self.chunk_size);
+        loop {
+            chunk.clear();
+            reader.take(self.chunk_size as u64).read_to_end(&mut chunk)?;
+            if chunk.is_empty() {
+                break
+            }
+            size += chunk.len() as u64;
+            chunks.push(uploader.upload(&chunk)?);
+        }
+        Ok((size, chunks))
+    }
+}
+
+/// Uploads chunks that aren't in the storage yet.
+struct ChunkUploader<'a, S> {
+    storage: &'a S,
+    /// Chunks that are known to be in the storage.
+    known: HashSet<B256>,
+    stats: BackupStats,
+}
+
+impl<S: ObjectStore> ChunkUploader<'_, S> {
+    /// Uploads the chunk if it's not in the storage yet, and returns its hash.
+    fn upload(&mut self, chunk: &[u8]) -> Result<B256, BackupError> {
+        let hash = chunk_hash(chunk);
+        let key = chunk_key(hash);
+        if self.known.insert(hash) && !self.storage.contains(&key)? {
+            self.storage.put(&key, chunk)?;
+            self.stats.uploaded_chunks += 1;
+            self.stats.uploaded_bytes += chunk.len() as u64;
+        } else {
+            self.stats.reused_chunks += 1;
+        }
+        Ok(hash)
+    }
+}
+
+/// Returns the number of chunks at the start of the file that match the given hashes, and their
+/// total size.
+fn verify_restored_chunks(
+    file: &mut File,
+    chunk_size: usize,
+    hashes: &[B256],
+) -> Result<(usize, u64), BackupError> {
+    let mut size = 0;
+    let mut chunk = Vec::with_capacity(chunk_size);
+    for (restored, hash) in hashes.iter().enumerate() {
+        chunk.clear();
+        (&mut *file).take(chunk_size as u64).read_to_end(&mut chunk)?;
+        if chunk.is_empty() || chunk_hash(&chunk) != *hash {
+            return Ok((restored, size))
+        }
+        size += chunk.len() as u64;
+    }
+    Ok((hashes.len(), size))
+}
+
+/// Returns the names of the files in the directory that are backed up, besides the database
+/// itself.
+///
+/// Static file configurations come first, so they're never ahead of the data they describe.
+fn backed_up_files(kind: BackupFileKind, dir: &Path) -> Result<Vec<String>, BackupError> {
+    if !dir.exists() {
+        return Ok(Vec::new())
+    }
+
+    let mut names = Vec::new();
+    for entry in reth_fs_util::read_dir(dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue
+        }
+        let Ok(name) = entry.file_name().into_string() else { continue };
+        if LOCK_FILES.contains(&name.as_str()) ||
+            (kind == BackupFileKind::Database && name == DATABASE_FILE)
+        {
+            continue
+        }
+        names.push(name);
+    }
+    names.sort_unstable_by_key(|name| (!name.ends_with(".conf"), name.clone()));
+
+    Ok(names)
+}
+
+fn chunk_hash(chunk: &[u8]) -> B256 {
+    B256::from_slice(&Sha256::digest(chunk))
+}
+
+fn chunk_key(hash: B256) -> String {
+    format!("chunks/{hash:x}")
+}
+
+fn manifest_key(id: u64) -> String {
+    format!("manifests/{id}.json")
+}
+
+fn signature_key(id: u64) -> String {
+    format!("manifests/{id}.json.sig")
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_libmdbx::WriteFlags;
+    use reth_object_store::LocalObjectStore;
+
+    const CHUNK_SIZE: usize = 16 * 1024;
+
+    fn put(env: &Environment, range: std::ops::Range<u32>) {
+        let txn = env.begin_rw_txn().unwrap();
+        let db = txn.open_db(None).unwrap();
+        for i in range {
+            txn.put(db.dbi(), i.to_be_bytes(), [i as u8; 64], WriteFlags::empty()).unwrap();
+        }
+        txn.commit().unwrap();
+    }
+
+    fn assert_contains(env: &Environment, range: std::ops::Range<u32>) {
+        let txn = env.begin_ro_txn().unwrap();
+        let db = txn.open_db(None).unwrap();
+        for i in range {
+            assert_eq!(
+                txn.get::<Vec<u8>>(db.dbi(), &i.to_be_bytes()).unwrap(),
+                Some(vec![i as u8; 64])
+            );
+        }
+    }
+
+    #[test]
+    fn create_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = BackupPaths { db: dir.path().join("db"), static_files: dir.path().join("sf") };
+        std::fs::create_dir_all(&paths.db).unwrap();
+        std::fs::create_dir_all(&paths.static_files).unwrap();
+
+        let env = Environment::builder().open(&paths.db).unwrap();
+        put(&env, 0..1000);
+        let data = (0..CHUNK_SIZE * 8).map(|i| i as u8).collect::<Vec<_>>();
+        std::fs::write(paths.static_files.join("static_file_headers_0_499999"), &data).unwrap();
+        std::fs::write(paths.static_files.join("static_file_headers_0_499999.conf"), b"conf")
+            .unwrap();
+
+        let backup = Backup::new(LocalObjectStore::new(dir.path().join("backup")))
+            .with_chunk_size(CHUNK_SIZE);
+        assert!(backup.latest_manifest().unwrap().is_none());
+
+        let (first, stats) = backup.create(&env, &paths).unwrap();
+        assert!(stats.uploaded_chunks > 8);
+        assert!(first.file(BackupFileKind::Database, DATABASE_FILE).is_some());
+        assert_eq!(
+            first.file(BackupFileKind::StaticFiles, "static_file_headers_0_499999").unwrap().size,
+            data.len() as u64
+        );
+
+        // Only the changed parts are uploaded again
+        put(&env, 1000..1010);
+        std::fs::write(paths.static_files.join("static_file_headers_0_499999.conf"), b"conf2")
+            .unwrap();
+        let (second, stats) = backup.create(&env, &paths).unwrap();
+        assert!(second.id > first.id);
+        assert_eq!(backup.latest_manifest().unwrap(), Some(second));
+        assert!(stats.reused_chunks >= 8);
+        assert!(stats.uploaded_chunks < first.files.iter().map(|f| f.chunks.len()).sum());
+
+        drop(env);
+
+        // Latest backup
+        let restore_dir = tempfile::tempdir().unwrap();
+        let restore_paths = BackupPaths {
+            db: restore_dir.path().join("db"),
+            static_files: restore_dir.path().join("sf"),
+        };
+        backup.restore(None, &restore_paths).unwrap();
+        assert_contains(&Environment::builder().open(&restore_paths.db).unwrap(), 0..1010);
+        assert_eq!(
+            std::fs::read(restore_paths.static_files.join("static_file_headers_0_499999")).unwrap(),
+            data
+        );
+        assert_eq!(
+            std::fs::read(restore_paths.static_files.join("static_file_headers_0_499999.conf"))
+                .unwrap(),
+            b"conf2"
+        );
+        assert!(matches!(backup.restore(None, &restore_paths), Err(BackupError::TargetExists(_))));
+
+        // Previous backup
+        let restore_dir = tempfile::tempdir().unwrap();
+        let restore_paths = BackupPaths {
+            db: restore_dir.path().join("db"),
+            static_files: restore_dir.path().join("sf"),
+        };
+        backup.restore(Some(first.id), &restore_paths).unwrap();
+        let env = Environment::builder().open(&restore_paths.db).unwrap();
+        assert_contains(&env, 0..1000);
+        let txn = env.begin_ro_txn().unwrap();
+        let db = txn.open_db(None).unwrap();
+        assert_eq!(txn.get::<Vec<u8>>(db.dbi(), &1000u32.to_be_bytes()).unwrap(), None);
+        assert_eq!(
+            std::fs::read(restore_paths.static_files.join("static_file_headers_0_499999.conf"))
+                .unwrap(),
+            b"conf"
+        );
+
+        assert!(matches!(
+            backup.restore(Some(0), &restore_paths),
+            Err(BackupError::BackupNotFound(0))
+        ));
+    }
+
+    #[test]
+    fn resume_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = BackupPaths { db: dir.path().join("db"), static_files: dir.path().join("sf") };
+        std::fs::create_dir_all(&paths.db).unwrap();
+
+        let env = Environment::builder().open(&paths.db).unwrap();
+        put(&env, 0..1000);
+        let backup = Backup::new(LocalObjectStore::new(dir.path().join("backup")))
+            .with_chunk_size(CHUNK_SIZE);
+        let (manifest, _) = backup.create(&env, &paths).unwrap();
+        drop(env);
+
+        let restore_dir = tempfile::tempdir().unwrap();
+        let restore_paths = BackupPaths {
+            db: restore_dir.path().join("db"),
+            static_files: restore_dir.path().join("sf"),
+        };
+        assert!(restore_paths.needs_restore());
+        backup.restore(None, &restore_paths).unwrap();
+        assert!(!restore_paths.needs_restore());
+
+        // Simulate an interrupted restore that left a partially written and corrupted database
+        let db_file = restore_paths.db.join(DATABASE_FILE);
+        let mut data = std::fs::read(&db_file).unwrap();
+        data.truncate(CHUNK_SIZE * 2 + 100);
+        data[CHUNK_SIZE + 1] ^= 0xff;
+        std::fs::write(&db_file, data).unwrap();
+        std::fs::write(restore_paths.db.join(RESTORE_MARKER), manifest.id.to_string()).unwrap();
+        assert!(restore_paths.needs_restore());
+
+        assert!(matches!(
+            backup.restore(Some(manifest.id + 1), &restore_paths),
+            Err(BackupError::RestoreInProgress(id)) if id == manifest.id
+        ));
+        backup.restore(None, &restore_paths).unwrap();
+        assert!(!restore_paths.needs_restore());
+        assert_contains(&Environment::builder().open(&restore_paths.db).unwrap(), 0..1000);
+    }
+
+    #[test]
+    fn signed_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = BackupPaths { db: dir.path().join("db"), static_files: dir.path().join("sf") };
+        std::fs::create_dir_all(&paths.db).unwrap();
+
+        let env = Environment::builder().open(&paths.db).unwrap();
+        put(&env, 0..10);
+        let signer = SecretKey::from_slice(&[0x46; 32]).unwrap();
+        let storage = LocalObjectStore::new(dir.path().join("backup"));
+        let (manifest, _) =
+            Backup::new(storage.clone()).with_signer(signer).create(&env, &paths).unwrap();
+
+        let trusted = Backup::new(storage.clone()).with_trusted_signer(signer_address(&signer));
+        assert_eq!(trusted.latest_manifest().unwrap(), Some(manifest.clone()));
+
+        let untrusted = Backup::new(storage.clone()).with_trusted_signer(Address::ZERO);
+        assert!(matches!(
+            untrusted.latest_manifest(),
+            Err(BackupError::UntrustedSigner { signer: address, .. }) if address == signer_address(&signer)
+        ));
+
+        // A tampered manifest doesn't match its signature
+        let key = manifest_key(manifest.id);
+        let mut tampered = manifest.clone();
+        tampered.files.clear();
+        storage.put(&key, &serde_json::to_vec_pretty(&tampered).unwrap()).unwrap();
+        assert!(matches!(trusted.manifest(manifest.id), Err(BackupError::UntrustedSigner { .. })));
+
+        // Unsigned backups are rejected
+        let (manifest, _) = Backup::new(storage).create(&env, &paths).unwrap();
+        assert!(matches!(
+            trusted.manifest(manifest.id),
+            Err(BackupError::MissingSignature(id)) if id == manifest.id
+        ));
+    }
+}
@@ -0,0 +1,62 @@
+use alloy_primitives::{Address, B256};
+use reth_fs_util::FsPathError;
+use reth_object_store::ObjectStoreError;
+use std::path::PathBuf;
+
+/// Errors that can occur when creating or restoring a backup.
+#[derive(Debug, thiserror::Error)]
+pub enum BackupError {
+    /// IO error.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Filesystem error.
+    #[error(transparent)]
+    FsPath(#[from] FsPathError),
+    /// Error copying the database.
+    #[error("failed to copy the database: {0}")]
+    Database(#[from] reth_libmdbx::Error),
+    /// Error encoding or decoding a manifest.
+    #[error("invalid backup manifest: {0}")]
+    Manifest(#[from] serde_json::Error),
+    /// Error returned by the object store the backups are kept in.
+    #[error("backup storage error: {0}")]
+    Storage(#[from] ObjectStoreError),
+    /// The backup is invalid.
+    #[error("invalid backup: {0}")]
+    InvalidBackup(String),
+    /// There is no backup to restore.
+    #[error("no backup found")]
+    NoBackup,
+    /// The backup with the given id doesn't exist.
+    #[error("backup {0} not found")]
+    BackupNotFound(u64),
+    /// A chunk referenced by the manifest is missing from the storage.
+    #[error("chunk {0} is missing from the backup storage")]
+    MissingChunk(B256),
+    /// A chunk doesn't match its hash.
+    #[error("chunk {0} is corrupted")]
+    CorruptedChunk(B256),
+    /// The manifest of the backup with the given id isn't signed.
+    #[error("manifest of backup {0} is not signed")]
+    MissingSignature(u64),
+    /// The signature of the manifest of the backup with the given id is invalid.
+    #[error("manifest of backup {0} has an invalid signature")]
+    InvalidSignature(u64),
+    /// The manifest is signed by a key that isn't trusted.
+    #[error("manifest of backup {id} is signed by untrusted signer {signer}")]
+    UntrustedSigner {
+        /// Id of the backup.
+        id: u64,
+        /// Address that signed the manifest.
+        signer: Address,
+    },
+    /// A restore of the backup with the given id was interrupted and has to be resumed first.
+    #[error("restore of backup {0} is in progress")]
+    RestoreInProgress(u64),
+    /// The restore target already contains data.
+    #[error("restore target {0:?} already contains data")]
+    TargetExists(PathBuf),
+    /// Backups aren't supported on this platform.
+    #[error("database backups are not supported on this platform")]
+    Unsupported,
+}
@@ -0,0 +1,26 @@
+//! Incremental backups of the database and static files.
+//!
+//! Backups are split into content-addressed chunks, so consecutive backups only upload the parts
+//! that changed. They can be kept in a local directory or an S3-compatible object storage, see
+//! [`ObjectStore`](reth_object_store::ObjectStore).
+//!
+//! Signed backups that are published on a web server can be used to bootstrap new nodes.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod backup;
+pub use backup::{Backup, BackupPaths, BackupStats, DEFAULT_CHUNK_SIZE};
+
+mod error;
+pub use error::BackupError;
+
+mod manifest;
+pub use manifest::{BackupFile, BackupFileKind, BackupManifest};
+
+mod signature;
@@ -0,0 +1,55 @@
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+
+/// Describes the files of a backup and the chunks they consist of.
+///
+/// Chunks are addressed by their SHA-256 hash and shared between backups, so a chunk that didn't
+/// change since a previous backup is stored only once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// Identifier of the backup, the time it was created at in milliseconds since the Unix epoch.
+    pub id: u64,
+    /// Size of the chunks the files are split into.
+    pub chunk_size: usize,
+    /// Backed up files.
+    pub files: Vec<BackupFile>,
+}
+
+impl BackupManifest {
+    /// Returns the total size of the backed up files.
+    pub fn size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+
+    /// Returns the backed up file of the given kind and name.
+    pub fn file(&self, kind: BackupFileKind, name: &str) -> Option<&BackupFile> {
+        self.files.iter().find(|file| file.kind == kind && file.name == name)
+    }
+}
+
+/// A backed up file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupFile {
+    /// Directory the file belongs to.
+    pub kind: BackupFileKind,
+    /// Name of the file inside its directory.
+    pub name: String,
+    /// Size of the file.
+    pub size: u64,
+    /// Modification time of the file in nanoseconds since the Unix epoch, used to skip reading
+    /// unchanged files. Not set for the database, which is copied from a snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<u64>,
+    /// Hashes of the chunks of the file, in order.
+    pub chunks: Vec<B256>,
+}
+
+/// Directory a [`BackupFile`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupFileKind {
+    /// The database directory.
+    Database,
+    /// The static files directory.
+    StaticFiles,
+}
@@ -0,0 +1,61 @@
+use alloy_primitives::{eip191_hash_message, hex, Address};
+use secp256k1::{
+    ecdsa::{RecoverableSignature, RecoveryId},
+    Message, SecretKey, SECP256K1,
+};
+
+/// Signs a manifest with the given key.
+///
+/// The manifest is signed as an [EIP-191](https://eips.ethereum.org/EIPS/eip-191) personal
+/// message, so signatures can also be created with common Ethereum tooling, e.g.
+/// `cast wallet sign`. Returns the hex encoded signature.
+pub(crate) fn sign_manifest(key: &SecretKey, manifest: &[u8]) -> String {
+    let message = Message::from_digest(eip191_hash_message(manifest).0);
+    let (recovery_id, signature) =
+        SECP256K1.sign_ecdsa_recoverable(&message, key).serialize_compact();
+
+    let mut bytes = [0; 65];
+    bytes[..64].copy_from_slice(&signature);
+    bytes[64] = 27 + recovery_id.to_i32() as u8;
+    hex::encode_prefixed(bytes)
+}
+
+/// Recovers the address that signed the manifest from its hex encoded signature.
+///
+/// Returns `None` if the signature is invalid.
+pub(crate) fn recover_manifest_signer(manifest: &[u8], signature: &[u8]) -> Option<Address> {
+    let signature = std::str::from_utf8(signature).ok()?.trim();
+    let bytes: [u8; 65] = hex::decode(signature).ok()?.try_into().ok()?;
+    // Both the legacy `27`/`28` and the raw `0`/`1` recovery ids are accepted
+    let recovery_id = RecoveryId::from_i32(i32::from(bytes[64] % 27)).ok()?;
+    let signature = RecoverableSignature::from_compact(&bytes[..64], recovery_id).ok()?;
+
+    let message = Message::from_digest(eip191_hash_message(manifest).0);
+    let public_key = SECP256K1.recover_ecdsa(&message, &signature).ok()?;
+    Some(Address::from_raw_public_key(&public_key.serialize_uncompressed()[1..]))
+}
+
+/// Returns the address of the given key.
+pub(crate) fn signer_address(key: &SecretKey) -> Address {
+    Address::from_raw_public_key(&key.public_key(SECP256K1).serialize_uncompressed()[1..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_recover() {
+        let key = SecretKey::from_slice(&[0x46; 32]).unwrap();
+        let signature = sign_manifest(&key, b"manifest");
+        assert_eq!(
+            recover_manifest_signer(b"manifest", signature.as_bytes()),
+            Some(signer_address(&key))
+        );
+        assert_ne!(
+            recover_manifest_signer(b"other manifest", signature.as_bytes()),
+            Some(signer_address(&key))
+        );
+        assert_eq!(recover_manifest_signer(b"manifest", b"0x1234"), None);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::DatabaseError;
 use metrics::{counter, gauge, histogram, Label};
 use std::sync::Arc;
 
@@ -33,12 +34,62 @@ pub trait DatabaseMetrics {
     fn histogram_metrics(&self) -> Vec<(&'static str, f64, Vec<Label>)> {
         vec![]
     }
+
+    /// Returns the [`TableStats`] of all tables of the database.
+    fn table_stats(&self) -> Result<Vec<TableStats>, DatabaseError> {
+        Ok(vec![])
+    }
 }
 
 impl<DB: DatabaseMetrics> DatabaseMetrics for Arc<DB> {
     fn report_metrics(&self) {
         <DB as DatabaseMetrics>::report_metrics(self)
     }
+
+    fn gauge_metrics(&self) -> Vec<(&'static str, f64, Vec<Label>)> {
+        <DB as DatabaseMetrics>::gauge_metrics(self)
+    }
+
+    fn counter_metrics(&self) -> Vec<(&'static str, u64, Vec<Label>)> {
+        <DB as DatabaseMetrics>::counter_metrics(self)
+    }
+
+    fn histogram_metrics(&self) -> Vec<(&'static str, f64, Vec<Label>)> {
+        <DB as DatabaseMetrics>::histogram_metrics(self)
+    }
+
+    fn table_stats(&self) -> Result<Vec<TableStats>, DatabaseError> {
+        <DB as DatabaseMetrics>::table_stats(self)
+    }
+}
+
+/// The number of entries and pages of a database table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableStats {
+    /// The name of the table.
+    pub table: &'static str,
+    /// The number of entries in the table.
+    pub entries: usize,
+    /// The size of a page in bytes.
+    pub page_size: usize,
+    /// The number of leaf pages.
+    pub leaf_pages: usize,
+    /// The number of branch pages.
+    pub branch_pages: usize,
+    /// The number of overflow pages.
+    pub overflow_pages: usize,
+}
+
+impl TableStats {
+    /// Returns the total number of pages of the table.
+    pub const fn pages(&self) -> usize {
+        self.leaf_pages + self.branch_pages + self.overflow_pages
+    }
+
+    /// Returns the size of the table in bytes.
+    pub const fn size(&self) -> usize {
+        self.page_size * self.pages()
+    }
 }
 
 /// The type used to store metadata about the database.
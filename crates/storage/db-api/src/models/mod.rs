@@ -143,6 +143,24 @@ impl Decode for StoredNibblesSubKey {
     }
 }
 
+impl Encode for VerklePath {
+    type Encoded = Vec<u8>;
+
+    // Delegate to the Compact implementation
+    fn encode(self) -> Self::Encoded {
+        let mut buf = Vec::with_capacity(self.0.len());
+        self.to_compact(&mut buf);
+        buf
+    }
+}
+
+impl Decode for VerklePath {
+    fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
+        let buf = value.as_ref();
+        Ok(Self::from_compact(buf, buf.len()).0)
+    }
+}
+
 impl Encode for PruneSegment {
     type Encoded = [u8; 1];
 
@@ -213,6 +231,8 @@ impl_compression_for_compact!(
     StoredNibbles,
     StoredNibblesSubKey,
     StorageTrieEntry,
+    StoredVerkleNode,
+    VerkleLeafBeforeBlock,
     StoredBlockBodyIndices,
     StoredBlockOmmers,
     StoredBlockWithdrawals,
@@ -308,8 +328,8 @@ mod tests {
     use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
     use reth_stages_types::{
         AccountHashingCheckpoint, CheckpointBlockRange, EntitiesCheckpoint, ExecutionCheckpoint,
-        HeadersCheckpoint, IndexHistoryCheckpoint, StageCheckpoint, StageUnitCheckpoint,
-        StorageHashingCheckpoint,
+        HeadersCheckpoint, IndexHistoryCheckpoint, SnapSyncCheckpoint, StageCheckpoint,
+        StageUnitCheckpoint, StorageHashingCheckpoint,
     };
 
     // each value in the database has an extra field named flags that encodes metadata about other
@@ -337,6 +357,7 @@ mod tests {
         assert_eq!(Receipt::bitflag_encoded_bytes(), 1);
         assert_eq!(ReceiptWithBloom::bitflag_encoded_bytes(), 0);
         assert_eq!(SealedHeader::bitflag_encoded_bytes(), 0);
+        assert_eq!(SnapSyncCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(StageCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(StageUnitCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(StoredBlockBodyIndices::bitflag_encoded_bytes(), 1);
@@ -370,6 +391,7 @@ mod tests {
         assert_eq!(Receipt::bitflag_encoded_bytes(), 2);
         assert_eq!(ReceiptWithBloom::bitflag_encoded_bytes(), 0);
         assert_eq!(SealedHeader::bitflag_encoded_bytes(), 0);
+        assert_eq!(SnapSyncCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(StageCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(StageUnitCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(StoredBlockBodyIndices::bitflag_encoded_bytes(), 1);
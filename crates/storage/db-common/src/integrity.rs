@@ -0,0 +1,624 @@
+//! Online consistency check of the stored chain.
+//!
+//! [`check_blocks`] cross-checks the headers, bodies, transactions, receipts, transaction lookup
+//! and history indices of a range of blocks in a single read transaction. The
+//! [`IntegrityChecker`] scans all blocks in small batches with a pause in between, keeps its
+//! progress and findings in a file so that it resumes after a restart, and exposes them through an
+//! [`IntegrityCheckHandle`].
+
+use parking_lot::RwLock;
+use reth_config::IntegrityCheckConfig;
+use reth_db::tables;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    models::{sharded_key::ShardedKey, storage_sharded_key::StorageShardedKey, BlockNumberAddress},
+    transaction::DbTx,
+};
+use reth_primitives::{proofs::calculate_transaction_root, Address, BlockNumber, B256};
+use reth_provider::{
+    BlockHashReader, BlockNumReader, BlockReader, ChangeSetReader, DatabaseProvider,
+    HeaderProvider, ProviderFactory, ProviderResult, PruneCheckpointReader, ReceiptProvider,
+    StageCheckpointReader, TransactionsProvider,
+};
+use reth_prune_types::{PruneModes, PruneSegment, MINIMUM_PRUNING_DISTANCE};
+use reth_stages_types::StageId;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt, fs, io,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, error, info, warn};
+
+/// A part of the stored chain that is cross-checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityCheck {
+    /// The header, canonical hash, hash to number index and total difficulty of a block.
+    Headers,
+    /// The body indices of a block and whether its transactions follow the ones of its parent.
+    Bodies,
+    /// The transactions of a block against the transactions root of its header.
+    Transactions,
+    /// The receipts of a block against its transactions and the gas used of its header.
+    Receipts,
+    /// The hash to number index of the transactions of a block.
+    TransactionLookup,
+    /// The account history index of the account changesets of a block.
+    AccountHistory,
+    /// The storage history index of the storage changesets of a block.
+    StorageHistory,
+}
+
+impl IntegrityCheck {
+    /// Returns the name of the check.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Headers => "headers",
+            Self::Bodies => "bodies",
+            Self::Transactions => "transactions",
+            Self::Receipts => "receipts",
+            Self::TransactionLookup => "transaction_lookup",
+            Self::AccountHistory => "account_history",
+            Self::StorageHistory => "storage_history",
+        }
+    }
+}
+
+impl fmt::Display for IntegrityCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An inconsistency of the stored chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityFinding {
+    /// The block the inconsistency was found in.
+    pub block: BlockNumber,
+    /// The check that found the inconsistency.
+    pub check: IntegrityCheck,
+    /// What is inconsistent.
+    pub message: String,
+}
+
+impl IntegrityFinding {
+    pub(crate) fn new(
+        block: BlockNumber,
+        check: IntegrityCheck,
+        message: impl Into<String>,
+    ) -> Self {
+        Self { block, check, message: message.into() }
+    }
+}
+
+impl fmt::Display for IntegrityFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "block {}: {}: {}", self.block, self.check, self.message)
+    }
+}
+
+/// Checks the blocks in the range, except for the blocks above the highest fully synced block.
+///
+/// Data that is pruned according to the prune checkpoints or the given prune modes is not checked.
+/// If receipts are pruned by a log filter, only the receipts of the last
+/// [`MINIMUM_PRUNING_DISTANCE`] blocks are checked.
+pub fn check_blocks<TX: DbTx>(
+    provider: &DatabaseProvider<TX>,
+    range: RangeInclusive<BlockNumber>,
+    prune_modes: &PruneModes,
+) -> ProviderResult<Vec<IntegrityFinding>> {
+    let tip = synced_tip(provider)?;
+    let pruned = PrunedSegments::new(provider, prune_modes, tip)?;
+    let mut findings = Vec::new();
+
+    let mut account_history = provider.tx_ref().cursor_read::<tables::AccountsHistory>()?;
+    let mut storage_history = provider.tx_ref().cursor_read::<tables::StoragesHistory>()?;
+    let mut storage_changesets =
+        provider.tx_ref().cursor_dup_read::<tables::StorageChangeSets>()?;
+
+    // The first transaction of the next block, if the body of the previous block is known.
+    let mut next_tx = match range.start().checked_sub(1) {
+        Some(parent) => provider.block_body_indices(parent)?.map(|body| body.next_tx_num()),
+        None => Some(0),
+    };
+
+    for block in *range.start()..=(*range.end()).min(tip) {
+        if pruned.is_pruned(PruneSegment::Headers, block) {
+            next_tx = None;
+            continue
+        }
+
+        let Some(header) = provider.header_by_number(block)? else {
+            findings.push(IntegrityFinding::new(block, IntegrityCheck::Headers, "header missing"));
+            next_tx = None;
+            continue
+        };
+        let hash = header.hash_slow();
+        match provider.block_hash(block)? {
+            Some(canonical) if canonical == hash => {}
+            Some(canonical) => findings.push(IntegrityFinding::new(
+                block,
+                IntegrityCheck::Headers,
+                format!("canonical hash {canonical} differs from header hash {hash}"),
+            )),
+            None => findings.push(IntegrityFinding::new(
+                block,
+                IntegrityCheck::Headers,
+                "canonical hash missing",
+            )),
+        }
+        if provider.block_number(hash)? != Some(block) {
+            findings.push(IntegrityFinding::new(
+                block,
+                IntegrityCheck::Headers,
+                format!("header hash {hash} isn't indexed"),
+            ))
+        }
+        if provider.header_td_by_number(block)?.is_none() {
+            findings.push(IntegrityFinding::new(
+                block,
+                IntegrityCheck::Headers,
+                "total difficulty missing",
+            ))
+        }
+
+        let Some(body) = provider.block_body_indices(block)? else {
+            findings.push(IntegrityFinding::new(block, IntegrityCheck::Bodies, "body missing"));
+            next_tx = None;
+            continue
+        };
+        if next_tx.is_some_and(|next_tx| next_tx != body.first_tx_num) {
+            findings.push(IntegrityFinding::new(
+                block,
+                IntegrityCheck::Bodies,
+                format!(
+                    "first transaction {} doesn't follow the transactions of the parent",
+                    body.first_tx_num
+                ),
+            ))
+        }
+        next_tx = Some(body.next_tx_num());
+
+        if !pruned.is_pruned(PruneSegment::Transactions, block) {
+            let transactions = provider
+                .transactions_by_tx_range(body.tx_num_range())?
+                .into_iter()
+                .map(|tx| tx.with_hash())
+                .collect::<Vec<_>>();
+            if transactions.len() as u64 != body.tx_count {
+                findings.push(IntegrityFinding::new(
+                    block,
+                    IntegrityCheck::Transactions,
+                    format!("{} of {} transactions found", transactions.len(), body.tx_count),
+                ))
+            } else if calculate_transaction_root(&transactions) != header.transactions_root {
+                findings.push(IntegrityFinding::new(
+                    block,
+                    IntegrityCheck::Transactions,
+                    "transactions root mismatch",
+                ))
+            }
+
+            if !pruned.is_pruned(PruneSegment::TransactionLookup, block) {
+                let mut missing = 0;
+                let mut first_missing = None;
+                for (tx_num, tx) in body.tx_num_range().zip(&transactions) {
+                    if provider.transaction_id(tx.hash)? != Some(tx_num) {
+                        missing += 1;
+                        first_missing.get_or_insert(tx.hash);
+                    }
+                }
+                if let Some(hash) = first_missing {
+                    findings.push(IntegrityFinding::new(
+                        block,
+                        IntegrityCheck::TransactionLookup,
+                        format!("{missing} transactions aren't indexed, e.g. {hash}"),
+                    ))
+                }
+            }
+        }
+
+        if !pruned.receipts_pruned(block) {
+            let receipts = provider.receipts_by_tx_range(body.tx_num_range())?;
+            if receipts.len() as u64 != body.tx_count {
+                findings.push(IntegrityFinding::new(
+                    block,
+                    IntegrityCheck::Receipts,
+                    format!("{} of {} receipts found", receipts.len(), body.tx_count),
+                ))
+            } else if let Some(receipt) = receipts.last() {
+                if receipt.cumulative_gas_used != header.gas_used {
+                    findings.push(IntegrityFinding::new(
+                        block,
+                        IntegrityCheck::Receipts,
+                        format!(
+                            "cumulative gas used {} differs from gas used {} of the header",
+                            receipt.cumulative_gas_used, header.gas_used
+                        ),
+                    ))
+                }
+            }
+        }
+
+        if !pruned.is_pruned(PruneSegment::AccountHistory, block) {
+            let mut missing = Vec::new();
+            for change in provider.account_block_changeset(block)? {
+                let shard = account_history.seek(ShardedKey::new(change.address, block))?;
+                if !shard
+                    .is_some_and(|(key, list)| key.key == change.address && list.contains(block))
+                {
+                    missing.push(change.address);
+                }
+            }
+            if let Some(address) = missing.first() {
+                findings.push(IntegrityFinding::new(
+                    block,
+                    IntegrityCheck::AccountHistory,
+                    format!("{} changed accounts aren't indexed, e.g. {address}", missing.len()),
+                ))
+            }
+        }
+
+        if !pruned.is_pruned(PruneSegment::StorageHistory, block) {
+            let mut missing = Vec::<(Address, B256)>::new();
+            for entry in storage_changesets.walk_range(BlockNumberAddress::range(block..=block))? {
+                let (BlockNumberAddress((_, address)), slot) = entry?;
+                let shard =
+                    storage_history.seek(StorageShardedKey::new(address, slot.key, block))?;
+                if !shard.is_some_and(|(key, list)| {
+                    key.address == address &&
+                        key.sharded_key.key == slot.key &&
+                        list.contains(block)
+                }) {
+                    missing.push((address, slot.key));
+                }
+            }
+            if let Some((address, slot)) = missing.first() {
+                findings.push(IntegrityFinding::new(
+                    block,
+                    IntegrityCheck::StorageHistory,
+                    format!(
+                        "{} changed storage slots aren't indexed, e.g. {slot} of {address}",
+                        missing.len()
+                    ),
+                ))
+            }
+        }
+    }
+
+    Ok(findings)
+}
+
+/// Returns the highest fully synced block, the blocks above it may be incomplete.
+fn synced_tip<Provider: StageCheckpointReader>(provider: &Provider) -> ProviderResult<BlockNumber> {
+    Ok(provider
+        .get_stage_checkpoint(StageId::Finish)?
+        .map(|checkpoint| checkpoint.block_number)
+        .unwrap_or_default())
+}
+
+/// The data that is pruned, by the prune checkpoints and the configured prune modes.
+struct PrunedSegments<'a> {
+    checkpoints: HashMap<PruneSegment, BlockNumber>,
+    modes: &'a PruneModes,
+    tip: BlockNumber,
+}
+
+impl<'a> PrunedSegments<'a> {
+    fn new<Provider: PruneCheckpointReader>(
+        provider: &Provider,
+        modes: &'a PruneModes,
+        tip: BlockNumber,
+    ) -> ProviderResult<Self> {
+        let checkpoints = provider
+            .get_prune_checkpoints()?
+            .into_iter()
+            .filter_map(|(segment, checkpoint)| Some((segment, checkpoint.block_number?)))
+            .collect();
+        Ok(Self { checkpoints, modes, tip })
+    }
+
+    /// Returns `true` if the data of the segment may be pruned for the block.
+    fn is_pruned(&self, segment: PruneSegment, block: BlockNumber) -> bool {
+        let mode = match segment {
+            PruneSegment::TransactionLookup => self.modes.transaction_lookup,
+            PruneSegment::Receipts => self.modes.receipts,
+            PruneSegment::AccountHistory => self.modes.account_history,
+            PruneSegment::StorageHistory => self.modes.storage_history,
+            PruneSegment::ReceiptsRetention => self.modes.receipts_retention.mode,
+            _ => None,
+        };
+        self.checkpoints.get(&segment).is_some_and(|pruned| block <= *pruned) ||
+            mode.is_some_and(|mode| mode.should_prune(block, self.tip))
+    }
+
+    /// Returns `true` if some receipts of the block may be pruned.
+    fn receipts_pruned(&self, block: BlockNumber) -> bool {
+        self.is_pruned(PruneSegment::Receipts, block) ||
+            self.is_pruned(PruneSegment::ReceiptsRetention, block) ||
+            self.is_pruned(PruneSegment::ContractLogs, block) ||
+            (!self.modes.receipts_log_filter.is_empty() &&
+                block <= self.tip.saturating_sub(MINIMUM_PRUNING_DISTANCE))
+    }
+}
+
+/// The progress and findings of the [`IntegrityChecker`], that are kept across restarts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IntegrityCheckState {
+    /// The next block that is checked.
+    pub next_block: BlockNumber,
+    /// The highest fully synced block when the last batch was checked.
+    pub tip: BlockNumber,
+    /// The number of completed scans of all blocks.
+    pub completed_scans: u64,
+    /// The UNIX timestamp in seconds of the end of the last completed scan.
+    pub last_scan_completed_at: Option<u64>,
+    /// The number of findings since the check was started or reset, including the ones that
+    /// aren't kept.
+    pub total_findings: u64,
+    /// The first findings since the check was started or reset, each finding is only kept once.
+    pub findings: Vec<IntegrityFinding>,
+}
+
+impl IntegrityCheckState {
+    /// Reads the state from the file at the given path, or returns the initial state if the file
+    /// doesn't exist.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        match fs::read(path) {
+            Ok(contents) => Ok(serde_json::from_slice(&contents)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Writes the state to the file at the given path.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_vec(self)?)
+    }
+
+    /// Adds the findings that aren't known yet, and keeps at most `max_findings` of them.
+    fn add_findings(&mut self, findings: Vec<IntegrityFinding>, max_findings: usize) {
+        for finding in findings {
+            if self.findings.contains(&finding) {
+                continue
+            }
+            self.total_findings += 1;
+            if self.findings.len() < max_findings {
+                self.findings.push(finding);
+            }
+        }
+    }
+}
+
+/// A handle to the state of a running [`IntegrityChecker`].
+#[derive(Debug, Clone, Default)]
+pub struct IntegrityCheckHandle {
+    inner: Arc<IntegrityCheckShared>,
+}
+
+#[derive(Debug, Default)]
+struct IntegrityCheckShared {
+    /// The current state, `None` if no checker is running.
+    state: RwLock<Option<IntegrityCheckState>>,
+    /// Whether the checker should start over with the next batch.
+    reset: AtomicBool,
+}
+
+impl IntegrityCheckHandle {
+    /// Returns the current state of the checker, or `None` if no checker is running.
+    pub fn state(&self) -> Option<IntegrityCheckState> {
+        self.inner.state.read().clone()
+    }
+
+    /// Makes the checker discard its findings and start over from the genesis block.
+    ///
+    /// Returns `false` if no checker is running.
+    pub fn reset(&self) -> bool {
+        if self.inner.state.read().is_none() {
+            return false
+        }
+        self.inner.reset.store(true, Ordering::Relaxed);
+        true
+    }
+
+    fn set_state(&self, state: &IntegrityCheckState) {
+        *self.inner.state.write() = Some(state.clone());
+    }
+
+    fn take_reset(&self) -> bool {
+        self.inner.reset.swap(false, Ordering::Relaxed)
+    }
+}
+
+/// Continuously scans all blocks with [`check_blocks`], in batches of
+/// [`IntegrityCheckConfig::batch_size`] blocks in separate read transactions.
+///
+/// The progress and findings are written to a file after every batch, so that the scan resumes
+/// where it left off after a restart. Findings are logged as errors. Once all blocks are checked,
+/// the next scan starts after [`IntegrityCheckConfig::interval`].
+#[derive(Debug)]
+pub struct IntegrityChecker<DB> {
+    provider_factory: ProviderFactory<DB>,
+    prune_modes: PruneModes,
+    config: IntegrityCheckConfig,
+    path: PathBuf,
+    handle: IntegrityCheckHandle,
+}
+
+impl<DB: Database> IntegrityChecker<DB> {
+    /// Creates a new checker that keeps its state in the file at the given path.
+    pub fn new(
+        provider_factory: ProviderFactory<DB>,
+        prune_modes: PruneModes,
+        config: IntegrityCheckConfig,
+        path: PathBuf,
+    ) -> Self {
+        Self {
+            provider_factory,
+            prune_modes,
+            config,
+            path,
+            handle: IntegrityCheckHandle::default(),
+        }
+    }
+
+    /// Sets the handle the state of the checker is published to.
+    pub fn with_handle(mut self, handle: IntegrityCheckHandle) -> Self {
+        self.handle = handle;
+        self
+    }
+
+    /// Returns the handle to the state of the checker.
+    pub fn handle(&self) -> IntegrityCheckHandle {
+        self.handle.clone()
+    }
+
+    /// Checks batches of blocks forever, blocking the current thread.
+    pub fn run(self) {
+        let mut state = IntegrityCheckState::read(&self.path).unwrap_or_else(|err| {
+            warn!(target: "reth::integrity", %err, path = ?self.path, "Failed to read integrity check state, starting over");
+            IntegrityCheckState::default()
+        });
+        info!(target: "reth::integrity", next_block = state.next_block, findings = state.total_findings, "Checking database integrity");
+        self.handle.set_state(&state);
+
+        loop {
+            if self.handle.take_reset() {
+                info!(target: "reth::integrity", "Restarting integrity check");
+                state = IntegrityCheckState::default();
+                self.save(&state);
+            }
+
+            if state.next_block == 0 && !self.scan_due(&state) {
+                // poll, so that a reset isn't delayed until the next scan
+                thread::sleep(Duration::from_secs(1));
+                continue
+            }
+
+            if let Err(err) = self.check_batch(&mut state) {
+                warn!(target: "reth::integrity", %err, next_block = state.next_block, "Failed to check database integrity");
+            }
+            self.save(&state);
+            thread::sleep(self.config.pause);
+        }
+    }
+
+    /// Checks the next batch of blocks and advances the state.
+    ///
+    /// If all blocks were checked, the scan is completed and the next one starts from the genesis
+    /// block.
+    pub fn check_batch(&self, state: &mut IntegrityCheckState) -> ProviderResult<()> {
+        let provider = self.provider_factory.provider()?;
+        state.tip = synced_tip(&provider)?;
+        if state.next_block > state.tip {
+            info!(target: "reth::integrity", tip = state.tip, findings = state.total_findings, "Completed integrity check of all blocks");
+            state.next_block = 0;
+            state.completed_scans += 1;
+            state.last_scan_completed_at = Some(unix_timestamp());
+            return Ok(())
+        }
+
+        let range = state.next_block..=
+            state.next_block.saturating_add(self.config.batch_size.max(1) - 1).min(state.tip);
+        debug!(target: "reth::integrity", ?range, "Checking blocks");
+        let findings = check_blocks(&provider, range.clone(), &self.prune_modes)?;
+        for finding in &findings {
+            error!(target: "reth::integrity", %finding, "Database inconsistency");
+        }
+        state.add_findings(findings, self.config.max_findings);
+        state.next_block = range.end() + 1;
+        Ok(())
+    }
+
+    /// Returns `true` if the next scan is due, i.e. no scan was completed yet or the last one was
+    /// completed at least [`IntegrityCheckConfig::interval`] ago.
+    fn scan_due(&self, state: &IntegrityCheckState) -> bool {
+        state.last_scan_completed_at.map_or(true, |completed| {
+            unix_timestamp().saturating_sub(completed) >= self.config.interval.as_secs()
+        })
+    }
+
+    /// Writes the state to the file and publishes it to the handle.
+    fn save(&self, state: &IntegrityCheckState) {
+        if let Err(err) = state.write(&self.path) {
+            warn!(target: "reth::integrity", %err, path = ?self.path, "Failed to write integrity check state");
+        }
+        self.handle.set_state(state);
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_provider::{test_utils::create_test_provider_factory, StageCheckpointWriter};
+    use reth_prune_types::PruneMode;
+    use reth_stages_types::StageCheckpoint;
+    use reth_testing_utils::generators::{self, random_block_range};
+
+    #[test]
+    fn finds_inconsistencies() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+        let blocks = random_block_range(&mut rng, 0..=10, B256::ZERO, 1..3, None, None);
+
+        let provider = factory.provider_rw().unwrap();
+        for block in &blocks {
+            provider
+                .insert_historical_block(block.clone().try_seal_with_senders().unwrap())
+                .unwrap();
+        }
+        provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(10)).unwrap();
+        provider.commit().unwrap();
+
+        // the receipts aren't written
+        let prune_modes = PruneModes { receipts: Some(PruneMode::Full), ..Default::default() };
+        let findings = check_blocks(&factory.provider().unwrap(), 0..=20, &prune_modes).unwrap();
+        assert_eq!(findings, Vec::new());
+
+        let provider = factory.provider_rw().unwrap();
+        provider.tx_ref().delete::<tables::CanonicalHeaders>(3, None).unwrap();
+        provider
+            .tx_ref()
+            .delete::<tables::TransactionHashNumbers>(blocks[5].body[0].hash, None)
+            .unwrap();
+        provider.commit().unwrap();
+
+        let findings = check_blocks(&factory.provider().unwrap(), 0..=10, &prune_modes).unwrap();
+        assert_eq!(
+            findings.iter().map(|finding| (finding.block, finding.check)).collect::<Vec<_>>(),
+            vec![(3, IntegrityCheck::Headers), (5, IntegrityCheck::TransactionLookup)]
+        );
+
+        // the receipts are checked without pruning
+        let findings =
+            check_blocks(&factory.provider().unwrap(), 0..=0, &PruneModes::none()).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].check, IntegrityCheck::Receipts);
+
+        // findings are only kept once
+        let mut state = IntegrityCheckState::default();
+        state.add_findings(findings.clone(), 0);
+        state.add_findings(findings, 10);
+        assert_eq!(state.total_findings, 2);
+        state.add_findings(state.findings.clone(), 10);
+        assert_eq!(state.findings.len(), 1);
+    }
+}
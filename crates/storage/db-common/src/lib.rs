@@ -10,5 +10,9 @@
 
 pub mod init;
 
+pub mod integrity;
+
+pub mod static_file_repair;
+
 mod db_tool;
 pub use db_tool::*;
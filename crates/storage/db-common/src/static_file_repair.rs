@@ -0,0 +1,814 @@
+//! Verification and repair of static files.
+//!
+//! [`verify_static_files`] checks the static files of each segment block by block: headers must
+//! hash to a hash that is indexed in the database and link to their parent, and transactions and
+//! receipts must match the transactions root and the gas used of their header.
+//!
+//! [`repair_static_files`] rewrites the corrupted static files. The data of each block is taken
+//! from the static file if it's intact, otherwise from the database or a
+//! [`StaticFileRepairSource`], e.g. a [`PeerRepairSource`] that downloads the blocks from peers.
+//! This way a single corrupted static file doesn't require a resync.
+
+use reth_db::{static_file::iter_static_files, tables};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    models::{CompactU256, StoredBlockBodyIndices},
+    table::Table,
+    transaction::DbTx,
+};
+use reth_network_p2p::{bodies::client::BodiesClient, headers::client::HeadersClient};
+use reth_primitives::{
+    proofs::calculate_transaction_root,
+    static_file::{find_fixed_range, SegmentHeader, SegmentRangeInclusive},
+    BlockNumber, Header, Receipt, SealedBlock, StaticFileSegment, TransactionSignedNoHash,
+    TxNumber, B256, U256,
+};
+use reth_provider::{
+    providers::{StaticFileProvider, StaticFileRowWriter},
+    BlockHashReader, BlockNumReader, BlockReader, DatabaseProvider, HeaderProvider, ProviderError,
+    ProviderFactory, ProviderResult, ReceiptProvider, StaticFileProviderFactory,
+    TransactionsProvider,
+};
+use std::{
+    fmt,
+    ops::{Range, RangeInclusive},
+    time::Duration,
+};
+use tokio::runtime::Handle;
+use tracing::{debug, info, warn};
+
+use crate::integrity::{IntegrityCheck, IntegrityFinding};
+
+/// The result of verifying a static file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticFileReport {
+    /// The segment of the static file.
+    pub segment: StaticFileSegment,
+    /// The blocks of the static file.
+    pub block_range: RangeInclusive<BlockNumber>,
+    /// The corrupted blocks of the static file.
+    pub findings: Vec<IntegrityFinding>,
+}
+
+impl StaticFileReport {
+    /// Returns `true` if any block of the static file is corrupted.
+    pub fn is_corrupted(&self) -> bool {
+        !self.findings.is_empty()
+    }
+}
+
+/// Progress of verifying or repairing static files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticFileProgress {
+    /// The segment of the static file that was processed.
+    pub segment: StaticFileSegment,
+    /// The blocks of the static file that was processed.
+    pub block_range: RangeInclusive<BlockNumber>,
+    /// The number of static files that were processed.
+    pub processed: usize,
+    /// The number of static files that are processed in total.
+    pub total: usize,
+}
+
+/// Where the data of the blocks of a repaired static file was taken from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticFileRepair {
+    /// The segment of the static file.
+    pub segment: StaticFileSegment,
+    /// The blocks of the static file.
+    pub block_range: RangeInclusive<BlockNumber>,
+    /// The number of blocks that were intact in the static file.
+    pub intact: u64,
+    /// The number of blocks that were taken from the database.
+    pub from_database: u64,
+    /// The number of blocks that were taken from the [`StaticFileRepairSource`].
+    pub from_source: u64,
+}
+
+/// Errors when repairing a static file.
+#[derive(Debug, thiserror::Error)]
+pub enum StaticFileRepairError {
+    /// No intact copy of the data of a block was found.
+    #[error("no intact {segment} of block {block} found")]
+    Unavailable {
+        /// The segment of the data.
+        segment: StaticFileSegment,
+        /// The block of the data.
+        block: BlockNumber,
+    },
+    /// Provider error.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+/// Provides the blocks that are neither intact in the static files nor in the database.
+pub trait StaticFileRepairSource {
+    /// Returns the block with the given number, if available. The block is only used if its
+    /// header hashes to the hash indexed in the database, and its transactions match the header.
+    fn block(&mut self, number: BlockNumber) -> ProviderResult<Option<SealedBlock>>;
+
+    /// Returns the receipts of the block, if available. The receipts are only used if they match
+    /// the gas used of the header.
+    ///
+    /// Receipts can't be downloaded from peers, so none are returned by default.
+    fn receipts(&mut self, header: &Header) -> ProviderResult<Option<Vec<Receipt>>> {
+        let _ = header;
+        Ok(None)
+    }
+}
+
+/// Only repairs static files from the intact data in the static files and the database.
+impl StaticFileRepairSource for () {
+    fn block(&mut self, _number: BlockNumber) -> ProviderResult<Option<SealedBlock>> {
+        Ok(None)
+    }
+}
+
+/// Downloads the blocks from peers.
+///
+/// The requests are driven on the given runtime, so the repair must not run on one of its worker
+/// threads, e.g. use [`tokio::task::spawn_blocking`].
+pub struct PeerRepairSource<C> {
+    client: C,
+    runtime: Handle,
+    timeout: Duration,
+}
+
+impl<C> PeerRepairSource<C> {
+    /// Creates a new [`PeerRepairSource`] that gives up on a block after the timeout.
+    pub const fn new(client: C, runtime: Handle, timeout: Duration) -> Self {
+        Self { client, runtime, timeout }
+    }
+}
+
+impl<C> fmt::Debug for PeerRepairSource<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PeerRepairSource").field("timeout", &self.timeout).finish_non_exhaustive()
+    }
+}
+
+impl<C: HeadersClient + BodiesClient> StaticFileRepairSource for PeerRepairSource<C> {
+    fn block(&mut self, number: BlockNumber) -> ProviderResult<Option<SealedBlock>> {
+        let client = &self.client;
+        let block = self.runtime.block_on(async {
+            tokio::time::timeout(self.timeout, async {
+                let header = client.get_header(number.into()).await.ok()?.into_data()?.seal_slow();
+                let body = client.get_block_body(header.hash()).await.ok()?.into_data()?;
+                Some(SealedBlock::new(header, body))
+            })
+            .await
+        });
+
+        let block = block.ok().flatten();
+        if block.is_none() {
+            debug!(target: "reth::static_file", number, "Failed to download block from peers");
+        }
+        Ok(block)
+    }
+}
+
+/// Where the data of a block is taken from, in order of preference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Origin {
+    StaticFile,
+    Database,
+    Source,
+}
+
+const ORIGINS: [Origin; 3] = [Origin::StaticFile, Origin::Database, Origin::Source];
+
+/// Verifies the static files of the segments, and reports the progress after each static file.
+///
+/// The headers are verified first, since transactions and receipts are verified against them.
+pub fn verify_static_files<DB: Database>(
+    factory: &ProviderFactory<DB>,
+    segments: &[StaticFileSegment],
+    mut on_progress: impl FnMut(StaticFileProgress),
+) -> ProviderResult<Vec<StaticFileReport>> {
+    let static_files = list_static_files(&factory.static_file_provider(), segments)?;
+    let total = static_files.len();
+
+    let mut reports = Vec::with_capacity(total);
+    for (processed, (segment, block_range)) in static_files.into_iter().enumerate() {
+        let provider = factory.provider()?.disable_long_read_transaction_safety();
+        let findings = verify_static_file(&provider, segment, block_range.clone())?;
+        if !findings.is_empty() {
+            warn!(target: "reth::static_file", %segment, ?block_range, findings = findings.len(), first = %findings[0], "Static file is corrupted");
+        }
+
+        on_progress(StaticFileProgress {
+            segment,
+            block_range: block_range.clone(),
+            processed: processed + 1,
+            total,
+        });
+        reports.push(StaticFileReport { segment, block_range, findings });
+    }
+
+    Ok(reports)
+}
+
+/// Verifies the blocks of a static file.
+pub fn verify_static_file<TX: DbTx>(
+    provider: &DatabaseProvider<TX>,
+    segment: StaticFileSegment,
+    block_range: RangeInclusive<BlockNumber>,
+) -> ProviderResult<Vec<IntegrityFinding>> {
+    let static_files = provider.static_file_provider();
+    let check = match segment {
+        StaticFileSegment::Headers => IntegrityCheck::Headers,
+        StaticFileSegment::Transactions => IntegrityCheck::Transactions,
+        StaticFileSegment::Receipts => IntegrityCheck::Receipts,
+    };
+    let mut findings = Vec::new();
+
+    let (mut parent_hash, mut parent_td) = parent_of(static_files, *block_range.start());
+    for block in block_range {
+        let result = match segment {
+            StaticFileSegment::Headers => {
+                let result = match static_files.header_by_number(block) {
+                    Ok(Some(header)) => check_header(provider, block, &header, parent_hash)?
+                        .map(|hash| (hash, parent_td.map(|td| td + header.difficulty))),
+                    Ok(None) => Err("header missing".to_string()),
+                    Err(err) => Err(format!("header unreadable: {err}")),
+                };
+                // The hash and total difficulty are stored next to the header
+                let (stored_hash, stored_td) = stored_hash_and_td(static_files, block);
+                (parent_hash, parent_td) = match &result {
+                    Ok((hash, td)) => (Some(*hash), *td),
+                    Err(_) => (stored_hash, stored_td),
+                };
+                result.and_then(|(hash, td)| match (stored_hash, stored_td) {
+                    (stored_hash, _) if stored_hash != Some(hash) => {
+                        Err(format!("stored hash {stored_hash:?} differs from hash {hash}"))
+                    }
+                    (_, stored_td) if td.is_some() && stored_td != td => {
+                        Err(format!("stored total difficulty {stored_td:?} differs from {td:?}"))
+                    }
+                    _ => Ok(()),
+                })
+            }
+            StaticFileSegment::Transactions => match header_and_body(provider, block)? {
+                Ok((header, body)) => match static_files
+                    .transactions_by_tx_range(body.tx_num_range())
+                {
+                    Ok(transactions) => check_transactions(&header, body.tx_count, &transactions),
+                    Err(err) => Err(format!("transactions unreadable: {err}")),
+                },
+                Err(message) => Err(message),
+            },
+            StaticFileSegment::Receipts => match header_and_body(provider, block)? {
+                Ok((header, body)) => {
+                    match static_files.receipts_by_tx_range(body.tx_num_range()) {
+                        Ok(receipts) => check_receipts(&header, body.tx_count, &receipts),
+                        Err(err) => Err(format!("receipts unreadable: {err}")),
+                    }
+                }
+                Err(message) => Err(message),
+            },
+        };
+
+        if let Err(message) = result {
+            findings.push(IntegrityFinding::new(block, check, message));
+        }
+    }
+
+    Ok(findings)
+}
+
+/// Repairs the corrupted static files, and reports the progress after each static file.
+///
+/// The reports are repaired in order, so headers have to come before transactions and receipts,
+/// like they're returned by [`verify_static_files`].
+pub fn repair_static_files<DB: Database>(
+    factory: &ProviderFactory<DB>,
+    reports: &[StaticFileReport],
+    source: &mut impl StaticFileRepairSource,
+    mut on_progress: impl FnMut(StaticFileProgress),
+) -> Result<Vec<StaticFileRepair>, StaticFileRepairError> {
+    let corrupted = reports.iter().filter(|report| report.is_corrupted()).collect::<Vec<_>>();
+    let total = corrupted.len();
+
+    let mut repairs = Vec::with_capacity(total);
+    for (processed, report) in corrupted.into_iter().enumerate() {
+        let repair =
+            repair_static_file(factory, report.segment, report.block_range.clone(), source)?;
+        on_progress(StaticFileProgress {
+            segment: report.segment,
+            block_range: report.block_range.clone(),
+            processed: processed + 1,
+            total,
+        });
+        repairs.push(repair);
+    }
+
+    Ok(repairs)
+}
+
+/// Rewrites a finalized static file from the intact data of each block in the static file, the
+/// database or the source.
+///
+/// The static files that can still be appended to can't be repaired. Transactions and receipts
+/// are checked against the headers, so corrupted headers have to be repaired first.
+pub fn repair_static_file<DB: Database>(
+    factory: &ProviderFactory<DB>,
+    segment: StaticFileSegment,
+    block_range: RangeInclusive<BlockNumber>,
+    source: &mut impl StaticFileRepairSource,
+) -> Result<StaticFileRepair, StaticFileRepairError> {
+    let provider = factory.provider()?.disable_long_read_transaction_safety();
+    let static_files = provider.static_file_provider();
+    let (start, end) = (*block_range.start(), *block_range.end());
+
+    let tx_range = match segment {
+        StaticFileSegment::Headers => None,
+        StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+            let first = provider
+                .block_body_indices(start)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(start))?;
+            let last = provider
+                .block_body_indices(end)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(end))?;
+            (last.next_tx_num() > first.first_tx_num)
+                .then(|| SegmentRangeInclusive::new(first.first_tx_num, last.next_tx_num() - 1))
+        }
+    };
+    let header = SegmentHeader::new(
+        find_fixed_range(start),
+        Some(SegmentRangeInclusive::new(start, end)),
+        tx_range,
+        segment,
+    );
+
+    let mut repair = StaticFileRepair {
+        segment,
+        block_range: block_range.clone(),
+        intact: 0,
+        from_database: 0,
+        from_source: 0,
+    };
+    static_files.replace_static_file(header, |writer| match segment {
+        StaticFileSegment::Headers => {
+            repair_headers(&provider, writer, block_range, source, &mut repair)
+        }
+        StaticFileSegment::Transactions => {
+            repair_transactions(&provider, writer, block_range, source, &mut repair)
+        }
+        StaticFileSegment::Receipts => {
+            repair_receipts(&provider, writer, block_range, source, &mut repair)
+        }
+    })?;
+
+    info!(
+        target: "reth::static_file",
+        %segment,
+        block_range = ?repair.block_range,
+        intact = repair.intact,
+        from_database = repair.from_database,
+        from_source = repair.from_source,
+        "Repaired static file"
+    );
+    Ok(repair)
+}
+
+fn repair_headers<TX: DbTx>(
+    provider: &DatabaseProvider<TX>,
+    writer: &mut StaticFileRowWriter,
+    block_range: RangeInclusive<BlockNumber>,
+    source: &mut impl StaticFileRepairSource,
+    repair: &mut StaticFileRepair,
+) -> Result<(), StaticFileRepairError> {
+    let static_files = provider.static_file_provider();
+    let start = *block_range.start();
+    let (Some(mut parent_hash), Some(mut td)) = parent_of(static_files, start) else {
+        return Err(StaticFileRepairError::Unavailable {
+            segment: StaticFileSegment::Headers,
+            block: start.saturating_sub(1),
+        })
+    };
+
+    for block in block_range {
+        let mut found = None;
+        for origin in ORIGINS {
+            let header = match origin {
+                Origin::StaticFile => static_files.header_by_number(block).ok().flatten(),
+                Origin::Database => {
+                    provider.tx_ref().get::<tables::Headers>(block).map_err(ProviderError::from)?
+                }
+                Origin::Source => source.block(block)?.map(|block| block.header.unseal()),
+            };
+            let Some(header) = header else { continue };
+            if let Ok(hash) = check_header(provider, block, &header, Some(parent_hash))? {
+                found = Some((origin, header, hash));
+                break
+            }
+        }
+        let (origin, header, hash) = found.ok_or(StaticFileRepairError::Unavailable {
+            segment: StaticFileSegment::Headers,
+            block,
+        })?;
+
+        td += header.difficulty;
+        writer.append_column(&header)?;
+        writer.append_column(CompactU256::from(td))?;
+        writer.append_column(hash)?;
+        repair.record(origin);
+        parent_hash = hash;
+    }
+
+    Ok(())
+}
+
+fn repair_transactions<TX: DbTx>(
+    provider: &DatabaseProvider<TX>,
+    writer: &mut StaticFileRowWriter,
+    block_range: RangeInclusive<BlockNumber>,
+    source: &mut impl StaticFileRepairSource,
+    repair: &mut StaticFileRepair,
+) -> Result<(), StaticFileRepairError> {
+    let static_files = provider.static_file_provider();
+
+    for block in block_range {
+        let (header, body) = header_and_body(provider, block)?.map_err(|_| {
+            StaticFileRepairError::Unavailable { segment: StaticFileSegment::Headers, block }
+        })?;
+
+        let mut found = None;
+        for origin in ORIGINS {
+            let transactions = match origin {
+                Origin::StaticFile => {
+                    static_files.transactions_by_tx_range(body.tx_num_range()).ok()
+                }
+                Origin::Database => {
+                    Some(database_rows::<_, tables::Transactions>(provider, body.tx_num_range())?)
+                }
+                Origin::Source => source
+                    .block(block)?
+                    .map(|block| block.body.into_iter().map(Into::into).collect()),
+            };
+            let Some(transactions) = transactions else { continue };
+            if check_transactions(&header, body.tx_count, &transactions).is_ok() {
+                found = Some((origin, transactions));
+                break
+            }
+        }
+        let (origin, transactions) = found.ok_or(StaticFileRepairError::Unavailable {
+            segment: StaticFileSegment::Transactions,
+            block,
+        })?;
+
+        for transaction in &transactions {
+            writer.append_column(transaction)?;
+        }
+        repair.record(origin);
+    }
+
+    Ok(())
+}
+
+fn repair_receipts<TX: DbTx>(
+    provider: &DatabaseProvider<TX>,
+    writer: &mut StaticFileRowWriter,
+    block_range: RangeInclusive<BlockNumber>,
+    source: &mut impl StaticFileRepairSource,
+    repair: &mut StaticFileRepair,
+) -> Result<(), StaticFileRepairError> {
+    let static_files = provider.static_file_provider();
+
+    for block in block_range {
+        let (header, body) = header_and_body(provider, block)?.map_err(|_| {
+            StaticFileRepairError::Unavailable { segment: StaticFileSegment::Headers, block }
+        })?;
+
+        let mut found = None;
+        for origin in ORIGINS {
+            let receipts = match origin {
+                Origin::StaticFile => static_files.receipts_by_tx_range(body.tx_num_range()).ok(),
+                Origin::Database => {
+                    Some(database_rows::<_, tables::Receipts>(provider, body.tx_num_range())?)
+                }
+                Origin::Source => source.receipts(&header)?,
+            };
+            let Some(receipts) = receipts else { continue };
+            if check_receipts(&header, body.tx_count, &receipts).is_ok() {
+                found = Some((origin, receipts));
+                break
+            }
+        }
+        let (origin, receipts) = found.ok_or(StaticFileRepairError::Unavailable {
+            segment: StaticFileSegment::Receipts,
+            block,
+        })?;
+
+        for receipt in &receipts {
+            writer.append_column(receipt)?;
+        }
+        repair.record(origin);
+    }
+
+    Ok(())
+}
+
+/// Lists the static files of the segments, headers first.
+fn list_static_files(
+    static_files: &StaticFileProvider,
+    segments: &[StaticFileSegment],
+) -> ProviderResult<Vec<(StaticFileSegment, RangeInclusive<BlockNumber>)>> {
+    let mut listed = iter_static_files(static_files.directory())
+        .map_err(|err| ProviderError::NippyJar(err.to_string()))?;
+
+    let mut list = Vec::new();
+    for segment in
+        [StaticFileSegment::Headers, StaticFileSegment::Transactions, StaticFileSegment::Receipts]
+    {
+        if !segments.contains(&segment) {
+            continue
+        }
+        let mut block_ranges = listed
+            .remove(&segment)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(block_range, _)| block_range)
+            .map(|block_range| block_range.start()..=block_range.end())
+            .collect::<Vec<_>>();
+        block_ranges.sort_unstable_by_key(|block_range| *block_range.start());
+        list.extend(block_ranges.into_iter().map(|block_range| (segment, block_range)));
+    }
+
+    Ok(list)
+}
+
+/// Returns the stored hash and total difficulty of the parent of the block.
+fn parent_of(
+    static_files: &StaticFileProvider,
+    block: BlockNumber,
+) -> (Option<B256>, Option<U256>) {
+    match block.checked_sub(1) {
+        Some(parent) => stored_hash_and_td(static_files, parent),
+        None => (Some(B256::ZERO), Some(U256::ZERO)),
+    }
+}
+
+/// Returns the hash and total difficulty of the block stored in the static files, if readable.
+fn stored_hash_and_td(
+    static_files: &StaticFileProvider,
+    block: BlockNumber,
+) -> (Option<B256>, Option<U256>) {
+    (
+        static_files.block_hash(block).ok().flatten(),
+        static_files.header_td_by_number(block).ok().flatten(),
+    )
+}
+
+/// Checks that the header is the header of the block that is indexed in the database, and that
+/// it links to its parent.
+///
+/// Returns the hash of the header, or what is wrong with it.
+fn check_header<TX: DbTx>(
+    provider: &DatabaseProvider<TX>,
+    block: BlockNumber,
+    header: &Header,
+    parent_hash: Option<B256>,
+) -> ProviderResult<Result<B256, String>> {
+    if header.number != block {
+        return Ok(Err(format!("header of block {} found", header.number)))
+    }
+    if let Some(parent_hash) = parent_hash.filter(|parent_hash| *parent_hash != header.parent_hash)
+    {
+        return Ok(Err(format!(
+            "parent hash {} differs from the hash {parent_hash} of the previous header",
+            header.parent_hash
+        )))
+    }
+    let hash = header.hash_slow();
+    if provider.block_number(hash)? != Some(block) {
+        return Ok(Err(format!("header hash {hash} isn't indexed")))
+    }
+    Ok(Ok(hash))
+}
+
+/// Returns the header and the body indices of the block that its transactions and receipts are
+/// checked against, or what is missing.
+fn header_and_body<TX: DbTx>(
+    provider: &DatabaseProvider<TX>,
+    block: BlockNumber,
+) -> ProviderResult<Result<(Header, StoredBlockBodyIndices), String>> {
+    let Some(header) = provider.static_file_provider().header_by_number(block).ok().flatten()
+    else {
+        return Ok(Err("header unreadable".to_string()))
+    };
+    let Some(body) = provider.block_body_indices(block)? else {
+        return Ok(Err("body indices missing".to_string()))
+    };
+    Ok(Ok((header, body)))
+}
+
+/// Checks the transactions of a block against its header.
+fn check_transactions(
+    header: &Header,
+    tx_count: u64,
+    transactions: &[TransactionSignedNoHash],
+) -> Result<(), String> {
+    if transactions.len() as u64 != tx_count {
+        return Err(format!("{} of {tx_count} transactions found", transactions.len()))
+    }
+    let transactions = transactions.iter().map(|tx| tx.clone().with_hash()).collect::<Vec<_>>();
+    if calculate_transaction_root(&transactions) != header.transactions_root {
+        return Err("transactions root mismatch".to_string())
+    }
+    Ok(())
+}
+
+/// Checks the receipts of a block against its header.
+fn check_receipts(header: &Header, tx_count: u64, receipts: &[Receipt]) -> Result<(), String> {
+    if receipts.len() as u64 != tx_count {
+        return Err(format!("{} of {tx_count} receipts found", receipts.len()))
+    }
+    if receipts.windows(2).any(|pair| pair[1].cumulative_gas_used < pair[0].cumulative_gas_used) {
+        return Err("cumulative gas used decreases".to_string())
+    }
+    if let Some(receipt) =
+        receipts.last().filter(|receipt| receipt.cumulative_gas_used != header.gas_used)
+    {
+        return Err(format!(
+            "cumulative gas used {} differs from gas used {} of the header",
+            receipt.cumulative_gas_used, header.gas_used
+        ))
+    }
+    Ok(())
+}
+
+/// Returns the rows of the table in the transaction range.
+fn database_rows<TX: DbTx, T: Table<Key = TxNumber>>(
+    provider: &DatabaseProvider<TX>,
+    range: Range<TxNumber>,
+) -> ProviderResult<Vec<T::Value>> {
+    Ok(provider
+        .tx_ref()
+        .cursor_read::<T>()?
+        .walk_range(range)?
+        .map(|entry| entry.map(|(_, value)| value))
+        .collect::<Result<_, _>>()?)
+}
+
+impl StaticFileRepair {
+    fn record(&mut self, origin: Origin) {
+        match origin {
+            Origin::StaticFile => self.intact += 1,
+            Origin::Database => self.from_database += 1,
+            Origin::Source => self.from_source += 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db_api::{models::StoredBlockBodyIndices, transaction::DbTxMut};
+    use reth_primitives::static_file::BLOCKS_PER_STATIC_FILE;
+    use reth_provider::{providers::StaticFileWriter, test_utils::create_test_provider_factory};
+    use reth_testing_utils::generators::{self, random_block_range};
+    use std::collections::HashMap;
+
+    impl StaticFileRepairSource for HashMap<BlockNumber, SealedBlock> {
+        fn block(&mut self, number: BlockNumber) -> ProviderResult<Option<SealedBlock>> {
+            Ok(self.get(&number).cloned())
+        }
+    }
+
+    /// Writes the first static file of the segment, finalized by an empty block of the next one.
+    fn write_finalized<DB: Database>(
+        factory: &ProviderFactory<DB>,
+        segment: StaticFileSegment,
+        block_range: RangeInclusive<BlockNumber>,
+        tx_range: Option<SegmentRangeInclusive>,
+        write: impl FnOnce(&mut StaticFileRowWriter) -> ProviderResult<()>,
+    ) {
+        let static_files = factory.static_file_provider();
+        {
+            let mut writer = static_files.latest_writer(segment).unwrap();
+            writer.set_block_range(0..=BLOCKS_PER_STATIC_FILE - 1);
+            writer.increment_block(BLOCKS_PER_STATIC_FILE).unwrap();
+            writer.commit().unwrap();
+        }
+        let header = SegmentHeader::new(
+            find_fixed_range(0),
+            Some(SegmentRangeInclusive::new(*block_range.start(), *block_range.end())),
+            tx_range,
+            segment,
+        );
+        static_files.replace_static_file(header, write).unwrap();
+    }
+
+    #[test]
+    fn repairs_corrupted_static_files() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+        let blocks = random_block_range(&mut rng, 0..=9, B256::ZERO, 1..3, None, None);
+
+        let provider = factory.provider_rw().unwrap();
+        let mut next_tx = 0;
+        for block in &blocks {
+            provider.tx_ref().put::<tables::HeaderNumbers>(block.hash(), block.number).unwrap();
+            let body =
+                StoredBlockBodyIndices { first_tx_num: next_tx, tx_count: block.body.len() as u64 };
+            next_tx = body.next_tx_num();
+            provider.tx_ref().put::<tables::BlockBodyIndices>(block.number, body).unwrap();
+        }
+        provider.commit().unwrap();
+
+        // The stored hash of block 4 and the header of block 6 are corrupted
+        write_finalized(&factory, StaticFileSegment::Headers, 0..=9, None, |writer| {
+            let mut td = U256::ZERO;
+            for block in &blocks {
+                td += block.difficulty;
+                let mut header = block.header.clone().unseal();
+                if block.number == 6 {
+                    header.gas_used += 1;
+                }
+                writer.append_column(&header)?;
+                writer.append_column(CompactU256::from(td))?;
+                writer.append_column(if block.number == 4 { B256::ZERO } else { block.hash() })?;
+            }
+            Ok(())
+        });
+        // The first transaction of block 2 is replaced by the one of block 3
+        write_finalized(
+            &factory,
+            StaticFileSegment::Transactions,
+            0..=9,
+            Some(SegmentRangeInclusive::new(0, next_tx - 1)),
+            |writer| {
+                for block in &blocks {
+                    for (index, transaction) in block.body.iter().enumerate() {
+                        let transaction = if block.number == 2 && index == 0 {
+                            &blocks[3].body[0]
+                        } else {
+                            transaction
+                        };
+                        writer.append_column(TransactionSignedNoHash::from(transaction.clone()))?;
+                    }
+                }
+                Ok(())
+            },
+        );
+
+        // The empty blocks finalizing the first static files aren't checked
+        let segments = [StaticFileSegment::Headers, StaticFileSegment::Transactions];
+        let verify = || {
+            let mut progress = Vec::new();
+            let reports =
+                verify_static_files(&factory, &segments, |report| progress.push(report.processed))
+                    .unwrap();
+            assert_eq!(progress, vec![1, 2, 3, 4]);
+            reports
+                .into_iter()
+                .filter(|report| *report.block_range.start() == 0)
+                .collect::<Vec<_>>()
+        };
+
+        let reports = verify();
+        assert_eq!(
+            reports
+                .iter()
+                .flat_map(|report| report
+                    .findings
+                    .iter()
+                    .map(|finding| (finding.block, finding.check)))
+                .collect::<Vec<_>>(),
+            vec![
+                (4, IntegrityCheck::Headers),
+                (6, IntegrityCheck::Headers),
+                (2, IntegrityCheck::Transactions)
+            ]
+        );
+
+        // The header of block 6 is neither intact nor in the database
+        assert!(matches!(
+            repair_static_files(&factory, &reports, &mut (), |_| {}),
+            Err(StaticFileRepairError::Unavailable {
+                segment: StaticFileSegment::Headers,
+                block: 6
+            })
+        ));
+
+        let mut source =
+            blocks.iter().map(|block| (block.number, block.clone())).collect::<HashMap<_, _>>();
+        let repairs = repair_static_files(&factory, &reports, &mut source, |_| {}).unwrap();
+        assert_eq!(
+            repairs
+                .iter()
+                .map(|repair| (repair.segment, repair.intact, repair.from_source))
+                .collect::<Vec<_>>(),
+            vec![(StaticFileSegment::Headers, 9, 1), (StaticFileSegment::Transactions, 9, 1)]
+        );
+
+        let reports = verify();
+        assert!(reports.iter().all(|report| !report.is_corrupted()), "{reports:?}");
+        assert_eq!(
+            factory.static_file_provider().transactions_by_tx_range(0..next_tx).unwrap().len(),
+            next_tx as usize
+        );
+    }
+}
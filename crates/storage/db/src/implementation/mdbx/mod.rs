@@ -7,12 +7,11 @@ use crate::{
     utils::default_page_size,
     DatabaseError,
 };
-use eyre::Context;
 use metrics::{gauge, Label};
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
     database::Database,
-    database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics},
+    database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics, TableStats},
     models::client_version::ClientVersion,
     transaction::{DbTx, DbTxMut},
 };
@@ -171,54 +170,38 @@ impl DatabaseMetrics for DatabaseEnv {
     fn gauge_metrics(&self) -> Vec<(&'static str, f64, Vec<Label>)> {
         let mut metrics = Vec::new();
 
-        let _ = self
-            .view(|tx| {
-                for table in Tables::ALL.iter().map(Tables::name) {
-                    let table_db = tx.inner.open_db(Some(table)).wrap_err("Could not open db.")?;
-
-                    let stats = tx
-                        .inner
-                        .db_stat(&table_db)
-                        .wrap_err(format!("Could not find table: {table}"))?;
-
-                    let page_size = stats.page_size() as usize;
-                    let leaf_pages = stats.leaf_pages();
-                    let branch_pages = stats.branch_pages();
-                    let overflow_pages = stats.overflow_pages();
-                    let num_pages = leaf_pages + branch_pages + overflow_pages;
-                    let table_size = page_size * num_pages;
-                    let entries = stats.entries();
-
-                    metrics.push((
-                        "db.table_size",
-                        table_size as f64,
-                        vec![Label::new("table", table)],
-                    ));
-                    metrics.push((
-                        "db.table_pages",
-                        leaf_pages as f64,
-                        vec![Label::new("table", table), Label::new("type", "leaf")],
-                    ));
-                    metrics.push((
-                        "db.table_pages",
-                        branch_pages as f64,
-                        vec![Label::new("table", table), Label::new("type", "branch")],
-                    ));
-                    metrics.push((
-                        "db.table_pages",
-                        overflow_pages as f64,
-                        vec![Label::new("table", table), Label::new("type", "overflow")],
-                    ));
-                    metrics.push((
-                        "db.table_entries",
-                        entries as f64,
-                        vec![Label::new("table", table)],
-                    ));
-                }
-
-                Ok::<(), eyre::Report>(())
-            })
-            .map_err(|error| error!(%error, "Failed to read db table stats"));
+        if let Ok(tables) =
+            self.table_stats().map_err(|error| error!(%error, "Failed to read db table stats"))
+        {
+            for stats in tables {
+                let table = stats.table;
+                metrics.push((
+                    "db.table_size",
+                    stats.size() as f64,
+                    vec![Label::new("table", table)],
+                ));
+                metrics.push((
+                    "db.table_pages",
+                    stats.leaf_pages as f64,
+                    vec![Label::new("table", table), Label::new("type", "leaf")],
+                ));
+                metrics.push((
+                    "db.table_pages",
+                    stats.branch_pages as f64,
+                    vec![Label::new("table", table), Label::new("type", "branch")],
+                ));
+                metrics.push((
+                    "db.table_pages",
+                    stats.overflow_pages as f64,
+                    vec![Label::new("table", table), Label::new("type", "overflow")],
+                ));
+                metrics.push((
+                    "db.table_entries",
+                    stats.entries as f64,
+                    vec![Label::new("table", table)],
+                ));
+            }
+        }
 
         if let Ok(freelist) =
             self.freelist().map_err(|error| error!(%error, "Failed to read db.freelist"))
@@ -238,6 +221,26 @@ impl DatabaseMetrics for DatabaseEnv {
 
         metrics
     }
+
+    fn table_stats(&self) -> Result<Vec<TableStats>, DatabaseError> {
+        let tx = self.inner.begin_ro_txn().map_err(|e| DatabaseError::InitTx(e.into()))?;
+        Tables::ALL
+            .iter()
+            .map(|table| {
+                let table_db =
+                    tx.open_db(Some(table.name())).map_err(|e| DatabaseError::Open(e.into()))?;
+                let stats = tx.db_stat(&table_db).map_err(|e| DatabaseError::Stats(e.into()))?;
+                Ok(TableStats {
+                    table: table.name(),
+                    entries: stats.entries(),
+                    page_size: stats.page_size() as usize,
+                    leaf_pages: stats.leaf_pages(),
+                    branch_pages: stats.branch_pages(),
+                    overflow_pages: stats.overflow_pages(),
+                })
+            })
+            .collect()
+    }
 }
 
 impl DatabaseMetadata for DatabaseEnv {
@@ -0,0 +1,375 @@
+//! Cursor wrapper for redb.
+
+use super::{
+    error_info,
+    table::{Layout, RawEntry, RawRange, TableHandle},
+    tx::{TransactionKind, RW},
+};
+use crate::{tables::utils::*, DatabaseError};
+use reth_db_api::{
+    common::{PairResult, ValueOnlyResult},
+    cursor::{
+        DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, DupWalker, RangeWalker,
+        ReverseWalker, Walker,
+    },
+    table::{Compress, DupSort, Encode, Table},
+};
+use reth_storage_errors::db::{DatabaseErrorInfo, DatabaseWriteError, DatabaseWriteOperation};
+use std::{
+    borrow::Cow,
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+};
+
+/// Read only Cursor.
+pub type CursorRO<T> = Cursor<super::RO, T>;
+/// Read write cursor.
+pub type CursorRW<T> = Cursor<RW, T>;
+
+/// Position of a [`Cursor`].
+#[derive(Debug)]
+enum Position {
+    /// The cursor wasn't positioned yet, or was moved before the first entry.
+    Unset,
+    /// The cursor is at the entry with the raw key.
+    At(Vec<u8>),
+    /// The cursor was moved past the last entry.
+    End,
+}
+
+/// Cursor wrapper to access KV items.
+///
+/// redb has no cursors, so the cursor remembers the raw key of its current entry and looks up
+/// its neighbours on every move.
+#[derive(Debug)]
+pub struct Cursor<K: TransactionKind, T: Table> {
+    /// The redb table.
+    table: TableHandle,
+    /// Layout of the table in redb.
+    layout: Layout,
+    /// Current position of the cursor.
+    position: Position,
+    /// Phantom data to enforce encoding/decoding.
+    _dbi: PhantomData<(K, T)>,
+}
+
+impl<K: TransactionKind, T: Table> Cursor<K, T> {
+    pub(crate) fn new(table: TableHandle) -> Self {
+        Self { table, layout: Layout::of::<T>(), position: Position::Unset, _dbi: PhantomData }
+    }
+
+    /// Moves the cursor to the entry, or past the last entry if there is none, and decodes it.
+    fn move_to(&mut self, entry: Result<Option<RawEntry>, DatabaseErrorInfo>) -> PairResult<T> {
+        match entry.map_err(DatabaseError::Read)? {
+            Some(entry) => {
+                self.position = Position::At(entry.0.clone());
+                Ok(Some(self.decode(entry)?))
+            }
+            None => {
+                self.position = Position::End;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Moves the cursor to the entry if there is one, and decodes it.
+    fn move_to_some(
+        &mut self,
+        entry: Result<Option<RawEntry>, DatabaseErrorInfo>,
+    ) -> PairResult<T> {
+        match entry.map_err(DatabaseError::Read)? {
+            Some(entry) => self.move_to(Ok(Some(entry))),
+            None => Ok(None),
+        }
+    }
+
+    /// Decodes the raw entry.
+    fn decode(&self, entry: RawEntry) -> Result<(T::Key, T::Value), DatabaseError> {
+        let (key, value) = self.layout.decode(entry);
+        decoder::<T>((Cow::Owned(key), Cow::Owned(value)))
+    }
+
+    /// Returns the encoded key of the current entry.
+    fn current_key(&self) -> Option<Vec<u8>> {
+        match &self.position {
+            Position::At(raw_key) => Some(self.layout.decode((raw_key.clone(), Vec::new())).0),
+            _ => None,
+        }
+    }
+
+    /// Moves the cursor to the first entry with a key greater than or equal to the encoded key.
+    fn seek_raw(&mut self, key: &[u8]) -> PairResult<T> {
+        let (start, _) = self.layout.encode(key, &[]);
+        let entry = self.table.first_in(&(Bound::Included(start), Bound::Unbounded));
+        self.move_to(entry)
+    }
+
+    /// Moves the cursor to the first entry with the encoded key and a value greater than or equal
+    /// to the encoded subkey.
+    fn seek_subkey_raw(&mut self, key: &[u8], subkey: &[u8]) -> PairResult<T> {
+        let (start, _) = self.layout.encode(key, subkey);
+        let entry = self.table.first_in(&(Bound::Included(start), self.layout.key_range(key).1));
+        self.move_to_some(entry)
+    }
+
+    /// Returns the error of a failed write.
+    fn write_error(
+        info: DatabaseErrorInfo,
+        operation: DatabaseWriteOperation,
+        key: impl Into<Vec<u8>>,
+    ) -> DatabaseError {
+        DatabaseWriteError { info, operation, table_name: T::NAME, key: key.into() }.into()
+    }
+}
+
+impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
+    fn first(&mut self) -> PairResult<T> {
+        let entry = self.table.first_in(&(Bound::Unbounded, Bound::Unbounded));
+        self.move_to(entry)
+    }
+
+    fn seek_exact(&mut self, key: <T as Table>::Key) -> PairResult<T> {
+        let entry = self.table.first_in(&self.layout.key_range(key.encode().as_ref()));
+        self.move_to(entry)
+    }
+
+    fn seek(&mut self, key: <T as Table>::Key) -> PairResult<T> {
+        self.seek_raw(key.encode().as_ref())
+    }
+
+    fn next(&mut self) -> PairResult<T> {
+        let range = match &self.position {
+            Position::Unset => (Bound::Unbounded, Bound::Unbounded),
+            Position::At(raw_key) => (Bound::Excluded(raw_key.clone()), Bound::Unbounded),
+            Position::End => return Ok(None),
+        };
+        let entry = self.table.first_in(&range);
+        self.move_to(entry)
+    }
+
+    fn prev(&mut self) -> PairResult<T> {
+        let range = match &self.position {
+            Position::Unset | Position::End => (Bound::Unbounded, Bound::Unbounded),
+            Position::At(raw_key) => (Bound::Unbounded, Bound::Excluded(raw_key.clone())),
+        };
+        match self.table.last_in(&range).map_err(DatabaseError::Read)? {
+            Some(entry) => self.move_to(Ok(Some(entry))),
+            None => {
+                self.position = Position::Unset;
+                Ok(None)
+            }
+        }
+    }
+
+    fn last(&mut self) -> PairResult<T> {
+        let entry = self.table.last_in(&(Bound::Unbounded, Bound::Unbounded));
+        self.move_to(entry)
+    }
+
+    fn current(&mut self) -> PairResult<T> {
+        let Position::At(raw_key) = &self.position else { return Ok(None) };
+        let range: RawRange = (Bound::Included(raw_key.clone()), Bound::Included(raw_key.clone()));
+        self.table
+            .first_in(&range)
+            .map_err(DatabaseError::Read)?
+            .map(|e| self.decode(e))
+            .transpose()
+    }
+
+    fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
+        let start = if let Some(start_key) = start_key {
+            self.seek(start_key).transpose()
+        } else {
+            self.first().transpose()
+        };
+
+        Ok(Walker::new(self, start))
+    }
+
+    fn walk_range(
+        &mut self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<RangeWalker<'_, T, Self>, DatabaseError> {
+        let start = match range.start_bound().cloned() {
+            Bound::Included(key) => self.seek(key),
+            Bound::Excluded(_key) => {
+                unreachable!("Rust doesn't allow for Bound::Excluded in starting bounds");
+            }
+            Bound::Unbounded => self.first(),
+        }
+        .transpose();
+        Ok(RangeWalker::new(self, start, range.end_bound().cloned()))
+    }
+
+    fn walk_back(
+        &mut self,
+        start_key: Option<T::Key>,
+    ) -> Result<ReverseWalker<'_, T, Self>, DatabaseError> {
+        let start =
+            if let Some(start_key) = start_key { self.seek(start_key) } else { self.last() }
+                .transpose();
+
+        Ok(ReverseWalker::new(self, start))
+    }
+}
+
+impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
+    /// Returns the next `(key, value)` pair of a DUPSORT table.
+    fn next_dup(&mut self) -> PairResult<T> {
+        if self.layout == Layout::Plain {
+            return Ok(None)
+        }
+        let (Position::At(raw_key), Some(key)) = (&self.position, self.current_key()) else {
+            return Ok(None)
+        };
+        let range = (Bound::Excluded(raw_key.clone()), self.layout.key_range(&key).1);
+        let entry = self.table.first_in(&range);
+        self.move_to_some(entry)
+    }
+
+    /// Returns the next `(key, value)` pair skipping the duplicates.
+    fn next_no_dup(&mut self) -> PairResult<T> {
+        match &self.position {
+            Position::Unset => self.first(),
+            Position::At(_) => {
+                let key = self.current_key().unwrap_or_default();
+                match self.layout.after_key(&key) {
+                    Some(range) => {
+                        let entry = self.table.first_in(&range);
+                        self.move_to(entry)
+                    }
+                    None => self.move_to(Ok(None)),
+                }
+            }
+            Position::End => Ok(None),
+        }
+    }
+
+    /// Returns the next `value` of a duplicate `key`.
+    fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
+        Ok(self.next_dup()?.map(|(_, value)| value))
+    }
+
+    fn seek_by_key_subkey(
+        &mut self,
+        key: <T as Table>::Key,
+        subkey: <T as DupSort>::SubKey,
+    ) -> ValueOnlyResult<T> {
+        Ok(self
+            .seek_subkey_raw(key.encode().as_ref(), subkey.encode().as_ref())?
+            .map(|(_, value)| value))
+    }
+
+    /// Depending on its arguments, returns an iterator starting at:
+    /// - Some(key), Some(subkey): a `key` item whose data is >= than `subkey`
+    /// - Some(key), None: first item of a specified `key`
+    /// - None, Some(subkey): like first case, but in the first key
+    /// - None, None: first item in the table of a DUPSORT table.
+    fn walk_dup(
+        &mut self,
+        key: Option<T::Key>,
+        subkey: Option<T::SubKey>,
+    ) -> Result<DupWalker<'_, T, Self>, DatabaseError> {
+        let start = match (key, subkey) {
+            (Some(key), Some(subkey)) => {
+                self.seek_subkey_raw(key.encode().as_ref(), subkey.encode().as_ref())
+            }
+            (Some(key), None) => self.seek_exact(key),
+            (None, Some(subkey)) => match self.first()? {
+                Some((key, _)) => {
+                    self.seek_subkey_raw(key.encode().as_ref(), subkey.encode().as_ref())
+                }
+                None => Ok(None),
+            },
+            (None, None) => self.first(),
+        }
+        .transpose();
+
+        Ok(DupWalker::<'_, T, Self> { cursor: self, start })
+    }
+}
+
+impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
+    /// Database operation that will update an existing row if a specified value already
+    /// exists in a table, and insert a new row if the specified value doesn't already exist
+    ///
+    /// For a DUPSORT table, `upsert` will not actually update-or-insert. If the key already exists,
+    /// it will append the value to the subkey, even if the subkeys are the same. So if you want
+    /// to properly upsert, you'll need to `seek_exact` & `delete_current` if the key+subkey was
+    /// found, before calling `upsert`.
+    fn upsert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key = key.encode();
+        let entry = self.layout.encode(key.as_ref(), value.compress().as_ref());
+        self.table
+            .insert(&entry)
+            .map_err(|info| Self::write_error(info, DatabaseWriteOperation::CursorUpsert, key))?;
+        self.position = Position::At(entry.0);
+        Ok(())
+    }
+
+    fn insert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key = key.encode();
+        let entry = self.layout.encode(key.as_ref(), value.compress().as_ref());
+        let result = match self.table.first_in(&self.layout.key_range(key.as_ref())) {
+            Ok(Some(_)) => Err(error_info("key already exists")),
+            Ok(None) => self.table.insert(&entry),
+            Err(info) => Err(info),
+        };
+        result
+            .map_err(|info| Self::write_error(info, DatabaseWriteOperation::CursorInsert, key))?;
+        self.position = Position::At(entry.0);
+        Ok(())
+    }
+
+    /// Appends the data to the end of the table. Consequently, the append operation
+    /// will fail if the inserted key is less than the last table key
+    fn append(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key = key.encode();
+        let entry = self.layout.encode(key.as_ref(), value.compress().as_ref());
+        let result = match self.table.last_in(&(Bound::Unbounded, Bound::Unbounded)) {
+            Ok(Some((last, _))) if last >= entry.0 => {
+                Err(error_info("key is not greater than the last key"))
+            }
+            Ok(_) => self.table.insert(&entry),
+            Err(info) => Err(info),
+        };
+        result
+            .map_err(|info| Self::write_error(info, DatabaseWriteOperation::CursorAppend, key))?;
+        self.position = Position::At(entry.0);
+        Ok(())
+    }
+
+    fn delete_current(&mut self) -> Result<(), DatabaseError> {
+        let Position::At(raw_key) = &self.position else {
+            return Err(DatabaseError::Delete(error_info("cursor is not positioned")))
+        };
+        self.table.remove(raw_key).map_err(DatabaseError::Delete)?;
+        Ok(())
+    }
+}
+
+impl<T: DupSort> DbDupCursorRW<T> for Cursor<RW, T> {
+    fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
+        let Some(key) = self.current_key() else {
+            return Err(DatabaseError::Delete(error_info("cursor is not positioned")))
+        };
+        self.table.remove_range(&self.layout.key_range(&key)).map_err(DatabaseError::Delete)
+    }
+
+    fn append_dup(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key = key.encode();
+        let entry = self.layout.encode(key.as_ref(), value.compress().as_ref());
+        let result = match self.table.last_in(&self.layout.key_range(key.as_ref())) {
+            Ok(Some((last, _))) if last >= entry.0 => {
+                Err(error_info("value is not greater than the last value of the key"))
+            }
+            Ok(_) => self.table.insert(&entry),
+            Err(info) => Err(info),
+        };
+        result.map_err(|info| {
+            Self::write_error(info, DatabaseWriteOperation::CursorAppendDup, key)
+        })?;
+        self.position = Position::At(entry.0);
+        Ok(())
+    }
+}
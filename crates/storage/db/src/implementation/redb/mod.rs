@@ -0,0 +1,284 @@
+//! Module that interacts with redb.
+
+use crate::{tables::Tables, DatabaseError};
+use reth_db_api::database::Database;
+use reth_storage_errors::db::DatabaseErrorInfo;
+use std::{
+    fmt,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use table::table_definition;
+use tx::Tx;
+
+pub mod cursor;
+mod table;
+pub mod tx;
+
+pub use tx::{TransactionKind, RO, RW};
+
+/// Name of the redb database file inside of the database directory.
+pub const REDB_FILE_NAME: &str = "reth.redb";
+
+/// Error code of all redb errors, which don't come with codes of their own.
+const REDB_ERROR_CODE: i32 = -1;
+
+/// Converts a redb error into [`DatabaseErrorInfo`].
+pub(crate) fn error_info(error: impl fmt::Display) -> DatabaseErrorInfo {
+    DatabaseErrorInfo { message: error.to_string(), code: REDB_ERROR_CODE }
+}
+
+/// Wrapper for the redb database.
+#[derive(Debug)]
+pub struct DatabaseEnv {
+    /// Inner redb database.
+    inner: redb::Database,
+}
+
+impl Database for DatabaseEnv {
+    type TX = Tx<RO>;
+    type TXMut = Tx<RW>;
+
+    fn tx(&self) -> Result<Self::TX, DatabaseError> {
+        Ok(Tx::new_ro(self.inner.begin_read().map_err(|e| DatabaseError::InitTx(error_info(e)))?))
+    }
+
+    fn tx_mut(&self) -> Result<Self::TXMut, DatabaseError> {
+        Ok(Tx::new_rw(Arc::new(Mutex::new(Some(
+            self.inner.begin_write().map_err(|e| DatabaseError::InitTx(error_info(e)))?,
+        )))))
+    }
+}
+
+impl DatabaseEnv {
+    /// Opens the database in the directory, creating it if it doesn't exist.
+    pub fn open(path: &Path) -> Result<Self, DatabaseError> {
+        let inner = redb::Database::create(path.join(REDB_FILE_NAME))
+            .map_err(|e| DatabaseError::Open(error_info(e)))?;
+        Ok(Self { inner })
+    }
+
+    /// Creates all the tables defined in [`Tables`], if necessary.
+    pub fn create_tables(&self) -> Result<(), DatabaseError> {
+        let tx = self.inner.begin_write().map_err(|e| DatabaseError::InitTx(error_info(e)))?;
+
+        for table in Tables::ALL {
+            tx.open_table(table_definition(table.name()))
+                .map_err(|e| DatabaseError::CreateTable(error_info(e)))?;
+        }
+
+        tx.commit().map_err(|e| DatabaseError::Commit(error_info(e)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tables::{
+        AccountChangeSets, CanonicalHeaders, PlainAccountState, PlainStorageState,
+    };
+    use reth_db_api::{
+        cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+        models::AccountBeforeTx,
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_primitives::{Account, Address, StorageEntry, B256, U256};
+
+    fn create_test_db() -> (tempfile::TempDir, DatabaseEnv) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = DatabaseEnv::open(dir.path()).unwrap();
+        db.create_tables().unwrap();
+        (dir, db)
+    }
+
+    #[test]
+    fn db_put_get_delete() {
+        let (_dir, db) = create_test_db();
+        let value = B256::with_last_byte(1);
+
+        let tx = db.tx_mut().unwrap();
+        tx.put::<CanonicalHeaders>(1, value).unwrap();
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+        assert_eq!(tx.get::<CanonicalHeaders>(1).unwrap(), Some(value));
+        assert_eq!(tx.get::<CanonicalHeaders>(2).unwrap(), None);
+        assert_eq!(tx.entries::<CanonicalHeaders>().unwrap(), 1);
+        drop(tx);
+
+        let tx = db.tx_mut().unwrap();
+        assert!(tx.delete::<CanonicalHeaders>(1, None).unwrap());
+        assert!(!tx.delete::<CanonicalHeaders>(1, None).unwrap());
+        tx.commit().unwrap();
+
+        assert_eq!(db.tx().unwrap().get::<CanonicalHeaders>(1).unwrap(), None);
+    }
+
+    #[test]
+    fn db_aborted_write_is_discarded() {
+        let (_dir, db) = create_test_db();
+
+        let tx = db.tx_mut().unwrap();
+        tx.put::<CanonicalHeaders>(1, B256::ZERO).unwrap();
+        tx.abort();
+
+        assert_eq!(db.tx().unwrap().entries::<CanonicalHeaders>().unwrap(), 0);
+    }
+
+    #[test]
+    fn db_cursor_walk() {
+        let (_dir, db) = create_test_db();
+
+        let tx = db.tx_mut().unwrap();
+        let mut cursor = tx.cursor_write::<CanonicalHeaders>().unwrap();
+        for block in [0, 1, 3, 5] {
+            cursor.append(block, B256::with_last_byte(block as u8)).unwrap();
+        }
+        assert!(cursor.append(4, B256::ZERO).is_err());
+        assert!(cursor.insert(3, B256::ZERO).is_err());
+        drop(cursor);
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+        let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+        let keys = |walker: Result<Vec<(u64, B256)>, DatabaseError>| {
+            walker.unwrap().into_iter().map(|(key, _)| key).collect::<Vec<_>>()
+        };
+
+        assert_eq!(keys(cursor.walk(None).unwrap().collect()), vec![0, 1, 3, 5]);
+        assert_eq!(keys(cursor.walk(Some(2)).unwrap().collect()), vec![3, 5]);
+        assert_eq!(keys(cursor.walk_range(1..5).unwrap().collect()), vec![1, 3]);
+        assert_eq!(keys(cursor.walk_back(Some(4)).unwrap().collect()), vec![5, 3, 1, 0]);
+        assert_eq!(keys(cursor.walk_back(None).unwrap().collect()), vec![5, 3, 1, 0]);
+
+        assert_eq!(cursor.seek_exact(3).unwrap().map(|(key, _)| key), Some(3));
+        assert_eq!(cursor.current().unwrap().map(|(key, _)| key), Some(3));
+        assert_eq!(cursor.prev().unwrap().map(|(key, _)| key), Some(1));
+        assert_eq!(cursor.seek_exact(4).unwrap(), None);
+        assert_eq!(cursor.seek(6).unwrap(), None);
+        assert_eq!(cursor.next().unwrap(), None);
+        assert_eq!(cursor.last().unwrap().map(|(key, _)| key), Some(5));
+    }
+
+    #[test]
+    fn db_cursor_delete_current() {
+        let (_dir, db) = create_test_db();
+
+        let tx = db.tx_mut().unwrap();
+        let mut cursor = tx.cursor_write::<CanonicalHeaders>().unwrap();
+        for block in 0..4 {
+            cursor.upsert(block, B256::ZERO).unwrap();
+        }
+
+        let mut walker = cursor.walk(Some(1)).unwrap();
+        walker.next().unwrap().unwrap();
+        walker.delete_current().unwrap();
+        assert_eq!(walker.next().unwrap().unwrap().0, 2);
+        drop(walker);
+        drop(cursor);
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+        let keys = tx
+            .cursor_read::<CanonicalHeaders>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|entry| entry.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn db_dup_cursor() {
+        let (_dir, db) = create_test_db();
+        let (first, second) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let entry =
+            |slot: u8| StorageEntry { key: B256::with_last_byte(slot), value: U256::from(slot) };
+
+        let tx = db.tx_mut().unwrap();
+        let mut cursor = tx.cursor_dup_write::<PlainStorageState>().unwrap();
+        for slot in [1, 3, 5] {
+            cursor.append_dup(first, entry(slot)).unwrap();
+        }
+        assert!(cursor.append_dup(first, entry(2)).is_err());
+        cursor.upsert(first, entry(2)).unwrap();
+        cursor.upsert(second, entry(1)).unwrap();
+        drop(cursor);
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+        assert_eq!(tx.get::<PlainStorageState>(first).unwrap(), Some(entry(1)));
+        assert_eq!(tx.entries::<PlainStorageState>().unwrap(), 5);
+
+        let mut cursor = tx.cursor_dup_read::<PlainStorageState>().unwrap();
+        let slots = cursor
+            .walk_dup(Some(first), None)
+            .unwrap()
+            .map(|entry| entry.unwrap().1.key)
+            .collect::<Vec<_>>();
+        assert_eq!(slots, [1, 2, 3, 5].map(B256::with_last_byte));
+
+        assert_eq!(
+            cursor.seek_by_key_subkey(first, B256::with_last_byte(4)).unwrap(),
+            Some(entry(5))
+        );
+        assert_eq!(cursor.next_dup().unwrap(), None);
+        assert_eq!(cursor.next_no_dup().unwrap(), Some((second, entry(1))));
+        assert_eq!(cursor.seek_by_key_subkey(second, B256::with_last_byte(2)).unwrap(), None);
+
+        assert_eq!(cursor.seek_exact(first).unwrap(), Some((first, entry(1))));
+        assert_eq!(cursor.next_dup_val().unwrap(), Some(entry(2)));
+        assert_eq!(cursor.next_no_dup().unwrap(), Some((second, entry(1))));
+    }
+
+    #[test]
+    fn db_dup_delete() {
+        let (_dir, db) = create_test_db();
+        let (first, second) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let entry =
+            |slot: u8| StorageEntry { key: B256::with_last_byte(slot), value: U256::from(slot) };
+
+        let tx = db.tx_mut().unwrap();
+        for slot in [1, 2, 3] {
+            tx.put::<PlainStorageState>(first, entry(slot)).unwrap();
+            tx.put::<PlainStorageState>(second, entry(slot)).unwrap();
+        }
+        assert!(tx.delete::<PlainStorageState>(first, Some(entry(2))).unwrap());
+
+        let mut cursor = tx.cursor_dup_write::<PlainStorageState>().unwrap();
+        cursor.seek_exact(second).unwrap();
+        cursor.delete_current_duplicates().unwrap();
+        drop(cursor);
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+        let entries = tx
+            .cursor_dup_read::<PlainStorageState>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(entries, vec![(first, entry(1)), (first, entry(3))]);
+    }
+
+    #[test]
+    fn db_clear() {
+        let (_dir, db) = create_test_db();
+
+        let tx = db.tx_mut().unwrap();
+        tx.put::<PlainAccountState>(Address::ZERO, Account::default()).unwrap();
+        tx.put::<AccountChangeSets>(1, AccountBeforeTx { address: Address::ZERO, info: None })
+            .unwrap();
+        tx.clear::<PlainAccountState>().unwrap();
+        tx.clear::<AccountChangeSets>().unwrap();
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+        assert_eq!(tx.entries::<PlainAccountState>().unwrap(), 0);
+        assert_eq!(tx.entries::<AccountChangeSets>().unwrap(), 0);
+    }
+}
@@ -0,0 +1,262 @@
+//! Access to the redb tables backing reth's tables.
+
+use super::error_info;
+use crate::tables::Tables;
+use redb::{
+    ReadOnlyTable, ReadableTable, ReadableTableMetadata, TableDefinition, WriteTransaction,
+};
+use reth_db_api::table::Table;
+use reth_storage_errors::db::DatabaseErrorInfo;
+use std::{
+    fmt,
+    ops::Bound,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+/// Raw `(key, value)` pair as stored in redb.
+pub(crate) type RawEntry = (Vec<u8>, Vec<u8>);
+
+/// Range of raw redb keys.
+pub(crate) type RawRange = (Bound<Vec<u8>>, Bound<Vec<u8>>);
+
+/// Shared handle of an open redb write transaction, `None` once it was committed.
+pub(crate) type SharedWriteTransaction = Arc<Mutex<Option<WriteTransaction>>>;
+
+/// Returns the redb definition of the table with the given name.
+pub(crate) const fn table_definition(
+    name: &str,
+) -> TableDefinition<'_, &'static [u8], &'static [u8]> {
+    TableDefinition::new(name)
+}
+
+/// How the entries of a table are laid out in redb.
+///
+/// redb has no equivalent of MDBX `DUPSORT` tables, so every `(key, value)` pair of a `DUPSORT`
+/// table is stored as a redb key on its own, with an empty redb value. The key is prefixed by its
+/// length, which keeps entries in the same order as MDBX as long as all keys of the table have the
+/// same length, as is the case for all of reth's `DUPSORT` tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Layout {
+    /// One redb entry per table entry.
+    Plain,
+    /// One redb key per `(key, value)` pair.
+    DupSort,
+}
+
+impl Layout {
+    /// Returns the layout of the table.
+    pub(crate) fn of<T: Table>() -> Self {
+        match T::NAME.parse::<Tables>() {
+            Ok(table) if table.is_dupsort() => Self::DupSort,
+            _ => Self::Plain,
+        }
+    }
+
+    /// Returns the raw redb entry of the encoded `(key, value)` pair.
+    pub(crate) fn encode(self, key: &[u8], value: &[u8]) -> RawEntry {
+        match self {
+            Self::Plain => (key.to_vec(), value.to_vec()),
+            Self::DupSort => {
+                let mut raw_key = Vec::with_capacity(2 + key.len() + value.len());
+                raw_key.extend_from_slice(&(key.len() as u16).to_be_bytes());
+                raw_key.extend_from_slice(key);
+                raw_key.extend_from_slice(value);
+                (raw_key, Vec::new())
+            }
+        }
+    }
+
+    /// Returns the encoded `(key, value)` pair of the raw redb entry.
+    pub(crate) fn decode(self, (mut raw_key, raw_value): RawEntry) -> RawEntry {
+        match self {
+            Self::Plain => (raw_key, raw_value),
+            Self::DupSort => {
+                let key_len = u16::from_be_bytes([raw_key[0], raw_key[1]]) as usize;
+                let value = raw_key.split_off(2 + key_len);
+                raw_key.drain(..2);
+                (raw_key, value)
+            }
+        }
+    }
+
+    /// Returns the range of raw keys of all entries with the encoded key.
+    pub(crate) fn key_range(self, key: &[u8]) -> RawRange {
+        match self {
+            Self::Plain => (Bound::Included(key.to_vec()), Bound::Included(key.to_vec())),
+            Self::DupSort => {
+                let (prefix, _) = self.encode(key, &[]);
+                let end = successor(&prefix).map_or(Bound::Unbounded, Bound::Excluded);
+                (Bound::Included(prefix), end)
+            }
+        }
+    }
+
+    /// Returns the range of raw keys of all entries after the ones with the encoded key, `None` if
+    /// there can't be any.
+    pub(crate) fn after_key(self, key: &[u8]) -> Option<RawRange> {
+        match self {
+            Self::Plain => Some((Bound::Excluded(key.to_vec()), Bound::Unbounded)),
+            Self::DupSort => {
+                let (prefix, _) = self.encode(key, &[]);
+                successor(&prefix).map(|start| (Bound::Included(start), Bound::Unbounded))
+            }
+        }
+    }
+}
+
+/// Returns the smallest byte string greater than all byte strings starting with `prefix`, `None`
+/// if there is no such string.
+fn successor(prefix: &[u8]) -> Option<Vec<u8>> {
+    let last = prefix.iter().rposition(|byte| *byte != u8::MAX)?;
+    let mut successor = prefix[..=last].to_vec();
+    successor[last] += 1;
+    Some(successor)
+}
+
+/// Borrows the bounds of the range.
+fn borrow_range(range: &RawRange) -> (Bound<&[u8]>, Bound<&[u8]>) {
+    (range.0.as_ref().map(Vec::as_slice), range.1.as_ref().map(Vec::as_slice))
+}
+
+/// Handle of a redb table, opened in a read-only or a read-write transaction.
+pub(crate) enum TableHandle {
+    /// Table of a read-only transaction.
+    Read(ReadOnlyTable<&'static [u8], &'static [u8]>),
+    /// Table of a read-write transaction.
+    ///
+    /// redb tables borrow their write transaction, so the table is opened for every operation.
+    Write { tx: SharedWriteTransaction, name: &'static str },
+}
+
+impl fmt::Debug for TableHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(table) => f.debug_tuple("Read").field(table).finish(),
+            Self::Write { name, .. } => f.debug_struct("Write").field("name", name).finish(),
+        }
+    }
+}
+
+/// Runs `$body` with `$table` bound to the opened redb table.
+macro_rules! with_table {
+    ($handle:expr, |$table:ident| $body:expr) => {
+        match $handle {
+            TableHandle::Read(table) => {
+                let $table = table;
+                $body
+            }
+            TableHandle::Write { tx, name } => {
+                let tx = tx.lock().unwrap_or_else(PoisonError::into_inner);
+                let tx = tx.as_ref().ok_or_else(committed_error)?;
+                let $table = &tx.open_table(table_definition(name)).map_err(error_info)?;
+                // bind the result, so that temporaries are dropped before the table
+                let result = $body;
+                result
+            }
+        }
+    };
+}
+
+/// Runs `$body` with `$table` bound to the opened redb table of a read-write transaction.
+macro_rules! with_table_mut {
+    ($handle:expr, |$table:ident| $body:expr) => {
+        match $handle {
+            TableHandle::Read(_) => Err(error_info("read-only transaction")),
+            TableHandle::Write { tx, name } => {
+                let tx = tx.lock().unwrap_or_else(PoisonError::into_inner);
+                let tx = tx.as_ref().ok_or_else(committed_error)?;
+                let $table = &mut tx.open_table(table_definition(name)).map_err(error_info)?;
+                // bind the result, so that temporaries are dropped before the table
+                let result = $body;
+                result
+            }
+        }
+    };
+}
+
+/// Error returned when a write transaction is used after it was committed.
+fn committed_error() -> DatabaseErrorInfo {
+    error_info("transaction was already committed")
+}
+
+impl TableHandle {
+    /// Returns the raw value of the raw key.
+    pub(crate) fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, DatabaseErrorInfo> {
+        with_table!(self, |table| Ok(table
+            .get(key)
+            .map_err(error_info)?
+            .map(|value| value.value().to_vec())))
+    }
+
+    /// Returns the first raw entry in the range.
+    pub(crate) fn first_in(&self, range: &RawRange) -> Result<Option<RawEntry>, DatabaseErrorInfo> {
+        with_table!(self, |table| {
+            let entry = table.range::<&[u8]>(borrow_range(range)).map_err(error_info)?.next();
+            entry.transpose().map_err(error_info).map(|entry| {
+                entry.map(|(key, value)| (key.value().to_vec(), value.value().to_vec()))
+            })
+        })
+    }
+
+    /// Returns the last raw entry in the range.
+    pub(crate) fn last_in(&self, range: &RawRange) -> Result<Option<RawEntry>, DatabaseErrorInfo> {
+        with_table!(self, |table| {
+            let entry = table.range::<&[u8]>(borrow_range(range)).map_err(error_info)?.next_back();
+            entry.transpose().map_err(error_info).map(|entry| {
+                entry.map(|(key, value)| (key.value().to_vec(), value.value().to_vec()))
+            })
+        })
+    }
+
+    /// Returns the number of raw entries in the table.
+    pub(crate) fn len(&self) -> Result<u64, DatabaseErrorInfo> {
+        with_table!(self, |table| table.len().map_err(error_info))
+    }
+
+    /// Inserts the raw entry, replacing the previous value of the raw key.
+    pub(crate) fn insert(&self, (key, value): &RawEntry) -> Result<(), DatabaseErrorInfo> {
+        with_table_mut!(self, |table| table
+            .insert(key.as_slice(), value.as_slice())
+            .map(drop)
+            .map_err(error_info))
+    }
+
+    /// Removes the raw key, returning `true` if it existed.
+    pub(crate) fn remove(&self, key: &[u8]) -> Result<bool, DatabaseErrorInfo> {
+        with_table_mut!(self, |table| table
+            .remove(key)
+            .map(|value| value.is_some())
+            .map_err(error_info))
+    }
+
+    /// Removes all raw entries in the range.
+    pub(crate) fn remove_range(&self, range: &RawRange) -> Result<(), DatabaseErrorInfo> {
+        with_table_mut!(self, |table| table
+            .retain_in::<&[u8], _>(borrow_range(range), |_, _| false)
+            .map_err(error_info))
+    }
+
+    /// Removes all raw entries of the table.
+    pub(crate) fn clear(&self) -> Result<(), DatabaseErrorInfo> {
+        with_table_mut!(self, |table| table.retain(|_, _| false).map_err(error_info))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dupsort_layout_roundtrip() {
+        let layout = Layout::DupSort;
+        let entry = layout.encode(&[1, 2], &[3, 4, 5]);
+        assert_eq!(entry, (vec![0, 2, 1, 2, 3, 4, 5], vec![]));
+        assert_eq!(layout.decode(entry), (vec![1, 2], vec![3, 4, 5]));
+    }
+
+    #[test]
+    fn successor_of_prefix() {
+        assert_eq!(successor(&[0, 1, 0xff]), Some(vec![0, 2]));
+        assert_eq!(successor(&[0xff, 0xff]), None);
+    }
+}
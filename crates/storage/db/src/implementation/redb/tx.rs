@@ -0,0 +1,204 @@
+//! Transaction wrapper for redb.
+
+use super::{
+    cursor::Cursor,
+    error_info,
+    table::{table_definition, Layout, SharedWriteTransaction, TableHandle},
+};
+use crate::{tables::utils::decode_one, DatabaseError};
+use reth_db_api::{
+    table::{Compress, DupSort, Encode, Table, TableImporter},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_storage_errors::db::{DatabaseWriteError, DatabaseWriteOperation};
+use std::{
+    borrow::Cow,
+    fmt,
+    marker::PhantomData,
+    sync::{Arc, PoisonError},
+};
+
+/// Marker of a read-only transaction.
+#[derive(Debug)]
+pub struct RO;
+
+/// Marker of a read-write transaction.
+#[derive(Debug)]
+pub struct RW;
+
+/// Kind of a transaction, either [`RO`] or [`RW`].
+pub trait TransactionKind: private::Sealed + fmt::Debug + Send + Sync + 'static {}
+
+impl TransactionKind for RO {}
+impl TransactionKind for RW {}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::RO {}
+    impl Sealed for super::RW {}
+}
+
+/// Inner redb transaction.
+enum TxInner {
+    Read(redb::ReadTransaction),
+    Write(SharedWriteTransaction),
+}
+
+impl fmt::Debug for TxInner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(_) => f.write_str("Read"),
+            Self::Write(_) => f.write_str("Write"),
+        }
+    }
+}
+
+/// Wrapper for the redb transaction.
+#[derive(Debug)]
+pub struct Tx<K: TransactionKind> {
+    inner: TxInner,
+    _kind: PhantomData<K>,
+}
+
+impl Tx<RO> {
+    /// Creates a new read-only `Tx`.
+    pub(crate) const fn new_ro(inner: redb::ReadTransaction) -> Self {
+        Self { inner: TxInner::Read(inner), _kind: PhantomData }
+    }
+}
+
+impl Tx<RW> {
+    /// Creates a new read-write `Tx`.
+    pub(crate) const fn new_rw(inner: SharedWriteTransaction) -> Self {
+        Self { inner: TxInner::Write(inner), _kind: PhantomData }
+    }
+}
+
+impl<K: TransactionKind> Tx<K> {
+    /// Opens the redb table backing the table.
+    fn open_table<T: Table>(&self) -> Result<TableHandle, DatabaseError> {
+        match &self.inner {
+            TxInner::Read(tx) => tx
+                .open_table(table_definition(T::NAME))
+                .map(TableHandle::Read)
+                .map_err(|e| DatabaseError::Open(error_info(e))),
+            TxInner::Write(tx) => Ok(TableHandle::Write { tx: Arc::clone(tx), name: T::NAME }),
+        }
+    }
+
+    /// Create db Cursor
+    pub fn new_cursor<T: Table>(&self) -> Result<Cursor<K, T>, DatabaseError> {
+        Ok(Cursor::new(self.open_table::<T>()?))
+    }
+}
+
+impl TableImporter for Tx<RW> {}
+
+impl<K: TransactionKind> DbTx for Tx<K> {
+    type Cursor<T: Table> = Cursor<K, T>;
+    type DupCursor<T: DupSort> = Cursor<K, T>;
+
+    fn get<T: Table>(&self, key: T::Key) -> Result<Option<<T as Table>::Value>, DatabaseError> {
+        let table = self.open_table::<T>()?;
+        let key = key.encode();
+        let value = match Layout::of::<T>() {
+            Layout::Plain => table.get(key.as_ref()),
+            layout => table
+                .first_in(&layout.key_range(key.as_ref()))
+                .map(|entry| entry.map(|entry| layout.decode(entry).1)),
+        };
+        value
+            .map_err(DatabaseError::Read)?
+            .map(|value| decode_one::<T>(Cow::Owned(value)))
+            .transpose()
+    }
+
+    fn commit(self) -> Result<bool, DatabaseError> {
+        match self.inner {
+            TxInner::Read(_) => Ok(true),
+            TxInner::Write(tx) => {
+                let tx = tx.lock().unwrap_or_else(PoisonError::into_inner).take();
+                if let Some(tx) = tx {
+                    tx.commit().map_err(|e| DatabaseError::Commit(error_info(e)))?;
+                }
+                Ok(false)
+            }
+        }
+    }
+
+    fn abort(self) {
+        drop(self.inner)
+    }
+
+    fn cursor_read<T: Table>(&self) -> Result<Self::Cursor<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    fn cursor_dup_read<T: DupSort>(&self) -> Result<Self::DupCursor<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    fn entries<T: Table>(&self) -> Result<usize, DatabaseError> {
+        Ok(self.open_table::<T>()?.len().map_err(DatabaseError::Stats)? as usize)
+    }
+
+    /// redb read transactions are not timed out, so this is a no-op.
+    fn disable_long_read_transaction_safety(&mut self) {}
+}
+
+impl DbTxMut for Tx<RW> {
+    type CursorMut<T: Table> = Cursor<RW, T>;
+    type DupCursorMut<T: DupSort> = Cursor<RW, T>;
+
+    fn put<T: Table>(&self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key = key.encode();
+        let value = value.compress();
+        self.open_table::<T>()?
+            .insert(&Layout::of::<T>().encode(key.as_ref(), value.as_ref()))
+            .map_err(|info| {
+                DatabaseWriteError {
+                    info,
+                    operation: DatabaseWriteOperation::Put,
+                    table_name: T::NAME,
+                    key: key.into(),
+                }
+                .into()
+            })
+    }
+
+    fn delete<T: Table>(
+        &self,
+        key: T::Key,
+        value: Option<T::Value>,
+    ) -> Result<bool, DatabaseError> {
+        let table = self.open_table::<T>()?;
+        let key = key.encode();
+        let layout = Layout::of::<T>();
+
+        match (layout, value) {
+            (Layout::DupSort, Some(value)) => {
+                table.remove(&layout.encode(key.as_ref(), value.compress().as_ref()).0)
+            }
+            (Layout::DupSort, None) => {
+                let range = layout.key_range(key.as_ref());
+                let exists = table.first_in(&range).map_err(DatabaseError::Delete)?.is_some();
+                table.remove_range(&range).map(|_| exists)
+            }
+            (Layout::Plain, _) => table.remove(key.as_ref()),
+        }
+        .map_err(DatabaseError::Delete)
+    }
+
+    fn clear<T: Table>(&self) -> Result<(), DatabaseError> {
+        self.open_table::<T>()?.clear().map_err(DatabaseError::Delete)
+    }
+
+    fn cursor_write<T: Table>(&self) -> Result<Self::CursorMut<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    fn cursor_dup_write<T: DupSort>(&self) -> Result<Self::DupCursorMut<T>, DatabaseError> {
+        self.new_cursor()
+    }
+}
@@ -19,6 +19,7 @@ mod implementation;
 pub mod lockfile;
 #[cfg(feature = "mdbx")]
 mod metrics;
+pub mod migration;
 pub mod static_file;
 pub mod tables;
 #[cfg(feature = "mdbx")]
@@ -27,6 +28,8 @@ pub mod version;
 
 #[cfg(feature = "mdbx")]
 pub mod mdbx;
+#[cfg(feature = "redb")]
+pub mod redb;
 
 pub use reth_storage_errors::db::{DatabaseError, DatabaseWriteOperation};
 pub use tables::*;
@@ -45,7 +48,7 @@ pub mod test_utils {
     use crate::mdbx::DatabaseArguments;
     use reth_db_api::{
         database::Database,
-        database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics},
+        database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics, TableStats},
         models::ClientVersion,
     };
     use reth_fs_util;
@@ -116,6 +119,14 @@ pub mod test_utils {
         fn report_metrics(&self) {
             self.db().report_metrics()
         }
+
+        fn gauge_metrics(&self) -> Vec<(&'static str, f64, Vec<::metrics::Label>)> {
+            self.db().gauge_metrics()
+        }
+
+        fn table_stats(&self) -> Result<Vec<TableStats>, DatabaseError> {
+            self.db().table_stats()
+        }
     }
 
     impl<DB: DatabaseMetadata> DatabaseMetadata for TempDatabase<DB> {
@@ -0,0 +1,139 @@
+//! Copying of tables between databases, e.g. to move a node to a different database backend.
+
+use crate::{TableViewer, Tables};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRW},
+    database::Database,
+    table::{DupSort, Table},
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+
+/// Copies all entries of the table from the `source` database into the same, empty table of the
+/// `destination` database.
+///
+/// Entries are read in a single read transaction and written in batches of `commit_threshold`
+/// entries, each committed on its own. Returns the number of copied entries.
+pub fn copy_table<S: Database, D: Database>(
+    source: &S,
+    destination: &D,
+    table: Tables,
+    commit_threshold: usize,
+) -> Result<usize, DatabaseError> {
+    table.view(&CopyTableViewer { source, destination, commit_threshold: commit_threshold.max(1) })
+}
+
+struct CopyTableViewer<'a, S, D> {
+    source: &'a S,
+    destination: &'a D,
+    commit_threshold: usize,
+}
+
+impl<S: Database, D: Database> TableViewer<usize> for CopyTableViewer<'_, S, D> {
+    type Error = DatabaseError;
+
+    fn view<T: Table>(&self) -> Result<usize, Self::Error> {
+        let mut source_tx = self.source.tx()?;
+        source_tx.disable_long_read_transaction_safety();
+        let mut source_cursor = source_tx.cursor_read::<T>()?;
+
+        let mut tx = self.destination.tx_mut()?;
+        let mut cursor = tx.cursor_write::<T>()?;
+        let mut entries = 0;
+        for entry in source_cursor.walk(None)? {
+            let (key, value) = entry?;
+            cursor.append(key, value)?;
+
+            entries += 1;
+            if entries % self.commit_threshold == 0 {
+                drop(cursor);
+                tx.commit()?;
+                tx = self.destination.tx_mut()?;
+                cursor = tx.cursor_write::<T>()?;
+            }
+        }
+        drop(cursor);
+        tx.commit()?;
+
+        Ok(entries)
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<usize, Self::Error> {
+        let mut source_tx = self.source.tx()?;
+        source_tx.disable_long_read_transaction_safety();
+        let mut source_cursor = source_tx.cursor_read::<T>()?;
+
+        let mut tx = self.destination.tx_mut()?;
+        let mut cursor = tx.cursor_dup_write::<T>()?;
+        let mut entries = 0;
+        for entry in source_cursor.walk(None)? {
+            let (key, value) = entry?;
+            cursor.append_dup(key, value)?;
+
+            entries += 1;
+            if entries % self.commit_threshold == 0 {
+                drop(cursor);
+                tx.commit()?;
+                tx = self.destination.tx_mut()?;
+                cursor = tx.cursor_dup_write::<T>()?;
+            }
+        }
+        drop(cursor);
+        tx.commit()?;
+
+        Ok(entries)
+    }
+}
+
+#[cfg(all(test, feature = "mdbx", feature = "redb"))]
+mod tests {
+    use super::*;
+    use crate::{
+        tables::{CanonicalHeaders, PlainStorageState},
+        test_utils::create_test_rw_db,
+    };
+    use reth_primitives::{Address, StorageEntry, B256, U256};
+
+    #[test]
+    fn copy_mdbx_to_redb_and_back() {
+        let mdbx = create_test_rw_db();
+        let dir = tempfile::TempDir::new().unwrap();
+        let redb = crate::redb::init_db(dir.path()).unwrap();
+
+        let storage = (0..5u8)
+            .flat_map(|address| {
+                (0..3u8).map(move |slot| {
+                    let entry =
+                        StorageEntry { key: B256::with_last_byte(slot), value: U256::from(slot) };
+                    (Address::with_last_byte(address), entry)
+                })
+            })
+            .collect::<Vec<_>>();
+        let tx = mdbx.tx_mut().unwrap();
+        for block in 0..10 {
+            tx.put::<CanonicalHeaders>(block, B256::with_last_byte(block as u8)).unwrap();
+        }
+        for (address, entry) in &storage {
+            tx.put::<PlainStorageState>(*address, *entry).unwrap();
+        }
+        tx.commit().unwrap();
+
+        assert_eq!(copy_table(&mdbx, &redb, Tables::CanonicalHeaders, 3).unwrap(), 10);
+        assert_eq!(copy_table(&mdbx, &redb, Tables::PlainStorageState, 4).unwrap(), 15);
+
+        let copied = redb
+            .tx()
+            .unwrap()
+            .cursor_read::<PlainStorageState>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(copied, storage);
+
+        let mdbx = create_test_rw_db();
+        assert_eq!(copy_table(&redb, &mdbx, Tables::PlainStorageState, 4).unwrap(), 15);
+        assert_eq!(mdbx.tx().unwrap().entries::<PlainStorageState>().unwrap(), 15);
+    }
+}
@@ -0,0 +1,40 @@
+//! Bindings for [redb](https://www.redb.org/), an experimental alternative to MDBX.
+//!
+//! All of reth's tables are stored in a single redb file inside of the database directory. See
+//! [`DatabaseEnv`] for how tables map to redb tables.
+
+use crate::version::{check_db_version_file, create_db_version_file, DatabaseVersionError};
+use eyre::Context;
+use std::path::Path;
+
+pub use crate::implementation::redb::*;
+
+/// Opens up an existing redb database or creates a new one at the specified path. Creates tables
+/// if necessary.
+pub fn init_db<P: AsRef<Path>>(path: P) -> eyre::Result<DatabaseEnv> {
+    let rpath = path.as_ref();
+    if rpath.join(REDB_FILE_NAME).exists() {
+        match check_db_version_file(rpath) {
+            Ok(_) => (),
+            Err(DatabaseVersionError::MissingFile) => create_db_version_file(rpath)?,
+            Err(err) => return Err(err.into()),
+        }
+    } else {
+        reth_fs_util::create_dir_all(rpath)
+            .wrap_err_with(|| format!("Could not create database directory {}", rpath.display()))?;
+        create_db_version_file(rpath)?;
+    }
+
+    let db = DatabaseEnv::open(rpath)?;
+    db.create_tables()?;
+    Ok(db)
+}
+
+/// Opens up an existing redb database. It doesn't create it or create tables if missing.
+pub fn open_db(path: &Path) -> eyre::Result<DatabaseEnv> {
+    if !path.join(REDB_FILE_NAME).exists() {
+        eyre::bail!("No redb database found at path: {}", path.display())
+    }
+    DatabaseEnv::open(path)
+        .with_context(|| format!("Could not open database at path: {}", path.display()))
+}
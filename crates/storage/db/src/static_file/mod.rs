@@ -18,6 +18,9 @@ use reth_primitives::{
 
 mod masks;
 
+/// Extension of the file marking a static file whose data is only kept in an object store.
+pub const REMOTE_MARKER_EXTENSION: &str = "remote";
+
 /// Alias type for a map of [`StaticFileSegment`] and sorted lists of existing static file ranges.
 type SortedStaticFiles =
     HashMap<StaticFileSegment, Vec<(SegmentRangeInclusive, Option<SegmentRangeInclusive>)>>;
@@ -25,6 +28,9 @@ type SortedStaticFiles =
 /// Given the `static_files` directory path, it returns a list over the existing `static_files`
 /// organized by [`StaticFileSegment`]. Each segment has a sorted list of block ranges and
 /// transaction ranges as presented in the file configuration.
+///
+/// Static files whose data is only kept in an object store are listed as well, see
+/// [`REMOTE_MARKER_EXTENSION`].
 pub fn iter_static_files(path: impl AsRef<Path>) -> Result<SortedStaticFiles, NippyJarError> {
     let path = path.as_ref();
     if !path.exists() {
@@ -39,10 +45,19 @@ pub fn iter_static_files(path: impl AsRef<Path>) -> Result<SortedStaticFiles, Ni
 
     for entry in entries {
         if entry.metadata().map_or(false, |metadata| metadata.is_file()) {
-            if let Some((segment, _)) =
-                StaticFileSegment::parse_filename(&entry.file_name().to_string_lossy())
-            {
-                let jar = NippyJar::<SegmentHeader>::load(&entry.path())?;
+            let mut path = entry.path();
+            if path.extension().map_or(false, |extension| extension == REMOTE_MARKER_EXTENSION) {
+                path.set_extension("");
+                // Listed by its data file instead
+                if path.exists() {
+                    continue
+                }
+            }
+
+            if let Some((segment, _)) = path.file_name().and_then(|file_name| {
+                StaticFileSegment::parse_filename(&file_name.to_string_lossy())
+            }) {
+                let jar = NippyJar::<SegmentHeader>::load(&path)?;
 
                 let (block_range, tx_range) = (
                     jar.user_header().block_range().copied(),
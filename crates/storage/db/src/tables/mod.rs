@@ -16,7 +16,7 @@ pub mod codecs;
 mod raw;
 pub use raw::{RawDupSort, RawKey, RawTable, RawValue, TableRawRow};
 
-#[cfg(feature = "mdbx")]
+#[cfg(any(feature = "mdbx", feature = "redb"))]
 pub(crate) mod utils;
 
 use reth_db_api::{
@@ -36,7 +36,10 @@ use reth_primitives::{
 use reth_primitives_traits::IntegerList;
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::StageCheckpoint;
-use reth_trie_common::{BranchNodeCompact, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey};
+use reth_trie_common::{
+    BranchNodeCompact, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey, StoredVerkleNode,
+    VerkleLeafBeforeBlock, VerklePath,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -409,6 +412,36 @@ tables! {
 
     /// Stores generic chain state info, like the last finalized block.
     table ChainState<Key = ChainStateKey, Value = BlockNumber>;
+
+    /// Stores the highest block number processed by each execution extension, keyed by its ID.
+    table ExExCheckpoints<Key = String, Value = BlockNumber>;
+
+    /// Stores the accounts moved out of [`PlainAccountState`] by state expiry, because they
+    /// weren't changed for the configured number of blocks.
+    table ColdAccountState<Key = Address, Value = Account>;
+
+    /// Stores the storage slots moved out of [`PlainStorageState`] by state expiry, because they
+    /// weren't changed for the configured number of blocks.
+    table ColdStorageState<Key = Address, Value = StorageEntry, SubKey = B256>;
+
+    /// Stores the leaves of the verkle trie, keyed by their tree key.
+    table VerkleLeaves<Key = B256, Value = B256>;
+
+    /// Stores the nodes of the verkle trie, keyed by their path from the root.
+    table VerkleNodes<Key = VerklePath, Value = StoredVerkleNode>;
+
+    /// Stores the values of the verkle trie leaves before the blocks that changed them, to unwind
+    /// the verkle trie.
+    table VerkleLeafChangeSets<Key = BlockNumber, Value = VerkleLeafBeforeBlock, SubKey = B256>;
+
+    /// Stores the accounts of a forked chain that were destroyed by a local block, with the first
+    /// block that destroyed them. The forked state of these accounts and their storage is shadowed
+    /// by the local state.
+    table ForkedAccountTombstones<Key = Address, Value = BlockNumber>;
+
+    /// Stores the storage slots of a forked chain that were cleared by a local block, with a zero
+    /// value. The forked state of these slots is shadowed by the local state.
+    table ForkedStorageTombstones<Key = Address, Value = StorageEntry, SubKey = B256>;
 }
 
 /// Keys for the `ChainState` table.
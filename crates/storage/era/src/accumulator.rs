@@ -0,0 +1,87 @@
+use reth_primitives::{B256, U256};
+use sha2::{Digest, Sha256};
+
+/// The maximum number of blocks in an epoch.
+pub const EPOCH_SIZE: usize = 8192;
+
+/// Depth of the merkle tree over the header records of an epoch.
+const EPOCH_DEPTH: usize = EPOCH_SIZE.trailing_zeros() as usize;
+
+/// Accumulator of the header records of an epoch.
+///
+/// The root is the SSZ hash tree root of `List[HeaderRecord, EPOCH_SIZE]`, where a header record
+/// is the container `{ block_hash: Bytes32, total_difficulty: uint256 }`.
+#[derive(Debug, Default, Clone)]
+pub struct Accumulator {
+    records: Vec<B256>,
+}
+
+impl Accumulator {
+    /// Returns the number of records.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no record was added.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Adds the header record of the next block.
+    pub fn push(&mut self, block_hash: B256, total_difficulty: U256) {
+        self.records.push(hash_pair(block_hash, total_difficulty.to_le_bytes().into()));
+    }
+
+    /// Computes the root of the records.
+    pub fn root(&self) -> B256 {
+        let mut zero = B256::ZERO;
+        let mut layer = self.records.clone();
+        for _ in 0..EPOCH_DEPTH {
+            if layer.len() % 2 == 1 {
+                layer.push(zero);
+            }
+            layer = layer.chunks_exact(2).map(|pair| hash_pair(pair[0], pair[1])).collect();
+            zero = hash_pair(zero, zero);
+        }
+        let root = layer.first().copied().unwrap_or(zero);
+
+        // mix in the length of the list
+        hash_pair(root, U256::from(self.records.len()).to_le_bytes().into())
+    }
+}
+
+fn hash_pair(left: B256, right: B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::new(hasher.finalize().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_root() {
+        // the root of an empty list is the zero subtree of depth 13 mixed in with length zero
+        let mut zero = B256::ZERO;
+        for _ in 0..EPOCH_DEPTH {
+            zero = hash_pair(zero, zero);
+        }
+        assert_eq!(Accumulator::default().root(), hash_pair(zero, B256::ZERO));
+    }
+
+    #[test]
+    fn root_commits_to_records() {
+        let mut accumulator = Accumulator::default();
+        accumulator.push(B256::with_last_byte(1), U256::from(1));
+        let root = accumulator.root();
+
+        let mut other = Accumulator::default();
+        other.push(B256::with_last_byte(1), U256::from(2));
+        assert_ne!(root, other.root());
+
+        accumulator.push(B256::with_last_byte(2), U256::from(3));
+        assert_ne!(root, accumulator.root());
+    }
+}
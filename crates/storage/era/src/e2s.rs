@@ -0,0 +1,59 @@
+//! Reading of e2store files, the container format of era1 files.
+//!
+//! An e2store file is a sequence of entries, each consisting of an 8 byte header and a value:
+//!
+//! ```text
+//! entry  = header | value
+//! header = type (2 bytes) | length (4 bytes, little endian) | reserved (2 bytes, zero)
+//! ```
+
+use crate::Era1Error;
+use std::io::{self, Read};
+
+/// Length of the header of an entry.
+pub const HEADER_LEN: usize = 8;
+
+/// A single entry of an e2store file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Type of the entry.
+    pub entry_type: u16,
+    /// Value of the entry.
+    pub value: Vec<u8>,
+}
+
+/// Reads the entries of an e2store file.
+#[derive(Debug)]
+pub struct E2StoreReader<R> {
+    reader: R,
+    offset: u64,
+}
+
+impl<R: Read> E2StoreReader<R> {
+    /// Creates a reader of the entries in the given reader.
+    pub const fn new(reader: R) -> Self {
+        Self { reader, offset: 0 }
+    }
+
+    /// Reads the next entry, or returns `None` at the end of the file.
+    pub fn read_entry(&mut self) -> Result<Option<Entry>, Era1Error> {
+        let mut header = [0; HEADER_LEN];
+        match self.reader.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err.into()),
+        }
+
+        let entry_type = u16::from_le_bytes([header[0], header[1]]);
+        let len = u32::from_le_bytes([header[2], header[3], header[4], header[5]]);
+        if header[6..] != [0, 0] {
+            return Err(Era1Error::InvalidEntryHeader { offset: self.offset })
+        }
+
+        let mut value = vec![0; len as usize];
+        self.reader.read_exact(&mut value)?;
+        self.offset += (HEADER_LEN + value.len()) as u64;
+
+        Ok(Some(Entry { entry_type, value }))
+    }
+}
@@ -0,0 +1,509 @@
+//! Reading of era1 files.
+
+use crate::{
+    accumulator::{Accumulator, EPOCH_SIZE},
+    e2s::{E2StoreReader, Entry},
+    Era1Error,
+};
+use alloy_rlp::{Buf, Decodable};
+use reth_primitives::{
+    BlockBody, BlockNumber, Bytes, Header, Receipt, ReceiptWithBloom, SealedBlock, SealedHeader,
+    B256, U256,
+};
+use reth_trie_common::root::ordered_trie_root_with_encoder;
+use std::{
+    fs::File,
+    io::{self, BufReader, Read},
+    ops::RangeInclusive,
+    path::Path,
+};
+
+/// Type of the version entry that starts the file.
+pub const VERSION: u16 = 0x3265;
+/// Type of the entry holding the snappy compressed RLP encoded header.
+pub const COMPRESSED_HEADER: u16 = 0x03;
+/// Type of the entry holding the snappy compressed RLP encoded body.
+pub const COMPRESSED_BODY: u16 = 0x04;
+/// Type of the entry holding the snappy compressed RLP encoded receipts.
+pub const COMPRESSED_RECEIPTS: u16 = 0x05;
+/// Type of the entry holding the SSZ encoded total difficulty.
+pub const TOTAL_DIFFICULTY: u16 = 0x06;
+/// Type of the entry holding the accumulator root of the blocks in the file.
+pub const ACCUMULATOR: u16 = 0x07;
+/// Type of the entry holding the offsets of the blocks in the file.
+pub const BLOCK_INDEX: u16 = 0x3266;
+
+/// A block of an era1 file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Era1Block {
+    /// The block header.
+    pub header: SealedHeader,
+    /// The block body.
+    pub body: BlockBody,
+    /// The RLP encoded list of receipts.
+    pub receipts: Bytes,
+    /// The total difficulty of the chain up to and including the block.
+    pub total_difficulty: U256,
+}
+
+impl Era1Block {
+    /// Returns the block.
+    pub fn block(&self) -> SealedBlock {
+        SealedBlock::new(self.header.clone(), self.body.clone())
+    }
+
+    /// Computes the receipts root from the receipts.
+    pub fn receipts_root(&self) -> Result<B256, Era1Error> {
+        let receipts = self.receipt_payloads()?;
+        Ok(ordered_trie_root_with_encoder(&receipts, |receipt, buf| buf.extend_from_slice(receipt)))
+    }
+
+    /// Checks that the receipts match the receipts root of the header.
+    pub fn validate_receipts(&self) -> Result<(), Era1Error> {
+        let computed = self.receipts_root()?;
+        if computed != self.header.receipts_root {
+            return Err(Era1Error::ReceiptsRootMismatch {
+                block: self.header.number,
+                expected: self.header.receipts_root,
+                computed,
+            })
+        }
+        Ok(())
+    }
+
+    /// Decodes the receipts.
+    ///
+    /// Returns `None` for receipts from before Byzantium, which commit to the intermediate state
+    /// root instead of the status of the transaction.
+    pub fn decode_receipts(&self) -> Result<Option<Vec<Receipt>>, Era1Error> {
+        let mut receipts = Vec::new();
+        for (encoded, payload) in self.receipt_items()? {
+            if has_post_state(payload)? {
+                return Ok(None)
+            }
+            receipts.push(ReceiptWithBloom::decode(&mut &encoded[..])?.receipt);
+        }
+        Ok(Some(receipts))
+    }
+
+    /// Returns the payloads of the receipts, i.e. the values of the receipts trie.
+    fn receipt_payloads(&self) -> Result<Vec<&[u8]>, Era1Error> {
+        Ok(self.receipt_items()?.into_iter().map(|(_, payload)| payload).collect())
+    }
+
+    /// Splits the receipts list into the encoded receipts and their payloads. The payload of a
+    /// typed receipt is its type followed by the RLP encoded fields, while a legacy receipt is only
+    /// the RLP encoded fields.
+    fn receipt_items(&self) -> Result<Vec<ReceiptItem<'_>>, Era1Error> {
+        let mut buf = &self.receipts[..];
+        let header = alloy_rlp::Header::decode(&mut buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString.into())
+        }
+        if buf.len() < header.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort.into())
+        }
+
+        let mut items = Vec::new();
+        let mut list = &buf[..header.payload_length];
+        while !list.is_empty() {
+            let rest = list;
+            let item = alloy_rlp::Header::decode(&mut list)?;
+            if list.len() < item.payload_length {
+                return Err(alloy_rlp::Error::InputTooShort.into())
+            }
+            list.advance(item.payload_length);
+
+            let encoded = &rest[..rest.len() - list.len()];
+            let payload =
+                if item.list { encoded } else { &encoded[encoded.len() - item.payload_length..] };
+            items.push((encoded, payload));
+        }
+        Ok(items)
+    }
+}
+
+/// An encoded receipt and its payload.
+type ReceiptItem<'a> = (&'a [u8], &'a [u8]);
+
+/// Returns `true` if the receipt payload starts with an intermediate state root.
+fn has_post_state(mut payload: &[u8]) -> Result<bool, Era1Error> {
+    // skip the type of typed receipts
+    if payload.first().is_some_and(|first| *first < alloy_rlp::EMPTY_LIST_CODE) {
+        payload.advance(1);
+    }
+    let header = alloy_rlp::Header::decode(&mut payload)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString.into())
+    }
+    let first = alloy_rlp::Header::decode(&mut payload)?;
+    Ok(!first.list && first.payload_length == B256::len_bytes())
+}
+
+/// Reads the blocks of an era1 file.
+///
+/// An era1 file holds up to [`EPOCH_SIZE`] sequential pre-merge blocks, followed by the
+/// accumulator root of their header records and an index of the block offsets:
+///
+/// ```text
+/// era1 := Version | block-tuple* | Accumulator | BlockIndex
+/// block-tuple := CompressedHeader | CompressedBody | CompressedReceipts | TotalDifficulty
+/// ```
+///
+/// The accumulator root is checked once all blocks have been read, see
+/// [`Era1Reader::accumulator`].
+#[derive(Debug)]
+pub struct Era1Reader<R> {
+    entries: E2StoreReader<R>,
+    accumulator: Accumulator,
+    first_block: Option<BlockNumber>,
+    last_block: Option<(BlockNumber, U256)>,
+    root: Option<B256>,
+}
+
+impl Era1Reader<BufReader<File>> {
+    /// Opens the era1 file at the given path.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Era1Error> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> Era1Reader<R> {
+    /// Creates a reader of the era1 file in the given reader.
+    pub fn new(reader: R) -> Result<Self, Era1Error> {
+        let mut entries = E2StoreReader::new(reader);
+        match entries.read_entry()? {
+            Some(Entry { entry_type: VERSION, value }) if value.is_empty() => {}
+            _ => return Err(Era1Error::MissingVersion),
+        }
+
+        Ok(Self {
+            entries,
+            accumulator: Accumulator::default(),
+            first_block: None,
+            last_block: None,
+            root: None,
+        })
+    }
+
+    /// Returns the verified accumulator root once all blocks have been read.
+    pub const fn accumulator(&self) -> Option<B256> {
+        self.root
+    }
+
+    /// Reads the next block, or returns `None` once all blocks have been read and the accumulator
+    /// root has been verified.
+    pub fn next_block(&mut self) -> Result<Option<Era1Block>, Era1Error> {
+        let Some([header, body, receipts, total_difficulty]) = self.next_tuple()? else {
+            return Ok(None)
+        };
+
+        let (header, total_difficulty) = self.read_header(&header, &total_difficulty)?;
+        let body = BlockBody::decode(&mut &decompress(&body)?[..])?;
+        let receipts = decompress(&receipts)?.into();
+
+        Ok(Some(Era1Block { header, body, receipts, total_difficulty }))
+    }
+
+    /// Reads the header and total difficulty of the next block, skipping its body and receipts.
+    /// Returns `None` once all blocks have been read and the accumulator root has been verified.
+    pub fn next_header(&mut self) -> Result<Option<(SealedHeader, U256)>, Era1Error> {
+        let Some([header, _, _, total_difficulty]) = self.next_tuple()? else { return Ok(None) };
+        self.read_header(&header, &total_difficulty).map(Some)
+    }
+
+    /// Reads all blocks and verifies the accumulator root. Returns the range of blocks in the
+    /// file.
+    pub fn verify(mut self) -> Result<RangeInclusive<BlockNumber>, Era1Error> {
+        while self.next_header()?.is_some() {}
+
+        let first = self.first_block.unwrap_or_default();
+        let (last, _) = self.last_block.unwrap_or_default();
+        Ok(first..=last)
+    }
+
+    /// Reads the entries of the next block tuple. Once the accumulator is reached, verifies the
+    /// root and returns `None`.
+    fn next_tuple(&mut self) -> Result<Option<[Vec<u8>; 4]>, Era1Error> {
+        if self.root.is_some() {
+            return Ok(None)
+        }
+
+        let entry = self.read_entry()?;
+        if entry.entry_type == ACCUMULATOR {
+            self.finish(entry)?;
+            return Ok(None)
+        }
+        if self.accumulator.len() == EPOCH_SIZE {
+            return Err(Era1Error::TooManyBlocks(EPOCH_SIZE))
+        }
+
+        let header = expect(entry, COMPRESSED_HEADER)?;
+        let body = expect(self.read_entry()?, COMPRESSED_BODY)?;
+        let receipts = expect(self.read_entry()?, COMPRESSED_RECEIPTS)?;
+        let total_difficulty = expect(self.read_entry()?, TOTAL_DIFFICULTY)?;
+        Ok(Some([header, body, receipts, total_difficulty]))
+    }
+
+    /// Decodes the header and total difficulty, checks them against the previous block and adds
+    /// them to the accumulator.
+    fn read_header(
+        &mut self,
+        header: &[u8],
+        total_difficulty: &[u8],
+    ) -> Result<(SealedHeader, U256), Era1Error> {
+        let header = Header::decode(&mut &decompress(header)?[..])?.seal_slow();
+        let total_difficulty = decode_u256(TOTAL_DIFFICULTY, total_difficulty)?;
+
+        match self.last_block {
+            Some((number, parent_total_difficulty)) => {
+                if header.number != number + 1 {
+                    return Err(Era1Error::NonSequentialBlock {
+                        expected: number + 1,
+                        got: header.number,
+                    })
+                }
+                if parent_total_difficulty + header.difficulty != total_difficulty {
+                    return Err(Era1Error::TotalDifficultyMismatch {
+                        block: header.number,
+                        total_difficulty,
+                    })
+                }
+            }
+            None => self.first_block = Some(header.number),
+        }
+        self.last_block = Some((header.number, total_difficulty));
+        self.accumulator.push(header.hash(), total_difficulty);
+
+        Ok((header, total_difficulty))
+    }
+
+    /// Checks the accumulator root and the block index.
+    fn finish(&mut self, accumulator: Entry) -> Result<(), Era1Error> {
+        let expected = B256::try_from(&accumulator.value[..]).map_err(|_| {
+            Era1Error::InvalidEntryLength { entry_type: ACCUMULATOR, len: accumulator.value.len() }
+        })?;
+
+        // block index := starting-number | offset* | count
+        let index = expect(self.read_entry()?, BLOCK_INDEX)?;
+        if index.len() < 16 || index.len() % 8 != 0 {
+            return Err(Era1Error::InvalidEntryLength { entry_type: BLOCK_INDEX, len: index.len() })
+        }
+        let start = u64::from_le_bytes(index[..8].try_into().expect("8 bytes"));
+        let count = u64::from_le_bytes(index[index.len() - 8..].try_into().expect("8 bytes"));
+        if count == 0 ||
+            count != self.accumulator.len() as u64 ||
+            index.len() as u64 != 16 + count * 8 ||
+            Some(start) != self.first_block
+        {
+            return Err(Era1Error::BlockIndexMismatch { start, count })
+        }
+
+        let computed = self.accumulator.root();
+        if computed != expected {
+            return Err(Era1Error::AccumulatorMismatch { expected, computed })
+        }
+        self.root = Some(computed);
+
+        Ok(())
+    }
+
+    fn read_entry(&mut self) -> Result<Entry, Era1Error> {
+        self.entries
+            .read_entry()?
+            .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof).into())
+    }
+}
+
+/// Returns the value of the entry if it is of the expected type.
+fn expect(entry: Entry, expected: u16) -> Result<Vec<u8>, Era1Error> {
+    if entry.entry_type != expected {
+        return Err(Era1Error::UnexpectedEntry { expected, got: entry.entry_type })
+    }
+    Ok(entry.value)
+}
+
+/// Decompresses a snappy framed value.
+fn decompress(value: &[u8]) -> Result<Vec<u8>, Era1Error> {
+    let mut decompressed = Vec::new();
+    snap::read::FrameDecoder::new(value).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+/// Decodes an SSZ encoded `uint256`.
+fn decode_u256(entry_type: u16, value: &[u8]) -> Result<U256, Era1Error> {
+    let bytes: [u8; 32] = value
+        .try_into()
+        .map_err(|_| Era1Error::InvalidEntryLength { entry_type, len: value.len() })?;
+    Ok(U256::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use reth_primitives::{proofs::calculate_receipt_root, Bloom, Log, TxType};
+    use std::io::Write;
+
+    fn write_entry(file: &mut Vec<u8>, entry_type: u16, value: &[u8]) {
+        file.extend_from_slice(&entry_type.to_le_bytes());
+        file.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        file.extend_from_slice(&[0, 0]);
+        file.extend_from_slice(value);
+    }
+
+    fn compress(value: &[u8]) -> Vec<u8> {
+        let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+        encoder.write_all(value).unwrap();
+        encoder.into_inner().unwrap()
+    }
+
+    /// Encodes an era1 file of the given headers and receipts, with the given accumulator root or
+    /// the computed one.
+    fn era1_file(blocks: &[(Header, Vec<u8>)], root: Option<B256>) -> Vec<u8> {
+        let mut file = Vec::new();
+        write_entry(&mut file, VERSION, &[]);
+
+        let mut accumulator = Accumulator::default();
+        let mut total_difficulty = U256::ZERO;
+        for (header, receipts) in blocks {
+            total_difficulty += header.difficulty;
+            accumulator.push(header.hash_slow(), total_difficulty);
+
+            let mut encoded = Vec::new();
+            header.encode(&mut encoded);
+            write_entry(&mut file, COMPRESSED_HEADER, &compress(&encoded));
+            encoded.clear();
+            BlockBody::default().encode(&mut encoded);
+            write_entry(&mut file, COMPRESSED_BODY, &compress(&encoded));
+            write_entry(&mut file, COMPRESSED_RECEIPTS, &compress(receipts));
+            write_entry(&mut file, TOTAL_DIFFICULTY, &total_difficulty.to_le_bytes::<32>());
+        }
+
+        write_entry(&mut file, ACCUMULATOR, root.unwrap_or_else(|| accumulator.root()).as_slice());
+        let mut index = blocks[0].0.number.to_le_bytes().to_vec();
+        for _ in blocks {
+            index.extend_from_slice(&0i64.to_le_bytes());
+        }
+        index.extend_from_slice(&(blocks.len() as u64).to_le_bytes());
+        write_entry(&mut file, BLOCK_INDEX, &index);
+
+        file
+    }
+
+    fn headers(range: RangeInclusive<BlockNumber>) -> Vec<(Header, Vec<u8>)> {
+        range
+            .map(|number| {
+                let header =
+                    Header { number, difficulty: U256::from(number + 1), ..Default::default() };
+                (header, vec![alloy_rlp::EMPTY_LIST_CODE])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn read_blocks() {
+        let file = era1_file(&headers(0..=2), None);
+        let mut reader = Era1Reader::new(&file[..]).unwrap();
+
+        let mut total_difficulty = U256::ZERO;
+        for number in 0..=2 {
+            let block = reader.next_block().unwrap().unwrap();
+            total_difficulty += U256::from(number + 1);
+            assert_eq!(block.header.number, number);
+            assert_eq!(block.total_difficulty, total_difficulty);
+            assert_eq!(block.body, BlockBody::default());
+            block.validate_receipts().unwrap();
+            assert_eq!(block.decode_receipts().unwrap(), Some(Vec::new()));
+        }
+        assert_eq!(reader.next_block().unwrap(), None);
+        assert!(reader.accumulator().is_some());
+
+        assert_eq!(Era1Reader::new(&file[..]).unwrap().verify().unwrap(), 0..=2);
+    }
+
+    #[test]
+    fn accumulator_mismatch() {
+        let file = era1_file(&headers(10..=12), Some(B256::random()));
+        assert!(matches!(
+            Era1Reader::new(&file[..]).unwrap().verify(),
+            Err(Era1Error::AccumulatorMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn non_sequential_blocks() {
+        let mut blocks = headers(0..=2);
+        blocks.remove(1);
+        let file = era1_file(&blocks, None);
+        assert!(matches!(
+            Era1Reader::new(&file[..]).unwrap().verify(),
+            Err(Era1Error::NonSequentialBlock { expected: 1, got: 2 })
+        ));
+    }
+
+    #[test]
+    #[allow(clippy::needless_update)]
+    fn receipts() {
+        let receipt = Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used: 21000,
+            logs: vec![Log::new_unchecked(
+                Default::default(),
+                Vec::new(),
+                Bytes::from_static(&[1]),
+            )],
+            ..Default::default()
+        };
+        let receipts = vec![receipt.clone().with_bloom(), Receipt::default().with_bloom()];
+        let mut encoded = Vec::new();
+        receipts.encode(&mut encoded);
+
+        let header = Header {
+            receipts_root: calculate_receipt_root(&receipts),
+            difficulty: U256::from(1),
+            ..Default::default()
+        };
+        let file = era1_file(&[(header, encoded)], None);
+        let block = Era1Reader::new(&file[..]).unwrap().next_block().unwrap().unwrap();
+
+        block.validate_receipts().unwrap();
+        assert_eq!(block.decode_receipts().unwrap(), Some(vec![receipt, Receipt::default()]));
+    }
+
+    #[test]
+    fn pre_byzantium_receipts() {
+        // [post state, cumulative gas used, bloom, logs]
+        let fields = (B256::random(), 21000u64, Bloom::default(), Vec::<Log>::new());
+        let mut payload = Vec::new();
+        alloy_rlp::Header {
+            list: true,
+            payload_length: fields.0.length() +
+                fields.1.length() +
+                fields.2.length() +
+                fields.3.length(),
+        }
+        .encode(&mut payload);
+        fields.0.encode(&mut payload);
+        fields.1.encode(&mut payload);
+        fields.2.encode(&mut payload);
+        fields.3.encode(&mut payload);
+        let mut encoded = Vec::new();
+        alloy_rlp::Header { list: true, payload_length: payload.len() }.encode(&mut encoded);
+        encoded.extend_from_slice(&payload);
+
+        let header = Header {
+            receipts_root: ordered_trie_root_with_encoder(&[&payload], |item, buf| {
+                buf.extend_from_slice(item)
+            }),
+            difficulty: U256::from(1),
+            ..Default::default()
+        };
+        let file = era1_file(&[(header, encoded)], None);
+        let block = Era1Reader::new(&file[..]).unwrap().next_block().unwrap().unwrap();
+
+        block.validate_receipts().unwrap();
+        assert_eq!(block.decode_receipts().unwrap(), None);
+    }
+}
@@ -0,0 +1,82 @@
+use reth_primitives::{BlockNumber, B256, U256};
+
+/// Errors that can occur when reading an era1 file.
+#[derive(Debug, thiserror::Error)]
+pub enum Era1Error {
+    /// IO error.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Error decoding an RLP encoded entry.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+    /// The reserved bytes of an entry header are not zero.
+    #[error("invalid header of entry at offset {offset}")]
+    InvalidEntryHeader {
+        /// Offset of the entry in the file.
+        offset: u64,
+    },
+    /// The file does not start with a version entry.
+    #[error("missing version entry")]
+    MissingVersion,
+    /// An entry of a different type was expected.
+    #[error("unexpected entry of type {got:#06x}, expected {expected:#06x}")]
+    UnexpectedEntry {
+        /// Expected entry type.
+        expected: u16,
+        /// Type of the entry that was read.
+        got: u16,
+    },
+    /// The value of an entry has an invalid length.
+    #[error("invalid length {len} of entry of type {entry_type:#06x}")]
+    InvalidEntryLength {
+        /// Type of the entry.
+        entry_type: u16,
+        /// Length of the entry value.
+        len: usize,
+    },
+    /// The file holds more blocks than an epoch.
+    #[error("file holds more than {0} blocks")]
+    TooManyBlocks(usize),
+    /// The blocks are not sequential.
+    #[error("expected block #{expected}, got block #{got}")]
+    NonSequentialBlock {
+        /// Expected block number.
+        expected: BlockNumber,
+        /// Number of the block that was read.
+        got: BlockNumber,
+    },
+    /// The total difficulty does not match the difficulty of the block.
+    #[error("total difficulty {total_difficulty} of block #{block} does not match its difficulty")]
+    TotalDifficultyMismatch {
+        /// Block number.
+        block: BlockNumber,
+        /// Total difficulty recorded in the file.
+        total_difficulty: U256,
+    },
+    /// The receipts do not match the receipts root of the block.
+    #[error("receipts root mismatch of block #{block}: expected {expected}, computed {computed}")]
+    ReceiptsRootMismatch {
+        /// Block number.
+        block: BlockNumber,
+        /// Receipts root of the header.
+        expected: B256,
+        /// Root computed from the receipts in the file.
+        computed: B256,
+    },
+    /// The block index does not match the blocks of the file.
+    #[error("block index of {count} blocks from #{start} does not match the file")]
+    BlockIndexMismatch {
+        /// First block of the index.
+        start: BlockNumber,
+        /// Number of blocks in the index.
+        count: u64,
+    },
+    /// The accumulator root does not match the blocks of the file.
+    #[error("accumulator mismatch: expected {expected}, computed {computed}")]
+    AccumulatorMismatch {
+        /// Accumulator root recorded in the file.
+        expected: B256,
+        /// Root computed from the blocks of the file.
+        computed: B256,
+    },
+}
@@ -0,0 +1,26 @@
+//! Reading of era1 files, archives of pre-merge chain history.
+//!
+//! An era1 file holds the headers, bodies, receipts and total difficulties of an epoch of 8192
+//! blocks, along with the accumulator root of the epoch that commits to the hashes and total
+//! difficulties of its blocks. See [`Era1Reader`].
+//!
+//! Specification: <https://github.com/eth-clients/e2store-format-specs/blob/main/formats/era1.md>
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+pub mod e2s;
+
+mod accumulator;
+pub use accumulator::{Accumulator, EPOCH_SIZE};
+
+pub mod era1;
+pub use era1::{Era1Block, Era1Reader};
+
+mod error;
+pub use error::Era1Error;
@@ -27,6 +27,12 @@ pub enum ProviderError {
     /// Nippy jar error.
     #[display("nippy jar error: {_0}")]
     NippyJar(String),
+    /// Error moving static files to or from their object store.
+    #[display("remote static file error: {_0}")]
+    RemoteStaticFile(String),
+    /// Error reading the state of the network a dev chain is forked from.
+    #[display("forked state error: {_0}")]
+    ForkedState(String),
     /// Trie witness error.
     #[display("trie witness error: {_0}")]
     TrieWitnessError(String),
@@ -132,6 +138,9 @@ pub enum ProviderError {
     /// Static File is finalized and cannot be written to.
     #[display("unable to write block #{_1} to finalized static file {_0}")]
     FinalizedStaticFile(StaticFileSegment, BlockNumber),
+    /// Static File is not finalized yet and cannot be replaced.
+    #[display("unable to replace static file {_0} of block #{_1} that is not finalized")]
+    UnfinalizedStaticFile(StaticFileSegment, BlockNumber),
     /// Trying to insert data from an unexpected block number.
     #[display("trying to append data to {_0} as block #{_1} but expected block #{_2}")]
     UnexpectedStaticFileBlockNumber(StaticFileSegment, BlockNumber, BlockNumber),
@@ -0,0 +1,181 @@
+use crate::ForkedStateError;
+use parking_lot::Mutex;
+use reth_fs_util::FsPathError;
+use reth_primitives::{
+    keccak256, Account, Address, Bytecode, Bytes, StorageKey, StorageValue, B256, U256,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::Write,
+    path::Path,
+};
+use tracing::warn;
+
+/// Accounts, storage slots and code fetched from the forked network.
+#[derive(Debug, Default)]
+pub(crate) struct ForkCache {
+    accounts: HashMap<Address, Option<Account>>,
+    storage: HashMap<(Address, StorageKey), StorageValue>,
+    bytecodes: HashMap<B256, Bytecode>,
+    file: Option<Mutex<File>>,
+}
+
+/// An entry of the cache file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum CacheEntry {
+    /// An account, `None` if it's empty.
+    Account { address: Address, account: Option<RemoteAccount> },
+    /// A storage slot.
+    Storage { address: Address, key: StorageKey, value: StorageValue },
+}
+
+/// An account as it's returned by the forked network.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub(crate) struct RemoteAccount {
+    pub(crate) balance: U256,
+    pub(crate) nonce: u64,
+    pub(crate) code: Bytes,
+}
+
+impl ForkCache {
+    /// Opens the cache file, replaying its entries, and appends fetched entries to it.
+    ///
+    /// Lines that can't be decoded, e.g. because the node was stopped while writing them, are
+    /// skipped.
+    pub(crate) fn open(path: &Path) -> Result<Self, ForkedStateError> {
+        let mut cache = Self::default();
+        let mut content = String::new();
+        if path.exists() {
+            content = reth_fs_util::read_to_string(path)?;
+            for line in content.lines().filter(|line| !line.is_empty()) {
+                match serde_json::from_str(line) {
+                    Ok(entry) => cache.insert(entry),
+                    Err(err) => warn!(target: "forked_state", ?path, %err, "Skipping cache entry"),
+                }
+            }
+        } else if let Some(parent) = path.parent() {
+            reth_fs_util::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|err| FsPathError::open(err, path))?;
+        if !content.is_empty() && !content.ends_with('\n') {
+            file.write_all(b"\n").map_err(|err| FsPathError::write(err, path))?;
+        }
+        cache.file = Some(Mutex::new(file));
+
+        Ok(cache)
+    }
+
+    pub(crate) fn account(&self, address: &Address) -> Option<Option<Account>> {
+        self.accounts.get(address).copied()
+    }
+
+    pub(crate) fn storage(&self, address: Address, key: StorageKey) -> Option<StorageValue> {
+        self.storage.get(&(address, key)).copied()
+    }
+
+    pub(crate) fn bytecode(&self, code_hash: &B256) -> Option<Bytecode> {
+        self.bytecodes.get(code_hash).cloned()
+    }
+
+    /// Caches the account and returns it, `None` if it's empty.
+    pub(crate) fn insert_account(
+        &mut self,
+        address: Address,
+        account: RemoteAccount,
+    ) -> Option<Account> {
+        let entry = CacheEntry::Account {
+            address,
+            account: (account != RemoteAccount::default()).then_some(account),
+        };
+        self.append(&entry);
+        self.insert(entry);
+        self.accounts[&address]
+    }
+
+    pub(crate) fn insert_storage(
+        &mut self,
+        address: Address,
+        key: StorageKey,
+        value: StorageValue,
+    ) {
+        let entry = CacheEntry::Storage { address, key, value };
+        self.append(&entry);
+        self.insert(entry);
+    }
+
+    fn insert(&mut self, entry: CacheEntry) {
+        match entry {
+            CacheEntry::Account { address, account } => {
+                let account = account.map(|account| {
+                    let bytecode_hash = (!account.code.is_empty()).then(|| {
+                        let hash = keccak256(&account.code);
+                        self.bytecodes.insert(hash, Bytecode::new_raw(account.code));
+                        hash
+                    });
+                    Account { nonce: account.nonce, balance: account.balance, bytecode_hash }
+                });
+                self.accounts.insert(address, account);
+            }
+            CacheEntry::Storage { address, key, value } => {
+                self.storage.insert((address, key), value);
+            }
+        }
+    }
+
+    /// Appends the entry to the cache file. Failures are logged, since the entry is still cached
+    /// in memory.
+    fn append(&self, entry: &CacheEntry) {
+        let Some(file) = &self.file else { return };
+        let mut line = serde_json::to_vec(entry).expect("cache entry is serializable");
+        line.push(b'\n');
+        if let Err(err) = file.lock().write_all(&line) {
+            warn!(target: "forked_state", %err, "Failed to write cache entry");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_file_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.jsonl");
+        let address = Address::with_last_byte(1);
+        let empty = Address::with_last_byte(2);
+        let code = Bytes::from_static(&[0x60, 0x00]);
+        let account = RemoteAccount { balance: U256::from(10), nonce: 1, code: code.clone() };
+
+        let mut cache = ForkCache::open(&path).unwrap();
+        let inserted = cache.insert_account(address, account).unwrap();
+        assert_eq!(inserted.bytecode_hash, Some(keccak256(&code)));
+        assert_eq!(cache.insert_account(empty, RemoteAccount::default()), None);
+        cache.insert_storage(address, B256::with_last_byte(1), U256::from(5));
+        drop(cache);
+
+        // a partially written entry is skipped and the next entry starts on a new line
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"type\":\"sto").unwrap();
+        drop(file);
+        let mut cache = ForkCache::open(&path).unwrap();
+        cache.insert_storage(address, B256::with_last_byte(2), U256::from(6));
+        drop(cache);
+
+        let cache = ForkCache::open(&path).unwrap();
+        assert_eq!(cache.account(&address), Some(Some(inserted)));
+        assert_eq!(cache.account(&empty), Some(None));
+        assert_eq!(cache.account(&Address::with_last_byte(3)), None);
+        assert_eq!(cache.bytecode(&keccak256(&code)), Some(Bytecode::new_raw(code)));
+        assert_eq!(cache.storage(address, B256::with_last_byte(1)), Some(U256::from(5)));
+        assert_eq!(cache.storage(address, B256::with_last_byte(2)), Some(U256::from(6)));
+    }
+}
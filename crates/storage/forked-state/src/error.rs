@@ -0,0 +1,32 @@
+use reth_fs_util::FsPathError;
+use reth_primitives::BlockNumber;
+use reth_storage_errors::provider::ProviderError;
+
+/// Errors that can occur when reading the state of a forked network.
+#[derive(Debug, thiserror::Error)]
+pub enum ForkedStateError {
+    /// IO error.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Filesystem error.
+    #[error(transparent)]
+    FsPath(#[from] FsPathError),
+    /// Error encoding or decoding JSON.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// Error requesting the forked network.
+    #[error(transparent)]
+    Transport(#[from] alloy_transport::TransportError),
+    /// The forked network doesn't have the block to fork at.
+    #[error("block {0} not found on the forked network")]
+    BlockNotFound(BlockNumber),
+    /// The runtime that requests the forked network was shut down.
+    #[error("runtime was shut down")]
+    RuntimeShutdown,
+}
+
+impl From<ForkedStateError> for ProviderError {
+    fn from(err: ForkedStateError) -> Self {
+        Self::ForkedState(err.to_string())
+    }
+}
@@ -0,0 +1,24 @@
+//! The state of a network that a dev chain is forked from, read over JSON-RPC.
+//!
+//! [`RpcForkedState`] fetches accounts, storage slots and code of the forked network at the
+//! [`ForkPoint`] lazily, when they are first read, and caches them. The cache can be kept in a
+//! file, so a restarted node doesn't fetch them again.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod cache;
+
+mod error;
+pub use error::ForkedStateError;
+
+mod point;
+pub use point::ForkPoint;
+
+mod rpc;
+pub use rpc::RpcForkedState;
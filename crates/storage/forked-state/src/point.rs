@@ -0,0 +1,53 @@
+use crate::ForkedStateError;
+use reth_primitives::{BlockHash, BlockNumber, ChainId};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The block of a network that a dev chain is forked at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkPoint {
+    /// Chain id of the forked network.
+    pub chain_id: ChainId,
+    /// Number of the block.
+    pub block_number: BlockNumber,
+    /// Hash of the block.
+    pub block_hash: BlockHash,
+}
+
+impl ForkPoint {
+    /// Loads the fork point from the given file, if it exists.
+    pub fn load(path: &Path) -> Result<Option<Self>, ForkedStateError> {
+        if !path.exists() {
+            return Ok(None)
+        }
+        Ok(Some(reth_fs_util::read_json_file(path)?))
+    }
+
+    /// Saves the fork point to the given file, creating its parent directory.
+    pub fn save(&self, path: &Path) -> Result<(), ForkedStateError> {
+        if let Some(parent) = path.parent() {
+            reth_fs_util::create_dir_all(parent)?;
+        }
+        reth_fs_util::write_json_file(path, self)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::B256;
+
+    #[test]
+    fn save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fork").join("fork.json");
+        assert_eq!(ForkPoint::load(&path).unwrap(), None);
+
+        let point =
+            ForkPoint { chain_id: 1, block_number: 100, block_hash: B256::with_last_byte(1) };
+        point.save(&path).unwrap();
+        assert_eq!(ForkPoint::load(&path).unwrap(), Some(point));
+    }
+}
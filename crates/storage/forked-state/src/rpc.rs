@@ -0,0 +1,157 @@
+use crate::{
+    cache::{ForkCache, RemoteAccount},
+    ForkPoint, ForkedStateError,
+};
+use alloy_provider::{Provider, ReqwestProvider};
+use parking_lot::RwLock;
+use reth_primitives::{
+    Account, Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode, StorageKey, StorageValue,
+    B256, U256,
+};
+use reth_storage_api::ForkedState;
+use reth_storage_errors::provider::ProviderResult;
+use std::{
+    future::{Future, IntoFuture},
+    panic::AssertUnwindSafe,
+    path::Path,
+};
+use tokio::runtime::Handle;
+use tracing::trace;
+use url::Url;
+
+/// The state of a forked network, fetched over JSON-RPC.
+///
+/// State is fetched at the [`ForkPoint`] when it's first read and cached afterwards.
+#[derive(Debug)]
+pub struct RpcForkedState {
+    provider: ReqwestProvider,
+    fork_point: ForkPoint,
+    runtime: Handle,
+    cache: RwLock<ForkCache>,
+}
+
+impl RpcForkedState {
+    /// Connects to the RPC endpoint of the network, forking it at the given block or at its
+    /// latest block.
+    pub fn connect(
+        url: Url,
+        block_number: Option<BlockNumber>,
+        runtime: Handle,
+    ) -> Result<Self, ForkedStateError> {
+        let provider = ReqwestProvider::new_http(url);
+        let fork_point = block_on(&runtime, {
+            let provider = provider.clone();
+            async move {
+                let chain_id = provider.get_chain_id().await?;
+                let block_number = match block_number {
+                    Some(block_number) => block_number,
+                    None => provider.get_block_number().await?,
+                };
+                let block = provider
+                    .get_block_by_number(BlockNumberOrTag::Number(block_number), false)
+                    .await?
+                    .ok_or(ForkedStateError::BlockNotFound(block_number))?;
+                let block_hash =
+                    block.header.hash.ok_or(ForkedStateError::BlockNotFound(block_number))?;
+                Ok::<_, ForkedStateError>(ForkPoint { chain_id, block_number, block_hash })
+            }
+        })?;
+
+        Ok(Self { provider, fork_point, runtime, cache: Default::default() })
+    }
+
+    /// Keeps the fetched state in the given file, and reads the state fetched previously from it.
+    ///
+    /// The file must only be used for the same fork point.
+    pub fn with_cache_file(mut self, path: &Path) -> Result<Self, ForkedStateError> {
+        self.cache = RwLock::new(ForkCache::open(path)?);
+        Ok(self)
+    }
+
+    /// Returns the block the network is forked at.
+    pub const fn fork_point(&self) -> ForkPoint {
+        self.fork_point
+    }
+
+    const fn block_id(&self) -> BlockId {
+        BlockId::number(self.fork_point.block_number)
+    }
+}
+
+impl ForkedState for RpcForkedState {
+    fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
+        if let Some(account) = self.cache.read().account(&address) {
+            return Ok(account)
+        }
+
+        trace!(target: "forked_state", %address, "Fetching account");
+        let block_id = self.block_id();
+        let provider = self.provider.clone();
+        let account = block_on(&self.runtime, async move {
+            let (balance, nonce, code) = tokio::try_join!(
+                provider.get_balance(address).block_id(block_id).into_future(),
+                provider.get_transaction_count(address).block_id(block_id).into_future(),
+                provider.get_code_at(address).block_id(block_id).into_future(),
+            )?;
+            Ok::<_, ForkedStateError>(RemoteAccount { balance, nonce, code })
+        })?;
+
+        Ok(self.cache.write().insert_account(address, account))
+    }
+
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        let cached = self.cache.read().storage(account, storage_key);
+        let value = if let Some(value) = cached {
+            value
+        } else {
+            trace!(target: "forked_state", %account, %storage_key, "Fetching storage slot");
+            let block_id = self.block_id();
+            let provider = self.provider.clone();
+            let value = block_on(&self.runtime, async move {
+                provider
+                    .get_storage_at(account, U256::from_be_bytes(storage_key.0))
+                    .block_id(block_id)
+                    .await
+                    .map_err(ForkedStateError::from)
+            })?;
+            self.cache.write().insert_storage(account, storage_key, value);
+            value
+        };
+
+        Ok((!value.is_zero()).then_some(value))
+    }
+
+    fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        Ok(self.cache.read().bytecode(&code_hash))
+    }
+}
+
+/// Runs the future to completion on the blocking pool of the runtime and waits for its output.
+///
+/// State is read from blocking code, which may run on a thread of the runtime itself, e.g. when
+/// an RPC handler reads it, where the future can't be run directly. Running it on the blocking
+/// pool works from any thread and doesn't spawn a thread per request.
+fn block_on<F, T>(runtime: &Handle, future: F) -> Result<T, ForkedStateError>
+where
+    F: Future<Output = Result<T, ForkedStateError>> + Send + 'static,
+    T: Send + 'static,
+{
+    let (tx, rx) = std::sync::mpsc::sync_channel(1);
+    runtime.spawn_blocking({
+        let runtime = runtime.clone();
+        move || {
+            let output = std::panic::catch_unwind(AssertUnwindSafe(|| runtime.block_on(future)));
+            let _ = tx.send(output);
+        }
+    });
+
+    match rx.recv() {
+        Ok(Ok(output)) => output,
+        Ok(Err(payload)) => std::panic::resume_unwind(payload),
+        Err(_) => Err(ForkedStateError::RuntimeShutdown),
+    }
+}
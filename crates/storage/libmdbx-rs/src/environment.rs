@@ -152,6 +152,21 @@ impl Environment {
         mdbx_result(unsafe { ffi::mdbx_env_sync_ex(self.env_ptr(), force, false) })
     }
 
+    /// Copies the environment to the given file descriptor, e.g. to make a backup of an
+    /// environment that is in use.
+    ///
+    /// The copy is made from a read-only transaction, so it's a consistent snapshot of the
+    /// environment. If `compact` is set, free pages are omitted and all pages are renumbered
+    /// sequentially.
+    ///
+    /// The file descriptor can be a pipe or socket, unless the environment has leaked pages.
+    #[cfg(unix)]
+    pub fn copy_to_fd(&self, fd: std::os::fd::RawFd, compact: bool) -> Result<()> {
+        let flags = if compact { ffi::MDBX_CP_COMPACT } else { ffi::MDBX_CP_DEFAULTS };
+        mdbx_result(unsafe { ffi::mdbx_env_copy2fd(self.env_ptr(), fd, flags) })?;
+        Ok(())
+    }
+
     /// Retrieves statistics about this environment.
     pub fn stat(&self) -> Result<Stat> {
         unsafe {
@@ -81,6 +81,31 @@ fn test_sync() {
     }
 }
 
+#[test]
+#[cfg(unix)]
+fn test_copy_to_fd() {
+    use std::os::fd::AsRawFd;
+
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+    {
+        let txn = env.begin_rw_txn().unwrap();
+        let db = txn.open_db(None).unwrap();
+        txn.put(db.dbi(), b"key", b"value", WriteFlags::empty()).unwrap();
+        txn.commit().unwrap();
+    }
+
+    let copy_dir = tempdir().unwrap();
+    let file = std::fs::File::create(copy_dir.path().join("mdbx.dat")).unwrap();
+    env.copy_to_fd(file.as_raw_fd(), false).unwrap();
+    drop(file);
+
+    let copy = Environment::builder().open(copy_dir.path()).unwrap();
+    let txn = copy.begin_ro_txn().unwrap();
+    let db = txn.open_db(None).unwrap();
+    assert_eq!(txn.get::<[u8; 5]>(db.dbi(), b"key").unwrap(), Some(*b"value"));
+}
+
 #[test]
 fn test_stat() {
     let dir = tempdir().unwrap();
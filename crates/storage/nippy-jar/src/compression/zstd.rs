@@ -56,6 +56,11 @@ impl Zstd {
         self
     }
 
+    /// Returns the compression level.
+    pub const fn level(&self) -> i32 {
+        self.level
+    }
+
     /// Creates a list of [`Decompressor`] if using dictionaries.
     pub fn decompressors(&self) -> Result<Vec<Decompressor<'_>>, NippyJarError> {
         if let Some(dictionaries) = &self.dictionaries {
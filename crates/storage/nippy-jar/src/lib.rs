@@ -184,6 +184,15 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self
     }
 
+    /// Adds [`compression::Zstd`] compression without dictionaries, using the given compression
+    /// level.
+    pub fn with_zstd_level(mut self, level: i32) -> Self {
+        self.compressor = Some(Compressors::Zstd(
+            compression::Zstd::new(false, 0, self.columns).with_level(level),
+        ));
+        self
+    }
+
     /// Adds [`compression::Lz4`] compression.
     pub fn with_lz4(mut self) -> Self {
         self.compressor = Some(Compressors::Lz4(compression::Lz4::default()));
@@ -303,6 +312,25 @@ impl<H: NippyJarHeader> NippyJar<H> {
         Ok(())
     }
 
+    /// Moves the data, offsets and configuration files of the jar at `from` to `to`, replacing the
+    /// existing ones.
+    ///
+    /// The configuration file is moved last, and files that are missing at `from` are skipped, so
+    /// an interrupted move can be resumed by calling this again.
+    pub fn move_files(from: &Path, to: &Path) -> Result<(), NippyJarError> {
+        for extension in [None, Some(OFFSETS_FILE_EXTENSION), Some(CONFIG_FILE_EXTENSION)] {
+            let (from, to) = match extension {
+                Some(extension) => (from.with_extension(extension), to.with_extension(extension)),
+                None => (from.to_path_buf(), to.to_path_buf()),
+            };
+            if from.exists() {
+                reth_fs_util::rename(from, to)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Returns a [`DataReader`] of the data and offset file
     pub fn open_data_reader(&self) -> Result<DataReader, NippyJarError> {
         DataReader::new(self.data_path())
@@ -871,6 +899,33 @@ mod tests {
     }
 
     /// Tests `NippyJar` with everything enabled: compression, filter, offset list and offset index.
+    #[test]
+    fn test_zstd_level() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let nippy = NippyJar::new_without_header(num_columns, file_path.path())
+            .with_zstd_level(19)
+            .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows)
+            .unwrap();
+
+        let loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy, loaded_nippy);
+        assert!(
+            matches!(loaded_nippy.compressor(), Some(Compressors::Zstd(zstd)) if !zstd.use_dict && zstd.level() == 19)
+        );
+
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let mut row_index = 0usize;
+        while let Some(row) = cursor.next_row().unwrap() {
+            assert_eq!((row[0], row[1]), (col1[row_index].as_slice(), col2[row_index].as_slice()));
+            row_index += 1;
+        }
+        assert_eq!(row_index, col1.len());
+    }
+
     #[test]
     fn test_full_nippy_jar() {
         let (col1, col2) = test_data(None);
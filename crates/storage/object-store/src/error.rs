@@ -0,0 +1,31 @@
+use reth_fs_util::FsPathError;
+
+/// Errors that can occur when accessing an object store.
+#[derive(Debug, thiserror::Error)]
+pub enum ObjectStoreError {
+    /// IO error.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Filesystem error.
+    #[error(transparent)]
+    FsPath(#[from] FsPathError),
+    /// Error sending a request to the object store.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// The object store rejected a request.
+    #[error("request for {key} failed with {status}: {body}")]
+    Status {
+        /// Key of the requested object.
+        key: String,
+        /// Status code of the response.
+        status: reqwest::StatusCode,
+        /// Body of the response.
+        body: String,
+    },
+    /// The endpoint of the object store is not a valid base URL.
+    #[error("invalid object store endpoint {0}")]
+    InvalidEndpoint(url::Url),
+    /// The object store can't be written to.
+    #[error("object store is read-only")]
+    ReadOnly,
+}
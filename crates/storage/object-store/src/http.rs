@@ -0,0 +1,108 @@
+use crate::{
+    s3::{block_on, status_error},
+    ObjectStore, ObjectStoreError,
+};
+use reqwest::{Method, StatusCode};
+use tokio::runtime::Handle;
+use url::Url;
+
+/// Reads objects published on a web server, e.g. a CDN or a public bucket.
+///
+/// Objects are addressed relative to the base URL, i.e. `<base>/<key>`. The store is read-only,
+/// writing to it fails with [`ObjectStoreError::ReadOnly`].
+#[derive(Debug, Clone)]
+pub struct HttpObjectStore {
+    base: Url,
+    client: reqwest::Client,
+    runtime: Handle,
+}
+
+impl HttpObjectStore {
+    /// Creates a new [`HttpObjectStore`] that reads objects below the given URL, and executes its
+    /// requests on the given runtime.
+    ///
+    /// Requests block until they're completed. When used from within an async task, they're
+    /// awaited on a separate thread.
+    pub fn new(mut base: Url, runtime: Handle) -> Result<Self, ObjectStoreError> {
+        if base.cannot_be_a_base() || !matches!(base.scheme(), "http" | "https") {
+            return Err(ObjectStoreError::InvalidEndpoint(base))
+        }
+        // Keys are joined to the base, which replaces its last segment unless it ends with `/`
+        if !base.path().ends_with('/') {
+            base.set_path(&format!("{}/", base.path()));
+        }
+        Ok(Self { base, client: reqwest::Client::new(), runtime })
+    }
+
+    /// Returns the URL the objects are read from.
+    pub const fn base(&self) -> &Url {
+        &self.base
+    }
+
+    /// Sends a request for the object with the given key, and returns the status and body of the
+    /// response.
+    fn request(
+        &self,
+        method: Method,
+        key: &str,
+    ) -> Result<(StatusCode, Vec<u8>), ObjectStoreError> {
+        let url = self
+            .base
+            .join(key)
+            .map_err(|_| ObjectStoreError::InvalidEndpoint(self.base.clone()))?;
+        let request = self.client.request(method, url);
+
+        block_on(&self.runtime, async move {
+            let response = request.send().await?;
+            let status = response.status();
+            Ok((status, response.bytes().await?.to_vec()))
+        })
+    }
+}
+
+impl ObjectStore for HttpObjectStore {
+    fn put(&self, _key: &str, _data: &[u8]) -> Result<(), ObjectStoreError> {
+        Err(ObjectStoreError::ReadOnly)
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ObjectStoreError> {
+        match self.request(Method::GET, key)? {
+            (StatusCode::NOT_FOUND, _) => Ok(None),
+            (status, body) if status.is_success() => Ok(Some(body)),
+            (status, body) => Err(status_error(key, status, &body)),
+        }
+    }
+
+    fn contains(&self, key: &str) -> Result<bool, ObjectStoreError> {
+        match self.request(Method::HEAD, key)? {
+            (StatusCode::NOT_FOUND, _) => Ok(false),
+            (status, _) if status.is_success() => Ok(true),
+            (status, body) => Err(status_error(key, status, &body)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_url() {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let store = HttpObjectStore::new(
+            "https://snapshots.example.com/mainnet".parse().unwrap(),
+            runtime.handle().clone(),
+        )
+        .unwrap();
+        assert_eq!(store.base().as_str(), "https://snapshots.example.com/mainnet/");
+        assert_eq!(
+            store.base().join("chunks/ab01").unwrap().as_str(),
+            "https://snapshots.example.com/mainnet/chunks/ab01"
+        );
+
+        assert!(matches!(
+            HttpObjectStore::new("file:///snapshots".parse().unwrap(), runtime.handle().clone()),
+            Err(ObjectStoreError::InvalidEndpoint(_))
+        ));
+    }
+}
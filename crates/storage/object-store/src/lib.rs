@@ -0,0 +1,55 @@
+//! Object stores that data can be kept in, e.g. backups or static files.
+//!
+//! Objects are kept either in a local directory, see [`LocalObjectStore`], or in an S3-compatible
+//! object storage, see [`S3ObjectStore`]. Objects published on a web server can be read with
+//! [`HttpObjectStore`].
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use std::{fmt::Debug, sync::Arc};
+
+mod error;
+pub use error::ObjectStoreError;
+
+mod http;
+pub use http::HttpObjectStore;
+
+mod local;
+pub use local::LocalObjectStore;
+
+mod s3;
+pub use s3::{parse_s3_location, S3Config, S3ObjectStore};
+
+/// A key-value object store.
+///
+/// Keys are relative paths with `/` as separator, e.g. `chunks/<hash>`.
+pub trait ObjectStore: Debug + Send + Sync {
+    /// Stores the object under the given key, replacing any existing one.
+    fn put(&self, key: &str, data: &[u8]) -> Result<(), ObjectStoreError>;
+
+    /// Returns the object stored under the given key, if any.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ObjectStoreError>;
+
+    /// Returns `true` if an object is stored under the given key.
+    fn contains(&self, key: &str) -> Result<bool, ObjectStoreError>;
+}
+
+impl<T: ObjectStore + ?Sized> ObjectStore for Arc<T> {
+    fn put(&self, key: &str, data: &[u8]) -> Result<(), ObjectStoreError> {
+        (**self).put(key, data)
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ObjectStoreError> {
+        (**self).get(key)
+    }
+
+    fn contains(&self, key: &str) -> Result<bool, ObjectStoreError> {
+        (**self).contains(key)
+    }
+}
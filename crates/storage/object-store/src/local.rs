@@ -0,0 +1,51 @@
+use crate::{ObjectStore, ObjectStoreError};
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+/// Keeps objects in a local directory, e.g. a mounted network drive.
+#[derive(Debug, Clone)]
+pub struct LocalObjectStore {
+    root: PathBuf,
+}
+
+impl LocalObjectStore {
+    /// Creates a new [`LocalObjectStore`] that keeps objects in the given directory.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Returns the directory the objects are kept in.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl ObjectStore for LocalObjectStore {
+    fn put(&self, key: &str, data: &[u8]) -> Result<(), ObjectStoreError> {
+        let path = self.root.join(key);
+        if let Some(parent) = path.parent() {
+            reth_fs_util::create_dir_all(parent)?;
+        }
+
+        // Written to a temporary file first, so an interrupted write never leaves a partial object
+        let mut tmp_path = path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        reth_fs_util::write(&tmp_path, data)?;
+        reth_fs_util::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ObjectStoreError> {
+        match std::fs::read(self.root.join(key)) {
+            Ok(data) => Ok(Some(data)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn contains(&self, key: &str) -> Result<bool, ObjectStoreError> {
+        Ok(self.root.join(key).is_file())
+    }
+}
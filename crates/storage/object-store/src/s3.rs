@@ -0,0 +1,292 @@
+use crate::{ObjectStore, ObjectStoreError};
+use alloy_primitives::hex;
+use hmac::{Hmac, Mac};
+use reqwest::{header::AUTHORIZATION, Method, StatusCode};
+use sha2::{Digest, Sha256};
+use std::{
+    fmt::{self, Write},
+    future::Future,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::runtime::Handle;
+use url::Url;
+
+/// Headers included in the request signature.
+const SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+
+/// Configuration of an S3-compatible object storage.
+#[derive(Clone)]
+pub struct S3Config {
+    /// Endpoint of the storage, e.g. `https://s3.us-east-1.amazonaws.com`.
+    pub endpoint: Url,
+    /// Region of the bucket, e.g. `us-east-1`.
+    pub region: String,
+    /// Name of the bucket.
+    pub bucket: String,
+    /// Prefix of the keys of all objects, e.g. `mainnet/`.
+    pub prefix: String,
+    /// Access key id of the credentials.
+    pub access_key_id: String,
+    /// Secret access key of the credentials.
+    pub secret_access_key: String,
+}
+
+impl fmt::Debug for S3Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("S3Config")
+            .field("endpoint", &self.endpoint)
+            .field("region", &self.region)
+            .field("bucket", &self.bucket)
+            .field("prefix", &self.prefix)
+            .field("access_key_id", &self.access_key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Keeps objects in an S3-compatible object storage, e.g. AWS S3, `MinIO` or Cloudflare R2.
+///
+/// Objects are addressed with path-style URLs, i.e. `<endpoint>/<bucket>/<key>`, and requests
+/// are signed with AWS Signature Version 4.
+#[derive(Debug, Clone)]
+pub struct S3ObjectStore {
+    config: S3Config,
+    client: reqwest::Client,
+    runtime: Handle,
+}
+
+impl S3ObjectStore {
+    /// Creates a new [`S3ObjectStore`] that executes its requests on the given runtime.
+    ///
+    /// Requests block until they're completed. When used from within an async task, they're
+    /// awaited on a separate thread.
+    pub fn new(config: S3Config, runtime: Handle) -> Self {
+        Self { config, client: reqwest::Client::new(), runtime }
+    }
+
+    /// Sends a signed request for the object with the given key, and returns the status and body
+    /// of the response.
+    fn request(
+        &self,
+        method: Method,
+        key: &str,
+        body: Option<&[u8]>,
+    ) -> Result<(StatusCode, Vec<u8>), ObjectStoreError> {
+        let mut url = self.config.endpoint.clone();
+        url.set_path(&format!(
+            "/{}/{}",
+            self.config.bucket,
+            uri_encode_path(&format!("{}{key}", self.config.prefix))
+        ));
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_string(),
+            (None, _) => return Err(ObjectStoreError::InvalidEndpoint(url)),
+        };
+
+        let payload_hash = hex::encode(Sha256::digest(body.unwrap_or_default()));
+        let (timestamp, date) = amz_date(SystemTime::now());
+        let scope = format!("{date}/{}/s3/aws4_request", self.config.region);
+
+        let canonical_request = format!(
+            "{method}\n{}\n\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{timestamp}\n\n{SIGNED_HEADERS}\n{payload_hash}",
+            url.path()
+        );
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{timestamp}\n{scope}\n{}",
+            hex::encode(Sha256::digest(canonical_request))
+        );
+        let signing_key =
+            signing_key(&self.config.secret_access_key, &date, &self.config.region, "s3");
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={SIGNED_HEADERS}, Signature={signature}",
+            self.config.access_key_id
+        );
+
+        let mut request = self
+            .client
+            .request(method, url)
+            .header("x-amz-date", timestamp)
+            .header("x-amz-content-sha256", payload_hash)
+            .header(AUTHORIZATION, authorization);
+        if let Some(body) = body {
+            request = request.body(body.to_vec());
+        }
+
+        block_on(&self.runtime, async move {
+            let response = request.send().await?;
+            let status = response.status();
+            Ok((status, response.bytes().await?.to_vec()))
+        })
+    }
+}
+
+impl ObjectStore for S3ObjectStore {
+    fn put(&self, key: &str, data: &[u8]) -> Result<(), ObjectStoreError> {
+        match self.request(Method::PUT, key, Some(data))? {
+            (status, _) if status.is_success() => Ok(()),
+            (status, body) => Err(status_error(key, status, &body)),
+        }
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ObjectStoreError> {
+        match self.request(Method::GET, key, None)? {
+            (StatusCode::NOT_FOUND, _) => Ok(None),
+            (status, body) if status.is_success() => Ok(Some(body)),
+            (status, body) => Err(status_error(key, status, &body)),
+        }
+    }
+
+    fn contains(&self, key: &str) -> Result<bool, ObjectStoreError> {
+        match self.request(Method::HEAD, key, None)? {
+            (StatusCode::NOT_FOUND, _) => Ok(false),
+            (status, _) if status.is_success() => Ok(true),
+            (status, body) => Err(status_error(key, status, &body)),
+        }
+    }
+}
+
+/// Splits an S3 location, e.g. `s3://my-bucket/mainnet/`, into its bucket and key prefix.
+///
+/// The prefix is either empty or ends with `/`. Returns `None` if the location is not an S3
+/// location or has no bucket.
+pub fn parse_s3_location(location: &str) -> Option<(String, String)> {
+    let location = location.strip_prefix("s3://")?;
+    let (bucket, prefix) = location.split_once('/').unwrap_or((location, ""));
+    if bucket.is_empty() {
+        return None
+    }
+
+    let mut prefix = prefix.to_string();
+    if !prefix.is_empty() && !prefix.ends_with('/') {
+        prefix.push('/');
+    }
+    Some((bucket.to_string(), prefix))
+}
+
+/// Runs the future to completion on the runtime.
+///
+/// Blocking on the runtime from one of its own threads panics, so in that case the future is run
+/// from a separate thread.
+pub(crate) fn block_on<F>(runtime: &Handle, future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    if Handle::try_current().is_err() {
+        return runtime.block_on(future)
+    }
+
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| runtime.block_on(future))
+            .join()
+            .unwrap_or_else(|payload| std::panic::resume_unwind(payload))
+    })
+}
+
+pub(crate) fn status_error(key: &str, status: StatusCode, body: &[u8]) -> ObjectStoreError {
+    ObjectStoreError::Status {
+        key: key.to_string(),
+        status,
+        body: String::from_utf8_lossy(body).into_owned(),
+    }
+}
+
+/// Percent-encodes everything but unreserved characters and `/`.
+fn uri_encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~' | b'/') {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+/// Returns the `x-amz-date` timestamp of the given time, e.g. `20150830T123600Z`, and its date,
+/// e.g. `20150830`.
+fn amz_date(time: SystemTime) -> (String, String) {
+    let seconds = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let (days, seconds_of_day) = ((seconds / 86400) as i64, seconds % 86400);
+
+    // <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    let date = format!("{year:04}{month:02}{day:02}");
+    let timestamp = format!(
+        "{date}T{:02}{:02}{:02}Z",
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60
+    );
+    (timestamp, date)
+}
+
+/// Derives the key that signs the requests of a day.
+fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> [u8; 32] {
+    let key = hmac_sha256(format!("AWS4{secret_access_key}").as_bytes(), date.as_bytes());
+    let key = hmac_sha256(&key, region.as_bytes());
+    let key = hmac_sha256(&key, service.as_bytes());
+    hmac_sha256(&key, b"aws4_request")
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn date() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_440_938_160);
+        assert_eq!(amz_date(time), ("20150830T123600Z".to_string(), "20150830".to_string()));
+
+        let time = UNIX_EPOCH + Duration::from_secs(951_825_600);
+        assert_eq!(amz_date(time).0, "20000229T120000Z");
+    }
+
+    #[test]
+    fn signing_key_derivation() {
+        // <https://docs.aws.amazon.com/IAM/latest/UserGuide/reference_sigv-create-signed-request.html>
+        let key =
+            signing_key("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY", "20150830", "us-east-1", "iam");
+        assert_eq!(
+            hex::encode(key),
+            "c4afb1cc5771d871763a393e44b703571b55cc28424d1a5e86da6ed3c154a4b9"
+        );
+    }
+
+    #[test]
+    fn location() {
+        assert_eq!(
+            parse_s3_location("s3://bucket/mainnet"),
+            Some(("bucket".to_string(), "mainnet/".to_string()))
+        );
+        assert_eq!(parse_s3_location("s3://bucket"), Some(("bucket".to_string(), String::new())));
+        assert_eq!(parse_s3_location("s3:///mainnet"), None);
+        assert_eq!(parse_s3_location("/backups"), None);
+    }
+
+    #[test]
+    fn encode_path() {
+        assert_eq!(uri_encode_path("mainnet/chunks/ab01"), "mainnet/chunks/ab01");
+        assert_eq!(uri_encode_path("my backups/LATEST"), "my%20backups/LATEST");
+    }
+}
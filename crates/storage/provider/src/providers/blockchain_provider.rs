@@ -1,11 +1,13 @@
 use crate::{
-    providers::StaticFileProvider, AccountReader, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, BlockSource, CanonChainTracker, CanonStateNotifications,
-    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    DatabaseProviderRO, EvmEnvProvider, FinalizedBlockReader, HeaderProvider, ProviderError,
-    ProviderFactory, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    RequestsProvider, StageCheckpointReader, StateProviderBox, StateProviderFactory,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    providers::{StaticFileProvider, StaticFileSegmentStats},
+    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
+    BlockSource, CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions,
+    ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory, DatabaseProviderRO,
+    EvmEnvProvider, ExExCheckpointReader, ExExCheckpointWriter, FinalizedBlockReader,
+    HeaderProvider, ProviderError, ProviderFactory, PruneCheckpointReader, ReceiptProvider,
+    ReceiptProviderIdExt, RequestsProvider, StageCheckpointReader, StateHistoryReader,
+    StateProviderBox, StateProviderFactory, StaticFileProviderFactory, StorageStatsReader,
+    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use alloy_rpc_types_engine::ForkchoiceState;
 use reth_chain_state::{
@@ -15,14 +17,16 @@ use reth_chain_state::{
 use reth_chainspec::{ChainInfo, ChainSpec};
 use reth_db_api::{
     database::Database,
+    database_metrics::{DatabaseMetrics, TableStats},
     models::{AccountBeforeTx, StoredBlockBodyIndices},
 };
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
     BlockNumberOrTag, BlockWithSenders, EthereumHardforks, Header, Receipt, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, TransactionMeta, TransactionSigned,
-    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+    SealedBlockWithSenders, SealedHeader, StorageEntry, StorageKey, TransactionMeta,
+    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
+    U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -1115,6 +1119,63 @@ where
     }
 }
 
+impl<DB> StorageStatsReader for BlockchainProvider2<DB>
+where
+    DB: Database + DatabaseMetrics,
+{
+    fn table_stats(&self) -> ProviderResult<Vec<TableStats>> {
+        self.database.table_stats()
+    }
+
+    fn static_file_stats(&self) -> ProviderResult<Vec<StaticFileSegmentStats>> {
+        self.database.static_file_stats()
+    }
+}
+
+impl<DB> StateHistoryReader for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    // the history indices only contain persisted blocks
+
+    fn account_history(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        self.database.provider()?.account_history(address, range, limit)
+    }
+
+    fn storage_history(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<(BlockNumber, U256)>> {
+        self.database.provider()?.storage_history(address, storage_key, range, limit)
+    }
+}
+
+impl<DB> ExExCheckpointReader for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    fn get_exex_checkpoint(&self, id: &str) -> ProviderResult<Option<BlockNumber>> {
+        self.database.get_exex_checkpoint(id)
+    }
+}
+
+impl<DB> ExExCheckpointWriter for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    fn save_exex_checkpoint(&self, id: &str, block_number: BlockNumber) -> ProviderResult<()> {
+        self.database.save_exex_checkpoint(id, block_number)
+    }
+}
+
 impl<DB> ChainSpecProvider for BlockchainProvider2<DB>
 where
     DB: Send + Sync,
@@ -1419,6 +1480,36 @@ where
             self.database.provider()?.account_block_changeset(block_number)
         }
     }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        if let Some(state) = self.canonical_in_memory_state.state_by_number(block_number) {
+            let changesets = state
+                .block()
+                .execution_output
+                .bundle
+                .reverts
+                .clone()
+                .into_plain_state_reverts()
+                .storage
+                .into_iter()
+                .flatten()
+                .flat_map(|revert| {
+                    revert.storage_revert.into_iter().map(move |(key, value)| {
+                        (
+                            revert.address,
+                            StorageEntry { key: key.into(), value: value.to_previous_value() },
+                        )
+                    })
+                })
+                .collect();
+            Ok(changesets)
+        } else {
+            self.database.provider()?.storage_block_changeset(block_number)
+        }
+    }
 }
 
 impl<DB> AccountReader for BlockchainProvider2<DB>
@@ -1,15 +1,20 @@
 use crate::{
-    providers::{state::latest::LatestStateProvider, StaticFileProvider},
+    providers::{state::latest::LatestStateProvider, StaticFileProvider, StaticFileSegmentStats},
     to_range,
     traits::{BlockSource, ReceiptProvider},
     BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, ProviderError,
-    PruneCheckpointReader, RequestsProvider, StageCheckpointReader, StateProviderBox,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    EvmEnvProvider, ExExCheckpointReader, ExExCheckpointWriter, ForkedState, HeaderProvider,
+    HeaderSyncGap, HeaderSyncGapProvider, ProviderError, PruneCheckpointReader, RequestsProvider,
+    StageCheckpointReader, StateProviderBox, StaticFileProviderFactory, StorageStatsReader,
+    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use reth_chainspec::{ChainInfo, ChainSpec, EthChainSpec};
 use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
-use reth_db_api::{database::Database, models::StoredBlockBodyIndices};
+use reth_db_api::{
+    database::Database,
+    database_metrics::{DatabaseMetrics, TableStats},
+    models::StoredBlockBodyIndices,
+};
 use reth_errors::{RethError, RethResult};
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
@@ -48,6 +53,8 @@ pub struct ProviderFactory<DB, Spec = ChainSpec> {
     static_file_provider: StaticFileProvider,
     /// Optional pruning configuration
     prune_modes: PruneModes,
+    /// The state of the network the chain is forked from, if it's a fork.
+    forked_state: Option<Arc<dyn ForkedState>>,
 }
 
 impl<DB> ProviderFactory<DB> {
@@ -57,7 +64,13 @@ impl<DB> ProviderFactory<DB> {
         chain_spec: Arc<ChainSpec>,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { db: Arc::new(db), chain_spec, static_file_provider, prune_modes: PruneModes::none() }
+        Self {
+            db: Arc::new(db),
+            chain_spec,
+            static_file_provider,
+            prune_modes: PruneModes::none(),
+            forked_state: None,
+        }
     }
 
     /// Enables metrics on the static file provider.
@@ -72,6 +85,18 @@ impl<DB> ProviderFactory<DB> {
         self
     }
 
+    /// Sets the state of the network the chain is forked from, which state providers read for
+    /// accounts and storage slots that were never written locally.
+    pub fn with_forked_state(mut self, forked_state: Arc<dyn ForkedState>) -> Self {
+        self.forked_state = Some(forked_state);
+        self
+    }
+
+    /// Returns the state of the network the chain is forked from, if it's a fork.
+    pub fn forked_state(&self) -> Option<&Arc<dyn ForkedState>> {
+        self.forked_state.as_ref()
+    }
+
     /// Returns reference to the underlying database.
     pub fn db_ref(&self) -> &DB {
         &self.db
@@ -98,6 +123,7 @@ impl ProviderFactory<DatabaseEnv> {
             chain_spec,
             static_file_provider,
             prune_modes: PruneModes::none(),
+            forked_state: None,
         })
     }
 }
@@ -116,7 +142,8 @@ impl<DB: Database> ProviderFactory<DB> {
             self.chain_spec.clone(),
             self.static_file_provider.clone(),
             self.prune_modes.clone(),
-        ))
+        )
+        .with_forked_state(self.forked_state.clone()))
     }
 
     /// Returns a provider with a created `DbTxMut` inside, which allows fetching and updating
@@ -125,19 +152,28 @@ impl<DB: Database> ProviderFactory<DB> {
     /// open.
     #[track_caller]
     pub fn provider_rw(&self) -> ProviderResult<DatabaseProviderRW<DB>> {
-        Ok(DatabaseProviderRW(DatabaseProvider::new_rw(
-            self.db.tx_mut()?,
-            self.chain_spec.clone(),
-            self.static_file_provider.clone(),
-            self.prune_modes.clone(),
-        )))
+        Ok(DatabaseProviderRW(
+            DatabaseProvider::new_rw(
+                self.db.tx_mut()?,
+                self.chain_spec.clone(),
+                self.static_file_provider.clone(),
+                self.prune_modes.clone(),
+            )
+            .with_forked_state(self.forked_state.clone()),
+        ))
     }
 
     /// State provider for latest block
     #[track_caller]
     pub fn latest(&self) -> ProviderResult<StateProviderBox> {
         trace!(target: "providers::db", "Returning latest state provider");
-        Ok(Box::new(LatestStateProvider::new(self.db.tx()?, self.static_file_provider())))
+        Ok(Box::new(
+            LatestStateProvider::new_detect_hashed_state_fallback(
+                self.db.tx()?,
+                self.static_file_provider(),
+            )?
+            .with_forked_state(self.forked_state.clone()),
+        ))
     }
 
     /// Storage provider for state at that given block
@@ -593,6 +629,31 @@ impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
     }
 }
 
+impl<DB: Database + DatabaseMetrics> StorageStatsReader for ProviderFactory<DB> {
+    fn table_stats(&self) -> ProviderResult<Vec<TableStats>> {
+        Ok(self.db.table_stats()?)
+    }
+
+    fn static_file_stats(&self) -> ProviderResult<Vec<StaticFileSegmentStats>> {
+        self.static_file_provider.segment_stats()
+    }
+}
+
+impl<DB: Database> ExExCheckpointReader for ProviderFactory<DB> {
+    fn get_exex_checkpoint(&self, id: &str) -> ProviderResult<Option<BlockNumber>> {
+        self.provider()?.get_exex_checkpoint(id)
+    }
+}
+
+impl<DB: Database> ExExCheckpointWriter for ProviderFactory<DB> {
+    fn save_exex_checkpoint(&self, id: &str, block_number: BlockNumber) -> ProviderResult<()> {
+        let provider = self.provider_rw()?;
+        provider.save_exex_checkpoint(id, block_number)?;
+        provider.commit()?;
+        Ok(())
+    }
+}
+
 impl<DB, Spec> Clone for ProviderFactory<DB, Spec> {
     fn clone(&self) -> Self {
         Self {
@@ -600,6 +661,7 @@ impl<DB, Spec> Clone for ProviderFactory<DB, Spec> {
             chain_spec: self.chain_spec.clone(),
             static_file_provider: self.static_file_provider.clone(),
             prune_modes: self.prune_modes.clone(),
+            forked_state: self.forked_state.clone(),
         }
     }
 }
@@ -609,7 +671,8 @@ mod tests {
     use crate::{
         providers::{StaticFileProvider, StaticFileWriter},
         test_utils::{blocks::TEST_BLOCK, create_test_provider_factory},
-        BlockHashReader, BlockNumReader, BlockWriter, HeaderSyncGapProvider, TransactionsProvider,
+        BlockHashReader, BlockNumReader, BlockWriter, HeaderSyncGapProvider, StateHistoryReader,
+        TransactionsProvider,
     };
     use assert_matches::assert_matches;
     use rand::Rng;
@@ -618,8 +681,18 @@ mod tests {
         mdbx::DatabaseArguments,
         tables,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
+        BlockNumberList,
+    };
+    use reth_db_api::{
+        models::{
+            storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress, ShardedKey,
+        },
+        transaction::DbTxMut,
+    };
+    use reth_primitives::{
+        static_file::SegmentRangeInclusive, Account, Address, Header, StaticFileSegment,
+        StorageEntry, TxNumber, B256, U256,
     };
-    use reth_primitives::{StaticFileSegment, TxNumber, B256, U256};
     use reth_prune_types::{PruneMode, PruneModes};
     use reth_storage_errors::provider::ProviderError;
     use reth_testing_utils::{
@@ -655,6 +728,21 @@ mod tests {
         provider.block_hash(0).unwrap();
     }
 
+    #[test]
+    fn exex_checkpoints() {
+        let factory = create_test_provider_factory();
+        assert_eq!(factory.get_exex_checkpoint("exex").unwrap(), None);
+
+        factory.save_exex_checkpoint("exex", 10).unwrap();
+        factory.save_exex_checkpoint("other", 5).unwrap();
+        assert_eq!(factory.get_exex_checkpoint("exex").unwrap(), Some(10));
+        assert_eq!(factory.get_exex_checkpoint("other").unwrap(), Some(5));
+
+        // checkpoints can go down on reorgs
+        factory.save_exex_checkpoint("exex", 8).unwrap();
+        assert_eq!(factory.get_exex_checkpoint("exex").unwrap(), Some(8));
+    }
+
     #[test]
     fn provider_factory_with_database_path() {
         let chain_spec = ChainSpecBuilder::mainnet().build();
@@ -781,4 +869,100 @@ mod tests {
         assert_eq!(gap.local_head, head);
         assert_eq!(gap.target.tip(), consensus_tip.into());
     }
+
+    #[test]
+    fn state_history() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let slot = B256::with_last_byte(2);
+        let account =
+            |nonce: u64| Account { nonce, balance: U256::from(nonce), bytecode_hash: None };
+
+        // the account and the slot are changed in blocks 2, 5 and 9
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey::new(address, u64::MAX),
+            BlockNumberList::new([2, 5, 9]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey::new(address, slot, u64::MAX),
+            BlockNumberList::new([2, 5, 9]).unwrap(),
+        )
+        .unwrap();
+        for (block_number, before) in [(2, None), (5, Some(1)), (9, Some(2))] {
+            tx.put::<tables::AccountChangeSets>(
+                block_number,
+                AccountBeforeTx { address, info: before.map(account) },
+            )
+            .unwrap();
+            tx.put::<tables::StorageChangeSets>(
+                BlockNumberAddress((block_number, address)),
+                StorageEntry { key: slot, value: U256::from(before.unwrap_or_default()) },
+            )
+            .unwrap();
+        }
+        tx.put::<tables::PlainAccountState>(address, account(3)).unwrap();
+        tx.put::<tables::PlainStorageState>(
+            address,
+            StorageEntry { key: slot, value: U256::from(3) },
+        )
+        .unwrap();
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.account_history(address, 0..=10, 10).unwrap(),
+            vec![(2, Some(account(1))), (5, Some(account(2))), (9, Some(account(3)))]
+        );
+        assert_eq!(
+            provider.account_history(address, 3..=8, 10).unwrap(),
+            vec![(5, Some(account(2)))]
+        );
+        assert_eq!(
+            provider.account_history(address, 0..=10, 1).unwrap(),
+            vec![(2, Some(account(1)))]
+        );
+        assert_eq!(
+            provider.storage_history(address, slot, 5..=10, 10).unwrap(),
+            vec![(5, U256::from(2)), (9, U256::from(3))]
+        );
+        assert!(provider.storage_history(address, B256::ZERO, 0..=10, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn storage_stats() {
+        let factory = create_test_provider_factory();
+
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::PlainAccountState>(Address::ZERO, Account::default())
+            .unwrap();
+        provider.commit().unwrap();
+
+        let static_file_provider = factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        for number in 0..3 {
+            let header = Header { number, ..Default::default() };
+            writer.append_header(&header, U256::ZERO, &B256::ZERO).unwrap();
+        }
+        writer.commit().unwrap();
+
+        let tables = factory.table_stats().unwrap();
+        assert_eq!(tables.len(), tables::Tables::ALL.len());
+        let accounts = tables.iter().find(|stats| stats.table == "PlainAccountState").unwrap();
+        assert_eq!(accounts.entries, 1);
+        assert!(accounts.size() > 0);
+
+        let static_files = factory.static_file_stats().unwrap();
+        let headers =
+            static_files.iter().find(|stats| stats.segment == StaticFileSegment::Headers).unwrap();
+        assert_eq!(headers.files, 1);
+        assert_eq!(headers.entries, 3);
+        assert_eq!(headers.block_range, Some(SegmentRangeInclusive::new(0, 2)));
+        assert_eq!(headers.tx_range, None);
+        assert!(headers.size > 0);
+    }
 }
@@ -1,19 +1,26 @@
 use crate::{
     bundle_state::StorageRevertsIter,
-    providers::{database::metrics, static_file::StaticFileWriter, StaticFileProvider},
+    providers::{
+        database::metrics,
+        snap_sync_pivot,
+        state::latest::{cold_account, forked_account},
+        static_file::StaticFileWriter,
+        StaticFileProvider,
+    },
     to_range,
     traits::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
     },
     writer::UnifiedStorageWriter,
     AccountReader, BlockExecutionReader, BlockExecutionWriter, BlockHashReader, BlockNumReader,
-    BlockReader, BlockWriter, BundleStateInit, EvmEnvProvider, FinalizedBlockReader,
-    FinalizedBlockWriter, HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
-    HistoricalStateProvider, HistoryWriter, LatestStateProvider, OriginalValuesKnown,
-    ProviderError, PruneCheckpointReader, PruneCheckpointWriter, RequestsProvider, RevertsInit,
-    StageCheckpointReader, StateChangeWriter, StateProviderBox, StateWriter, StatsReader,
-    StorageReader, StorageTrieWriter, TransactionVariant, TransactionsProvider,
-    TransactionsProviderExt, TrieWriter, WithdrawalsProvider,
+    BlockReader, BlockWriter, BundleStateInit, EvmEnvProvider, ExExCheckpointReader,
+    ExExCheckpointWriter, FinalizedBlockReader, FinalizedBlockWriter, ForkedState, HashingWriter,
+    HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HistoricalStateProvider, HistoryWriter,
+    LatestStateProvider, OriginalValuesKnown, ProviderError, PruneCheckpointReader,
+    PruneCheckpointWriter, RequestsProvider, RevertsInit, StageCheckpointReader, StateChangeWriter,
+    StateHistoryReader, StateProviderBox, StateWriter, StatsReader, StorageReader,
+    StorageTrieWriter, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
+    TrieWriter, WithdrawalsProvider,
 };
 use itertools::{izip, Itertools};
 use rayon::slice::ParallelSliceMut;
@@ -39,9 +46,9 @@ use reth_network_p2p::headers::downloader::SyncTarget;
 use reth_primitives::{
     keccak256, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber,
     BlockWithSenders, Bytecode, GotExpected, Header, Receipt, Requests, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, StaticFileSegment, StorageEntry, TransactionMeta,
-    TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber,
-    Withdrawal, Withdrawals, B256, U256,
+    SealedBlockWithSenders, SealedHeader, StaticFileSegment, StorageEntry, StorageKey,
+    TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash,
+    TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneLimiter, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -115,6 +122,8 @@ pub struct DatabaseProvider<TX> {
     static_file_provider: StaticFileProvider,
     /// Pruning configuration
     prune_modes: PruneModes,
+    /// The state of the network the chain is forked from, if it's a fork.
+    forked_state: Option<Arc<dyn ForkedState>>,
 }
 
 impl<TX> DatabaseProvider<TX> {
@@ -123,6 +132,18 @@ impl<TX> DatabaseProvider<TX> {
         &self.static_file_provider
     }
 
+    /// Sets the state of the network the chain is forked from, which state providers read for
+    /// accounts and storage slots that were never written locally.
+    pub fn with_forked_state(mut self, forked_state: Option<Arc<dyn ForkedState>>) -> Self {
+        self.forked_state = forked_state;
+        self
+    }
+
+    /// Returns the state of the network the chain is forked from, if it's a fork.
+    pub fn forked_state(&self) -> Option<&dyn ForkedState> {
+        self.forked_state.as_deref()
+    }
+
     /// Returns reference to prune modes.
     pub const fn prune_modes_ref(&self) -> &PruneModes {
         &self.prune_modes
@@ -137,7 +158,7 @@ impl<TX: DbTxMut> DatabaseProvider<TX> {
         static_file_provider: StaticFileProvider,
         prune_modes: PruneModes,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes }
+        Self { tx, chain_spec, static_file_provider, prune_modes, forked_state: None }
     }
 }
 
@@ -150,9 +171,19 @@ impl<TX: DbTx + 'static> DatabaseProvider<TX> {
         if block_number == self.best_block_number().unwrap_or_default() &&
             block_number == self.last_block_number().unwrap_or_default()
         {
-            return Ok(Box::new(LatestStateProvider::new(self.tx, self.static_file_provider)))
+            return Ok(Box::new(
+                LatestStateProvider::new_detect_hashed_state_fallback(
+                    self.tx,
+                    self.static_file_provider,
+                )?
+                .with_forked_state(self.forked_state),
+            ))
         }
 
+        // The hashed state only holds the state from the pivot block on.
+        let hashed_state_fallback =
+            snap_sync_pivot(&self.tx)?.is_some_and(|pivot_block| block_number >= pivot_block);
+
         // +1 as the changeset that we want is the one that was applied after this block.
         block_number += 1;
 
@@ -162,7 +193,9 @@ impl<TX: DbTx + 'static> DatabaseProvider<TX> {
             self.get_prune_checkpoint(PruneSegment::StorageHistory)?;
 
         let mut state_provider =
-            HistoricalStateProvider::new(self.tx, block_number, self.static_file_provider);
+            HistoricalStateProvider::new(self.tx, block_number, self.static_file_provider)
+                .with_hashed_state_fallback(hashed_state_fallback)
+                .with_forked_state(self.forked_state);
 
         // If we pruned account or storage history, we can't return state on every historical block.
         // Instead, we should cap it at the latest prune checkpoint for corresponding prune segment.
@@ -275,7 +308,7 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         static_file_provider: StaticFileProvider,
         prune_modes: PruneModes,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes }
+        Self { tx, chain_spec, static_file_provider, prune_modes, forked_state: None }
     }
 
     /// Consume `DbTx` or `DbTxMut`.
@@ -872,7 +905,10 @@ impl<TX: DbTx> DatabaseProvider<TX> {
             let AccountBeforeTx { info: old_info, address } = account_before;
             match state.entry(address) {
                 hash_map::Entry::Vacant(entry) => {
-                    let new_info = plain_accounts_cursor.seek_exact(address)?.map(|kv| kv.1);
+                    let new_info = match plain_accounts_cursor.seek_exact(address)? {
+                        Some((_, account)) => Some(account),
+                        None => cold_account(&self.tx, address)?,
+                    };
                     entry.insert((old_info, new_info, HashMap::new()));
                 }
                 hash_map::Entry::Occupied(mut entry) => {
@@ -890,7 +926,11 @@ impl<TX: DbTx> DatabaseProvider<TX> {
             // get account state or insert from plain state.
             let account_state = match state.entry(address) {
                 hash_map::Entry::Vacant(entry) => {
-                    let present_info = plain_accounts_cursor.seek_exact(address)?.map(|kv| kv.1);
+                    // accounts with storage changes only may have been moved to the cold state
+                    let present_info = match plain_accounts_cursor.seek_exact(address)? {
+                        Some((_, account)) => Some(account),
+                        None => cold_account(&self.tx, address)?,
+                    };
                     entry.insert((present_info, present_info, HashMap::new()))
                 }
                 hash_map::Entry::Occupied(entry) => entry.into_mut(),
@@ -929,6 +969,25 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         Ok(self.tx.commit()?)
     }
 
+    /// Removes the tombstones of accounts that were first destroyed at or after the given block,
+    /// which exposes their forked state again.
+    ///
+    /// The tombstones of storage slots are kept: slots that were written in the unwound blocks are
+    /// restored to the plain state from the changesets, which include their forked values.
+    fn unwind_forked_account_tombstones(&self, first_block: BlockNumber) -> ProviderResult<()> {
+        if self.forked_state.is_none() {
+            return Ok(())
+        }
+        let mut cursor = self.tx.cursor_write::<tables::ForkedAccountTombstones>()?;
+        let mut walker = cursor.walk(None)?;
+        while let Some((_, block_number)) = walker.next().transpose()? {
+            if block_number >= first_block {
+                walker.delete_current()?;
+            }
+        }
+        Ok(())
+    }
+
     /// Remove the last N blocks of state.
     ///
     /// The latest state will be unwound
@@ -965,6 +1024,7 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
             block_bodies.last().expect("already checked if there are blocks").1.last_tx_num();
 
         let storage_range = BlockNumberAddress::range(range.clone());
+        self.unwind_forked_account_tombstones(*range.start())?;
 
         let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
         let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
@@ -1061,6 +1121,7 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
             block_bodies.last().expect("already checked if there are blocks").1.last_tx_num();
 
         let storage_range = BlockNumberAddress::range(range.clone());
+        self.unwind_forked_account_tombstones(*range.start())?;
 
         let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
         let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
@@ -1689,7 +1750,13 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
 
 impl<TX: DbTx> AccountReader for DatabaseProvider<TX> {
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
-        Ok(self.tx.get::<tables::PlainAccountState>(address)?)
+        match self.tx.get::<tables::PlainAccountState>(address)? {
+            None => match cold_account(&self.tx, address)? {
+                None => forked_account(&self.tx, self.forked_state(), address),
+                account => Ok(account),
+            },
+            account => Ok(account),
+        }
     }
 }
 
@@ -1712,10 +1779,16 @@ impl<TX: DbTx> AccountExtReader for DatabaseProvider<TX> {
         iter: impl IntoIterator<Item = Address>,
     ) -> ProviderResult<Vec<(Address, Option<Account>)>> {
         let mut plain_accounts = self.tx.cursor_read::<tables::PlainAccountState>()?;
-        Ok(iter
-            .into_iter()
-            .map(|address| plain_accounts.seek_exact(address).map(|a| (address, a.map(|(_, v)| v))))
-            .collect::<Result<Vec<_>, _>>()?)
+        let mut cold_accounts = self.tx.cursor_read::<tables::ColdAccountState>()?;
+        iter.into_iter()
+            .map(|address| {
+                let account = match plain_accounts.seek_exact(address)? {
+                    Some((_, account)) => Some(account),
+                    None => cold_accounts.seek_exact(address)?.map(|(_, account)| account),
+                };
+                Ok((address, account))
+            })
+            .collect()
     }
 
     fn changed_accounts_and_blocks_with_range(
@@ -1752,6 +1825,159 @@ impl<TX: DbTx> ChangeSetReader for DatabaseProvider<TX> {
             })
             .collect()
     }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        let range = block_number..=block_number;
+        self.tx
+            .cursor_dup_read::<tables::StorageChangeSets>()?
+            .walk_range(BlockNumberAddress::range(range))?
+            .map(|result| -> ProviderResult<_> {
+                let (block_address, storage_entry) = result?;
+                Ok((block_address.address(), storage_entry))
+            })
+            .collect()
+    }
+}
+
+impl<TX: DbTx> DatabaseProvider<TX> {
+    /// Returns an error if the history of the given segment is pruned at the given block.
+    fn ensure_history_available(
+        &self,
+        segment: PruneSegment,
+        block_number: BlockNumber,
+    ) -> ProviderResult<()> {
+        let pruned =
+            self.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number);
+        if pruned.is_some_and(|pruned| block_number <= pruned) {
+            return Err(ProviderError::StateAtBlockPruned(block_number))
+        }
+        Ok(())
+    }
+
+    /// Returns the blocks of the history index from the start of the range on, until `limit`
+    /// blocks in the range are found.
+    ///
+    /// One more block than requested is returned if it exists, even if it's after the range, so
+    /// the value after the last requested block can be read from the changeset of the next one.
+    fn history_blocks<T, K>(
+        &self,
+        key: K,
+        key_filter: impl Fn(&K) -> bool,
+        range: &RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>
+    where
+        T: Table<Key = K, Value = BlockNumberList>,
+    {
+        let mut blocks = Vec::new();
+        // the shards are keyed by their highest block, so the walk starts at the first shard that
+        // can contain the start of the range
+        for entry in self.tx.cursor_read::<T>()?.walk(Some(key))? {
+            let (key, list) = entry?;
+            if !key_filter(&key) {
+                break
+            }
+            for block in list.iter().skip_while(|block| block < range.start()) {
+                blocks.push(block);
+                if block > *range.end() || blocks.len() > limit {
+                    return Ok(blocks)
+                }
+            }
+        }
+        Ok(blocks)
+    }
+}
+
+impl<TX: DbTx> StateHistoryReader for DatabaseProvider<TX> {
+    fn account_history(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        self.ensure_history_available(PruneSegment::AccountHistory, *range.start())?;
+        let blocks = self.history_blocks::<tables::AccountsHistory, _>(
+            ShardedKey::new(address, *range.start()),
+            |key| key.key == address,
+            &range,
+            limit,
+        )?;
+
+        // the account after a block is the account before the next block that changed it, or the
+        // account in the plain state if no later block changed it
+        let mut changesets = self.tx.cursor_dup_read::<tables::AccountChangeSets>()?;
+        let mut history = Vec::with_capacity(blocks.len().min(limit));
+        for (index, block_number) in blocks.iter().copied().enumerate() {
+            if history.len() == limit || !range.contains(&block_number) {
+                break
+            }
+            let account = match blocks.get(index + 1) {
+                Some(&next) => {
+                    changesets
+                        .seek_by_key_subkey(next, address)?
+                        .filter(|account| account.address == address)
+                        .ok_or(ProviderError::AccountChangesetNotFound {
+                            block_number: next,
+                            address,
+                        })?
+                        .info
+                }
+                None => self.basic_account(address)?,
+            };
+            history.push((block_number, account));
+        }
+        Ok(history)
+    }
+
+    fn storage_history(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<(BlockNumber, U256)>> {
+        self.ensure_history_available(PruneSegment::StorageHistory, *range.start())?;
+        let blocks = self.history_blocks::<tables::StoragesHistory, _>(
+            StorageShardedKey::new(address, storage_key, *range.start()),
+            |key| key.address == address && key.sharded_key.key == storage_key,
+            &range,
+            limit,
+        )?;
+
+        // the value after a block is the value before the next block that changed it, or the
+        // value in the plain state if no later block changed it
+        let mut changesets = self.tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+        let mut plain_state = self.tx.cursor_dup_read::<tables::PlainStorageState>()?;
+        let mut history = Vec::with_capacity(blocks.len().min(limit));
+        for (index, block_number) in blocks.iter().copied().enumerate() {
+            if history.len() == limit || !range.contains(&block_number) {
+                break
+            }
+            let value = match blocks.get(index + 1) {
+                Some(&next) => {
+                    changesets
+                        .seek_by_key_subkey(BlockNumberAddress((next, address)), storage_key)?
+                        .filter(|entry| entry.key == storage_key)
+                        .ok_or_else(|| ProviderError::StorageChangesetNotFound {
+                            block_number: next,
+                            address,
+                            storage_key: Box::new(storage_key),
+                        })?
+                        .value
+                }
+                None => plain_state
+                    .seek_by_key_subkey(address, storage_key)?
+                    .filter(|entry| entry.key == storage_key)
+                    .map(|entry| entry.value)
+                    .unwrap_or_default(),
+            };
+            history.push((block_number, value));
+        }
+        Ok(history)
+    }
 }
 
 impl<TX: DbTx> HeaderSyncGapProvider for DatabaseProvider<TX> {
@@ -2647,8 +2873,15 @@ impl<TX: DbTxMut + DbTx> StateChangeWriter for DatabaseProvider<TX> {
         // Write storage changes
         tracing::trace!("Writing storage changes");
         let mut storages_cursor = self.tx_ref().cursor_dup_write::<tables::PlainStorageState>()?;
+        let mut cold_storages_cursor =
+            self.tx_ref().cursor_dup_read::<tables::ColdStorageState>()?;
         let mut storage_changeset_cursor =
             self.tx_ref().cursor_dup_write::<tables::StorageChangeSets>()?;
+        let mut account_tombstones_cursor = self
+            .forked_state
+            .is_some()
+            .then(|| self.tx_ref().cursor_write::<tables::ForkedAccountTombstones>())
+            .transpose()?;
         for (block_index, mut storage_changes) in reverts.storage.into_iter().enumerate() {
             let block_number = first_block + block_index as BlockNumber;
 
@@ -2677,6 +2910,21 @@ impl<TX: DbTxMut + DbTx> StateChangeWriter for DatabaseProvider<TX> {
                             wiped_storage.push((entry.key, entry.value))
                         }
                     }
+                    // slots moved to the cold state by state expiry are wiped as well
+                    if let Some((_, entry)) = cold_storages_cursor.seek_exact(address)? {
+                        wiped_storage.push((entry.key, entry.value));
+                        while let Some(entry) = cold_storages_cursor.next_dup_val()? {
+                            wiped_storage.push((entry.key, entry.value))
+                        }
+                        wiped_storage.sort_unstable_by_key(|(key, _)| *key);
+                    }
+                    // the forked storage of the account isn't known, so it's shadowed by a
+                    // tombstone that is removed when the block is unwound
+                    if let Some(cursor) = &mut account_tombstones_cursor {
+                        if cursor.seek_exact(address)?.is_none() {
+                            cursor.upsert(address, block_number)?;
+                        }
+                    }
                 }
 
                 tracing::trace!(?address, ?storage, "Writing storage reverts");
@@ -2717,8 +2965,15 @@ impl<TX: DbTxMut + DbTx> StateChangeWriter for DatabaseProvider<TX> {
         // Write new account state
         tracing::trace!(len = changes.accounts.len(), "Writing new account state");
         let mut accounts_cursor = self.tx_ref().cursor_write::<tables::PlainAccountState>()?;
+        // changed accounts and storage slots moved to the cold state by state expiry are moved
+        // back to the plain state.
+        let mut cold_accounts_cursor = self.tx_ref().cursor_write::<tables::ColdAccountState>()?;
         // write account to database.
         for (address, account) in changes.accounts {
+            if cold_accounts_cursor.seek_exact(address)?.is_some() {
+                tracing::trace!(?address, "Deleting cold state account");
+                cold_accounts_cursor.delete_current()?;
+            }
             if let Some(account) = account {
                 tracing::trace!(?address, "Updating plain state account");
                 accounts_cursor.upsert(address, account.into())?;
@@ -2738,11 +2993,21 @@ impl<TX: DbTxMut + DbTx> StateChangeWriter for DatabaseProvider<TX> {
         // Write new storage state and wipe storage if needed.
         tracing::trace!(len = changes.storage.len(), "Writing new storage state");
         let mut storages_cursor = self.tx_ref().cursor_dup_write::<tables::PlainStorageState>()?;
+        let mut cold_storages_cursor =
+            self.tx_ref().cursor_dup_write::<tables::ColdStorageState>()?;
+        let mut storage_tombstones_cursor = self
+            .forked_state
+            .is_some()
+            .then(|| self.tx_ref().cursor_dup_write::<tables::ForkedStorageTombstones>())
+            .transpose()?;
         for PlainStorageChangeset { address, wipe_storage, storage } in changes.storage {
             // Wiping of storage.
             if wipe_storage && storages_cursor.seek_exact(address)?.is_some() {
                 storages_cursor.delete_current_duplicates()?;
             }
+            if wipe_storage && cold_storages_cursor.seek_exact(address)?.is_some() {
+                cold_storages_cursor.delete_current_duplicates()?;
+            }
             // cast storages to B256.
             let mut storage = storage
                 .into_iter()
@@ -2758,9 +3023,26 @@ impl<TX: DbTxMut + DbTx> StateChangeWriter for DatabaseProvider<TX> {
                         storages_cursor.delete_current()?;
                     }
                 }
+                if let Some(db_entry) =
+                    cold_storages_cursor.seek_by_key_subkey(address, entry.key)?
+                {
+                    if db_entry.key == entry.key {
+                        cold_storages_cursor.delete_current()?;
+                    }
+                }
 
                 if !entry.value.is_zero() {
                     storages_cursor.upsert(address, entry)?;
+                } else if let Some(cursor) = &mut storage_tombstones_cursor {
+                    // the slot is missing from the plain state now, which must not expose its
+                    // forked value
+                    if cursor
+                        .seek_by_key_subkey(address, entry.key)?
+                        .filter(|tombstone| tombstone.key == entry.key)
+                        .is_none()
+                    {
+                        cursor.upsert(address, entry)?;
+                    }
                 }
             }
         }
@@ -3688,6 +3970,18 @@ impl<TX: DbTxMut> FinalizedBlockWriter for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> ExExCheckpointReader for DatabaseProvider<TX> {
+    fn get_exex_checkpoint(&self, id: &str) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.tx.get::<tables::ExExCheckpoints>(id.to_string())?)
+    }
+}
+
+impl<TX: DbTxMut> ExExCheckpointWriter for DatabaseProvider<TX> {
+    fn save_exex_checkpoint(&self, id: &str, block_number: BlockNumber) -> ProviderResult<()> {
+        Ok(self.tx.put::<tables::ExExCheckpoints>(id.to_string(), block_number)?)
+    }
+}
+
 /// Helper method to recover senders for any blocks in the db which do not have senders. This
 /// compares the length of the input senders [`Vec`], with the length of given transactions [`Vec`],
 /// and will add to the input senders vec if there are more transactions.
@@ -2,10 +2,11 @@ use crate::{
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
     BlockSource, BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
     CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    EvmEnvProvider, FinalizedBlockReader, FullExecutionDataProvider, HeaderProvider, ProviderError,
-    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, RequestsProvider,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider, TreeViewer, WithdrawalsProvider,
+    EvmEnvProvider, ExExCheckpointReader, ExExCheckpointWriter, FinalizedBlockReader,
+    FullExecutionDataProvider, HeaderProvider, ProviderError, PruneCheckpointReader,
+    ReceiptProvider, ReceiptProviderIdExt, RequestsProvider, StageCheckpointReader,
+    StateHistoryReader, StateProviderBox, StateProviderFactory, StaticFileProviderFactory,
+    StorageStatsReader, TransactionVariant, TransactionsProvider, TreeViewer, WithdrawalsProvider,
 };
 use reth_blockchain_tree_api::{
     error::{CanonicalError, InsertBlockError},
@@ -16,14 +17,15 @@ use reth_chain_state::{ChainInfoTracker, ForkChoiceNotifications, ForkChoiceSubs
 use reth_chainspec::{ChainInfo, ChainSpec, EthChainSpec};
 use reth_db_api::{
     database::Database,
+    database_metrics::{DatabaseMetrics, TableStats},
     models::{AccountBeforeTx, StoredBlockBodyIndices},
 };
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
     BlockNumberOrTag, BlockWithSenders, Header, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
-    Withdrawal, Withdrawals, B256, U256,
+    SealedHeader, StorageEntry, StorageKey, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -42,14 +44,15 @@ pub use database::*;
 
 mod static_file;
 pub use static_file::{
-    StaticFileAccess, StaticFileJarProvider, StaticFileProvider, StaticFileProviderRW,
-    StaticFileProviderRWRefMut, StaticFileWriter,
+    RemoteStaticFiles, StaticFileAccess, StaticFileJarProvider, StaticFileProvider,
+    StaticFileProviderRW, StaticFileProviderRWRefMut, StaticFileRowWriter, StaticFileSegmentStats,
+    StaticFileWriter,
 };
 
 mod state;
 pub use state::{
     historical::{HistoricalStateProvider, HistoricalStateProviderRef},
-    latest::{LatestStateProvider, LatestStateProviderRef},
+    latest::{is_snap_synced, snap_sync_pivot, LatestStateProvider, LatestStateProviderRef},
 };
 
 mod bundle_state_provider;
@@ -62,6 +65,9 @@ pub use consistent_view::{ConsistentDbView, ConsistentViewError};
 mod blockchain_provider;
 pub use blockchain_provider::BlockchainProvider2;
 
+mod secondary;
+pub use secondary::{SecondaryWatcher, TipChange};
+
 /// The main type for interacting with the blockchain.
 ///
 /// This type serves as the main entry point for interacting with the blockchain and provides data
@@ -613,6 +619,61 @@ where
     }
 }
 
+impl<DB> StorageStatsReader for BlockchainProvider<DB>
+where
+    DB: Database + DatabaseMetrics,
+{
+    fn table_stats(&self) -> ProviderResult<Vec<TableStats>> {
+        self.database.table_stats()
+    }
+
+    fn static_file_stats(&self) -> ProviderResult<Vec<StaticFileSegmentStats>> {
+        self.database.static_file_stats()
+    }
+}
+
+impl<DB> StateHistoryReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn account_history(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        self.database.provider()?.account_history(address, range, limit)
+    }
+
+    fn storage_history(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<(BlockNumber, U256)>> {
+        self.database.provider()?.storage_history(address, storage_key, range, limit)
+    }
+}
+
+impl<DB> ExExCheckpointReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn get_exex_checkpoint(&self, id: &str) -> ProviderResult<Option<BlockNumber>> {
+        self.database.get_exex_checkpoint(id)
+    }
+}
+
+impl<DB> ExExCheckpointWriter for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn save_exex_checkpoint(&self, id: &str, block_number: BlockNumber) -> ProviderResult<()> {
+        self.database.save_exex_checkpoint(id, block_number)
+    }
+}
+
 impl<DB, ChainSpec> ChainSpecProvider for BlockchainProvider<DB, ChainSpec>
 where
     DB: Send + Sync,
@@ -761,6 +822,10 @@ where
     fn make_canonical(&self, block_hash: BlockHash) -> Result<CanonicalOutcome, CanonicalError> {
         self.tree.make_canonical(block_hash)
     }
+
+    fn unwind(&self, unwind_to: BlockNumber) -> Result<(), CanonicalError> {
+        self.tree.unwind(unwind_to)
+    }
 }
 
 impl<DB> BlockchainTreeViewer for BlockchainProvider<DB>
@@ -968,6 +1033,13 @@ where
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         self.database.provider()?.account_block_changeset(block_number)
     }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        self.database.provider()?.storage_block_changeset(block_number)
+    }
 }
 
 impl<DB> AccountReader for BlockchainProvider<DB>
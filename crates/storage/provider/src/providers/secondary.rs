@@ -0,0 +1,160 @@
+use crate::{BlockHashReader, BlockNumReader, ProviderFactory, StaticFileProviderFactory};
+use reth_db_api::database::Database;
+use reth_primitives::BlockNumHash;
+use reth_storage_errors::provider::ProviderResult;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+
+/// The capacity of the channel of [`SecondaryWatcher::spawn`].
+const TIP_CHANGES_CHANNEL_SIZE: usize = 64;
+
+/// A change of the canonical tip written by the node that owns the data directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TipChange {
+    /// The previous tip, if any.
+    pub old: Option<BlockNumHash>,
+    /// The new tip.
+    pub new: BlockNumHash,
+    /// Whether the previous tip is no longer canonical.
+    pub reorg: bool,
+}
+
+/// Follows the canonical tip of a data directory that is written by another process.
+///
+/// MDBX supports readers in multiple processes, so a secondary process can open the database of a
+/// running node with [`open_db_read_only`](reth_db::open_db_read_only) and the static files with
+/// [`StaticFileProvider::read_only`](crate::providers::StaticFileProvider::read_only), e.g. to
+/// serve RPC requests. New database transactions always see the latest committed state, but the
+/// static file index of a read-only provider is only loaded once, so it has to be refreshed when
+/// the node commits new blocks. The watcher does that, and reports the tip changes.
+///
+/// ## Usage
+///
+/// [`SecondaryWatcher::poll`] should be called periodically, or the watcher can be moved to a
+/// background thread with [`SecondaryWatcher::spawn`].
+#[derive(Debug)]
+pub struct SecondaryWatcher<DB> {
+    factory: ProviderFactory<DB>,
+    tip: Option<BlockNumHash>,
+}
+
+impl<DB: Database> SecondaryWatcher<DB> {
+    /// Creates a new watcher over a read-only [`ProviderFactory`].
+    pub const fn new(factory: ProviderFactory<DB>) -> Self {
+        Self { factory, tip: None }
+    }
+
+    /// Returns the last tip seen by the watcher.
+    pub const fn tip(&self) -> Option<BlockNumHash> {
+        self.tip
+    }
+
+    /// Refreshes the static files and returns the tip change since the last poll, if any.
+    pub fn poll(&mut self) -> ProviderResult<Option<TipChange>> {
+        // static files are committed before the database, so reading the tip from the database
+        // first ensures the refreshed static files contain it
+        let number = self.factory.best_block_number()?;
+        self.factory.static_file_provider().refresh()?;
+        let Some(hash) = self.factory.block_hash(number)? else { return Ok(None) };
+
+        let new = BlockNumHash::new(number, hash);
+        if self.tip == Some(new) {
+            return Ok(None)
+        }
+
+        let reorg = match self.tip {
+            Some(old) => {
+                old.number >= number || self.factory.block_hash(old.number)? != Some(old.hash)
+            }
+            None => false,
+        };
+        let old = self.tip.replace(new);
+        debug!(target: "providers::secondary", ?old, ?new, reorg, "Canonical tip changed");
+
+        Ok(Some(TipChange { old, new, reorg }))
+    }
+}
+
+impl<DB: Database + 'static> SecondaryWatcher<DB> {
+    /// Polls the watcher on a background thread at the given interval, and sends the tip changes
+    /// to the returned channel.
+    ///
+    /// The thread exits once all receivers are dropped.
+    pub fn spawn(mut self, interval: Duration) -> broadcast::Receiver<TipChange> {
+        let (tx, rx) = broadcast::channel(TIP_CHANGES_CHANNEL_SIZE);
+        std::thread::Builder::new()
+            .name("secondary-watcher".to_string())
+            .spawn(move || loop {
+                match self.poll() {
+                    Ok(Some(change)) => {
+                        if tx.send(change).is_err() {
+                            break
+                        }
+                    }
+                    Ok(None) => {
+                        if tx.receiver_count() == 0 {
+                            break
+                        }
+                    }
+                    Err(err) => {
+                        warn!(
+                            target: "providers::secondary",
+                            %err,
+                            "Failed to poll the canonical tip"
+                        )
+                    }
+                }
+                std::thread::sleep(interval);
+            })
+            .expect("failed to spawn secondary watcher thread");
+        rx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::create_test_provider_factory, StageCheckpointWriter};
+    use reth_db::tables;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives::B256;
+    use reth_stages_types::{StageCheckpoint, StageId};
+
+    #[test]
+    fn tip_changes() {
+        let factory = create_test_provider_factory();
+        let mut watcher = SecondaryWatcher::new(factory.clone());
+        assert_eq!(watcher.poll().unwrap(), None);
+
+        let set_tip = |number: u64, hash: B256| {
+            let provider = factory.provider_rw().unwrap();
+            provider.tx_ref().put::<tables::CanonicalHeaders>(number, hash).unwrap();
+            provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(number)).unwrap();
+            provider.commit().unwrap();
+        };
+
+        let first = BlockNumHash::new(0, B256::with_last_byte(1));
+        set_tip(first.number, first.hash);
+        assert_eq!(
+            watcher.poll().unwrap(),
+            Some(TipChange { old: None, new: first, reorg: false })
+        );
+        assert_eq!(watcher.poll().unwrap(), None);
+
+        let second = BlockNumHash::new(1, B256::with_last_byte(2));
+        set_tip(second.number, second.hash);
+        assert_eq!(
+            watcher.poll().unwrap(),
+            Some(TipChange { old: Some(first), new: second, reorg: false })
+        );
+
+        let replaced = BlockNumHash::new(1, B256::with_last_byte(3));
+        set_tip(replaced.number, replaced.hash);
+        assert_eq!(
+            watcher.poll().unwrap(),
+            Some(TipChange { old: Some(second), new: replaced, reorg: true })
+        );
+        assert_eq!(watcher.tip(), Some(replaced));
+    }
+}
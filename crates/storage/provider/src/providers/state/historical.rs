@@ -1,6 +1,15 @@
 use crate::{
-    providers::{state::macros::delegate_provider_impls, StaticFileProvider},
-    AccountReader, BlockHashReader, ProviderError, StateProvider, StateRootProvider,
+    providers::{
+        state::{
+            latest::{
+                bytecode, cold_account, cold_storage, forked_account, forked_storage,
+                hashed_account, hashed_storage,
+            },
+            macros::delegate_provider_impls,
+        },
+        StaticFileProvider,
+    },
+    AccountReader, BlockHashReader, ForkedState, ProviderError, StateProvider, StateRootProvider,
 };
 use reth_db::{tables, BlockNumberList};
 use reth_db_api::{
@@ -17,13 +26,13 @@ use reth_storage_api::StateProofProvider;
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     prefix_set::TriePrefixSetsMut, proof::Proof, updates::TrieUpdates, witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, StateRoot, StorageRoot,
+    AccountProof, HashedPostState, HashedStorage, MultiProof, StateRoot, StorageRoot,
 };
 use reth_trie_db::{
     DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
     DatabaseStorageRoot, DatabaseTrieWitness,
 };
-use std::{collections::HashMap, fmt::Debug};
+use std::{collections::HashMap, fmt::Debug, sync::Arc};
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -46,6 +55,12 @@ pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     lowest_available_blocks: LowestAvailableBlocks,
     /// Static File provider
     static_file_provider: StaticFileProvider,
+    /// Whether to read the hashed state if an account or storage slot is missing from the plain
+    /// state, see [`is_snap_synced`](crate::providers::is_snap_synced).
+    hashed_state_fallback: bool,
+    /// The state of the network the chain is forked from, which is the state of accounts and
+    /// storage slots before they're first written.
+    forked_state: Option<&'b dyn ForkedState>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -63,7 +78,14 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         block_number: BlockNumber,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { tx, block_number, lowest_available_blocks: Default::default(), static_file_provider }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            static_file_provider,
+            hashed_state_fallback: false,
+            forked_state: None,
+        }
     }
 
     /// Create new `StateProvider` for historical block number and lowest block numbers at which
@@ -74,7 +96,28 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         lowest_available_blocks: LowestAvailableBlocks,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { tx, block_number, lowest_available_blocks, static_file_provider }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks,
+            static_file_provider,
+            hashed_state_fallback: false,
+            forked_state: None,
+        }
+    }
+
+    /// Sets whether to read the hashed state if an account or storage slot is missing from the
+    /// plain state, see [`is_snap_synced`](crate::providers::is_snap_synced).
+    pub const fn with_hashed_state_fallback(mut self, hashed_state_fallback: bool) -> Self {
+        self.hashed_state_fallback = hashed_state_fallback;
+        self
+    }
+
+    /// Sets the state of the network the chain is forked from, which is read for accounts and
+    /// storage slots that weren't written locally before the block.
+    pub const fn with_forked_state(mut self, forked_state: Option<&'b dyn ForkedState>) -> Self {
+        self.forked_state = forked_state;
+        self
     }
 
     /// Lookup an account in the `AccountsHistory` table
@@ -230,7 +273,9 @@ impl<'b, TX: DbTx> AccountReader for HistoricalStateProviderRef<'b, TX> {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
         match self.account_history_lookup(address)? {
-            HistoryInfo::NotYetWritten => Ok(None),
+            HistoryInfo::NotYetWritten => self
+                .forked_state
+                .map_or(Ok(None), |forked_state| forked_state.basic_account(address)),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(self
                 .tx
                 .cursor_dup_read::<tables::AccountChangeSets>()?
@@ -242,7 +287,14 @@ impl<'b, TX: DbTx> AccountReader for HistoricalStateProviderRef<'b, TX> {
                 })?
                 .info),
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
-                Ok(self.tx.get::<tables::PlainAccountState>(address)?)
+                if let Some(account) = self.tx.get::<tables::PlainAccountState>(address)? {
+                    return Ok(Some(account))
+                }
+                match cold_account(self.tx, address)? {
+                    None if self.hashed_state_fallback => hashed_account(self.tx, address),
+                    None => forked_account(self.tx, self.forked_state, address),
+                    account => Ok(account),
+                }
             }
         }
     }
@@ -361,6 +413,17 @@ impl<'b, TX: DbTx> StateProofProvider for HistoricalStateProviderRef<'b, TX> {
             .map_err(Into::<ProviderError>::into)
     }
 
+    fn multiproof(
+        &self,
+        hashed_state: HashedPostState,
+        targets: HashMap<B256, Vec<B256>>,
+    ) -> ProviderResult<MultiProof> {
+        let mut revert_state = self.revert_state()?;
+        revert_state.extend(hashed_state);
+        Proof::overlay_multiproof(self.tx, revert_state, targets)
+            .map_err(Into::<ProviderError>::into)
+    }
+
     fn witness(
         &self,
         overlay: HashedPostState,
@@ -381,7 +444,9 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
         storage_key: StorageKey,
     ) -> ProviderResult<Option<StorageValue>> {
         match self.storage_history_lookup(address, storage_key)? {
-            HistoryInfo::NotYetWritten => Ok(None),
+            HistoryInfo::NotYetWritten => self
+                .forked_state
+                .map_or(Ok(None), |forked_state| forked_state.storage(address, storage_key)),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
                 self.tx
                     .cursor_dup_read::<tables::StorageChangeSets>()?
@@ -394,19 +459,32 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
                     })?
                     .value,
             )),
-            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(self
-                .tx
-                .cursor_dup_read::<tables::PlainStorageState>()?
-                .seek_by_key_subkey(address, storage_key)?
-                .filter(|entry| entry.key == storage_key)
-                .map(|entry| entry.value)
-                .or(Some(StorageValue::ZERO))),
+            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
+                let value = self
+                    .tx
+                    .cursor_dup_read::<tables::PlainStorageState>()?
+                    .seek_by_key_subkey(address, storage_key)?
+                    .filter(|entry| entry.key == storage_key)
+                    .map(|entry| entry.value);
+                let value = match value {
+                    None => cold_storage(self.tx, address, storage_key)?,
+                    value => value,
+                };
+                let value = match value {
+                    None if self.hashed_state_fallback => {
+                        hashed_storage(self.tx, address, storage_key)?
+                    }
+                    None => forked_storage(self.tx, self.forked_state, address, storage_key)?,
+                    value => value,
+                };
+                Ok(value.or(Some(StorageValue::ZERO)))
+            }
         }
     }
 
     /// Get account code by its hash
     fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
-        self.tx.get::<tables::Bytecodes>(code_hash).map_err(Into::into)
+        bytecode(self.tx, self.forked_state, code_hash)
     }
 }
 
@@ -422,6 +500,11 @@ pub struct HistoricalStateProvider<TX: DbTx> {
     lowest_available_blocks: LowestAvailableBlocks,
     /// Static File provider
     static_file_provider: StaticFileProvider,
+    /// Whether to read the hashed state if an account or storage slot is missing from the plain
+    /// state, see [`is_snap_synced`](crate::providers::is_snap_synced).
+    hashed_state_fallback: bool,
+    /// The state of the network the chain is forked from, if it's a fork.
+    forked_state: Option<Arc<dyn ForkedState>>,
 }
 
 impl<TX: DbTx> HistoricalStateProvider<TX> {
@@ -431,7 +514,28 @@ impl<TX: DbTx> HistoricalStateProvider<TX> {
         block_number: BlockNumber,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { tx, block_number, lowest_available_blocks: Default::default(), static_file_provider }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            static_file_provider,
+            hashed_state_fallback: false,
+            forked_state: None,
+        }
+    }
+
+    /// Sets whether to read the hashed state if an account or storage slot is missing from the
+    /// plain state, see [`is_snap_synced`](crate::providers::is_snap_synced).
+    pub const fn with_hashed_state_fallback(mut self, hashed_state_fallback: bool) -> Self {
+        self.hashed_state_fallback = hashed_state_fallback;
+        self
+    }
+
+    /// Sets the state of the network the chain is forked from, see
+    /// [`HistoricalStateProviderRef::with_forked_state`].
+    pub fn with_forked_state(mut self, forked_state: Option<Arc<dyn ForkedState>>) -> Self {
+        self.forked_state = forked_state;
+        self
     }
 
     /// Set the lowest block number at which the account history is available.
@@ -461,6 +565,8 @@ impl<TX: DbTx> HistoricalStateProvider<TX> {
             self.lowest_available_blocks,
             self.static_file_provider.clone(),
         )
+        .with_hashed_state_fallback(self.hashed_state_fallback)
+        .with_forked_state(self.forked_state.as_deref())
     }
 }
 
@@ -500,16 +606,19 @@ mod tests {
     use crate::{
         providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
         test_utils::create_test_provider_factory,
-        AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
-        StaticFileProviderFactory,
+        AccountReader, ForkedState, HistoricalStateProvider, HistoricalStateProviderRef,
+        LatestStateProviderRef, StateProvider, StaticFileProviderFactory,
     };
     use reth_db::{tables, BlockNumberList};
     use reth_db_api::{
         models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
         transaction::{DbTx, DbTxMut},
     };
-    use reth_primitives::{address, b256, Account, Address, StorageEntry, B256, U256};
-    use reth_storage_errors::provider::ProviderError;
+    use reth_primitives::{
+        address, b256, Account, Address, Bytecode, Bytes, StorageEntry, StorageKey, StorageValue,
+        B256, U256,
+    };
+    use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
     const ADDRESS: Address = address!("0000000000000000000000000000000000000001");
     const HIGHER_ADDRESS: Address = address!("0000000000000000000000000000000000000005");
@@ -752,6 +861,96 @@ mod tests {
         );
     }
 
+    /// Forked state with the same account at every address and the same value in every slot.
+    #[derive(Debug)]
+    struct MockForkedState {
+        account: Account,
+        value: StorageValue,
+        bytecode: Bytecode,
+    }
+
+    impl ForkedState for MockForkedState {
+        fn basic_account(&self, _address: Address) -> ProviderResult<Option<Account>> {
+            Ok(Some(self.account))
+        }
+
+        fn storage(
+            &self,
+            _account: Address,
+            _storage_key: StorageKey,
+        ) -> ProviderResult<Option<StorageValue>> {
+            Ok(Some(self.value))
+        }
+
+        fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+            Ok((Some(code_hash) == self.account.bytecode_hash).then(|| self.bytecode.clone()))
+        }
+    }
+
+    #[test]
+    fn forked_state_fallback() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+        let static_file_provider = factory.static_file_provider();
+
+        let code_hash = B256::with_last_byte(1);
+        let forked = MockForkedState {
+            account: Account { nonce: 1, balance: U256::from(1), bytecode_hash: Some(code_hash) },
+            value: U256::from(1),
+            bytecode: Bytecode::new_raw(Bytes::from_static(&[0x00])),
+        };
+
+        // the account is destroyed at block 3
+        tx.put::<tables::ForkedAccountTombstones>(ADDRESS, 3).unwrap();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([3]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            3,
+            AccountBeforeTx { address: ADDRESS, info: Some(forked.account) },
+        )
+        .unwrap();
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey {
+                address: ADDRESS,
+                sharded_key: ShardedKey { key: STORAGE, highest_block_number: u64::MAX },
+            },
+            BlockNumberList::new([3]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSets>(
+            (3, ADDRESS).into(),
+            StorageEntry { key: STORAGE, value: forked.value },
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let tx = factory.provider().unwrap().into_tx();
+        let forked_state: &dyn ForkedState = &forked;
+
+        let latest = LatestStateProviderRef::new(&tx, static_file_provider.clone())
+            .with_forked_state(Some(forked_state));
+        assert_eq!(latest.basic_account(ADDRESS), Ok(None));
+        assert_eq!(latest.storage(ADDRESS, STORAGE), Ok(None));
+        assert_eq!(latest.basic_account(HIGHER_ADDRESS), Ok(Some(forked.account)));
+        assert_eq!(latest.storage(HIGHER_ADDRESS, STORAGE), Ok(Some(forked.value)));
+        assert_eq!(latest.bytecode_by_hash(code_hash), Ok(Some(forked.bytecode.clone())));
+
+        let before = HistoricalStateProviderRef::new(&tx, 2, static_file_provider.clone())
+            .with_forked_state(Some(forked_state));
+        assert_eq!(before.basic_account(ADDRESS), Ok(Some(forked.account)));
+        assert_eq!(before.storage(ADDRESS, STORAGE), Ok(Some(forked.value)));
+
+        let after = HistoricalStateProviderRef::new(&tx, 4, static_file_provider)
+            .with_forked_state(Some(forked_state));
+        assert_eq!(after.basic_account(ADDRESS), Ok(None));
+        assert_eq!(after.storage(ADDRESS, STORAGE), Ok(Some(U256::ZERO)));
+        assert_eq!(after.basic_account(HIGHER_ADDRESS), Ok(Some(forked.account)));
+        assert_eq!(after.storage(HIGHER_ADDRESS, STORAGE), Ok(Some(forked.value)));
+    }
+
     #[test]
     fn history_provider_unavailable() {
         let factory = create_test_provider_factory();
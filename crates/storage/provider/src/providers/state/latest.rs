@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use crate::{
     providers::{state::macros::delegate_provider_impls, StaticFileProvider},
-    AccountReader, BlockHashReader, StateProvider, StateRootProvider,
+    AccountReader, BlockHashReader, ForkedState, StateProvider, StateRootProvider,
 };
 use reth_db::tables;
 use reth_db_api::{
@@ -10,17 +10,134 @@ use reth_db_api::{
     transaction::DbTx,
 };
 use reth_primitives::{
-    Account, Address, BlockNumber, Bytecode, Bytes, StaticFileSegment, StorageKey, StorageValue,
-    B256,
+    keccak256, Account, Address, BlockNumber, Bytecode, Bytes, StaticFileSegment, StorageKey,
+    StorageValue, B256,
 };
+use reth_stages_types::StageId;
 use reth_storage_api::StateProofProvider;
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{
     prefix_set::TriePrefixSetsMut, proof::Proof, updates::TrieUpdates, witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, StateRoot, StorageRoot,
+    AccountProof, HashedPostState, HashedStorage, MultiProof, StateRoot, StorageRoot,
 };
 use reth_trie_db::{DatabaseProof, DatabaseStateRoot, DatabaseStorageRoot, DatabaseTrieWitness};
 
+/// Returns `true` if the state was downloaded by the [`StageId::SnapSync`] stage, i.e. its
+/// checkpoint records a pivot block.
+///
+/// The plain state of a snap synced database only contains the accounts and storage slots whose
+/// addresses and keys were known before the snap sync or were changed after it, the rest of the
+/// state is only available as hashed state. The hashed state is written together with the plain
+/// state, so state providers fall back to it for the state from the pivot block on.
+pub fn is_snap_synced<TX: DbTx>(tx: &TX) -> ProviderResult<bool> {
+    Ok(snap_sync_pivot(tx)?.is_some())
+}
+
+/// Returns the pivot block whose state was downloaded by the [`StageId::SnapSync`] stage, see
+/// [`is_snap_synced`].
+pub fn snap_sync_pivot<TX: DbTx>(tx: &TX) -> ProviderResult<Option<BlockNumber>> {
+    Ok(tx
+        .get::<tables::StageCheckpoints>(StageId::SnapSync.to_string())?
+        .and_then(|checkpoint| checkpoint.snap_sync_stage_checkpoint())
+        .map(|checkpoint| checkpoint.pivot_block))
+}
+
+/// Reads an account moved out of the plain state by state expiry.
+///
+/// Expired accounts are moved back to the plain state once they're changed, so an account is
+/// never in both the plain and the cold state.
+pub(crate) fn cold_account<TX: DbTx>(tx: &TX, address: Address) -> ProviderResult<Option<Account>> {
+    Ok(tx.get::<tables::ColdAccountState>(address)?)
+}
+
+/// Reads a storage slot moved out of the plain state by state expiry, see [`cold_account`].
+pub(crate) fn cold_storage<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    storage_key: StorageKey,
+) -> ProviderResult<Option<StorageValue>> {
+    Ok(tx
+        .cursor_dup_read::<tables::ColdStorageState>()?
+        .seek_by_key_subkey(address, storage_key)?
+        .filter(|entry| entry.key == storage_key)
+        .map(|entry| entry.value))
+}
+
+/// Reads an account from the hashed state.
+pub(crate) fn hashed_account<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+) -> ProviderResult<Option<Account>> {
+    Ok(tx.get::<tables::HashedAccounts>(keccak256(address))?)
+}
+
+/// Reads a storage slot from the hashed state.
+pub(crate) fn hashed_storage<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    storage_key: StorageKey,
+) -> ProviderResult<Option<StorageValue>> {
+    let hashed_key = keccak256(storage_key);
+    Ok(tx
+        .cursor_dup_read::<tables::HashedStorages>()?
+        .seek_by_key_subkey(keccak256(address), hashed_key)?
+        .filter(|entry| entry.key == hashed_key)
+        .map(|entry| entry.value))
+}
+
+/// Reads an account that is missing from the local state from the forked state, unless it was
+/// destroyed by a local block.
+///
+/// Accounts that are written locally are in the plain state afterwards, so an account that is
+/// missing from it was either never written or destroyed, which leaves a tombstone.
+pub(crate) fn forked_account<TX: DbTx>(
+    tx: &TX,
+    forked_state: Option<&dyn ForkedState>,
+    address: Address,
+) -> ProviderResult<Option<Account>> {
+    let Some(forked_state) = forked_state else { return Ok(None) };
+    if tx.get::<tables::ForkedAccountTombstones>(address)?.is_some() {
+        return Ok(None)
+    }
+    forked_state.basic_account(address)
+}
+
+/// Reads a storage slot that is missing from the local state from the forked state, unless it was
+/// cleared or the account was destroyed by a local block, see [`forked_account`].
+pub(crate) fn forked_storage<TX: DbTx>(
+    tx: &TX,
+    forked_state: Option<&dyn ForkedState>,
+    address: Address,
+    storage_key: StorageKey,
+) -> ProviderResult<Option<StorageValue>> {
+    let Some(forked_state) = forked_state else { return Ok(None) };
+    if tx.get::<tables::ForkedAccountTombstones>(address)?.is_some() {
+        return Ok(None)
+    }
+    let cleared = tx
+        .cursor_dup_read::<tables::ForkedStorageTombstones>()?
+        .seek_by_key_subkey(address, storage_key)?
+        .is_some_and(|entry| entry.key == storage_key);
+    if cleared {
+        return Ok(None)
+    }
+    forked_state.storage(address, storage_key)
+}
+
+/// Reads code from the database, or from the forked state if it's missing.
+pub(crate) fn bytecode<TX: DbTx>(
+    tx: &TX,
+    forked_state: Option<&dyn ForkedState>,
+    code_hash: B256,
+) -> ProviderResult<Option<Bytecode>> {
+    match tx.get::<tables::Bytecodes>(code_hash)? {
+        None => {
+            forked_state.map_or(Ok(None), |forked_state| forked_state.bytecode_by_hash(code_hash))
+        }
+        bytecode => Ok(bytecode),
+    }
+}
+
 /// State provider over latest state that takes tx reference.
 #[derive(Debug)]
 pub struct LatestStateProviderRef<'b, TX: DbTx> {
@@ -28,19 +145,45 @@ pub struct LatestStateProviderRef<'b, TX: DbTx> {
     tx: &'b TX,
     /// Static File provider
     static_file_provider: StaticFileProvider,
+    /// Whether to read the hashed state if an account or storage slot is missing from the plain
+    /// state, see [`is_snap_synced`].
+    hashed_state_fallback: bool,
+    /// The state of the network the chain is forked from, if it's a fork.
+    forked_state: Option<&'b dyn ForkedState>,
 }
 
 impl<'b, TX: DbTx> LatestStateProviderRef<'b, TX> {
     /// Create new state provider
     pub const fn new(tx: &'b TX, static_file_provider: StaticFileProvider) -> Self {
-        Self { tx, static_file_provider }
+        Self { tx, static_file_provider, hashed_state_fallback: false, forked_state: None }
+    }
+
+    /// Sets whether to read the hashed state if an account or storage slot is missing from the
+    /// plain state, see [`is_snap_synced`].
+    pub const fn with_hashed_state_fallback(mut self, hashed_state_fallback: bool) -> Self {
+        self.hashed_state_fallback = hashed_state_fallback;
+        self
+    }
+
+    /// Sets the state of the network the chain is forked from, which is read for accounts and
+    /// storage slots that were never written locally.
+    pub const fn with_forked_state(mut self, forked_state: Option<&'b dyn ForkedState>) -> Self {
+        self.forked_state = forked_state;
+        self
     }
 }
 
 impl<'b, TX: DbTx> AccountReader for LatestStateProviderRef<'b, TX> {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
-        self.tx.get::<tables::PlainAccountState>(address).map_err(Into::into)
+        if let Some(account) = self.tx.get::<tables::PlainAccountState>(address)? {
+            return Ok(Some(account))
+        }
+        match cold_account(self.tx, address)? {
+            None if self.hashed_state_fallback => hashed_account(self.tx, address),
+            None => forked_account(self.tx, self.forked_state, address),
+            account => Ok(account),
+        }
     }
 }
 
@@ -135,6 +278,15 @@ impl<'b, TX: DbTx> StateProofProvider for LatestStateProviderRef<'b, TX> {
             .map_err(Into::<ProviderError>::into)
     }
 
+    fn multiproof(
+        &self,
+        hashed_state: HashedPostState,
+        targets: HashMap<B256, Vec<B256>>,
+    ) -> ProviderResult<MultiProof> {
+        Proof::overlay_multiproof(self.tx, hashed_state, targets)
+            .map_err(Into::<ProviderError>::into)
+    }
+
     fn witness(
         &self,
         overlay: HashedPostState,
@@ -142,6 +294,30 @@ impl<'b, TX: DbTx> StateProofProvider for LatestStateProviderRef<'b, TX> {
     ) -> ProviderResult<HashMap<B256, Bytes>> {
         TrieWitness::overlay_witness(self.tx, overlay, target).map_err(Into::<ProviderError>::into)
     }
+
+    #[cfg(feature = "verkle")]
+    fn verkle_proof(
+        &self,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<Option<reth_trie::VerkleAccountProof>> {
+        use reth_trie_verkle::{db, embedding, proof};
+
+        if !db::has_verkle_trie(self.tx)? {
+            return Ok(None)
+        }
+        let keys = [embedding::basic_data_key(&address), embedding::code_hash_key(&address)]
+            .into_iter()
+            .chain(slots.iter().map(|slot| embedding::storage_slot_key(&address, (*slot).into())))
+            .collect::<Vec<_>>();
+        let (leaves, proof) =
+            proof::verkle_proof(&mut db::verkle_trie_reader(self.tx)?, keys.iter().copied())?;
+        Ok(Some(reth_trie::VerkleAccountProof {
+            address,
+            leaves: keys.into_iter().map(|key| (key, leaves[&key])).collect(),
+            proof,
+        }))
+    }
 }
 
 impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
@@ -157,12 +333,18 @@ impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
                 return Ok(Some(entry.value))
             }
         }
-        Ok(None)
+        if let Some(value) = cold_storage(self.tx, account, storage_key)? {
+            return Ok(Some(value))
+        }
+        if self.hashed_state_fallback {
+            return hashed_storage(self.tx, account, storage_key)
+        }
+        forked_storage(self.tx, self.forked_state, account, storage_key)
     }
 
     /// Get account code by its hash
     fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
-        self.tx.get::<tables::Bytecodes>(code_hash).map_err(Into::into)
+        bytecode(self.tx, self.forked_state, code_hash)
     }
 }
 
@@ -173,18 +355,42 @@ pub struct LatestStateProvider<TX: DbTx> {
     db: TX,
     /// Static File provider
     static_file_provider: StaticFileProvider,
+    /// Whether to read the hashed state if an account or storage slot is missing from the plain
+    /// state, see [`is_snap_synced`].
+    hashed_state_fallback: bool,
+    /// The state of the network the chain is forked from, if it's a fork.
+    forked_state: Option<Arc<dyn ForkedState>>,
 }
 
 impl<TX: DbTx> LatestStateProvider<TX> {
     /// Create new state provider
     pub const fn new(db: TX, static_file_provider: StaticFileProvider) -> Self {
-        Self { db, static_file_provider }
+        Self { db, static_file_provider, hashed_state_fallback: false, forked_state: None }
+    }
+
+    /// Create new state provider that falls back to the hashed state if the database was snap
+    /// synced, see [`is_snap_synced`].
+    pub fn new_detect_hashed_state_fallback(
+        db: TX,
+        static_file_provider: StaticFileProvider,
+    ) -> ProviderResult<Self> {
+        let hashed_state_fallback = is_snap_synced(&db)?;
+        Ok(Self { db, static_file_provider, hashed_state_fallback, forked_state: None })
+    }
+
+    /// Sets the state of the network the chain is forked from, see
+    /// [`LatestStateProviderRef::with_forked_state`].
+    pub fn with_forked_state(mut self, forked_state: Option<Arc<dyn ForkedState>>) -> Self {
+        self.forked_state = forked_state;
+        self
     }
 
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     fn as_ref(&self) -> LatestStateProviderRef<'_, TX> {
         LatestStateProviderRef::new(&self.db, self.static_file_provider.clone())
+            .with_hashed_state_fallback(self.hashed_state_fallback)
+            .with_forked_state(self.forked_state.as_deref())
     }
 }
 
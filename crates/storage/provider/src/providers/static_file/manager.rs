@@ -1,7 +1,9 @@
 use super::{
-    metrics::StaticFileProviderMetrics, writer::StaticFileWriters, LoadedJar,
-    StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
-    BLOCKS_PER_STATIC_FILE,
+    metrics::StaticFileProviderMetrics,
+    remote::StaticFileKey,
+    writer::{with_compression_codec, StaticFileWriters},
+    LoadedJar, RemoteStaticFiles, StaticFileJarProvider, StaticFileProviderRW,
+    StaticFileProviderRWRefMut, BLOCKS_PER_STATIC_FILE,
 };
 use crate::{
     to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, DatabaseProvider,
@@ -11,9 +13,13 @@ use crate::{
 use dashmap::DashMap;
 use parking_lot::RwLock;
 use reth_chainspec::ChainInfo;
+use reth_codecs::Compact;
 use reth_db::{
     lockfile::StorageLock,
-    static_file::{iter_static_files, HeaderMask, ReceiptMask, StaticFileCursor, TransactionMask},
+    static_file::{
+        iter_static_files, HeaderMask, ReceiptMask, StaticFileCursor, TransactionMask,
+        REMOTE_MARKER_EXTENSION,
+    },
     tables,
 };
 use reth_db_api::{
@@ -22,10 +28,17 @@ use reth_db_api::{
     table::Table,
     transaction::DbTx,
 };
-use reth_nippy_jar::NippyJar;
+use reth_fs_util::FsPathError;
+use reth_nippy_jar::{
+    compression::Compressors, ConsistencyFailStrategy, NippyJar, NippyJarCursor, NippyJarError,
+    NippyJarWriter,
+};
 use reth_primitives::{
     keccak256,
-    static_file::{find_fixed_range, HighestStaticFiles, SegmentHeader, SegmentRangeInclusive},
+    static_file::{
+        find_fixed_range, CompressionCodec, HighestStaticFiles, SegmentHeader,
+        SegmentRangeInclusive, StaticFileCompression,
+    },
     Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Header, Receipt,
     SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, TransactionMeta,
     TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
@@ -35,18 +48,54 @@ use reth_stages_types::{PipelineTarget, StageId};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
     collections::{hash_map::Entry, BTreeMap, HashMap},
+    error::Error as StdError,
     ops::{Deref, Range, RangeBounds, RangeInclusive},
     path::{Path, PathBuf},
     sync::{mpsc, Arc},
 };
 use strum::IntoEnumIterator;
-use tracing::{info, trace, warn};
+use tracing::{debug, info, trace, warn};
+
+/// Directory inside the static files directory that static files are written to while they're
+/// recompressed.
+const RECOMPRESSION_DIR: &str = "recompress";
+
+/// Extension of the file marking a recompressed static file as completely written.
+const RECOMPRESSION_MARKER_EXTENSION: &str = "done";
 
 /// Alias type for a map that can be queried for block ranges from a transaction
 /// segment respectively. It uses `TxNumber` to represent the transaction end of a static file
 /// range.
 type SegmentRanges = HashMap<StaticFileSegment, BTreeMap<TxNumber, SegmentRangeInclusive>>;
 
+/// The files, entries and size of a static file segment, see
+/// [`StaticFileProvider::segment_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaticFileSegmentStats {
+    /// The segment.
+    pub segment: StaticFileSegment,
+    /// The number of static files of the segment.
+    pub files: usize,
+    /// The number of rows in all static files of the segment.
+    pub entries: usize,
+    /// The size of all static files of the segment in bytes.
+    pub size: u64,
+    /// The blocks stored in the static files of the segment.
+    pub block_range: Option<SegmentRangeInclusive>,
+    /// The transactions stored in the static files of the segment.
+    pub tx_range: Option<SegmentRangeInclusive>,
+}
+
+/// Returns the range spanning both ranges.
+fn extend_range(
+    range: Option<SegmentRangeInclusive>,
+    other: &SegmentRangeInclusive,
+) -> SegmentRangeInclusive {
+    range.map_or(*other, |range| {
+        SegmentRangeInclusive::new(range.start().min(other.start()), range.end().max(other.end()))
+    })
+}
+
 /// Access mode on a static file provider. RO/RW.
 #[derive(Debug, Default, PartialEq, Eq)]
 pub enum StaticFileAccess {
@@ -77,6 +126,9 @@ impl StaticFileProvider {
     /// Creates a new [`StaticFileProvider`].
     fn new(path: impl AsRef<Path>, access: StaticFileAccess) -> ProviderResult<Self> {
         let provider = Self(Arc::new(StaticFileProviderInner::new(path, access)?));
+        if provider.access.is_read_write() {
+            provider.heal_recompression()?;
+        }
         provider.initialize_index()?;
         Ok(provider)
     }
@@ -115,6 +167,10 @@ pub struct StaticFileProviderInner {
     /// Whether [`StaticFileJarProvider`] loads filters into memory. If not, `by_hash` queries
     /// won't be able to be queried directly.
     load_filters: bool,
+    /// Compression codecs of newly created static files.
+    compression: StaticFileCompression,
+    /// Object store that the data of old static files is moved to.
+    remote: Option<RemoteStaticFiles>,
     /// Maintains a writer set of [`StaticFileSegment`].
     writers: StaticFileWriters,
     metrics: Option<Arc<StaticFileProviderMetrics>>,
@@ -140,6 +196,8 @@ impl StaticFileProviderInner {
             static_files_tx_index: Default::default(),
             path: path.as_ref().to_path_buf(),
             load_filters: false,
+            compression: StaticFileCompression::default(),
+            remote: None,
             metrics: None,
             access,
             _lock_file,
@@ -151,6 +209,16 @@ impl StaticFileProviderInner {
     pub const fn is_read_only(&self) -> bool {
         self.access.is_read_only()
     }
+
+    /// Returns the compression codecs of newly created static files.
+    pub const fn compression(&self) -> &StaticFileCompression {
+        &self.compression
+    }
+
+    /// Returns the object store that the data of old static files is moved to, if any.
+    pub const fn remote(&self) -> Option<&RemoteStaticFiles> {
+        self.remote.as_ref()
+    }
 }
 
 impl StaticFileProvider {
@@ -162,6 +230,26 @@ impl StaticFileProvider {
         Self(Arc::new(provider))
     }
 
+    /// Sets the compression codecs of newly created static files.
+    ///
+    /// Existing static files are only converted by [`StaticFileProvider::recompress_static_files`].
+    pub fn with_compression(self, compression: StaticFileCompression) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.compression = compression;
+        Self(Arc::new(provider))
+    }
+
+    /// Moves the data of old static files to an object store, see [`RemoteStaticFiles`].
+    ///
+    /// Existing static files are only moved by [`StaticFileProvider::offload_static_files`].
+    pub fn with_remote(self, remote: RemoteStaticFiles) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.remote = Some(remote);
+        Self(Arc::new(provider))
+    }
+
     /// Enables metrics on the [`StaticFileProvider`].
     pub fn with_metrics(self) -> Self {
         let mut provider =
@@ -174,40 +262,66 @@ impl StaticFileProvider {
     pub fn report_metrics(&self) -> ProviderResult<()> {
         let Some(metrics) = &self.metrics else { return Ok(()) };
 
+        for stats in self.segment_stats()? {
+            metrics.record_segment(stats.segment, stats.size, stats.files, stats.entries);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the [`StaticFileSegmentStats`] of all segments that have static files on disk,
+    /// ordered by segment.
+    pub fn segment_stats(&self) -> ProviderResult<Vec<StaticFileSegmentStats>> {
         let static_files =
             iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
-        for (segment, ranges) in static_files {
-            let mut entries = 0;
-            let mut size = 0;
 
-            for (block_range, _) in &ranges {
-                let fixed_block_range = find_fixed_range(block_range.start());
-                let jar_provider = self
-                    .get_segment_provider(segment, || Some(fixed_block_range), None)?
-                    .ok_or(ProviderError::MissingStaticFileBlock(segment, block_range.start()))?;
+        let mut segments = Vec::with_capacity(static_files.len());
+        for (segment, ranges) in static_files {
+            let mut stats = StaticFileSegmentStats {
+                segment,
+                files: ranges.len(),
+                entries: 0,
+                size: 0,
+                block_range: None,
+                tx_range: None,
+            };
+
+            for (block_range, tx_range) in &ranges {
+                // Only the configuration is loaded, so static files that are only kept in the
+                // object store aren't downloaded.
+                let jar = NippyJar::<SegmentHeader>::load(
+                    &self.path.join(segment.filename(&find_fixed_range(block_range.start()))),
+                )
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
 
-                entries += jar_provider.rows();
+                stats.entries += jar.rows();
 
-                let data_size = reth_fs_util::metadata(jar_provider.data_path())
+                let data_size = reth_fs_util::metadata(jar.data_path())
                     .map(|metadata| metadata.len())
                     .unwrap_or_default();
-                let index_size = reth_fs_util::metadata(jar_provider.index_path())
+                let index_size = reth_fs_util::metadata(jar.index_path())
                     .map(|metadata| metadata.len())
                     .unwrap_or_default();
-                let offsets_size = reth_fs_util::metadata(jar_provider.offsets_path())
+                let offsets_size = reth_fs_util::metadata(jar.offsets_path())
                     .map(|metadata| metadata.len())
                     .unwrap_or_default();
-                let config_size = reth_fs_util::metadata(jar_provider.config_path())
+                let config_size = reth_fs_util::metadata(jar.config_path())
                     .map(|metadata| metadata.len())
                     .unwrap_or_default();
 
-                size += data_size + index_size + offsets_size + config_size;
+                stats.size += data_size + index_size + offsets_size + config_size;
+
+                stats.block_range = Some(extend_range(stats.block_range, block_range));
+                if let Some(tx_range) = tx_range {
+                    stats.tx_range = Some(extend_range(stats.tx_range, tx_range));
+                }
             }
 
-            metrics.record_segment(segment, size, ranges.len(), entries);
+            segments.push(stats);
         }
+        segments.sort_unstable_by_key(|stats| stats.segment);
 
-        Ok(())
+        Ok(segments)
     }
 
     /// Gets the [`StaticFileJarProvider`] of the requested segment and block.
@@ -295,6 +409,19 @@ impl StaticFileProvider {
         fixed_block_range: SegmentRangeInclusive,
     ) -> ProviderResult<()> {
         let key = (fixed_block_range.end(), segment);
+        if let Some(remote) = &self.remote {
+            remote.remove(key);
+            // Removed first, so a partially deleted static file isn't listed anymore
+            let marker_path = self
+                .path
+                .join(segment.filename(&fixed_block_range))
+                .with_extension(REMOTE_MARKER_EXTENSION);
+            if marker_path.exists() {
+                reth_fs_util::remove_file(marker_path)
+                    .map_err(|e| ProviderError::FsPathError(e.to_string()))?;
+            }
+        }
+
         let jar = if let Some((_, jar)) = self.map.remove(&key) {
             jar.jar
         } else {
@@ -333,17 +460,34 @@ impl StaticFileProvider {
         trace!(target: "provider::static_file", ?segment, ?fixed_block_range, "Getting provider");
         let mut provider: StaticFileJarProvider<'_> = if let Some(jar) = self.map.get(&key) {
             trace!(target: "provider::static_file", ?segment, ?fixed_block_range, "Jar found in cache");
+            if let Some(remote) = &self.remote {
+                remote.touch(key);
+            }
             jar.into()
         } else {
             trace!(target: "provider::static_file", ?segment, ?fixed_block_range, "Creating jar from scratch");
-            let path = self.path.join(segment.filename(fixed_block_range));
-            let mut jar =
-                NippyJar::load(&path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
-            if self.load_filters {
-                jar.load_filters().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+            if let Some(remote) = &self.remote {
+                self.download_remote_static_file(remote, segment, fixed_block_range)?;
             }
-
-            self.map.entry(key).insert(LoadedJar::new(jar)?).downgrade().into()
+            // The jar is loaded while holding the entry, so it can't be loaded while its files are
+            // being replaced by `recompress_static_files`.
+            self.map
+                .entry(key)
+                .or_try_insert_with(|| {
+                    let path = self.path.join(segment.filename(fixed_block_range));
+                    let mut jar = NippyJar::load(&path)
+                        .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+                    // Evicted again before it was loaded
+                    if let Some(remote) = &self.remote {
+                        remote.download(&jar)?;
+                    }
+                    if self.load_filters {
+                        jar.load_filters().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+                    }
+                    LoadedJar::new(jar)
+                })?
+                .downgrade()
+                .into()
         };
 
         if let Some(metrics) = &self.metrics {
@@ -352,6 +496,380 @@ impl StaticFileProvider {
         Ok(provider)
     }
 
+    /// Rewrites all static files whose compression codec differs from the configured one, see
+    /// [`StaticFileProvider::with_compression`]. The latest static file of each segment is skipped,
+    /// since it can still be appended to.
+    ///
+    /// Each static file is written to a temporary directory first, and then moved in place of the
+    /// original one. Reads of the static file wait until it's replaced.
+    ///
+    /// Returns the number of recompressed static files.
+    pub fn recompress_static_files(&self) -> ProviderResult<usize> {
+        if self.access.is_read_only() {
+            return Err(ProviderError::ReadOnlyStaticFileAccess)
+        }
+        // Clears the leftovers of any previously failed recompression
+        self.heal_recompression()?;
+
+        let mut recompressed = 0;
+        let static_files =
+            iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        for (segment, ranges) in static_files {
+            let Some(highest_block) = self.get_highest_static_file_block(segment) else { continue };
+            let codec = self.compression.codec(segment);
+
+            for (block_range, _) in ranges {
+                let fixed_block_range = find_fixed_range(block_range.start());
+                if fixed_block_range.end() >= highest_block {
+                    continue
+                }
+
+                if self.recompress_jar(segment, fixed_block_range, codec)? {
+                    recompressed += 1;
+                }
+            }
+        }
+
+        // Removes the temporary directory
+        self.heal_recompression()?;
+
+        Ok(recompressed)
+    }
+
+    /// Rewrites a single static file with the given compression codec, if it's not using it
+    /// already. Returns `true` if the static file was recompressed.
+    fn recompress_jar(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: SegmentRangeInclusive,
+        codec: CompressionCodec,
+    ) -> ProviderResult<bool> {
+        let map_err = |e: NippyJarError| ProviderError::NippyJar(e.to_string());
+        let fs_err = |e: FsPathError| ProviderError::FsPathError(e.to_string());
+
+        let path = self.path.join(segment.filename(&fixed_block_range));
+        let jar = NippyJar::<SegmentHeader>::load(&path).map_err(map_err)?;
+        let current_codec = match jar.compressor() {
+            None => Some(CompressionCodec::None),
+            Some(Compressors::Lz4(_)) => Some(CompressionCodec::Lz4),
+            // A level of zero is zstd's default level
+            Some(Compressors::Zstd(zstd)) if !zstd.use_dict => {
+                Some(CompressionCodec::Zstd(if zstd.level() == 0 {
+                    CompressionCodec::DEFAULT_ZSTD_LEVEL
+                } else {
+                    zstd.level()
+                }))
+            }
+            Some(Compressors::Zstd(_)) => None,
+        };
+        if current_codec == Some(codec) {
+            return Ok(false)
+        }
+
+        // Static files that are only kept in the object store would have to be downloaded first.
+        if !jar.data_path().exists() {
+            debug!(target: "provider::static_file", ?segment, ?fixed_block_range, "Skipping recompression of remote static file");
+            return Ok(false)
+        }
+
+        // Filters and perfect hashing functions live in the index file, which is not rewritten.
+        if jar.index_path().exists() {
+            debug!(target: "provider::static_file", ?segment, ?fixed_block_range, "Skipping recompression of static file with an index");
+            return Ok(false)
+        }
+
+        debug!(target: "provider::static_file", ?segment, ?fixed_block_range, ?current_codec, %codec, "Recompressing static file");
+        let tmp_dir = self.path.join(RECOMPRESSION_DIR);
+        reth_fs_util::create_dir_all(&tmp_dir).map_err(fs_err)?;
+        let tmp_path = tmp_dir.join(segment.filename(&fixed_block_range));
+
+        let tmp_jar = with_compression_codec(
+            NippyJar::new(jar.columns(), &tmp_path, jar.user_header().clone()),
+            codec,
+        );
+        let mut writer =
+            NippyJarWriter::new(tmp_jar, ConsistencyFailStrategy::Heal).map_err(map_err)?;
+        let mut cursor = NippyJarCursor::new(&jar).map_err(map_err)?;
+        while let Some(row) = cursor.next_row().map_err(map_err)? {
+            for column in row {
+                writer
+                    .append_column(Some(Ok::<_, Box<dyn StdError + Send + Sync>>(column)))
+                    .map_err(map_err)?;
+            }
+        }
+        writer.commit().map_err(map_err)?;
+
+        // From here on, an interrupted recompression is finished by `heal_recompression`.
+        let marker_path = tmp_path.with_extension(RECOMPRESSION_MARKER_EXTENSION);
+        reth_fs_util::write(&marker_path, []).map_err(fs_err)?;
+
+        let key = (fixed_block_range.end(), segment);
+        let entry = self.map.entry(key);
+
+        // The static file could have been modified while it was copied, e.g. by an unwind.
+        let current_jar = NippyJar::<SegmentHeader>::load(&path).map_err(map_err)?;
+        if current_jar.rows() != jar.rows() || current_jar.user_header() != jar.user_header() {
+            reth_fs_util::remove_file(&marker_path).map_err(fs_err)?;
+            NippyJar::<SegmentHeader>::load(&tmp_path)
+                .map_err(map_err)?
+                .delete()
+                .map_err(map_err)?;
+            return Ok(false)
+        }
+
+        NippyJar::<SegmentHeader>::move_files(&tmp_path, &path).map_err(map_err)?;
+        reth_fs_util::remove_file(&marker_path).map_err(fs_err)?;
+
+        // Readers load the new static file on the next access.
+        if let dashmap::mapref::entry::Entry::Occupied(entry) = entry {
+            entry.remove();
+        }
+
+        Ok(true)
+    }
+
+    /// Replaces a finalized static file with one made of the rows appended by `write`, e.g. to
+    /// repair a corrupted static file. The rows are written with the configured compression codec.
+    ///
+    /// Like on recompression, the static file is written to a temporary directory first, and then
+    /// moved in place of the original one, so the original one can still be read by `write`. Reads
+    /// of the static file wait until it's replaced. If `write` fails, the static file is kept.
+    pub fn replace_static_file<E: From<ProviderError>>(
+        &self,
+        header: SegmentHeader,
+        write: impl FnOnce(&mut StaticFileRowWriter) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let map_err = |e: NippyJarError| ProviderError::NippyJar(e.to_string());
+        let fs_err = |e: FsPathError| ProviderError::FsPathError(e.to_string());
+
+        if self.access.is_read_only() {
+            return Err(ProviderError::ReadOnlyStaticFileAccess.into())
+        }
+        let segment = header.segment();
+        let fixed_block_range = find_fixed_range(header.expected_block_start());
+        // The latest static file can still be appended to
+        if self
+            .get_highest_static_file_block(segment)
+            .map_or(true, |highest_block| fixed_block_range.end() >= highest_block)
+        {
+            return Err(
+                ProviderError::UnfinalizedStaticFile(segment, fixed_block_range.start()).into()
+            )
+        }
+
+        let path = self.path.join(segment.filename(&fixed_block_range));
+        let tmp_dir = self.path.join(RECOMPRESSION_DIR);
+        reth_fs_util::create_dir_all(&tmp_dir).map_err(fs_err)?;
+        let tmp_path = tmp_dir.join(segment.filename(&fixed_block_range));
+
+        let ranges = header.tx_range().copied().zip(header.block_range().copied());
+        let index_path =
+            NippyJar::<SegmentHeader>::new(segment.columns(), &path, header.clone()).index_path();
+        let tmp_jar = with_compression_codec(
+            NippyJar::new(segment.columns(), &tmp_path, header),
+            self.compression.codec(segment),
+        );
+        let mut writer = StaticFileRowWriter {
+            writer: NippyJarWriter::new(tmp_jar, ConsistencyFailStrategy::Heal).map_err(map_err)?,
+            buf: Vec::new(),
+        };
+        if let Err(err) = write(&mut writer) {
+            NippyJar::<SegmentHeader>::load(&tmp_path)
+                .and_then(|jar| jar.delete())
+                .map_err(map_err)?;
+            return Err(err)
+        }
+        writer.writer.commit().map_err(map_err)?;
+
+        // From here on, an interrupted replacement is finished by `heal_recompression`.
+        let marker_path = tmp_path.with_extension(RECOMPRESSION_MARKER_EXTENSION);
+        reth_fs_util::write(&marker_path, []).map_err(fs_err)?;
+
+        let key = (fixed_block_range.end(), segment);
+        let entry = self.map.entry(key);
+
+        // The index of the original static file doesn't match the new rows, and a remote static
+        // file is now kept on local disk until it's uploaded again.
+        for stale_path in [index_path, path.with_extension(REMOTE_MARKER_EXTENSION)] {
+            if stale_path.exists() {
+                reth_fs_util::remove_file(stale_path).map_err(fs_err)?;
+            }
+        }
+        if let Some(remote) = &self.remote {
+            remote.remove(key);
+        }
+        NippyJar::<SegmentHeader>::move_files(&tmp_path, &path).map_err(map_err)?;
+        reth_fs_util::remove_file(&marker_path).map_err(fs_err)?;
+
+        // Readers load the new static file on the next access.
+        if let dashmap::mapref::entry::Entry::Occupied(entry) = entry {
+            entry.remove();
+        }
+
+        // The transactions of a corrupted static file might not have been indexed.
+        if let Some((tx_range, block_range)) = ranges {
+            let mut tx_index = self.static_files_tx_index.write();
+            let index = tx_index.entry(segment).or_default();
+            index.retain(|_, indexed| {
+                find_fixed_range(indexed.start()).start() != fixed_block_range.start()
+            });
+            index.insert(tx_range.end(), block_range);
+        }
+
+        Ok(())
+    }
+
+    /// Finishes moving the static files whose recompression was interrupted after they were
+    /// written, and removes any partially written ones.
+    fn heal_recompression(&self) -> ProviderResult<()> {
+        let fs_err = |e: FsPathError| ProviderError::FsPathError(e.to_string());
+        let tmp_dir = self.path.join(RECOMPRESSION_DIR);
+        if !tmp_dir.exists() {
+            return Ok(())
+        }
+
+        for entry in reth_fs_util::read_dir(&tmp_dir).map_err(fs_err)? {
+            let marker_path = entry.map_err(|e| ProviderError::NippyJar(e.to_string()))?.path();
+            if marker_path.extension().map_or(false, |ext| ext == RECOMPRESSION_MARKER_EXTENSION) {
+                let tmp_path = marker_path.with_extension("");
+                let Some(file_name) = tmp_path.file_name() else { continue };
+
+                info!(target: "provider::static_file", path = ?self.path.join(file_name), "Finishing interrupted static file recompression");
+                NippyJar::<SegmentHeader>::move_files(&tmp_path, &self.path.join(file_name))
+                    .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+            }
+        }
+
+        reth_fs_util::remove_dir_all(&tmp_dir).map_err(fs_err)?;
+        Ok(())
+    }
+
+    /// Uploads all finalized static files that end below the cutoff of the object store, see
+    /// [`StaticFileProvider::with_remote`], and evicts them from local disk in least recently
+    /// used order until they fit the cache size.
+    ///
+    /// Returns the number of uploaded static files.
+    pub fn offload_static_files(&self) -> ProviderResult<usize> {
+        let Some(remote) = &self.remote else { return Ok(0) };
+        if self.access.is_read_only() {
+            return Err(ProviderError::ReadOnlyStaticFileAccess)
+        }
+
+        let mut uploaded = 0;
+        let static_files =
+            iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        for (segment, ranges) in static_files {
+            let Some(highest_block) = self.get_highest_static_file_block(segment) else { continue };
+
+            for (block_range, _) in ranges {
+                let fixed_block_range = find_fixed_range(block_range.start());
+                // The latest static file can still be appended to
+                if fixed_block_range.end() >= highest_block ||
+                    !remote.is_remote(fixed_block_range.end())
+                {
+                    continue
+                }
+
+                let jar = NippyJar::<SegmentHeader>::load(
+                    &self.path.join(segment.filename(&fixed_block_range)),
+                )
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+                // Filters and perfect hashing functions live in the index file, which is not
+                // uploaded.
+                if !jar.data_path().exists() || jar.index_path().exists() {
+                    continue
+                }
+
+                if remote.upload(&jar)? {
+                    uploaded += 1;
+                }
+
+                let size = [jar.data_path().to_path_buf(), jar.offsets_path()]
+                    .iter()
+                    .map(|path| {
+                        reth_fs_util::metadata(path)
+                            .map(|metadata| metadata.len())
+                            .unwrap_or_default()
+                    })
+                    .sum();
+                remote.insert((fixed_block_range.end(), segment), size);
+            }
+        }
+
+        self.evict_remote_static_files(remote, None)?;
+
+        Ok(uploaded)
+    }
+
+    /// Downloads the static file if it's only kept in the object store, and evicts other static
+    /// files to make room for it.
+    fn download_remote_static_file(
+        &self,
+        remote: &RemoteStaticFiles,
+        segment: StaticFileSegment,
+        fixed_block_range: &SegmentRangeInclusive,
+    ) -> ProviderResult<()> {
+        let marker_path = self
+            .path
+            .join(segment.filename(fixed_block_range))
+            .with_extension(REMOTE_MARKER_EXTENSION);
+        if !marker_path.exists() {
+            return Ok(())
+        }
+
+        let jar =
+            NippyJar::<SegmentHeader>::load(&self.path.join(segment.filename(fixed_block_range)))
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        if remote.download(&jar)? {
+            self.evict_remote_static_files(remote, Some((fixed_block_range.end(), segment)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the data of the least recently used remote static files from local disk until they
+    /// fit the cache size. The static file `keep` is never evicted.
+    ///
+    /// Static files that are in use by the calling thread can't be evicted without deadlocking,
+    /// so static files that are in use are skipped and stay cached.
+    fn evict_remote_static_files(
+        &self,
+        remote: &RemoteStaticFiles,
+        keep: Option<StaticFileKey>,
+    ) -> ProviderResult<()> {
+        let fs_err = |e: FsPathError| ProviderError::FsPathError(e.to_string());
+
+        for ((fixed_block_range_end, segment), size) in remote.evict(keep) {
+            let key = (fixed_block_range_end, segment);
+            // Holding the entry makes loads of the static file wait until it's evicted
+            let Some(entry) = self.map.try_entry(key) else {
+                remote.insert(key, size);
+                continue
+            };
+
+            let path = self.path.join(segment.filename(&find_fixed_range(fixed_block_range_end)));
+            debug!(target: "provider::static_file", ?path, "Evicting remote static file from local disk");
+
+            // The marker is written first, so the static file is still listed if the eviction is
+            // interrupted.
+            reth_fs_util::write(path.with_extension(REMOTE_MARKER_EXTENSION), [])
+                .map_err(fs_err)?;
+            let jar = NippyJar::<SegmentHeader>::load(&path)
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+            for path in [jar.data_path().to_path_buf(), jar.offsets_path()] {
+                if path.exists() {
+                    reth_fs_util::remove_file(path).map_err(fs_err)?;
+                }
+            }
+
+            if let dashmap::mapref::entry::Entry::Occupied(entry) = entry {
+                entry.remove();
+            }
+        }
+
+        Ok(())
+    }
+
     /// Gets a static file segment's block range from the provider inner block
     /// index.
     fn get_segment_ranges_from_block(
@@ -477,6 +995,7 @@ impl StaticFileProvider {
         let mut max_block = self.static_files_max_block.write();
         let mut tx_index = self.static_files_tx_index.write();
 
+        max_block.clear();
         tx_index.clear();
 
         for (segment, ranges) in
@@ -507,6 +1026,25 @@ impl StaticFileProvider {
         Ok(())
     }
 
+    /// Reloads the index from disk and drops the cached providers of the static files that might
+    /// have changed since the last time the index was loaded.
+    ///
+    /// Used by a read-only provider in a secondary process, which doesn't see the static files
+    /// written by the process that opened the data directory for writing otherwise.
+    pub fn refresh(&self) -> ProviderResult<()> {
+        let previous = self.static_files_max_block.read().clone();
+        self.initialize_index()?;
+        let current = self.static_files_max_block.read().clone();
+
+        // only the last static file of a segment is appended to, but it might also have been
+        // finalized or removed by an unwind
+        for (segment, block) in previous.into_iter().chain(current) {
+            self.remove_cached_provider(segment, find_fixed_range(block).end());
+        }
+
+        Ok(())
+    }
+
     /// Ensures that any broken invariants which cannot be healed on the spot return a pipeline
     /// target to unwind to.
     ///
@@ -1022,6 +1560,27 @@ impl StaticFileProvider {
     }
 }
 
+/// Appends the rows of a static file that replaces a finalized one, see
+/// [`StaticFileProvider::replace_static_file`].
+#[derive(Debug)]
+pub struct StaticFileRowWriter {
+    writer: NippyJarWriter<SegmentHeader>,
+    buf: Vec<u8>,
+}
+
+impl StaticFileRowWriter {
+    /// Appends a column of the current row. The columns of a row are appended in the order of
+    /// the segment, e.g. the header, its total difficulty and its hash for headers.
+    pub fn append_column<T: Compact>(&mut self, column: T) -> ProviderResult<()> {
+        self.buf.clear();
+        column.to_compact(&mut self.buf);
+
+        self.writer
+            .append_column(Some(Ok::<_, Box<dyn StdError + Send + Sync>>(&self.buf)))
+            .map_err(|e| ProviderError::NippyJar(e.to_string()))
+    }
+}
+
 /// Helper trait to manage different [`StaticFileProviderRW`] of an `Arc<StaticFileProvider`
 pub trait StaticFileWriter {
     /// Returns a mutable reference to a [`StaticFileProviderRW`] of a [`StaticFileSegment`].
@@ -1,5 +1,8 @@
 mod manager;
-pub use manager::{StaticFileAccess, StaticFileProvider, StaticFileWriter};
+pub use manager::{
+    StaticFileAccess, StaticFileProvider, StaticFileRowWriter, StaticFileSegmentStats,
+    StaticFileWriter,
+};
 
 mod jar;
 pub use jar::StaticFileJarProvider;
@@ -9,6 +12,9 @@ pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
 mod metrics;
 
+mod remote;
+pub use remote::RemoteStaticFiles;
+
 use reth_nippy_jar::NippyJar;
 use reth_primitives::{static_file::SegmentHeader, StaticFileSegment};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
@@ -59,9 +65,17 @@ mod tests {
     use super::*;
     use crate::{test_utils::create_test_provider_factory, HeaderProvider};
     use rand::seq::SliceRandom;
-    use reth_db::{CanonicalHeaders, HeaderNumbers, HeaderTerminalDifficulties, Headers};
+    use reth_db::{
+        static_file::REMOTE_MARKER_EXTENSION, CanonicalHeaders, HeaderNumbers,
+        HeaderTerminalDifficulties, Headers,
+    };
     use reth_db_api::transaction::DbTxMut;
-    use reth_primitives::{static_file::find_fixed_range, B256, U256};
+    use reth_nippy_jar::compression::Compressors;
+    use reth_object_store::LocalObjectStore;
+    use reth_primitives::{
+        static_file::{find_fixed_range, CompressionCodec, StaticFileCompression},
+        Header, B256, U256,
+    };
     use reth_testing_utils::generators::{self, random_header_range};
 
     #[test]
@@ -141,4 +155,134 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_recompress() {
+        let static_files_path = tempfile::tempdir().unwrap();
+        let mut headers = random_header_range(&mut generators::rng(), 0..100, B256::random())
+            .into_iter()
+            .map(|header| header.unseal())
+            .collect::<Vec<_>>();
+        for number in [BLOCKS_PER_STATIC_FILE - 1, BLOCKS_PER_STATIC_FILE] {
+            headers.push(Header { number, ..headers[0].clone() });
+        }
+
+        // The first static file is finished by the last header, which starts the second one
+        {
+            let manager = StaticFileProvider::read_write(static_files_path.path()).unwrap();
+            let mut writer = manager.latest_writer(StaticFileSegment::Headers).unwrap();
+            for header in &headers {
+                if header.number == BLOCKS_PER_STATIC_FILE - 1 {
+                    writer.set_block_range(0..=BLOCKS_PER_STATIC_FILE - 2);
+                }
+                writer.append_header(header, U256::ZERO, &B256::ZERO).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        let compression =
+            StaticFileCompression { headers: CompressionCodec::Zstd(19), ..Default::default() };
+        let manager = StaticFileProvider::read_write(static_files_path.path())
+            .unwrap()
+            .with_compression(compression);
+        assert_eq!(manager.recompress_static_files().unwrap(), 1);
+        assert_eq!(manager.recompress_static_files().unwrap(), 0);
+
+        for (block, codec) in
+            [(0, CompressionCodec::Zstd(19)), (BLOCKS_PER_STATIC_FILE, CompressionCodec::Lz4)]
+        {
+            let jar = NippyJar::<SegmentHeader>::load(
+                &static_files_path
+                    .path()
+                    .join(StaticFileSegment::Headers.filename(&find_fixed_range(block))),
+            )
+            .unwrap();
+            let jar_codec = match jar.compressor() {
+                Some(Compressors::Zstd(zstd)) => CompressionCodec::Zstd(zstd.level()),
+                Some(Compressors::Lz4(_)) => CompressionCodec::Lz4,
+                None => CompressionCodec::None,
+            };
+            assert_eq!(jar_codec, codec);
+        }
+
+        // The row of the header finishing the first static file doesn't match its block number
+        for header in
+            headers.into_iter().filter(|header| header.number != BLOCKS_PER_STATIC_FILE - 1)
+        {
+            assert_eq!(manager.header_by_number(header.number).unwrap(), Some(header));
+        }
+    }
+
+    #[test]
+    fn test_remote_static_files() {
+        let static_files_path = tempfile::tempdir().unwrap();
+        let store_path = tempfile::tempdir().unwrap();
+        let mut headers = random_header_range(&mut generators::rng(), 0..100, B256::random())
+            .into_iter()
+            .map(|header| header.unseal())
+            .collect::<Vec<_>>();
+        for number in [
+            BLOCKS_PER_STATIC_FILE - 1,
+            BLOCKS_PER_STATIC_FILE,
+            2 * BLOCKS_PER_STATIC_FILE - 1,
+            2 * BLOCKS_PER_STATIC_FILE,
+        ] {
+            headers.push(Header { number, ..headers[0].clone() });
+        }
+
+        // The first two static files are finished by the headers which start the next ones
+        {
+            let manager = StaticFileProvider::read_write(static_files_path.path()).unwrap();
+            let mut writer = manager.latest_writer(StaticFileSegment::Headers).unwrap();
+            for header in &headers {
+                if header.number == BLOCKS_PER_STATIC_FILE - 1 {
+                    writer.set_block_range(0..=BLOCKS_PER_STATIC_FILE - 2);
+                } else if header.number == 2 * BLOCKS_PER_STATIC_FILE - 1 {
+                    writer.set_block_range(BLOCKS_PER_STATIC_FILE..=2 * BLOCKS_PER_STATIC_FILE - 2);
+                }
+                writer.append_header(header, U256::ZERO, &B256::ZERO).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        let remote = || {
+            RemoteStaticFiles::new(
+                Arc::new(LocalObjectStore::new(store_path.path())),
+                2 * BLOCKS_PER_STATIC_FILE,
+                0,
+            )
+        };
+        let path = |block| {
+            static_files_path
+                .path()
+                .join(StaticFileSegment::Headers.filename(&find_fixed_range(block)))
+        };
+
+        let manager =
+            StaticFileProvider::read_write(static_files_path.path()).unwrap().with_remote(remote());
+        assert_eq!(manager.offload_static_files().unwrap(), 2);
+        assert_eq!(manager.offload_static_files().unwrap(), 0);
+        for block in [0, BLOCKS_PER_STATIC_FILE] {
+            assert!(path(block).with_extension(REMOTE_MARKER_EXTENSION).exists());
+            assert!(!path(block).exists());
+        }
+        assert!(path(2 * BLOCKS_PER_STATIC_FILE).exists());
+        drop(manager);
+
+        // Remote static files are downloaded on access, and evict each other from local disk
+        let manager =
+            StaticFileProvider::read_write(static_files_path.path()).unwrap().with_remote(remote());
+        assert_eq!(manager.header_by_number(5).unwrap(), Some(headers[5].clone()));
+        assert!(!path(0).with_extension(REMOTE_MARKER_EXTENSION).exists());
+        manager.header_by_number(BLOCKS_PER_STATIC_FILE).unwrap();
+        assert!(path(0).with_extension(REMOTE_MARKER_EXTENSION).exists());
+        assert!(!path(BLOCKS_PER_STATIC_FILE).with_extension(REMOTE_MARKER_EXTENSION).exists());
+
+        // The rows of the headers finishing static files don't match their block numbers
+        for header in headers.into_iter().filter(|header| {
+            ![BLOCKS_PER_STATIC_FILE - 1, 2 * BLOCKS_PER_STATIC_FILE - 1].contains(&header.number)
+        }) {
+            assert_eq!(manager.header_by_number(header.number).unwrap(), Some(header));
+        }
+    }
 }
@@ -0,0 +1,370 @@
+use parking_lot::Mutex;
+use reth_db::static_file::REMOTE_MARKER_EXTENSION;
+use reth_fs_util::FsPathError;
+use reth_nippy_jar::NippyJar;
+use reth_object_store::ObjectStore;
+use reth_primitives::{
+    keccak256, static_file::SegmentHeader, BlockNumber, StaticFileSegment, B256,
+};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::File,
+    io::{Read, Write},
+    path::Path,
+    sync::Arc,
+};
+use tracing::info;
+
+/// Default size of the parts static files are uploaded in.
+const DEFAULT_PART_SIZE: usize = 64 * 1024 * 1024;
+
+/// Key of a static file in the map of loaded static files.
+pub(crate) type StaticFileKey = (BlockNumber, StaticFileSegment);
+
+/// Object store that the data of old static files is moved to, e.g. for archive nodes that only
+/// keep recent data on local disk.
+///
+/// Finalized static files that end below the cutoff block are uploaded to the object store by
+/// [`StaticFileProvider::offload_static_files`](super::StaticFileProvider::offload_static_files).
+/// Afterwards, their data and offsets are cached on local disk up to the configured size, and
+/// evicted in least recently used order. Evicted static files are downloaded again on their next
+/// access. Their configuration files always stay on local disk.
+#[derive(Debug)]
+pub struct RemoteStaticFiles {
+    store: Arc<dyn ObjectStore>,
+    /// Static files that end below this block are moved to the object store.
+    cutoff: BlockNumber,
+    /// Maximum size of the data of remote static files kept on local disk.
+    cache_size: u64,
+    /// Size of the parts static files are uploaded in.
+    part_size: usize,
+    /// Remote static files that are kept on local disk.
+    cache: Mutex<RemoteCache>,
+    /// Serializes downloads, so concurrent readers don't download the same static file twice.
+    download_lock: Mutex<()>,
+}
+
+impl RemoteStaticFiles {
+    /// Creates a new [`RemoteStaticFiles`] that moves static files ending below `cutoff` to the
+    /// object store, and caches up to `cache_size` bytes of them on local disk.
+    pub fn new(store: Arc<dyn ObjectStore>, cutoff: BlockNumber, cache_size: u64) -> Self {
+        Self {
+            store,
+            cutoff,
+            cache_size,
+            part_size: DEFAULT_PART_SIZE,
+            cache: Default::default(),
+            download_lock: Default::default(),
+        }
+    }
+
+    /// Sets the size of the parts static files are uploaded in.
+    pub const fn with_part_size(mut self, part_size: usize) -> Self {
+        self.part_size = part_size;
+        self
+    }
+
+    /// Returns the block that static files ending below it are moved to the object store.
+    pub const fn cutoff(&self) -> BlockNumber {
+        self.cutoff
+    }
+
+    /// Returns the maximum size of the data of remote static files kept on local disk.
+    pub const fn cache_size(&self) -> u64 {
+        self.cache_size
+    }
+
+    /// Returns the size of the data of remote static files currently kept on local disk.
+    pub fn cached_size(&self) -> u64 {
+        self.cache.lock().size
+    }
+
+    /// Returns `true` if the static file ending at the given block is moved to the object store.
+    pub(crate) const fn is_remote(&self, block_end: BlockNumber) -> bool {
+        block_end < self.cutoff
+    }
+
+    /// Marks the static file as accessed, if it's cached.
+    pub(crate) fn touch(&self, key: StaticFileKey) {
+        self.cache.lock().touch(key);
+    }
+
+    /// Adds a local static file with a remote copy to the cache, unless it's cached already.
+    pub(crate) fn insert(&self, key: StaticFileKey, size: u64) {
+        let mut cache = self.cache.lock();
+        if !cache.files.contains_key(&key) {
+            cache.insert(key, size);
+        }
+    }
+
+    /// Removes the static file from the cache, e.g. when it's deleted.
+    pub(crate) fn remove(&self, key: StaticFileKey) {
+        self.cache.lock().remove(key);
+    }
+
+    /// Removes the least recently used static files from the cache until it fits the cache size,
+    /// and returns them with their size. The static file `keep` is never removed.
+    pub(crate) fn evict(&self, keep: Option<StaticFileKey>) -> Vec<(StaticFileKey, u64)> {
+        self.cache.lock().evict(self.cache_size, keep)
+    }
+
+    /// Uploads the data and offsets of the static file, unless the object store already has a
+    /// copy that matches its configuration. Returns `true` if the static file was uploaded.
+    pub(crate) fn upload(&self, jar: &NippyJar<SegmentHeader>) -> ProviderResult<bool> {
+        let name = file_name(jar.data_path())?;
+        let config_hash = config_hash(jar)?;
+        if self.manifest(&name)?.is_some_and(|manifest| manifest.config_hash == config_hash) {
+            return Ok(false)
+        }
+
+        info!(target: "provider::static_file", path = ?jar.data_path(), "Uploading static file");
+        let data = self.upload_file(&name, DATA_KIND, jar.data_path())?;
+        let offsets = self.upload_file(&name, OFFSETS_KIND, &jar.offsets_path())?;
+
+        // The manifest is uploaded last, since its presence marks a complete upload
+        let manifest = RemoteManifest { config_hash, data, offsets };
+        let manifest = serde_json::to_vec(&manifest).map_err(remote_err)?;
+        self.store.put(&manifest_key(&name), &manifest).map_err(remote_err)?;
+
+        Ok(true)
+    }
+
+    /// Downloads the data and offsets of the static file if they're not on local disk, and adds
+    /// it to the cache. Returns `true` if the static file was downloaded.
+    pub(crate) fn download(&self, jar: &NippyJar<SegmentHeader>) -> ProviderResult<bool> {
+        let data_path = jar.data_path();
+        if data_path.exists() {
+            return Ok(false)
+        }
+
+        let _lock = self.download_lock.lock();
+        // Downloaded by another reader while waiting for the lock
+        if data_path.exists() {
+            return Ok(false)
+        }
+
+        let name = file_name(data_path)?;
+        let manifest = self.manifest(&name)?.ok_or_else(|| {
+            ProviderError::RemoteStaticFile(format!("{name} is missing from the object store"))
+        })?;
+        if manifest.config_hash != config_hash(jar)? {
+            return Err(ProviderError::RemoteStaticFile(format!(
+                "remote copy of {name} doesn't match its configuration"
+            )))
+        }
+
+        info!(target: "provider::static_file", path = ?data_path, size = manifest.data.size, "Downloading static file");
+        // The data file is moved in place last, since its presence marks a complete download
+        self.download_file(&name, OFFSETS_KIND, &manifest.offsets, &jar.offsets_path())?;
+        self.download_file(&name, DATA_KIND, &manifest.data, data_path)?;
+        let marker_path = data_path.with_extension(REMOTE_MARKER_EXTENSION);
+        if marker_path.exists() {
+            reth_fs_util::remove_file(marker_path).map_err(fs_err)?;
+        }
+
+        let header = jar.user_header();
+        self.insert(
+            (header.expected_block_end(), header.segment()),
+            manifest.data.size + manifest.offsets.size,
+        );
+
+        Ok(true)
+    }
+
+    /// Returns the manifest of the uploaded static file, if any.
+    fn manifest(&self, name: &str) -> ProviderResult<Option<RemoteManifest>> {
+        self.store
+            .get(&manifest_key(name))
+            .map_err(remote_err)?
+            .map(|manifest| serde_json::from_slice(&manifest).map_err(remote_err))
+            .transpose()
+    }
+
+    /// Uploads the file in parts.
+    fn upload_file(&self, name: &str, kind: &str, path: &Path) -> ProviderResult<RemoteFile> {
+        let mut file = File::open(path).map_err(|err| fs_err(FsPathError::open(err, path)))?;
+        let mut remote_file = RemoteFile { size: 0, parts: Vec::new() };
+        let mut part = Vec::with_capacity(self.part_size);
+        loop {
+            part.clear();
+            (&mut file)
+                .take(self.part_size as u64)
+                .read_to_end(&mut part)
+                .map_err(|err| fs_err(FsPathError::read(err, path)))?;
+            if part.is_empty() {
+                break
+            }
+
+            self.store
+                .put(&part_key(name, kind, remote_file.parts.len()), &part)
+                .map_err(remote_err)?;
+            remote_file.size += part.len() as u64;
+            remote_file.parts.push(keccak256(&part));
+        }
+        Ok(remote_file)
+    }
+
+    /// Downloads the parts of the file to a temporary file, and then moves it to `path`.
+    fn download_file(
+        &self,
+        name: &str,
+        kind: &str,
+        remote_file: &RemoteFile,
+        path: &Path,
+    ) -> ProviderResult<()> {
+        let mut tmp_path = path.to_path_buf().into_os_string();
+        tmp_path.push(".download");
+        let tmp_path = Path::new(&tmp_path);
+
+        let mut file = reth_fs_util::create_file(tmp_path).map_err(fs_err)?;
+        let mut size = 0;
+        for (index, hash) in remote_file.parts.iter().enumerate() {
+            let key = part_key(name, kind, index);
+            let part = self.store.get(&key).map_err(remote_err)?.ok_or_else(|| {
+                ProviderError::RemoteStaticFile(format!("{key} is missing from the object store"))
+            })?;
+            if keccak256(&part) != *hash {
+                return Err(ProviderError::RemoteStaticFile(format!("{key} is corrupted")))
+            }
+            file.write_all(&part).map_err(|err| fs_err(FsPathError::write(err, tmp_path)))?;
+            size += part.len() as u64;
+        }
+
+        if size != remote_file.size {
+            return Err(ProviderError::RemoteStaticFile(format!(
+                "downloaded {size} bytes of {name} {kind} instead of {}",
+                remote_file.size
+            )))
+        }
+        file.sync_all().map_err(|err| fs_err(FsPathError::write(err, tmp_path)))?;
+        reth_fs_util::rename(tmp_path, path).map_err(fs_err)?;
+
+        Ok(())
+    }
+}
+
+/// Local static files with a remote copy, by their last access.
+#[derive(Debug, Default)]
+struct RemoteCache {
+    /// Size and last access of each static file.
+    files: HashMap<StaticFileKey, (u64, u64)>,
+    /// Total size of the static files.
+    size: u64,
+    /// Counter ordering the accesses.
+    clock: u64,
+}
+
+impl RemoteCache {
+    fn touch(&mut self, key: StaticFileKey) {
+        if let Some((_, last_access)) = self.files.get_mut(&key) {
+            self.clock += 1;
+            *last_access = self.clock;
+        }
+    }
+
+    fn insert(&mut self, key: StaticFileKey, size: u64) {
+        self.clock += 1;
+        if let Some((previous_size, _)) = self.files.insert(key, (size, self.clock)) {
+            self.size -= previous_size;
+        }
+        self.size += size;
+    }
+
+    fn remove(&mut self, key: StaticFileKey) {
+        if let Some((size, _)) = self.files.remove(&key) {
+            self.size -= size;
+        }
+    }
+
+    fn evict(&mut self, limit: u64, keep: Option<StaticFileKey>) -> Vec<(StaticFileKey, u64)> {
+        let mut evicted = Vec::new();
+        while self.size > limit {
+            let Some(key) = self
+                .files
+                .iter()
+                .filter(|(key, _)| Some(**key) != keep)
+                .min_by_key(|(_, (_, last_access))| *last_access)
+                .map(|(key, _)| *key)
+            else {
+                break
+            };
+
+            let (size, _) = self.files.remove(&key).expect("key exists");
+            self.size -= size;
+            evicted.push((key, size));
+        }
+        evicted
+    }
+}
+
+/// Describes an uploaded static file.
+#[derive(Debug, Serialize, Deserialize)]
+struct RemoteManifest {
+    /// Hash of the configuration file the data belongs to.
+    config_hash: B256,
+    /// The data file.
+    data: RemoteFile,
+    /// The offsets file.
+    offsets: RemoteFile,
+}
+
+/// An uploaded file.
+#[derive(Debug, Serialize, Deserialize)]
+struct RemoteFile {
+    /// Size of the file.
+    size: u64,
+    /// Hashes of the parts of the file, in order.
+    parts: Vec<B256>,
+}
+
+const DATA_KIND: &str = "data";
+const OFFSETS_KIND: &str = "offsets";
+
+fn manifest_key(name: &str) -> String {
+    format!("{name}/manifest.json")
+}
+
+fn part_key(name: &str, kind: &str, index: usize) -> String {
+    format!("{name}/{kind}/{index}")
+}
+
+fn file_name(path: &Path) -> ProviderResult<String> {
+    path.file_name().map(|file_name| file_name.to_string_lossy().into_owned()).ok_or_else(|| {
+        ProviderError::RemoteStaticFile(format!("invalid static file path {path:?}"))
+    })
+}
+
+fn config_hash(jar: &NippyJar<SegmentHeader>) -> ProviderResult<B256> {
+    Ok(keccak256(reth_fs_util::read(jar.config_path()).map_err(fs_err)?))
+}
+
+fn fs_err(err: FsPathError) -> ProviderError {
+    ProviderError::FsPathError(err.to_string())
+}
+
+fn remote_err(err: impl Display) -> ProviderError {
+    ProviderError::RemoteStaticFile(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = RemoteCache::default();
+        let key = |end| (end, StaticFileSegment::Headers);
+        cache.insert(key(0), 10);
+        cache.insert(key(1), 10);
+        cache.insert(key(2), 10);
+        cache.touch(key(0));
+
+        assert_eq!(cache.evict(20, None), vec![(key(1), 10)]);
+        assert_eq!(cache.evict(0, Some(key(0))), vec![(key(2), 10)]);
+        assert_eq!(cache.size, 10);
+        assert!(cache.evict(10, None).is_empty());
+    }
+}
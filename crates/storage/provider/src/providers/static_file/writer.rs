@@ -7,7 +7,7 @@ use reth_codecs::Compact;
 use reth_db_api::models::CompactU256;
 use reth_nippy_jar::{ConsistencyFailStrategy, NippyJar, NippyJarError, NippyJarWriter};
 use reth_primitives::{
-    static_file::{find_fixed_range, SegmentHeader, SegmentRangeInclusive},
+    static_file::{find_fixed_range, CompressionCodec, SegmentHeader, SegmentRangeInclusive},
     BlockHash, BlockNumber, Header, Receipt, StaticFileSegment, TransactionSignedNoHash, TxNumber,
     U256,
 };
@@ -145,7 +145,8 @@ impl StaticFileProviderRW {
             ),
             Err(ProviderError::MissingStaticFileBlock(_, _)) => {
                 let path = static_file_provider.directory().join(segment.filename(&block_range));
-                (create_jar(segment, &path, block_range), path)
+                let codec = static_file_provider.compression().codec(segment);
+                (create_jar(segment, &path, block_range, codec), path)
             }
             Err(err) => return Err(err),
         };
@@ -815,18 +816,25 @@ fn create_jar(
     segment: StaticFileSegment,
     path: &Path,
     expected_block_range: SegmentRangeInclusive,
+    codec: CompressionCodec,
 ) -> NippyJar<SegmentHeader> {
-    let mut jar = NippyJar::new(
+    let jar = NippyJar::new(
         segment.columns(),
         path,
         SegmentHeader::new(expected_block_range, None, None, segment),
     );
 
-    // Transaction and Receipt already have the compression scheme used natively in its encoding.
-    // (zstd-dictionary)
-    if segment.is_headers() {
-        jar = jar.with_lz4();
-    }
+    with_compression_codec(jar, codec)
+}
 
-    jar
+/// Sets the compressor of a [`NippyJar`] that hasn't been written to yet.
+pub(super) fn with_compression_codec(
+    jar: NippyJar<SegmentHeader>,
+    codec: CompressionCodec,
+) -> NippyJar<SegmentHeader> {
+    match codec {
+        CompressionCodec::None => jar,
+        CompressionCodec::Lz4 => jar.with_lz4(),
+        CompressionCodec::Zstd(level) => jar.with_zstd_level(level),
+    }
 }
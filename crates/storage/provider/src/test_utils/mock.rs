@@ -1,27 +1,33 @@
 use crate::{
+    providers::StaticFileSegmentStats,
     traits::{BlockSource, ReceiptProvider},
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, ReceiptProviderIdExt,
-    RequestsProvider, StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider,
-    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, PruneCheckpointReader,
+    ReceiptProviderIdExt, RequestsProvider, StateHistoryReader, StateProvider, StateProviderBox,
+    StateProviderFactory, StateRootProvider, StorageStatsReader, TransactionVariant,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use parking_lot::Mutex;
 use reth_chainspec::{ChainInfo, ChainSpec};
-use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
+use reth_db_api::{
+    database_metrics::TableStats,
+    models::{AccountBeforeTx, StoredBlockBodyIndices},
+};
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
-    keccak256, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber,
-    BlockNumberOrTag, BlockWithSenders, Bytecode, Bytes, Header, Receipt, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, StorageKey, StorageValue, TransactionMeta,
+    keccak256, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash,
+    BlockNumber, BlockNumberOrTag, BlockWithSenders, Bytecode, Bytes, Header, Receipt, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, StorageEntry, StorageKey, StorageValue, TransactionMeta,
     TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
     U256,
 };
+use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{StageCheckpointReader, StateProofProvider};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{
     prefix_set::TriePrefixSetsMut, updates::TrieUpdates, AccountProof, HashedPostState,
-    HashedStorage,
+    HashedStorage, MultiProof,
 };
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
@@ -43,6 +49,12 @@ pub struct MockEthProvider {
     pub chain_spec: Arc<ChainSpec>,
     /// Local state roots
     pub state_roots: Arc<Mutex<Vec<B256>>>,
+    /// Local prune checkpoints
+    pub prune_checkpoints: Arc<Mutex<HashMap<PruneSegment, PruneCheckpoint>>>,
+    /// Local receipt store, keyed by block hash
+    pub receipts: Arc<Mutex<HashMap<B256, Vec<Receipt>>>>,
+    /// Local finalized block
+    pub finalized_block: Arc<Mutex<Option<BlockNumHash>>>,
 }
 
 impl Default for MockEthProvider {
@@ -53,6 +65,9 @@ impl Default for MockEthProvider {
             accounts: Default::default(),
             chain_spec: Arc::new(reth_chainspec::ChainSpecBuilder::mainnet().build()),
             state_roots: Default::default(),
+            prune_checkpoints: Default::default(),
+            receipts: Default::default(),
+            finalized_block: Default::default(),
         }
     }
 }
@@ -121,6 +136,11 @@ impl MockEthProvider {
         }
     }
 
+    /// Add prune checkpoint to local prune checkpoint store
+    pub fn add_prune_checkpoint(&self, segment: PruneSegment, checkpoint: PruneCheckpoint) {
+        self.prune_checkpoints.lock().insert(segment, checkpoint);
+    }
+
     /// Add account to local account store
     pub fn add_account(&self, address: Address, account: ExtendedAccount) {
         self.accounts.lock().insert(address, account);
@@ -137,6 +157,16 @@ impl MockEthProvider {
     pub fn add_state_root(&self, state_root: B256) {
         self.state_roots.lock().push(state_root);
     }
+
+    /// Add the receipts of a block to local receipt store
+    pub fn add_receipts(&self, hash: B256, receipts: Vec<Receipt>) {
+        self.receipts.lock().insert(hash, receipts);
+    }
+
+    /// Set the finalized block
+    pub fn set_finalized_block(&self, block: BlockNumHash) {
+        *self.finalized_block.lock() = Some(block);
+    }
 }
 
 impl HeaderProvider for MockEthProvider {
@@ -359,8 +389,12 @@ impl ReceiptProvider for MockEthProvider {
         Ok(None)
     }
 
-    fn receipts_by_block(&self, _block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
-        Ok(None)
+    fn receipts_by_block(&self, block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
+        let hash = match block {
+            BlockHashOrNumber::Hash(hash) => Some(hash),
+            BlockHashOrNumber::Number(number) => self.block_hash(number)?,
+        };
+        Ok(hash.and_then(|hash| self.receipts.lock().get(&hash).cloned()))
     }
 
     fn receipts_by_tx_range(
@@ -438,7 +472,7 @@ impl BlockIdReader for MockEthProvider {
     }
 
     fn finalized_block_num_hash(&self) -> ProviderResult<Option<reth_primitives::BlockNumHash>> {
-        Ok(None)
+        Ok(*self.finalized_block.lock())
     }
 }
 
@@ -475,7 +509,17 @@ impl BlockReader for MockEthProvider {
         Ok(None)
     }
 
-    fn block_body_indices(&self, _num: u64) -> ProviderResult<Option<StoredBlockBodyIndices>> {
+    fn block_body_indices(&self, num: u64) -> ProviderResult<Option<StoredBlockBodyIndices>> {
+        // transactions are numbered in the iteration order of the blocks, see `transaction_by_id`
+        let lock = self.blocks.lock();
+        let mut first_tx_num = 0;
+        for block in lock.values() {
+            if block.number == num {
+                let tx_count = block.body.len() as u64;
+                return Ok(Some(StoredBlockBodyIndices { first_tx_num, tx_count }))
+            }
+            first_tx_num += block.body.len() as u64;
+        }
         Ok(None)
     }
 
@@ -567,6 +611,29 @@ impl StageCheckpointReader for MockEthProvider {
     }
 }
 
+impl StorageStatsReader for MockEthProvider {
+    fn table_stats(&self) -> ProviderResult<Vec<TableStats>> {
+        Ok(vec![])
+    }
+
+    fn static_file_stats(&self) -> ProviderResult<Vec<StaticFileSegmentStats>> {
+        Ok(vec![])
+    }
+}
+
+impl PruneCheckpointReader for MockEthProvider {
+    fn get_prune_checkpoint(
+        &self,
+        segment: PruneSegment,
+    ) -> ProviderResult<Option<PruneCheckpoint>> {
+        Ok(self.prune_checkpoints.lock().get(&segment).copied())
+    }
+
+    fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
+        Ok(self.prune_checkpoints.lock().iter().map(|(segment, cp)| (*segment, *cp)).collect())
+    }
+}
+
 impl StateRootProvider for MockEthProvider {
     fn hashed_state_root(&self, _state: HashedPostState) -> ProviderResult<B256> {
         Ok(self.state_roots.lock().pop().unwrap_or_default())
@@ -618,6 +685,14 @@ impl StateProofProvider for MockEthProvider {
         Ok(AccountProof::new(address))
     }
 
+    fn multiproof(
+        &self,
+        _hashed_state: HashedPostState,
+        _targets: HashMap<B256, Vec<B256>>,
+    ) -> ProviderResult<MultiProof> {
+        Ok(MultiProof::default())
+    }
+
     fn witness(
         &self,
         _overlay: HashedPostState,
@@ -777,6 +852,27 @@ impl RequestsProvider for MockEthProvider {
     }
 }
 
+impl StateHistoryReader for MockEthProvider {
+    fn account_history(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        Ok(Vec::default())
+    }
+
+    fn storage_history(
+        &self,
+        _address: Address,
+        _storage_key: StorageKey,
+        _range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<(BlockNumber, U256)>> {
+        Ok(Vec::default())
+    }
+}
+
 impl ChangeSetReader for MockEthProvider {
     fn account_block_changeset(
         &self,
@@ -784,4 +880,11 @@ impl ChangeSetReader for MockEthProvider {
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         Ok(Vec::default())
     }
+
+    fn storage_block_changeset(
+        &self,
+        _block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        Ok(Vec::default())
+    }
 }
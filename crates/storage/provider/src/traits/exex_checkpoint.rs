@@ -0,0 +1,16 @@
+use reth_errors::ProviderResult;
+use reth_primitives::BlockNumber;
+
+/// Functionality to read the checkpoints of execution extensions from the database.
+pub trait ExExCheckpointReader: Send + Sync {
+    /// Returns the last block number processed by the `ExEx` with the given ID.
+    ///
+    /// If the `ExEx` has not saved a checkpoint yet, this returns `None`.
+    fn get_exex_checkpoint(&self, id: &str) -> ProviderResult<Option<BlockNumber>>;
+}
+
+/// Functionality to write the checkpoints of execution extensions to the database.
+pub trait ExExCheckpointWriter: Send + Sync {
+    /// Saves the last block number processed by the `ExEx` with the given ID in the DB.
+    fn save_exex_checkpoint(&self, id: &str, block_number: BlockNumber) -> ProviderResult<()>;
+}
@@ -1,9 +1,10 @@
 //! Helper provider traits to encapsulate all provider traits for simplicity.
 
 use crate::{
-    AccountReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, StageCheckpointReader, StateProviderFactory,
-    StaticFileProviderFactory, TransactionsProvider,
+    AccountReader, BlockReaderIdExt, CanonChainTracker, ChainSpecProvider, ChangeSetReader,
+    DatabaseProviderFactory, EvmEnvProvider, ExExCheckpointReader, ExExCheckpointWriter,
+    HeaderProvider, PruneCheckpointReader, StageCheckpointReader, StateHistoryReader,
+    StateProviderFactory, StaticFileProviderFactory, StorageStatsReader, TransactionsProvider,
 };
 use reth_chain_state::{CanonStateSubscriptions, ForkChoiceSubscriptions};
 use reth_chainspec::{ChainSpec, EthChainSpec};
@@ -21,7 +22,13 @@ pub trait FullProvider<DB: Database, ChainSpec: EthChainSpec>:
     + ChangeSetReader
     + CanonStateSubscriptions
     + ForkChoiceSubscriptions
+    + CanonChainTracker
     + StageCheckpointReader
+    + PruneCheckpointReader
+    + StateHistoryReader
+    + StorageStatsReader
+    + ExExCheckpointReader
+    + ExExCheckpointWriter
     + Clone
     + Unpin
     + 'static
@@ -39,7 +46,13 @@ impl<T, DB: Database, ChainSpec: EthChainSpec> FullProvider<DB, ChainSpec> for T
         + ChangeSetReader
         + CanonStateSubscriptions
         + ForkChoiceSubscriptions
+        + CanonChainTracker
         + StageCheckpointReader
+        + PruneCheckpointReader
+        + StateHistoryReader
+        + StorageStatsReader
+        + ExExCheckpointReader
+        + ExExCheckpointWriter
         + Clone
         + Unpin
         + 'static
@@ -56,6 +69,9 @@ pub trait FullRpcProvider:
     + HeaderProvider
     + TransactionsProvider
     + StageCheckpointReader
+    + PruneCheckpointReader
+    + StateHistoryReader
+    + StorageStatsReader
     + Clone
     + Unpin
     + 'static
@@ -70,6 +86,9 @@ impl<T> FullRpcProvider for T where
         + HeaderProvider
         + TransactionsProvider
         + StageCheckpointReader
+        + PruneCheckpointReader
+        + StateHistoryReader
+        + StorageStatsReader
         + Clone
         + Unpin
         + 'static
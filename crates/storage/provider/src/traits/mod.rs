@@ -36,7 +36,7 @@ mod static_file_provider;
 pub use static_file_provider::StaticFileProviderFactory;
 
 mod stats;
-pub use stats::StatsReader;
+pub use stats::{StatsReader, StorageStatsReader};
 
 mod full;
 pub use full::{FullProvider, FullRpcProvider};
@@ -46,3 +46,6 @@ pub use tree_viewer::TreeViewer;
 
 mod finalized_block;
 pub use finalized_block::{FinalizedBlockReader, FinalizedBlockWriter};
+
+mod exex_checkpoint;
+pub use exex_checkpoint::{ExExCheckpointReader, ExExCheckpointWriter};
@@ -1,4 +1,5 @@
-use reth_db_api::table::Table;
+use crate::providers::StaticFileSegmentStats;
+use reth_db_api::{database_metrics::TableStats, table::Table};
 use reth_storage_errors::provider::ProviderResult;
 
 /// The trait for fetching provider statistics.
@@ -8,3 +9,13 @@ pub trait StatsReader: Send + Sync {
     /// route to different data sources other than [Table].
     fn count_entries<T: Table>(&self) -> ProviderResult<usize>;
 }
+
+/// The trait for fetching the composition of the node's storage.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait StorageStatsReader: Send + Sync {
+    /// Returns the [`TableStats`] of all database tables.
+    fn table_stats(&self) -> ProviderResult<Vec<TableStats>>;
+
+    /// Returns the [`StaticFileSegmentStats`] of all static file segments.
+    fn static_file_stats(&self) -> ProviderResult<Vec<StaticFileSegmentStats>>;
+}
@@ -511,7 +511,10 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{test_utils::create_test_provider_factory, AccountReader, TrieWriter};
+    use crate::{
+        test_utils::create_test_provider_factory, AccountReader, DatabaseProviderRW, ForkedState,
+        LatestStateProviderRef, StateProvider, StaticFileProviderFactory, TrieWriter,
+    };
     use reth_db::tables;
     use reth_db_api::{
         cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO},
@@ -519,14 +522,17 @@ mod tests {
         transaction::{DbTx, DbTxMut},
     };
     use reth_primitives::{
-        keccak256, Account, Address, Receipt, Receipts, StorageEntry, B256, U256,
+        keccak256, Account, Address, Bytecode, Receipt, Receipts, StorageEntry, B256, U256,
     };
+    use reth_storage_errors::provider::ProviderResult;
     use reth_trie::{test_utils::state_root, HashedPostState, HashedStorage, StateRoot};
     use reth_trie_db::DatabaseStateRoot;
     use revm::{
         db::{
             states::{
-                bundle_state::BundleRetention, changes::PlainStorageRevert, PlainStorageChangeset,
+                bundle_state::BundleRetention,
+                changes::{PlainStateReverts, PlainStorageRevert},
+                PlainStorageChangeset, RevertToSlot, StateChangeset,
             },
             BundleState, EmptyDB,
         },
@@ -535,7 +541,10 @@ mod tests {
         },
         DatabaseCommit, State,
     };
-    use std::collections::{BTreeMap, HashMap};
+    use std::{
+        collections::{BTreeMap, HashMap},
+        sync::Arc,
+    };
 
     #[test]
     fn wiped_entries_are_removed() {
@@ -718,6 +727,207 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cold_state_is_moved_back() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let (address_a, address_b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let entry =
+            |slot: u8| StorageEntry { key: B256::with_last_byte(slot), value: U256::from(slot) };
+        let tx = provider.tx_ref();
+        tx.put::<tables::ColdAccountState>(address_a, Account::default()).unwrap();
+        tx.put::<tables::ColdAccountState>(address_b, Account::default()).unwrap();
+        for slot in [1, 2] {
+            tx.put::<tables::ColdStorageState>(address_a, entry(slot)).unwrap();
+        }
+        tx.put::<tables::ColdStorageState>(address_b, entry(1)).unwrap();
+
+        // expired state is read from the cold state
+        assert_eq!(provider.basic_account(address_a), Ok(Some(Account::default())));
+
+        // A and its first slot are changed, the storage of B is wiped. Reverts are written first,
+        // as they're taken from the state before the changes.
+        let account_a = RevmAccountInfo { nonce: 1, ..Default::default() };
+        provider
+            .write_state_reverts(
+                PlainStateReverts {
+                    accounts: vec![vec![]],
+                    storage: vec![vec![PlainStorageRevert {
+                        address: address_b,
+                        wiped: true,
+                        storage_revert: vec![],
+                    }]],
+                },
+                1,
+            )
+            .unwrap();
+        provider
+            .write_state_changes(StateChangeset {
+                accounts: vec![(address_a, Some(account_a.clone()))],
+                storage: vec![
+                    PlainStorageChangeset {
+                        address: address_a,
+                        wipe_storage: false,
+                        storage: vec![(U256::from_be_bytes(entry(1).key.0), U256::from(3))],
+                    },
+                    PlainStorageChangeset {
+                        address: address_b,
+                        wipe_storage: true,
+                        storage: vec![],
+                    },
+                ],
+                contracts: vec![],
+            })
+            .unwrap();
+
+        assert_eq!(
+            provider.tx_ref().get::<tables::PlainAccountState>(address_a),
+            Ok(Some(account_a.into()))
+        );
+        assert_eq!(
+            provider.tx_ref().get::<tables::PlainStorageState>(address_a),
+            Ok(Some(StorageEntry { value: U256::from(3), ..entry(1) }))
+        );
+        let cold_accounts = provider
+            .tx_ref()
+            .cursor_read::<tables::ColdAccountState>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(cold_accounts, vec![(address_b, Account::default())]);
+        let cold_storage = provider
+            .tx_ref()
+            .cursor_read::<tables::ColdStorageState>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(cold_storage, vec![(address_a, entry(2))]);
+
+        // the wiped cold storage of B is recorded in the changeset
+        assert_eq!(
+            provider.tx_ref().get::<tables::StorageChangeSets>(BlockNumberAddress((1, address_b))),
+            Ok(Some(entry(1)))
+        );
+    }
+
+    /// Forked state with the same account at every address and the same value in every slot.
+    #[derive(Debug, Clone, Copy)]
+    struct MockForkedState {
+        account: Account,
+        value: U256,
+    }
+
+    impl ForkedState for MockForkedState {
+        fn basic_account(&self, _address: Address) -> ProviderResult<Option<Account>> {
+            Ok(Some(self.account))
+        }
+
+        fn storage(&self, _account: Address, _storage_key: B256) -> ProviderResult<Option<U256>> {
+            Ok(Some(self.value))
+        }
+
+        fn bytecode_by_hash(&self, _code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn forked_state_tombstones() {
+        let forked = MockForkedState {
+            account: Account { nonce: 5, ..Default::default() },
+            value: U256::from(7),
+        };
+        let factory = create_test_provider_factory().with_forked_state(Arc::new(forked));
+        let provider = factory.provider_rw().unwrap();
+        provider.tx_ref().put::<tables::BlockBodyIndices>(1, Default::default()).unwrap();
+
+        let (address_a, address_b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let (slot_1, slot_2) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        let latest = |provider: &DatabaseProviderRW<_>| {
+            LatestStateProviderRef::new(provider.tx_ref(), factory.static_file_provider())
+                .with_forked_state(Some(&forked))
+                .basic_account(address_a)
+                .unwrap()
+        };
+        let storage = |provider: &DatabaseProviderRW<_>, address, slot| {
+            LatestStateProviderRef::new(provider.tx_ref(), factory.static_file_provider())
+                .with_forked_state(Some(&forked))
+                .storage(address, slot)
+                .unwrap()
+        };
+
+        // block 1 destroys A and clears the first slot of B, both were read from the forked state
+        provider
+            .write_state_reverts(
+                PlainStateReverts {
+                    accounts: vec![vec![(
+                        address_a,
+                        Some(RevmAccountInfo { nonce: 5, ..Default::default() }),
+                    )]],
+                    storage: vec![vec![
+                        PlainStorageRevert {
+                            address: address_a,
+                            wiped: true,
+                            storage_revert: vec![],
+                        },
+                        PlainStorageRevert {
+                            address: address_b,
+                            wiped: false,
+                            storage_revert: vec![(
+                                U256::from_be_bytes(slot_1.0),
+                                RevertToSlot::Some(forked.value),
+                            )],
+                        },
+                    ]],
+                },
+                1,
+            )
+            .unwrap();
+        provider
+            .write_state_changes(StateChangeset {
+                accounts: vec![(address_a, None)],
+                storage: vec![
+                    PlainStorageChangeset {
+                        address: address_a,
+                        wipe_storage: true,
+                        storage: vec![],
+                    },
+                    PlainStorageChangeset {
+                        address: address_b,
+                        wipe_storage: false,
+                        storage: vec![(U256::from_be_bytes(slot_1.0), U256::ZERO)],
+                    },
+                ],
+                contracts: vec![],
+            })
+            .unwrap();
+
+        assert_eq!(
+            provider.tx_ref().get::<tables::ForkedAccountTombstones>(address_a),
+            Ok(Some(1))
+        );
+        assert_eq!(
+            provider.tx_ref().get::<tables::ForkedStorageTombstones>(address_b),
+            Ok(Some(StorageEntry { key: slot_1, value: U256::ZERO }))
+        );
+        assert_eq!(latest(&provider), None);
+        assert_eq!(storage(&provider, address_a, slot_1), None);
+        assert_eq!(storage(&provider, address_b, slot_1), None);
+        assert_eq!(storage(&provider, address_b, slot_2), Some(forked.value));
+
+        // unwinding the block restores the forked state
+        provider.remove_state(1..=1).unwrap();
+        assert_eq!(provider.tx_ref().get::<tables::ForkedAccountTombstones>(address_a), Ok(None));
+        assert_eq!(latest(&provider), Some(forked.account));
+        assert_eq!(storage(&provider, address_a, slot_1), Some(forked.value));
+        assert_eq!(storage(&provider, address_b, slot_1), Some(forked.value));
+    }
+
     #[test]
     fn write_to_db_storage() {
         let factory = create_test_provider_factory();
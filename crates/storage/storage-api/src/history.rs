@@ -0,0 +1,32 @@
+use auto_impl::auto_impl;
+use reth_primitives::{Account, Address, BlockNumber, StorageKey, U256};
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Reader of the value history of accounts and storage slots, based on the history indices which
+/// list the blocks that changed them.
+#[auto_impl(&, Arc, Box)]
+pub trait StateHistoryReader: Send + Sync {
+    /// Returns the blocks in the given range that changed the account, in ascending order, each
+    /// with the account after the block.
+    ///
+    /// Returns at most `limit` entries.
+    fn account_history(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>>;
+
+    /// Returns the blocks in the given range that changed the storage slot, in ascending order,
+    /// each with the value of the slot after the block.
+    ///
+    /// Returns at most `limit` entries.
+    fn storage_history(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<(BlockNumber, U256)>>;
+}
@@ -25,6 +25,9 @@ pub use block_hash::*;
 mod header;
 pub use header::*;
 
+mod history;
+pub use history::*;
+
 mod prune_checkpoint;
 pub use prune_checkpoint::*;
 
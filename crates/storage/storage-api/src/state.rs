@@ -2,8 +2,8 @@ use super::{AccountReader, BlockHashReader, BlockIdReader, StateProofProvider, S
 use auto_impl::auto_impl;
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
-    Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag, Bytecode, StorageKey,
-    StorageValue, B256, KECCAK_EMPTY, U256,
+    Account, Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag, Bytecode,
+    StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
@@ -73,6 +73,29 @@ pub trait StateProvider:
     }
 }
 
+/// The state of the network a dev chain is forked from, at the block it's forked at.
+///
+/// State providers read accounts and storage slots that were never written by a local block from
+/// the forked state, so the local state is layered on top of it.
+#[auto_impl(&, Arc, Box)]
+pub trait ForkedState: std::fmt::Debug + Send + Sync {
+    /// Get basic account information.
+    fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>>;
+
+    /// Get storage of given account.
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>>;
+
+    /// Get account code by its hash.
+    ///
+    /// Returns `None` for code hashes of accounts that weren't read with
+    /// [`ForkedState::basic_account`].
+    fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>>;
+}
+
 /// Light wrapper that returns `StateProvider` implementations that correspond to the given
 /// `BlockNumber`, the latest state, or the pending state.
 ///
@@ -2,7 +2,7 @@ use reth_primitives::{Address, Bytes, B256};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     prefix_set::TriePrefixSetsMut, updates::TrieUpdates, AccountProof, HashedPostState,
-    HashedStorage,
+    HashedStorage, MultiProof, VerkleAccountProof,
 };
 use revm::db::BundleState;
 use std::collections::HashMap;
@@ -92,10 +92,32 @@ pub trait StateProofProvider: Send + Sync {
         slots: &[B256],
     ) -> ProviderResult<AccountProof>;
 
+    /// Get a single multiproof for the target hashed accounts and hashed slots in the
+    /// `HashedPostState` on top of the current state.
+    ///
+    /// Trie nodes shared by the proofs of multiple targets are only included once.
+    fn multiproof(
+        &self,
+        hashed_state: HashedPostState,
+        targets: HashMap<B256, Vec<B256>>,
+    ) -> ProviderResult<MultiProof>;
+
     /// Get trie witness for provided state.
     fn witness(
         &self,
         overlay: HashedPostState,
         target: HashedPostState,
     ) -> ProviderResult<HashMap<B256, Bytes>>;
+
+    /// Get the verkle trie leaves of an account and the given storage slots in the current state,
+    /// with their proof.
+    ///
+    /// Returns `None` if the state isn't stored in a verkle trie.
+    fn verkle_proof(
+        &self,
+        _address: Address,
+        _slots: &[B256],
+    ) -> ProviderResult<Option<VerkleAccountProof>> {
+        Ok(None)
+    }
 }
@@ -14,6 +14,7 @@
 
 use crate::{
     metrics::{IncCounterOnDrop, TaskExecutorMetrics},
+    priority::PriorityRuntimes,
     shutdown::{signal, GracefulShutdown, GracefulShutdownGuard, Shutdown, Signal},
 };
 use dyn_clone::DynClone;
@@ -40,8 +41,11 @@ use tracing::{debug, error};
 use tracing_futures::Instrument;
 
 pub mod metrics;
+pub mod priority;
 pub mod shutdown;
 
+pub use priority::TaskPriority;
+
 #[cfg(feature = "rayon")]
 pub mod pool;
 
@@ -164,6 +168,8 @@ pub struct TaskManager {
     on_shutdown: Shutdown,
     /// How many [`GracefulShutdown`] tasks are currently active
     graceful_tasks: Arc<AtomicUsize>,
+    /// Runtimes of the [`TaskPriority`] classes that have their own.
+    runtimes: Arc<PriorityRuntimes>,
 }
 
 // === impl TaskManager ===
@@ -190,6 +196,7 @@ impl TaskManager {
             signal: Some(signal),
             on_shutdown,
             graceful_tasks: Arc::new(AtomicUsize::new(0)),
+            runtimes: Default::default(),
         }
     }
 
@@ -202,6 +209,7 @@ impl TaskManager {
             panicked_tasks_tx: self.panicked_tasks_tx.clone(),
             metrics: Default::default(),
             graceful_tasks: Arc::clone(&self.graceful_tasks),
+            runtimes: Arc::clone(&self.runtimes),
         }
     }
 
@@ -292,6 +300,8 @@ pub struct TaskExecutor {
     metrics: TaskExecutorMetrics,
     /// How many [`GracefulShutdown`] tasks are currently active
     graceful_tasks: Arc<AtomicUsize>,
+    /// Runtimes of the [`TaskPriority`] classes that have their own.
+    runtimes: Arc<PriorityRuntimes>,
 }
 
 // === impl TaskExecutor ===
@@ -307,6 +317,41 @@ impl TaskExecutor {
         &self.on_shutdown
     }
 
+    /// Starts a runtime with `worker_threads` threads of its own for the tasks of the given
+    /// priority class, see [`Self::with_priority`].
+    ///
+    /// Every thread of the runtime applies the settings of the class when it starts, see
+    /// [`TaskPriority::set_thread_hook`]. A runtime that was already started for the class is shut
+    /// down.
+    pub fn start_priority_runtime(
+        &self,
+        priority: TaskPriority,
+        worker_threads: usize,
+    ) -> std::io::Result<()> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(worker_threads)
+            .thread_name(format!("reth-{priority}"))
+            .on_thread_start(move || priority.apply_to_current_thread())
+            .enable_all()
+            .build()?;
+        if let Some(previous) = self.runtimes.insert(priority, runtime) {
+            previous.shutdown_background();
+        }
+        Ok(())
+    }
+
+    /// Returns an executor that spawns tasks onto the runtime of the given priority class.
+    ///
+    /// If no runtime was started for the class, the returned executor spawns onto the same runtime
+    /// as this one.
+    pub fn with_priority(&self, priority: TaskPriority) -> Self {
+        let mut executor = self.clone();
+        if let Some(handle) = self.runtimes.handle(priority) {
+            executor.handle = handle;
+        }
+        executor
+    }
+
     /// Spawns a future on the tokio runtime depending on the [`TaskKind`]
     fn spawn_on_rt<F>(&self, fut: F, task_kind: TaskKind) -> JoinHandle<()>
     where
@@ -673,6 +718,48 @@ mod tests {
     }
 
     // Tests that spawned tasks are terminated if the `TaskManager` drops
+    #[test]
+    fn test_priority_runtime() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let manager = TaskManager::new(runtime.handle().clone());
+        let executor = manager.executor();
+
+        let thread_name = || std::thread::current().name().map(String::from);
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        // without a runtime of its own, the class spawns onto the default runtime
+        let t = tx.clone();
+        executor.with_priority(TaskPriority::Rpc).spawn(async move {
+            t.send(thread_name()).unwrap();
+        });
+        assert_ne!(rx.recv().unwrap().as_deref(), Some("reth-rpc"));
+
+        let started = Arc::new(AtomicUsize::new(0));
+        let s = Arc::clone(&started);
+        TaskPriority::Rpc.set_thread_hook(move || {
+            s.fetch_add(1, Ordering::Relaxed);
+        });
+        executor.start_priority_runtime(TaskPriority::Rpc, 2).unwrap();
+        executor.with_priority(TaskPriority::Rpc).spawn(async move {
+            tx.send(thread_name()).unwrap();
+        });
+        assert_eq!(rx.recv().unwrap().as_deref(), Some("reth-rpc"));
+        assert!(started.load(Ordering::Relaxed) >= 1);
+
+        // threads outside of the runtime apply the hook of their class
+        let before = started.load(Ordering::Relaxed);
+        TaskPriority::Rpc.apply_to_current_thread();
+        TaskPriority::Background.apply_to_current_thread();
+        assert!(started.load(Ordering::Relaxed) > before);
+
+        // executors created afterwards share the runtime
+        let (tx, rx) = std::sync::mpsc::channel();
+        manager.executor().with_priority(TaskPriority::Rpc).spawn(async move {
+            tx.send(thread_name()).unwrap();
+        });
+        assert_eq!(rx.recv().unwrap().as_deref(), Some("reth-rpc"));
+    }
+
     #[test]
     fn test_manager_shutdown_critical() {
         let runtime = tokio::runtime::Runtime::new().unwrap();
@@ -1,10 +1,12 @@
 //! Additional helpers for executing tracing calls
 
+use crate::TaskPriority;
 use std::{
+    cmp::Ordering,
     future::Future,
     panic::{catch_unwind, AssertUnwindSafe},
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, Mutex, PoisonError},
     task::{ready, Context, Poll},
     thread,
 };
@@ -18,24 +20,71 @@ use tokio::sync::{oneshot, AcquireError, OwnedSemaphorePermit, Semaphore};
 ///
 /// This types serves as an entry guard for the [`BlockingTaskPool`] and is used to rate limit
 /// parallel blocking tasks in the pool.
+///
+/// Clones share the same semaphore, the maximum can be changed at runtime with
+/// [`BlockingTaskGuard::set_max_blocking_tasks`].
 #[derive(Clone, Debug)]
-pub struct BlockingTaskGuard(Arc<Semaphore>);
+pub struct BlockingTaskGuard {
+    semaphore: Arc<Semaphore>,
+    /// The current maximum number of blocking tasks in parallel.
+    max_blocking_tasks: Arc<Mutex<usize>>,
+}
 
 impl BlockingTaskGuard {
     /// Create a new `BlockingTaskGuard` with the given maximum number of blocking tasks in
     /// parallel.
     pub fn new(max_blocking_tasks: usize) -> Self {
-        Self(Arc::new(Semaphore::new(max_blocking_tasks)))
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_blocking_tasks)),
+            max_blocking_tasks: Arc::new(Mutex::new(max_blocking_tasks)),
+        }
+    }
+
+    /// Returns the maximum number of blocking tasks in parallel.
+    pub fn max_blocking_tasks(&self) -> usize {
+        *self.max_blocking_tasks.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Changes the maximum number of blocking tasks in parallel.
+    ///
+    /// Tasks that already acquired a permit are not affected, if the maximum is lowered while
+    /// more tasks are running, new tasks have to wait until enough running tasks finished.
+    ///
+    /// # Panics
+    ///
+    /// If the maximum is lowered while more tasks are running and this is called outside of the
+    /// tokio runtime.
+    pub fn set_max_blocking_tasks(&self, max_blocking_tasks: usize) {
+        let mut current = self.max_blocking_tasks.lock().unwrap_or_else(PoisonError::into_inner);
+        match max_blocking_tasks.cmp(&*current) {
+            Ordering::Greater => self.semaphore.add_permits(max_blocking_tasks - *current),
+            Ordering::Less => {
+                let excess = *current - max_blocking_tasks;
+                let held = excess - self.semaphore.forget_permits(excess);
+                if held > 0 {
+                    // the remaining permits are held by running tasks, drop them once released
+                    let semaphore = self.semaphore.clone();
+                    let held = u32::try_from(held).unwrap_or(u32::MAX);
+                    tokio::spawn(async move {
+                        if let Ok(permit) = semaphore.acquire_many_owned(held).await {
+                            permit.forget();
+                        }
+                    });
+                }
+            }
+            Ordering::Equal => {}
+        }
+        *current = max_blocking_tasks;
     }
 
     /// See also [`Semaphore::acquire_owned`]
     pub async fn acquire_owned(self) -> Result<OwnedSemaphorePermit, AcquireError> {
-        self.0.acquire_owned().await
+        self.semaphore.acquire_owned().await
     }
 
     /// See also [`Semaphore::acquire_many_owned`]
     pub async fn acquire_many_owned(self, n: u32) -> Result<OwnedSemaphorePermit, AcquireError> {
-        self.0.acquire_many_owned(n).await
+        self.semaphore.acquire_many_owned(n).await
     }
 }
 
@@ -75,6 +124,17 @@ impl BlockingTaskPool {
         Self::builder().build().map(Self::new)
     }
 
+    /// Builds a new threadpool with the default configuration whose threads apply the settings of
+    /// the given priority class when they start, see [`TaskPriority::apply_to_current_thread`].
+    pub fn build_with_priority(
+        priority: TaskPriority,
+    ) -> Result<Self, rayon::ThreadPoolBuildError> {
+        Self::builder()
+            .start_handler(move |_| priority.apply_to_current_thread())
+            .build()
+            .map(Self::new)
+    }
+
     /// Asynchronous wrapper around Rayon's
     /// [`ThreadPool::spawn`](rayon::ThreadPool::spawn).
     ///
@@ -171,4 +231,26 @@ mod tests {
         let res = res.await;
         assert!(res.is_err());
     }
+
+    #[tokio::test]
+    async fn blocking_task_guard_resize() {
+        let guard = BlockingTaskGuard::new(2);
+        let first = guard.clone().acquire_owned().await.unwrap();
+        let second = guard.clone().acquire_owned().await.unwrap();
+
+        guard.set_max_blocking_tasks(3);
+        assert_eq!(guard.max_blocking_tasks(), 3);
+        let third = guard.clone().acquire_owned().await.unwrap();
+
+        // lowering the maximum does not affect running tasks
+        guard.set_max_blocking_tasks(1);
+        assert_eq!(guard.max_blocking_tasks(), 1);
+        drop((first, second));
+        tokio::task::yield_now().await;
+        assert!(guard.semaphore.try_acquire().is_err());
+
+        drop(third);
+        let _permit = guard.clone().acquire_owned().await.unwrap();
+        assert!(guard.semaphore.try_acquire().is_err());
+    }
 }
@@ -0,0 +1,103 @@
+//! Priority classes of tasks and the runtimes they run on.
+
+use parking_lot::RwLock;
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    sync::Arc,
+};
+use tokio::runtime::{Handle, Runtime};
+
+/// A function that applies the settings of a priority class to the current thread.
+type ThreadHook = Arc<dyn Fn() + Send + Sync>;
+
+/// The thread hooks of the priority classes, see [`TaskPriority::set_thread_hook`].
+static THREAD_HOOKS: RwLock<Vec<(TaskPriority, ThreadHook)>> = RwLock::new(Vec::new());
+
+/// The priority class of a task.
+///
+/// Each class can get a runtime with its own worker threads, see
+/// [`TaskExecutor::start_priority_runtime`](crate::TaskExecutor::start_priority_runtime), so that
+/// e.g. heavy RPC tracing can't delay the processing of consensus messages.
+///
+/// Threads that are not part of a runtime, e.g. the engine's or those of a rayon pool, get the
+/// settings of their class, like CPU pinning, with [`TaskPriority::apply_to_current_thread`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskPriority {
+    /// Tasks on the consensus critical path, e.g. the engine processing `newPayload`.
+    Consensus,
+    /// Tasks serving RPC requests.
+    Rpc,
+    /// Background maintenance tasks, e.g. pruning and monitoring.
+    Background,
+}
+
+impl TaskPriority {
+    /// All priority classes.
+    pub const ALL: [Self; 3] = [Self::Consensus, Self::Rpc, Self::Background];
+
+    /// Returns the name of the priority class.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Consensus => "consensus",
+            Self::Rpc => "rpc",
+            Self::Background => "background",
+        }
+    }
+
+    /// Sets the function that applies the settings of this class to the current thread, e.g. pins
+    /// it to CPU cores. It's invoked by [`Self::apply_to_current_thread`], including on every
+    /// thread of the runtime of the class.
+    pub fn set_thread_hook(self, hook: impl Fn() + Send + Sync + 'static) {
+        let mut hooks = THREAD_HOOKS.write();
+        hooks.retain(|(priority, _)| *priority != self);
+        hooks.push((self, Arc::new(hook)));
+    }
+
+    /// Applies the settings of this class to the current thread, if a hook was set with
+    /// [`Self::set_thread_hook`].
+    ///
+    /// This should be called when a thread that runs work of this class starts.
+    pub fn apply_to_current_thread(self) {
+        let hook = THREAD_HOOKS
+            .read()
+            .iter()
+            .find(|(priority, _)| *priority == self)
+            .map(|(_, hook)| Arc::clone(hook));
+        if let Some(hook) = hook {
+            hook()
+        }
+    }
+}
+
+impl Display for TaskPriority {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The runtimes started for priority classes, shared by all executors of a
+/// [`TaskManager`](crate::TaskManager).
+#[derive(Debug, Default)]
+pub(crate) struct PriorityRuntimes(RwLock<HashMap<TaskPriority, Runtime>>);
+
+impl PriorityRuntimes {
+    /// Returns the handle to the runtime of the priority class, if it was started.
+    pub(crate) fn handle(&self, priority: TaskPriority) -> Option<Handle> {
+        self.0.read().get(&priority).map(|rt| rt.handle().clone())
+    }
+
+    /// Stores the runtime of the priority class, returning the runtime it replaces.
+    pub(crate) fn insert(&self, priority: TaskPriority, runtime: Runtime) -> Option<Runtime> {
+        self.0.write().insert(priority, runtime)
+    }
+}
+
+impl Drop for PriorityRuntimes {
+    fn drop(&mut self) {
+        for (_, runtime) in self.0.get_mut().drain() {
+            // dropping a runtime blocks, which panics if this happens within an async context
+            runtime.shutdown_background();
+        }
+    }
+}
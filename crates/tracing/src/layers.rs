@@ -2,9 +2,9 @@ use std::path::{Path, PathBuf};
 
 use rolling_file::{RollingConditionBasic, RollingFileAppender};
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{filter::Directive, EnvFilter, Layer, Registry};
+use tracing_subscriber::{filter::Directive, reload, EnvFilter, Layer, Registry};
 
-use crate::formatter::LogFormat;
+use crate::{formatter::LogFormat, log_filter::LayerFilter};
 
 /// A worker guard returned by the file layer.
 ///
@@ -33,17 +33,32 @@ const DEFAULT_ENV_FILTER_DIRECTIVES: [&str; 5] = [
 /// Each layer can be configured separately and then combined into a tracing subscriber.
 pub(crate) struct Layers {
     inner: Vec<BoxedLayer<Registry>>,
+    filters: Vec<LayerFilter>,
 }
 
 impl Layers {
     /// Creates a new `Layers` instance.
     pub(crate) fn new() -> Self {
-        Self { inner: vec![] }
+        Self { inner: vec![], filters: vec![] }
     }
 
-    /// Consumes the `Layers` instance, returning the inner vector of layers.
-    pub(crate) fn into_inner(self) -> Vec<BoxedLayer<Registry>> {
-        self.inner
+    /// Consumes the `Layers` instance, returning the inner vector of layers and the filters of
+    /// the layers.
+    pub(crate) fn into_inner(self) -> (Vec<BoxedLayer<Registry>>, Vec<LayerFilter>) {
+        (self.inner, self.filters)
+    }
+
+    /// Wraps the filter of a layer so it can be changed at runtime, see
+    /// [`LogFilterHandle`](crate::LogFilterHandle).
+    fn reloadable_filter(
+        &mut self,
+        default_directive: Option<Directive>,
+        filters: &str,
+    ) -> eyre::Result<reload::Layer<EnvFilter, Registry>> {
+        let (filter, handle) =
+            reload::Layer::new(build_env_filter(default_directive.clone(), filters)?);
+        self.filters.push(LayerFilter { default_directive, filters: filters.to_string(), handle });
+        Ok(filter)
     }
 
     /// Adds a journald layer to the layers collection.
@@ -54,7 +69,7 @@ impl Layers {
     /// # Returns
     /// An `eyre::Result<()>` indicating the success or failure of the operation.
     pub(crate) fn journald(&mut self, filter: &str) -> eyre::Result<()> {
-        let journald_filter = build_env_filter(None, filter)?;
+        let journald_filter = self.reloadable_filter(None, filter)?;
         let layer = tracing_journald::layer()?.with_filter(journald_filter).boxed();
         self.inner.push(layer);
         Ok(())
@@ -80,12 +95,21 @@ impl Layers {
         filters: &str,
         color: Option<String>,
     ) -> eyre::Result<()> {
-        let filter = build_env_filter(Some(default_directive), filters)?;
+        let filter = self.reloadable_filter(Some(default_directive), filters)?;
         let layer = format.apply(filter, color, None);
         self.inner.push(layer.boxed());
         Ok(())
     }
 
+    /// Adds a layer that exports spans via OTLP, which is disabled until an exporter is installed
+    /// with [`install_otlp_exporter`](crate::install_otlp_exporter).
+    #[cfg(feature = "otlp")]
+    pub(crate) fn otlp(&mut self) -> crate::otlp::ExportLayer {
+        let (layer, export) = crate::otlp::ExportLayer::new();
+        self.inner.push(layer);
+        export
+    }
+
     /// Adds a file logging layer to the layers collection.
     ///
     /// # Arguments
@@ -102,7 +126,7 @@ impl Layers {
         file_info: FileInfo,
     ) -> eyre::Result<FileWorkerGuard> {
         let (writer, guard) = file_info.create_log_writer();
-        let file_filter = build_env_filter(None, filter)?;
+        let file_filter = self.reloadable_filter(None, filter)?;
         let layer = format.apply(file_filter, None, Some(writer));
         self.inner.push(layer);
         Ok(guard)
@@ -166,7 +190,7 @@ impl FileInfo {
 ///
 /// # Returns
 /// An `eyre::Result<EnvFilter>` that can be used to configure a tracing subscriber.
-fn build_env_filter(
+pub(crate) fn build_env_filter(
     default_directive: Option<Directive>,
     directives: &str,
 ) -> eyre::Result<EnvFilter> {
@@ -51,10 +51,16 @@ pub use tracing_subscriber;
 // Re-export our types
 pub use formatter::LogFormat;
 pub use layers::{FileInfo, FileWorkerGuard};
+pub use log_filter::{LogFilterHandle, TargetLevel};
+#[cfg(feature = "otlp")]
+pub use otlp::{install_otlp_exporter, shutdown_otlp_exporter, OtlpConfig, DEFAULT_OTLP_FILTER};
 pub use test_tracer::TestTracer;
 
 mod formatter;
 mod layers;
+mod log_filter;
+#[cfg(feature = "otlp")]
+mod otlp;
 mod test_tracer;
 
 use crate::layers::Layers;
@@ -212,7 +218,15 @@ impl Tracer for RethTracer {
 
         // The error is returned if the global default subscriber is already set,
         // so it's safe to ignore it
-        let _ = tracing_subscriber::registry().with(layers.into_inner()).try_init();
+        #[cfg(feature = "otlp")]
+        let export = layers.otlp();
+
+        let (layers, filters) = layers.into_inner();
+        if tracing_subscriber::registry().with(layers).try_init().is_ok() {
+            LogFilterHandle::new(filters).register_global();
+            #[cfg(feature = "otlp")]
+            export.register_global();
+        }
         Ok(file_guard)
     }
 }
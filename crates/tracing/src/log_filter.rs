@@ -0,0 +1,256 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, OnceLock, PoisonError},
+    time::{Duration, Instant},
+};
+
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::{filter::Directive, reload, EnvFilter, Registry};
+
+use crate::layers::build_env_filter;
+
+/// The handle registered by [`RethTracer::init`](crate::RethTracer).
+static GLOBAL_LOG_FILTERS: OnceLock<LogFilterHandle> = OnceLock::new();
+
+/// Handle to the reloadable [`EnvFilter`] of a logging layer.
+pub(crate) type FilterReloadHandle = reload::Handle<EnvFilter, Registry>;
+
+/// The filter configuration of a logging layer, see [`crate::LayerInfo`].
+#[derive(Debug)]
+pub(crate) struct LayerFilter {
+    /// Directive for the default logging level, if any.
+    pub(crate) default_directive: Option<Directive>,
+    /// Additional filter directives as a comma-separated string.
+    pub(crate) filters: String,
+    /// Handle to replace the filter of the layer.
+    pub(crate) handle: FilterReloadHandle,
+}
+
+/// A log level override of a target, see [`LogFilterHandle::set_target_level`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetLevel {
+    /// The target the override applies to, e.g. `reth::network`.
+    pub target: String,
+    /// The maximum level of events of the target that are logged.
+    pub level: LevelFilter,
+    /// When the override is removed again, if it is temporary.
+    pub expires_at: Option<Instant>,
+}
+
+impl TargetLevel {
+    /// Returns the time until the override is removed, if it is temporary.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.expires_at.map(|expires_at| expires_at.saturating_duration_since(Instant::now()))
+    }
+}
+
+/// Handle to change the log levels of individual targets while the node is running.
+///
+/// Overrides apply to all layers of the installed subscriber: stdout, file and journald. They are
+/// added on top of the configured filters, and removing an override restores the configured
+/// filters of the target.
+///
+/// Clones share the same state.
+#[derive(Debug, Clone)]
+pub struct LogFilterHandle {
+    inner: Arc<Mutex<LogFilters>>,
+}
+
+#[derive(Debug)]
+struct LogFilters {
+    layers: Vec<LayerFilter>,
+    overrides: BTreeMap<String, Override>,
+    next_id: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Override {
+    id: u64,
+    level: LevelFilter,
+    expires_at: Option<Instant>,
+}
+
+impl LogFilterHandle {
+    /// Creates a new handle for the given layers.
+    pub(crate) fn new(layers: Vec<LayerFilter>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(LogFilters {
+                layers,
+                overrides: BTreeMap::new(),
+                next_id: 0,
+            })),
+        }
+    }
+
+    /// Returns the handle of the subscriber installed by [`RethTracer`](crate::RethTracer), if
+    /// any.
+    pub fn global() -> Option<Self> {
+        GLOBAL_LOG_FILTERS.get().cloned()
+    }
+
+    /// Registers the handle of the installed subscriber, see [`Self::global`].
+    pub(crate) fn register_global(self) {
+        let _ = GLOBAL_LOG_FILTERS.set(self);
+    }
+
+    /// Sets the maximum level of events of the target that are logged, e.g. `debug` for
+    /// `reth::network`.
+    ///
+    /// If a duration is given, the override is removed again once it elapsed. Replaces any
+    /// previous override of the target.
+    pub fn set_target_level(
+        &self,
+        target: &str,
+        level: LevelFilter,
+        duration: Option<Duration>,
+    ) -> eyre::Result<()> {
+        // reject targets that can't be parsed as a directive before storing them
+        target_directive(target, level)?;
+
+        let mut filters = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        let id = filters.next_id;
+        filters.next_id += 1;
+        let expires_at = duration.map(|duration| Instant::now() + duration);
+        filters.overrides.insert(target.to_string(), Override { id, level, expires_at });
+        filters.reload()?;
+        drop(filters);
+
+        if let Some(duration) = duration {
+            let this = self.clone();
+            let target = target.to_string();
+            std::thread::Builder::new().name("log-filter-expiry".to_string()).spawn(move || {
+                std::thread::sleep(duration);
+                let mut filters = this.inner.lock().unwrap_or_else(PoisonError::into_inner);
+                // the override may have been replaced in the meantime
+                if filters.overrides.get(&target).is_some_and(|o| o.id == id) {
+                    filters.overrides.remove(&target);
+                    let _ = filters.reload();
+                }
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the override of the target.
+    ///
+    /// Returns `true` if the target had an override.
+    pub fn reset_target(&self, target: &str) -> eyre::Result<bool> {
+        let mut filters = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        if filters.overrides.remove(target).is_none() {
+            return Ok(false)
+        }
+        filters.reload()?;
+        Ok(true)
+    }
+
+    /// Removes the overrides of all targets.
+    pub fn reset(&self) -> eyre::Result<()> {
+        let mut filters = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        filters.overrides.clear();
+        filters.reload()
+    }
+
+    /// Returns the overrides of all targets, sorted by target.
+    pub fn target_levels(&self) -> Vec<TargetLevel> {
+        self.inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .overrides
+            .iter()
+            .map(|(target, o)| TargetLevel {
+                target: target.clone(),
+                level: o.level,
+                expires_at: o.expires_at,
+            })
+            .collect()
+    }
+}
+
+impl LogFilters {
+    /// Rebuilds the filter of every layer from its configured filters and the overrides.
+    fn reload(&self) -> eyre::Result<()> {
+        for layer in &self.layers {
+            let filter = self.overrides.iter().try_fold(
+                build_env_filter(layer.default_directive.clone(), &layer.filters)?,
+                |filter, (target, o)| {
+                    Ok::<_, eyre::Report>(filter.add_directive(target_directive(target, o.level)?))
+                },
+            )?;
+            layer.handle.reload(filter)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the directive that sets the level of the target.
+fn target_directive(target: &str, level: LevelFilter) -> eyre::Result<Directive> {
+    if target.is_empty() || target.contains([',', '=', '[', ']', '{', '}']) {
+        eyre::bail!("invalid log target `{target}`")
+    }
+    Ok(format!("{target}={level}").parse()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::Filter;
+
+    fn handle() -> (LogFilterHandle, reload::Layer<EnvFilter, Registry>) {
+        let (filter, handle) = reload::Layer::new(EnvFilter::new("info"));
+        let layer = LayerFilter {
+            default_directive: Some(LevelFilter::INFO.into()),
+            filters: String::new(),
+            handle,
+        };
+        (LogFilterHandle::new(vec![layer]), filter)
+    }
+
+    #[test]
+    fn set_and_reset_target_level() {
+        let (handle, filter) = handle();
+        assert_eq!(Filter::<Registry>::max_level_hint(&filter), Some(LevelFilter::INFO));
+
+        handle.set_target_level("reth::network", LevelFilter::DEBUG, None).unwrap();
+        assert_eq!(Filter::<Registry>::max_level_hint(&filter), Some(LevelFilter::DEBUG));
+        assert_eq!(
+            handle.target_levels(),
+            vec![TargetLevel {
+                target: "reth::network".to_string(),
+                level: LevelFilter::DEBUG,
+                expires_at: None,
+            }]
+        );
+
+        assert!(handle.reset_target("reth::network").unwrap());
+        assert!(!handle.reset_target("reth::network").unwrap());
+        assert_eq!(Filter::<Registry>::max_level_hint(&filter), Some(LevelFilter::INFO));
+        assert!(handle.target_levels().is_empty());
+    }
+
+    #[test]
+    fn temporary_target_level_expires() {
+        let (handle, filter) = handle();
+
+        handle
+            .set_target_level("reth::sync", LevelFilter::TRACE, Some(Duration::from_millis(50)))
+            .unwrap();
+        assert_eq!(Filter::<Registry>::max_level_hint(&filter), Some(LevelFilter::TRACE));
+        assert!(handle.target_levels()[0].remaining().is_some());
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !handle.target_levels().is_empty() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(handle.target_levels().is_empty());
+        assert_eq!(Filter::<Registry>::max_level_hint(&filter), Some(LevelFilter::INFO));
+    }
+
+    #[test]
+    fn rejects_invalid_target() {
+        let (handle, _filter) = handle();
+        assert!(handle.set_target_level("reth=debug", LevelFilter::DEBUG, None).is_err());
+        assert!(handle.set_target_level("", LevelFilter::DEBUG, None).is_err());
+        assert!(handle.target_levels().is_empty());
+    }
+}
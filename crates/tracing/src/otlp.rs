@@ -0,0 +1,140 @@
+//! Export of spans to an OpenTelemetry collector via OTLP.
+
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+use opentelemetry::{trace::TracerProvider as _, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    runtime,
+    trace::{Config, Sampler, TracerProvider},
+    Resource,
+};
+use tracing_subscriber::{reload, EnvFilter, Layer, Registry};
+
+use crate::layers::BoxedLayer;
+
+/// The export layer registered by [`RethTracer::init`](crate::RethTracer).
+static GLOBAL_EXPORT_LAYER: OnceLock<ExportLayer> = OnceLock::new();
+
+/// Default filter of exported spans: RPC requests, engine API calls, block processing and stage
+/// runs.
+pub const DEFAULT_OTLP_FILTER: &str = "rpc=debug,engine=trace,sync::pipeline=debug";
+
+/// Configuration of the OTLP span exporter, see [`install_otlp_exporter`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtlpConfig {
+    /// The gRPC endpoint of the collector, e.g. `http://localhost:4317`.
+    pub endpoint: String,
+    /// The ratio of traces that are sampled, between `0.0` and `1.0`.
+    pub sample_ratio: f64,
+    /// The `service.name` resource attribute of exported spans.
+    pub service_name: String,
+    /// Filter directives of the exported spans, see [`DEFAULT_OTLP_FILTER`].
+    pub filter: String,
+}
+
+impl OtlpConfig {
+    /// Creates a new config that samples all traces of the default filter.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            sample_ratio: 1.0,
+            service_name: "reth".to_string(),
+            filter: DEFAULT_OTLP_FILTER.to_string(),
+        }
+    }
+}
+
+/// The disabled layer of the subscriber that exports spans once an exporter is installed.
+pub(crate) struct ExportLayer {
+    layer: reload::Handle<Option<BoxedLayer<Registry>>, Registry>,
+    filter: reload::Handle<EnvFilter, Registry>,
+    provider: Mutex<Option<TracerProvider>>,
+}
+
+impl ExportLayer {
+    /// Returns the layer to add to the subscriber and the handle to install an exporter.
+    pub(crate) fn new() -> (BoxedLayer<Registry>, Self) {
+        let (layer, layer_handle) = reload::Layer::new(None);
+        let (filter, filter_handle) = reload::Layer::new(EnvFilter::new("off"));
+        let this = Self { layer: layer_handle, filter: filter_handle, provider: Mutex::new(None) };
+        (layer.with_filter(filter).boxed(), this)
+    }
+
+    /// Registers the export layer of the installed subscriber.
+    pub(crate) fn register_global(self) {
+        let _ = GLOBAL_EXPORT_LAYER.set(self);
+    }
+}
+
+impl std::fmt::Debug for ExportLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExportLayer").field("provider", &self.provider).finish_non_exhaustive()
+    }
+}
+
+/// Starts exporting spans to the configured OTLP collector.
+///
+/// Spans are exported in batches on the tokio runtime, so this must be called from within a
+/// runtime. Replaces the exporter of a previous call.
+///
+/// Returns an error if the subscriber was not installed by [`RethTracer`](crate::RethTracer).
+pub fn install_otlp_exporter(config: &OtlpConfig) -> eyre::Result<()> {
+    let Some(export) = GLOBAL_EXPORT_LAYER.get() else {
+        eyre::bail!("the tracing subscriber does not support span export")
+    };
+    if !(0.0..=1.0).contains(&config.sample_ratio) {
+        eyre::bail!(
+            "invalid sample ratio {}, expected a value between 0 and 1",
+            config.sample_ratio
+        )
+    }
+    let filter = EnvFilter::builder().parse(&config.filter)?;
+
+    let provider = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(&config.endpoint))
+        .with_trace_config(
+            Config::default()
+                .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+                    config.sample_ratio,
+                ))))
+                .with_resource(Resource::new([KeyValue::new(
+                    "service.name",
+                    config.service_name.clone(),
+                )])),
+        )
+        .install_batch(runtime::Tokio)?;
+    let layer = tracing_opentelemetry::layer().with_tracer(provider.tracer("reth"));
+
+    export.layer.reload(Some(layer.boxed()))?;
+    export.filter.reload(filter)?;
+
+    let previous = export.provider.lock().unwrap_or_else(PoisonError::into_inner).replace(provider);
+    if let Some(previous) = previous {
+        let _ = previous.shutdown();
+    }
+    Ok(())
+}
+
+/// Exports all pending spans and stops the exporter installed by [`install_otlp_exporter`], if
+/// any.
+pub fn shutdown_otlp_exporter() {
+    let Some(export) = GLOBAL_EXPORT_LAYER.get() else { return };
+    let _ = export.filter.reload(EnvFilter::new("off"));
+    let _ = export.layer.reload(None);
+    if let Some(provider) = export.provider.lock().unwrap_or_else(PoisonError::into_inner).take() {
+        let _ = provider.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn install_requires_reth_tracer() {
+        let err = install_otlp_exporter(&OtlpConfig::new("http://localhost:4317")).unwrap_err();
+        assert!(err.to_string().contains("does not support span export"));
+    }
+}
@@ -3,6 +3,7 @@
 pub use disk::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore};
 pub use mem::InMemoryBlobStore;
 pub use noop::NoopBlobStore;
+pub use overflow::{BlobOverflowConfig, BlobOverflowEviction, BlobOverflowStore};
 use reth_primitives::{BlobTransactionSidecar, B256};
 use std::{
     fmt,
@@ -13,6 +14,7 @@ pub use tracker::{BlobStoreCanonTracker, BlobStoreUpdates};
 pub mod disk;
 mod mem;
 mod noop;
+pub mod overflow;
 mod tracker;
 
 /// A blob store that can be used to store blob data of EIP4844 transactions.
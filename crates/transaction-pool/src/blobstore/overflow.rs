@@ -0,0 +1,403 @@
+//! A disk backed store for blob transactions that don't fit into the blob pool.
+
+use crate::blobstore::{disk::DiskFileBlobStoreError, BlobStoreError};
+use parking_lot::Mutex;
+use reth_primitives::{
+    Address, PooledTransactionsElement, PooledTransactionsElementEcRecovered, TxHash,
+};
+use std::{collections::HashMap, fmt, fs, io, path::PathBuf, str::FromStr, sync::Arc};
+use tracing::{debug, trace};
+
+/// The default maximum number of transactions kept in the [`BlobOverflowStore`].
+pub const DEFAULT_BLOB_OVERFLOW_MAX_TXS: usize = 2_000;
+
+/// The default maximum size of the [`BlobOverflowStore`] in megabytes.
+pub const DEFAULT_BLOB_OVERFLOW_MAX_SIZE_MB: usize = 1024;
+
+/// Decides which transaction is evicted if the [`BlobOverflowStore`] is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlobOverflowEviction {
+    /// Evict the transaction with the lowest max fee per blob gas.
+    #[default]
+    LowestFee,
+    /// Evict the transaction that was stored first.
+    Oldest,
+}
+
+impl fmt::Display for BlobOverflowEviction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LowestFee => f.write_str("lowest-fee"),
+            Self::Oldest => f.write_str("oldest"),
+        }
+    }
+}
+
+impl FromStr for BlobOverflowEviction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lowest-fee" => Ok(Self::LowestFee),
+            "oldest" => Ok(Self::Oldest),
+            _ => Err(format!("invalid eviction policy `{s}`, expected `lowest-fee` or `oldest`")),
+        }
+    }
+}
+
+/// Configuration for a [`BlobOverflowStore`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobOverflowConfig {
+    /// Maximum number of transactions in the store.
+    pub max_txs: usize,
+    /// Maximum combined size (in bytes) of the encoded transactions in the store.
+    pub max_size: usize,
+    /// Which transactions are evicted once a limit is exceeded.
+    pub eviction: BlobOverflowEviction,
+}
+
+impl Default for BlobOverflowConfig {
+    fn default() -> Self {
+        Self {
+            max_txs: DEFAULT_BLOB_OVERFLOW_MAX_TXS,
+            max_size: DEFAULT_BLOB_OVERFLOW_MAX_SIZE_MB * 1024 * 1024,
+            eviction: Default::default(),
+        }
+    }
+}
+
+impl BlobOverflowConfig {
+    /// Sets the policy that decides which transactions are evicted.
+    pub const fn with_eviction(mut self, eviction: BlobOverflowEviction) -> Self {
+        self.eviction = eviction;
+        self
+    }
+}
+
+/// A disk backed store for blob transactions that were discarded because the blob pool was full.
+///
+/// Each transaction is written to its own file, including its sidecar, and an in memory index
+/// tracks fees and sizes, so that the best transactions can be re-admitted into the pool once
+/// there's room again.
+#[derive(Clone, Debug)]
+pub struct BlobOverflowStore {
+    inner: Arc<BlobOverflowStoreInner>,
+}
+
+impl BlobOverflowStore {
+    /// Opens a new, empty store in the given directory.
+    ///
+    /// Any transactions left in the directory are removed.
+    pub fn open(
+        dir: impl Into<PathBuf>,
+        config: BlobOverflowConfig,
+    ) -> Result<Self, DiskFileBlobStoreError> {
+        let dir = dir.into();
+        match fs::remove_dir_all(&dir) {
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(DiskFileBlobStoreError::Open(dir, err)),
+        }
+        debug!(target:"txpool::blob", ?dir, "Creating blob overflow store");
+        fs::create_dir_all(&dir).map_err(|err| DiskFileBlobStoreError::Open(dir.clone(), err))?;
+
+        Ok(Self {
+            inner: Arc::new(BlobOverflowStoreInner { dir, config, index: Default::default() }),
+        })
+    }
+
+    /// Returns the configuration of the store.
+    pub fn config(&self) -> &BlobOverflowConfig {
+        &self.inner.config
+    }
+
+    /// Writes the blob transaction to disk.
+    ///
+    /// If this exceeds the limits of the store, transactions are evicted according to the
+    /// configured [`BlobOverflowEviction`], this can include the given transaction. Returns the
+    /// hashes of the evicted transactions.
+    pub fn insert(
+        &self,
+        tx: PooledTransactionsElementEcRecovered,
+    ) -> Result<Vec<TxHash>, BlobStoreError> {
+        let hash = *tx.hash();
+        if self.contains(&hash) {
+            return Ok(Vec::new())
+        }
+
+        let entry = OverflowEntry {
+            signer: tx.signer(),
+            max_fee_per_blob_gas: tx.max_fee_per_blob_gas().unwrap_or_default(),
+            size: 0,
+            id: 0,
+        };
+        let mut buf = Vec::new();
+        tx.encode_enveloped(&mut buf);
+
+        trace!(target:"txpool::blob", ?hash, "Writing blob transaction to overflow store");
+        let path = self.inner.tx_file(hash);
+        fs::write(&path, &buf).map_err(|err| DiskFileBlobStoreError::WriteFile(hash, path, err))?;
+
+        let mut index = self.inner.index.lock();
+        index.insert(hash, OverflowEntry { size: buf.len(), ..entry });
+
+        let mut evicted = Vec::new();
+        while index.entries.len() > self.inner.config.max_txs ||
+            index.size > self.inner.config.max_size
+        {
+            let Some(hash) = index.worst(self.inner.config.eviction) else { break };
+            index.remove(&hash);
+            self.inner.delete_file(hash);
+            evicted.push(hash);
+        }
+
+        Ok(evicted)
+    }
+
+    /// Removes the best transactions from the store and returns them, as long as they fit into
+    /// the given limits.
+    ///
+    /// Transactions are selected by their max fee per blob gas, the returned transactions are
+    /// ordered by sender and nonce.
+    pub fn take_best(
+        &self,
+        max_txs: usize,
+        max_size: usize,
+    ) -> Vec<PooledTransactionsElementEcRecovered> {
+        let selected = {
+            let mut index = self.inner.index.lock();
+            let mut candidates =
+                index.entries.iter().map(|(hash, entry)| (*hash, *entry)).collect::<Vec<_>>();
+            candidates.sort_unstable_by(|(_, a), (_, b)| {
+                b.max_fee_per_blob_gas.cmp(&a.max_fee_per_blob_gas).then(a.id.cmp(&b.id))
+            });
+
+            let mut selected = Vec::new();
+            let mut size = 0;
+            for (hash, entry) in candidates {
+                if selected.len() >= max_txs {
+                    break
+                }
+                if size + entry.size > max_size {
+                    continue
+                }
+                size += entry.size;
+                index.remove(&hash);
+                selected.push((hash, entry));
+            }
+            selected
+        };
+
+        let mut txs = Vec::with_capacity(selected.len());
+        for (hash, entry) in selected {
+            let path = self.inner.tx_file(hash);
+            match fs::read(&path) {
+                Ok(data) => match PooledTransactionsElement::decode_enveloped(&mut data.as_slice())
+                {
+                    Ok(tx) => {
+                        txs.push(PooledTransactionsElementEcRecovered::from_signed_transaction(
+                            tx,
+                            entry.signer,
+                        ))
+                    }
+                    Err(err) => {
+                        debug!(target:"txpool::blob", %err, ?hash, "Failed to decode overflowed blob transaction")
+                    }
+                },
+                Err(err) => {
+                    debug!(target:"txpool::blob", %err, ?path, "Failed to read overflowed blob transaction")
+                }
+            }
+            self.inner.delete_file(hash);
+        }
+
+        txs.sort_unstable_by_key(|tx| (tx.signer(), tx.nonce()));
+        txs
+    }
+
+    /// Returns true if the transaction is in the store.
+    pub fn contains(&self, tx: &TxHash) -> bool {
+        self.inner.index.lock().entries.contains_key(tx)
+    }
+
+    /// Returns the number of transactions in the store.
+    pub fn len(&self) -> usize {
+        self.inner.index.lock().entries.len()
+    }
+
+    /// Returns true if the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the combined size of the encoded transactions in the store.
+    pub fn data_size(&self) -> usize {
+        self.inner.index.lock().size
+    }
+}
+
+struct BlobOverflowStoreInner {
+    dir: PathBuf,
+    config: BlobOverflowConfig,
+    index: Mutex<OverflowIndex>,
+}
+
+impl BlobOverflowStoreInner {
+    /// Returns the path to the file of the given transaction.
+    #[inline]
+    fn tx_file(&self, tx: TxHash) -> PathBuf {
+        self.dir.join(format!("{tx:x}"))
+    }
+
+    /// Deletes the file of the given transaction.
+    fn delete_file(&self, tx: TxHash) {
+        let path = self.tx_file(tx);
+        if let Err(err) = fs::remove_file(&path) {
+            let err = DiskFileBlobStoreError::DeleteFile(tx, path, err);
+            debug!(target:"txpool::blob", %err);
+        }
+    }
+}
+
+impl fmt::Debug for BlobOverflowStoreInner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlobOverflowStoreInner")
+            .field("dir", &self.dir)
+            .field("config", &self.config)
+            .field("entries", &self.index.try_lock().map(|index| index.entries.len()))
+            .finish()
+    }
+}
+
+/// Tracks the transactions in the [`BlobOverflowStore`].
+#[derive(Debug, Default)]
+struct OverflowIndex {
+    entries: HashMap<TxHash, OverflowEntry>,
+    /// Combined size of all entries.
+    size: usize,
+    /// The id assigned to the next entry, increases with every insert.
+    next_id: u64,
+}
+
+impl OverflowIndex {
+    fn insert(&mut self, hash: TxHash, mut entry: OverflowEntry) {
+        entry.id = self.next_id;
+        self.next_id += 1;
+        self.size += entry.size;
+        self.entries.insert(hash, entry);
+    }
+
+    fn remove(&mut self, hash: &TxHash) {
+        if let Some(entry) = self.entries.remove(hash) {
+            self.size -= entry.size;
+        }
+    }
+
+    /// Returns the transaction that is evicted first with the given policy.
+    fn worst(&self, eviction: BlobOverflowEviction) -> Option<TxHash> {
+        let entries = self.entries.iter();
+        match eviction {
+            BlobOverflowEviction::LowestFee => entries
+                .min_by(|(_, a), (_, b)| {
+                    a.max_fee_per_blob_gas.cmp(&b.max_fee_per_blob_gas).then(a.id.cmp(&b.id))
+                })
+                .map(|(hash, _)| *hash),
+            BlobOverflowEviction::Oldest => {
+                entries.min_by_key(|(_, entry)| entry.id).map(|(hash, _)| *hash)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct OverflowEntry {
+    signer: Address,
+    max_fee_per_blob_gas: u128,
+    /// Size of the encoded transaction.
+    size: usize,
+    id: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        BlobTransaction, BlobTransactionSidecar, Signature, Transaction, TransactionSigned,
+        TxEip4844,
+    };
+
+    fn tmp_store(config: BlobOverflowConfig) -> (BlobOverflowStore, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = BlobOverflowStore::open(dir.path().join("overflow"), config).unwrap();
+        (store, dir)
+    }
+
+    fn blob_tx(nonce: u64, max_fee_per_blob_gas: u128) -> PooledTransactionsElementEcRecovered {
+        let tx = TransactionSigned::from_transaction_and_signature(
+            Transaction::Eip4844(TxEip4844 { nonce, max_fee_per_blob_gas, ..Default::default() }),
+            Signature::default(),
+        );
+        let sidecar = BlobTransactionSidecar { blobs: vec![], commitments: vec![], proofs: vec![] };
+        let tx = BlobTransaction::try_from_signed(tx, sidecar).unwrap();
+        PooledTransactionsElementEcRecovered::from_signed_transaction(
+            PooledTransactionsElement::BlobTransaction(tx),
+            Address::with_last_byte(1),
+        )
+    }
+
+    #[test]
+    fn insert_take_best() {
+        let (store, _dir) = tmp_store(Default::default());
+        let txs = [blob_tx(0, 10), blob_tx(1, 30), blob_tx(2, 20)];
+        for tx in &txs {
+            assert!(store.insert(tx.clone()).unwrap().is_empty());
+        }
+        assert_eq!(store.len(), 3);
+
+        // only room for the two best transactions, returned in nonce order
+        let taken = store.take_best(2, usize::MAX);
+        assert_eq!(taken, vec![txs[1].clone(), txs[2].clone()]);
+        assert_eq!(store.len(), 1);
+        assert!(store.contains(txs[0].hash()));
+        assert_eq!(store.data_size(), txs[0].envelope_encoded().len());
+
+        assert_eq!(store.take_best(10, 0), vec![]);
+        assert_eq!(store.take_best(10, usize::MAX), vec![txs[0].clone()]);
+        assert!(store.is_empty());
+        assert_eq!(store.data_size(), 0);
+    }
+
+    #[test]
+    fn evict_lowest_fee() {
+        let (store, _dir) = tmp_store(BlobOverflowConfig { max_txs: 2, ..Default::default() });
+        let txs = [blob_tx(0, 20), blob_tx(1, 10), blob_tx(2, 30)];
+        assert!(store.insert(txs[0].clone()).unwrap().is_empty());
+        assert!(store.insert(txs[1].clone()).unwrap().is_empty());
+        assert_eq!(store.insert(txs[2].clone()).unwrap(), vec![*txs[1].hash()]);
+
+        // a transaction that is worse than all stored ones is evicted right away
+        let worst = blob_tx(3, 1);
+        assert_eq!(store.insert(worst.clone()).unwrap(), vec![*worst.hash()]);
+        assert_eq!(store.len(), 2);
+    }
+
+    #[test]
+    fn evict_oldest() {
+        let config = BlobOverflowConfig { max_txs: 2, ..Default::default() }
+            .with_eviction(BlobOverflowEviction::Oldest);
+        let (store, _dir) = tmp_store(config);
+        let txs = [blob_tx(0, 20), blob_tx(1, 10), blob_tx(2, 1)];
+        assert!(store.insert(txs[0].clone()).unwrap().is_empty());
+        assert!(store.insert(txs[1].clone()).unwrap().is_empty());
+        assert_eq!(store.insert(txs[2].clone()).unwrap(), vec![*txs[0].hash()]);
+        assert!(store.contains(txs[2].hash()));
+    }
+
+    #[test]
+    fn parse_eviction() {
+        for eviction in [BlobOverflowEviction::LowestFee, BlobOverflowEviction::Oldest] {
+            assert_eq!(eviction.to_string().parse::<BlobOverflowEviction>(), Ok(eviction));
+        }
+        assert!("newest".parse::<BlobOverflowEviction>().is_err());
+    }
+}
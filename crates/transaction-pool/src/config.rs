@@ -1,12 +1,20 @@
 use crate::{
+    blobstore::BlobOverflowStore,
+    policy::{ReplacementPolicy, TransactionPolicy},
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     PoolSize, TransactionOrigin,
 };
-use reth_primitives::{Address, EIP4844_TX_TYPE_ID};
-use std::collections::HashSet;
+use reth_primitives::{
+    Address, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, LEGACY_TX_TYPE_ID,
+};
+use std::{collections::HashSet, fmt, str::FromStr, sync::Arc};
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub const TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
 
+/// The default number of transactions a sender can have in the pool before they are evicted first
+/// by [`EvictionPolicy::SenderOverage`].
+pub const TXPOOL_FAIR_ACCOUNT_SLOTS_DEFAULT: usize = 4;
+
 /// The default maximum allowed number of transactions in the given subpool.
 pub const TXPOOL_SUBPOOL_MAX_TXS_DEFAULT: usize = 10_000;
 
@@ -35,8 +43,17 @@ pub struct PoolConfig {
     pub queued_limit: SubPoolLimit,
     /// Max number of transactions in the blob sub-pool
     pub blob_limit: SubPoolLimit,
+    /// Max combined size (in bytes) of the transactions in all sub-pools.
+    ///
+    /// This is enforced in addition to the limits of the individual sub-pools.
+    pub max_total_size: Option<usize>,
     /// Max number of executable transaction slots guaranteed per account
     pub max_account_slots: usize,
+    /// Number of transactions a sender can have in the pool before its transactions are
+    /// considered overage by [`EvictionPolicy::SenderOverage`].
+    pub fair_account_slots: usize,
+    /// How transactions are chosen for eviction once the pool exceeds its limits.
+    pub eviction_policy: EvictionPolicy,
     /// Price bump (in %) for the transaction pool underpriced check.
     pub price_bumps: PriceBumpConfig,
     /// How to handle locally received transactions:
@@ -46,6 +63,16 @@ pub struct PoolConfig {
     pub pending_tx_listener_buffer_size: usize,
     /// Bound on number of new transactions from `reth_network::TransactionsManager` to buffer.
     pub new_tx_listener_buffer_size: usize,
+    /// Custom admission rules that are checked before a transaction is validated.
+    pub admission_policy: Option<Arc<dyn TransactionPolicy>>,
+    /// Custom rule that decides whether a transaction may replace a pooled transaction with the
+    /// same sender and nonce.
+    ///
+    /// If set, this is used instead of the [`price_bumps`](Self::price_bumps).
+    pub replacement_policy: Option<Arc<dyn ReplacementPolicy>>,
+    /// Disk backed store for blob transactions that are discarded because the blob sub-pool is
+    /// full, so that they can be re-admitted later instead of being dropped.
+    pub blob_overflow: Option<BlobOverflowStore>,
 }
 
 impl PoolConfig {
@@ -55,7 +82,18 @@ impl PoolConfig {
         self.blob_limit.is_exceeded(pool_size.blob, pool_size.blob_size) ||
             self.pending_limit.is_exceeded(pool_size.pending, pool_size.pending_size) ||
             self.basefee_limit.is_exceeded(pool_size.basefee, pool_size.basefee_size) ||
-            self.queued_limit.is_exceeded(pool_size.queued, pool_size.queued_size)
+            self.queued_limit.is_exceeded(pool_size.queued, pool_size.queued_size) ||
+            self.is_total_size_exceeded(pool_size.total_size())
+    }
+
+    /// Returns whether the given combined size of all sub-pools exceeds the
+    /// [`max_total_size`](Self::max_total_size).
+    #[inline]
+    pub const fn is_total_size_exceeded(&self, total_size: usize) -> bool {
+        match self.max_total_size {
+            Some(max_total_size) => max_total_size < total_size,
+            None => false,
+        }
     }
 }
 
@@ -66,11 +104,17 @@ impl Default for PoolConfig {
             basefee_limit: Default::default(),
             queued_limit: Default::default(),
             blob_limit: Default::default(),
+            max_total_size: None,
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            fair_account_slots: TXPOOL_FAIR_ACCOUNT_SLOTS_DEFAULT,
+            eviction_policy: Default::default(),
             price_bumps: Default::default(),
             local_transactions_config: Default::default(),
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
+            admission_policy: None,
+            replacement_policy: None,
+            blob_overflow: None,
         }
     }
 }
@@ -107,11 +151,53 @@ impl Default for SubPoolLimit {
     }
 }
 
+/// Decides which transactions are evicted first once the pool exceeds its limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EvictionPolicy {
+    /// Evict the worst transactions of the sub-pool that is over its limit.
+    #[default]
+    Fee,
+    /// Before evicting by fee, evict the highest nonce transactions of the senders that exceed
+    /// their [`fair_account_slots`](PoolConfig::fair_account_slots) the most.
+    ///
+    /// Local transactions are exempt from this.
+    SenderOverage,
+}
+
+impl fmt::Display for EvictionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fee => f.write_str("fee"),
+            Self::SenderOverage => f.write_str("sender-overage"),
+        }
+    }
+}
+
+impl FromStr for EvictionPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fee" => Ok(Self::Fee),
+            "sender-overage" => Ok(Self::SenderOverage),
+            _ => Err(format!("invalid eviction policy `{s}`, expected `fee` or `sender-overage`")),
+        }
+    }
+}
+
 /// Price bump config (in %) for the transaction pool underpriced check.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct PriceBumpConfig {
     /// Default price bump (in %) for the transaction pool underpriced check.
     pub default_price_bump: u128,
+    /// Price bump (in %) to replace a legacy or EIP-2930 transaction.
+    ///
+    /// Falls back to the [`default_price_bump`](Self::default_price_bump) if not set.
+    pub legacy_price_bump: Option<u128>,
+    /// Price bump (in %) to replace an EIP-1559 transaction.
+    ///
+    /// Falls back to the [`default_price_bump`](Self::default_price_bump) if not set.
+    pub eip1559_price_bump: Option<u128>,
     /// Replace blob price bump (in %) for the transaction pool underpriced check.
     pub replace_blob_tx_price_bump: u128,
 }
@@ -120,10 +206,16 @@ impl PriceBumpConfig {
     /// Returns the price bump required to replace the given transaction type.
     #[inline]
     pub(crate) const fn price_bump(&self, tx_type: u8) -> u128 {
-        if tx_type == EIP4844_TX_TYPE_ID {
-            return self.replace_blob_tx_price_bump
+        let price_bump = match tx_type {
+            EIP4844_TX_TYPE_ID => return self.replace_blob_tx_price_bump,
+            LEGACY_TX_TYPE_ID | EIP2930_TX_TYPE_ID => self.legacy_price_bump,
+            EIP1559_TX_TYPE_ID => self.eip1559_price_bump,
+            _ => None,
+        };
+        match price_bump {
+            Some(price_bump) => price_bump,
+            None => self.default_price_bump,
         }
-        self.default_price_bump
     }
 }
 
@@ -131,6 +223,8 @@ impl Default for PriceBumpConfig {
     fn default() -> Self {
         Self {
             default_price_bump: DEFAULT_PRICE_BUMP,
+            legacy_price_bump: None,
+            eip1559_price_bump: None,
             replace_blob_tx_price_bump: REPLACE_BLOB_PRICE_BUMP,
         }
     }
@@ -238,6 +332,23 @@ mod tests {
         assert!(config.is_exceeded(pool_size));
     }
 
+    #[test]
+    fn test_price_bump_by_type() {
+        let price_bumps = PriceBumpConfig {
+            legacy_price_bump: Some(20),
+            eip1559_price_bump: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(price_bumps.price_bump(LEGACY_TX_TYPE_ID), 20);
+        assert_eq!(price_bumps.price_bump(EIP2930_TX_TYPE_ID), 20);
+        assert_eq!(price_bumps.price_bump(EIP1559_TX_TYPE_ID), 5);
+        assert_eq!(price_bumps.price_bump(EIP4844_TX_TYPE_ID), REPLACE_BLOB_PRICE_BUMP);
+
+        let price_bumps = PriceBumpConfig::default();
+        assert_eq!(price_bumps.price_bump(LEGACY_TX_TYPE_ID), DEFAULT_PRICE_BUMP);
+        assert_eq!(price_bumps.price_bump(EIP1559_TX_TYPE_ID), DEFAULT_PRICE_BUMP);
+    }
+
     #[test]
     fn test_default_config() {
         let config = LocalTransactionConfig::default();
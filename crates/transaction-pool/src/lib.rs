@@ -150,10 +150,16 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
-use crate::{identifier::TransactionId, pool::PoolInner};
+use crate::{
+    error::InvalidPoolTransactionError, identifier::TransactionId, policy::PolicyTransaction,
+    pool::PoolInner,
+};
 use aquamarine as _;
 use reth_eth_wire_types::HandleMempoolData;
-use reth_primitives::{Address, BlobTransactionSidecar, PooledTransactionsElement, TxHash, U256};
+use reth_primitives::{
+    Address, BlobTransactionSidecar, PooledTransactionsElement,
+    PooledTransactionsElementEcRecovered, TxHash, U256,
+};
 use reth_storage_api::StateProviderFactory;
 use std::{collections::HashSet, sync::Arc};
 use tokio::sync::mpsc::Receiver;
@@ -162,10 +168,10 @@ use tracing::{instrument, trace};
 pub use crate::{
     blobstore::{BlobStore, BlobStoreError},
     config::{
-        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-        DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, REPLACE_BLOB_PRICE_BUMP,
-        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
-        TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+        EvictionPolicy, LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit,
+        DEFAULT_PRICE_BUMP, DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, REPLACE_BLOB_PRICE_BUMP,
+        TXPOOL_FAIR_ACCOUNT_SLOTS_DEFAULT, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+        TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
@@ -184,6 +190,7 @@ pub mod error;
 pub mod maintain;
 pub mod metrics;
 pub mod noop;
+pub mod policy;
 pub mod pool;
 pub mod validate;
 
@@ -254,6 +261,13 @@ where
     ) -> (TxHash, TransactionValidationOutcome<V::Transaction>) {
         let hash = *transaction.hash();
 
+        if let Some(policy) = &self.config().admission_policy {
+            if let Err(err) = policy.check(origin, &PolicyTransaction::new(&transaction)) {
+                let err = InvalidPoolTransactionError::Other(Box::new(err));
+                return (hash, TransactionValidationOutcome::Invalid(transaction, err))
+            }
+        }
+
         let outcome = self.pool.validator().validate_transaction(origin, transaction).await;
 
         (hash, outcome)
@@ -378,6 +392,10 @@ where
         self.pool.add_pending_listener(kind)
     }
 
+    fn reorg_reinjection_listener(&self) -> Receiver<ReorgReinjectionEvent> {
+        self.pool.add_reorg_reinjection_listener()
+    }
+
     fn blob_transaction_sidecars_listener(&self) -> Receiver<NewBlobSidecar> {
         self.pool.add_blob_sidecar_listener()
     }
@@ -564,6 +582,14 @@ where
     fn cleanup_blobs(&self) {
         self.pool.cleanup_blobs()
     }
+
+    fn take_overflowed_blob_transactions(&self) -> Vec<PooledTransactionsElementEcRecovered> {
+        self.pool.take_overflowed_blob_transactions()
+    }
+
+    fn on_reorg_reinjection(&self, event: ReorgReinjectionEvent) {
+        self.pool.on_reorg_reinjection(event)
+    }
 }
 
 impl<V, T: TransactionOrdering, S> Clone for Pool<V, T, S> {
@@ -2,10 +2,13 @@
 
 use crate::{
     blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
-    error::PoolError,
+    error::{InvalidPoolTransactionError, PoolError, PoolErrorKind},
     metrics::MaintainPoolMetrics,
-    traits::{CanonicalStateUpdate, ChangedAccount, TransactionPool, TransactionPoolExt},
-    BlockInfo, PoolTransaction,
+    traits::{
+        CanonicalStateUpdate, ChangedAccount, ReorgReinjectionEvent, TransactionPool,
+        TransactionPoolExt,
+    },
+    BlockInfo, PoolTransaction, ValidPoolTransaction,
 };
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
@@ -17,17 +20,20 @@ use reth_execution_types::ExecutionOutcome;
 use reth_fs_util::FsPathError;
 use reth_primitives::{
     Address, BlockHash, BlockNumber, BlockNumberOrTag, IntoRecoveredTransaction,
-    PooledTransactionsElementEcRecovered, TransactionSigned,
+    InvalidTransactionError, PooledTransactionsElementEcRecovered, TransactionSigned, TxHash, B256,
 };
 use reth_storage_api::{errors::provider::ProviderError, BlockReaderIdExt, StateProviderFactory};
 use reth_tasks::TaskSpawner;
 use std::{
     borrow::Borrow,
-    collections::HashSet,
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
 };
-use tokio::sync::oneshot;
+use tokio::{sync::oneshot, time::Instant};
 use tracing::{debug, error, info, trace, warn};
 
 /// Additional settings for maintaining the transaction pool
@@ -55,12 +61,33 @@ impl Default for MaintainPoolConfig {
 pub struct LocalTransactionBackupConfig {
     /// Path to transactions backup file
     pub transactions_path: Option<PathBuf>,
+    /// Whether transactions received from the network are saved as well.
+    pub include_remote_transactions: bool,
+    /// Interval at which the transactions are saved while the node is running, in addition to on
+    /// shutdown.
+    pub journal_interval: Option<Duration>,
 }
 
 impl LocalTransactionBackupConfig {
     /// Receive path to transactions backup and return initialized config
     pub const fn with_local_txs_backup(transactions_path: PathBuf) -> Self {
-        Self { transactions_path: Some(transactions_path) }
+        Self {
+            transactions_path: Some(transactions_path),
+            include_remote_transactions: false,
+            journal_interval: None,
+        }
+    }
+
+    /// Sets whether transactions received from the network are saved as well.
+    pub const fn with_remote_transactions(mut self, include_remote_transactions: bool) -> Self {
+        self.include_remote_transactions = include_remote_transactions;
+        self
+    }
+
+    /// Sets the interval at which the transactions are saved while the node is running.
+    pub const fn with_journal_interval(mut self, journal_interval: Option<Duration>) -> Self {
+        self.journal_interval = journal_interval;
+        self
     }
 }
 
@@ -321,11 +348,13 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
 
                 // update the pool then re-inject the pruned transactions
                 // find all transactions that were mined in the old chain but not in the new chain
+                let mut unrecoverable_transactions = Vec::new();
                 let pruned_old_transactions = old_blocks
                     .transactions_ecrecovered()
                     .filter(|tx| !new_mined_transactions.contains(&tx.hash))
                     .filter_map(|tx| {
-                        if tx.is_eip4844() {
+                        let hash = tx.hash;
+                        let tx = if tx.is_eip4844() {
                             // reorged blobs no longer include the blob, which is necessary for
                             // validating the transaction. Even though the transaction could have
                             // been validated previously, we still need the blob in order to
@@ -345,7 +374,11 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                                 })
                         } else {
                             tx.try_into().ok()
+                        };
+                        if tx.is_none() {
+                            unrecoverable_transactions.push(hash);
                         }
+                        tx
                     })
                     .collect::<Vec<_>>();
 
@@ -367,7 +400,19 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                 // Because the transactions are not finalized, the corresponding blobs are still in
                 // blob store (if we previously received them from the network)
                 metrics.inc_reinserted_transactions(pruned_old_transactions.len());
-                let _ = pool.add_external_transactions(pruned_old_transactions).await;
+                let reinjection = reinject_reorged_transactions(
+                    &pool,
+                    new_tip.hash(),
+                    pending_block_base_fee,
+                    pruned_old_transactions,
+                    unrecoverable_transactions,
+                )
+                .await;
+                metrics.record_reinjection(&reinjection);
+                pool.on_reorg_reinjection(reinjection);
+
+                // re-admit blob transactions that didn't fit into the pool before
+                readmit_overflowed_blob_transactions(&pool).await;
 
                 // keep track of new mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&new_blocks);
@@ -441,6 +486,10 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                 };
                 pool.on_canonical_state_change(update);
 
+                // mined blob transactions may have freed up space, re-admit blob transactions that
+                // didn't fit into the pool before
+                readmit_overflowed_blob_transactions(&pool).await;
+
                 // keep track of mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&blocks);
             }
@@ -570,8 +619,95 @@ fn changed_accounts_iter(
         .map(|(address, acc)| ChangedAccount { address, nonce: acc.nonce, balance: acc.balance })
 }
 
+/// Re-injects the transactions of reorged blocks into the pool and reports what happened to them.
+///
+/// The transactions are revalidated against the new canonical state in priority order, see
+/// [`sort_reorged_transactions`]. Transactions that could not be restored from the reorged blocks
+/// are reported as invalid.
+async fn reinject_reorged_transactions<P>(
+    pool: &P,
+    new_tip: B256,
+    base_fee: u64,
+    mut transactions: Vec<P::Transaction>,
+    unrecoverable: Vec<TxHash>,
+) -> ReorgReinjectionEvent
+where
+    P: TransactionPool,
+{
+    sort_reorged_transactions(&mut transactions, base_fee);
+
+    let mut event =
+        ReorgReinjectionEvent { new_tip, dropped_invalid: unrecoverable, ..Default::default() };
+    for result in pool.add_external_transactions(transactions).await {
+        match result {
+            Ok(hash) => event.reinjected.push(hash),
+            Err(err) => match err.kind {
+                // the transaction is still in the pool
+                PoolErrorKind::AlreadyImported => event.reinjected.push(err.hash),
+                PoolErrorKind::ReplacementUnderpriced |
+                PoolErrorKind::ExistingConflictingTransactionType(..) |
+                PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Consensus(
+                    InvalidTransactionError::NonceNotConsistent,
+                )) => event.dropped_replaced.push(err.hash),
+                _ => event.dropped_invalid.push(err.hash),
+            },
+        }
+    }
+
+    if !event.is_empty() {
+        debug!(
+            target: "txpool",
+            ?new_tip,
+            reinjected=%event.reinjected.len(),
+            dropped_invalid=%event.dropped_invalid.len(),
+            dropped_replaced=%event.dropped_replaced.len(),
+            "Re-injected reorged transactions"
+        );
+    }
+    event
+}
+
+/// Orders reorged transactions so that the most valuable transactions are revalidated first.
+///
+/// Senders are ordered by the effective tip of their lowest nonce transaction at the given base
+/// fee, the transactions of a sender stay in nonce order.
+fn sort_reorged_transactions<T: PoolTransaction>(transactions: &mut [T], base_fee: u64) {
+    let mut sender_priority = HashMap::<Address, (u64, u128)>::new();
+    for tx in transactions.iter() {
+        let tip = tx.effective_tip_per_gas(base_fee).unwrap_or_default();
+        let (nonce, priority) = sender_priority.entry(tx.sender()).or_insert((tx.nonce(), tip));
+        if tx.nonce() < *nonce {
+            *nonce = tx.nonce();
+            *priority = tip;
+        }
+    }
+    transactions
+        .sort_by_key(|tx| (Reverse(sender_priority[&tx.sender()].1), tx.sender(), tx.nonce()));
+}
+
+/// Takes blob transactions from the pool's overflow store that fit into the blob sub-pool again and
+/// re-validates them.
+async fn readmit_overflowed_blob_transactions<P>(pool: &P)
+where
+    P: TransactionPoolExt,
+{
+    let transactions = pool
+        .take_overflowed_blob_transactions()
+        .into_iter()
+        .map(<<P as TransactionPool>::Transaction as PoolTransaction>::from_pooled)
+        .collect::<Vec<_>>();
+    if transactions.is_empty() {
+        return
+    }
+    debug!(target: "txpool", num_txs=%transactions.len(), "Re-admitting overflowed blob transactions");
+    let _ = pool.add_external_transactions(transactions).await;
+}
+
 /// Loads transactions from a file, decodes them from the RLP format, and inserts them
 /// into the transaction pool on node boot up.
+///
+/// The file contains a list of local transactions, optionally followed by a list of remote
+/// transactions. All transactions are validated again before they are inserted.
 /// The file is removed after the transactions have been successfully processed.
 async fn load_and_reinsert_transactions<P>(
     pool: P,
@@ -591,51 +727,77 @@ where
         return Ok(())
     }
 
-    let txs_signed: Vec<TransactionSigned> = alloy_rlp::Decodable::decode(&mut data.as_slice())?;
+    let mut buf = data.as_slice();
+    let local_txs: Vec<TransactionSigned> = alloy_rlp::Decodable::decode(&mut buf)?;
+    let remote_txs: Vec<TransactionSigned> =
+        if buf.is_empty() { Vec::new() } else { alloy_rlp::Decodable::decode(&mut buf)? };
+
+    let into_pool_transactions = |txs: Vec<TransactionSigned>| {
+        txs.into_iter()
+            .filter_map(|tx| tx.try_ecrecovered())
+            .filter_map(|tx| {
+                // Filter out errors
+                tx.try_into().ok()
+            })
+            .collect::<Vec<_>>()
+    };
 
-    let pool_transactions = txs_signed
-        .into_iter()
-        .filter_map(|tx| tx.try_ecrecovered())
-        .filter_map(|tx| {
-            // Filter out errors
-            tx.try_into().ok()
-        })
-        .collect::<Vec<_>>();
+    let outcome = pool
+        .add_transactions(crate::TransactionOrigin::Local, into_pool_transactions(local_txs))
+        .await;
+    info!(target: "txpool", txs_file =?file_path, num_txs=%outcome.len(), "Successfully reinserted local transactions from file");
 
-    let outcome = pool.add_transactions(crate::TransactionOrigin::Local, pool_transactions).await;
+    if !remote_txs.is_empty() {
+        let outcome = pool.add_external_transactions(into_pool_transactions(remote_txs)).await;
+        info!(target: "txpool", txs_file =?file_path, num_txs=%outcome.len(), "Successfully reinserted remote transactions from file");
+    }
 
-    info!(target: "txpool", txs_file =?file_path, num_txs=%outcome.len(), "Successfully reinserted local transactions from file");
     reth_fs_util::remove_file(file_path)?;
     Ok(())
 }
 
-fn save_local_txs_backup<P>(pool: P, file_path: &Path)
+/// Writes the local transactions of the pool to the file, followed by the remote transactions if
+/// `include_remote` is set.
+///
+/// Remote blob transactions are not saved, because their sidecars are not part of the file.
+fn save_local_txs_backup<P>(pool: P, file_path: &Path, include_remote: bool)
 where
     P: TransactionPool,
 {
     let local_transactions = pool.get_local_transactions();
-    if local_transactions.is_empty() {
-        trace!(target: "txpool", "no local transactions to save");
+    let remote_transactions = if include_remote {
+        pool.get_external_transactions()
+            .into_iter()
+            .filter(|tx| !tx.is_eip4844())
+            .collect::<Vec<_>>()
+    } else {
+        Vec::new()
+    };
+    if local_transactions.is_empty() && remote_transactions.is_empty() {
+        trace!(target: "txpool", "no transactions to save");
         return
     }
 
-    let local_transactions = local_transactions
-        .into_iter()
-        .map(|tx| tx.to_recovered_transaction().into_signed())
-        .collect::<Vec<_>>();
+    let into_signed = |txs: Vec<Arc<ValidPoolTransaction<P::Transaction>>>| {
+        txs.into_iter().map(|tx| tx.to_recovered_transaction().into_signed()).collect::<Vec<_>>()
+    };
+    let local_transactions = into_signed(local_transactions);
+    let remote_transactions = into_signed(remote_transactions);
 
-    let num_txs = local_transactions.len();
     let mut buf = Vec::new();
     alloy_rlp::encode_list(&local_transactions, &mut buf);
-    info!(target: "txpool", txs_file =?file_path, num_txs=%num_txs, "Saving current local transactions");
+    if !remote_transactions.is_empty() {
+        alloy_rlp::encode_list(&remote_transactions, &mut buf);
+    }
+    info!(target: "txpool", txs_file =?file_path, num_local_txs=%local_transactions.len(), num_remote_txs=%remote_transactions.len(), "Saving current transactions");
     let parent_dir = file_path.parent().map(std::fs::create_dir_all).transpose();
 
     match parent_dir.map(|_| reth_fs_util::write(file_path, buf)) {
         Ok(_) => {
-            info!(target: "txpool", txs_file=?file_path, "Wrote local transactions to file");
+            info!(target: "txpool", txs_file=?file_path, "Wrote transactions to file");
         }
         Err(err) => {
-            warn!(target: "txpool", %err, txs_file=?file_path, "Failed to write local transactions to file");
+            warn!(target: "txpool", %err, txs_file=?file_path, "Failed to write transactions to file");
         }
     }
 }
@@ -656,6 +818,9 @@ pub enum TransactionsBackupError {
 
 /// Task which manages saving local transactions to the persistent file in case of shutdown.
 /// Reloads the transactions from the file on the boot up and inserts them into the pool.
+///
+/// If configured, remote transactions are saved as well, and the file is also written
+/// periodically, so that transactions survive a crash.
 pub async fn backup_local_transactions_task<P>(
     shutdown: reth_tasks::shutdown::GracefulShutdown,
     pool: P,
@@ -663,7 +828,12 @@ pub async fn backup_local_transactions_task<P>(
 ) where
     P: TransactionPool + Clone,
 {
-    let Some(transactions_path) = config.transactions_path else {
+    let LocalTransactionBackupConfig {
+        transactions_path,
+        include_remote_transactions,
+        journal_interval,
+    } = config;
+    let Some(transactions_path) = transactions_path else {
         // nothing to do
         return
     };
@@ -672,10 +842,24 @@ pub async fn backup_local_transactions_task<P>(
         error!(target: "txpool", "{}", err)
     }
 
-    let graceful_guard = shutdown.await;
+    let graceful_guard = if let Some(journal_interval) = journal_interval {
+        let mut interval =
+            tokio::time::interval_at(Instant::now() + journal_interval, journal_interval);
+        let mut shutdown = std::pin::pin!(shutdown);
+        loop {
+            tokio::select! {
+                guard = &mut shutdown => break guard,
+                _ = interval.tick() => {
+                    save_local_txs_backup(pool.clone(), &transactions_path, include_remote_transactions);
+                }
+            }
+        }
+    } else {
+        shutdown.await
+    };
 
     // write transactions to disk
-    save_local_txs_backup(pool, &transactions_path);
+    save_local_txs_backup(pool, &transactions_path, include_remote_transactions);
 
     drop(graceful_guard)
 }
@@ -684,7 +868,9 @@ pub async fn backup_local_transactions_task<P>(
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
+        blobstore::InMemoryBlobStore,
+        test_utils::{testing_pool, MockTransaction},
+        validate::EthTransactionValidatorBuilder,
         CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionOrigin,
     };
     use reth_chainspec::MAINNET;
@@ -693,6 +879,60 @@ mod tests {
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
     use reth_tasks::TaskManager;
 
+    #[test]
+    fn sort_reorged_transactions_by_priority() {
+        let low = MockTransaction::eip1559().with_priority_fee(1).with_max_fee(100);
+        let low_next = low.next().with_priority_fee(50).with_max_fee(100);
+        let high = MockTransaction::eip1559().with_priority_fee(10).with_max_fee(100);
+
+        let mut transactions = vec![low.clone(), low_next.clone(), high.clone()];
+        sort_reorged_transactions(&mut transactions, 0);
+        assert_eq!(transactions, vec![high, low, low_next]);
+    }
+
+    #[tokio::test]
+    async fn reinject_reorged_transactions_outcome() {
+        let pool = testing_pool();
+        let pooled = MockTransaction::eip1559().with_priority_fee(10).with_max_fee(100);
+        let replaced = MockTransaction::eip1559().with_priority_fee(10).with_max_fee(100);
+        pool.add_external_transactions(vec![pooled.clone(), replaced.clone()]).await;
+
+        // same nonce as a pooled transaction without a sufficient price bump
+        let replaced = replaced.rng_hash();
+        let reinjected = MockTransaction::eip1559();
+        let unrecoverable = B256::random();
+
+        let new_tip = B256::random();
+        let event = reinject_reorged_transactions(
+            &pool,
+            new_tip,
+            0,
+            vec![pooled.clone(), replaced.clone(), reinjected.clone()],
+            vec![unrecoverable],
+        )
+        .await;
+
+        assert_eq!(event.new_tip, new_tip);
+        assert_eq!(event.len(), 4);
+        assert!(event.reinjected.contains(&pooled.get_hash()));
+        assert!(event.reinjected.contains(&reinjected.get_hash()));
+        assert_eq!(event.dropped_replaced, vec![replaced.get_hash()]);
+        assert_eq!(event.dropped_invalid, vec![unrecoverable]);
+    }
+
+    #[tokio::test]
+    async fn reorg_reinjection_listener() {
+        let pool = testing_pool();
+        let mut listener = pool.reorg_reinjection_listener();
+        let event = ReorgReinjectionEvent {
+            new_tip: B256::random(),
+            reinjected: vec![B256::random()],
+            ..Default::default()
+        };
+        pool.on_reorg_reinjection(event.clone());
+        assert_eq!(listener.recv().await, Some(event));
+    }
+
     #[test]
     fn changed_acc_entry() {
         let changed_acc = ChangedAccountEntry(ChangedAccount::empty(Address::random()));
@@ -752,6 +992,57 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_save_and_reload_remote_txs_backup() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let transactions_path = temp_dir.path().join(FILENAME).with_extension(EXTENSION);
+        let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
+        let tx = PooledTransactionsElement::decode_enveloped(&mut &tx_bytes[..]).unwrap();
+        let provider = MockEthProvider::default();
+        let transaction: EthPooledTransaction = tx.try_into_ecrecovered().unwrap().into();
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider, blob_store.clone());
+        let new_pool = || {
+            Pool::new(
+                validator.clone(),
+                CoinbaseTipOrdering::default(),
+                blob_store.clone(),
+                Default::default(),
+            )
+        };
+        let config = LocalTransactionBackupConfig::with_local_txs_backup(transactions_path.clone())
+            .with_remote_transactions(true);
+
+        let txpool = new_pool();
+        txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+
+        let manager = TaskManager::new(tokio::runtime::Handle::current());
+        let (pool, task_config) = (txpool.clone(), config.clone());
+        manager.executor().spawn_critical_with_graceful_shutdown_signal("test task", |shutdown| {
+            backup_local_transactions_task(shutdown, pool, task_config)
+        });
+        manager.graceful_shutdown();
+
+        // no local transactions, followed by the remote transaction
+        let data = fs::read(&transactions_path).unwrap();
+        let mut buf = data.as_slice();
+        let local: Vec<TransactionSigned> = alloy_rlp::Decodable::decode(&mut buf).unwrap();
+        let remote: Vec<TransactionSigned> = alloy_rlp::Decodable::decode(&mut buf).unwrap();
+        assert!(local.is_empty());
+        assert_eq!(remote.len(), 1);
+
+        // the transaction is reinserted as remote transaction on startup
+        let txpool = new_pool();
+        load_and_reinsert_transactions(txpool.clone(), &transactions_path).await.unwrap();
+        let reloaded = txpool.get_external_transactions();
+        assert_eq!(reloaded.len(), 1);
+        assert_eq!(reloaded[0].hash(), transaction.hash());
+        assert!(!transactions_path.exists());
+    }
+
     #[test]
     fn test_update_with_higher_finalized_block() {
         let mut tracker = FinalizedBlockTracker::new(Some(10));
@@ -1,5 +1,6 @@
 //! Transaction pool metrics.
 
+use crate::traits::ReorgReinjectionEvent;
 use reth_metrics::{
     metrics::{Counter, Gauge},
     Metrics,
@@ -39,6 +40,13 @@ pub struct TxPoolMetrics {
     /// Number of all transactions of all sub-pools: pending + basefee + queued
     pub(crate) total_transactions: Gauge,
 
+    /// Number of transactions evicted because their sender exceeded its fair share of slots
+    pub(crate) evicted_sender_overage_transactions: Counter,
+    /// Number of transactions evicted because a sub-pool exceeded its limits
+    pub(crate) evicted_subpool_limit_transactions: Counter,
+    /// Number of transactions evicted because the pool exceeded its total size budget
+    pub(crate) evicted_total_size_transactions: Counter,
+
     /// How often the pool was updated after the canonical state changed
     pub(crate) performed_state_updates: Counter,
 }
@@ -55,6 +63,16 @@ pub struct BlobStoreMetrics {
     pub(crate) blobstore_byte_size: Gauge,
     /// How many blobs are currently in the blobstore
     pub(crate) blobstore_entries: Gauge,
+    /// Number of discarded blob transactions that were moved to the overflow store
+    pub(crate) blobstore_overflow_inserts: Counter,
+    /// Number of blob transactions that were evicted from the overflow store
+    pub(crate) blobstore_overflow_evictions: Counter,
+    /// Number of blob transactions that were taken from the overflow store to be re-admitted
+    pub(crate) blobstore_overflow_readmitted: Counter,
+    /// The number of bytes the transactions in the overflow store take up
+    pub(crate) blobstore_overflow_byte_size: Gauge,
+    /// How many transactions are currently in the overflow store
+    pub(crate) blobstore_overflow_entries: Gauge,
 }
 
 /// Transaction pool maintenance metrics
@@ -70,6 +88,14 @@ pub struct MaintainPoolMetrics {
     /// Counter for the number of transactions reinserted into the pool following a blockchain
     /// reorganization (reorg).
     pub(crate) reinserted_transactions: Counter,
+    /// Counter for the number of reorged transactions that were added back to the pool.
+    pub(crate) reinjected_transactions: Counter,
+    /// Counter for the number of reorged transactions that were dropped because they're invalid
+    /// against the new canonical state.
+    pub(crate) reinjection_dropped_invalid_transactions: Counter,
+    /// Counter for the number of reorged transactions that were dropped because their nonce is
+    /// already taken by another transaction.
+    pub(crate) reinjection_dropped_replaced_transactions: Counter,
     /// Counter for the number of finalized blob transactions that have been removed from tracking.
     pub(crate) deleted_tracked_finalized_blobs: Counter,
 }
@@ -85,6 +111,14 @@ impl MaintainPoolMetrics {
         self.reinserted_transactions.increment(count as u64);
     }
 
+    #[inline]
+    pub(crate) fn record_reinjection(&self, event: &ReorgReinjectionEvent) {
+        self.reinjected_transactions.increment(event.reinjected.len() as u64);
+        self.reinjection_dropped_invalid_transactions.increment(event.dropped_invalid.len() as u64);
+        self.reinjection_dropped_replaced_transactions
+            .increment(event.dropped_replaced.len() as u64);
+    }
+
     #[inline]
     pub(crate) fn inc_deleted_tracked_blobs(&self, count: usize) {
         self.deleted_tracked_finalized_blobs.increment(count as u64);
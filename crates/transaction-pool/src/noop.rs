@@ -8,7 +8,7 @@ use crate::{
     error::PoolError,
     traits::{
         BestTransactionsAttributes, GetPooledTransactionLimit, NewBlobSidecar,
-        TransactionListenerKind,
+        ReorgReinjectionEvent, TransactionListenerKind,
     },
     validate::ValidTransaction,
     AllPoolTransactions, AllTransactionsEvents, BestTransactions, BlockInfo, EthPoolTransaction,
@@ -100,6 +100,10 @@ impl TransactionPool for NoopTransactionPool {
         mpsc::channel(1).1
     }
 
+    fn reorg_reinjection_listener(&self) -> Receiver<ReorgReinjectionEvent> {
+        mpsc::channel(1).1
+    }
+
     fn new_transactions_listener_for(
         &self,
         _kind: TransactionListenerKind,
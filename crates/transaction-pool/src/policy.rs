@@ -0,0 +1,249 @@
+//! Admission and replacement policies for the transaction pool.
+//!
+//! A [`TransactionPolicy`] is consulted before a transaction is validated and can reject
+//! transactions based on node specific rules, for example denied function selectors, a minimum
+//! priority fee or a sender allowlist. The policy is configured via
+//! [`PoolConfig::admission_policy`](crate::PoolConfig::admission_policy).
+//!
+//! A [`ReplacementPolicy`] decides whether a transaction may replace a pooled transaction with the
+//! same sender and nonce, instead of the configured
+//! [`PoolConfig::price_bumps`](crate::PoolConfig::price_bumps). The policy is configured via
+//! [`PoolConfig::replacement_policy`](crate::PoolConfig::replacement_policy).
+
+use crate::{error::PoolTransactionError, PoolTransaction, TransactionOrigin};
+use reth_primitives::{Address, TxHash};
+use std::{collections::HashSet, fmt, sync::Arc};
+
+/// Custom admission rule that is applied to every transaction before it is validated.
+pub trait TransactionPolicy: fmt::Debug + Send + Sync {
+    /// Returns an error if the transaction must not be admitted to the pool.
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &PolicyTransaction<'_>,
+    ) -> Result<(), PolicyViolation>;
+}
+
+/// Custom rule that decides whether a transaction may replace a pooled transaction with the same
+/// sender and nonce.
+pub trait ReplacementPolicy: fmt::Debug + Send + Sync {
+    /// Returns `true` if the `replacement` transaction may replace the `existing` transaction.
+    fn allows_replacement(
+        &self,
+        existing: &PolicyTransaction<'_>,
+        replacement: &PolicyTransaction<'_>,
+    ) -> bool;
+}
+
+/// The fields of a transaction that are exposed to a [`TransactionPolicy`] or
+/// [`ReplacementPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PolicyTransaction<'a> {
+    /// Hash of the transaction.
+    pub hash: TxHash,
+    /// Sender of the transaction.
+    pub sender: Address,
+    /// Recipient of the transaction, `None` for contract creations.
+    pub to: Option<Address>,
+    /// Nonce of the transaction.
+    pub nonce: u64,
+    /// Max fee per gas, this is the gas price for legacy transactions.
+    pub max_fee_per_gas: u128,
+    /// EIP-1559 priority fee, `None` for legacy transactions.
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// Max fee per blob gas, `None` for non-blob transactions.
+    pub max_fee_per_blob_gas: Option<u128>,
+    /// Type of the transaction.
+    pub tx_type: u8,
+    /// Input data of the transaction.
+    pub input: &'a [u8],
+}
+
+impl<'a> PolicyTransaction<'a> {
+    /// Creates the policy view of the given pool transaction.
+    pub fn new<T: PoolTransaction>(transaction: &'a T) -> Self {
+        Self {
+            hash: *transaction.hash(),
+            sender: transaction.sender(),
+            to: transaction.to(),
+            nonce: transaction.nonce(),
+            max_fee_per_gas: transaction.max_fee_per_gas(),
+            max_priority_fee_per_gas: transaction.max_priority_fee_per_gas(),
+            max_fee_per_blob_gas: transaction.max_fee_per_blob_gas(),
+            tx_type: transaction.tx_type(),
+            input: transaction.input(),
+        }
+    }
+
+    /// Returns the 4byte function selector of a call, if the input is long enough.
+    pub fn selector(&self) -> Option<[u8; 4]> {
+        self.to?;
+        self.input.get(..4).map(|selector| selector.try_into().expect("length is 4"))
+    }
+}
+
+/// Error returned if a transaction is rejected by a [`TransactionPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("transaction rejected by pool policy: {0}")]
+pub struct PolicyViolation(pub String);
+
+impl PolicyViolation {
+    /// Creates a new violation with the given reason.
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self(reason.into())
+    }
+}
+
+impl PoolTransactionError for PolicyViolation {
+    fn is_bad_transaction(&self) -> bool {
+        // policies are node specific and not a reason to penalize the peer
+        false
+    }
+}
+
+/// Rejects calls to any of the denied 4byte function selectors.
+#[derive(Debug, Clone, Default)]
+pub struct DenySelectors {
+    selectors: HashSet<[u8; 4]>,
+}
+
+impl DenySelectors {
+    /// Creates a new policy that denies the given selectors.
+    pub fn new(selectors: impl IntoIterator<Item = [u8; 4]>) -> Self {
+        Self { selectors: selectors.into_iter().collect() }
+    }
+}
+
+impl TransactionPolicy for DenySelectors {
+    fn check(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &PolicyTransaction<'_>,
+    ) -> Result<(), PolicyViolation> {
+        match transaction.selector() {
+            Some(selector) if self.selectors.contains(&selector) => Err(PolicyViolation::new(
+                format!("selector 0x{} is denied", reth_primitives::hex::encode(selector)),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Rejects transactions that pay less than the configured priority fee.
+///
+/// For legacy transactions the priority fee is not known upfront, so they are always admitted.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinPriorityFee(pub u128);
+
+impl TransactionPolicy for MinPriorityFee {
+    fn check(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &PolicyTransaction<'_>,
+    ) -> Result<(), PolicyViolation> {
+        match transaction.max_priority_fee_per_gas {
+            Some(fee) if fee < self.0 => Err(PolicyViolation::new(format!(
+                "priority fee {fee} is below the minimum of {}",
+                self.0
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Only admits transactions sent by one of the allowed senders.
+///
+/// Local transactions are always admitted.
+#[derive(Debug, Clone, Default)]
+pub struct SenderAllowlist {
+    senders: HashSet<Address>,
+}
+
+impl SenderAllowlist {
+    /// Creates a new policy that only admits the given senders.
+    pub fn new(senders: impl IntoIterator<Item = Address>) -> Self {
+        Self { senders: senders.into_iter().collect() }
+    }
+}
+
+impl TransactionPolicy for SenderAllowlist {
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &PolicyTransaction<'_>,
+    ) -> Result<(), PolicyViolation> {
+        if origin.is_local() || self.senders.contains(&transaction.sender) {
+            return Ok(())
+        }
+        Err(PolicyViolation::new(format!("sender {} is not allowed", transaction.sender)))
+    }
+}
+
+/// Admits a transaction only if all policies admit it.
+#[derive(Debug, Clone, Default)]
+pub struct PolicySet {
+    policies: Vec<Arc<dyn TransactionPolicy>>,
+}
+
+impl PolicySet {
+    /// Adds a policy to the set.
+    pub fn with_policy<P: TransactionPolicy + 'static>(mut self, policy: P) -> Self {
+        self.policies.push(Arc::new(policy));
+        self
+    }
+}
+
+impl TransactionPolicy for PolicySet {
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &PolicyTransaction<'_>,
+    ) -> Result<(), PolicyViolation> {
+        self.policies.iter().try_for_each(|policy| policy.check(origin, transaction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+    use reth_primitives::Bytes;
+
+    fn call(input: &[u8]) -> MockTransaction {
+        MockTransaction::eip1559().with_input(Bytes::copy_from_slice(input)).with_priority_fee(10)
+    }
+
+    #[test]
+    fn deny_selectors() {
+        let policy = DenySelectors::new([[0xa9, 0x05, 0x9c, 0xbb]]);
+        let denied = call(&[0xa9, 0x05, 0x9c, 0xbb, 0x00]);
+        let allowed = call(&[0x09, 0x5e, 0xa7, 0xb3]);
+        let short = call(&[0xa9, 0x05]);
+
+        let origin = TransactionOrigin::External;
+        assert!(policy.check(origin, &PolicyTransaction::new(&denied)).is_err());
+        assert!(policy.check(origin, &PolicyTransaction::new(&allowed)).is_ok());
+        assert!(policy.check(origin, &PolicyTransaction::new(&short)).is_ok());
+    }
+
+    #[test]
+    fn policy_set() {
+        let tx = call(&[]);
+        let allowed = SenderAllowlist::new([tx.sender()]);
+
+        let policy = PolicySet::default().with_policy(MinPriorityFee(5)).with_policy(allowed);
+        assert!(policy.check(TransactionOrigin::External, &PolicyTransaction::new(&tx)).is_ok());
+
+        let policy = policy.with_policy(MinPriorityFee(11));
+        let err =
+            policy.check(TransactionOrigin::External, &PolicyTransaction::new(&tx)).unwrap_err();
+        assert_eq!(err, PolicyViolation::new("priority fee 10 is below the minimum of 11"));
+
+        let other = call(&[]);
+        let policy = PolicySet::default().with_policy(SenderAllowlist::new([tx.sender()]));
+        assert!(policy
+            .check(TransactionOrigin::External, &PolicyTransaction::new(&other))
+            .is_err());
+        assert!(policy.check(TransactionOrigin::Local, &PolicyTransaction::new(&other)).is_ok());
+    }
+}
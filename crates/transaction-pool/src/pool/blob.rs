@@ -217,13 +217,35 @@ impl<T: PoolTransaction> BlobTransactions<T> {
     /// This is done by removing transactions according to their ordering in the pool, defined by
     /// the [`BlobOrd`] struct.
     ///
+    /// Local transactions are only removed if the pool is still over the limit after all
+    /// non-local transactions have been removed.
+    ///
     /// Removed transactions are returned in the order they were removed.
     pub(crate) fn truncate_pool(
         &mut self,
         limit: SubPoolLimit,
     ) -> Vec<Arc<ValidPoolTransaction<T>>> {
         let mut removed = Vec::new();
+        if !self.exceeds(&limit) {
+            return removed
+        }
+
+        // first truncate only non-local transactions
+        let non_local = self
+            .all
+            .iter()
+            .rev()
+            .filter(|tx| !tx.transaction.is_local())
+            .map(|tx| *tx.transaction.id())
+            .collect::<Vec<_>>();
+        for id in non_local {
+            if !self.exceeds(&limit) {
+                return removed
+            }
+            removed.push(self.remove_transaction(&id).expect("transaction exists"));
+        }
 
+        // the remaining transactions are local
         while self.exceeds(&limit) {
             let tx = self.all.last().expect("pool is not empty");
             let id = *tx.transaction.id();
@@ -452,7 +474,10 @@ impl Ord for BlobOrd {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::{MockTransaction, MockTransactionFactory};
+    use crate::{
+        test_utils::{MockTransaction, MockTransactionFactory},
+        TransactionOrigin,
+    };
 
     /// Represents the fees for a single transaction, which will be built inside of a test.
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -673,6 +698,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn truncate_keeps_local_transactions() {
+        let mut factory = MockTransactionFactory::default();
+        let mut pool = BlobTransactions::default();
+
+        // the local transaction has the lowest fees and would be evicted first otherwise
+        let local = Arc::new(factory.validated_with_origin(
+            TransactionOrigin::Local,
+            MockTransaction::eip4844().with_blob_fee(1).with_max_fee(1),
+        ));
+        pool.add_transaction(local.clone());
+        for _ in 0..2 {
+            pool.add_transaction(
+                factory
+                    .validated_arc(MockTransaction::eip4844().with_blob_fee(100).with_max_fee(100)),
+            );
+        }
+
+        let removed = pool.truncate_pool(SubPoolLimit { max_txs: 1, max_size: usize::MAX });
+        assert_eq!(removed.len(), 2);
+        assert!(removed.iter().all(|tx| !tx.is_local()));
+        assert!(pool.contains(local.id()));
+
+        let removed = pool.truncate_pool(SubPoolLimit { max_txs: 0, max_size: usize::MAX });
+        assert_eq!(removed.len(), 1);
+        assert!(!pool.contains(local.id()));
+    }
+
     #[test]
     fn priority_tests() {
         // Test vectors from:
@@ -85,7 +85,8 @@ use parking_lot::{Mutex, RwLock, RwLockReadGuard};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_primitives::{
     Address, BlobTransaction, BlobTransactionSidecar, IntoRecoveredTransaction,
-    PooledTransactionsElement, TransactionSigned, TxHash, B256,
+    PooledTransactionsElement, PooledTransactionsElementEcRecovered, TransactionSigned, TxHash,
+    B256,
 };
 use std::{
     collections::{HashMap, HashSet},
@@ -100,7 +101,9 @@ use crate::{
     blobstore::BlobStore,
     metrics::BlobStoreMetrics,
     pool::txpool::UpdateOutcome,
-    traits::{GetPooledTransactionLimit, NewBlobSidecar, TransactionListenerKind},
+    traits::{
+        GetPooledTransactionLimit, NewBlobSidecar, ReorgReinjectionEvent, TransactionListenerKind,
+    },
     validate::ValidTransaction,
 };
 pub use best::BestTransactionFilter;
@@ -127,6 +130,8 @@ pub const NEW_TX_LISTENER_BUFFER_SIZE: usize = 1024;
 
 const BLOB_SIDECAR_LISTENER_BUFFER_SIZE: usize = 512;
 
+const REORG_REINJECTION_LISTENER_BUFFER_SIZE: usize = 64;
+
 /// Transaction pool internals.
 pub struct PoolInner<V, T, S>
 where
@@ -150,6 +155,8 @@ where
     transaction_listener: Mutex<Vec<TransactionListener<T::Transaction>>>,
     /// Listener for new blob transaction sidecars added to the pool.
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
+    /// Listeners for the outcome of re-injecting reorged transactions.
+    reorg_reinjection_listener: Mutex<Vec<mpsc::Sender<ReorgReinjectionEvent>>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
 }
@@ -172,6 +179,7 @@ where
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
             blob_transaction_sidecar_listener: Default::default(),
+            reorg_reinjection_listener: Default::default(),
             config,
             blob_store,
             blob_store_metrics: Default::default(),
@@ -261,6 +269,35 @@ where
         rx
     }
 
+    /// Adds a new listener that gets notified about the outcome of re-injecting the transactions
+    /// of reorged blocks.
+    pub fn add_reorg_reinjection_listener(&self) -> mpsc::Receiver<ReorgReinjectionEvent> {
+        let (sender, rx) = mpsc::channel(REORG_REINJECTION_LISTENER_BUFFER_SIZE);
+        self.reorg_reinjection_listener.lock().push(sender);
+        rx
+    }
+
+    /// Notify all listeners about the outcome of re-injecting the transactions of reorged blocks.
+    pub(crate) fn on_reorg_reinjection(&self, event: ReorgReinjectionEvent) {
+        self.reorg_reinjection_listener.lock().retain_mut(|sender| {
+            match sender.try_send(event.clone()) {
+                Ok(()) => true,
+                Err(err) => {
+                    if matches!(err, mpsc::error::TrySendError::Full(_)) {
+                        debug!(
+                            target: "txpool",
+                            new_tip=?event.new_tip,
+                            "failed to send reorg reinjection event; channel full",
+                        );
+                        true
+                    } else {
+                        false
+                    }
+                }
+            }
+        });
+    }
+
     /// If the pool contains the transaction, this adds a new listener that gets notified about
     /// transaction events.
     pub(crate) fn add_transaction_event_listener(
@@ -783,10 +820,14 @@ where
     /// Enforces the size limits of pool and returns the discarded transactions if violated.
     ///
     /// If some of the transactions are blob transactions, they are also removed from the blob
-    /// store.
+    /// store. If a [`BlobOverflowStore`](crate::blobstore::BlobOverflowStore) is configured, they
+    /// are moved there first.
     pub(crate) fn discard_worst(&self) -> HashSet<TxHash> {
         let discarded = self.pool.write().discard_worst();
 
+        // keep discarded blob transactions on disk, so they can be re-admitted later
+        self.overflow_discarded_blob_transactions(discarded.iter());
+
         // delete any blobs associated with discarded blob transactions
         self.delete_discarded_blobs(discarded.iter());
 
@@ -827,6 +868,71 @@ where
         self.blob_store_metrics.blobstore_entries.set(self.blob_store.blobs_len() as f64);
     }
 
+    /// Moves discarded blob transactions, together with their sidecar, into the overflow store if
+    /// one is configured.
+    fn overflow_discarded_blob_transactions<'a>(
+        &'a self,
+        transactions: impl IntoIterator<Item = &'a Arc<ValidPoolTransaction<T::Transaction>>>,
+    ) {
+        let Some(overflow) = &self.config.blob_overflow else { return };
+
+        let mut inserted = false;
+        for tx in transactions.into_iter().filter(|tx| tx.transaction.is_eip4844()) {
+            let Ok(Some(sidecar)) = self.blob_store.get(*tx.hash()) else { continue };
+            let Ok(tx) = PooledTransactionsElementEcRecovered::try_from_blob_transaction(
+                tx.to_recovered_transaction(),
+                sidecar,
+            ) else {
+                continue
+            };
+            let hash = *tx.hash();
+            match overflow.insert(tx) {
+                Ok(evicted) => {
+                    self.blob_store_metrics.blobstore_overflow_inserts.increment(1);
+                    self.blob_store_metrics
+                        .blobstore_overflow_evictions
+                        .increment(evicted.len() as u64);
+                    inserted = true;
+                }
+                Err(err) => {
+                    warn!(target: "txpool", %err, "[{:?}] failed to move blob transaction to overflow store", hash);
+                }
+            }
+        }
+
+        if inserted {
+            self.update_blob_overflow_metrics();
+        }
+    }
+
+    /// Takes the best transactions from the blob overflow store that fit into the free space of
+    /// the blob sub-pool.
+    pub(crate) fn take_overflowed_blob_transactions(
+        &self,
+    ) -> Vec<PooledTransactionsElementEcRecovered> {
+        let Some(overflow) = &self.config.blob_overflow else { return Vec::new() };
+        if overflow.is_empty() {
+            return Vec::new()
+        }
+
+        let size = self.size();
+        let limit = self.config.blob_limit;
+        let txs = overflow.take_best(
+            limit.max_txs.saturating_sub(size.blob),
+            limit.max_size.saturating_sub(size.blob_size),
+        );
+        self.blob_store_metrics.blobstore_overflow_readmitted.increment(txs.len() as u64);
+        self.update_blob_overflow_metrics();
+        txs
+    }
+
+    fn update_blob_overflow_metrics(&self) {
+        if let Some(overflow) = &self.config.blob_overflow {
+            self.blob_store_metrics.blobstore_overflow_byte_size.set(overflow.data_size() as f64);
+            self.blob_store_metrics.blobstore_overflow_entries.set(overflow.len() as f64);
+        }
+    }
+
     /// Deletes all blob transactions that were discarded.
     fn delete_discarded_blobs<'a>(
         &'a self,
@@ -1141,12 +1247,15 @@ impl<T: PoolTransaction> OnNewCanonicalStateOutcome<T> {
 #[cfg(test)]
 mod tests {
     use crate::{
-        blobstore::{BlobStore, InMemoryBlobStore},
+        blobstore::{BlobOverflowStore, BlobStore, InMemoryBlobStore},
         test_utils::{MockTransaction, TestPoolBuilder},
         validate::ValidTransaction,
         BlockInfo, PoolConfig, SubPoolLimit, TransactionOrigin, TransactionValidationOutcome, U256,
     };
-    use reth_primitives::{kzg::Blob, transaction::generate_blob_sidecar};
+    use reth_primitives::{
+        kzg::{Blob, BYTES_PER_BLOB},
+        transaction::generate_blob_sidecar,
+    };
     use std::{fs, path::PathBuf};
 
     #[test]
@@ -1228,4 +1337,56 @@ mod tests {
         // Assert that the pool's blob store matches the expected blob store.
         assert_eq!(*test_pool.blob_store(), blob_store);
     }
+
+    #[test]
+    fn test_overflow_discarded_blob_txs() {
+        let dir = tempfile::tempdir().unwrap();
+        let overflow = BlobOverflowStore::open(dir.path(), Default::default()).unwrap();
+        let blob_limit = SubPoolLimit::new(2, usize::MAX);
+        let test_pool = &TestPoolBuilder::default()
+            .with_config(PoolConfig {
+                blob_limit,
+                blob_overflow: Some(overflow.clone()),
+                ..Default::default()
+            })
+            .pool;
+        test_pool
+            .set_block_info(BlockInfo { pending_blob_fee: Some(10_000_000), ..Default::default() });
+
+        let sidecar = generate_blob_sidecar(vec![Blob::new([0; BYTES_PER_BLOB])]);
+        let mut hashes = Vec::new();
+        for _ in 0..4 {
+            let tx = MockTransaction::eip4844_with_sidecar(sidecar.clone());
+            hashes.push(
+                test_pool
+                    .add_transaction(
+                        TransactionOrigin::External,
+                        TransactionValidationOutcome::Valid {
+                            balance: U256::from(1_000),
+                            state_nonce: 0,
+                            transaction: ValidTransaction::ValidWithSidecar {
+                                transaction: tx,
+                                sidecar: sidecar.clone(),
+                            },
+                            propagate: true,
+                        },
+                    )
+                    .unwrap(),
+            );
+            test_pool.discard_worst();
+        }
+
+        // the discarded transactions are kept in the overflow store
+        assert_eq!(test_pool.size().blob, blob_limit.max_txs);
+        assert_eq!(overflow.len(), 2);
+
+        // no room in the blob pool
+        assert!(test_pool.take_overflowed_blob_transactions().is_empty());
+
+        let remaining =
+            hashes.into_iter().filter(|hash| test_pool.get(hash).is_some()).collect::<Vec<_>>();
+        test_pool.remove_transactions(remaining[..1].to_vec());
+        assert_eq!(test_pool.take_overflowed_blob_transactions().len(), 1);
+        assert_eq!(overflow.len(), 1);
+    }
 }
@@ -183,23 +183,56 @@ impl<T: ParkedOrd> ParkedPool<T> {
     /// Then, for each sender, all transactions for that sender are removed, until the pool limits
     /// have been met.
     ///
+    /// Senders of local transactions are only truncated if the pool is still over the limit after
+    /// all non-local senders have been removed.
+    ///
     /// Any removed transactions are returned.
     pub fn truncate_pool(
         &mut self,
         limit: SubPoolLimit,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let mut removed = Vec::new();
         if !self.exceeds(&limit) {
             // if we are below the limits, we don't need to drop anything
-            return Vec::new()
+            return removed
         }
 
-        let mut removed = Vec::new();
+        // first truncate only non-local senders, returning if the pool end up under the limit
+        self.remove_senders_to_limit(&limit, false, &mut removed);
+        if !self.exceeds(&limit) {
+            return removed
+        }
+
+        // now repeat for local senders, since their transactions must be removed now for the pool
+        // to be under the limit
+        self.remove_senders_to_limit(&limit, true, &mut removed);
+
+        removed
+    }
+
+    /// Removes transactions of the least recently active senders until the pool is under the
+    /// given limit.
+    ///
+    /// If `remove_locals` is false, senders of local transactions are skipped.
+    fn remove_senders_to_limit(
+        &mut self,
+        limit: &SubPoolLimit,
+        remove_locals: bool,
+        removed: &mut Vec<Arc<ValidPoolTransaction<T::Transaction>>>,
+    ) {
+        // senders in the order they should be truncated, least recently active first
+        let senders =
+            self.last_sender_submission.iter().rev().map(|s| s.sender_id).collect::<Vec<_>>();
+
+        for sender_id in senders {
+            if !self.exceeds(limit) {
+                break
+            }
 
-        while limit.is_exceeded(self.len(), self.size()) && !self.last_sender_submission.is_empty()
-        {
-            // NOTE: This will not panic due to `!last_sender_transaction.is_empty()`
-            let sender_id = self.last_sender_submission.last().expect("not empty").sender_id;
             let list = self.get_txs_by_sender(sender_id);
+            if !remove_locals && self.is_local_sender(&list) {
+                continue
+            }
 
             // Drop transactions from this sender until the pool is under limits
             for txid in list.into_iter().rev() {
@@ -207,13 +240,16 @@ impl<T: ParkedOrd> ParkedPool<T> {
                     removed.push(tx);
                 }
 
-                if !self.exceeds(&limit) {
+                if !self.exceeds(limit) {
                     break
                 }
             }
         }
+    }
 
-        removed
+    /// Returns true if any of the given transactions is a local transaction.
+    fn is_local_sender(&self, ids: &[TransactionId]) -> bool {
+        ids.iter().any(|id| self.by_id.get(id).is_some_and(|tx| tx.transaction.is_local()))
     }
 
     fn next_id(&mut self) -> u64 {
@@ -519,7 +555,10 @@ impl<T: PoolTransaction> Ord for QueuedOrd<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::{MockTransaction, MockTransactionFactory, MockTransactionSet};
+    use crate::{
+        test_utils::{MockTransaction, MockTransactionFactory, MockTransactionSet},
+        TransactionOrigin,
+    };
     use reth_primitives::{address, TxType};
     use std::collections::HashSet;
 
@@ -653,6 +692,37 @@ mod tests {
         assert_eq!(parked, expected_parked);
     }
 
+    #[test]
+    fn truncate_parked_keeps_local_senders() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = ParkedPool::<BasefeeOrd<_>>::default();
+
+        let a_sender = address!("000000000000000000000000000000000000000a");
+        let b_sender = address!("000000000000000000000000000000000000000b");
+
+        // A submits its local transactions first, so it is the least recently active sender
+        let a = MockTransactionSet::dependent(a_sender, 0, 2, TxType::Eip1559).into_vec();
+        let b = MockTransactionSet::dependent(b_sender, 0, 2, TxType::Eip1559).into_vec();
+
+        for tx in a.clone() {
+            pool.add_transaction(Arc::new(f.validated_with_origin(TransactionOrigin::Local, tx)));
+        }
+        for tx in b {
+            pool.add_transaction(f.validated_arc(tx));
+        }
+
+        // the remote sender is truncated first, even though it submitted more recently
+        let removed = pool.truncate_pool(SubPoolLimit { max_txs: 2, max_size: usize::MAX });
+        assert_eq!(removed.len(), 2);
+        assert!(removed.iter().all(|tx| tx.sender() == b_sender));
+        assert!(pool.all().all(|tx| tx.is_local()));
+
+        // local transactions are only removed if the pool is still over the limit
+        let removed = pool.truncate_pool(SubPoolLimit { max_txs: 1, max_size: usize::MAX });
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].nonce(), a[1].nonce());
+    }
+
     #[test]
     fn test_truncate_parked_with_large_tx() {
         let mut f = MockTransactionFactory::default();
@@ -1,10 +1,11 @@
 //! The internal transaction pool implementation.
 
 use crate::{
-    config::{LocalTransactionConfig, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
+    config::{EvictionPolicy, LocalTransactionConfig, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind},
     identifier::{SenderId, TransactionId},
     metrics::{AllTransactionsMetrics, TxPoolMetrics},
+    policy::{PolicyTransaction, ReplacementPolicy},
     pool::{
         best::BestTransactions,
         blob::BlobTransactions,
@@ -15,7 +16,7 @@ use crate::{
         AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
     },
     traits::{BestTransactionsAttributes, BlockInfo, PoolSize},
-    PoolConfig, PoolResult, PoolTransaction, PriceBumpConfig, TransactionOrdering,
+    PoolConfig, PoolResult, PoolTransaction, PriceBumpConfig, SubPoolLimit, TransactionOrdering,
     ValidPoolTransaction, U256,
 };
 use reth_primitives::{
@@ -363,6 +364,16 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.config.is_exceeded(self.size())
     }
 
+    /// Returns `true` if the given sub-pool is over its configured limit.
+    fn is_subpool_exceeded(&self, pool: SubPool) -> bool {
+        match pool {
+            SubPool::Queued => self.queued_pool.exceeds(&self.config.queued_limit),
+            SubPool::Pending => self.pending_pool.exceeds(&self.config.pending_limit),
+            SubPool::BaseFee => self.basefee_pool.exceeds(&self.config.basefee_limit),
+            SubPool::Blob => self.blob_pool.exceeds(&self.config.blob_limit),
+        }
+    }
+
     /// Returns the transaction for the given hash.
     pub(crate) fn get(
         &self,
@@ -777,10 +788,20 @@ impl<T: TransactionOrdering> TxPool<T> {
     /// If the current size exceeds the given bounds, the worst transactions are evicted from the
     /// pool and returned.
     ///
+    /// If the [`EvictionPolicy::SenderOverage`] is configured, transactions of the senders that
+    /// occupy the most slots are evicted first. Afterwards the pool-wide
+    /// [`max_total_size`](PoolConfig::max_total_size) is enforced.
+    ///
     /// This returns all transactions that were removed from the entire pool.
     pub(crate) fn discard_worst(&mut self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
         let mut removed = Vec::new();
 
+        if self.config.eviction_policy == EvictionPolicy::SenderOverage {
+            self.discard_sender_overage(&mut removed);
+            self.metrics.evicted_sender_overage_transactions.increment(removed.len() as u64);
+        }
+        let evicted_before_subpool_limits = removed.len();
+
         // Helper macro that discards the worst transactions for the pools
         macro_rules! discard_worst {
             ($this:ident, $removed:ident, [$($limit:ident => $pool:ident),* $(,)*]) => {
@@ -835,10 +856,96 @@ impl<T: TransactionOrdering> TxPool<T> {
                 queued_limit  => queued_pool,
             ]
         );
+        self.metrics
+            .evicted_subpool_limit_transactions
+            .increment((removed.len() - evicted_before_subpool_limits) as u64);
+
+        let evicted_before_total_size = removed.len();
+        self.discard_total_size_overage(&mut removed);
+        self.metrics
+            .evicted_total_size_transactions
+            .increment((removed.len() - evicted_before_total_size) as u64);
 
         removed
     }
 
+    /// Evicts the highest nonce transaction of the sender that exceeds its
+    /// [`fair_account_slots`](PoolConfig::fair_account_slots) the most, for as long as the pool is
+    /// over its limits.
+    ///
+    /// A transaction is only evicted if its sub-pool is over its limit or if the pool exceeds its
+    /// total size budget. Local transactions are never evicted here.
+    fn discard_sender_overage(
+        &mut self,
+        removed: &mut Vec<Arc<ValidPoolTransaction<T::Transaction>>>,
+    ) {
+        while self.is_exceeded() {
+            let total_size_exceeded = self.config.is_total_size_exceeded(self.size().total_size());
+            let fair_account_slots = self.config.fair_account_slots;
+
+            let worst = self
+                .all_transactions
+                .tx_counter
+                .iter()
+                .filter(|(_, count)| **count > fair_account_slots)
+                .filter_map(|(sender, count)| {
+                    let (id, tx) = self.all_transactions.txs_iter(*sender).last()?;
+                    let evictable = !tx.transaction.is_local() &&
+                        (total_size_exceeded || self.is_subpool_exceeded(tx.subpool));
+                    evictable.then_some((*count, *id))
+                })
+                .max_by_key(|(count, id)| (*count, *id));
+
+            let Some((_, id)) = worst else { break };
+
+            trace!(target: "txpool", ?id, "discarding transaction of sender over its fair share of slots");
+
+            // this is the sender's highest nonce transaction, so there are no descendants
+            if let Some(tx) = self.remove_transaction(&id) {
+                removed.push(tx);
+            }
+        }
+    }
+
+    /// Evicts the worst transactions until the combined size of all sub-pools is within the
+    /// [`max_total_size`](PoolConfig::max_total_size).
+    ///
+    /// Transactions are evicted from the queued sub-pool first, then from the basefee, blob and
+    /// finally the pending sub-pool.
+    fn discard_total_size_overage(
+        &mut self,
+        removed: &mut Vec<Arc<ValidPoolTransaction<T::Transaction>>>,
+    ) {
+        let Some(max_total_size) = self.config.max_total_size else { return };
+
+        macro_rules! discard_total_size {
+            ($this:ident, $removed:ident, [$($pool:ident),* $(,)*]) => {
+                $ (
+                let total_size = $this.size().total_size();
+                if total_size > max_total_size {
+                    // shrink this sub-pool by the amount the total budget is exceeded
+                    let limit = SubPoolLimit::new(
+                        usize::MAX,
+                        $this.$pool.size().saturating_sub(total_size - max_total_size),
+                    );
+
+                    for tx in $this.$pool.truncate_pool(limit) {
+                        $this.all_transactions.remove_transaction(tx.id());
+
+                        let id = *tx.id();
+                        $removed.push(tx);
+
+                        // remove all its descendants from the entire pool
+                        $this.remove_descendants(&id, $removed);
+                    }
+                }
+                )*
+            };
+        }
+
+        discard_total_size!(self, removed, [queued_pool, basefee_pool, blob_pool, pending_pool]);
+    }
+
     /// Number of transactions in the entire pool
     pub(crate) fn len(&self) -> usize {
         self.all_transactions.len()
@@ -934,6 +1041,8 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     pending_fees: PendingFees,
     /// Configured price bump settings for replacements
     price_bumps: PriceBumpConfig,
+    /// Custom replacement rule that is used instead of the `price_bumps`, if configured.
+    replacement_policy: Option<Arc<dyn ReplacementPolicy>>,
     /// How to handle [`TransactionOrigin::Local`](crate::TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
     /// All Transactions metrics
@@ -946,6 +1055,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         Self {
             max_account_slots: config.max_account_slots,
             price_bumps: config.price_bumps,
+            replacement_policy: config.replacement_policy.clone(),
             local_transactions_config: config.local_transactions_config.clone(),
             ..Default::default()
         }
@@ -1568,8 +1678,18 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 let maybe_replacement = transaction.as_ref();
 
                 // Ensure the new transaction is not underpriced
-                if Self::is_underpriced(existing_transaction, maybe_replacement, &self.price_bumps)
-                {
+                let underpriced = match &self.replacement_policy {
+                    Some(policy) => !policy.allows_replacement(
+                        &PolicyTransaction::new(&existing_transaction.transaction),
+                        &PolicyTransaction::new(&maybe_replacement.transaction),
+                    ),
+                    None => Self::is_underpriced(
+                        existing_transaction,
+                        maybe_replacement,
+                        &self.price_bumps,
+                    ),
+                };
+                if underpriced {
                     return Err(InsertErr::Underpriced {
                         transaction: pool_tx.transaction,
                         existing: *entry.get().transaction.hash(),
@@ -1715,6 +1835,7 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
             last_seen_block_hash: Default::default(),
             pending_fees: Default::default(),
             price_bumps: Default::default(),
+            replacement_policy: None,
             local_transactions_config: Default::default(),
             metrics: Default::default(),
         }
@@ -1854,7 +1975,6 @@ mod tests {
     use crate::{
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
         traits::TransactionOrigin,
-        SubPoolLimit,
     };
 
     #[test]
@@ -2394,6 +2514,51 @@ mod tests {
         assert_eq!(pool.len(), 1);
     }
 
+    #[test]
+    fn insert_replace_custom_replacement_policy() {
+        /// Allows any replacement that does not lower the max fee.
+        #[derive(Debug)]
+        struct NoDecreasePolicy;
+
+        impl ReplacementPolicy for NoDecreasePolicy {
+            fn allows_replacement(
+                &self,
+                existing: &PolicyTransaction<'_>,
+                replacement: &PolicyTransaction<'_>,
+            ) -> bool {
+                replacement.max_fee_per_gas >= existing.max_fee_per_gas
+            }
+        }
+
+        let on_chain_balance = U256::ZERO;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions {
+            replacement_policy: Some(Arc::new(NoDecreasePolicy)),
+            ..Default::default()
+        };
+        let mut tx = MockTransaction::eip1559().inc_limit();
+        tx.set_priority_fee(100);
+        tx.set_max_fee(100);
+        let first = f.validated(tx.clone());
+        let _ = pool.insert_tx(first.clone(), on_chain_balance, on_chain_nonce).unwrap();
+
+        // a lower max fee is rejected by the policy
+        let mut replacement = f.validated(tx.rng_hash());
+        replacement.transaction.set_max_fee(99);
+        let err =
+            pool.insert_tx(replacement.clone(), on_chain_balance, on_chain_nonce).unwrap_err();
+        assert!(matches!(err, InsertErr::Underpriced { .. }));
+        assert!(pool.contains(first.hash()));
+
+        // the same fees are not enough for the default price bump, but the policy allows it
+        replacement.transaction.set_max_fee(100);
+        let res = pool.insert_tx(replacement.clone(), on_chain_balance, on_chain_nonce).unwrap();
+        assert_eq!(res.replaced_tx.unwrap().0.hash(), first.hash());
+        assert!(pool.contains(replacement.hash()));
+        assert_eq!(pool.len(), 1);
+    }
+
     #[test]
     fn insert_conflicting_type_normal_to_blob() {
         let on_chain_balance = U256::from(10_000);
@@ -2817,6 +2982,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn discard_sender_overage_first() {
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            pending_limit: SubPoolLimit::new(6, usize::MAX),
+            fair_account_slots: 2,
+            eviction_policy: EvictionPolicy::SenderOverage,
+            ..Default::default()
+        };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+
+        // a sender with five well paying transactions
+        let mut spam = vec![MockTransaction::eip1559().inc_price_by(100)];
+        for _ in 1..5 {
+            let next = spam.last().unwrap().next();
+            spam.push(next);
+        }
+        for tx in &spam {
+            pool.add_transaction(f.validated(tx.clone()), U256::MAX, 0).unwrap();
+        }
+
+        // a sender with two cheaper transactions
+        let tx = MockTransaction::eip1559();
+        pool.add_transaction(f.validated(tx.clone()), U256::MAX, 0).unwrap();
+        pool.add_transaction(f.validated(tx.next()), U256::MAX, 0).unwrap();
+        assert_eq!(pool.size().pending, 7);
+
+        // the sender that exceeds its fair share the most is evicted first, despite its fees
+        let removed = pool.discard_worst();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].sender(), spam[4].sender());
+        assert_eq!(removed[0].nonce(), spam[4].nonce());
+        pool.assert_invariants();
+    }
+
+    #[test]
+    fn discard_total_size_overage() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), PoolConfig::default());
+
+        let mut pending = MockTransaction::eip1559();
+        pending.set_size(100);
+        pool.add_transaction(f.validated(pending), U256::MAX, 0).unwrap();
+        // nonce gap, so this is queued
+        let mut queued = MockTransaction::eip1559().inc_nonce();
+        queued.set_size(100);
+        pool.add_transaction(f.validated(queued.clone()), U256::MAX, 0).unwrap();
+        assert_eq!(pool.size().queued, 1);
+        assert!(pool.discard_worst().is_empty());
+
+        // the queued transaction is evicted first once the total budget is exceeded
+        pool.config.max_total_size = Some(pool.size().total_size() - 1);
+        let removed = pool.discard_worst();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].sender(), queued.sender());
+        assert_eq!(pool.size().pending, 1);
+        pool.assert_invariants();
+    }
+
     #[test]
     fn discard_blobs_at_capacity() {
         let mut f = MockTransactionFactory::default();
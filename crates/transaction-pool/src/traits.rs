@@ -139,6 +139,10 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// commitments/proofs) for eip-4844 transactions inserted into the pool
     fn blob_transaction_sidecars_listener(&self) -> Receiver<NewBlobSidecar>;
 
+    /// Returns a new [Receiver] that yields the outcome of re-injecting the transactions of
+    /// reorged blocks, once per handled reorg.
+    fn reorg_reinjection_listener(&self) -> Receiver<ReorgReinjectionEvent>;
+
     /// Returns a new stream that yields new valid transactions added to the pool
     /// depending on the given [TransactionListenerKind] argument.
     fn new_transactions_listener_for(
@@ -367,7 +371,7 @@ pub trait TransactionPool: Send + Sync + Clone {
 
     /// Returns all pending transactions that where submitted as [TransactionOrigin::Local]
     fn get_local_pending_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
-        self.get_transactions_by_origin(TransactionOrigin::Local)
+        self.get_pending_transactions_by_origin(TransactionOrigin::Local)
     }
 
     /// Returns all pending transactions that where submitted as [TransactionOrigin::Private]
@@ -445,6 +449,17 @@ pub trait TransactionPoolExt: TransactionPool {
 
     /// Maintenance function to cleanup blobs that are no longer needed.
     fn cleanup_blobs(&self);
+
+    /// Takes the blob transactions that were moved to the
+    /// [`BlobOverflowStore`](crate::blobstore::BlobOverflowStore) because the blob sub-pool was
+    /// full, as many as currently fit into the blob sub-pool.
+    ///
+    /// The returned transactions are no longer tracked and need to be added to the pool again.
+    fn take_overflowed_blob_transactions(&self) -> Vec<PooledTransactionsElementEcRecovered>;
+
+    /// Notifies all [`TransactionPool::reorg_reinjection_listener`] subscribers about the outcome
+    /// of re-injecting the transactions of reorged blocks.
+    fn on_reorg_reinjection(&self, event: ReorgReinjectionEvent);
 }
 
 /// Determines what kind of new transactions should be emitted by a stream of transactions.
@@ -570,6 +585,37 @@ pub struct NewBlobSidecar {
     pub sidecar: Arc<BlobTransactionSidecar>,
 }
 
+/// The outcome of re-injecting the transactions that were mined in blocks that are no longer
+/// canonical after a reorg.
+///
+/// Transactions are revalidated against the state of the new canonical chain, so not all of them
+/// make it back into the pool.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReorgReinjectionEvent {
+    /// Hash of the tip of the new canonical chain.
+    pub new_tip: B256,
+    /// Transactions that were added back to the pool.
+    pub reinjected: Vec<TxHash>,
+    /// Transactions that are invalid against the new canonical state, or could not be restored,
+    /// e.g. blob transactions without a sidecar.
+    pub dropped_invalid: Vec<TxHash>,
+    /// Transactions whose nonce is already taken by another transaction of the same sender,
+    /// either mined in the new chain or in the pool.
+    pub dropped_replaced: Vec<TxHash>,
+}
+
+impl ReorgReinjectionEvent {
+    /// Returns the total number of transactions that were re-injected or dropped.
+    pub fn len(&self) -> usize {
+        self.reinjected.len() + self.dropped_invalid.len() + self.dropped_replaced.len()
+    }
+
+    /// Returns `true` if no transactions were handled.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 /// Where the transaction originates from.
 ///
 /// Depending on where the transaction was picked up, it affects how the transaction is handled
@@ -1257,6 +1303,12 @@ pub struct PoolSize {
 // === impl PoolSize ===
 
 impl PoolSize {
+    /// Returns the combined size (in bytes) of the transactions in all sub-pools.
+    #[inline]
+    pub const fn total_size(&self) -> usize {
+        self.pending_size + self.basefee_size + self.queued_size + self.blob_size
+    }
+
     /// Asserts that the invariants of the pool size are met.
     #[cfg(test)]
     pub(crate) fn assert_invariants(&self) {
@@ -0,0 +1,30 @@
+use assert_matches::assert_matches;
+use reth_transaction_pool::{
+    error::{InvalidPoolTransactionError, PoolErrorKind},
+    policy::MinPriorityFee,
+    test_utils::{MockTransactionFactory, TestPoolBuilder},
+    PoolConfig, TransactionOrigin, TransactionPool,
+};
+use std::sync::Arc;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_admission_policy() {
+    let config =
+        PoolConfig { admission_policy: Some(Arc::new(MinPriorityFee(100))), ..Default::default() };
+    let txpool = TestPoolBuilder::default().with_config(config);
+    let mut mock_tx_factory = MockTransactionFactory::default();
+
+    let rejected = mock_tx_factory.create_eip1559().transaction.with_priority_fee(99);
+    let err = txpool.add_transaction(TransactionOrigin::External, rejected).await.unwrap_err();
+    assert_matches!(
+        err.kind,
+        PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Other(_))
+    );
+    assert!(!err.is_bad_transaction());
+    assert!(txpool.is_empty());
+
+    let admitted =
+        mock_tx_factory.create_eip1559().transaction.with_priority_fee(100).with_max_fee(1_000);
+    assert_matches!(txpool.add_transaction(TransactionOrigin::External, admitted).await, Ok(_));
+    assert_eq!(txpool.len(), 1);
+}
@@ -23,6 +23,12 @@ pub use storage::StorageTrieEntry;
 mod subnode;
 pub use subnode::StoredSubNode;
 
+mod verkle;
+pub use verkle::{
+    StoredVerkleNode, VerkleAccountProof, VerkleIpaProof, VerkleLeafBeforeBlock, VerklePath,
+    VerkleProof,
+};
+
 mod proofs;
 #[cfg(any(test, feature = "test-utils"))]
 pub use proofs::triehash;
@@ -30,4 +36,7 @@ pub use proofs::*;
 
 pub mod root;
 
+mod range_proof;
+pub use range_proof::{verify_range_proof, RangeProofError};
+
 pub use alloy_trie::{nodes::*, proof, BranchNodeCompact, HashBuilder, TrieMask, EMPTY_ROOT_HASH};
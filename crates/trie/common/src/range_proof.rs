@@ -0,0 +1,332 @@
+//! Merkle proofs of ranges of trie leaves.
+
+use crate::Nibbles;
+use alloy_primitives::{keccak256, Bytes, B256};
+use alloy_rlp::Decodable;
+use alloy_trie::{
+    nodes::{word_rlp, TrieNode},
+    HashBuilder, EMPTY_ROOT_HASH,
+};
+use std::collections::HashMap;
+
+/// An error of [`verify_range_proof`].
+#[derive(Clone, Debug, PartialEq, Eq, derive_more::Display)]
+pub enum RangeProofError {
+    /// The leaves are not in ascending order, or start before the first key of the range.
+    #[display("leaves are not sorted or start before the first key")]
+    UnsortedLeaves,
+    /// A node on the boundaries of the range is missing from the proof.
+    #[display("proof is missing node {_0}")]
+    MissingNode(B256),
+    /// A node of the proof can't be decoded.
+    #[display("failed to decode proof node: {_0}")]
+    Decode(alloy_rlp::Error),
+    /// The leaves and the proof don't match the root.
+    #[display("root mismatch: got {got}, expected {expected}")]
+    RootMismatch {
+        /// The root computed from the leaves and the proof.
+        got: B256,
+        /// The expected root.
+        expected: B256,
+    },
+}
+
+impl std::error::Error for RangeProofError {}
+
+impl From<alloy_rlp::Error> for RangeProofError {
+    fn from(err: alloy_rlp::Error) -> Self {
+        Self::Decode(err)
+    }
+}
+
+/// Verifies that `leaves` are all leaves of the trie with the given root, from `first_key` up to
+/// the last of the leaves.
+///
+/// The proof has to contain the nodes on the paths to `first_key` and to the last leaf, which
+/// provide the hashes of the subtries outside of the range. Without a proof, the leaves have to be
+/// all leaves of the trie.
+///
+/// Returns whether the trie has more leaves after the last one.
+pub fn verify_range_proof(
+    root: B256,
+    first_key: B256,
+    leaves: &[(B256, Vec<u8>)],
+    proof: &[Bytes],
+) -> Result<bool, RangeProofError> {
+    if leaves.first().is_some_and(|(key, _)| *key < first_key) ||
+        leaves.windows(2).any(|pair| pair[0].0 >= pair[1].0)
+    {
+        return Err(RangeProofError::UnsortedLeaves)
+    }
+
+    let mut hash_builder = HashBuilder::default();
+    if proof.is_empty() || (root == EMPTY_ROOT_HASH && leaves.is_empty()) {
+        for (key, value) in leaves {
+            hash_builder.add_leaf(Nibbles::unpack(key), value);
+        }
+        return check_root(hash_builder.root(), root).map(|()| false)
+    }
+
+    let first = Nibbles::unpack(first_key);
+    let last = leaves.last().map_or_else(|| first.clone(), |(key, _)| Nibbles::unpack(key));
+    let mut range = RangeBoundaries {
+        nodes: proof.iter().map(|node| (keccak256(node), node)).collect(),
+        first,
+        last,
+        left: Vec::new(),
+        right: Vec::new(),
+    };
+    range.walk(Nibbles::default(), &word_rlp(&root))?;
+
+    for element in range.left {
+        element.add_to(&mut hash_builder);
+    }
+    for (key, value) in leaves {
+        hash_builder.add_leaf(Nibbles::unpack(key), value);
+    }
+    let more = !range.right.is_empty();
+    for element in range.right {
+        element.add_to(&mut hash_builder);
+    }
+    check_root(hash_builder.root(), root).map(|()| more)
+}
+
+/// Returns an error if the computed root doesn't match the expected one.
+fn check_root(got: B256, expected: B256) -> Result<(), RangeProofError> {
+    if got == expected {
+        Ok(())
+    } else {
+        Err(RangeProofError::RootMismatch { got, expected })
+    }
+}
+
+/// The position of the keys below a path, relative to the range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Position {
+    /// All keys are before the range.
+    Left,
+    /// All keys are in the range.
+    Inside,
+    /// All keys are after the range.
+    Right,
+    /// Some keys are in the range, and some are not.
+    Overlapping,
+}
+
+/// A subtrie outside of the range, which is taken from the proof.
+#[derive(Debug)]
+enum Element {
+    /// A subtrie by the hash of its root node.
+    Hash(Nibbles, B256),
+    /// A leaf with its RLP encoded value.
+    Leaf(Nibbles, Vec<u8>),
+}
+
+impl Element {
+    fn add_to(self, hash_builder: &mut HashBuilder) {
+        match self {
+            Self::Hash(path, hash) => hash_builder.add_branch(path, hash, false),
+            Self::Leaf(key, value) => hash_builder.add_leaf(key, &value),
+        }
+    }
+}
+
+/// Collects the subtries outside of a range from the proof nodes on its boundaries.
+#[derive(Debug)]
+struct RangeBoundaries<'a> {
+    /// The proof nodes by their hashes.
+    nodes: HashMap<B256, &'a Bytes>,
+    /// The first key of the range.
+    first: Nibbles,
+    /// The last key of the range.
+    last: Nibbles,
+    /// The subtries before the range, in ascending order.
+    left: Vec<Element>,
+    /// The subtries after the range, in ascending order.
+    right: Vec<Element>,
+}
+
+impl RangeBoundaries<'_> {
+    /// Walks the node at the path, given by its RLP encoded reference in its parent.
+    fn walk(&mut self, path: Nibbles, reference: &[u8]) -> Result<(), RangeProofError> {
+        let hash =
+            (reference.len() == B256::len_bytes() + 1).then(|| B256::from_slice(&reference[1..]));
+        let position = self.position(&path);
+        match (position, hash) {
+            (Position::Inside, _) => return Ok(()),
+            (Position::Left | Position::Right, Some(hash)) => {
+                self.push(position, Element::Hash(path, hash));
+                return Ok(())
+            }
+            _ => {}
+        }
+
+        // The node overlaps the range, or is embedded in its parent.
+        let node = match hash {
+            Some(hash) => {
+                let node = self.nodes.get(&hash).ok_or(RangeProofError::MissingNode(hash))?;
+                TrieNode::decode(&mut &node[..])?
+            }
+            None => TrieNode::decode(&mut &reference[..])?,
+        };
+        match node {
+            TrieNode::Branch(branch) => {
+                let nibbles = (0..16).filter(|nibble| branch.state_mask.is_bit_set(*nibble));
+                for (nibble, child) in nibbles.zip(&branch.stack) {
+                    let mut child_path = path.clone();
+                    child_path.push(nibble);
+                    self.walk(child_path, child)?;
+                }
+            }
+            TrieNode::Extension(extension) => {
+                let mut child_path = path;
+                child_path.extend_from_slice(&extension.key);
+                self.walk(child_path, &extension.child)?;
+            }
+            TrieNode::Leaf(leaf) => {
+                let mut key = path;
+                key.extend_from_slice(&leaf.key);
+                let position = self.position(&key);
+                if position != Position::Inside {
+                    self.push(position, Element::Leaf(key, leaf.value));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the position of the keys below the path.
+    fn position(&self, path: &Nibbles) -> Position {
+        let first = &self.first[..path.len().min(self.first.len())];
+        let last = &self.last[..path.len().min(self.last.len())];
+        if path[..] < *first {
+            return Position::Left
+        }
+        if path[..] > *last {
+            return Position::Right
+        }
+
+        // The smallest key below the path is the path padded with zeros, the largest one is the
+        // path padded with `0xf`.
+        let after_first =
+            path[..] > *first || self.first[first.len()..].iter().all(|nibble| *nibble == 0);
+        let before_last =
+            path[..] < *last || self.last[last.len()..].iter().all(|nibble| *nibble == 0xf);
+        if after_first && before_last {
+            Position::Inside
+        } else {
+            Position::Overlapping
+        }
+    }
+
+    fn push(&mut self, position: Position, element: Element) {
+        if position == Position::Left {
+            self.left.push(element)
+        } else {
+            self.right.push(element)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use alloy_trie::proof::ProofRetainer;
+
+    /// Returns the sorted leaves of a trie.
+    fn leaves(count: u64) -> Vec<(B256, Vec<u8>)> {
+        let mut leaves = (0..count)
+            .map(|index| (keccak256(index.to_be_bytes()), alloy_rlp::encode(index + 1)))
+            .collect::<Vec<_>>();
+        leaves.sort();
+        leaves
+    }
+
+    /// Returns the root of the trie with the leaves, and the proof of the keys.
+    fn root_and_proof(leaves: &[(B256, Vec<u8>)], keys: &[B256]) -> (B256, Vec<Bytes>) {
+        let targets = keys.iter().map(Nibbles::unpack).collect();
+        let mut hash_builder =
+            HashBuilder::default().with_proof_retainer(ProofRetainer::new(targets));
+        for (key, value) in leaves {
+            hash_builder.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = hash_builder.root();
+        (root, hash_builder.take_proofs().into_values().collect())
+    }
+
+    #[test]
+    fn full_range() {
+        let leaves = leaves(100);
+        let (root, _) = root_and_proof(&leaves, &[]);
+        assert_eq!(verify_range_proof(root, B256::ZERO, &leaves, &[]), Ok(false));
+        assert!(matches!(
+            verify_range_proof(root, B256::ZERO, &leaves[1..], &[]),
+            Err(RangeProofError::RootMismatch { .. })
+        ));
+        assert_eq!(verify_range_proof(EMPTY_ROOT_HASH, B256::ZERO, &[], &[]), Ok(false));
+    }
+
+    #[test]
+    fn partial_ranges() {
+        let leaves = leaves(100);
+
+        // The first key is a leaf.
+        let range = &leaves[10..20];
+        let (root, proof) = root_and_proof(&leaves, &[range[0].0, range[9].0]);
+        assert_eq!(verify_range_proof(root, range[0].0, range, &proof), Ok(true));
+
+        // The first key is between two leaves.
+        let first_key = B256::from(U256::from_be_bytes(leaves[9].0 .0) + U256::from(1));
+        let (root, proof) = root_and_proof(&leaves, &[first_key, range[9].0]);
+        assert_eq!(verify_range_proof(root, first_key, range, &proof), Ok(true));
+
+        // The range reaches the last leaf.
+        let range = &leaves[90..];
+        let (root, proof) = root_and_proof(&leaves, &[range[0].0, range[9].0]);
+        assert_eq!(verify_range_proof(root, range[0].0, range, &proof), Ok(false));
+
+        // There are no leaves after the first key.
+        let first_key = B256::repeat_byte(0xff);
+        let (root, proof) = root_and_proof(&leaves, &[first_key]);
+        assert_eq!(verify_range_proof(root, first_key, &[], &proof), Ok(false));
+    }
+
+    #[test]
+    fn invalid_ranges() {
+        let leaves = leaves(100);
+        let range = &leaves[10..20];
+        let (root, proof) = root_and_proof(&leaves, &[range[0].0, range[9].0]);
+
+        // A leaf is missing.
+        let mut missing = range.to_vec();
+        missing.remove(5);
+        assert!(matches!(
+            verify_range_proof(root, range[0].0, &missing, &proof),
+            Err(RangeProofError::RootMismatch { .. })
+        ));
+
+        // A leaf has a different value.
+        let mut modified = range.to_vec();
+        modified[5].1 = alloy_rlp::encode(0u64);
+        assert!(matches!(
+            verify_range_proof(root, range[0].0, &modified, &proof),
+            Err(RangeProofError::RootMismatch { .. })
+        ));
+
+        // The first leaf is missing.
+        assert!(matches!(
+            verify_range_proof(root, range[0].0, &range[1..], &proof),
+            Err(RangeProofError::RootMismatch { .. })
+        ));
+
+        // The proof of the last leaf is missing.
+        let (root, proof) = root_and_proof(&leaves, &[range[0].0]);
+        assert!(verify_range_proof(root, range[0].0, range, &proof).is_err());
+
+        assert_eq!(
+            verify_range_proof(root, range[1].0, range, &proof),
+            Err(RangeProofError::UnsortedLeaves)
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use alloy_primitives::{Address, Bytes, FixedBytes, B256, B512};
+use bytes::Buf;
+use derive_more::Deref;
+use reth_codecs::Compact;
+use serde::{Deserialize, Serialize};
+
+/// The path of a verkle trie node from the root, which is the common prefix of the stems below
+/// it.
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Deref,
+)]
+pub struct VerklePath(pub Vec<u8>);
+
+impl From<&[u8]> for VerklePath {
+    #[inline]
+    fn from(value: &[u8]) -> Self {
+        Self(value.to_vec())
+    }
+}
+
+impl Compact for VerklePath {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(&self.0);
+        self.0.len()
+    }
+
+    fn from_compact(mut buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let path = buf[..len].to_vec();
+        buf.advance(len);
+        (Self(path), buf)
+    }
+}
+
+/// A node of the verkle trie.
+///
+/// Commitments are stored uncompressed, as 32 bytes of the big endian `x` coordinate followed by
+/// 32 bytes of the big endian `y` coordinate, so that loading a node doesn't need a square root.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StoredVerkleNode {
+    /// An internal node, committing to the commitments of its 256 children.
+    Internal {
+        /// The commitment of the node.
+        commitment: B512,
+    },
+    /// A stem node, committing to the 256 leaves sharing its stem.
+    Stem {
+        /// The 31 byte stem of the leaves.
+        stem: FixedBytes<31>,
+        /// The commitment to the leaves with sub indices below 128.
+        c1: B512,
+        /// The commitment to the leaves with sub indices from 128.
+        c2: B512,
+        /// The commitment of the node.
+        commitment: B512,
+    },
+}
+
+impl StoredVerkleNode {
+    /// Returns the commitment of the node.
+    pub const fn commitment(&self) -> &B512 {
+        match self {
+            Self::Internal { commitment } | Self::Stem { commitment, .. } => commitment,
+        }
+    }
+}
+
+impl Compact for StoredVerkleNode {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        match self {
+            Self::Internal { commitment } => {
+                buf.put_u8(0);
+                buf.put_slice(commitment.as_slice());
+                1 + 64
+            }
+            Self::Stem { stem, c1, c2, commitment } => {
+                buf.put_u8(1);
+                buf.put_slice(stem.as_slice());
+                buf.put_slice(c1.as_slice());
+                buf.put_slice(c2.as_slice());
+                buf.put_slice(commitment.as_slice());
+                1 + 31 + 64 * 3
+            }
+        }
+    }
+
+    fn from_compact(mut buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let node = match buf.get_u8() {
+            0 => Self::Internal { commitment: B512::from_slice(&buf[..64]) },
+            1 => Self::Stem {
+                stem: FixedBytes::from_slice(&buf[..31]),
+                c1: B512::from_slice(&buf[31..95]),
+                c2: B512::from_slice(&buf[95..159]),
+                commitment: B512::from_slice(&buf[159..223]),
+            },
+            _ => unreachable!("Junk data in database: unknown verkle node type"),
+        };
+        let len = match node {
+            Self::Internal { .. } => 64,
+            Self::Stem { .. } => 31 + 64 * 3,
+        };
+        buf.advance(len);
+        (node, buf)
+    }
+}
+
+/// A leaf of the verkle trie before a block changed it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerkleLeafBeforeBlock {
+    /// The tree key of the leaf.
+    pub key: B256,
+    /// The value of the leaf, if it existed.
+    pub value: Option<B256>,
+}
+
+// NOTE: The key is encoded first and uncompressed, so that it can be used as a subkey.
+impl Compact for VerkleLeafBeforeBlock {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.key.as_slice());
+        if let Some(value) = self.value {
+            buf.put_slice(value.as_slice());
+            64
+        } else {
+            32
+        }
+    }
+
+    fn from_compact(mut buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let key = B256::from_slice(&buf[..32]);
+        buf.advance(32);
+        let value = (len > 32).then(|| {
+            let value = B256::from_slice(&buf[..32]);
+            buf.advance(32);
+            value
+        });
+        (Self { key, value }, buf)
+    }
+}
+
+/// A proof of the values of verkle trie leaves: a multiproof of the openings of the commitments
+/// on the paths to their stems.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerkleProof {
+    /// The stems of the stem nodes found instead of the proven stems, in the order they are first
+    /// needed.
+    pub other_stems: Vec<FixedBytes<31>>,
+    /// The depth of the stem node or missing child of each proven stem, in ascending order of the
+    /// stems, shifted left by 3 bits and combined with its extension status: 0 if the stem is
+    /// absent, 1 if another stem was found and 2 if the stem is present.
+    pub depth_extension_present: Bytes,
+    /// The serialized commitments of the nodes on the paths to the proven stems, except the root,
+    /// in the order they are opened.
+    pub commitments_by_path: Vec<B256>,
+    /// The serialized commitment to the quotient polynomial of the multiproof.
+    pub d: B256,
+    /// The inner product argument of the multiproof.
+    pub ipa_proof: VerkleIpaProof,
+}
+
+/// The inner product argument of a [`VerkleProof`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerkleIpaProof {
+    /// The serialized left commitments of the rounds.
+    pub cl: Vec<B256>,
+    /// The serialized right commitments of the rounds.
+    pub cr: Vec<B256>,
+    /// The little endian scalar the committed vector is folded into.
+    pub final_evaluation: B256,
+}
+
+/// The verkle trie leaves of an account and its storage slots with their proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerkleAccountProof {
+    /// The address of the account.
+    pub address: Address,
+    /// The tree keys of the proven leaves with their values, if they exist.
+    pub leaves: Vec<(B256, Option<B256>)>,
+    /// The proof of the leaves.
+    pub proof: VerkleProof,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verkle_node_roundtrip() {
+        let nodes = [
+            StoredVerkleNode::Internal { commitment: B512::repeat_byte(1) },
+            StoredVerkleNode::Stem {
+                stem: FixedBytes::repeat_byte(2),
+                c1: B512::repeat_byte(3),
+                c2: B512::repeat_byte(4),
+                commitment: B512::repeat_byte(5),
+            },
+        ];
+        for node in nodes {
+            let mut buf = Vec::new();
+            let len = node.to_compact(&mut buf);
+            assert_eq!(StoredVerkleNode::from_compact(&buf, len).0, node);
+        }
+
+        for value in [None, Some(B256::repeat_byte(7))] {
+            let leaf = VerkleLeafBeforeBlock { key: B256::repeat_byte(6), value };
+            let mut buf = Vec::new();
+            let len = leaf.to_compact(&mut buf);
+            assert_eq!(VerkleLeafBeforeBlock::from_compact(&buf, len).0, leaf);
+        }
+    }
+}
@@ -3,7 +3,8 @@ use reth_db_api::transaction::DbTx;
 use reth_execution_errors::StateProofError;
 use reth_primitives::{Address, B256};
 use reth_trie::{hashed_cursor::HashedPostStateCursorFactory, proof::Proof, HashedPostState};
-use reth_trie_common::AccountProof;
+use reth_trie_common::{AccountProof, MultiProof};
+use std::collections::HashMap;
 
 /// Extends [`Proof`] with operations specific for working with a database transaction.
 pub trait DatabaseProof<'a, TX> {
@@ -17,6 +18,14 @@ pub trait DatabaseProof<'a, TX> {
         address: Address,
         slots: &[B256],
     ) -> Result<AccountProof, StateProofError>;
+
+    /// Generates the state multiproof for target hashed accounts and hashed slots on top of this
+    /// [`HashedPostState`].
+    fn overlay_multiproof(
+        tx: &'a TX,
+        post_state: HashedPostState,
+        targets: HashMap<B256, Vec<B256>>,
+    ) -> Result<MultiProof, StateProofError>;
 }
 
 impl<'a, TX: DbTx> DatabaseProof<'a, TX>
@@ -42,4 +51,20 @@ impl<'a, TX: DbTx> DatabaseProof<'a, TX>
             .with_prefix_sets_mut(prefix_sets)
             .account_proof(address, slots)
     }
+
+    fn overlay_multiproof(
+        tx: &'a TX,
+        post_state: HashedPostState,
+        targets: HashMap<B256, Vec<B256>>,
+    ) -> Result<MultiProof, StateProofError> {
+        let prefix_sets = post_state.construct_prefix_sets();
+        let sorted = post_state.into_sorted();
+        let hashed_cursor_factory =
+            HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), &sorted);
+        Self::from_tx(tx)
+            .with_hashed_cursor_factory(hashed_cursor_factory)
+            .with_prefix_sets_mut(prefix_sets)
+            .with_targets(targets)
+            .multiproof()
+    }
 }
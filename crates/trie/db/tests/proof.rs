@@ -7,10 +7,11 @@ use reth_provider::{
     test_utils::create_test_provider_factory, HashingWriter, ProviderFactory, TrieWriter,
 };
 use reth_storage_errors::provider::ProviderResult;
-use reth_trie::{proof::Proof, Nibbles, StateRoot};
+use reth_trie::{proof::Proof, HashedPostState, Nibbles, StateRoot};
 use reth_trie_common::{AccountProof, StorageProof};
 use reth_trie_db::{DatabaseProof, DatabaseStateRoot};
 use std::{
+    collections::HashSet,
     str::FromStr,
     sync::{Arc, LazyLock},
 };
@@ -131,6 +132,43 @@ fn testspec_proofs() {
     }
 }
 
+#[test]
+fn testspec_multiproof() {
+    // Create test database and insert genesis accounts.
+    let factory = create_test_provider_factory();
+    let root = insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+    let targets = [
+        "0x2031f89b3ea8014eb51a78c316e42af3e0d7695f",
+        "0x33f0fc440b8477fcfbe9d0bf8649e7dea9baedb2",
+        "0x62b0dd4aab2b1a0a04e279e2b828791a10755528",
+        "0x1ed9b1dd266b607ee278726d324b855a093394a6",
+    ]
+    .map(|target| Address::from_str(target).unwrap());
+
+    let provider = factory.provider().unwrap();
+    let multiproof = Proof::overlay_multiproof(
+        provider.tx_ref(),
+        HashedPostState::default(),
+        targets.iter().map(|target| (keccak256(target), Vec::new())).collect(),
+    )
+    .unwrap();
+
+    let mut proof_nodes = 0;
+    for target in targets {
+        let expected = Proof::from_tx(provider.tx_ref()).account_proof(target, &[]).unwrap();
+        let account_proof = multiproof.account_proof(target, &[]).unwrap();
+        similar_asserts::assert_eq!(account_proof, expected);
+        assert_eq!(account_proof.verify(root), Ok(()));
+        proof_nodes += account_proof.proof.len();
+    }
+
+    // The root and the shared branch nodes are only included once.
+    let unique_nodes = multiproof.account_subtree.values().collect::<HashSet<_>>();
+    assert_eq!(unique_nodes.len(), multiproof.account_subtree.len());
+    assert!(multiproof.account_subtree.len() < proof_nodes);
+}
+
 #[test]
 fn testspec_empty_storage_proof() {
     // Create test database and insert genesis accounts.
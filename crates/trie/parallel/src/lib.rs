@@ -21,6 +21,10 @@ pub mod async_root;
 #[cfg(feature = "parallel")]
 pub mod parallel_root;
 
+/// Cursors restricted to a single account subtrie.
+#[cfg(feature = "parallel")]
+mod subtrie;
+
 /// Parallel state root metrics.
 #[cfg(feature = "metrics")]
 pub mod metrics;
@@ -1,31 +1,44 @@
 #[cfg(feature = "metrics")]
 use crate::metrics::ParallelStateRootMetrics;
-use crate::{stats::ParallelTrieTracker, storage_root_targets::StorageRootTargets};
-use alloy_rlp::{BufMut, Encodable};
-use rayon::prelude::*;
+use crate::{
+    stats::ParallelTrieTracker,
+    storage_root_targets::StorageRootTargets,
+    subtrie::{SubtrieHashedCursor, SubtrieTrieCursor},
+};
+use alloy_rlp::{BufMut, Decodable, Encodable};
+use rayon::{prelude::*, ThreadPool};
 use reth_db_api::database::Database;
 use reth_execution_errors::StorageRootError;
-use reth_primitives::B256;
+use reth_primitives::{Account, B256};
 use reth_provider::{providers::ConsistentDbView, DatabaseProviderFactory, ProviderError};
 use reth_trie::{
-    hashed_cursor::{HashedCursorFactory, HashedPostStateCursorFactory},
+    hashed_cursor::{HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory},
     node_iter::{TrieElement, TrieNodeIter},
-    trie_cursor::TrieCursorFactory,
-    updates::TrieUpdates,
+    prefix_set::PrefixSetMut,
+    trie_cursor::{TrieCursor, TrieCursorFactory},
+    updates::{StorageTrieUpdates, TrieUpdates},
     walker::TrieWalker,
-    HashBuilder, HashedPostState, Nibbles, StorageRoot, TrieAccount,
+    HashBuilder, HashedPostState, Nibbles, StorageRoot, TrieAccount, TrieNode,
 };
+use reth_trie_common::proof::ProofRetainer;
 use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 use thiserror::Error;
 use tracing::*;
 
+/// Storage root, number of updated storage trie nodes and storage trie updates of an account.
+type StorageRootResult = (B256, usize, StorageTrieUpdates);
+
 /// Parallel incremental state root calculator.
 ///
 /// The calculator starts off by pre-computing storage roots of changed
 /// accounts in parallel. Once that's done, it proceeds to walking the state
 /// trie retrieving the pre-computed storage roots when needed.
 ///
+/// If a thread pool is provided, the account trie is partitioned by the first nibble
+/// of the hashed address and all 16 subtries are walked concurrently on the pool.
+/// The subtrie roots are then merged into the state root.
+///
 /// Internally, the calculator uses [`ConsistentDbView`] since
 /// it needs to rely on database state saying the same until
 /// the last transaction is open.
@@ -38,6 +51,8 @@ pub struct ParallelStateRoot<DB, Provider> {
     view: ConsistentDbView<DB, Provider>,
     /// Changed hashed state.
     hashed_state: HashedPostState,
+    /// Thread pool to partition the account trie on.
+    thread_pool: Option<Arc<ThreadPool>>,
     /// Parallel state root metrics.
     #[cfg(feature = "metrics")]
     metrics: ParallelStateRootMetrics,
@@ -49,10 +64,17 @@ impl<DB, Provider> ParallelStateRoot<DB, Provider> {
         Self {
             view,
             hashed_state,
+            thread_pool: None,
             #[cfg(feature = "metrics")]
             metrics: ParallelStateRootMetrics::default(),
         }
     }
+
+    /// Set the thread pool to compute the account subtries on.
+    pub fn with_thread_pool(mut self, thread_pool: Arc<ThreadPool>) -> Self {
+        self.thread_pool = Some(thread_pool);
+        self
+    }
 }
 
 impl<DB, Provider> ParallelStateRoot<DB, Provider>
@@ -75,14 +97,25 @@ where
     fn calculate(
         self,
         retain_updates: bool,
+    ) -> Result<(B256, TrieUpdates), ParallelStateRootError> {
+        match self.thread_pool.clone() {
+            Some(thread_pool) => thread_pool.install(|| self.calculate_inner(retain_updates)),
+            None => self.calculate_inner(retain_updates),
+        }
+    }
+
+    fn calculate_inner(
+        mut self,
+        retain_updates: bool,
     ) -> Result<(B256, TrieUpdates), ParallelStateRootError> {
         let mut tracker = ParallelTrieTracker::default();
-        let prefix_sets = self.hashed_state.construct_prefix_sets().freeze();
+        let hashed_state = std::mem::take(&mut self.hashed_state);
+        let prefix_sets = hashed_state.construct_prefix_sets().freeze();
         let storage_root_targets = StorageRootTargets::new(
-            self.hashed_state.accounts.keys().copied(),
+            hashed_state.accounts.keys().copied(),
             prefix_sets.storage_prefix_sets,
         );
-        let hashed_state_sorted = self.hashed_state.into_sorted();
+        let hashed_state_sorted = hashed_state.into_sorted();
 
         // Pre-calculate storage roots in parallel for accounts which were changed.
         tracker.set_precomputed_storage_roots(storage_root_targets.len() as u64);
@@ -109,27 +142,193 @@ where
             })
             .collect::<Result<HashMap<_, _>, ParallelStateRootError>>()?;
 
-        trace!(target: "trie::parallel_state_root", "calculating state root");
-        let mut trie_updates = TrieUpdates::default();
+        let (root, trie_updates) = if self.thread_pool.is_some() {
+            trace!(target: "trie::parallel_state_root", "calculating state root from subtries");
+            let mut storage_roots_by_nibble = vec![HashMap::default(); 16];
+            for (hashed_address, result) in storage_roots {
+                storage_roots_by_nibble[first_nibble(&hashed_address) as usize]
+                    .insert(hashed_address, result);
+            }
 
-        let provider_ro = self.view.provider_ro()?;
-        let hashed_cursor_factory = HashedPostStateCursorFactory::new(
-            DatabaseHashedCursorFactory::new(provider_ro.tx_ref()),
-            &hashed_state_sorted,
-        );
-        let trie_cursor_factory = DatabaseTrieCursorFactory::new(provider_ro.tx_ref());
+            let subtries = storage_roots_by_nibble
+                .into_par_iter()
+                .enumerate()
+                .map(|(nibble, mut storage_roots)| {
+                    let nibble = nibble as u8;
+                    let provider_ro = self.view.provider_ro()?;
+                    let hashed_cursor_factory = HashedPostStateCursorFactory::new(
+                        DatabaseHashedCursorFactory::new(provider_ro.tx_ref()),
+                        &hashed_state_sorted,
+                    );
+                    let trie_cursor_factory = DatabaseTrieCursorFactory::new(provider_ro.tx_ref());
 
-        let walker = TrieWalker::new(
-            trie_cursor_factory.account_trie_cursor().map_err(ProviderError::Database)?,
-            prefix_sets.account_prefix_set,
-        )
-        .with_deletions_retained(retain_updates);
-        let mut account_node_iter = TrieNodeIter::new(
-            walker,
-            hashed_cursor_factory.hashed_account_cursor().map_err(ProviderError::Database)?,
+                    let prefix_set = PrefixSetMut::from(
+                        prefix_sets
+                            .account_prefix_set
+                            .iter()
+                            .filter(|key| key.first() == Some(nibble))
+                            .cloned(),
+                    )
+                    .freeze();
+                    let walker = TrieWalker::new(
+                        SubtrieTrieCursor::new(
+                            trie_cursor_factory
+                                .account_trie_cursor()
+                                .map_err(ProviderError::Database)?,
+                            nibble,
+                        ),
+                        prefix_set,
+                    )
+                    .with_deletions_retained(retain_updates);
+                    let mut account_node_iter = TrieNodeIter::new(
+                        walker,
+                        SubtrieHashedCursor::new(
+                            hashed_cursor_factory
+                                .hashed_account_cursor()
+                                .map_err(ProviderError::Database)?,
+                            nibble,
+                        ),
+                    );
+
+                    // Retain the subtrie root node to merge it into the state trie.
+                    let mut hash_builder =
+                        HashBuilder::default().with_updates(retain_updates).with_proof_retainer(
+                            ProofRetainer::new(vec![Nibbles::from_nibbles_unchecked([nibble])]),
+                        );
+                    let mut tracker = ParallelTrieTracker::default();
+                    let mut trie_updates = TrieUpdates::default();
+                    self.add_account_nodes(
+                        &trie_cursor_factory,
+                        &hashed_cursor_factory,
+                        &mut account_node_iter,
+                        &mut hash_builder,
+                        &mut storage_roots,
+                        &mut tracker,
+                        &mut trie_updates,
+                        retain_updates,
+                    )?;
+                    hash_builder.root();
+                    let root_node = hash_builder.take_proofs().remove(&Nibbles::default());
+
+                    trie_updates.finalize(
+                        account_node_iter.walker,
+                        hash_builder,
+                        prefix_sets
+                            .destroyed_accounts
+                            .iter()
+                            .filter(|hashed_address| first_nibble(hashed_address) == nibble)
+                            .copied()
+                            .collect(),
+                    );
+                    Ok((root_node, trie_updates, tracker.finish().missed_leaves()))
+                })
+                .collect::<Result<Vec<_>, ParallelStateRootError>>()?;
+
+            // All keys of a subtrie share the first nibble, so its root is either a leaf
+            // or an extension node. Add them to the state trie under their full path.
+            let mut hash_builder = HashBuilder::default();
+            let mut trie_updates = TrieUpdates::default();
+            for (root_node, updates, missed_leaves) in subtries {
+                tracker.add_missed_leaves(missed_leaves);
+                trie_updates.extend(updates);
+
+                let Some(root_node) = root_node else { continue };
+                match TrieNode::decode(&mut &root_node[..]).map_err(ProviderError::from)? {
+                    TrieNode::Leaf(leaf) => hash_builder.add_leaf(leaf.key, &leaf.value),
+                    // Extension child is a branch node with at least two children, which is
+                    // always referenced by hash.
+                    TrieNode::Extension(extension) => hash_builder.add_branch(
+                        extension.key,
+                        B256::from_slice(&extension.child[1..]),
+                        false,
+                    ),
+                    TrieNode::Branch(_) => unreachable!("subtrie keys share the first nibble"),
+                }
+            }
+            (hash_builder.root(), trie_updates)
+        } else {
+            trace!(target: "trie::parallel_state_root", "calculating state root");
+            let provider_ro = self.view.provider_ro()?;
+            let hashed_cursor_factory = HashedPostStateCursorFactory::new(
+                DatabaseHashedCursorFactory::new(provider_ro.tx_ref()),
+                &hashed_state_sorted,
+            );
+            let trie_cursor_factory = DatabaseTrieCursorFactory::new(provider_ro.tx_ref());
+
+            let walker = TrieWalker::new(
+                trie_cursor_factory.account_trie_cursor().map_err(ProviderError::Database)?,
+                prefix_sets.account_prefix_set,
+            )
+            .with_deletions_retained(retain_updates);
+            let mut account_node_iter = TrieNodeIter::new(
+                walker,
+                hashed_cursor_factory.hashed_account_cursor().map_err(ProviderError::Database)?,
+            );
+
+            let mut hash_builder = HashBuilder::default().with_updates(retain_updates);
+            let mut trie_updates = TrieUpdates::default();
+            self.add_account_nodes(
+                &trie_cursor_factory,
+                &hashed_cursor_factory,
+                &mut account_node_iter,
+                &mut hash_builder,
+                &mut storage_roots,
+                &mut tracker,
+                &mut trie_updates,
+                retain_updates,
+            )?;
+
+            let root = hash_builder.root();
+
+            trie_updates.finalize(
+                account_node_iter.walker,
+                hash_builder,
+                prefix_sets.destroyed_accounts,
+            );
+            (root, trie_updates)
+        };
+
+        let stats = tracker.finish();
+
+        #[cfg(feature = "metrics")]
+        self.metrics.record_state_trie(stats);
+
+        trace!(
+            target: "trie::parallel_state_root",
+            %root,
+            duration = ?stats.duration(),
+            branches_added = stats.branches_added(),
+            leaves_added = stats.leaves_added(),
+            missed_leaves = stats.missed_leaves(),
+            precomputed_storage_roots = stats.precomputed_storage_roots(),
+            "calculated state root"
         );
 
-        let mut hash_builder = HashBuilder::default().with_updates(retain_updates);
+        Ok((root, trie_updates))
+    }
+
+    /// Add the account trie nodes returned by the iterator to the hash builder.
+    ///
+    /// Storage roots of the account leaves are taken from the pre-computed ones and calculated
+    /// in place if missing.
+    #[allow(clippy::too_many_arguments)]
+    fn add_account_nodes<T, H, C, A>(
+        &self,
+        trie_cursor_factory: &T,
+        hashed_cursor_factory: &H,
+        account_node_iter: &mut TrieNodeIter<C, A>,
+        hash_builder: &mut HashBuilder,
+        storage_roots: &mut HashMap<B256, StorageRootResult>,
+        tracker: &mut ParallelTrieTracker,
+        trie_updates: &mut TrieUpdates,
+        retain_updates: bool,
+    ) -> Result<(), ParallelStateRootError>
+    where
+        T: TrieCursorFactory + Clone,
+        H: HashedCursorFactory + Clone,
+        C: TrieCursor,
+        A: HashedCursor<Value = Account>,
+    {
         let mut account_rlp = Vec::with_capacity(128);
         while let Some(node) = account_node_iter.try_next().map_err(ProviderError::Database)? {
             match node {
@@ -165,35 +364,15 @@ where
                 }
             }
         }
-
-        let root = hash_builder.root();
-
-        trie_updates.finalize(
-            account_node_iter.walker,
-            hash_builder,
-            prefix_sets.destroyed_accounts,
-        );
-
-        let stats = tracker.finish();
-
-        #[cfg(feature = "metrics")]
-        self.metrics.record_state_trie(stats);
-
-        trace!(
-            target: "trie::parallel_state_root",
-            %root,
-            duration = ?stats.duration(),
-            branches_added = stats.branches_added(),
-            leaves_added = stats.leaves_added(),
-            missed_leaves = stats.missed_leaves(),
-            precomputed_storage_roots = stats.precomputed_storage_roots(),
-            "calculated state root"
-        );
-
-        Ok((root, trie_updates))
+        Ok(())
     }
 }
 
+/// Returns the first nibble of the hashed key.
+const fn first_nibble(hashed_key: &B256) -> u8 {
+    hashed_key.0[0] >> 4
+}
+
 /// Error during parallel state root calculation.
 #[derive(Error, Debug)]
 pub enum ParallelStateRootError {
@@ -220,9 +399,14 @@ impl From<ParallelStateRootError> for ProviderError {
 mod tests {
     use super::*;
     use rand::Rng;
-    use reth_primitives::{keccak256, Account, Address, StorageEntry, U256};
-    use reth_provider::{test_utils::create_test_provider_factory, HashingWriter};
-    use reth_trie::{test_utils, HashedStorage};
+    use rayon::ThreadPoolBuilder;
+    use reth_primitives::{keccak256, Address, StorageEntry, U256};
+    use reth_provider::{
+        providers::ProviderFactory, test_utils::create_test_provider_factory, HashingWriter,
+        StateChangeWriter, TrieWriter,
+    };
+    use reth_trie::{test_utils, HashedStorage, StateRoot};
+    use reth_trie_db::DatabaseStateRoot;
 
     #[tokio::test]
     async fn random_parallel_root() {
@@ -306,4 +490,123 @@ mod tests {
             test_utils::state_root(state)
         );
     }
+
+    /// Asserts that the partitioned state root and trie updates match the serial ones and
+    /// commits the changes to the database.
+    fn assert_partitioned_root<DB: Database>(
+        factory: &ProviderFactory<DB>,
+        thread_pool: &Arc<ThreadPool>,
+        hashed_state: HashedPostState,
+    ) {
+        let (expected_root, expected_updates) = {
+            let provider = factory.provider().unwrap();
+            StateRoot::overlay_root_with_updates(provider.tx_ref(), hashed_state.clone()).unwrap()
+        };
+
+        let consistent_view = ConsistentDbView::new(factory.clone(), None);
+        let (root, updates) = ParallelStateRoot::new(consistent_view, hashed_state.clone())
+            .with_thread_pool(thread_pool.clone())
+            .incremental_root_with_updates()
+            .unwrap();
+        assert_eq!(root, expected_root);
+        assert_eq!(updates, expected_updates);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.write_hashed_state(&hashed_state.into_sorted()).unwrap();
+        provider_rw.write_trie_updates(&updates).unwrap();
+        provider_rw.commit().unwrap();
+    }
+
+    fn random_storage(rng: &mut impl Rng, len: usize) -> HashedStorage {
+        HashedStorage::from_iter(
+            false,
+            (0..len).map(|_| (B256::random(), U256::from(rng.gen::<u64>() + 1))),
+        )
+    }
+
+    #[test]
+    fn random_partitioned_root() {
+        let factory = create_test_provider_factory();
+        let thread_pool = Arc::new(ThreadPoolBuilder::new().num_threads(4).build().unwrap());
+        let mut rng = rand::thread_rng();
+
+        let mut hashed_addresses = Vec::new();
+        let mut hashed_state = HashedPostState::default();
+        for _ in 0..500 {
+            let hashed_address = B256::random();
+            hashed_addresses.push(hashed_address);
+            let account = Account { balance: U256::from(rng.gen::<u64>()), ..Default::default() };
+            hashed_state.accounts.insert(hashed_address, Some(account));
+            if rng.gen_bool(0.3) {
+                hashed_state.storages.insert(hashed_address, random_storage(&mut rng, 10));
+            }
+        }
+        assert_partitioned_root(&factory, &thread_pool, hashed_state);
+
+        for _ in 0..5 {
+            let mut hashed_state = HashedPostState::default();
+            for hashed_address in &hashed_addresses {
+                if rng.gen_bool(0.1) {
+                    let account =
+                        Account { balance: U256::from(rng.gen::<u64>()), ..Default::default() };
+                    hashed_state.accounts.insert(*hashed_address, Some(account));
+                } else if rng.gen_bool(0.05) {
+                    hashed_state.accounts.insert(*hashed_address, None);
+                    hashed_state.storages.insert(*hashed_address, HashedStorage::new(true));
+                } else if rng.gen_bool(0.05) {
+                    hashed_state.storages.insert(*hashed_address, random_storage(&mut rng, 5));
+                }
+            }
+            for _ in 0..50 {
+                let hashed_address = B256::random();
+                hashed_addresses.push(hashed_address);
+                let account =
+                    Account { balance: U256::from(rng.gen::<u64>()), ..Default::default() };
+                hashed_state.accounts.insert(hashed_address, Some(account));
+            }
+            assert_partitioned_root(&factory, &thread_pool, hashed_state);
+        }
+
+        // Nothing changed.
+        assert_partitioned_root(&factory, &thread_pool, HashedPostState::default());
+    }
+
+    #[test]
+    fn partitioned_root_sparse_trie() {
+        let factory = create_test_provider_factory();
+        let thread_pool = Arc::new(ThreadPoolBuilder::new().num_threads(2).build().unwrap());
+        let account = Account { nonce: 1, ..Default::default() };
+
+        // Empty trie.
+        assert_partitioned_root(&factory, &thread_pool, HashedPostState::default());
+
+        // Single account.
+        let single = B256::with_last_byte(1);
+        let mut hashed_state = HashedPostState::default();
+        hashed_state.accounts.insert(single, Some(account));
+        assert_partitioned_root(&factory, &thread_pool, hashed_state);
+
+        // All accounts share the first nibble, so there is no branch node at the root.
+        let mut hashed_state = HashedPostState::default();
+        for byte in [0x01, 0x02, 0x0f] {
+            let mut hashed_address = B256::random();
+            hashed_address.0[0] = byte;
+            hashed_state.accounts.insert(hashed_address, Some(account));
+        }
+        assert_partitioned_root(&factory, &thread_pool, hashed_state);
+
+        // Accounts spread over other subtries.
+        let mut hashed_state = HashedPostState::default();
+        for byte in [0x10, 0x11, 0xf0] {
+            let mut hashed_address = B256::random();
+            hashed_address.0[0] = byte;
+            hashed_state.accounts.insert(hashed_address, Some(account));
+        }
+        assert_partitioned_root(&factory, &thread_pool, hashed_state);
+
+        // Destroy the single account.
+        let mut hashed_state = HashedPostState::default();
+        hashed_state.accounts.insert(single, None);
+        assert_partitioned_root(&factory, &thread_pool, hashed_state);
+    }
 }
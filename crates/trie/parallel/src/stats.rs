@@ -57,6 +57,11 @@ impl ParallelTrieTracker {
         self.missed_leaves += 1;
     }
 
+    /// Add the number of missed leaves counted by a separate tracker.
+    pub fn add_missed_leaves(&mut self, count: u64) {
+        self.missed_leaves += count;
+    }
+
     /// Called when root calculation is finished to return trie statistics.
     pub fn finish(self) -> ParallelTrieStats {
         ParallelTrieStats {
@@ -0,0 +1,145 @@
+use reth_db::DatabaseError;
+use reth_primitives::B256;
+use reth_trie::{
+    hashed_cursor::HashedCursor, trie_cursor::TrieCursor, BranchNodeCompact, Nibbles, TrieMask,
+};
+
+/// Account trie cursor restricted to the subtrie under a single first nibble.
+///
+/// The root node is exposed with every child other than the subtrie's nibble masked out, so that
+/// the walker never leaves the subtrie.
+#[derive(Debug)]
+pub(crate) struct SubtrieTrieCursor<C> {
+    cursor: C,
+    nibble: u8,
+}
+
+impl<C> SubtrieTrieCursor<C> {
+    /// Create new subtrie cursor for the given first nibble.
+    pub(crate) const fn new(cursor: C, nibble: u8) -> Self {
+        Self { cursor, nibble }
+    }
+
+    fn prefix(&self) -> Nibbles {
+        Nibbles::from_nibbles_unchecked([self.nibble])
+    }
+
+    /// Masks out all root node children other than the subtrie nibble. The root hash is dropped
+    /// since it commits to the whole trie.
+    fn root_node(&self, node: BranchNodeCompact) -> Option<BranchNodeCompact> {
+        if !node.state_mask.is_bit_set(self.nibble) {
+            return None
+        }
+
+        let mask = TrieMask::from_nibble(self.nibble);
+        let hashes = if node.hash_mask.is_bit_set(self.nibble) {
+            vec![node.hash_for_nibble(self.nibble)]
+        } else {
+            Vec::new()
+        };
+        Some(BranchNodeCompact::new(
+            mask,
+            node.tree_mask & mask,
+            node.hash_mask & mask,
+            hashes,
+            None,
+        ))
+    }
+
+    fn restrict(
+        &self,
+        entry: Option<(Nibbles, BranchNodeCompact)>,
+    ) -> Option<(Nibbles, BranchNodeCompact)> {
+        entry.filter(|(key, _)| key.first() == Some(self.nibble))
+    }
+}
+
+impl<C: TrieCursor> TrieCursor for SubtrieTrieCursor<C> {
+    fn seek_exact(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        if key.is_empty() {
+            let entry = self.cursor.seek_exact(key)?;
+            return Ok(entry.and_then(|(key, node)| Some((key, self.root_node(node)?))))
+        }
+
+        if key[0] != self.nibble {
+            return Ok(None)
+        }
+        self.cursor.seek_exact(key)
+    }
+
+    fn seek(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        if key.is_empty() {
+            if let Some(root) = self.seek_exact(key.clone())? {
+                return Ok(Some(root))
+            }
+        }
+
+        let key = match key.first() {
+            Some(first) if first > self.nibble => return Ok(None),
+            Some(first) if first == self.nibble => key,
+            _ => self.prefix(),
+        };
+        let entry = self.cursor.seek(key)?;
+        Ok(self.restrict(entry))
+    }
+
+    fn next(&mut self) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        match self.cursor.next()? {
+            Some((key, _)) if key.first().map_or(true, |first| first < self.nibble) => {
+                let entry = self.cursor.seek(self.prefix())?;
+                Ok(self.restrict(entry))
+            }
+            entry => Ok(self.restrict(entry)),
+        }
+    }
+
+    fn current(&mut self) -> Result<Option<Nibbles>, DatabaseError> {
+        Ok(self
+            .cursor
+            .current()?
+            .filter(|key| key.first().map_or(true, |first| first == self.nibble)))
+    }
+}
+
+/// Hashed account cursor restricted to the keys under a single first nibble.
+#[derive(Debug)]
+pub(crate) struct SubtrieHashedCursor<C> {
+    cursor: C,
+    nibble: u8,
+}
+
+impl<C> SubtrieHashedCursor<C> {
+    /// Create new subtrie cursor for the given first nibble.
+    pub(crate) const fn new(cursor: C, nibble: u8) -> Self {
+        Self { cursor, nibble }
+    }
+
+    const fn contains(&self, key: &B256) -> bool {
+        key.0[0] >> 4 == self.nibble
+    }
+}
+
+impl<C: HashedCursor> HashedCursor for SubtrieHashedCursor<C> {
+    type Value = C::Value;
+
+    fn seek(&mut self, key: B256) -> Result<Option<(B256, Self::Value)>, DatabaseError> {
+        let mut lower_bound = B256::ZERO;
+        lower_bound.0[0] = self.nibble << 4;
+
+        let key = key.max(lower_bound);
+        if !self.contains(&key) {
+            return Ok(None)
+        }
+        Ok(self.cursor.seek(key)?.filter(|(key, _)| self.contains(key)))
+    }
+
+    fn next(&mut self) -> Result<Option<(B256, Self::Value)>, DatabaseError> {
+        Ok(self.cursor.next()?.filter(|(key, _)| self.contains(key)))
+    }
+}
@@ -7,7 +7,7 @@ use itertools::Either;
 use reth_execution_errors::{StateProofError, TrieWitnessError};
 use reth_primitives::{constants::EMPTY_ROOT_HASH, keccak256, Bytes, B256};
 use reth_trie_common::{
-    BranchNode, HashBuilder, Nibbles, TrieAccount, TrieNode, CHILD_INDEX_RANGE,
+    BranchNode, HashBuilder, Nibbles, StorageMultiProof, TrieAccount, TrieNode, CHILD_INDEX_RANGE,
 };
 use std::collections::{BTreeMap, HashMap, HashSet};
 
@@ -84,12 +84,14 @@ where
         // information for the witness.
         let mut account_rlp = Vec::with_capacity(128);
         let mut account_trie_nodes = BTreeMap::default();
+        // accounts that don't exist in the trie, e.g. an unused coinbase, have no storage proof
+        let empty_storage_multiproof = StorageMultiProof::default();
         for (hashed_address, hashed_slots) in proof_targets {
             let key = Nibbles::unpack(hashed_address);
             let storage_multiproof = account_multiproof
                 .storages
                 .get(&hashed_address)
-                .ok_or(TrieWitnessError::MissingStorageMultiProof(hashed_address))?;
+                .unwrap_or(&empty_storage_multiproof);
 
             // Gather and record account trie nodes.
             let account = state
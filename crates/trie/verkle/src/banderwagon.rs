@@ -0,0 +1,250 @@
+//! The Banderwagon group: the prime order quotient group of the Bandersnatch curve that verkle
+//! commitments live in.
+
+use ark_ec::{
+    twisted_edwards::{Affine, TECurveConfig},
+    CurveGroup, VariableBaseMSM,
+};
+use ark_ed_on_bls12_381_bandersnatch::{BandersnatchConfig, EdwardsAffine, EdwardsProjective};
+use ark_ff::{batch_inversion, BigInteger, Field, One, PrimeField, Zero};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+pub use ark_ed_on_bls12_381_bandersnatch::{Fq, Fr};
+
+/// An element of the Banderwagon group.
+///
+/// Two Bandersnatch points `(x, y)` and `(-x, -y)` represent the same element.
+#[derive(Debug, Clone, Copy)]
+pub struct Element(EdwardsProjective);
+
+impl Element {
+    /// The identity element.
+    pub fn zero() -> Self {
+        Self(EdwardsProjective::zero())
+    }
+
+    /// The generator of the group.
+    pub fn generator() -> Self {
+        Self(EdwardsProjective::from(BandersnatchConfig::GENERATOR))
+    }
+
+    /// Returns `true` if this is the identity element.
+    pub fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+
+    /// Serializes the element to 32 bytes: the big endian `x` coordinate of the representative
+    /// with the lexicographically largest `y` coordinate.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let affine = self.0.into_affine();
+        let x = if is_lexicographically_largest(&affine.y) { affine.x } else { -affine.x };
+        to_bytes_be(&x)
+    }
+
+    /// Deserializes an element serialized by [`Self::to_bytes`].
+    ///
+    /// Returns `None` if the bytes are not a canonical encoding of a group element.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let x = Fq::from_be_bytes_mod_order(bytes);
+        if to_bytes_be(&x) != *bytes {
+            return None
+        }
+        Self::from_x(x)
+    }
+
+    /// Returns the element with the given `x` coordinate, if there is one in the group.
+    fn from_x(x: Fq) -> Option<Self> {
+        let x2 = x.square();
+        // The point is in the prime order subgroup if `1 - a * x^2` is a square.
+        let numerator = Fq::one() - BandersnatchConfig::COEFF_A * x2;
+        if numerator.legendre().is_qnr() {
+            return None
+        }
+        let denominator = Fq::one() - BandersnatchConfig::COEFF_D * x2;
+        let y = (numerator * denominator.inverse()?).sqrt()?;
+        let y = if is_lexicographically_largest(&y) { y } else { -y };
+        Some(Self(Affine::new_unchecked(x, y).into()))
+    }
+
+    /// Serializes the element without compression, to 32 bytes of the big endian `x` coordinate
+    /// followed by 32 bytes of the big endian `y` coordinate.
+    pub fn to_uncompressed_bytes(&self) -> [u8; 64] {
+        let affine = self.0.into_affine();
+        let mut bytes = [0; 64];
+        bytes[..32].copy_from_slice(&to_bytes_be(&affine.x));
+        bytes[32..].copy_from_slice(&to_bytes_be(&affine.y));
+        bytes
+    }
+
+    /// Deserializes an element serialized by [`Self::to_uncompressed_bytes`].
+    ///
+    /// The bytes are trusted to encode a point of the group, which is only checked to be on the
+    /// curve.
+    pub fn from_uncompressed_bytes(bytes: &[u8; 64]) -> Option<Self> {
+        let point = Affine::new_unchecked(
+            Fq::from_be_bytes_mod_order(&bytes[..32]),
+            Fq::from_be_bytes_mod_order(&bytes[32..]),
+        );
+        point.is_on_curve().then(|| Self(point.into()))
+    }
+
+    /// Maps the element to a scalar, as `x / y` interpreted as a scalar field element.
+    ///
+    /// This is how a commitment is embedded into the commitment of its parent node.
+    pub fn map_to_scalar_field(&self) -> Fr {
+        let affine = self.0.into_affine();
+        base_to_scalar(affine.x * affine.y.inverse().expect("y is never zero in the group"))
+    }
+
+    /// Maps many elements to scalars, with a single field inversion.
+    pub fn batch_map_to_scalar_field(elements: &[Self]) -> Vec<Fr> {
+        let points = elements.iter().map(|element| element.0).collect::<Vec<_>>();
+        let affine = EdwardsProjective::normalize_batch(&points);
+        let mut ys = affine.iter().map(|point| point.y).collect::<Vec<_>>();
+        batch_inversion(&mut ys);
+        affine.iter().zip(ys).map(|(point, y_inv)| base_to_scalar(point.x * y_inv)).collect()
+    }
+
+    /// Computes `sum(scalars[i] * points[i])`.
+    pub fn msm(points: &[EdwardsAffine], scalars: &[Fr]) -> Self {
+        Self(EdwardsProjective::msm_unchecked(points, scalars))
+    }
+
+    /// Returns the affine representation of the element.
+    pub fn to_affine(&self) -> EdwardsAffine {
+        self.0.into_affine()
+    }
+
+    /// Returns the affine representations of many elements, with a single field inversion.
+    pub fn batch_to_affine(elements: &[Self]) -> Vec<EdwardsAffine> {
+        let points = elements.iter().map(|element| element.0).collect::<Vec<_>>();
+        EdwardsProjective::normalize_batch(&points)
+    }
+}
+
+impl PartialEq for Element {
+    fn eq(&self, other: &Self) -> bool {
+        // `(x1, y1)` and `(x2, y2)` are the same element if `x1 * y2 == x2 * y1`, the projective
+        // `z` coordinates cancel out.
+        self.0.x * other.0.y == other.0.x * self.0.y
+    }
+}
+
+impl Eq for Element {}
+
+impl Default for Element {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl From<EdwardsAffine> for Element {
+    fn from(point: EdwardsAffine) -> Self {
+        Self(point.into())
+    }
+}
+
+impl Add for Element {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Element {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Element {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl Neg for Element {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl Mul<Fr> for Element {
+    type Output = Self;
+
+    fn mul(self, rhs: Fr) -> Self {
+        Self(self.0 * rhs)
+    }
+}
+
+/// Serializes a scalar to 32 little endian bytes.
+pub fn scalar_to_bytes(scalar: &Fr) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    bytes.copy_from_slice(&scalar.into_bigint().to_bytes_le());
+    bytes
+}
+
+/// Deserializes a scalar serialized by [`scalar_to_bytes`].
+///
+/// Returns `None` if the bytes are not a canonical encoding of a scalar.
+pub fn scalar_from_bytes(bytes: &[u8; 32]) -> Option<Fr> {
+    let scalar = Fr::from_le_bytes_mod_order(bytes);
+    (scalar_to_bytes(&scalar) == *bytes).then_some(scalar)
+}
+
+fn base_to_scalar(value: Fq) -> Fr {
+    Fr::from_le_bytes_mod_order(&value.into_bigint().to_bytes_le())
+}
+
+fn to_bytes_be(value: &Fq) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    bytes.copy_from_slice(&value.into_bigint().to_bytes_be());
+    bytes
+}
+
+fn is_lexicographically_largest(value: &Fq) -> bool {
+    value.into_bigint() > Fq::MODULUS_MINUS_ONE_DIV_TWO
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialization_roundtrip() {
+        let mut point = Element::generator();
+        for _ in 0..16 {
+            let bytes = point.to_bytes();
+            assert_eq!(Element::from_bytes(&bytes), Some(point));
+            assert_eq!(
+                Element::from_uncompressed_bytes(&point.to_uncompressed_bytes()),
+                Some(point)
+            );
+            point = point + point;
+        }
+    }
+
+    #[test]
+    fn equivalent_representatives() {
+        let point = Element::generator() * Fr::from(7u64);
+        let affine = point.to_affine();
+        let other = Element::from(Affine::new_unchecked(-affine.x, -affine.y));
+        assert_eq!(point, other);
+        assert_eq!(point.to_bytes(), other.to_bytes());
+        assert_eq!(point.map_to_scalar_field(), other.map_to_scalar_field());
+    }
+
+    #[test]
+    fn batch_map_to_scalar_field() {
+        let points = (1..8u64).map(|i| Element::generator() * Fr::from(i)).collect::<Vec<_>>();
+        assert_eq!(
+            Element::batch_map_to_scalar_field(&points),
+            points.iter().map(Element::map_to_scalar_field).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! The common reference string of the Pedersen vector commitments.
+
+use crate::banderwagon::{Element, Fq, Fr};
+use ark_ed_on_bls12_381_bandersnatch::EdwardsAffine;
+use ark_ff::{PrimeField, Zero};
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+
+/// The number of children of a node, and the length of the committed vectors.
+pub const VERKLE_NODE_WIDTH: usize = 256;
+
+/// The seed the generators of the [`Crs`] are derived from.
+const CRS_SEED: &[u8] = b"eth_verkle_oct_2021";
+
+/// The generators used to commit to vectors of [`VERKLE_NODE_WIDTH`] scalars.
+#[derive(Debug)]
+pub struct Crs {
+    /// The generators of the vector elements.
+    pub g: Vec<EdwardsAffine>,
+    /// The generator used to bind the evaluation in inner product arguments.
+    pub q: Element,
+}
+
+impl Crs {
+    /// Returns the common reference string, generating it on first use.
+    pub fn get() -> &'static Self {
+        static CRS: OnceLock<Crs> = OnceLock::new();
+        CRS.get_or_init(Self::generate)
+    }
+
+    /// Generates the generators by hashing the seed with an incrementing counter, and keeping the
+    /// hashes that are valid `x` coordinates of group elements.
+    fn generate() -> Self {
+        let mut g = Vec::with_capacity(VERKLE_NODE_WIDTH);
+        let mut counter = 0u64;
+        while g.len() < VERKLE_NODE_WIDTH {
+            let hash = Sha256::new().chain_update(CRS_SEED).chain_update(counter.to_be_bytes());
+            let x = Fq::from_be_bytes_mod_order(&hash.finalize());
+            let mut bytes = [0; 32];
+            bytes.copy_from_slice(&ark_ff::BigInteger::to_bytes_be(&x.into_bigint()));
+            if let Some(point) = Element::from_bytes(&bytes) {
+                g.push(point);
+            }
+            counter += 1;
+        }
+
+        Self { g: Element::batch_to_affine(&g), q: Element::generator() }
+    }
+
+    /// Commits to a vector of at most [`VERKLE_NODE_WIDTH`] scalars.
+    pub fn commit(&self, values: &[Fr]) -> Element {
+        Element::msm(&self.g[..values.len()], values)
+    }
+
+    /// Commits to a vector given by its non-zero elements.
+    pub fn commit_sparse(&self, values: &[(usize, Fr)]) -> Element {
+        let (points, scalars): (Vec<_>, Vec<_>) = values
+            .iter()
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(index, value)| (self.g[*index], *value))
+            .unzip();
+        Element::msm(&points, &scalars)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generators() {
+        let crs = Crs::get();
+        assert_eq!(
+            Element::from(crs.g[0]).to_bytes(),
+            alloy_primitives::hex!(
+                "01587ad1336675eb912550ec2a28eb8923b824b490dd2ba82e48f14590a298a0"
+            )
+        );
+        assert_eq!(
+            Element::from(crs.g[255]).to_bytes(),
+            alloy_primitives::hex!(
+                "3de2be346b539395b0c0de56a5ccca54a317f1b5c80107b0802af9a62276a4d8"
+            )
+        );
+    }
+
+    #[test]
+    fn sparse_commitment() {
+        let crs = Crs::get();
+        let mut values = vec![Fr::zero(); VERKLE_NODE_WIDTH];
+        values[3] = Fr::from(5u64);
+        values[200] = Fr::from(7u64);
+        assert_eq!(
+            crs.commit(&values),
+            crs.commit_sparse(&[(3, Fr::from(5u64)), (200, Fr::from(7u64))])
+        );
+        assert_eq!(crs.commit_sparse(&[]), Element::zero());
+    }
+}
@@ -0,0 +1,241 @@
+//! Database access for the verkle trie.
+
+use crate::{
+    embedding::{key, Stem},
+    trie::{VerkleTrieReader, VerkleTrieUpdates},
+};
+use alloy_primitives::{BlockNumber, B256};
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRW},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_storage_errors::db::DatabaseError;
+use reth_trie_common::{StoredVerkleNode, VerkleLeafBeforeBlock, VerklePath};
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+/// A [`DatabaseVerkleTrieReader`] over the cursors of a transaction.
+pub type TxVerkleTrieReader<TX> = DatabaseVerkleTrieReader<
+    <TX as DbTx>::Cursor<tables::VerkleNodes>,
+    <TX as DbTx>::Cursor<tables::VerkleLeaves>,
+>;
+
+/// Reads the verkle trie from the database.
+#[derive(Debug)]
+pub struct DatabaseVerkleTrieReader<N, L> {
+    nodes: N,
+    leaves: L,
+}
+
+impl<N, L> DatabaseVerkleTrieReader<N, L> {
+    /// Creates a reader of the verkle trie from cursors of its nodes and leaves.
+    pub const fn new(nodes: N, leaves: L) -> Self {
+        Self { nodes, leaves }
+    }
+}
+
+/// Returns a reader of the verkle trie in the transaction.
+pub fn verkle_trie_reader<TX: DbTx>(tx: &TX) -> Result<TxVerkleTrieReader<TX>, DatabaseError> {
+    Ok(DatabaseVerkleTrieReader::new(tx.cursor_read()?, tx.cursor_read()?))
+}
+
+impl<N, L> VerkleTrieReader for DatabaseVerkleTrieReader<N, L>
+where
+    N: DbCursorRO<tables::VerkleNodes>,
+    L: DbCursorRO<tables::VerkleLeaves>,
+{
+    fn node(&mut self, path: &[u8]) -> Result<Option<StoredVerkleNode>, DatabaseError> {
+        Ok(self.nodes.seek_exact(VerklePath::from(path))?.map(|(_, node)| node))
+    }
+
+    fn children(&mut self, path: &[u8]) -> Result<Vec<u8>, DatabaseError> {
+        // The nodes below a child come right after it, so each seek finds the next child.
+        let mut children = Vec::new();
+        let mut next = Some(0u8);
+        while let Some(index) = next {
+            let mut child = path.to_vec();
+            child.push(index);
+            let Some((found, _)) = self.nodes.seek(VerklePath(child))? else { break };
+            if found.len() <= path.len() || !found.starts_with(path) {
+                break
+            }
+            let found = found[path.len()];
+            children.push(found);
+            next = found.checked_add(1);
+        }
+        Ok(children)
+    }
+
+    fn leaves(&mut self, stem: &Stem) -> Result<Vec<(u8, B256)>, DatabaseError> {
+        self.leaves
+            .walk_range(key(stem, 0)..=key(stem, u8::MAX))?
+            .map(|entry| entry.map(|(key, value)| (key[31], value)))
+            .collect()
+    }
+}
+
+/// Writes the updates of the verkle trie to the database.
+pub fn write_verkle_updates<TX: DbTxMut + DbTx>(
+    tx: &TX,
+    updates: &VerkleTrieUpdates,
+) -> Result<(), DatabaseError> {
+    let mut nodes = tx.cursor_write::<tables::VerkleNodes>()?;
+    for (path, node) in &updates.nodes {
+        match node {
+            Some(node) => nodes.upsert(path.clone(), node.clone())?,
+            None => {
+                if nodes.seek_exact(path.clone())?.is_some() {
+                    nodes.delete_current()?;
+                }
+            }
+        }
+    }
+
+    let mut leaves = tx.cursor_write::<tables::VerkleLeaves>()?;
+    for (key, (_, value)) in &updates.leaves {
+        match value {
+            Some(value) => leaves.upsert(*key, *value)?,
+            None => {
+                if leaves.seek_exact(*key)?.is_some() {
+                    leaves.delete_current()?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes the values of verkle trie leaves before the blocks that changed them.
+pub fn write_verkle_changesets<TX: DbTxMut + DbTx>(
+    tx: &TX,
+    changesets: impl IntoIterator<Item = (BlockNumber, VerkleLeafBeforeBlock)>,
+) -> Result<(), DatabaseError> {
+    let mut changesets = changesets.into_iter().collect::<Vec<_>>();
+    changesets.sort_unstable_by_key(|(block, leaf)| (*block, leaf.key));
+
+    let mut cursor = tx.cursor_dup_write::<tables::VerkleLeafChangeSets>()?;
+    for (block, leaf) in changesets {
+        cursor.append_dup(block, leaf)?;
+    }
+    Ok(())
+}
+
+/// Returns the values of the verkle trie leaves changed by the blocks in `range` before the
+/// first block of the range, and removes the changesets of the range.
+pub fn take_verkle_changesets<TX: DbTxMut + DbTx>(
+    tx: &TX,
+    range: RangeInclusive<BlockNumber>,
+) -> Result<BTreeMap<B256, Option<B256>>, DatabaseError> {
+    let mut leaves = BTreeMap::new();
+    let mut cursor = tx.cursor_dup_write::<tables::VerkleLeafChangeSets>()?;
+    let mut entry = cursor.seek(*range.start())?;
+    while let Some((block, leaf)) = entry {
+        if block > *range.end() {
+            break
+        }
+        // The changesets are walked in ascending order of blocks, so the first value of a leaf
+        // is its value before the range.
+        leaves.entry(leaf.key).or_insert(leaf.value);
+        cursor.delete_current()?;
+        entry = cursor.current()?;
+    }
+    Ok(leaves)
+}
+
+/// Removes the changesets of the verkle trie leaves up to and including `block`.
+pub fn prune_verkle_changesets<TX: DbTxMut + DbTx>(
+    tx: &TX,
+    block: BlockNumber,
+) -> Result<(), DatabaseError> {
+    let mut cursor = tx.cursor_dup_write::<tables::VerkleLeafChangeSets>()?;
+    while let Some((changeset_block, _)) = cursor.first()? {
+        if changeset_block > block {
+            break
+        }
+        cursor.delete_current_duplicates()?;
+    }
+    Ok(())
+}
+
+/// Returns `true` if the database holds a verkle trie.
+pub fn has_verkle_trie<TX: DbTx>(tx: &TX) -> Result<bool, DatabaseError> {
+    Ok(tx.cursor_read::<tables::VerkleNodes>()?.first()?.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trie::{update_verkle_trie, verkle_root, InMemoryVerkleTrie};
+    use reth_db::test_utils::create_test_rw_db;
+    use reth_db_api::database::Database;
+
+    #[test]
+    fn database_trie() {
+        let db = create_test_rw_db();
+        let leaf = |prefix: &[u8], suffix| {
+            let mut stem = [0; 31];
+            stem[..prefix.len()].copy_from_slice(prefix);
+            key(&stem, suffix)
+        };
+        let batches = [
+            BTreeMap::from([
+                (leaf(&[1, 2], 0), Some(B256::with_last_byte(1))),
+                (leaf(&[1, 3], 1), Some(B256::with_last_byte(2))),
+                (leaf(&[2], 1), Some(B256::with_last_byte(3))),
+            ]),
+            BTreeMap::from([
+                (leaf(&[1, 2, 3], 4), Some(B256::with_last_byte(4))),
+                (leaf(&[1, 3], 1), None),
+            ]),
+        ];
+
+        let mut memory = InMemoryVerkleTrie::default();
+        for (block, changes) in batches.into_iter().enumerate() {
+            let expected = memory.update(changes.clone()).unwrap();
+
+            let tx = db.tx_mut().unwrap();
+            let mut reader = verkle_trie_reader(&tx).unwrap();
+            let (root, updates) = update_verkle_trie(&mut reader, changes).unwrap();
+            assert_eq!(root, expected);
+            write_verkle_updates(&tx, &updates).unwrap();
+            write_verkle_changesets(
+                &tx,
+                updates.leaves.iter().map(|(key, (value, _))| {
+                    (block as BlockNumber, VerkleLeafBeforeBlock { key: *key, value: *value })
+                }),
+            )
+            .unwrap();
+            tx.commit().unwrap();
+        }
+
+        let tx = db.tx_mut().unwrap();
+        let mut reader = verkle_trie_reader(&tx).unwrap();
+        assert_eq!(verkle_root(&mut reader).unwrap(), verkle_root(&mut memory).unwrap());
+        assert_eq!(reader.children(&[]).unwrap(), vec![1, 2]);
+        assert_eq!(reader.children(&[1]).unwrap(), vec![2]);
+        assert_eq!(reader.children(&[1, 2]).unwrap(), vec![0, 3]);
+        assert!(has_verkle_trie(&tx).unwrap());
+
+        // Unwinding the second batch restores the root of the first one.
+        let unwind = take_verkle_changesets(&tx, 1..=1).unwrap();
+        assert_eq!(unwind[&leaf(&[1, 3], 1)], Some(B256::with_last_byte(2)));
+        assert_eq!(unwind[&leaf(&[1, 2, 3], 4)], None);
+        let (root, updates) = update_verkle_trie(&mut reader, unwind).unwrap();
+        write_verkle_updates(&tx, &updates).unwrap();
+        let mut first = InMemoryVerkleTrie::default();
+        assert_eq!(
+            root,
+            first
+                .update(BTreeMap::from([
+                    (leaf(&[1, 2], 0), Some(B256::with_last_byte(1))),
+                    (leaf(&[1, 3], 1), Some(B256::with_last_byte(2))),
+                    (leaf(&[2], 1), Some(B256::with_last_byte(3))),
+                ]))
+                .unwrap()
+        );
+        assert_eq!(tx.entries::<tables::VerkleLeafChangeSets>().unwrap(), 3);
+
+        prune_verkle_changesets(&tx, 0).unwrap();
+        assert_eq!(tx.entries::<tables::VerkleLeafChangeSets>().unwrap(), 0);
+    }
+}
@@ -0,0 +1,216 @@
+//! The embedding of the Ethereum state into the verkle trie, as specified by
+//! [EIP-6800](https://eips.ethereum.org/EIPS/eip-6800).
+
+use crate::{banderwagon::Fr, crs::Crs};
+use alloy_primitives::{Address, B256, U256};
+use ark_ff::{BigInteger, PrimeField};
+use reth_primitives::Account;
+
+/// The first 31 bytes of a tree key, which select the stem node holding its leaf.
+pub type Stem = [u8; 31];
+
+/// The leaf holding the version, code size, nonce and balance of an account.
+pub const BASIC_DATA_LEAF_KEY: u8 = 0;
+/// The leaf holding the code hash of an account.
+pub const CODE_HASH_LEAF_KEY: u8 = 1;
+/// The offset of the storage slots that share the stem of the account header.
+pub const HEADER_STORAGE_OFFSET: u64 = 64;
+/// The offset of the code chunks that share the stem of the account header.
+pub const CODE_OFFSET: u64 = 128;
+/// The number of bytes of code in a code chunk.
+pub const CODE_CHUNK_SIZE: usize = 31;
+
+const PUSH1: u8 = 0x60;
+const PUSH32: u8 = 0x7f;
+
+/// Returns the stem of the leaves of `address` at `tree_index`.
+pub fn stem(address: &Address, tree_index: U256) -> Stem {
+    stem_of_word(&address.into_word(), tree_index)
+}
+
+/// Returns the stem of the leaves of an address left padded to 32 bytes at `tree_index`.
+fn stem_of_word(address: &B256, tree_index: U256) -> Stem {
+    let mut input = [0u8; 64];
+    input[..32].copy_from_slice(address.as_slice());
+    input[32..].copy_from_slice(&tree_index.to_le_bytes::<32>());
+
+    // The input is committed to as 16 byte little endian chunks, after a marker of its length.
+    let mut scalars = [Fr::from(2u64 + 256 * 64); 5];
+    for (scalar, chunk) in scalars[1..].iter_mut().zip(input.chunks_exact(16)) {
+        *scalar = Fr::from_le_bytes_mod_order(chunk);
+    }
+    let commitment = Crs::get().commit(&scalars);
+    let hash = commitment.map_to_scalar_field().into_bigint().to_bytes_le();
+
+    let mut stem = [0; 31];
+    stem.copy_from_slice(&hash[..31]);
+    stem
+}
+
+/// Returns the tree key of the leaf at `sub_index` of the stem of `address` at `tree_index`.
+pub fn tree_key(address: &Address, tree_index: U256, sub_index: u8) -> B256 {
+    key(&stem(address, tree_index), sub_index)
+}
+
+/// Returns the tree key of the leaf at `sub_index` of `stem`.
+pub fn key(stem: &Stem, sub_index: u8) -> B256 {
+    let mut key = B256::ZERO;
+    key[..31].copy_from_slice(stem);
+    key[31] = sub_index;
+    key
+}
+
+/// Splits a tree key into its stem and sub index.
+pub fn split_key(key: &B256) -> (Stem, u8) {
+    let mut stem = [0; 31];
+    stem.copy_from_slice(&key[..31]);
+    (stem, key[31])
+}
+
+/// Returns the tree key of the basic data of `address`.
+pub fn basic_data_key(address: &Address) -> B256 {
+    tree_key(address, U256::ZERO, BASIC_DATA_LEAF_KEY)
+}
+
+/// Returns the tree key of the code hash of `address`.
+pub fn code_hash_key(address: &Address) -> B256 {
+    tree_key(address, U256::ZERO, CODE_HASH_LEAF_KEY)
+}
+
+/// Returns the tree key of the storage slot `slot` of `address`.
+pub fn storage_slot_key(address: &Address, slot: U256) -> B256 {
+    let header_slots = U256::from(CODE_OFFSET - HEADER_STORAGE_OFFSET);
+    if slot < header_slots {
+        let position = U256::from(HEADER_STORAGE_OFFSET) + slot;
+        return tree_key(address, U256::ZERO, position.byte(0))
+    }
+
+    // The main storage starts at `256^31`, which is computed per stem to avoid an overflow.
+    let tree_index = (U256::from(1) << 240) + (slot >> 8);
+    tree_key(address, tree_index, slot.byte(0))
+}
+
+/// Returns the tree key of the code chunk `chunk` of `address`.
+pub fn code_chunk_key(address: &Address, chunk: u64) -> B256 {
+    let position = U256::from(CODE_OFFSET) + U256::from(chunk);
+    tree_key(address, position >> 8, position.byte(0))
+}
+
+/// Returns the basic data leaf of an account with code of `code_size` bytes.
+///
+/// The leaf is the version (1 byte), reserved bytes (4 bytes), code size (3 bytes), nonce
+/// (8 bytes) and balance (16 bytes), all big endian.
+pub fn basic_data_leaf(account: &Account, code_size: u32) -> B256 {
+    let mut leaf = B256::ZERO;
+    leaf[5..8].copy_from_slice(&code_size.to_be_bytes()[1..]);
+    leaf[8..16].copy_from_slice(&account.nonce.to_be_bytes());
+    leaf[16..].copy_from_slice(&account.balance.to_be_bytes::<32>()[16..]);
+    leaf
+}
+
+/// Splits code into chunks of [`CODE_CHUNK_SIZE`] bytes.
+///
+/// Each chunk is prefixed by the number of its leading bytes that are push data of an
+/// instruction in a previous chunk.
+pub fn chunkify_code(code: &[u8]) -> Vec<B256> {
+    let chunks = code.len().div_ceil(CODE_CHUNK_SIZE);
+    let mut padded = code.to_vec();
+    padded.resize(chunks * CODE_CHUNK_SIZE, 0);
+
+    // The number of remaining push data bytes at each position.
+    let mut push_data = vec![0usize; padded.len()];
+    let mut position = 0;
+    while position < padded.len() {
+        let opcode = padded[position];
+        position += 1;
+        if (PUSH1..=PUSH32).contains(&opcode) {
+            let size = (opcode - PUSH1 + 1) as usize;
+            for offset in 0..size {
+                if let Some(remaining) = push_data.get_mut(position + offset) {
+                    *remaining = size - offset;
+                }
+            }
+            position += size;
+        }
+    }
+
+    padded
+        .chunks_exact(CODE_CHUNK_SIZE)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut leaf = B256::ZERO;
+            leaf[0] = push_data[index * CODE_CHUNK_SIZE].min(CODE_CHUNK_SIZE) as u8;
+            leaf[1..].copy_from_slice(chunk);
+            leaf
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256};
+
+    #[test]
+    fn header_leaves_share_stem() {
+        let address = address!("0000000000000000000000000000000000000001");
+        let (stem, basic_data) = split_key(&basic_data_key(&address));
+        assert_eq!(basic_data, BASIC_DATA_LEAF_KEY);
+        assert_eq!(split_key(&code_hash_key(&address)), (stem, CODE_HASH_LEAF_KEY));
+        assert_eq!(split_key(&storage_slot_key(&address, U256::from(63))), (stem, 127));
+        assert_eq!(split_key(&code_chunk_key(&address, 127)), (stem, 255));
+
+        assert_ne!(split_key(&storage_slot_key(&address, U256::from(64))).0, stem);
+        assert_ne!(split_key(&code_chunk_key(&address, 128)).0, stem);
+        assert_eq!(
+            split_key(&storage_slot_key(&address, U256::MAX)).0,
+            split_key(&storage_slot_key(&address, U256::MAX - U256::from(255))).0
+        );
+    }
+
+    /// The tree key of go-verkle's `TestGetTreeKey`, with an address of 32 bytes.
+    #[test]
+    fn go_verkle_tree_key() {
+        let mut address = B256::ZERO;
+        for byte in address.iter_mut().skip(1).step_by(2) {
+            *byte = 0xff;
+        }
+        let tree_index = (U256::from(1) << 129) + U256::from(3);
+        assert_eq!(
+            key(&stem_of_word(&address, tree_index), 1),
+            b256!("6ede905763d5856cd2d67936541e82aa78f7141bf8cd5ff6c962170f3e9dc201")
+        );
+    }
+
+    #[test]
+    fn basic_data() {
+        let account = Account { nonce: 0x0102, balance: U256::from(0x0304), bytecode_hash: None };
+        assert_eq!(
+            basic_data_leaf(&account, 0x050607),
+            b256!("0000000000050607000000000000010200000000000000000000000000000304")
+        );
+    }
+
+    #[test]
+    fn chunkify() {
+        assert!(chunkify_code(&[]).is_empty());
+
+        // PUSH4 at the end of the first chunk spills its data into the second one.
+        let mut code = vec![0u8; 30];
+        code.push(PUSH1 + 3);
+        code.extend([0xaa; 4]);
+        let chunks = chunkify_code(&code);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0][0], 0);
+        assert_eq!(chunks[0][31], PUSH1 + 3);
+        assert_eq!(chunks[1][0], 4);
+        assert_eq!(&chunks[1][1..5], &[0xaa; 4]);
+
+        // PUSH32 at the start of the code spills two bytes of its data into the second chunk.
+        let mut code = vec![PUSH32];
+        code.extend([0xbb; 32]);
+        code.extend([0; 31]);
+        let chunks = chunkify_code(&code);
+        assert_eq!(chunks.iter().map(|chunk| chunk[0]).collect::<Vec<_>>(), vec![0, 2, 0]);
+    }
+}
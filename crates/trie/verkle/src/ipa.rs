@@ -0,0 +1,405 @@
+//! Inner product arguments and multipoint openings of Pedersen vector commitments.
+//!
+//! Vectors are polynomials in evaluation form over the domain `0..256`, and a multiproof opens
+//! many commitments at points of the domain with a single inner product argument.
+
+use crate::{
+    banderwagon::{scalar_to_bytes, Element, Fr},
+    crs::{Crs, VERKLE_NODE_WIDTH},
+};
+use ark_ff::{batch_inversion, Field, One, Zero};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, sync::OnceLock};
+
+/// A Fiat-Shamir transcript.
+#[derive(Debug)]
+pub struct Transcript {
+    state: Sha256,
+}
+
+impl Transcript {
+    /// Creates a transcript with the given label.
+    pub fn new(label: &[u8]) -> Self {
+        Self { state: Sha256::new().chain_update(label) }
+    }
+
+    /// Separates the domains of different protocols using the same transcript.
+    pub fn domain_sep(&mut self, label: &[u8]) {
+        self.state.update(label);
+    }
+
+    /// Appends a scalar.
+    pub fn append_scalar(&mut self, scalar: &Fr, label: &[u8]) {
+        self.state.update(label);
+        self.state.update(scalar_to_bytes(scalar));
+    }
+
+    /// Appends a group element.
+    pub fn append_point(&mut self, point: &Element, label: &[u8]) {
+        self.state.update(label);
+        self.state.update(point.to_bytes());
+    }
+
+    /// Returns a challenge derived from everything appended so far.
+    pub fn challenge_scalar(&mut self, label: &[u8]) -> Fr {
+        self.domain_sep(label);
+        let hash = std::mem::take(&mut self.state).finalize();
+        let challenge = <Fr as ark_ff::PrimeField>::from_le_bytes_mod_order(&hash);
+        // The new state starts from the challenge, which summarises the previous state.
+        self.append_scalar(&challenge, label);
+        challenge
+    }
+}
+
+/// Precomputed values for working with polynomials in evaluation form over the domain.
+#[derive(Debug)]
+struct Domain {
+    /// `A'(x_i)`, where `A(X)` is the polynomial vanishing on the domain.
+    weights: Vec<Fr>,
+    /// `1 / A'(x_i)`.
+    inverted_weights: Vec<Fr>,
+    /// `1 / i` for `0 < i < VERKLE_NODE_WIDTH`, with a zero at index 0.
+    inverses: Vec<Fr>,
+}
+
+impl Domain {
+    fn get() -> &'static Self {
+        static DOMAIN: OnceLock<Domain> = OnceLock::new();
+        DOMAIN.get_or_init(|| {
+            let weights = (0..VERKLE_NODE_WIDTH)
+                .map(|i| {
+                    (0..VERKLE_NODE_WIDTH)
+                        .filter(|j| *j != i)
+                        .map(|j| Fr::from(i as u64) - Fr::from(j as u64))
+                        .product()
+                })
+                .collect::<Vec<Fr>>();
+            let mut inverted_weights = weights.clone();
+            batch_inversion(&mut inverted_weights);
+            let mut inverses = (0..VERKLE_NODE_WIDTH as u64).map(Fr::from).collect::<Vec<_>>();
+            batch_inversion(&mut inverses);
+            Self { weights, inverted_weights, inverses }
+        })
+    }
+
+    /// Returns `1 / (i - j)`.
+    fn inverse_difference(&self, i: usize, j: usize) -> Fr {
+        if i > j {
+            self.inverses[i - j]
+        } else {
+            -self.inverses[j - i]
+        }
+    }
+
+    /// Returns `(f(X) - f(x_m)) / (X - x_m)` in evaluation form.
+    fn divide_on_domain(&self, f: &[Fr], m: usize) -> Vec<Fr> {
+        let mut quotient = vec![Fr::zero(); VERKLE_NODE_WIDTH];
+        for i in (0..VERKLE_NODE_WIDTH).filter(|i| *i != m) {
+            let value = (f[i] - f[m]) * self.inverse_difference(i, m);
+            quotient[i] = value;
+            // The evaluation at `x_m` follows from L'Hôpital's rule.
+            quotient[m] -= value * self.weights[m] * self.inverted_weights[i];
+        }
+        quotient
+    }
+
+    /// Returns the coefficients `L_i(z)` of the Lagrange basis polynomials at a point `z` that
+    /// is not in the domain, so that `f(z) = sum(f[i] * L_i(z))`.
+    fn barycentric_coefficients(&self, z: Fr) -> Vec<Fr> {
+        let mut differences =
+            (0..VERKLE_NODE_WIDTH).map(|i| z - Fr::from(i as u64)).collect::<Vec<_>>();
+        let vanishing: Fr = differences.iter().product();
+        batch_inversion(&mut differences);
+        differences
+            .into_iter()
+            .zip(&self.inverted_weights)
+            .map(|(inverse, inverted_weight)| vanishing * inverse * inverted_weight)
+            .collect()
+    }
+}
+
+fn inner_product(a: &[Fr], b: &[Fr]) -> Fr {
+    a.iter().zip(b).map(|(a, b)| *a * b).sum()
+}
+
+/// An inner product argument proving that `<a, b> = y` for a committed vector `a`, where `b` are
+/// the barycentric coefficients of a point, i.e. that the committed polynomial evaluates to `y`
+/// at that point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpaProof {
+    /// The left commitments of the rounds.
+    pub l: Vec<Element>,
+    /// The right commitments of the rounds.
+    pub r: Vec<Element>,
+    /// The single element the vector is folded into.
+    pub a: Fr,
+}
+
+impl IpaProof {
+    fn create(transcript: &mut Transcript, commitment: Element, mut a: Vec<Fr>, point: Fr) -> Self {
+        let crs = Crs::get();
+        let mut b = Domain::get().barycentric_coefficients(point);
+        let mut g = crs.g.iter().map(|g| Element::from(*g)).collect::<Vec<_>>();
+        let y = inner_product(&a, &b);
+
+        transcript.domain_sep(b"ipa");
+        transcript.append_point(&commitment, b"C");
+        transcript.append_scalar(&point, b"input point");
+        transcript.append_scalar(&y, b"output point");
+        let q = crs.q * transcript.challenge_scalar(b"w");
+
+        let mut l = Vec::new();
+        let mut r = Vec::new();
+        while a.len() > 1 {
+            let half = a.len() / 2;
+            let (a_l, a_r) = a.split_at(half);
+            let (b_l, b_r) = b.split_at(half);
+            let (g_l, g_r) = g.split_at(half);
+            let (g_l_affine, g_r_affine) =
+                (Element::batch_to_affine(g_l), Element::batch_to_affine(g_r));
+
+            let c_l = Element::msm(&g_l_affine, a_r) + q * inner_product(a_r, b_l);
+            let c_r = Element::msm(&g_r_affine, a_l) + q * inner_product(a_l, b_r);
+            transcript.append_point(&c_l, b"L");
+            transcript.append_point(&c_r, b"R");
+            l.push(c_l);
+            r.push(c_r);
+
+            let x = transcript.challenge_scalar(b"x");
+            let x_inv = x.inverse().expect("challenge is not zero");
+            a = a_l.iter().zip(a_r).map(|(l, r)| *l + x * r).collect();
+            b = b_l.iter().zip(b_r).map(|(l, r)| *l + x_inv * r).collect();
+            g = g_l.iter().zip(g_r).map(|(l, r)| *l + *r * x_inv).collect();
+        }
+
+        Self { l, r, a: a[0] }
+    }
+
+    fn verify(&self, transcript: &mut Transcript, commitment: Element, point: Fr, y: Fr) -> bool {
+        let crs = Crs::get();
+        let rounds = VERKLE_NODE_WIDTH.trailing_zeros() as usize;
+        if self.l.len() != rounds || self.r.len() != rounds {
+            return false
+        }
+
+        transcript.domain_sep(b"ipa");
+        transcript.append_point(&commitment, b"C");
+        transcript.append_scalar(&point, b"input point");
+        transcript.append_scalar(&y, b"output point");
+        let q = crs.q * transcript.challenge_scalar(b"w");
+
+        let mut commitment = commitment + q * y;
+        let mut challenges = Vec::with_capacity(rounds);
+        for (l, r) in self.l.iter().zip(&self.r) {
+            transcript.append_point(l, b"L");
+            transcript.append_point(r, b"R");
+            let x = transcript.challenge_scalar(b"x");
+            let Some(x_inv) = x.inverse() else { return false };
+            commitment += *l * x + *r * x_inv;
+            challenges.push(x_inv);
+        }
+
+        // Fold the generators and the barycentric coefficients with the inverted challenges: the
+        // coefficient of the `i`-th element is the product of the challenges of the rounds in
+        // which it was in the right half.
+        let mut coefficients = vec![Fr::one(); VERKLE_NODE_WIDTH];
+        for (i, coefficient) in coefficients.iter_mut().enumerate() {
+            for (round, x_inv) in challenges.iter().enumerate() {
+                if i & (VERKLE_NODE_WIDTH >> (round + 1)) != 0 {
+                    *coefficient *= x_inv;
+                }
+            }
+        }
+        let g = Element::msm(&crs.g, &coefficients);
+        let b = inner_product(&Domain::get().barycentric_coefficients(point), &coefficients);
+
+        commitment == g * self.a + q * (self.a * b)
+    }
+}
+
+/// An opening of a committed polynomial at a point of the domain.
+#[derive(Debug, Clone)]
+pub struct ProverQuery {
+    /// The commitment to the polynomial.
+    pub commitment: Element,
+    /// The polynomial in evaluation form.
+    pub polynomial: Vec<Fr>,
+    /// The point of the domain.
+    pub point: u8,
+    /// The evaluation of the polynomial at the point.
+    pub result: Fr,
+}
+
+/// An opening to verify.
+#[derive(Debug, Clone)]
+pub struct VerifierQuery {
+    /// The commitment to the polynomial.
+    pub commitment: Element,
+    /// The point of the domain.
+    pub point: u8,
+    /// The claimed evaluation of the polynomial at the point.
+    pub result: Fr,
+}
+
+/// A proof of many openings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiProof {
+    /// The commitment to the aggregated quotient polynomial.
+    pub d: Element,
+    /// The proof of the evaluation of the aggregated polynomials at a random point.
+    pub ipa: IpaProof,
+}
+
+impl MultiProof {
+    /// Proves the openings of all queries.
+    pub fn create(queries: &[ProverQuery]) -> Self {
+        let crs = Crs::get();
+        let domain = Domain::get();
+        let mut transcript = Transcript::new(b"vt");
+        transcript.domain_sep(b"multiproof");
+        for query in queries {
+            transcript.append_point(&query.commitment, b"C");
+            transcript.append_scalar(&Fr::from(query.point as u64), b"z");
+            transcript.append_scalar(&query.result, b"y");
+        }
+        let r = transcript.challenge_scalar(b"r");
+        let powers = powers_of(r, queries.len());
+
+        // Aggregate the polynomials opened at the same point, so that each point is divided out
+        // once.
+        let mut aggregated = BTreeMap::<u8, Vec<Fr>>::new();
+        for (query, power) in queries.iter().zip(&powers) {
+            let polynomial = aggregated
+                .entry(query.point)
+                .or_insert_with(|| vec![Fr::zero(); VERKLE_NODE_WIDTH]);
+            for (aggregated, value) in polynomial.iter_mut().zip(&query.polynomial) {
+                *aggregated += *power * value;
+            }
+        }
+
+        // g(X) = sum(r^i * (f_i(X) - y_i) / (X - z_i))
+        let mut g = vec![Fr::zero(); VERKLE_NODE_WIDTH];
+        for (point, polynomial) in &aggregated {
+            for (g, quotient) in
+                g.iter_mut().zip(domain.divide_on_domain(polynomial, *point as usize))
+            {
+                *g += quotient;
+            }
+        }
+        let d = crs.commit(&g);
+        transcript.append_point(&d, b"D");
+        let t = transcript.challenge_scalar(b"t");
+
+        // h(X) = sum(r^i * f_i(X) / (t - z_i))
+        let mut inverses =
+            aggregated.keys().map(|point| t - Fr::from(*point as u64)).collect::<Vec<_>>();
+        batch_inversion(&mut inverses);
+        let mut h = vec![Fr::zero(); VERKLE_NODE_WIDTH];
+        for (polynomial, inverse) in aggregated.values().zip(inverses) {
+            for (h, value) in h.iter_mut().zip(polynomial) {
+                *h += inverse * value;
+            }
+        }
+        let e = crs.commit(&h);
+        transcript.append_point(&e, b"E");
+
+        let h_minus_g = h.iter().zip(&g).map(|(h, g)| *h - g).collect();
+        let ipa = IpaProof::create(&mut transcript, e - d, h_minus_g, t);
+
+        Self { d, ipa }
+    }
+
+    /// Verifies the openings of all queries.
+    pub fn verify(&self, queries: &[VerifierQuery]) -> bool {
+        let mut transcript = Transcript::new(b"vt");
+        transcript.domain_sep(b"multiproof");
+        for query in queries {
+            transcript.append_point(&query.commitment, b"C");
+            transcript.append_scalar(&Fr::from(query.point as u64), b"z");
+            transcript.append_scalar(&query.result, b"y");
+        }
+        let r = transcript.challenge_scalar(b"r");
+        let powers = powers_of(r, queries.len());
+
+        transcript.append_point(&self.d, b"D");
+        let t = transcript.challenge_scalar(b"t");
+
+        // E = sum(r^i / (t - z_i) * C_i), and g(t) - h(t) = sum(r^i * y_i / (t - z_i))
+        let mut coefficients =
+            queries.iter().map(|query| t - Fr::from(query.point as u64)).collect::<Vec<_>>();
+        batch_inversion(&mut coefficients);
+        let mut evaluation = Fr::zero();
+        for ((coefficient, power), query) in coefficients.iter_mut().zip(&powers).zip(queries) {
+            *coefficient *= power;
+            evaluation += *coefficient * query.result;
+        }
+        let commitments = Element::batch_to_affine(
+            &queries.iter().map(|query| query.commitment).collect::<Vec<_>>(),
+        );
+        let e = Element::msm(&commitments, &coefficients);
+        transcript.append_point(&e, b"E");
+
+        self.ipa.verify(&mut transcript, e - self.d, t, evaluation)
+    }
+}
+
+fn powers_of(base: Fr, count: usize) -> Vec<Fr> {
+    std::iter::successors(Some(Fr::one()), |power| Some(*power * base)).take(count).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polynomial(seed: u64) -> Vec<Fr> {
+        (0..VERKLE_NODE_WIDTH as u64).map(|i| Fr::from(seed * 1000 + i * i)).collect()
+    }
+
+    #[test]
+    fn divide_on_domain() {
+        let domain = Domain::get();
+        let f = polynomial(3);
+        let q = domain.divide_on_domain(&f, 7);
+        // q(X) * (X - 7) + f(7) = f(X) on the domain.
+        for (i, q) in q.iter().enumerate() {
+            assert_eq!(*q * (Fr::from(i as u64) - Fr::from(7u64)) + f[7], f[i]);
+        }
+        // q at 7 is consistent with the other evaluations.
+        let z = Fr::from(1000u64);
+        let coefficients = domain.barycentric_coefficients(z);
+        assert_eq!(
+            inner_product(&q, &coefficients) * (z - Fr::from(7u64)) + f[7],
+            inner_product(&f, &coefficients)
+        );
+    }
+
+    #[test]
+    fn multiproof() {
+        let crs = Crs::get();
+        let queries = [(1, 0u8), (2, 0), (3, 255), (1, 17)]
+            .map(|(seed, point)| {
+                let polynomial = polynomial(seed);
+                ProverQuery {
+                    commitment: crs.commit(&polynomial),
+                    result: polynomial[point as usize],
+                    polynomial,
+                    point,
+                }
+            })
+            .to_vec();
+        let proof = MultiProof::create(&queries);
+
+        let mut verifier_queries = queries
+            .iter()
+            .map(|query| VerifierQuery {
+                commitment: query.commitment,
+                point: query.point,
+                result: query.result,
+            })
+            .collect::<Vec<_>>();
+        assert!(proof.verify(&verifier_queries));
+
+        verifier_queries[2].result += Fr::one();
+        assert!(!proof.verify(&verifier_queries));
+    }
+}
@@ -0,0 +1,18 @@
+//! Experimental verkle trie implementation.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+pub mod banderwagon;
+pub mod crs;
+pub mod db;
+pub mod embedding;
+pub mod ipa;
+pub mod proof;
+pub mod state;
+pub mod trie;
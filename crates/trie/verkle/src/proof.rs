@@ -0,0 +1,442 @@
+//! Proofs of verkle trie leaves.
+
+use crate::{
+    banderwagon::{scalar_from_bytes, scalar_to_bytes, Element, Fr},
+    crs::VERKLE_NODE_WIDTH,
+    embedding::{key, split_key, Stem},
+    ipa::{IpaProof, MultiProof, ProverQuery, VerifierQuery},
+    trie::{leaf_scalars, stem_scalar, Node, VerkleTrieReader},
+};
+use alloy_primitives::{FixedBytes, B256};
+use ark_ff::{One, Zero};
+use reth_storage_errors::db::DatabaseError;
+use reth_trie_common::{VerkleIpaProof, VerkleProof};
+use std::collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// How a proven stem was found in the trie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExtensionStatus {
+    /// The child on the path to the stem is missing.
+    Absent = 0,
+    /// A stem node with another stem is on the path to the stem.
+    Other = 1,
+    /// The stem node of the stem is in the trie.
+    Present = 2,
+}
+
+impl ExtensionStatus {
+    const fn from_byte(byte: u8) -> Option<Self> {
+        match byte & 0b111 {
+            0 => Some(Self::Absent),
+            1 => Some(Self::Other),
+            2 => Some(Self::Present),
+            _ => None,
+        }
+    }
+}
+
+/// A proven stem with the leaves proven under it.
+#[derive(Debug)]
+struct ProvenStem {
+    stem: Stem,
+    /// The length of the path of the stem node or missing child on the path to the stem.
+    depth: usize,
+    status: ExtensionStatus,
+    /// The stem of the stem node found instead, if the status is [`ExtensionStatus::Other`].
+    other: Option<Stem>,
+    /// The sub indices of the proven leaves with their values.
+    leaves: Vec<(u8, Option<B256>)>,
+}
+
+/// Which commitment of the node at a path is opened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Commitment {
+    /// The commitment of the node itself.
+    Node,
+    /// The commitment to the leaves with sub indices below 128 of a stem node.
+    C1,
+    /// The commitment to the leaves with sub indices from 128 of a stem node.
+    C2,
+}
+
+type CommitmentKey = (Vec<u8>, Commitment);
+
+/// An opening of a commitment at a point.
+type Query = (CommitmentKey, u8, Fr);
+
+/// Returns the openings proving the leaves of the stems, or `None` if a commitment is missing
+/// or the stems claim different results for the same opening.
+///
+/// The commitments are requested in the order they are serialized in the proof.
+fn queries(
+    stems: &[ProvenStem],
+    mut commitment: impl FnMut(&CommitmentKey) -> Option<Element>,
+) -> Option<Vec<Query>> {
+    let mut queries = Vec::new();
+    let mut opened = HashMap::new();
+    let mut consistent = true;
+    let mut open =
+        |key: CommitmentKey, point: u8, result: Fr| match opened.entry((key.clone(), point)) {
+            Entry::Occupied(entry) => consistent &= *entry.get() == result,
+            Entry::Vacant(entry) => {
+                entry.insert(result);
+                queries.push((key, point, result));
+            }
+        };
+
+    for proven in stems {
+        let terminal = proven.stem[..proven.depth].to_vec();
+        for level in 0..proven.depth {
+            let parent = proven.stem[..level].to_vec();
+            commitment(&(parent.clone(), Commitment::Node))?;
+            let child = proven.stem[..=level].to_vec();
+            let result = if level + 1 == proven.depth && proven.status == ExtensionStatus::Absent {
+                Fr::zero()
+            } else {
+                commitment(&(child, Commitment::Node))?.map_to_scalar_field()
+            };
+            open((parent, Commitment::Node), proven.stem[level], result);
+        }
+
+        let stem = match proven.status {
+            ExtensionStatus::Absent => continue,
+            ExtensionStatus::Other => proven.other?,
+            ExtensionStatus::Present => proven.stem,
+        };
+        let node = (terminal.clone(), Commitment::Node);
+        open(node.clone(), 0, Fr::one());
+        open(node.clone(), 1, stem_scalar(&stem));
+        if proven.status == ExtensionStatus::Other {
+            continue
+        }
+
+        for (suffix, value) in &proven.leaves {
+            let (kind, point) =
+                if *suffix < 128 { (Commitment::C1, 2) } else { (Commitment::C2, 3) };
+            let key = (terminal.clone(), kind);
+            open(node.clone(), point, commitment(&key)?.map_to_scalar_field());
+            let (low, high) = leaf_scalars(value.as_ref());
+            let index = 2 * (suffix % 128);
+            open(key.clone(), index, low);
+            open(key, index + 1, high);
+        }
+    }
+
+    consistent.then_some(queries)
+}
+
+/// Proves the values of the leaves with the given keys in the stored trie.
+///
+/// Returns the values of the leaves, `None` for missing ones, and the proof.
+pub fn verkle_proof<R: VerkleTrieReader>(
+    reader: &mut R,
+    keys: impl IntoIterator<Item = B256>,
+) -> Result<(BTreeMap<B256, Option<B256>>, VerkleProof), DatabaseError> {
+    let mut suffixes = BTreeMap::<Stem, BTreeSet<u8>>::new();
+    for key in keys {
+        let (stem, suffix) = split_key(&key);
+        suffixes.entry(stem).or_default().insert(suffix);
+    }
+
+    // Find the nodes on the paths to the stems.
+    let mut nodes = HashMap::<Vec<u8>, Node>::new();
+    let root = reader.node(&[])?.map(Node::from_stored).transpose()?;
+    nodes.insert(Vec::new(), root.unwrap_or(Node::Internal { commitment: Element::zero() }));
+    let mut values = BTreeMap::new();
+    let mut stems = Vec::with_capacity(suffixes.len());
+    let mut other_stems = Vec::new();
+    for (stem, suffixes) in suffixes {
+        let mut depth = 0;
+        let (status, other) = loop {
+            depth += 1;
+            let path = stem[..depth].to_vec();
+            let node = match nodes.get(&path) {
+                Some(node) => Some(node.clone()),
+                None => reader.node(&path)?.map(Node::from_stored).transpose()?,
+            };
+            let Some(node) = node else { break (ExtensionStatus::Absent, None) };
+            nodes.insert(path, node.clone());
+            match node {
+                Node::Internal { .. } => continue,
+                Node::Stem(node) if node.stem == stem => break (ExtensionStatus::Present, None),
+                Node::Stem(node) => {
+                    let other = FixedBytes(node.stem);
+                    if !other_stems.contains(&other) {
+                        other_stems.push(other);
+                    }
+                    break (ExtensionStatus::Other, Some(node.stem))
+                }
+            }
+        };
+
+        let leaves = if status == ExtensionStatus::Present {
+            let stored = reader.leaves(&stem)?.into_iter().collect::<HashMap<_, _>>();
+            suffixes.into_iter().map(|suffix| (suffix, stored.get(&suffix).copied())).collect()
+        } else {
+            suffixes.into_iter().map(|suffix| (suffix, None)).collect::<Vec<_>>()
+        };
+        values.extend(leaves.iter().map(|(suffix, value)| (key(&stem, *suffix), *value)));
+        stems.push(ProvenStem { stem, depth, status, other, leaves });
+    }
+
+    let mut commitments = Vec::new();
+    let mut serialized = HashSet::new();
+    let queries = queries(&stems, |key| {
+        let node = &nodes[&key.0];
+        let commitment = match (key.1, node) {
+            (Commitment::Node, node) => node.commitment(),
+            (Commitment::C1, Node::Stem(node)) => node.c1,
+            (Commitment::C2, Node::Stem(node)) => node.c2,
+            _ => return None,
+        };
+        if !key.0.is_empty() && serialized.insert(key.clone()) {
+            commitments.push(B256::from(commitment.to_bytes()));
+        }
+        Some(commitment)
+    })
+    .ok_or(DatabaseError::Decode)?;
+
+    // Gather the committed vectors of the opened commitments.
+    let mut polynomials = HashMap::<CommitmentKey, (Element, Vec<Fr>)>::new();
+    let mut prover_queries = Vec::with_capacity(queries.len());
+    for (key, point, result) in queries {
+        if !polynomials.contains_key(&key) {
+            let polynomial = polynomial(reader, &nodes[&key.0], &key)?;
+            polynomials.insert(key.clone(), polynomial);
+        }
+        let (commitment, polynomial) = polynomials[&key].clone();
+        prover_queries.push(ProverQuery { commitment, polynomial, point, result });
+    }
+    let multiproof = MultiProof::create(&prover_queries);
+
+    let proof = VerkleProof {
+        other_stems,
+        depth_extension_present: stems
+            .iter()
+            .map(|stem| ((stem.depth as u8) << 3) | stem.status as u8)
+            .collect::<Vec<_>>()
+            .into(),
+        commitments_by_path: commitments,
+        d: B256::from(multiproof.d.to_bytes()),
+        ipa_proof: VerkleIpaProof {
+            cl: multiproof.ipa.l.iter().map(|point| B256::from(point.to_bytes())).collect(),
+            cr: multiproof.ipa.r.iter().map(|point| B256::from(point.to_bytes())).collect(),
+            final_evaluation: B256::from(scalar_to_bytes(&multiproof.ipa.a)),
+        },
+    };
+    Ok((values, proof))
+}
+
+/// Returns a commitment of a node with the vector it commits to.
+fn polynomial<R: VerkleTrieReader>(
+    reader: &mut R,
+    node: &Node,
+    key: &CommitmentKey,
+) -> Result<(Element, Vec<Fr>), DatabaseError> {
+    let mut polynomial = vec![Fr::zero(); VERKLE_NODE_WIDTH];
+    let commitment = match (node, key.1) {
+        (Node::Internal { commitment }, _) => {
+            let mut children = Vec::new();
+            let mut commitments = Vec::new();
+            for index in reader.children(&key.0)? {
+                let mut path = key.0.clone();
+                path.push(index);
+                if let Some(child) = reader.node(&path)? {
+                    children.push(index as usize);
+                    commitments.push(Node::from_stored(child)?.commitment());
+                }
+            }
+            for (index, scalar) in
+                children.into_iter().zip(Element::batch_map_to_scalar_field(&commitments))
+            {
+                polynomial[index] = scalar;
+            }
+            *commitment
+        }
+        (Node::Stem(node), Commitment::Node) => {
+            polynomial[0] = Fr::one();
+            polynomial[1] = stem_scalar(&node.stem);
+            polynomial[2] = node.c1.map_to_scalar_field();
+            polynomial[3] = node.c2.map_to_scalar_field();
+            node.commitment
+        }
+        (Node::Stem(node), kind) => {
+            for (suffix, value) in reader.leaves(&node.stem)? {
+                if (suffix < 128) == (kind == Commitment::C1) {
+                    let (low, high) = leaf_scalars(Some(&value));
+                    let index = 2 * (suffix as usize % 128);
+                    polynomial[index] = low;
+                    polynomial[index + 1] = high;
+                }
+            }
+            if kind == Commitment::C1 {
+                node.c1
+            } else {
+                node.c2
+            }
+        }
+    };
+    Ok((commitment, polynomial))
+}
+
+/// Verifies a proof of the values of verkle trie leaves against the root of the trie, where
+/// `None` claims that a leaf is missing.
+pub fn verify_verkle_proof(
+    root: B256,
+    leaves: &BTreeMap<B256, Option<B256>>,
+    proof: &VerkleProof,
+) -> bool {
+    verify(root, leaves, proof).is_some()
+}
+
+fn verify(root: B256, leaves: &BTreeMap<B256, Option<B256>>, proof: &VerkleProof) -> Option<()> {
+    let mut proven = BTreeMap::<Stem, Vec<(u8, Option<B256>)>>::new();
+    for (key, value) in leaves {
+        let (stem, suffix) = split_key(key);
+        proven.entry(stem).or_default().push((suffix, *value));
+    }
+    if proven.len() != proof.depth_extension_present.len() {
+        return None
+    }
+
+    let mut stems = Vec::with_capacity(proven.len());
+    for ((stem, leaves), byte) in proven.into_iter().zip(proof.depth_extension_present.iter()) {
+        let status = ExtensionStatus::from_byte(*byte)?;
+        let depth = (*byte >> 3) as usize;
+        if depth == 0 || depth > stem.len() {
+            return None
+        }
+        let other = match status {
+            ExtensionStatus::Present => None,
+            ExtensionStatus::Absent | ExtensionStatus::Other => {
+                if leaves.iter().any(|(_, value)| value.is_some()) {
+                    return None
+                }
+                if status == ExtensionStatus::Other {
+                    let other = proof
+                        .other_stems
+                        .iter()
+                        .find(|other| other[..depth] == stem[..depth] && other.0 != stem)?;
+                    Some(other.0)
+                } else {
+                    None
+                }
+            }
+        };
+        stems.push(ProvenStem { stem, depth, status, other, leaves });
+    }
+
+    let root = Element::from_bytes(&root.0)?;
+    let mut serialized = proof.commitments_by_path.iter();
+    let mut commitments = HashMap::<CommitmentKey, Element>::new();
+    let queries = queries(&stems, |key| {
+        if key.0.is_empty() && key.1 == Commitment::Node {
+            return Some(root)
+        }
+        if let Some(commitment) = commitments.get(key) {
+            return Some(*commitment)
+        }
+        let commitment = Element::from_bytes(&serialized.next()?.0)?;
+        commitments.insert(key.clone(), commitment);
+        Some(commitment)
+    })?;
+    if serialized.next().is_some() {
+        return None
+    }
+
+    let multiproof = MultiProof {
+        d: Element::from_bytes(&proof.d.0)?,
+        ipa: IpaProof {
+            l: proof
+                .ipa_proof
+                .cl
+                .iter()
+                .map(|point| Element::from_bytes(&point.0))
+                .collect::<Option<_>>()?,
+            r: proof
+                .ipa_proof
+                .cr
+                .iter()
+                .map(|point| Element::from_bytes(&point.0))
+                .collect::<Option<_>>()?,
+            a: scalar_from_bytes(&proof.ipa_proof.final_evaluation.0)?,
+        },
+    };
+    let queries = queries
+        .into_iter()
+        .map(|(key, point, result)| {
+            let commitment = if key.0.is_empty() { root } else { commitments[&key] };
+            VerifierQuery { commitment, point, result }
+        })
+        .collect::<Vec<_>>();
+    multiproof.verify(&queries).then_some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trie::InMemoryVerkleTrie;
+
+    fn leaf_key(stem_prefix: &[u8], suffix: u8) -> B256 {
+        let mut stem = [0xff; 31];
+        stem[..stem_prefix.len()].copy_from_slice(stem_prefix);
+        key(&stem, suffix)
+    }
+
+    #[test]
+    fn prove_and_verify() {
+        let mut trie = InMemoryVerkleTrie::default();
+        let root = trie
+            .update(BTreeMap::from([
+                (leaf_key(&[1, 1], 0), Some(B256::with_last_byte(1))),
+                (leaf_key(&[1, 1], 200), Some(B256::with_last_byte(2))),
+                (leaf_key(&[1, 2], 5), Some(B256::ZERO)),
+                (leaf_key(&[3], 7), Some(B256::with_last_byte(3))),
+            ]))
+            .unwrap();
+
+        let keys = [
+            // Present
+            leaf_key(&[1, 1], 0),
+            leaf_key(&[1, 1], 200),
+            // Missing leaf of a present stem
+            leaf_key(&[1, 1], 1),
+            // Absent stem
+            leaf_key(&[2], 0),
+            // Other stem in the place of the stem
+            leaf_key(&[3, 1], 7),
+        ];
+        let (values, proof) = verkle_proof(&mut trie, keys).unwrap();
+        assert_eq!(values[&keys[0]], Some(B256::with_last_byte(1)));
+        assert_eq!(values[&keys[1]], Some(B256::with_last_byte(2)));
+        assert_eq!(values[&keys[2]], None);
+        assert_eq!(values[&keys[3]], None);
+        assert_eq!(values[&keys[4]], None);
+        assert_eq!(proof.other_stems.len(), 1);
+        assert!(verify_verkle_proof(root, &values, &proof));
+
+        // The proof survives serialization.
+        let json = serde_json::to_string(&proof).unwrap();
+        assert!(verify_verkle_proof(root, &values, &serde_json::from_str(&json).unwrap()));
+
+        // Wrong values and roots are rejected.
+        for key in keys {
+            let mut wrong = values.clone();
+            wrong.insert(key, Some(B256::with_last_byte(9)));
+            assert!(!verify_verkle_proof(root, &wrong, &proof));
+        }
+        let other_root = trie
+            .update(BTreeMap::from([(leaf_key(&[4], 0), Some(B256::with_last_byte(4)))]))
+            .unwrap();
+        assert!(!verify_verkle_proof(other_root, &values, &proof));
+    }
+
+    #[test]
+    fn empty_trie() {
+        let mut trie = InMemoryVerkleTrie::default();
+        let keys = [leaf_key(&[1], 0)];
+        let (values, proof) = verkle_proof(&mut trie, keys).unwrap();
+        assert_eq!(values[&keys[0]], None);
+        assert!(verify_verkle_proof(B256::ZERO, &values, &proof));
+    }
+}
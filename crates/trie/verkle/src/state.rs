@@ -0,0 +1,368 @@
+//! Conversion of state changes into changes of verkle trie leaves.
+//!
+//! Every write of an account or storage slot is a present leaf, including writes of zero storage
+//! values, so leaves are only removed by unwinding the blocks that created them. Destroyed
+//! accounts get zero basic data and code hash leaves, and their code chunks are left in place.
+
+use crate::embedding::{
+    basic_data_key, basic_data_leaf, chunkify_code, code_chunk_key, code_hash_key, storage_slot_key,
+};
+use alloy_primitives::{Address, BlockNumber, B256, U256};
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::BlockNumberAddress,
+    transaction::DbTx,
+};
+use reth_primitives::{Account, KECCAK_EMPTY};
+use reth_storage_errors::db::DatabaseError;
+use reth_trie_common::VerkleLeafBeforeBlock;
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    ops::RangeInclusive,
+};
+
+/// The changes of the verkle trie leaves made by a range of blocks.
+#[derive(Debug, Default)]
+pub struct VerkleLeafChanges {
+    /// The values of the changed leaves after the range.
+    pub leaves: BTreeMap<B256, Option<B256>>,
+    /// The values of the leaves before each block that changed them.
+    pub changesets: Vec<(BlockNumber, VerkleLeafBeforeBlock)>,
+}
+
+/// A change of the state made by a block.
+#[derive(Debug)]
+enum StateChange {
+    Account(Address, Option<Account>),
+    Storage(Address, B256, U256),
+}
+
+/// The accounts and storage slots changed by a range of blocks, with their values before the
+/// first block of the range.
+#[derive(Debug, Default)]
+struct RangeChanges {
+    /// The state changes of each block, with the values after the block.
+    blocks: BTreeMap<BlockNumber, Vec<StateChange>>,
+    /// The accounts before the range.
+    accounts: BTreeMap<Address, Option<Account>>,
+    /// The storage slots before the range.
+    storages: BTreeMap<(Address, B256), U256>,
+}
+
+impl RangeChanges {
+    /// Reads the changesets of the range. The value of an account or storage slot after a block
+    /// is its value before the next block that changed it, or its current value.
+    fn read<TX: DbTx>(tx: &TX, range: RangeInclusive<BlockNumber>) -> Result<Self, DatabaseError> {
+        let mut accounts = BTreeMap::<_, Vec<_>>::new();
+        for entry in tx.cursor_read::<tables::AccountChangeSets>()?.walk_range(range.clone())? {
+            let (block, account) = entry?;
+            accounts.entry(account.address).or_default().push((block, account.info));
+        }
+
+        let mut storages = BTreeMap::<_, Vec<_>>::new();
+        for entry in tx
+            .cursor_read::<tables::StorageChangeSets>()?
+            .walk_range(BlockNumberAddress::range(range))?
+        {
+            let (BlockNumberAddress((block, address)), slot) = entry?;
+            storages.entry((address, slot.key)).or_default().push((block, slot.value));
+        }
+
+        let mut changes = Self::default();
+        for (address, before) in accounts {
+            let current = current_account(tx, address)?;
+            changes.accounts.insert(address, before[0].1);
+            for (index, (block, _)) in before.iter().enumerate() {
+                let after = before.get(index + 1).map_or(current, |(_, account)| *account);
+                changes
+                    .blocks
+                    .entry(*block)
+                    .or_default()
+                    .push(StateChange::Account(address, after));
+            }
+        }
+        for ((address, slot), before) in storages {
+            let current = current_storage(tx, address, slot)?;
+            changes.storages.insert((address, slot), before[0].1);
+            for (index, (block, _)) in before.iter().enumerate() {
+                let after = before.get(index + 1).map_or(current, |(_, value)| *value);
+                changes
+                    .blocks
+                    .entry(*block)
+                    .or_default()
+                    .push(StateChange::Storage(address, slot, after));
+            }
+        }
+        Ok(changes)
+    }
+}
+
+/// Returns the changes of the verkle trie leaves made by the blocks in `range`, computed from the
+/// account and storage changesets and the current state.
+pub fn verkle_leaf_changes<TX: DbTx>(
+    tx: &TX,
+    range: RangeInclusive<BlockNumber>,
+) -> Result<VerkleLeafChanges, DatabaseError> {
+    let changes = RangeChanges::read(tx, range)?;
+    let mut leaves = Leaves::new(tx.cursor_read::<tables::VerkleLeaves>()?);
+    for (block, block_changes) in changes.blocks {
+        for change in block_changes {
+            match change {
+                StateChange::Account(address, account) => {
+                    leaves.set_account(tx, block, address, account)?
+                }
+                StateChange::Storage(address, slot, value) => {
+                    leaves.set(block, storage_slot_key(&address, slot.into()), value.into())?
+                }
+            }
+        }
+    }
+    Ok(leaves.into_changes())
+}
+
+/// Returns the leaves of the verkle trie of the state before the blocks in `range`, computed from
+/// the current state and the account and storage changesets of the range.
+pub fn verkle_state_leaves<TX: DbTx>(
+    tx: &TX,
+    range: RangeInclusive<BlockNumber>,
+) -> Result<BTreeMap<B256, Option<B256>>, DatabaseError> {
+    let RangeChanges { mut accounts, mut storages, .. } = RangeChanges::read(tx, range)?;
+
+    let mut current_accounts = tx.cursor_read::<tables::PlainAccountState>()?;
+    let mut cold_accounts = tx.cursor_read::<tables::ColdAccountState>()?;
+    for entry in current_accounts.walk(None)?.chain(cold_accounts.walk(None)?) {
+        let (address, account) = entry?;
+        accounts.entry(address).or_insert(Some(account));
+    }
+    let mut current_storages = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+    let mut cold_storages = tx.cursor_dup_read::<tables::ColdStorageState>()?;
+    for entry in current_storages.walk(None)?.chain(cold_storages.walk(None)?) {
+        let (address, slot) = entry?;
+        storages.entry((address, slot.key)).or_insert(slot.value);
+    }
+
+    let mut leaves = BTreeMap::new();
+    for (address, account) in accounts {
+        if let Some(account) = account {
+            leaves.extend(account_leaves(tx, &address, &account)?);
+        }
+    }
+    for ((address, slot), value) in storages {
+        if !value.is_zero() {
+            leaves.insert(storage_slot_key(&address, slot.into()), Some(value.into()));
+        }
+    }
+    Ok(leaves)
+}
+
+/// Returns the leaves of an account: its basic data, code hash and code chunks.
+fn account_leaves<TX: DbTx>(
+    tx: &TX,
+    address: &Address,
+    account: &Account,
+) -> Result<Vec<(B256, Option<B256>)>, DatabaseError> {
+    let code_hash = account.bytecode_hash.unwrap_or(KECCAK_EMPTY);
+    let code = if code_hash == KECCAK_EMPTY {
+        Vec::new()
+    } else {
+        tx.get::<tables::Bytecodes>(code_hash)?
+            .map(|code| code.original_byte_slice().to_vec())
+            .unwrap_or_default()
+    };
+
+    let mut leaves = vec![
+        (basic_data_key(address), Some(basic_data_leaf(account, code.len() as u32))),
+        (code_hash_key(address), Some(code_hash)),
+    ];
+    leaves.extend(
+        chunkify_code(&code)
+            .into_iter()
+            .enumerate()
+            .map(|(chunk, value)| (code_chunk_key(address, chunk as u64), Some(value))),
+    );
+    Ok(leaves)
+}
+
+/// Reads an account from the plain state, or the cold state if it expired.
+fn current_account<TX: DbTx>(tx: &TX, address: Address) -> Result<Option<Account>, DatabaseError> {
+    match tx.get::<tables::PlainAccountState>(address)? {
+        Some(account) => Ok(Some(account)),
+        None => tx.get::<tables::ColdAccountState>(address),
+    }
+}
+
+/// Reads a storage slot from the plain state, or the cold state if it expired.
+fn current_storage<TX: DbTx>(tx: &TX, address: Address, slot: B256) -> Result<U256, DatabaseError> {
+    for value in [
+        tx.cursor_dup_read::<tables::PlainStorageState>()?.seek_by_key_subkey(address, slot)?,
+        tx.cursor_dup_read::<tables::ColdStorageState>()?.seek_by_key_subkey(address, slot)?,
+    ] {
+        if let Some(entry) = value.filter(|entry| entry.key == slot) {
+            return Ok(entry.value)
+        }
+    }
+    Ok(U256::ZERO)
+}
+
+/// The leaves changed while walking a range of blocks, read from the database on first access.
+#[derive(Debug)]
+struct Leaves<C> {
+    cursor: C,
+    /// The values of the leaves before the range and their current values.
+    leaves: BTreeMap<B256, (Option<B256>, Option<B256>)>,
+    changesets: Vec<(BlockNumber, VerkleLeafBeforeBlock)>,
+}
+
+impl<C: DbCursorRO<tables::VerkleLeaves>> Leaves<C> {
+    const fn new(cursor: C) -> Self {
+        Self { cursor, leaves: BTreeMap::new(), changesets: Vec::new() }
+    }
+
+    fn get(&mut self, key: B256) -> Result<Option<B256>, DatabaseError> {
+        match self.leaves.entry(key) {
+            Entry::Occupied(entry) => Ok(entry.get().1),
+            Entry::Vacant(entry) => {
+                let value = self.cursor.seek_exact(key)?.map(|(_, value)| value);
+                entry.insert((value, value));
+                Ok(value)
+            }
+        }
+    }
+
+    /// Writes a leaf in `block`. Zero values aren't written to missing leaves, since they don't
+    /// change the state.
+    fn set(&mut self, block: BlockNumber, key: B256, value: B256) -> Result<(), DatabaseError> {
+        let previous = self.get(key)?;
+        if previous == Some(value) || (previous.is_none() && value.is_zero()) {
+            return Ok(())
+        }
+        self.changesets.push((block, VerkleLeafBeforeBlock { key, value: previous }));
+        self.leaves.get_mut(&key).expect("leaf is read").1 = Some(value);
+        Ok(())
+    }
+
+    fn set_account<TX: DbTx>(
+        &mut self,
+        tx: &TX,
+        block: BlockNumber,
+        address: Address,
+        account: Option<Account>,
+    ) -> Result<(), DatabaseError> {
+        let Some(account) = account else {
+            self.set(block, basic_data_key(&address), B256::ZERO)?;
+            return self.set(block, code_hash_key(&address), B256::ZERO)
+        };
+
+        // Code chunks are only written when the code changes.
+        let code_hash = account.bytecode_hash.unwrap_or(KECCAK_EMPTY);
+        let code_changed = self.get(code_hash_key(&address))? != Some(code_hash);
+        for (key, value) in account_leaves(tx, &address, &account)? {
+            let value = value.expect("account leaves are present");
+            if key == basic_data_key(&address) || key == code_hash_key(&address) || code_changed {
+                self.set(block, key, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn into_changes(self) -> VerkleLeafChanges {
+        VerkleLeafChanges {
+            leaves: self
+                .leaves
+                .into_iter()
+                .filter(|(_, (before, after))| before != after)
+                .map(|(key, (_, after))| (key, after))
+                .collect(),
+            changesets: self.changesets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{verkle_trie_reader, write_verkle_updates},
+        trie::{update_verkle_trie, InMemoryVerkleTrie},
+    };
+    use alloy_primitives::{keccak256, Bytes};
+    use reth_db::test_utils::create_test_rw_db;
+    use reth_db_api::{database::Database, models::AccountBeforeTx, transaction::DbTxMut};
+    use reth_primitives::{Bytecode, StorageEntry};
+
+    #[test]
+    fn range_leaf_changes() {
+        let db = create_test_rw_db();
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let (first, second) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        let code = Bytes::from_static(&[0x60, 0x01, 0x00]);
+        let account = |nonce, balance: u64, bytecode_hash| Account {
+            nonce,
+            balance: U256::from(balance),
+            bytecode_hash,
+        };
+
+        // The state after block 2, and the changesets of blocks 1 and 2.
+        let tx = db.tx_mut().unwrap();
+        tx.put::<tables::PlainAccountState>(a, account(1, 1, None)).unwrap();
+        tx.put::<tables::PlainAccountState>(b, account(0, 3, Some(keccak256(&code)))).unwrap();
+        tx.put::<tables::Bytecodes>(keccak256(&code), Bytecode::new_raw(code.clone())).unwrap();
+        for (key, value) in [(first, 6), (second, 7)] {
+            tx.put::<tables::PlainStorageState>(a, StorageEntry { key, value: U256::from(value) })
+                .unwrap();
+        }
+        for (block, address, info) in [
+            (1, a, Some(account(0, 1, None))),
+            (1, b, None),
+            (2, b, Some(account(0, 2, Some(keccak256(&code))))),
+        ] {
+            tx.put::<tables::AccountChangeSets>(block, AccountBeforeTx { address, info }).unwrap();
+        }
+        for (block, key, value) in [(1, first, 5), (2, second, 0)] {
+            tx.put::<tables::StorageChangeSets>(
+                BlockNumberAddress((block, a)),
+                StorageEntry { key, value: U256::from(value) },
+            )
+            .unwrap();
+        }
+
+        let genesis = verkle_state_leaves(&tx, 1..=2).unwrap();
+        assert_eq!(
+            genesis,
+            BTreeMap::from([
+                (basic_data_key(&a), Some(basic_data_leaf(&account(0, 1, None), 0))),
+                (code_hash_key(&a), Some(KECCAK_EMPTY)),
+                (storage_slot_key(&a, first.into()), Some(U256::from(5).into())),
+            ])
+        );
+        let (_, updates) =
+            update_verkle_trie(&mut verkle_trie_reader(&tx).unwrap(), genesis).unwrap();
+        write_verkle_updates(&tx, &updates).unwrap();
+
+        let changes = verkle_leaf_changes(&tx, 1..=2).unwrap();
+        let (root, _) =
+            update_verkle_trie(&mut verkle_trie_reader(&tx).unwrap(), changes.leaves).unwrap();
+        let expected =
+            InMemoryVerkleTrie::default().update(verkle_state_leaves(&tx, 3..=3).unwrap()).unwrap();
+        assert_eq!(root, expected);
+
+        // The basic data leaf of the new account is changed by both blocks, and its code hash and
+        // code chunk by the first one.
+        assert_eq!(
+            changes.changesets.iter().filter(|(_, leaf)| leaf.key == basic_data_key(&b)).count(),
+            2
+        );
+        assert!(changes
+            .changesets
+            .contains(&(1, VerkleLeafBeforeBlock { key: code_chunk_key(&b, 0), value: None })));
+        assert!(changes.changesets.contains(&(
+            1,
+            VerkleLeafBeforeBlock {
+                key: basic_data_key(&a),
+                value: Some(basic_data_leaf(&account(0, 1, None), 0))
+            }
+        )));
+        assert_eq!(changes.changesets.len(), 7);
+    }
+}
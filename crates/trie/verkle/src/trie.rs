@@ -0,0 +1,613 @@
+//! The verkle trie and its incremental updates.
+
+use crate::{
+    banderwagon::{Element, Fr},
+    crs::Crs,
+    embedding::{key, split_key, Stem},
+};
+use alloy_primitives::{FixedBytes, B256, B512};
+use ark_ff::{Field, One, PrimeField, Zero};
+use reth_storage_errors::db::DatabaseError;
+use reth_trie_common::{StoredVerkleNode, VerklePath};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Read access to a stored verkle trie.
+pub trait VerkleTrieReader {
+    /// Returns the node at `path`.
+    fn node(&mut self, path: &[u8]) -> Result<Option<StoredVerkleNode>, DatabaseError>;
+
+    /// Returns the indices of the children of the internal node at `path`.
+    fn children(&mut self, path: &[u8]) -> Result<Vec<u8>, DatabaseError>;
+
+    /// Returns the leaves of `stem` with their sub indices, in ascending order.
+    fn leaves(&mut self, stem: &Stem) -> Result<Vec<(u8, B256)>, DatabaseError>;
+}
+
+/// A node of the verkle trie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    /// An internal node, committing to the commitments of its children.
+    Internal {
+        /// The commitment of the node.
+        commitment: Element,
+    },
+    /// A stem node, committing to the leaves sharing its stem.
+    Stem(Box<StemNode>),
+}
+
+impl Node {
+    /// Returns the commitment of the node.
+    pub const fn commitment(&self) -> Element {
+        match self {
+            Self::Internal { commitment } => *commitment,
+            Self::Stem(node) => node.commitment,
+        }
+    }
+
+    /// Decodes a stored node.
+    pub fn from_stored(node: StoredVerkleNode) -> Result<Self, DatabaseError> {
+        let element =
+            |bytes: &B512| Element::from_uncompressed_bytes(&bytes.0).ok_or(DatabaseError::Decode);
+        Ok(match node {
+            StoredVerkleNode::Internal { commitment } => {
+                Self::Internal { commitment: element(&commitment)? }
+            }
+            StoredVerkleNode::Stem { stem, c1, c2, commitment } => Self::Stem(Box::new(StemNode {
+                stem: stem.0,
+                c1: element(&c1)?,
+                c2: element(&c2)?,
+                commitment: element(&commitment)?,
+            })),
+        })
+    }
+
+    /// Encodes the node to be stored.
+    pub fn to_stored(&self) -> StoredVerkleNode {
+        let bytes = |element: &Element| B512::from(element.to_uncompressed_bytes());
+        match self {
+            Self::Internal { commitment } => {
+                StoredVerkleNode::Internal { commitment: bytes(commitment) }
+            }
+            Self::Stem(node) => StoredVerkleNode::Stem {
+                stem: FixedBytes(node.stem),
+                c1: bytes(&node.c1),
+                c2: bytes(&node.c2),
+                commitment: bytes(&node.commitment),
+            },
+        }
+    }
+}
+
+/// A stem node of the verkle trie.
+///
+/// Each leaf is split into two scalars: its lower 16 bytes plus `2^128`, which marks the leaf as
+/// present, and its upper 16 bytes. `c1` commits to the scalars of the leaves with sub indices
+/// below 128 and `c2` to the rest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StemNode {
+    /// The stem of the leaves.
+    pub stem: Stem,
+    /// The commitment to the leaves with sub indices below 128.
+    pub c1: Element,
+    /// The commitment to the leaves with sub indices from 128.
+    pub c2: Element,
+    /// The commitment to `[1, stem, c1, c2]`.
+    pub commitment: Element,
+}
+
+impl StemNode {
+    /// Creates the stem node of the given leaves.
+    pub fn new(stem: Stem, leaves: impl IntoIterator<Item = (u8, B256)>) -> Self {
+        let mut c1 = Vec::new();
+        let mut c2 = Vec::new();
+        for (suffix, value) in leaves {
+            let (low, high) = leaf_scalars(Some(&value));
+            let index = 2 * (suffix as usize % 128);
+            let values = if suffix < 128 { &mut c1 } else { &mut c2 };
+            values.extend([(index, low), (index + 1, high)]);
+        }
+
+        let crs = Crs::get();
+        let (c1, c2) = (crs.commit_sparse(&c1), crs.commit_sparse(&c2));
+        Self { stem, c1, c2, commitment: stem_commitment(&stem, &c1, &c2) }
+    }
+
+    /// Returns the stem node with the leaves changed from their previous to their new values.
+    fn update(&self, changes: &[(u8, Option<B256>, Option<B256>)]) -> Self {
+        let mut c1 = Vec::new();
+        let mut c2 = Vec::new();
+        for (suffix, old, new) in changes {
+            let (old_low, old_high) = leaf_scalars(old.as_ref());
+            let (new_low, new_high) = leaf_scalars(new.as_ref());
+            let index = 2 * (*suffix as usize % 128);
+            let deltas = if *suffix < 128 { &mut c1 } else { &mut c2 };
+            deltas.extend([(index, new_low - old_low), (index + 1, new_high - old_high)]);
+        }
+
+        let crs = Crs::get();
+        let c1 = self.c1 + crs.commit_sparse(&c1);
+        let c2 = self.c2 + crs.commit_sparse(&c2);
+        let commitment = self.commitment +
+            crs.commit_sparse(&[
+                (2, c1.map_to_scalar_field() - self.c1.map_to_scalar_field()),
+                (3, c2.map_to_scalar_field() - self.c2.map_to_scalar_field()),
+            ]);
+        Self { stem: self.stem, c1, c2, commitment }
+    }
+}
+
+/// Returns the commitment of a stem node.
+fn stem_commitment(stem: &Stem, c1: &Element, c2: &Element) -> Element {
+    Crs::get().commit_sparse(&[
+        (0, Fr::one()),
+        (1, stem_scalar(stem)),
+        (2, c1.map_to_scalar_field()),
+        (3, c2.map_to_scalar_field()),
+    ])
+}
+
+/// Returns the scalar a stem is committed to as.
+pub fn stem_scalar(stem: &Stem) -> Fr {
+    Fr::from_le_bytes_mod_order(stem)
+}
+
+/// Returns the low and high scalars a leaf is committed to as, which are zero for a missing leaf.
+pub fn leaf_scalars(value: Option<&B256>) -> (Fr, Fr) {
+    let Some(value) = value else { return (Fr::zero(), Fr::zero()) };
+    let marker = Fr::from(2u64).pow([128]);
+    (Fr::from_le_bytes_mod_order(&value[..16]) + marker, Fr::from_le_bytes_mod_order(&value[16..]))
+}
+
+/// The changes of a verkle trie update, to be written to the database.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct VerkleTrieUpdates {
+    /// The changed nodes, or `None` if they were removed.
+    pub nodes: BTreeMap<VerklePath, Option<StoredVerkleNode>>,
+    /// The changed leaves with their previous and new values.
+    pub leaves: BTreeMap<B256, (Option<B256>, Option<B256>)>,
+}
+
+impl VerkleTrieUpdates {
+    /// Returns `true` if there are no changes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.leaves.is_empty()
+    }
+
+    /// Extends the updates with later ones.
+    pub fn extend(&mut self, other: Self) {
+        self.nodes.extend(other.nodes);
+        for (key, (old, new)) in other.leaves {
+            self.leaves.entry(key).and_modify(|(_, current)| *current = new).or_insert((old, new));
+        }
+        self.leaves.retain(|_, (old, new)| old != new);
+    }
+}
+
+/// Returns the root of the stored verkle trie: the serialized commitment of its root node, or
+/// zero for an empty trie.
+pub fn verkle_root<R: VerkleTrieReader>(reader: &mut R) -> Result<B256, DatabaseError> {
+    let root = reader.node(&[])?.map(Node::from_stored).transpose()?;
+    Ok(B256::from(root.map(|node| node.commitment()).unwrap_or_default().to_bytes()))
+}
+
+/// Applies leaf changes to the stored verkle trie, where `None` removes a leaf.
+///
+/// Returns the new root and the changes to write to the database.
+pub fn update_verkle_trie<R: VerkleTrieReader>(
+    reader: &mut R,
+    changes: BTreeMap<B256, Option<B256>>,
+) -> Result<(B256, VerkleTrieUpdates), DatabaseError> {
+    let mut stems = BTreeMap::<Stem, Vec<(u8, Option<B256>)>>::new();
+    for (key, value) in changes {
+        let (stem, suffix) = split_key(&key);
+        stems.entry(stem).or_default().push((suffix, value));
+    }
+
+    let mut updater = Updater { reader, nodes: BTreeMap::new(), original: BTreeMap::new() };
+    let mut leaves = BTreeMap::new();
+    for (stem, changes) in stems {
+        updater.update_stem(stem, changes, &mut leaves)?;
+    }
+    let root = updater.commit()?;
+
+    let nodes = updater
+        .nodes
+        .into_iter()
+        .map(|(path, node)| (VerklePath(path), node.as_ref().map(Node::to_stored)))
+        .collect();
+    Ok((B256::from(root.to_bytes()), VerkleTrieUpdates { nodes, leaves }))
+}
+
+/// Applies changes to a stored trie, keeping the changed nodes in memory.
+#[derive(Debug)]
+struct Updater<'a, R> {
+    reader: &'a mut R,
+    /// The changed nodes, or `None` if they were removed.
+    nodes: BTreeMap<Vec<u8>, Option<Node>>,
+    /// The stored nodes at the changed paths.
+    original: BTreeMap<Vec<u8>, Option<Node>>,
+}
+
+impl<R: VerkleTrieReader> Updater<'_, R> {
+    fn get(&mut self, path: &[u8]) -> Result<Option<Node>, DatabaseError> {
+        if let Some(node) = self.nodes.get(path) {
+            return Ok(node.clone())
+        }
+        self.reader.node(path)?.map(Node::from_stored).transpose()
+    }
+
+    fn set(&mut self, path: Vec<u8>, node: Option<Node>) -> Result<(), DatabaseError> {
+        if !self.original.contains_key(&path) {
+            let original = self.reader.node(&path)?.map(Node::from_stored).transpose()?;
+            self.original.insert(path.clone(), original);
+        }
+        self.nodes.insert(path, node);
+        Ok(())
+    }
+
+    fn children(&mut self, path: &[u8]) -> Result<BTreeSet<u8>, DatabaseError> {
+        let mut children = self.reader.children(path)?.into_iter().collect::<BTreeSet<_>>();
+        for (child, node) in self
+            .nodes
+            .range(path.to_vec()..)
+            .take_while(|(child, _)| child.starts_with(path))
+            .filter(|(child, _)| child.len() == path.len() + 1)
+        {
+            if node.is_some() {
+                children.insert(child[path.len()]);
+            } else {
+                children.remove(&child[path.len()]);
+            }
+        }
+        Ok(children)
+    }
+
+    /// Applies the changes of the leaves of `stem`, and places its stem node in the trie.
+    fn update_stem(
+        &mut self,
+        stem: Stem,
+        changes: Vec<(u8, Option<B256>)>,
+        leaves: &mut BTreeMap<B256, (Option<B256>, Option<B256>)>,
+    ) -> Result<(), DatabaseError> {
+        let mut current = self.reader.leaves(&stem)?.into_iter().collect::<BTreeMap<_, _>>();
+        let mut changed = Vec::new();
+        for (suffix, new) in changes {
+            let old = current.get(&suffix).copied();
+            if old != new {
+                changed.push((suffix, old, new));
+                leaves.insert(key(&stem, suffix), (old, new));
+                match new {
+                    Some(value) => current.insert(suffix, value),
+                    None => current.remove(&suffix),
+                };
+            }
+        }
+        if changed.is_empty() {
+            return Ok(())
+        }
+
+        if self.get(&[])?.is_none() {
+            self.set(Vec::new(), Some(Node::Internal { commitment: Element::zero() }))?;
+        }
+
+        let mut path = Vec::new();
+        loop {
+            path.push(stem[path.len()]);
+            match self.get(&path)? {
+                Some(Node::Internal { .. }) => continue,
+                Some(Node::Stem(node)) if node.stem == stem => {
+                    if current.is_empty() {
+                        self.set(path.clone(), None)?;
+                        self.collapse(path)?;
+                    } else {
+                        self.set(path, Some(Node::Stem(Box::new(node.update(&changed)))))?;
+                    }
+                }
+                // The leaves are new, because a stem node exists for any stored leaves.
+                existing => {
+                    let node = StemNode::new(stem, current);
+                    if let Some(Node::Stem(other)) = existing {
+                        // Push the other stem node down until the stems diverge.
+                        self.set(
+                            path.clone(),
+                            Some(Node::Internal { commitment: Element::zero() }),
+                        )?;
+                        while other.stem[path.len()] == stem[path.len()] {
+                            path.push(stem[path.len()]);
+                            self.set(
+                                path.clone(),
+                                Some(Node::Internal { commitment: Element::zero() }),
+                            )?;
+                        }
+                        let mut other_path = path.clone();
+                        other_path.push(other.stem[path.len()]);
+                        self.set(other_path, Some(Node::Stem(other)))?;
+                        path.push(stem[path.len()]);
+                    }
+                    self.set(path, Some(Node::Stem(Box::new(node))))?;
+                }
+            }
+            return Ok(())
+        }
+    }
+
+    /// Restores the shape of the trie after the node at `path` was removed: internal nodes
+    /// without children are removed, and a stem node that is the only child of an internal node
+    /// replaces it.
+    fn collapse(&mut self, mut path: Vec<u8>) -> Result<(), DatabaseError> {
+        while path.len() > 1 {
+            path.pop();
+            let children = self.children(&path)?;
+            if children.is_empty() {
+                self.set(path.clone(), None)?;
+                continue
+            }
+            if children.len() == 1 {
+                let mut child = path.clone();
+                child.extend(children);
+                if let Some(Node::Stem(node)) = self.get(&child)? {
+                    self.set(child, None)?;
+                    self.set(path.clone(), Some(Node::Stem(node)))?;
+                    continue
+                }
+            }
+            break
+        }
+        Ok(())
+    }
+
+    /// Computes the commitments of the internal nodes above the changed nodes, from the bottom
+    /// up, and returns the commitment of the root.
+    fn commit(&mut self) -> Result<Element, DatabaseError> {
+        let mut internal = BTreeSet::new();
+        for (path, node) in &self.nodes {
+            internal.extend((0..path.len()).map(|len| path[..len].to_vec()));
+            if matches!(node, Some(Node::Internal { .. })) {
+                internal.insert(path.clone());
+            }
+        }
+
+        let mut internal = internal.into_iter().collect::<Vec<_>>();
+        internal.sort_by_key(|path| std::cmp::Reverse(path.len()));
+        for path in internal {
+            if !matches!(self.get(&path)?, Some(Node::Internal { .. })) {
+                continue
+            }
+
+            let commitment = match self.original.get(&path).cloned() {
+                // The node is new, so it commits to all of its children.
+                Some(None | Some(Node::Stem(_))) => {
+                    let mut values = Vec::new();
+                    for index in self.children(&path)? {
+                        let mut child = path.clone();
+                        child.push(index);
+                        let commitment = self.get(&child)?.map(|node| node.commitment());
+                        values.push((index as usize, commitment.unwrap_or_default()));
+                    }
+                    let (indices, commitments): (Vec<_>, Vec<_>) = values.into_iter().unzip();
+                    let scalars = Element::batch_map_to_scalar_field(&commitments);
+                    Crs::get().commit_sparse(&indices.into_iter().zip(scalars).collect::<Vec<_>>())
+                }
+                // The node existed, so only the changed children are committed to again.
+                original => {
+                    let commitment = match original {
+                        Some(Some(node)) => node.commitment(),
+                        _ => self.get(&path)?.map(|node| node.commitment()).unwrap_or_default(),
+                    };
+                    let changed = self
+                        .original
+                        .range(path.clone()..)
+                        .take_while(|(child, _)| child.starts_with(&path))
+                        .filter(|(child, _)| child.len() == path.len() + 1)
+                        .map(|(child, node)| {
+                            (child.clone(), node.as_ref().map(Node::commitment).unwrap_or_default())
+                        })
+                        .collect::<Vec<_>>();
+                    let mut deltas = Vec::with_capacity(changed.len());
+                    for (child, old) in changed {
+                        let new =
+                            self.get(&child)?.map(|node| node.commitment()).unwrap_or_default();
+                        deltas.push((
+                            child[path.len()] as usize,
+                            new.map_to_scalar_field() - old.map_to_scalar_field(),
+                        ));
+                    }
+                    commitment + Crs::get().commit_sparse(&deltas)
+                }
+            };
+            self.set(path, Some(Node::Internal { commitment }))?;
+        }
+
+        Ok(self.get(&[])?.map(|node| node.commitment()).unwrap_or_default())
+    }
+}
+
+/// A verkle trie kept in memory.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct InMemoryVerkleTrie {
+    nodes: BTreeMap<Vec<u8>, StoredVerkleNode>,
+    leaves: BTreeMap<B256, B256>,
+}
+
+impl InMemoryVerkleTrie {
+    /// Applies leaf changes, where `None` removes a leaf, and returns the new root.
+    pub fn update(&mut self, changes: BTreeMap<B256, Option<B256>>) -> Result<B256, DatabaseError> {
+        let (root, updates) = update_verkle_trie(self, changes)?;
+        self.apply(updates);
+        Ok(root)
+    }
+
+    /// Applies the updates of [`update_verkle_trie`].
+    pub fn apply(&mut self, updates: VerkleTrieUpdates) {
+        for (path, node) in updates.nodes {
+            match node {
+                Some(node) => self.nodes.insert(path.0, node),
+                None => self.nodes.remove(&path.0),
+            };
+        }
+        for (key, (_, value)) in updates.leaves {
+            match value {
+                Some(value) => self.leaves.insert(key, value),
+                None => self.leaves.remove(&key),
+            };
+        }
+    }
+
+    /// Returns the leaves of the trie.
+    pub const fn leaves(&self) -> &BTreeMap<B256, B256> {
+        &self.leaves
+    }
+}
+
+impl VerkleTrieReader for InMemoryVerkleTrie {
+    fn node(&mut self, path: &[u8]) -> Result<Option<StoredVerkleNode>, DatabaseError> {
+        Ok(self.nodes.get(path).cloned())
+    }
+
+    fn children(&mut self, path: &[u8]) -> Result<Vec<u8>, DatabaseError> {
+        Ok(self
+            .nodes
+            .range(path.to_vec()..)
+            .take_while(|(child, _)| child.starts_with(path))
+            .filter(|(child, _)| child.len() == path.len() + 1)
+            .map(|(child, _)| child[path.len()])
+            .collect())
+    }
+
+    fn leaves(&mut self, stem: &Stem) -> Result<Vec<(u8, B256)>, DatabaseError> {
+        Ok(self
+            .leaves
+            .range(key(stem, 0)..=key(stem, u8::MAX))
+            .map(|(key, value)| (key[31], *value))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Computes the root of the trie with the given leaves from scratch.
+    fn root(leaves: &BTreeMap<B256, B256>) -> B256 {
+        fn commit(path: &[u8], stems: &BTreeMap<Stem, Vec<(u8, B256)>>) -> Element {
+            let mut below = stems.iter().filter(|(stem, _)| stem.starts_with(path)).peekable();
+            let (stem, leaves) = below.next().expect("at least one stem");
+            if below.peek().is_none() && !path.is_empty() {
+                return StemNode::new(*stem, leaves.clone()).commitment
+            }
+
+            let children = (0..=u8::MAX)
+                .filter_map(|index| {
+                    let mut child = path.to_vec();
+                    child.push(index);
+                    stems
+                        .keys()
+                        .any(|stem| stem.starts_with(&child))
+                        .then(|| (index as usize, commit(&child, stems).map_to_scalar_field()))
+                })
+                .collect::<Vec<_>>();
+            Crs::get().commit_sparse(&children)
+        }
+
+        let mut stems = BTreeMap::<Stem, Vec<(u8, B256)>>::new();
+        for (key, value) in leaves {
+            let (stem, suffix) = split_key(key);
+            stems.entry(stem).or_default().push((suffix, *value));
+        }
+        if stems.is_empty() {
+            return B256::ZERO
+        }
+        B256::from(commit(&[], &stems).to_bytes())
+    }
+
+    fn leaf_key(stem_prefix: &[u8], suffix: u8) -> B256 {
+        let mut stem = [0xff; 31];
+        stem[..stem_prefix.len()].copy_from_slice(stem_prefix);
+        key(&stem, suffix)
+    }
+
+    #[test]
+    fn empty_trie() {
+        let mut trie = InMemoryVerkleTrie::default();
+        assert_eq!(verkle_root(&mut trie).unwrap(), B256::ZERO);
+        assert_eq!(trie.update(BTreeMap::new()).unwrap(), B256::ZERO);
+    }
+
+    /// The root of rust-verkle's `insert_key0value0`, which hashes the root commitment to a scalar
+    /// and serializes it little endian.
+    #[test]
+    fn rust_verkle_root() {
+        let mut trie = InMemoryVerkleTrie::default();
+        let root = trie.update(BTreeMap::from([(B256::ZERO, Some(B256::ZERO))])).unwrap();
+        let commitment = Element::from_bytes(&root).unwrap();
+        assert_eq!(
+            ark_ff::BigInteger::to_bytes_le(&commitment.map_to_scalar_field().into_bigint()),
+            alloy_primitives::hex!(
+                "ff00a9f3f2d4f58fc23bceebf6b2310419ceac2c30445e2f374e571487715015"
+            )
+        );
+    }
+
+    #[test]
+    fn insert_split_and_collapse() {
+        let mut trie = InMemoryVerkleTrie::default();
+        let first = leaf_key(&[1, 2, 3], 0);
+        let second = leaf_key(&[1, 2, 4], 200);
+        let third = leaf_key(&[1, 2, 4], 3);
+
+        let root_first =
+            trie.update(BTreeMap::from([(first, Some(B256::with_last_byte(1)))])).unwrap();
+        assert_eq!(root_first, root(trie.leaves()));
+        assert_eq!(verkle_root(&mut trie).unwrap(), root_first);
+
+        // The stems share two bytes, so the first stem node is pushed down two levels.
+        let root_all = trie
+            .update(BTreeMap::from([
+                (second, Some(B256::with_last_byte(2))),
+                (third, Some(B256::with_last_byte(3))),
+            ]))
+            .unwrap();
+        assert_eq!(root_all, root(trie.leaves()));
+        assert!(matches!(trie.node(&[1, 2, 3]).unwrap(), Some(StoredVerkleNode::Stem { .. })));
+
+        let updated = trie.update(BTreeMap::from([(third, Some(B256::ZERO))])).unwrap();
+        assert_eq!(updated, root(trie.leaves()));
+
+        // Removing the leaves of the second stem moves the first stem node back up.
+        let removed = trie.update(BTreeMap::from([(second, None), (third, None)])).unwrap();
+        assert_eq!(removed, root_first);
+        assert!(matches!(trie.node(&[1]).unwrap(), Some(StoredVerkleNode::Stem { .. })));
+        assert_eq!(trie.node(&[1, 2]).unwrap(), None);
+
+        assert_eq!(trie.update(BTreeMap::from([(first, None)])).unwrap(), B256::ZERO);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(8))]
+
+        #[test]
+        fn incremental_updates(
+            batches in proptest::collection::vec(
+                proptest::collection::btree_map(
+                    (proptest::collection::vec(0..3u8, 3), any::<u8>()),
+                    proptest::option::of(any::<u8>()),
+                    1..8,
+                ),
+                1..4,
+            )
+        ) {
+            let mut trie = InMemoryVerkleTrie::default();
+            for batch in batches {
+                let changes = batch
+                    .into_iter()
+                    .map(|((prefix, suffix), value)| {
+                        (leaf_key(&prefix, suffix), value.map(B256::with_last_byte))
+                    })
+                    .collect();
+                let updated = trie.update(changes).unwrap();
+                prop_assert_eq!(updated, root(trie.leaves()));
+            }
+        }
+    }
+}
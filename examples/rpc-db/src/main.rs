@@ -12,11 +12,11 @@
 //! cast rpc myrpcExt_customMethod
 //! ```
 
-use std::{path::Path, sync::Arc};
+use std::{path::Path, sync::Arc, time::Duration};
 
 use reth::{
     providers::{
-        providers::{BlockchainProvider, StaticFileProvider},
+        providers::{BlockchainProvider, SecondaryWatcher, StaticFileProvider},
         ProviderFactory,
     },
     rpc::eth::EthApi,
@@ -55,6 +55,15 @@ async fn main() -> eyre::Result<()> {
         StaticFileProvider::read_only(db_path.join("static_files"))?,
     );
 
+    // Follow the blocks committed by the node if it's running, which requires refreshing the
+    // static files
+    let mut tip_changes = SecondaryWatcher::new(factory.clone()).spawn(Duration::from_secs(1));
+    tokio::spawn(async move {
+        while let Ok(change) = tip_changes.recv().await {
+            println!("New tip: {:?}", change.new);
+        }
+    });
+
     // 2. Setup the blockchain provider using only the database provider and a noop for the tree to
     //    satisfy trait bounds. Tree is not used in this example since we are only operating on the
     //    disk and don't handle new blocks/live sync etc, which is done by the blockchain tree.